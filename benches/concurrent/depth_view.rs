@@ -0,0 +1,107 @@
+use criterion::{BenchmarkId, Criterion};
+use orderbook_rs::OrderBook;
+use pricelevel::{Id, Side, TimeInForce};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Threads sharing the book
+const THREAD_COUNT: usize = 8;
+
+/// Levels per side read by every read operation
+const DEPTH: usize = 10;
+
+/// Register benchmarks comparing top-N depth reads that walk the skip maps
+/// with reads of the published depth view, at several read ratios.
+pub fn register_depth_view_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderBook - Depth View");
+
+    for read_ratio in [25usize, 50, 75, 95].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("snapshot_reads", read_ratio),
+            read_ratio,
+            |b, &read_ratio| {
+                b.iter_custom(|iters| measure_mixed(iters, read_ratio, false));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("depth_view_reads", read_ratio),
+            read_ratio,
+            |b, &read_ratio| {
+                b.iter_custom(|iters| measure_mixed(iters, read_ratio, true));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Every thread runs `iterations` operations, `read_ratio` percent of them
+/// top-`DEPTH` reads and the rest add/cancel pairs near the touch.
+fn measure_mixed(iterations: u64, read_ratio: usize, use_view: bool) -> Duration {
+    let mut order_book: OrderBook = OrderBook::new("TEST-SYMBOL");
+    if use_view {
+        order_book.enable_depth_view(DEPTH);
+    }
+    for i in 0..200u128 {
+        order_book
+            .add_limit_order(
+                Id::new_uuid(),
+                990 - i % 50,
+                10,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .unwrap();
+        order_book
+            .add_limit_order(
+                Id::new_uuid(),
+                1010 + i % 50,
+                10,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .unwrap();
+    }
+    let order_book = Arc::new(order_book);
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT + 1)); // +1 for main thread
+
+    let handles: Vec<_> = (0..THREAD_COUNT)
+        .map(|thread_id| {
+            let book = Arc::clone(&order_book);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for i in 0..iterations {
+                    if (i as usize % 100) < read_ratio {
+                        if use_view {
+                            let view = book.depth_view().unwrap();
+                            std::hint::black_box(view.bids.len() + view.asks.len());
+                        } else {
+                            std::hint::black_box(book.create_snapshot(DEPTH));
+                        }
+                    } else {
+                        let id = Id::new_uuid();
+                        let price = 995 - (thread_id as u128 + u128::from(i % 4));
+                        book.add_limit_order(id, price, 5, Side::Buy, TimeInForce::Gtc, None)
+                            .unwrap();
+                        book.cancel_order(id).ok();
+                    }
+                }
+                barrier.wait();
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let start = Instant::now();
+    barrier.wait();
+    let duration = start.elapsed();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+    duration
+}
@@ -0,0 +1,90 @@
+use criterion::{BenchmarkId, Criterion};
+use crossbeam::utils::CachePadded;
+use orderbook_rs::OrderBook;
+use pricelevel::{Id, Side, TimeInForce};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Register benchmarks showing the cost of false sharing on per-side
+/// state, and the book's behaviour with one writer per side.
+pub fn register_false_sharing_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderBook - False Sharing");
+
+    group.bench_function(BenchmarkId::new("adjacent_counters", "packed"), |b| {
+        b.iter_custom(|iters| {
+            let counters: Arc<[AtomicU64; 2]> = Arc::new([AtomicU64::new(0), AtomicU64::new(0)]);
+            measure_per_side(iters, move |side, i| {
+                counters[side].fetch_add(i, Ordering::Relaxed);
+            })
+        });
+    });
+
+    group.bench_function(BenchmarkId::new("adjacent_counters", "padded"), |b| {
+        b.iter_custom(|iters| {
+            let counters: Arc<[CachePadded<AtomicU64>; 2]> = Arc::new([
+                CachePadded::new(AtomicU64::new(0)),
+                CachePadded::new(AtomicU64::new(0)),
+            ]);
+            measure_per_side(iters, move |side, i| {
+                counters[side].fetch_add(i, Ordering::Relaxed);
+            })
+        });
+    });
+
+    group.bench_function("one_writer_per_side", |b| {
+        b.iter_custom(|iters| {
+            let book: Arc<OrderBook> = Arc::new(OrderBook::new("TEST-SYMBOL"));
+            measure_per_side(iters, move |side, i| {
+                // Bids below 1_000, asks above 2_000: nothing matches.
+                let (side, price) = if side == 0 {
+                    (Side::Buy, 1_000 - u128::from(i % 100))
+                } else {
+                    (Side::Sell, 2_000 + u128::from(i % 100))
+                };
+                let id = Id::new_uuid();
+                book.add_limit_order(id, price, 10, side, TimeInForce::Gtc, None)
+                    .unwrap();
+                let _ = book.best_bid();
+                let _ = book.best_ask();
+                book.cancel_order(id).ok();
+            })
+        });
+    });
+
+    group.finish();
+}
+
+/// Runs `op(side, i)` for `i in 0..iterations` on two threads at once, one
+/// per side (`0` and `1`), and returns the wall time.
+fn measure_per_side<F>(iterations: u64, op: F) -> Duration
+where
+    F: Fn(usize, u64) + Send + Sync + 'static,
+{
+    let op = Arc::new(op);
+    let barrier = Arc::new(Barrier::new(3)); // +1 for main thread
+    let handles: Vec<_> = (0..2)
+        .map(|side| {
+            let op = Arc::clone(&op);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for i in 0..iterations {
+                    op(side, i);
+                }
+                barrier.wait();
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let start = Instant::now();
+    barrier.wait();
+    let duration = start.elapsed();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+    duration
+}
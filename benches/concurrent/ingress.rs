@@ -0,0 +1,119 @@
+use criterion::{BenchmarkId, Criterion, Throughput};
+use orderbook_rs::{WaitStrategy, command_ring};
+use std::sync::mpsc::sync_channel;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CAPACITY: usize = 1024;
+
+/// Register throughput benchmarks for the Sequencer ingress: the command
+/// ring under each wait strategy against the `std` and crossbeam bounded
+/// channels it replaced, with one and four producers.
+pub fn register_ingress_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Sequencer - Ingress");
+    group.throughput(Throughput::Elements(1));
+
+    for producers in [1u64, 4] {
+        for (name, wait) in [
+            ("ring_busy_spin", WaitStrategy::BusySpin),
+            ("ring_yielding", WaitStrategy::Yielding),
+            ("ring_spin_then_park", WaitStrategy::default()),
+        ] {
+            group.bench_with_input(
+                BenchmarkId::new(name, producers),
+                &producers,
+                |b, &producers| {
+                    b.iter_custom(|iters| {
+                        let (sender, receiver) = command_ring::<u64>(CAPACITY, wait);
+                        measure(
+                            producers,
+                            iters,
+                            move |value| sender.send(value).unwrap(),
+                            move || receiver.recv().unwrap(),
+                        )
+                    });
+                },
+            );
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("std_sync_channel", producers),
+            &producers,
+            |b, &producers| {
+                b.iter_custom(|iters| {
+                    let (sender, receiver) = sync_channel::<u64>(CAPACITY);
+                    measure(
+                        producers,
+                        iters,
+                        move |value| sender.send(value).unwrap(),
+                        move || receiver.recv().unwrap(),
+                    )
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("crossbeam_bounded", producers),
+            &producers,
+            |b, &producers| {
+                b.iter_custom(|iters| {
+                    let (sender, receiver) = crossbeam::channel::bounded::<u64>(CAPACITY);
+                    measure(
+                        producers,
+                        iters,
+                        move |value| sender.send(value).unwrap(),
+                        move || receiver.recv().unwrap(),
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Pushes `iterations` values through a queue from `producers` threads
+/// (the remainder goes to the first one) while the calling thread consumes
+/// them, and returns the wall time from the start barrier to the last
+/// receive.
+fn measure<S, R>(producers: u64, iterations: u64, send: S, recv: R) -> Duration
+where
+    S: Fn(u64) + Clone + Send + 'static,
+    R: Fn() -> u64,
+{
+    let barrier = Arc::new(Barrier::new(producers as usize + 1)); // +1 for the consumer
+    let handles: Vec<_> = (0..producers)
+        .map(|producer| {
+            let send = send.clone();
+            let barrier = Arc::clone(&barrier);
+            let count = iterations / producers
+                + if producer == 0 {
+                    iterations % producers
+                } else {
+                    0
+                };
+            thread::spawn(move || {
+                barrier.wait();
+                for value in 0..count {
+                    send(value);
+                }
+            })
+        })
+        .collect();
+    drop(send);
+
+    barrier.wait();
+    let start = Instant::now();
+    let mut checksum = 0u64;
+    for _ in 0..iterations {
+        checksum = checksum.wrapping_add(recv());
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(checksum);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    elapsed
+}
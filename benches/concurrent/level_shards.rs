@@ -0,0 +1,93 @@
+use criterion::{BenchmarkId, Criterion};
+use orderbook_rs::OrderBook;
+use pricelevel::{Id, Side, TimeInForce};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Distinct bid price levels loaded before timing starts
+const PREPOPULATED_LEVELS: u64 = 50_000;
+
+/// Writer threads adding and cancelling long-tail levels
+const THREAD_COUNT: usize = 8;
+
+/// Price range covered by each shard
+const SHARD_WIDTH: u128 = 10_000;
+
+/// Register benchmarks comparing the single price level structure with
+/// price-range shards under level churn on a very deep book.
+pub fn register_level_shard_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderBook - Level Shards");
+
+    // One shard is the single structure.
+    for shard_count in [1usize, 4, 16].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("deep_level_churn", shard_count),
+            shard_count,
+            |b, &shard_count| {
+                b.iter_custom(|iters| measure_deep_level_churn(shard_count, iters));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Measures time for every thread to add and then cancel `iterations`
+/// orders at new price levels, each thread in its own part of a book
+/// already holding many levels.
+fn measure_deep_level_churn(shard_count: usize, iterations: u64) -> Duration {
+    let order_book: Arc<OrderBook> = Arc::new(OrderBook::with_level_shards(
+        "TEST-SYMBOL",
+        SHARD_WIDTH,
+        shard_count,
+    ));
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT + 1)); // +1 for main thread
+
+    for i in 0..PREPOPULATED_LEVELS {
+        let price = 1 + u128::from(i) * 3;
+        order_book
+            .add_limit_order(Id::new_uuid(), price, 10, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+    }
+
+    let mut handles = Vec::with_capacity(THREAD_COUNT);
+
+    for thread_id in 0..THREAD_COUNT {
+        let thread_order_book = Arc::clone(&order_book);
+        let thread_barrier = Arc::clone(&barrier);
+
+        handles.push(thread::spawn(move || {
+            // Wait for all threads to be ready
+            thread_barrier.wait();
+
+            for i in 0..iterations {
+                // New levels between the resting ones, spread over the book.
+                let id = Id::new_uuid();
+                let price = 2 + (thread_id as u128) * 18_000 + u128::from(i % 6_000) * 3;
+                thread_order_book
+                    .add_limit_order(id, price, 10, Side::Buy, TimeInForce::Gtc, None)
+                    .unwrap();
+                thread_order_book.cancel_order(id).ok();
+            }
+
+            // Signal completion
+            thread_barrier.wait();
+        }));
+    }
+
+    // Start timing
+    barrier.wait();
+    let start = Instant::now();
+
+    // Wait for all threads to complete
+    barrier.wait();
+    let duration = start.elapsed();
+
+    // Join all threads
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    duration
+}
@@ -1,14 +1,29 @@
 use criterion::criterion_group;
 
 mod contention;
+mod depth_view;
+mod false_sharing;
+mod ingress;
+mod level_shards;
+mod order_index_shards;
 mod register;
 
 pub use contention::register_contention_benchmarks;
+pub use depth_view::register_depth_view_benchmarks;
+pub use false_sharing::register_false_sharing_benchmarks;
+pub use ingress::register_ingress_benchmarks;
+pub use level_shards::register_level_shard_benchmarks;
+pub use order_index_shards::register_order_index_shard_benchmarks;
 pub use register::register_benchmarks;
 
 // Import and re-export our main concurrent benchmarks
 criterion_group!(
     concurrent_benches,
     register_benchmarks,
-    register_contention_benchmarks
+    register_contention_benchmarks,
+    register_depth_view_benchmarks,
+    register_false_sharing_benchmarks,
+    register_ingress_benchmarks,
+    register_level_shard_benchmarks,
+    register_order_index_shard_benchmarks
 );
@@ -0,0 +1,88 @@
+use criterion::{BenchmarkId, Criterion};
+use orderbook_rs::OrderBook;
+use pricelevel::{Id, Side, TimeInForce};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Resting orders loaded before timing starts
+const PREPOPULATED_ORDERS: u64 = 100_000;
+
+/// Writer threads hammering the order-id index
+const THREAD_COUNT: usize = 8;
+
+/// Register benchmarks comparing order-index shard counts under add/cancel
+/// churn on a large book.
+pub fn register_order_index_shard_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderBook - Order Index Shards");
+
+    for shard_count in [4usize, 16, 64, 256].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("add_cancel_churn", shard_count),
+            shard_count,
+            |b, &shard_count| {
+                b.iter_custom(|iters| measure_add_cancel_churn(shard_count, iters));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Measures time for every thread to add and then cancel `iterations`
+/// non-crossing orders on a book already holding many resting orders.
+fn measure_add_cancel_churn(shard_count: usize, iterations: u64) -> Duration {
+    let order_book: Arc<OrderBook> = Arc::new(OrderBook::with_order_index(
+        "TEST-SYMBOL",
+        PREPOPULATED_ORDERS as usize * 2,
+        shard_count,
+    ));
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT + 1)); // +1 for main thread
+
+    for i in 0..PREPOPULATED_ORDERS {
+        let price = 1_000 - u128::from(i % 100);
+        order_book
+            .add_limit_order(Id::new_uuid(), price, 10, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+    }
+
+    let mut handles = Vec::with_capacity(THREAD_COUNT);
+
+    for thread_id in 0..THREAD_COUNT {
+        let thread_order_book = Arc::clone(&order_book);
+        let thread_barrier = Arc::clone(&barrier);
+
+        handles.push(thread::spawn(move || {
+            // Wait for all threads to be ready
+            thread_barrier.wait();
+
+            for i in 0..iterations {
+                // Asks well above the bids so nothing matches.
+                let id = Id::new_uuid();
+                let price = 2_000 + (thread_id as u128) * 100 + u128::from(i % 100);
+                thread_order_book
+                    .add_limit_order(id, price, 10, Side::Sell, TimeInForce::Gtc, None)
+                    .unwrap();
+                thread_order_book.cancel_order(id).ok();
+            }
+
+            // Signal completion
+            thread_barrier.wait();
+        }));
+    }
+
+    // Start timing
+    barrier.wait();
+    let start = Instant::now();
+
+    // Wait for all threads to complete
+    barrier.wait();
+    let duration = start.elapsed();
+
+    // Join all threads
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    duration
+}
@@ -6,6 +6,11 @@ mod serialization;
 mod simple;
 
 use concurrent::register_benchmarks as register_concurrent_benchmarks;
+use concurrent::register_depth_view_benchmarks;
+use concurrent::register_false_sharing_benchmarks;
+use concurrent::register_ingress_benchmarks;
+use concurrent::register_level_shard_benchmarks;
+use concurrent::register_order_index_shard_benchmarks;
 use order_book::register_benchmarks as register_order_book_benchmarks;
 use serialization::register_benchmarks as register_serialization_benchmarks;
 use simple::basic::benchmark_data;
@@ -16,6 +21,11 @@ criterion_group!(
     benchmark_data,
     register_order_book_benchmarks,
     register_concurrent_benchmarks,
+    register_order_index_shard_benchmarks,
+    register_level_shard_benchmarks,
+    register_false_sharing_benchmarks,
+    register_depth_view_benchmarks,
+    register_ingress_benchmarks,
     register_serialization_benchmarks,
 );
 
@@ -0,0 +1,138 @@
+// alloc_small_match — feature-gated allocation profile of small matches.
+// Most real matches print 1–4 fills; this reports the allocations per
+// crossing submit for each fill count, with and without a trade
+// listener installed, so the listener path can be checked to add no
+// `MatchResult` clone on top of the bare match. Both columns are
+// measured on the same book (the listener is toggled in place) because
+// the absolute count carries a small per-instance offset from the
+// concurrent maps' hashing.
+//
+// Build / run:
+//
+//     cargo bench --features alloc-counters --bench alloc_small_match
+
+#![cfg(feature = "alloc-counters")]
+
+use orderbook_rs::utils::CountingAllocator;
+use orderbook_rs::{OrderBook, TradeListener, TradeResult};
+use pricelevel::{Id, Side, TimeInForce};
+use std::alloc::System;
+use std::sync::Arc;
+
+#[global_allocator]
+static GLOBAL: CountingAllocator<System> = CountingAllocator::new(System);
+
+const SCENARIO: &str = "alloc_small_match";
+const WARMUP_MATCHES: u64 = 10_000;
+const MEASURED_MATCHES: u64 = 100_000;
+const MAKER_QTY: u64 = 10;
+
+#[derive(Clone, Copy)]
+enum Taker {
+    Limit,
+    Market,
+}
+
+impl Taker {
+    fn label(self) -> &'static str {
+        match self {
+            Taker::Limit => "limit",
+            Taker::Market => "market",
+        }
+    }
+}
+
+fn listener() -> TradeListener {
+    Arc::new(|trade: &TradeResult| {
+        std::hint::black_box(trade.quote_notional);
+    })
+}
+
+/// Rest `fills` makers at one price, then take all of them. Only the
+/// take is counted.
+fn one_match(book: &OrderBook<()>, fills: u64, taker: Taker, next_id: &mut u64) -> u64 {
+    for _ in 0..fills {
+        *next_id += 1;
+        let _ = book.add_limit_order(
+            Id::from_u64(*next_id),
+            100,
+            MAKER_QTY,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        );
+    }
+    *next_id += 1;
+    let id = Id::from_u64(*next_id);
+    let quantity = fills * MAKER_QTY;
+
+    let before = GLOBAL.snapshot();
+    match taker {
+        Taker::Limit => {
+            let _ = std::hint::black_box(book.add_limit_order(
+                id,
+                100,
+                quantity,
+                Side::Buy,
+                TimeInForce::Ioc,
+                None,
+            ));
+        }
+        Taker::Market => {
+            let _ = std::hint::black_box(book.submit_market_order(id, quantity, Side::Buy));
+        }
+    }
+    GLOBAL.snapshot().since(before).allocs
+}
+
+fn measure(book: &OrderBook<()>, fills: u64, taker: Taker, next_id: &mut u64) -> f64 {
+    for _ in 0..WARMUP_MATCHES {
+        one_match(book, fills, taker, next_id);
+    }
+    let allocs: u64 = (0..MEASURED_MATCHES)
+        .map(|_| one_match(book, fills, taker, next_id))
+        .sum();
+    allocs as f64 / MEASURED_MATCHES as f64
+}
+
+fn main() {
+    let mut rows = String::new();
+    println!("scenario        : {SCENARIO}");
+    println!("measured        : {MEASURED_MATCHES} matches per cell");
+    println!("taker   fills  bare   listener  delta");
+
+    for taker in [Taker::Limit, Taker::Market] {
+        let book = OrderBook::<()>::new("ALLOC");
+        let mut next_id = 0;
+        for fills in 1..=4u64 {
+            book.remove_trade_listener();
+            let bare = measure(&book, fills, taker, &mut next_id);
+            book.set_trade_listener(listener());
+            let with_listener = measure(&book, fills, taker, &mut next_id);
+            let delta = with_listener - bare;
+            println!(
+                "{:<7} {fills:<6} {bare:<6.2} {with_listener:<9.2} {delta:+.2}",
+                taker.label()
+            );
+            rows.push_str(&format!(
+                "| {:<6} | {fills} | {bare:.2} | {with_listener:.2} | {delta:+.2} |\n",
+                taker.label()
+            ));
+        }
+    }
+
+    let summary = format!(
+        "# {SCENARIO}\n\
+         \n\
+         | taker  | fills | bare allocs/match | listener allocs/match | delta |\n\
+         |--------|-------|-------------------|-----------------------|-------|\n\
+         {rows}"
+    );
+    let _ = std::fs::create_dir_all("target/alloc-counters");
+    let path = format!("target/alloc-counters/{SCENARIO}.md");
+    if let Err(e) = std::fs::write(&path, summary) {
+        eprintln!("could not write {path}: {e}");
+    } else {
+        eprintln!("wrote {path}");
+    }
+}
@@ -0,0 +1,300 @@
+// bench_compare — performance regression gate.
+//
+// Runs the key HDR scenarios (add_only, cancel_only, aggressive_walk,
+// mixed_70_20_10) at reduced op counts, `--runs` times each, then compares
+// the median throughput and p99 of every scenario against the committed
+// baseline in
+// `benches/baseline.json`. Exits non-zero when any scenario loses more
+// than `--threshold` percent of throughput or gains more than that in p99.
+//
+//   cargo bench --bench bench_compare                     # gate, 10 %
+//   cargo bench --bench bench_compare -- --threshold 25   # looser gate
+//   cargo bench --bench bench_compare -- --runs 9         # steadier medians
+//   cargo bench --bench bench_compare -- --write-baseline # re-baseline
+//
+// The baseline is host-specific: regenerate it on the machine that runs
+// the gate (and commit it) whenever that machine changes.
+
+#[path = "hdr_common.rs"]
+mod common;
+
+use common::{Rng, new_histogram, owner, pick_owner, pick_side, record, submit_gtc};
+use hdrhistogram::Histogram;
+use pricelevel::{Id, Side, TimeInForce};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const DEFAULT_BASELINE: &str = "benches/baseline.json";
+const DEFAULT_THRESHOLD_PCT: f64 = 10.0;
+const DEFAULT_RUNS: usize = 5;
+const WARMUP_OPS: u64 = 50_000;
+const MEASURED_OPS: u64 = 200_000;
+const SEED: u64 = 0xA5A5_A5A5_A5A5_A5A5;
+
+/// Histogram of one run plus the wall-clock time of its measured loop.
+type Sample = (Histogram<u64>, Duration);
+
+/// One scenario's measurement, as stored in the baseline file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Measurement {
+    /// Measured operations per second of wall-clock time.
+    throughput_ops_per_sec: f64,
+    /// 99th percentile per-operation latency in nanoseconds.
+    p99_ns: u64,
+}
+
+/// On-disk baseline: scenario name → measurement.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Baseline {
+    scenarios: BTreeMap<String, Measurement>,
+}
+
+struct Args {
+    baseline: String,
+    threshold_pct: f64,
+    runs: usize,
+    write_baseline: bool,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = Self {
+            baseline: DEFAULT_BASELINE.to_string(),
+            threshold_pct: DEFAULT_THRESHOLD_PCT,
+            runs: DEFAULT_RUNS,
+            write_baseline: false,
+        };
+        let mut iter = std::env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--baseline" => {
+                    args.baseline = iter.next().ok_or("--baseline needs a path")?;
+                }
+                "--threshold" => {
+                    let value = iter.next().ok_or("--threshold needs a percentage")?;
+                    args.threshold_pct = value
+                        .parse()
+                        .map_err(|_| format!("invalid --threshold: {value}"))?;
+                }
+                "--runs" => {
+                    let value = iter.next().ok_or("--runs needs a count")?;
+                    args.runs = value
+                        .parse()
+                        .ok()
+                        .filter(|runs| *runs > 0)
+                        .ok_or_else(|| format!("invalid --runs: {value}"))?;
+                }
+                "--write-baseline" => args.write_baseline = true,
+                // `cargo bench` appends `--bench` to every harness-less target.
+                "--bench" => {}
+                other => return Err(format!("unknown argument: {other}")),
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Time `ops` invocations of `op`, returning the histogram and the total
+/// wall-clock time of the measured loop.
+fn measure(ops: u64, mut op: impl FnMut(u64)) -> Sample {
+    let mut hist = new_histogram();
+    let started = Instant::now();
+    for i in 0..ops {
+        record(&mut hist, || op(i));
+    }
+    (hist, started.elapsed())
+}
+
+fn add_only() -> Sample {
+    let book = common::fresh_book();
+    let mut rng = Rng::new(SEED);
+    for i in 0..WARMUP_OPS {
+        submit_gtc(&book, &mut rng, i);
+    }
+    measure(MEASURED_OPS, |i| {
+        submit_gtc(&book, &mut rng, WARMUP_OPS + i)
+    })
+}
+
+fn cancel_only() -> Sample {
+    let book = common::fresh_book();
+    let mut rng = Rng::new(SEED);
+    for i in 0..MEASURED_OPS {
+        submit_gtc(&book, &mut rng, i + 1);
+    }
+    measure(MEASURED_OPS, |i| {
+        let _ = book.cancel_order(Id::from_u64(i + 1));
+    })
+}
+
+fn aggressive_walk() -> Sample {
+    const RESTING_PER_LEVEL: u64 = 100;
+    const NUM_LEVELS: u64 = 50;
+    const OPS: u64 = 50_000;
+
+    let book = common::fresh_book();
+    let mut rng = Rng::new(SEED);
+    let maker = owner(0xAA);
+    let taker = owner(0xBB);
+    let mut next_id = 1u64;
+    for level in 0..NUM_LEVELS {
+        for _ in 0..RESTING_PER_LEVEL {
+            let _ = book.add_limit_order_with_user(
+                Id::from_u64(next_id),
+                (100 + level) as u128,
+                rng.range(1, 10),
+                Side::Sell,
+                TimeInForce::Gtc,
+                maker,
+                None,
+            );
+            next_id += 1;
+        }
+    }
+    measure(OPS, |i| {
+        let qty = rng.range(5, 20);
+        let _ =
+            book.submit_market_order_with_user(Id::from_u64(next_id + i), qty, Side::Buy, taker);
+    })
+}
+
+fn mixed_70_20_10() -> Sample {
+    let book = common::fresh_book();
+    let mut rng = Rng::new(SEED);
+    let mut next_id: u64 = 1;
+    let mut step = |rng: &mut Rng| match rng.next() % 100 {
+        0..70 => {
+            submit_gtc(&book, rng, next_id);
+            next_id += 1;
+        }
+        70..90 => {
+            if next_id > 1 {
+                let target = rng.range(1, next_id - 1);
+                let _ = book.cancel_order(Id::from_u64(target));
+            }
+        }
+        _ => {
+            let id = Id::from_u64(next_id);
+            next_id += 1;
+            let qty = rng.range(1, 10);
+            let _ = book.submit_market_order_with_user(id, qty, pick_side(rng), pick_owner(rng));
+        }
+    };
+    for _ in 0..WARMUP_OPS {
+        step(&mut rng);
+    }
+    measure(MEASURED_OPS, |_| step(&mut rng))
+}
+
+/// Median of `values`, which must be non-empty.
+fn median<V: Copy + PartialOrd>(mut values: Vec<V>) -> V {
+    values.sort_by(|a, b| a.partial_cmp(b).expect("comparable"));
+    values[values.len() / 2]
+}
+
+fn run_scenarios(runs: usize) -> Baseline {
+    let scenarios = [
+        ("add_only", add_only as fn() -> Sample),
+        ("cancel_only", cancel_only),
+        ("aggressive_walk", aggressive_walk),
+        ("mixed_70_20_10", mixed_70_20_10),
+    ];
+    let mut baseline = Baseline::default();
+    for (name, run) in scenarios {
+        // Throughput and p99 are taken as independent medians: a run with
+        // a scheduler hiccup skews one metric without dragging the other.
+        let (throughputs, p99s): (Vec<f64>, Vec<u64>) = (0..runs)
+            .map(|_| {
+                let (hist, elapsed) = run();
+                (
+                    hist.len() as f64 / elapsed.as_secs_f64(),
+                    hist.value_at_quantile(0.99),
+                )
+            })
+            .unzip();
+        let measurement = Measurement {
+            throughput_ops_per_sec: median(throughputs),
+            p99_ns: median(p99s),
+        };
+        println!(
+            "{name:<16} {:>14.0} ops/s   p99 {:>8} ns",
+            measurement.throughput_ops_per_sec, measurement.p99_ns
+        );
+        baseline.scenarios.insert(name.to_string(), measurement);
+    }
+    baseline
+}
+
+/// Compare `current` against `baseline`; returns one line per regression.
+fn regressions(baseline: &Baseline, current: &Baseline, threshold_pct: f64) -> Vec<String> {
+    let mut failures = Vec::new();
+    for (name, now) in &current.scenarios {
+        let Some(then) = baseline.scenarios.get(name) else {
+            println!("{name:<16} not in baseline, skipped");
+            continue;
+        };
+        let throughput_delta =
+            (now.throughput_ops_per_sec / then.throughput_ops_per_sec - 1.0) * 100.0;
+        let p99_delta = (now.p99_ns as f64 / then.p99_ns.max(1) as f64 - 1.0) * 100.0;
+        println!("{name:<16} throughput {throughput_delta:+7.1} %   p99 {p99_delta:+7.1} %");
+        if throughput_delta < -threshold_pct {
+            failures.push(format!(
+                "{name}: throughput {:.0} ops/s is {:.1} % below baseline {:.0} ops/s",
+                now.throughput_ops_per_sec, -throughput_delta, then.throughput_ops_per_sec
+            ));
+        }
+        if p99_delta > threshold_pct {
+            failures.push(format!(
+                "{name}: p99 {} ns is {p99_delta:.1} % above baseline {} ns",
+                now.p99_ns, then.p99_ns
+            ));
+        }
+    }
+    failures
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("bench_compare: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let current = run_scenarios(args.runs);
+
+    if args.write_baseline {
+        let json = serde_json::to_string_pretty(&current).expect("serialize baseline");
+        if let Err(e) = std::fs::write(&args.baseline, json + "\n") {
+            eprintln!("bench_compare: cannot write {}: {e}", args.baseline);
+            return ExitCode::from(2);
+        }
+        eprintln!("wrote {}", args.baseline);
+        return ExitCode::SUCCESS;
+    }
+
+    let baseline: Baseline = match std::fs::read_to_string(&args.baseline)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+    {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("bench_compare: cannot load {}: {e}", args.baseline);
+            return ExitCode::from(2);
+        }
+    };
+
+    let failures = regressions(&baseline, &current, args.threshold_pct);
+    if failures.is_empty() {
+        println!("no regression above {} %", args.threshold_pct);
+        ExitCode::SUCCESS
+    } else {
+        for failure in &failures {
+            eprintln!("REGRESSION {failure}");
+        }
+        ExitCode::FAILURE
+    }
+}
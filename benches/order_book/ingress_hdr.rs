@@ -0,0 +1,84 @@
+// ingress_hdr — enqueue-to-dequeue latency of the Sequencer ingress.
+// Compares the command ring under each wait strategy with the `std` and
+// crossbeam bounded channels it replaced.
+//
+// One producer paces a send every PACE so the queue stays near empty and
+// the histogram shows hand-off latency (including consumer wake-up)
+// rather than queueing delay. Each message carries its send `Instant`.
+
+#[path = "hdr_common.rs"]
+mod common;
+
+use common::{new_histogram, persist, report};
+use hdrhistogram::Histogram;
+use orderbook_rs::{WaitStrategy, command_ring};
+use std::sync::mpsc::sync_channel;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CAPACITY: usize = 1024;
+const WARMUP_OPS: u64 = 10_000;
+const MEASURED_OPS: u64 = 100_000;
+const PACE: Duration = Duration::from_micros(2);
+
+/// Run the paced producer on its own thread and record, on this thread,
+/// how long each message took to arrive.
+fn run<S, R>(send: S, recv: R) -> Histogram<u64>
+where
+    S: Fn(Instant) + Send + 'static,
+    R: Fn() -> Instant,
+{
+    let producer = thread::spawn(move || {
+        let mut next = Instant::now();
+        for _ in 0..WARMUP_OPS + MEASURED_OPS {
+            next += PACE;
+            while Instant::now() < next {
+                std::hint::spin_loop();
+            }
+            send(Instant::now());
+        }
+    });
+
+    let mut hist = new_histogram();
+    for i in 0..WARMUP_OPS + MEASURED_OPS {
+        let sent = recv();
+        if i >= WARMUP_OPS {
+            hist.record((sent.elapsed().as_nanos() as u64).max(1))
+                .expect("record");
+        }
+    }
+    producer.join().expect("producer");
+    hist
+}
+
+fn main() {
+    for (name, wait) in [
+        ("ingress_ring_busy_spin", WaitStrategy::BusySpin),
+        ("ingress_ring_yielding", WaitStrategy::Yielding),
+        ("ingress_ring_spin_then_park", WaitStrategy::default()),
+    ] {
+        let (sender, receiver) = command_ring::<Instant>(CAPACITY, wait);
+        let hist = run(
+            move |sent| sender.send(sent).expect("send"),
+            move || receiver.recv().expect("recv"),
+        );
+        report(name, &hist);
+        persist(name, &hist).expect("persist hgrm");
+    }
+
+    let (sender, receiver) = sync_channel::<Instant>(CAPACITY);
+    let hist = run(
+        move |sent| sender.send(sent).expect("send"),
+        move || receiver.recv().expect("recv"),
+    );
+    report("ingress_std_sync_channel", &hist);
+    persist("ingress_std_sync_channel", &hist).expect("persist hgrm");
+
+    let (sender, receiver) = crossbeam::channel::bounded::<Instant>(CAPACITY);
+    let hist = run(
+        move |sent| sender.send(sent).expect("send"),
+        move || receiver.recv().expect("recv"),
+    );
+    report("ingress_crossbeam_bounded", &hist);
+    persist("ingress_crossbeam_bounded", &hist).expect("persist hgrm");
+}
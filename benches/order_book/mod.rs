@@ -5,6 +5,7 @@ pub mod matching;
 pub mod mixed_operations;
 pub mod replay;
 pub mod snapshot;
+pub mod thin_book_sweep;
 pub mod update_orders;
 
 // Import common benchmarks into the main bench group
@@ -17,4 +18,5 @@ pub fn register_benchmarks(c: &mut criterion::Criterion) {
     mass_cancel::register_benchmarks(c);
     snapshot::register_benchmarks(c);
     replay::register_benchmarks(c);
+    thin_book_sweep::register_benchmarks(c);
 }
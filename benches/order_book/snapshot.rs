@@ -88,6 +88,26 @@ pub fn register_benchmarks(c: &mut Criterion) {
         );
     }
 
+    // ─── enriched_snapshot vs enriched_aggregate_snapshot, top 20 ───
+    for &order_count in &[1_000, 10_000] {
+        group.bench_with_input(
+            BenchmarkId::new("enriched_snapshot_depth_20", order_count),
+            &order_count,
+            |b, &count| {
+                let book = make_populated_book(count);
+                b.iter(|| black_box(book.enriched_snapshot(20)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("enriched_aggregate_snapshot_depth_20", order_count),
+            &order_count,
+            |b, &count| {
+                let book = make_populated_book(count);
+                b.iter(|| black_box(book.enriched_aggregate_snapshot(20)));
+            },
+        );
+    }
+
     // ─── snapshot JSON round-trip ───────────────────────────────────
     for &order_count in &[100, 1_000] {
         group.bench_with_input(
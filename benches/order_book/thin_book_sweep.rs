@@ -0,0 +1,55 @@
+use criterion::{BatchSize, BenchmarkId, Criterion};
+use orderbook_rs::OrderBook;
+use pricelevel::{Id, Side, TimeInForce};
+use std::hint::black_box;
+
+/// Register benchmarks for market orders sweeping a thin book, one small
+/// order per level, followed by the best-price read a quoting client
+/// issues after every fill.
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderBook - Thin Book Sweep");
+
+    for &levels in &[10u64, 100, 1_000] {
+        group.bench_with_input(
+            BenchmarkId::new("sweep_then_read_best", levels),
+            &levels,
+            |b, &levels| {
+                b.iter_batched_ref(
+                    || thin_book(levels),
+                    |book| {
+                        // Taken by reference so dropping the book is not timed.
+                        // Consume all but the last level.
+                        let result = book
+                            .submit_market_order(Id::new_uuid(), levels - 1, Side::Buy)
+                            .expect("sweep");
+                        black_box(result);
+                        black_box(book.best_ask());
+                        black_box(book.best_bid());
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// One unit-quantity ask per level above a single resting bid.
+fn thin_book(levels: u64) -> OrderBook<()> {
+    let book = OrderBook::<()>::new("TEST-SYMBOL");
+    book.add_limit_order(Id::new_uuid(), 1_000, 1, Side::Buy, TimeInForce::Gtc, None)
+        .expect("bid");
+    for i in 0..levels {
+        book.add_limit_order(
+            Id::new_uuid(),
+            1_001 + u128::from(i),
+            1,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+    }
+    book
+}
@@ -19,7 +19,9 @@ fn make_book_change() -> PriceLevelChangedEvent {
         side: Side::Buy,
         price: 50_000_000,
         quantity: 1_000,
+        order_count: 1,
         engine_seq: 0,
+        config_version: 0,
     }
 }
 
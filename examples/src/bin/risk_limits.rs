@@ -21,7 +21,7 @@ fn main() {
     let _ = setup_logger();
     info!("Risk limits demo");
 
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
     let acct_a = account(1);
 
     // Reference price for the band check needs at least one trade or a
@@ -0,0 +1,32 @@
+//! Arbitrary bytes as an on-disk journal segment.
+//!
+//! The input is written as `segment-00000000000000000000.journal` and the
+//! journal is reopened over it, which runs the last-sequence recovery
+//! scan. Integrity verification and a full read must then return entries
+//! or typed errors, never panic or read past the mapping.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use orderbook_rs::{FileJournal, Journal};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(dir) = tempfile::tempdir() else {
+        return;
+    };
+    let segment = dir.path().join(format!("segment-{:020}.journal", 0));
+    if std::fs::write(&segment, data).is_err() {
+        return;
+    }
+
+    let Ok(journal) = FileJournal::<()>::open(dir.path()) else {
+        return;
+    };
+    let _ = journal.last_sequence();
+    let _ = journal.verify_integrity();
+    if let Ok(entries) = journal.read_from(0) {
+        for entry in entries {
+            let _ = entry;
+        }
+    }
+});
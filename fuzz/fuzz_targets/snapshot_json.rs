@@ -0,0 +1,33 @@
+//! Arbitrary bytes into the snapshot JSON restore path.
+//!
+//! A package that parses is validated and restored through
+//! `restore_from_snapshot_package`. Almost every mutated input fails the
+//! checksum, so the inner snapshot is also restored directly to reach the
+//! level and order rebuild. Neither path may panic, and a book that
+//! accepted a snapshot must pass the invariant auditor.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use orderbook_rs::OrderBook;
+use orderbook_rs::orderbook::OrderBookSnapshotPackage;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(package) = OrderBookSnapshotPackage::from_json(text) else {
+        return;
+    };
+
+    let snapshot = package.snapshot.clone();
+    let mut book = OrderBook::<()>::new("FUZZ");
+    if book.restore_from_snapshot_package(package).is_ok() {
+        assert!(book.audit().is_empty(), "{:?}", book.audit());
+    }
+
+    let book = OrderBook::<()>::new("FUZZ");
+    if book.restore_from_snapshot(snapshot).is_ok() {
+        assert!(book.audit().is_empty(), "{:?}", book.audit());
+    }
+});
@@ -0,0 +1,72 @@
+//! Arbitrary bytes into the binary wire protocol decoders.
+//!
+//! The input is read as a stream of frames, each dispatched on its kind
+//! byte, and is also handed whole to every payload decoder. Decoding must
+//! fail with a `WireError` rather than panic, and an outbound message that
+//! decodes must survive an encode / decode round trip unchanged.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use orderbook_rs::wire::{
+    MessageKind, decode_book_update, decode_cancel_order, decode_cancel_replace,
+    decode_exec_report, decode_frame, decode_mass_cancel, decode_new_order, decode_trade_print,
+    encode_book_update, encode_exec_report, encode_trade_print,
+};
+
+fn decode_payload(kind: u8, payload: &[u8]) {
+    let Ok(kind) = MessageKind::from_u8(kind) else {
+        return;
+    };
+    match kind {
+        MessageKind::NewOrder => {
+            let _ = decode_new_order(payload);
+        }
+        MessageKind::CancelOrder => {
+            let _ = decode_cancel_order(payload);
+        }
+        MessageKind::CancelReplace => {
+            let _ = decode_cancel_replace(payload);
+        }
+        MessageKind::MassCancel => {
+            let _ = decode_mass_cancel(payload);
+        }
+        MessageKind::ExecReport => {
+            if let Ok(report) = decode_exec_report(payload) {
+                let mut out = Vec::new();
+                encode_exec_report(&report, &mut out);
+                assert_eq!(decode_exec_report(&out).ok(), Some(report));
+            }
+        }
+        MessageKind::TradePrint => {
+            if let Ok(trade) = decode_trade_print(payload) {
+                let mut out = Vec::new();
+                encode_trade_print(&trade, &mut out);
+                assert_eq!(decode_trade_print(&out).ok(), Some(trade));
+            }
+        }
+        MessageKind::BookUpdate => {
+            if let Ok(update) = decode_book_update(payload) {
+                let mut out = Vec::new();
+                encode_book_update(&update, &mut out);
+                assert_eq!(decode_book_update(&out).ok(), Some(update));
+            }
+        }
+        _ => {}
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut rest = data;
+    while let Ok((kind, payload, consumed)) = decode_frame(rest) {
+        decode_payload(kind, payload);
+        let Some(next) = rest.get(consumed..) else {
+            break;
+        };
+        rest = next;
+    }
+
+    for kind in [0x01, 0x02, 0x03, 0x04, 0x81, 0x82, 0x83] {
+        decode_payload(kind, data);
+    }
+});
@@ -896,8 +896,9 @@
 
 // Safety and documentation discipline (rules/global_rules.md, CLAUDE.md):
 // no `unsafe` may ship without an explicit, documented `#[allow(unsafe_code)]`
-// (the mmap blocks in `sequencer::file_journal` and the `CountingAllocator`
-// module), and every `pub` item must carry a doc comment.
+// (the mmap blocks in `sequencer::file_journal`, the slot accesses in
+// `sequencer::ring` and the `CountingAllocator` module), and every `pub`
+// item must carry a doc comment.
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
 
@@ -907,7 +908,7 @@ pub mod prelude;
 
 /// Shared internal helpers exposed at the crate root.
 ///
-/// Currently re-exports `current_time_millis`. When the optional
+/// Currently exposes `current_time_millis` and `current_time_nanos`. When the optional
 /// `alloc-counters` feature is enabled, also exposes `CountingAllocator`
 /// and `AllocSnapshot` for opt-in allocation instrumentation in bench /
 /// test binaries.
@@ -928,38 +929,111 @@ pub mod wire;
 
 #[cfg(feature = "bincode")]
 pub use orderbook::BincodeEventSerializer;
-#[cfg(feature = "journal")]
-pub use orderbook::FileJournal;
 #[cfg(feature = "nats")]
 pub use orderbook::NatsTradePublisher;
+#[cfg(feature = "arrow")]
+pub use orderbook::arrow::{ArrowExportError, DepthRecorder, DepthSample};
+pub use orderbook::audit::InvariantViolation;
 pub use orderbook::book_change_event::{PriceLevelChangedEvent, PriceLevelChangedListener};
+pub use orderbook::book_config::BookConfig;
+pub use orderbook::cancel_on_disconnect::{DisconnectEvent, DisconnectListener};
+pub use orderbook::circuit_breaker::{
+    CircuitBreakerAction, CircuitBreakerConfig, CircuitBreakerEvent, CircuitBreakerListener,
+};
 pub use orderbook::clock::{Clock, MonotonicClock, StubClock};
+pub use orderbook::consolidated::{ConsolidatedBook, ConsolidatedLevel, VenueLevel};
+pub use orderbook::dead_mans_switch::{DeadMansSwitch, DeadMansSwitchTrip};
+pub use orderbook::depth_mode::DepthMode;
+pub use orderbook::executor::{
+    BookExecutor, DEFAULT_EXECUTOR_QUEUE_CAPACITY, ExecutorConfig, WorkerStartHook,
+};
+pub use orderbook::fee_ledger::{FeeLedger, FeeLedgerEntry, FeeSummary};
+#[cfg(feature = "deterministic")]
+pub use orderbook::fixed_point::{FIXED_BASIS_POINTS_MULTIPLIER, PriceRatio, SignedRatio};
 pub use orderbook::implied_volatility::{
-    BlackScholes, IVConfig, IVError, IVParams, IVQuality, IVResult, OptionType, PriceSource,
-    SolverConfig,
+    Binomial, BlackScholes, DEFAULT_BINOMIAL_STEPS, ExerciseStyle, Greeks, GreeksEntry,
+    GreeksSnapshot, IVConfig, IVError, IVParams, IVQuality, IVResult, IVSurface, IVSurfacePoint,
+    LegQuote, OptionType, ParityArbitrage, ParityParams, ParityReport, ParityStrategy, PriceSource,
+    SolverConfig, SurfaceFailure, SurfaceIV, SurfaceInput,
 };
-pub use orderbook::iterators::LevelInfo;
+pub use orderbook::iterators::{LadderRow, LevelInfo, OrderCursor, OrdersPage};
+#[cfg(feature = "latency")]
+pub use orderbook::latency::{LatencyConfig, LatencyRecorder, LatencySummary, SymbolLatency};
 pub use orderbook::manager::{BookManager, BookManagerStd, BookManagerTokio};
-pub use orderbook::market_impact::{MarketImpact, OrderSimulation};
+pub use orderbook::market_impact::{LiquidationPoint, MarketImpact, OrderSimulation};
+pub use orderbook::market_protection::{MarketProtection, ProtectionRemainder};
+pub use orderbook::modify::OrderModification;
+pub use orderbook::order_ack::{OrderAck, RestingInfo};
+pub use orderbook::order_age::LevelAge;
+pub use orderbook::order_id_dedup::OrderIdDedup;
 pub use orderbook::order_state::{
     CancelReason, OrderStateListener, OrderStateTracker, OrderStatus,
 };
+pub use orderbook::order_tag::OrderTag;
+pub use orderbook::perp::{
+    DEFAULT_FUNDING_INTERVAL_MS, FundingConfig, FundingSettlement, MAX_CATCH_UP_SETTLEMENTS,
+    PerpSnapshot, PerpTracker,
+};
+pub use orderbook::post_only::PostOnlyMode;
+pub use orderbook::price_scale::{MAX_SCALE_DECIMALS, PriceScale};
+pub use orderbook::priority_class::PriorityClass;
+pub use orderbook::queue_peek::QueueEntry;
+pub use orderbook::quote_quality::{QuoteQualityMonitor, QuoteQualityStats};
+pub use orderbook::rate_limit::{RateLimitConfig, RateLimiter, TokenBucketConfig};
+pub use orderbook::read_txn::{DEFAULT_READ_TXN_DEPTH, ReadTxn};
+pub use orderbook::reference_price::{ExternalPrice, ExternalPriceSource};
 pub use orderbook::reject_reason::RejectReason;
+pub use orderbook::replay::{
+    CsvMarketData, MarketDataEvent, MarketReplay, MarketReplayError, ReplayClock, ReplayReport,
+    StrategyAction, StrategyOutcome,
+};
+pub use orderbook::replenishment::{ReplenishmentEvent, ReplenishmentListener};
 pub use orderbook::risk::{ReferencePriceSource, RiskConfig, RiskState};
+pub use orderbook::router::{
+    ChildFill, ChildOrder, RoutingPlan, RoutingReport, RoutingStrategy, SmartOrderRouter,
+};
 pub use orderbook::sequencer::{
-    InMemoryJournal, Journal, JournalEntry, JournalError, JournalReadIter, ReplayBookConfig,
-    ReplayEngine, ReplayError, SequencerCommand, SequencerEvent, SequencerResult, snapshots_match,
+    InMemoryJournal, Journal, JournalEntry, JournalError, JournalFilter, JournalReadIter,
+    ReplayBookConfig, ReplayEngine, ReplayError, RingReceiver, RingSender, SequencedResult,
+    SequencerCommand, SequencerCommandKind, SequencerEvent, SequencerResult, WaitStrategy,
+    command_ring, snapshots_match,
 };
 pub use orderbook::serialization::{EventSerializer, JsonEventSerializer, SerializationError};
-pub use orderbook::snapshot::{EnrichedSnapshot, MetricFlags};
-pub use orderbook::statistics::{DepthStats, DistributionBin};
+pub use orderbook::session::{MILLIS_PER_DAY, SessionClose, SessionPhase, TradingSession};
+pub use orderbook::shutdown::{
+    ShutdownCoordinator, ShutdownHandle, ShutdownReport, ShutdownStage, ShutdownStepError,
+};
+pub use orderbook::simulation::{
+    AgentAction, AgentContext, AgentLatency, AgentPosition, AgentReport, AgentStats,
+    ArrivalProcess, DEFAULT_MAX_ACTIONS_PER_TICK, ExecutionAlgo, ExecutionHandle, ExecutionReport,
+    ExecutionSchedule, LatencyModel, MarketSimulator, SimulatedAction, SimulatedEvent,
+    SimulationConfig, SimulationReport, SimulationRunner, SizeDistribution, Strategy,
+};
+pub use orderbook::snapshot::{
+    EnrichedSnapshot, LevelAggregate, LevelAggregates, MetricFlags, VenueSnapshot,
+};
+pub use orderbook::speed_bump::{BumpedOrder, SpeedBump, SpeedBumpAdmission, SpeedBumpRelease};
+pub use orderbook::statistics::{
+    DepthStats, DistributionBin, LIFETIME_BUCKETS, LifetimeDistribution, QuoteLifetimeStats,
+};
+#[cfg(feature = "special_orders")]
+pub use orderbook::stop_trigger::StopTriggerReference;
 pub use orderbook::stp::STPMode;
-pub use orderbook::trade::{TradeEvent, TradeInfo, TradeListener, TradeResult, TransactionInfo};
+pub use orderbook::trade::{
+    FillParty, LiquidityFlag, PriceLevelFill, TradeAmendment, TradeEvent, TradeExtraFields,
+    TradeFill, TradeInfo, TradeListener, TradeResult, TransactionInfo,
+};
+pub use orderbook::trade_amendment::{DEFAULT_EXECUTION_HISTORY_CAPACITY, ExecutionHistory};
+#[cfg(feature = "journal")]
+pub use orderbook::{AppendLatency, FileJournal, JournalHealth, JournalReadOptions};
 #[cfg(feature = "nats")]
 pub use orderbook::{BookChangeBatch, BookChangeEntry, NatsBookChangePublisher};
 pub use orderbook::{
-    FeeOverflow, FeeSchedule, ManagerError, MassCancelResult, OrderBook, OrderBookError,
-    OrderBookSnapshot,
+    BookStats, CapacityStats, DEFAULT_FEE_VOLUME_WINDOW_MS, DEFAULT_LEVEL_POOL_SIZE, DepthQuote,
+    EventLogConfig, EventLogError, EventLogFormat, EventLogSink, ExpiryWheelStats, FeeOverflow,
+    FeeSchedule, FeeTier, LevelPoolStats, ManagerError, MassCancelResult, MidpointOrder, OrderBook,
+    OrderBookBuilder, OrderBookError, OrderBookSnapshot, QuoteLevel, RejectCode, TieredFeeSchedule,
+    TopOfBook, UserOrderSummary,
 };
 pub use utils::current_time_millis;
 #[cfg(feature = "alloc-counters")]
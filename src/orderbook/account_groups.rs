@@ -0,0 +1,134 @@
+//! Account groups for anti-internalization.
+//!
+//! Self-trade prevention normally compares owners: a taker conflicts with a
+//! resting order only when both carry the same user id. Firms that trade
+//! through several accounts also need those accounts kept apart, so the
+//! book keeps a registry mapping user ids to an account group id. When the
+//! taker belongs to a group, every resting order owned by a member of that
+//! group conflicts with it exactly like a same-user order.
+//!
+//! Groups do not add a mode of their own: the book's
+//! [`STPMode`](super::stp::STPMode) decides what happens on a conflict, and
+//! with [`STPMode::None`](super::stp::STPMode::None) groups are ignored.
+//! Anonymous orders (`Hash32::zero()`) bypass the check as before. Cancels
+//! caused by a group conflict carry
+//! [`CancelReason::SelfTradePrevention`](super::order_state::CancelReason::SelfTradePrevention)
+//! and a cancelled taker fails with
+//! [`OrderBookError::SelfTradePrevented`](super::error::OrderBookError::SelfTradePrevented).
+
+use super::book::OrderBook;
+use dashmap::DashMap;
+use pricelevel::Hash32;
+use tracing::trace;
+
+/// Registry of account group memberships, keyed by user id.
+///
+/// Each user belongs to at most one group. Lookups short-circuit while the
+/// registry is empty, so books without groups pay one length check per
+/// STP scan.
+#[derive(Debug, Default)]
+pub(crate) struct AccountGroups {
+    members: DashMap<Hash32, u64>,
+}
+
+impl AccountGroups {
+    /// The group `user_id` belongs to, if any.
+    #[inline]
+    pub(crate) fn group_of(&self, user_id: Hash32) -> Option<u64> {
+        if self.members.is_empty() {
+            return None;
+        }
+        self.members.get(&user_id).map(|group| *group)
+    }
+
+    /// Replace every membership with `members`.
+    pub(crate) fn replace(&self, members: Vec<(Hash32, u64)>) {
+        self.members.clear();
+        for (user_id, group) in members {
+            self.members.insert(user_id, group);
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Put `user_id` in account group `group`, returning the group it was
+    /// in before, if any.
+    ///
+    /// From the next match on, orders of `user_id` and of every other
+    /// member of `group` are kept from trading with each other according
+    /// to the book's [`STPMode`](super::stp::STPMode). Resting orders are
+    /// not touched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError, STPMode};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// book.set_stp_mode(STPMode::CancelTaker);
+    /// let desk_a = Hash32::new([1; 32]);
+    /// let desk_b = Hash32::new([2; 32]);
+    /// book.set_account_group(desk_a, 7);
+    /// book.set_account_group(desk_b, 7);
+    ///
+    /// book.add_limit_order_with_user(
+    ///     Id::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, desk_a, None,
+    /// )?;
+    /// let crossed = book.add_limit_order_with_user(
+    ///     Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, desk_b, None,
+    /// );
+    /// assert!(matches!(crossed, Err(OrderBookError::SelfTradePrevented { .. })));
+    /// # Ok::<(), OrderBookError>(())
+    /// ```
+    pub fn set_account_group(&self, user_id: Hash32, group: u64) -> Option<u64> {
+        trace!(
+            "Order book {}: Account {} joins group {}",
+            self.symbol, user_id, group
+        );
+        self.account_groups.members.insert(user_id, group)
+    }
+
+    /// Remove `user_id` from its account group, returning the group it was
+    /// in, if any.
+    pub fn clear_account_group(&self, user_id: Hash32) -> Option<u64> {
+        self.account_groups
+            .members
+            .remove(&user_id)
+            .map(|(_, group)| group)
+    }
+
+    /// The account group `user_id` belongs to, if any.
+    #[inline]
+    #[must_use]
+    pub fn account_group(&self, user_id: Hash32) -> Option<u64> {
+        self.account_groups.group_of(user_id)
+    }
+
+    /// Every account group membership as `(user_id, group)`, sorted by id
+    /// bytes.
+    #[must_use]
+    pub fn account_groups(&self) -> Vec<(Hash32, u64)> {
+        let mut members: Vec<(Hash32, u64)> = self
+            .account_groups
+            .members
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+        members.sort_unstable_by_key(|(user_id, _)| user_id.0);
+        members
+    }
+
+    /// The members of `group`, sorted by id bytes.
+    #[must_use]
+    pub fn account_group_members(&self, group: u64) -> Vec<Hash32> {
+        self.account_groups()
+            .into_iter()
+            .filter(|(_, member_of)| *member_of == group)
+            .map(|(user_id, _)| user_id)
+            .collect()
+    }
+}
@@ -0,0 +1,452 @@
+//! Arrow and Parquet export of engine output.
+//!
+//! Converts three kinds of output into Arrow [`RecordBatch`]es with a
+//! fixed schema, and writes batches as Parquet, so research tooling
+//! (Polars, pandas, DuckDB, Spark) can load them directly:
+//!
+//! - the trade tape — [`TradeResult`]s collected from a trade listener —
+//!   one row per fill ([`trade_schema`]);
+//! - periodic [`OrderBookSnapshot`]s, one row per price level
+//!   ([`snapshot_schema`]);
+//! - a depth series recorded by [`DepthRecorder`], one row per sample
+//!   ([`depth_schema`]).
+//!
+//! Every schema carries `orderbook_rs.schema` and
+//! `orderbook_rs.schema_version` metadata. Columns are only ever added at
+//! the end, with a version bump.
+//!
+//! Arrow has no unsigned 128-bit type, so prices are written as `UInt64`;
+//! a price above [`u64::MAX`] fails the conversion with
+//! [`ArrowExportError::ValueOverflow`]. Timestamps are Unix milliseconds
+//! as `Timestamp(Millisecond)`, sides are `"buy"` / `"sell"` and ids are
+//! their string form.
+//!
+//! Enabled by the `arrow` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use orderbook_rs::OrderBook;
+//! use orderbook_rs::orderbook::arrow::{snapshots_to_record_batch, write_parquet};
+//! use pricelevel::{Id, Side, TimeInForce};
+//!
+//! let book = OrderBook::<()>::new("BTC/USD");
+//! book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+//! book.add_limit_order(Id::new_uuid(), 101, 7, Side::Sell, TimeInForce::Gtc, None)?;
+//!
+//! let batch = snapshots_to_record_batch(&[book.create_snapshot(10)])?;
+//! assert_eq!(batch.num_rows(), 2);
+//!
+//! let mut parquet = Vec::new();
+//! write_parquet(&mut parquet, &batch)?;
+//! assert!(parquet.starts_with(b"PAR1"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::orderbook::book::OrderBook;
+use crate::orderbook::snapshot::OrderBookSnapshot;
+use crate::orderbook::trade::TradeResult;
+use ::arrow::array::{
+    ArrayRef, RecordBatch, StringBuilder, TimestampMillisecondBuilder, UInt32Builder, UInt64Builder,
+};
+use ::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use ::arrow::error::ArrowError;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use pricelevel::Side;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Version of the schemas below, stored in each schema's metadata.
+pub const ARROW_SCHEMA_VERSION: u32 = 1;
+
+/// Errors from converting engine output to Arrow or writing Parquet.
+#[derive(Debug, thiserror::Error)]
+pub enum ArrowExportError {
+    /// Building a record batch failed.
+    #[error("arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// Writing Parquet failed.
+    #[error("parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    /// A value does not fit its column type.
+    #[error("{column} value {value} does not fit in 64 bits")]
+    ValueOverflow {
+        /// Column being written
+        column: &'static str,
+        /// Value that overflowed
+        value: u128,
+    },
+}
+
+/// Schema of [`trades_to_record_batch`]: one row per fill.
+///
+/// | column | type |
+/// |---|---|
+/// | `symbol` | `Utf8` |
+/// | `engine_seq` | `UInt64` |
+/// | `timestamp` | `Timestamp(Millisecond)` |
+/// | `trade_id` | `Utf8` |
+/// | `taker_order_id` | `Utf8` |
+/// | `maker_order_id` | `Utf8` |
+/// | `taker_side` | `Utf8` |
+/// | `price` | `UInt64` |
+/// | `quantity` | `UInt64` |
+#[must_use]
+pub fn trade_schema() -> SchemaRef {
+    schema(
+        "trades",
+        vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("engine_seq", DataType::UInt64, false),
+            timestamp_field(),
+            Field::new("trade_id", DataType::Utf8, false),
+            Field::new("taker_order_id", DataType::Utf8, false),
+            Field::new("maker_order_id", DataType::Utf8, false),
+            Field::new("taker_side", DataType::Utf8, false),
+            Field::new("price", DataType::UInt64, false),
+            Field::new("quantity", DataType::UInt64, false),
+        ],
+    )
+}
+
+/// Schema of [`snapshots_to_record_batch`]: one row per price level,
+/// with `level` counting from `0` at the best price of each side.
+///
+/// | column | type |
+/// |---|---|
+/// | `symbol` | `Utf8` |
+/// | `timestamp` | `Timestamp(Millisecond)` |
+/// | `side` | `Utf8` |
+/// | `level` | `UInt32` |
+/// | `price` | `UInt64` |
+/// | `visible_quantity` | `UInt64` |
+/// | `hidden_quantity` | `UInt64` |
+/// | `order_count` | `UInt32` |
+#[must_use]
+pub fn snapshot_schema() -> SchemaRef {
+    schema(
+        "snapshots",
+        vec![
+            Field::new("symbol", DataType::Utf8, false),
+            timestamp_field(),
+            Field::new("side", DataType::Utf8, false),
+            Field::new("level", DataType::UInt32, false),
+            Field::new("price", DataType::UInt64, false),
+            Field::new("visible_quantity", DataType::UInt64, false),
+            Field::new("hidden_quantity", DataType::UInt64, false),
+            Field::new("order_count", DataType::UInt32, false),
+        ],
+    )
+}
+
+/// Schema of [`depth_to_record_batch`]: one row per [`DepthSample`].
+/// Best prices are null when the side is empty.
+///
+/// | column | type |
+/// |---|---|
+/// | `symbol` | `Utf8` |
+/// | `timestamp` | `Timestamp(Millisecond)` |
+/// | `best_bid` | `UInt64`, nullable |
+/// | `best_ask` | `UInt64`, nullable |
+/// | `bid_depth` | `UInt64` |
+/// | `ask_depth` | `UInt64` |
+/// | `bid_levels` | `UInt32` |
+/// | `ask_levels` | `UInt32` |
+#[must_use]
+pub fn depth_schema() -> SchemaRef {
+    schema(
+        "depth",
+        vec![
+            Field::new("symbol", DataType::Utf8, false),
+            timestamp_field(),
+            Field::new("best_bid", DataType::UInt64, true),
+            Field::new("best_ask", DataType::UInt64, true),
+            Field::new("bid_depth", DataType::UInt64, false),
+            Field::new("ask_depth", DataType::UInt64, false),
+            Field::new("bid_levels", DataType::UInt32, false),
+            Field::new("ask_levels", DataType::UInt32, false),
+        ],
+    )
+}
+
+/// Convert a trade tape to a [`trade_schema`] batch.
+///
+/// # Errors
+///
+/// [`ArrowExportError::ValueOverflow`] for a price above [`u64::MAX`].
+pub fn trades_to_record_batch(trades: &[TradeResult]) -> Result<RecordBatch, ArrowExportError> {
+    let fills = trades
+        .iter()
+        .map(|trade| trade.match_result.trades().as_vec().len())
+        .sum();
+    let mut symbol = StringBuilder::new();
+    let mut engine_seq = UInt64Builder::with_capacity(fills);
+    let mut timestamp = TimestampMillisecondBuilder::with_capacity(fills);
+    let mut trade_id = StringBuilder::new();
+    let mut taker_order_id = StringBuilder::new();
+    let mut maker_order_id = StringBuilder::new();
+    let mut taker_side = StringBuilder::new();
+    let mut price = UInt64Builder::with_capacity(fills);
+    let mut quantity = UInt64Builder::with_capacity(fills);
+
+    for trade in trades {
+        for fill in trade.match_result.trades().as_vec() {
+            symbol.append_value(&trade.symbol);
+            engine_seq.append_value(trade.engine_seq);
+            timestamp.append_value(millis(fill.timestamp().as_u64()));
+            trade_id.append_value(fill.trade_id().to_string());
+            taker_order_id.append_value(fill.taker_order_id().to_string());
+            maker_order_id.append_value(fill.maker_order_id().to_string());
+            taker_side.append_value(side_name(fill.taker_side()));
+            price.append_value(narrow("price", fill.price().as_u128())?);
+            quantity.append_value(fill.quantity().as_u64());
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(symbol.finish()),
+        Arc::new(engine_seq.finish()),
+        Arc::new(timestamp.finish()),
+        Arc::new(trade_id.finish()),
+        Arc::new(taker_order_id.finish()),
+        Arc::new(maker_order_id.finish()),
+        Arc::new(taker_side.finish()),
+        Arc::new(price.finish()),
+        Arc::new(quantity.finish()),
+    ];
+    Ok(RecordBatch::try_new(trade_schema(), columns)?)
+}
+
+/// Convert snapshots to a [`snapshot_schema`] batch, bids before asks
+/// within each snapshot.
+///
+/// # Errors
+///
+/// [`ArrowExportError::ValueOverflow`] for a price above [`u64::MAX`].
+pub fn snapshots_to_record_batch(
+    snapshots: &[OrderBookSnapshot],
+) -> Result<RecordBatch, ArrowExportError> {
+    let mut symbol = StringBuilder::new();
+    let mut timestamp = TimestampMillisecondBuilder::new();
+    let mut side = StringBuilder::new();
+    let mut level = UInt32Builder::new();
+    let mut price = UInt64Builder::new();
+    let mut visible_quantity = UInt64Builder::new();
+    let mut hidden_quantity = UInt64Builder::new();
+    let mut order_count = UInt32Builder::new();
+
+    for snapshot in snapshots {
+        for (level_side, levels) in [(Side::Buy, &snapshot.bids), (Side::Sell, &snapshot.asks)] {
+            for (index, entry) in levels.iter().enumerate() {
+                symbol.append_value(&snapshot.symbol);
+                timestamp.append_value(millis(snapshot.timestamp));
+                side.append_value(side_name(level_side));
+                level.append_value(saturating_u32(index));
+                price.append_value(narrow("price", entry.price().as_u128())?);
+                visible_quantity.append_value(entry.visible_quantity().as_u64());
+                hidden_quantity.append_value(entry.hidden_quantity().as_u64());
+                order_count.append_value(saturating_u32(entry.order_count()));
+            }
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(symbol.finish()),
+        Arc::new(timestamp.finish()),
+        Arc::new(side.finish()),
+        Arc::new(level.finish()),
+        Arc::new(price.finish()),
+        Arc::new(visible_quantity.finish()),
+        Arc::new(hidden_quantity.finish()),
+        Arc::new(order_count.finish()),
+    ];
+    Ok(RecordBatch::try_new(snapshot_schema(), columns)?)
+}
+
+/// Convert a depth series to a [`depth_schema`] batch.
+///
+/// # Errors
+///
+/// [`ArrowExportError::ValueOverflow`] for a price above [`u64::MAX`].
+pub fn depth_to_record_batch(samples: &[DepthSample]) -> Result<RecordBatch, ArrowExportError> {
+    let mut symbol = StringBuilder::new();
+    let mut timestamp = TimestampMillisecondBuilder::with_capacity(samples.len());
+    let mut best_bid = UInt64Builder::with_capacity(samples.len());
+    let mut best_ask = UInt64Builder::with_capacity(samples.len());
+    let mut bid_depth = UInt64Builder::with_capacity(samples.len());
+    let mut ask_depth = UInt64Builder::with_capacity(samples.len());
+    let mut bid_levels = UInt32Builder::with_capacity(samples.len());
+    let mut ask_levels = UInt32Builder::with_capacity(samples.len());
+
+    for sample in samples {
+        symbol.append_value(&sample.symbol);
+        timestamp.append_value(millis(sample.timestamp));
+        best_bid.append_option(sample.best_bid.map(|p| narrow("best_bid", p)).transpose()?);
+        best_ask.append_option(sample.best_ask.map(|p| narrow("best_ask", p)).transpose()?);
+        bid_depth.append_value(sample.bid_depth);
+        ask_depth.append_value(sample.ask_depth);
+        bid_levels.append_value(sample.bid_levels);
+        ask_levels.append_value(sample.ask_levels);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(symbol.finish()),
+        Arc::new(timestamp.finish()),
+        Arc::new(best_bid.finish()),
+        Arc::new(best_ask.finish()),
+        Arc::new(bid_depth.finish()),
+        Arc::new(ask_depth.finish()),
+        Arc::new(bid_levels.finish()),
+        Arc::new(ask_levels.finish()),
+    ];
+    Ok(RecordBatch::try_new(depth_schema(), columns)?)
+}
+
+/// Write `batch` to `writer` as a Snappy-compressed Parquet file.
+///
+/// # Errors
+///
+/// [`ArrowExportError::Parquet`] if encoding or writing fails.
+pub fn write_parquet<W>(writer: W, batch: &RecordBatch) -> Result<(), ArrowExportError>
+where
+    W: Write + Send,
+{
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), Some(properties))?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Aggregate depth of one book at one instant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthSample {
+    /// Book symbol
+    pub symbol: String,
+    /// Book clock time in milliseconds
+    pub timestamp: u64,
+    /// Best bid price, if any
+    pub best_bid: Option<u128>,
+    /// Best ask price, if any
+    pub best_ask: Option<u128>,
+    /// Visible bid quantity over the sampled levels
+    pub bid_depth: u64,
+    /// Visible ask quantity over the sampled levels
+    pub ask_depth: u64,
+    /// Bid levels sampled
+    pub bid_levels: u32,
+    /// Ask levels sampled
+    pub ask_levels: u32,
+}
+
+impl DepthSample {
+    /// Summarise `snapshot`.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &OrderBookSnapshot) -> Self {
+        Self {
+            symbol: snapshot.symbol.clone(),
+            timestamp: snapshot.timestamp,
+            best_bid: snapshot.best_bid().map(|(price, _)| price),
+            best_ask: snapshot.best_ask().map(|(price, _)| price),
+            bid_depth: snapshot.total_bid_volume(),
+            ask_depth: snapshot.total_ask_volume(),
+            bid_levels: saturating_u32(snapshot.bids.len()),
+            ask_levels: saturating_u32(snapshot.asks.len()),
+        }
+    }
+}
+
+/// Records a depth series by sampling books on demand.
+#[derive(Debug, Clone)]
+pub struct DepthRecorder {
+    depth: usize,
+    samples: Vec<DepthSample>,
+}
+
+impl DepthRecorder {
+    /// A recorder summing the best `depth` levels of each side.
+    #[must_use]
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Sample `book` at its clock's current time.
+    pub fn record<T>(&mut self, book: &OrderBook<T>)
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        self.samples.push(DepthSample::from_snapshot(
+            &book.create_snapshot(self.depth),
+        ));
+    }
+
+    /// Samples recorded so far.
+    #[must_use]
+    pub fn samples(&self) -> &[DepthSample] {
+        &self.samples
+    }
+
+    /// Take the samples recorded so far, leaving the recorder empty.
+    pub fn take_samples(&mut self) -> Vec<DepthSample> {
+        std::mem::take(&mut self.samples)
+    }
+
+    /// The samples as a [`depth_schema`] batch.
+    ///
+    /// # Errors
+    ///
+    /// See [`depth_to_record_batch`].
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowExportError> {
+        depth_to_record_batch(&self.samples)
+    }
+}
+
+fn schema(name: &str, fields: Vec<Field>) -> SchemaRef {
+    let metadata = HashMap::from([
+        ("orderbook_rs.schema".to_string(), name.to_string()),
+        (
+            "orderbook_rs.schema_version".to_string(),
+            ARROW_SCHEMA_VERSION.to_string(),
+        ),
+    ]);
+    Arc::new(Schema::new_with_metadata(fields, metadata))
+}
+
+fn timestamp_field() -> Field {
+    Field::new(
+        "timestamp",
+        DataType::Timestamp(TimeUnit::Millisecond, None),
+        false,
+    )
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "buy",
+        Side::Sell => "sell",
+    }
+}
+
+fn narrow(column: &'static str, value: u128) -> Result<u64, ArrowExportError> {
+    u64::try_from(value).map_err(|_| ArrowExportError::ValueOverflow { column, value })
+}
+
+fn millis(timestamp: u64) -> i64 {
+    i64::try_from(timestamp).unwrap_or(i64::MAX)
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
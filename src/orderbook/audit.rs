@@ -0,0 +1,422 @@
+//! Structural invariant auditor.
+//!
+//! [`OrderBook::audit`] cross-checks the price levels against every index
+//! and cache the book maintains alongside them: the book must not be
+//! crossed or locked, each resting order must be indexed in
+//! `order_locations` and `user_orders` exactly where it rests (and those
+//! indexes must hold nothing else), a valid cached best price must equal
+//! the real one, and the O(1) depth totals must equal the sum over the
+//! levels. It walks the whole book, so it is meant for tests, soak runs
+//! and periodic production health checks rather than the hot path.
+//!
+//! With the `audit` feature, [`OrderBook::set_audit_interval`] makes the
+//! book run the auditor on its own every N operations and panic on the
+//! first violation.
+
+use super::book::OrderBook;
+use pricelevel::{Hash32, Id, Side};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "audit")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// One broken invariant reported by [`OrderBook::audit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum InvariantViolation {
+    /// The best bid is above the best ask.
+    CrossedBook {
+        /// Best bid price
+        best_bid: u128,
+        /// Best ask price
+        best_ask: u128,
+    },
+
+    /// The best bid equals the best ask.
+    LockedBook {
+        /// The shared best price
+        price: u128,
+    },
+
+    /// A price level is stored under a key other than its own price.
+    LevelPriceMismatch {
+        /// Side of the level
+        side: Side,
+        /// Key the level is stored under
+        key: u128,
+        /// Price the level reports
+        level_price: u128,
+    },
+
+    /// A price level with no orders is still in the book.
+    EmptyLevel {
+        /// Side of the level
+        side: Side,
+        /// Price of the level
+        price: u128,
+    },
+
+    /// The same order id rests more than once.
+    DuplicateOrder {
+        /// The duplicated order
+        order_id: Id,
+    },
+
+    /// A resting order has no `order_locations` entry.
+    MissingOrderLocation {
+        /// The unindexed order
+        order_id: Id,
+        /// Side it rests on
+        side: Side,
+        /// Price it rests at
+        price: u128,
+    },
+
+    /// A resting order's `order_locations` entry points elsewhere.
+    MisplacedOrderLocation {
+        /// The misindexed order
+        order_id: Id,
+        /// Side and price it rests at
+        resting: (Side, u128),
+        /// Side and price the index records
+        indexed: (Side, u128),
+    },
+
+    /// An `order_locations` entry for an order that is not resting.
+    StaleOrderLocation {
+        /// The indexed order
+        order_id: Id,
+        /// Indexed side
+        side: Side,
+        /// Indexed price
+        price: u128,
+    },
+
+    /// A resting order is missing from its user's `user_orders` entry.
+    MissingUserOrder {
+        /// Owner of the order
+        user_id: Hash32,
+        /// The unindexed order
+        order_id: Id,
+    },
+
+    /// A `user_orders` entry lists an order that is not resting, or that
+    /// belongs to another user.
+    StaleUserOrder {
+        /// User the entry is filed under
+        user_id: Hash32,
+        /// The listed order
+        order_id: Id,
+    },
+
+    /// A valid cached best price differs from the best price in the book.
+    StaleCachedBestPrice {
+        /// Side of the cached price
+        side: Side,
+        /// Cached price
+        cached: u128,
+        /// Best price in the book, `None` for an empty side
+        actual: Option<u128>,
+    },
+
+    /// The O(1) depth totals of a side differ from the sum over its levels.
+    DepthTotalsMismatch {
+        /// Side of the totals
+        side: Side,
+        /// Tracked total quantity (visible plus hidden)
+        tracked_quantity: u64,
+        /// Summed total quantity (visible plus hidden)
+        actual_quantity: u64,
+        /// Tracked order count
+        tracked_orders: usize,
+        /// Summed order count
+        actual_orders: usize,
+    },
+}
+
+/// Cadence of the automatic audit enabled by
+/// [`OrderBook::set_audit_interval`].
+#[cfg(feature = "audit")]
+#[derive(Debug, Default)]
+pub(super) struct AuditSchedule {
+    /// Operations between audits; `0` disables the schedule
+    interval: AtomicU64,
+    /// Gated operations counted since the interval was set
+    operations: AtomicU64,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CrossedBook { best_bid, best_ask } => {
+                write!(f, "crossed book: best bid {best_bid} > best ask {best_ask}")
+            }
+            Self::LockedBook { price } => write!(f, "locked book at {price}"),
+            Self::LevelPriceMismatch {
+                side,
+                key,
+                level_price,
+            } => write!(
+                f,
+                "{side} level stored at {key} reports price {level_price}"
+            ),
+            Self::EmptyLevel { side, price } => write!(f, "empty {side} level at {price}"),
+            Self::DuplicateOrder { order_id } => write!(f, "order {order_id} rests twice"),
+            Self::MissingOrderLocation {
+                order_id,
+                side,
+                price,
+            } => write!(
+                f,
+                "order {order_id} resting at {side} {price} has no location entry"
+            ),
+            Self::MisplacedOrderLocation {
+                order_id,
+                resting,
+                indexed,
+            } => write!(
+                f,
+                "order {order_id} rests at {} {} but is indexed at {} {}",
+                resting.0, resting.1, indexed.0, indexed.1
+            ),
+            Self::StaleOrderLocation {
+                order_id,
+                side,
+                price,
+            } => write!(
+                f,
+                "location entry {side} {price} for order {order_id} that is not resting"
+            ),
+            Self::MissingUserOrder { user_id, order_id } => {
+                write!(f, "order {order_id} missing from user {user_id} index")
+            }
+            Self::StaleUserOrder { user_id, order_id } => {
+                write!(
+                    f,
+                    "user {user_id} index lists order {order_id} it does not own"
+                )
+            }
+            Self::StaleCachedBestPrice {
+                side,
+                cached,
+                actual,
+            } => write!(f, "cached best {side} {cached} differs from {actual:?}"),
+            Self::DepthTotalsMismatch {
+                side,
+                tracked_quantity,
+                actual_quantity,
+                tracked_orders,
+                actual_orders,
+            } => write!(
+                f,
+                "{side} depth totals {tracked_quantity} qty / {tracked_orders} orders, levels hold {actual_quantity} qty / {actual_orders} orders"
+            ),
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Check the book's structural invariants and return every violation
+    /// found; an empty vector means the book is consistent.
+    ///
+    /// Takes the submit gate exclusively, so every submit, modify and
+    /// cancel waits for the audit and the result describes one quiescent
+    /// state. Must not be called from a trade or price-level listener.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None).unwrap();
+    /// book.add_limit_order(Id::new(), 101, 10, Side::Sell, TimeInForce::Gtc, None).unwrap();
+    ///
+    /// assert!(book.audit().is_empty());
+    /// ```
+    #[must_use]
+    pub fn audit(&self) -> Vec<InvariantViolation> {
+        let _gate = self.submit_gate_write();
+        self.collect_violations()
+    }
+
+    /// Run [`Self::audit`] automatically every `every` mutating
+    /// operations; `0` (the default) disables it.
+    ///
+    /// The check runs on the thread whose operation reaches the interval,
+    /// before that operation takes the submit gate, and panics with every
+    /// violation found. Intended for tests and soak runs, where a broken
+    /// invariant should stop the run close to the operation that caused
+    /// it.
+    #[cfg(feature = "audit")]
+    pub fn set_audit_interval(&self, every: u64) {
+        self.audit_schedule.interval.store(every, Ordering::Relaxed);
+        self.audit_schedule.operations.store(0, Ordering::Relaxed);
+    }
+
+    /// Count one gated operation and audit the book when the configured
+    /// interval is reached. Called by the submit gate accessors before
+    /// they acquire the gate, so it can take the gate exclusively itself.
+    #[cfg(feature = "audit")]
+    pub(super) fn run_scheduled_audit(&self) {
+        let every = self.audit_schedule.interval.load(Ordering::Relaxed);
+        if every == 0 {
+            return;
+        }
+        let operations = self
+            .audit_schedule
+            .operations
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        if !operations.is_multiple_of(every) {
+            return;
+        }
+        let violations = {
+            let _gate = self
+                .submit_gate
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            self.collect_violations()
+        };
+        assert!(
+            violations.is_empty(),
+            "order book {} failed its audit after {operations} operations: {violations:?}",
+            self.symbol
+        );
+    }
+
+    /// The audit itself, without the gate. The caller must exclude
+    /// concurrent mutation.
+    pub(super) fn collect_violations(&self) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+        let mut resting: HashMap<Id, Hash32> = HashMap::new();
+
+        for (side, levels) in [(Side::Buy, &self.bids), (Side::Sell, &self.asks)] {
+            let mut actual_quantity = 0u64;
+            let mut actual_orders = 0usize;
+            for entry in levels.iter() {
+                let price = *entry.key();
+                let level = entry.value();
+                if level.price() != price {
+                    violations.push(InvariantViolation::LevelPriceMismatch {
+                        side,
+                        key: price,
+                        level_price: level.price(),
+                    });
+                }
+                if level.order_count() == 0 {
+                    violations.push(InvariantViolation::EmptyLevel { side, price });
+                }
+                actual_quantity = actual_quantity
+                    .saturating_add(level.visible_quantity())
+                    .saturating_add(level.hidden_quantity());
+                actual_orders += level.order_count();
+
+                for order in level.iter_orders() {
+                    let order_id = order.id();
+                    let user_id = order.user_id();
+                    if resting.insert(order_id, user_id).is_some() {
+                        violations.push(InvariantViolation::DuplicateOrder { order_id });
+                        continue;
+                    }
+                    match self
+                        .order_locations
+                        .get(&order_id)
+                        .map(|location| *location)
+                    {
+                        None => violations.push(InvariantViolation::MissingOrderLocation {
+                            order_id,
+                            side,
+                            price,
+                        }),
+                        Some((indexed_price, indexed_side))
+                            if (indexed_price, indexed_side) != (price, side) =>
+                        {
+                            violations.push(InvariantViolation::MisplacedOrderLocation {
+                                order_id,
+                                resting: (side, price),
+                                indexed: (indexed_side, indexed_price),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                    let indexed_for_user = self
+                        .user_orders
+                        .get(&user_id)
+                        .is_some_and(|ids| ids.contains(&order_id));
+                    if !indexed_for_user {
+                        violations.push(InvariantViolation::MissingUserOrder { user_id, order_id });
+                    }
+                }
+            }
+
+            let tracked_quantity = self.depth_totals.quantity(side);
+            let tracked_orders = self.depth_totals.order_count(side);
+            if (tracked_quantity, tracked_orders) != (actual_quantity, actual_orders) {
+                violations.push(InvariantViolation::DepthTotalsMismatch {
+                    side,
+                    tracked_quantity,
+                    actual_quantity,
+                    tracked_orders,
+                    actual_orders,
+                });
+            }
+        }
+
+        for entry in self.order_locations.iter() {
+            let (price, side) = *entry.value();
+            if !resting.contains_key(entry.key()) {
+                violations.push(InvariantViolation::StaleOrderLocation {
+                    order_id: *entry.key(),
+                    side,
+                    price,
+                });
+            }
+        }
+
+        for entry in self.user_orders.iter() {
+            let user_id = *entry.key();
+            for order_id in entry.value() {
+                if resting.get(order_id) != Some(&user_id) {
+                    violations.push(InvariantViolation::StaleUserOrder {
+                        user_id,
+                        order_id: *order_id,
+                    });
+                }
+            }
+        }
+
+        let best_bid = self.bids.back().map(|entry| *entry.key());
+        let best_ask = self.asks.front().map(|entry| *entry.key());
+        match (best_bid, best_ask) {
+            (Some(best_bid), Some(best_ask)) if best_bid > best_ask => {
+                violations.push(InvariantViolation::CrossedBook { best_bid, best_ask });
+            }
+            (Some(best_bid), Some(best_ask)) if best_bid == best_ask => {
+                violations.push(InvariantViolation::LockedBook { price: best_bid });
+            }
+            _ => {}
+        }
+        for (side, cached, actual) in [
+            (Side::Buy, self.cache.get_cached_best_bid(), best_bid),
+            (Side::Sell, self.cache.get_cached_best_ask(), best_ask),
+        ] {
+            if let Some(cached) = cached
+                && Some(cached) != actual
+            {
+                violations.push(InvariantViolation::StaleCachedBestPrice {
+                    side,
+                    cached,
+                    actual,
+                });
+            }
+        }
+
+        violations
+    }
+}
@@ -1,31 +1,71 @@
 //! Core OrderBook implementation for managing price levels and orders
 
-use super::cache::PriceLevelCache;
+use super::account_groups::AccountGroups;
+#[cfg(feature = "audit")]
+use super::audit::AuditSchedule;
+use super::book_config::BookConfig;
+use super::book_stats::{BookCounters, BookStats};
+use super::cache::{PriceLevelCache, TopOfBook};
+use super::cancel_on_disconnect::Heartbeats;
+use super::circuit_breaker::CircuitBreakerState;
 use super::clock::{Clock, MonotonicClock};
+use super::depth_mode::DepthMode;
+use super::depth_totals::DepthTotals;
+use super::depth_view::DepthView;
 use super::error::OrderBookError;
-use super::fees::FeeSchedule;
-use super::iterators::{LevelInfo, LevelsInRange, LevelsUntilDepth, LevelsWithCumulativeDepth};
-use super::market_impact::{MarketImpact, OrderSimulation};
+use super::expiry_wheel::{ExpiryWheel, ExpiryWheelStats};
+use super::fees::{FeeSchedule, TieredFeeSchedule};
+use super::iterators::{
+    Ladder, LevelInfo, LevelsInRange, LevelsUntilDepth, LevelsWithCumulativeDepth, OrderCursor,
+    OrdersPage,
+};
+use super::level_gate::LevelGate;
+use super::level_map::PriceLevelMap;
+use super::level_pool::{LevelPool, LevelPoolStats};
+use super::listener_slot::ListenerSlot;
+use super::market_impact::{LiquidationPoint, MarketImpact, OrderSimulation};
+use super::market_protection::ProtectionRemainder;
+use super::midpoint::MidpointSegment;
+use super::order_age::OrderLifetimes;
+use super::order_id_dedup::OrderIdDedup;
+use super::order_state::{CancelReason, OrderStatus};
+use super::order_tag::OrderTags;
+use super::price_scale::PriceScale;
+use super::priority_class::PriorityClasses;
+use super::rate_limit::{RateLimitConfig, RateLimiter};
+use super::reference_price::{ExternalPriceSource, ExternalPrices};
+use super::reject_reason::RejectReason;
+use super::replenishment::ReplenishmentListener;
 use super::risk::{ReferencePriceSource, RiskConfig, RiskState};
-use super::snapshot::{EnrichedSnapshot, MetricFlags, OrderBookSnapshot, OrderBookSnapshotPackage};
+use super::session::SessionState;
+use super::snapshot::{
+    EnrichedSnapshot, LevelAggregate, LevelAggregates, MetricFlags, OrderBookSnapshot,
+    OrderBookSnapshotPackage,
+};
+use super::speed_bump::SpeedBumpQueue;
 use super::statistics::{DepthStats, DistributionBin};
+use super::trade_amendment::ExecutionHistory;
 use crate::orderbook::book_change_event::PriceLevelChangedListener;
 #[cfg(feature = "special_orders")]
 use crate::orderbook::repricing::SpecialOrderTracker;
+#[cfg(feature = "special_orders")]
+use crate::orderbook::stop_trigger::StopTriggerState;
 use crate::orderbook::stp::STPMode;
-use crate::orderbook::trade::{TradeListener, TradeResult};
+use crate::orderbook::trade::TradeListener;
 use crossbeam::atomic::AtomicCell;
-use crossbeam_skiplist::SkipMap;
-use dashmap::DashMap;
+use crossbeam::utils::CachePadded;
+use dashmap::{DashMap, DashSet};
 use either::Either;
 #[cfg(feature = "special_orders")]
 use pricelevel::OrderUpdate;
-use pricelevel::{Hash32, Id, MatchResult, OrderType, PriceLevel, Side, UuidGenerator};
+use pricelevel::{
+    Hash32, Id, MatchResult, OrderType, PriceLevel, Side, TakerKind, TimeInForce, UuidGenerator,
+};
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::marker::PhantomData;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tracing::trace;
 use uuid::Uuid;
 
@@ -33,6 +73,18 @@ use uuid::Uuid;
 /// One basis point = 0.01% = 0.0001
 const DEFAULT_BASIS_POINTS_MULTIPLIER: f64 = 10_000.0;
 
+/// Most lock shards an order index is split into. Larger requests are
+/// clamped, so rounding up to a power of two cannot overflow.
+const MAX_ORDER_INDEX_SHARDS: usize = 1 << 16;
+
+/// `shard_count` as a `DashMap` shard amount: a power of two between 2 and
+/// [`MAX_ORDER_INDEX_SHARDS`].
+pub(super) fn index_shard_amount(shard_count: usize) -> usize {
+    shard_count
+        .clamp(2, MAX_ORDER_INDEX_SHARDS)
+        .next_power_of_two()
+}
+
 /// The OrderBook manages a collection of price levels for both bid and ask sides.
 /// It supports adding, cancelling, and matching orders with lock-free operations where possible.
 pub struct OrderBook<T = ()> {
@@ -42,14 +94,16 @@ pub struct OrderBook<T = ()> {
     /// Bid side price levels (buy orders), stored in a concurrent ordered map (skip list)
     /// The map is keyed by price levels and stores Arc references to PriceLevel instances
     /// Using SkipMap provides O(log N) operations with automatic ordering, eliminating
-    /// the need to sort prices during matching (optimization from O(N log N) to O(M log N))
-    pub(super) bids: SkipMap<u128, Arc<PriceLevel>>,
+    /// the need to sort prices during matching (optimization from O(N log N) to O(M log N)).
+    /// Optionally split into price-range shards, see [`Self::with_level_shards`].
+    pub(super) bids: PriceLevelMap,
 
     /// Ask side price levels (sell orders), stored in a concurrent ordered map (skip list)
     /// The map is keyed by price levels and stores Arc references to PriceLevel instances
     /// Using SkipMap provides O(log N) operations with automatic ordering, eliminating
-    /// the need to sort prices during matching (optimization from O(N log N) to O(M log N))
-    pub(super) asks: SkipMap<u128, Arc<PriceLevel>>,
+    /// the need to sort prices during matching (optimization from O(N log N) to O(M log N)).
+    /// Optionally split into price-range shards, see [`Self::with_level_shards`].
+    pub(super) asks: PriceLevelMap,
 
     /// A concurrent map from order ID to (price, side) for fast lookups
     /// This avoids having to search through all price levels to find an order
@@ -60,6 +114,17 @@ pub struct OrderBook<T = ()> {
     /// to enable O(1) user-based mass cancellation.
     pub(super) user_orders: DashMap<Hash32, Vec<Id>>,
 
+    /// Owner of every resting order: the reverse of `user_orders`, kept in
+    /// lockstep with it. Lets the matching engine name a maker's user and
+    /// untrack a filled maker without scanning every user's order list.
+    pub(super) order_owners: DashMap<Id, Hash32>,
+
+    /// Caller-supplied `extra_fields` of resting orders, keyed by order id.
+    /// Price levels store `OrderType<()>`, so the payload lives here and is
+    /// re-attached by [`Self::convert_from_unit_type`]. Never populated when
+    /// `T` is zero-sized.
+    pub(super) extra_fields: DashMap<Id, T>,
+
     /// Generator for unique transaction IDs
     pub(super) transaction_id_generator: UuidGenerator,
 
@@ -71,8 +136,15 @@ pub struct OrderBook<T = ()> {
     /// and stamped on every outbound event (`TradeResult`,
     /// `PriceLevelChangedEvent`) so consumers can perform cross-stream gap
     /// detection and temporal ordering. Per `OrderBook<T>` instance — replay
-    /// into a fresh book yields fresh seqs, not the originals.
-    pub(super) engine_seq: AtomicU64,
+    /// into a fresh book yields fresh seqs, not the originals. On its own
+    /// cache line: every submitting thread bumps it, and it must not drag
+    /// the read-mostly fields around it into that contention.
+    pub(super) engine_seq: CachePadded<AtomicU64>,
+
+    /// Next execution id, minted by [`Self::next_execution_id`] for each
+    /// captured fill. Starts at `1` so that `0` can mean "not assigned"
+    /// on payloads that pre-date execution ids.
+    pub(super) execution_seq: AtomicU64,
 
     /// Operational kill switch. When `true`, every public `submit_*`,
     /// `add_order`, and non-cancel `update_order` call short-circuits with
@@ -83,6 +155,12 @@ pub struct OrderBook<T = ()> {
     /// [`OrderBookSnapshotPackage::kill_switch_engaged`](super::snapshot::OrderBookSnapshotPackage::kill_switch_engaged).
     pub(super) kill_switch: AtomicBool,
 
+    /// When `true`, every emitted [`TradeResult`] carries
+    /// [`TradeResult::level_fills`]. Set with
+    /// [`Self::set_trade_level_aggregation`]; not persisted across
+    /// snapshot/restore.
+    pub(super) trade_level_aggregation: AtomicBool,
+
     /// Pre-trade risk state: optional [`RiskConfig`] plus per-account
     /// counters and per-order entries. When the embedded config is
     /// `None` (default), every check is a passthrough and every hook
@@ -92,12 +170,71 @@ pub struct OrderBook<T = ()> {
     /// post-restore by walking the snapshot's resting orders.
     pub(super) risk_state: RiskState,
 
-    /// The last price at which a trade occurred
-    pub(super) last_trade_price: AtomicCell<u128>,
+    /// Externally supplied index and mark prices, read by price bands,
+    /// stop triggers, pegged orders and enriched snapshots. Not persisted
+    /// across snapshot/restore.
+    pub(super) external_prices: ExternalPrices,
+
+    /// Progress through the configured trading session, mirroring
+    /// [`BookConfig::trading_session`]. Reset, like the schedule, by
+    /// every configuration change that replaces it.
+    pub(super) session: SessionState,
+
+    /// Trade price window, trip state and listener of the circuit
+    /// breaker configured in [`BookConfig::circuit_breaker`].
+    pub(super) circuit_breaker: CircuitBreakerState,
+
+    /// Token-bucket rate limiter for new flow. Like [`Self::risk_state`]
+    /// it is always present and a passthrough until
+    /// [`Self::set_rate_limit_config`] installs a configuration. Neither
+    /// the config nor the buckets are persisted across snapshot/restore.
+    pub(super) rate_limiter: RateLimiter,
+
+    /// Users blocked by [`Self::block_user`]. New flow from a blocked
+    /// user is rejected with [`OrderBookError::UserBlocked`]; cancels are
+    /// not gated. Persisted across snapshot/restore via
+    /// [`OrderBookSnapshotPackage::blocked_users`](super::snapshot::OrderBookSnapshotPackage::blocked_users).
+    pub(super) blocked_users: DashSet<Hash32>,
+
+    /// Account group registry consulted by self-trade prevention, set
+    /// with [`Self::set_account_group`]. Persisted across snapshot/restore
+    /// via [`OrderBookSnapshotPackage::account_groups`](super::snapshot::OrderBookSnapshotPackage::account_groups).
+    pub(super) account_groups: AccountGroups,
+
+    /// Class priority switch and priority class registry, set with
+    /// [`Self::set_class_priority`] and [`Self::set_priority_class`].
+    /// Persisted across snapshot/restore via
+    /// [`OrderBookSnapshotPackage::priority_classes`](super::snapshot::OrderBookSnapshotPackage::priority_classes).
+    pub(super) priority_classes: PriorityClasses,
+
+    /// Resting orders of the midpoint (dark) segment, see
+    /// [`Self::submit_midpoint_order`]. Persisted across snapshot/restore
+    /// via [`OrderBookSnapshotPackage::midpoint_orders`](super::snapshot::OrderBookSnapshotPackage::midpoint_orders).
+    pub(super) midpoint: Mutex<MidpointSegment>,
+
+    /// Aggressive orders held by the speed bump, see
+    /// [`Self::submit_through_speed_bump`]. Not persisted across
+    /// snapshot/restore.
+    pub(super) speed_bump: Mutex<SpeedBumpQueue<T>>,
+
+    /// Time-windowed duplicate order id detector, inactive until
+    /// [`Self::set_duplicate_order_id_window`] installs a window. Neither
+    /// the window nor the recorded ids are persisted across
+    /// snapshot/restore.
+    pub(super) order_id_dedup: OrderIdDedup,
+
+    /// The last price at which a trade occurred. Padded away from the
+    /// read-mostly fields, since every trade writes it.
+    pub(super) last_trade_price: CachePadded<AtomicCell<u128>>,
 
     /// Flag indicating if there was a trade
     pub(super) has_traded: AtomicBool,
 
+    /// Execution id of the book's most recent trade, or `0` when that
+    /// trade was not emitted with one. Lets a bust of the latest fill
+    /// roll `last_trade_price` back.
+    pub(super) last_execution_id: AtomicU64,
+
     /// The timestamp of market close, if applicable (for DAY orders)
     pub(super) market_close_timestamp: AtomicU64,
 
@@ -107,6 +244,36 @@ pub struct OrderBook<T = ()> {
     /// A cache for storing best bid/ask prices to avoid recalculation
     pub(super) cache: PriceLevelCache,
 
+    /// Per-side resting quantity and order count, maintained on every
+    /// level mutation
+    pub(super) depth_totals: DepthTotals,
+
+    /// Emptied price levels kept for reuse when their price is re-created
+    pub(super) level_pool: LevelPool,
+
+    /// Optional top-N depth view republished after every mutation
+    pub(super) depth_view: DepthView,
+
+    /// Deadlines of resting `Gtd` orders and the set of resting `Day`
+    /// orders, so expiry sweeps only visit due orders
+    pub(super) expiry_wheel: ExpiryWheel,
+
+    /// Cumulative activity counters behind [`Self::stats`]
+    pub(super) counters: BookCounters,
+
+    /// Entry times and lifetime histograms behind
+    /// [`Self::quote_lifetimes`]
+    pub(super) order_lifetimes: OrderLifetimes,
+
+    /// Tags of resting orders, set with [`Self::tag_order`]. Persisted
+    /// across snapshot/restore via
+    /// [`OrderBookSnapshotPackage::order_tags`](super::snapshot::OrderBookSnapshotPackage::order_tags).
+    pub(super) order_tags: OrderTags,
+
+    /// Cancel-on-disconnect sessions registered with
+    /// [`Self::register_heartbeat`]. Not persisted across snapshot/restore.
+    pub(super) heartbeats: Heartbeats,
+
     /// Book-level linearization gate for multi-level fill-or-kill (#209).
     ///
     /// Every mutating entry point takes the **read** side (uncontended:
@@ -121,49 +288,80 @@ pub struct OrderBook<T = ()> {
     /// synchronous).
     pub(super) submit_gate: std::sync::RwLock<()>,
 
-    /// listens to possible trades when an order is added
-    pub trade_listener: Option<TradeListener>,
+    /// Guards price-level lifetime against concurrent admission, striped
+    /// by price; see [`LevelGate`].
+    pub(super) level_gate: LevelGate,
+
+    /// listens to possible trades when an order is added; swapped through
+    /// [`Self::set_trade_listener`]
+    pub(super) trade_listener: ListenerSlot<TradeListener>,
 
     /// Phantom data to maintain generic type parameter
     _phantom: PhantomData<T>,
 
     /// listens to order book changes. This provides a point to update a corresponding external order book e.g. in the UI
-    pub price_level_changed_listener: Option<PriceLevelChangedListener>,
+    pub(super) price_level_changed_listener: ListenerSlot<PriceLevelChangedListener>,
+
+    /// Receives iceberg and reserve replenishments; swapped through
+    /// [`Self::set_replenishment_listener`]
+    pub(super) replenishment_listener: ListenerSlot<ReplenishmentListener>,
 
     /// Tracker for special orders that require re-pricing (PeggedOrder and TrailingStop)
     #[cfg(feature = "special_orders")]
     pub(super) special_order_tracker: SpecialOrderTracker,
 
-    /// Minimum price increment for orders. When set, order prices must be
-    /// exact multiples of this value. `None` disables validation (default).
-    pub(super) tick_size: Option<u128>,
+    /// Stop trigger reference and external mark price
+    #[cfg(feature = "special_orders")]
+    pub(super) stop_trigger: StopTriggerState,
 
-    /// Minimum quantity increment for orders. When set, order quantities must be
-    /// exact multiples of this value. `None` disables validation (default).
-    pub(super) lot_size: Option<u64>,
+    /// Automatic invariant audit cadence (see [`Self::set_audit_interval`])
+    #[cfg(feature = "audit")]
+    pub(super) audit_schedule: AuditSchedule,
 
-    /// Minimum order size. When set, orders with `total_quantity() < min` are
-    /// rejected. `None` disables validation (default).
-    pub(super) min_order_size: Option<u64>,
+    /// Validation, fee and risk parameters — tick / lot size, order size
+    /// and notional limits, the fee schedule and the risk config. Swapped
+    /// as a whole by [`Self::reload_config`], so every admission reads one
+    /// consistent copy. The risk config is mirrored into
+    /// [`Self::risk_state`] under [`Self::config_writer`].
+    pub(super) config: AtomicCell<BookConfig>,
 
-    /// Maximum order size. When set, orders with `total_quantity() > max` are
-    /// rejected. `None` disables validation (default).
-    pub(super) max_order_size: Option<u64>,
+    /// Number of configuration changes applied since construction (or
+    /// the value restored from a snapshot package); stamped on trades,
+    /// price-level events and snapshot packages.
+    pub(super) config_version: AtomicU64,
+
+    /// Serializes configuration writers so a read-modify-write through
+    /// [`Self::update_config`] never loses a concurrent change, and the
+    /// risk mirror always matches the published config.
+    pub(super) config_writer: std::sync::Mutex<()>,
 
     /// Self-Trade Prevention mode. When set to a mode other than `None`,
     /// the matching engine checks `user_id` on incoming and resting orders
     /// to prevent self-trades. Default is `STPMode::None` (disabled).
-    pub(super) stp_mode: STPMode,
+    /// `STPMode` is a `u8`, so the cell is lock-free.
+    pub(super) stp_mode: AtomicCell<STPMode>,
 
-    /// Fee schedule for calculating trading fees. When None, no fees are applied.
-    /// Fees are calculated during trade execution and can be configured per orderbook.
-    pub(super) fee_schedule: Option<FeeSchedule>,
+    /// Decimal scale of raw prices and quantities, used to render scaled
+    /// metrics, snapshots and trade events. Defaults to the identity scale.
+    pub(super) price_scale: PriceScale,
 
     /// Optional order state tracker for explicit lifecycle tracking.
     /// When `Some`, every order transition (Open, PartiallyFilled, Filled,
     /// Cancelled, Rejected) is recorded. When `None`, zero overhead.
     pub(super) order_state_tracker: Option<super::order_state::OrderStateTracker>,
 
+    /// Optional bounded history of emitted fills that
+    /// [`bust_trade`](Self::bust_trade) and
+    /// [`correct_trade`](Self::correct_trade) look executions up in.
+    /// When `None`, zero overhead and no fill can be amended.
+    pub(super) execution_history: Option<ExecutionHistory>,
+
+    /// Optional volume-tiered fee schedule. When `Some`, it prices every
+    /// fill in place of the flat `fee_schedule` and is credited with the
+    /// fill's notional. Shared through an `Arc` so several books can
+    /// accumulate one venue-wide volume per user.
+    pub(super) tiered_fee_schedule: Option<Arc<TieredFeeSchedule>>,
+
     /// Pluggable source of millisecond timestamps stamped on inbound
     /// orders, snapshots, and lifecycle transitions. Defaults to
     /// [`MonotonicClock`] (wall-clock); tests and sequencer replay can
@@ -199,7 +397,7 @@ where
         use std::collections::BTreeMap;
         use std::sync::atomic::Ordering;
 
-        let mut state = serializer.serialize_struct("OrderBook", 9)?;
+        let mut state = serializer.serialize_struct("OrderBook", 11)?;
 
         // Serialize symbol
         state.serialize_field("symbol", &self.symbol)?;
@@ -246,7 +444,8 @@ where
         // is a recomputable optimization, not book state.
 
         // Serialize fee schedule
-        state.serialize_field("fee_schedule", &self.fee_schedule)?;
+        state.serialize_field("fee_schedule", &self.config.load().fee_schedule)?;
+        state.serialize_field("price_scale", &self.price_scale)?;
 
         // Skip trade_listener (cannot be serialized) and transaction_id_generator, _phantom
 
@@ -259,10 +458,28 @@ where
     T: Default + Clone + Send + Sync + 'static,
 {
     /// Convert OrderType<()> to `OrderType<T>` for return values
+    ///
+    /// The order's `extra_fields` are read from the side table populated at
+    /// submission; orders without an entry get `T::default()`.
     pub fn convert_from_unit_type(&self, order: &OrderType<()>) -> OrderType<T>
     where
         T: Default,
     {
+        let extra_fields = self.lookup_extra_fields(&order.id());
+        self.convert_with_extra_fields(order, extra_fields)
+    }
+
+    /// Convert OrderType<()> to `OrderType<T>` carrying `extra_fields`, or
+    /// `T::default()` when `None`.
+    pub(super) fn convert_with_extra_fields(
+        &self,
+        order: &OrderType<()>,
+        extra_fields: Option<T>,
+    ) -> OrderType<T>
+    where
+        T: Default,
+    {
+        let extra_fields = extra_fields.unwrap_or_default();
         match order {
             OrderType::Standard {
                 id,
@@ -281,7 +498,7 @@ where
                 user_id: *user_id,
                 timestamp: *timestamp,
                 time_in_force: *time_in_force,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::IcebergOrder {
                 id,
@@ -302,7 +519,7 @@ where
                 user_id: *user_id,
                 timestamp: *timestamp,
                 time_in_force: *time_in_force,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::PostOnly {
                 id,
@@ -321,7 +538,7 @@ where
                 user_id: *user_id,
                 timestamp: *timestamp,
                 time_in_force: *time_in_force,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::TrailingStop {
                 id,
@@ -344,7 +561,7 @@ where
                 time_in_force: *time_in_force,
                 trail_amount: *trail_amount,
                 last_reference_price: *last_reference_price,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::PeggedOrder {
                 id,
@@ -367,7 +584,7 @@ where
                 time_in_force: *time_in_force,
                 reference_price_offset: *reference_price_offset,
                 reference_price_type: *reference_price_type,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::MarketToLimit {
                 id,
@@ -386,7 +603,7 @@ where
                 user_id: *user_id,
                 timestamp: *timestamp,
                 time_in_force: *time_in_force,
-                extra_fields: T::default(),
+                extra_fields,
             },
             OrderType::ReserveOrder {
                 id,
@@ -413,7 +630,7 @@ where
                 replenish_threshold: *replenish_threshold,
                 replenish_amount: *replenish_amount,
                 auto_replenish: *auto_replenish,
-                extra_fields: T::default(),
+                extra_fields,
             },
         }
     }
@@ -438,33 +655,63 @@ where
 
         Self {
             symbol: symbol.to_string(),
-            bids: SkipMap::new(),
-            asks: SkipMap::new(),
+            bids: PriceLevelMap::new(),
+            asks: PriceLevelMap::new(),
             order_locations: DashMap::new(),
             user_orders: DashMap::new(),
+            order_owners: DashMap::new(),
+            extra_fields: DashMap::new(),
             transaction_id_generator: UuidGenerator::new(namespace),
             next_order_id: AtomicU64::new(1),
-            engine_seq: AtomicU64::new(0),
+            engine_seq: CachePadded::new(AtomicU64::new(0)),
+            execution_seq: AtomicU64::new(1),
             kill_switch: AtomicBool::new(false),
+            trade_level_aggregation: AtomicBool::new(false),
             risk_state: RiskState::new(),
-            last_trade_price: AtomicCell::new(0),
+            external_prices: ExternalPrices::default(),
+            session: SessionState::default(),
+            circuit_breaker: CircuitBreakerState::default(),
+            rate_limiter: RateLimiter::new(),
+            blocked_users: DashSet::new(),
+            account_groups: AccountGroups::default(),
+            priority_classes: PriorityClasses::default(),
+            midpoint: Mutex::default(),
+            speed_bump: Mutex::default(),
+            order_id_dedup: OrderIdDedup::new(),
+            last_trade_price: CachePadded::new(AtomicCell::new(0)),
             has_traded: AtomicBool::new(false),
+            last_execution_id: AtomicU64::new(0),
             submit_gate: std::sync::RwLock::new(()),
+            level_gate: LevelGate::new(),
             market_close_timestamp: AtomicU64::new(0),
             has_market_close: AtomicBool::new(false),
             cache: PriceLevelCache::new(),
-            trade_listener: None,
+            depth_totals: DepthTotals::new(),
+            level_pool: LevelPool::default(),
+            depth_view: DepthView::default(),
+            expiry_wheel: ExpiryWheel::new(),
+            counters: BookCounters::default(),
+            order_lifetimes: OrderLifetimes::default(),
+            order_tags: OrderTags::default(),
+            heartbeats: Heartbeats::default(),
+            trade_listener: ListenerSlot::default(),
             _phantom: PhantomData,
-            price_level_changed_listener: None,
+            price_level_changed_listener: ListenerSlot::default(),
+            replenishment_listener: ListenerSlot::default(),
             #[cfg(feature = "special_orders")]
             special_order_tracker: SpecialOrderTracker::new(),
-            tick_size: None,
-            lot_size: None,
-            min_order_size: None,
-            max_order_size: None,
-            stp_mode: STPMode::None,
-            fee_schedule: None,
+            #[cfg(feature = "special_orders")]
+            stop_trigger: StopTriggerState::default(),
+            #[cfg(feature = "audit")]
+            audit_schedule: AuditSchedule::default(),
+            config: AtomicCell::new(BookConfig::default()),
+            config_version: AtomicU64::new(0),
+            config_writer: std::sync::Mutex::new(()),
+            stp_mode: AtomicCell::new(STPMode::None),
+            price_scale: PriceScale::default(),
             order_state_tracker: None,
+            execution_history: None,
+            tiered_fee_schedule: None,
             clock,
         }
     }
@@ -562,6 +809,22 @@ where
         self.engine_seq.load(Ordering::Acquire)
     }
 
+    /// Mint the execution id of the next emitted fill.
+    #[inline]
+    pub(super) fn next_execution_id(&self) -> u64 {
+        self.execution_seq.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// The execution id the next fill will receive, without advancing.
+    ///
+    /// Captured by snapshot packages so execution ids keep increasing
+    /// across a restore.
+    #[inline]
+    #[must_use]
+    pub fn execution_seq(&self) -> u64 {
+        self.execution_seq.load(Ordering::Acquire)
+    }
+
     /// Refresh the operational depth gauges with the current count
     /// of distinct bid / ask price levels.
     ///
@@ -664,6 +927,150 @@ where
         Ok(())
     }
 
+    /// Unblock `user_id` so its new flow is admitted again. Returns
+    /// `true` if the user was blocked. Orders cancelled by
+    /// [`Self::block_user`] are not restored.
+    pub fn unblock_user(&self, user_id: Hash32) -> bool {
+        self.blocked_users.remove(&user_id).is_some()
+    }
+
+    /// `true` if `user_id` is currently blocked.
+    #[inline]
+    #[must_use]
+    pub fn is_user_blocked(&self, user_id: Hash32) -> bool {
+        !self.blocked_users.is_empty() && self.blocked_users.contains(&user_id)
+    }
+
+    /// Every blocked user, sorted by id bytes.
+    #[must_use]
+    pub fn blocked_users(&self) -> Vec<Hash32> {
+        let mut users: Vec<Hash32> = self.blocked_users.iter().map(|user| *user).collect();
+        users.sort_unstable_by_key(|user| user.0);
+        users
+    }
+
+    /// Reject the current operation if `user_id` is blocked, recording an
+    /// `OrderStatus::Rejected` transition for `order_id` when an order
+    /// state tracker is configured.
+    #[inline]
+    pub(super) fn check_user_blocked_or_reject(
+        &self,
+        order_id: Id,
+        user_id: Hash32,
+    ) -> Result<(), OrderBookError> {
+        if self.is_user_blocked(user_id) {
+            let err = OrderBookError::UserBlocked { user_id };
+            self.reject_with_risk(order_id, &err);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Reject the current operation if the rate limiter refuses a
+    /// submission by `user_id`, recording an `OrderStatus::Rejected`
+    /// transition for `order_id` when an order state tracker is
+    /// configured. Passthrough when no [`RateLimitConfig`] is installed.
+    #[inline]
+    pub(super) fn check_rate_limit_or_reject(
+        &self,
+        order_id: Id,
+        user_id: Hash32,
+    ) -> Result<(), OrderBookError> {
+        if self.rate_limiter.config().is_none() {
+            return Ok(());
+        }
+        let now_ms = self.clock().now_millis().as_u64();
+        if let Err(err) = self.rate_limiter.check_admission(user_id, now_ms) {
+            self.reject_with_risk(order_id, &err);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Install or replace the rate-limit configuration on this book.
+    ///
+    /// Every `add_order` / `submit_*` call then consumes one token from
+    /// the submitting user's bucket and one from the book-wide bucket,
+    /// and is rejected with [`OrderBookError::RateLimited`] when either is
+    /// empty. Buckets are refilled from the book's clock and reset to full
+    /// whenever the configuration changes.
+    ///
+    /// Gates run in the order
+    /// `kill_switch → user_block → rate_limit → risk → STP → fees → match`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError, RateLimitConfig, TokenBucketConfig};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_rate_limit_config(
+    ///     RateLimitConfig::new().with_global(TokenBucketConfig::new(1, 1)),
+    /// );
+    ///
+    /// assert!(book.add_limit_order(Id::new(), 100, 1, Side::Buy, TimeInForce::Gtc, None).is_ok());
+    /// let second = book.add_limit_order(Id::new(), 100, 1, Side::Buy, TimeInForce::Gtc, None);
+    /// assert!(matches!(second, Err(OrderBookError::RateLimited { .. })));
+    /// ```
+    pub fn set_rate_limit_config(&mut self, config: RateLimitConfig) {
+        self.rate_limiter.set_config(config);
+    }
+
+    /// Read-only access to the active rate-limit configuration, if any.
+    #[inline]
+    #[must_use]
+    pub fn rate_limit_config(&self) -> Option<&RateLimitConfig> {
+        self.rate_limiter.config()
+    }
+
+    /// Drop the active rate-limit configuration and every bucket.
+    pub fn disable_rate_limit(&mut self) {
+        self.rate_limiter.disable();
+    }
+
+    /// Reject order ids reused within `window_ms` milliseconds of book
+    /// clock.
+    ///
+    /// Every `add_order` whose id is not already resting is recorded; a
+    /// later `add_order` with the same id inside the window is rejected
+    /// with [`OrderBookError::DuplicateOrderId`] even if the original has
+    /// filled, been cancelled, or been rejected by a later validation
+    /// step. Replacing the window forgets every recorded id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_duplicate_order_id_window(60_000);
+    ///
+    /// let id = Id::new();
+    /// book.add_limit_order(id, 100, 1, Side::Buy, TimeInForce::Gtc, None).unwrap();
+    /// book.cancel_order(id).unwrap();
+    ///
+    /// let resubmitted = book.add_limit_order(id, 100, 1, Side::Buy, TimeInForce::Gtc, None);
+    /// assert!(matches!(resubmitted, Err(OrderBookError::DuplicateOrderId { .. })));
+    /// ```
+    pub fn set_duplicate_order_id_window(&mut self, window_ms: u64) {
+        self.order_id_dedup.set_window(window_ms);
+    }
+
+    /// The active duplicate order id window in milliseconds, if any.
+    #[inline]
+    #[must_use]
+    pub fn duplicate_order_id_window(&self) -> Option<u64> {
+        self.order_id_dedup.window_ms()
+    }
+
+    /// Stop rejecting recently used order ids. Ids of resting orders are
+    /// still rejected.
+    pub fn disable_duplicate_order_id_window(&mut self) {
+        self.order_id_dedup.disable();
+    }
+
     /// Install or replace the active risk configuration on this book.
     ///
     /// Counters and per-order risk state are preserved so that history
@@ -674,22 +1081,61 @@ where
     /// Risk gates run in the documented order
     /// `kill_switch → risk → STP → fees → match`, before any matching,
     /// fee, or STP work happens.
-    pub fn set_risk_config(&mut self, config: RiskConfig) {
-        self.risk_state.set_config(config);
+    pub fn set_risk_config(&self, config: RiskConfig) {
+        self.apply_config(|current| current.risk = Some(config));
     }
 
-    /// Read-only access to the active risk configuration, if any.
+    /// A copy of the active risk configuration, if any.
     #[inline]
     #[must_use]
-    pub fn risk_config(&self) -> Option<&RiskConfig> {
-        self.risk_state.config()
+    pub fn risk_config(&self) -> Option<RiskConfig> {
+        self.config().risk
     }
 
     /// Drop the active risk configuration. Counters and per-order risk
     /// state are retained so a subsequent [`Self::set_risk_config`]
     /// re-engages the gates without dropping history.
-    pub fn disable_risk(&mut self) {
-        self.risk_state.disable();
+    pub fn disable_risk(&self) {
+        self.apply_config(|config| config.risk = None);
+    }
+
+    /// Supply the reference price used by a price band configured with
+    /// [`ReferencePriceSource::External`], in raw price units. Shorthand
+    /// for [`Self::set_reference_price`] of the
+    /// [`ExternalPriceSource::Index`] price observed now on the book
+    /// clock. A zero price clears it, which skips the band until the next
+    /// update; so does a stale price. The price is kept across
+    /// [`Self::set_risk_config`] but is not persisted across
+    /// snapshot/restore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError, ReferencePriceSource, RiskConfig};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_risk_config(
+    ///     RiskConfig::new().with_price_band_ticks(5, ReferencePriceSource::External),
+    /// );
+    /// book.set_external_reference_price(1_000);
+    ///
+    /// assert!(book.add_limit_order(Id::new(), 1_005, 1, Side::Buy, TimeInForce::Gtc, None).is_ok());
+    /// let fat_finger = book.add_limit_order(Id::new(), 1_050, 1, Side::Buy, TimeInForce::Gtc, None);
+    /// assert!(matches!(fat_finger, Err(OrderBookError::RiskPriceBandTicks { .. })));
+    /// ```
+    pub fn set_external_reference_price(&self, price: u128) {
+        let now_ms = self.clock().now_millis().as_u64();
+        self.set_reference_price(ExternalPriceSource::Index, price, now_ms);
+    }
+
+    /// The index price the price band reads: the latest one supplied via
+    /// [`Self::set_external_reference_price`] or
+    /// [`Self::set_reference_price`], unless stale.
+    #[inline]
+    #[must_use]
+    pub fn external_reference_price(&self) -> Option<u128> {
+        self.fresh_reference_price(ExternalPriceSource::Index)
     }
 
     /// Resolve the reference price for the price-band check.
@@ -698,65 +1144,168 @@ where
     /// `None` when no trade has executed yet on this book. `Mid`
     /// returns the integer midpoint of the best bid and ask when both
     /// are present; otherwise it falls back to `LastTrade`.
-    /// `FixedPrice` always returns the operator-pinned value.
+    /// `FixedPrice` always returns the operator-pinned value. `External`
+    /// returns the fresh index price, if any (see
+    /// [`Self::external_reference_price`]).
+    ///
+    /// Staleness of the index price is judged against `config`, the
+    /// snapshot the rest of the admission reads.
     #[inline]
     #[must_use]
-    pub(super) fn resolve_reference_price(&self, source: ReferencePriceSource) -> Option<u128> {
+    pub(super) fn resolve_reference_price(
+        &self,
+        config: &BookConfig,
+        source: ReferencePriceSource,
+    ) -> Option<u128> {
         match source {
             ReferencePriceSource::LastTrade => self.last_trade_price(),
-            ReferencePriceSource::Mid => match (self.best_bid(), self.best_ask()) {
-                // `midpoint` computes (bid + ask) / 2 without the intermediate
-                // `bid + ask` overflowing u128 at extreme prices.
-                (Some(bid), Some(ask)) => Some(bid.midpoint(ask)),
-                _ => self.last_trade_price(),
-            },
+            ReferencePriceSource::Mid => self.bbo().midpoint().or_else(|| self.last_trade_price()),
             ReferencePriceSource::FixedPrice(p) => Some(p),
+            ReferencePriceSource::External => self.reference_price_within(
+                ExternalPriceSource::Index,
+                config.reference_price_max_age_ms,
+            ),
         }
     }
 
     /// Apply the pre-trade risk gates to a limit-order admission.
     ///
-    /// Returns `Ok(())` immediately when no risk config is installed.
+    /// Returns `Ok(())` immediately when `config` carries no risk config.
     /// Otherwise resolves the reference price (when the price band is
     /// configured) and delegates to
-    /// [`RiskState::check_limit_admission`]. Allocation-free on the
-    /// happy path; cold rejection allocates one error variant.
+    /// [`RiskState::check_limit_admission`], then the tick-denominated
+    /// band against `config`'s tick size. `config` is the snapshot the
+    /// caller validates the rest of the order against, so a concurrent
+    /// reload never pairs old limits with a new tick size. Allocation-free
+    /// on the happy path; cold rejection allocates one error variant.
     #[inline]
     pub(super) fn check_risk_limit_admission(
         &self,
+        config: &BookConfig,
         account: pricelevel::Hash32,
         price: u128,
         quantity: u64,
     ) -> Result<(), OrderBookError> {
-        let Some(cfg) = self.risk_state.config() else {
+        let Some(cfg) = config.risk.as_ref() else {
             return Ok(());
         };
         let reference = cfg
             .reference_price
-            .and_then(|src| self.resolve_reference_price(src));
+            .and_then(|src| self.resolve_reference_price(config, src));
+        self.risk_state
+            .check_limit_admission(Some(cfg), account, price, quantity, reference)?;
         self.risk_state
-            .check_limit_admission(account, price, quantity, reference)
+            .check_tick_band(Some(cfg), price, reference, config.tick_size.unwrap_or(1))
     }
 
     /// Acquire the shared (read) side of the submit gate (#209). Poisoning
     /// can only occur if a panic unwound while a guard was held; the
     /// protected data is `()` so recovery is always safe — log and
     /// continue rather than propagating the poison.
-    pub(super) fn submit_gate_read(&self) -> std::sync::RwLockReadGuard<'_, ()> {
-        self.submit_gate.read().unwrap_or_else(|poisoned| {
+    ///
+    /// Due trading session transitions are applied, and a circuit breaker
+    /// whose cool-down has ended is resumed, before the gate is taken (see
+    /// [`Self::advance_session`]).
+    ///
+    /// With class priority enabled, or a reserve fill limit configured,
+    /// the exclusive side is taken instead: keeping a level in class order
+    /// and rotating throttled reserve orders both re-queue orders, which
+    /// no concurrent operation may observe (see
+    /// [`Self::set_class_priority`] and
+    /// [`BookConfig::reserve_fill_limit`](crate::BookConfig::reserve_fill_limit)).
+    pub(super) fn submit_gate_read(&self) -> SubmitGateGuard<'_> {
+        if self.priority_classes.is_enabled() || self.config.load().reserve_fill_limit.is_some() {
+            return self.submit_gate_write();
+        }
+        #[cfg(feature = "audit")]
+        self.run_scheduled_audit();
+        self.run_session_schedule();
+        self.run_circuit_breaker_schedule();
+        self.run_heartbeat_schedule();
+        let lock = self.submit_gate.read().unwrap_or_else(|poisoned| {
             tracing::error!("submit gate poisoned by a prior panic; recovering read guard");
             poisoned.into_inner()
-        })
+        });
+        self.guard_submit_gate(SubmitGateLock::Read(lock))
     }
 
     /// Acquire the exclusive (write) side of the submit gate for a
     /// fill-or-kill submit (#209). See [`Self::submit_gate_read`] for the
     /// poisoning policy.
-    pub(super) fn submit_gate_write(&self) -> std::sync::RwLockWriteGuard<'_, ()> {
-        self.submit_gate.write().unwrap_or_else(|poisoned| {
+    pub(super) fn submit_gate_write(&self) -> SubmitGateGuard<'_> {
+        #[cfg(feature = "audit")]
+        self.run_scheduled_audit();
+        self.run_session_schedule();
+        self.run_circuit_breaker_schedule();
+        self.run_heartbeat_schedule();
+        let lock = self.submit_gate.write().unwrap_or_else(|poisoned| {
             tracing::error!("submit gate poisoned by a prior panic; recovering write guard");
             poisoned.into_inner()
-        })
+        });
+        self.guard_submit_gate(SubmitGateLock::Write(lock))
+    }
+
+    /// Wrap a held submit gate. Under `special_orders`, a book with
+    /// automatic pegged re-pricing enabled is attached so that releasing
+    /// the guard runs the re-price pass before the gate is let go.
+    fn guard_submit_gate<'a>(&'a self, lock: SubmitGateLock<'a>) -> SubmitGateGuard<'a> {
+        SubmitGateGuard {
+            lock,
+            #[cfg(feature = "special_orders")]
+            reprice_on_release: self
+                .special_order_tracker
+                .auto_reprice()
+                .then_some(self as &dyn RepriceOnRelease),
+        }
+    }
+
+    /// Acquire the shared side of the level gate at `price` for the span
+    /// between looking the level up (or creating it) and adding an order to
+    /// it. The guard must be dropped before calling
+    /// [`Self::remove_level_if_empty`].
+    pub(super) fn level_gate_read(&self, price: u128) -> std::sync::RwLockReadGuard<'_, ()> {
+        self.level_gate.read(price)
+    }
+
+    /// Unlink the level at `price` from `side` and park it in the level
+    /// pool, but only if it is still empty once every in-flight admission
+    /// at that price has finished. Returns whether a level was removed.
+    ///
+    /// A level observed empty by a cancel or a match may have gained an
+    /// order from a concurrent add in the meantime; removing it then would
+    /// orphan that order. Only the gate stripe of `price` is locked, so
+    /// admissions at other prices carry on.
+    pub(super) fn remove_level_if_empty(&self, side: Side, price: u128) -> bool {
+        let _gate = self.level_gate.write(price);
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let Some(entry) = levels.get(&price) else {
+            return false;
+        };
+        if entry.value().order_count() != 0 {
+            return false;
+        }
+        entry.remove();
+        self.level_pool.release(Arc::clone(entry.value()));
+        true
+    }
+
+    /// Fold a just-mutated level into the depth totals. If `level` has been
+    /// unlinked and a new level created at its price in the meantime, the
+    /// linked one is reconciled instead, so a late reconcile of the old
+    /// level cannot overwrite the accounting of its replacement.
+    pub(super) fn reconcile_depth(&self, side: Side, level: &PriceLevel) {
+        let _gate = self.level_gate_read(level.price());
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        match levels.get(&level.price()) {
+            Some(linked) => self.depth_totals.reconcile(side, linked.value()),
+            None => self.depth_totals.reconcile(side, level),
+        }
     }
 
     /// Acquire the submit gate in the mode the submit needs: exclusive
@@ -776,9 +1325,9 @@ where
     /// [`TradeListener`] and [`PriceLevelChangedListener`].
     pub(super) fn acquire_submit_gate(&self, exclusive: bool) -> SubmitGateGuard<'_> {
         if exclusive {
-            SubmitGateGuard::Write(self.submit_gate_write())
+            self.submit_gate_write()
         } else {
-            SubmitGateGuard::Read(self.submit_gate_read())
+            self.submit_gate_read()
         }
     }
 
@@ -786,7 +1335,7 @@ where
     /// resting order (`UpdatePrice` / `UpdatePriceAndQuantity` /
     /// `Replace`).
     ///
-    /// Returns `Ok(())` immediately when no risk config is installed.
+    /// Returns `Ok(())` immediately when `config` carries no risk config.
     /// Otherwise resolves the reference price (the same way as
     /// [`Self::check_risk_limit_admission`]) and delegates to
     /// [`RiskState::check_modify_admission`], which checks the price band
@@ -799,19 +1348,32 @@ where
     #[allow(clippy::too_many_arguments)]
     pub(super) fn check_risk_modify_admission(
         &self,
+        config: &BookConfig,
         order_id: pricelevel::Id,
         account: pricelevel::Hash32,
         new_price: u128,
         new_qty: u64,
     ) -> Result<(), OrderBookError> {
-        let Some(cfg) = self.risk_state.config() else {
+        let Some(cfg) = config.risk.as_ref() else {
             return Ok(());
         };
         let reference = cfg
             .reference_price
-            .and_then(|src| self.resolve_reference_price(src));
-        self.risk_state
-            .check_modify_admission(order_id, account, new_price, new_qty, reference)
+            .and_then(|src| self.resolve_reference_price(config, src));
+        self.risk_state.check_modify_admission(
+            Some(cfg),
+            order_id,
+            account,
+            new_price,
+            new_qty,
+            reference,
+        )?;
+        self.risk_state.check_tick_band(
+            Some(cfg),
+            new_price,
+            reference,
+            config.tick_size.unwrap_or(1),
+        )
     }
 
     /// Create a new order book for the given symbol with tick size validation.
@@ -827,11 +1389,125 @@ where
     /// # Returns
     /// A new `OrderBook` instance with tick size validation enabled
     pub fn with_tick_size(symbol: &str, tick_size: u128) -> Self {
+        let book = Self::new(symbol);
+        book.store_config(BookConfig::new().with_tick_size(tick_size));
+        book
+    }
+
+    /// Create a new order book for the given symbol with its per-order
+    /// indexes pre-sized for `capacity` resting orders.
+    ///
+    /// The indexes keyed by order id (locations and owners) are allocated
+    /// up front, so a book that fills to `capacity` orders never rehashes
+    /// them on the add path. The per-user order index is keyed by user, so
+    /// it is sized separately with
+    /// [`OrderBookBuilder::user_capacity`](crate::OrderBookBuilder::user_capacity).
+    /// Resting orders themselves are owned by their `PriceLevel` and
+    /// allocated by `pricelevel`; their storage is not affected.
+    ///
+    /// # Arguments
+    /// - `symbol`: The trading symbol for this order book
+    /// - `capacity`: Expected peak number of resting orders
+    pub fn with_order_capacity(symbol: &str, capacity: usize) -> Self {
+        let mut book = Self::new(symbol);
+        book.order_locations = DashMap::with_capacity(capacity);
+        book.order_owners = DashMap::with_capacity(capacity);
+        book
+    }
+
+    /// Create a new order book for the given symbol with its per-order
+    /// indexes pre-sized for `capacity` resting orders and split into
+    /// `shard_count` lock shards.
+    ///
+    /// The order-id location index is hit by every add, cancel, modify
+    /// and fill, so at millions of orders under many writer threads its
+    /// shard locks become a contention point. Each index is a sharded map
+    /// keyed by the order-id (or user-id) hash; the default shard count
+    /// is four times the available parallelism. Raising it trades memory
+    /// for fewer collisions between concurrent writers — see the
+    /// `order_index_shards` group of the concurrent benchmarks.
+    ///
+    /// `shard_count` is rounded up to a power of two, with a minimum of 2
+    /// and a maximum of 65 536.
+    ///
+    /// # Arguments
+    /// - `symbol`: The trading symbol for this order book
+    /// - `capacity`: Expected peak number of resting orders
+    /// - `shard_count`: Number of lock shards per index
+    pub fn with_order_index(symbol: &str, capacity: usize, shard_count: usize) -> Self {
+        let shard_count = index_shard_amount(shard_count);
+        let mut book = Self::new(symbol);
+        book.order_locations = DashMap::with_capacity_and_shard_amount(capacity, shard_count);
+        book.user_orders = DashMap::with_shard_amount(shard_count);
+        book.order_owners = DashMap::with_capacity_and_shard_amount(capacity, shard_count);
+        book
+    }
+
+    /// Create a new order book for the given symbol with each side's price
+    /// levels split into `shard_count` price-range shards of `shard_width`
+    /// price units.
+    ///
+    /// Every shard is an independent skip list, so very deep books (far
+    /// option strikes, long-tail levels) get shorter towers per list and
+    /// writers at distant prices stop contending on the same nodes. Shard
+    /// `i` holds prices `[i * shard_width, (i + 1) * shard_width)`; the last
+    /// shard also holds every higher price. Pick a width that keeps the
+    /// active prices spread over several shards — see the `level_shards`
+    /// group of the concurrent benchmarks. Ordering, matching and every
+    /// query behave exactly as with the single structure.
+    ///
+    /// Both values are raised to at least 1; one shard is the default
+    /// layout.
+    ///
+    /// # Arguments
+    /// - `symbol`: The trading symbol for this order book
+    /// - `shard_width`: Price range covered by each shard, in price units
+    /// - `shard_count`: Number of shards per side
+    pub fn with_level_shards(symbol: &str, shard_width: u128, shard_count: usize) -> Self {
         let mut book = Self::new(symbol);
-        book.tick_size = Some(tick_size);
+        book.bids = PriceLevelMap::sharded(shard_width, shard_count);
+        book.asks = PriceLevelMap::sharded(shard_width, shard_count);
         book
     }
 
+    /// Number of price levels in each shard of `side`, lowest prices first.
+    /// A single entry when the book is not sharded.
+    #[must_use]
+    pub fn level_shard_occupancy(&self, side: Side) -> Vec<usize> {
+        match side {
+            Side::Buy => self.bids.shard_lens(),
+            Side::Sell => self.asks.shard_lens(),
+        }
+    }
+
+    /// Number of resting orders the order-id location index can hold
+    /// without reallocating. Compare with the resting order count to size
+    /// [`Self::with_order_capacity`].
+    #[must_use]
+    pub fn order_index_capacity(&self) -> usize {
+        self.order_locations.capacity()
+    }
+
+    /// Reclaim the memory of price levels removed from the book at a point
+    /// of the caller's choosing.
+    ///
+    /// The bid and ask skip maps already use epoch-based reclamation:
+    /// removing an empty level unlinks its node immediately, but the node
+    /// and its `PriceLevel` are only destroyed once no concurrent reader
+    /// can still hold them. That teardown runs on whichever thread next
+    /// advances the global epoch — usually a thread in the middle of an
+    /// unrelated add, cancel or match — which shows up as tail latency
+    /// after bursts of level deletions.
+    ///
+    /// Calling this from a quiescent point (between batches, or from a
+    /// housekeeping thread) flushes the calling thread's deferred
+    /// destructions and advances the epoch, so the teardown happens here
+    /// instead. It never blocks readers and is always safe to call; levels
+    /// still referenced elsewhere are kept alive by their `Arc`.
+    pub fn reclaim_removed_levels(&self) {
+        crossbeam::epoch::pin().flush();
+    }
+
     /// Create a new order book for the given symbol with lot size validation.
     ///
     /// Orders added to this book must have quantities that are exact multiples
@@ -845,8 +1521,8 @@ where
     /// # Returns
     /// A new `OrderBook` instance with lot size validation enabled
     pub fn with_lot_size(symbol: &str, lot_size: u64) -> Self {
-        let mut book = Self::new(symbol);
-        book.lot_size = Some(lot_size);
+        let book = Self::new(symbol);
+        book.store_config(BookConfig::new().with_lot_size(lot_size));
         book
     }
 
@@ -856,33 +1532,63 @@ where
 
         Self {
             symbol: symbol.to_string(),
-            bids: SkipMap::new(),
-            asks: SkipMap::new(),
+            bids: PriceLevelMap::new(),
+            asks: PriceLevelMap::new(),
             order_locations: DashMap::new(),
             user_orders: DashMap::new(),
+            order_owners: DashMap::new(),
+            extra_fields: DashMap::new(),
             transaction_id_generator: UuidGenerator::new(namespace),
             next_order_id: AtomicU64::new(1),
-            engine_seq: AtomicU64::new(0),
+            engine_seq: CachePadded::new(AtomicU64::new(0)),
+            execution_seq: AtomicU64::new(1),
             kill_switch: AtomicBool::new(false),
+            trade_level_aggregation: AtomicBool::new(false),
             risk_state: RiskState::new(),
-            last_trade_price: AtomicCell::new(0),
+            external_prices: ExternalPrices::default(),
+            session: SessionState::default(),
+            circuit_breaker: CircuitBreakerState::default(),
+            rate_limiter: RateLimiter::new(),
+            blocked_users: DashSet::new(),
+            account_groups: AccountGroups::default(),
+            priority_classes: PriorityClasses::default(),
+            midpoint: Mutex::default(),
+            speed_bump: Mutex::default(),
+            order_id_dedup: OrderIdDedup::new(),
+            last_trade_price: CachePadded::new(AtomicCell::new(0)),
             has_traded: AtomicBool::new(false),
+            last_execution_id: AtomicU64::new(0),
             submit_gate: std::sync::RwLock::new(()),
+            level_gate: LevelGate::new(),
             market_close_timestamp: AtomicU64::new(0),
             has_market_close: AtomicBool::new(false),
             cache: PriceLevelCache::new(),
-            trade_listener: Some(trade_listener),
+            depth_totals: DepthTotals::new(),
+            level_pool: LevelPool::default(),
+            depth_view: DepthView::default(),
+            expiry_wheel: ExpiryWheel::new(),
+            counters: BookCounters::default(),
+            order_lifetimes: OrderLifetimes::default(),
+            order_tags: OrderTags::default(),
+            heartbeats: Heartbeats::default(),
+            trade_listener: ListenerSlot::new(Some(trade_listener)),
             _phantom: PhantomData,
-            price_level_changed_listener: None,
+            price_level_changed_listener: ListenerSlot::default(),
+            replenishment_listener: ListenerSlot::default(),
             #[cfg(feature = "special_orders")]
             special_order_tracker: SpecialOrderTracker::new(),
-            tick_size: None,
-            lot_size: None,
-            min_order_size: None,
-            max_order_size: None,
-            stp_mode: STPMode::None,
-            fee_schedule: None,
+            #[cfg(feature = "special_orders")]
+            stop_trigger: StopTriggerState::default(),
+            #[cfg(feature = "audit")]
+            audit_schedule: AuditSchedule::default(),
+            config: AtomicCell::new(BookConfig::default()),
+            config_version: AtomicU64::new(0),
+            config_writer: std::sync::Mutex::new(()),
+            stp_mode: AtomicCell::new(STPMode::None),
+            price_scale: PriceScale::default(),
             order_state_tracker: None,
+            execution_history: None,
+            tiered_fee_schedule: None,
             clock: Arc::new(MonotonicClock) as Arc<dyn Clock>,
         }
     }
@@ -905,55 +1611,224 @@ where
 
         Self {
             symbol: symbol.to_string(),
-            bids: SkipMap::new(),
-            asks: SkipMap::new(),
+            bids: PriceLevelMap::new(),
+            asks: PriceLevelMap::new(),
             order_locations: DashMap::new(),
             user_orders: DashMap::new(),
+            order_owners: DashMap::new(),
+            extra_fields: DashMap::new(),
             transaction_id_generator: UuidGenerator::new(namespace),
             next_order_id: AtomicU64::new(1),
-            engine_seq: AtomicU64::new(0),
+            engine_seq: CachePadded::new(AtomicU64::new(0)),
+            execution_seq: AtomicU64::new(1),
             kill_switch: AtomicBool::new(false),
+            trade_level_aggregation: AtomicBool::new(false),
             risk_state: RiskState::new(),
-            last_trade_price: AtomicCell::new(0),
+            external_prices: ExternalPrices::default(),
+            session: SessionState::default(),
+            circuit_breaker: CircuitBreakerState::default(),
+            rate_limiter: RateLimiter::new(),
+            blocked_users: DashSet::new(),
+            account_groups: AccountGroups::default(),
+            priority_classes: PriorityClasses::default(),
+            midpoint: Mutex::default(),
+            speed_bump: Mutex::default(),
+            order_id_dedup: OrderIdDedup::new(),
+            last_trade_price: CachePadded::new(AtomicCell::new(0)),
             has_traded: AtomicBool::new(false),
+            last_execution_id: AtomicU64::new(0),
             submit_gate: std::sync::RwLock::new(()),
+            level_gate: LevelGate::new(),
             market_close_timestamp: AtomicU64::new(0),
             has_market_close: AtomicBool::new(false),
             cache: PriceLevelCache::new(),
-            trade_listener: Some(trade_listener),
+            depth_totals: DepthTotals::new(),
+            level_pool: LevelPool::default(),
+            depth_view: DepthView::default(),
+            expiry_wheel: ExpiryWheel::new(),
+            counters: BookCounters::default(),
+            order_lifetimes: OrderLifetimes::default(),
+            order_tags: OrderTags::default(),
+            heartbeats: Heartbeats::default(),
+            trade_listener: ListenerSlot::new(Some(trade_listener)),
             _phantom: PhantomData,
-            price_level_changed_listener: Some(book_changed_listener),
+            price_level_changed_listener: ListenerSlot::new(Some(book_changed_listener)),
+            replenishment_listener: ListenerSlot::default(),
             #[cfg(feature = "special_orders")]
             special_order_tracker: SpecialOrderTracker::new(),
-            tick_size: None,
-            lot_size: None,
-            min_order_size: None,
-            max_order_size: None,
-            stp_mode: STPMode::None,
-            fee_schedule: None,
+            #[cfg(feature = "special_orders")]
+            stop_trigger: StopTriggerState::default(),
+            #[cfg(feature = "audit")]
+            audit_schedule: AuditSchedule::default(),
+            config: AtomicCell::new(BookConfig::default()),
+            config_version: AtomicU64::new(0),
+            config_writer: std::sync::Mutex::new(()),
+            stp_mode: AtomicCell::new(STPMode::None),
+            price_scale: PriceScale::default(),
             order_state_tracker: None,
+            execution_history: None,
+            tiered_fee_schedule: None,
             clock: Arc::new(MonotonicClock) as Arc<dyn Clock>,
         }
     }
 
-    /// Set a trade listener for this order book
-    pub fn set_trade_listener(&mut self, trade_listener: TradeListener) {
-        self.trade_listener = Some(trade_listener);
+    /// Set a trade listener for this order book, replacing any previous one.
+    ///
+    /// Takes `&self`, so a listener can be installed or swapped on a book
+    /// already shared behind an `Arc`. Trades emitted after the call
+    /// returns go to the new listener.
+    pub fn set_trade_listener(&self, trade_listener: TradeListener) {
+        self.trade_listener.replace(Some(trade_listener));
     }
 
     /// Remove the trade listener from this order book
-    pub fn remove_trade_listener(&mut self) {
-        self.trade_listener = None;
+    pub fn remove_trade_listener(&self) {
+        self.trade_listener.replace(None);
     }
 
-    /// set price level listener for this order book
-    pub fn set_price_level_listener(&mut self, listener: PriceLevelChangedListener) {
-        self.price_level_changed_listener = Some(listener);
+    /// The installed trade listener, if any.
+    #[must_use]
+    pub fn trade_listener(&self) -> Option<TradeListener> {
+        self.trade_listener.get()
+    }
+
+    /// Set the price level listener for this order book, replacing any
+    /// previous one. Like [`Self::set_trade_listener`] it works on a
+    /// shared book.
+    pub fn set_price_level_listener(&self, listener: PriceLevelChangedListener) {
+        self.price_level_changed_listener.replace(Some(listener));
     }
 
     /// remove price level listener for this order book
-    pub fn remove_price_level_listener(&mut self) {
-        self.price_level_changed_listener = None;
+    pub fn remove_price_level_listener(&self) {
+        self.price_level_changed_listener.replace(None);
+    }
+
+    /// The installed price level listener, if any.
+    #[must_use]
+    pub fn price_level_listener(&self) -> Option<PriceLevelChangedListener> {
+        self.price_level_changed_listener.get()
+    }
+
+    /// A consistent copy of the book's validation, fee and risk parameters.
+    #[must_use]
+    pub fn config(&self) -> BookConfig {
+        self.config.load()
+    }
+
+    /// Number of configuration changes applied to this book.
+    ///
+    /// Starts at `0` for a freshly constructed or built book and is bumped
+    /// by every [`Self::reload_config`] / [`Self::update_config`], every
+    /// individual parameter setter and [`Self::set_stp_mode`]. Restored from the snapshot package on
+    /// [`Self::restore_from_snapshot_package`]. Stamped on trades, price
+    /// level events and snapshot packages as `config_version`.
+    #[inline]
+    #[must_use]
+    pub fn config_version(&self) -> u64 {
+        self.config_version.load(Ordering::Acquire)
+    }
+
+    /// Atomically replace every validation, fee and risk parameter.
+    ///
+    /// Takes `&self`, so it works on a book shared behind an `Arc` while
+    /// other threads keep submitting: each admission validates against
+    /// either the previous or the new configuration in full, never a mix.
+    /// Resting orders are not re-validated against the new limits, and
+    /// risk counters are kept as by [`Self::set_risk_config`].
+    ///
+    /// Returns the new [`Self::config_version`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when `config`
+    /// fails [`BookConfig::validate`]; the active configuration and its
+    /// version are then left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{BookConfig, OrderBook, OrderBookError};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// book.reload_config(BookConfig::new().with_tick_size(5)).unwrap();
+    ///
+    /// let off_tick = book.add_limit_order(Id::new(), 102, 1, Side::Buy, TimeInForce::Gtc, None);
+    /// assert!(matches!(off_tick, Err(OrderBookError::InvalidTickSize { .. })));
+    ///
+    /// let zero_tick = book.reload_config(BookConfig::new().with_tick_size(0));
+    /// assert!(zero_tick.is_err());
+    /// assert_eq!(book.tick_size(), Some(5));
+    /// ```
+    pub fn reload_config(&self, config: BookConfig) -> Result<u64, OrderBookError> {
+        self.update_config(|current| *current = config)
+    }
+
+    /// Atomically edit the active configuration in place.
+    ///
+    /// `edit` receives a copy of the active [`BookConfig`]; the result is
+    /// validated and published as one change. Concurrent edits are
+    /// serialized, so none is lost. Returns the new
+    /// [`Self::config_version`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when the edited
+    /// configuration fails [`BookConfig::validate`]; nothing is published.
+    pub fn update_config<F>(&self, edit: F) -> Result<u64, OrderBookError>
+    where
+        F: FnOnce(&mut BookConfig),
+    {
+        let _writer = self.config_writer_lock();
+        let mut config = self.config.load();
+        edit(&mut config);
+        config.validate()?;
+        Ok(self.publish_config(config))
+    }
+
+    /// Unvalidated edit behind the individual parameter setters, which
+    /// have never rejected a value.
+    fn apply_config<F>(&self, edit: F)
+    where
+        F: FnOnce(&mut BookConfig),
+    {
+        let _writer = self.config_writer_lock();
+        let mut config = self.config.load();
+        edit(&mut config);
+        self.publish_config(config);
+    }
+
+    /// Store `config` and bump the version. Caller holds the writer lock.
+    fn publish_config(&self, config: BookConfig) -> u64 {
+        self.store_config(config);
+        self.config_version.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Install `config` and its risk mirror without bumping the version;
+    /// used while the book is still being constructed or restored.
+    pub(super) fn store_config(&self, config: BookConfig) {
+        if config.risk != self.risk_state.config() {
+            match config.risk {
+                Some(risk) => self.risk_state.set_config(risk),
+                None => self.risk_state.disable(),
+            }
+        }
+        if config.trading_session != self.config.load().trading_session {
+            self.session.configure(config.trading_session);
+        }
+        if config.circuit_breaker != self.config.load().circuit_breaker {
+            self.circuit_breaker.configure(config.circuit_breaker);
+        }
+        self.config.store(config);
+    }
+
+    /// Acquire the configuration writer lock. The protected data is `()`,
+    /// so a poisoned lock is recovered like the submit gate.
+    fn config_writer_lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.config_writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     /// Set the fee schedule for this order book
@@ -980,8 +1855,8 @@ where
     /// // Disable fees
     /// book.set_fee_schedule(None);
     /// ```
-    pub fn set_fee_schedule(&mut self, fee_schedule: Option<FeeSchedule>) {
-        self.fee_schedule = fee_schedule;
+    pub fn set_fee_schedule(&self, fee_schedule: Option<FeeSchedule>) {
+        self.apply_config(|config| config.fee_schedule = fee_schedule);
     }
 
     /// Get the current fee schedule for this order book
@@ -991,7 +1866,73 @@ where
     /// The current fee schedule, or None if no fees are configured
     #[must_use]
     pub fn fee_schedule(&self) -> Option<FeeSchedule> {
-        self.fee_schedule
+        self.config.load().fee_schedule
+    }
+
+    /// Install a volume-tiered fee schedule, or remove it with `None`.
+    ///
+    /// While installed it replaces [`Self::fee_schedule`]: each fill is
+    /// charged at the maker's and taker's tiers as they stood before the
+    /// trade, then its notional is credited to both users' rolling volume
+    /// at the fill's match time. Orders without a user id are charged the
+    /// base schedule and accumulate no volume. Busts and corrections
+    /// re-price the corrected fill but do not adjust tracked volume.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{FeeSchedule, OrderBook, TieredFeeSchedule};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    /// use std::sync::Arc;
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// let fees = Arc::new(
+    ///     TieredFeeSchedule::new(FeeSchedule::new(0, 10)).with_tier(1_000, FeeSchedule::new(0, 5)),
+    /// );
+    /// book.set_tiered_fee_schedule(Some(Arc::clone(&fees)));
+    ///
+    /// let (maker, taker) = (Hash32::new([1; 32]), Hash32::new([2; 32]));
+    /// book.add_limit_order_with_user(Id::new(), 100, 20, Side::Sell, TimeInForce::Gtc, maker, None)?;
+    /// let (_, trade) = book.add_limit_order_with_user_and_result(
+    ///     Id::new(), 100, 10, Side::Buy, TimeInForce::Ioc, taker, None,
+    /// )?;
+    /// // Charged at the base tier; the 1_000 of notional reaches the next.
+    /// assert_eq!(trade.expect("crossed").total_taker_fees, 1);
+    /// assert_eq!(fees.schedule_for(taker, "BTC/USD", book.clock().now_millis().as_u64()), FeeSchedule::new(0, 5));
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn set_tiered_fee_schedule(&mut self, schedule: Option<Arc<TieredFeeSchedule>>) {
+        self.tiered_fee_schedule = schedule;
+    }
+
+    /// The installed volume-tiered fee schedule, if any.
+    #[must_use]
+    pub fn tiered_fee_schedule(&self) -> Option<&Arc<TieredFeeSchedule>> {
+        self.tiered_fee_schedule.as_ref()
+    }
+
+    /// Set the decimal scale of this book's raw prices and quantities.
+    ///
+    /// The scale does not affect matching; it is carried on snapshots and
+    /// trade results and used by the `scaled_*` metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, PriceScale};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_price_scale(PriceScale::new(2, 8));
+    /// assert_eq!(book.price_scale().price_decimals, 2);
+    /// ```
+    pub fn set_price_scale(&mut self, price_scale: PriceScale) {
+        self.price_scale = price_scale;
+    }
+
+    /// Get the decimal scale of this book's raw prices and quantities.
+    #[must_use]
+    pub fn price_scale(&self) -> PriceScale {
+        self.price_scale
     }
 
     /// Set the minimum price increment for orders.
@@ -1002,8 +1943,8 @@ where
     ///
     /// # Arguments
     /// - `tick_size`: Minimum price increment. Must be > 0
-    pub fn set_tick_size(&mut self, tick_size: u128) {
-        self.tick_size = Some(tick_size);
+    pub fn set_tick_size(&self, tick_size: u128) {
+        self.apply_config(|config| config.tick_size = Some(tick_size));
     }
 
     /// Set or clear the minimum price increment from an [`Option`].
@@ -1021,8 +1962,8 @@ where
     /// - `tick_size`: `Some(increment)` to enable (increment must be > 0), or
     ///   `None` to disable validation.
     #[inline]
-    pub fn set_tick_size_opt(&mut self, tick_size: Option<u128>) {
-        self.tick_size = tick_size;
+    pub fn set_tick_size_opt(&self, tick_size: Option<u128>) {
+        self.apply_config(|config| config.tick_size = tick_size);
     }
 
     /// Returns the configured tick size, if any.
@@ -1030,7 +1971,7 @@ where
     /// `None` means tick size validation is disabled (all prices accepted).
     #[must_use]
     pub fn tick_size(&self) -> Option<u128> {
-        self.tick_size
+        self.config.load().tick_size
     }
 
     /// Set the minimum quantity increment for orders.
@@ -1041,8 +1982,8 @@ where
     ///
     /// # Arguments
     /// - `lot_size`: Minimum quantity increment. Must be > 0
-    pub fn set_lot_size(&mut self, lot_size: u64) {
-        self.lot_size = Some(lot_size);
+    pub fn set_lot_size(&self, lot_size: u64) {
+        self.apply_config(|config| config.lot_size = Some(lot_size));
     }
 
     /// Set or clear the minimum quantity increment from an [`Option`].
@@ -1061,8 +2002,8 @@ where
     /// - `lot_size`: `Some(increment)` to enable (increment must be > 0), or
     ///   `None` to disable validation.
     #[inline]
-    pub fn set_lot_size_opt(&mut self, lot_size: Option<u64>) {
-        self.lot_size = lot_size;
+    pub fn set_lot_size_opt(&self, lot_size: Option<u64>) {
+        self.apply_config(|config| config.lot_size = lot_size);
     }
 
     /// Returns the configured lot size, if any.
@@ -1071,7 +2012,7 @@ where
     #[must_use]
     #[inline]
     pub fn lot_size(&self) -> Option<u64> {
-        self.lot_size
+        self.config.load().lot_size
     }
 
     /// Set the minimum order size.
@@ -1081,8 +2022,8 @@ where
     ///
     /// # Arguments
     /// - `size`: Minimum allowed order quantity
-    pub fn set_min_order_size(&mut self, size: u64) {
-        self.min_order_size = Some(size);
+    pub fn set_min_order_size(&self, size: u64) {
+        self.apply_config(|config| config.min_order_size = Some(size));
     }
 
     /// Set the maximum order size.
@@ -1092,8 +2033,8 @@ where
     ///
     /// # Arguments
     /// - `size`: Maximum allowed order quantity
-    pub fn set_max_order_size(&mut self, size: u64) {
-        self.max_order_size = Some(size);
+    pub fn set_max_order_size(&self, size: u64) {
+        self.apply_config(|config| config.max_order_size = Some(size));
     }
 
     /// Returns the configured minimum order size, if any.
@@ -1102,7 +2043,7 @@ where
     #[must_use]
     #[inline]
     pub fn min_order_size(&self) -> Option<u64> {
-        self.min_order_size
+        self.config.load().min_order_size
     }
 
     /// Returns the configured maximum order size, if any.
@@ -1111,7 +2052,50 @@ where
     #[must_use]
     #[inline]
     pub fn max_order_size(&self) -> Option<u64> {
-        self.max_order_size
+        self.config.load().max_order_size
+    }
+
+    /// Set the minimum order notional.
+    ///
+    /// Orders with `price × total_quantity() < min_order_notional` are
+    /// rejected with `OrderBookError::OrderNotionalOutOfRange`.
+    ///
+    /// # Arguments
+    /// - `notional`: Minimum allowed order notional, in raw price × raw
+    ///   quantity units
+    pub fn set_min_order_notional(&self, notional: u128) {
+        self.apply_config(|config| config.min_order_notional = Some(notional));
+    }
+
+    /// Set the maximum order notional.
+    ///
+    /// Orders with `price × total_quantity() > max_order_notional` are
+    /// rejected with `OrderBookError::OrderNotionalOutOfRange`. A product
+    /// that overflows `u128` always exceeds the maximum.
+    ///
+    /// # Arguments
+    /// - `notional`: Maximum allowed order notional, in raw price × raw
+    ///   quantity units
+    pub fn set_max_order_notional(&self, notional: u128) {
+        self.apply_config(|config| config.max_order_notional = Some(notional));
+    }
+
+    /// Returns the configured minimum order notional, if any.
+    ///
+    /// `None` means no minimum notional validation (default).
+    #[must_use]
+    #[inline]
+    pub fn min_order_notional(&self) -> Option<u128> {
+        self.config.load().min_order_notional
+    }
+
+    /// Returns the configured maximum order notional, if any.
+    ///
+    /// `None` means no maximum notional validation (default).
+    #[must_use]
+    #[inline]
+    pub fn max_order_notional(&self) -> Option<u128> {
+        self.config.load().max_order_notional
     }
 
     /// Set the Self-Trade Prevention mode.
@@ -1120,10 +2104,16 @@ where
     /// checks `user_id` on incoming and resting orders to prevent self-trades.
     /// Orders with `Hash32::zero()` always bypass STP checks.
     ///
+    /// Takes `&self`, so the mode can be changed on a shared book; each
+    /// submit matches under the mode it read on entry. Bumps
+    /// [`Self::config_version`].
+    ///
     /// # Arguments
     /// - `mode`: The STP mode to activate
-    pub fn set_stp_mode(&mut self, mode: STPMode) {
-        self.stp_mode = mode;
+    pub fn set_stp_mode(&self, mode: STPMode) {
+        let _writer = self.config_writer_lock();
+        self.stp_mode.store(mode);
+        self.config_version.fetch_add(1, Ordering::AcqRel);
     }
 
     /// Returns the configured Self-Trade Prevention mode.
@@ -1132,7 +2122,26 @@ where
     #[must_use]
     #[inline]
     pub fn stp_mode(&self) -> STPMode {
-        self.stp_mode
+        self.stp_mode.load()
+    }
+
+    /// Attach [`TradeResult::level_fills`] to every emitted trade result.
+    ///
+    /// A taker sweeping many small resting orders produces one fill per
+    /// maker; the per-level view condenses them into one entry per price
+    /// with the total quantity and the number of counterparties, so
+    /// consumers that only need the levels skip the detailed fills. The
+    /// detailed fills are always kept. Off by default.
+    pub fn set_trade_level_aggregation(&self, enabled: bool) {
+        self.trade_level_aggregation
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// `true` if emitted trade results carry [`TradeResult::level_fills`].
+    #[must_use]
+    #[inline]
+    pub fn trade_level_aggregation(&self) -> bool {
+        self.trade_level_aggregation.load(Ordering::Relaxed)
     }
 
     /// Set an order state tracker for explicit lifecycle tracking.
@@ -1222,8 +2231,8 @@ where
     /// # Returns
     /// A new `OrderBook` instance with STP enabled
     pub fn with_stp_mode(symbol: &str, stp_mode: STPMode) -> Self {
-        let mut book = Self::new(symbol);
-        book.stp_mode = stp_mode;
+        let book = Self::new(symbol);
+        book.stp_mode.store(stp_mode);
         book
     }
 
@@ -1263,11 +2272,15 @@ where
             return Some(cached_bid);
         }
 
-        // SkipMap maintains sorted order, best bid (highest price) is last
+        // SkipMap maintains sorted order, best bid (highest price) is last.
+        // Stamp the read with the update sequence so a level removed while
+        // we probed is never cached after its remover invalidated.
+        let update_seq = self.cache.update_seq();
         let best_price = self.bids.iter().next_back().map(|entry| *entry.key());
 
         // Update only the bid slot — never evict the ask side.
-        self.cache.update_best_bid(best_price);
+        self.cache
+            .publish_best_price(Side::Buy, best_price, update_seq);
 
         best_price
     }
@@ -1282,23 +2295,103 @@ where
         }
 
         // SkipMap maintains sorted order, best ask (lowest price) is first
+        let update_seq = self.cache.update_seq();
         let best_price = self.asks.iter().next().map(|entry| *entry.key());
 
         // Update only the ask slot — never evict the bid side.
-        self.cache.update_best_ask(best_price);
+        self.cache
+            .publish_best_price(Side::Sell, best_price, update_seq);
 
         best_price
     }
 
+    /// Best bid, best ask and the total quantity at each, as one
+    /// consistent read.
+    ///
+    /// Served from a seqlock-published cache slot when no mutation has
+    /// happened since it was filled; otherwise both sides are recomputed
+    /// in a single pass and republished. A recomputation that overlaps a
+    /// mutation, whether it finished during the read or is still in
+    /// flight, is retried, so the four values never pair a bid from before
+    /// a mutation with an ask from after it. Only a read made from inside
+    /// a mutation of this book (a listener, a peg reprice), or one that
+    /// keeps overlapping writes for dozens of attempts, returns the live,
+    /// unvalidated state. [`Self::mid_price`], [`Self::spread`] and
+    /// [`Self::micro_price`] are derived from this value.
+    pub fn top_of_book(&self) -> TopOfBook {
+        if let Some(top) = self.cache.get_cached_top_of_book() {
+            return top;
+        }
+
+        let (top, stable) = self
+            .cache
+            .read_stable(|| self.compute_top_of_book(), |top| top.update_seq);
+        if stable {
+            self.cache.update_top_of_book(&top);
+        }
+        top
+    }
+
+    /// Best bid and offer, with the quantity at each and the update
+    /// sequence they were read at, as one consistent unit.
+    ///
+    /// Equivalent to [`Self::top_of_book`]. Calling [`Self::best_bid`] then
+    /// [`Self::best_ask`] can pair prices from different book states under
+    /// concurrent mutation; every metric that needs both sides (mid,
+    /// spread, micro price, the `Mid` references of pegs, stops and risk,
+    /// implied volatility inputs) reads them through this instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    /// let before = book.bbo();
+    /// book.add_limit_order(Id::new_uuid(), 104, 5, Side::Sell, TimeInForce::Gtc, None)?;
+    ///
+    /// let bbo = book.bbo();
+    /// assert_eq!((bbo.best_bid, bbo.best_ask), (Some(100), Some(104)));
+    /// assert_eq!(bbo.midpoint(), Some(102));
+    /// assert!(bbo.update_seq > before.update_seq);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn bbo(&self) -> TopOfBook {
+        self.top_of_book()
+    }
+
+    /// Read both best levels from the skip maps, stamped with the cache
+    /// update sequence observed before the read.
+    fn compute_top_of_book(&self) -> TopOfBook {
+        let update_seq = self.cache.update_seq();
+        let (best_bid, bid_quantity) = self.bids.iter().next_back().map_or((None, 0), |entry| {
+            (
+                Some(*entry.key()),
+                entry.value().total_quantity().unwrap_or(0),
+            )
+        });
+        let (best_ask, ask_quantity) = self.asks.iter().next().map_or((None, 0), |entry| {
+            (
+                Some(*entry.key()),
+                entry.value().total_quantity().unwrap_or(0),
+            )
+        });
+        TopOfBook {
+            best_bid,
+            best_ask,
+            bid_quantity,
+            ask_quantity,
+            update_seq,
+        }
+    }
+
     /// Get the mid price (average of best bid and best ask)
     pub fn mid_price(&self) -> Option<f64> {
-        match (
-            OrderBook::<T>::best_bid(self),
-            OrderBook::<T>::best_ask(self),
-        ) {
-            (Some(bid), Some(ask)) => Some((bid as f64 + ask as f64) / 2.0),
-            _ => None,
-        }
+        self.bbo().mid_price()
     }
 
     /// Get the last trade price, if any
@@ -1312,13 +2405,7 @@ where
 
     /// Get the spread (best ask - best bid)
     pub fn spread(&self) -> Option<u128> {
-        match (
-            OrderBook::<T>::best_bid(self),
-            OrderBook::<T>::best_ask(self),
-        ) {
-            (Some(bid), Some(ask)) => Some(ask.saturating_sub(bid)),
-            _ => None,
-        }
+        self.bbo().spread()
     }
 
     /// Finds the price where cumulative depth reaches the target quantity
@@ -1348,6 +2435,17 @@ where
     /// ```
     #[must_use]
     pub fn price_at_depth(&self, target_depth: u64, side: Side) -> Option<u128> {
+        self.price_at_depth_with_mode(target_depth, side, DepthMode::Total)
+    }
+
+    /// [`Self::price_at_depth`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn price_at_depth_with_mode(
+        &self,
+        target_depth: u64,
+        side: Side,
+        mode: DepthMode,
+    ) -> Option<u128> {
         let price_levels = match side {
             Side::Buy => &self.bids,
             Side::Sell => &self.asks,
@@ -1368,7 +2466,7 @@ where
         for entry in iter {
             let price = *entry.key();
             let price_level = entry.value();
-            cumulative = cumulative.saturating_add(price_level.total_quantity().unwrap_or(0));
+            cumulative = cumulative.saturating_add(mode.level_quantity(price_level));
 
             if cumulative >= target_depth {
                 return Some(price);
@@ -1404,6 +2502,17 @@ where
     /// ```
     #[must_use]
     pub fn cumulative_depth_to_target(&self, target_depth: u64, side: Side) -> Option<(u128, u64)> {
+        self.cumulative_depth_to_target_with_mode(target_depth, side, DepthMode::Total)
+    }
+
+    /// [`Self::cumulative_depth_to_target`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn cumulative_depth_to_target_with_mode(
+        &self,
+        target_depth: u64,
+        side: Side,
+        mode: DepthMode,
+    ) -> Option<(u128, u64)> {
         let price_levels = match side {
             Side::Buy => &self.bids,
             Side::Sell => &self.asks,
@@ -1424,7 +2533,7 @@ where
         for entry in iter {
             let price = *entry.key();
             let price_level = entry.value();
-            cumulative = cumulative.saturating_add(price_level.total_quantity().unwrap_or(0));
+            cumulative = cumulative.saturating_add(mode.level_quantity(price_level));
 
             if cumulative >= target_depth {
                 return Some((price, cumulative));
@@ -1445,7 +2554,8 @@ where
     /// Returns 0 if the side is empty or if levels is 0.
     ///
     /// # Performance
-    /// O(min(levels, N) * log N) where N is the total number of price levels.
+    /// O(min(levels, N) * log N) where N is the total number of price levels,
+    /// or O(1) when `levels` covers the whole side.
     ///
     /// # Examples
     /// ```
@@ -1459,6 +2569,18 @@ where
     /// ```
     #[must_use]
     pub fn total_depth_at_levels(&self, levels: usize, side: Side) -> u64 {
+        self.total_depth_at_levels_with_mode(levels, side, DepthMode::Total)
+    }
+
+    /// [`Self::total_depth_at_levels`] counting the depth selected by `mode`.
+    /// Only [`DepthMode::Total`] answers a whole-side query in O(1).
+    #[must_use]
+    pub fn total_depth_at_levels_with_mode(
+        &self,
+        levels: usize,
+        side: Side,
+        mode: DepthMode,
+    ) -> u64 {
         if levels == 0 {
             return 0;
         }
@@ -1471,6 +2593,9 @@ where
         if price_levels.is_empty() {
             return 0;
         }
+        if mode == DepthMode::Total && levels >= price_levels.len() {
+            return self.side_total_quantity(side);
+        }
 
         let mut total = 0u64;
 
@@ -1486,7 +2611,7 @@ where
             }
 
             let price_level = entry.value();
-            total = total.saturating_add(price_level.total_quantity().unwrap_or(0));
+            total = total.saturating_add(mode.level_quantity(price_level));
         }
 
         total
@@ -1555,7 +2680,8 @@ where
     pub fn spread_bps(&self, bps_multiplier: Option<f64>) -> Option<f64> {
         let multiplier = bps_multiplier.unwrap_or(DEFAULT_BASIS_POINTS_MULTIPLIER);
 
-        match (self.best_bid(), self.best_ask(), self.mid_price()) {
+        let top = self.bbo();
+        match (top.best_bid, top.best_ask, top.mid_price()) {
             (Some(bid), Some(ask), Some(mid)) if mid > 0.0 => {
                 let spread = ask.saturating_sub(bid) as f64;
                 Some((spread / mid) * multiplier)
@@ -1596,6 +2722,12 @@ where
     /// ```
     #[must_use]
     pub fn vwap(&self, quantity: u64, side: Side) -> Option<f64> {
+        self.vwap_with_mode(quantity, side, DepthMode::Total)
+    }
+
+    /// [`Self::vwap`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn vwap_with_mode(&self, quantity: u64, side: Side, mode: DepthMode) -> Option<f64> {
         if quantity == 0 {
             return None;
         }
@@ -1628,7 +2760,7 @@ where
 
             let price = *entry.key();
             let price_level = entry.value();
-            let available = price_level.total_quantity().unwrap_or(0);
+            let available = mode.level_quantity(price_level);
 
             if available == 0 {
                 continue;
@@ -1674,35 +2806,58 @@ where
     /// ```
     #[must_use]
     pub fn micro_price(&self) -> Option<f64> {
-        let best_bid_price = self.best_bid()?;
-        let best_ask_price = self.best_ask()?;
+        // micro_price = (ask_price * bid_volume + bid_price * ask_volume) / (bid_volume + ask_volume)
+        self.bbo().micro_price()
+    }
 
-        // Get volumes at best levels
-        let bid_volume = self
-            .bids
-            .get(&best_bid_price)?
-            .value()
-            .total_quantity()
-            .unwrap_or(0);
-        let ask_volume = self
-            .asks
-            .get(&best_ask_price)?
-            .value()
-            .total_quantity()
-            .unwrap_or(0);
+    /// Best bid in display units, per [`Self::price_scale`].
+    #[must_use]
+    pub fn scaled_best_bid(&self) -> Option<f64> {
+        self.best_bid()
+            .map(|price| self.price_scale.price_to_f64(price))
+    }
 
-        let total_volume = bid_volume.saturating_add(ask_volume);
+    /// Best ask in display units, per [`Self::price_scale`].
+    #[must_use]
+    pub fn scaled_best_ask(&self) -> Option<f64> {
+        self.best_ask()
+            .map(|price| self.price_scale.price_to_f64(price))
+    }
 
-        if total_volume == 0 {
-            return None;
-        }
+    /// [`Self::mid_price`] in display units, per [`Self::price_scale`].
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::{OrderBook, PriceScale};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_price_scale(PriceScale::new(2, 0));
+    /// let _ = book.add_limit_order(Id::new(), 10_000, 5, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 10_050, 5, Side::Sell, TimeInForce::Gtc, None);
+    ///
+    /// assert_eq!(book.scaled_mid_price(), Some(100.25));
+    /// ```
+    #[must_use]
+    pub fn scaled_mid_price(&self) -> Option<f64> {
+        self.mid_price()
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
 
-        // micro_price = (ask_price * bid_volume + bid_price * ask_volume) / (bid_volume + ask_volume)
-        let numerator = (best_ask_price as f64 * bid_volume as f64)
-            + (best_bid_price as f64 * ask_volume as f64);
-        let denominator = total_volume as f64;
+    /// [`Self::micro_price`] in display units, per [`Self::price_scale`].
+    #[must_use]
+    pub fn scaled_micro_price(&self) -> Option<f64> {
+        self.micro_price()
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
 
-        Some(numerator / denominator)
+    /// [`Self::vwap`] in display units, per [`Self::price_scale`].
+    ///
+    /// `quantity` is in raw units, like every other quantity on the book.
+    #[must_use]
+    pub fn scaled_vwap(&self, quantity: u64, side: Side) -> Option<f64> {
+        self.vwap(quantity, side)
+            .map(|price| self.price_scale.scale_price_f64(price))
     }
 
     /// Calculates the order book imbalance ratio for the top N levels
@@ -1721,7 +2876,8 @@ where
     ///   - Returns `0.0` if both sides are empty or `levels` is 0
     ///
     /// # Performance
-    /// O(M log N) where M is the number of levels requested.
+    /// O(M log N) where M is the number of levels requested, or O(1) when
+    /// `levels` covers both sides.
     ///
     /// # Examples
     /// ```
@@ -1739,12 +2895,18 @@ where
     /// ```
     #[must_use]
     pub fn order_book_imbalance(&self, levels: usize) -> f64 {
+        self.order_book_imbalance_with_mode(levels, DepthMode::Total)
+    }
+
+    /// [`Self::order_book_imbalance`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn order_book_imbalance_with_mode(&self, levels: usize, mode: DepthMode) -> f64 {
         if levels == 0 {
             return 0.0;
         }
 
-        let bid_volume = self.total_depth_at_levels(levels, Side::Buy);
-        let ask_volume = self.total_depth_at_levels(levels, Side::Sell);
+        let bid_volume = self.total_depth_at_levels_with_mode(levels, Side::Buy, mode);
+        let ask_volume = self.total_depth_at_levels_with_mode(levels, Side::Sell, mode);
 
         let total_volume = bid_volume.saturating_add(ask_volume);
 
@@ -1758,6 +2920,44 @@ where
         (bid_f64 - ask_f64) / (bid_f64 + ask_f64)
     }
 
+    /// Imbalance of resting order counts over the top N levels of each
+    /// side, in the same -1.0 to 1.0 range as
+    /// [`Self::order_book_imbalance`].
+    ///
+    /// Read next to the quantity imbalance it separates a side held up by
+    /// one large order from a side with many small ones. Returns `0.0` if
+    /// both sides are empty or `levels` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 30, Side::Buy, TimeInForce::Gtc, None);
+    /// for _ in 0..3 {
+    ///     let _ = book.add_limit_order(Id::new(), 105, 10, Side::Sell, TimeInForce::Gtc, None);
+    /// }
+    ///
+    /// assert_eq!(book.order_book_imbalance(5), 0.0);
+    /// assert_eq!(book.order_count_imbalance(5), -0.5);
+    /// ```
+    #[must_use]
+    pub fn order_count_imbalance(&self, levels: usize) -> f64 {
+        let count = |side: Side| -> usize {
+            self.levels_with_cumulative_depth(side)
+                .take(levels)
+                .map(|level| level.order_count)
+                .fold(0, usize::saturating_add)
+        };
+        let (bid_orders, ask_orders) = (count(Side::Buy), count(Side::Sell));
+        let total = bid_orders.saturating_add(ask_orders);
+        if total == 0 {
+            return 0.0;
+        }
+        (bid_orders as f64 - ask_orders as f64) / total as f64
+    }
+
     /// Calculates the market impact of a hypothetical order
     ///
     /// Analyzes how an order would affect the market by walking through
@@ -1808,6 +3008,17 @@ where
     /// ```
     #[must_use]
     pub fn market_impact(&self, quantity: u64, side: Side) -> MarketImpact {
+        self.market_impact_with_mode(quantity, side, DepthMode::Total)
+    }
+
+    /// [`Self::market_impact`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn market_impact_with_mode(
+        &self,
+        quantity: u64,
+        side: Side,
+        mode: DepthMode,
+    ) -> MarketImpact {
         if quantity == 0 {
             return MarketImpact::empty();
         }
@@ -1855,7 +3066,7 @@ where
         for entry in iter {
             let price = *entry.key();
             let price_level = entry.value();
-            let available = price_level.total_quantity().unwrap_or(0);
+            let available = mode.level_quantity(price_level);
 
             if available == 0 {
                 continue;
@@ -1938,6 +3149,17 @@ where
     /// ```
     #[must_use]
     pub fn simulate_market_order(&self, quantity: u64, side: Side) -> OrderSimulation {
+        self.simulate_market_order_with_mode(quantity, side, DepthMode::Total)
+    }
+
+    /// [`Self::simulate_market_order`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn simulate_market_order_with_mode(
+        &self,
+        quantity: u64,
+        side: Side,
+        mode: DepthMode,
+    ) -> OrderSimulation {
         if quantity == 0 {
             return OrderSimulation::empty();
         }
@@ -1973,7 +3195,7 @@ where
 
             let price = *entry.key();
             let price_level = entry.value();
-            let available = price_level.total_quantity().unwrap_or(0);
+            let available = mode.level_quantity(price_level);
 
             if available == 0 {
                 continue;
@@ -2000,6 +3222,148 @@ where
         }
     }
 
+    /// Computes a cost-to-liquidate curve for increasing order sizes
+    ///
+    /// Evaluates `steps` evenly spaced quantities up to `max_qty`
+    /// (`max_qty * i / steps` for `i` in `1..=steps`) and reports, for each,
+    /// the average execution price a market order of that size would get and
+    /// its slippage from the touch. All points are computed in a single walk
+    /// over the levels being hit, so this is much cheaper than calling
+    /// [`Self::market_impact`] once per size.
+    ///
+    /// # Arguments
+    /// - `side`: The side of the liquidating order (Buy = execute against asks, Sell = execute against bids)
+    /// - `max_qty`: The largest quantity on the curve (in units)
+    /// - `steps`: Number of points on the curve
+    ///
+    /// # Returns
+    /// A vector of [`LiquidationPoint`]s in increasing quantity order. The
+    /// curve stops at the last size the resting depth can fully absorb, so it
+    /// holds fewer than `steps` points when liquidity runs out. Each size
+    /// appears once: when `steps` exceeds `max_qty`, sizes that round down
+    /// to zero or to the previous size are skipped, so the curve never
+    /// holds more than `max_qty` points. Empty when `max_qty` or `steps` is
+    /// zero, or when the side being hit is empty.
+    ///
+    /// # Performance
+    /// O(M log N + min(steps, max_qty)) where M is the number of levels
+    /// needed to absorb the largest fillable size.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 95, 10, Side::Buy, TimeInForce::Gtc, None);
+    ///
+    /// // Cost of selling 5, 10, 15 and 20 units into the bids
+    /// let curve = book.liquidation_curve(Side::Sell, 20, 4);
+    /// assert_eq!(curve.len(), 4);
+    /// assert_eq!(curve[1].avg_price, 100.0);
+    /// assert_eq!(curve[3].avg_price, 97.5);
+    /// ```
+    #[must_use]
+    pub fn liquidation_curve(
+        &self,
+        side: Side,
+        max_qty: u64,
+        steps: usize,
+    ) -> Vec<LiquidationPoint> {
+        self.liquidation_curve_with_mode(side, max_qty, steps, DepthMode::Total)
+    }
+
+    /// [`Self::liquidation_curve`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn liquidation_curve_with_mode(
+        &self,
+        side: Side,
+        max_qty: u64,
+        steps: usize,
+        mode: DepthMode,
+    ) -> Vec<LiquidationPoint> {
+        if max_qty == 0 || steps == 0 {
+            return Vec::new();
+        }
+
+        // For Buy orders, we execute against asks (in ascending order)
+        // For Sell orders, we execute against bids (in descending order)
+        let price_levels = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+
+        if price_levels.is_empty() {
+            return Vec::new();
+        }
+
+        // `max_qty * step / steps` in u128 so the product cannot overflow.
+        let target_at =
+            |step: u128| -> u64 { ((u128::from(max_qty) * step) / steps as u128) as u64 };
+        // First step whose size reaches `qty`: `ceil(qty * steps / max_qty)`.
+        // Jumping straight to it skips the steps that would repeat a size.
+        let step_for =
+            |qty: u64| -> u128 { (u128::from(qty) * steps as u128).div_ceil(u128::from(max_qty)) };
+
+        // Presize from the arguments only up to a small bound: the points
+        // actually emitted depend on the book's depth, not on `steps`.
+        let capacity = steps
+            .min(usize::try_from(max_qty).unwrap_or(usize::MAX))
+            .min(1024);
+        let mut points = Vec::with_capacity(capacity);
+        let mut target = target_at(step_for(1));
+        let mut best_price: Option<u128> = None;
+        let mut total_cost = 0u128;
+        let mut total_filled = 0u64;
+
+        // Iterate in price-priority order
+        let iter = match side {
+            Side::Buy => Either::Left(price_levels.iter()), // Lowest to highest (asks)
+            Side::Sell => Either::Right(price_levels.iter().rev()), // Highest to lowest (bids)
+        };
+
+        'levels: for entry in iter {
+            let price = *entry.key();
+            let available = mode.level_quantity(entry.value());
+
+            if available == 0 {
+                continue;
+            }
+
+            let best = *best_price.get_or_insert(price);
+            let level_end = total_filled.saturating_add(available);
+
+            // Emit every curve point that this level completes.
+            while target <= level_end {
+                let partial = u128::from(target - total_filled);
+                let cost = total_cost.saturating_add(price.saturating_mul(partial));
+                let avg_price = cost as f64 / target as f64;
+                let slippage_bps = if best > 0 {
+                    ((avg_price - best as f64).abs() / best as f64)
+                        * DEFAULT_BASIS_POINTS_MULTIPLIER
+                } else {
+                    0.0
+                };
+                points.push(LiquidationPoint {
+                    quantity: target,
+                    avg_price,
+                    slippage_bps,
+                });
+
+                if target >= max_qty {
+                    break 'levels;
+                }
+                target = target_at(step_for(target + 1));
+            }
+
+            total_cost = total_cost.saturating_add(price.saturating_mul(u128::from(available)));
+            total_filled = level_end;
+        }
+
+        points
+    }
+
     /// Calculates available liquidity within a specific price range
     ///
     /// Sums up the total quantity available at price levels that fall
@@ -2014,7 +3378,8 @@ where
     /// Total quantity available in the specified price range (in units)
     ///
     /// # Performance
-    /// O(M log N) where M is the number of levels in the range.
+    /// O(log N + M) where M is the number of levels in the range: the band is
+    /// located with a `SkipMap` seek rather than a scan from the book's edge.
     ///
     /// # Examples
     /// ```
@@ -2032,6 +3397,18 @@ where
     /// ```
     #[must_use]
     pub fn liquidity_in_range(&self, min_price: u128, max_price: u128, side: Side) -> u64 {
+        self.liquidity_in_range_with_mode(min_price, max_price, side, DepthMode::Total)
+    }
+
+    /// [`Self::liquidity_in_range`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn liquidity_in_range_with_mode(
+        &self,
+        min_price: u128,
+        max_price: u128,
+        side: Side,
+        mode: DepthMode,
+    ) -> u64 {
         if min_price > max_price {
             return 0;
         }
@@ -2041,29 +3418,11 @@ where
             Side::Sell => &self.asks,
         };
 
-        if price_levels.is_empty() {
-            return 0;
-        }
-
-        let mut total_liquidity = 0u64;
-
-        for entry in price_levels.iter() {
-            let price = *entry.key();
-
-            if price < min_price {
-                continue;
-            }
-
-            if price > max_price {
-                break;
-            }
-
-            let price_level = entry.value();
-            let quantity = price_level.total_quantity().unwrap_or(0);
-            total_liquidity = total_liquidity.saturating_add(quantity);
-        }
-
-        total_liquidity
+        price_levels
+            .range(min_price..=max_price)
+            .fold(0u64, |total, entry| {
+                total.saturating_add(mode.level_quantity(entry.value()))
+            })
     }
 
     /// Returns the number of orders ahead in queue at a specific price level
@@ -2418,7 +3777,7 @@ where
     /// An iterator yielding only levels within the price range
     ///
     /// # Performance
-    /// Skips levels outside range, O(M log N) where M is levels in range.
+    /// Seeks directly to the band, O(log N + M) where M is levels in range.
     ///
     /// # Examples
     /// ```
@@ -2452,6 +3811,125 @@ where
         LevelsInRange::new(price_levels, side, min_price, max_price)
     }
 
+    /// Returns an iterator over aligned (bid, ask) level pairs, best prices
+    /// first, for at most `depth` rows.
+    ///
+    /// Row `i` holds the `i`-th best bid and the `i`-th best ask, each with
+    /// its own side's cumulative depth; a side with fewer levels yields
+    /// `None` for the remaining rows. Both sides are walked once, lazily.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 99, 15, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 101, 5, Side::Sell, TimeInForce::Gtc, None);
+    ///
+    /// let rows: Vec<_> = book.ladder(5).collect();
+    /// assert_eq!(rows.len(), 2);
+    /// assert_eq!(rows[0].ask.as_ref().map(|l| l.price), Some(101));
+    /// assert!(rows[1].ask.is_none());
+    /// ```
+    #[must_use]
+    pub fn ladder(&self, depth: usize) -> Ladder<'_> {
+        Ladder::new(&self.bids, &self.asks, depth)
+    }
+
+    /// Renders [`Self::ladder`] as an aligned text table, one row per line.
+    ///
+    /// Columns are bid quantity, bid price, ask price and ask quantity,
+    /// right-aligned to the widest value in each column. Missing levels are
+    /// left blank. Intended for terminals and logs.
+    #[must_use]
+    pub fn format_ladder(&self, depth: usize) -> String {
+        let cells: Vec<[String; 4]> = self
+            .ladder(depth)
+            .map(|row| {
+                let (bid_qty, bid_price) = row
+                    .bid
+                    .map(|l| (l.quantity.to_string(), l.price.to_string()))
+                    .unwrap_or_default();
+                let (ask_price, ask_qty) = row
+                    .ask
+                    .map(|l| (l.price.to_string(), l.quantity.to_string()))
+                    .unwrap_or_default();
+                [bid_qty, bid_price, ask_price, ask_qty]
+            })
+            .collect();
+
+        let header = ["BID QTY", "BID", "ASK", "ASK QTY"];
+        let mut widths = header.map(str::len);
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let mut out = format!(
+            "{:>w0$} {:>w1$} | {:>w2$} {:>w3$}\n",
+            header[0],
+            header[1],
+            header[2],
+            header[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        for row in &cells {
+            out.push_str(&format!(
+                "{:>w0$} {:>w1$} | {:>w2$} {:>w3$}\n",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            ));
+        }
+        out
+    }
+
+    /// Returns an iterator over price levels starting at `price` and moving
+    /// away from the touch
+    ///
+    /// Bids yield levels at or below `price` (descending), asks levels at
+    /// or above it (ascending). `cumulative_depth` accumulates from the
+    /// first yielded level. The start is found with a `SkipMap` seek, so no
+    /// level better than `price` is visited.
+    ///
+    /// # Arguments
+    /// - `price`: Starting price (inclusive, in price units)
+    /// - `side`: The side to iterate (Buy for bids, Sell for asks)
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 95, 15, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 90, 20, Side::Buy, TimeInForce::Gtc, None);
+    ///
+    /// let prices: Vec<u128> = book.levels_from_price(97, Side::Buy).map(|l| l.price).collect();
+    /// assert_eq!(prices, vec![95, 90]);
+    /// ```
+    #[must_use]
+    pub fn levels_from_price(&self, price: u128, side: Side) -> LevelsWithCumulativeDepth<'_> {
+        let price_levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+
+        LevelsWithCumulativeDepth::from_price(price_levels, side, price)
+    }
+
     /// Finds the first price level matching a predicate
     ///
     /// Searches through price levels in price-priority order and returns the first
@@ -2667,47 +4145,173 @@ where
             Side::Buy => &self.bids,
             Side::Sell => &self.asks,
         };
-
-        if let Some(entry) = price_levels.get(&price) {
-            entry
-                .value()
-                .iter_orders()
-                .map(|order| Arc::new(self.convert_from_unit_type(&order)))
-                .collect()
-        } else {
-            Vec::new()
-        }
+
+        if let Some(entry) = price_levels.get(&price) {
+            entry
+                .value()
+                .iter_orders()
+                .map(|order| Arc::new(self.convert_from_unit_type(&order)))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get all orders in the book
+    ///
+    /// Collects every resting order into a `Vec`. Prefer
+    /// [`Self::iter_all_orders`] or [`Self::orders_page`] on large books.
+    pub fn get_all_orders(&self) -> Vec<Arc<OrderType<T>>>
+    where
+        T: Default,
+    {
+        trace!("Order book {}: Getting all orders", self.symbol);
+        self.iter_all_orders().collect()
+    }
+
+    /// Returns a lazy iterator over every resting order, bids first then asks.
+    ///
+    /// Each side is walked in price-time priority (see
+    /// [`Self::iter_orders_on_side`]). Only one price level is materialized
+    /// at a time, so memory stays bounded by the largest level rather than
+    /// the size of the book.
+    ///
+    /// # Consistency
+    /// The iterator reads the live book: levels are visited as they exist
+    /// when the walk reaches them, so orders added or removed concurrently
+    /// may or may not be observed. For a point-in-time view, take
+    /// [`Self::create_snapshot`].
+    pub fn iter_all_orders(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_
+    where
+        T: Default,
+    {
+        self.iter_orders_on_side(Side::Buy)
+            .chain(self.iter_orders_on_side(Side::Sell))
+    }
+
+    /// Returns a lazy iterator over the resting orders on one side.
+    ///
+    /// Levels are visited best price first (descending for bids, ascending
+    /// for asks); within a level, orders are yielded by `(timestamp,
+    /// sequence)`. Each level is materialized only when the walk reaches it.
+    ///
+    /// # Arguments
+    /// - `side`: The side to iterate (`Buy` for bids, `Sell` for asks).
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 99, 15, Side::Buy, TimeInForce::Gtc, None);
+    ///
+    /// let first = book.iter_orders_on_side(Side::Buy).next();
+    /// assert_eq!(first.map(|order| order.price().as_u128()), Some(100));
+    /// ```
+    pub fn iter_orders_on_side(&self, side: Side) -> impl Iterator<Item = Arc<OrderType<T>>> + '_
+    where
+        T: Default,
+    {
+        let levels = match side {
+            Side::Buy => Either::Left(self.bids.iter().rev()),
+            Side::Sell => Either::Right(self.asks.iter()),
+        };
+        levels
+            .flat_map(|entry| entry.value().snapshot_orders())
+            .map(move |order| Arc::new(self.convert_from_unit_type(&order)))
     }
 
-    /// Get all orders in the book
-    pub fn get_all_orders(&self) -> Vec<Arc<OrderType<T>>>
+    /// Returns one page of resting orders on one side, resuming after `cursor`.
+    ///
+    /// Pages follow the same order as [`Self::iter_orders_on_side`]. Pass
+    /// `None` for the first page and the returned `next_cursor` for each
+    /// following page; `next_cursor` is `None` once the side is exhausted.
+    /// Resuming seeks directly to the cursor's price level, so each call
+    /// costs O(log N + limit) regardless of how deep into the book it is.
+    ///
+    /// # Arguments
+    /// - `side`: The side to page through (`Buy` for bids, `Sell` for asks).
+    /// - `cursor`: Position returned by the previous page, or `None`.
+    /// - `limit`: Maximum number of orders to return.
+    ///
+    /// # Consistency
+    /// Paging reads the live book. If the cursor's order has left its level
+    /// between calls, the page resumes at the first order of that level with
+    /// a timestamp not earlier than the cursor's, so orders sharing that
+    /// millisecond may be returned twice but none are skipped. Orders that
+    /// arrive behind the cursor are not revisited.
+    pub fn orders_page(
+        &self,
+        side: Side,
+        cursor: Option<&OrderCursor>,
+        limit: usize,
+    ) -> OrdersPage<T>
     where
         T: Default,
     {
-        trace!("Order book {}: Getting all orders", self.symbol);
-        let mut result = Vec::new();
+        use std::ops::Bound;
 
-        // Get all bid orders
-        for item in self.bids.iter() {
-            let price_level = item.value();
-            let converted_orders: Vec<Arc<OrderType<T>>> = price_level
-                .iter_orders()
-                .map(|order| Arc::new(self.convert_from_unit_type(&order)))
-                .collect();
-            result.extend(converted_orders);
+        if limit == 0 {
+            return OrdersPage {
+                orders: Vec::new(),
+                next_cursor: cursor.copied(),
+            };
         }
 
-        // Get all ask orders
-        for item in self.asks.iter() {
-            let price_level = item.value();
-            let converted_orders: Vec<Arc<OrderType<T>>> = price_level
-                .iter_orders()
-                .map(|order| Arc::new(self.convert_from_unit_type(&order)))
-                .collect();
-            result.extend(converted_orders);
+        let bounds = match (side, cursor) {
+            (_, None) => (Bound::Unbounded, Bound::Unbounded),
+            (Side::Buy, Some(c)) => (Bound::Unbounded, Bound::Included(c.price)),
+            (Side::Sell, Some(c)) => (Bound::Included(c.price), Bound::Unbounded),
+        };
+        let mut levels = match side {
+            Side::Buy => Either::Left(self.bids.range(bounds).rev()),
+            Side::Sell => Either::Right(self.asks.range(bounds)),
+        };
+
+        let mut orders = Vec::with_capacity(limit);
+        let mut last: Option<OrderCursor> = None;
+        for entry in levels.by_ref() {
+            let price = *entry.key();
+            let level_orders = entry.value().snapshot_orders();
+            let start = match cursor {
+                Some(c) if c.price == price => level_orders
+                    .iter()
+                    .position(|order| order.id() == c.order_id)
+                    .map(|pos| pos + 1)
+                    .unwrap_or_else(|| {
+                        level_orders
+                            .partition_point(|order| order.timestamp().as_u64() < c.timestamp)
+                    }),
+                _ => 0,
+            };
+
+            let mut remaining = level_orders.iter().skip(start);
+            for order in remaining.by_ref() {
+                orders.push(Arc::new(self.convert_from_unit_type(order)));
+                last = Some(OrderCursor {
+                    price,
+                    timestamp: order.timestamp().as_u64(),
+                    order_id: order.id(),
+                });
+                if orders.len() == limit {
+                    break;
+                }
+            }
+            if orders.len() == limit {
+                let more = remaining.next().is_some() || levels.next().is_some();
+                return OrdersPage {
+                    orders,
+                    next_cursor: if more { last } else { None },
+                };
+            }
         }
 
-        result
+        OrdersPage {
+            orders,
+            next_cursor: None,
+        }
     }
 
     /// Get an order by its ID
@@ -2757,6 +4361,12 @@ where
     /// When STP is enabled and `user_id` is non-zero, the matching engine
     /// checks resting orders for same-user conflicts before executing fills.
     ///
+    /// With a [`MarketProtection`](super::market_protection::MarketProtection)
+    /// configured the sweep stops at the
+    /// [protection price](Self::market_protection_price), and the
+    /// remainder is cancelled or rests as a limit order under `order_id`
+    /// at that price, per [`ProtectionRemainder`].
+    ///
     /// # Arguments
     /// * `order_id` — Unique identifier for this market order.
     /// * `quantity` — Quantity to match.
@@ -2767,7 +4377,9 @@ where
     /// # Errors
     /// Returns [`OrderBookError::InsufficientLiquidity`] when no liquidity
     /// is available, or [`OrderBookError::SelfTradePrevented`] when STP
-    /// cancels the taker before any fills occur.
+    /// cancels the taker before any fills occur. A remainder that would
+    /// rest fails with [`OrderBookError::DuplicateOrderId`] before any
+    /// matching when `order_id` is already resting.
     pub fn match_market_order_with_user(
         &self,
         order_id: Id,
@@ -2779,28 +4391,41 @@ where
             "Order book {}: Matching market order {} for {} at side {:?}",
             self.symbol, order_id, quantity, side
         );
-        let match_result =
-            OrderBook::<T>::match_order_with_user(self, order_id, side, quantity, None, user_id)?;
-
-        // Emit trade-count metric and trigger trade listener if any
-        // transactions printed. The metric is independent of whether
-        // a listener is configured; the listener emission still gates
-        // on `Some(ref listener)`.
-        let trades_emitted = match_result.trades().len() as u64;
-        if trades_emitted > 0 {
-            super::metrics::record_trades(trades_emitted);
-            if let Some(ref listener) = self.trade_listener {
-                let mut trade_result = TradeResult::with_fees(
-                    self.symbol.clone(),
-                    match_result.clone(),
-                    self.fee_schedule,
-                );
-                trade_result.engine_seq = self.next_engine_seq();
-                listener(&trade_result);
-            }
+        // #209: shared submit gate, held through publication so the filled
+        // makers' `extra_fields` are still in place when the trade is built.
+        let _gate = self.submit_gate_read();
+        let listener = self.trade_listener();
+        let protection = self.resolve_market_protection(side);
+        if matches!(protection, Some((_, ProtectionRemainder::RestAsLimit)))
+            && self.order_locations.contains_key(&order_id)
+        {
+            self.record_reject(RejectReason::DuplicateOrderId);
+            return Err(OrderBookError::DuplicateOrderId { order_id });
         }
+        let outcome = self.match_order_with_user_outcome(
+            order_id,
+            side,
+            quantity,
+            protection.map(|(price, _)| price),
+            user_id,
+            TakerKind::Standard,
+            self.capture_fills(listener.is_some()),
+        )?;
+        let taker_stp_cancelled = outcome.taker_stp_cancelled;
+        let result = self.publish_trades(listener, outcome);
 
-        Ok(match_result)
+        if let Some(protection) = protection {
+            self.settle_protected_remainder(
+                order_id,
+                side,
+                quantity,
+                user_id,
+                protection,
+                &result,
+                taker_stp_cancelled,
+            )?;
+        }
+        Ok(result)
     }
 
     /// Match a market order specified by quote-notional amount.
@@ -2850,6 +4475,11 @@ where
     /// matching engine checks resting orders for same-user conflicts
     /// before executing fills.
     ///
+    /// With a [`MarketProtection`](super::market_protection::MarketProtection)
+    /// configured the sweep stops at the
+    /// [protection price](Self::market_protection_price); the unspent
+    /// amount is always cancelled, whatever the [`ProtectionRemainder`].
+    ///
     /// # Errors
     ///
     /// Returns [`OrderBookError::InsufficientLiquidityNotional`] when no
@@ -2868,24 +4498,18 @@ where
         );
         // #209: shared submit gate — notional market sweeps mutate the book.
         let _gate = self.submit_gate_read();
-        let match_result =
-            OrderBook::<T>::match_order_by_amount_with_user(self, order_id, side, amount, user_id)?;
-
-        let trades_emitted = match_result.trades().len() as u64;
-        if trades_emitted > 0 {
-            super::metrics::record_trades(trades_emitted);
-            if let Some(ref listener) = self.trade_listener {
-                let mut trade_result = TradeResult::with_fees(
-                    self.symbol.clone(),
-                    match_result.clone(),
-                    self.fee_schedule,
-                );
-                trade_result.engine_seq = self.next_engine_seq();
-                listener(&trade_result);
-            }
-        }
+        let listener = self.trade_listener();
+        let outcome = OrderBook::<T>::match_order_by_amount_with_user(
+            self,
+            order_id,
+            side,
+            amount,
+            self.market_protection_price(side),
+            user_id,
+            self.capture_fills(listener.is_some()),
+        )?;
 
-        Ok(match_result)
+        Ok(self.publish_trades(listener, outcome))
     }
 
     /// Attempts to match a limit order in the order book.
@@ -2916,6 +4540,10 @@ where
 
     /// Attempts to match a limit order with Self-Trade Prevention support.
     ///
+    /// The unfilled part is returned to the caller, never rested, so this
+    /// is the immediate-or-cancel case of
+    /// [`Self::match_limit_order_with_tif`].
+    ///
     /// # Arguments
     /// * `order_id` — Unique identifier for this limit order.
     /// * `quantity` — Quantity to match.
@@ -2934,39 +4562,101 @@ where
         side: Side,
         limit_price: u128,
         user_id: Hash32,
+    ) -> Result<MatchResult, OrderBookError> {
+        self.match_limit_order_with_tif(
+            order_id,
+            quantity,
+            side,
+            limit_price,
+            TimeInForce::Ioc,
+            user_id,
+        )
+    }
+
+    /// Match a limit order under `time_in_force`, with Self-Trade
+    /// Prevention support.
+    ///
+    /// Nothing rests: every time in force but [`TimeInForce::Fok`] matches
+    /// what is available up to `limit_price` and returns the rest to the
+    /// caller, as [`TimeInForce::Ioc`] does. A fill-or-kill order is
+    /// checked with [`Self::can_fill_fully_with_user`] under the exclusive
+    /// submit gate, so it either fills `quantity` in full or is killed
+    /// before any trade, exactly like a fill-or-kill [`Self::add_order`].
+    ///
+    /// # Errors
+    /// Returns [`OrderBookError::InsufficientLiquidity`] when a
+    /// fill-or-kill order cannot be filled in full; `available` is the
+    /// quantity it could have filled. Returns
+    /// [`OrderBookError::SelfTradePrevented`] when STP cancels the taker
+    /// before any fills occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// book.add_limit_order(Id::new(), 100, 5, Side::Sell, TimeInForce::Gtc, None).unwrap();
+    ///
+    /// let killed =
+    ///     book.match_limit_order_with_tif(Id::new(), 8, Side::Buy, 100, TimeInForce::Fok, Hash32::zero());
+    /// assert!(matches!(killed, Err(OrderBookError::InsufficientLiquidity { available: 5, .. })));
+    /// assert_eq!(book.best_ask(), Some(100));
+    ///
+    /// let partial =
+    ///     book.match_limit_order_with_tif(Id::new(), 8, Side::Buy, 100, TimeInForce::Ioc, Hash32::zero());
+    /// assert_eq!(partial.unwrap().remaining_quantity().as_u64(), 3);
+    /// ```
+    pub fn match_limit_order_with_tif(
+        &self,
+        order_id: Id,
+        quantity: u64,
+        side: Side,
+        limit_price: u128,
+        time_in_force: TimeInForce,
+        user_id: Hash32,
     ) -> Result<MatchResult, OrderBookError> {
         trace!(
-            "Order book {}: Matching limit order {} for {} at side {:?} with limit price {}",
-            self.symbol, order_id, quantity, side, limit_price
+            "Order book {}: Matching limit order {} for {} at side {:?} with limit price {} ({})",
+            self.symbol, order_id, quantity, side, limit_price, time_in_force
         );
-        let match_result = OrderBook::<T>::match_order_with_user(
-            self,
+        // #209: shared submit gate, held through publication (see
+        // `match_market_order_with_user`); exclusive for fill-or-kill so its
+        // feasibility check and sweep see the same book.
+        let fill_or_kill = matches!(time_in_force, TimeInForce::Fok);
+        let _gate = self.acquire_submit_gate(fill_or_kill);
+        if fill_or_kill {
+            let available =
+                self.fok_fillable_quantity(side, quantity, Some(limit_price), user_id, order_id);
+            if available < quantity {
+                self.track_state(
+                    order_id,
+                    OrderStatus::Cancelled {
+                        filled_quantity: 0,
+                        reason: CancelReason::InsufficientLiquidity,
+                    },
+                );
+                self.record_reject(RejectReason::InsufficientLiquidity);
+                return Err(OrderBookError::InsufficientLiquidity {
+                    side,
+                    requested: quantity,
+                    available,
+                });
+            }
+        }
+        let listener = self.trade_listener();
+        let outcome = self.match_order_with_user_outcome(
             order_id,
             side,
             quantity,
             Some(limit_price),
             user_id,
+            TakerKind::Standard,
+            self.capture_fills(listener.is_some()),
         )?;
 
-        // Emit trade-count metric and trigger trade listener if any
-        // transactions printed. The metric is independent of whether
-        // a listener is configured; the listener emission still gates
-        // on `Some(ref listener)`.
-        let trades_emitted = match_result.trades().len() as u64;
-        if trades_emitted > 0 {
-            super::metrics::record_trades(trades_emitted);
-            if let Some(ref listener) = self.trade_listener {
-                let mut trade_result = TradeResult::with_fees(
-                    self.symbol.clone(),
-                    match_result.clone(),
-                    self.fee_schedule,
-                );
-                trade_result.engine_seq = self.next_engine_seq();
-                listener(&trade_result);
-            }
-        }
-
-        Ok(match_result)
+        Ok(self.publish_trades(listener, outcome))
     }
 
     /// Create a snapshot of the current order book state
@@ -3010,7 +4700,10 @@ where
     ///
     /// The returned package includes the book's configuration fields
     /// (`fee_schedule`, `stp_mode`, `tick_size`, `lot_size`,
-    /// `min_order_size`, `max_order_size`) so that
+    /// `min_order_size`, `max_order_size`, `min_order_notional`,
+    /// `max_order_notional`, `market_protection`, `post_only_mode`,
+    /// `reference_price_max_age_ms`, `trading_session`, `circuit_breaker`,
+    /// `speed_bump`, `reserve_fill_limit`, `price_scale`) and the config version so that
     /// [`restore_from_snapshot_package`](Self::restore_from_snapshot_package)
     /// can fully reconstruct the book's state.
     pub fn create_snapshot_package(
@@ -3019,17 +4712,40 @@ where
     ) -> Result<OrderBookSnapshotPackage, OrderBookError> {
         let snapshot = self.create_snapshot(depth);
         let mut package = OrderBookSnapshotPackage::new(snapshot)?;
-        package.fee_schedule = self.fee_schedule;
-        package.stp_mode = self.stp_mode;
-        package.tick_size = self.tick_size;
-        package.lot_size = self.lot_size;
-        package.min_order_size = self.min_order_size;
-        package.max_order_size = self.max_order_size;
+        // Read the version first: a reload racing the snapshot then shows
+        // up as an older version next to newer parameters, never the
+        // reverse.
+        let config_version = self.config_version();
+        let config = self.config.load();
+        package.fee_schedule = config.fee_schedule;
+        package.stp_mode = self.stp_mode();
+        package.tick_size = config.tick_size;
+        package.lot_size = config.lot_size;
+        package.min_order_size = config.min_order_size;
+        package.max_order_size = config.max_order_size;
+        package.min_order_notional = config.min_order_notional;
+        package.max_order_notional = config.max_order_notional;
         package.engine_seq = self.engine_seq();
+        package.execution_seq = self.execution_seq();
+        package.config_version = config_version;
         package.kill_switch_engaged = self.is_kill_switch_engaged();
-        package.risk_config = self.risk_state.config().cloned();
+        package.risk_config = config.risk;
+        package.market_protection = config.market_protection;
+        package.post_only_mode = config.post_only_mode;
+        package.reference_price_max_age_ms = config.reference_price_max_age_ms;
+        package.trading_session = config.trading_session;
+        package.circuit_breaker = config.circuit_breaker;
+        package.speed_bump = config.speed_bump;
+        package.reserve_fill_limit = config.reserve_fill_limit;
         package.market_close_timestamp = self.market_close_timestamp.load(Ordering::Relaxed);
         package.has_market_close = self.has_market_close.load(Ordering::Relaxed);
+        package.price_scale = self.price_scale;
+        package.blocked_users = self.blocked_users();
+        package.account_groups = self.account_groups();
+        package.class_priority = self.is_class_priority_enabled();
+        package.priority_classes = self.priority_classes();
+        package.order_tags = self.order_tag_entries();
+        package.midpoint_orders = self.all_midpoint_orders();
         Ok(package)
     }
 
@@ -3042,8 +4758,12 @@ where
     ///
     /// This restores both the order data and the configuration fields
     /// (`fee_schedule`, `stp_mode`, `tick_size`, `lot_size`,
-    /// `min_order_size`, `max_order_size`, `engine_seq`,
-    /// `kill_switch_engaged`, and the scheduled market close) that were captured by
+    /// `min_order_size`, `max_order_size`, `min_order_notional`,
+    /// `max_order_notional`, `price_scale`, `engine_seq`, `execution_seq`,
+    /// `config_version`, `kill_switch_engaged`, `blocked_users`,
+    /// `account_groups`, `class_priority`, `priority_classes`, `order_tags`,
+    /// `midpoint_orders`, and the scheduled market close)
+    /// that were captured by
     /// [`create_snapshot_package`](Self::create_snapshot_package).
     ///
     /// The kill-switch flag is operator-driven and not journaled by
@@ -3055,27 +4775,47 @@ where
     /// # Errors
     ///
     /// Returns [`OrderBookError::ChecksumMismatch`] /
-    /// [`OrderBookError::InvalidOperation`] when package validation
+    /// [`OrderBookError::UnsupportedSnapshotVersion`] when package validation
     /// fails, and every error [`restore_from_snapshot`](Self::restore_from_snapshot)
     /// documents. All of them fire before any live state is mutated
     /// (#207) — a failed package restore leaves the book, its
     /// configuration, and its risk state untouched.
     pub fn restore_from_snapshot_package(
         &mut self,
-        package: OrderBookSnapshotPackage,
+        mut package: OrderBookSnapshotPackage,
     ) -> Result<(), OrderBookError> {
         // Extract config before consuming the package via into_snapshot().
-        let fee_schedule = package.fee_schedule;
+        let config = BookConfig {
+            tick_size: package.tick_size,
+            lot_size: package.lot_size,
+            min_order_size: package.min_order_size,
+            max_order_size: package.max_order_size,
+            min_order_notional: package.min_order_notional,
+            max_order_notional: package.max_order_notional,
+            fee_schedule: package.fee_schedule,
+            risk: package.risk_config,
+            market_protection: package.market_protection,
+            post_only_mode: package.post_only_mode,
+            reference_price_max_age_ms: package.reference_price_max_age_ms,
+            trading_session: package.trading_session,
+            circuit_breaker: package.circuit_breaker,
+            speed_bump: package.speed_bump,
+            reserve_fill_limit: package.reserve_fill_limit,
+        };
         let stp_mode = package.stp_mode;
-        let tick_size = package.tick_size;
-        let lot_size = package.lot_size;
-        let min_order_size = package.min_order_size;
-        let max_order_size = package.max_order_size;
         let engine_seq = package.engine_seq;
+        let execution_seq = package.execution_seq.max(1);
+        let config_version = package.config_version;
         let kill_switch_engaged = package.kill_switch_engaged;
-        let risk_config = package.risk_config.clone();
         let market_close_timestamp = package.market_close_timestamp;
         let has_market_close = package.has_market_close;
+        let price_scale = package.price_scale;
+        let blocked_users = std::mem::take(&mut package.blocked_users);
+        let account_groups = std::mem::take(&mut package.account_groups);
+        let class_priority = package.class_priority;
+        let priority_classes = std::mem::take(&mut package.priority_classes);
+        let order_tags = std::mem::take(&mut package.order_tags);
+        let midpoint_orders = std::mem::take(&mut package.midpoint_orders);
 
         // Take ownership of the validated snapshot.
         let snapshot = package.into_snapshot()?;
@@ -3095,35 +4835,42 @@ where
         // returns `None` rather than `Some(empty)`. Entries and counters
         // are cleared here and rebuilt during the commit walk below
         // (`rebuild_risk = true`), which registers every restored resting
-        // order exactly like the live admission path.
-        if let Some(risk_config) = risk_config {
-            self.risk_state.set_config(risk_config);
-        } else {
-            self.risk_state.disable();
-        }
+        // order exactly like the live admission path. The rest of the
+        // captured configuration is installed alongside, together with
+        // its version, so stamps resume where the source book left off.
+        self.store_config(config);
+        self.config_version.store(config_version, Ordering::Release);
         self.risk_state.clear();
 
         self.commit_restored_levels(&prepared, true);
 
-        // Apply configuration that was captured in the package.
-        self.fee_schedule = fee_schedule;
-        self.stp_mode = stp_mode;
-        self.tick_size = tick_size;
-        self.lot_size = lot_size;
-        self.min_order_size = min_order_size;
-        self.max_order_size = max_order_size;
+        // Apply the remaining configuration captured in the package.
+        self.stp_mode.store(stp_mode);
+        self.price_scale = price_scale;
 
         // Restore the engine's outbound monotonic counter so that the
         // first `next_engine_seq()` call on this restored book returns
         // exactly the snapshotted value, preserving cross-snapshot
-        // monotonicity for downstream consumers.
+        // monotonicity for downstream consumers. Execution ids resume the
+        // same way, so fills from before and after the restore never share
+        // one.
         self.engine_seq.store(engine_seq, Ordering::Release);
+        self.execution_seq.store(execution_seq, Ordering::Release);
 
         // Restore the operational kill-switch flag so that a book
         // recovered from disaster snapshot resumes in the same
         // operational mode it was halted in.
         self.kill_switch
             .store(kill_switch_engaged, Ordering::Relaxed);
+        self.blocked_users.clear();
+        for user_id in blocked_users {
+            self.blocked_users.insert(user_id);
+        }
+        self.account_groups.replace(account_groups);
+        self.priority_classes
+            .replace(class_priority, priority_classes);
+        self.restore_order_tags(order_tags);
+        self.restore_midpoint_orders(midpoint_orders);
 
         // Restore the scheduled market close so DAY / GTD expiry resumes against the
         // same session boundary the book was snapshotted with. `restore_from_snapshot`
@@ -3176,8 +4923,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`OrderBookError::InvalidOperation`] when the snapshot's
-    /// symbol does not match this book or when one side carries two
+    /// Returns [`OrderBookError::SnapshotSymbolMismatch`] when the
+    /// snapshot's symbol does not match this book and
+    /// [`OrderBookError::DuplicateSnapshotLevel`] when one side carries two
     /// levels at the same price (the install would keep only one while
     /// the index rebuild registered both),
     /// [`OrderBookError::PriceLevelError`] when a level snapshot fails
@@ -3195,11 +4943,9 @@ where
     /// Symbol guard shared by the snapshot restore entry points.
     fn ensure_snapshot_symbol(&self, snapshot: &OrderBookSnapshot) -> Result<(), OrderBookError> {
         if snapshot.symbol != self.symbol {
-            return Err(OrderBookError::InvalidOperation {
-                message: format!(
-                    "Snapshot symbol {} does not match order book symbol {}",
-                    snapshot.symbol, self.symbol
-                ),
+            return Err(OrderBookError::SnapshotSymbolMismatch {
+                snapshot_symbol: snapshot.symbol.clone(),
+                book_symbol: self.symbol.clone(),
             });
         }
         Ok(())
@@ -3216,7 +4962,7 @@ where
         snapshot: OrderBookSnapshot,
     ) -> Result<PreparedSnapshotLevels, OrderBookError> {
         let convert = |levels: Vec<pricelevel::PriceLevelSnapshot>,
-                       side: &str|
+                       side: Side|
          -> Result<Vec<(u128, Arc<PriceLevel>)>, OrderBookError> {
             let mut converted = Vec::with_capacity(levels.len());
             for level_snapshot in levels {
@@ -3234,18 +4980,16 @@ where
             // rebuild silently dropped one level; neither outcome is
             // acceptable, so the snapshot is rejected up front.
             if let Some(window) = converted.windows(2).find(|pair| pair[0].0 == pair[1].0) {
-                return Err(OrderBookError::InvalidOperation {
-                    message: format!(
-                        "Snapshot contains two {side} levels at the same price {}",
-                        window[0].0
-                    ),
+                return Err(OrderBookError::DuplicateSnapshotLevel {
+                    side,
+                    price: window[0].0,
                 });
             }
             Ok(converted)
         };
 
-        let bids = convert(snapshot.bids, "bid")?;
-        let asks = convert(snapshot.asks, "ask")?;
+        let bids = convert(snapshot.bids, Side::Buy)?;
+        let asks = convert(snapshot.asks, Side::Sell)?;
 
         // Cross-level duplicate-id check. Per-level duplicates are already
         // rejected by `PriceLevel::from_snapshot` (pricelevel 0.9); an id
@@ -3290,6 +5034,7 @@ where
     /// per-user `Vec` order is consumed by `cancel_orders_by_user`, needs
     /// the fixed traversal. One scratch buffer is reused across levels.
     fn commit_restored_levels(&self, prepared: &PreparedSnapshotLevels, rebuild_risk: bool) {
+        let _mutation = self.cache.begin_mutation();
         self.cache.invalidate();
 
         // Clear all existing data
@@ -3300,7 +5045,11 @@ where
             drop(entry);
         }
         self.order_locations.clear();
+        self.extra_fields.clear();
         self.user_orders.clear();
+        self.order_owners.clear();
+        self.order_lifetimes.clear();
+        self.order_tags.clear();
         // The special-order tracker is a full replacement on restore: clear it
         // here and rebuild it below from the restored resting orders, mirroring
         // the `user_orders` / `order_locations` rebuild (#194).
@@ -3308,14 +5057,23 @@ where
         self.special_order_tracker.clear();
         self.has_traded.store(false, Ordering::Relaxed);
         self.last_trade_price.store(0);
+        self.last_execution_id.store(0, Ordering::Relaxed);
+        if let Some(history) = &self.execution_history {
+            history.clear();
+        }
         self.has_market_close.store(false, Ordering::Relaxed);
         self.market_close_timestamp.store(0, Ordering::Relaxed);
 
+        self.depth_totals.clear();
+        self.level_pool.clear();
+        self.expiry_wheel.clear();
         for (price, level) in &prepared.bids {
             self.bids.insert(*price, level.clone());
+            self.depth_totals.reconcile(Side::Buy, level);
         }
         for (price, level) in &prepared.asks {
             self.asks.insert(*price, level.clone());
+            self.depth_totals.reconcile(Side::Sell, level);
         }
 
         let mut level_orders: Vec<Arc<OrderType<()>>> = Vec::new();
@@ -3324,6 +5082,9 @@ where
                 level.snapshot_by_seq_into(&mut level_orders);
                 for order in &level_orders {
                     self.order_locations.insert(order.id(), (*price, side));
+                    self.order_lifetimes
+                        .on_rest(order.id(), order.timestamp().as_u64());
+                    self.schedule_expiry(order.id(), order.time_in_force());
                     self.track_user_order(order.user_id(), order.id());
                     #[cfg(feature = "special_orders")]
                     self.reregister_special_order(order.as_ref());
@@ -3347,6 +5108,8 @@ where
         };
         rebuild_side(&prepared.bids, Side::Buy);
         rebuild_side(&prepared.asks, Side::Sell);
+        self.cache.invalidate();
+        self.refresh_depth_view();
     }
 
     /// Re-register a restored resting order with the special-order tracker
@@ -3485,6 +5248,136 @@ where
             depth, // Use depth for imbalance calculation
             flags,
         )
+        .with_price_scale(self.price_scale)
+        .with_reference_prices(
+            self.reference_price(ExternalPriceSource::Index),
+            self.reference_price(ExternalPriceSource::Mark),
+        )
+    }
+
+    /// The top `depth` levels of each side as [`LevelAggregate`]s, best
+    /// level first, without visiting any order.
+    ///
+    /// The view is built from each level's running totals in an O(depth)
+    /// walk and cached until the next book mutation, so repeated reads of
+    /// an unchanged book are a copy of the cached buffer. Like
+    /// [`Self::top_of_book`], a build that overlaps a mutation is retried,
+    /// and `update_seq` identifies the book state the levels were read at.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 99, 5, Side::Buy, TimeInForce::Gtc, None);
+    ///
+    /// let levels = book.level_aggregates(1);
+    /// assert_eq!(levels.bids.len(), 1);
+    /// assert_eq!(levels.bids[0].price, 100);
+    /// assert_eq!(levels.bids[0].visible_quantity, 10);
+    /// ```
+    #[must_use]
+    pub fn level_aggregates(&self, depth: usize) -> LevelAggregates {
+        let (levels, _) = self.pinned_level_aggregates(depth);
+        LevelAggregates {
+            bids: levels.bids.iter().take(depth).copied().collect(),
+            asks: levels.asks.iter().take(depth).copied().collect(),
+            update_seq: levels.update_seq,
+        }
+    }
+
+    /// Creates an enriched snapshot from the cached level aggregates.
+    ///
+    /// Computes the same metrics as [`Self::enriched_snapshot`] but leaves
+    /// the per-order `bids` / `asks` fields empty and fills `bid_levels` /
+    /// `ask_levels` instead, so it never iterates orders. Use it when only
+    /// the level totals and metrics are needed.
+    ///
+    /// # Performance
+    /// O(depth) on the first call after a mutation, then a copy of the
+    /// cached view.
+    #[must_use]
+    pub fn enriched_aggregate_snapshot(&self, depth: usize) -> EnrichedSnapshot {
+        self.enriched_aggregate_snapshot_with_metrics(depth, MetricFlags::ALL)
+    }
+
+    /// Creates an aggregate-only enriched snapshot with custom metric
+    /// selection. See [`Self::enriched_aggregate_snapshot`] and
+    /// [`Self::enriched_snapshot_with_metrics`].
+    #[must_use]
+    pub fn enriched_aggregate_snapshot_with_metrics(
+        &self,
+        depth: usize,
+        flags: MetricFlags,
+    ) -> EnrichedSnapshot {
+        let levels = self.level_aggregates(depth);
+        EnrichedSnapshot::from_aggregates(
+            self.symbol.clone(),
+            self.clock().now_millis().as_u64(),
+            levels.bids,
+            levels.asks,
+            depth,
+            depth,
+            flags,
+        )
+        .with_price_scale(self.price_scale)
+        .with_reference_prices(
+            self.reference_price(ExternalPriceSource::Index),
+            self.reference_price(ExternalPriceSource::Mark),
+        )
+    }
+
+    /// The cached level aggregates covering `depth`, rebuilt and
+    /// republished on a miss, and whether no mutation overlapped the build,
+    /// neither one that finished during it nor one still in flight, so
+    /// every level comes from the book state at `update_seq`. Cached views
+    /// are always consistent; a rebuild made from inside a mutation, or
+    /// one that keeps overlapping mutations, is returned as is.
+    pub(super) fn pinned_level_aggregates(&self, depth: usize) -> (Arc<LevelAggregates>, bool) {
+        if let Some(levels) = self.cache.get_cached_level_aggregates(depth) {
+            return (levels, true);
+        }
+
+        let (levels, consistent) = self.cache.read_stable(
+            || self.compute_level_aggregates(depth),
+            |levels| levels.update_seq,
+        );
+        let levels = Arc::new(levels);
+        if consistent {
+            self.cache
+                .update_level_aggregates(depth, Arc::clone(&levels));
+        }
+        (levels, consistent)
+    }
+
+    /// Read the top `depth` levels of each side from the skip maps,
+    /// stamped with the cache update sequence observed before the read.
+    pub(super) fn compute_level_aggregates(&self, depth: usize) -> LevelAggregates {
+        let update_seq = self.cache.update_seq();
+        let aggregate = |level: &PriceLevel| LevelAggregate {
+            price: level.price(),
+            visible_quantity: level.visible_quantity(),
+            hidden_quantity: level.hidden_quantity(),
+            order_count: level.order_count(),
+        };
+        LevelAggregates {
+            bids: self
+                .bids
+                .iter()
+                .rev()
+                .take(depth)
+                .map(|entry| aggregate(entry.value()))
+                .collect(),
+            asks: self
+                .asks
+                .iter()
+                .take(depth)
+                .map(|entry| aggregate(entry.value()))
+                .collect(),
+            update_seq,
+        }
     }
 
     /// Get the total volume at each price level
@@ -3688,7 +5581,7 @@ where
     /// quantity available on that side (in units).
     ///
     /// # Performance
-    /// O(N + M) where N is bid levels and M is ask levels.
+    /// O(1): reads the per-side totals the book maintains on every change.
     ///
     /// # Examples
     /// ```
@@ -3708,19 +5601,84 @@ where
     /// ```
     #[must_use]
     pub fn buy_sell_pressure(&self) -> (u64, u64) {
-        let buy_pressure: u64 = self
-            .bids
-            .iter()
-            .map(|entry| entry.value().total_quantity().unwrap_or(0))
-            .sum();
+        (
+            self.side_total_quantity(Side::Buy),
+            self.side_total_quantity(Side::Sell),
+        )
+    }
 
-        let sell_pressure: u64 = self
-            .asks
-            .iter()
-            .map(|entry| entry.value().total_quantity().unwrap_or(0))
-            .sum();
+    /// Total visible plus hidden quantity resting on `side`, in units.
+    ///
+    /// # Performance
+    /// O(1). The total is reconciled after every change to a level; a read
+    /// that overlaps a mutation may reflect it partially, but once the book
+    /// is quiescent it equals the sum over the side's levels.
+    #[must_use]
+    pub fn side_total_quantity(&self, side: Side) -> u64 {
+        self.depth_totals.quantity(side)
+    }
+
+    /// Number of orders resting on `side`.
+    ///
+    /// # Performance
+    /// O(1), with the same consistency as [`Self::side_total_quantity`].
+    #[must_use]
+    pub fn side_order_count(&self, side: Side) -> usize {
+        self.depth_totals.order_count(side)
+    }
+
+    /// Cumulative activity counters of this book: orders added, cancelled
+    /// and filled, trades, traded volume and price range, rejects by
+    /// reason, and uptime. Counted with relaxed atomics on the same paths
+    /// that feed [`crate::orderbook::metrics`], independently of the
+    /// `metrics` feature.
+    #[must_use]
+    pub fn stats(&self) -> BookStats {
+        self.counters.snapshot()
+    }
 
-        (buy_pressure, sell_pressure)
+    /// Zero the counters returned by [`Self::stats`] and restart its
+    /// uptime. Also zeroes the distributions of [`Self::quote_lifetimes`].
+    pub fn reset_stats(&self) {
+        self.counters.reset();
+        self.reset_order_lifetimes();
+    }
+
+    /// Counters of the timing wheel that schedules `Gtd` and `Day` order
+    /// expiry for [`Self::evict_expired_orders`].
+    #[must_use]
+    pub fn expiry_wheel_stats(&self) -> ExpiryWheelStats {
+        self.expiry_wheel.stats()
+    }
+
+    /// Counters of the pool of emptied price levels kept for reuse.
+    #[must_use]
+    pub fn level_pool_stats(&self) -> LevelPoolStats {
+        self.level_pool.stats()
+    }
+
+    /// Keep at most `max_size` emptied price levels for reuse (default
+    /// [`DEFAULT_LEVEL_POOL_SIZE`](super::level_pool::DEFAULT_LEVEL_POOL_SIZE)).
+    /// Zero disables pooling. Parked levels beyond the new limit are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_level_pool_max_size(16);
+    ///
+    /// let id = Id::new();
+    /// book.add_limit_order(id, 100, 1, Side::Buy, TimeInForce::Gtc, None).unwrap();
+    /// book.cancel_order(id).unwrap();
+    /// book.add_limit_order(Id::new(), 100, 1, Side::Buy, TimeInForce::Gtc, None).unwrap();
+    ///
+    /// let stats = book.level_pool_stats();
+    /// assert_eq!((stats.recycled, stats.hits), (1, 1));
+    /// ```
+    pub fn set_level_pool_max_size(&mut self, max_size: usize) {
+        self.level_pool.set_max_size(max_size);
     }
 
     /// Detects if the order book is thin (has low liquidity)
@@ -3731,13 +5689,14 @@ where
     ///
     /// # Arguments
     /// - `threshold`: Minimum total volume required (in units)
-    /// - `levels`: Number of top levels to check on each side
+    /// - `levels`: Number of top levels to check on each side (`0` for all)
     ///
     /// # Returns
     /// `true` if either side has insufficient liquidity, `false` otherwise
     ///
     /// # Performance
-    /// O(N) where N is levels to check.
+    /// O(N) where N is levels to check, or O(1) when `levels` is `0` (the
+    /// whole side) or covers every level.
     ///
     /// # Examples
     /// ```
@@ -3754,10 +5713,14 @@ where
     /// ```
     #[must_use]
     pub fn is_thin_book(&self, threshold: u64, levels: usize) -> bool {
-        let bid_stats = self.depth_statistics(Side::Buy, levels);
-        let ask_stats = self.depth_statistics(Side::Sell, levels);
-
-        bid_stats.total_volume < threshold || ask_stats.total_volume < threshold
+        [Side::Buy, Side::Sell].into_iter().any(|side| {
+            let volume = if levels == 0 {
+                self.side_total_quantity(side)
+            } else {
+                self.total_depth_at_levels(levels, side)
+            };
+            volume < threshold
+        })
     }
 
     /// Calculates depth distribution histogram for a side
@@ -3877,7 +5840,8 @@ where
 // Implementation of RepricingOperations trait for OrderBook
 #[cfg(feature = "special_orders")]
 use crate::orderbook::repricing::{
-    RepricingOperations, RepricingResult, calculate_pegged_price, calculate_trailing_stop_price,
+    PegReference, RepricingOperations, RepricingResult, calculate_pegged_price,
+    calculate_trailing_stop_price,
 };
 
 #[cfg(feature = "special_orders")]
@@ -3893,20 +5857,20 @@ where
     /// `if ...is_ok()`; now it is surfaced (#174). A peg that simply has no
     /// reference / no valid passive tick this cycle is a no-op, not a failure,
     /// and is not recorded.
+    ///
+    /// The caller holds the submit gate.
     fn reprice_pegged_collecting(&self, failures: &mut Vec<(Id, String)>) -> usize {
         let pegged_ids = self.special_order_tracker.pegged_order_ids();
         if pegged_ids.is_empty() {
             return 0;
         }
 
-        let best_bid = self.best_bid();
-        let best_ask = self.best_ask();
+        let PegReference {
+            best_bid,
+            best_ask,
+            last_trade,
+        } = self.peg_reference();
         let mid_price = self.mid_price().map(|p| p as u128);
-        let last_trade = if self.has_traded.load(Ordering::Relaxed) {
-            Some(self.last_trade_price.load())
-        } else {
-            None
-        };
 
         let mut repriced_count = 0;
 
@@ -3927,7 +5891,7 @@ where
                         best_ask,
                         mid_price,
                         last_trade,
-                        self.tick_size,
+                        self.tick_size(),
                     )
                     && new_price != current_price.as_u128()
                 {
@@ -3935,7 +5899,7 @@ where
                         order_id,
                         new_price: pricelevel::Price::new(new_price),
                     };
-                    match self.update_order(update) {
+                    match self.update_order_ungated(update) {
                         Ok(_) => {
                             repriced_count += 1;
                             trace!(
@@ -3968,6 +5932,7 @@ where
     /// Re-price every trailing stop, returning the count repriced and pushing a
     /// `(order_id, reason)` pair onto `failures` for each rejected
     /// `update_order` (mirrors [`Self::reprice_pegged_collecting`], #174).
+    /// The caller holds the submit gate.
     fn reprice_trailing_collecting(&self, failures: &mut Vec<(Id, String)>) -> usize {
         let trailing_ids = self.special_order_tracker.trailing_stop_ids();
         if trailing_ids.is_empty() {
@@ -4001,15 +5966,13 @@ where
                             market_price,
                         )
                     {
-                        // Update the order with new stop price
-                        // We need to update both price and last_reference_price
-                        // For now, we update the price; the reference price update
-                        // requires modifying the order directly
-                        let update = OrderUpdate::UpdatePrice {
+                        // Move the stop and the trailed extreme together, so
+                        // the next pass measures from the new reference.
+                        match self.update_trailing_state_ungated(
                             order_id,
-                            new_price: pricelevel::Price::new(new_stop_price),
-                        };
-                        match self.update_order(update) {
+                            pricelevel::Price::new(new_stop_price),
+                            pricelevel::Price::new(new_reference),
+                        ) {
                             Ok(_) => {
                                 repriced_count += 1;
                                 trace!(
@@ -4045,6 +6008,44 @@ where
 
         repriced_count
     }
+
+    /// The reference prices pegged orders track, read from the book now.
+    fn peg_reference(&self) -> PegReference {
+        let last_trade = match self.special_order_tracker.last_trade_source() {
+            Some(source) => self.fresh_reference_price(source),
+            None => self
+                .has_traded
+                .load(Ordering::Relaxed)
+                .then(|| self.last_trade_price.load()),
+        };
+        let bbo = self.bbo();
+        PegReference {
+            best_bid: bbo.best_bid,
+            best_ask: bbo.best_ask,
+            last_trade,
+        }
+    }
+
+    /// The automatic re-price pass run when a submit gate guard is
+    /// released with [`Self::set_auto_reprice_pegged`] enabled. The caller
+    /// holds the gate. See the batching rules documented there.
+    pub(super) fn auto_reprice_pegged_pass(&self) {
+        if self.special_order_tracker.pegged_order_count() == 0 {
+            return;
+        }
+        self.special_order_tracker
+            .coalesce_auto_reprice(self.peg_reference(), || {
+                let mut failures = Vec::new();
+                let repriced = self.reprice_pegged_collecting(&mut failures);
+                trace!(
+                    "Automatic re-price of {}: {} pegged orders moved, {} rejected",
+                    self.symbol,
+                    repriced,
+                    failures.len()
+                );
+                self.peg_reference()
+            });
+    }
 }
 
 #[cfg(feature = "special_orders")]
@@ -4059,6 +6060,7 @@ where
     /// [`reprice_special_orders`](Self::reprice_special_orders), whose
     /// [`RepricingResult::failed_orders`] records every rejected re-price.
     fn reprice_pegged_orders(&self) -> Result<usize, OrderBookError> {
+        let _gate = self.submit_gate_read();
         Ok(self.reprice_pegged_collecting(&mut Vec::new()))
     }
 
@@ -4068,6 +6070,7 @@ where
     /// [`reprice_special_orders`](Self::reprice_special_orders) for the
     /// failure-reporting variant.
     fn reprice_trailing_stops(&self) -> Result<usize, OrderBookError> {
+        let _gate = self.submit_gate_read();
         Ok(self.reprice_trailing_collecting(&mut Vec::new()))
     }
 
@@ -4080,6 +6083,8 @@ where
     /// rejected re-price leaves the order at its prior price (validate-first
     /// modify, #98/#168).
     fn reprice_special_orders(&self) -> Result<RepricingResult, OrderBookError> {
+        // One gate acquisition for the whole pass, as for a batch submit.
+        let _gate = self.submit_gate_read();
         let mut failed_orders = Vec::new();
         let pegged_count = self.reprice_pegged_collecting(&mut failed_orders);
         let trailing_count = self.reprice_trailing_collecting(&mut failed_orders);
@@ -4120,6 +6125,113 @@ impl<T> OrderBook<T>
 where
     T: Clone + Default + Send + Sync + 'static,
 {
+    /// Enable or disable automatic re-pricing of pegged orders.
+    ///
+    /// When enabled, every mutating entry point (submits, cancels,
+    /// modifies, market orders, mass cancels) ends with a re-price pass
+    /// run before it releases the submit gate, so pegged orders follow a
+    /// best bid, best ask or last trade change within the same call and
+    /// no other submit observes the stale peg. To avoid reprice storms,
+    /// passes are batched:
+    ///
+    /// - one pass per call, however many levels or orders it touched;
+    /// - the pass runs only when the references differ from those the
+    ///   previous pass left the book at, so a peg's own move does not
+    ///   trigger another pass;
+    /// - a call that finds a pass already running on another thread
+    ///   skips its own.
+    ///
+    /// Rejected re-prices leave the order at its old price and are only
+    /// traced; use
+    /// [`reprice_special_orders`](RepricingOperations::reprice_special_orders)
+    /// to collect them. Trailing stops are still re-priced on demand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{
+    ///     Hash32, Id, OrderType, PegReferenceType, Price, Quantity, Side, TimeInForce, TimestampMs,
+    /// };
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// book.set_auto_reprice_pegged(true);
+    /// book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None)
+    ///     .unwrap();
+    /// let peg = Id::new();
+    /// book.add_order(OrderType::PeggedOrder {
+    ///     id: peg,
+    ///     price: Price::new(100),
+    ///     quantity: Quantity::new(5),
+    ///     side: Side::Buy,
+    ///     user_id: Hash32::zero(),
+    ///     timestamp: TimestampMs::new(0),
+    ///     time_in_force: TimeInForce::Gtc,
+    ///     reference_price_offset: 0,
+    ///     reference_price_type: PegReferenceType::BestBid,
+    ///     extra_fields: (),
+    /// })
+    /// .unwrap();
+    ///
+    /// book.add_limit_order(Id::new(), 102, 10, Side::Buy, TimeInForce::Gtc, None)
+    ///     .unwrap();
+    /// assert_eq!(book.get_order(peg).unwrap().price().as_u128(), 102);
+    /// ```
+    pub fn set_auto_reprice_pegged(&self, enabled: bool) {
+        self.special_order_tracker.set_auto_reprice(enabled);
+    }
+
+    /// Returns whether pegged orders are re-priced automatically.
+    pub fn auto_reprice_pegged(&self) -> bool {
+        self.special_order_tracker.auto_reprice()
+    }
+
+    /// Make `PegReferenceType::LastTrade` pegs track the fresh `source`
+    /// price supplied with [`Self::set_reference_price`] instead of the
+    /// book's last trade; `None` restores the last trade. While the
+    /// external price is missing or stale those pegs are left where they
+    /// are. With [`Self::set_auto_reprice_pegged`] enabled, a new external
+    /// price re-prices them like a trade would.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{ExternalPriceSource, OrderBook};
+    /// use pricelevel::{
+    ///     Hash32, Id, OrderType, PegReferenceType, Price, Quantity, Side, TimeInForce, TimestampMs,
+    /// };
+    ///
+    /// let book = OrderBook::<()>::new("BTC-PERP");
+    /// book.set_auto_reprice_pegged(true);
+    /// book.set_peg_last_trade_source(Some(ExternalPriceSource::Index));
+    /// let peg = Id::new();
+    /// book.add_order(OrderType::PeggedOrder {
+    ///     id: peg,
+    ///     price: Price::new(90),
+    ///     quantity: Quantity::new(5),
+    ///     side: Side::Buy,
+    ///     user_id: Hash32::zero(),
+    ///     timestamp: TimestampMs::new(0),
+    ///     time_in_force: TimeInForce::Gtc,
+    ///     reference_price_offset: -10,
+    ///     reference_price_type: PegReferenceType::LastTrade,
+    ///     extra_fields: (),
+    /// })
+    /// .unwrap();
+    ///
+    /// book.set_external_reference_price(120);
+    /// assert_eq!(book.get_order(peg).unwrap().price().as_u128(), 110);
+    /// ```
+    pub fn set_peg_last_trade_source(&self, source: Option<ExternalPriceSource>) {
+        self.special_order_tracker.set_last_trade_source(source);
+    }
+
+    /// The external price `LastTrade` pegs track, if any. See
+    /// [`Self::set_peg_last_trade_source`].
+    pub fn peg_last_trade_source(&self) -> Option<ExternalPriceSource> {
+        self.special_order_tracker.last_trade_source()
+    }
+
     /// Returns the number of tracked pegged orders
     pub fn pegged_order_count(&self) -> usize {
         self.special_order_tracker.pegged_order_count()
@@ -4153,12 +6265,52 @@ struct PreparedSnapshotLevels {
     asks: Vec<(u128, Arc<PriceLevel>)>,
 }
 
-/// Guard over the submit gate (#209) in either mode — held for the length
-/// of one mutating entry-point call. Only the drop timing matters, hence
-/// the unused-field allowances.
-pub(super) enum SubmitGateGuard<'a> {
+/// The submit gate (#209) held in either mode. Only the drop timing
+/// matters, hence the unused-field allowances.
+pub(super) enum SubmitGateLock<'a> {
     /// Shared mode: every non-FOK mutating entry point.
     Read(#[allow(dead_code)] std::sync::RwLockReadGuard<'a, ()>),
     /// Exclusive mode: a fill-or-kill submit's feasibility + sweep window.
     Write(#[allow(dead_code)] std::sync::RwLockWriteGuard<'a, ()>),
 }
+
+/// Guard over the submit gate — held for the length of one mutating
+/// entry-point call.
+pub(super) struct SubmitGateGuard<'a> {
+    #[allow(dead_code)]
+    lock: SubmitGateLock<'a>,
+    /// Book whose pegged orders are re-priced when the guard is released,
+    /// still under the gate, so the pass completes the same update cycle.
+    #[cfg(feature = "special_orders")]
+    reprice_on_release: Option<&'a dyn RepriceOnRelease>,
+}
+
+/// Type-erased hook behind [`SubmitGateGuard`]'s automatic re-price.
+#[cfg(feature = "special_orders")]
+pub(super) trait RepriceOnRelease {
+    /// Run the automatic pegged re-price pass. The caller holds the gate.
+    fn reprice_on_release(&self);
+}
+
+#[cfg(feature = "special_orders")]
+impl<T> RepriceOnRelease for OrderBook<T>
+where
+    T: Clone + Default + Send + Sync + 'static,
+{
+    fn reprice_on_release(&self) {
+        self.auto_reprice_pegged_pass();
+    }
+}
+
+#[cfg(feature = "special_orders")]
+impl Drop for SubmitGateGuard<'_> {
+    fn drop(&mut self) {
+        // `lock` is dropped after this body, so the pass runs under the
+        // gate and its ungated modifies cannot nest an acquisition.
+        if let Some(book) = self.reprice_on_release
+            && !std::thread::panicking()
+        {
+            book.reprice_on_release();
+        }
+    }
+}
@@ -28,6 +28,14 @@ pub struct PriceLevelChangedEvent {
     /// latest visible quantity of the order book at this price level
     pub quantity: u64,
 
+    /// Number of orders resting at this price level after the change;
+    /// `0` once the level is gone.
+    ///
+    /// Defaults to `0` when deserializing payloads that pre-date the
+    /// field.
+    #[serde(default)]
+    pub order_count: usize,
+
     /// Strictly monotonic global engine sequence number for this event.
     /// See [`crate::orderbook::trade::TradeResult::engine_seq`] for the
     /// full cross-stream monotonicity contract.
@@ -36,6 +44,13 @@ pub struct PriceLevelChangedEvent {
     /// that pre-date `engine_seq` so existing consumers keep parsing.
     #[serde(default)]
     pub engine_seq: u64,
+
+    /// `OrderBook::config_version` when the event was emitted.
+    ///
+    /// Defaults to `0` when deserializing payloads from format versions
+    /// that pre-date `config_version`.
+    #[serde(default)]
+    pub config_version: u64,
 }
 
 /// A thread-safe listener callback for price level change events.
@@ -62,7 +77,9 @@ mod tests {
             side: Side::Buy,
             price: 50_000,
             quantity: 250,
+            order_count: 1,
             engine_seq,
+            config_version: 0,
         }
     }
 
@@ -72,7 +89,9 @@ mod tests {
             side: Side::Sell,
             price: 1,
             quantity: 1,
+            order_count: 1,
             engine_seq: 0,
+            config_version: 0,
         };
         assert_eq!(event.engine_seq, 0);
     }
@@ -0,0 +1,325 @@
+//! Runtime-reloadable validation, fee and risk parameters of an
+//! [`OrderBook`](super::book::OrderBook).
+//!
+//! A [`BookConfig`] groups everything an operator may retune while the
+//! book keeps trading: tick and lot size, order size and notional limits,
+//! the fee schedule, the [`RiskConfig`] (price bands and per-account
+//! limits), the [`MarketProtection`] of market orders, the
+//! [`PostOnlyMode`] of crossing post-only orders, the age past which
+//! an external reference price goes stale, the [`TradingSession`]
+//! schedule, the [`CircuitBreakerConfig`], the [`SpeedBump`] and the
+//! reserve fill limit. The book holds it in a single atomic cell, so
+//! [`OrderBook::reload_config`](super::book::OrderBook::reload_config)
+//! swaps every parameter at once through `&self` — including on a book
+//! shared behind an `Arc` — and every admission validates against one
+//! consistent copy, never a mix of old and new values.
+//!
+//! Each reload bumps the book's config version, which is stamped on
+//! trades, price-level events and snapshot packages so consumers can tell
+//! which parameters produced them.
+
+use super::circuit_breaker::CircuitBreakerConfig;
+use super::error::OrderBookError;
+use super::fees::FeeSchedule;
+use super::market_protection::MarketProtection;
+use super::post_only::PostOnlyMode;
+use super::risk::RiskConfig;
+use super::session::TradingSession;
+use super::speed_bump::SpeedBump;
+use serde::{Deserialize, Serialize};
+
+/// Validation, fee and risk parameters of an order book, swapped
+/// atomically at runtime.
+///
+/// Every field is optional; the default disables every check and charges
+/// no fees, matching [`OrderBook::new`](super::book::OrderBook::new).
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{BookConfig, FeeSchedule, OrderBook};
+/// use std::sync::Arc;
+///
+/// let book = Arc::new(OrderBook::<()>::new("BTC/USD"));
+/// let version = book
+///     .reload_config(
+///         BookConfig::new()
+///             .with_tick_size(10)
+///             .with_order_size_range(Some(1), Some(1_000))
+///             .with_fee_schedule(FeeSchedule::new(-2, 5)),
+///     )
+///     .expect("valid configuration");
+///
+/// assert_eq!(version, 1);
+/// assert_eq!(book.tick_size(), Some(10));
+/// assert_eq!(book.config_version(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookConfig {
+    /// Minimum price increment; prices must be exact multiples.
+    pub tick_size: Option<u128>,
+    /// Minimum quantity increment; quantities must be exact multiples.
+    pub lot_size: Option<u64>,
+    /// Smallest accepted `total_quantity()`.
+    pub min_order_size: Option<u64>,
+    /// Largest accepted `total_quantity()`.
+    pub max_order_size: Option<u64>,
+    /// Smallest accepted `price × total_quantity()`.
+    pub min_order_notional: Option<u128>,
+    /// Largest accepted `price × total_quantity()`.
+    pub max_order_notional: Option<u128>,
+    /// Maker/taker fees applied to every trade.
+    pub fee_schedule: Option<FeeSchedule>,
+    /// Pre-trade risk limits, including the price bands.
+    pub risk: Option<RiskConfig>,
+    /// Sweep limits applied to every market order.
+    #[serde(default)]
+    pub market_protection: Option<MarketProtection>,
+    /// Whether a crossing post-only order is rejected or slid.
+    #[serde(default)]
+    pub post_only_mode: PostOnlyMode,
+    /// Age in milliseconds past which an externally supplied index or
+    /// mark price is stale and ignored. `None` never expires them.
+    #[serde(default)]
+    pub reference_price_max_age_ms: Option<u64>,
+    /// Daily schedule of pre-open, continuous and closed phases. `None`
+    /// trades continuously.
+    #[serde(default)]
+    pub trading_session: Option<TradingSession>,
+    /// Price velocity limits that halt the book or switch it to an
+    /// auction.
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Delay applied to aggressive orders submitted through the speed
+    /// bump. `None` processes them at once.
+    #[serde(default)]
+    pub speed_bump: Option<SpeedBump>,
+    /// Most quantity one incoming order may take from a single reserve
+    /// order before the reserve rotates behind the rest of its level.
+    /// `None` leaves reserve fills unthrottled.
+    #[serde(default)]
+    pub reserve_fill_limit: Option<u64>,
+}
+
+impl BookConfig {
+    /// Construct a configuration with every check disabled.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tick size. Must be non-zero.
+    #[must_use]
+    pub fn with_tick_size(mut self, tick_size: u128) -> Self {
+        self.tick_size = Some(tick_size);
+        self
+    }
+
+    /// Set the lot size. Must be non-zero.
+    #[must_use]
+    pub fn with_lot_size(mut self, lot_size: u64) -> Self {
+        self.lot_size = Some(lot_size);
+        self
+    }
+
+    /// Set the accepted order size range; `None` leaves a bound open.
+    #[must_use]
+    pub fn with_order_size_range(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_order_size = min;
+        self.max_order_size = max;
+        self
+    }
+
+    /// Set the accepted order notional range; `None` leaves a bound open.
+    #[must_use]
+    pub fn with_order_notional_range(mut self, min: Option<u128>, max: Option<u128>) -> Self {
+        self.min_order_notional = min;
+        self.max_order_notional = max;
+        self
+    }
+
+    /// Set the fee schedule. The taker fee must not be negative.
+    #[must_use]
+    pub fn with_fee_schedule(mut self, fee_schedule: FeeSchedule) -> Self {
+        self.fee_schedule = Some(fee_schedule);
+        self
+    }
+
+    /// Set the pre-trade risk limits and price bands.
+    #[must_use]
+    pub fn with_risk(mut self, risk: RiskConfig) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Set the market order protection. See [`MarketProtection::validate`].
+    #[must_use]
+    pub fn with_market_protection(mut self, protection: MarketProtection) -> Self {
+        self.market_protection = Some(protection);
+        self
+    }
+
+    /// Set how a post-only order that would cross the book is handled.
+    #[must_use]
+    pub fn with_post_only_mode(mut self, mode: PostOnlyMode) -> Self {
+        self.post_only_mode = mode;
+        self
+    }
+
+    /// Set the age past which an externally supplied reference price is
+    /// stale. Must be non-zero.
+    #[must_use]
+    pub fn with_reference_price_max_age_ms(mut self, max_age_ms: u64) -> Self {
+        self.reference_price_max_age_ms = Some(max_age_ms);
+        self
+    }
+
+    /// Set the trading session schedule. See [`TradingSession::validate`].
+    #[must_use]
+    pub fn with_trading_session(mut self, session: TradingSession) -> Self {
+        self.trading_session = Some(session);
+        self
+    }
+
+    /// Set the circuit breaker. See [`CircuitBreakerConfig::validate`].
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Set the speed bump. See [`SpeedBump::validate`].
+    #[must_use]
+    pub fn with_speed_bump(mut self, speed_bump: SpeedBump) -> Self {
+        self.speed_bump = Some(speed_bump);
+        self
+    }
+
+    /// Set the reserve fill limit. Must be non-zero.
+    #[must_use]
+    pub fn with_reserve_fill_limit(mut self, limit: u64) -> Self {
+        self.reserve_fill_limit = Some(limit);
+        self
+    }
+
+    /// Check the parameters for consistency.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when the tick or
+    /// lot size, the reference price age or the reserve fill limit is
+    /// zero, a minimum order size or notional exceeds its
+    /// maximum, the taker fee is negative, or the market protection fails
+    /// [`MarketProtection::validate`], the trading session fails
+    /// [`TradingSession::validate`], the circuit breaker fails
+    /// [`CircuitBreakerConfig::validate`] or the speed bump fails
+    /// [`SpeedBump::validate`].
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        if self.tick_size == Some(0) {
+            return Err(invalid("tick size must be non-zero"));
+        }
+        if self.lot_size == Some(0) {
+            return Err(invalid("lot size must be non-zero"));
+        }
+        if self.reference_price_max_age_ms == Some(0) {
+            return Err(invalid("reference price max age must be non-zero"));
+        }
+        if self.reserve_fill_limit == Some(0) {
+            return Err(invalid("reserve fill limit must be non-zero"));
+        }
+        if let (Some(min), Some(max)) = (self.min_order_size, self.max_order_size)
+            && min > max
+        {
+            return Err(invalid(format!(
+                "minimum order size {min} exceeds maximum {max}"
+            )));
+        }
+        if let (Some(min), Some(max)) = (self.min_order_notional, self.max_order_notional)
+            && min > max
+        {
+            return Err(invalid(format!(
+                "minimum order notional {min} exceeds maximum {max}"
+            )));
+        }
+        if let Some(fees) = self.fee_schedule
+            && fees.taker_fee_bps < 0
+        {
+            return Err(invalid(format!(
+                "taker fee {} bps must not be negative",
+                fees.taker_fee_bps
+            )));
+        }
+        if let Some(protection) = self.market_protection {
+            protection.validate()?;
+        }
+        if let Some(session) = self.trading_session {
+            session.validate()?;
+        }
+        if let Some(breaker) = self.circuit_breaker {
+            breaker.validate()?;
+        }
+        if let Some(speed_bump) = self.speed_bump {
+            speed_bump.validate()?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: impl Into<String>) -> OrderBookError {
+    OrderBookError::InvalidConfiguration {
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::risk::ReferencePriceSource;
+
+    #[test]
+    fn default_disables_every_check() {
+        let config = BookConfig::new();
+        assert_eq!(config, BookConfig::default());
+        assert!(config.validate().is_ok());
+        assert!(config.tick_size.is_none() && config.risk.is_none());
+    }
+
+    #[test]
+    fn validate_rejects_inconsistent_parameters() {
+        let rejected = [
+            BookConfig::new().with_tick_size(0),
+            BookConfig::new().with_lot_size(0),
+            BookConfig::new().with_order_size_range(Some(10), Some(5)),
+            BookConfig::new().with_order_notional_range(Some(10), Some(5)),
+            BookConfig::new().with_fee_schedule(FeeSchedule::new(1, -1)),
+            BookConfig::new().with_market_protection(MarketProtection::new()),
+            BookConfig::new().with_trading_session(TradingSession::new(0, 0)),
+            BookConfig::new().with_circuit_breaker(CircuitBreakerConfig::new(100, 0, 1)),
+        ];
+        for config in rejected {
+            assert!(
+                matches!(
+                    config.validate(),
+                    Err(OrderBookError::InvalidConfiguration { .. })
+                ),
+                "{config:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn serde_roundtrip_preserves_every_field() {
+        let config = BookConfig::new()
+            .with_tick_size(5)
+            .with_lot_size(2)
+            .with_order_size_range(Some(2), None)
+            .with_order_notional_range(None, Some(1_000_000))
+            .with_fee_schedule(FeeSchedule::new(-1, 3))
+            .with_risk(RiskConfig::new().with_price_band_ticks(8, ReferencePriceSource::Mid))
+            .with_market_protection(MarketProtection::new().with_max_slippage_bps(50));
+        let json = serde_json::to_string(&config).expect("serialize");
+        let decoded: BookConfig = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded, config);
+    }
+}
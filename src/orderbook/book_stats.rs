@@ -0,0 +1,297 @@
+//! Cumulative per-book activity counters.
+//!
+//! Every book keeps a set of relaxed atomic counters — orders added,
+//! cancelled and filled, trades, traded volume and price range, rejects by
+//! [`RejectReason`] — updated on the same single emission points as the
+//! operational [`metrics`](crate::orderbook::metrics) hooks, but available
+//! without a recorder and queryable per book through
+//! [`OrderBook::stats`](crate::OrderBook::stats).
+//!
+//! The counters are operational only: they never influence matching, are
+//! not part of snapshots, and measure uptime with the process monotonic
+//! clock rather than the book's injected [`Clock`](crate::Clock).
+//! [`OrderBook::reset_stats`](crate::OrderBook::reset_stats) zeroes them
+//! and restarts the uptime.
+
+use super::reject_reason::RejectReason;
+use crossbeam::atomic::AtomicCell;
+use dashmap::DashMap;
+use pricelevel::MatchResult;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Reject codes counted in a fixed slot; higher caller-supplied
+/// [`RejectReason::Other`] codes are counted in a map.
+const REJECT_SLOTS: usize = 64;
+
+/// Point-in-time copy of a book's cumulative counters, as returned by
+/// [`OrderBook::stats`](crate::OrderBook::stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookStats {
+    /// Orders admitted to matching after passing every pre-trade check
+    pub orders_added: u64,
+    /// Orders that ended cancelled, for any [`CancelReason`](crate::orderbook::order_state::CancelReason)
+    pub orders_cancelled: u64,
+    /// Orders, maker or taker, that were completely filled
+    pub orders_filled: u64,
+    /// Trades executed
+    pub trades: u64,
+    /// Quantity traded
+    pub volume: u64,
+    /// Sum of price times quantity over every trade
+    pub notional_volume: u128,
+    /// Highest trade price, `None` before the first trade
+    pub highest_trade_price: Option<u128>,
+    /// Lowest trade price, `None` before the first trade
+    pub lowest_trade_price: Option<u128>,
+    /// Iceberg and reserve orders refreshed from their hidden quantity,
+    /// one per [`ReplenishmentEvent`](crate::orderbook::replenishment::ReplenishmentEvent)
+    pub replenishments: u64,
+    /// Rejected submissions
+    pub rejects_total: u64,
+    /// Rejected submissions per reason, in ascending wire code, omitting
+    /// reasons never seen
+    pub rejects: Vec<(RejectReason, u64)>,
+    /// Milliseconds since the book was created or its stats last reset
+    pub uptime_ms: u64,
+}
+
+impl BookStats {
+    /// Rejects recorded for `reason`.
+    #[must_use]
+    pub fn rejects_for(&self, reason: RejectReason) -> u64 {
+        self.rejects
+            .iter()
+            .find(|(seen, _)| *seen == reason)
+            .map_or(0, |(_, count)| *count)
+    }
+}
+
+/// Live counters behind [`BookStats`].
+#[derive(Debug)]
+pub(crate) struct BookCounters {
+    orders_added: AtomicU64,
+    orders_cancelled: AtomicU64,
+    orders_filled: AtomicU64,
+    trades: AtomicU64,
+    volume: AtomicU64,
+    notional_volume: AtomicCell<u128>,
+    highest_trade_price: AtomicCell<u128>,
+    lowest_trade_price: AtomicCell<u128>,
+    replenishments: AtomicU64,
+    rejects: [AtomicU64; REJECT_SLOTS],
+    other_rejects: DashMap<u16, u64>,
+    started_at: AtomicCell<Instant>,
+}
+
+impl Default for BookCounters {
+    fn default() -> Self {
+        Self {
+            orders_added: AtomicU64::new(0),
+            orders_cancelled: AtomicU64::new(0),
+            orders_filled: AtomicU64::new(0),
+            trades: AtomicU64::new(0),
+            volume: AtomicU64::new(0),
+            notional_volume: AtomicCell::new(0),
+            highest_trade_price: AtomicCell::new(0),
+            lowest_trade_price: AtomicCell::new(u128::MAX),
+            replenishments: AtomicU64::new(0),
+            rejects: std::array::from_fn(|_| AtomicU64::new(0)),
+            other_rejects: DashMap::new(),
+            started_at: AtomicCell::new(Instant::now()),
+        }
+    }
+}
+
+impl BookCounters {
+    #[inline]
+    pub(crate) fn record_added(&self) {
+        self.orders_added.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_cancelled(&self) {
+        self.orders_cancelled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_filled(&self) {
+        self.orders_filled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_replenishment(&self) {
+        self.replenishments.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_reject(&self, reason: RejectReason) {
+        let code = reason.as_u16();
+        match self.rejects.get(usize::from(code)) {
+            Some(slot) => {
+                slot.fetch_add(1, Ordering::Relaxed);
+            }
+            None => *self.other_rejects.entry(code).or_insert(0) += 1,
+        }
+    }
+
+    /// Count every trade in `match_result`.
+    pub(crate) fn record_trades(&self, match_result: &MatchResult) {
+        let trades = match_result.trades().as_vec();
+        if trades.is_empty() {
+            return;
+        }
+        let mut volume = 0u64;
+        let mut notional = 0u128;
+        let mut high = 0u128;
+        let mut low = u128::MAX;
+        for trade in trades {
+            let price = trade.price().as_u128();
+            let quantity = trade.quantity().as_u64();
+            volume = volume.saturating_add(quantity);
+            notional = notional.saturating_add(price.saturating_mul(u128::from(quantity)));
+            high = high.max(price);
+            low = low.min(price);
+        }
+        self.trades
+            .fetch_add(trades.len() as u64, Ordering::Relaxed);
+        self.volume.fetch_add(volume, Ordering::Relaxed);
+        self.notional_volume.fetch_add(notional);
+        self.highest_trade_price.fetch_max(high);
+        self.lowest_trade_price.fetch_min(low);
+    }
+
+    /// Take a busted fill back out of the trade, volume and notional
+    /// counters. The traded price range is left as it was.
+    pub(crate) fn record_bust(&self, price: u128, quantity: u64) {
+        let _ = self
+            .trades
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                Some(n.saturating_sub(1))
+            });
+        self.take_volume(price, quantity);
+    }
+
+    /// Restate a corrected fill: swap its old price and quantity for the
+    /// new ones in the volume and notional counters, and widen the traded
+    /// price range to the new price.
+    pub(crate) fn record_correction(&self, old: (u128, u64), new: (u128, u64)) {
+        self.take_volume(old.0, old.1);
+        let (price, quantity) = new;
+        self.volume.fetch_add(quantity, Ordering::Relaxed);
+        self.notional_volume
+            .fetch_add(price.saturating_mul(u128::from(quantity)));
+        self.highest_trade_price.fetch_max(price);
+        self.lowest_trade_price.fetch_min(price);
+    }
+
+    fn take_volume(&self, price: u128, quantity: u64) {
+        let notional = price.saturating_mul(u128::from(quantity));
+        let _ = self
+            .volume
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+                Some(v.saturating_sub(quantity))
+            });
+        let _ = self
+            .notional_volume
+            .fetch_update(|v| Some(v.saturating_sub(notional)));
+    }
+
+    /// Zero every counter and restart the uptime.
+    pub(crate) fn reset(&self) {
+        self.orders_added.store(0, Ordering::Relaxed);
+        self.orders_cancelled.store(0, Ordering::Relaxed);
+        self.orders_filled.store(0, Ordering::Relaxed);
+        self.trades.store(0, Ordering::Relaxed);
+        self.volume.store(0, Ordering::Relaxed);
+        self.notional_volume.store(0);
+        self.highest_trade_price.store(0);
+        self.lowest_trade_price.store(u128::MAX);
+        self.replenishments.store(0, Ordering::Relaxed);
+        for slot in &self.rejects {
+            slot.store(0, Ordering::Relaxed);
+        }
+        self.other_rejects.clear();
+        self.started_at.store(Instant::now());
+    }
+
+    pub(crate) fn snapshot(&self) -> BookStats {
+        let mut rejects: Vec<(RejectReason, u64)> = self
+            .rejects
+            .iter()
+            .enumerate()
+            .filter_map(|(code, slot)| {
+                let count = slot.load(Ordering::Relaxed);
+                (count > 0).then(|| (RejectReason::from_u16(code as u16), count))
+            })
+            .collect();
+        let mut other: Vec<(RejectReason, u64)> = self
+            .other_rejects
+            .iter()
+            .map(|entry| (RejectReason::from_u16(*entry.key()), *entry.value()))
+            .collect();
+        other.sort_by_key(|(reason, _)| reason.as_u16());
+        rejects.extend(other);
+
+        let trades = self.trades.load(Ordering::Relaxed);
+        let traded = |price: u128| (trades > 0).then_some(price);
+        BookStats {
+            orders_added: self.orders_added.load(Ordering::Relaxed),
+            orders_cancelled: self.orders_cancelled.load(Ordering::Relaxed),
+            orders_filled: self.orders_filled.load(Ordering::Relaxed),
+            trades,
+            volume: self.volume.load(Ordering::Relaxed),
+            notional_volume: self.notional_volume.load(),
+            highest_trade_price: traded(self.highest_trade_price.load()),
+            lowest_trade_price: traded(self.lowest_trade_price.load()),
+            replenishments: self.replenishments.load(Ordering::Relaxed),
+            rejects_total: rejects.iter().map(|(_, count)| count).sum(),
+            rejects,
+            uptime_ms: u64::try_from(self.started_at.load().elapsed().as_millis())
+                .unwrap_or(u64::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_are_bucketed_by_code() {
+        let counters = BookCounters::default();
+        counters.record_reject(RejectReason::RateLimited);
+        counters.record_reject(RejectReason::RateLimited);
+        counters.record_reject(RejectReason::KillSwitchActive);
+        counters.record_reject(RejectReason::Other(9_000));
+        counters.record_reject(RejectReason::Other(50));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.rejects_total, 5);
+        assert_eq!(
+            stats.rejects,
+            vec![
+                (RejectReason::KillSwitchActive, 1),
+                (RejectReason::RateLimited, 2),
+                (RejectReason::Other(50), 1),
+                (RejectReason::Other(9_000), 1),
+            ]
+        );
+        assert_eq!(stats.rejects_for(RejectReason::RateLimited), 2);
+        assert_eq!(stats.rejects_for(RejectReason::UserBlocked), 0);
+
+        counters.reset();
+        let stats = counters.snapshot();
+        assert_eq!(stats.rejects_total, 0);
+        assert!(stats.rejects.is_empty());
+    }
+
+    #[test]
+    fn test_trade_price_range_is_none_before_first_trade() {
+        let stats = BookCounters::default().snapshot();
+        assert_eq!(stats.trades, 0);
+        assert_eq!(stats.highest_trade_price, None);
+        assert_eq!(stats.lowest_trade_price, None);
+    }
+}
@@ -0,0 +1,506 @@
+//! Fluent construction of a fully configured [`OrderBook`].
+//!
+//! The `with_*` constructors each cover one option and do not compose;
+//! [`OrderBookBuilder`] collects every construction-time setting in one
+//! chain and checks them together in [`OrderBookBuilder::build`], so an
+//! inconsistent configuration (a zero tick size, a minimum order size
+//! above the maximum, ...) is rejected before the book accepts flow.
+//!
+//! Matching is always price-time priority; there is no alternative
+//! matching algorithm to select.
+
+use super::book::{OrderBook, index_shard_amount};
+use super::book_change_event::PriceLevelChangedListener;
+use super::book_config::BookConfig;
+use super::circuit_breaker::CircuitBreakerConfig;
+use super::clock::Clock;
+use super::error::OrderBookError;
+use super::fees::{FeeSchedule, TieredFeeSchedule};
+use super::level_map::PriceLevelMap;
+use super::market_protection::MarketProtection;
+use super::order_state::OrderStateTracker;
+use super::post_only::PostOnlyMode;
+use super::price_scale::PriceScale;
+use super::rate_limit::RateLimitConfig;
+use super::risk::RiskConfig;
+use super::session::TradingSession;
+use super::speed_bump::SpeedBump;
+use super::stp::STPMode;
+use super::trade::TradeListener;
+use super::trade_amendment::ExecutionHistory;
+use dashmap::DashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Builder for an [`OrderBook`] configured in one fluent chain.
+///
+/// Created by [`OrderBook::builder`]. Every setting is optional; an
+/// unset option keeps the default of [`OrderBook::new`].
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{FeeSchedule, OrderBook, STPMode};
+///
+/// let book = OrderBook::<()>::builder("BTC/USD")
+///     .tick_size(10)
+///     .lot_size(5)
+///     .order_size_range(Some(5), Some(1_000))
+///     .stp_mode(STPMode::CancelTaker)
+///     .fee_schedule(FeeSchedule::new(-2, 5))
+///     .market_close_timestamp(1_900_000_000_000)
+///     .build()
+///     .expect("valid configuration");
+///
+/// assert_eq!(book.tick_size(), Some(10));
+/// assert_eq!(book.stp_mode(), STPMode::CancelTaker);
+/// ```
+pub struct OrderBookBuilder<T> {
+    symbol: String,
+    clock: Option<Arc<dyn Clock>>,
+    trade_id_namespace: Option<Uuid>,
+    order_index: Option<(usize, Option<usize>)>,
+    user_capacity: Option<usize>,
+    trade_listener: Option<TradeListener>,
+    price_level_listener: Option<PriceLevelChangedListener>,
+    tick_size: Option<u128>,
+    lot_size: Option<u64>,
+    min_order_size: Option<u64>,
+    max_order_size: Option<u64>,
+    min_order_notional: Option<u128>,
+    max_order_notional: Option<u128>,
+    stp_mode: STPMode,
+    trade_level_aggregation: bool,
+    fee_schedule: Option<FeeSchedule>,
+    price_scale: PriceScale,
+    risk_config: Option<RiskConfig>,
+    market_protection: Option<MarketProtection>,
+    post_only_mode: PostOnlyMode,
+    reference_price_max_age_ms: Option<u64>,
+    trading_session: Option<TradingSession>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    speed_bump: Option<SpeedBump>,
+    reserve_fill_limit: Option<u64>,
+    rate_limit_config: Option<RateLimitConfig>,
+    duplicate_order_id_window: Option<u64>,
+    market_close_timestamp: Option<u64>,
+    level_pool_max_size: Option<usize>,
+    level_shards: Option<(u128, usize)>,
+    level_capacity: Option<usize>,
+    order_state_tracker: Option<OrderStateTracker>,
+    execution_history: Option<ExecutionHistory>,
+    tiered_fee_schedule: Option<Arc<TieredFeeSchedule>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> OrderBookBuilder<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Start a builder for `symbol` with every option unset.
+    #[must_use]
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            clock: None,
+            trade_id_namespace: None,
+            order_index: None,
+            user_capacity: None,
+            trade_listener: None,
+            price_level_listener: None,
+            tick_size: None,
+            lot_size: None,
+            min_order_size: None,
+            max_order_size: None,
+            min_order_notional: None,
+            max_order_notional: None,
+            stp_mode: STPMode::None,
+            trade_level_aggregation: false,
+            fee_schedule: None,
+            price_scale: PriceScale::default(),
+            risk_config: None,
+            market_protection: None,
+            post_only_mode: PostOnlyMode::Reject,
+            reference_price_max_age_ms: None,
+            trading_session: None,
+            circuit_breaker: None,
+            speed_bump: None,
+            reserve_fill_limit: None,
+            rate_limit_config: None,
+            duplicate_order_id_window: None,
+            market_close_timestamp: None,
+            level_pool_max_size: None,
+            level_shards: None,
+            level_capacity: None,
+            order_state_tracker: None,
+            execution_history: None,
+            tiered_fee_schedule: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Timestamp source (see [`OrderBook::with_clock`]).
+    #[must_use]
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Trade-ID namespace (see [`OrderBook::set_trade_id_namespace`]).
+    #[must_use]
+    pub fn trade_id_namespace(mut self, namespace: Uuid) -> Self {
+        self.trade_id_namespace = Some(namespace);
+        self
+    }
+
+    /// Pre-size the order indexes (see [`OrderBook::with_order_capacity`]).
+    #[must_use]
+    pub fn order_capacity(mut self, capacity: usize) -> Self {
+        let shards = self.order_index.and_then(|(_, shards)| shards);
+        self.order_index = Some((capacity, shards));
+        self
+    }
+
+    /// Pre-size the per-user order index for `users` distinct users with
+    /// resting orders.
+    #[must_use]
+    pub fn user_capacity(mut self, users: usize) -> Self {
+        self.user_capacity = Some(users);
+        self
+    }
+
+    /// Pre-size every index for `levels_hint` price levels per side and
+    /// `orders_hint` resting orders (see [`OrderBook::with_capacity`]).
+    #[must_use]
+    pub fn capacity(mut self, levels_hint: usize, orders_hint: usize) -> Self {
+        self.level_capacity = Some(levels_hint);
+        self.order_capacity(orders_hint)
+    }
+
+    /// Shard count of the order indexes, rounded up to a power of two
+    /// between 2 and 65 536 (see [`OrderBook::with_order_index`]).
+    #[must_use]
+    pub fn order_index_shards(mut self, shard_count: usize) -> Self {
+        let capacity = self.order_index.map_or(0, |(capacity, _)| capacity);
+        self.order_index = Some((capacity, Some(shard_count)));
+        self
+    }
+
+    /// Split each side's price levels into `shard_count` price-range
+    /// shards of `shard_width` price units (see
+    /// [`OrderBook::with_level_shards`]).
+    #[must_use]
+    pub fn level_shards(mut self, shard_width: u128, shard_count: usize) -> Self {
+        self.level_shards = Some((shard_width, shard_count));
+        self
+    }
+
+    /// Listener invoked with every trade.
+    #[must_use]
+    pub fn trade_listener(mut self, listener: TradeListener) -> Self {
+        self.trade_listener = Some(listener);
+        self
+    }
+
+    /// Listener invoked on every price level change.
+    #[must_use]
+    pub fn price_level_listener(mut self, listener: PriceLevelChangedListener) -> Self {
+        self.price_level_listener = Some(listener);
+        self
+    }
+
+    /// Minimum price increment. Must be non-zero.
+    #[must_use]
+    pub fn tick_size(mut self, tick_size: u128) -> Self {
+        self.tick_size = Some(tick_size);
+        self
+    }
+
+    /// Minimum quantity increment. Must be non-zero.
+    #[must_use]
+    pub fn lot_size(mut self, lot_size: u64) -> Self {
+        self.lot_size = Some(lot_size);
+        self
+    }
+
+    /// Allowed order quantity range; `None` leaves a bound unchecked.
+    #[must_use]
+    pub fn order_size_range(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_order_size = min;
+        self.max_order_size = max;
+        self
+    }
+
+    /// Allowed order notional range; `None` leaves a bound unchecked.
+    #[must_use]
+    pub fn order_notional_range(mut self, min: Option<u128>, max: Option<u128>) -> Self {
+        self.min_order_notional = min;
+        self.max_order_notional = max;
+        self
+    }
+
+    /// Self-trade prevention mode.
+    #[must_use]
+    pub fn stp_mode(mut self, mode: STPMode) -> Self {
+        self.stp_mode = mode;
+        self
+    }
+
+    /// Attach per-level aggregates to every emitted trade result; see
+    /// [`OrderBook::set_trade_level_aggregation`].
+    #[must_use]
+    pub fn trade_level_aggregation(mut self, enabled: bool) -> Self {
+        self.trade_level_aggregation = enabled;
+        self
+    }
+
+    /// Maker/taker fee schedule. The taker fee must not be negative.
+    #[must_use]
+    pub fn fee_schedule(mut self, fee_schedule: FeeSchedule) -> Self {
+        self.fee_schedule = Some(fee_schedule);
+        self
+    }
+
+    /// Decimal scale of raw prices and quantities.
+    #[must_use]
+    pub fn price_scale(mut self, price_scale: PriceScale) -> Self {
+        self.price_scale = price_scale;
+        self
+    }
+
+    /// Pre-trade risk limits (see [`OrderBook::set_risk_config`]).
+    #[must_use]
+    pub fn risk_config(mut self, config: RiskConfig) -> Self {
+        self.risk_config = Some(config);
+        self
+    }
+
+    /// Sweep limits for market orders (see [`MarketProtection`]).
+    #[must_use]
+    pub fn market_protection(mut self, protection: MarketProtection) -> Self {
+        self.market_protection = Some(protection);
+        self
+    }
+
+    /// Handling of crossing post-only orders (see [`PostOnlyMode`]).
+    #[must_use]
+    pub fn post_only_mode(mut self, mode: PostOnlyMode) -> Self {
+        self.post_only_mode = mode;
+        self
+    }
+
+    /// Age past which an external reference price is stale (see
+    /// [`OrderBook::set_reference_price`]).
+    #[must_use]
+    pub fn reference_price_max_age_ms(mut self, max_age_ms: u64) -> Self {
+        self.reference_price_max_age_ms = Some(max_age_ms);
+        self
+    }
+
+    /// Daily trading session schedule (see [`TradingSession`]).
+    #[must_use]
+    pub fn trading_session(mut self, session: TradingSession) -> Self {
+        self.trading_session = Some(session);
+        self
+    }
+
+    /// Price velocity circuit breaker (see [`CircuitBreakerConfig`]).
+    #[must_use]
+    pub fn circuit_breaker(mut self, breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Delay applied to aggressive orders (see [`SpeedBump`]).
+    #[must_use]
+    pub fn speed_bump(mut self, speed_bump: SpeedBump) -> Self {
+        self.speed_bump = Some(speed_bump);
+        self
+    }
+
+    /// Most quantity one incoming order may take from a single reserve
+    /// order before it rotates (see [`BookConfig::reserve_fill_limit`]).
+    #[must_use]
+    pub fn reserve_fill_limit(mut self, limit: u64) -> Self {
+        self.reserve_fill_limit = Some(limit);
+        self
+    }
+
+    /// Token-bucket rate limits (see [`OrderBook::set_rate_limit_config`]).
+    #[must_use]
+    pub fn rate_limit_config(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit_config = Some(config);
+        self
+    }
+
+    /// Duplicate order id window in milliseconds (see
+    /// [`OrderBook::set_duplicate_order_id_window`]).
+    #[must_use]
+    pub fn duplicate_order_id_window(mut self, window_ms: u64) -> Self {
+        self.duplicate_order_id_window = Some(window_ms);
+        self
+    }
+
+    /// Market close timestamp in milliseconds, used to expire `Day`
+    /// orders. Must be non-zero.
+    #[must_use]
+    pub fn market_close_timestamp(mut self, timestamp: u64) -> Self {
+        self.market_close_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Maximum number of emptied price levels kept for reuse.
+    #[must_use]
+    pub fn level_pool_max_size(mut self, max_size: usize) -> Self {
+        self.level_pool_max_size = Some(max_size);
+        self
+    }
+
+    /// Order lifecycle tracker.
+    #[must_use]
+    pub fn order_state_tracker(mut self, tracker: OrderStateTracker) -> Self {
+        self.order_state_tracker = Some(tracker);
+        self
+    }
+
+    /// History of emitted fills for trade busts and corrections (see
+    /// [`OrderBook::set_execution_history`]).
+    #[must_use]
+    pub fn execution_history(mut self, history: ExecutionHistory) -> Self {
+        self.execution_history = Some(history);
+        self
+    }
+
+    /// Volume-tiered fee schedule, charged in place of
+    /// [`Self::fee_schedule`] (see [`OrderBook::set_tiered_fee_schedule`]).
+    #[must_use]
+    pub fn tiered_fee_schedule(mut self, schedule: Arc<TieredFeeSchedule>) -> Self {
+        self.tiered_fee_schedule = Some(schedule);
+        self
+    }
+
+    /// Validate the configuration and build the book.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when the symbol is
+    /// empty, the tick or lot size is zero, a minimum order size or
+    /// notional exceeds its maximum, the taker fee is negative, or the
+    /// market close timestamp is zero.
+    pub fn build(self) -> Result<OrderBook<T>, OrderBookError> {
+        self.validate()?;
+        let config = self.book_config();
+
+        let mut book = match self.clock {
+            Some(clock) => OrderBook::with_clock(&self.symbol, clock),
+            None => OrderBook::new(&self.symbol),
+        };
+        if let Some(namespace) = self.trade_id_namespace {
+            book.set_trade_id_namespace(namespace);
+        }
+        let users = self.user_capacity.unwrap_or(0);
+        match self.order_index {
+            Some((capacity, Some(shard_count))) => {
+                let shard_count = index_shard_amount(shard_count);
+                book.order_locations =
+                    DashMap::with_capacity_and_shard_amount(capacity, shard_count);
+                book.user_orders = DashMap::with_capacity_and_shard_amount(users, shard_count);
+                book.order_owners = DashMap::with_capacity_and_shard_amount(capacity, shard_count);
+            }
+            Some((capacity, None)) => {
+                book.order_locations = DashMap::with_capacity(capacity);
+                book.user_orders = DashMap::with_capacity(users);
+                book.order_owners = DashMap::with_capacity(capacity);
+            }
+            None => book.user_orders = DashMap::with_capacity(users),
+        }
+        if let Some(levels_hint) = self.level_capacity {
+            if let Some((capacity, _)) = self.order_index
+                && size_of::<T>() != 0
+            {
+                book.extra_fields = DashMap::with_capacity(capacity);
+            }
+            book.presize_levels(levels_hint);
+        }
+        if let Some((shard_width, shard_count)) = self.level_shards {
+            book.bids = PriceLevelMap::sharded(shard_width, shard_count);
+            book.asks = PriceLevelMap::sharded(shard_width, shard_count);
+        }
+
+        book.trade_listener.replace(self.trade_listener);
+        book.price_level_changed_listener
+            .replace(self.price_level_listener);
+        book.store_config(config);
+        book.stp_mode.store(self.stp_mode);
+        book.set_trade_level_aggregation(self.trade_level_aggregation);
+        book.price_scale = self.price_scale;
+        book.order_state_tracker = self.order_state_tracker;
+        book.execution_history = self.execution_history;
+        book.tiered_fee_schedule = self.tiered_fee_schedule;
+
+        if let Some(config) = self.rate_limit_config {
+            book.set_rate_limit_config(config);
+        }
+        if let Some(window_ms) = self.duplicate_order_id_window {
+            book.set_duplicate_order_id_window(window_ms);
+        }
+        if let Some(max_size) = self.level_pool_max_size {
+            book.set_level_pool_max_size(max_size);
+        }
+        if let Some(timestamp) = self.market_close_timestamp {
+            book.set_market_close_timestamp(timestamp);
+        }
+
+        Ok(book)
+    }
+
+    /// The reloadable parameters collected so far.
+    fn book_config(&self) -> BookConfig {
+        BookConfig {
+            tick_size: self.tick_size,
+            lot_size: self.lot_size,
+            min_order_size: self.min_order_size,
+            max_order_size: self.max_order_size,
+            min_order_notional: self.min_order_notional,
+            max_order_notional: self.max_order_notional,
+            fee_schedule: self.fee_schedule,
+            risk: self.risk_config,
+            market_protection: self.market_protection,
+            post_only_mode: self.post_only_mode,
+            reference_price_max_age_ms: self.reference_price_max_age_ms,
+            trading_session: self.trading_session,
+            circuit_breaker: self.circuit_breaker,
+            speed_bump: self.speed_bump,
+            reserve_fill_limit: self.reserve_fill_limit,
+        }
+    }
+
+    fn validate(&self) -> Result<(), OrderBookError> {
+        if self.symbol.is_empty() {
+            return Err(invalid("symbol must not be empty"));
+        }
+        self.book_config().validate()?;
+        if self.market_close_timestamp == Some(0) {
+            return Err(invalid("market close timestamp must be non-zero"));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: impl Into<String>) -> OrderBookError {
+    OrderBookError::InvalidConfiguration {
+        message: message.into(),
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Start an [`OrderBookBuilder`] for `symbol`.
+    #[must_use]
+    pub fn builder(symbol: &str) -> OrderBookBuilder<T> {
+        OrderBookBuilder::new(symbol)
+    }
+}
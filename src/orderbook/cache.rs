@@ -4,10 +4,194 @@
    Date: 15/7/25
 ******************************************************************************/
 
-use crossbeam::atomic::AtomicCell;
+use crate::orderbook::snapshot::LevelAggregates;
+use crate::orderbook::sync::{
+    AtomicBool, AtomicCell, AtomicU64, Ordering, RwLock, fence, thread_local,
+};
+use crossbeam::utils::CachePadded;
+use pricelevel::Side;
 use serde::ser::SerializeStruct;
-use serde::{Serialize, Serializer};
-use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize, Serializer};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Low bits of the update state counting mutations in flight; the update
+/// sequence sits above them.
+const WRITER_BITS: u32 = 16;
+const ONE_WRITER: u64 = 1;
+const ONE_SEQ: u64 = 1 << WRITER_BITS;
+
+/// Validation attempts a reader makes without yielding, then with
+/// [`std::thread::yield_now`] between them, before it gives up and returns
+/// an unvalidated read.
+const SPIN_ATTEMPTS: usize = 4;
+const MAX_ATTEMPTS: usize = 64;
+
+thread_local! {
+    /// Caches whose book the current thread is mutating, by address. A
+    /// read from inside a mutation (a listener, a peg reprice) can never
+    /// see that book stable, so it is not retried.
+    // Loom's `thread_local!` has no `const` initializer form.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static MUTATING: RefCell<Vec<usize>> = RefCell::new(Vec::new());
+}
+
+/// Best bid and ask with the total quantity resting at each, read as one
+/// consistent unit by [`OrderBook::top_of_book`](crate::OrderBook::top_of_book).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopOfBook {
+    /// Best (highest) bid price, if the bid side is non-empty
+    pub best_bid: Option<u128>,
+    /// Best (lowest) ask price, if the ask side is non-empty
+    pub best_ask: Option<u128>,
+    /// Total quantity resting at the best bid (`0` if none)
+    pub bid_quantity: u64,
+    /// Total quantity resting at the best ask (`0` if none)
+    pub ask_quantity: u64,
+    /// Number of cache invalidations (book mutations) seen before this
+    /// value was computed. Equal values come from the same book state.
+    pub update_seq: u64,
+}
+
+impl TopOfBook {
+    /// Midpoint of the best bid and ask, if both sides are present.
+    #[must_use]
+    pub fn mid_price(&self) -> Option<f64> {
+        match (self.best_bid, self.best_ask) {
+            (Some(bid), Some(ask)) => Some((bid as f64 + ask as f64) / 2.0),
+            _ => None,
+        }
+    }
+
+    /// Integer midpoint of the best bid and ask, rounded down, if both
+    /// sides are present. Never overflows, even at extreme prices.
+    #[must_use]
+    pub fn midpoint(&self) -> Option<u128> {
+        Some(self.best_bid?.midpoint(self.best_ask?))
+    }
+
+    /// Best ask minus best bid, if both sides are present.
+    #[must_use]
+    pub fn spread(&self) -> Option<u128> {
+        match (self.best_bid, self.best_ask) {
+            (Some(bid), Some(ask)) => Some(ask.saturating_sub(bid)),
+            _ => None,
+        }
+    }
+
+    /// Quantity-weighted micro price
+    /// `(ask × bid_qty + bid × ask_qty) / (bid_qty + ask_qty)`, if both sides
+    /// are present with non-zero total quantity.
+    #[must_use]
+    pub fn micro_price(&self) -> Option<f64> {
+        let (bid, ask) = (self.best_bid?, self.best_ask?);
+        let total = self.bid_quantity.saturating_add(self.ask_quantity);
+        if total == 0 {
+            return None;
+        }
+        let numerator =
+            (ask as f64 * self.bid_quantity as f64) + (bid as f64 * self.ask_quantity as f64);
+        Some(numerator / total as f64)
+    }
+}
+
+/// Seqlock-protected storage of the last published [`TopOfBook`].
+///
+/// `seq` is even while the slot is stable and odd while a writer is
+/// storing it; `0` means nothing has been published. Prices are split
+/// into two `u64` halves so every field is a plain atomic word.
+#[derive(Debug, Default)]
+struct TopOfBookSlot {
+    seq: AtomicU64,
+    bid_hi: AtomicU64,
+    bid_lo: AtomicU64,
+    ask_hi: AtomicU64,
+    ask_lo: AtomicU64,
+    bid_quantity: AtomicU64,
+    ask_quantity: AtomicU64,
+    /// Bit 0: bid present. Bit 1: ask present.
+    flags: AtomicU64,
+    update_seq: AtomicU64,
+}
+
+const TOP_HAS_BID: u64 = 1;
+const TOP_HAS_ASK: u64 = 2;
+
+impl TopOfBookSlot {
+    /// Publish `top`. If another writer holds the slot the value is
+    /// dropped; the next reader simply recomputes.
+    fn publish(&self, top: &TopOfBook) {
+        let seq = self.seq.load(Ordering::Relaxed);
+        if seq % 2 == 1
+            || self
+                .seq
+                .compare_exchange(seq, seq + 1, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return;
+        }
+        // Order the odd `seq` before the field stores below.
+        fence(Ordering::Release);
+
+        let bid = top.best_bid.unwrap_or(0);
+        let ask = top.best_ask.unwrap_or(0);
+        let mut flags = 0;
+        if top.best_bid.is_some() {
+            flags |= TOP_HAS_BID;
+        }
+        if top.best_ask.is_some() {
+            flags |= TOP_HAS_ASK;
+        }
+        self.bid_hi.store((bid >> 64) as u64, Ordering::Relaxed);
+        self.bid_lo.store(bid as u64, Ordering::Relaxed);
+        self.ask_hi.store((ask >> 64) as u64, Ordering::Relaxed);
+        self.ask_lo.store(ask as u64, Ordering::Relaxed);
+        self.bid_quantity.store(top.bid_quantity, Ordering::Relaxed);
+        self.ask_quantity.store(top.ask_quantity, Ordering::Relaxed);
+        self.flags.store(flags, Ordering::Relaxed);
+        self.update_seq.store(top.update_seq, Ordering::Relaxed);
+
+        self.seq.store(seq + 2, Ordering::Release);
+    }
+
+    /// The published value, or `None` if nothing is published or a writer
+    /// raced with this read.
+    fn read(&self) -> Option<TopOfBook> {
+        let before = self.seq.load(Ordering::Acquire);
+        if before == 0 || before % 2 == 1 {
+            return None;
+        }
+        let bid = (u128::from(self.bid_hi.load(Ordering::Relaxed)) << 64)
+            | u128::from(self.bid_lo.load(Ordering::Relaxed));
+        let ask = (u128::from(self.ask_hi.load(Ordering::Relaxed)) << 64)
+            | u128::from(self.ask_lo.load(Ordering::Relaxed));
+        let bid_quantity = self.bid_quantity.load(Ordering::Relaxed);
+        let ask_quantity = self.ask_quantity.load(Ordering::Relaxed);
+        let flags = self.flags.load(Ordering::Relaxed);
+        let update_seq = self.update_seq.load(Ordering::Relaxed);
+        // Order the field loads above before the `seq` re-check.
+        fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) != before {
+            return None;
+        }
+        Some(TopOfBook {
+            best_bid: (flags & TOP_HAS_BID != 0).then_some(bid),
+            best_ask: (flags & TOP_HAS_ASK != 0).then_some(ask),
+            bid_quantity,
+            ask_quantity,
+            update_seq,
+        })
+    }
+}
+
+/// Cached best price of one side and whether it is trustworthy. Kept
+/// together so a cache hit touches a single cache line.
+#[derive(Debug, Default)]
+struct BestPriceSlot {
+    /// Meaningful only when `valid` is set.
+    price: AtomicCell<u128>,
+    valid: AtomicBool,
+}
 
 /// A best bid / ask fast-path cache for an [`OrderBook`](crate::OrderBook).
 ///
@@ -22,16 +206,43 @@ use std::sync::atomic::{AtomicBool, Ordering};
 /// to clear both sides, and a missing side is recomputed from the skiplist. Only
 /// non-empty sides are cached — an empty side leaves its flag clear and is
 /// recomputed (an O(1) skiplist probe) on the next read.
+///
+/// Alongside the per-side prices the cache holds one seqlock-published
+/// [`TopOfBook`] — both best prices and their quantities — which readers
+/// take as a single consistent unit, so derived metrics never pair a bid
+/// cached before a mutation with an ask cached after it. Every
+/// invalidation advances an update sequence; a published value is served
+/// only while the sequence it was computed at is still current.
+///
+/// Every mutation also marks its start with [`begin_mutation`](Self::begin_mutation):
+/// the update state counts mutations in flight alongside the sequence,
+/// like the odd phase of a seqlock. A reader that stamps its walk with
+/// [`update_seq`](Self::update_seq) and finds the book
+/// [stable](Self::is_stable_at) at that sequence afterwards overlapped no
+/// mutation at all, neither one that finished during the walk nor one
+/// still in progress; only such reads are published.
+///
+/// The same rule governs the cached top-N [`LevelAggregates`]. That view
+/// is double buffered: a rebuilt view is published by swapping an `Arc`,
+/// so readers still holding the previous buffer keep a consistent copy
+/// while the next one is built, and the lock is only held for the swap.
+///
+/// The bid slot, ask slot, update sequence and top-of-book slot each sit
+/// on their own cache line, so threads working different sides of the
+/// book do not invalidate each other's lines.
 #[derive(Debug, Default)]
 pub struct PriceLevelCache {
-    /// Cached best bid price. Meaningful only when `bid_valid` is set.
-    best_bid_price: AtomicCell<u128>,
-    /// Cached best ask price. Meaningful only when `ask_valid` is set.
-    best_ask_price: AtomicCell<u128>,
-    /// Whether `best_bid_price` currently holds a trustworthy value.
-    bid_valid: AtomicBool,
-    /// Whether `best_ask_price` currently holds a trustworthy value.
-    ask_valid: AtomicBool,
+    /// Cached best bid.
+    bid: CachePadded<BestPriceSlot>,
+    /// Cached best ask.
+    ask: CachePadded<BestPriceSlot>,
+    /// Number of invalidations so far, shifted above the number of
+    /// mutations in flight.
+    update_seq: CachePadded<AtomicU64>,
+    /// Last published top of book.
+    top: CachePadded<TopOfBookSlot>,
+    /// Last published level aggregates with the depth they were built at.
+    levels: RwLock<Option<(usize, Arc<LevelAggregates>)>>,
 }
 
 impl Serialize for PriceLevelCache {
@@ -40,10 +251,10 @@ impl Serialize for PriceLevelCache {
         S: Serializer,
     {
         let mut state = serializer.serialize_struct("PriceLevelCache", 4)?;
-        state.serialize_field("best_bid_price", &self.best_bid_price.load())?;
-        state.serialize_field("best_ask_price", &self.best_ask_price.load())?;
-        state.serialize_field("bid_valid", &self.bid_valid.load(Ordering::Relaxed))?;
-        state.serialize_field("ask_valid", &self.ask_valid.load(Ordering::Relaxed))?;
+        state.serialize_field("best_bid_price", &self.bid.price.load())?;
+        state.serialize_field("best_ask_price", &self.ask.price.load())?;
+        state.serialize_field("bid_valid", &self.bid.valid.load(Ordering::Relaxed))?;
+        state.serialize_field("ask_valid", &self.ask.valid.load(Ordering::Relaxed))?;
         state.end()
     }
 }
@@ -51,27 +262,180 @@ impl Serialize for PriceLevelCache {
 impl PriceLevelCache {
     /// Create an empty cache with both sides invalid.
     pub fn new() -> Self {
-        Self {
-            best_bid_price: AtomicCell::new(0),
-            best_ask_price: AtomicCell::new(0),
-            bid_valid: AtomicBool::new(false),
-            ask_valid: AtomicBool::new(false),
-        }
+        Self::default()
     }
 
-    /// Invalidate both sides. Called by every book mutation.
+    /// Invalidate both sides, the published top of book and the level
+    /// aggregates. Called by every book mutation, again after its last
+    /// write to the skip maps, so a value a concurrent reader computed
+    /// from a half-applied mutation is never served afterwards.
     pub fn invalidate(&self) {
-        self.bid_valid.store(false, Ordering::Relaxed);
-        self.ask_valid.store(false, Ordering::Relaxed);
+        // Advance the sequence before clearing the flags so a concurrent
+        // `publish_best_price` either sees the new sequence or has its
+        // flag cleared here. Clearing and publishing are both swaps, so
+        // the flag's read-modify-writes are totally ordered and whichever
+        // comes second synchronizes with the first (a `SeqCst` store is an
+        // `xchg` on x86 anyway).
+        self.update_seq.fetch_add(ONE_SEQ, Ordering::SeqCst);
+        self.bid.valid.swap(false, Ordering::SeqCst);
+        self.ask.valid.swap(false, Ordering::SeqCst);
+    }
+
+    /// Like [`Self::invalidate`] but keeps the other side's best price.
+    /// For a mutation known to touch only `side` of the book.
+    pub fn invalidate_side(&self, side: Side) {
+        self.update_seq.fetch_add(ONE_SEQ, Ordering::SeqCst);
+        self.slot(side).valid.swap(false, Ordering::SeqCst);
+    }
+
+    /// Publish the best price of `side` as computed by a writer at
+    /// `update_seq`, so readers hit the cache instead of probing the
+    /// skiplist. Retracted if the sequence has moved on, so a price from a
+    /// superseded book state is never left behind. `None` (an empty side)
+    /// leaves the slot invalid.
+    pub fn publish_best_price(&self, side: Side, best: Option<u128>, update_seq: u64) {
+        let Some(price) = best else {
+            return;
+        };
+        let slot = self.slot(side);
+        slot.price.store(price);
+        // A swap, not a store: if this follows an invalidation's clear, it
+        // synchronizes with it and the check below sees its sequence bump.
+        slot.valid.swap(true, Ordering::SeqCst);
+        if self.update_seq.load(Ordering::SeqCst) >> WRITER_BITS != update_seq {
+            slot.valid.store(false, Ordering::SeqCst);
+        }
+    }
+
+    fn slot(&self, side: Side) -> &BestPriceSlot {
+        match side {
+            Side::Buy => &self.bid,
+            Side::Sell => &self.ask,
+        }
+    }
+
+    /// Number of invalidations so far.
+    pub fn update_seq(&self) -> u64 {
+        self.update_seq.load(Ordering::Acquire) >> WRITER_BITS
+    }
+
+    /// Mark the start of a book mutation, before its first write to the
+    /// skip maps. Until the returned guard drops, no read is
+    /// [stable](Self::is_stable_at). The mutation still calls
+    /// [`Self::invalidate`] after its last write; one that ends up writing
+    /// nothing leaves the sequence, and so every published value, as is.
+    #[must_use = "the mutation ends when the guard drops"]
+    pub fn begin_mutation(&self) -> MutationGuard<'_> {
+        self.update_seq.fetch_add(ONE_WRITER, Ordering::SeqCst);
+        // Pairs with the acquire fence in `is_stable_at`: a reader that
+        // sees any write of this mutation also sees it in flight.
+        fence(Ordering::Release);
+        let address = self.address();
+        MUTATING.with(|mutating| mutating.borrow_mut().push(address));
+        MutationGuard { cache: self }
+    }
+
+    /// Whether no mutation has started since a read stamped with
+    /// `update_seq` began and none is in flight, so everything the read
+    /// saw comes from the book state at `update_seq`.
+    pub fn is_stable_at(&self, update_seq: u64) -> bool {
+        // Orders the caller's reads of the book before the check.
+        fence(Ordering::Acquire);
+        self.update_seq.load(Ordering::Acquire) == update_seq << WRITER_BITS
+    }
+
+    /// Run `read`, which stamps its result with [`Self::update_seq`] taken
+    /// before it touches the book, until the result is
+    /// [stable](Self::is_stable_at). Returns the last result and whether it
+    /// is. Gives up after a bounded number of attempts under sustained
+    /// writes, and at once when called from inside a mutation of this
+    /// book, which cannot end while the caller waits for it.
+    pub fn read_stable<R>(
+        &self,
+        mut read: impl FnMut() -> R,
+        update_seq: impl Fn(&R) -> u64,
+    ) -> (R, bool) {
+        let mut value = read();
+        if self.is_stable_at(update_seq(&value)) {
+            return (value, true);
+        }
+        if self.is_mutating_on_this_thread() {
+            return (value, false);
+        }
+        for attempt in 1..MAX_ATTEMPTS {
+            if attempt >= SPIN_ATTEMPTS {
+                std::thread::yield_now();
+            }
+            value = read();
+            if self.is_stable_at(update_seq(&value)) {
+                return (value, true);
+            }
+        }
+        (value, false)
+    }
+
+    /// Whether the calling thread is inside [`Self::begin_mutation`].
+    pub fn is_mutating_on_this_thread(&self) -> bool {
+        let address = self.address();
+        MUTATING.with(|mutating| mutating.borrow().contains(&address))
+    }
+
+    fn address(&self) -> usize {
+        std::ptr::from_ref(self) as usize
+    }
+
+    /// The published top of book, or `None` if none is published for the
+    /// current update sequence (or a publish raced with this read).
+    pub fn get_cached_top_of_book(&self) -> Option<TopOfBook> {
+        self.top
+            .read()
+            .filter(|top| top.update_seq == self.update_seq())
+    }
+
+    /// Publish `top` for readers of [`Self::get_cached_top_of_book`]. The
+    /// caller computes `top` at `top.update_seq` and checks it is
+    /// [stable](Self::is_stable_at) there; a value whose sequence is
+    /// already stale is never served.
+    pub fn update_top_of_book(&self, top: &TopOfBook) {
+        self.top.publish(top);
+    }
+
+    /// The published level aggregates if they were built for at least
+    /// `depth` levels per side at the current update sequence. The result
+    /// may hold more than `depth` levels.
+    pub fn get_cached_level_aggregates(&self, depth: usize) -> Option<Arc<LevelAggregates>> {
+        let guard = match self.levels.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard
+            .as_ref()
+            .filter(|(built_depth, levels)| {
+                *built_depth >= depth && levels.update_seq == self.update_seq()
+            })
+            .map(|(_, levels)| Arc::clone(levels))
+    }
+
+    /// Publish `levels`, built for `depth` levels per side, for readers of
+    /// [`Self::get_cached_level_aggregates`]. The caller checks `levels`
+    /// is [stable](Self::is_stable_at); a view whose sequence is already
+    /// stale is never served.
+    pub fn update_level_aggregates(&self, depth: usize, levels: Arc<LevelAggregates>) {
+        let mut guard = match self.levels.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *guard = Some((depth, levels));
     }
 
     /// Returns the cached best bid, or `None` on a cache miss (an empty or
     /// invalidated bid side). A cached price of `0` is a valid hit.
     pub fn get_cached_best_bid(&self) -> Option<u128> {
-        // Acquire pairs with the Release in `update_best_bid`, so a reader that
-        // observes `bid_valid == true` also observes the price stored before it.
-        if self.bid_valid.load(Ordering::Acquire) {
-            Some(self.best_bid_price.load())
+        // Acquire pairs with the store of `valid` in `publish_best_price`, so a
+        // reader that observes `valid == true` also observes the price stored
+        // before it.
+        if self.bid.valid.load(Ordering::Acquire) {
+            Some(self.bid.price.load())
         } else {
             None
         }
@@ -80,8 +444,8 @@ impl PriceLevelCache {
     /// Returns the cached best ask, or `None` on a cache miss (an empty or
     /// invalidated ask side). A cached price of `0` is a valid hit.
     pub fn get_cached_best_ask(&self) -> Option<u128> {
-        if self.ask_valid.load(Ordering::Acquire) {
-            Some(self.best_ask_price.load())
+        if self.ask.valid.load(Ordering::Acquire) {
+            Some(self.ask.price.load())
         } else {
             None
         }
@@ -90,32 +454,60 @@ impl PriceLevelCache {
     /// Update only the bid slot. `Some(price)` caches the price (including `0`);
     /// `None` (an empty side) leaves the slot invalid so the next read
     /// recomputes. The ask slot is never touched.
+    ///
+    /// Unlike [`Self::publish_best_price`] this does not check the update
+    /// sequence, so a price read before a concurrent level removal would
+    /// outlive it. Test-only: it seeds the cache directly.
+    #[cfg(test)]
     pub fn update_best_bid(&self, best_bid: Option<u128>) {
         match best_bid {
             Some(price) => {
-                self.best_bid_price.store(price);
+                self.bid.price.store(price);
                 // Release so the price store above is visible to any reader that
-                // sees `bid_valid == true`.
-                self.bid_valid.store(true, Ordering::Release);
+                // sees `valid == true`.
+                self.bid.valid.store(true, Ordering::Release);
             }
-            None => self.bid_valid.store(false, Ordering::Relaxed),
+            None => self.bid.valid.store(false, Ordering::Relaxed),
         }
     }
 
     /// Update only the ask slot. `Some(price)` caches the price (including `0`);
     /// `None` (an empty side) leaves the slot invalid so the next read
     /// recomputes. The bid slot is never touched.
+    #[cfg(test)]
     pub fn update_best_ask(&self, best_ask: Option<u128>) {
         match best_ask {
             Some(price) => {
-                self.best_ask_price.store(price);
-                self.ask_valid.store(true, Ordering::Release);
+                self.ask.price.store(price);
+                self.ask.valid.store(true, Ordering::Release);
             }
-            None => self.ask_valid.store(false, Ordering::Relaxed),
+            None => self.ask.valid.store(false, Ordering::Relaxed),
         }
     }
 }
 
+/// A book mutation in flight, returned by
+/// [`PriceLevelCache::begin_mutation`]. Dropping it ends the mutation.
+#[derive(Debug)]
+pub struct MutationGuard<'a> {
+    cache: &'a PriceLevelCache,
+}
+
+impl Drop for MutationGuard<'_> {
+    fn drop(&mut self) {
+        let address = self.cache.address();
+        MUTATING.with(|mutating| {
+            let mut mutating = mutating.borrow_mut();
+            if let Some(index) = mutating.iter().rposition(|entry| *entry == address) {
+                mutating.swap_remove(index);
+            }
+        });
+        self.cache
+            .update_seq
+            .fetch_sub(ONE_WRITER, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,6 +554,149 @@ mod tests {
         assert_eq!(cache.get_cached_best_bid(), Some(100));
     }
 
+    #[test]
+    fn test_top_of_book_is_served_until_invalidated() {
+        let cache = PriceLevelCache::new();
+        assert_eq!(cache.get_cached_top_of_book(), None);
+
+        let top = TopOfBook {
+            best_bid: Some(u128::MAX - 1),
+            best_ask: None,
+            bid_quantity: 7,
+            ask_quantity: 0,
+            update_seq: cache.update_seq(),
+        };
+        cache.update_top_of_book(&top);
+        assert_eq!(cache.get_cached_top_of_book(), Some(top));
+
+        cache.invalidate();
+        assert_eq!(cache.get_cached_top_of_book(), None);
+
+        // A value computed before the invalidation is never served.
+        cache.update_top_of_book(&top);
+        assert_eq!(cache.get_cached_top_of_book(), None);
+    }
+
+    #[test]
+    fn test_top_of_book_reads_are_never_torn() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let cache = Arc::new(PriceLevelCache::new());
+        let writer = {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for i in 1..20_000u64 {
+                    // Every published value satisfies ask = bid + 1 and
+                    // ask_quantity = bid_quantity.
+                    let bid = u128::from(i) << 64 | u128::from(i);
+                    cache.update_top_of_book(&TopOfBook {
+                        best_bid: Some(bid),
+                        best_ask: Some(bid + 1),
+                        bid_quantity: i,
+                        ask_quantity: i,
+                        update_seq: 0,
+                    });
+                }
+            })
+        };
+        for _ in 0..20_000 {
+            if let Some(top) = cache.get_cached_top_of_book() {
+                let bid = top.best_bid.expect("bid");
+                assert_eq!(top.best_ask, Some(bid + 1));
+                assert_eq!(top.bid_quantity, top.ask_quantity);
+                assert_eq!(bid as u64, top.bid_quantity);
+            }
+        }
+        writer.join().expect("writer");
+    }
+
+    #[test]
+    fn test_top_of_book_derived_metrics() {
+        let top = TopOfBook {
+            best_bid: Some(100),
+            best_ask: Some(110),
+            bid_quantity: 30,
+            ask_quantity: 10,
+            update_seq: 0,
+        };
+        assert_eq!(top.mid_price(), Some(105.0));
+        assert_eq!(top.spread(), Some(10));
+        // (110 × 30 + 100 × 10) / 40
+        assert_eq!(top.micro_price(), Some(107.5));
+        assert_eq!(TopOfBook::default().mid_price(), None);
+    }
+
+    #[test]
+    fn test_level_aggregates_are_served_for_covered_depths_until_invalidated() {
+        let cache = PriceLevelCache::new();
+        assert!(cache.get_cached_level_aggregates(1).is_none());
+
+        let levels = Arc::new(LevelAggregates {
+            update_seq: cache.update_seq(),
+            ..LevelAggregates::default()
+        });
+        cache.update_level_aggregates(10, Arc::clone(&levels));
+        let cached = cache.get_cached_level_aggregates(5).expect("covered depth");
+        assert!(Arc::ptr_eq(&cached, &levels));
+        assert!(cache.get_cached_level_aggregates(11).is_none());
+
+        cache.invalidate();
+        assert!(cache.get_cached_level_aggregates(5).is_none());
+        // The buffer handed out earlier is unaffected.
+        assert_eq!(cached.update_seq, 0);
+    }
+
+    #[test]
+    fn test_published_best_price_survives_only_at_its_sequence() {
+        let cache = PriceLevelCache::new();
+        cache.publish_best_price(Side::Sell, Some(110), cache.update_seq());
+        assert_eq!(cache.get_cached_best_ask(), Some(110));
+        assert_eq!(cache.get_cached_best_bid(), None);
+
+        let stale = cache.update_seq();
+        cache.invalidate();
+        cache.publish_best_price(Side::Sell, Some(105), stale);
+        assert_eq!(cache.get_cached_best_ask(), None);
+    }
+
+    #[test]
+    fn test_invalidate_side_keeps_the_other_side() {
+        let cache = PriceLevelCache::new();
+        cache.update_best_bid(Some(100));
+        cache.update_best_ask(Some(110));
+        let seq = cache.update_seq();
+        cache.invalidate_side(Side::Buy);
+        assert_eq!(cache.get_cached_best_bid(), None);
+        assert_eq!(cache.get_cached_best_ask(), Some(110));
+        assert!(cache.update_seq() > seq);
+    }
+
+    #[test]
+    fn test_reads_are_unstable_while_a_mutation_is_in_flight() {
+        let cache = PriceLevelCache::new();
+        let seq = cache.update_seq();
+        assert!(cache.is_stable_at(seq));
+
+        let mutation = cache.begin_mutation();
+        // Nothing invalidated yet, but a read now may see a partial write.
+        assert_eq!(cache.update_seq(), seq);
+        assert!(!cache.is_stable_at(seq));
+        assert!(cache.is_mutating_on_this_thread());
+        let (read, stable) = cache.read_stable(|| cache.update_seq(), |seq| *seq);
+        assert_eq!((read, stable), (seq, false));
+
+        cache.invalidate();
+        drop(mutation);
+        assert!(!cache.is_stable_at(seq));
+        assert!(cache.is_stable_at(seq + 1));
+        assert!(!cache.is_mutating_on_this_thread());
+
+        // A mutation that writes nothing keeps published values current.
+        drop(cache.begin_mutation());
+        assert!(cache.is_stable_at(seq + 1));
+    }
+
     #[test]
     fn test_invalidate_clears_both_sides() {
         let cache = PriceLevelCache::new();
@@ -171,4 +706,18 @@ mod tests {
         assert_eq!(cache.get_cached_best_bid(), None);
         assert_eq!(cache.get_cached_best_ask(), None);
     }
+
+    #[test]
+    fn test_hot_slots_do_not_share_a_cache_line() {
+        let cache = PriceLevelCache::new();
+        let address = |slot: *const u8| slot as usize;
+        let bid = address((&*cache.bid as *const BestPriceSlot).cast());
+        let ask = address((&*cache.ask as *const BestPriceSlot).cast());
+        let seq = address((&*cache.update_seq as *const AtomicU64).cast());
+        let line = std::mem::align_of::<CachePadded<AtomicU64>>();
+        assert!(line >= 64);
+        for (a, b) in [(bid, ask), (bid, seq), (ask, seq)] {
+            assert_ne!(a / line, b / line);
+        }
+    }
 }
@@ -0,0 +1,278 @@
+//! Heartbeat-driven cancel-on-disconnect.
+//!
+//! A client session registers its user with
+//! [`OrderBook::register_heartbeat`] and a time-to-live, then keeps the
+//! registration alive by calling [`OrderBook::heartbeat`] before the TTL
+//! runs out. A session that misses its deadline is treated as
+//! disconnected: its registration is dropped, every resting order of the
+//! user is cancelled with [`CancelReason::Disconnected`], and a
+//! [`DisconnectEvent`] is delivered to the listener installed with
+//! [`OrderBook::set_disconnect_listener`].
+//!
+//! Expiry is checked before every gated operation, so a busy book cancels
+//! on its own. A quiet book cancels when a timer calls
+//! [`OrderBook::expire_heartbeats`];
+//! [`BookManagerStd::expire_heartbeats_across_books`](crate::orderbook::manager::BookManagerStd::expire_heartbeats_across_books)
+//! does so for a whole manager, whose `*_across_books` heartbeat methods
+//! let one session cover its orders on every book. Registrations belong to
+//! live client connections and are not saved in snapshot packages.
+//!
+//! Like other listeners, the disconnect listener runs on the thread that
+//! noticed the expiry, after the submit gate is released, and must not
+//! call back into the book.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::listener_slot::ListenerSlot;
+use super::mass_cancel::MassCancelResult;
+use super::order_state::CancelReason;
+use pricelevel::Hash32;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::info;
+
+/// A user whose heartbeat expired, delivered to the listener installed
+/// with [`OrderBook::set_disconnect_listener`] and returned by
+/// [`OrderBook::expire_heartbeats`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisconnectEvent {
+    /// Symbol of the book
+    pub symbol: String,
+    /// User whose session missed its heartbeat
+    pub user_id: Hash32,
+    /// Book clock time the heartbeat was due, in milliseconds
+    pub deadline_ms: u64,
+    /// Book clock time the expiry was processed, in milliseconds
+    pub timestamp_ms: u64,
+    /// Orders of the user cancelled on disconnect
+    pub cancelled: MassCancelResult,
+}
+
+/// Receives the disconnects of sessions that missed their heartbeat.
+pub type DisconnectListener = Arc<dyn Fn(&DisconnectEvent) + Send + Sync>;
+
+/// One registered session.
+#[derive(Debug, Clone, Copy)]
+struct HeartbeatSession {
+    ttl_ms: u64,
+    deadline_ms: u64,
+}
+
+/// Per-book heartbeat registrations.
+#[derive(Default)]
+pub(crate) struct Heartbeats {
+    /// Earliest deadline of any registration; zero when there is none, so
+    /// books without sessions skip the clock read on every operation.
+    next_deadline: AtomicU64,
+    sessions: Mutex<HashMap<Hash32, HeartbeatSession>>,
+    listener: ListenerSlot<DisconnectListener>,
+}
+
+impl Heartbeats {
+    fn sessions(&self) -> MutexGuard<'_, HashMap<Hash32, HeartbeatSession>> {
+        // The map only holds plain deadlines, which a panic cannot leave
+        // half-written, so a poisoned lock is recovered.
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Whether a registration may have expired at `now_ms`.
+    #[inline]
+    fn due(&self, now_ms: u64) -> bool {
+        let next = self.next_deadline.load(Ordering::Acquire);
+        next != 0 && now_ms >= next
+    }
+
+    /// Publish the earliest deadline of `sessions`.
+    fn update_next_deadline(&self, sessions: &HashMap<Hash32, HeartbeatSession>) {
+        let next = sessions
+            .values()
+            .map(|session| session.deadline_ms)
+            .min()
+            .unwrap_or(0);
+        self.next_deadline.store(next, Ordering::Release);
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Register a session for `user_id` that must call
+    /// [`Self::heartbeat`] at least every `ttl_ms` milliseconds of book
+    /// clock time. Re-registering a user replaces its TTL and restarts its
+    /// deadline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::ZeroHeartbeatTtl`] when `ttl_ms` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{Clock, OrderBook, ReplayClock};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    /// use std::sync::Arc;
+    ///
+    /// let clock = Arc::new(ReplayClock::new(0));
+    /// let book = OrderBook::<()>::with_clock("BTC/USD", Arc::clone(&clock) as Arc<dyn Clock>);
+    /// let user = Hash32::new([7; 32]);
+    /// book.register_heartbeat(user, 1_000)?;
+    /// book.add_limit_order_with_user(Id::from_u64(1), 100, 5, Side::Buy, TimeInForce::Gtc, user, None)?;
+    ///
+    /// clock.advance_to(900);
+    /// assert!(book.heartbeat(user));
+    ///
+    /// clock.advance_to(1_900);
+    /// let disconnects = book.expire_heartbeats();
+    /// assert_eq!(disconnects[0].deadline_ms, 1_900);
+    /// assert_eq!(disconnects[0].cancelled.cancelled_count(), 1);
+    /// assert!(!book.heartbeat(user));
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn register_heartbeat(&self, user_id: Hash32, ttl_ms: u64) -> Result<(), OrderBookError> {
+        if ttl_ms == 0 {
+            return Err(OrderBookError::ZeroHeartbeatTtl { user_id });
+        }
+        let deadline_ms = self.clock().now_millis().as_u64().saturating_add(ttl_ms);
+        let mut sessions = self.heartbeats.sessions();
+        sessions.insert(
+            user_id,
+            HeartbeatSession {
+                ttl_ms,
+                deadline_ms,
+            },
+        );
+        self.heartbeats.update_next_deadline(&sessions);
+        Ok(())
+    }
+
+    /// Refresh the session of `user_id`, pushing its deadline `ttl_ms`
+    /// past now. Returns `false` if the user has no live registration,
+    /// either never registered or already disconnected; a session found
+    /// expired is disconnected first rather than revived.
+    pub fn heartbeat(&self, user_id: Hash32) -> bool {
+        self.run_heartbeat_schedule();
+        let now = self.clock().now_millis().as_u64();
+        let mut sessions = self.heartbeats.sessions();
+        let Some(session) = sessions.get_mut(&user_id) else {
+            return false;
+        };
+        session.deadline_ms = now.saturating_add(session.ttl_ms);
+        self.heartbeats.update_next_deadline(&sessions);
+        true
+    }
+
+    /// Drop the registration of `user_id` without cancelling its orders,
+    /// as on an orderly logout. Returns `false` if it was not registered.
+    pub fn unregister_heartbeat(&self, user_id: Hash32) -> bool {
+        let mut sessions = self.heartbeats.sessions();
+        let removed = sessions.remove(&user_id).is_some();
+        self.heartbeats.update_next_deadline(&sessions);
+        removed
+    }
+
+    /// Book clock time the next heartbeat of `user_id` is due, in
+    /// milliseconds, or `None` if it is not registered.
+    #[must_use]
+    pub fn heartbeat_deadline(&self, user_id: Hash32) -> Option<u64> {
+        self.heartbeats
+            .sessions()
+            .get(&user_id)
+            .map(|session| session.deadline_ms)
+    }
+
+    /// Install the disconnect listener, replacing any previous one.
+    pub fn set_disconnect_listener(&self, listener: DisconnectListener) {
+        self.heartbeats.listener.replace(Some(listener));
+    }
+
+    /// Remove the disconnect listener.
+    pub fn remove_disconnect_listener(&self) {
+        self.heartbeats.listener.replace(None);
+    }
+
+    /// Disconnect every session whose deadline has passed, cancelling its
+    /// user's resting orders. Returns the disconnects processed, earliest
+    /// deadline first; each is also delivered to the disconnect listener.
+    ///
+    /// Call this from a timer so quiet books cancel without waiting for
+    /// their next operation.
+    pub fn expire_heartbeats(&self) -> Vec<DisconnectEvent> {
+        let now = self.clock().now_millis().as_u64();
+        if !self.heartbeats.due(now) {
+            return Vec::new();
+        }
+        let events = {
+            let _gate = self
+                .submit_gate
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            self.expire_heartbeats_ungated(now)
+        };
+        if let Some(listener) = self.heartbeats.listener.get() {
+            for event in &events {
+                listener(event);
+            }
+        }
+        events
+    }
+
+    /// Disconnect expired sessions before a gated operation. Called by the
+    /// submit gate accessors before they acquire the gate, so it can take
+    /// the gate exclusively itself.
+    #[inline]
+    pub(super) fn run_heartbeat_schedule(&self) {
+        if self.heartbeats.next_deadline.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        let _ = self.expire_heartbeats();
+    }
+
+    fn expire_heartbeats_ungated(&self, now_ms: u64) -> Vec<DisconnectEvent> {
+        let mut expired: Vec<(Hash32, u64)> = {
+            let mut sessions = self.heartbeats.sessions();
+            let mut expired = Vec::new();
+            sessions.retain(|user_id, session| {
+                let live = now_ms < session.deadline_ms;
+                if !live {
+                    expired.push((*user_id, session.deadline_ms));
+                }
+                live
+            });
+            self.heartbeats.update_next_deadline(&sessions);
+            expired
+        };
+        expired.sort_unstable_by_key(|(user_id, deadline_ms)| (*deadline_ms, user_id.0));
+
+        expired
+            .into_iter()
+            .map(|(user_id, deadline_ms)| {
+                let order_ids = self
+                    .user_orders
+                    .remove(&user_id)
+                    .map(|(_, ids)| ids)
+                    .unwrap_or_default();
+                let cancelled =
+                    self.cancel_order_batch_with_reason(&order_ids, CancelReason::Disconnected);
+                info!(
+                    symbol = %self.symbol,
+                    %user_id,
+                    deadline_ms,
+                    cancelled = cancelled.cancelled_count(),
+                    "heartbeat expired; user disconnected"
+                );
+                DisconnectEvent {
+                    symbol: self.symbol.clone(),
+                    user_id,
+                    deadline_ms,
+                    timestamp_ms: now_ms,
+                    cancelled,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,126 @@
+//! Warm-start preallocation and occupancy introspection.
+//!
+//! A book built with [`OrderBook::with_capacity`] (or
+//! [`OrderBookBuilder::capacity`](super::builder::OrderBookBuilder::capacity))
+//! sizes its indexes for the expected peak up front, so the first seconds of
+//! a trading session do not pay for rehashing while the book fills up.
+//! [`OrderBook::capacity_stats`] reports how full those indexes are, to
+//! check the hints against a real session.
+
+use super::book::OrderBook;
+use super::depth_totals::DepthTotals;
+use super::level_pool::{LevelPool, LevelPoolStats};
+use super::matching::warm_matching_pool;
+use serde::{Deserialize, Serialize};
+
+/// Buffers of every kind placed in the matching pool of the thread that
+/// builds a pre-sized book.
+pub const WARM_MATCHING_BUFFERS: usize = 4;
+
+/// Occupancy of a book's indexes, as returned by
+/// [`OrderBook::capacity_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapacityStats {
+    /// Resting orders in the book
+    pub resting_orders: usize,
+    /// Resting orders the order-id index holds without reallocating
+    pub order_index_capacity: usize,
+    /// Bid price levels
+    pub bid_levels: usize,
+    /// Ask price levels
+    pub ask_levels: usize,
+    /// Price levels per side the depth index holds without reallocating
+    pub level_index_capacity: usize,
+    /// Counters of the pool of emptied levels
+    pub level_pool: LevelPoolStats,
+}
+
+impl CapacityStats {
+    /// Resting orders over the order-id index capacity. Close to `1.0`
+    /// means the next orders may trigger a rehash.
+    #[must_use]
+    pub fn order_index_load_factor(&self) -> f64 {
+        load_factor(self.resting_orders, self.order_index_capacity)
+    }
+
+    /// Levels of the fuller side over the depth index capacity.
+    #[must_use]
+    pub fn level_index_load_factor(&self) -> f64 {
+        load_factor(
+            self.bid_levels.max(self.ask_levels),
+            self.level_index_capacity,
+        )
+    }
+}
+
+fn load_factor(used: usize, capacity: usize) -> f64 {
+    if capacity == 0 {
+        0.0
+    } else {
+        used as f64 / capacity as f64
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Create a new order book for the given symbol with its indexes
+    /// pre-sized for `levels_hint` price levels per side and `orders_hint`
+    /// resting orders.
+    ///
+    /// Sizes the order indexes as [`Self::with_order_capacity`] does, plus
+    /// the `extra_fields` store when `T` carries data, the per-price depth
+    /// index and the pool of emptied levels. The matching buffers of the
+    /// calling thread are pre-allocated too, so build the book on the
+    /// thread that will match. Other threads can be warmed with
+    /// [`Self::warm_matching_pool`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    ///
+    /// let book = OrderBook::<()>::with_capacity("BTC/USD", 1_000, 50_000);
+    /// let stats = book.capacity_stats();
+    /// assert!(stats.order_index_capacity >= 50_000);
+    /// assert!(stats.level_index_capacity >= 1_000);
+    /// assert_eq!(stats.order_index_load_factor(), 0.0);
+    /// ```
+    pub fn with_capacity(symbol: &str, levels_hint: usize, orders_hint: usize) -> Self {
+        let mut book = Self::with_order_capacity(symbol, orders_hint);
+        if size_of::<T>() != 0 {
+            book.extra_fields = dashmap::DashMap::with_capacity(orders_hint);
+        }
+        book.presize_levels(levels_hint);
+        book
+    }
+
+    /// Size the per-price indexes for `levels_hint` levels per side and warm
+    /// the calling thread's matching pool.
+    pub(super) fn presize_levels(&mut self, levels_hint: usize) {
+        self.depth_totals = DepthTotals::with_level_capacity(levels_hint);
+        self.level_pool = LevelPool::with_capacity(self.level_pool.max_size(), levels_hint);
+        warm_matching_pool(WARM_MATCHING_BUFFERS);
+    }
+
+    /// Pre-allocate the matching buffers of the calling thread. Matching
+    /// reuses per-thread buffers, so each thread that submits orders
+    /// allocates its own on first use unless warmed here.
+    pub fn warm_matching_pool(&self) {
+        warm_matching_pool(WARM_MATCHING_BUFFERS);
+    }
+
+    /// Occupancy of the book's indexes against their capacity.
+    #[must_use]
+    pub fn capacity_stats(&self) -> CapacityStats {
+        CapacityStats {
+            resting_orders: self.order_locations.len(),
+            order_index_capacity: self.order_locations.capacity(),
+            bid_levels: self.bids.len(),
+            ask_levels: self.asks.len(),
+            level_index_capacity: self.depth_totals.level_capacity(),
+            level_pool: self.level_pool.stats(),
+        }
+    }
+}
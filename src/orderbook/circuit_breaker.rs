@@ -0,0 +1,372 @@
+//! Volatility circuit breaker.
+//!
+//! A [`CircuitBreakerConfig`], part of the book's
+//! [`BookConfig`](super::book_config::BookConfig), trips when traded
+//! prices move too far too fast: when the range of trade prices within a
+//! rolling window exceeds `max_move_bps` of its low, or, with a
+//! [`reference`](CircuitBreakerConfig::reference) source, when a trade
+//! prints that far from the fresh index or mark price.
+//!
+//! A tripped book moves to the [`CircuitBreakerAction`]'s trading phase
+//! for the cool-down: [`SessionPhase::Halted`] rejects all new flow,
+//! [`SessionPhase::VolatilityAuction`] lets non-crossing orders rest but
+//! nothing trade, like the pre-open auction of a
+//! [`TradingSession`](super::session::TradingSession). Both are rejected
+//! with [`OrderBookError::MarketClosed`](super::error::OrderBookError::MarketClosed).
+//! The trade that trips the breaker, and the rest of the sweep it belongs
+//! to, still execute.
+//!
+//! Every trip and resumption is reported to the listener installed with
+//! [`OrderBook::set_circuit_breaker_listener`]. The book resumes on its
+//! own before the first gated operation after the cool-down, or early
+//! through [`OrderBook::reset_circuit_breaker`]. Like other listeners, the
+//! circuit breaker listener runs on the thread that caused the event and
+//! must not call back into the book.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::listener_slot::ListenerSlot;
+use super::reference_price::ExternalPriceSource;
+use super::session::SessionPhase;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What a tripped circuit breaker does for the cool-down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CircuitBreakerAction {
+    /// Reject all new flow (default).
+    #[default]
+    Halt,
+    /// Let non-crossing orders rest, rejecting market and crossing orders.
+    Auction,
+}
+
+impl std::fmt::Display for CircuitBreakerAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitBreakerAction::Halt => write!(f, "Halt"),
+            CircuitBreakerAction::Auction => write!(f, "Auction"),
+        }
+    }
+}
+
+/// Price velocity limits of a circuit breaker.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{CircuitBreakerAction, CircuitBreakerConfig};
+///
+/// // Trip on a 5% move within one minute, auction for thirty seconds.
+/// let breaker = CircuitBreakerConfig::new(500, 60_000, 30_000)
+///     .with_action(CircuitBreakerAction::Auction);
+/// assert!(breaker.validate().is_ok());
+/// assert!(CircuitBreakerConfig::new(0, 60_000, 30_000).validate().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Largest accepted move, in basis points.
+    pub max_move_bps: u32,
+    /// Rolling window the trade price range is measured over, in
+    /// milliseconds.
+    pub window_ms: u64,
+    /// How long the book stays tripped, in milliseconds.
+    pub cool_down_ms: u64,
+    /// Trading phase of a tripped book.
+    #[serde(default)]
+    pub action: CircuitBreakerAction,
+    /// External price trades are also measured against, when fresh.
+    #[serde(default)]
+    pub reference: Option<ExternalPriceSource>,
+}
+
+impl CircuitBreakerConfig {
+    /// Trip on a move of more than `max_move_bps` within `window_ms`,
+    /// halting for `cool_down_ms`.
+    #[must_use]
+    pub fn new(max_move_bps: u32, window_ms: u64, cool_down_ms: u64) -> Self {
+        Self {
+            max_move_bps,
+            window_ms,
+            cool_down_ms,
+            action: CircuitBreakerAction::Halt,
+            reference: None,
+        }
+    }
+
+    /// Set the trading phase of a tripped book.
+    #[must_use]
+    pub fn with_action(mut self, action: CircuitBreakerAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Also trip when a trade moves more than the limit away from the
+    /// fresh `source` price.
+    #[must_use]
+    pub fn with_reference(mut self, source: ExternalPriceSource) -> Self {
+        self.reference = Some(source);
+        self
+    }
+
+    /// Check the limits for consistency.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when the move
+    /// limit, window or cool-down is zero.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        if self.max_move_bps == 0 || self.window_ms == 0 || self.cool_down_ms == 0 {
+            return Err(OrderBookError::InvalidConfiguration {
+                message: "circuit breaker move limit, window and cool-down must be non-zero"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether `price` is more than the limit away from `anchor`.
+    fn exceeded(&self, anchor: u128, price: u128) -> bool {
+        anchor > 0
+            && anchor.abs_diff(price).saturating_mul(10_000)
+                > anchor.saturating_mul(u128::from(self.max_move_bps))
+    }
+}
+
+/// A circuit breaker state change, delivered to the listener installed
+/// with [`OrderBook::set_circuit_breaker_listener`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitBreakerEvent {
+    /// The book entered the action's trading phase.
+    Tripped {
+        /// Symbol of the book
+        symbol: String,
+        /// Action in force until `resumes_at_ms`
+        action: CircuitBreakerAction,
+        /// Price the move was measured from: the window's extreme or the
+        /// reference price
+        anchor_price: u128,
+        /// Trade price that tripped the breaker
+        trigger_price: u128,
+        /// Book clock time of the trip, in milliseconds
+        timestamp_ms: u64,
+        /// Book clock time the cool-down ends, in milliseconds
+        resumes_at_ms: u64,
+    },
+    /// The book returned to normal trading.
+    Resumed {
+        /// Symbol of the book
+        symbol: String,
+        /// Book clock time of the resumption, in milliseconds
+        timestamp_ms: u64,
+    },
+}
+
+/// Receives circuit breaker state changes.
+pub type CircuitBreakerListener = Arc<dyn Fn(&CircuitBreakerEvent) + Send + Sync>;
+
+/// Per-book breaker state.
+#[derive(Default)]
+pub(crate) struct CircuitBreakerState {
+    /// Mirrors `BookConfig::circuit_breaker.is_some()` so books without a
+    /// breaker skip the clock read on every trade.
+    enabled: AtomicBool,
+    /// Trade prices within the window, oldest first.
+    trades: Mutex<VecDeque<(u64, u128)>>,
+    /// End of the cool-down; zero while trading normally.
+    tripped_until: AtomicU64,
+    listener: ListenerSlot<CircuitBreakerListener>,
+}
+
+impl CircuitBreakerState {
+    /// Track a newly configured breaker, starting from an empty window
+    /// and an untripped book.
+    pub(crate) fn configure(&self, config: Option<CircuitBreakerConfig>) {
+        self.enabled.store(config.is_some(), Ordering::Release);
+        self.tripped_until.store(0, Ordering::Release);
+        self.window().clear();
+    }
+
+    fn window(&self) -> std::sync::MutexGuard<'_, VecDeque<(u64, u128)>> {
+        // The window only holds plain prices, which a panic cannot leave
+        // half-written, so a poisoned lock is recovered.
+        self.trades
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// The configured circuit breaker, if any.
+    #[must_use]
+    #[inline]
+    pub fn circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.config().circuit_breaker
+    }
+
+    /// Install the circuit breaker listener, replacing any previous one.
+    pub fn set_circuit_breaker_listener(&self, listener: CircuitBreakerListener) {
+        self.circuit_breaker.listener.replace(Some(listener));
+    }
+
+    /// Remove the circuit breaker listener.
+    pub fn remove_circuit_breaker_listener(&self) {
+        self.circuit_breaker.listener.replace(None);
+    }
+
+    /// End of the current cool-down on the book clock, or `None` while
+    /// the breaker is not tripped.
+    #[must_use]
+    pub fn circuit_breaker_resumes_at(&self) -> Option<u64> {
+        match self.circuit_breaker.tripped_until.load(Ordering::Acquire) {
+            0 => None,
+            until if self.clock().now_millis().as_u64() < until => Some(until),
+            _ => None,
+        }
+    }
+
+    /// Trading phase the circuit breaker imposes now, or `None` while it
+    /// is not tripped.
+    #[must_use]
+    pub fn circuit_breaker_phase(&self) -> Option<SessionPhase> {
+        self.circuit_breaker_resumes_at()?;
+        Some(match self.circuit_breaker().map(|config| config.action) {
+            Some(CircuitBreakerAction::Auction) => SessionPhase::VolatilityAuction,
+            _ => SessionPhase::Halted,
+        })
+    }
+
+    /// End the cool-down now. Returns whether the breaker was tripped.
+    pub fn reset_circuit_breaker(&self) -> bool {
+        let until = self.circuit_breaker.tripped_until.swap(0, Ordering::AcqRel);
+        if until == 0 {
+            return false;
+        }
+        self.emit_circuit_breaker_resumed();
+        true
+    }
+
+    /// Resume a book whose cool-down has ended. Called by the submit gate
+    /// accessors before they acquire the gate.
+    pub(super) fn run_circuit_breaker_schedule(&self) {
+        let until = self.circuit_breaker.tripped_until.load(Ordering::Acquire);
+        if until == 0 || self.clock().now_millis().as_u64() < until {
+            return;
+        }
+        if self
+            .circuit_breaker
+            .tripped_until
+            .compare_exchange(until, 0, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            self.emit_circuit_breaker_resumed();
+        }
+    }
+
+    /// Feed one trade price to the breaker, tripping it when the move
+    /// limit is exceeded. Called by the matching engine for every level
+    /// that trades.
+    #[inline]
+    pub(super) fn observe_trade_price(&self, price: u128) {
+        if !self.circuit_breaker.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        self.observe_trade_price_slow(price);
+    }
+
+    #[cold]
+    fn observe_trade_price_slow(&self, price: u128) {
+        let Some(config) = self.circuit_breaker() else {
+            return;
+        };
+        if self.circuit_breaker.tripped_until.load(Ordering::Acquire) != 0 {
+            return;
+        }
+        let now = self.clock().now_millis().as_u64();
+
+        let mut anchor = config
+            .reference
+            .and_then(|source| self.fresh_reference_price(source))
+            .filter(|&reference| config.exceeded(reference, price));
+        {
+            let mut window = self.circuit_breaker.window();
+            let horizon = now.saturating_sub(config.window_ms);
+            while window.front().is_some_and(|&(at, _)| at < horizon) {
+                window.pop_front();
+            }
+            window.push_back((now, price));
+            if anchor.is_none() {
+                let (low, high) = window.iter().fold((price, price), |(low, high), &(_, p)| {
+                    (low.min(p), high.max(p))
+                });
+                if config.exceeded(low, high) {
+                    anchor = Some(if price == low { high } else { low });
+                }
+            }
+            if anchor.is_some() {
+                window.clear();
+            }
+        }
+        let Some(anchor_price) = anchor else {
+            return;
+        };
+
+        let resumes_at_ms = now.saturating_add(config.cool_down_ms).max(1);
+        if self
+            .circuit_breaker
+            .tripped_until
+            .compare_exchange(0, resumes_at_ms, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+        tracing::warn!(
+            symbol = %self.symbol,
+            anchor_price,
+            trigger_price = price,
+            resumes_at_ms,
+            "circuit breaker tripped"
+        );
+        if let Some(listener) = self.circuit_breaker.listener.get() {
+            listener(&CircuitBreakerEvent::Tripped {
+                symbol: self.symbol.clone(),
+                action: config.action,
+                anchor_price,
+                trigger_price: price,
+                timestamp_ms: now,
+                resumes_at_ms,
+            });
+        }
+    }
+
+    fn emit_circuit_breaker_resumed(&self) {
+        tracing::info!(symbol = %self.symbol, "circuit breaker resumed");
+        if let Some(listener) = self.circuit_breaker.listener.get() {
+            listener(&CircuitBreakerEvent::Resumed {
+                symbol: self.symbol.clone(),
+                timestamp_ms: self.clock().now_millis().as_u64(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_limit_is_relative_to_the_anchor() {
+        let config = CircuitBreakerConfig::new(500, 1_000, 1_000);
+        assert!(!config.exceeded(100, 105));
+        assert!(config.exceeded(100, 106));
+        assert!(config.exceeded(100, 94));
+        assert!(!config.exceeded(0, 1));
+        assert_eq!(CircuitBreakerAction::Auction.to_string(), "Auction");
+        assert_eq!(CircuitBreakerAction::default(), CircuitBreakerAction::Halt);
+    }
+}
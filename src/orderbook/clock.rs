@@ -11,6 +11,10 @@
 //!   byte-identical sequencer replay. Each call to `now_millis` advances
 //!   an internal counter by a fixed `step` (default `1`).
 //!
+//! Fills additionally carry a nanosecond matched-at stamp taken from
+//! [`Clock::now_nanos`]; [`MonotonicClock`] reads the wall clock at full
+//! resolution, while every other clock derives it from `now_millis`.
+//!
 //! Both implementations are `Send + Sync` so an `Arc<dyn Clock>` can be
 //! shared across threads. The trait is object-safe and is stored on
 //! [`crate::orderbook::book::OrderBook`] as `Arc<dyn Clock>`.
@@ -37,6 +41,15 @@ pub trait Clock: Send + Sync + fmt::Debug {
     /// - replay / test ([`StubClock`]): a monotonic logical counter,
     ///   not wall-clock.
     fn now_millis(&self) -> TimestampMs;
+
+    /// Current timestamp in nanoseconds, in the same time base as
+    /// [`Self::now_millis`].
+    ///
+    /// The default scales one `now_millis` reading, so a
+    /// [`StubClock`] advances exactly as it does for a millisecond stamp.
+    fn now_nanos(&self) -> u64 {
+        self.now_millis().as_u64().saturating_mul(1_000_000)
+    }
 }
 
 /// Production clock wrapping [`crate::utils::current_time_millis`].
@@ -53,6 +66,11 @@ impl Clock for MonotonicClock {
     fn now_millis(&self) -> TimestampMs {
         TimestampMs::new(crate::utils::current_time_millis())
     }
+
+    #[inline]
+    fn now_nanos(&self) -> u64 {
+        crate::utils::current_time_nanos()
+    }
 }
 
 /// Deterministic stub clock. Each call to [`Clock::now_millis`] advances
@@ -140,6 +158,13 @@ mod tests {
         assert_eq!(clock.now_millis().as_u64(), 2);
     }
 
+    #[test]
+    fn test_stub_clock_nanos_scale_one_tick() {
+        let clock = StubClock::starting_at(7);
+        assert_eq!(clock.now_nanos(), 7_000_000);
+        assert_eq!(clock.peek(), 8);
+    }
+
     #[test]
     fn test_stub_clock_with_step() {
         let clock = StubClock::with_step(100, 5);
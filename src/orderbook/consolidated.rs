@@ -0,0 +1,248 @@
+//! Consolidated depth across several books.
+//!
+//! A [`ConsolidatedBook`] borrows a set of [`OrderBook`]s — typically the
+//! same instrument listed on several simulated venues — and merges their
+//! depth into one price ladder. Every consolidated level keeps the share of
+//! each venue resting at that price, which is what smart-order-routing
+//! research needs to decide where to send child orders.
+//!
+//! The view holds no state of its own: each query reads the books as they
+//! are at that moment, one venue after the other, so two venues mutating
+//! concurrently may be observed at slightly different instants.
+
+use super::book::OrderBook;
+use super::iterators::{PriceLevelIter, side_range};
+use super::manager::BookManager;
+use pricelevel::Side;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// One venue's share of a [`ConsolidatedLevel`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueLevel {
+    /// Venue the quantity rests on
+    pub venue: String,
+    /// Displayed quantity at the price on this venue (in units)
+    pub visible_quantity: u64,
+    /// Displayed plus hidden quantity at the price on this venue (in units)
+    pub quantity: u64,
+    /// Orders resting at the price on this venue
+    pub order_count: usize,
+}
+
+/// One price of the consolidated ladder, summed over every venue quoting it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsolidatedLevel {
+    /// Level price, in price units
+    pub price: u128,
+    /// Displayed quantity over all venues (in units)
+    pub visible_quantity: u64,
+    /// Displayed plus hidden quantity over all venues (in units)
+    pub quantity: u64,
+    /// Orders resting at the price over all venues
+    pub order_count: usize,
+    /// Per-venue breakdown, in the order the venues were added
+    pub venues: Vec<VenueLevel>,
+}
+
+impl ConsolidatedLevel {
+    /// The breakdown entry of `venue`, if it quotes this price.
+    #[must_use]
+    pub fn venue(&self, venue: &str) -> Option<&VenueLevel> {
+        self.venues.iter().find(|level| level.venue == venue)
+    }
+}
+
+/// Merged best-price view over several order books.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{ConsolidatedBook, OrderBook};
+/// use pricelevel::{Id, Side, TimeInForce};
+///
+/// let lit = OrderBook::<()>::new("BTC/USD");
+/// let alt = OrderBook::<()>::new("BTC/USD");
+/// lit.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+/// alt.add_limit_order(Id::new_uuid(), 100, 3, Side::Buy, TimeInForce::Gtc, None)?;
+/// alt.add_limit_order(Id::new_uuid(), 99, 7, Side::Buy, TimeInForce::Gtc, None)?;
+///
+/// let book = ConsolidatedBook::new()
+///     .with_venue("LIT", &lit)
+///     .with_venue("ALT", &alt);
+/// let best = book.best_bid().expect("bid");
+/// assert_eq!((best.price, best.quantity, best.venues.len()), (100, 8, 2));
+/// assert_eq!(book.levels(Side::Buy, 10).len(), 2);
+/// # Ok::<(), orderbook_rs::OrderBookError>(())
+/// ```
+pub struct ConsolidatedBook<'a, T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    venues: Vec<(String, &'a OrderBook<T>)>,
+}
+
+impl<'a, T> ConsolidatedBook<'a, T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// An empty view with no venue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { venues: Vec::new() }
+    }
+
+    /// Every book of `manager`, each one a venue named after its symbol,
+    /// added in symbol order.
+    #[must_use]
+    pub fn from_manager<M>(manager: &'a M) -> Self
+    where
+        M: BookManager<T>,
+    {
+        let mut symbols = manager.symbols();
+        symbols.sort_unstable();
+        let mut view = Self::new();
+        for symbol in symbols {
+            if let Some(book) = manager.get_book(&symbol) {
+                view.add_venue(symbol, book);
+            }
+        }
+        view
+    }
+
+    /// Add `book` as `venue`, builder style.
+    #[must_use]
+    pub fn with_venue(mut self, venue: impl Into<String>, book: &'a OrderBook<T>) -> Self {
+        self.add_venue(venue, book);
+        self
+    }
+
+    /// Add `book` as `venue`. A venue already present is replaced.
+    pub fn add_venue(&mut self, venue: impl Into<String>, book: &'a OrderBook<T>) {
+        let venue = venue.into();
+        match self.venues.iter_mut().find(|(name, _)| *name == venue) {
+            Some(entry) => entry.1 = book,
+            None => self.venues.push((venue, book)),
+        }
+    }
+
+    /// Venue names, in the order they were added.
+    pub fn venues(&self) -> impl Iterator<Item = &str> {
+        self.venues.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// The book behind `venue`.
+    #[must_use]
+    pub fn book(&self, venue: &str) -> Option<&'a OrderBook<T>> {
+        self.venues
+            .iter()
+            .find(|(name, _)| name == venue)
+            .map(|(_, book)| *book)
+    }
+
+    /// The best `depth` consolidated levels of `side`, best first.
+    #[must_use]
+    pub fn levels(&self, side: Side, depth: usize) -> Vec<ConsolidatedLevel> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        let mut merged: BTreeMap<u128, ConsolidatedLevel> = BTreeMap::new();
+        for (venue, book) in &self.venues {
+            // The best `depth` merged prices lie within every venue's own
+            // best `depth`.
+            for entry in best_levels(book, side).take(depth) {
+                let level = entry.value();
+                let quantity = level.total_quantity().unwrap_or(0);
+                let visible_quantity = level.visible_quantity();
+                let order_count = level.order_count();
+                let entry = merged
+                    .entry(level.price())
+                    .or_insert_with(|| ConsolidatedLevel {
+                        price: level.price(),
+                        visible_quantity: 0,
+                        quantity: 0,
+                        order_count: 0,
+                        venues: Vec::new(),
+                    });
+                entry.visible_quantity = entry.visible_quantity.saturating_add(visible_quantity);
+                entry.quantity = entry.quantity.saturating_add(quantity);
+                entry.order_count += order_count;
+                entry.venues.push(VenueLevel {
+                    venue: venue.clone(),
+                    visible_quantity,
+                    quantity,
+                    order_count,
+                });
+            }
+        }
+        match side {
+            Side::Buy => merged.into_values().rev().take(depth).collect(),
+            Side::Sell => merged.into_values().take(depth).collect(),
+        }
+    }
+
+    /// The highest bid over all venues, with every venue quoting it.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<ConsolidatedLevel> {
+        self.levels(Side::Buy, 1).pop()
+    }
+
+    /// The lowest ask over all venues, with every venue quoting it.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<ConsolidatedLevel> {
+        self.levels(Side::Sell, 1).pop()
+    }
+
+    /// Consolidated ask minus consolidated bid. `None` if either side is
+    /// empty or the venues are crossed against each other.
+    #[must_use]
+    pub fn spread(&self) -> Option<u128> {
+        let bid = self.best_price(Side::Buy)?;
+        let ask = self.best_price(Side::Sell)?;
+        ask.checked_sub(bid)
+    }
+
+    /// `true` if one venue's best bid is at or above another venue's best
+    /// ask, an arbitrage a router could take.
+    #[must_use]
+    pub fn is_crossed(&self) -> bool {
+        matches!(
+            (self.best_price(Side::Buy), self.best_price(Side::Sell)),
+            (Some(bid), Some(ask)) if bid >= ask
+        )
+    }
+
+    /// Best price of `side` over all venues, without building the level.
+    fn best_price(&self, side: Side) -> Option<u128> {
+        let prices = self
+            .venues
+            .iter()
+            .filter_map(|(_, book)| best_levels(book, side).next().map(|entry| *entry.key()));
+        match side {
+            Side::Buy => prices.max(),
+            Side::Sell => prices.min(),
+        }
+    }
+}
+
+impl<T> Default for ConsolidatedBook<'_, T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Levels of `side` of `book`, best first.
+fn best_levels<T>(book: &OrderBook<T>, side: Side) -> PriceLevelIter<'_>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let levels = match side {
+        Side::Buy => &book.bids,
+        Side::Sell => &book.asks,
+    };
+    side_range(levels, side, (Bound::Unbounded, Bound::Unbounded))
+}
@@ -0,0 +1,273 @@
+//! Venue-wide dead-man's switch.
+//!
+//! Where [cancel-on-disconnect](crate::orderbook::cancel_on_disconnect)
+//! watches one client session, a [`DeadMansSwitch`] watches the operator
+//! or gateway driving a whole [book manager](crate::orderbook::manager):
+//! once [armed](DeadMansSwitch::arm), it must be re-armed before the
+//! duration runs out. When it lapses, the next
+//! [`BookManagerStd::check_dead_mans_switch`](crate::orderbook::manager::BookManagerStd::check_dead_mans_switch)
+//! engages the kill switch of every managed book, leaving them cancel-only,
+//! and cancels the resting orders of the switch's
+//! [purge users](DeadMansSwitch::with_purge_users) with
+//! [`CancelReason::DeadMansSwitch`].
+//!
+//! The switch is a cheap, cloneable handle sharing one deadline, so the
+//! task that refreshes it need not own the manager. Under Tokio,
+//! [`DeadMansSwitch::expired`] resolves once the switch lapses, which lets
+//! a watchdog task await the trip instead of polling.
+//!
+//! A tripped switch is disarmed. The books stay cancel-only until each
+//! kill switch is released with
+//! [`OrderBook::release_kill_switch`], and the switch is armed again.
+
+use super::book::OrderBook;
+use super::clock::{Clock, MonotonicClock};
+use super::mass_cancel::MassCancelResult;
+use super::order_state::CancelReason;
+use pricelevel::Hash32;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Books put in cancel-only mode by a lapsed [`DeadMansSwitch`], as
+/// returned by
+/// [`BookManagerStd::check_dead_mans_switch`](crate::orderbook::manager::BookManagerStd::check_dead_mans_switch).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadMansSwitchTrip {
+    /// Switch clock time the switch lapsed, in milliseconds
+    pub deadline_ms: u64,
+    /// Switch clock time the trip was processed, in milliseconds
+    pub timestamp_ms: u64,
+    /// Orders of the purge users cancelled, keyed by symbol
+    pub purged: HashMap<String, MassCancelResult>,
+}
+
+#[derive(Debug)]
+struct SwitchState {
+    clock: Arc<dyn Clock>,
+    /// Clock time the switch lapses; zero while disarmed.
+    deadline_ms: AtomicU64,
+    purge_users: Mutex<Vec<Hash32>>,
+    /// Wakes [`DeadMansSwitch::expired`] when the switch is armed.
+    armed: Notify,
+}
+
+/// Handle to a dead-man's switch. Clones share the same deadline.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+/// use orderbook_rs::{Clock, DeadMansSwitch, ReplayClock};
+/// use pricelevel::{Hash32, Id, Side, TimeInForce};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let clock = Arc::new(ReplayClock::new(0));
+/// let gateway = Hash32::new([1; 32]);
+/// let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+/// mgr.add_book("BTC/USD")?;
+/// mgr.set_dead_mans_switch(
+///     DeadMansSwitch::with_clock(Arc::clone(&clock) as Arc<dyn Clock>)
+///         .with_purge_users(vec![gateway]),
+/// );
+/// if let Some(book) = mgr.get_book("BTC/USD") {
+///     book.add_limit_order_with_user(Id::from_u64(1), 100, 5, Side::Buy, TimeInForce::Gtc, gateway, None)?;
+/// }
+///
+/// let switch = mgr.dead_mans_switch().clone();
+/// switch.arm(Duration::from_secs(5));
+/// clock.advance_to(4_000);
+/// assert!(mgr.check_dead_mans_switch().is_none());
+///
+/// clock.advance_to(5_000);
+/// let trip = mgr.check_dead_mans_switch().expect("lapsed");
+/// assert_eq!(trip.purged["BTC/USD"].cancelled_count(), 1);
+/// assert!(mgr.get_book("BTC/USD").is_some_and(|book| book.is_kill_switch_engaged()));
+/// assert!(!switch.is_armed());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadMansSwitch {
+    state: Arc<SwitchState>,
+}
+
+impl DeadMansSwitch {
+    /// A disarmed switch on the wall clock, with no purge users.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(MonotonicClock))
+    }
+
+    /// A disarmed switch measuring its deadline on `clock`.
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            state: Arc::new(SwitchState {
+                clock,
+                deadline_ms: AtomicU64::new(0),
+                purge_users: Mutex::new(Vec::new()),
+                armed: Notify::new(),
+            }),
+        }
+    }
+
+    /// Set the users whose orders are cancelled when the switch lapses.
+    #[must_use]
+    pub fn with_purge_users(self, users: Vec<Hash32>) -> Self {
+        self.set_purge_users(users);
+        self
+    }
+
+    /// Replace the users whose orders are cancelled when the switch
+    /// lapses.
+    pub fn set_purge_users(&self, users: Vec<Hash32>) {
+        *self.purge_users_guard() = users;
+    }
+
+    /// Users whose orders are cancelled when the switch lapses.
+    #[must_use]
+    pub fn purge_users(&self) -> Vec<Hash32> {
+        self.purge_users_guard().clone()
+    }
+
+    /// Arm the switch to lapse `duration` from now, or push back the
+    /// deadline of an armed switch. Call it again before the deadline to
+    /// keep the books trading.
+    pub fn arm(&self, duration: Duration) {
+        let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let deadline_ms = self
+            .state
+            .clock
+            .now_millis()
+            .as_u64()
+            .saturating_add(duration_ms)
+            .max(1);
+        self.state.deadline_ms.store(deadline_ms, Ordering::Release);
+        self.state.armed.notify_one();
+    }
+
+    /// Disarm the switch so it never lapses until armed again.
+    pub fn disarm(&self) {
+        self.state.deadline_ms.store(0, Ordering::Release);
+    }
+
+    /// Whether the switch is armed.
+    #[must_use]
+    pub fn is_armed(&self) -> bool {
+        self.deadline_ms().is_some()
+    }
+
+    /// Clock time the armed switch lapses, in milliseconds, or `None`
+    /// while disarmed.
+    #[must_use]
+    pub fn deadline_ms(&self) -> Option<u64> {
+        match self.state.deadline_ms.load(Ordering::Acquire) {
+            0 => None,
+            deadline_ms => Some(deadline_ms),
+        }
+    }
+
+    /// Wait until the switch lapses, returning its deadline. Does not
+    /// trip anything itself: the watchdog awaiting it then calls
+    /// [`BookManagerTokio::check_dead_mans_switch`](crate::orderbook::manager::BookManagerTokio::check_dead_mans_switch).
+    ///
+    /// Sleeps in wall-clock time, re-reading the switch clock on waking,
+    /// and waits for the switch to be armed while it is disarmed. Meant
+    /// for one watchdog per switch.
+    pub async fn expired(&self) -> u64 {
+        loop {
+            let Some(deadline_ms) = self.deadline_ms() else {
+                self.state.armed.notified().await;
+                continue;
+            };
+            let now = self.state.clock.now_millis().as_u64();
+            if now >= deadline_ms {
+                return deadline_ms;
+            }
+            tokio::time::sleep(Duration::from_millis(deadline_ms - now)).await;
+        }
+    }
+
+    /// Disarm the switch if it has lapsed, returning its deadline and the
+    /// time it was noticed. Only one caller observes each lapse.
+    pub(crate) fn take_lapsed(&self) -> Option<(u64, u64)> {
+        let deadline_ms = self.deadline_ms()?;
+        let now = self.state.clock.now_millis().as_u64();
+        if now < deadline_ms {
+            return None;
+        }
+        self.state
+            .deadline_ms
+            .compare_exchange(deadline_ms, 0, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| (deadline_ms, now))
+    }
+
+    fn purge_users_guard(&self) -> std::sync::MutexGuard<'_, Vec<Hash32>> {
+        // A plain list of users, which a panic cannot leave half-written,
+        // so a poisoned lock is recovered.
+        self.state
+            .purge_users
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Default for DeadMansSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Engage the kill switch, then cancel every resting order of
+    /// `purge_users` with [`CancelReason::DeadMansSwitch`].
+    pub(crate) fn trip_dead_mans_switch(&self, purge_users: &[Hash32]) -> MassCancelResult {
+        self.engage_kill_switch();
+        // #209: shared submit gate, as for the other mass cancels.
+        let _gate = self.submit_gate_read();
+        let order_ids: Vec<_> = purge_users
+            .iter()
+            .filter_map(|user_id| self.user_orders.remove(user_id))
+            .flat_map(|(_, ids)| ids)
+            .collect();
+        let purged = self.cancel_order_batch_with_reason(&order_ids, CancelReason::DeadMansSwitch);
+        warn!(
+            symbol = %self.symbol,
+            purged = purged.cancelled_count(),
+            "dead man's switch lapsed; book is cancel-only"
+        );
+        purged
+    }
+}
+
+/// Trip `switch` across `books` if it has lapsed.
+pub(crate) fn trip_books<T>(
+    books: &HashMap<String, OrderBook<T>>,
+    switch: &DeadMansSwitch,
+) -> Option<DeadMansSwitchTrip>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let (deadline_ms, timestamp_ms) = switch.take_lapsed()?;
+    let purge_users = switch.purge_users();
+    let purged = books
+        .iter()
+        .map(|(symbol, book)| (symbol.clone(), book.trip_dead_mans_switch(&purge_users)))
+        .collect();
+    Some(DeadMansSwitchTrip {
+        deadline_ms,
+        timestamp_ms,
+        purged,
+    })
+}
@@ -0,0 +1,58 @@
+//! Which resting quantity the depth analytics count.
+//!
+//! A price level holds displayed quantity and, for iceberg and reserve
+//! orders, hidden quantity that is matched but not shown. The depth
+//! analytics ([`OrderBook::vwap`](crate::OrderBook::vwap),
+//! [`OrderBook::market_impact`](crate::OrderBook::market_impact),
+//! [`OrderBook::total_depth_at_levels`](crate::OrderBook::total_depth_at_levels)
+//! and their neighbours) count both by default. Each has a `_with_mode`
+//! variant taking a [`DepthMode`]; [`DepthMode::Visible`] restricts it to
+//! what a taker can see on the book, for pre-trade analysis that must not
+//! rely on liquidity it cannot observe.
+
+use super::snapshot::LevelAggregate;
+use pricelevel::PriceLevel;
+use serde::{Deserialize, Serialize};
+
+/// The quantity of a price level counted by the depth analytics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DepthMode {
+    /// Only the displayed quantity.
+    Visible,
+
+    /// Displayed plus hidden quantity: everything a sweep would fill
+    /// (default).
+    #[default]
+    Total,
+}
+
+impl DepthMode {
+    /// The quantity of `level` counted in this mode.
+    #[must_use]
+    #[inline]
+    pub fn level_quantity(self, level: &PriceLevel) -> u64 {
+        match self {
+            DepthMode::Visible => level.visible_quantity(),
+            DepthMode::Total => level.total_quantity().unwrap_or(0),
+        }
+    }
+
+    /// The quantity of an aggregated `level` counted in this mode.
+    #[must_use]
+    #[inline]
+    pub fn aggregate_quantity(self, level: &LevelAggregate) -> u64 {
+        match self {
+            DepthMode::Visible => level.visible_quantity,
+            DepthMode::Total => level.total_quantity(),
+        }
+    }
+}
+
+impl std::fmt::Display for DepthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepthMode::Visible => write!(f, "Visible"),
+            DepthMode::Total => write!(f, "Total"),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Compact best-N depth quote for outbound market data.
+//!
+//! [`OrderBook::depth_quote`] captures the best `N` levels of each side in
+//! a fixed-size, `Copy` [`DepthQuote`]. It never touches the heap, so it can
+//! be built on the publishing path and copied straight into an outbound
+//! message (UDP multicast, ITCH-style feeds). `N` is a const generic, so
+//! the quote's layout is fixed at compile time: `depth_quote::<3>()` is
+//! the classic BBO plus two levels.
+//!
+//! Both sides are read in one pass and stamped with the cache update
+//! sequence, exactly like [`OrderBook::top_of_book`]: a read that overlaps
+//! a book mutation is retried, so a quote never mixes levels from before
+//! and after a change.
+
+use super::book::OrderBook;
+use crossbeam_skiplist::map::Entry;
+use pricelevel::PriceLevel;
+use std::sync::Arc;
+
+/// One price level of a [`DepthQuote`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuoteLevel {
+    /// Level price, in price units
+    pub price: u128,
+    /// Visible plus hidden quantity resting at the level
+    pub quantity: u64,
+    /// Number of orders resting at the level
+    pub order_count: u32,
+}
+
+/// The best `N` levels of each side, best first, in a fixed-size value.
+///
+/// Entries past [`Self::bid_depth`] / [`Self::ask_depth`] are
+/// [`QuoteLevel::default`]; [`Self::bids`] and [`Self::asks`] return only
+/// the filled ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthQuote<const N: usize> {
+    /// Bid levels, highest price first
+    pub bid_levels: [QuoteLevel; N],
+    /// Ask levels, lowest price first
+    pub ask_levels: [QuoteLevel; N],
+    /// Filled entries of `bid_levels`
+    pub bid_depth: usize,
+    /// Filled entries of `ask_levels`
+    pub ask_depth: usize,
+    /// Cache update sequence the quote was read at. Equal values come
+    /// from the same book state, and match [`TopOfBook::update_seq`](super::TopOfBook::update_seq).
+    pub update_seq: u64,
+}
+
+impl<const N: usize> Default for DepthQuote<N> {
+    fn default() -> Self {
+        Self {
+            bid_levels: [QuoteLevel::default(); N],
+            ask_levels: [QuoteLevel::default(); N],
+            bid_depth: 0,
+            ask_depth: 0,
+            update_seq: 0,
+        }
+    }
+}
+
+impl<const N: usize> DepthQuote<N> {
+    /// The filled bid levels, best first.
+    #[must_use]
+    pub fn bids(&self) -> &[QuoteLevel] {
+        &self.bid_levels[..self.bid_depth]
+    }
+
+    /// The filled ask levels, best first.
+    #[must_use]
+    pub fn asks(&self) -> &[QuoteLevel] {
+        &self.ask_levels[..self.ask_depth]
+    }
+
+    /// The best bid, if any.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<QuoteLevel> {
+        self.bids().first().copied()
+    }
+
+    /// The best ask, if any.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<QuoteLevel> {
+        self.asks().first().copied()
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// The best `N` levels of each side with quantity and order count, as
+    /// one consistent, allocation-free read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// for (price, side) in [(99, Side::Buy), (100, Side::Buy), (101, Side::Sell)] {
+    ///     book.add_limit_order(Id::new_uuid(), price, 5, side, TimeInForce::Gtc, None)?;
+    /// }
+    ///
+    /// let quote = book.depth_quote::<3>();
+    /// assert_eq!(quote.bids().len(), 2);
+    /// assert_eq!(quote.best_bid().map(|level| level.price), Some(100));
+    /// assert_eq!(quote.asks()[0].order_count, 1);
+    /// assert_eq!(quote.update_seq, book.top_of_book().update_seq);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    #[must_use]
+    pub fn depth_quote<const N: usize>(&self) -> DepthQuote<N> {
+        self.cache
+            .read_stable(|| self.compute_depth_quote(), |quote| quote.update_seq)
+            .0
+    }
+
+    /// Read the best `N` levels of each side, stamped with the cache update
+    /// sequence observed before the read.
+    fn compute_depth_quote<const N: usize>(&self) -> DepthQuote<N> {
+        let mut quote = DepthQuote {
+            update_seq: self.cache.update_seq(),
+            ..DepthQuote::default()
+        };
+        for (slot, entry) in quote.bid_levels.iter_mut().zip(self.bids.iter().rev()) {
+            *slot = quote_level(entry);
+            quote.bid_depth += 1;
+        }
+        for (slot, entry) in quote.ask_levels.iter_mut().zip(self.asks.iter()) {
+            *slot = quote_level(entry);
+            quote.ask_depth += 1;
+        }
+        quote
+    }
+}
+
+fn quote_level(entry: Entry<'_, u128, Arc<PriceLevel>>) -> QuoteLevel {
+    let level = entry.value();
+    QuoteLevel {
+        price: *entry.key(),
+        quantity: level.total_quantity().unwrap_or(0),
+        order_count: u32::try_from(level.order_count()).unwrap_or(u32::MAX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricelevel::{Id, Side, TimeInForce};
+
+    #[test]
+    fn test_depth_quote_fills_best_first_and_pads() {
+        let book = OrderBook::<()>::new("TEST");
+        for (id, price, side) in [
+            (1, 98, Side::Buy),
+            (2, 100, Side::Buy),
+            (3, 100, Side::Buy),
+            (4, 99, Side::Buy),
+            (5, 101, Side::Sell),
+        ] {
+            assert!(
+                book.add_limit_order(Id::from_u64(id), price, 2, side, TimeInForce::Gtc, None)
+                    .is_ok()
+            );
+        }
+
+        let quote = book.depth_quote::<2>();
+        assert_eq!(
+            quote.bids(),
+            [
+                QuoteLevel {
+                    price: 100,
+                    quantity: 4,
+                    order_count: 2
+                },
+                QuoteLevel {
+                    price: 99,
+                    quantity: 2,
+                    order_count: 1
+                },
+            ]
+        );
+        assert_eq!(quote.asks().len(), 1);
+        assert_eq!(quote.ask_levels[1], QuoteLevel::default());
+        assert_eq!(book.depth_quote::<0>().bids(), []);
+        assert!(DepthQuote::<4>::default().best_ask().is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! Constant-time per-side depth totals for `OrderBook<T>`.
+//!
+//! [`DepthTotals`] keeps the total resting quantity (visible plus hidden)
+//! and order count of each side in atomics, so whole-book queries such as
+//! [`OrderBook::buy_sell_pressure`](crate::OrderBook::buy_sell_pressure)
+//! do not walk every level.
+//!
+//! Totals are maintained by reconciliation rather than per-operation
+//! deltas: after mutating a level, the book calls [`DepthTotals::reconcile`]
+//! with it, which reads the level's own counters and applies the
+//! difference from the value last accounted for that price. Each price is
+//! read and reconciled under its map entry lock, so two threads mutating the same
+//! level never double-count, and effects a delta could not see (hidden
+//! quantity dropped with a fully consumed reserve order, a fill racing a
+//! resize) are still picked up. Once the book is quiescent the totals equal
+//! the sum over its levels.
+
+use crossbeam::utils::CachePadded;
+use dashmap::DashMap;
+use pricelevel::{PriceLevel, Side};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Running totals of one side.
+#[derive(Debug, Default)]
+struct SideTotals {
+    quantity: AtomicU64,
+    order_count: AtomicU64,
+    /// Price → `(quantity, order_count)` last folded into the totals
+    accounted: DashMap<u128, (u64, u64)>,
+}
+
+impl SideTotals {
+    fn reconcile(&self, level: &PriceLevel) {
+        let entry = self.accounted.entry(level.price());
+        // Read the level under the entry lock: a read taken before it could
+        // be older than one a racing reconcile already applied, and would
+        // then overwrite the newer value.
+        let quantity = level
+            .visible_quantity()
+            .saturating_add(level.hidden_quantity());
+        let order_count = level.order_count() as u64;
+        let current = (quantity, order_count);
+
+        let previous = match entry {
+            dashmap::Entry::Occupied(mut entry) => {
+                let previous = *entry.get();
+                if current == (0, 0) {
+                    entry.remove();
+                } else {
+                    entry.insert(current);
+                }
+                previous
+            }
+            dashmap::Entry::Vacant(entry) => {
+                if current != (0, 0) {
+                    entry.insert(current);
+                }
+                (0, 0)
+            }
+        };
+        // Applied while the entry lock is held (`previous` was read under
+        // it), so every price contributes a value it actually had.
+        Self::apply(&self.quantity, previous.0, current.0);
+        Self::apply(&self.order_count, previous.1, current.1);
+    }
+
+    fn apply(total: &AtomicU64, previous: u64, current: u64) {
+        if current >= previous {
+            total.fetch_add(current - previous, Ordering::AcqRel);
+        } else {
+            total.fetch_sub(previous - current, Ordering::AcqRel);
+        }
+    }
+
+    fn clear(&self) {
+        self.accounted.clear();
+        self.quantity.store(0, Ordering::Release);
+        self.order_count.store(0, Ordering::Release);
+    }
+}
+
+/// Per-side resting quantity and order count, readable in O(1). Each side
+/// is cache-line padded so bid and ask writers do not contend.
+#[derive(Debug, Default)]
+pub struct DepthTotals {
+    bids: CachePadded<SideTotals>,
+    asks: CachePadded<SideTotals>,
+}
+
+impl DepthTotals {
+    /// Create empty totals.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create empty totals whose per-price index holds `levels` levels per
+    /// side without reallocating.
+    #[must_use]
+    pub fn with_level_capacity(levels: usize) -> Self {
+        let side = || SideTotals {
+            accounted: DashMap::with_capacity(levels),
+            ..SideTotals::default()
+        };
+        Self {
+            bids: CachePadded::new(side()),
+            asks: CachePadded::new(side()),
+        }
+    }
+
+    /// Number of levels per side the per-price index holds without
+    /// reallocating, the smaller of the two sides.
+    #[must_use]
+    pub fn level_capacity(&self) -> usize {
+        self.bids
+            .accounted
+            .capacity()
+            .min(self.asks.accounted.capacity())
+    }
+
+    /// Fold the current state of `level` on `side` into the totals. Call
+    /// after every mutation of a level, including the one that empties it.
+    pub fn reconcile(&self, side: Side, level: &PriceLevel) {
+        self.side(side).reconcile(level);
+    }
+
+    /// Total visible plus hidden quantity resting on `side`.
+    #[must_use]
+    pub fn quantity(&self, side: Side) -> u64 {
+        self.side(side).quantity.load(Ordering::Acquire)
+    }
+
+    /// Number of orders resting on `side`.
+    #[must_use]
+    pub fn order_count(&self, side: Side) -> usize {
+        self.side(side).order_count.load(Ordering::Acquire) as usize
+    }
+
+    /// Reset both sides to empty.
+    pub fn clear(&self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    fn side(&self, side: Side) -> &SideTotals {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, TimeInForce, TimestampMs};
+
+    fn order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_tracks_level_changes_and_is_idempotent() {
+        let totals = DepthTotals::new();
+        let level = PriceLevel::new(100);
+        level.add_order(order(1, 10)).expect("add");
+        level.add_order(order(2, 5)).expect("add");
+
+        totals.reconcile(Side::Buy, &level);
+        totals.reconcile(Side::Buy, &level);
+        assert_eq!(totals.quantity(Side::Buy), 15);
+        assert_eq!(totals.order_count(Side::Buy), 2);
+        assert_eq!(totals.quantity(Side::Sell), 0);
+
+        level
+            .update_order(pricelevel::OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .expect("cancel");
+        totals.reconcile(Side::Buy, &level);
+        assert_eq!(totals.quantity(Side::Buy), 5);
+        assert_eq!(totals.order_count(Side::Buy), 1);
+
+        totals.clear();
+        assert_eq!(totals.quantity(Side::Buy), 0);
+        assert_eq!(totals.order_count(Side::Buy), 0);
+    }
+}
@@ -0,0 +1,143 @@
+//! Read-mostly top-N depth view, republished on every book change.
+//!
+//! [`OrderBook::level_aggregates`] rebuilds its view lazily on the first
+//! read after a mutation, and [`OrderBook::create_snapshot`] walks the skip
+//! maps on every call, so a read-heavy workload keeps traversing the
+//! concurrent skiplists while writers modify them. With the depth view
+//! enabled, every mutation instead rebuilds the top `depth` levels once,
+//! after its last write, and publishes them as an immutable
+//! [`LevelAggregates`] behind an `Arc` swap, RCU style. Readers take the
+//! current `Arc` and read plain slices; a reader holding an older view
+//! keeps a consistent copy while the next one is published.
+//!
+//! Views are published in update-sequence order, so a slow writer never
+//! replaces a newer view with an older one. While mutations are in flight
+//! the view may lag the book; once the book is quiescent it reflects the
+//! book's final state.
+
+use super::book::OrderBook;
+use super::snapshot::LevelAggregates;
+use std::sync::{Arc, RwLock};
+
+/// Holder of the published view. `depth == 0` means disabled.
+#[derive(Debug, Default)]
+pub struct DepthView {
+    depth: usize,
+    current: RwLock<Arc<LevelAggregates>>,
+}
+
+impl DepthView {
+    /// Levels per side kept in the view, `0` when disabled.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn load(&self) -> Arc<LevelAggregates> {
+        let guard = match self.current.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        Arc::clone(&guard)
+    }
+
+    /// Replace the published view with `view` unless the published one was
+    /// built at a later update sequence.
+    fn publish(&self, view: LevelAggregates) {
+        let mut guard = match self.current.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if view.update_seq >= guard.update_seq {
+            *guard = Arc::new(view);
+        }
+    }
+
+    fn reset(&mut self, depth: usize) {
+        self.depth = depth;
+        *self.current.get_mut().unwrap_or_else(|p| p.into_inner()) =
+            Arc::new(LevelAggregates::default());
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Keep an immutable view of the top `depth` levels per side,
+    /// republished after every book change and read with
+    /// [`Self::depth_view`]. Each mutation then also pays an O(depth)
+    /// rebuild, so enable it for read-heavy workloads only. `0` disables
+    /// the view.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let mut book = OrderBook::<()>::new("BTC/USD");
+    /// book.enable_depth_view(10);
+    /// book.add_limit_order(Id::new(), 100, 5, Side::Buy, TimeInForce::Gtc, None).unwrap();
+    ///
+    /// let view = book.depth_view().unwrap();
+    /// assert_eq!(view.bids[0].price, 100);
+    /// assert!(view.asks.is_empty());
+    /// ```
+    pub fn enable_depth_view(&mut self, depth: usize) {
+        self.depth_view.reset(depth);
+        self.refresh_depth_view();
+    }
+
+    /// Stop maintaining the depth view.
+    pub fn disable_depth_view(&mut self) {
+        self.depth_view.reset(0);
+    }
+
+    /// The latest published depth view, or `None` if the view is not
+    /// enabled. O(1): clones an `Arc`, never touches the skip maps.
+    #[must_use]
+    pub fn depth_view(&self) -> Option<Arc<LevelAggregates>> {
+        (self.depth_view.depth() > 0).then(|| self.depth_view.load())
+    }
+
+    /// Rebuild and publish the depth view if it is enabled. Called after
+    /// the last skip-map write of every mutation.
+    pub(super) fn refresh_depth_view(&self) {
+        let depth = self.depth_view.depth();
+        if depth > 0 {
+            self.depth_view
+                .publish(self.compute_level_aggregates(depth));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::snapshot::LevelAggregate;
+
+    fn view(update_seq: u64, price: u128) -> LevelAggregates {
+        LevelAggregates {
+            bids: vec![LevelAggregate {
+                price,
+                visible_quantity: 1,
+                hidden_quantity: 0,
+                order_count: 1,
+            }],
+            asks: Vec::new(),
+            update_seq,
+        }
+    }
+
+    #[test]
+    fn test_older_view_never_replaces_a_newer_one() {
+        let holder = DepthView::default();
+        holder.publish(view(5, 100));
+        let held = holder.load();
+        holder.publish(view(4, 99));
+        assert_eq!(holder.load().bids[0].price, 100);
+        holder.publish(view(6, 101));
+        assert_eq!(holder.load().bids[0].price, 101);
+        // Readers keep the view they loaded.
+        assert_eq!(held.bids[0].price, 100);
+    }
+}
@@ -1,6 +1,9 @@
 //! Order book error types
 
 use pricelevel::{Hash32, PriceLevelError, Side};
+use serde::de;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
 /// Errors that can occur within the OrderBook
@@ -52,17 +55,66 @@ pub enum OrderBookError {
         spent: u128,
     },
 
-    /// Operation not permitted for specified order type
+    /// Operation not permitted for specified order type. Engine paths
+    /// report through the structured variants below; this variant remains
+    /// for callers that need a free-form rejection.
     InvalidOperation {
         /// Description of the error
         message: String,
     },
 
+    /// Price update rejected because the new price equals the order's
+    /// current price.
+    PriceUnchanged {
+        /// The order that was to be repriced
+        order_id: pricelevel::Id,
+        /// The order's current (and requested) price
+        price: u128,
+    },
+
+    /// Order rejected at admission because its `Gtd` / `Day` deadline has
+    /// already passed.
+    OrderExpired {
+        /// The expired order
+        order_id: pricelevel::Id,
+    },
+
+    /// Resting the order would overflow the aggregate `u64` quantity of
+    /// its price level.
+    LevelQuantityOverflow {
+        /// The order that could not rest
+        order_id: pricelevel::Id,
+        /// Price of the level that would overflow
+        price: u128,
+    },
+
+    /// Book configuration rejected at construction time, e.g. by
+    /// `OrderBookBuilder::build`.
+    InvalidConfiguration {
+        /// Description of the rejected setting
+        message: String,
+    },
+
     /// New flow (submit / modify / replace) is rejected because the
     /// kill switch is engaged. Cancel and mass-cancel paths still
     /// operate so operators can drain the book in an orderly way.
     KillSwitchActive,
 
+    /// New flow rejected because the submitting user is blocked by
+    /// `OrderBook::block_user`. Cancels are still accepted.
+    UserBlocked {
+        /// The blocked user
+        user_id: pricelevel::Hash32,
+    },
+
+    /// New flow rejected by the book's rate limiter: the submitter's or
+    /// the book's token bucket is empty. Cancels are never throttled.
+    RateLimited {
+        /// Milliseconds until the submission would be admitted
+        /// (`u64::MAX` if the bucket never refills)
+        retry_after_ms: u64,
+    },
+
     /// Error while serializing snapshot data
     SerializationError {
         /// Underlying error message
@@ -83,6 +135,38 @@ pub enum OrderBookError {
         actual: String,
     },
 
+    /// Snapshot format version outside the range this build can read
+    UnsupportedSnapshotVersion {
+        /// Version recorded in the snapshot
+        version: u32,
+        /// Oldest readable version
+        min_supported: u32,
+        /// Newest readable version
+        max_supported: u32,
+    },
+
+    /// Snapshot taken from a book with a different symbol
+    SnapshotSymbolMismatch {
+        /// Symbol recorded in the snapshot
+        snapshot_symbol: String,
+        /// Symbol of the book being restored
+        book_symbol: String,
+    },
+
+    /// Snapshot holds two levels at the same price on one side
+    DuplicateSnapshotLevel {
+        /// Side holding the duplicate levels
+        side: Side,
+        /// Duplicated price
+        price: u128,
+    },
+
+    /// Perpetual index price is not positive and finite
+    InvalidIndexPrice {
+        /// The rejected index price
+        index_price: f64,
+    },
+
     /// Order price is not a multiple of the configured tick size
     InvalidTickSize {
         /// The order price that failed validation
@@ -109,6 +193,18 @@ pub enum OrderBookError {
         max: Option<u64>,
     },
 
+    /// Order notional (`price × total quantity`) is outside the allowed
+    /// min/max range. A product that overflows `u128` is reported as
+    /// `u128::MAX`.
+    OrderNotionalOutOfRange {
+        /// The order notional that failed validation
+        notional: u128,
+        /// The configured minimum order notional, if any
+        min: Option<u128>,
+        /// The configured maximum order notional, if any
+        max: Option<u128>,
+    },
+
     /// Order rejected because its `order_id` duplicates an order that is
     /// already resting on the book. Admitting it would overwrite the
     /// existing order's location and orphan it (the prior order could no
@@ -120,6 +216,11 @@ pub enum OrderBookError {
     /// not atomic against two concurrent submissions of the same fresh id
     /// on the lock-free admission path — serializing order ids is the
     /// ingress / sequencing layer's responsibility.
+    ///
+    /// Also returned when a duplicate-id window is installed with
+    /// `OrderBook::set_duplicate_order_id_window` and the id was already
+    /// submitted within that window, even if the original order is no
+    /// longer resting. The windowed check is atomic.
     DuplicateOrderId {
         /// The duplicate order ID that was rejected
         order_id: pricelevel::Id,
@@ -205,6 +306,132 @@ pub enum OrderBookError {
         limit_bps: u32,
     },
 
+    /// Limit order rejected because its price deviates from the reference
+    /// price by more than the configured number of ticks. Shares the
+    /// stable wire code `RejectReason::RiskPriceBand` with
+    /// [`Self::RiskPriceBand`].
+    RiskPriceBandTicks {
+        /// Limit price submitted by the caller (raw ticks).
+        submitted: u128,
+        /// Resolved reference price at check time (raw ticks).
+        reference: u128,
+        /// Deviation in whole ticks, rounded down.
+        deviation_ticks: u128,
+        /// Configured maximum allowed deviation in ticks.
+        limit_ticks: u64,
+    },
+
+    /// No fill with this execution id can be busted or corrected: it was
+    /// never emitted, has been evicted from the execution history or
+    /// already busted, or the book keeps no execution history.
+    ExecutionNotFound {
+        /// The execution id that was looked up
+        execution_id: u64,
+    },
+
+    /// A trailing-stop state update targeted a resting order that is not
+    /// a trailing stop.
+    NotTrailingStop {
+        /// The order that was to be updated
+        order_id: pricelevel::Id,
+    },
+
+    /// An operation needed an externally supplied reference price that is
+    /// missing or stale.
+    ReferencePriceUnavailable {
+        /// The reference price that was needed
+        source: crate::orderbook::reference_price::ExternalPriceSource,
+    },
+
+    /// The book does not admit the order in its current trading phase:
+    /// all new flow while closed or halted by the circuit breaker, and
+    /// market or crossing orders during an auction.
+    MarketClosed {
+        /// The session phase the order arrived in
+        phase: crate::orderbook::session::SessionPhase,
+    },
+
+    /// An [`OrderUpdate`](pricelevel::OrderUpdate) converted into an
+    /// [`OrderModification`](crate::OrderModification) was a cancel or a
+    /// replace, which are not modifications.
+    NotAModification {
+        /// The order the update targeted
+        order_id: pricelevel::Id,
+    },
+
+    /// A modification left every field of the resting order unchanged.
+    NothingToModify {
+        /// The order that was to be modified
+        order_id: pricelevel::Id,
+    },
+
+    /// A modification set a hidden quantity on an order that has no hidden
+    /// tranche (anything but an iceberg or reserve order).
+    NoHiddenTranche {
+        /// The order that was to be modified
+        order_id: pricelevel::Id,
+    },
+
+    /// A modification set an immediate time in force (IOC or FOK) on a
+    /// resting order.
+    ImmediateTifOnResting {
+        /// The order that was to be modified
+        order_id: pricelevel::Id,
+        /// The rejected time in force
+        time_in_force: pricelevel::TimeInForce,
+    },
+
+    /// A modification set the displayed quantity of a resting order to
+    /// zero; cancel the order instead.
+    ZeroQuantity {
+        /// The order that was to be modified
+        order_id: pricelevel::Id,
+    },
+
+    /// A partial cancel asked to remove zero, or at least the whole displayed
+    /// quantity, of a resting order; cancel the order instead.
+    InvalidReduction {
+        /// The order that was to be reduced
+        order_id: pricelevel::Id,
+        /// Quantity the caller asked to remove
+        requested: u64,
+        /// Displayed quantity the order had
+        displayed: u64,
+    },
+
+    /// A trade correction asked for a zero quantity; bust the fill instead.
+    CorrectionToZero {
+        /// The execution that was to be corrected
+        execution_id: u64,
+    },
+
+    /// A midpoint order had a zero quantity or a minimum execution size
+    /// above its quantity.
+    InvalidMidpointQuantity {
+        /// The rejected order
+        order_id: pricelevel::Id,
+        /// Quantity of the order
+        quantity: u64,
+        /// Minimum size per execution
+        min_execution_quantity: u64,
+    },
+
+    /// An order tag label was longer than
+    /// [`OrderTag::MAX_LABEL_LEN`](crate::OrderTag::MAX_LABEL_LEN) bytes.
+    OrderTagTooLong {
+        /// Length of the label, in bytes
+        len: usize,
+        /// Longest accepted label, in bytes
+        max: usize,
+    },
+
+    /// A cancel-on-disconnect session was registered with a zero heartbeat
+    /// TTL.
+    ZeroHeartbeatTtl {
+        /// The user whose session was being registered
+        user_id: pricelevel::Hash32,
+    },
+
     /// Failed to publish a trade event to NATS JetStream.
     #[cfg(feature = "nats")]
     NatsPublishError {
@@ -259,6 +486,30 @@ impl fmt::Display for OrderBookError {
             OrderBookError::InvalidOperation { message } => {
                 write!(f, "Invalid operation: {message}")
             }
+            OrderBookError::InvalidConfiguration { message } => {
+                write!(f, "Invalid configuration: {message}")
+            }
+            OrderBookError::PriceUnchanged { order_id, price } => {
+                write!(
+                    f,
+                    "Cannot update price to the same value: order {order_id} already at {price}"
+                )
+            }
+            OrderBookError::OrderExpired { order_id } => {
+                write!(f, "Order has already expired: {order_id}")
+            }
+            OrderBookError::LevelQuantityOverflow { order_id, price } => {
+                write!(
+                    f,
+                    "resting order {order_id} would overflow the aggregate capacity of level {price}"
+                )
+            }
+            OrderBookError::RateLimited { retry_after_ms } => {
+                write!(f, "rate limited: retry after {retry_after_ms} ms")
+            }
+            OrderBookError::UserBlocked { user_id } => {
+                write!(f, "user blocked: new flow from user {user_id} is rejected")
+            }
             OrderBookError::KillSwitchActive => {
                 write!(
                     f,
@@ -277,6 +528,37 @@ impl fmt::Display for OrderBookError {
                     "Checksum mismatch: expected {expected}, but computed {actual}"
                 )
             }
+            OrderBookError::UnsupportedSnapshotVersion {
+                version,
+                min_supported,
+                max_supported,
+            } => {
+                write!(
+                    f,
+                    "Unsupported snapshot version: {version} (supported {min_supported}..={max_supported})"
+                )
+            }
+            OrderBookError::SnapshotSymbolMismatch {
+                snapshot_symbol,
+                book_symbol,
+            } => {
+                write!(
+                    f,
+                    "Snapshot symbol {snapshot_symbol} does not match order book symbol {book_symbol}"
+                )
+            }
+            OrderBookError::DuplicateSnapshotLevel { side, price } => {
+                write!(
+                    f,
+                    "Snapshot contains two {side} levels at the same price {price}"
+                )
+            }
+            OrderBookError::InvalidIndexPrice { index_price } => {
+                write!(
+                    f,
+                    "index price must be positive and finite, got {index_price}"
+                )
+            }
             OrderBookError::InvalidTickSize { price, tick_size } => {
                 write!(
                     f,
@@ -295,10 +577,16 @@ impl fmt::Display for OrderBookError {
                     "order size out of range: quantity {quantity}, min {min:?}, max {max:?}"
                 )
             }
+            OrderBookError::OrderNotionalOutOfRange { notional, min, max } => {
+                write!(
+                    f,
+                    "order notional out of range: notional {notional}, min {min:?}, max {max:?}"
+                )
+            }
             OrderBookError::DuplicateOrderId { order_id } => {
                 write!(
                     f,
-                    "duplicate order id: order {order_id} is already resting on the book"
+                    "duplicate order id: order {order_id} is resting or was recently submitted"
                 )
             }
             OrderBookError::MissingUserId { order_id } => {
@@ -355,6 +643,85 @@ impl fmt::Display for OrderBookError {
                     "risk: submitted price {submitted} deviates {deviation_bps} bps from reference {reference} (limit {limit_bps} bps)"
                 )
             }
+            OrderBookError::RiskPriceBandTicks {
+                submitted,
+                reference,
+                deviation_ticks,
+                limit_ticks,
+            } => {
+                write!(
+                    f,
+                    "risk: submitted price {submitted} deviates {deviation_ticks} ticks from reference {reference} (limit {limit_ticks} ticks)"
+                )
+            }
+            OrderBookError::ExecutionNotFound { execution_id } => {
+                write!(f, "execution not found: {execution_id}")
+            }
+            OrderBookError::NotTrailingStop { order_id } => {
+                write!(f, "order {order_id} is not a trailing stop")
+            }
+            OrderBookError::ReferencePriceUnavailable { source } => {
+                write!(f, "{source} reference price is missing or stale")
+            }
+            OrderBookError::MarketClosed { phase } => {
+                write!(f, "order not accepted in trading phase {phase}")
+            }
+            OrderBookError::NotAModification { order_id } => {
+                write!(
+                    f,
+                    "update of order {order_id} is a cancel or replace, not a modification"
+                )
+            }
+            OrderBookError::NothingToModify { order_id } => {
+                write!(f, "modification of order {order_id} changes nothing")
+            }
+            OrderBookError::NoHiddenTranche { order_id } => {
+                write!(f, "order {order_id} has no hidden quantity to modify")
+            }
+            OrderBookError::ImmediateTifOnResting {
+                order_id,
+                time_in_force,
+            } => {
+                write!(f, "resting order {order_id} cannot become {time_in_force}")
+            }
+            OrderBookError::ZeroQuantity { order_id } => {
+                write!(
+                    f,
+                    "order {order_id} cannot be modified to a zero displayed quantity"
+                )
+            }
+            OrderBookError::InvalidReduction {
+                order_id,
+                requested,
+                displayed,
+            } => {
+                write!(
+                    f,
+                    "cannot reduce order {order_id} by {requested}: it displays {displayed}"
+                )
+            }
+            OrderBookError::CorrectionToZero { execution_id } => {
+                write!(
+                    f,
+                    "cannot correct execution {execution_id} to zero quantity; bust it instead"
+                )
+            }
+            OrderBookError::InvalidMidpointQuantity {
+                order_id,
+                quantity,
+                min_execution_quantity,
+            } => {
+                write!(
+                    f,
+                    "midpoint order {order_id} has quantity {quantity} and minimum execution size {min_execution_quantity}"
+                )
+            }
+            OrderBookError::OrderTagTooLong { len, max } => {
+                write!(f, "order tag label of {len} bytes exceeds {max} bytes")
+            }
+            OrderBookError::ZeroHeartbeatTtl { user_id } => {
+                write!(f, "heartbeat TTL for user {user_id} must be non-zero")
+            }
             #[cfg(feature = "nats")]
             OrderBookError::NatsPublishError { message } => {
                 write!(f, "nats publish error: {message}")
@@ -467,7 +834,58 @@ impl Clone for OrderBookError {
             OrderBookError::InvalidOperation { message } => OrderBookError::InvalidOperation {
                 message: message.clone(),
             },
+            OrderBookError::InvalidConfiguration { message } => {
+                OrderBookError::InvalidConfiguration {
+                    message: message.clone(),
+                }
+            }
+            OrderBookError::PriceUnchanged { order_id, price } => OrderBookError::PriceUnchanged {
+                order_id: *order_id,
+                price: *price,
+            },
+            OrderBookError::OrderExpired { order_id } => OrderBookError::OrderExpired {
+                order_id: *order_id,
+            },
+            OrderBookError::LevelQuantityOverflow { order_id, price } => {
+                OrderBookError::LevelQuantityOverflow {
+                    order_id: *order_id,
+                    price: *price,
+                }
+            }
+            OrderBookError::UnsupportedSnapshotVersion {
+                version,
+                min_supported,
+                max_supported,
+            } => OrderBookError::UnsupportedSnapshotVersion {
+                version: *version,
+                min_supported: *min_supported,
+                max_supported: *max_supported,
+            },
+            OrderBookError::SnapshotSymbolMismatch {
+                snapshot_symbol,
+                book_symbol,
+            } => OrderBookError::SnapshotSymbolMismatch {
+                snapshot_symbol: snapshot_symbol.clone(),
+                book_symbol: book_symbol.clone(),
+            },
+            OrderBookError::DuplicateSnapshotLevel { side, price } => {
+                OrderBookError::DuplicateSnapshotLevel {
+                    side: *side,
+                    price: *price,
+                }
+            }
+            OrderBookError::InvalidIndexPrice { index_price } => {
+                OrderBookError::InvalidIndexPrice {
+                    index_price: *index_price,
+                }
+            }
             OrderBookError::KillSwitchActive => OrderBookError::KillSwitchActive,
+            OrderBookError::RateLimited { retry_after_ms } => OrderBookError::RateLimited {
+                retry_after_ms: *retry_after_ms,
+            },
+            OrderBookError::UserBlocked { user_id } => {
+                OrderBookError::UserBlocked { user_id: *user_id }
+            }
             OrderBookError::SerializationError { message } => OrderBookError::SerializationError {
                 message: message.clone(),
             },
@@ -501,6 +919,13 @@ impl Clone for OrderBookError {
                     max: *max,
                 }
             }
+            OrderBookError::OrderNotionalOutOfRange { notional, min, max } => {
+                OrderBookError::OrderNotionalOutOfRange {
+                    notional: *notional,
+                    min: *min,
+                    max: *max,
+                }
+            }
             OrderBookError::DuplicateOrderId { order_id } => OrderBookError::DuplicateOrderId {
                 order_id: *order_id,
             },
@@ -553,6 +978,78 @@ impl Clone for OrderBookError {
                 deviation_bps: *deviation_bps,
                 limit_bps: *limit_bps,
             },
+            OrderBookError::RiskPriceBandTicks {
+                submitted,
+                reference,
+                deviation_ticks,
+                limit_ticks,
+            } => OrderBookError::RiskPriceBandTicks {
+                submitted: *submitted,
+                reference: *reference,
+                deviation_ticks: *deviation_ticks,
+                limit_ticks: *limit_ticks,
+            },
+            OrderBookError::ExecutionNotFound { execution_id } => {
+                OrderBookError::ExecutionNotFound {
+                    execution_id: *execution_id,
+                }
+            }
+            OrderBookError::NotTrailingStop { order_id } => OrderBookError::NotTrailingStop {
+                order_id: *order_id,
+            },
+            OrderBookError::ReferencePriceUnavailable { source } => {
+                OrderBookError::ReferencePriceUnavailable { source: *source }
+            }
+            OrderBookError::MarketClosed { phase } => {
+                OrderBookError::MarketClosed { phase: *phase }
+            }
+            OrderBookError::NotAModification { order_id } => OrderBookError::NotAModification {
+                order_id: *order_id,
+            },
+            OrderBookError::NothingToModify { order_id } => OrderBookError::NothingToModify {
+                order_id: *order_id,
+            },
+            OrderBookError::NoHiddenTranche { order_id } => OrderBookError::NoHiddenTranche {
+                order_id: *order_id,
+            },
+            OrderBookError::ImmediateTifOnResting {
+                order_id,
+                time_in_force,
+            } => OrderBookError::ImmediateTifOnResting {
+                order_id: *order_id,
+                time_in_force: *time_in_force,
+            },
+            OrderBookError::ZeroQuantity { order_id } => OrderBookError::ZeroQuantity {
+                order_id: *order_id,
+            },
+            OrderBookError::InvalidReduction {
+                order_id,
+                requested,
+                displayed,
+            } => OrderBookError::InvalidReduction {
+                order_id: *order_id,
+                requested: *requested,
+                displayed: *displayed,
+            },
+            OrderBookError::CorrectionToZero { execution_id } => OrderBookError::CorrectionToZero {
+                execution_id: *execution_id,
+            },
+            OrderBookError::InvalidMidpointQuantity {
+                order_id,
+                quantity,
+                min_execution_quantity,
+            } => OrderBookError::InvalidMidpointQuantity {
+                order_id: *order_id,
+                quantity: *quantity,
+                min_execution_quantity: *min_execution_quantity,
+            },
+            OrderBookError::OrderTagTooLong { len, max } => OrderBookError::OrderTagTooLong {
+                len: *len,
+                max: *max,
+            },
+            OrderBookError::ZeroHeartbeatTtl { user_id } => {
+                OrderBookError::ZeroHeartbeatTtl { user_id: *user_id }
+            }
             #[cfg(feature = "nats")]
             OrderBookError::NatsPublishError { message } => OrderBookError::NatsPublishError {
                 message: message.clone(),
@@ -567,6 +1064,661 @@ impl Clone for OrderBookError {
     }
 }
 
+/// Stable numeric code of an [`OrderBookError`] variant.
+///
+/// Unlike [`RejectReason`](crate::orderbook::reject_reason::RejectReason),
+/// which folds several errors into one wire reject and maps internal
+/// failures to `Other(0)`, every [`OrderBookError`] variant has its own
+/// code here, so a gateway can translate any failure into a
+/// protocol-level code without parsing the display string. Codes are
+/// never reused or renumbered; serde encodes the numeric value.
+///
+/// | Variant                         | u16 |
+/// |---------------------------------|-----|
+/// | `PriceLevelError`               | 1   |
+/// | `OrderNotFound`                 | 2   |
+/// | `InvalidPriceLevel`             | 3   |
+/// | `PriceCrossing`                 | 4   |
+/// | `InsufficientLiquidity`         | 5   |
+/// | `InsufficientLiquidityNotional` | 6   |
+/// | `InvalidOperation`              | 7   |
+/// | `InvalidConfiguration`          | 8   |
+/// | `PriceUnchanged`                | 9   |
+/// | `OrderExpired`                  | 10  |
+/// | `LevelQuantityOverflow`         | 11  |
+/// | `KillSwitchActive`              | 12  |
+/// | `UserBlocked`                   | 13  |
+/// | `RateLimited`                   | 14  |
+/// | `SerializationError`            | 15  |
+/// | `DeserializationError`          | 16  |
+/// | `ChecksumMismatch`              | 17  |
+/// | `UnsupportedSnapshotVersion`    | 18  |
+/// | `SnapshotSymbolMismatch`        | 19  |
+/// | `DuplicateSnapshotLevel`        | 20  |
+/// | `InvalidIndexPrice`             | 21  |
+/// | `InvalidTickSize`               | 22  |
+/// | `InvalidLotSize`                | 23  |
+/// | `OrderSizeOutOfRange`           | 24  |
+/// | `OrderNotionalOutOfRange`       | 25  |
+/// | `DuplicateOrderId`              | 26  |
+/// | `QuantityOverflow`              | 27  |
+/// | `MissingUserId`                 | 28  |
+/// | `SelfTradePrevented`            | 29  |
+/// | `RiskMaxOpenOrders`             | 30  |
+/// | `RiskMaxNotional`               | 31  |
+/// | `RiskPriceBand`                 | 32  |
+/// | `RiskPriceBandTicks`            | 33  |
+/// | `NatsPublishError`              | 34  |
+/// | `NatsSerializationError`        | 35  |
+/// | `ExecutionNotFound`             | 36  |
+/// | `NotTrailingStop`               | 37  |
+/// | `ReferencePriceUnavailable`     | 38  |
+/// | `MarketClosed`                  | 39  |
+/// | `NotAModification`              | 40  |
+/// | `NothingToModify`               | 41  |
+/// | `NoHiddenTranche`               | 42  |
+/// | `ImmediateTifOnResting`         | 43  |
+/// | `ZeroQuantity`                  | 44  |
+/// | `InvalidReduction`              | 45  |
+/// | `CorrectionToZero`              | 46  |
+/// | `InvalidMidpointQuantity`       | 47  |
+/// | `OrderTagTooLong`               | 48  |
+/// | `ZeroHeartbeatTtl`              | 49  |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u16)]
+pub enum RejectCode {
+    /// [`OrderBookError::PriceLevelError`]
+    PriceLevelError = 1,
+    /// [`OrderBookError::OrderNotFound`]
+    OrderNotFound = 2,
+    /// [`OrderBookError::InvalidPriceLevel`]
+    InvalidPriceLevel = 3,
+    /// [`OrderBookError::PriceCrossing`]
+    PriceCrossing = 4,
+    /// [`OrderBookError::InsufficientLiquidity`]
+    InsufficientLiquidity = 5,
+    /// [`OrderBookError::InsufficientLiquidityNotional`]
+    InsufficientLiquidityNotional = 6,
+    /// [`OrderBookError::InvalidOperation`]
+    InvalidOperation = 7,
+    /// [`OrderBookError::InvalidConfiguration`]
+    InvalidConfiguration = 8,
+    /// [`OrderBookError::PriceUnchanged`]
+    PriceUnchanged = 9,
+    /// [`OrderBookError::OrderExpired`]
+    OrderExpired = 10,
+    /// [`OrderBookError::LevelQuantityOverflow`]
+    LevelQuantityOverflow = 11,
+    /// [`OrderBookError::KillSwitchActive`]
+    KillSwitchActive = 12,
+    /// [`OrderBookError::UserBlocked`]
+    UserBlocked = 13,
+    /// [`OrderBookError::RateLimited`]
+    RateLimited = 14,
+    /// [`OrderBookError::SerializationError`]
+    SerializationError = 15,
+    /// [`OrderBookError::DeserializationError`]
+    DeserializationError = 16,
+    /// [`OrderBookError::ChecksumMismatch`]
+    ChecksumMismatch = 17,
+    /// [`OrderBookError::UnsupportedSnapshotVersion`]
+    UnsupportedSnapshotVersion = 18,
+    /// [`OrderBookError::SnapshotSymbolMismatch`]
+    SnapshotSymbolMismatch = 19,
+    /// [`OrderBookError::DuplicateSnapshotLevel`]
+    DuplicateSnapshotLevel = 20,
+    /// [`OrderBookError::InvalidIndexPrice`]
+    InvalidIndexPrice = 21,
+    /// [`OrderBookError::InvalidTickSize`]
+    InvalidTickSize = 22,
+    /// [`OrderBookError::InvalidLotSize`]
+    InvalidLotSize = 23,
+    /// [`OrderBookError::OrderSizeOutOfRange`]
+    OrderSizeOutOfRange = 24,
+    /// [`OrderBookError::OrderNotionalOutOfRange`]
+    OrderNotionalOutOfRange = 25,
+    /// [`OrderBookError::DuplicateOrderId`]
+    DuplicateOrderId = 26,
+    /// [`OrderBookError::QuantityOverflow`]
+    QuantityOverflow = 27,
+    /// [`OrderBookError::MissingUserId`]
+    MissingUserId = 28,
+    /// [`OrderBookError::SelfTradePrevented`]
+    SelfTradePrevented = 29,
+    /// [`OrderBookError::RiskMaxOpenOrders`]
+    RiskMaxOpenOrders = 30,
+    /// [`OrderBookError::RiskMaxNotional`]
+    RiskMaxNotional = 31,
+    /// [`OrderBookError::RiskPriceBand`]
+    RiskPriceBand = 32,
+    /// [`OrderBookError::RiskPriceBandTicks`]
+    RiskPriceBandTicks = 33,
+    /// `OrderBookError::NatsPublishError` (`nats` feature)
+    NatsPublishError = 34,
+    /// `OrderBookError::NatsSerializationError` (`nats` feature)
+    NatsSerializationError = 35,
+    /// [`OrderBookError::ExecutionNotFound`]
+    ExecutionNotFound = 36,
+    /// [`OrderBookError::NotTrailingStop`]
+    NotTrailingStop = 37,
+    /// [`OrderBookError::ReferencePriceUnavailable`]
+    ReferencePriceUnavailable = 38,
+    /// [`OrderBookError::MarketClosed`]
+    MarketClosed = 39,
+    /// [`OrderBookError::NotAModification`]
+    NotAModification = 40,
+    /// [`OrderBookError::NothingToModify`]
+    NothingToModify = 41,
+    /// [`OrderBookError::NoHiddenTranche`]
+    NoHiddenTranche = 42,
+    /// [`OrderBookError::ImmediateTifOnResting`]
+    ImmediateTifOnResting = 43,
+    /// [`OrderBookError::ZeroQuantity`]
+    ZeroQuantity = 44,
+    /// [`OrderBookError::InvalidReduction`]
+    InvalidReduction = 45,
+    /// [`OrderBookError::CorrectionToZero`]
+    CorrectionToZero = 46,
+    /// [`OrderBookError::InvalidMidpointQuantity`]
+    InvalidMidpointQuantity = 47,
+    /// [`OrderBookError::OrderTagTooLong`]
+    OrderTagTooLong = 48,
+    /// [`OrderBookError::ZeroHeartbeatTtl`]
+    ZeroHeartbeatTtl = 49,
+}
+
+impl RejectCode {
+    const ALL: [Self; 49] = [
+        Self::PriceLevelError,
+        Self::OrderNotFound,
+        Self::InvalidPriceLevel,
+        Self::PriceCrossing,
+        Self::InsufficientLiquidity,
+        Self::InsufficientLiquidityNotional,
+        Self::InvalidOperation,
+        Self::InvalidConfiguration,
+        Self::PriceUnchanged,
+        Self::OrderExpired,
+        Self::LevelQuantityOverflow,
+        Self::KillSwitchActive,
+        Self::UserBlocked,
+        Self::RateLimited,
+        Self::SerializationError,
+        Self::DeserializationError,
+        Self::ChecksumMismatch,
+        Self::UnsupportedSnapshotVersion,
+        Self::SnapshotSymbolMismatch,
+        Self::DuplicateSnapshotLevel,
+        Self::InvalidIndexPrice,
+        Self::InvalidTickSize,
+        Self::InvalidLotSize,
+        Self::OrderSizeOutOfRange,
+        Self::OrderNotionalOutOfRange,
+        Self::DuplicateOrderId,
+        Self::QuantityOverflow,
+        Self::MissingUserId,
+        Self::SelfTradePrevented,
+        Self::RiskMaxOpenOrders,
+        Self::RiskMaxNotional,
+        Self::RiskPriceBand,
+        Self::RiskPriceBandTicks,
+        Self::NatsPublishError,
+        Self::NatsSerializationError,
+        Self::ExecutionNotFound,
+        Self::NotTrailingStop,
+        Self::ReferencePriceUnavailable,
+        Self::MarketClosed,
+        Self::NotAModification,
+        Self::NothingToModify,
+        Self::NoHiddenTranche,
+        Self::ImmediateTifOnResting,
+        Self::ZeroQuantity,
+        Self::InvalidReduction,
+        Self::CorrectionToZero,
+        Self::InvalidMidpointQuantity,
+        Self::OrderTagTooLong,
+        Self::ZeroHeartbeatTtl,
+    ];
+
+    /// Numeric wire code.
+    #[inline]
+    #[must_use]
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+
+    /// Reconstruct a code from its numeric value; `None` for a value no
+    /// variant carries.
+    #[must_use]
+    pub fn from_u16(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.as_u16() == code)
+    }
+}
+
+impl fmt::Display for RejectCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Serialize as the numeric code via [`RejectCode::as_u16`].
+impl Serialize for RejectCode {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.as_u16())
+    }
+}
+
+/// Deserialize from the numeric code; unknown codes are an error.
+impl<'de> Deserialize<'de> for RejectCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = u16::deserialize(deserializer)?;
+        Self::from_u16(code)
+            .ok_or_else(|| de::Error::custom(format_args!("unknown reject code {code}")))
+    }
+}
+
+impl OrderBookError {
+    /// Stable numeric code of this error's variant.
+    #[must_use]
+    pub fn code(&self) -> RejectCode {
+        match self {
+            OrderBookError::PriceLevelError(_) => RejectCode::PriceLevelError,
+            OrderBookError::OrderNotFound(_) => RejectCode::OrderNotFound,
+            OrderBookError::InvalidPriceLevel(_) => RejectCode::InvalidPriceLevel,
+            OrderBookError::PriceCrossing { .. } => RejectCode::PriceCrossing,
+            OrderBookError::InsufficientLiquidity { .. } => RejectCode::InsufficientLiquidity,
+            OrderBookError::InsufficientLiquidityNotional { .. } => {
+                RejectCode::InsufficientLiquidityNotional
+            }
+            OrderBookError::InvalidOperation { .. } => RejectCode::InvalidOperation,
+            OrderBookError::InvalidConfiguration { .. } => RejectCode::InvalidConfiguration,
+            OrderBookError::PriceUnchanged { .. } => RejectCode::PriceUnchanged,
+            OrderBookError::OrderExpired { .. } => RejectCode::OrderExpired,
+            OrderBookError::LevelQuantityOverflow { .. } => RejectCode::LevelQuantityOverflow,
+            OrderBookError::KillSwitchActive => RejectCode::KillSwitchActive,
+            OrderBookError::UserBlocked { .. } => RejectCode::UserBlocked,
+            OrderBookError::RateLimited { .. } => RejectCode::RateLimited,
+            OrderBookError::SerializationError { .. } => RejectCode::SerializationError,
+            OrderBookError::DeserializationError { .. } => RejectCode::DeserializationError,
+            OrderBookError::ChecksumMismatch { .. } => RejectCode::ChecksumMismatch,
+            OrderBookError::UnsupportedSnapshotVersion { .. } => {
+                RejectCode::UnsupportedSnapshotVersion
+            }
+            OrderBookError::SnapshotSymbolMismatch { .. } => RejectCode::SnapshotSymbolMismatch,
+            OrderBookError::DuplicateSnapshotLevel { .. } => RejectCode::DuplicateSnapshotLevel,
+            OrderBookError::InvalidIndexPrice { .. } => RejectCode::InvalidIndexPrice,
+            OrderBookError::InvalidTickSize { .. } => RejectCode::InvalidTickSize,
+            OrderBookError::InvalidLotSize { .. } => RejectCode::InvalidLotSize,
+            OrderBookError::OrderSizeOutOfRange { .. } => RejectCode::OrderSizeOutOfRange,
+            OrderBookError::OrderNotionalOutOfRange { .. } => RejectCode::OrderNotionalOutOfRange,
+            OrderBookError::DuplicateOrderId { .. } => RejectCode::DuplicateOrderId,
+            OrderBookError::QuantityOverflow { .. } => RejectCode::QuantityOverflow,
+            OrderBookError::MissingUserId { .. } => RejectCode::MissingUserId,
+            OrderBookError::SelfTradePrevented { .. } => RejectCode::SelfTradePrevented,
+            OrderBookError::RiskMaxOpenOrders { .. } => RejectCode::RiskMaxOpenOrders,
+            OrderBookError::RiskMaxNotional { .. } => RejectCode::RiskMaxNotional,
+            OrderBookError::RiskPriceBand { .. } => RejectCode::RiskPriceBand,
+            OrderBookError::RiskPriceBandTicks { .. } => RejectCode::RiskPriceBandTicks,
+            OrderBookError::ExecutionNotFound { .. } => RejectCode::ExecutionNotFound,
+            OrderBookError::NotTrailingStop { .. } => RejectCode::NotTrailingStop,
+            OrderBookError::ReferencePriceUnavailable { .. } => {
+                RejectCode::ReferencePriceUnavailable
+            }
+            OrderBookError::MarketClosed { .. } => RejectCode::MarketClosed,
+            OrderBookError::NotAModification { .. } => RejectCode::NotAModification,
+            OrderBookError::NothingToModify { .. } => RejectCode::NothingToModify,
+            OrderBookError::NoHiddenTranche { .. } => RejectCode::NoHiddenTranche,
+            OrderBookError::ImmediateTifOnResting { .. } => RejectCode::ImmediateTifOnResting,
+            OrderBookError::ZeroQuantity { .. } => RejectCode::ZeroQuantity,
+            OrderBookError::InvalidReduction { .. } => RejectCode::InvalidReduction,
+            OrderBookError::CorrectionToZero { .. } => RejectCode::CorrectionToZero,
+            OrderBookError::InvalidMidpointQuantity { .. } => RejectCode::InvalidMidpointQuantity,
+            OrderBookError::OrderTagTooLong { .. } => RejectCode::OrderTagTooLong,
+            OrderBookError::ZeroHeartbeatTtl { .. } => RejectCode::ZeroHeartbeatTtl,
+            #[cfg(feature = "nats")]
+            OrderBookError::NatsPublishError { .. } => RejectCode::NatsPublishError,
+            #[cfg(feature = "nats")]
+            OrderBookError::NatsSerializationError { .. } => RejectCode::NatsSerializationError,
+        }
+    }
+}
+
+/// Serialize as `{ "code", "reject_reason", "message", "fields" }`:
+/// the [`RejectCode`], the wire
+/// [`RejectReason`](crate::orderbook::reject_reason::RejectReason), the
+/// display string, and the variant's fields by name. A wrapped
+/// [`PriceLevelError`] is carried as its display string under `error`;
+/// tuple variants name their field `order_id` / `price`.
+impl Serialize for OrderBookError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let reason = crate::orderbook::reject_reason::RejectReason::from(self);
+        let mut state = serializer.serialize_struct("OrderBookError", 4)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("reject_reason", &reason)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("fields", &ErrorFields(self))?;
+        state.end()
+    }
+}
+
+/// The named fields of one [`OrderBookError`] variant.
+struct ErrorFields<'a>(&'a OrderBookError);
+
+impl Serialize for ErrorFields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = "ErrorFields";
+        match self.0 {
+            OrderBookError::PriceLevelError(err) => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("error", &err.to_string())?;
+                s.end()
+            }
+            OrderBookError::OrderNotFound(order_id) => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::InvalidPriceLevel(price) => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("price", price)?;
+                s.end()
+            }
+            OrderBookError::PriceCrossing {
+                price,
+                side,
+                opposite_price,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("price", price)?;
+                s.serialize_field("side", side)?;
+                s.serialize_field("opposite_price", opposite_price)?;
+                s.end()
+            }
+            OrderBookError::InsufficientLiquidity {
+                side,
+                requested,
+                available,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("side", side)?;
+                s.serialize_field("requested", requested)?;
+                s.serialize_field("available", available)?;
+                s.end()
+            }
+            OrderBookError::InsufficientLiquidityNotional {
+                side,
+                requested,
+                spent,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("side", side)?;
+                s.serialize_field("requested", requested)?;
+                s.serialize_field("spent", spent)?;
+                s.end()
+            }
+            OrderBookError::InvalidOperation { message }
+            | OrderBookError::InvalidConfiguration { message }
+            | OrderBookError::SerializationError { message }
+            | OrderBookError::DeserializationError { message } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("message", message)?;
+                s.end()
+            }
+            #[cfg(feature = "nats")]
+            OrderBookError::NatsPublishError { message }
+            | OrderBookError::NatsSerializationError { message } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("message", message)?;
+                s.end()
+            }
+            OrderBookError::PriceUnchanged { order_id, price }
+            | OrderBookError::LevelQuantityOverflow { order_id, price } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("order_id", order_id)?;
+                s.serialize_field("price", price)?;
+                s.end()
+            }
+            OrderBookError::OrderExpired { order_id }
+            | OrderBookError::DuplicateOrderId { order_id }
+            | OrderBookError::MissingUserId { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::KillSwitchActive => serializer.serialize_struct(name, 0)?.end(),
+            OrderBookError::UserBlocked { user_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("user_id", user_id)?;
+                s.end()
+            }
+            OrderBookError::RateLimited { retry_after_ms } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("retry_after_ms", retry_after_ms)?;
+                s.end()
+            }
+            OrderBookError::ChecksumMismatch { expected, actual } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("expected", expected)?;
+                s.serialize_field("actual", actual)?;
+                s.end()
+            }
+            OrderBookError::UnsupportedSnapshotVersion {
+                version,
+                min_supported,
+                max_supported,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("version", version)?;
+                s.serialize_field("min_supported", min_supported)?;
+                s.serialize_field("max_supported", max_supported)?;
+                s.end()
+            }
+            OrderBookError::SnapshotSymbolMismatch {
+                snapshot_symbol,
+                book_symbol,
+            } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("snapshot_symbol", snapshot_symbol)?;
+                s.serialize_field("book_symbol", book_symbol)?;
+                s.end()
+            }
+            OrderBookError::DuplicateSnapshotLevel { side, price } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("side", side)?;
+                s.serialize_field("price", price)?;
+                s.end()
+            }
+            OrderBookError::InvalidIndexPrice { index_price } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("index_price", index_price)?;
+                s.end()
+            }
+            OrderBookError::InvalidTickSize { price, tick_size } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("price", price)?;
+                s.serialize_field("tick_size", tick_size)?;
+                s.end()
+            }
+            OrderBookError::InvalidLotSize { quantity, lot_size } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("quantity", quantity)?;
+                s.serialize_field("lot_size", lot_size)?;
+                s.end()
+            }
+            OrderBookError::OrderSizeOutOfRange { quantity, min, max } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("quantity", quantity)?;
+                s.serialize_field("min", min)?;
+                s.serialize_field("max", max)?;
+                s.end()
+            }
+            OrderBookError::OrderNotionalOutOfRange { notional, min, max } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("notional", notional)?;
+                s.serialize_field("min", min)?;
+                s.serialize_field("max", max)?;
+                s.end()
+            }
+            OrderBookError::QuantityOverflow { visible, hidden } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("visible", visible)?;
+                s.serialize_field("hidden", hidden)?;
+                s.end()
+            }
+            OrderBookError::SelfTradePrevented {
+                mode,
+                taker_order_id,
+                user_id,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("mode", mode)?;
+                s.serialize_field("taker_order_id", taker_order_id)?;
+                s.serialize_field("user_id", user_id)?;
+                s.end()
+            }
+            OrderBookError::RiskMaxOpenOrders {
+                account,
+                current,
+                limit,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("account", account)?;
+                s.serialize_field("current", current)?;
+                s.serialize_field("limit", limit)?;
+                s.end()
+            }
+            OrderBookError::RiskMaxNotional {
+                account,
+                current,
+                attempted,
+                limit,
+            } => {
+                let mut s = serializer.serialize_struct(name, 4)?;
+                s.serialize_field("account", account)?;
+                s.serialize_field("current", current)?;
+                s.serialize_field("attempted", attempted)?;
+                s.serialize_field("limit", limit)?;
+                s.end()
+            }
+            OrderBookError::RiskPriceBand {
+                submitted,
+                reference,
+                deviation_bps,
+                limit_bps,
+            } => {
+                let mut s = serializer.serialize_struct(name, 4)?;
+                s.serialize_field("submitted", submitted)?;
+                s.serialize_field("reference", reference)?;
+                s.serialize_field("deviation_bps", deviation_bps)?;
+                s.serialize_field("limit_bps", limit_bps)?;
+                s.end()
+            }
+            OrderBookError::RiskPriceBandTicks {
+                submitted,
+                reference,
+                deviation_ticks,
+                limit_ticks,
+            } => {
+                let mut s = serializer.serialize_struct(name, 4)?;
+                s.serialize_field("submitted", submitted)?;
+                s.serialize_field("reference", reference)?;
+                s.serialize_field("deviation_ticks", deviation_ticks)?;
+                s.serialize_field("limit_ticks", limit_ticks)?;
+                s.end()
+            }
+            OrderBookError::ExecutionNotFound { execution_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("execution_id", execution_id)?;
+                s.end()
+            }
+            OrderBookError::NotTrailingStop { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::ReferencePriceUnavailable { source } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("source", source)?;
+                s.end()
+            }
+            OrderBookError::MarketClosed { phase } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("phase", phase)?;
+                s.end()
+            }
+            OrderBookError::NotAModification { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::NothingToModify { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::NoHiddenTranche { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::ImmediateTifOnResting {
+                order_id,
+                time_in_force,
+            } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("order_id", order_id)?;
+                s.serialize_field("time_in_force", time_in_force)?;
+                s.end()
+            }
+            OrderBookError::ZeroQuantity { order_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("order_id", order_id)?;
+                s.end()
+            }
+            OrderBookError::InvalidReduction {
+                order_id,
+                requested,
+                displayed,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("order_id", order_id)?;
+                s.serialize_field("requested", requested)?;
+                s.serialize_field("displayed", displayed)?;
+                s.end()
+            }
+            OrderBookError::CorrectionToZero { execution_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("execution_id", execution_id)?;
+                s.end()
+            }
+            OrderBookError::InvalidMidpointQuantity {
+                order_id,
+                quantity,
+                min_execution_quantity,
+            } => {
+                let mut s = serializer.serialize_struct(name, 3)?;
+                s.serialize_field("order_id", order_id)?;
+                s.serialize_field("quantity", quantity)?;
+                s.serialize_field("min_execution_quantity", min_execution_quantity)?;
+                s.end()
+            }
+            OrderBookError::OrderTagTooLong { len, max } => {
+                let mut s = serializer.serialize_struct(name, 2)?;
+                s.serialize_field("len", len)?;
+                s.serialize_field("max", max)?;
+                s.end()
+            }
+            OrderBookError::ZeroHeartbeatTtl { user_id } => {
+                let mut s = serializer.serialize_struct(name, 1)?;
+                s.serialize_field("user_id", user_id)?;
+                s.end()
+            }
+        }
+    }
+}
+
 /// Errors that can occur in BookManager operations
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -581,6 +1733,31 @@ pub enum ManagerError {
         /// The symbol that already has a book.
         symbol: String,
     },
+
+    /// No order book is registered for the symbol.
+    BookNotFound {
+        /// The symbol that was looked up.
+        symbol: String,
+    },
+
+    /// The worker thread running the symbol's book has exited and no
+    /// longer accepts commands.
+    WorkerStopped {
+        /// The symbol whose worker is gone.
+        symbol: String,
+    },
+
+    /// The OS refused to spawn a worker thread for the symbol's book.
+    WorkerSpawnFailed {
+        /// The symbol the worker was for.
+        symbol: String,
+        /// The underlying spawn error.
+        message: String,
+    },
+
+    /// The executor has no journal, so commands are not sequenced, or it
+    /// shut down before the awaited sequence number became durable.
+    JournalUnavailable,
 }
 
 impl fmt::Display for ManagerError {
@@ -592,6 +1769,18 @@ impl fmt::Display for ManagerError {
             ManagerError::BookAlreadyExists { symbol } => {
                 write!(f, "order book already exists for symbol: {symbol}")
             }
+            ManagerError::BookNotFound { symbol } => {
+                write!(f, "no order book for symbol: {symbol}")
+            }
+            ManagerError::WorkerStopped { symbol } => {
+                write!(f, "book worker stopped for symbol: {symbol}")
+            }
+            ManagerError::WorkerSpawnFailed { symbol, message } => {
+                write!(f, "failed to spawn book worker for {symbol}: {message}")
+            }
+            ManagerError::JournalUnavailable => {
+                write!(f, "no journal is sequencing commands")
+            }
         }
     }
 }
@@ -855,4 +2044,78 @@ mod tests {
             }) if expected == "hash1" && actual == "hash2"
         ));
     }
+
+    #[test]
+    fn test_reject_code_round_trips_and_is_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for code in RejectCode::ALL {
+            assert!(seen.insert(code.as_u16()), "duplicate code {code}");
+            assert_eq!(RejectCode::from_u16(code.as_u16()), Some(code));
+        }
+        assert_eq!(RejectCode::from_u16(0), None);
+        assert_eq!(RejectCode::from_u16(RejectCode::ALL.len() as u16 + 1), None);
+    }
+
+    #[test]
+    fn test_reject_code_serializes_as_number() {
+        let json = serde_json::to_string(&RejectCode::RiskMaxNotional).unwrap();
+        assert_eq!(json, "31");
+        let back: RejectCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, RejectCode::RiskMaxNotional);
+        assert!(serde_json::from_str::<RejectCode>("999").is_err());
+    }
+
+    #[test]
+    fn test_error_code_per_variant() {
+        assert_eq!(
+            OrderBookError::KillSwitchActive.code(),
+            RejectCode::KillSwitchActive
+        );
+        let error = OrderBookError::OrderExpired {
+            order_id: Id::from_u64(1),
+        };
+        assert_eq!(error.code(), RejectCode::OrderExpired);
+        assert_eq!(error.code().as_u16(), 10);
+    }
+
+    #[test]
+    fn test_error_serializes_code_reason_and_fields() {
+        let error = OrderBookError::InvalidTickSize {
+            price: 105,
+            tick_size: 10,
+        };
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["code"], 22);
+        assert_eq!(value["reject_reason"], 7);
+        assert_eq!(value["message"], error.to_string());
+        assert_eq!(value["fields"]["price"], 105);
+        assert_eq!(value["fields"]["tick_size"], 10);
+
+        let value = serde_json::to_value(OrderBookError::KillSwitchActive).unwrap();
+        assert_eq!(value["code"], 12);
+        assert_eq!(value["fields"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_clone_structured_variants() {
+        let error = OrderBookError::DuplicateSnapshotLevel {
+            side: Side::Sell,
+            price: 42,
+        };
+        assert!(matches!(
+            error.clone(),
+            OrderBookError::DuplicateSnapshotLevel {
+                side: Side::Sell,
+                price: 42
+            }
+        ));
+        let error = OrderBookError::SnapshotSymbolMismatch {
+            snapshot_symbol: "A".to_string(),
+            book_symbol: "B".to_string(),
+        };
+        assert_eq!(
+            error.clone().to_string(),
+            "Snapshot symbol A does not match order book symbol B"
+        );
+    }
 }
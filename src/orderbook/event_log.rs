@@ -0,0 +1,517 @@
+//! Durable file sink for trades and price-level changes.
+//!
+//! [`EventLogSink`] appends every event it receives to a log file as one
+//! line, either JSON Lines or CSV ([`EventLogFormat`]), and rotates to a
+//! new file once the current one reaches a size or age limit. It plugs
+//! into a book through the ordinary listener hooks:
+//!
+//! ```
+//! use orderbook_rs::{EventLogConfig, EventLogFormat, EventLogSink, OrderBook};
+//! use pricelevel::{Id, Side, TimeInForce};
+//! use std::sync::Arc;
+//!
+//! let dir = tempfile::tempdir()?;
+//! let config = EventLogConfig::new(dir.path())
+//!     .with_format(EventLogFormat::Csv)
+//!     .with_max_file_bytes(16 * 1024 * 1024);
+//! let sink = Arc::new(EventLogSink::new(config)?);
+//!
+//! let mut book = OrderBook::<()>::new("BTC/USD");
+//! book.set_trade_listener(sink.trade_listener());
+//! book.set_price_level_listener(sink.price_level_listener("BTC/USD"));
+//!
+//! book.add_limit_order(Id::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, None)?;
+//! book.submit_market_order(Id::new_uuid(), 2, Side::Buy)?;
+//! sink.flush()?;
+//! assert_eq!(sink.events_written(), 3);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Record layout
+//!
+//! Both formats carry the same flat record, in this column order:
+//!
+//! | field | trade | level |
+//! |---|---|---|
+//! | `kind` | `"trade"` | `"level"` |
+//! | `symbol` | book symbol | symbol given to the listener |
+//! | `engine_seq` | [`TradeResult::engine_seq`] | [`PriceLevelChangedEvent::engine_seq`] |
+//! | `timestamp` | fill time, ms | sink clock when logged, ms |
+//! | `side` | taker side, `buy` / `sell` | level side |
+//! | `price` | fill price | level price |
+//! | `quantity` | fill quantity | new visible quantity, `0` when removed |
+//! | `taker_order_id` | taker id | empty |
+//! | `maker_order_id` | maker id | empty |
+//!
+//! A trade writes one record per fill. Every CSV file starts with a header
+//! row; CSV fields are quoted per RFC 4180 when needed. JSON records omit
+//! the two id fields for level changes.
+//!
+//! # Files and rotation
+//!
+//! Files are named `{prefix}-{index:06}.{jsonl|csv}` inside the configured
+//! directory. A new sink continues after the highest index already there,
+//! so restarts never overwrite earlier logs. A file is rotated before a
+//! record that would take it past
+//! [`max_file_bytes`](EventLogConfig::max_file_bytes), or once it is
+//! [`max_file_age_ms`](EventLogConfig::max_file_age_ms) old by the sink's
+//! clock; a file always receives at least one record.
+//!
+//! # Hot path
+//!
+//! Writes happen synchronously on the thread invoking the listener,
+//! through a buffered writer behind a mutex. Buffered lines reach the file
+//! on [`EventLogSink::flush`], on rotation and when the sink is dropped.
+//! A failed write cannot be returned through a listener: it is counted in
+//! [`EventLogSink::write_errors`] and logged with `tracing`. Callers that
+//! need backpressure-free matching should put a channel in front of
+//! [`EventLogSink::log_trade`] / [`EventLogSink::log_book_change`].
+
+use crate::orderbook::book_change_event::{PriceLevelChangedEvent, PriceLevelChangedListener};
+use crate::orderbook::clock::{Clock, MonotonicClock};
+use crate::orderbook::trade::{TradeListener, TradeResult};
+use pricelevel::Side;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::warn;
+
+const CSV_HEADER: &str =
+    "kind,symbol,engine_seq,timestamp,side,price,quantity,taker_order_id,maker_order_id\n";
+
+/// Errors from an [`EventLogSink`].
+#[derive(Debug, thiserror::Error)]
+pub enum EventLogError {
+    /// Creating, writing or flushing a log file failed.
+    #[error("event log I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Encoding a JSON record failed.
+    #[error("event log JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Line format of an event log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventLogFormat {
+    /// One JSON object per line (`.jsonl`).
+    #[default]
+    JsonLines,
+    /// Comma-separated values with a header row (`.csv`).
+    Csv,
+}
+
+impl EventLogFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Where and how an [`EventLogSink`] writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLogConfig {
+    /// Directory holding the log files; created if missing.
+    pub directory: PathBuf,
+
+    /// File name prefix.
+    pub file_prefix: String,
+
+    /// Line format.
+    pub format: EventLogFormat,
+
+    /// Rotate before a file would exceed this many bytes. `None` disables
+    /// size-based rotation.
+    pub max_file_bytes: Option<u64>,
+
+    /// Rotate once a file has been open this many milliseconds by the
+    /// sink's clock. `None` disables time-based rotation.
+    pub max_file_age_ms: Option<u64>,
+}
+
+impl EventLogConfig {
+    /// JSON Lines files named `events-*.jsonl` in `directory`, without
+    /// rotation.
+    #[inline]
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_prefix: "events".to_string(),
+            format: EventLogFormat::JsonLines,
+            max_file_bytes: None,
+            max_file_age_ms: None,
+        }
+    }
+
+    /// Set the file name prefix.
+    #[inline]
+    #[must_use]
+    pub fn with_file_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.file_prefix = prefix.into();
+        self
+    }
+
+    /// Set the line format.
+    #[inline]
+    #[must_use]
+    pub fn with_format(mut self, format: EventLogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Rotate by size.
+    #[inline]
+    #[must_use]
+    pub fn with_max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = Some(bytes);
+        self
+    }
+
+    /// Rotate by age.
+    #[inline]
+    #[must_use]
+    pub fn with_max_file_age_ms(mut self, age_ms: u64) -> Self {
+        self.max_file_age_ms = Some(age_ms);
+        self
+    }
+
+    fn path(&self, index: u64) -> PathBuf {
+        self.directory.join(format!(
+            "{}-{index:06}.{}",
+            self.file_prefix,
+            self.format.extension()
+        ))
+    }
+}
+
+/// One log line; see the module docs for the layout.
+#[derive(Serialize)]
+struct LogRecord<'a> {
+    kind: &'static str,
+    symbol: &'a str,
+    engine_seq: u64,
+    timestamp: u64,
+    side: &'static str,
+    price: u128,
+    quantity: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    taker_order_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maker_order_id: Option<String>,
+}
+
+struct LogFile {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    bytes: u64,
+    records: u64,
+    opened_at: u64,
+}
+
+struct SinkState {
+    file: LogFile,
+    next_index: u64,
+}
+
+/// Appends trades and price-level changes to rotating JSON Lines or CSV
+/// files. See the [module docs](self).
+pub struct EventLogSink {
+    config: EventLogConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<SinkState>,
+    events_written: AtomicU64,
+    write_errors: AtomicU64,
+    rotations: AtomicU64,
+}
+
+impl EventLogSink {
+    /// Open a sink stamping level changes and timing rotation with the
+    /// wall clock.
+    ///
+    /// # Errors
+    ///
+    /// [`EventLogError::Io`] if the directory or first file cannot be
+    /// created.
+    pub fn new(config: EventLogConfig) -> Result<Self, EventLogError> {
+        Self::with_clock(config, Arc::new(MonotonicClock))
+    }
+
+    /// Open a sink using `clock`, e.g. the book's own clock for replayed
+    /// or simulated runs.
+    ///
+    /// # Errors
+    ///
+    /// [`EventLogError::Io`] if the directory or first file cannot be
+    /// created.
+    pub fn with_clock(
+        config: EventLogConfig,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self, EventLogError> {
+        fs::create_dir_all(&config.directory)?;
+        let index = next_free_index(&config)?;
+        let file = open_file(&config, index, clock.now_millis().as_u64())?;
+        Ok(Self {
+            config,
+            clock,
+            state: Mutex::new(SinkState {
+                file,
+                next_index: index + 1,
+            }),
+            events_written: AtomicU64::new(0),
+            write_errors: AtomicU64::new(0),
+            rotations: AtomicU64::new(0),
+        })
+    }
+
+    /// The sink's configuration.
+    #[must_use]
+    pub fn config(&self) -> &EventLogConfig {
+        &self.config
+    }
+
+    /// A [`TradeListener`] logging every trade through this sink.
+    #[must_use]
+    pub fn trade_listener(self: &Arc<Self>) -> TradeListener {
+        let sink = Arc::clone(self);
+        Arc::new(move |trade: &TradeResult| {
+            if let Err(error) = sink.log_trade(trade) {
+                sink.write_errors.fetch_add(1, Ordering::Relaxed);
+                warn!("event log: failed to write trade: {error}");
+            }
+        })
+    }
+
+    /// A [`PriceLevelChangedListener`] logging every change under `symbol`.
+    /// Change events do not carry their book's symbol, so each book gets
+    /// its own listener.
+    #[must_use]
+    pub fn price_level_listener(
+        self: &Arc<Self>,
+        symbol: impl Into<String>,
+    ) -> PriceLevelChangedListener {
+        let sink = Arc::clone(self);
+        let symbol = symbol.into();
+        Arc::new(move |change: PriceLevelChangedEvent| {
+            if let Err(error) = sink.log_book_change(&symbol, &change) {
+                sink.write_errors.fetch_add(1, Ordering::Relaxed);
+                warn!("event log: failed to write book change: {error}");
+            }
+        })
+    }
+
+    /// Append one record per fill of `trade`.
+    ///
+    /// # Errors
+    ///
+    /// [`EventLogError`] if encoding, rotating or writing fails.
+    pub fn log_trade(&self, trade: &TradeResult) -> Result<(), EventLogError> {
+        let mut state = self.lock();
+        for fill in trade.match_result.trades().as_vec() {
+            let record = LogRecord {
+                kind: "trade",
+                symbol: &trade.symbol,
+                engine_seq: trade.engine_seq,
+                timestamp: fill.timestamp().as_u64(),
+                side: side_name(fill.taker_side()),
+                price: fill.price().as_u128(),
+                quantity: fill.quantity().as_u64(),
+                taker_order_id: Some(fill.taker_order_id().to_string()),
+                maker_order_id: Some(fill.maker_order_id().to_string()),
+            };
+            self.append(&mut state, &record)?;
+        }
+        Ok(())
+    }
+
+    /// Append a record for a change on `symbol`'s book.
+    ///
+    /// # Errors
+    ///
+    /// [`EventLogError`] if encoding, rotating or writing fails.
+    pub fn log_book_change(
+        &self,
+        symbol: &str,
+        change: &PriceLevelChangedEvent,
+    ) -> Result<(), EventLogError> {
+        let record = LogRecord {
+            kind: "level",
+            symbol,
+            engine_seq: change.engine_seq,
+            timestamp: self.clock.now_millis().as_u64(),
+            side: side_name(change.side),
+            price: change.price,
+            quantity: change.quantity,
+            taker_order_id: None,
+            maker_order_id: None,
+        };
+        let mut state = self.lock();
+        self.append(&mut state, &record)
+    }
+
+    /// Flush buffered records to the current file.
+    ///
+    /// # Errors
+    ///
+    /// [`EventLogError::Io`] if the flush fails.
+    pub fn flush(&self) -> Result<(), EventLogError> {
+        self.lock().file.writer.flush()?;
+        Ok(())
+    }
+
+    /// Path of the file currently written to.
+    #[must_use]
+    pub fn current_path(&self) -> PathBuf {
+        self.lock().file.path.clone()
+    }
+
+    /// Records written so far.
+    #[must_use]
+    pub fn events_written(&self) -> u64 {
+        self.events_written.load(Ordering::Relaxed)
+    }
+
+    /// Listener writes that failed.
+    #[must_use]
+    pub fn write_errors(&self) -> u64 {
+        self.write_errors.load(Ordering::Relaxed)
+    }
+
+    /// Files rotated out so far.
+    #[must_use]
+    pub fn rotations(&self) -> u64 {
+        self.rotations.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SinkState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn append(&self, state: &mut SinkState, record: &LogRecord<'_>) -> Result<(), EventLogError> {
+        let line = match self.config.format {
+            EventLogFormat::JsonLines => {
+                let mut line = serde_json::to_string(record)?;
+                line.push('\n');
+                line
+            }
+            EventLogFormat::Csv => csv_line(record),
+        };
+
+        let now = self.clock.now_millis().as_u64();
+        let file = &state.file;
+        let too_big = self
+            .config
+            .max_file_bytes
+            .is_some_and(|max| file.bytes + line.len() as u64 > max);
+        let too_old = self
+            .config
+            .max_file_age_ms
+            .is_some_and(|max| now.saturating_sub(file.opened_at) >= max);
+        if file.records > 0 && (too_big || too_old) {
+            let next = open_file(&self.config, state.next_index, now)?;
+            state.next_index += 1;
+            let mut previous = std::mem::replace(&mut state.file, next);
+            previous.writer.flush()?;
+            self.rotations.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let file = &mut state.file;
+        file.writer.write_all(line.as_bytes())?;
+        file.bytes += line.len() as u64;
+        file.records += 1;
+        self.events_written.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for EventLogSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLogSink")
+            .field("config", &self.config)
+            .field("events_written", &self.events_written())
+            .field("write_errors", &self.write_errors())
+            .field("rotations", &self.rotations())
+            .finish_non_exhaustive()
+    }
+}
+
+/// One past the highest index of an existing log file of this config.
+fn next_free_index(config: &EventLogConfig) -> io::Result<u64> {
+    let head = format!("{}-", config.file_prefix);
+    let tail = format!(".{}", config.format.extension());
+    let mut next = 0;
+    for entry in fs::read_dir(&config.directory)? {
+        let name = entry?.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if let Some(index) = name
+            .strip_prefix(&head)
+            .and_then(|rest| rest.strip_suffix(&tail))
+            .and_then(|index| index.parse::<u64>().ok())
+        {
+            next = next.max(index + 1);
+        }
+    }
+    Ok(next)
+}
+
+fn open_file(config: &EventLogConfig, index: u64, now: u64) -> io::Result<LogFile> {
+    let path = config.path(index);
+    let file = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)?;
+    let mut writer = BufWriter::new(file);
+    let mut bytes = 0;
+    if config.format == EventLogFormat::Csv {
+        writer.write_all(CSV_HEADER.as_bytes())?;
+        bytes = CSV_HEADER.len() as u64;
+    }
+    Ok(LogFile {
+        writer,
+        path,
+        bytes,
+        records: 0,
+        opened_at: now,
+    })
+}
+
+fn csv_line(record: &LogRecord<'_>) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{}\n",
+        record.kind,
+        csv_field(record.symbol),
+        record.engine_seq,
+        record.timestamp,
+        record.side,
+        record.price,
+        record.quantity,
+        record.taker_order_id.as_deref().unwrap_or_default(),
+        record.maker_order_id.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Quote `value` if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "buy",
+        Side::Sell => "sell",
+    }
+}
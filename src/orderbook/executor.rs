@@ -0,0 +1,1026 @@
+//! Thread-per-book command execution.
+//!
+//! [`BookExecutor`] moves each [`OrderBook`] onto a dedicated worker thread
+//! that owns it exclusively and consumes a bounded MPSC
+//! [command ring](crate::orderbook::sequencer::ring) of
+//! [`SequencerCommand`]s — the classic shard-by-symbol design. Any number
+//! of producer threads may [`submit`](BookExecutor::submit) commands; each
+//! book sees them strictly in queue order, so there is never contention on
+//! a book's submit gate and per-symbol ordering is deterministic.
+//!
+//! Commands are never stolen across workers: a book is only ever touched
+//! by its own thread. A full queue applies backpressure to the producer.
+//! How an idle worker waits for its next command is set with
+//! [`ExecutorConfig::with_wait_strategy`]: the default spins briefly and
+//! then parks, [`WaitStrategy::BusySpin`] trades a dedicated core for the
+//! lowest wake-up latency.
+//!
+//! The crate does not pin threads itself. Supply the cores with
+//! [`ExecutorConfig::with_core_ids`] and a
+//! [`worker start hook`](ExecutorConfig::with_worker_start_hook) that calls
+//! your platform's affinity API; the hook runs on the worker thread before
+//! it takes its first command.
+//!
+//! On multi-socket machines, build the book with
+//! [`BookExecutor::spawn_book_with`] instead of
+//! [`spawn_book`](BookExecutor::spawn_book). The builder runs on the worker
+//! after the start hook, so once the hook has pinned the thread, the
+//! operating system's first-touch policy places the book's levels, indexes
+//! and caches on that core's NUMA node rather than the spawning thread's.
+//!
+//! [`BookExecutor::shutdown`] and [`BookExecutor::remove_book`] drain
+//! gracefully: every command already queued is executed before the book is
+//! handed back.
+//!
+//! With the `latency` feature, [`ExecutorConfig::with_latency_recorder`]
+//! records every command's enqueue-to-result latency per symbol; see
+//! [`latency`](crate::orderbook::latency).
+//!
+//! An executor built with
+//! [`BookExecutor::with_journal`](crate::orderbook::executor::BookExecutor::with_journal)
+//! acts as the sequencer: every executed command, on any book, is appended
+//! to the journal as a [`SequencerEvent`] under the next global sequence
+//! number. Appends run on a dedicated journal writer thread, so a book's
+//! worker moves on to its next command without waiting for the journal's
+//! I/O; the command's reply is sent once it has been appended.
+//! [`submit_sequenced`](crate::orderbook::executor::BookExecutor::submit_sequenced)
+//! returns that number with the result, and
+//! [`await_sequence_durable`](crate::orderbook::executor::BookExecutor::await_sequence_durable)
+//! resolves once the journal reports it durable, so a client can
+//! acknowledge downstream only what a restart would replay. Events are
+//! appended with their book's symbol, so
+//! [`Journal::read_filtered`](crate::orderbook::sequencer::Journal::read_filtered)
+//! picks out one book's commands;
+//! [`ReplayEngine`](crate::orderbook::sequencer::ReplayEngine) replays every
+//! entry, so a journal it rebuilds a book from should come from an executor
+//! running that book alone.
+
+use crate::orderbook::OrderBook;
+use crate::orderbook::error::ManagerError;
+#[cfg(feature = "latency")]
+use crate::orderbook::latency::{LatencyRecorder, SymbolLatency, SymbolLatencyRecorder};
+use crate::orderbook::mass_cancel::MassCancelResult;
+use crate::orderbook::sequencer::{
+    Journal, JournalError, RingSender, SequencedResult, SequencerCommand, SequencerEvent,
+    SequencerResult, WaitStrategy, command_ring,
+};
+use crate::orderbook::trade::TradeResult;
+use pricelevel::{Id, OrderUpdate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread::JoinHandle;
+#[cfg(feature = "latency")]
+use std::time::Instant;
+use tokio::sync::watch;
+use tracing::{error, info};
+
+/// Default per-book command queue capacity.
+pub const DEFAULT_EXECUTOR_QUEUE_CAPACITY: usize = 1024;
+
+/// Callback run on each worker thread before it processes commands.
+///
+/// Receives the book symbol and the core assigned to the worker, if any.
+pub type WorkerStartHook = Arc<dyn Fn(&str, Option<usize>) + Send + Sync>;
+
+/// Configuration for a [`BookExecutor`].
+#[derive(Clone)]
+pub struct ExecutorConfig {
+    queue_capacity: usize,
+    wait_strategy: WaitStrategy,
+    core_ids: Vec<usize>,
+    on_worker_start: Option<WorkerStartHook>,
+    #[cfg(feature = "latency")]
+    latency: Option<LatencyRecorder>,
+}
+
+impl ExecutorConfig {
+    /// Configuration with the default queue capacity and wait strategy, no
+    /// core assignment and no start hook.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            queue_capacity: DEFAULT_EXECUTOR_QUEUE_CAPACITY,
+            wait_strategy: WaitStrategy::default(),
+            core_ids: Vec::new(),
+            on_worker_start: None,
+            #[cfg(feature = "latency")]
+            latency: None,
+        }
+    }
+
+    /// Bound each book's command queue to `capacity` pending commands,
+    /// rounded up to a power of two (minimum two).
+    #[must_use]
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity;
+        self
+    }
+
+    /// How each worker waits for its next command.
+    #[must_use]
+    pub fn with_wait_strategy(mut self, wait_strategy: WaitStrategy) -> Self {
+        self.wait_strategy = wait_strategy;
+        self
+    }
+
+    /// Assign cores to workers round-robin, in the order books are spawned.
+    /// The assigned core is passed to the worker start hook.
+    #[must_use]
+    pub fn with_core_ids(mut self, core_ids: Vec<usize>) -> Self {
+        self.core_ids = core_ids;
+        self
+    }
+
+    /// Run `hook` on every worker thread before it takes its first command,
+    /// typically to pin the thread to the assigned core.
+    #[must_use]
+    pub fn with_worker_start_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, Option<usize>) + Send + Sync + 'static,
+    {
+        self.on_worker_start = Some(Arc::new(hook));
+        self
+    }
+
+    /// Record each command's latency, from enqueue to result, into
+    /// `recorder` under the book's symbol.
+    #[cfg(feature = "latency")]
+    #[must_use]
+    pub fn with_latency_recorder(mut self, recorder: LatencyRecorder) -> Self {
+        self.latency = Some(recorder);
+        self
+    }
+
+    /// The latency recorder, if one is installed.
+    #[cfg(feature = "latency")]
+    #[must_use]
+    pub fn latency_recorder(&self) -> Option<&LatencyRecorder> {
+        self.latency.as_ref()
+    }
+
+    /// Per-book command queue capacity.
+    #[must_use]
+    pub fn queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+
+    /// How each worker waits for its next command.
+    #[must_use]
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait_strategy
+    }
+
+    /// Cores assigned to workers round-robin.
+    #[must_use]
+    pub fn core_ids(&self) -> &[usize] {
+        &self.core_ids
+    }
+
+    /// Core for the `worker_index`-th spawned worker, if cores are configured.
+    fn core_for(&self, worker_index: usize) -> Option<usize> {
+        if self.core_ids.is_empty() {
+            None
+        } else {
+            Some(self.core_ids[worker_index % self.core_ids.len()])
+        }
+    }
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ExecutorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ExecutorConfig");
+        debug
+            .field("queue_capacity", &self.queue_capacity)
+            .field("wait_strategy", &self.wait_strategy)
+            .field("core_ids", &self.core_ids)
+            .field("on_worker_start", &self.on_worker_start.is_some());
+        #[cfg(feature = "latency")]
+        debug.field("latency", &self.latency.is_some());
+        debug.finish()
+    }
+}
+
+/// Channel a command's outcome is delivered on.
+enum Reply {
+    Result(SyncSender<SequencerResult>),
+    Sequenced(SyncSender<Result<SequencedResult, JournalError>>),
+}
+
+/// A queued command and the channel its result is delivered on.
+struct Job<T> {
+    command: SequencerCommand<T>,
+    reply: Reply,
+    /// When the command was queued, if latency is being recorded
+    #[cfg(feature = "latency")]
+    enqueued_at: Option<Instant>,
+}
+
+/// Handle to one book's worker thread.
+struct BookWorker<T> {
+    sender: RingSender<Job<T>>,
+    handle: JoinHandle<OrderBook<T>>,
+}
+
+impl<T> BookWorker<T> {
+    /// Close the queue, wait for the worker to execute everything already
+    /// queued, and take the book back. `None` if the worker panicked.
+    fn drain(self, symbol: &str) -> Option<OrderBook<T>> {
+        drop(self.sender);
+        match self.handle.join() {
+            Ok(book) => Some(book),
+            Err(_) => {
+                error!("Book worker for {} panicked; book lost", symbol);
+                None
+            }
+        }
+    }
+}
+
+/// The journal operations the executor needs, with the serde bounds of
+/// [`Journal`] erased so the executor itself does not require them.
+trait EventSink<T>: Send + Sync {
+    fn append(&self, symbol: &str, event: &SequencerEvent<T>) -> Result<(), JournalError>;
+    fn flush(&self) -> Result<(), JournalError>;
+    fn last_sequence(&self) -> Option<u64>;
+    fn durable_sequence(&self) -> Option<u64>;
+}
+
+struct JournalSink<J: ?Sized>(Arc<J>);
+
+impl<T, J> EventSink<T> for JournalSink<J>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
+    J: Journal<T> + ?Sized,
+{
+    fn append(&self, symbol: &str, event: &SequencerEvent<T>) -> Result<(), JournalError> {
+        self.0.append_for_symbol(symbol, event)
+    }
+
+    fn flush(&self) -> Result<(), JournalError> {
+        self.0.flush()
+    }
+
+    fn last_sequence(&self) -> Option<u64> {
+        self.0.last_sequence()
+    }
+
+    fn durable_sequence(&self) -> Option<u64> {
+        self.0.durable_sequence()
+    }
+}
+
+/// A request to the journal writer thread.
+// Appends are the hot path; boxing them to shrink the rare flush would only
+// add an allocation per command.
+#[allow(clippy::large_enum_variant)]
+enum WriterRequest<T> {
+    /// Journal an executed command, then deliver its outcome.
+    Append {
+        symbol: Arc<str>,
+        command: SequencerCommand<T>,
+        result: SequencerResult,
+        timestamp_ns: u64,
+        reply: Reply,
+        /// Where and since when to record the command's latency
+        #[cfg(feature = "latency")]
+        latency: Option<(SymbolLatencyRecorder, Instant)>,
+    },
+    /// Flush the journal once every earlier append is done.
+    Flush(SyncSender<Result<(), JournalError>>),
+}
+
+/// Global sequencing of executed commands into the journal, shared by
+/// every worker.
+///
+/// Workers hand each executed command to a dedicated writer thread and
+/// move straight on to their next command; they never wait for the
+/// journal's I/O. The writer numbers requests in arrival order, appends
+/// them one by one and only then replies to the submitter, so the journal
+/// receives events in sequence order, gap-free, whichever worker executed
+/// them, and a reply always follows its append.
+struct Sequencing<T> {
+    /// Hand-off to the writer; `None` once it is being shut down
+    sender: Option<SyncSender<WriterRequest<T>>>,
+    /// One past the highest sequence number the journal reports durable.
+    durable: watch::Receiver<u64>,
+    writer: Option<JoinHandle<()>>,
+}
+
+/// The error a request to a stopped journal writer fails with.
+fn writer_stopped() -> JournalError {
+    JournalError::Io {
+        message: "journal writer stopped".to_string(),
+        path: None,
+    }
+}
+
+impl<T> Sequencing<T>
+where
+    T: Send + 'static,
+{
+    /// Start the writer thread appending to `journal`, numbering from one
+    /// past its last sequence number. Up to `capacity` executed commands
+    /// may wait for the writer before workers block.
+    fn spawn(journal: Box<dyn EventSink<T>>, capacity: usize) -> Self {
+        let next_sequence = journal
+            .last_sequence()
+            .map_or(0, |seq| seq.saturating_add(1));
+        let durable = journal
+            .durable_sequence()
+            .map_or(0, |seq| seq.saturating_add(1));
+        let (durable_tx, durable) = watch::channel(durable);
+        let (sender, receiver) = sync_channel(capacity.max(1));
+        let writer = std::thread::Builder::new()
+            .name("journal-writer".to_string())
+            .spawn(move || {
+                let mut writer = JournalWriter {
+                    journal,
+                    next_sequence,
+                    durable: durable_tx,
+                };
+                for request in receiver {
+                    writer.handle(request);
+                }
+            });
+        let (sender, writer) = match writer {
+            Ok(handle) => (Some(sender), Some(handle)),
+            Err(err) => {
+                // Requests then fail with `writer_stopped`.
+                error!(error = %err, "failed to spawn the journal writer");
+                (None, None)
+            }
+        };
+        Self {
+            sender,
+            durable,
+            writer,
+        }
+    }
+
+    /// Hand `request` to the writer. If the writer has stopped, the
+    /// command's result is still delivered, without a sequence number.
+    fn submit(&self, request: WriterRequest<T>) {
+        let request = match &self.sender {
+            Some(sender) => match sender.send(request) {
+                Ok(()) => return,
+                Err(std::sync::mpsc::SendError(request)) => request,
+            },
+            None => request,
+        };
+        match request {
+            WriterRequest::Append {
+                symbol,
+                result,
+                reply,
+                #[cfg(feature = "latency")]
+                latency,
+                ..
+            } => {
+                error!(symbol = %symbol, "command executed but not journaled: writer stopped");
+                #[cfg(feature = "latency")]
+                record_latency(latency);
+                deliver(reply, result, Err(writer_stopped()));
+            }
+            WriterRequest::Flush(reply) => {
+                let _ = reply.send(Err(writer_stopped()));
+            }
+        }
+    }
+
+    /// Flush the journal after every append handed over so far.
+    fn flush(&self) -> Result<(), JournalError> {
+        let (reply, outcome) = sync_channel(1);
+        self.submit(WriterRequest::Flush(reply));
+        outcome.recv().unwrap_or_else(|_| Err(writer_stopped()))
+    }
+}
+
+impl<T> Drop for Sequencing<T> {
+    /// Closes the hand-off and waits for the writer to journal everything
+    /// already handed over.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(writer) = self.writer.take()
+            && writer.join().is_err()
+        {
+            error!("journal writer panicked");
+        }
+    }
+}
+
+/// State owned by the journal writer thread.
+struct JournalWriter<T> {
+    journal: Box<dyn EventSink<T>>,
+    next_sequence: u64,
+    durable: watch::Sender<u64>,
+}
+
+impl<T> JournalWriter<T> {
+    fn handle(&mut self, request: WriterRequest<T>) {
+        match request {
+            WriterRequest::Append {
+                symbol,
+                command,
+                result,
+                timestamp_ns,
+                reply,
+                #[cfg(feature = "latency")]
+                latency,
+            } => {
+                let (result, sequence) = self.record(&symbol, command, result, timestamp_ns);
+                if let Err(err) = &sequence {
+                    error!(
+                        symbol = %symbol,
+                        error = %err,
+                        "command executed but not journaled"
+                    );
+                }
+                #[cfg(feature = "latency")]
+                record_latency(latency);
+                deliver(reply, result, sequence);
+            }
+            WriterRequest::Flush(reply) => {
+                let outcome = self.journal.flush();
+                self.publish_durable();
+                let _ = reply.send(outcome);
+            }
+        }
+    }
+
+    /// Journal `command` and its `result`, executed on the book trading
+    /// `symbol`, under the next sequence number. The result is handed back
+    /// whether or not the append succeeded; a failed append does not use
+    /// up its number.
+    fn record(
+        &mut self,
+        symbol: &str,
+        command: SequencerCommand<T>,
+        result: SequencerResult,
+        timestamp_ns: u64,
+    ) -> (SequencerResult, Result<u64, JournalError>) {
+        let event = SequencerEvent {
+            sequence_num: self.next_sequence,
+            timestamp_ns,
+            command,
+            result,
+        };
+        if let Err(err) = self.journal.append(symbol, &event) {
+            return (event.result, Err(err));
+        }
+        self.next_sequence = self.next_sequence.saturating_add(1);
+        self.publish_durable();
+        (event.result, Ok(event.sequence_num))
+    }
+
+    /// Advance the durable watermark to what the journal reports.
+    fn publish_durable(&self) {
+        let Some(durable) = self.journal.durable_sequence() else {
+            return;
+        };
+        let durable = durable.saturating_add(1);
+        self.durable.send_if_modified(|current| {
+            let advanced = durable > *current;
+            if advanced {
+                *current = durable;
+            }
+            advanced
+        });
+    }
+}
+
+/// Record a command's enqueue-to-result latency, if it is being recorded.
+#[cfg(feature = "latency")]
+fn record_latency(latency: Option<(SymbolLatencyRecorder, Instant)>) {
+    if let Some((recorder, enqueued_at)) = latency {
+        recorder.record(enqueued_at.elapsed());
+    }
+}
+
+/// Send a command's outcome on `reply`. The submitter may have dropped its
+/// receiver.
+fn deliver(reply: Reply, result: SequencerResult, sequence: Result<u64, JournalError>) {
+    match reply {
+        Reply::Result(reply) => {
+            let _ = reply.send(result);
+        }
+        Reply::Sequenced(reply) => {
+            let _ = reply.send(sequence.map(|sequence_num| SequencedResult {
+                sequence_num,
+                result,
+            }));
+        }
+    }
+}
+
+/// Execute `job` on `book` and deliver its outcome, or, when the executor
+/// sequences commands, hand it to the journal writer, which delivers it
+/// once journaled. The latency sample is recorded before the reply is
+/// sent, so a submitter that has its result also sees its sample in the
+/// report.
+fn run_job<T>(
+    book: &OrderBook<T>,
+    symbol: &Arc<str>,
+    sequencing: Option<&Sequencing<T>>,
+    #[cfg(feature = "latency")] latency: Option<&SymbolLatencyRecorder>,
+    job: Job<T>,
+) where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    #[cfg(feature = "latency")]
+    let latency = latency.cloned().zip(job.enqueued_at);
+
+    let Some(sequencing) = sequencing else {
+        let result = execute_command(book, job.command);
+        #[cfg(feature = "latency")]
+        record_latency(latency);
+        // Sequenced replies are only queued on executors with a journal.
+        if let Reply::Result(reply) = job.reply {
+            let _ = reply.send(result);
+        }
+        return;
+    };
+
+    let command = job.command.clone();
+    let result = execute_command(book, job.command);
+    sequencing.submit(WriterRequest::Append {
+        symbol: Arc::clone(symbol),
+        command,
+        result,
+        timestamp_ns: book.clock().now_nanos(),
+        reply: job.reply,
+        #[cfg(feature = "latency")]
+        latency,
+    });
+}
+
+/// Runs each order book on its own worker thread and routes commands to it
+/// by symbol. See the [module docs](self).
+pub struct BookExecutor<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    config: ExecutorConfig,
+    workers: HashMap<String, BookWorker<T>>,
+    /// Workers spawned so far; drives round-robin core assignment
+    spawned: usize,
+    /// Journal sequencing shared by every worker, if configured
+    sequencing: Option<Arc<Sequencing<T>>>,
+}
+
+impl<T> BookExecutor<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Create an executor with no books.
+    #[must_use]
+    pub fn new(config: ExecutorConfig) -> Self {
+        Self {
+            config,
+            workers: HashMap::new(),
+            spawned: 0,
+            sequencing: None,
+        }
+    }
+
+    /// Move `book` onto a new worker thread, keyed by its symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::BookAlreadyExists`] if a worker already runs
+    /// a book for the same symbol, or [`ManagerError::WorkerSpawnFailed`]
+    /// if the OS refuses to create the thread.
+    pub fn spawn_book(&mut self, book: OrderBook<T>) -> Result<(), ManagerError> {
+        let symbol = book.symbol().to_string();
+        self.spawn_worker(symbol, move || book)
+    }
+
+    /// Start a worker for `symbol` that builds its own book by calling
+    /// `build` on the worker thread, after the start hook has run.
+    ///
+    /// Use this to keep per-book state local to the worker's NUMA node:
+    /// memory the book allocates while being built is first touched by the
+    /// pinned worker. Commands are routed by `symbol`, which should match
+    /// the built book's symbol.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::spawn_book`].
+    pub fn spawn_book_with<F>(&mut self, symbol: &str, build: F) -> Result<(), ManagerError>
+    where
+        F: FnOnce() -> OrderBook<T> + Send + 'static,
+    {
+        self.spawn_worker(symbol.to_string(), build)
+    }
+
+    fn spawn_worker<F>(&mut self, symbol: String, build: F) -> Result<(), ManagerError>
+    where
+        F: FnOnce() -> OrderBook<T> + Send + 'static,
+    {
+        if self.workers.contains_key(&symbol) {
+            return Err(ManagerError::BookAlreadyExists { symbol });
+        }
+
+        let (sender, receiver) =
+            command_ring::<Job<T>>(self.config.queue_capacity, self.config.wait_strategy);
+        let core_id = self.config.core_for(self.spawned);
+        let hook = self.config.on_worker_start.clone();
+        #[cfg(feature = "latency")]
+        let latency: Option<SymbolLatencyRecorder> = self
+            .config
+            .latency
+            .as_ref()
+            .map(|recorder| recorder.for_symbol(&symbol));
+        let worker_symbol = symbol.clone();
+        let sequencing = self.sequencing.clone();
+        let handle = std::thread::Builder::new()
+            .name(format!("book-{symbol}"))
+            .spawn(move || {
+                if let Some(hook) = hook {
+                    hook(&worker_symbol, core_id);
+                }
+                let book = build();
+                info!("Book worker for {} started", worker_symbol);
+                let symbol: Arc<str> = Arc::from(worker_symbol.as_str());
+                for job in receiver {
+                    run_job(
+                        &book,
+                        &symbol,
+                        sequencing.as_deref(),
+                        #[cfg(feature = "latency")]
+                        latency.as_ref(),
+                        job,
+                    );
+                }
+                info!("Book worker for {} drained", worker_symbol);
+                book
+            })
+            .map_err(|err| ManagerError::WorkerSpawnFailed {
+                symbol: symbol.clone(),
+                message: err.to_string(),
+            })?;
+
+        self.spawned += 1;
+        self.workers.insert(symbol, BookWorker { sender, handle });
+        Ok(())
+    }
+
+    /// Queue `command` for the book trading `symbol`.
+    ///
+    /// Blocks while the book's queue is full. The returned receiver yields
+    /// the command's [`SequencerResult`] once the worker has executed it;
+    /// dropping it does not cancel the command.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::BookNotFound`] if no worker runs `symbol`, or
+    /// [`ManagerError::WorkerStopped`] if its worker has exited.
+    pub fn submit(
+        &self,
+        symbol: &str,
+        command: SequencerCommand<T>,
+    ) -> Result<Receiver<SequencerResult>, ManagerError> {
+        let (reply, result) = sync_channel(1);
+        self.enqueue(symbol, command, Reply::Result(reply))?;
+        Ok(result)
+    }
+
+    /// Queue `command` for the book trading `symbol`, like
+    /// [`Self::submit`], and deliver its result with the global sequence
+    /// number it was journaled under.
+    ///
+    /// The receiver yields [`JournalError`] if the append failed; the
+    /// command has then still been executed against the book.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::JournalUnavailable`] if the executor has no
+    /// journal, and the errors of [`Self::submit`].
+    pub fn submit_sequenced(
+        &self,
+        symbol: &str,
+        command: SequencerCommand<T>,
+    ) -> Result<Receiver<Result<SequencedResult, JournalError>>, ManagerError> {
+        if self.sequencing.is_none() {
+            return Err(ManagerError::JournalUnavailable);
+        }
+        let (reply, result) = sync_channel(1);
+        self.enqueue(symbol, command, Reply::Sequenced(reply))?;
+        Ok(result)
+    }
+
+    /// Wait until the journal is durable up to and including
+    /// `sequence_num`. The returned future does not borrow the executor,
+    /// so it can be moved into a task.
+    ///
+    /// # Errors
+    ///
+    /// The future yields [`ManagerError::JournalUnavailable`] if the
+    /// executor has no journal, or if it is dropped before `sequence_num`
+    /// becomes durable.
+    pub fn await_sequence_durable(
+        &self,
+        sequence_num: u64,
+    ) -> impl Future<Output = Result<(), ManagerError>> + Send + 'static {
+        let durable = self
+            .sequencing
+            .as_ref()
+            .map(|sequencing| sequencing.durable.clone());
+        async move {
+            let mut durable = durable.ok_or(ManagerError::JournalUnavailable)?;
+            durable
+                .wait_for(|&durable| durable > sequence_num)
+                .await
+                .map(|_| ())
+                .map_err(|_| ManagerError::JournalUnavailable)
+        }
+    }
+
+    /// Highest sequence number the journal reports durable, or `None`
+    /// without a journal or before the first durable entry.
+    #[must_use]
+    pub fn durable_sequence(&self) -> Option<u64> {
+        let durable = *self.sequencing.as_ref()?.durable.borrow();
+        durable.checked_sub(1)
+    }
+
+    /// Flush the journal and publish how far it is now durable, for
+    /// journals that buffer writes. Does nothing without a journal.
+    ///
+    /// # Errors
+    ///
+    /// Returns the journal's flush error.
+    pub fn flush_journal(&self) -> Result<(), JournalError> {
+        match &self.sequencing {
+            Some(sequencing) => sequencing.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn enqueue(
+        &self,
+        symbol: &str,
+        command: SequencerCommand<T>,
+        reply: Reply,
+    ) -> Result<(), ManagerError> {
+        let worker = self
+            .workers
+            .get(symbol)
+            .ok_or_else(|| ManagerError::BookNotFound {
+                symbol: symbol.to_string(),
+            })?;
+        let job = Job {
+            command,
+            reply,
+            #[cfg(feature = "latency")]
+            enqueued_at: self.config.latency.as_ref().map(|_| Instant::now()),
+        };
+        worker
+            .sender
+            .send(job)
+            .map_err(|_| ManagerError::WorkerStopped {
+                symbol: symbol.to_string(),
+            })
+    }
+
+    /// Drain the worker for `symbol` and take its book back.
+    ///
+    /// Every command queued before this call is executed first. Returns
+    /// `None` if no worker runs `symbol` or the worker panicked.
+    pub fn remove_book(&mut self, symbol: &str) -> Option<OrderBook<T>> {
+        self.workers.remove(symbol)?.drain(symbol)
+    }
+
+    /// Symbols with a running worker.
+    #[must_use]
+    pub fn symbols(&self) -> Vec<String> {
+        self.workers.keys().cloned().collect()
+    }
+
+    /// Whether a worker runs a book for `symbol`.
+    #[must_use]
+    pub fn has_book(&self, symbol: &str) -> bool {
+        self.workers.contains_key(symbol)
+    }
+
+    /// Number of running workers.
+    #[must_use]
+    pub fn book_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// The executor's configuration.
+    #[must_use]
+    pub fn config(&self) -> &ExecutorConfig {
+        &self.config
+    }
+
+    /// Per-symbol command latency percentiles, or `None` if no latency
+    /// recorder is installed. See
+    /// [`LatencyRecorder::percentile_report`].
+    #[cfg(feature = "latency")]
+    #[must_use]
+    pub fn percentile_report(&self) -> Option<std::collections::BTreeMap<String, SymbolLatency>> {
+        self.config
+            .latency
+            .as_ref()
+            .map(LatencyRecorder::percentile_report)
+    }
+
+    /// Stop accepting commands, drain every queue and take the books back.
+    ///
+    /// Books whose worker panicked are logged and omitted.
+    pub fn shutdown(mut self) -> HashMap<String, OrderBook<T>> {
+        std::mem::take(&mut self.workers)
+            .into_iter()
+            .filter_map(|(symbol, worker)| {
+                let book = worker.drain(&symbol)?;
+                Some((symbol, book))
+            })
+            .collect()
+    }
+}
+
+impl<T> BookExecutor<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + Default + 'static,
+{
+    /// Create an executor with no books that journals every command it
+    /// executes to `journal`, numbering them from one past the journal's
+    /// last sequence number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::orderbook::sequencer::InMemoryJournal;
+    /// use orderbook_rs::{BookExecutor, ExecutorConfig, Journal, OrderBook, SequencerCommand};
+    /// use pricelevel::Side;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let journal = Arc::new(InMemoryJournal::<()>::new());
+    /// let mut executor = BookExecutor::with_journal(ExecutorConfig::new(), journal.clone());
+    /// executor.spawn_book(OrderBook::new("BTC/USD"))?;
+    ///
+    /// let cancel = SequencerCommand::CancelBySide { side: Side::Buy };
+    /// let sequenced = executor.submit_sequenced("BTC/USD", cancel)?.recv()??;
+    /// assert_eq!(sequenced.sequence_num, 0);
+    /// assert_eq!(journal.last_sequence(), Some(0));
+    /// assert_eq!(executor.durable_sequence(), Some(0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_journal<J>(config: ExecutorConfig, journal: Arc<J>) -> Self
+    where
+        J: Journal<T> + ?Sized + 'static,
+    {
+        let capacity = config.queue_capacity;
+        let mut executor = Self::new(config);
+        executor.sequencing = Some(Arc::new(Sequencing::spawn(
+            Box::new(JournalSink(journal)),
+            capacity,
+        )));
+        executor
+    }
+}
+
+impl<T> Drop for BookExecutor<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Drains every worker so queued commands are not lost; the books are
+    /// dropped afterwards. Use [`shutdown`](BookExecutor::shutdown) to keep
+    /// them.
+    fn drop(&mut self) {
+        for (symbol, worker) in self.workers.drain() {
+            let _ = worker.drain(&symbol);
+        }
+    }
+}
+
+impl<T> fmt::Debug for BookExecutor<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BookExecutor")
+            .field("config", &self.config)
+            .field("symbols", &self.symbols())
+            .field("journaled", &self.sequencing.is_some())
+            .finish()
+    }
+}
+
+/// Id of the order an update targets.
+fn update_order_id(update: &OrderUpdate) -> Id {
+    match *update {
+        OrderUpdate::UpdatePrice { order_id, .. }
+        | OrderUpdate::UpdateQuantity { order_id, .. }
+        | OrderUpdate::UpdatePriceAndQuantity { order_id, .. }
+        | OrderUpdate::Cancel { order_id }
+        | OrderUpdate::Replace { order_id, .. } => order_id,
+    }
+}
+
+/// Execute one command against `book` and describe the outcome.
+fn execute_command<T>(book: &OrderBook<T>, command: SequencerCommand<T>) -> SequencerResult
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let rejected = |err: crate::orderbook::OrderBookError| SequencerResult::Rejected {
+        reason: err.to_string(),
+    };
+    let traded = |match_result| SequencerResult::TradeExecuted {
+        trade_result: TradeResult::new(book.symbol().to_string(), match_result),
+    };
+
+    match command {
+        SequencerCommand::AddOrder(order) => {
+            let order_id = order.id();
+            match book.add_order(order) {
+                Ok(_) => SequencerResult::OrderAdded { order_id },
+                Err(err) => rejected(err),
+            }
+        }
+        SequencerCommand::CancelOrder(order_id) => match book.cancel_order(order_id) {
+            Ok(_) => SequencerResult::OrderCancelled { order_id },
+            Err(err) => rejected(err),
+        },
+        SequencerCommand::UpdateOrder(update) => {
+            let order_id = update_order_id(&update);
+            match book.update_order(update) {
+                Ok(_) => SequencerResult::OrderUpdated { order_id },
+                Err(err) => rejected(err),
+            }
+        }
+        SequencerCommand::MarketOrder { id, quantity, side } => {
+            match book.submit_market_order(id, quantity, side) {
+                Ok(match_result) => traded(match_result),
+                Err(err) => rejected(err),
+            }
+        }
+        SequencerCommand::MarketOrderByAmount { id, amount, side } => {
+            match book.submit_market_order_by_amount(id, amount, side) {
+                Ok(match_result) => traded(match_result),
+                Err(err) => rejected(err),
+            }
+        }
+        SequencerCommand::CancelAll => SequencerResult::MassCancelled {
+            result: book.cancel_all_orders(),
+        },
+        SequencerCommand::CancelBySide { side } => SequencerResult::MassCancelled {
+            result: book.cancel_orders_by_side(side),
+        },
+        SequencerCommand::CancelByUser { user_id } => SequencerResult::MassCancelled {
+            result: book.cancel_orders_by_user(user_id),
+        },
+        SequencerCommand::CancelByPriceRange {
+            side,
+            min_price,
+            max_price,
+        } => SequencerResult::MassCancelled {
+            result: book.cancel_orders_by_price_range(side, min_price, max_price),
+        },
+        SequencerCommand::EvictExpiredOrders { now_ms } => {
+            let evicted: Vec<Id> = book
+                .evict_expired_orders(now_ms)
+                .iter()
+                .map(|order| order.id())
+                .collect();
+            SequencerResult::MassCancelled {
+                result: MassCancelResult::new(evicted.len(), evicted),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_assignment_is_round_robin() {
+        let config = ExecutorConfig::new().with_core_ids(vec![2, 5]);
+        assert_eq!(config.core_for(0), Some(2));
+        assert_eq!(config.core_for(1), Some(5));
+        assert_eq!(config.core_for(2), Some(2));
+        assert_eq!(ExecutorConfig::default().core_for(0), None);
+    }
+
+    #[test]
+    fn test_update_order_id_of_cancel() {
+        let order_id = Id::from_u64(7);
+        let update = OrderUpdate::Cancel { order_id };
+        assert_eq!(update_order_id(&update), order_id);
+    }
+}
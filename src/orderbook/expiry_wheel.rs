@@ -0,0 +1,387 @@
+//! Hierarchical timing wheel of resting order deadlines.
+//!
+//! [`OrderBook::evict_expired_orders`](crate::OrderBook::evict_expired_orders)
+//! used to walk every resting order to find the expired ones. The book now
+//! registers each resting `Gtd` order here under its deadline, and each
+//! `Day` order in a set that expires as a whole at market close, so a sweep
+//! only touches the orders that are actually due.
+//!
+//! The wheel has [`WHEEL_LEVELS`] levels of [`WHEEL_SLOTS`] slots with a
+//! 1 ms tick; a slot at level `n` spans `64^n` ms, so the wheel covers
+//! `64^6` ms (about 795 days) ahead of its current time. Deadlines further
+//! out wait in an overflow list until they come within range. Advancing
+//! jumps straight to the next occupied slot using a per-level occupancy
+//! bitmap, and entries in a higher-level slot cascade down to finer slots
+//! as their time approaches.
+//!
+//! Scheduling is O(1). Unscheduling is O(1) as well: the deadline index is
+//! the source of truth, and a slot entry whose index value no longer
+//! matches (the order was cancelled, filled or rescheduled) is discarded
+//! when its slot is processed.
+
+use dashmap::{DashMap, DashSet};
+use pricelevel::Id;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Levels in the wheel.
+pub const WHEEL_LEVELS: usize = 6;
+
+/// Slots per level.
+pub const WHEEL_SLOTS: usize = 64;
+
+const SLOT_BITS: u32 = 6;
+const SLOT_MASK: u64 = (WHEEL_SLOTS as u64) - 1;
+
+/// Milliseconds covered by the wheel ahead of its current time.
+const HORIZON_MS: u64 = 1 << (SLOT_BITS as usize * WHEEL_LEVELS);
+
+/// Counters of an [`ExpiryWheel`], as returned by
+/// [`OrderBook::expiry_wheel_stats`](crate::OrderBook::expiry_wheel_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpiryWheelStats {
+    /// `Gtd` orders currently scheduled
+    pub scheduled: usize,
+    /// `Day` orders currently tracked
+    pub day_orders: usize,
+    /// Scheduled deadlines beyond the wheel's horizon
+    pub overflow: usize,
+    /// Wheel time in Unix milliseconds; every deadline up to it has been
+    /// processed. `None` before the first order is scheduled
+    pub elapsed_ms: Option<u64>,
+    /// Deadlines scheduled since creation
+    pub total_scheduled: u64,
+    /// Deadlines that came due since creation
+    pub total_due: u64,
+    /// Entries moved to a finer slot since creation
+    pub cascades: u64,
+    /// Stale entries discarded since creation
+    pub stale_discarded: u64,
+}
+
+#[derive(Debug)]
+struct Level {
+    /// Bit `i` is set while `slots[i]` is non-empty
+    occupied: u64,
+    slots: Vec<Vec<(u64, Id)>>,
+}
+
+impl Level {
+    fn new() -> Self {
+        Self {
+            occupied: 0,
+            slots: (0..WHEEL_SLOTS).map(|_| Vec::new()).collect(),
+        }
+    }
+}
+
+/// Milliseconds spanned by one slot of `level`.
+fn slot_range(level: usize) -> u64 {
+    1 << (SLOT_BITS as usize * level)
+}
+
+/// Milliseconds spanned by all slots of `level`.
+fn level_range(level: usize) -> u64 {
+    slot_range(level + 1)
+}
+
+/// Level whose slots distinguish `deadline` from `elapsed`.
+fn level_for(elapsed: u64, deadline: u64) -> usize {
+    let masked = ((elapsed ^ deadline) | SLOT_MASK).min(HORIZON_MS - 1);
+    let significant = 63 - masked.leading_zeros() as usize;
+    significant / SLOT_BITS as usize
+}
+
+#[derive(Debug)]
+struct Wheel {
+    elapsed: Option<u64>,
+    levels: Vec<Level>,
+    /// Deadlines already behind `elapsed` when scheduled
+    behind: Vec<(u64, Id)>,
+    /// Deadlines beyond the horizon
+    overflow: Vec<(u64, Id)>,
+}
+
+impl Wheel {
+    fn new() -> Self {
+        Self {
+            elapsed: None,
+            levels: (0..WHEEL_LEVELS).map(|_| Level::new()).collect(),
+            behind: Vec::new(),
+            overflow: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, deadline: u64, id: Id, now: u64) {
+        let elapsed = *self.elapsed.get_or_insert(now);
+        if deadline <= elapsed {
+            self.behind.push((deadline, id));
+        } else if deadline - elapsed >= HORIZON_MS {
+            self.overflow.push((deadline, id));
+        } else {
+            let level = level_for(elapsed, deadline);
+            let slot = ((deadline >> (SLOT_BITS as usize * level)) & SLOT_MASK) as usize;
+            self.levels[level].slots[slot].push((deadline, id));
+            self.levels[level].occupied |= 1 << slot;
+        }
+    }
+
+    /// The earliest occupied slot as `(level, slot, slot start)`.
+    fn next_expiration(&self, elapsed: u64) -> Option<(usize, usize, u64)> {
+        self.levels.iter().enumerate().find_map(|(index, level)| {
+            if level.occupied == 0 {
+                return None;
+            }
+            let slot_range = slot_range(index);
+            let now_slot = (elapsed / slot_range) & SLOT_MASK;
+            let rotated = level.occupied.rotate_right(now_slot as u32);
+            let slot = ((u64::from(rotated.trailing_zeros()) + now_slot) & SLOT_MASK) as usize;
+            let level_start = elapsed & !(level_range(index) - 1);
+            let mut start = level_start + slot as u64 * slot_range;
+            if start <= elapsed {
+                // Only the top level wraps: a slot "before" the current
+                // one is a full rotation ahead.
+                start += level_range(index);
+            }
+            Some((index, slot, start))
+        })
+    }
+}
+
+/// Deadline index of a book's resting `Gtd` and `Day` orders.
+#[derive(Debug)]
+pub struct ExpiryWheel {
+    /// Current deadline of each scheduled `Gtd` order
+    deadlines: DashMap<Id, u64>,
+    day_orders: DashSet<Id>,
+    wheel: Mutex<Wheel>,
+    total_scheduled: AtomicU64,
+    total_due: AtomicU64,
+    cascades: AtomicU64,
+    stale_discarded: AtomicU64,
+}
+
+impl Default for ExpiryWheel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExpiryWheel {
+    /// Create an empty wheel.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            deadlines: DashMap::new(),
+            day_orders: DashSet::new(),
+            wheel: Mutex::new(Wheel::new()),
+            total_scheduled: AtomicU64::new(0),
+            total_due: AtomicU64::new(0),
+            cascades: AtomicU64::new(0),
+            stale_discarded: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Wheel> {
+        match self.wheel.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Schedule order `id` to come due at `deadline` (Unix ms), replacing
+    /// any earlier schedule. `now` anchors the wheel on first use.
+    pub fn schedule(&self, id: Id, deadline: u64, now: u64) {
+        self.deadlines.insert(id, deadline);
+        self.lock().insert(deadline, id, now);
+        self.total_scheduled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Track `id` as a `Day` order.
+    pub fn track_day_order(&self, id: Id) {
+        self.day_orders.insert(id);
+    }
+
+    /// Forget order `id`. O(1); its slot entry is discarded lazily.
+    pub fn unschedule(&self, id: &Id) {
+        self.deadlines.remove(id);
+        self.day_orders.remove(id);
+    }
+
+    /// Forget every order.
+    pub fn clear(&self) {
+        self.deadlines.clear();
+        self.day_orders.clear();
+        let mut wheel = self.lock();
+        let elapsed = wheel.elapsed;
+        *wheel = Wheel::new();
+        wheel.elapsed = elapsed;
+    }
+
+    /// Advance the wheel to `now` and return the `Gtd` orders whose deadline
+    /// is at or before it, plus every tracked `Day` order if `day_due`.
+    /// Returned orders are unscheduled.
+    pub fn take_due(&self, now: u64, day_due: bool) -> Vec<Id> {
+        let mut due = Vec::new();
+        let mut wheel = self.lock();
+
+        let behind = std::mem::take(&mut wheel.behind);
+        for (deadline, id) in behind {
+            if deadline <= now {
+                self.collect(deadline, id, &mut due);
+            } else {
+                wheel.behind.push((deadline, id));
+            }
+        }
+
+        if let Some(mut elapsed) = wheel.elapsed
+            && now > elapsed
+        {
+            while let Some((level, slot, start)) = wheel.next_expiration(elapsed) {
+                if start > now {
+                    break;
+                }
+                elapsed = start;
+                wheel.elapsed = Some(elapsed);
+                wheel.levels[level].occupied &= !(1 << slot);
+                let entries = std::mem::take(&mut wheel.levels[level].slots[slot]);
+                for (deadline, id) in entries {
+                    if self.deadlines.get(&id).map(|d| *d) != Some(deadline) {
+                        self.stale_discarded.fetch_add(1, Ordering::Relaxed);
+                    } else if deadline <= now {
+                        self.collect(deadline, id, &mut due);
+                    } else {
+                        self.cascades.fetch_add(1, Ordering::Relaxed);
+                        wheel.insert(deadline, id, elapsed);
+                    }
+                }
+            }
+            wheel.elapsed = Some(now);
+
+            let overflow = std::mem::take(&mut wheel.overflow);
+            for (deadline, id) in overflow {
+                if deadline <= now {
+                    self.collect(deadline, id, &mut due);
+                } else {
+                    wheel.insert(deadline, id, now);
+                }
+            }
+        }
+        drop(wheel);
+
+        if day_due {
+            let day: Vec<Id> = self.day_orders.iter().map(|id| *id).collect();
+            for id in &day {
+                self.day_orders.remove(id);
+            }
+            due.extend(day);
+        }
+        due
+    }
+
+    /// Push `id` if `deadline` is still its current deadline, unscheduling it.
+    fn collect(&self, deadline: u64, id: Id, due: &mut Vec<Id>) {
+        if self
+            .deadlines
+            .remove_if(&id, |_, d| *d == deadline)
+            .is_some()
+        {
+            self.total_due.fetch_add(1, Ordering::Relaxed);
+            due.push(id);
+        } else {
+            self.stale_discarded.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Current counters.
+    #[must_use]
+    pub fn stats(&self) -> ExpiryWheelStats {
+        let (elapsed_ms, overflow) = {
+            let wheel = self.lock();
+            (wheel.elapsed, wheel.overflow.len())
+        };
+        ExpiryWheelStats {
+            scheduled: self.deadlines.len(),
+            day_orders: self.day_orders.len(),
+            overflow,
+            elapsed_ms,
+            total_scheduled: self.total_scheduled.load(Ordering::Relaxed),
+            total_due: self.total_due.load(Ordering::Relaxed),
+            cascades: self.cascades.load(Ordering::Relaxed),
+            stale_discarded: self.stale_discarded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadlines_come_due_exactly_at_their_tick() {
+        let wheel = ExpiryWheel::new();
+        let base = 1_700_000_000_000;
+        // Spread across levels: same slot, next slots, hours, days, years.
+        let offsets = [1, 2, 63, 64, 65, 4_095, 4_096, 3_600_000, 86_400_000 * 3];
+        let ids: Vec<Id> = offsets.iter().map(|_| Id::new_uuid()).collect();
+        for (id, offset) in ids.iter().zip(offsets) {
+            wheel.schedule(*id, base + offset, base);
+        }
+
+        for (id, offset) in ids.iter().zip(offsets) {
+            assert!(wheel.take_due(base + offset - 1, false).is_empty());
+            assert_eq!(wheel.take_due(base + offset, false), vec![*id]);
+        }
+        assert_eq!(wheel.stats().scheduled, 0);
+        assert!(wheel.stats().cascades > 0);
+    }
+
+    #[test]
+    fn test_large_jumps_and_overflow_deadlines() {
+        let wheel = ExpiryWheel::new();
+        let near = Id::new_uuid();
+        let far = Id::new_uuid();
+        wheel.schedule(near, 5_000, 0);
+        wheel.schedule(far, HORIZON_MS * 3, 0);
+        assert_eq!(wheel.stats().overflow, 1);
+
+        assert_eq!(wheel.take_due(HORIZON_MS * 2 + 1, false), vec![near]);
+        assert_eq!(wheel.stats().overflow, 0);
+        assert!(wheel.take_due(HORIZON_MS * 3 - 1, false).is_empty());
+        assert_eq!(wheel.take_due(HORIZON_MS * 3, false), vec![far]);
+    }
+
+    #[test]
+    fn test_unscheduled_and_rescheduled_entries_are_discarded() {
+        let wheel = ExpiryWheel::new();
+        let (a, b, c) = (Id::new_uuid(), Id::new_uuid(), Id::new_uuid());
+        wheel.schedule(a, 100, 0);
+        wheel.schedule(b, 100, 0);
+        wheel.schedule(c, 100, 0);
+        wheel.unschedule(&a);
+        wheel.schedule(b, 300, 0);
+
+        assert_eq!(wheel.take_due(200, false), vec![c]);
+        assert_eq!(wheel.take_due(300, false), vec![b]);
+        assert_eq!(wheel.stats().stale_discarded, 2);
+    }
+
+    #[test]
+    fn test_past_deadlines_and_day_orders() {
+        let wheel = ExpiryWheel::new();
+        let late = Id::new_uuid();
+        let day = Id::new_uuid();
+        wheel.schedule(Id::new_uuid(), 1_000, 1_000);
+        wheel.schedule(late, 500, 1_000);
+        wheel.track_day_order(day);
+
+        // `now` behind the wheel: only what is due at that instant.
+        assert!(wheel.take_due(499, false).is_empty());
+        assert_eq!(wheel.take_due(500, false), vec![late]);
+        let due = wheel.take_due(1_000, true);
+        assert_eq!(due.len(), 2);
+        assert!(due.contains(&day));
+        assert_eq!(wheel.stats().day_orders, 0);
+    }
+}
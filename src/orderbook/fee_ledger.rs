@@ -0,0 +1,309 @@
+//! Per-user, per-symbol fee accrual.
+//!
+//! [`FeeLedger`] attributes the fee of every fill of a [`TradeResult`] to
+//! the maker and taker that paid it and accumulates the result per user
+//! and symbol, ready for fee reports and exports.
+//!
+//! The ledger is not wired into the matching path: drive it from a
+//! `TradeListener`. [`FeeLedger::record_charged`] books the fees the book
+//! already charged, using the owners carried on [`TradeResult::fills`],
+//! and follows busts and corrections.
+//! [`FeeLedger::record_trade`] (flat [`FeeSchedule`]) and
+//! [`FeeLedger::record_trade_tiered`] ([`TieredFeeSchedule`]) recompute the
+//! fees and take an `owner_of` lookup from order id to user id.
+
+use crate::orderbook::fees::{FeeSchedule, TieredFeeSchedule};
+use crate::orderbook::trade::{LiquidityFlag, TradeResult};
+use pricelevel::{Hash32, Id};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Fees accrued by one user on one symbol.
+///
+/// Fees are in the same unit as the notional (price × quantity). Negative
+/// values are rebates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSummary {
+    /// Sum of maker fees (negative = net rebate)
+    pub maker_fees: i128,
+
+    /// Sum of taker fees
+    pub taker_fees: i128,
+
+    /// Notional traded as maker
+    pub maker_notional: u128,
+
+    /// Notional traded as taker
+    pub taker_notional: u128,
+
+    /// Number of maker fills
+    pub maker_fills: u64,
+
+    /// Number of taker fills
+    pub taker_fills: u64,
+}
+
+impl FeeSummary {
+    /// Net fees paid: `maker_fees + taker_fees`, saturating.
+    #[must_use]
+    pub fn net_fees(&self) -> i128 {
+        self.maker_fees.saturating_add(self.taker_fees)
+    }
+
+    /// Total notional traded on either side, saturating.
+    #[must_use]
+    pub fn total_notional(&self) -> u128 {
+        self.maker_notional.saturating_add(self.taker_notional)
+    }
+
+    fn add(&mut self, fee: i128, notional: u128, is_maker: bool) {
+        if is_maker {
+            self.maker_fees = self.maker_fees.saturating_add(fee);
+            self.maker_notional = self.maker_notional.saturating_add(notional);
+            self.maker_fills = self.maker_fills.saturating_add(1);
+        } else {
+            self.taker_fees = self.taker_fees.saturating_add(fee);
+            self.taker_notional = self.taker_notional.saturating_add(notional);
+            self.taker_fills = self.taker_fills.saturating_add(1);
+        }
+    }
+
+    /// Undo one [`Self::add`], for a fill that was busted or corrected.
+    fn remove(&mut self, fee: i128, notional: u128, is_maker: bool) {
+        if is_maker {
+            self.maker_fees = self.maker_fees.saturating_sub(fee);
+            self.maker_notional = self.maker_notional.saturating_sub(notional);
+            self.maker_fills = self.maker_fills.saturating_sub(1);
+        } else {
+            self.taker_fees = self.taker_fees.saturating_sub(fee);
+            self.taker_notional = self.taker_notional.saturating_sub(notional);
+            self.taker_fills = self.taker_fills.saturating_sub(1);
+        }
+    }
+
+    fn merge(&mut self, other: &FeeSummary) {
+        self.maker_fees = self.maker_fees.saturating_add(other.maker_fees);
+        self.taker_fees = self.taker_fees.saturating_add(other.taker_fees);
+        self.maker_notional = self.maker_notional.saturating_add(other.maker_notional);
+        self.taker_notional = self.taker_notional.saturating_add(other.taker_notional);
+        self.maker_fills = self.maker_fills.saturating_add(other.maker_fills);
+        self.taker_fills = self.taker_fills.saturating_add(other.taker_fills);
+    }
+}
+
+/// One row of a [`FeeLedger::export`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeLedgerEntry {
+    /// User the fees were charged to
+    pub user_id: Hash32,
+
+    /// Symbol the fees were charged on
+    pub symbol: String,
+
+    /// Accrued fees
+    pub summary: FeeSummary,
+}
+
+/// Accumulates maker and taker fees per user per symbol.
+///
+/// Thread-safe: all state lives behind a single [`Mutex`], so a ledger
+/// can be shared with `TradeListener` closures via `Arc`.
+#[derive(Debug, Default)]
+pub struct FeeLedger {
+    entries: Mutex<HashMap<(Hash32, String), FeeSummary>>,
+}
+
+impl FeeLedger {
+    /// Create an empty ledger.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a single `fee` charged to `user_id` on `notional` traded on
+    /// `symbol`.
+    pub fn record_fee(
+        &self,
+        user_id: Hash32,
+        symbol: &str,
+        notional: u128,
+        fee: i128,
+        is_maker: bool,
+    ) {
+        let mut entries = match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        entries
+            .entry((user_id, symbol.to_string()))
+            .or_default()
+            .add(fee, notional, is_maker);
+    }
+
+    /// Record the fees the book charged on every fill in `trade`, to the
+    /// owners named on [`TradeResult::fills`].
+    ///
+    /// A compensating result from a bust or correction first takes the
+    /// superseded fill back out, so the ledger follows the fill as it now
+    /// stands. Records nothing for a result without fills or amendment,
+    /// such as one built directly with [`TradeResult::new`].
+    pub fn record_charged(&self, trade: &TradeResult) {
+        if let Some(amendment) = &trade.amendment {
+            let superseded = amendment.superseded();
+            let notional = superseded.notional();
+            let mut entries = match self.entries.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            for party in [&superseded.maker, &superseded.taker] {
+                entries
+                    .entry((party.user_id, trade.symbol.clone()))
+                    .or_default()
+                    .remove(party.fee, notional, party.liquidity == LiquidityFlag::Maker);
+            }
+        }
+        for fill in &trade.fills {
+            let notional = fill.notional();
+            for party in [&fill.maker, &fill.taker] {
+                self.record_fee(
+                    party.user_id,
+                    &trade.symbol,
+                    notional,
+                    party.fee,
+                    party.liquidity == LiquidityFlag::Maker,
+                );
+            }
+        }
+    }
+
+    /// Record every fill in `trade` at the rates of `schedule`.
+    ///
+    /// Fees are computed per fill with [`FeeSchedule::calculate_fee`], so
+    /// they sum to [`TradeResult::total_maker_fees`] /
+    /// [`TradeResult::total_taker_fees`] for the same schedule. Sides
+    /// whose order has no owner in `owner_of` are skipped.
+    pub fn record_trade<F>(&self, trade: &TradeResult, schedule: &FeeSchedule, owner_of: F)
+    where
+        F: Fn(Id) -> Option<Hash32>,
+    {
+        self.record_fills(trade, owner_of, |_, notional, is_maker| {
+            schedule.calculate_fee(notional, is_maker)
+        });
+    }
+
+    /// Record every fill in `trade` at each user's tier of `schedule` at
+    /// `now_ms`.
+    ///
+    /// This only reads the tiers; credit the traded volume separately with
+    /// [`TieredFeeSchedule::record_trade`] (after this call, so the fills
+    /// are charged at the tier in force before they executed). A book with
+    /// the schedule installed already charges and credits its fills, so
+    /// use [`Self::record_charged`] for its trades instead.
+    pub fn record_trade_tiered<F>(
+        &self,
+        trade: &TradeResult,
+        schedule: &TieredFeeSchedule,
+        owner_of: F,
+        now_ms: u64,
+    ) where
+        F: Fn(Id) -> Option<Hash32>,
+    {
+        self.record_fills(trade, owner_of, |user_id, notional, is_maker| {
+            schedule.calculate_fee(user_id, &trade.symbol, notional, is_maker, now_ms)
+        });
+    }
+
+    /// Fees accrued by `user_id`, one `(symbol, summary)` pair per symbol,
+    /// sorted by symbol.
+    #[must_use]
+    pub fn fees_for_user(&self, user_id: Hash32) -> Vec<(String, FeeSummary)> {
+        let entries = match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut fees: Vec<(String, FeeSummary)> = entries
+            .iter()
+            .filter(|((user, _), _)| *user == user_id)
+            .map(|((_, symbol), summary)| (symbol.clone(), *summary))
+            .collect();
+        fees.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        fees
+    }
+
+    /// Fees accrued by `user_id` on `symbol`, if any.
+    #[must_use]
+    pub fn fees_for_user_symbol(&self, user_id: Hash32, symbol: &str) -> Option<FeeSummary> {
+        let entries = match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        entries.get(&(user_id, symbol.to_string())).copied()
+    }
+
+    /// Fees accrued by `user_id` across every symbol.
+    #[must_use]
+    pub fn user_total(&self, user_id: Hash32) -> FeeSummary {
+        let mut total = FeeSummary::default();
+        for (_, summary) in self.fees_for_user(user_id) {
+            total.merge(&summary);
+        }
+        total
+    }
+
+    /// Every `(user, symbol)` entry, sorted by user id bytes then symbol
+    /// so the export is deterministic.
+    #[must_use]
+    pub fn export(&self) -> Vec<FeeLedgerEntry> {
+        let entries = match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut exported: Vec<FeeLedgerEntry> = entries
+            .iter()
+            .map(|((user_id, symbol), summary)| FeeLedgerEntry {
+                user_id: *user_id,
+                symbol: symbol.clone(),
+                summary: *summary,
+            })
+            .collect();
+        exported.sort_unstable_by(|a, b| {
+            a.user_id
+                .0
+                .cmp(&b.user_id.0)
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        exported
+    }
+
+    /// Discard every accrued fee.
+    pub fn reset(&self) {
+        let mut entries = match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        entries.clear();
+    }
+
+    fn record_fills<F, C>(&self, trade: &TradeResult, owner_of: F, fee_for: C)
+    where
+        F: Fn(Id) -> Option<Hash32>,
+        C: Fn(Hash32, u128, bool) -> i128,
+    {
+        for fill in trade.match_result.trades().as_vec() {
+            let notional = fill
+                .price()
+                .as_u128()
+                .saturating_mul(u128::from(fill.quantity().as_u64()));
+            for (order_id, is_maker) in [
+                (fill.maker_order_id(), true),
+                (fill.taker_order_id(), false),
+            ] {
+                if let Some(user_id) = owner_of(order_id) {
+                    let fee = fee_for(user_id, notional, is_maker);
+                    self.record_fee(user_id, &trade.symbol, notional, fee, is_maker);
+                }
+            }
+        }
+    }
+}
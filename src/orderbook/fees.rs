@@ -1,6 +1,10 @@
 //! Fee schedule implementation for OrderBook trading fees
 
+use crate::orderbook::trade::TradeResult;
+use pricelevel::Hash32;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
 
 /// Denominator for basis-point fee math: 1 bps = 1 / 10_000 of the notional.
 const BPS_DENOMINATOR: u128 = 10_000;
@@ -399,6 +403,236 @@ impl Default for FeeSchedule {
     }
 }
 
+/// Default rolling window for tiered fee volume: 30 days.
+pub const DEFAULT_FEE_VOLUME_WINDOW_MS: u64 = 30 * 24 * 60 * 60 * 1_000;
+
+/// Number of buckets the rolling volume window is divided into.
+const VOLUME_BUCKETS_PER_WINDOW: u64 = 30;
+
+/// One volume tier of a [`TieredFeeSchedule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// Minimum rolling traded notional (price × quantity) a user needs to
+    /// qualify for this tier
+    pub min_volume: u128,
+
+    /// Maker and taker rates applied in this tier
+    pub schedule: FeeSchedule,
+}
+
+/// Volume-tiered maker/taker fee schedule with per-symbol overrides
+///
+/// Each user's traded notional is accumulated internally from
+/// [`TradeResult`]s (see [`Self::record_trade`]) over a rolling window
+/// (30 days by default). The user pays the rates of the highest tier whose
+/// [`FeeTier::min_volume`] their rolling volume reaches, or the base
+/// schedule below the first tier. A per-symbol override replaces the
+/// tiered rates for every user trading that symbol.
+///
+/// The window is tracked in 30 equal buckets, so volume ages out with a
+/// granularity of `window_ms / 30` (one day for the default window).
+///
+/// Thread-safe: volume state lives behind a [`Mutex`].
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{FeeSchedule, TieredFeeSchedule};
+/// use pricelevel::Hash32;
+///
+/// let fees = TieredFeeSchedule::new(FeeSchedule::new(2, 10))
+///     .with_tier(1_000_000, FeeSchedule::new(0, 7))
+///     .with_tier(10_000_000, FeeSchedule::new(-1, 5));
+///
+/// let user = Hash32::new([1; 32]);
+/// assert_eq!(fees.schedule_for(user, "BTC/USD", 0), FeeSchedule::new(2, 10));
+///
+/// fees.record_volume(user, 2_000_000, 0);
+/// assert_eq!(fees.schedule_for(user, "BTC/USD", 0), FeeSchedule::new(0, 7));
+/// ```
+#[derive(Debug)]
+pub struct TieredFeeSchedule {
+    base: FeeSchedule,
+    tiers: Vec<FeeTier>,
+    window_ms: u64,
+    symbol_overrides: HashMap<String, FeeSchedule>,
+    volumes: Mutex<HashMap<Hash32, VecDeque<(u64, u128)>>>,
+}
+
+impl TieredFeeSchedule {
+    /// Create a tiered schedule charging `base` below the first tier
+    #[must_use = "TieredFeeSchedule does nothing unless used"]
+    pub fn new(base: FeeSchedule) -> Self {
+        Self {
+            base,
+            tiers: Vec::new(),
+            window_ms: DEFAULT_FEE_VOLUME_WINDOW_MS,
+            symbol_overrides: HashMap::new(),
+            volumes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Add a tier applying `schedule` from `min_volume` of rolling
+    /// notional. A tier with the same `min_volume` is replaced.
+    #[must_use]
+    pub fn with_tier(mut self, min_volume: u128, schedule: FeeSchedule) -> Self {
+        self.tiers.retain(|tier| tier.min_volume != min_volume);
+        self.tiers.push(FeeTier {
+            min_volume,
+            schedule,
+        });
+        self.tiers.sort_unstable_by_key(|tier| tier.min_volume);
+        self
+    }
+
+    /// Set the rolling volume window in milliseconds (at least 30ms, one
+    /// per bucket)
+    #[must_use]
+    pub fn with_window_ms(mut self, window_ms: u64) -> Self {
+        self.window_ms = window_ms.max(VOLUME_BUCKETS_PER_WINDOW);
+        self
+    }
+
+    /// Charge `schedule` on `symbol` regardless of the user's tier
+    #[must_use]
+    pub fn with_symbol_override(
+        mut self,
+        symbol: impl Into<String>,
+        schedule: FeeSchedule,
+    ) -> Self {
+        self.symbol_overrides.insert(symbol.into(), schedule);
+        self
+    }
+
+    /// Schedule applied below the first tier
+    #[must_use]
+    pub fn base(&self) -> FeeSchedule {
+        self.base
+    }
+
+    /// Configured tiers, sorted by ascending `min_volume`
+    #[must_use]
+    pub fn tiers(&self) -> &[FeeTier] {
+        &self.tiers
+    }
+
+    /// Rolling volume window in milliseconds
+    #[must_use]
+    pub fn window_ms(&self) -> u64 {
+        self.window_ms
+    }
+
+    /// Add `notional` to `user_id`'s rolling volume at `now_ms`
+    pub fn record_volume(&self, user_id: Hash32, notional: u128, now_ms: u64) {
+        let bucket_ms = self.bucket_ms();
+        let bucket = now_ms - now_ms % bucket_ms;
+        let mut volumes = match self.volumes.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let buckets = volumes.entry(user_id).or_default();
+        // Timestamps from several books may interleave slightly out of
+        // order; keep the buckets sorted so pruning from the front stays
+        // correct.
+        match buckets.iter().rposition(|(start, _)| *start <= bucket) {
+            Some(index) if buckets[index].0 == bucket => {
+                let total = &mut buckets[index].1;
+                *total = total.saturating_add(notional);
+            }
+            Some(index) => buckets.insert(index + 1, (bucket, notional)),
+            None => buckets.push_front((bucket, notional)),
+        }
+        let newest = buckets
+            .back()
+            .map_or(now_ms, |(start, _)| now_ms.max(*start));
+        let oldest = self.oldest_bucket(newest);
+        while buckets.front().is_some_and(|(start, _)| *start < oldest) {
+            buckets.pop_front();
+        }
+    }
+
+    /// Credit every fill in `trade` to its maker and taker at `now_ms`
+    ///
+    /// Owners are read from [`TradeResult::fills`]; a side whose order was
+    /// placed without a user id (`Hash32::zero()`) is not credited.
+    pub fn record_trade(&self, trade: &TradeResult, now_ms: u64) {
+        for fill in &trade.fills {
+            let notional = fill.notional();
+            for user_id in [fill.maker.user_id, fill.taker.user_id] {
+                if user_id != Hash32::zero() {
+                    self.record_volume(user_id, notional, now_ms);
+                }
+            }
+        }
+    }
+
+    /// Traded notional of `user_id` within the window ending at `now_ms`
+    #[must_use]
+    pub fn rolling_volume(&self, user_id: Hash32, now_ms: u64) -> u128 {
+        let volumes = match self.volumes.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let oldest = self.oldest_bucket(now_ms);
+        volumes.get(&user_id).map_or(0, |buckets| {
+            buckets
+                .iter()
+                .filter(|(start, _)| *start >= oldest && *start <= now_ms)
+                .fold(0u128, |sum, (_, notional)| sum.saturating_add(*notional))
+        })
+    }
+
+    /// Fee schedule `user_id` pays on `symbol` at `now_ms`
+    #[must_use]
+    pub fn schedule_for(&self, user_id: Hash32, symbol: &str, now_ms: u64) -> FeeSchedule {
+        if let Some(schedule) = self.symbol_overrides.get(symbol) {
+            return *schedule;
+        }
+        let volume = self.rolling_volume(user_id, now_ms);
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map_or(self.base, |tier| tier.schedule)
+    }
+
+    /// Fee `user_id` pays on `notional` traded on `symbol` at `now_ms`
+    ///
+    /// Same rounding and saturation as [`FeeSchedule::calculate_fee`].
+    #[must_use = "Fee calculation result must be used"]
+    pub fn calculate_fee(
+        &self,
+        user_id: Hash32,
+        symbol: &str,
+        notional: u128,
+        is_maker: bool,
+        now_ms: u64,
+    ) -> i128 {
+        self.schedule_for(user_id, symbol, now_ms)
+            .calculate_fee(notional, is_maker)
+    }
+
+    /// Discard all tracked volume
+    pub fn reset_volumes(&self) {
+        let mut volumes = match self.volumes.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        volumes.clear();
+    }
+
+    fn bucket_ms(&self) -> u64 {
+        (self.window_ms / VOLUME_BUCKETS_PER_WINDOW).max(1)
+    }
+
+    /// Start of the oldest bucket still inside the window ending at `now_ms`
+    fn oldest_bucket(&self, now_ms: u64) -> u64 {
+        let bucket_ms = self.bucket_ms();
+        let current = now_ms - now_ms % bucket_ms;
+        current.saturating_sub(bucket_ms.saturating_mul(VOLUME_BUCKETS_PER_WINDOW - 1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -692,4 +926,109 @@ mod tests {
         let schedule = FeeSchedule::default();
         assert!(schedule.is_zero_fee());
     }
+
+    #[test]
+    fn test_tiered_schedule_picks_highest_reached_tier() {
+        let fees = TieredFeeSchedule::new(FeeSchedule::new(2, 10))
+            .with_tier(10_000, FeeSchedule::new(-1, 5))
+            .with_tier(1_000, FeeSchedule::new(0, 7));
+        let user = Hash32::new([1; 32]);
+
+        assert_eq!(fees.tiers()[0].min_volume, 1_000);
+        assert_eq!(fees.schedule_for(user, "BTC", 0), FeeSchedule::new(2, 10));
+        fees.record_volume(user, 1_000, 0);
+        assert_eq!(fees.schedule_for(user, "BTC", 0), FeeSchedule::new(0, 7));
+        fees.record_volume(user, 9_000, 10);
+        assert_eq!(fees.schedule_for(user, "BTC", 10), FeeSchedule::new(-1, 5));
+        assert_eq!(fees.calculate_fee(user, "BTC", 10_000, true, 10), -1);
+    }
+
+    #[test]
+    fn test_tiered_schedule_volume_rolls_out_of_window() {
+        let fees = TieredFeeSchedule::new(FeeSchedule::zero_fee())
+            .with_window_ms(3_000)
+            .with_tier(500, FeeSchedule::taker_only(1));
+        let user = Hash32::new([2; 32]);
+
+        fees.record_volume(user, 500, 50);
+        fees.record_volume(user, 200, 1_500);
+        assert_eq!(fees.rolling_volume(user, 2_999), 700);
+        // The first 100ms bucket has left the 3s window.
+        assert_eq!(fees.rolling_volume(user, 3_000), 200);
+        assert_eq!(
+            fees.schedule_for(user, "BTC", 3_000),
+            FeeSchedule::zero_fee()
+        );
+        assert_eq!(fees.rolling_volume(user, 10_000), 0);
+    }
+
+    #[test]
+    fn test_tiered_schedule_out_of_order_volume_keeps_buckets_sorted() {
+        let fees = TieredFeeSchedule::new(FeeSchedule::zero_fee()).with_window_ms(3_000);
+        let user = Hash32::new([5; 32]);
+
+        fees.record_volume(user, 100, 2_050);
+        // Late arrivals land in their own (older) buckets, or fold into an
+        // existing one, instead of being appended behind a newer bucket.
+        fees.record_volume(user, 10, 150);
+        fees.record_volume(user, 1, 2_099);
+        fees.record_volume(user, 5, 1_020);
+        assert_eq!(fees.rolling_volume(user, 2_999), 116);
+        // 150 leaves the window first, then 1_020, then the 2_000 bucket.
+        assert_eq!(fees.rolling_volume(user, 3_100), 106);
+        assert_eq!(fees.rolling_volume(user, 4_000), 101);
+        assert_eq!(fees.rolling_volume(user, 5_000), 0);
+
+        // Anything older than the window of the newest bucket is dropped.
+        fees.record_volume(user, 1, 3_050);
+        fees.record_volume(user, 1_000, 0);
+        assert_eq!(fees.rolling_volume(user, 3_050), 117);
+    }
+
+    #[test]
+    fn test_tiered_schedule_symbol_override_and_trade_tracking() {
+        use crate::orderbook::trade::{FillParty, LiquidityFlag, TradeFill};
+        use pricelevel::{Id, MatchResult, Quantity, Side};
+
+        let fees = TieredFeeSchedule::new(FeeSchedule::new(2, 10))
+            .with_tier(1_000, FeeSchedule::new(0, 7))
+            .with_symbol_override("PROMO", FeeSchedule::zero_fee());
+        let maker = Hash32::new([3; 32]);
+        let taker_order = Id::new_uuid();
+        let party = |user_id, side, liquidity| FillParty {
+            order_id: Id::new_uuid(),
+            user_id,
+            side,
+            liquidity,
+            fee: 0,
+        };
+
+        let mut trade = TradeResult::new(
+            "BTC".to_string(),
+            MatchResult::new(taker_order, Quantity::new(10)),
+        );
+        trade.fills.push(TradeFill {
+            trade_id: Id::new_uuid(),
+            execution_id: 1,
+            matched_at_ns: 0,
+            price: 100,
+            quantity: 10,
+            maker: party(maker, Side::Sell, LiquidityFlag::Maker),
+            // Submitted without a user id, so the taker side is not credited.
+            taker: party(Hash32::zero(), Side::Buy, LiquidityFlag::Taker),
+            maker_remaining_quantity: 0,
+        });
+
+        fees.record_trade(&trade, 0);
+        assert_eq!(fees.rolling_volume(maker, 0), 1_000);
+        assert_eq!(fees.rolling_volume(Hash32::zero(), 0), 0);
+        assert_eq!(fees.schedule_for(maker, "BTC", 0), FeeSchedule::new(0, 7));
+        assert_eq!(
+            fees.schedule_for(maker, "PROMO", 0),
+            FeeSchedule::zero_fee()
+        );
+
+        fees.reset_volumes();
+        assert_eq!(fees.rolling_volume(maker, 0), 0);
+    }
 }
@@ -0,0 +1,295 @@
+//! Float-free analytics for deterministic replay verification.
+//!
+//! The analytics on [`OrderBook`] (`mid_price`, `micro_price`, `vwap`,
+//! `spread_bps`, `order_book_imbalance`) return `f64`. Float results can
+//! differ in the last bits across platforms and compiler settings, so two
+//! replays of the same journal cannot be compared bit for bit. This
+//! module adds a parallel `*_fixed` API that computes the same quantities
+//! with integer arithmetic only and returns them as exact, fully reduced
+//! fractions: equal book states always produce identical values on every
+//! machine.
+//!
+//! [`PriceRatio`] is a non-negative fraction (prices, spreads); [`SignedRatio`]
+//! carries a sign (imbalance). Both convert to a scaled integer with
+//! `to_scaled(decimals)`, which truncates toward zero, and to `f64` for
+//! display only.
+
+use super::book::OrderBook;
+use either::Either;
+use pricelevel::Side;
+use serde::{Deserialize, Serialize};
+
+/// Basis points per unit, the default multiplier of
+/// [`OrderBook::spread_bps_fixed`].
+pub const FIXED_BASIS_POINTS_MULTIPLIER: u128 = 10_000;
+
+/// Greatest common divisor, for reducing fractions.
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Exact non-negative fraction `numerator / denominator`, always stored in
+/// lowest terms with a non-zero denominator, so equal values compare
+/// equal field by field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PriceRatio {
+    numerator: u128,
+    denominator: u128,
+}
+
+impl PriceRatio {
+    /// Build a reduced fraction, or `None` if `denominator` is zero.
+    #[must_use]
+    pub fn new(numerator: u128, denominator: u128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator);
+        Some(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// The whole number `value`.
+    #[must_use]
+    pub fn from_integer(value: u128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    /// Numerator in lowest terms.
+    #[must_use]
+    pub fn numerator(&self) -> u128 {
+        self.numerator
+    }
+
+    /// Denominator in lowest terms, never zero.
+    #[must_use]
+    pub fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    /// The value times `10^decimals`, truncated toward zero, or `None` on
+    /// overflow.
+    #[must_use]
+    pub fn to_scaled(&self, decimals: u32) -> Option<u128> {
+        let scale = 10u128.checked_pow(decimals)?;
+        let whole = self.numerator / self.denominator;
+        let rest = self.numerator % self.denominator;
+        // `rest < denominator`, so `rest * scale / denominator < scale`.
+        let fraction = rest.checked_mul(scale)? / self.denominator;
+        whole.checked_mul(scale)?.checked_add(fraction)
+    }
+
+    /// Nearest `f64`, for display. Not bit-stable across platforms.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// Exact signed fraction `numerator / denominator` in lowest terms with a
+/// positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignedRatio {
+    numerator: i128,
+    denominator: u128,
+}
+
+impl SignedRatio {
+    /// Exactly zero.
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    /// `(positive - negative) / denominator`, reduced, or `None` if
+    /// `denominator` is zero or the difference does not fit in `i128`.
+    #[must_use]
+    pub fn from_difference(positive: u128, negative: u128, denominator: u128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let (magnitude, negative_sign) = if positive >= negative {
+            (positive - negative, false)
+        } else {
+            (negative - positive, true)
+        };
+        let divisor = gcd(magnitude, denominator);
+        let magnitude = i128::try_from(magnitude / divisor).ok()?;
+        Some(Self {
+            numerator: if negative_sign { -magnitude } else { magnitude },
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Numerator in lowest terms, carrying the sign.
+    #[must_use]
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// Denominator in lowest terms, never zero.
+    #[must_use]
+    pub fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    /// The value times `10^decimals`, truncated toward zero, or `None` on
+    /// overflow.
+    #[must_use]
+    pub fn to_scaled(&self, decimals: u32) -> Option<i128> {
+        let magnitude = PriceRatio {
+            numerator: self.numerator.unsigned_abs(),
+            denominator: self.denominator,
+        }
+        .to_scaled(decimals)?;
+        let magnitude = i128::try_from(magnitude).ok()?;
+        Some(if self.numerator < 0 {
+            -magnitude
+        } else {
+            magnitude
+        })
+    }
+
+    /// Nearest `f64`, for display. Not bit-stable across platforms.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// [`Self::mid_price`] as an exact fraction. `None` if either side is
+    /// empty or `bid + ask` overflows `u128`.
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 101, 10, Side::Sell, TimeInForce::Gtc, None);
+    ///
+    /// let mid = book.mid_price_fixed().unwrap();
+    /// assert_eq!((mid.numerator(), mid.denominator()), (201, 2));
+    /// assert_eq!(mid.to_scaled(2), Some(10_050));
+    /// ```
+    #[must_use]
+    pub fn mid_price_fixed(&self) -> Option<PriceRatio> {
+        let top = self.bbo();
+        let (bid, ask) = (top.best_bid?, top.best_ask?);
+        PriceRatio::new(bid.checked_add(ask)?, 2)
+    }
+
+    /// [`Self::spread_bps`] as an exact fraction. `bps_multiplier`
+    /// defaults to [`FIXED_BASIS_POINTS_MULTIPLIER`]. `None` if either side
+    /// is empty, the mid price is zero, or the product overflows.
+    #[must_use]
+    pub fn spread_bps_fixed(&self, bps_multiplier: Option<u128>) -> Option<PriceRatio> {
+        let multiplier = bps_multiplier.unwrap_or(FIXED_BASIS_POINTS_MULTIPLIER);
+        let top = self.bbo();
+        let (bid, ask) = (top.best_bid?, top.best_ask?);
+        let spread = ask.saturating_sub(bid);
+        // spread / ((bid + ask) / 2) * multiplier
+        let sum = bid.checked_add(ask)?;
+        PriceRatio::new(spread.checked_mul(2)?.checked_mul(multiplier)?, sum)
+    }
+
+    /// [`Self::micro_price`] as an exact fraction. `None` if either side
+    /// is empty, both best levels are empty, or the weighted sum
+    /// overflows `u128`.
+    #[must_use]
+    pub fn micro_price_fixed(&self) -> Option<PriceRatio> {
+        let top = self.bbo();
+        let (bid, ask) = (top.best_bid?, top.best_ask?);
+        let bid_quantity = u128::from(top.bid_quantity);
+        let ask_quantity = u128::from(top.ask_quantity);
+        let numerator = ask
+            .checked_mul(bid_quantity)?
+            .checked_add(bid.checked_mul(ask_quantity)?)?;
+        PriceRatio::new(numerator, bid_quantity + ask_quantity)
+    }
+
+    /// [`Self::vwap`] as an exact fraction. `None` if `quantity` is zero,
+    /// the side cannot fill it, or the notional overflows `u128`.
+    #[must_use]
+    pub fn vwap_fixed(&self, quantity: u64, side: Side) -> Option<PriceRatio> {
+        if quantity == 0 {
+            return None;
+        }
+        let levels = match side {
+            Side::Buy => Either::Left(self.asks.iter()),
+            Side::Sell => Either::Right(self.bids.iter().rev()),
+        };
+
+        let mut remaining = quantity;
+        let mut notional = 0u128;
+        for entry in levels {
+            if remaining == 0 {
+                break;
+            }
+            let fill = remaining.min(entry.value().total_quantity().unwrap_or(0));
+            notional = notional.checked_add(entry.key().checked_mul(u128::from(fill))?)?;
+            remaining -= fill;
+        }
+        if remaining > 0 {
+            return None;
+        }
+        PriceRatio::new(notional, u128::from(quantity))
+    }
+
+    /// [`Self::order_book_imbalance`] as an exact fraction in `[-1, 1]`.
+    /// [`SignedRatio::ZERO`] if `levels` is zero or both sides are empty.
+    #[must_use]
+    pub fn order_book_imbalance_fixed(&self, levels: usize) -> SignedRatio {
+        if levels == 0 {
+            return SignedRatio::ZERO;
+        }
+        let bid = u128::from(self.total_depth_at_levels(levels, Side::Buy));
+        let ask = u128::from(self.total_depth_at_levels(levels, Side::Sell));
+        SignedRatio::from_difference(bid, ask, bid + ask).unwrap_or(SignedRatio::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratios_are_reduced_and_scale_by_truncation() {
+        let ratio = PriceRatio::new(10, 4).expect("non-zero denominator");
+        assert_eq!((ratio.numerator(), ratio.denominator()), (5, 2));
+        assert_eq!(ratio, PriceRatio::new(5, 2).expect("ratio"));
+        assert_eq!(ratio.to_scaled(0), Some(2));
+        assert_eq!(ratio.to_scaled(3), Some(2_500));
+        assert_eq!(
+            PriceRatio::new(2, 3).expect("ratio").to_scaled(4),
+            Some(6_666)
+        );
+        assert_eq!(PriceRatio::new(1, 0), None);
+        assert_eq!(PriceRatio::from_integer(u128::MAX).to_scaled(1), None);
+    }
+
+    #[test]
+    fn test_signed_ratio_keeps_the_sign() {
+        let ratio = SignedRatio::from_difference(25, 75, 100).expect("ratio");
+        assert_eq!((ratio.numerator(), ratio.denominator()), (-1, 2));
+        assert_eq!(ratio.to_scaled(2), Some(-50));
+        assert_eq!(
+            SignedRatio::from_difference(3, 3, 6),
+            Some(SignedRatio::ZERO)
+        );
+        assert_eq!(SignedRatio::from_difference(1, 0, 0), None);
+    }
+}
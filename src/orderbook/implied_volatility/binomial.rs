@@ -0,0 +1,304 @@
+//! Cox-Ross-Rubinstein binomial pricer for American options.
+//!
+//! Black-Scholes assumes European exercise, which underprices options
+//! whose early-exercise right has value — most visibly deep in-the-money
+//! puts on non-dividend-paying equities. This module prices both exercise
+//! styles on a recombining CRR tree so the IV solver can invert American
+//! quotes correctly.
+
+use super::black_scholes::BlackScholes;
+use super::types::{ExerciseStyle, Greeks, IVParams, OptionType};
+
+/// Default number of time steps in the binomial tree.
+pub const DEFAULT_BINOMIAL_STEPS: usize = 200;
+
+/// Relative volatility bump used for the finite-difference vega.
+const VEGA_BUMP: f64 = 1e-4;
+
+/// Absolute rate bump used for the finite-difference rho.
+const RATE_BUMP: f64 = 1e-4;
+
+/// Option values at the first two time steps of a rolled-back tree.
+struct TreeNodes {
+    /// Length of one time step in years.
+    dt: f64,
+    /// Up factor `u`; the down factor is `1 / u`.
+    up: f64,
+    /// Value at the root.
+    root: f64,
+    /// Values after one step: up, down.
+    step1: [f64; 2],
+    /// Values after two steps: up-up, up-down, down-down.
+    step2: [f64; 3],
+}
+
+/// Cox-Ross-Rubinstein binomial tree pricer.
+///
+/// Supports both [`ExerciseStyle::European`] and
+/// [`ExerciseStyle::American`] exercise, selected from
+/// [`IVParams::exercise_style`]. Accuracy improves with the number of
+/// steps; [`DEFAULT_BINOMIAL_STEPS`] keeps the pricing error well below a
+/// typical tick for equity options.
+pub struct Binomial;
+
+impl Binomial {
+    /// Calculates the option price on a CRR tree with `steps` time steps.
+    ///
+    /// u = e^(σ√Δt), d = 1/u, p = (e^(rΔt) - d) / (u - d)
+    ///
+    /// For American exercise every node takes the larger of the
+    /// continuation value and the immediate exercise value.
+    ///
+    /// When the tree is degenerate (`vol <= 0`, `steps == 0`, or a
+    /// volatility so small that `p` leaves `[0, 1]`), the zero-volatility
+    /// limit is returned instead.
+    ///
+    /// # Arguments
+    /// - `params`: Option parameters (spot, strike, time, rate, type, exercise style)
+    /// - `vol`: Volatility (σ)
+    /// - `steps`: Number of time steps in the tree
+    ///
+    /// # Returns
+    /// Theoretical option price
+    #[must_use]
+    pub fn price(params: &IVParams, vol: f64, steps: usize) -> f64 {
+        if params.time_to_expiry <= 0.0 {
+            return params.intrinsic_value();
+        }
+        Self::roll_back(params, vol, steps)
+            .map_or_else(|| Self::zero_vol_price(params), |tree| tree.root)
+    }
+
+    /// Rolls the CRR tree back to its root, keeping the values of the
+    /// first two steps. `None` when the tree is degenerate (see
+    /// [`Self::price`]).
+    fn roll_back(params: &IVParams, vol: f64, steps: usize) -> Option<TreeNodes> {
+        let dt = params.time_to_expiry / steps.max(1) as f64;
+        let up = (vol * dt.sqrt()).exp();
+        let down = 1.0 / up;
+        let growth = (params.risk_free_rate * dt).exp();
+        let prob = (growth - down) / (up - down);
+
+        if vol <= 0.0 || steps == 0 || !(0.0..=1.0).contains(&prob) || !prob.is_finite() {
+            return None;
+        }
+
+        let discount = 1.0 / growth;
+        let american = params.exercise_style == ExerciseStyle::American;
+        let payoff = |spot: f64| match params.option_type {
+            OptionType::Call => (spot - params.strike).max(0.0),
+            OptionType::Put => (params.strike - spot).max(0.0),
+        };
+
+        // Terminal payoffs: node i has i down-moves.
+        let mut values: Vec<f64> = (0..=steps)
+            .map(|i| {
+                let spot = params.spot * up.powi((steps - i) as i32) * down.powi(i as i32);
+                payoff(spot)
+            })
+            .collect();
+
+        let mut step1 = [0.0; 2];
+        let mut step2 = [0.0; 3];
+        for step in (0..steps).rev() {
+            match step + 1 {
+                2 => step2.copy_from_slice(&values[..3]),
+                1 => step1.copy_from_slice(&values[..2]),
+                _ => {}
+            }
+            for i in 0..=step {
+                let continuation = discount * (prob * values[i] + (1.0 - prob) * values[i + 1]);
+                values[i] = if american {
+                    let spot = params.spot * up.powi((step - i) as i32) * down.powi(i as i32);
+                    continuation.max(payoff(spot))
+                } else {
+                    continuation
+                };
+            }
+        }
+
+        Some(TreeNodes {
+            dt,
+            up,
+            root: values[0],
+            step1,
+            step2,
+        })
+    }
+
+    /// Calculates vega (∂price/∂σ) by central finite difference on the tree.
+    ///
+    /// # Arguments
+    /// - `params`: Option parameters
+    /// - `vol`: Current volatility estimate
+    /// - `steps`: Number of time steps in the tree
+    ///
+    /// # Returns
+    /// Vega value (change in price per unit change in volatility)
+    #[must_use]
+    pub fn vega(params: &IVParams, vol: f64, steps: usize) -> f64 {
+        if params.time_to_expiry <= 0.0 || vol <= 0.0 {
+            return 0.0;
+        }
+        let bump = (vol * VEGA_BUMP).max(1e-6);
+        let low = (vol - bump).max(f64::MIN_POSITIVE);
+        let high = vol + bump;
+        (Self::price(params, high, steps) - Self::price(params, low, steps)) / (high - low)
+    }
+
+    /// Calculates all Greeks on the tree.
+    ///
+    /// Delta, gamma and theta are read from the nodes of the first two
+    /// steps (theta compares the root with the middle node two steps
+    /// later, at the same spot); vega is [`Self::vega`] and rho a central
+    /// finite difference in the rate. Units match
+    /// [`BlackScholes::greeks`] (theta is daily). At expiry, or when the
+    /// tree is degenerate, the closed-form limits of
+    /// [`BlackScholes::greeks`] are returned.
+    ///
+    /// # Arguments
+    /// - `params`: Option parameters (including exercise style)
+    /// - `vol`: Volatility (σ)
+    /// - `steps`: Number of time steps in the tree (at least two are used)
+    ///
+    /// # Returns
+    /// [`Greeks`] with delta, gamma, vega, theta (daily) and rho
+    #[must_use]
+    pub fn greeks(params: &IVParams, vol: f64, steps: usize) -> Greeks {
+        let steps = steps.max(2);
+        let tree = if params.time_to_expiry > 0.0 {
+            Self::roll_back(params, vol, steps)
+        } else {
+            None
+        };
+        let Some(tree) = tree else {
+            return BlackScholes::greeks(params, vol);
+        };
+
+        let spot = params.spot;
+        let (up, down) = (tree.up, 1.0 / tree.up);
+        let [value_u, value_d] = tree.step1;
+        let [value_uu, value_ud, value_dd] = tree.step2;
+        let delta_u = (value_uu - value_ud) / (spot * up * up - spot);
+        let delta_d = (value_ud - value_dd) / (spot - spot * down * down);
+
+        let mut rate = params.clone();
+        rate.risk_free_rate = params.risk_free_rate + RATE_BUMP;
+        let rate_up = Self::price(&rate, vol, steps);
+        rate.risk_free_rate = params.risk_free_rate - RATE_BUMP;
+        let rate_down = Self::price(&rate, vol, steps);
+
+        Greeks {
+            delta: (value_u - value_d) / (spot * up - spot * down),
+            gamma: (delta_u - delta_d) / (0.5 * spot * (up * up - down * down)),
+            vega: Self::vega(params, vol, steps),
+            theta: (value_ud - tree.root) / (2.0 * tree.dt) / 365.0,
+            rho: (rate_up - rate_down) / (2.0 * RATE_BUMP),
+        }
+    }
+
+    /// Zero-volatility limit: the underlying grows deterministically at
+    /// the risk-free rate, so the option is worth its best discounted
+    /// exercise value.
+    fn zero_vol_price(params: &IVParams) -> f64 {
+        let discount = (-params.risk_free_rate * params.time_to_expiry).exp();
+        let at_expiry = match params.option_type {
+            OptionType::Call => (params.spot - params.strike * discount).max(0.0),
+            OptionType::Put => (params.strike * discount - params.spot).max(0.0),
+        };
+        match params.exercise_style {
+            ExerciseStyle::European => at_expiry,
+            ExerciseStyle::American => at_expiry.max(params.intrinsic_value()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::implied_volatility::BlackScholes;
+
+    #[test]
+    fn test_european_tree_converges_to_black_scholes() {
+        for params in [
+            IVParams::call(100.0, 100.0, 0.5, 0.05),
+            IVParams::put(100.0, 110.0, 0.5, 0.05),
+        ] {
+            let tree = Binomial::price(&params, 0.25, 500);
+            let closed_form = BlackScholes::price(&params, 0.25);
+            assert!((tree - closed_form).abs() < 0.02, "{tree} vs {closed_form}");
+        }
+    }
+
+    #[test]
+    fn test_american_put_has_early_exercise_premium() {
+        let european = IVParams::put(80.0, 100.0, 1.0, 0.05);
+        let american = european
+            .clone()
+            .with_exercise_style(ExerciseStyle::American);
+
+        let euro_price = Binomial::price(&european, 0.2, DEFAULT_BINOMIAL_STEPS);
+        let amer_price = Binomial::price(&american, 0.2, DEFAULT_BINOMIAL_STEPS);
+
+        // A deep ITM put is worth at least intrinsic when exercisable now,
+        // while the European price is discounted below it.
+        assert!(euro_price < american.intrinsic_value());
+        assert!(amer_price >= american.intrinsic_value() - 1e-12);
+        assert!(amer_price > euro_price);
+    }
+
+    #[test]
+    fn test_american_call_without_dividends_equals_european() {
+        let european = IVParams::call(100.0, 95.0, 0.5, 0.03);
+        let american = european
+            .clone()
+            .with_exercise_style(ExerciseStyle::American);
+        let euro_price = Binomial::price(&european, 0.3, DEFAULT_BINOMIAL_STEPS);
+        let amer_price = Binomial::price(&american, 0.3, DEFAULT_BINOMIAL_STEPS);
+        assert!((euro_price - amer_price).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_degenerate_tree_uses_zero_vol_limit() {
+        let put =
+            IVParams::put(90.0, 100.0, 1.0, 0.05).with_exercise_style(ExerciseStyle::American);
+        assert!((Binomial::price(&put, 0.0, 100) - 10.0).abs() < 1e-12);
+        assert!((Binomial::price(&put, 1e-6, 100) - 10.0).abs() < 1e-12);
+        assert_eq!(Binomial::vega(&put, 0.0, 100), 0.0);
+    }
+
+    #[test]
+    fn test_european_tree_greeks_match_black_scholes() {
+        let params = IVParams::put(100.0, 105.0, 0.5, 0.05);
+        let tree = Binomial::greeks(&params, 0.25, 500);
+        let closed_form = BlackScholes::greeks(&params, 0.25);
+        assert!((tree.delta - closed_form.delta).abs() < 1e-3);
+        assert!((tree.gamma - closed_form.gamma).abs() < 1e-4);
+        assert!((tree.vega / closed_form.vega - 1.0).abs() < 0.02);
+        assert!((tree.theta - closed_form.theta).abs() < 1e-4);
+        assert!((tree.rho - closed_form.rho).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_american_put_greeks_reflect_early_exercise() {
+        let european = IVParams::put(80.0, 100.0, 1.0, 0.05);
+        let american = european
+            .clone()
+            .with_exercise_style(ExerciseStyle::American);
+        let euro = BlackScholes::greeks(&european, 0.2);
+        let amer = Binomial::greeks(&american, 0.2, DEFAULT_BINOMIAL_STEPS);
+
+        // Deep in the money the American put is exercised now: it moves
+        // one-for-one with the spot and carries almost no rate exposure.
+        assert!((amer.delta + 1.0).abs() < 0.05, "{}", amer.delta);
+        assert!(amer.delta < euro.delta);
+        assert!(amer.rho.abs() < euro.rho.abs());
+    }
+
+    #[test]
+    fn test_vega_positive() {
+        let params =
+            IVParams::put(100.0, 100.0, 0.5, 0.05).with_exercise_style(ExerciseStyle::American);
+        assert!(Binomial::vega(&params, 0.25, DEFAULT_BINOMIAL_STEPS) > 0.0);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides a lightweight implementation of the Black-Scholes
 //! option pricing model for use in implied volatility calculations.
 
-use super::types::{IVParams, OptionType};
+use super::types::{Greeks, IVParams, OptionType};
 use std::f64::consts::PI;
 
 /// Square root of 2, precomputed for efficiency.
@@ -298,6 +298,75 @@ impl BlackScholes {
         // Convert to daily theta
         theta_annual / 365.0
     }
+
+    /// Calculates rho (∂price/∂r) - sensitivity to the risk-free rate.
+    ///
+    /// For calls: ρ = K · T · e^(-rT) · N(d2)
+    /// For puts:  ρ = -K · T · e^(-rT) · N(-d2)
+    ///
+    /// # Arguments
+    /// - `params`: Option parameters
+    /// - `vol`: Volatility
+    ///
+    /// # Returns
+    /// Rho value (change in price per unit change in the rate)
+    #[must_use]
+    pub fn rho(params: &IVParams, vol: f64) -> f64 {
+        if params.time_to_expiry <= 0.0 || vol <= 0.0 {
+            return 0.0;
+        }
+
+        let d1 = Self::d1(
+            params.spot,
+            params.strike,
+            params.risk_free_rate,
+            params.time_to_expiry,
+            vol,
+        );
+        let d2 = Self::d2(d1, vol, params.time_to_expiry);
+        let discounted_strike = params.strike
+            * params.time_to_expiry
+            * (-params.risk_free_rate * params.time_to_expiry).exp();
+
+        match params.option_type {
+            OptionType::Call => discounted_strike * Self::norm_cdf(d2),
+            OptionType::Put => -discounted_strike * Self::norm_cdf(-d2),
+        }
+    }
+
+    /// Calculates all Greeks for an option in one call.
+    ///
+    /// # Arguments
+    /// - `params`: Option parameters
+    /// - `vol`: Volatility
+    ///
+    /// # Returns
+    /// [`Greeks`] with delta, gamma, vega, theta (daily) and rho
+    #[must_use]
+    pub fn greeks(params: &IVParams, vol: f64) -> Greeks {
+        Greeks {
+            delta: Self::delta(params, vol),
+            gamma: Self::gamma(params, vol),
+            vega: Self::vega(params, vol),
+            theta: Self::theta(params, vol),
+            rho: Self::rho(params, vol),
+        }
+    }
+
+    /// Calculates Greeks for every `(params, vol)` pair of a chain.
+    ///
+    /// # Arguments
+    /// - `chain`: Option parameters paired with their volatility
+    ///
+    /// # Returns
+    /// One [`Greeks`] per input, in input order
+    #[must_use]
+    pub fn greeks_batch(chain: &[(IVParams, f64)]) -> Vec<Greeks> {
+        chain
+            .iter()
+            .map(|(params, vol)| Self::greeks(params, *vol))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -415,6 +484,40 @@ mod tests {
         assert!(theta < 0.0);
     }
 
+    #[test]
+    fn test_rho_matches_finite_difference() {
+        let bump = 1e-5;
+        for params in [
+            IVParams::call(100.0, 105.0, 0.5, 0.05),
+            IVParams::put(100.0, 105.0, 0.5, 0.05),
+        ] {
+            let mut up = params.clone();
+            up.risk_free_rate += bump;
+            let mut down = params.clone();
+            down.risk_free_rate -= bump;
+            let numeric =
+                (BlackScholes::price(&up, 0.3) - BlackScholes::price(&down, 0.3)) / (2.0 * bump);
+            assert!((BlackScholes::rho(&params, 0.3) - numeric).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_greeks_batch_matches_individual() {
+        let chain = vec![
+            (IVParams::call(100.0, 95.0, 0.25, 0.01), 0.2),
+            (IVParams::put(100.0, 105.0, 0.5, 0.01), 0.3),
+        ];
+        let batch = BlackScholes::greeks_batch(&chain);
+        assert_eq!(batch.len(), 2);
+        for ((params, vol), greeks) in chain.iter().zip(&batch) {
+            assert_eq!(*greeks, BlackScholes::greeks(params, *vol));
+            assert_eq!(greeks.delta, BlackScholes::delta(params, *vol));
+            assert_eq!(greeks.rho, BlackScholes::rho(params, *vol));
+        }
+        assert!(batch[0].rho > 0.0);
+        assert!(batch[1].rho < 0.0);
+    }
+
     #[test]
     fn test_price_at_expiry() {
         // At expiry, option is worth intrinsic value
@@ -64,6 +64,21 @@ pub enum IVError {
         /// Maximum bound.
         max_bound: f64,
     },
+
+    /// No order book is registered for an option contract's symbol.
+    BookNotFound {
+        /// Symbol that was looked up.
+        symbol: String,
+    },
+
+    /// The best quotes have not been refreshed within the configured
+    /// staleness threshold.
+    StaleQuotes {
+        /// Age of the stalest best quote in milliseconds.
+        age_ms: u64,
+        /// Maximum allowed quote age in milliseconds.
+        max_age_ms: u64,
+    },
 }
 
 impl fmt::Display for IVError {
@@ -124,6 +139,15 @@ impl fmt::Display for IVError {
                     "volatility {volatility:.4} is outside bounds [{min_bound:.4}, {max_bound:.4}]"
                 )
             }
+            IVError::BookNotFound { symbol } => {
+                write!(f, "no order book found for symbol {symbol}")
+            }
+            IVError::StaleQuotes { age_ms, max_age_ms } => {
+                write!(
+                    f,
+                    "best quotes are stale: {age_ms} ms old exceeds maximum of {max_age_ms} ms"
+                )
+            }
         }
     }
 }
@@ -180,5 +204,16 @@ mod tests {
             max_bound: 5.0,
         };
         assert!(err.to_string().contains("outside bounds"));
+
+        let err = IVError::BookNotFound {
+            symbol: "BTC-C-50000".to_string(),
+        };
+        assert!(err.to_string().contains("BTC-C-50000"));
+
+        let err = IVError::StaleQuotes {
+            age_ms: 5_000,
+            max_age_ms: 1_000,
+        };
+        assert!(err.to_string().contains("5000 ms old"));
     }
 }
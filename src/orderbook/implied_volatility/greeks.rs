@@ -0,0 +1,182 @@
+//! Chain-wide Greeks snapshots for risk dashboards.
+//!
+//! A [`GreeksSnapshot`] solves the IV of every contract in a chain from
+//! its order book and records the resulting Greeks. It is `Serialize` /
+//! `Deserialize` and can be published through any
+//! [`EventSerializer`](crate::orderbook::serialization::EventSerializer)
+//! (and, with the `nats` feature, through
+//! `NatsTradePublisher::publish_greeks`).
+
+use super::integration::IVConfig;
+use super::solver::model_greeks;
+use super::surface::{SurfaceFailure, SurfaceInput, solve_chain};
+use super::types::{ExerciseStyle, Greeks, IVParams, IVQuality, OptionType, PriceSource};
+use crate::orderbook::manager::BookManager;
+use serde::{Deserialize, Serialize};
+
+/// Greeks for one contract of a chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GreeksEntry {
+    /// Symbol of the contract's order book.
+    pub symbol: String,
+    /// Option strike price in price units.
+    pub strike: f64,
+    /// Time to expiration in years.
+    pub time_to_expiry: f64,
+    /// Option type (Call or Put).
+    pub option_type: OptionType,
+    /// Exercise style the IV and Greeks were computed under.
+    #[serde(default)]
+    pub exercise_style: ExerciseStyle,
+    /// Implied volatility the Greeks were computed at.
+    pub iv: f64,
+    /// Liquidity quality of the IV.
+    pub quality: IVQuality,
+    /// Greeks at `iv`.
+    pub greeks: Greeks,
+}
+
+/// Point-in-time Greeks for an options chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GreeksSnapshot {
+    /// Underlying identifier, used as the publish subject suffix.
+    pub underlying: String,
+    /// Underlying spot price used for every entry.
+    pub spot: f64,
+    /// Risk-free rate used for every entry.
+    pub risk_free_rate: f64,
+    /// Snapshot timestamp in milliseconds since epoch.
+    pub timestamp_ms: u64,
+    /// One entry per contract whose IV could be solved, in input order.
+    pub entries: Vec<GreeksEntry>,
+    /// Symbols of the contracts skipped because their IV could not be
+    /// solved.
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+impl GreeksSnapshot {
+    /// Builds a snapshot by solving every input's IV from its book in
+    /// `manager` and computing the Greeks at that IV.
+    ///
+    /// Each input's [`exercise_style`](SurfaceInput::exercise_style)
+    /// selects the model for both: Black-Scholes for European contracts,
+    /// a binomial tree of `config.solver.binomial_steps` steps (with
+    /// finite-difference Greeks) for American ones.
+    ///
+    /// Solving runs in parallel, as in
+    /// [`IVSurface::from_books`](super::IVSurface::from_books). Contracts
+    /// whose IV cannot be solved are listed in [`Self::skipped`]; use
+    /// [`Self::from_books_with_failures`] to get the errors.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_books<T, M>(
+        manager: &M,
+        underlying: &str,
+        inputs: &[SurfaceInput],
+        spot: f64,
+        risk_free_rate: f64,
+        price_source: PriceSource,
+        config: &IVConfig,
+        timestamp_ms: u64,
+    ) -> Self
+    where
+        T: Clone + Send + Sync + Default + 'static,
+        M: BookManager<T> + ?Sized,
+    {
+        Self::from_books_with_failures(
+            manager,
+            underlying,
+            inputs,
+            spot,
+            risk_free_rate,
+            price_source,
+            config,
+            timestamp_ms,
+        )
+        .0
+    }
+
+    /// Like [`Self::from_books`], also returning why each skipped
+    /// contract failed.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_books_with_failures<T, M>(
+        manager: &M,
+        underlying: &str,
+        inputs: &[SurfaceInput],
+        spot: f64,
+        risk_free_rate: f64,
+        price_source: PriceSource,
+        config: &IVConfig,
+        timestamp_ms: u64,
+    ) -> (Self, Vec<SurfaceFailure>)
+    where
+        T: Clone + Send + Sync + Default + 'static,
+        M: BookManager<T> + ?Sized,
+    {
+        let mut entries = Vec::with_capacity(inputs.len());
+        let mut failures = Vec::new();
+        for (input, result) in
+            solve_chain(manager, inputs, spot, risk_free_rate, price_source, config)
+        {
+            match result {
+                Ok(result) => {
+                    let params = IVParams::new(
+                        spot,
+                        input.strike,
+                        input.time_to_expiry,
+                        risk_free_rate,
+                        input.option_type,
+                    )
+                    .with_exercise_style(input.exercise_style);
+                    entries.push(GreeksEntry {
+                        symbol: input.symbol.clone(),
+                        strike: input.strike,
+                        time_to_expiry: input.time_to_expiry,
+                        option_type: input.option_type,
+                        exercise_style: input.exercise_style,
+                        iv: result.iv,
+                        quality: result.quality,
+                        greeks: model_greeks(&params, result.iv, &config.solver),
+                    });
+                }
+                Err(error) => failures.push(SurfaceFailure {
+                    symbol: input.symbol.clone(),
+                    error,
+                }),
+            }
+        }
+
+        let snapshot = Self {
+            underlying: underlying.to_string(),
+            spot,
+            risk_free_rate,
+            timestamp_ms,
+            entries,
+            skipped: failures.iter().map(|f| f.symbol.clone()).collect(),
+        };
+        (snapshot, failures)
+    }
+
+    /// Sum of the Greeks across every entry, i.e. the Greeks of a
+    /// portfolio long one unit of each contract.
+    #[must_use]
+    pub fn total(&self) -> Greeks {
+        self.entries
+            .iter()
+            .fold(Greeks::default(), |acc, entry| Greeks {
+                delta: acc.delta + entry.greeks.delta,
+                gamma: acc.gamma + entry.greeks.gamma,
+                vega: acc.vega + entry.greeks.vega,
+                theta: acc.theta + entry.greeks.theta,
+                rho: acc.rho + entry.greeks.rho,
+            })
+    }
+
+    /// Returns the entry for `symbol`, if its IV was solved.
+    #[must_use]
+    pub fn entry(&self, symbol: &str) -> Option<&GreeksEntry> {
+        self.entries.iter().find(|entry| entry.symbol == symbol)
+    }
+}
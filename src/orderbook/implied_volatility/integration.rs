@@ -8,7 +8,9 @@ use super::error::IVError;
 use super::solver::{SolverConfig, solve_iv};
 use super::types::{IVParams, IVQuality, IVResult, PriceSource};
 use crate::orderbook::book::OrderBook;
-use pricelevel::Side;
+use either::Either;
+use pricelevel::{PriceLevel, Side};
+use std::sync::Arc;
 
 /// Threshold for high quality IV calculation (spread < 100 bps = 1%).
 const HIGH_QUALITY_SPREAD_BPS: f64 = 100.0;
@@ -26,6 +28,10 @@ pub struct IVConfig {
     /// Price scale factor to convert u64 prices to f64.
     /// For example, if prices are in cents, use 100.0 to get dollars.
     pub price_scale: f64,
+    /// Maximum age in milliseconds of the best quotes, measured on the
+    /// book's clock from the newest order at each best level
+    /// (default: `None`, no staleness check).
+    pub max_quote_age_ms: Option<u64>,
 }
 
 impl Default for IVConfig {
@@ -34,6 +40,7 @@ impl Default for IVConfig {
             solver: SolverConfig::default(),
             max_spread_bps: 1000.0,
             price_scale: 1.0,
+            max_quote_age_ms: None,
         }
     }
 }
@@ -65,6 +72,13 @@ impl IVConfig {
         self.solver = solver;
         self
     }
+
+    /// Rejects books whose best quotes are older than `max_age_ms`.
+    #[must_use]
+    pub fn with_max_quote_age(mut self, max_age_ms: u64) -> Self {
+        self.max_quote_age_ms = Some(max_age_ms);
+        self
+    }
 }
 
 impl<T> OrderBook<T>
@@ -79,7 +93,7 @@ where
     ///
     /// # Arguments
     /// - `params`: Option parameters (spot, strike, time, rate, type)
-    /// - `price_source`: How to derive price from bid/ask (MidPrice, WeightedMid, LastTrade)
+    /// - `price_source`: How to derive price from bid/ask (see [`PriceSource`])
     ///
     /// # Returns
     /// - `Ok(IVResult)` with calculated IV and metadata
@@ -97,7 +111,7 @@ where
     /// # Example
     /// ```ignore
     /// use orderbook_rs::OrderBook;
-    /// use orderbook_rs::implied_volatility::{IVParams, OptionType, PriceSource};
+    /// use orderbook_rs::implied_volatility::{ExerciseStyle, IVParams, OptionType, PriceSource};
     ///
     /// let book = OrderBook::<()>::new("AAPL-C-150");
     /// // Add orders to the book...
@@ -108,6 +122,7 @@ where
     ///     time_to_expiry: 30.0 / 365.0,
     ///     risk_free_rate: 0.05,
     ///     option_type: OptionType::Call,
+    ///     exercise_style: ExerciseStyle::European,
     /// };
     ///
     /// match book.implied_volatility(&params, PriceSource::MidPrice) {
@@ -137,7 +152,10 @@ where
     /// # Errors
     ///
     /// - [`IVError::NoPriceAvailable`] if the book has neither a usable price nor
-    ///   a last trade to derive one from.
+    ///   a last trade to derive one from, or no level on one side meets a
+    ///   [`PriceSource::SizeFilteredMid`] minimum size.
+    /// - [`IVError::StaleQuotes`] if `config.max_quote_age_ms` is set and a
+    ///   best quote is older than it.
     /// - [`IVError::CrossedBook`] if the book is crossed or locked (no meaningful mid).
     /// - [`IVError::SpreadTooWide`] if the bid/ask spread exceeds
     ///   `config.max_spread_bps`.
@@ -153,6 +171,14 @@ where
         price_source: PriceSource,
         config: &IVConfig,
     ) -> Result<IVResult, IVError> {
+        // Reject books whose best quotes have gone stale
+        if let Some(max_age_ms) = config.max_quote_age_ms
+            && let Some(age_ms) = self.best_quote_age_ms()
+            && age_ms > max_age_ms
+        {
+            return Err(IVError::StaleQuotes { age_ms, max_age_ms });
+        }
+
         // Extract price from order book
         let (price, spread_bps) = self.extract_price_for_iv(price_source, config.price_scale)?;
 
@@ -193,10 +219,9 @@ where
         source: PriceSource,
         price_scale: f64,
     ) -> Result<(f64, f64), IVError> {
-        let best_bid = self.best_bid();
-        let best_ask = self.best_ask();
+        let bbo = self.bbo();
 
-        match (best_bid, best_ask) {
+        match (bbo.best_bid, bbo.best_ask) {
             (Some(bid), Some(ask)) => {
                 let bid_f = bid as f64 / price_scale;
                 let ask_f = ask as f64 / price_scale;
@@ -205,9 +230,9 @@ where
                 // before quality classification: such a book would otherwise
                 // yield a zero/negative spread that slips under the max-spread
                 // guard and is mislabelled high quality, feeding a bogus mid
-                // into the solver. `best_bid`/`best_ask` are two independent
-                // reads, so a transient torn read can surface this even though a
-                // stable book never crosses.
+                // into the solver. The pair comes from one consistent `bbo()`
+                // read, so this only fires on a book that is genuinely crossed
+                // or locked.
                 reject_crossed_or_locked(bid_f, ask_f)?;
 
                 let mid = (bid_f + ask_f) / 2.0;
@@ -228,6 +253,12 @@ where
                         .last_trade_price()
                         .map(|p| p as f64 / price_scale)
                         .unwrap_or(mid),
+                    PriceSource::DepthWeightedMid { levels } => self
+                        .depth_weighted_mid_for_iv(levels.max(1), price_scale)
+                        .unwrap_or(mid),
+                    PriceSource::SizeFilteredMid { min_size } => {
+                        return self.size_filtered_mid_for_iv(min_size, price_scale);
+                    }
                 };
 
                 Ok((price, spread_bps))
@@ -271,6 +302,97 @@ where
         }
     }
 
+    /// Depth-weighted mid over the top `levels` levels: each side's VWAP
+    /// weighted by the opposite side's quantity. `None` if either side
+    /// holds no quantity.
+    fn depth_weighted_mid_for_iv(&self, levels: usize, price_scale: f64) -> Option<f64> {
+        let (bid_vwap, bid_qty) = self.vwap_and_quantity_for_iv(Side::Buy, levels)?;
+        let (ask_vwap, ask_qty) = self.vwap_and_quantity_for_iv(Side::Sell, levels)?;
+
+        let bid_qty = bid_qty as f64;
+        let ask_qty = ask_qty as f64;
+        let weighted = (ask_vwap * bid_qty + bid_vwap * ask_qty) / (bid_qty + ask_qty);
+        Some(weighted / price_scale)
+    }
+
+    /// VWAP (unscaled) and total quantity of the top `levels` levels of
+    /// `side`, or `None` if they hold no quantity.
+    fn vwap_and_quantity_for_iv(&self, side: Side, levels: usize) -> Option<(f64, u64)> {
+        let price_levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let iter = match side {
+            Side::Buy => Either::Left(price_levels.iter().rev()),
+            Side::Sell => Either::Right(price_levels.iter()),
+        };
+
+        let mut total_value = 0u128;
+        let mut total_quantity = 0u64;
+        for entry in iter.take(levels) {
+            let quantity = entry.value().total_quantity().unwrap_or(0);
+            total_value =
+                total_value.saturating_add(entry.key().saturating_mul(u128::from(quantity)));
+            total_quantity = total_quantity.saturating_add(quantity);
+        }
+
+        if total_quantity == 0 {
+            None
+        } else {
+            Some((total_value as f64 / total_quantity as f64, total_quantity))
+        }
+    }
+
+    /// Mid and spread of the best bid and ask levels holding at least
+    /// `min_size` units.
+    fn size_filtered_mid_for_iv(
+        &self,
+        min_size: u64,
+        price_scale: f64,
+    ) -> Result<(f64, f64), IVError> {
+        let qualifies =
+            |level: &Arc<PriceLevel>| level.total_quantity().unwrap_or(0) >= min_size.max(1);
+
+        let bid = self
+            .bids
+            .iter()
+            .rev()
+            .find(|entry| qualifies(entry.value()))
+            .map(|entry| *entry.key());
+        let ask = self
+            .asks
+            .iter()
+            .find(|entry| qualifies(entry.value()))
+            .map(|entry| *entry.key());
+
+        let (Some(bid), Some(ask)) = (bid, ask) else {
+            return Err(IVError::NoPriceAvailable);
+        };
+        let bid_f = bid as f64 / price_scale;
+        let ask_f = ask as f64 / price_scale;
+        reject_crossed_or_locked(bid_f, ask_f)?;
+
+        let mid = (bid_f + ask_f) / 2.0;
+        Ok((mid, ((ask_f - bid_f) / mid) * 10_000.0))
+    }
+
+    /// Age in milliseconds of the stalest best quote, where a level's age
+    /// is measured from its newest order. `None` if the book is empty.
+    fn best_quote_age_ms(&self) -> Option<u64> {
+        let now = self.clock().now_millis().as_u64();
+        let level_age = |level: &Arc<PriceLevel>| {
+            level
+                .iter_orders()
+                .map(|order| order.timestamp().as_u64())
+                .max()
+                .map(|newest| now.saturating_sub(newest))
+        };
+
+        let bid_age = self.bids.back().and_then(|entry| level_age(entry.value()));
+        let ask_age = self.asks.front().and_then(|entry| level_age(entry.value()));
+        bid_age.max(ask_age)
+    }
+
     /// Gets the total quantity at a specific price level.
     fn quantity_at_price(&self, price: u128, side: Side) -> u64 {
         let price_levels = match side {
@@ -614,4 +736,74 @@ mod tests {
         assert!((price - 4.70).abs() < 0.01);
         assert!((spread_bps - 10_000.0).abs() < 1.0); // 100% spread indicator
     }
+
+    #[test]
+    fn test_extract_price_depth_weighted_mid() {
+        let book = OrderBook::<()>::new("TEST-OPT");
+        let _ = book.add_limit_order(Id::new(), 450, 10, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 440, 30, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 470, 20, Side::Sell, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 480, 20, Side::Sell, TimeInForce::Gtc, None);
+
+        let (price, _) = book
+            .extract_price_for_iv(PriceSource::DepthWeightedMid { levels: 2 }, 100.0)
+            .unwrap();
+
+        // bid VWAP = 4.425 over 40, ask VWAP = 4.75 over 40
+        assert!((price - (4.425 + 4.75) / 2.0).abs() < 1e-9);
+
+        // levels == 0 is the micro price
+        let (micro, _) = book
+            .extract_price_for_iv(PriceSource::DepthWeightedMid { levels: 0 }, 100.0)
+            .unwrap();
+        let expected = book.micro_price().unwrap() / 100.0;
+        assert!((micro - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_extract_price_size_filtered_mid() {
+        let book = create_test_book();
+        // Tiny quotes inside the real market
+        let _ = book.add_limit_order(Id::new(), 455, 1, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 465, 1, Side::Sell, TimeInForce::Gtc, None);
+
+        let (price, spread_bps) = book
+            .extract_price_for_iv(PriceSource::SizeFilteredMid { min_size: 10 }, 100.0)
+            .unwrap();
+        assert!((price - 4.60).abs() < 1e-9);
+        assert!((spread_bps - (0.20 / 4.60) * 10_000.0).abs() < 1e-6);
+
+        let result =
+            book.extract_price_for_iv(PriceSource::SizeFilteredMid { min_size: 1_000 }, 100.0);
+        assert!(matches!(result, Err(IVError::NoPriceAvailable)));
+    }
+
+    #[test]
+    fn test_implied_volatility_rejects_stale_quotes() {
+        use crate::orderbook::clock::StubClock;
+
+        let book =
+            OrderBook::<()>::with_clock("TEST-OPT", Arc::new(StubClock::with_step(0, 1_000)));
+        let _ = book.add_limit_order(Id::new(), 540, 100, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 550, 100, Side::Sell, TimeInForce::Gtc, None);
+
+        let params = IVParams::call(100.0, 100.0, 0.25, 0.05);
+        let config = IVConfig::default().with_price_scale(100.0);
+
+        let stale = config.clone().with_max_quote_age(500);
+        let result = book.implied_volatility_with_config(&params, PriceSource::MidPrice, &stale);
+        assert!(matches!(
+            result,
+            Err(IVError::StaleQuotes {
+                max_age_ms: 500,
+                ..
+            })
+        ));
+
+        let fresh = config.with_max_quote_age(1_000_000);
+        assert!(
+            book.implied_volatility_with_config(&params, PriceSource::MidPrice, &fresh)
+                .is_ok()
+        );
+    }
 }
@@ -23,10 +23,32 @@
 //! Newton-Raphson root finding which converges quickly (3-5 iterations)
 //! because vega (∂price/∂σ) is always positive.
 //!
+//! # Exercise style
+//!
+//! [`IVParams::exercise_style`] selects the pricing model the solver
+//! inverts: Black-Scholes for [`ExerciseStyle::European`], a
+//! Cox-Ross-Rubinstein [`Binomial`] tree for [`ExerciseStyle::American`]
+//! (whose early-exercise premium matters most for deep ITM puts).
+//!
+//! # Surfaces
+//!
+//! [`IVSurface`] solves a whole options chain held in a
+//! [`BookManager`](crate::BookManager), fits a cubic spline per expiry,
+//! and interpolates between expiries in total variance.
+//!
+//! [`GreeksSnapshot`] computes delta, gamma, vega, theta and rho for the
+//! same kind of chain, ready to publish through an
+//! [`EventSerializer`](crate::EventSerializer).
+//!
+//! # Put-call parity
+//!
+//! [`ParityReport`] checks a call, put and underlying book for executable
+//! conversion or reversal arbitrage after spreads and taker fees.
+//!
 //! # Example
 //!
 //! ```ignore
-//! use orderbook_rs::implied_volatility::{IVParams, OptionType, PriceSource};
+//! use orderbook_rs::implied_volatility::{ExerciseStyle, IVParams, OptionType, PriceSource};
 //!
 //! let params = IVParams {
 //!     spot: 3000.0,
@@ -34,20 +56,29 @@
 //!     time_to_expiry: 30.0 / 365.0,
 //!     risk_free_rate: 0.0,
 //!     option_type: OptionType::Call,
+//!     exercise_style: ExerciseStyle::European,
 //! };
 //!
 //! let result = book.implied_volatility(&params, PriceSource::MidPrice)?;
 //! println!("IV: {:.2}%", result.iv * 100.0);
 //! ```
 
+mod binomial;
 mod black_scholes;
 mod error;
+mod greeks;
 mod integration;
+mod parity;
 mod solver;
+mod surface;
 mod types;
 
+pub use binomial::{Binomial, DEFAULT_BINOMIAL_STEPS};
 pub use black_scholes::BlackScholes;
 pub use error::IVError;
+pub use greeks::{GreeksEntry, GreeksSnapshot};
 pub use integration::IVConfig;
+pub use parity::{LegQuote, ParityArbitrage, ParityParams, ParityReport, ParityStrategy};
 pub use solver::{SolverConfig, solve_iv, solve_iv_bisection};
-pub use types::{IVParams, IVQuality, IVResult, OptionType, PriceSource};
+pub use surface::{IVSurface, IVSurfacePoint, SurfaceFailure, SurfaceIV, SurfaceInput};
+pub use types::{ExerciseStyle, Greeks, IVParams, IVQuality, IVResult, OptionType, PriceSource};
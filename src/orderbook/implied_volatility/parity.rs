@@ -0,0 +1,357 @@
+//! Put-call parity arbitrage checks across paired option books.
+//!
+//! For European options on a non-dividend-paying underlying,
+//! `C - P = S - K·e^(-rT)`. The synthetic forward `C - P` can be sold at
+//! `C_bid - P_ask` and bought at `C_ask - P_bid`; the underlying can be
+//! bought at `S_ask` and sold at `S_bid`. A [`ParityReport`] compares the
+//! two after crossing every spread and paying each book's taker fee, and
+//! reports the executable arbitrage, if any:
+//!
+//! - **Conversion** — buy the underlying, buy the put, sell the call.
+//!   Profitable when `C_bid - P_ask` exceeds [`ParityReport::upper_bound`].
+//! - **Reversal** — sell the underlying, sell the put, buy the call.
+//!   Profitable when `C_ask - P_bid` is below [`ParityReport::lower_bound`].
+
+use super::error::IVError;
+use crate::orderbook::book::OrderBook;
+use pricelevel::Side;
+use serde::{Deserialize, Serialize};
+
+/// Contract terms shared by the call and put being checked.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParityParams {
+    /// Common strike price in price units.
+    pub strike: f64,
+    /// Common time to expiration in years.
+    pub time_to_expiry: f64,
+    /// Risk-free interest rate (annualized).
+    pub risk_free_rate: f64,
+    /// Price scale factor to convert book prices to f64, as in
+    /// [`IVConfig::price_scale`](super::IVConfig::price_scale). Applies to
+    /// all three books.
+    pub price_scale: f64,
+}
+
+impl ParityParams {
+    /// Creates parity parameters with a price scale of `1.0`.
+    #[must_use]
+    pub fn new(strike: f64, time_to_expiry: f64, risk_free_rate: f64) -> Self {
+        Self {
+            strike,
+            time_to_expiry,
+            risk_free_rate,
+            price_scale: 1.0,
+        }
+    }
+
+    /// Sets the price scale factor.
+    #[must_use]
+    pub fn with_price_scale(mut self, price_scale: f64) -> Self {
+        self.price_scale = price_scale;
+        self
+    }
+
+    /// Present value of the strike: `K·e^(-rT)`.
+    #[must_use]
+    pub fn discounted_strike(&self) -> f64 {
+        self.strike * (-self.risk_free_rate * self.time_to_expiry).exp()
+    }
+}
+
+/// Direction of a put-call parity arbitrage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParityStrategy {
+    /// Long underlying, long put, short call.
+    Conversion,
+    /// Short underlying, short put, long call.
+    Reversal,
+}
+
+/// An executable put-call parity violation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParityArbitrage {
+    /// Which side of parity is violated.
+    pub strategy: ParityStrategy,
+    /// Locked-in profit per unit after spreads and fees, in price units.
+    pub edge: f64,
+    /// Units executable at the touch: the smallest best-level quantity
+    /// among the three legs.
+    pub quantity: u64,
+}
+
+/// Best-level quotes for one leg, scaled to price units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LegQuote {
+    /// Best bid price.
+    pub bid: f64,
+    /// Quantity at the best bid.
+    pub bid_quantity: u64,
+    /// Best ask price.
+    pub ask: f64,
+    /// Quantity at the best ask.
+    pub ask_quantity: u64,
+    /// Taker fee of the leg's book in basis points (`0` if the book has
+    /// no fee schedule).
+    pub taker_fee_bps: i32,
+}
+
+impl LegQuote {
+    fn from_book<T>(book: &OrderBook<T>, price_scale: f64) -> Result<Self, IVError>
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        let bbo = book.bbo();
+        let (Some(bid), Some(ask)) = (bbo.best_bid, bbo.best_ask) else {
+            return Err(IVError::NoPriceAvailable);
+        };
+        let quantity_at = |price: u128, side: Side| {
+            let levels = match side {
+                Side::Buy => &book.bids,
+                Side::Sell => &book.asks,
+            };
+            levels
+                .get(&price)
+                .and_then(|entry| entry.value().total_quantity().ok())
+                .unwrap_or(0)
+        };
+
+        Ok(Self {
+            bid: bid as f64 / price_scale,
+            bid_quantity: quantity_at(bid, Side::Buy),
+            ask: ask as f64 / price_scale,
+            ask_quantity: quantity_at(ask, Side::Sell),
+            taker_fee_bps: book.fee_schedule().map_or(0, |fees| fees.taker_fee_bps),
+        })
+    }
+
+    /// Taker fee paid on one unit traded at `price`.
+    fn fee(&self, price: f64) -> f64 {
+        price * f64::from(self.taker_fee_bps) / 10_000.0
+    }
+}
+
+/// Put-call parity check for one strike and expiry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParityReport {
+    /// Contract terms the check was run with.
+    pub params: ParityParams,
+    /// Call book quotes.
+    pub call: LegQuote,
+    /// Put book quotes.
+    pub put: LegQuote,
+    /// Underlying book quotes.
+    pub underlying: LegQuote,
+    /// Price at which the synthetic forward can be sold: `C_bid - P_ask`.
+    pub synthetic_bid: f64,
+    /// Price at which the synthetic forward can be bought: `C_ask - P_bid`.
+    pub synthetic_ask: f64,
+    /// Lowest synthetic forward price that does not allow a reversal:
+    /// `S_bid - K·e^(-rT)` less the reversal's fees.
+    pub lower_bound: f64,
+    /// Highest synthetic forward price that does not allow a conversion:
+    /// `S_ask - K·e^(-rT)` plus the conversion's fees.
+    pub upper_bound: f64,
+}
+
+impl ParityReport {
+    /// Checks put-call parity between `call`, `put` and `underlying` at
+    /// their best quotes.
+    ///
+    /// Each leg is assumed to cross the spread and pay its own book's
+    /// taker fee.
+    ///
+    /// # Errors
+    ///
+    /// - [`IVError::InvalidParams`] if the strike or price scale is not
+    ///   positive and finite, or the time to expiry is negative.
+    /// - [`IVError::NoPriceAvailable`] if any book lacks a best bid or ask.
+    pub fn from_books<T>(
+        call: &OrderBook<T>,
+        put: &OrderBook<T>,
+        underlying: &OrderBook<T>,
+        params: &ParityParams,
+    ) -> Result<Self, IVError>
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        for (name, value) in [
+            ("strike", params.strike),
+            ("price_scale", params.price_scale),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(IVError::InvalidParams {
+                    message: format!("{name} must be positive and finite, got {value}"),
+                });
+            }
+        }
+        if !params.time_to_expiry.is_finite() || params.time_to_expiry < 0.0 {
+            return Err(IVError::InvalidParams {
+                message: format!(
+                    "time_to_expiry must be non-negative and finite, got {}",
+                    params.time_to_expiry
+                ),
+            });
+        }
+
+        let call = LegQuote::from_book(call, params.price_scale)?;
+        let put = LegQuote::from_book(put, params.price_scale)?;
+        let underlying = LegQuote::from_book(underlying, params.price_scale)?;
+        let pv_strike = params.discounted_strike();
+
+        let conversion_fees =
+            underlying.fee(underlying.ask) + put.fee(put.ask) + call.fee(call.bid);
+        let reversal_fees = underlying.fee(underlying.bid) + put.fee(put.bid) + call.fee(call.ask);
+
+        Ok(Self {
+            params: *params,
+            call,
+            put,
+            underlying,
+            synthetic_bid: call.bid - put.ask,
+            synthetic_ask: call.ask - put.bid,
+            lower_bound: underlying.bid - pv_strike - reversal_fees,
+            upper_bound: underlying.ask - pv_strike + conversion_fees,
+        })
+    }
+
+    /// Profit per unit of a conversion after spreads and fees. Positive
+    /// values are an arbitrage.
+    #[must_use]
+    pub fn conversion_edge(&self) -> f64 {
+        self.synthetic_bid - self.upper_bound
+    }
+
+    /// Profit per unit of a reversal after spreads and fees. Positive
+    /// values are an arbitrage.
+    #[must_use]
+    pub fn reversal_edge(&self) -> f64 {
+        self.lower_bound - self.synthetic_ask
+    }
+
+    /// The executable arbitrage, if either edge is positive.
+    #[must_use]
+    pub fn arbitrage(&self) -> Option<ParityArbitrage> {
+        let conversion = self.conversion_edge();
+        let reversal = self.reversal_edge();
+        if conversion > 0.0 {
+            Some(ParityArbitrage {
+                strategy: ParityStrategy::Conversion,
+                edge: conversion,
+                quantity: self
+                    .underlying
+                    .ask_quantity
+                    .min(self.put.ask_quantity)
+                    .min(self.call.bid_quantity),
+            })
+        } else if reversal > 0.0 {
+            Some(ParityArbitrage {
+                strategy: ParityStrategy::Reversal,
+                edge: reversal,
+                quantity: self
+                    .underlying
+                    .bid_quantity
+                    .min(self.put.bid_quantity)
+                    .min(self.call.ask_quantity),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeeSchedule;
+    use pricelevel::{Id, TimeInForce};
+
+    fn book(symbol: &str, bid: u128, ask: u128, quantity: u64) -> OrderBook<()> {
+        let book = OrderBook::<()>::new(symbol);
+        let _ = book.add_limit_order(Id::new(), bid, quantity, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), ask, quantity, Side::Sell, TimeInForce::Gtc, None);
+        book
+    }
+
+    // K = 100, r = 0, so parity is C - P = S - 100.
+    fn params() -> ParityParams {
+        ParityParams::new(100.0, 0.5, 0.0).with_price_scale(100.0)
+    }
+
+    #[test]
+    fn test_consistent_books_have_no_arbitrage() {
+        let underlying = book("SPOT", 10_000, 10_010, 100);
+        let call = book("C100", 500, 510, 10);
+        let put = book("P100", 495, 505, 10);
+
+        let report = ParityReport::from_books(&call, &put, &underlying, &params()).unwrap();
+        assert!((report.synthetic_bid - (5.00 - 5.05)).abs() < 1e-9);
+        assert!((report.synthetic_ask - (5.10 - 4.95)).abs() < 1e-9);
+        assert!((report.lower_bound - 0.0).abs() < 1e-9);
+        assert!((report.upper_bound - 0.10).abs() < 1e-9);
+        assert!(report.arbitrage().is_none());
+    }
+
+    #[test]
+    fn test_rich_call_is_a_conversion() {
+        let underlying = book("SPOT", 10_000, 10_010, 100);
+        let call = book("C100", 600, 610, 10);
+        let put = book("P100", 495, 505, 7);
+
+        let report = ParityReport::from_books(&call, &put, &underlying, &params()).unwrap();
+        let arbitrage = report.arbitrage().expect("conversion available");
+        assert_eq!(arbitrage.strategy, ParityStrategy::Conversion);
+        // Sell call 6.00, buy put 5.05, buy spot 100.10, receive 100 at expiry.
+        assert!((arbitrage.edge - 0.85).abs() < 1e-9);
+        assert_eq!(arbitrage.quantity, 7);
+    }
+
+    #[test]
+    fn test_rich_put_is_a_reversal() {
+        let underlying = book("SPOT", 10_000, 10_010, 100);
+        let call = book("C100", 500, 510, 10);
+        let put = book("P100", 600, 610, 10);
+
+        let report = ParityReport::from_books(&call, &put, &underlying, &params()).unwrap();
+        let arbitrage = report.arbitrage().expect("reversal available");
+        assert_eq!(arbitrage.strategy, ParityStrategy::Reversal);
+        // Sell spot 100.00, sell put 6.00, buy call 5.10, pay 100 at expiry.
+        assert!((arbitrage.edge - 0.90).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fees_can_remove_the_edge() {
+        let underlying = book("SPOT", 10_000, 10_010, 100);
+        let call = book("C100", 520, 530, 10);
+        let put = book("P100", 495, 505, 10);
+
+        let report = ParityReport::from_books(&call, &put, &underlying, &params()).unwrap();
+        assert!(report.arbitrage().is_some());
+
+        // 20 bps on 100.10 of underlying costs ~0.20, more than the 0.05 edge.
+        underlying.set_fee_schedule(Some(FeeSchedule::new(0, 20)));
+        let report = ParityReport::from_books(&call, &put, &underlying, &params()).unwrap();
+        assert!(report.conversion_edge() < 0.0);
+        assert!(report.arbitrage().is_none());
+    }
+
+    #[test]
+    fn test_missing_quotes_and_invalid_params() {
+        let underlying = book("SPOT", 10_000, 10_010, 100);
+        let call = book("C100", 500, 510, 10);
+        let empty = OrderBook::<()>::new("P100");
+
+        assert!(matches!(
+            ParityReport::from_books(&call, &empty, &underlying, &params()),
+            Err(IVError::NoPriceAvailable)
+        ));
+        assert!(matches!(
+            ParityReport::from_books(
+                &call,
+                &call,
+                &underlying,
+                &ParityParams::new(100.0, -1.0, 0.0)
+            ),
+            Err(IVError::InvalidParams { .. })
+        ));
+    }
+}
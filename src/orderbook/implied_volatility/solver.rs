@@ -3,9 +3,10 @@
 //! This module provides a numerical solver to find the implied volatility
 //! that makes the Black-Scholes price equal to the observed market price.
 
+use super::binomial::{Binomial, DEFAULT_BINOMIAL_STEPS};
 use super::black_scholes::BlackScholes;
 use super::error::IVError;
-use super::types::IVParams;
+use super::types::{ExerciseStyle, Greeks, IVParams};
 
 /// Configuration for the Newton-Raphson solver.
 #[derive(Debug, Clone)]
@@ -22,6 +23,9 @@ pub struct SolverConfig {
     pub max_iv: f64,
     /// Minimum vega threshold to avoid division by near-zero.
     pub min_vega: f64,
+    /// Time steps of the binomial tree used for American options
+    /// (default: [`DEFAULT_BINOMIAL_STEPS`]).
+    pub binomial_steps: usize,
 }
 
 impl Default for SolverConfig {
@@ -33,6 +37,7 @@ impl Default for SolverConfig {
             min_iv: 0.001,
             max_iv: 5.0,
             min_vega: 1e-10,
+            binomial_steps: DEFAULT_BINOMIAL_STEPS,
         }
     }
 }
@@ -72,6 +77,39 @@ impl SolverConfig {
         self.max_iv = max_iv;
         self
     }
+
+    /// Sets the number of binomial tree steps used for American options.
+    #[must_use]
+    pub fn with_binomial_steps(mut self, binomial_steps: usize) -> Self {
+        self.binomial_steps = binomial_steps;
+        self
+    }
+}
+
+/// Prices the option with the model matching its exercise style.
+fn model_price(params: &IVParams, vol: f64, config: &SolverConfig) -> f64 {
+    match params.exercise_style {
+        ExerciseStyle::European => BlackScholes::price(params, vol),
+        ExerciseStyle::American => Binomial::price(params, vol, config.binomial_steps),
+    }
+}
+
+/// Vega under the model matching the option's exercise style.
+fn model_vega(params: &IVParams, vol: f64, config: &SolverConfig) -> f64 {
+    match params.exercise_style {
+        ExerciseStyle::European => BlackScholes::vega(params, vol),
+        ExerciseStyle::American => Binomial::vega(params, vol, config.binomial_steps),
+    }
+}
+
+/// Greeks under the model matching the option's exercise style:
+/// closed-form for European options, finite differences on the binomial
+/// tree for American ones.
+pub(super) fn model_greeks(params: &IVParams, vol: f64, config: &SolverConfig) -> Greeks {
+    match params.exercise_style {
+        ExerciseStyle::European => BlackScholes::greeks(params, vol),
+        ExerciseStyle::American => Binomial::greeks(params, vol, config.binomial_steps),
+    }
 }
 
 /// Validates input parameters for IV calculation.
@@ -161,8 +199,12 @@ fn smart_initial_guess(params: &IVParams, market_price: f64) -> f64 {
 ///
 /// Convergence is typically fast (3-5 iterations) because vega is always positive.
 ///
+/// European options are priced with Black-Scholes; American options
+/// ([`ExerciseStyle::American`]) are priced on a binomial tree of
+/// `config.binomial_steps` steps with a finite-difference vega.
+///
 /// # Arguments
-/// - `params`: Option parameters (spot, strike, time, rate, type)
+/// - `params`: Option parameters (spot, strike, time, rate, type, exercise style)
 /// - `market_price`: Observed market price to match
 /// - `config`: Solver configuration
 ///
@@ -230,7 +272,7 @@ pub fn solve_iv(
 
     // Newton-Raphson iteration
     for iteration in 0..config.max_iterations {
-        let price = BlackScholes::price(params, iv);
+        let price = model_price(params, iv, config);
 
         // Inputs are validated finite, so a non-finite price/iv here means the
         // iteration degenerated numerically. Bail with a typed error instead of
@@ -258,7 +300,7 @@ pub fn solve_iv(
             return Ok((iv, iteration + 1));
         }
 
-        let vega = BlackScholes::vega(params, iv);
+        let vega = model_vega(params, iv, config);
 
         // Handle near-zero vega (can happen for deep ITM/OTM or near expiry)
         if vega.abs() < config.min_vega {
@@ -342,8 +384,8 @@ pub fn solve_iv_bisection(
     let mut high = config.max_iv;
 
     // Verify solution exists in bounds
-    let price_low = BlackScholes::price(params, low);
-    let price_high = BlackScholes::price(params, high);
+    let price_low = model_price(params, low, config);
+    let price_high = model_price(params, high, config);
 
     if market_price < price_low || market_price > price_high {
         return Err(IVError::VolatilityOutOfBounds {
@@ -359,7 +401,7 @@ pub fn solve_iv_bisection(
 
     for iteration in 0..config.max_iterations {
         let mid = (low + high) / 2.0;
-        let price = BlackScholes::price(params, mid);
+        let price = model_price(params, mid, config);
         let diff = price - market_price;
 
         if diff.abs() < config.tolerance || (high - low) < config.tolerance {
@@ -642,4 +684,34 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_solve_iv_american_put_round_trips() {
+        let params =
+            IVParams::put(80.0, 100.0, 1.0, 0.05).with_exercise_style(ExerciseStyle::American);
+        let config = SolverConfig::default();
+        let target_vol = 0.3;
+        let market_price = Binomial::price(&params, target_vol, config.binomial_steps);
+
+        let (iv, _) = solve_iv(&params, market_price, &config).unwrap();
+        assert!((iv - target_vol).abs() < TOLERANCE, "iv = {iv}");
+
+        let (iv_bisect, _) = solve_iv_bisection(&params, market_price, &config).unwrap();
+        assert!((iv_bisect - target_vol).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_european_model_overstates_american_put_iv() {
+        let american =
+            IVParams::put(85.0, 100.0, 1.0, 0.05).with_exercise_style(ExerciseStyle::American);
+        let european = IVParams::put(85.0, 100.0, 1.0, 0.05);
+        let config = SolverConfig::default();
+        let market_price = Binomial::price(&american, 0.25, config.binomial_steps);
+
+        let (american_iv, _) = solve_iv(&american, market_price, &config).unwrap();
+        let (european_iv, _) = solve_iv(&european, market_price, &config).unwrap();
+        assert!((american_iv - 0.25).abs() < TOLERANCE);
+        // Ignoring the early-exercise premium pushes the implied vol up.
+        assert!(european_iv > american_iv + 0.01);
+    }
 }
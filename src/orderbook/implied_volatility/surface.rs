@@ -0,0 +1,574 @@
+//! Implied volatility surface construction and interpolation.
+//!
+//! An [`IVSurface`] is built from one order book per option contract.
+//! IVs are solved per book (in parallel across scoped threads), grouped
+//! into expiry slices, and each slice is fitted with a natural cubic
+//! spline in strike. Queries between expiries interpolate linearly in
+//! total variance (`σ² · T`), which keeps the surface free of calendar
+//! arbitrage whenever the input slices are.
+
+use super::error::IVError;
+use super::integration::IVConfig;
+use super::types::{ExerciseStyle, IVParams, IVQuality, IVResult, OptionType, PriceSource};
+use crate::orderbook::book::OrderBook;
+use crate::orderbook::manager::BookManager;
+use serde::{Deserialize, Serialize};
+
+/// Tolerance used to decide that two strikes or expiries are the same node.
+const NODE_EPSILON: f64 = 1e-9;
+
+/// One option contract to include in a surface build.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceInput {
+    /// Symbol of the contract's order book in the [`BookManager`].
+    pub symbol: String,
+    /// Option strike price in price units.
+    pub strike: f64,
+    /// Time to expiration in years.
+    pub time_to_expiry: f64,
+    /// Option type (Call or Put).
+    pub option_type: OptionType,
+    /// Exercise style, selecting the model the IV is solved with
+    /// (defaults to European).
+    #[serde(default)]
+    pub exercise_style: ExerciseStyle,
+}
+
+impl SurfaceInput {
+    /// Creates a new surface input for a European contract.
+    #[must_use]
+    pub fn new(
+        symbol: impl Into<String>,
+        strike: f64,
+        time_to_expiry: f64,
+        option_type: OptionType,
+    ) -> Self {
+        Self {
+            symbol: symbol.into(),
+            strike,
+            time_to_expiry,
+            option_type,
+            exercise_style: ExerciseStyle::European,
+        }
+    }
+
+    /// Sets the exercise style.
+    #[must_use]
+    pub fn with_exercise_style(mut self, exercise_style: ExerciseStyle) -> Self {
+        self.exercise_style = exercise_style;
+        self
+    }
+}
+
+/// A solved IV node on the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IVSurfacePoint {
+    /// Option strike price in price units.
+    pub strike: f64,
+    /// Time to expiration in years.
+    pub time_to_expiry: f64,
+    /// Implied volatility at this node.
+    pub iv: f64,
+    /// Bid-ask spread in basis points at calculation time.
+    pub spread_bps: f64,
+    /// Liquidity quality of the node.
+    pub quality: IVQuality,
+}
+
+/// Result of an [`IVSurface::iv_at`] query.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceIV {
+    /// Implied volatility at the queried point.
+    pub iv: f64,
+    /// Node quality when the query hits a node exactly, otherwise
+    /// [`IVQuality::Interpolated`].
+    pub quality: IVQuality,
+    /// `true` when the query lies outside the strike or expiry range
+    /// covered by the nodes and was answered by flat extrapolation.
+    pub extrapolated: bool,
+}
+
+/// A contract that could not contribute a node to the surface.
+#[derive(Debug, Clone)]
+pub struct SurfaceFailure {
+    /// Symbol of the contract's order book.
+    pub symbol: String,
+    /// Why the IV could not be computed.
+    pub error: IVError,
+}
+
+/// One expiry slice: strikes sorted ascending with fitted spline moments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExpirySlice {
+    time_to_expiry: f64,
+    points: Vec<IVSurfacePoint>,
+    /// Second derivatives of the natural cubic spline at each strike.
+    moments: Vec<f64>,
+}
+
+/// Implied volatility surface fitted across an options chain.
+///
+/// Build it from live books with [`IVSurface::from_books`] or from
+/// precomputed nodes with [`IVSurface::from_points`], then query it with
+/// [`IVSurface::iv_at`].
+///
+/// # Interpolation
+///
+/// - **Strike:** natural cubic spline per expiry slice; flat beyond the
+///   outermost strikes of the slice.
+/// - **Expiry:** linear in total variance between the two neighbouring
+///   slices; flat in IV beyond the shortest and longest expiries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IVSurface {
+    slices: Vec<ExpirySlice>,
+    #[serde(skip)]
+    failures: Vec<SurfaceFailure>,
+}
+
+impl IVSurface {
+    /// Builds a surface by solving the IV of every input's book.
+    ///
+    /// Books are looked up in `manager` by symbol and solved in parallel
+    /// with [`OrderBook::implied_volatility_with_config`](crate::OrderBook::implied_volatility_with_config).
+    /// Inputs whose book is missing or whose IV cannot be solved are
+    /// skipped and reported through [`Self::failures`]. When several
+    /// inputs share a strike and expiry (e.g. a call and a put), the node
+    /// with the tighter spread is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IVError::InvalidParams`] if no input produced a valid IV.
+    pub fn from_books<T, M>(
+        manager: &M,
+        inputs: &[SurfaceInput],
+        spot: f64,
+        risk_free_rate: f64,
+        price_source: PriceSource,
+        config: &IVConfig,
+    ) -> Result<Self, IVError>
+    where
+        T: Clone + Send + Sync + Default + 'static,
+        M: BookManager<T> + ?Sized,
+    {
+        let mut failures = Vec::new();
+        let mut points = Vec::with_capacity(inputs.len());
+        for (input, result) in
+            solve_chain(manager, inputs, spot, risk_free_rate, price_source, config)
+        {
+            match result {
+                Ok(result) => points.push(IVSurfacePoint {
+                    strike: input.strike,
+                    time_to_expiry: input.time_to_expiry,
+                    iv: result.iv,
+                    spread_bps: result.spread_bps,
+                    quality: result.quality,
+                }),
+                Err(error) => failures.push(SurfaceFailure {
+                    symbol: input.symbol.clone(),
+                    error,
+                }),
+            }
+        }
+
+        let mut surface = Self::from_points(points)?;
+        surface.failures = failures;
+        Ok(surface)
+    }
+
+    /// Builds a surface from already-solved nodes.
+    ///
+    /// Nodes with a non-finite or non-positive IV, strike, or expiry are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IVError::InvalidParams`] if no valid node remains.
+    pub fn from_points(points: Vec<IVSurfacePoint>) -> Result<Self, IVError> {
+        let mut points: Vec<IVSurfacePoint> = points
+            .into_iter()
+            .filter(|p| {
+                p.iv.is_finite()
+                    && p.iv > 0.0
+                    && p.strike.is_finite()
+                    && p.strike > 0.0
+                    && p.time_to_expiry.is_finite()
+                    && p.time_to_expiry > 0.0
+            })
+            .collect();
+        if points.is_empty() {
+            return Err(IVError::InvalidParams {
+                message: "no valid IV nodes to build a surface from".to_string(),
+            });
+        }
+
+        points.sort_by(|a, b| {
+            a.time_to_expiry
+                .total_cmp(&b.time_to_expiry)
+                .then(a.strike.total_cmp(&b.strike))
+                .then(a.spread_bps.total_cmp(&b.spread_bps))
+        });
+
+        let mut slices: Vec<ExpirySlice> = Vec::new();
+        for point in points {
+            match slices.last_mut() {
+                Some(slice) if same_node(slice.time_to_expiry, point.time_to_expiry) => {
+                    // Sorted by spread within a node, so the first seen wins.
+                    let duplicate = slice
+                        .points
+                        .last()
+                        .is_some_and(|last| same_node(last.strike, point.strike));
+                    if !duplicate {
+                        slice.points.push(point);
+                    }
+                }
+                _ => slices.push(ExpirySlice {
+                    time_to_expiry: point.time_to_expiry,
+                    points: vec![point],
+                    moments: Vec::new(),
+                }),
+            }
+        }
+
+        for slice in &mut slices {
+            let strikes: Vec<f64> = slice.points.iter().map(|p| p.strike).collect();
+            let ivs: Vec<f64> = slice.points.iter().map(|p| p.iv).collect();
+            slice.moments = natural_spline_moments(&strikes, &ivs);
+        }
+
+        Ok(Self {
+            slices,
+            failures: Vec::new(),
+        })
+    }
+
+    /// Returns the interpolated IV at `strike` and `time_to_expiry`.
+    ///
+    /// Returns `None` for a non-finite or non-positive `time_to_expiry`,
+    /// or a non-finite `strike`.
+    #[must_use]
+    pub fn iv_at(&self, strike: f64, time_to_expiry: f64) -> Option<SurfaceIV> {
+        if !strike.is_finite() || !time_to_expiry.is_finite() || time_to_expiry <= 0.0 {
+            return None;
+        }
+
+        let upper = self
+            .slices
+            .partition_point(|s| s.time_to_expiry < time_to_expiry - NODE_EPSILON);
+
+        // Exact expiry hit, or flat extrapolation beyond either end.
+        let single = if upper < self.slices.len()
+            && same_node(self.slices[upper].time_to_expiry, time_to_expiry)
+        {
+            Some((&self.slices[upper], false))
+        } else if upper == 0 {
+            Some((&self.slices[0], true))
+        } else if upper == self.slices.len() {
+            Some((&self.slices[upper - 1], true))
+        } else {
+            None
+        };
+
+        if let Some((slice, expiry_extrapolated)) = single {
+            let (iv, quality, strike_extrapolated) = slice.iv_at(strike);
+            let quality = if expiry_extrapolated {
+                IVQuality::Interpolated
+            } else {
+                quality
+            };
+            return Some(SurfaceIV {
+                iv,
+                quality,
+                extrapolated: expiry_extrapolated || strike_extrapolated,
+            });
+        }
+
+        let near = &self.slices[upper - 1];
+        let far = &self.slices[upper];
+        let (near_iv, _, near_extrapolated) = near.iv_at(strike);
+        let (far_iv, _, far_extrapolated) = far.iv_at(strike);
+
+        let near_var = near_iv * near_iv * near.time_to_expiry;
+        let far_var = far_iv * far_iv * far.time_to_expiry;
+        let weight =
+            (time_to_expiry - near.time_to_expiry) / (far.time_to_expiry - near.time_to_expiry);
+        let total_var = near_var + weight * (far_var - near_var);
+
+        Some(SurfaceIV {
+            iv: (total_var.max(0.0) / time_to_expiry).sqrt(),
+            quality: IVQuality::Interpolated,
+            extrapolated: near_extrapolated || far_extrapolated,
+        })
+    }
+
+    /// Returns every node on the surface, sorted by expiry then strike.
+    #[must_use]
+    pub fn points(&self) -> Vec<IVSurfacePoint> {
+        self.slices
+            .iter()
+            .flat_map(|slice| slice.points.iter().copied())
+            .collect()
+    }
+
+    /// Returns the distinct expiries on the surface, ascending.
+    #[must_use]
+    pub fn expiries(&self) -> Vec<f64> {
+        self.slices.iter().map(|s| s.time_to_expiry).collect()
+    }
+
+    /// Returns the inputs skipped by [`Self::from_books`] and why.
+    #[must_use]
+    pub fn failures(&self) -> &[SurfaceFailure] {
+        &self.failures
+    }
+}
+
+impl ExpirySlice {
+    /// Spline value at `strike`, node quality (or `Interpolated`), and
+    /// whether the strike was outside the slice's range.
+    fn iv_at(&self, strike: f64) -> (f64, IVQuality, bool) {
+        let points = &self.points;
+        let first = &points[0];
+        let last = &points[points.len() - 1];
+
+        if strike < first.strike - NODE_EPSILON {
+            return (first.iv, IVQuality::Interpolated, true);
+        }
+        if strike > last.strike + NODE_EPSILON {
+            return (last.iv, IVQuality::Interpolated, true);
+        }
+
+        let idx = points.partition_point(|p| p.strike < strike - NODE_EPSILON);
+        if idx < points.len() && same_node(points[idx].strike, strike) {
+            return (points[idx].iv, points[idx].quality, false);
+        }
+
+        // `strike` lies strictly between points[idx - 1] and points[idx].
+        let (lo, hi) = (&points[idx - 1], &points[idx]);
+        let (m_lo, m_hi) = (self.moments[idx - 1], self.moments[idx]);
+        let h = hi.strike - lo.strike;
+        let a = (hi.strike - strike) / h;
+        let b = (strike - lo.strike) / h;
+        let iv = a * lo.iv
+            + b * hi.iv
+            + ((a * a * a - a) * m_lo + (b * b * b - b) * m_hi) * (h * h) / 6.0;
+
+        (iv.max(0.0), IVQuality::Interpolated, false)
+    }
+}
+
+/// Solves the IV of every input's book, in input order.
+///
+/// Books are looked up in `manager` by symbol; a missing book yields
+/// [`IVError::BookNotFound`]. Solving is spread over scoped threads, one
+/// contiguous chunk of inputs per available core.
+pub(super) fn solve_chain<'a, T, M>(
+    manager: &M,
+    inputs: &'a [SurfaceInput],
+    spot: f64,
+    risk_free_rate: f64,
+    price_source: PriceSource,
+    config: &IVConfig,
+) -> Vec<(&'a SurfaceInput, Result<IVResult, IVError>)>
+where
+    T: Clone + Send + Sync + Default + 'static,
+    M: BookManager<T> + ?Sized,
+{
+    let jobs: Vec<(&SurfaceInput, Option<&OrderBook<T>>)> = inputs
+        .iter()
+        .map(|input| (input, manager.get_book(&input.symbol)))
+        .collect();
+
+    let workers = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(jobs.len())
+        .max(1);
+    let chunk_size = jobs.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = jobs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&(input, book)| {
+                            let result = match book {
+                                Some(book) => {
+                                    let params = IVParams::new(
+                                        spot,
+                                        input.strike,
+                                        input.time_to_expiry,
+                                        risk_free_rate,
+                                        input.option_type,
+                                    )
+                                    .with_exercise_style(input.exercise_style);
+                                    book.implied_volatility_with_config(
+                                        &params,
+                                        price_source,
+                                        config,
+                                    )
+                                }
+                                None => Err(IVError::BookNotFound {
+                                    symbol: input.symbol.clone(),
+                                }),
+                            };
+                            (input, result)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+/// Two strikes or expiries closer than [`NODE_EPSILON`] are the same node.
+fn same_node(a: f64, b: f64) -> bool {
+    (a - b).abs() <= NODE_EPSILON
+}
+
+/// Second derivatives of the natural cubic spline through `(xs, ys)`.
+///
+/// Solved with the Thomas algorithm on the tridiagonal system; the end
+/// moments are zero. Fewer than three points yield all-zero moments,
+/// i.e. linear interpolation.
+fn natural_spline_moments(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut moments = vec![0.0; n];
+    if n < 3 {
+        return moments;
+    }
+
+    let mut diag = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    let mut upper = vec![0.0; n];
+    for i in 1..n - 1 {
+        let h_prev = xs[i] - xs[i - 1];
+        let h_next = xs[i + 1] - xs[i];
+        diag[i] = 2.0 * (h_prev + h_next);
+        upper[i] = h_next;
+        rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h_next - (ys[i] - ys[i - 1]) / h_prev);
+    }
+
+    // Forward sweep over the interior rows.
+    for i in 2..n - 1 {
+        let h_prev = xs[i] - xs[i - 1];
+        let factor = h_prev / diag[i - 1];
+        diag[i] -= factor * upper[i - 1];
+        rhs[i] -= factor * rhs[i - 1];
+    }
+
+    // Back substitution.
+    for i in (1..n - 1).rev() {
+        moments[i] = (rhs[i] - upper[i] * moments[i + 1]) / diag[i];
+    }
+
+    moments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(strike: f64, time_to_expiry: f64, iv: f64) -> IVSurfacePoint {
+        IVSurfacePoint {
+            strike,
+            time_to_expiry,
+            iv,
+            spread_bps: 50.0,
+            quality: IVQuality::High,
+        }
+    }
+
+    #[test]
+    fn test_from_points_rejects_empty() {
+        assert!(matches!(
+            IVSurface::from_points(vec![node(100.0, 0.25, f64::NAN)]),
+            Err(IVError::InvalidParams { .. })
+        ));
+    }
+
+    #[test]
+    fn test_node_hit_returns_node_quality() {
+        let surface = IVSurface::from_points(vec![
+            node(90.0, 0.25, 0.30),
+            node(100.0, 0.25, 0.25),
+            node(110.0, 0.25, 0.28),
+        ])
+        .unwrap();
+
+        let hit = surface.iv_at(100.0, 0.25).unwrap();
+        assert!((hit.iv - 0.25).abs() < 1e-12);
+        assert_eq!(hit.quality, IVQuality::High);
+        assert!(!hit.extrapolated);
+
+        let between = surface.iv_at(95.0, 0.25).unwrap();
+        assert_eq!(between.quality, IVQuality::Interpolated);
+        assert!(between.iv > 0.25 && between.iv < 0.30);
+    }
+
+    #[test]
+    fn test_spline_reproduces_linear_smile() {
+        // A natural spline through collinear points is the line itself.
+        let surface = IVSurface::from_points(vec![
+            node(80.0, 0.5, 0.40),
+            node(90.0, 0.5, 0.35),
+            node(100.0, 0.5, 0.30),
+            node(110.0, 0.5, 0.25),
+        ])
+        .unwrap();
+        let iv = surface.iv_at(95.0, 0.5).unwrap().iv;
+        assert!((iv - 0.325).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_expiry_interpolation_is_linear_in_total_variance() {
+        let surface =
+            IVSurface::from_points(vec![node(100.0, 0.25, 0.20), node(100.0, 1.0, 0.30)]).unwrap();
+
+        let t = 0.5;
+        let expected_var = 0.20f64.powi(2) * 0.25 + (0.25 / 0.75) * (0.30f64.powi(2) - 0.01);
+        let expected = (expected_var / t).sqrt();
+        let result = surface.iv_at(100.0, t).unwrap();
+        assert!((result.iv - expected).abs() < 1e-12);
+        assert_eq!(result.quality, IVQuality::Interpolated);
+        assert!(!result.extrapolated);
+    }
+
+    #[test]
+    fn test_flat_extrapolation_is_flagged() {
+        let surface =
+            IVSurface::from_points(vec![node(90.0, 0.25, 0.30), node(110.0, 0.25, 0.20)]).unwrap();
+
+        let below = surface.iv_at(50.0, 0.25).unwrap();
+        assert!((below.iv - 0.30).abs() < 1e-12);
+        assert!(below.extrapolated);
+
+        let later = surface.iv_at(110.0, 2.0).unwrap();
+        assert!((later.iv - 0.20).abs() < 1e-12);
+        assert!(later.extrapolated);
+        assert_eq!(later.quality, IVQuality::Interpolated);
+
+        assert!(surface.iv_at(100.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_node_keeps_tighter_spread() {
+        let mut wide = node(100.0, 0.25, 0.40);
+        wide.spread_bps = 800.0;
+        wide.quality = IVQuality::Low;
+        let surface = IVSurface::from_points(vec![wide, node(100.0, 0.25, 0.25)]).unwrap();
+
+        assert_eq!(surface.points().len(), 1);
+        let hit = surface.iv_at(100.0, 0.25).unwrap();
+        assert!((hit.iv - 0.25).abs() < 1e-12);
+        assert_eq!(hit.quality, IVQuality::High);
+    }
+}
@@ -11,6 +11,19 @@ pub enum OptionType {
     Put,
 }
 
+/// Exercise style of the option contract.
+///
+/// Selects the pricing model used by the IV solver: closed-form
+/// Black-Scholes for European options, a binomial tree for American ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExerciseStyle {
+    /// Exercisable only at expiry.
+    #[default]
+    European,
+    /// Exercisable at any time up to expiry.
+    American,
+}
+
 /// Price source for IV calculation from order book.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PriceSource {
@@ -21,6 +34,21 @@ pub enum PriceSource {
     WeightedMid,
     /// Last traded price from the order book.
     LastTrade,
+    /// Depth-weighted mid over the top `levels` levels of each side: each
+    /// side's VWAP weighted by the opposite side's quantity, as in
+    /// [`EnrichedSnapshot::depth_weighted_mid`](crate::orderbook::EnrichedSnapshot::depth_weighted_mid).
+    /// A `levels` of `0` is treated as `1`, which is the micro price.
+    DepthWeightedMid {
+        /// Number of price levels per side to include.
+        levels: usize,
+    },
+    /// Mid of the best bid and ask levels holding at least `min_size`
+    /// units, skipping smaller levels closer to the touch. The reported
+    /// spread is that of the qualifying levels.
+    SizeFilteredMid {
+        /// Minimum total quantity a level needs to be considered.
+        min_size: u64,
+    },
 }
 
 /// IV calculation quality indicator based on liquidity.
@@ -54,10 +82,13 @@ pub struct IVParams {
     pub risk_free_rate: f64,
     /// Option type (Call or Put).
     pub option_type: OptionType,
+    /// Exercise style (defaults to European).
+    #[serde(default)]
+    pub exercise_style: ExerciseStyle,
 }
 
 impl IVParams {
-    /// Creates new IV parameters.
+    /// Creates new IV parameters for a European option.
     ///
     /// # Arguments
     /// - `spot`: Underlying spot price in price units
@@ -79,9 +110,17 @@ impl IVParams {
             time_to_expiry,
             risk_free_rate,
             option_type,
+            exercise_style: ExerciseStyle::European,
         }
     }
 
+    /// Sets the exercise style.
+    #[must_use]
+    pub fn with_exercise_style(mut self, exercise_style: ExerciseStyle) -> Self {
+        self.exercise_style = exercise_style;
+        self
+    }
+
     /// Creates parameters for a call option.
     #[must_use]
     pub fn call(spot: f64, strike: f64, time_to_expiry: f64, risk_free_rate: f64) -> Self {
@@ -137,6 +176,25 @@ impl IVParams {
     }
 }
 
+/// Full set of first-order Greeks (plus gamma) for an option.
+///
+/// Units follow [`BlackScholes`](super::BlackScholes): `vega` and `rho` are
+/// per unit change in volatility and rate (not per 1%), and `theta` is per
+/// calendar day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Greeks {
+    /// Sensitivity of the price to the underlying (∂price/∂S).
+    pub delta: f64,
+    /// Rate of change of delta (∂²price/∂S²).
+    pub gamma: f64,
+    /// Sensitivity of the price to volatility (∂price/∂σ).
+    pub vega: f64,
+    /// Daily time decay (∂price/∂t, per day).
+    pub theta: f64,
+    /// Sensitivity of the price to the risk-free rate (∂price/∂r).
+    pub rho: f64,
+}
+
 /// Result of IV calculation.
 ///
 /// Contains the calculated implied volatility along with metadata
@@ -191,6 +249,24 @@ impl IVResult {
     pub fn is_acceptable_quality(&self) -> bool {
         matches!(self.quality, IVQuality::High | IVQuality::Medium)
     }
+
+    /// Computes the Greeks of the option described by `params` at the
+    /// solved implied volatility, with the model its
+    /// [`exercise_style`](IVParams::exercise_style) selects: Black-Scholes
+    /// for European options, finite differences on a
+    /// [`DEFAULT_BINOMIAL_STEPS`](super::DEFAULT_BINOMIAL_STEPS)-step tree
+    /// for American ones.
+    #[must_use]
+    pub fn greeks(&self, params: &IVParams) -> Greeks {
+        self.greeks_with_config(params, &super::SolverConfig::default())
+    }
+
+    /// Like [`Self::greeks`], with American options priced on a tree of
+    /// `config.binomial_steps` steps.
+    #[must_use]
+    pub fn greeks_with_config(&self, params: &IVParams, config: &super::SolverConfig) -> Greeks {
+        super::solver::model_greeks(params, self.iv, config)
+    }
 }
 
 #[cfg(test)]
@@ -237,6 +313,20 @@ mod tests {
         assert!(params.is_otm());
     }
 
+    #[test]
+    fn test_iv_params_exercise_style_defaults_to_european() {
+        let params = IVParams::put(100.0, 110.0, 0.25, 0.05);
+        assert_eq!(params.exercise_style, ExerciseStyle::European);
+
+        // Payloads without the field keep decoding as European.
+        let json = r#"{"spot":100.0,"strike":110.0,"time_to_expiry":0.25,"risk_free_rate":0.05,"option_type":"Put"}"#;
+        let decoded: IVParams = serde_json::from_str(json).unwrap();
+        assert_eq!(decoded.exercise_style, ExerciseStyle::European);
+
+        let american = params.with_exercise_style(ExerciseStyle::American);
+        assert_eq!(american.exercise_style, ExerciseStyle::American);
+    }
+
     #[test]
     fn test_iv_params_atm() {
         let params = IVParams::call(100.0, 100.0, 0.25, 0.05);
@@ -251,6 +341,31 @@ mod tests {
         assert!((result.iv_percent() - 25.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_iv_result_greeks_use_solved_iv() {
+        let params = IVParams::call(100.0, 100.0, 0.25, 0.05);
+        let result = IVResult::new(0.25, 5.5, 50.0, 4, IVQuality::High);
+        assert_eq!(
+            result.greeks(&params),
+            super::super::BlackScholes::greeks(&params, 0.25)
+        );
+    }
+
+    #[test]
+    fn test_iv_result_greeks_follow_exercise_style() {
+        let european = IVParams::put(80.0, 100.0, 1.0, 0.05);
+        let american = european
+            .clone()
+            .with_exercise_style(ExerciseStyle::American);
+        let result = IVResult::new(0.2, 20.0, 50.0, 4, IVQuality::High);
+        let config = super::super::SolverConfig::default().with_binomial_steps(100);
+        assert_eq!(
+            result.greeks_with_config(&american, &config),
+            super::super::Binomial::greeks(&american, 0.2, 100)
+        );
+        assert!(result.greeks(&american).delta < result.greeks(&european).delta);
+    }
+
     #[test]
     fn test_iv_result_quality() {
         let high = IVResult::new(0.25, 10.0, 50.0, 5, IVQuality::High);
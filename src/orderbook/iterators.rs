@@ -4,20 +4,36 @@
 //! and structure without unnecessary allocations. All iterators support standard
 //! iterator combinators and can short-circuit early.
 
-use crossbeam_skiplist::SkipMap;
-use crossbeam_skiplist::map::Iter;
+use super::level_map::{LevelRange, PriceLevelMap};
 use either::Either;
-use pricelevel::{PriceLevel, Side};
+use pricelevel::{Id, OrderType, Side};
+use serde::{Deserialize, Serialize};
 use std::iter::Rev;
+use std::ops::Bound;
 use std::sync::Arc;
 
-/// Direction-erased iterator over price levels in a [`SkipMap`].
+/// Price bounds used to seek into a side's [`PriceLevelMap`].
+pub(super) type PriceBounds = (Bound<u128>, Bound<u128>);
+
+/// Direction-erased iterator over a range of price levels in a [`PriceLevelMap`].
 ///
-/// Wraps either a reverse (highest-to-lowest) or forward (lowest-to-highest) iterator:
+/// Wraps either a reverse (highest-to-lowest) or forward (lowest-to-highest) range:
 /// bids ([`Side::Buy`]) iterate in descending price order, while asks ([`Side::Sell`])
-/// iterate in ascending price order.
-type PriceLevelIter<'a> =
-    Either<Rev<Iter<'a, u128, Arc<PriceLevel>>>, Iter<'a, u128, Arc<PriceLevel>>>;
+/// iterate in ascending price order. Backed by `PriceLevelMap::range`, so only the levels
+/// inside the bounds are ever visited.
+pub(super) type PriceLevelIter<'a> = Either<Rev<LevelRange<'a>>, LevelRange<'a>>;
+
+/// Seeks to `bounds` and orients the walk best price first for `side`.
+pub(super) fn side_range(
+    price_levels: &PriceLevelMap,
+    side: Side,
+    bounds: PriceBounds,
+) -> PriceLevelIter<'_> {
+    match side {
+        Side::Buy => Either::Left(price_levels.range(bounds).rev()), // Highest to lowest
+        Side::Sell => Either::Right(price_levels.range(bounds)),     // Lowest to highest
+    }
+}
 
 /// Information about a price level including price, quantity, and cumulative depth
 #[derive(Debug, Clone)]
@@ -30,6 +46,19 @@ pub struct LevelInfo {
 
     /// Cumulative depth up to and including this level (in units)
     pub cumulative_depth: u64,
+
+    /// Number of orders resting at this level
+    pub order_count: usize,
+}
+
+impl LevelInfo {
+    /// Mean order size at this level (in units), `None` for a level with
+    /// no orders. Tells one large order apart from many small ones with
+    /// the same total quantity.
+    #[must_use]
+    pub fn average_order_size(&self) -> Option<f64> {
+        (self.order_count > 0).then(|| self.quantity as f64 / self.order_count as f64)
+    }
 }
 
 /// Iterator over price levels with cumulative depth tracking
@@ -46,16 +75,31 @@ impl<'a> LevelsWithCumulativeDepth<'a> {
     /// Creates a new iterator over levels with cumulative depth
     ///
     /// # Arguments
-    /// - `price_levels`: Reference to the SkipMap of price levels
+    /// - `price_levels`: Reference to the map of price levels
     /// - `side`: Side to iterate (Buy for bids, Sell for asks)
-    pub fn new(price_levels: &'a SkipMap<u128, Arc<PriceLevel>>, side: Side) -> Self {
-        let iter = match side {
-            Side::Buy => Either::Left(price_levels.iter().rev()), // Highest to lowest
-            Side::Sell => Either::Right(price_levels.iter()),     // Lowest to highest
-        };
+    pub fn new(price_levels: &'a PriceLevelMap, side: Side) -> Self {
+        Self {
+            iter: side_range(price_levels, side, (Bound::Unbounded, Bound::Unbounded)),
+            cumulative_depth: 0,
+        }
+    }
 
+    /// Creates an iterator that starts at `price` and walks away from the touch
+    ///
+    /// Bids yield levels at or below `price`, asks at or above it. Cumulative
+    /// depth is counted from the first yielded level.
+    ///
+    /// # Arguments
+    /// - `price_levels`: Reference to the map of price levels
+    /// - `side`: Side to iterate (Buy for bids, Sell for asks)
+    /// - `price`: Starting price (inclusive, in price units)
+    pub fn from_price(price_levels: &'a PriceLevelMap, side: Side, price: u128) -> Self {
+        let bounds = match side {
+            Side::Buy => (Bound::Unbounded, Bound::Included(price)),
+            Side::Sell => (Bound::Included(price), Bound::Unbounded),
+        };
         Self {
-            iter,
+            iter: side_range(price_levels, side, bounds),
             cumulative_depth: 0,
         }
     }
@@ -74,6 +118,7 @@ impl<'a> Iterator for LevelsWithCumulativeDepth<'a> {
                 price,
                 quantity,
                 cumulative_depth: self.cumulative_depth,
+                order_count: entry.value().order_count(),
             }
         })
     }
@@ -94,21 +139,12 @@ impl<'a> LevelsUntilDepth<'a> {
     /// Creates a new iterator that stops at target depth
     ///
     /// # Arguments
-    /// - `price_levels`: Reference to the SkipMap of price levels
+    /// - `price_levels`: Reference to the map of price levels
     /// - `side`: Side to iterate (Buy for bids, Sell for asks)
     /// - `target_depth`: Target cumulative depth (in units)
-    pub fn new(
-        price_levels: &'a SkipMap<u128, Arc<PriceLevel>>,
-        side: Side,
-        target_depth: u64,
-    ) -> Self {
-        let iter = match side {
-            Side::Buy => Either::Left(price_levels.iter().rev()),
-            Side::Sell => Either::Right(price_levels.iter()),
-        };
-
+    pub fn new(price_levels: &'a PriceLevelMap, side: Side, target_depth: u64) -> Self {
         Self {
-            iter,
+            iter: side_range(price_levels, side, (Bound::Unbounded, Bound::Unbounded)),
             target_depth,
             cumulative_depth: 0,
             finished: false,
@@ -133,6 +169,7 @@ impl<'a> Iterator for LevelsUntilDepth<'a> {
                 price,
                 quantity,
                 cumulative_depth: self.cumulative_depth,
+                order_count: entry.value().order_count(),
             };
 
             // Check if we've reached target depth
@@ -148,40 +185,36 @@ impl<'a> Iterator for LevelsUntilDepth<'a> {
 /// Iterator over price levels within a specific price range
 ///
 /// Only yields levels where the price falls within [min_price, max_price] inclusive.
-/// Useful for analyzing liquidity in specific price bands.
+/// Useful for analyzing liquidity in specific price bands. The band is located with
+/// `SkipMap::range`, so the cost is O(log N + M) where M is the number of levels
+/// inside the band, regardless of how deep in the book it sits.
 pub struct LevelsInRange<'a> {
     iter: PriceLevelIter<'a>,
-    side: Side,
-    min_price: u128,
-    max_price: u128,
-    finished: bool,
 }
 
 impl<'a> LevelsInRange<'a> {
     /// Creates a new iterator over levels in a price range
     ///
+    /// An inverted range (`min_price > max_price`) yields nothing.
+    ///
     /// # Arguments
-    /// - `price_levels`: Reference to the SkipMap of price levels
+    /// - `price_levels`: Reference to the map of price levels
     /// - `side`: Side to iterate (Buy for bids, Sell for asks)
     /// - `min_price`: Minimum price (inclusive, in price units)
     /// - `max_price`: Maximum price (inclusive, in price units)
     pub fn new(
-        price_levels: &'a SkipMap<u128, Arc<PriceLevel>>,
+        price_levels: &'a PriceLevelMap,
         side: Side,
         min_price: u128,
         max_price: u128,
     ) -> Self {
-        let iter = match side {
-            Side::Buy => Either::Left(price_levels.iter().rev()),
-            Side::Sell => Either::Right(price_levels.iter()),
+        let bounds = if min_price <= max_price {
+            (Bound::Included(min_price), Bound::Included(max_price))
+        } else {
+            (Bound::Excluded(min_price), Bound::Excluded(min_price))
         };
-
         Self {
-            iter,
-            side,
-            min_price,
-            max_price,
-            finished: false,
+            iter: side_range(price_levels, side, bounds),
         }
     }
 }
@@ -190,52 +223,123 @@ impl<'a> Iterator for LevelsInRange<'a> {
     type Item = LevelInfo;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.finished {
-            return None;
+        self.iter.next().map(|entry| LevelInfo {
+            price: *entry.key(),
+            quantity: entry.value().total_quantity().unwrap_or(0),
+            cumulative_depth: 0, // Not tracked in range iterator
+            order_count: entry.value().order_count(),
+        })
+    }
+}
+
+/// One row of a two-sided ladder: the bid and ask levels at the same
+/// distance from the touch.
+///
+/// Either side is `None` once that side of the book has run out of levels.
+#[derive(Debug, Clone)]
+pub struct LadderRow {
+    /// Bid level at this depth, with cumulative bid depth
+    pub bid: Option<LevelInfo>,
+
+    /// Ask level at this depth, with cumulative ask depth
+    pub ask: Option<LevelInfo>,
+}
+
+impl std::fmt::Display for LadderRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.bid {
+            Some(bid) => write!(f, "{} @ {}", bid.quantity, bid.price)?,
+            None => write!(f, "-")?,
+        }
+        write!(f, " | ")?;
+        match &self.ask {
+            Some(ask) => write!(f, "{} @ {}", ask.price, ask.quantity),
+            None => write!(f, "-"),
         }
+    }
+}
 
-        for entry in self.iter.by_ref() {
-            let price = *entry.key();
+/// Iterator over aligned (bid, ask) level pairs from the touch outward
+///
+/// Walks both sides in price-priority order in a single pass, yielding one
+/// [`LadderRow`] per depth until `depth` rows have been produced or both
+/// sides are exhausted. Each side keeps its own cumulative depth.
+pub struct Ladder<'a> {
+    bids: LevelsWithCumulativeDepth<'a>,
+    asks: LevelsWithCumulativeDepth<'a>,
+    remaining: usize,
+}
 
-            // Ordered early exit: the underlying SkipMap iteration is sorted, so
-            // once a price passes the FAR edge of the band no later entry can be
-            // in range. Buy iterates descending (high→low): a price below the
-            // band ends it. Sell iterates ascending (low→high): a price above the
-            // band ends it.
-            let past_far_edge = match self.side {
-                Side::Buy => price < self.min_price,
-                Side::Sell => price > self.max_price,
-            };
-            if past_far_edge {
-                self.finished = true;
-                return None;
-            }
+impl<'a> Ladder<'a> {
+    /// Creates a new ladder iterator
+    ///
+    /// # Arguments
+    /// - `bids`: Reference to the map of bid price levels
+    /// - `asks`: Reference to the map of ask price levels
+    /// - `depth`: Maximum number of rows to yield
+    pub fn new(bids: &'a PriceLevelMap, asks: &'a PriceLevelMap, depth: usize) -> Self {
+        Self {
+            bids: LevelsWithCumulativeDepth::new(bids, Side::Buy),
+            asks: LevelsWithCumulativeDepth::new(asks, Side::Sell),
+            remaining: depth,
+        }
+    }
+}
 
-            // Check if price is within range.
-            if price >= self.min_price && price <= self.max_price {
-                let quantity = entry.value().total_quantity().unwrap_or(0);
+impl<'a> Iterator for Ladder<'a> {
+    type Item = LadderRow;
 
-                return Some(LevelInfo {
-                    price,
-                    quantity,
-                    cumulative_depth: 0, // Not tracked in range iterator
-                });
-            }
-            // Otherwise we are still on the NEAR side of the band (Buy: above
-            // max; Sell: below min) — keep scanning toward it.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
         }
 
-        self.finished = true;
-        None
+        let bid = self.bids.next();
+        let ask = self.asks.next();
+        if bid.is_none() && ask.is_none() {
+            self.remaining = 0;
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(LadderRow { bid, ask })
     }
 }
 
+/// Resume position for [`crate::OrderBook::orders_page`].
+///
+/// Identifies the last order returned by the previous page: its price level,
+/// its timestamp and its id. Cursors are plain values and can be persisted
+/// between calls (or processes) by reconciliation tooling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderCursor {
+    /// Price level of the last returned order (in price units)
+    pub price: u128,
+
+    /// Timestamp of the last returned order (milliseconds)
+    pub timestamp: u64,
+
+    /// Id of the last returned order
+    pub order_id: Id,
+}
+
+/// One page of resting orders returned by [`crate::OrderBook::orders_page`].
+#[derive(Debug, Clone)]
+pub struct OrdersPage<T> {
+    /// Orders in price-time priority, at most `limit` of them
+    pub orders: Vec<Arc<OrderType<T>>>,
+
+    /// Cursor to pass to the next call, or `None` when the side is exhausted
+    pub next_cursor: Option<OrderCursor>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pricelevel::PriceLevel;
 
-    fn make_map(prices: impl IntoIterator<Item = u128>) -> SkipMap<u128, Arc<PriceLevel>> {
-        let map = SkipMap::new();
+    fn make_map(prices: impl IntoIterator<Item = u128>) -> PriceLevelMap {
+        let map = PriceLevelMap::new();
         for p in prices {
             map.insert(p, Arc::new(PriceLevel::new(p)));
         }
@@ -243,37 +347,46 @@ mod tests {
     }
 
     #[test]
-    fn test_levels_in_range_terminates_at_far_edge_sell() {
+    fn test_levels_in_range_visits_only_the_band_sell() {
         // Wide ascending book 1..=1000, narrow band [10, 12] near the low end.
         let map = make_map(1..=1000u128);
         let mut it = LevelsInRange::new(&map, Side::Sell, 10, 12);
         let prices: Vec<u128> = (&mut it).map(|l| l.price).collect();
         assert_eq!(prices, vec![10, 11, 12], "only in-band levels are yielded");
         assert!(
-            it.finished,
-            "iterator marks itself finished at the far edge"
-        );
-        // Early-exit proof: the underlying iterator was NOT drained to the end —
-        // entries past the far edge (13..=1000) remain. A non-short-circuiting
-        // scan would have consumed all of them.
-        assert!(
-            it.iter.next().is_some(),
-            "iteration must stop at the far edge, leaving later entries unconsumed"
+            it.iter.next().is_none(),
+            "the underlying range ends at the far edge of the band"
         );
     }
 
     #[test]
-    fn test_levels_in_range_terminates_at_far_edge_buy() {
+    fn test_levels_in_range_visits_only_the_band_buy() {
         // Buy iterates descending; narrow band [988, 990] near the high end.
         let map = make_map(1..=1000u128);
         let mut it = LevelsInRange::new(&map, Side::Buy, 988, 990);
         let prices: Vec<u128> = (&mut it).map(|l| l.price).collect();
         assert_eq!(prices, vec![990, 989, 988], "descending in-band yield");
-        assert!(it.finished);
-        assert!(
-            it.iter.next().is_some(),
-            "iteration must stop below the band, leaving lower entries unconsumed"
-        );
+        assert!(it.iter.next().is_none());
+    }
+
+    #[test]
+    fn test_levels_in_range_inverted_band_is_empty() {
+        let map = make_map(1..=10u128);
+        assert_eq!(LevelsInRange::new(&map, Side::Buy, 8, 2).count(), 0);
+        assert_eq!(LevelsInRange::new(&map, Side::Sell, 8, 2).count(), 0);
+    }
+
+    #[test]
+    fn test_levels_from_price_walks_away_from_touch() {
+        let map = make_map([10, 20, 30, 40]);
+        let bids: Vec<u128> = LevelsWithCumulativeDepth::from_price(&map, Side::Buy, 25)
+            .map(|l| l.price)
+            .collect();
+        assert_eq!(bids, vec![20, 10]);
+        let asks: Vec<u128> = LevelsWithCumulativeDepth::from_price(&map, Side::Sell, 20)
+            .map(|l| l.price)
+            .collect();
+        assert_eq!(asks, vec![20, 30, 40]);
     }
 
     #[test]
@@ -0,0 +1,461 @@
+//! Runtime tail-latency recording for the book executor.
+//!
+//! A [`LatencyRecorder`] keeps one HDR histogram per symbol of the
+//! end-to-end latency of every command a [`BookExecutor`] runs: from the
+//! moment [`BookExecutor::submit`] enqueues it to the moment its result is
+//! ready, so queueing behind earlier commands is included. Benches measure
+//! the engine in isolation; this measures what a client of a running
+//! system sees, cheaply enough to leave on in production.
+//!
+//! Recording is opt-in. Install a recorder with
+//! [`ExecutorConfig::with_latency_recorder`] or, for a manager converted
+//! with `into_executor`, with `enable_latency_recording` on
+//! [`BookManagerStd`](crate::BookManagerStd) /
+//! [`BookManagerTokio`](crate::BookManagerTokio). Without one, the
+//! executor takes no timestamps.
+//!
+//! Each worker records into its own histogram, so the hot path takes an
+//! uncontended lock. [`LatencyRecorder::percentile_report`] reads every
+//! symbol's current window. With a [`LatencyConfig::with_reset_interval`]
+//! the window restarts once the interval has elapsed; the closed window's
+//! summary stays available as [`SymbolLatency::previous`], so p99.9 tracks
+//! recent behaviour rather than the whole uptime. Windows are timed by a
+//! [`Clock`]; [`LatencyRecorder::with_clock`] installs one other than the
+//! default [`MonotonicClock`].
+//!
+//! Enabled by the `latency` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use orderbook_rs::OrderBook;
+//! use orderbook_rs::orderbook::executor::{BookExecutor, ExecutorConfig};
+//! use orderbook_rs::orderbook::latency::{LatencyConfig, LatencyRecorder};
+//! use orderbook_rs::orderbook::sequencer::SequencerCommand;
+//! use pricelevel::{Id, Side};
+//! use std::time::Duration;
+//!
+//! let recorder = LatencyRecorder::new(
+//!     LatencyConfig::new().with_reset_interval(Duration::from_secs(60)),
+//! )?;
+//! let mut executor =
+//!     BookExecutor::new(ExecutorConfig::new().with_latency_recorder(recorder.clone()));
+//! executor.spawn_book(OrderBook::<()>::new("BTC/USD"))?;
+//!
+//! let command = SequencerCommand::MarketOrder {
+//!     id: Id::new_uuid(),
+//!     quantity: 1,
+//!     side: Side::Buy,
+//! };
+//! executor.submit("BTC/USD", command)?.recv()?;
+//!
+//! let report = recorder.percentile_report();
+//! assert_eq!(report["BTC/USD"].current.count, 1);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`BookExecutor`]: crate::orderbook::executor::BookExecutor
+//! [`BookExecutor::submit`]: crate::orderbook::executor::BookExecutor::submit
+//! [`ExecutorConfig::with_latency_recorder`]: crate::orderbook::executor::ExecutorConfig::with_latency_recorder
+
+use crate::orderbook::clock::{Clock, MonotonicClock};
+use dashmap::DashMap;
+use hdrhistogram::{CreationError, Histogram};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Default number of significant decimal digits kept by each histogram.
+pub const DEFAULT_SIGNIFICANT_FIGURES: u8 = 3;
+
+/// Default largest latency tracked exactly; longer samples are clamped.
+pub const DEFAULT_MAX_LATENCY: Duration = Duration::from_secs(60);
+
+/// Shape of the histograms a [`LatencyRecorder`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyConfig {
+    significant_figures: u8,
+    max_latency: Duration,
+    reset_interval: Option<Duration>,
+}
+
+impl LatencyConfig {
+    /// Three significant figures, samples up to one minute, no periodic
+    /// reset.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            significant_figures: DEFAULT_SIGNIFICANT_FIGURES,
+            max_latency: DEFAULT_MAX_LATENCY,
+            reset_interval: None,
+        }
+    }
+
+    /// Keep `figures` significant decimal digits (0 to 5). More digits
+    /// cost more memory per histogram.
+    #[must_use]
+    pub fn with_significant_figures(mut self, figures: u8) -> Self {
+        self.significant_figures = figures;
+        self
+    }
+
+    /// Track latencies up to `max` exactly; longer samples are recorded
+    /// as `max`.
+    #[must_use]
+    pub fn with_max_latency(mut self, max: Duration) -> Self {
+        self.max_latency = max;
+        self
+    }
+
+    /// Start a new window every `interval`, keeping the summary of the one
+    /// that closed.
+    #[must_use]
+    pub fn with_reset_interval(mut self, interval: Duration) -> Self {
+        self.reset_interval = Some(interval);
+        self
+    }
+
+    /// Significant decimal digits per histogram.
+    #[must_use]
+    pub fn significant_figures(&self) -> u8 {
+        self.significant_figures
+    }
+
+    /// Largest latency tracked exactly.
+    #[must_use]
+    pub fn max_latency(&self) -> Duration {
+        self.max_latency
+    }
+
+    /// Window length, if windows are reset periodically.
+    #[must_use]
+    pub fn reset_interval(&self) -> Option<Duration> {
+        self.reset_interval
+    }
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Percentiles of one window of samples. All latencies in nanoseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    /// Samples recorded
+    pub count: u64,
+    /// Smallest sample
+    pub min_ns: u64,
+    /// Mean of the samples
+    pub mean_ns: f64,
+    /// Median
+    pub p50_ns: u64,
+    /// 90th percentile
+    pub p90_ns: u64,
+    /// 99th percentile
+    pub p99_ns: u64,
+    /// 99.9th percentile
+    pub p999_ns: u64,
+    /// 99.99th percentile
+    pub p9999_ns: u64,
+    /// Largest sample
+    pub max_ns: u64,
+    /// How long the window has been (or was) open
+    pub window_ns: u64,
+}
+
+impl LatencySummary {
+    fn of(histogram: &Histogram<u64>, window: Duration) -> Self {
+        if histogram.is_empty() {
+            return Self {
+                window_ns: saturating_nanos(window),
+                ..Self::default()
+            };
+        }
+        Self {
+            count: histogram.len(),
+            min_ns: histogram.min(),
+            mean_ns: histogram.mean(),
+            p50_ns: histogram.value_at_quantile(0.50),
+            p90_ns: histogram.value_at_quantile(0.90),
+            p99_ns: histogram.value_at_quantile(0.99),
+            p999_ns: histogram.value_at_quantile(0.999),
+            p9999_ns: histogram.value_at_quantile(0.9999),
+            max_ns: histogram.max(),
+            window_ns: saturating_nanos(window),
+        }
+    }
+}
+
+/// Latency of one symbol, as returned by
+/// [`LatencyRecorder::percentile_report`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolLatency {
+    /// The window being recorded
+    pub current: LatencySummary,
+    /// The last window closed by the periodic reset, if any
+    pub previous: Option<LatencySummary>,
+}
+
+/// One symbol's histogram and the window it covers.
+#[derive(Debug)]
+struct Window {
+    histogram: Histogram<u64>,
+    /// Clock reading, in nanoseconds, when the window opened
+    opened_ns: u64,
+    previous: Option<LatencySummary>,
+}
+
+impl Window {
+    /// Close the window if `interval` has elapsed by `now_ns`.
+    fn roll(&mut self, interval: Option<Duration>, now_ns: u64) {
+        let Some(interval) = interval else {
+            return;
+        };
+        let open_for = self.open_for(now_ns);
+        if open_for >= interval {
+            self.previous = Some(LatencySummary::of(&self.histogram, open_for));
+            self.histogram.reset();
+            self.opened_ns = now_ns;
+        }
+    }
+
+    fn report(&self, now_ns: u64) -> SymbolLatency {
+        SymbolLatency {
+            current: LatencySummary::of(&self.histogram, self.open_for(now_ns)),
+            previous: self.previous,
+        }
+    }
+
+    fn open_for(&self, now_ns: u64) -> Duration {
+        Duration::from_nanos(now_ns.saturating_sub(self.opened_ns))
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: LatencyConfig,
+    /// Empty histogram with the configured bounds, cloned for each symbol
+    template: Histogram<u64>,
+    /// Times the windows
+    clock: Arc<dyn Clock>,
+    windows: DashMap<String, Arc<Mutex<Window>>>,
+}
+
+/// Per-symbol HDR histograms of command latency. Cloning shares the
+/// histograms, so an operator can keep a clone to read reports from while
+/// the executor records into another.
+#[derive(Debug, Clone)]
+pub struct LatencyRecorder {
+    inner: Arc<Inner>,
+}
+
+impl LatencyRecorder {
+    /// Create a recorder with no symbols.
+    ///
+    /// # Errors
+    ///
+    /// Returns the histogram [`CreationError`] if `config` asks for more
+    /// than five significant figures or a maximum latency below 2 ns.
+    pub fn new(config: LatencyConfig) -> Result<Self, CreationError> {
+        Self::with_clock(config, Arc::new(MonotonicClock))
+    }
+
+    /// Create a recorder whose windows are timed by `clock` instead of the
+    /// wall clock, so a test or a replay decides when a window closes.
+    /// Sample latencies are measured by the caller and are not affected.
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::new`].
+    pub fn with_clock(config: LatencyConfig, clock: Arc<dyn Clock>) -> Result<Self, CreationError> {
+        let template = Histogram::new_with_bounds(
+            1,
+            saturating_nanos(config.max_latency),
+            config.significant_figures,
+        )?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                config,
+                template,
+                clock,
+                windows: DashMap::new(),
+            }),
+        })
+    }
+
+    /// The recorder's configuration.
+    #[must_use]
+    pub fn config(&self) -> &LatencyConfig {
+        &self.inner.config
+    }
+
+    /// Record one `latency` sample for `symbol`. The executor records
+    /// through a per-worker [`SymbolLatencyRecorder`] instead, which skips
+    /// the symbol lookup.
+    pub fn record(&self, symbol: &str, latency: Duration) {
+        self.for_symbol(symbol).record(latency);
+    }
+
+    /// Handle recording into `symbol`'s histogram, creating it if needed.
+    #[must_use]
+    pub fn for_symbol(&self, symbol: &str) -> SymbolLatencyRecorder {
+        let window = self
+            .inner
+            .windows
+            .entry(symbol.to_string())
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(Window {
+                    histogram: self.inner.template.clone(),
+                    opened_ns: self.inner.clock.now_nanos(),
+                    previous: None,
+                }))
+            })
+            .clone();
+        SymbolLatencyRecorder {
+            window,
+            reset_interval: self.inner.config.reset_interval,
+            clock: Arc::clone(&self.inner.clock),
+        }
+    }
+
+    /// Percentiles of every symbol's current window, and of the last
+    /// closed one when windows are reset periodically.
+    #[must_use]
+    pub fn percentile_report(&self) -> BTreeMap<String, SymbolLatency> {
+        let now_ns = self.inner.clock.now_nanos();
+        self.inner
+            .windows
+            .iter()
+            .map(|entry| {
+                let mut window = lock(entry.value());
+                window.roll(self.inner.config.reset_interval, now_ns);
+                (entry.key().clone(), window.report(now_ns))
+            })
+            .collect()
+    }
+
+    /// Clear every histogram and restart every window, forgetting the
+    /// previous windows too.
+    pub fn reset(&self) {
+        let now_ns = self.inner.clock.now_nanos();
+        for entry in self.inner.windows.iter() {
+            let mut window = lock(entry.value());
+            window.histogram.reset();
+            window.opened_ns = now_ns;
+            window.previous = None;
+        }
+    }
+}
+
+/// Records into one symbol's histogram. Obtained from
+/// [`LatencyRecorder::for_symbol`].
+#[derive(Debug, Clone)]
+pub struct SymbolLatencyRecorder {
+    window: Arc<Mutex<Window>>,
+    reset_interval: Option<Duration>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SymbolLatencyRecorder {
+    /// Record one sample. Zero is recorded as 1 ns and anything above the
+    /// configured maximum as the maximum.
+    pub fn record(&self, latency: Duration) {
+        let nanos = saturating_nanos(latency).max(1);
+        let mut window = lock(&self.window);
+        if self.reset_interval.is_some() {
+            window.roll(self.reset_interval, self.clock.now_nanos());
+        }
+        window.histogram.saturating_record(nanos);
+    }
+}
+
+/// Lock a window, recovering from poisoning: a histogram is valid after
+/// any partial update.
+fn lock(window: &Mutex<Window>) -> MutexGuard<'_, Window> {
+    window
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn saturating_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricelevel::TimestampMs;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn test_summary_of_recorded_samples() {
+        let recorder = LatencyRecorder::new(LatencyConfig::new()).expect("recorder");
+        for micros in 1..=1_000 {
+            recorder.record("BTC/USD", Duration::from_micros(micros));
+        }
+
+        let report = recorder.percentile_report();
+        let latency = report["BTC/USD"];
+        assert_eq!(latency.current.count, 1_000);
+        assert_eq!(latency.previous, None);
+        assert!(latency.current.min_ns >= 999 && latency.current.min_ns <= 1_000);
+        assert!(latency.current.max_ns >= 999_000);
+        let p50 = latency.current.p50_ns as f64;
+        assert!((p50 - 500_000.0).abs() / 500_000.0 < 0.01, "{p50}");
+        assert!(latency.current.p999_ns >= latency.current.p99_ns);
+    }
+
+    #[derive(Debug)]
+    struct ManualClock(AtomicU64);
+
+    impl Clock for ManualClock {
+        fn now_millis(&self) -> TimestampMs {
+            TimestampMs::new(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    #[test]
+    fn test_periodic_reset_keeps_the_closed_window() {
+        let clock = Arc::new(ManualClock(AtomicU64::new(0)));
+        let recorder = LatencyRecorder::with_clock(
+            LatencyConfig::new().with_reset_interval(Duration::from_millis(1)),
+            clock.clone(),
+        )
+        .expect("recorder");
+        let handle = recorder.for_symbol("ETH/USD");
+        handle.record(Duration::from_micros(5));
+        handle.record(Duration::from_micros(7));
+        assert_eq!(recorder.percentile_report()["ETH/USD"].current.count, 2);
+        clock.0.store(5, Ordering::Relaxed);
+
+        let latency = recorder.percentile_report()["ETH/USD"];
+        assert_eq!(latency.current.count, 0);
+        let previous = latency.previous.expect("closed window");
+        assert_eq!(previous.count, 2);
+        assert_eq!(previous.window_ns, 5_000_000);
+    }
+
+    #[test]
+    fn test_reset_and_clamping() {
+        let recorder =
+            LatencyRecorder::new(LatencyConfig::new().with_max_latency(Duration::from_millis(1)))
+                .expect("recorder");
+        recorder.record("BTC/USD", Duration::ZERO);
+        recorder.record("BTC/USD", Duration::from_secs(10));
+        let latency = recorder.percentile_report()["BTC/USD"];
+        assert_eq!(latency.current.count, 2);
+        assert_eq!(latency.current.min_ns, 1);
+        assert!(latency.current.max_ns <= 1_001_000);
+
+        recorder.reset();
+        assert_eq!(recorder.percentile_report()["BTC/USD"].current.count, 0);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        assert!(LatencyRecorder::new(LatencyConfig::new().with_significant_figures(6)).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Price-striped gate between level admission and level removal.
+
+use crossbeam::utils::CachePadded;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Number of lock stripes. A power of two, so a stripe is picked with a
+/// shift of the mixed price.
+const STRIPES: usize = 64;
+
+/// Guards price-level lifetime against concurrent admission.
+///
+/// Resting an order holds the **read** side of its price's stripe from
+/// `get_or_insert_with` until the order is in the level; removing an
+/// emptied level takes the **write** side of the same stripe and
+/// re-checks emptiness, so a level is never unlinked after another thread
+/// has added to it.
+///
+/// The lock is striped by price rather than shared by the whole book: a
+/// removal only has to exclude admissions at its own price, so it stalls
+/// at most the prices hashing to its stripe instead of every add on the
+/// book. Stripes are cache-padded so readers of different stripes do not
+/// share a line.
+pub(super) struct LevelGate {
+    stripes: Box<[CachePadded<RwLock<()>>]>,
+}
+
+impl LevelGate {
+    /// A gate with every stripe open.
+    pub(super) fn new() -> Self {
+        Self {
+            stripes: (0..STRIPES)
+                .map(|_| CachePadded::new(RwLock::new(())))
+                .collect(),
+        }
+    }
+
+    /// Shared side of the stripe of `price`, held while adding to the
+    /// level at `price`.
+    pub(super) fn read(&self, price: u128) -> RwLockReadGuard<'_, ()> {
+        // The gate guards no data, so a poisoned stripe is recovered.
+        self.stripe(price)
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Exclusive side of the stripe of `price`, held while unlinking the
+    /// level at `price`.
+    pub(super) fn write(&self, price: u128) -> RwLockWriteGuard<'_, ()> {
+        self.stripe(price)
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Index of the stripe guarding `price`. Prices are mixed first so
+    /// tick-aligned prices spread over every stripe.
+    pub(super) fn stripe_of(price: u128) -> usize {
+        let folded = (price as u64) ^ ((price >> 64) as u64);
+        (folded.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (64 - STRIPES.trailing_zeros())) as usize
+    }
+
+    fn stripe(&self, price: u128) -> &RwLock<()> {
+        &self.stripes[Self::stripe_of(price)]
+    }
+}
@@ -0,0 +1,285 @@
+//! Price-range sharded price level map.
+//!
+//! Each side of the book keeps its price levels in a [`PriceLevelMap`]. By
+//! default the map is a single `SkipMap`. For very deep books (far option
+//! strikes, long-tail levels) it can be split into price-range shards, each
+//! an independent `SkipMap`. Every shard holds fewer levels, so its towers
+//! are shorter and concurrent writers at distant prices link nodes in
+//! different lists.
+//!
+//! Shard `i` covers prices `[i * shard_width, (i + 1) * shard_width)`, and
+//! the last shard also holds every price above its range. Ordered walks
+//! visit the shards in price order, so iteration order is the same as with
+//! a single map. Point operations touch one shard; `front`, `back`, `len`
+//! and `is_empty` visit the shards in turn, which is cheap for the small
+//! shard counts sharding is meant for.
+
+use crossbeam_skiplist::SkipMap;
+use crossbeam_skiplist::map::{Entry, Range};
+use pricelevel::PriceLevel;
+use std::iter::{Flatten, FusedIterator};
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+/// Price bounds of an ordered walk.
+type PriceBounds = (Bound<u128>, Bound<u128>);
+
+/// Price levels of one side of the book, optionally sharded by price range.
+pub struct PriceLevelMap {
+    shards: Box<[SkipMap<u128, Arc<PriceLevel>>]>,
+    shard_width: u128,
+}
+
+impl PriceLevelMap {
+    /// An unsharded map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::sharded(u128::MAX, 1)
+    }
+
+    /// A map split into `shard_count` shards of `shard_width` price units.
+    ///
+    /// Both values are raised to at least 1. Prices at or above
+    /// `shard_width * (shard_count - 1)` share the last shard.
+    #[must_use]
+    pub fn sharded(shard_width: u128, shard_count: usize) -> Self {
+        Self {
+            shards: (0..shard_count.max(1)).map(|_| SkipMap::new()).collect(),
+            shard_width: shard_width.max(1),
+        }
+    }
+
+    /// Number of shards.
+    #[must_use]
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Width of each shard in price units.
+    #[must_use]
+    pub fn shard_width(&self) -> u128 {
+        self.shard_width
+    }
+
+    /// Number of levels in each shard, lowest prices first.
+    #[must_use]
+    pub fn shard_lens(&self) -> Vec<usize> {
+        self.shards.iter().map(SkipMap::len).collect()
+    }
+
+    #[inline]
+    fn shard_index(&self, price: u128) -> usize {
+        let last = self.shards.len() - 1;
+        usize::try_from(price / self.shard_width).map_or(last, |index| index.min(last))
+    }
+
+    #[inline]
+    fn shard(&self, price: u128) -> &SkipMap<u128, Arc<PriceLevel>> {
+        &self.shards[self.shard_index(price)]
+    }
+
+    /// The level at `price`.
+    #[inline]
+    pub fn get(&self, price: &u128) -> Option<Entry<'_, u128, Arc<PriceLevel>>> {
+        self.shard(*price).get(price)
+    }
+
+    /// `true` if a level exists at `price`.
+    #[inline]
+    pub fn contains_key(&self, price: &u128) -> bool {
+        self.shard(*price).contains_key(price)
+    }
+
+    /// Insert `level` at `price`, replacing any existing level.
+    pub fn insert(&self, price: u128, level: Arc<PriceLevel>) -> Entry<'_, u128, Arc<PriceLevel>> {
+        self.shard(price).insert(price, level)
+    }
+
+    /// The level at `price`, inserting the one built by `make` if absent.
+    #[inline]
+    pub fn get_or_insert_with<F>(&self, price: u128, make: F) -> Entry<'_, u128, Arc<PriceLevel>>
+    where
+        F: FnOnce() -> Arc<PriceLevel>,
+    {
+        self.shard(price).get_or_insert_with(price, make)
+    }
+
+    /// Remove the level at `price`.
+    pub fn remove(&self, price: &u128) -> Option<Entry<'_, u128, Arc<PriceLevel>>> {
+        self.shard(*price).remove(price)
+    }
+
+    /// The lowest-priced level.
+    #[inline]
+    pub fn front(&self) -> Option<Entry<'_, u128, Arc<PriceLevel>>> {
+        self.shards.iter().find_map(SkipMap::front)
+    }
+
+    /// The highest-priced level.
+    #[inline]
+    pub fn back(&self) -> Option<Entry<'_, u128, Arc<PriceLevel>>> {
+        self.shards.iter().rev().find_map(SkipMap::back)
+    }
+
+    /// Remove and return the lowest-priced level.
+    pub fn pop_front(&self) -> Option<Entry<'_, u128, Arc<PriceLevel>>> {
+        self.shards.iter().find_map(SkipMap::pop_front)
+    }
+
+    /// Number of levels.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(SkipMap::len).sum()
+    }
+
+    /// `true` if there are no levels.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(SkipMap::is_empty)
+    }
+
+    /// Remove every level.
+    pub fn clear(&self) {
+        for shard in &*self.shards {
+            shard.clear();
+        }
+    }
+
+    /// Every level in ascending price order.
+    pub fn iter(&self) -> LevelRange<'_> {
+        self.range(..)
+    }
+
+    /// The levels with prices in `range`, in ascending price order. Only the
+    /// shards overlapping `range` are visited.
+    pub fn range<R: RangeBounds<u128>>(&self, range: R) -> LevelRange<'_> {
+        let bounds: PriceBounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let first = match bounds.0 {
+            Bound::Included(price) | Bound::Excluded(price) => self.shard_index(price),
+            Bound::Unbounded => 0,
+        };
+        let last = match bounds.1 {
+            Bound::Included(price) | Bound::Excluded(price) => self.shard_index(price),
+            Bound::Unbounded => self.shards.len() - 1,
+        };
+        let shards = self.shards.get(first..=last).unwrap_or_default();
+        LevelRange {
+            inner: ShardRanges {
+                shards: shards.iter(),
+                bounds,
+            }
+            .flatten(),
+        }
+    }
+}
+
+impl Default for PriceLevelMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for PriceLevelMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriceLevelMap")
+            .field("shard_count", &self.shards.len())
+            .field("shard_width", &self.shard_width)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The per-shard ranges of one ordered walk.
+struct ShardRanges<'a> {
+    shards: std::slice::Iter<'a, SkipMap<u128, Arc<PriceLevel>>>,
+    bounds: PriceBounds,
+}
+
+impl<'a> Iterator for ShardRanges<'a> {
+    type Item = Range<'a, u128, PriceBounds, u128, Arc<PriceLevel>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.shards.next().map(|shard| shard.range(self.bounds))
+    }
+}
+
+impl DoubleEndedIterator for ShardRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.shards
+            .next_back()
+            .map(|shard| shard.range(self.bounds))
+    }
+}
+
+/// Ordered walk over the levels of a [`PriceLevelMap`], ascending from the
+/// front and descending from the back.
+pub struct LevelRange<'a> {
+    inner: Flatten<ShardRanges<'a>>,
+}
+
+impl<'a> Iterator for LevelRange<'a> {
+    type Item = Entry<'a, u128, Arc<PriceLevel>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl DoubleEndedIterator for LevelRange<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl FusedIterator for LevelRange<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: u128) -> Arc<PriceLevel> {
+        Arc::new(PriceLevel::new(price))
+    }
+
+    fn prices(walk: LevelRange<'_>) -> Vec<u128> {
+        walk.map(|entry| *entry.key()).collect()
+    }
+
+    #[test]
+    fn sharded_walks_match_a_single_map() {
+        let single = PriceLevelMap::new();
+        let sharded = PriceLevelMap::sharded(10, 4);
+        for price in [3, 9, 10, 25, 31, 39, 40, 1_000] {
+            single.insert(price, level(price));
+            sharded.insert(price, level(price));
+        }
+        assert_eq!(sharded.shard_lens(), vec![2, 1, 1, 4]);
+        assert_eq!(prices(sharded.iter()), prices(single.iter()));
+        assert_eq!(
+            prices(sharded.iter()).into_iter().rev().collect::<Vec<_>>(),
+            sharded
+                .iter()
+                .rev()
+                .map(|entry| *entry.key())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(prices(sharded.range(9..=31)), vec![9, 10, 25, 31]);
+        assert_eq!(
+            sharded
+                .range(..40)
+                .rev()
+                .map(|e| *e.key())
+                .collect::<Vec<_>>(),
+            vec![39, 31, 25, 10, 9, 3]
+        );
+        assert_eq!(sharded.front().map(|e| *e.key()), Some(3));
+        assert_eq!(sharded.back().map(|e| *e.key()), Some(1_000));
+        assert_eq!(sharded.len(), 8);
+        assert!(sharded.remove(&25).is_some());
+        assert!(!sharded.contains_key(&25));
+        assert_eq!(sharded.pop_front().map(|e| *e.key()), Some(3));
+        sharded.clear();
+        assert!(sharded.is_empty());
+        assert_eq!(sharded.shard_count(), 4);
+    }
+}
@@ -0,0 +1,216 @@
+//! Reuse of emptied price levels.
+//!
+//! In fast markets the levels at and near the touch empty and refill many
+//! times per second. Each time, the book would drop the `PriceLevel` (its
+//! order queue and statistics) and allocate a new one for the same price.
+//! [`LevelPool`] parks emptied levels instead and hands them back when their
+//! price is re-created.
+//!
+//! A `PriceLevel`'s price is fixed at construction, so the pool is keyed by
+//! price: a parked level can only serve the price it was built for. The pool
+//! holds at most [`LevelPool::max_size`] levels; a level released into a
+//! full pool is dropped as before. Statistics are reset when a level is
+//! parked, so a reused level is indistinguishable from a new one.
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use pricelevel::PriceLevel;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default number of empty levels a book keeps for reuse.
+pub const DEFAULT_LEVEL_POOL_SIZE: usize = 64;
+
+/// Counters of a [`LevelPool`], as returned by
+/// [`OrderBook::level_pool_stats`](crate::OrderBook::level_pool_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelPoolStats {
+    /// Empty levels currently parked
+    pub pooled: usize,
+    /// Maximum number of parked levels
+    pub max_size: usize,
+    /// Level creations served from the pool
+    pub hits: u64,
+    /// Level creations that had to allocate
+    pub misses: u64,
+    /// Emptied levels parked for reuse
+    pub recycled: u64,
+    /// Emptied levels dropped because the pool was full or already held
+    /// a level at that price
+    pub discarded: u64,
+}
+
+/// Per-book free list of empty price levels, keyed by price.
+#[derive(Debug)]
+pub struct LevelPool {
+    levels: DashMap<u128, Arc<PriceLevel>>,
+    max_size: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    recycled: AtomicU64,
+    discarded: AtomicU64,
+}
+
+impl Default for LevelPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_LEVEL_POOL_SIZE)
+    }
+}
+
+impl LevelPool {
+    /// Create an empty pool holding at most `max_size` levels. Zero
+    /// disables pooling.
+    #[must_use]
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            levels: DashMap::new(),
+            max_size,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            recycled: AtomicU64::new(0),
+            discarded: AtomicU64::new(0),
+        }
+    }
+
+    /// Create an empty pool holding at most `max_size` levels, with room
+    /// for `capacity` of them allocated up front.
+    #[must_use]
+    pub fn with_capacity(max_size: usize, capacity: usize) -> Self {
+        Self {
+            levels: DashMap::with_capacity(capacity.min(max_size)),
+            ..Self::new(max_size)
+        }
+    }
+
+    /// A level for `price`: the parked one if there is one, otherwise a new
+    /// allocation.
+    pub fn acquire(&self, price: u128) -> Arc<PriceLevel> {
+        if let Some((_, level)) = self.levels.remove(&price) {
+            // A parked level only regains orders if an add raced with its
+            // removal from the book; such a level is not reused.
+            if is_empty(&level) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return level;
+            }
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        Arc::new(PriceLevel::new(price))
+    }
+
+    /// Park `level`, just removed from the book, for reuse. Dropped instead
+    /// if it is not empty, the pool is full, or a level at the same price is
+    /// already parked.
+    pub fn release(&self, level: Arc<PriceLevel>) {
+        if !is_empty(&level) || self.levels.len() >= self.max_size {
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        match self.levels.entry(level.price()) {
+            Entry::Occupied(_) => {
+                self.discarded.fetch_add(1, Ordering::Relaxed);
+            }
+            Entry::Vacant(entry) => {
+                level.stats().reset();
+                entry.insert(level);
+                self.recycled.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Maximum number of parked levels.
+    #[must_use]
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Change the maximum number of parked levels, dropping parked levels
+    /// beyond the new limit.
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        let excess = self.levels.len().saturating_sub(max_size);
+        if excess > 0 {
+            let prices: Vec<u128> = self
+                .levels
+                .iter()
+                .take(excess)
+                .map(|entry| *entry.key())
+                .collect();
+            for price in prices {
+                self.levels.remove(&price);
+            }
+        }
+    }
+
+    /// Drop every parked level. Counters are kept.
+    pub fn clear(&self) {
+        self.levels.clear();
+    }
+
+    /// Current counters.
+    #[must_use]
+    pub fn stats(&self) -> LevelPoolStats {
+        LevelPoolStats {
+            pooled: self.levels.len(),
+            max_size: self.max_size,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            discarded: self.discarded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn is_empty(level: &PriceLevel) -> bool {
+    level.order_count() == 0 && level.visible_quantity() == 0 && level.hidden_quantity() == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn order(price: u128) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::new_uuid(),
+            price: Price::new(price),
+            quantity: Quantity::new(10),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_released_level_is_reused_for_its_price_only() {
+        let pool = LevelPool::new(4);
+        let level = pool.acquire(100);
+        pool.release(Arc::clone(&level));
+
+        assert!(!Arc::ptr_eq(&pool.acquire(101), &level));
+        assert!(Arc::ptr_eq(&pool.acquire(100), &level));
+        let stats = pool.stats();
+        assert_eq!((stats.hits, stats.misses, stats.recycled), (1, 2, 1));
+        assert_eq!(stats.pooled, 0);
+    }
+
+    #[test]
+    fn test_non_empty_and_overflowing_levels_are_discarded() {
+        let mut pool = LevelPool::new(1);
+        let busy = pool.acquire(100);
+        busy.add_order(order(100)).expect("add");
+        pool.release(busy);
+        pool.release(pool.acquire(101));
+        pool.release(pool.acquire(102));
+        assert_eq!(pool.stats().pooled, 1);
+        assert_eq!(pool.stats().discarded, 2);
+
+        pool.set_max_size(0);
+        assert_eq!(pool.stats().pooled, 0);
+        pool.release(pool.acquire(103));
+        assert_eq!(pool.stats().pooled, 0);
+    }
+}
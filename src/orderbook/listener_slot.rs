@@ -0,0 +1,67 @@
+//! Listener storage that can be swapped on a shared book.
+
+use std::sync::RwLock;
+
+/// An optional listener installed, replaced or removed through `&self`.
+///
+/// Emitters clone the `Arc` out under a brief read lock and invoke it
+/// after the lock is released, so a slow listener never blocks a swap
+/// and a listener may itself install another. A swap takes effect for
+/// events emitted after it returns; an operation already in flight may
+/// still deliver to the listener it loaded.
+pub(super) struct ListenerSlot<L> {
+    listener: RwLock<Option<L>>,
+}
+
+impl<L: Clone> ListenerSlot<L> {
+    /// A slot holding `listener`.
+    pub(super) fn new(listener: Option<L>) -> Self {
+        Self {
+            listener: RwLock::new(listener),
+        }
+    }
+
+    /// The installed listener, if any.
+    #[inline]
+    pub(super) fn get(&self) -> Option<L> {
+        // The slot only holds an `Option<L>`, which a panic cannot leave
+        // half-written, so a poisoned lock is recovered.
+        self.listener
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Install `listener` (or clear the slot with `None`), returning the
+    /// previous one.
+    pub(super) fn replace(&self, listener: Option<L>) -> Option<L> {
+        let mut slot = self
+            .listener
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        std::mem::replace(&mut *slot, listener)
+    }
+}
+
+impl<L: Clone> Default for ListenerSlot<L> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn replace_returns_the_previous_listener() {
+        let slot: ListenerSlot<Arc<u32>> = ListenerSlot::default();
+        assert!(slot.get().is_none());
+        assert!(slot.replace(Some(Arc::new(1))).is_none());
+        assert_eq!(slot.replace(Some(Arc::new(2))).as_deref(), Some(&1));
+        assert_eq!(slot.get().as_deref(), Some(&2));
+        assert_eq!(slot.replace(None).as_deref(), Some(&2));
+        assert!(slot.get().is_none());
+    }
+}
@@ -10,12 +10,22 @@
 //! for both standard library (`BookManagerStd`) and Tokio (`BookManagerTokio`) channels.
 
 use crate::orderbook::OrderBook;
-use crate::orderbook::error::ManagerError;
+use crate::orderbook::cancel_on_disconnect::DisconnectEvent;
+use crate::orderbook::dead_mans_switch::{self, DeadMansSwitch, DeadMansSwitchTrip};
+use crate::orderbook::error::{ManagerError, OrderBookError};
+use crate::orderbook::executor::{BookExecutor, ExecutorConfig};
+#[cfg(feature = "latency")]
+use crate::orderbook::latency::{LatencyConfig, LatencyRecorder, SymbolLatency};
 use crate::orderbook::mass_cancel::MassCancelResult;
+use crate::orderbook::session::SessionClose;
+use crate::orderbook::snapshot::VenueSnapshot;
 use crate::orderbook::trade::{TradeEvent, TradeListener, TradeResult};
+#[cfg(feature = "latency")]
+use hdrhistogram::CreationError;
 use pricelevel::{Hash32, OrderType, Side, TimestampMs};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{error, info};
 
 /// Trait for managing multiple order books with centralized trade event routing.
@@ -80,6 +90,13 @@ where
     trade_sender: std::sync::mpsc::Sender<TradeEvent>,
     /// Receiver for trade events (taken when processor starts)
     trade_receiver: Option<std::sync::mpsc::Receiver<TradeEvent>>,
+    /// Command latency recorder handed to the executor, if enabled
+    #[cfg(feature = "latency")]
+    latency: Option<LatencyRecorder>,
+    /// Coordination epoch of the last `snapshot_all`
+    snapshot_epoch: AtomicU64,
+    /// Venue-wide dead-man's switch, disarmed until armed
+    dead_mans_switch: DeadMansSwitch,
 }
 
 impl<T> BookManagerStd<T>
@@ -94,6 +111,10 @@ where
             books: HashMap::new(),
             trade_sender: sender,
             trade_receiver: Some(receiver),
+            #[cfg(feature = "latency")]
+            latency: None,
+            snapshot_epoch: AtomicU64::new(0),
+            dead_mans_switch: DeadMansSwitch::new(),
         }
     }
 
@@ -205,6 +226,36 @@ where
             .collect()
     }
 
+    /// Block a user on every managed book, cancelling all of its resting
+    /// orders.
+    ///
+    /// Returns a map from symbol to the [`MassCancelResult`] for that book.
+    /// See [`OrderBook::block_user`].
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` — the user to block
+    pub fn block_user_across_books(&self, user_id: Hash32) -> HashMap<String, MassCancelResult> {
+        self.books
+            .iter()
+            .map(|(symbol, book)| (symbol.clone(), book.block_user(user_id)))
+            .collect()
+    }
+
+    /// Unblock a user on every managed book.
+    ///
+    /// Returns the number of books on which the user was blocked.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` — the user to unblock
+    pub fn unblock_user_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.unblock_user(user_id))
+            .count()
+    }
+
     /// Cancel all orders on a specific side across all managed books.
     ///
     /// Returns a map from symbol to the [`MassCancelResult`] for that book.
@@ -253,6 +304,224 @@ where
             .map(|(symbol, book)| (symbol.clone(), book.evict_expired_orders(now_ms)))
             .collect()
     }
+
+    /// Apply due trading session transitions across all managed books.
+    ///
+    /// Calls [`OrderBook::advance_session`] on every book, so a scheduler
+    /// ticking this processes each session close even on books without
+    /// traffic. Returns the closes processed, keyed by symbol; books
+    /// without a schedule or without a due close are omitted.
+    #[must_use]
+    pub fn advance_sessions(&self) -> HashMap<String, SessionClose> {
+        self.books
+            .iter()
+            .filter_map(|(symbol, book)| {
+                book.advance_session()
+                    .map(|closed| (symbol.clone(), closed))
+            })
+            .collect()
+    }
+
+    /// Register a cancel-on-disconnect session for `user_id` on every
+    /// managed book, so one heartbeat covers the user's orders across the
+    /// whole basket. See [`OrderBook::register_heartbeat`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::ZeroHeartbeatTtl`] when `ttl_ms` is zero,
+    /// before any book is registered.
+    pub fn register_heartbeat_across_books(
+        &self,
+        user_id: Hash32,
+        ttl_ms: u64,
+    ) -> Result<(), OrderBookError> {
+        for book in self.books.values() {
+            book.register_heartbeat(user_id, ttl_ms)?;
+        }
+        Ok(())
+    }
+
+    /// Refresh the session of `user_id` on every managed book. Returns the
+    /// number of books where it was still live; see
+    /// [`OrderBook::heartbeat`].
+    pub fn heartbeat_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.heartbeat(user_id))
+            .count()
+    }
+
+    /// Drop the session of `user_id` on every managed book without
+    /// cancelling its orders. Returns the number of books it was
+    /// registered on.
+    pub fn unregister_heartbeat_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.unregister_heartbeat(user_id))
+            .count()
+    }
+
+    /// Disconnect expired sessions across all managed books.
+    ///
+    /// Calls [`OrderBook::expire_heartbeats`] on every book, so a timer
+    /// ticking this cancels a silent user's orders even on books without
+    /// traffic. Returns the disconnects processed, keyed by symbol; books
+    /// without an expired session are omitted.
+    #[must_use]
+    pub fn expire_heartbeats_across_books(&self) -> HashMap<String, Vec<DisconnectEvent>> {
+        self.books
+            .iter()
+            .filter_map(|(symbol, book)| {
+                let disconnects = book.expire_heartbeats();
+                (!disconnects.is_empty()).then(|| (symbol.clone(), disconnects))
+            })
+            .collect()
+    }
+
+    /// The manager's dead-man's switch. Clone it to hand the refreshing
+    /// side its own handle; see [`DeadMansSwitch`].
+    #[must_use]
+    pub fn dead_mans_switch(&self) -> &DeadMansSwitch {
+        &self.dead_mans_switch
+    }
+
+    /// Replace the manager's dead-man's switch, e.g. with one that has a
+    /// purge list or a replay clock. Handles to the previous switch no
+    /// longer affect this manager.
+    pub fn set_dead_mans_switch(&mut self, switch: DeadMansSwitch) {
+        self.dead_mans_switch = switch;
+    }
+
+    /// Trip the dead-man's switch if it has lapsed: engage the kill switch
+    /// of every managed book and cancel the resting orders of the switch's
+    /// purge users. Returns `None` while the switch is disarmed or within
+    /// its deadline.
+    ///
+    /// Call this from a timer, or after [`DeadMansSwitch::expired`]
+    /// resolves. Books added after the trip are not affected.
+    pub fn check_dead_mans_switch(&self) -> Option<DeadMansSwitchTrip> {
+        dead_mans_switch::trip_books(&self.books, &self.dead_mans_switch)
+    }
+
+    /// Capture a checksum-protected snapshot package of every managed book
+    /// at one coordinated point in time.
+    ///
+    /// Every book's submit gate is held exclusively, taken in symbol order,
+    /// while the packages are built, so no order flow reaches any book
+    /// between the first and the last package. All packages are stamped
+    /// with the same, freshly incremented, coordination epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised while building a package.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+    /// mgr.add_book("BTC/USD")?;
+    /// mgr.add_book("ETH/USD")?;
+    /// if let Some(book) = mgr.get_book("BTC/USD") {
+    ///     book.add_limit_order(Id::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, None)?;
+    /// }
+    ///
+    /// let venue = mgr.snapshot_all(usize::MAX)?;
+    /// assert_eq!(venue.epoch, 1);
+    /// assert_eq!(venue.books.len(), 2);
+    /// venue.validate()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot_all(&self, depth: usize) -> Result<VenueSnapshot, OrderBookError> {
+        snapshot_books(&self.books, &self.snapshot_epoch, depth)
+    }
+
+    /// Record the end-to-end latency (enqueue to result) of every command
+    /// once the manager runs on a [`BookExecutor`], in one HDR histogram
+    /// per symbol. Takes effect at [`Self::into_executor`] unless its
+    /// config already carries a recorder.
+    ///
+    /// Returns a handle sharing the histograms; keep it to read
+    /// [`LatencyRecorder::percentile_report`] after the manager has been
+    /// converted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the histogram [`CreationError`] for an invalid `config`.
+    #[cfg(feature = "latency")]
+    pub fn enable_latency_recording(
+        &mut self,
+        config: LatencyConfig,
+    ) -> Result<LatencyRecorder, CreationError> {
+        let recorder = LatencyRecorder::new(config)?;
+        self.latency = Some(recorder.clone());
+        Ok(recorder)
+    }
+
+    /// Per-symbol command latency percentiles, or `None` if recording is
+    /// not enabled. Empty until the manager runs on an executor.
+    #[cfg(feature = "latency")]
+    #[must_use]
+    pub fn percentile_report(&self) -> Option<BTreeMap<String, SymbolLatency>> {
+        self.latency
+            .as_ref()
+            .map(LatencyRecorder::percentile_report)
+    }
+
+    /// Move every book onto its own worker thread of a [`BookExecutor`],
+    /// which then routes commands by symbol via
+    /// [`BookExecutor::submit`].
+    ///
+    /// Each book keeps its trade listener, so start the trade processor
+    /// (see [`Self::start_trade_processor`]) before converting;
+    /// otherwise the receiver is dropped with the manager and trade events
+    /// are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::WorkerSpawnFailed`] if a worker thread cannot
+    /// be created; books already spawned are drained and dropped.
+    pub fn into_executor(self, config: ExecutorConfig) -> Result<BookExecutor<T>, ManagerError> {
+        #[cfg(feature = "latency")]
+        let config = match self.latency {
+            Some(recorder) if config.latency_recorder().is_none() => {
+                config.with_latency_recorder(recorder)
+            }
+            _ => config,
+        };
+        let mut executor = BookExecutor::new(config);
+        for book in self.books.into_values() {
+            executor.spawn_book(book)?;
+        }
+        Ok(executor)
+    }
+}
+
+/// Hold every book's submit gate, in symbol order, while each is packaged
+/// under the next epoch of `epoch`.
+fn snapshot_books<T>(
+    books: &HashMap<String, OrderBook<T>>,
+    epoch: &AtomicU64,
+    depth: usize,
+) -> Result<VenueSnapshot, OrderBookError>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let ordered: BTreeMap<&String, &OrderBook<T>> = books.iter().collect();
+    let _gates: Vec<_> = ordered
+        .values()
+        .map(|book| book.acquire_submit_gate(true))
+        .collect();
+    let epoch = epoch.fetch_add(1, Ordering::Relaxed) + 1;
+    let books = ordered
+        .into_iter()
+        .map(|(symbol, book)| Ok((symbol.clone(), book.create_snapshot_package(depth)?)))
+        .collect::<Result<_, OrderBookError>>()?;
+    Ok(VenueSnapshot { epoch, books })
 }
 
 impl<T> BookManager<T> for BookManagerStd<T>
@@ -352,6 +621,13 @@ where
     trade_sender: tokio::sync::mpsc::UnboundedSender<TradeEvent>,
     /// Receiver for trade events (taken when processor starts)
     trade_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<TradeEvent>>,
+    /// Command latency recorder handed to the executor, if enabled
+    #[cfg(feature = "latency")]
+    latency: Option<LatencyRecorder>,
+    /// Coordination epoch of the last `snapshot_all`
+    snapshot_epoch: AtomicU64,
+    /// Venue-wide dead-man's switch, disarmed until armed
+    dead_mans_switch: DeadMansSwitch,
 }
 
 impl<T> BookManagerTokio<T>
@@ -366,6 +642,10 @@ where
             books: HashMap::new(),
             trade_sender: sender,
             trade_receiver: Some(receiver),
+            #[cfg(feature = "latency")]
+            latency: None,
+            snapshot_epoch: AtomicU64::new(0),
+            dead_mans_switch: DeadMansSwitch::new(),
         }
     }
 
@@ -456,6 +736,36 @@ where
             .collect()
     }
 
+    /// Block a user on every managed book, cancelling all of its resting
+    /// orders.
+    ///
+    /// Returns a map from symbol to the [`MassCancelResult`] for that book.
+    /// See [`OrderBook::block_user`].
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` — the user to block
+    pub fn block_user_across_books(&self, user_id: Hash32) -> HashMap<String, MassCancelResult> {
+        self.books
+            .iter()
+            .map(|(symbol, book)| (symbol.clone(), book.block_user(user_id)))
+            .collect()
+    }
+
+    /// Unblock a user on every managed book.
+    ///
+    /// Returns the number of books on which the user was blocked.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` — the user to unblock
+    pub fn unblock_user_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.unblock_user(user_id))
+            .count()
+    }
+
     /// Cancel all orders on a specific side across all managed books.
     ///
     /// Returns a map from symbol to the [`MassCancelResult`] for that book.
@@ -504,6 +814,164 @@ where
             .map(|(symbol, book)| (symbol.clone(), book.evict_expired_orders(now_ms)))
             .collect()
     }
+
+    /// Apply due trading session transitions across all managed books.
+    ///
+    /// Calls [`OrderBook::advance_session`] on every book, so a scheduler
+    /// ticking this processes each session close even on books without
+    /// traffic. Returns the closes processed, keyed by symbol; books
+    /// without a schedule or without a due close are omitted.
+    #[must_use]
+    pub fn advance_sessions(&self) -> HashMap<String, SessionClose> {
+        self.books
+            .iter()
+            .filter_map(|(symbol, book)| {
+                book.advance_session()
+                    .map(|closed| (symbol.clone(), closed))
+            })
+            .collect()
+    }
+
+    /// Register a cancel-on-disconnect session for `user_id` on every
+    /// managed book. See [`BookManagerStd::register_heartbeat_across_books`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::ZeroHeartbeatTtl`] when `ttl_ms` is zero,
+    /// before any book is registered.
+    pub fn register_heartbeat_across_books(
+        &self,
+        user_id: Hash32,
+        ttl_ms: u64,
+    ) -> Result<(), OrderBookError> {
+        for book in self.books.values() {
+            book.register_heartbeat(user_id, ttl_ms)?;
+        }
+        Ok(())
+    }
+
+    /// Refresh the session of `user_id` on every managed book. See
+    /// [`BookManagerStd::heartbeat_across_books`].
+    pub fn heartbeat_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.heartbeat(user_id))
+            .count()
+    }
+
+    /// Drop the session of `user_id` on every managed book. See
+    /// [`BookManagerStd::unregister_heartbeat_across_books`].
+    pub fn unregister_heartbeat_across_books(&self, user_id: Hash32) -> usize {
+        self.books
+            .values()
+            .filter(|book| book.unregister_heartbeat(user_id))
+            .count()
+    }
+
+    /// Disconnect expired sessions across all managed books. See
+    /// [`BookManagerStd::expire_heartbeats_across_books`].
+    #[must_use]
+    pub fn expire_heartbeats_across_books(&self) -> HashMap<String, Vec<DisconnectEvent>> {
+        self.books
+            .iter()
+            .filter_map(|(symbol, book)| {
+                let disconnects = book.expire_heartbeats();
+                (!disconnects.is_empty()).then(|| (symbol.clone(), disconnects))
+            })
+            .collect()
+    }
+
+    /// The manager's dead-man's switch. See
+    /// [`BookManagerStd::dead_mans_switch`].
+    #[must_use]
+    pub fn dead_mans_switch(&self) -> &DeadMansSwitch {
+        &self.dead_mans_switch
+    }
+
+    /// Replace the manager's dead-man's switch. See
+    /// [`BookManagerStd::set_dead_mans_switch`].
+    pub fn set_dead_mans_switch(&mut self, switch: DeadMansSwitch) {
+        self.dead_mans_switch = switch;
+    }
+
+    /// Trip the dead-man's switch if it has lapsed. See
+    /// [`BookManagerStd::check_dead_mans_switch`]; a watchdog task can
+    /// await [`DeadMansSwitch::expired`] before calling it.
+    pub fn check_dead_mans_switch(&self) -> Option<DeadMansSwitchTrip> {
+        dead_mans_switch::trip_books(&self.books, &self.dead_mans_switch)
+    }
+
+    /// Capture a checksum-protected snapshot package of every managed book
+    /// at one coordinated point in time.
+    ///
+    /// See [`BookManagerStd::snapshot_all`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised while building a package.
+    pub fn snapshot_all(&self, depth: usize) -> Result<VenueSnapshot, OrderBookError> {
+        snapshot_books(&self.books, &self.snapshot_epoch, depth)
+    }
+
+    /// Record the end-to-end latency (enqueue to result) of every command
+    /// once the manager runs on a [`BookExecutor`], in one HDR histogram
+    /// per symbol. Takes effect at [`Self::into_executor`] unless its
+    /// config already carries a recorder.
+    ///
+    /// Returns a handle sharing the histograms; keep it to read
+    /// [`LatencyRecorder::percentile_report`] after the manager has been
+    /// converted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the histogram [`CreationError`] for an invalid `config`.
+    #[cfg(feature = "latency")]
+    pub fn enable_latency_recording(
+        &mut self,
+        config: LatencyConfig,
+    ) -> Result<LatencyRecorder, CreationError> {
+        let recorder = LatencyRecorder::new(config)?;
+        self.latency = Some(recorder.clone());
+        Ok(recorder)
+    }
+
+    /// Per-symbol command latency percentiles, or `None` if recording is
+    /// not enabled. Empty until the manager runs on an executor.
+    #[cfg(feature = "latency")]
+    #[must_use]
+    pub fn percentile_report(&self) -> Option<BTreeMap<String, SymbolLatency>> {
+        self.latency
+            .as_ref()
+            .map(LatencyRecorder::percentile_report)
+    }
+
+    /// Move every book onto its own worker thread of a [`BookExecutor`],
+    /// which then routes commands by symbol via
+    /// [`BookExecutor::submit`].
+    ///
+    /// Each book keeps its trade listener, so start the trade processor
+    /// (see [`Self::start_trade_processor`]) before converting;
+    /// otherwise the receiver is dropped with the manager and trade events
+    /// are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::WorkerSpawnFailed`] if a worker thread cannot
+    /// be created; books already spawned are drained and dropped.
+    pub fn into_executor(self, config: ExecutorConfig) -> Result<BookExecutor<T>, ManagerError> {
+        #[cfg(feature = "latency")]
+        let config = match self.latency {
+            Some(recorder) if config.latency_recorder().is_none() => {
+                config.with_latency_recorder(recorder)
+            }
+            _ => config,
+        };
+        let mut executor = BookExecutor::new(config);
+        for book in self.books.into_values() {
+            executor.spawn_book(book)?;
+        }
+        Ok(executor)
+    }
 }
 
 impl<T> BookManager<T> for BookManagerTokio<T>
@@ -6,6 +6,7 @@
 //! - Expected slippage
 //! - Number of price levels consumed
 //! - Available liquidity in price ranges
+//! - Cost-to-liquidate curves across increasing order sizes
 
 use serde::{Deserialize, Serialize};
 
@@ -64,6 +65,24 @@ pub struct OrderSimulation {
     pub remaining_quantity: u64,
 }
 
+/// One point on a cost-to-liquidate curve
+///
+/// Produced by [`OrderBook::liquidation_curve`](crate::OrderBook::liquidation_curve):
+/// each point describes what a market order of `quantity` units would pay
+/// on average if it swept the side being hit right now.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LiquidationPoint {
+    /// Order quantity this point describes (in units)
+    pub quantity: u64,
+
+    /// Volume-weighted average execution price for `quantity` (in price units)
+    pub avg_price: f64,
+
+    /// Distance of `avg_price` from the best price on the side being hit,
+    /// in basis points. Always non-negative.
+    pub slippage_bps: f64,
+}
+
 impl MarketImpact {
     /// Creates a new MarketImpact with all fields set to zero/empty
     ///
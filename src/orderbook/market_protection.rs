@@ -0,0 +1,324 @@
+//! Market order protection.
+//!
+//! A plain market order sweeps the opposite side until it is filled,
+//! however far that moves the price. Exchanges bound the sweep with
+//! "market with protection" semantics, and [`MarketProtection`] brings
+//! them to the book: installed in its [`BookConfig`], it caps every
+//! market order at a number of price levels and/or a slippage in basis
+//! points from the touch (the best opposite price when the order
+//! arrives).
+//!
+//! The bounds resolve to one protection price per order, the worst price
+//! it may trade at. What is left when the sweep reaches it is cancelled
+//! or, with [`ProtectionRemainder::RestAsLimit`], rests as a good-till-
+//! cancel limit order at the protection price.
+//!
+//! [`BookConfig`]: super::book_config::BookConfig
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::order_state::OrderStatus;
+use super::reject_reason::RejectReason;
+use pricelevel::{Hash32, Id, MatchResult, OrderType, Price, Quantity, Side, TimeInForce};
+use serde::{Deserialize, Serialize};
+
+/// Basis points in one whole.
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// What happens to the part of a protected market order that the
+/// protection price leaves unfilled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProtectionRemainder {
+    /// Cancel the remainder, like the unfilled part of a market order on
+    /// a book that ran out of liquidity.
+    #[default]
+    Cancel,
+    /// Rest the remainder as a good-till-cancel limit order at the
+    /// protection price, under the market order's id and owner.
+    RestAsLimit,
+}
+
+/// Sweep limits applied to every market order of a book.
+///
+/// At least one bound must be set. With both, the tighter one wins.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{BookConfig, MarketProtection, OrderBook, ProtectionRemainder};
+/// use pricelevel::{Id, Side, TimeInForce};
+///
+/// let book = OrderBook::<()>::new("BTC/USD");
+/// for price in [100, 101, 102, 103] {
+///     book.add_limit_order(Id::new(), price, 10, Side::Sell, TimeInForce::Gtc, None)
+///         .unwrap();
+/// }
+/// book.reload_config(BookConfig::new().with_market_protection(
+///     MarketProtection::new()
+///         .with_max_levels(2)
+///         .with_remainder(ProtectionRemainder::RestAsLimit),
+/// ))
+/// .unwrap();
+///
+/// let taker = Id::new();
+/// let result = book.submit_market_order(taker, 50, Side::Buy).unwrap();
+/// assert_eq!(result.remaining_quantity().as_u64(), 30);
+/// assert_eq!(book.best_bid(), Some(101));
+/// assert!(book.get_order(taker).is_some());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketProtection {
+    /// Most price levels a market order may trade at, counted from the
+    /// touch.
+    pub max_levels: Option<u32>,
+    /// Furthest a market order may trade from the touch, in basis points.
+    pub max_slippage_bps: Option<u32>,
+    /// What happens to the unfilled remainder.
+    pub remainder: ProtectionRemainder,
+}
+
+impl MarketProtection {
+    /// Construct a protection with no bound set and
+    /// [`ProtectionRemainder::Cancel`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap market orders at `max_levels` price levels. Must be non-zero.
+    #[must_use]
+    pub fn with_max_levels(mut self, max_levels: u32) -> Self {
+        self.max_levels = Some(max_levels);
+        self
+    }
+
+    /// Cap market orders at `max_slippage_bps` basis points from the
+    /// touch. Must be below `10_000`.
+    #[must_use]
+    pub fn with_max_slippage_bps(mut self, max_slippage_bps: u32) -> Self {
+        self.max_slippage_bps = Some(max_slippage_bps);
+        self
+    }
+
+    /// Set what happens to the unfilled remainder.
+    #[must_use]
+    pub fn with_remainder(mut self, remainder: ProtectionRemainder) -> Self {
+        self.remainder = remainder;
+        self
+    }
+
+    /// Check the bounds for consistency.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when no bound is
+    /// set, `max_levels` is zero, or `max_slippage_bps` is `10_000` or
+    /// more (a sell order would be protected at a price of zero).
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        let message = match (self.max_levels, self.max_slippage_bps) {
+            (None, None) => "market protection must bound levels or slippage".to_string(),
+            (Some(0), _) => "market protection max levels must be non-zero".to_string(),
+            (_, Some(bps)) if u128::from(bps) >= BPS_DENOMINATOR => {
+                format!("market protection slippage {bps} bps must be below 10000")
+            }
+            _ => return Ok(()),
+        };
+        Err(OrderBookError::InvalidConfiguration { message })
+    }
+
+    /// The slippage bound for a `side` market order against `touch`,
+    /// rounded onto the `tick` grid towards the touch.
+    fn slippage_price(&self, side: Side, touch: u128, tick: u128) -> Option<u128> {
+        let bps = u128::from(self.max_slippage_bps?);
+        let offset = touch.saturating_mul(bps) / BPS_DENOMINATOR;
+        let tick = tick.max(1);
+        Some(match side {
+            Side::Buy => {
+                let bound = touch.saturating_add(offset);
+                bound - bound % tick
+            }
+            Side::Sell => {
+                let bound = touch.saturating_sub(offset);
+                match bound % tick {
+                    0 => bound,
+                    rest => bound.saturating_add(tick - rest),
+                }
+            }
+        })
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Returns the market order protection, if any.
+    ///
+    /// `None` means market orders sweep until filled (default).
+    #[must_use]
+    #[inline]
+    pub fn market_protection(&self) -> Option<MarketProtection> {
+        self.config().market_protection
+    }
+
+    /// The protection price a `side` market order submitted now would get:
+    /// the worst price it may trade at under the book's
+    /// [`MarketProtection`].
+    ///
+    /// The level bound is the price of the last level the order may reach,
+    /// or of the deepest level when the book is shallower than that.
+    /// Returns `None` when no protection is configured or the opposite
+    /// side is empty.
+    #[must_use]
+    pub fn market_protection_price(&self, side: Side) -> Option<u128> {
+        let protection = self.market_protection()?;
+        let opposite = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        let touch = match side {
+            Side::Buy => opposite.front(),
+            Side::Sell => opposite.back(),
+        }
+        .map(|entry| *entry.key())?;
+
+        let level_price = protection.max_levels.and_then(|levels| {
+            let levels = usize::try_from(levels).unwrap_or(usize::MAX);
+            match side {
+                Side::Buy => opposite.iter().take(levels).last(),
+                Side::Sell => opposite.iter().rev().take(levels).last(),
+            }
+            .map(|entry| *entry.key())
+        });
+        let slippage_price = protection.slippage_price(side, touch, self.tick_size().unwrap_or(1));
+
+        match (level_price, slippage_price) {
+            (Some(level), Some(slippage)) => Some(match side {
+                Side::Buy => level.min(slippage),
+                Side::Sell => level.max(slippage),
+            }),
+            (level, slippage) => level.or(slippage),
+        }
+    }
+
+    /// Resolve the protection of a `side` market order about to sweep:
+    /// its protection price and remainder policy. `None` sweeps unbounded.
+    pub(super) fn resolve_market_protection(
+        &self,
+        side: Side,
+    ) -> Option<(u128, ProtectionRemainder)> {
+        let remainder = self.market_protection()?.remainder;
+        self.market_protection_price(side)
+            .map(|price| (price, remainder))
+    }
+
+    /// Apply `remainder` to base-quantity market order `order_id` after
+    /// its protected sweep left `result`: rest what is left as a
+    /// good-till-cancel limit order at `price` and record its state, or
+    /// cancel it — an order the protection let trade nothing fails with
+    /// [`OrderBookError::InsufficientLiquidity`] like any market order.
+    /// Nothing rests after STP cancelled the taker.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn settle_protected_remainder(
+        &self,
+        order_id: Id,
+        side: Side,
+        quantity: u64,
+        user_id: Hash32,
+        (price, remainder): (u128, ProtectionRemainder),
+        result: &MatchResult,
+        taker_stp_cancelled: bool,
+    ) -> Result<(), OrderBookError> {
+        let remaining = result.remaining_quantity().as_u64();
+        if remaining == 0 || taker_stp_cancelled {
+            return Ok(());
+        }
+        if remainder == ProtectionRemainder::Cancel {
+            if result.trades().is_empty() {
+                self.record_reject(RejectReason::InsufficientLiquidity);
+                return Err(OrderBookError::InsufficientLiquidity {
+                    side,
+                    requested: quantity,
+                    available: 0,
+                });
+            }
+            return Ok(());
+        }
+
+        let order = OrderType::Standard {
+            id: order_id,
+            price: Price::new(price),
+            quantity: Quantity::new(remaining),
+            side,
+            user_id,
+            timestamp: self.clock().now_millis(),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: T::default(),
+        };
+        self.rest_order(&order, remaining)?;
+        let filled_quantity = quantity.saturating_sub(remaining);
+        if filled_quantity > 0 {
+            self.track_state(
+                order_id,
+                OrderStatus::PartiallyFilled {
+                    original_quantity: quantity,
+                    filled_quantity,
+                },
+            );
+        } else {
+            self.track_state(order_id, OrderStatus::Open);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_requires_a_sane_bound() {
+        assert!(MarketProtection::new().validate().is_err());
+        assert!(
+            MarketProtection::new()
+                .with_max_levels(0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            MarketProtection::new()
+                .with_max_slippage_bps(10_000)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            MarketProtection::new()
+                .with_max_levels(1)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            MarketProtection::new()
+                .with_max_slippage_bps(0)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn slippage_price_rounds_towards_the_touch() {
+        let protection = MarketProtection::new().with_max_slippage_bps(150);
+        // 1.5% of 1_000 = 15: buys stop at 1_015 → 1_010 on a 10 tick,
+        // sells at 985 → 990.
+        assert_eq!(protection.slippage_price(Side::Buy, 1_000, 10), Some(1_010));
+        assert_eq!(protection.slippage_price(Side::Sell, 1_000, 10), Some(990));
+        assert_eq!(protection.slippage_price(Side::Buy, 1_000, 1), Some(1_015));
+        assert_eq!(protection.slippage_price(Side::Sell, 1_000, 1), Some(985));
+        assert_eq!(
+            MarketProtection::new()
+                .with_max_levels(1)
+                .slippage_price(Side::Buy, 1_000, 1),
+            None
+        );
+    }
+}
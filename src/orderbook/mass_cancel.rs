@@ -14,6 +14,7 @@ use super::book_change_event::PriceLevelChangedEvent;
 use super::order_state::{CancelReason, OrderStatus};
 use pricelevel::{Hash32, Id, OrderType, Side, TimestampMs};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::trace;
 
@@ -152,6 +153,7 @@ where
         // #209: shared submit gate — the bulk walk must not interleave
         // with a concurrent FOK's exclusive feasibility + sweep window.
         let _gate = self.submit_gate_read();
+        let _mutation = self.cache.begin_mutation();
         self.cache.invalidate();
         trace!("Order book {}: Mass cancel ALL orders (bulk)", self.symbol);
 
@@ -187,14 +189,16 @@ where
         }
 
         // 2. Emit PriceLevelChangedEvent (qty → 0) for every affected level
-        if let Some(ref listener) = self.price_level_changed_listener {
+        if let Some(listener) = self.price_level_listener() {
             for entry in self.bids.iter() {
                 let engine_seq = self.next_engine_seq();
                 listener(PriceLevelChangedEvent {
                     side: Side::Buy,
                     price: *entry.key(),
                     quantity: 0,
+                    order_count: 0,
                     engine_seq,
+                    config_version: self.config_version(),
                 });
             }
             for entry in self.asks.iter() {
@@ -203,7 +207,9 @@ where
                     side: Side::Sell,
                     price: *entry.key(),
                     quantity: 0,
+                    order_count: 0,
                     engine_seq,
+                    config_version: self.config_version(),
                 });
             }
         }
@@ -227,11 +233,17 @@ where
 
         // 3. Clear tracking maps
         self.order_locations.clear();
+        self.extra_fields.clear();
+        self.expiry_wheel.clear();
         self.user_orders.clear();
+        self.order_owners.clear();
+        self.order_lifetimes.clear();
+        self.order_tags.clear();
 
         // 4. Drain both SkipMaps
         while self.bids.pop_front().is_some() {}
         while self.asks.pop_front().is_some() {}
+        self.depth_totals.clear();
 
         // 5. Clear special order tracker
         #[cfg(feature = "special_orders")]
@@ -244,6 +256,7 @@ where
         self.risk_state.clear();
 
         self.cache.invalidate();
+        self.refresh_depth_view();
         // Refresh the depth gauges; both sides are now empty.
         self.record_depth_metric();
 
@@ -398,6 +411,56 @@ where
         self.cancel_order_batch_with_reason(&order_ids, CancelReason::MassCancelByUser)
     }
 
+    /// Block `user_id` and cancel all of its resting orders.
+    ///
+    /// Until [`OrderBook::unblock_user`] is called, every `add_order` /
+    /// `submit_*` call carrying `user_id` is rejected with
+    /// [`OrderBookError::UserBlocked`](super::error::OrderBookError::UserBlocked).
+    /// Cancels are not gated. Cancelled orders are reported with
+    /// [`CancelReason::UserBlocked`].
+    ///
+    /// The block and the cancel happen under the exclusive submit gate, so
+    /// no gated submission by the user can rest an order between the two.
+    /// Blocking an already-blocked user cancels anything still resting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderBookError};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let book: OrderBook<()> = OrderBook::new("TEST");
+    /// let user = Hash32::new([1u8; 32]);
+    ///
+    /// book.add_limit_order_with_user(
+    ///     Id::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, user, None,
+    /// )?;
+    ///
+    /// let result = book.block_user(user);
+    /// assert_eq!(result.cancelled_count(), 1);
+    ///
+    /// let rejected = book.add_limit_order_with_user(
+    ///     Id::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, user, None,
+    /// );
+    /// assert!(matches!(rejected, Err(OrderBookError::UserBlocked { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block_user(&self, user_id: Hash32) -> MassCancelResult {
+        let _gate = self.submit_gate_write();
+        trace!("Order book {}: Block user {}", self.symbol, user_id);
+
+        self.blocked_users.insert(user_id);
+        let order_ids = self
+            .user_orders
+            .remove(&user_id)
+            .map(|(_, ids)| ids)
+            .unwrap_or_default();
+
+        self.cancel_order_batch_with_reason(&order_ids, CancelReason::UserBlocked)
+    }
+
     /// Cancel all resting orders on a given side within a price range
     /// (inclusive on both ends).
     ///
@@ -511,6 +574,13 @@ where
     /// `now_ms >= market_close` (`Day`); a `Gtd` whose deadline equals `now_ms`
     /// is evicted. `Gtc`, `Ioc`, and `Fok` resting orders are never touched.
     ///
+    /// # Cost
+    ///
+    /// Resting `Gtd` orders are scheduled on the book's expiry timing wheel
+    /// and `Day` orders are tracked alongside it, so a sweep visits only the
+    /// orders that are due and the price levels holding them — not every
+    /// resting order. See [`Self::expiry_wheel_stats`].
+    ///
     /// # Determinism contract
     ///
     /// The returned vector — and the [`PriceLevelChangedEvent`] and
@@ -574,37 +644,71 @@ where
     pub fn evict_expired_orders(&self, now_ms: TimestampMs) -> Vec<Arc<OrderType<T>>> {
         // #209: shared submit gate (see `cancel_all_orders`).
         let _gate = self.submit_gate_read();
+        self.evict_expired_orders_ungated(now_ms)
+    }
+
+    /// [`Self::evict_expired_orders`] without the submit gate, for callers
+    /// that already exclude concurrent submits.
+    pub(super) fn evict_expired_orders_ungated(
+        &self,
+        now_ms: TimestampMs,
+    ) -> Vec<Arc<OrderType<T>>> {
         let now = now_ms.as_u64();
         trace!(
             "Order book {}: Evicting expired orders as of {} ms",
             self.symbol, now
         );
 
-        // Phase 1: collect the IDs of every expired resting order in the fixed
-        // determinism-contract order (bids ascending, then asks ascending;
-        // within each level, ascending insertion sequence). `SkipMap::iter`
-        // yields ascending price keys, and `snapshot_by_seq_into` yields the
-        // exact order the matching engine consumes resting orders — the
-        // non-deterministic `iter_orders` view must NOT be used here or replay
-        // would diverge. One scratch buffer is reused across levels to avoid a
-        // per-level allocation. Expiry uses `tif_expired_at` — the same
-        // definition admission uses — so the boundary case (deadline == now)
-        // can never diverge.
+        // Phase 1: ask the expiry wheel which orders are due. Only `Gtd`
+        // orders whose deadline has passed and, once the market has closed,
+        // the tracked `Day` orders come back, so the sweep costs O(due)
+        // rather than a scan of every resting order.
+        let day_due = self
+            .market_close_for_expiry()
+            .is_some_and(|close| now >= close);
+        let due = self.expiry_wheel.take_due(now, day_due);
+        if due.is_empty() {
+            return Vec::new();
+        }
+
+        // Phase 2: put the due orders in the fixed determinism-contract order
+        // (bids ascending, then asks ascending; within each level, ascending
+        // insertion sequence). Only the levels holding a due order are
+        // snapshotted, and `snapshot_by_seq_into` yields the exact order the
+        // matching engine consumes resting orders — the non-deterministic
+        // `iter_orders` view must NOT be used here or replay would diverge.
+        // Expiry is re-checked with `tif_expired_at` — the same definition
+        // admission uses — so the boundary case (deadline == now) can never
+        // diverge. A due order that no longer rests is dropped; one that is
+        // not expired after all (a `Day` order whose market close moved) is
+        // handed back to the wheel.
+        let mut due_levels: BTreeMap<(bool, u128), Vec<Id>> = BTreeMap::new();
+        for order_id in due {
+            if let Some(location) = self.order_locations.get(&order_id) {
+                let (price, side) = *location;
+                due_levels
+                    .entry((side == Side::Sell, price))
+                    .or_default()
+                    .push(order_id);
+            }
+        }
+
         let mut expired_ids: Vec<Id> = Vec::new();
         let mut level_orders: Vec<Arc<OrderType<()>>> = Vec::new();
-        for entry in self.bids.iter() {
+        for ((is_ask, price), ids) in &due_levels {
+            let price_levels = if *is_ask { &self.asks } else { &self.bids };
+            let Some(entry) = price_levels.get(price) else {
+                continue;
+            };
             entry.value().snapshot_by_seq_into(&mut level_orders);
             for order in &level_orders {
-                if self.tif_expired_at(order.time_in_force(), now) {
-                    expired_ids.push(order.id());
+                if !ids.contains(&order.id()) {
+                    continue;
                 }
-            }
-        }
-        for entry in self.asks.iter() {
-            entry.value().snapshot_by_seq_into(&mut level_orders);
-            for order in &level_orders {
                 if self.tif_expired_at(order.time_in_force(), now) {
                     expired_ids.push(order.id());
+                } else {
+                    self.schedule_expiry(order.id(), order.time_in_force());
                 }
             }
         }
@@ -613,7 +717,7 @@ where
             return Vec::new();
         }
 
-        // Phase 2: cancel each expired order through the shared single-order
+        // Phase 3: cancel each expired order through the shared single-order
         // path, preserving the collection order. This is what keeps the caches,
         // trackers, and emitted events consistent and in the documented order.
         let mut evicted = Vec::with_capacity(expired_ids.len());
@@ -639,7 +743,7 @@ where
     ///
     /// Calls [`Self::cancel_order_with_reason`] for each ID. Orders that no
     /// longer exist (e.g. concurrently cancelled) are silently skipped.
-    fn cancel_order_batch_with_reason(
+    pub(super) fn cancel_order_batch_with_reason(
         &self,
         order_ids: &[Id],
         reason: CancelReason,
@@ -668,7 +772,7 @@ where
     /// deliberately avoided so the resulting id sequence (which lands in the
     /// journaled `MassCancelled` payload) is replay-stable across processes. One
     /// scratch buffer is reused across levels to avoid a per-level allocation.
-    fn collect_order_ids_by_side(&self, side: Side) -> Vec<Id> {
+    pub(super) fn collect_order_ids_by_side(&self, side: Side) -> Vec<Id> {
         let price_levels = match side {
             Side::Buy => &self.bids,
             Side::Sell => &self.asks,
@@ -1305,7 +1409,7 @@ mod tests {
 
         let events: Arc<Mutex<Vec<PriceLevelChangedEvent>>> = Arc::new(Mutex::new(Vec::new()));
         let sink = Arc::clone(&events);
-        let mut book = expiring_book();
+        let book = expiring_book();
         book.set_price_level_listener(Arc::new(move |ev: PriceLevelChangedEvent| {
             if let Ok(mut v) = sink.lock() {
                 v.push(ev);
@@ -7,10 +7,15 @@
 use crate::orderbook::book_change_event::PriceLevelChangedEvent;
 use crate::orderbook::order_state::{CancelReason, OrderStatus};
 use crate::orderbook::pool::MatchingPool;
-use crate::orderbook::stp::{STPAction, check_stp_at_level};
+use crate::orderbook::replenishment::hidden_tranches;
+use crate::orderbook::reserve_throttle::{is_reserve, throttled_depth};
+use crate::orderbook::stp::{STPAction, StpScope, check_stp_at_level};
+use crate::orderbook::trade::{FillParty, LiquidityFlag, TradeFill};
 use crate::{OrderBook, OrderBookError};
 use either::Either;
-use pricelevel::{Hash32, Id, MatchResult, OrderType, Quantity, Side, TakerKind, TimeInForce};
+use pricelevel::{
+    Hash32, Id, MatchResult, OrderType, PriceLevel, Quantity, Side, TakerKind, TimeInForce, Trade,
+};
 use std::sync::atomic::Ordering;
 
 /// Matchable depth of a single resting order: its visible quantity plus any
@@ -23,12 +28,13 @@ use std::sync::atomic::Ordering;
 /// `PriceLevel::matchable_quantity` (the authoritative upstream dry run). This
 /// helper remains only for the STP `CancelMaker` case, which must sum the
 /// matchable depth of the *non-self* makers — a per-user filter the upstream
-/// primitive cannot express. It is therefore the one FOK-feasibility path that
-/// no longer rides on pricelevel's authoritative dry run, so its per-order total
-/// MUST stay equal to `OrderType::match_against`'s for every resting kind: if a
-/// new `OrderType` variant is added whose sweep total differs from
-/// `visible + drawable_hidden`, update this helper or the CancelMaker FOK path
-/// will silently mis-predict while the delegated paths stay correct.
+/// primitive cannot express — and for levels throttled by a reserve fill
+/// limit, where each reserve counts at most the limit. Those are the
+/// FOK-feasibility paths that no longer ride on pricelevel's authoritative dry
+/// run, so its per-order total MUST stay equal to `OrderType::match_against`'s
+/// for every resting kind: if a new `OrderType` variant is added whose sweep
+/// total differs from `visible + drawable_hidden`, update this helper or those
+/// FOK paths will silently mis-predict while the delegated paths stay correct.
 #[inline]
 fn order_matchable_qty(order: &OrderType<()>) -> u64 {
     let visible = order.visible_quantity().as_u64();
@@ -61,6 +67,9 @@ pub(crate) struct MatchOutcome {
     /// crossable level, so the book must reject it (`PriceCrossing`) —
     /// structurally zero trades were emitted.
     pub(crate) taker_post_only_rejected: bool,
+    /// One [`TradeFill`] per trade in `result`, fees not yet charged, when
+    /// the sweep was asked to capture them; empty otherwise.
+    pub(crate) fills: Vec<TradeFill>,
 }
 
 impl MatchOutcome {
@@ -71,17 +80,99 @@ impl MatchOutcome {
             result,
             taker_stp_cancelled: false,
             taker_post_only_rejected: false,
+            fills: Vec::new(),
         }
     }
 }
 
+// Static memory pool for better performance. Module-level so the publish
+// path can hand captured fills back once the trade listener has returned.
+thread_local! {
+    static MATCHING_POOL: MatchingPool = MatchingPool::new();
+}
+
+/// Pre-allocate `buffers` buffers of every kind in this thread's matching
+/// pool.
+pub(crate) fn warm_matching_pool(buffers: usize) {
+    MATCHING_POOL.with(|pool| pool.warm(buffers));
+}
+
+/// Hand a trade's captured fills back to this thread's matching pool.
+pub(crate) fn recycle_fills(fills: Vec<TradeFill>) {
+    MATCHING_POOL.with(|pool| pool.return_fills_vec(fills));
+}
+
+/// Lend `f` this thread's pooled order snapshot buffer, empty, and return
+/// it to the pool cleared.
+pub(crate) fn with_order_snapshot_vec<R>(
+    f: impl FnOnce(&mut Vec<std::sync::Arc<pricelevel::OrderType<()>>>) -> R,
+) -> R {
+    let mut orders = MATCHING_POOL.with(|pool| pool.get_order_snapshot_vec());
+    let result = f(&mut orders);
+    MATCHING_POOL.with(|pool| pool.return_order_snapshot_vec(orders));
+    result
+}
+
+/// Counterparty detail gathered while a sweep runs, for sweeps whose
+/// trades will be published. A fully filled maker has left its level by
+/// the time the trade is built, so its owner and remaining size are
+/// recorded here, level by level, while the book still tracks it.
+struct FillCapture {
+    taker_user_id: Hash32,
+    fills: Vec<TradeFill>,
+}
+
+impl FillCapture {
+    fn new(taker_user_id: Hash32) -> Self {
+        Self {
+            taker_user_id,
+            fills: MATCHING_POOL.with(MatchingPool::get_fills_vec),
+        }
+    }
+
+    /// Record `trade` under `execution_id`. Fees are left at zero for
+    /// `build_trade_result` to charge from the book's fee schedule.
+    fn record(
+        &mut self,
+        trade: &Trade,
+        execution_id: u64,
+        matched_at_ns: u64,
+        maker_user_id: Hash32,
+        maker_remaining_quantity: u64,
+    ) {
+        self.fills.push(TradeFill {
+            trade_id: trade.trade_id(),
+            execution_id,
+            matched_at_ns,
+            price: trade.price().as_u128(),
+            quantity: trade.quantity().as_u64(),
+            maker: FillParty {
+                order_id: trade.maker_order_id(),
+                user_id: maker_user_id,
+                side: trade.maker_side(),
+                liquidity: LiquidityFlag::Maker,
+                fee: 0,
+            },
+            taker: FillParty {
+                order_id: trade.taker_order_id(),
+                user_id: self.taker_user_id,
+                side: trade.taker_side(),
+                liquidity: LiquidityFlag::Taker,
+                fee: 0,
+            },
+            maker_remaining_quantity,
+        });
+    }
+}
+
 /// Selects how the matching loop measures its budget.
 ///
 /// `BaseQty` is the legacy base-asset quantity path (existing market and
 /// limit orders). `QuoteAmount` is the quote-notional path used by
 /// `match_market_order_by_amount` (Binance `quoteOrderQty` semantics).
-/// Always `None` limit price for the notional path — quote-notional is
-/// market-only.
+/// Quote-notional is market-only: its only price guard is the
+/// [`MarketProtection`](super::market_protection::MarketProtection)
+/// bound, and its remainder never rests.
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum MatchMode {
     /// Base-quantity match. `limit_price = None` for market orders.
@@ -96,18 +187,21 @@ pub(crate) enum MatchMode {
     QuoteAmount {
         /// Total quote-asset value to consume from the book.
         amount: u128,
+        /// Optional protection price ceiling (Buy) / floor (Sell).
+        limit_price: Option<u128>,
     },
 }
 
 impl MatchMode {
     /// Returns the limit-price guard used inside the level walk. `None`
-    /// for any market path (base-qty market or quote-notional).
+    /// for an unprotected market path (base-qty market or quote-notional).
     #[inline]
     #[must_use]
     fn limit_price(&self) -> Option<u128> {
         match self {
-            Self::BaseQty { limit_price, .. } => *limit_price,
-            Self::QuoteAmount { .. } => None,
+            Self::BaseQty { limit_price, .. } | Self::QuoteAmount { limit_price, .. } => {
+                *limit_price
+            }
         }
     }
 
@@ -153,7 +247,7 @@ impl StopCondition {
             MatchMode::BaseQty { quantity, .. } => Self::BaseQty {
                 remaining: *quantity,
             },
-            MatchMode::QuoteAmount { amount } => Self::QuoteAmount { remaining: *amount },
+            MatchMode::QuoteAmount { amount, .. } => Self::QuoteAmount { remaining: *amount },
         }
     }
 
@@ -239,8 +333,12 @@ where
             limit_price,
             Hash32::zero(),
             TakerKind::Standard,
+            false,
         )
-        .map(|o| o.result)
+        .map(|o| {
+            self.release_filled_extra_fields(&o.result);
+            o.result
+        })
     }
 
     /// Internal matching function with Self-Trade Prevention support.
@@ -276,13 +374,19 @@ where
             limit_price,
             taker_user_id,
             TakerKind::Standard,
+            false,
         )
-        .map(|o| o.result)
+        .map(|o| {
+            self.release_filled_extra_fields(&o.result);
+            o.result
+        })
     }
 
     /// Like [`Self::match_order_with_user`] but returns the full [`MatchOutcome`],
     /// including the STP-cancel signal the resting caller in `add_order` needs to
-    /// avoid resting a self-cross residual (#97).
+    /// avoid resting a self-cross residual (#97). `capture_fills` fills
+    /// [`MatchOutcome::fills`] for callers that will build a `TradeResult`.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn match_order_with_user_outcome(
         &self,
         order_id: Id,
@@ -291,6 +395,7 @@ where
         limit_price: Option<u128>,
         taker_user_id: Hash32,
         taker_kind: TakerKind,
+        capture_fills: bool,
     ) -> Result<MatchOutcome, OrderBookError> {
         self.match_order_inner(
             order_id,
@@ -301,6 +406,7 @@ where
             },
             taker_user_id,
             taker_kind,
+            capture_fills,
         )
     }
 
@@ -309,8 +415,8 @@ where
     /// Public callers reach this through
     /// [`OrderBook::match_market_order_by_amount_with_user`]; the function
     /// here is the matching-loop seam that drives the unified inner loop
-    /// with `MatchMode::QuoteAmount`. Always market-only — there is no
-    /// `limit_price` analogue for notional orders.
+    /// with `MatchMode::QuoteAmount`. Always market-only: `limit_price` is
+    /// only ever the market protection price.
     ///
     /// # Errors
     /// Returns [`OrderBookError::InsufficientLiquidityNotional`] when no
@@ -323,16 +429,21 @@ where
         order_id: Id,
         side: Side,
         amount: u128,
+        limit_price: Option<u128>,
         taker_user_id: Hash32,
-    ) -> Result<MatchResult, OrderBookError> {
+        capture_fills: bool,
+    ) -> Result<MatchOutcome, OrderBookError> {
         self.match_order_inner(
             order_id,
             side,
-            MatchMode::QuoteAmount { amount },
+            MatchMode::QuoteAmount {
+                amount,
+                limit_price,
+            },
             taker_user_id,
             TakerKind::Standard,
+            capture_fills,
         )
-        .map(|o| o.result)
     }
 
     /// Unified matching loop driven by [`MatchMode`] / [`StopCondition`].
@@ -350,6 +461,11 @@ where
     /// admission-time validation in `modifications.rs` and ensures
     /// notional walks never emit `qty=0` trades when budget is below one
     /// full lot.
+    ///
+    /// With `capture_fills` every matched level is snapshotted before it
+    /// is matched (the STP scan's snapshot is reused when STP is active)
+    /// so [`MatchOutcome::fills`] can name each maker's owner and
+    /// remaining size; the sweep pays nothing extra without it.
     fn match_order_inner(
         &self,
         order_id: Id,
@@ -357,20 +473,33 @@ where
         mode: MatchMode,
         taker_user_id: Hash32,
         taker_kind: TakerKind,
+        capture_fills: bool,
     ) -> Result<MatchOutcome, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
         self.cache.invalidate();
         let mut match_result =
             MatchResult::new(order_id, Quantity::new(mode.initial_match_quantity()));
         let mut stop = StopCondition::from_mode(&mode);
         let limit_price = mode.limit_price();
-        let lot = self.lot_size.unwrap_or(1);
+        let lot = self.lot_size().unwrap_or(1);
         // Deterministic taker timestamp for per-level matching: `pricelevel` 0.8's
         // `match_order` no longer reads the wall clock. Computed once so every trade
         // in this submit shares the taker's match time and replay stays deterministic.
         let taker_ts = self.clock().now_millis();
+        // Read once so every level of the sweep is throttled alike.
+        let reserve_fill_limit = self.config().reserve_fill_limit;
 
         // Determine if STP checks are needed for this match
-        let stp_active = self.stp_mode.is_enabled() && taker_user_id != Hash32::zero();
+        // Read once so the whole sweep runs under one STP mode even if it
+        // is changed concurrently.
+        let stp_mode = self.stp_mode();
+        let stp_active = stp_mode.is_enabled() && taker_user_id != Hash32::zero();
+        // The taker's account group is resolved once per sweep.
+        let stp_scope = if stp_active {
+            StpScope::with_groups(taker_user_id, &self.account_groups)
+        } else {
+            StpScope::user(taker_user_id)
+        };
 
         // Choose the appropriate side for matching
         let match_side = match side {
@@ -385,11 +514,6 @@ where
                 .map(MatchOutcome::resting);
         }
 
-        // Use static memory pool for better performance
-        thread_local! {
-            static MATCHING_POOL: MatchingPool = MatchingPool::new();
-        }
-
         // Get reusable vectors from pool. `filled_orders` / `empty_price_levels`
         // are needed by every sweep. `stp_orders` is the per-level STP scan
         // scratch buffer (#107), reused across conflicting levels instead of a
@@ -404,6 +528,7 @@ where
         } else {
             Vec::new()
         };
+        let mut capture = capture_fills.then(|| FillCapture::new(taker_user_id));
 
         // Track whether STP cancelled the taker
         let mut stp_taker_cancelled = false;
@@ -471,6 +596,10 @@ where
                 continue;
             }
 
+            // Hidden quantity resting here before the sweep, to report the
+            // iceberg and reserve orders it replenishes.
+            let hidden_before = hidden_tranches(price_level);
+
             // --- STP pre-processing ---
             // When STP is active, check for self-trade conflicts before matching.
             // This is done per-price-level to handle partial fills correctly.
@@ -488,7 +617,7 @@ where
                 // `(timestamp, seq)`-ordered (the residual gap closed by #132 /
                 // PriceLevel#102).
                 price_level.snapshot_by_seq_into(&mut stp_orders);
-                let action = check_stp_at_level(&stp_orders, taker_user_id, self.stp_mode);
+                let action = check_stp_at_level(&stp_orders, stp_scope, stp_mode);
 
                 match action {
                     STPAction::NoConflict => {
@@ -500,13 +629,13 @@ where
                         if safe_quantity > 0 {
                             let match_qty = qty_cap.min(safe_quantity);
                             if match_qty > 0 {
-                                let price_level_match = price_level.match_order(
+                                let price_level_match = self.match_level(
+                                    price_level,
                                     match_qty,
                                     order_id,
-                                    TimeInForce::Gtc,
                                     taker_kind,
                                     taker_ts,
-                                    &self.transaction_id_generator,
+                                    reserve_fill_limit,
                                 );
                                 let executed = match_qty.saturating_sub(
                                     price_level_match.remaining_quantity().as_u64(),
@@ -519,6 +648,8 @@ where
                                     price_level,
                                     side,
                                     &mut empty_price_levels,
+                                    capture.as_mut(),
+                                    &hidden_before,
                                 );
                                 stop.consume(executed, price);
                             }
@@ -540,7 +671,7 @@ where
                         // order_locations re-resolution either), so level removal stays
                         // with the post-walk empty_price_levels drain (#95).
                         for order in &stp_orders {
-                            if order.user_id() == taker_user_id {
+                            if stp_scope.conflicts_with(order.user_id()) {
                                 self.cancel_resting_maker_on_level(
                                     price_level,
                                     side.opposite(),
@@ -565,13 +696,13 @@ where
                         if safe_quantity > 0 {
                             let match_qty = qty_cap.min(safe_quantity);
                             if match_qty > 0 {
-                                let price_level_match = price_level.match_order(
+                                let price_level_match = self.match_level(
+                                    price_level,
                                     match_qty,
                                     order_id,
-                                    TimeInForce::Gtc,
                                     taker_kind,
                                     taker_ts,
-                                    &self.transaction_id_generator,
+                                    reserve_fill_limit,
                                 );
                                 let executed = match_qty.saturating_sub(
                                     price_level_match.remaining_quantity().as_u64(),
@@ -584,6 +715,8 @@ where
                                     price_level,
                                     side,
                                     &mut empty_price_levels,
+                                    capture.as_mut(),
+                                    &hidden_before,
                                 );
                                 stop.consume(executed, price);
                             }
@@ -607,13 +740,13 @@ where
             }
 
             // --- Normal matching (no STP conflict or after CancelMaker cleanup) ---
-            let price_level_match = price_level.match_order(
+            let price_level_match = self.match_level(
+                price_level,
                 qty_cap,
                 order_id,
-                TimeInForce::Gtc,
                 taker_kind,
                 taker_ts,
-                &self.transaction_id_generator,
+                reserve_fill_limit,
             );
             let executed = qty_cap.saturating_sub(price_level_match.remaining_quantity().as_u64());
 
@@ -625,6 +758,8 @@ where
                 price_level,
                 side,
                 &mut empty_price_levels,
+                capture.as_mut(),
+                &hidden_before,
             );
             stop.consume(executed, price);
 
@@ -637,8 +772,22 @@ where
         // Batch remove empty price levels
         let levels_removed = !empty_price_levels.is_empty();
         for price in &empty_price_levels {
-            match_side.remove(price);
+            self.remove_level_if_empty(side.opposite(), *price);
         }
+        // Invalidate once for the whole walk, dropping anything a
+        // concurrent reader cached from the partially matched book, then
+        // publish the swept side's new best price so the next reader does
+        // not have to recompute it.
+        self.cache.invalidate();
+        let update_seq = self.cache.update_seq();
+        let new_best = match side {
+            Side::Buy => match_side.front(),
+            Side::Sell => match_side.back(),
+        }
+        .map(|entry| *entry.key());
+        self.cache
+            .publish_best_price(side.opposite(), new_best, update_seq);
+        self.refresh_depth_view();
         if levels_removed {
             // Refresh the operational depth gauges now that levels may
             // have been removed. No-op when the `metrics` feature is
@@ -659,6 +808,7 @@ where
                 },
             );
             self.order_locations.remove(filled_id);
+            self.expiry_wheel.unschedule(filled_id);
             self.untrack_order_by_id(filled_id);
         }
 
@@ -686,11 +836,9 @@ where
                     reason: CancelReason::SelfTradePrevention,
                 },
             );
-            crate::orderbook::metrics::record_reject(
-                crate::orderbook::reject_reason::RejectReason::SelfTradePrevention,
-            );
+            self.record_reject(crate::orderbook::reject_reason::RejectReason::SelfTradePrevention);
             return Err(OrderBookError::SelfTradePrevented {
-                mode: self.stp_mode,
+                mode: stp_mode,
                 taker_order_id: order_id,
                 user_id: taker_user_id,
             });
@@ -703,7 +851,7 @@ where
                     quantity,
                     limit_price: None,
                 } => {
-                    crate::orderbook::metrics::record_reject(
+                    self.record_reject(
                         crate::orderbook::reject_reason::RejectReason::InsufficientLiquidity,
                     );
                     return Err(OrderBookError::InsufficientLiquidity {
@@ -712,8 +860,8 @@ where
                         available: 0,
                     });
                 }
-                MatchMode::QuoteAmount { amount } => {
-                    crate::orderbook::metrics::record_reject(
+                MatchMode::QuoteAmount { amount, .. } => {
+                    self.record_reject(
                         crate::orderbook::reject_reason::RejectReason::InsufficientLiquidity,
                     );
                     return Err(OrderBookError::InsufficientLiquidityNotional {
@@ -748,6 +896,7 @@ where
             result: match_result,
             taker_stp_cancelled: stp_taker_cancelled,
             taker_post_only_rejected: post_only_rejected,
+            fills: capture.map(|capture| capture.fills).unwrap_or_default(),
         })
     }
 
@@ -762,7 +911,8 @@ where
             .iter()
             .map(|t| t.quantity().as_u64())
             .fold(0u64, u64::saturating_add);
-        let mut rebuilt = MatchResult::new(order_id, Quantity::new(executed_qty));
+        let mut rebuilt =
+            MatchResult::with_capacity(order_id, Quantity::new(executed_qty), src.trades().len());
         for trade in src.trades().as_vec() {
             // `add_trade` only fails on underflow; with `executed_qty`
             // exactly equal to the sum of trade quantities this cannot
@@ -793,7 +943,7 @@ where
                 quantity,
                 limit_price: None,
             } => {
-                crate::orderbook::metrics::record_reject(
+                self.record_reject(
                     crate::orderbook::reject_reason::RejectReason::InsufficientLiquidity,
                 );
                 Err(OrderBookError::InsufficientLiquidity {
@@ -802,8 +952,8 @@ where
                     available: 0,
                 })
             }
-            MatchMode::QuoteAmount { amount } => {
-                crate::orderbook::metrics::record_reject(
+            MatchMode::QuoteAmount { amount, .. } => {
+                self.record_reject(
                     crate::orderbook::reject_reason::RejectReason::InsufficientLiquidity,
                 );
                 Err(OrderBookError::InsufficientLiquidityNotional {
@@ -831,6 +981,10 @@ where
     /// the maker's per-account `resting_notional` (and `open_count` on
     /// full fill) is decremented. The hook is a no-op when no
     /// `RiskConfig` is installed, matching the rest of the risk plumbing.
+    ///
+    /// `hidden_before` holds the level's hidden tranches as they were
+    /// before the sweep reached it; makers that drew on them are reported
+    /// as replenished.
     #[allow(clippy::too_many_arguments)]
     fn process_level_match(
         &self,
@@ -841,34 +995,56 @@ where
         price_level: &std::sync::Arc<pricelevel::PriceLevel>,
         side: Side,
         empty_price_levels: &mut Vec<u128>,
+        capture: Option<&mut FillCapture>,
+        hidden_before: &[(Id, u64)],
     ) {
         // Process trades if any occurred
         if !price_level_match.trades().as_vec().is_empty() {
             // Update last trade price atomically
             self.last_trade_price.store(price);
             self.has_traded.store(true, Ordering::Relaxed);
+            self.observe_trade_price(price);
 
             // Add trades to result and update per-account risk counters
             // for the maker side of every trade.
-            for trade in price_level_match.trades().as_vec() {
+            let mut capture = capture;
+            let mut last_execution_id = 0;
+            for (index, trade) in price_level_match.trades().as_vec().iter().enumerate() {
                 // add_trade returns Result in v0.7; ignore error since
                 // pricelevel already validated the quantities during matching
-                let _ = match_result.add_trade(*trade);
+                let added = match_result.add_trade(*trade).is_ok();
                 self.risk_state.on_fill(
                     trade.maker_order_id(),
                     trade.quantity().as_u64(),
                     trade.price().as_u128(),
                 );
+                // Keep the captured fills aligned with `match_result`.
+                if added && let Some(capture) = capture.as_deref_mut() {
+                    let (maker_user_id, maker_remaining) =
+                        self.maker_after_fill(price_level_match, price_level, index);
+                    last_execution_id = self.next_execution_id();
+                    capture.record(
+                        trade,
+                        last_execution_id,
+                        self.clock.now_nanos(),
+                        maker_user_id,
+                        maker_remaining,
+                    );
+                }
             }
+            self.last_execution_id
+                .store(last_execution_id, Ordering::Relaxed);
 
             // Notify price level changes
-            if let Some(listener) = &self.price_level_changed_listener {
+            if let Some(listener) = self.price_level_listener() {
                 let engine_seq = self.next_engine_seq();
                 listener(PriceLevelChangedEvent {
                     side: side.opposite(),
                     price: price_level.price(),
                     quantity: price_level.visible_quantity(),
+                    order_count: price_level.order_count(),
                     engine_seq,
+                    config_version: self.config_version(),
                 });
             }
         }
@@ -892,12 +1068,61 @@ where
             filled_orders.push((filled_order_id, filled_quantity));
         }
 
+        self.reconcile_depth(side.opposite(), price_level);
+        self.report_replenishments(
+            price_level_match,
+            price_level,
+            side.opposite(),
+            hidden_before,
+        );
+        // A replenished tranche was re-queued at the back of the level.
+        if !hidden_before.is_empty() {
+            self.enforce_class_priority(price_level);
+        }
+
         // Check if price level is empty and mark for removal
         if price_level.order_count() == 0 {
             empty_price_levels.push(price);
         }
     }
 
+    /// Owner of the maker of `level_match.trades()[index]` and the quantity
+    /// (visible plus hidden) it had left resting right after that trade.
+    ///
+    /// Called before the sweep untracks its filled makers, so the owner is
+    /// still indexed. A maker the level match filled has nothing left once
+    /// its last trade is done; only a partially filled one is looked up on
+    /// the level, which is at most one maker per level in a plain sweep.
+    fn maker_after_fill(
+        &self,
+        level_match: &MatchResult,
+        price_level: &PriceLevel,
+        index: usize,
+    ) -> (Hash32, u64) {
+        let level_trades = level_match.trades().as_vec();
+        let maker_id = level_trades[index].maker_order_id();
+        let filled_later: u64 = level_trades[index + 1..]
+            .iter()
+            .filter(|trade| trade.maker_order_id() == maker_id)
+            .map(|trade| trade.quantity().as_u64())
+            .fold(0, u64::saturating_add);
+        let resting = if level_match.filled_order_ids().contains(&maker_id) {
+            0
+        } else {
+            price_level
+                .iter_orders()
+                .find(|order| order.id() == maker_id)
+                .map_or(0, |order| {
+                    order
+                        .visible_quantity()
+                        .as_u64()
+                        .saturating_add(order.hidden_quantity().as_u64())
+                })
+        };
+        let owner = self.order_owner(&maker_id).unwrap_or_else(Hash32::zero);
+        (owner, resting.saturating_add(filled_later))
+    }
+
     /// Optimized peek match without memory pooling or sorting
     ///
     /// # Performance Optimization
@@ -977,8 +1202,18 @@ where
             return 0;
         }
 
-        let lot = self.lot_size.unwrap_or(1);
-        let stp_active = self.stp_mode.is_enabled() && taker_user_id != Hash32::zero();
+        let lot = self.lot_size().unwrap_or(1);
+        let reserve_fill_limit = self.config().reserve_fill_limit;
+        // Read once so the whole sweep runs under one STP mode even if it
+        // is changed concurrently.
+        let stp_mode = self.stp_mode();
+        let stp_active = stp_mode.is_enabled() && taker_user_id != Hash32::zero();
+        // The taker's account group is resolved once per sweep.
+        let stp_scope = if stp_active {
+            StpScope::with_groups(taker_user_id, &self.account_groups)
+        } else {
+            StpScope::user(taker_user_id)
+        };
 
         let price_iter = match side {
             Side::Buy => Either::Left(price_levels.iter()),
@@ -1027,10 +1262,18 @@ where
                 // feasibility STP decision matches the real match even under
                 // non-monotonic timestamps (#132).
                 let orders = price_level.snapshot_by_insertion_seq();
-                match check_stp_at_level(&orders, taker_user_id, self.stp_mode) {
+                match check_stp_at_level(&orders, stp_scope, stp_mode) {
                     // No self-trade: the whole level is reachable — delegate to the
                     // upstream dry run.
-                    STPAction::NoConflict => (price_level.matchable_quantity(cap, taker_id), false),
+                    STPAction::NoConflict => (
+                        Self::level_matchable_quantity(
+                            price_level,
+                            cap,
+                            taker_id,
+                            reserve_fill_limit,
+                        ),
+                        false,
+                    ),
                     // Same-user makers are cancelled, not filled: only non-self
                     // resting depth is reachable; the walk continues. The upstream
                     // primitive cannot filter by user, so the non-self matchable
@@ -1038,8 +1281,11 @@ where
                     STPAction::CancelMaker => {
                         let non_self: u64 = orders
                             .iter()
-                            .filter(|o| o.user_id() != taker_user_id)
-                            .map(|o| order_matchable_qty(o))
+                            .filter(|o| !stp_scope.conflicts_with(o.user_id()))
+                            .map(|o| match reserve_fill_limit {
+                                Some(limit) => throttled_depth(o, order_matchable_qty(o), limit),
+                                None => order_matchable_qty(o),
+                            })
                             .sum();
                         (non_self, false)
                     }
@@ -1050,7 +1296,10 @@ where
                     | STPAction::CancelBoth { safe_quantity, .. } => (safe_quantity, true),
                 }
             } else {
-                (price_level.matchable_quantity(cap, taker_id), false)
+                (
+                    Self::level_matchable_quantity(price_level, cap, taker_id, reserve_fill_limit),
+                    false,
+                )
             };
 
             matched = matched.saturating_add(cap.min(reachable));
@@ -1062,6 +1311,57 @@ where
         matched
     }
 
+    /// Reachable depth of `level` for fill-or-kill feasibility. Without a
+    /// reserve fill limit this is pricelevel's authoritative dry run; with
+    /// one, each reserve order counts at most the limit, summed per order
+    /// like the `CancelMaker` path.
+    fn level_matchable_quantity(
+        level: &PriceLevel,
+        cap: u64,
+        taker_id: Id,
+        reserve_fill_limit: Option<u64>,
+    ) -> u64 {
+        match reserve_fill_limit {
+            Some(limit) if level.iter_orders().any(|order| is_reserve(&order)) => level
+                .snapshot_by_insertion_seq()
+                .iter()
+                .map(|order| throttled_depth(order, order_matchable_qty(order), limit))
+                .sum(),
+            _ => level.matchable_quantity(cap, taker_id),
+        }
+    }
+
+    /// Whether an immediate `side` order for `quantity`, limited to
+    /// `limit_price` (`None` for a market sweep), would fill in full —
+    /// the fill-or-kill admission check, run without touching the book.
+    ///
+    /// Unlike [`Self::peek_match`] it counts only what the sweep would
+    /// actually fill: `lot_size` rounding and drawable reserve depth
+    /// included. Bypasses STP; use [`Self::can_fill_fully_with_user`] to
+    /// leave out the liquidity STP would withhold from a user.
+    ///
+    /// The answer holds only while the book is unchanged; a concurrent
+    /// submit may consume the depth it counted.
+    #[must_use]
+    pub fn can_fill_fully(&self, quantity: u64, side: Side, limit_price: Option<u128>) -> bool {
+        self.can_fill_fully_with_user(quantity, side, limit_price, Hash32::zero())
+    }
+
+    /// Like [`Self::can_fill_fully`], for an order owned by `user_id`:
+    /// under the book's STP mode, makers of the same user count as the
+    /// sweep would treat them — skipped when cancelled, and ending the
+    /// sweep when the taker would be cancelled.
+    #[must_use]
+    pub fn can_fill_fully_with_user(
+        &self,
+        quantity: u64,
+        side: Side,
+        limit_price: Option<u128>,
+        user_id: Hash32,
+    ) -> bool {
+        self.fok_fillable_quantity(side, quantity, limit_price, user_id, Id::nil()) >= quantity
+    }
+
     /// Batch operation for multiple order matches (additional optimization)
     pub fn match_orders_batch(
         &self,
@@ -17,6 +17,18 @@
 //!   of distinct price levels on each side.
 //! - `orderbook_trades_total` — counter, incremented exactly once per
 //!   emitted trade transaction (a `MatchResult` may contain several).
+//! - `orderbook_journal_append_seconds` — histogram of `FileJournal`
+//!   append latency, flush included.
+//! - `orderbook_journal_bytes_written_total` — counter of bytes appended
+//!   to the journal, entry framing included.
+//! - `orderbook_journal_rotations_total` — counter of segment rotations.
+//! - `orderbook_journal_segment_utilization` — gauge, fraction (0.0 to
+//!   1.0) of the active segment in use after the latest append.
+//! - `orderbook_journal_fsync_lag_seconds` — gauge, how long the latest
+//!   append waited for its flush.
+//! - `orderbook_journal_damaged_entries_total{kind="…"}` — counter of
+//!   damaged entries met: `corrupt` (CRC mismatch), `undecodable` (bad
+//!   header or payload) or `torn_tail` (cut off on open).
 //!
 //! # Determinism
 //!
@@ -43,6 +55,34 @@ pub const DEPTH_LEVELS_ASK: &str = "orderbook_depth_levels_ask";
 /// Counter name: monotonic count of every emitted trade transaction.
 pub const TRADES_TOTAL: &str = "orderbook_trades_total";
 
+/// Histogram name: journal append latency in seconds, flush included.
+pub const JOURNAL_APPEND_SECONDS: &str = "orderbook_journal_append_seconds";
+
+/// Counter name: bytes appended to the journal, entry framing included.
+pub const JOURNAL_BYTES_WRITTEN_TOTAL: &str = "orderbook_journal_bytes_written_total";
+
+/// Counter name: journal segment rotations.
+pub const JOURNAL_ROTATIONS_TOTAL: &str = "orderbook_journal_rotations_total";
+
+/// Gauge name: fraction of the active journal segment in use.
+pub const JOURNAL_SEGMENT_UTILIZATION: &str = "orderbook_journal_segment_utilization";
+
+/// Gauge name: seconds the latest journal append waited for its flush.
+pub const JOURNAL_FSYNC_LAG_SECONDS: &str = "orderbook_journal_fsync_lag_seconds";
+
+/// Counter name: damaged journal entries met, labelled by kind.
+pub const JOURNAL_DAMAGED_ENTRIES_TOTAL: &str = "orderbook_journal_damaged_entries_total";
+
+/// `kind` label of a journal entry whose CRC did not match.
+pub const JOURNAL_DAMAGE_CORRUPT: &str = "corrupt";
+
+/// `kind` label of a journal entry whose header or payload could not be
+/// decoded.
+pub const JOURNAL_DAMAGE_UNDECODABLE: &str = "undecodable";
+
+/// `kind` label of a torn journal tail entry cut off on open.
+pub const JOURNAL_DAMAGE_TORN_TAIL: &str = "torn_tail";
+
 /// Record an order rejection.
 ///
 /// Increments `orderbook_rejects_total` by 1 with the
@@ -97,11 +137,71 @@ pub fn record_trades(n: u64) {
 #[cfg(not(feature = "metrics"))]
 pub fn record_trades(_n: u64) {}
 
+/// Record a journal append of `bytes` that took `latency`, of which
+/// `fsync` was spent flushing, leaving the active segment `utilization`
+/// full.
+///
+/// Compiles to a no-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(feature = "metrics")]
+pub fn record_journal_append(
+    bytes: u64,
+    latency: std::time::Duration,
+    fsync: std::time::Duration,
+    utilization: f64,
+) {
+    metrics::histogram!(JOURNAL_APPEND_SECONDS).record(latency.as_secs_f64());
+    metrics::counter!(JOURNAL_BYTES_WRITTEN_TOTAL).increment(bytes);
+    metrics::gauge!(JOURNAL_SEGMENT_UTILIZATION).set(utilization);
+    metrics::gauge!(JOURNAL_FSYNC_LAG_SECONDS).set(fsync.as_secs_f64());
+}
+
+/// No-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(not(feature = "metrics"))]
+pub fn record_journal_append(
+    _bytes: u64,
+    _latency: std::time::Duration,
+    _fsync: std::time::Duration,
+    _utilization: f64,
+) {
+}
+
+/// Record a journal segment rotation.
+///
+/// Compiles to a no-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(feature = "metrics")]
+pub fn record_journal_rotation() {
+    metrics::counter!(JOURNAL_ROTATIONS_TOTAL).increment(1);
+}
+
+/// No-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(not(feature = "metrics"))]
+pub fn record_journal_rotation() {}
+
+/// Record a damaged journal entry of `kind`, one of the
+/// `JOURNAL_DAMAGE_*` labels.
+///
+/// Compiles to a no-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(feature = "metrics")]
+pub fn record_journal_damaged_entry(kind: &'static str) {
+    metrics::counter!(JOURNAL_DAMAGED_ENTRIES_TOTAL, "kind" => kind).increment(1);
+}
+
+/// No-op when the `metrics` feature is disabled.
+#[inline]
+#[cfg(not(feature = "metrics"))]
+pub fn record_journal_damaged_entry(_kind: &'static str) {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
-    /// All four call-sites must compile and run without panicking
+    /// All call-sites must compile and run without panicking
     /// regardless of feature state. The actual counter behaviour is
     /// covered by `tests/metrics/` (feature-gated).
     #[test]
@@ -112,5 +212,8 @@ mod tests {
         record_depth(3, 5);
         record_trades(0);
         record_trades(4);
+        record_journal_append(64, Duration::from_micros(3), Duration::from_micros(2), 0.5);
+        record_journal_rotation();
+        record_journal_damaged_entry(JOURNAL_DAMAGE_CORRUPT);
     }
 }
@@ -0,0 +1,420 @@
+//! Midpoint (dark) segment.
+//!
+//! Alongside the lit price levels, each book keeps a segment of unpriced
+//! [`MidpointOrder`]s. They never appear in depth, the best bid and offer
+//! or any lit query. A midpoint order executes only against contra
+//! midpoint orders, at the lit midpoint at that moment:
+//! `(best_bid + best_ask) / 2`, rounded down to the price unit. Books that
+//! need half-tick midpoints should use a [`PriceScale`](super::price_scale::PriceScale)
+//! with at least one extra digit. Without both sides lit there is no
+//! midpoint, so nothing executes and new orders rest.
+//!
+//! Matching is in time priority and runs when an order arrives. Resting
+//! orders that could not match (a limit outside the midpoint, a minimum
+//! execution size that does not fit) are re-crossed on demand with
+//! [`OrderBook::cross_midpoint_orders`], for instance after the lit
+//! midpoint moves.
+//!
+//! - [`MidpointOrder::with_min_execution_quantity`] sets a minimum size for
+//!   every execution of the order. Once its remaining quantity falls
+//!   below the minimum, the remaining quantity is the minimum.
+//! - [`MidpointOrder::with_limit_price`] bounds the midpoint the order
+//!   accepts.
+//! - Self-trade prevention skips contra orders of the same user or account
+//!   group instead of cancelling anything.
+//!
+//! Midpoint executions are reported through the trade listener as ordinary
+//! [`TradeResult`]s with [`TradeResult::dark`] set. They do not move the
+//! last trade price, the book statistics or the circuit breaker, and the
+//! orders are outside the risk layer and the order state tracker. Resting
+//! midpoint orders travel with snapshot packages.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::stp::StpScope;
+use super::trade::{FillParty, LiquidityFlag, TradeFill, TradeResult};
+use pricelevel::{Hash32, Id, MatchResult, Price, Quantity, Side, Trade};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{MutexGuard, PoisonError};
+use tracing::trace;
+
+/// An unpriced order of the midpoint segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MidpointOrder {
+    /// Order identifier, unique across the lit book and the segment.
+    pub id: Id,
+    /// Buy or sell.
+    pub side: Side,
+    /// Quantity still to execute.
+    pub quantity: u64,
+    /// Minimum quantity of any single execution; `0` for none.
+    pub min_execution_quantity: u64,
+    /// Highest midpoint a buy accepts, or lowest a sell accepts.
+    pub limit_price: Option<u128>,
+    /// Owner, for self-trade prevention.
+    pub user_id: Hash32,
+    /// When the order was accepted, in milliseconds from the book clock.
+    /// Stamped by [`OrderBook::submit_midpoint_order`].
+    pub timestamp: u64,
+}
+
+impl MidpointOrder {
+    /// An anonymous order for `quantity` with no minimum size or limit.
+    #[must_use]
+    pub fn new(id: Id, side: Side, quantity: u64) -> Self {
+        Self {
+            id,
+            side,
+            quantity,
+            min_execution_quantity: 0,
+            limit_price: None,
+            user_id: Hash32::zero(),
+            timestamp: 0,
+        }
+    }
+
+    /// Require every execution to be at least `quantity`.
+    #[must_use]
+    pub fn with_min_execution_quantity(mut self, quantity: u64) -> Self {
+        self.min_execution_quantity = quantity;
+        self
+    }
+
+    /// Only execute while the midpoint is at or better than `price`.
+    #[must_use]
+    pub fn with_limit_price(mut self, price: u128) -> Self {
+        self.limit_price = Some(price);
+        self
+    }
+
+    /// Set the owner.
+    #[must_use]
+    pub fn with_user_id(mut self, user_id: Hash32) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    /// `true` if the order may execute at `midpoint`.
+    #[must_use]
+    pub fn accepts(&self, midpoint: u128) -> bool {
+        match (self.limit_price, self.side) {
+            (None, _) => true,
+            (Some(limit), Side::Buy) => midpoint <= limit,
+            (Some(limit), Side::Sell) => midpoint >= limit,
+        }
+    }
+
+    /// The smallest execution the order takes now.
+    fn min_fill(&self) -> u64 {
+        self.min_execution_quantity.min(self.quantity).max(1)
+    }
+}
+
+/// Resting midpoint orders per side, in time priority.
+#[derive(Debug, Default)]
+pub(crate) struct MidpointSegment {
+    bids: VecDeque<MidpointOrder>,
+    asks: VecDeque<MidpointOrder>,
+}
+
+impl MidpointSegment {
+    fn side_mut(&mut self, side: Side) -> &mut VecDeque<MidpointOrder> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+
+    fn contains(&self, id: Id) -> bool {
+        self.bids
+            .iter()
+            .chain(&self.asks)
+            .any(|order| order.id == id)
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn midpoint_segment(&self) -> MutexGuard<'_, MidpointSegment> {
+        self.midpoint.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The price midpoint orders execute at now: the lit midpoint rounded
+    /// down, or `None` unless both sides are lit.
+    #[must_use]
+    pub fn midpoint_price(&self) -> Option<u128> {
+        self.bbo().midpoint()
+    }
+
+    /// Submit an order to the midpoint segment. It executes against
+    /// resting contra midpoint orders at [`Self::midpoint_price`], and any
+    /// remaining quantity rests. Returns the executions, if any, which the
+    /// trade listener also receives.
+    ///
+    /// # Errors
+    ///
+    /// The kill switch, a blocked user and a trading phase that does not
+    /// trade reject the order as for a market order. A zero quantity or a
+    /// minimum execution size above the quantity is
+    /// [`OrderBookError::InvalidMidpointQuantity`], an id already resting in the
+    /// lit book or the segment is [`OrderBookError::DuplicateOrderId`], and
+    /// an anonymous order while self-trade prevention is enabled is
+    /// [`OrderBookError::MissingUserId`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{MidpointOrder, OrderBook};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    /// book.add_limit_order(Id::new_uuid(), 110, 5, Side::Sell, TimeInForce::Gtc, None)?;
+    ///
+    /// let seller = Id::new_uuid();
+    /// assert!(book.submit_midpoint_order(MidpointOrder::new(seller, Side::Sell, 50))?.is_none());
+    /// let trade = book
+    ///     .submit_midpoint_order(MidpointOrder::new(Id::new_uuid(), Side::Buy, 20))?
+    ///     .expect("crosses at the midpoint");
+    /// assert!(trade.dark);
+    /// assert_eq!(trade.fills[0].price, 105);
+    /// assert_eq!(book.midpoint_orders(Side::Sell)[0].quantity, 30);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn submit_midpoint_order(
+        &self,
+        mut order: MidpointOrder,
+    ) -> Result<Option<TradeResult>, OrderBookError> {
+        let _gate = self.submit_gate_read();
+        self.check_kill_switch_or_reject(order.id)?;
+        self.check_user_blocked_or_reject(order.id, order.user_id)?;
+        if order.quantity == 0 || order.min_execution_quantity > order.quantity {
+            return Err(OrderBookError::InvalidMidpointQuantity {
+                order_id: order.id,
+                quantity: order.quantity,
+                min_execution_quantity: order.min_execution_quantity,
+            });
+        }
+        if self.stp_mode().is_enabled() && order.user_id == Hash32::zero() {
+            return Err(OrderBookError::MissingUserId { order_id: order.id });
+        }
+        self.check_session_or_reject(order.id)?;
+        trace!(
+            "Order book {}: Midpoint order {} {} {}",
+            self.symbol, order.id, order.side, order.quantity
+        );
+
+        let mut segment = self.midpoint_segment();
+        if self.order_locations.contains_key(&order.id) || segment.contains(order.id) {
+            return Err(OrderBookError::DuplicateOrderId { order_id: order.id });
+        }
+        order.timestamp = self.clock().now_millis().as_u64();
+        let trade = self
+            .midpoint_price()
+            .and_then(|mid| self.match_midpoint(&mut order, &mut segment, mid));
+        if order.quantity > 0 {
+            segment.side_mut(order.side).push_back(order);
+        }
+        drop(segment);
+        Ok(trade.map(|trade| self.publish_midpoint_trade(trade)))
+    }
+
+    /// Re-cross resting midpoint orders at the current midpoint, for
+    /// orders whose limit or minimum size kept them from matching before.
+    /// Buys take in time priority. Returns the executions, which the trade
+    /// listener also receives.
+    pub fn cross_midpoint_orders(&self) -> Vec<TradeResult> {
+        let _gate = self.submit_gate_read();
+        let Some(mid) = self.midpoint_price() else {
+            return Vec::new();
+        };
+        if self.check_session(|| true).is_err() {
+            return Vec::new();
+        }
+        let mut trades = Vec::new();
+        let mut segment = self.midpoint_segment();
+        let mut index = 0;
+        while index < segment.bids.len() {
+            let mut bid = segment.bids[index];
+            if let Some(trade) = self.match_midpoint(&mut bid, &mut segment, mid) {
+                trades.push(trade);
+            }
+            if bid.quantity == 0 {
+                segment.bids.remove(index);
+            } else {
+                segment.bids[index] = bid;
+                index += 1;
+            }
+        }
+        drop(segment);
+        trades
+            .into_iter()
+            .map(|trade| self.publish_midpoint_trade(trade))
+            .collect()
+    }
+
+    /// Cancel a resting midpoint order, returning it.
+    pub fn cancel_midpoint_order(&self, order_id: Id) -> Option<MidpointOrder> {
+        let mut segment = self.midpoint_segment();
+        for side in [Side::Buy, Side::Sell] {
+            let orders = segment.side_mut(side);
+            if let Some(index) = orders.iter().position(|order| order.id == order_id) {
+                return orders.remove(index);
+            }
+        }
+        None
+    }
+
+    /// The resting midpoint orders on `side`, in time priority.
+    #[must_use]
+    pub fn midpoint_orders(&self, side: Side) -> Vec<MidpointOrder> {
+        let mut segment = self.midpoint_segment();
+        segment.side_mut(side).iter().copied().collect()
+    }
+
+    /// Every resting midpoint order, bids then asks, each in time priority.
+    pub(super) fn all_midpoint_orders(&self) -> Vec<MidpointOrder> {
+        let segment = self.midpoint_segment();
+        segment.bids.iter().chain(&segment.asks).copied().collect()
+    }
+
+    /// Replace the segment with `orders`, keeping their order per side.
+    pub(super) fn restore_midpoint_orders(&self, orders: Vec<MidpointOrder>) {
+        let mut segment = self.midpoint_segment();
+        *segment = MidpointSegment::default();
+        for order in orders {
+            segment.side_mut(order.side).push_back(order);
+        }
+    }
+
+    /// Execute `taker` against the contra side of `segment` at `mid`,
+    /// reducing both. Returns the trade result, unpublished, when anything
+    /// executed.
+    fn match_midpoint(
+        &self,
+        taker: &mut MidpointOrder,
+        segment: &mut MidpointSegment,
+        mid: u128,
+    ) -> Option<TradeResult> {
+        if !taker.accepts(mid) {
+            return None;
+        }
+        let stp_active = self.stp_mode().is_enabled() && taker.user_id != Hash32::zero();
+        let scope = StpScope::with_groups(taker.user_id, &self.account_groups);
+        let timestamp = self.clock().now_millis();
+        let mut match_result = MatchResult::new(taker.id, Quantity::new(taker.quantity));
+        let mut fills = Vec::new();
+        let contra = segment.side_mut(taker.side.opposite());
+        let mut index = 0;
+        while index < contra.len() && taker.quantity > 0 {
+            let maker = &mut contra[index];
+            let quantity = taker.quantity.min(maker.quantity);
+            if !maker.accepts(mid)
+                || (stp_active && scope.conflicts_with(maker.user_id))
+                || quantity < taker.min_fill()
+                || quantity < maker.min_fill()
+            {
+                index += 1;
+                continue;
+            }
+            let trade = Trade::with_timestamp(
+                Id::from_uuid(self.transaction_id_generator.next()),
+                taker.id,
+                maker.id,
+                Price::new(mid),
+                Quantity::new(quantity),
+                taker.side,
+                timestamp,
+            );
+            if match_result.add_trade(trade).is_err() {
+                break;
+            }
+            taker.quantity -= quantity;
+            maker.quantity -= quantity;
+            fills.push(TradeFill {
+                trade_id: trade.trade_id(),
+                execution_id: self.next_execution_id(),
+                matched_at_ns: self.clock().now_nanos(),
+                price: mid,
+                quantity,
+                maker: FillParty {
+                    order_id: maker.id,
+                    user_id: maker.user_id,
+                    side: maker.side,
+                    liquidity: LiquidityFlag::Maker,
+                    fee: 0,
+                },
+                taker: FillParty {
+                    order_id: taker.id,
+                    user_id: taker.user_id,
+                    side: taker.side,
+                    liquidity: LiquidityFlag::Taker,
+                    fee: 0,
+                },
+                maker_remaining_quantity: maker.quantity,
+            });
+            if maker.quantity == 0 {
+                match_result.add_filled_order_id(maker.id);
+                contra.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        if fills.is_empty() {
+            return None;
+        }
+        let mut trade_result = self.build_trade_result(match_result, fills);
+        trade_result.dark = true;
+        Some(trade_result)
+    }
+
+    /// Hand a midpoint execution to the trade listener.
+    fn publish_midpoint_trade(&self, trade_result: TradeResult) -> TradeResult {
+        if let Some(listener) = self.trade_listener() {
+            listener(&trade_result);
+        }
+        trade_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn midpoint_rounds_down_without_overflow() {
+        let book = OrderBook::<()>::new("TEST");
+        assert_eq!(book.midpoint_price(), None);
+        for (id, price, side) in [(1, 101, Side::Buy), (2, 104, Side::Sell)] {
+            assert!(
+                book.add_limit_order(
+                    Id::from_u64(id),
+                    price,
+                    1,
+                    side,
+                    pricelevel::TimeInForce::Gtc,
+                    None
+                )
+                .is_ok()
+            );
+        }
+        assert_eq!(book.midpoint_price(), Some(102));
+    }
+
+    #[test]
+    fn min_execution_quantity_shrinks_with_the_remainder() {
+        let order =
+            MidpointOrder::new(Id::from_u64(1), Side::Buy, 3).with_min_execution_quantity(5);
+        assert_eq!(order.min_fill(), 3);
+        assert_eq!(
+            MidpointOrder::new(Id::from_u64(2), Side::Buy, 3).min_fill(),
+            1
+        );
+        let limited = MidpointOrder::new(Id::from_u64(3), Side::Sell, 3).with_limit_price(100);
+        assert!(limited.accepts(100));
+        assert!(!limited.accepts(99));
+    }
+}
@@ -1,9 +1,27 @@
 //! OrderBook implementation for managing multiple price levels and order matching.
 
+/// Structural invariant auditor.
+pub mod audit;
 pub mod book;
+/// Fluent construction of a fully configured order book.
+pub mod builder;
+/// Heartbeat-driven cancel-on-disconnect.
+pub mod cancel_on_disconnect;
+/// Warm-start preallocation and occupancy introspection.
+pub mod capacity;
+/// Circuit breaker halting the book on fast price moves.
+pub mod circuit_breaker;
 /// Pluggable timestamp source for the matching core.
 pub mod clock;
+/// Consolidated best-price view across several books.
+pub mod consolidated;
+/// Venue-wide dead-man's switch for book managers.
+pub mod dead_mans_switch;
 pub mod error;
+/// Rotating JSON Lines / CSV file sink for trades and book changes.
+pub mod event_log;
+/// Thread-per-book command execution with per-symbol queues.
+pub mod executor;
 /// Implied volatility calculation from order book prices.
 pub mod implied_volatility;
 /// Functional-style iterators for order book analysis.
@@ -13,21 +31,38 @@ pub mod manager;
 /// Market impact simulation and liquidity analysis.
 pub mod market_impact;
 pub mod matching;
+/// Midpoint (dark) segment: unpriced orders executing at the lit midpoint.
+pub mod midpoint;
+/// Priority classes ranking participants within a price level.
+pub mod priority_class;
 /// Aggregate statistics for order book analysis.
 pub mod statistics;
 
 /// Self-Trade Prevention (STP) types and logic.
 pub mod stp;
 
+mod account_groups;
 /// Price level change events for real-time order book updates.
 pub mod book_change_event;
 mod cache;
+/// Visible-only or total depth in the analytics API.
+pub mod depth_mode;
+/// Compact, allocation-free best-N depth quote.
+pub mod depth_quote;
+mod depth_totals;
+mod depth_view;
+mod level_gate;
+mod listener_slot;
 /// Contains the core logic for modifying the order book state, such as adding, canceling, or updating orders.
 pub mod modifications;
+/// Unified modification of a resting order's price, size and time in force.
+pub mod modify;
 pub mod operations;
 mod pool;
 mod private;
+mod reserve_throttle;
 pub mod snapshot;
+mod sync;
 mod tests;
 /// Enhanced trade result that includes symbol information
 pub mod trade;
@@ -35,24 +70,111 @@ pub mod trade;
 /// Fee schedule implementation for trading fees
 pub mod fees;
 
+/// Per-user, per-symbol fee accrual ledger.
+pub mod fee_ledger;
+
 /// Mass cancel operations for bulk order removal.
 pub mod mass_cancel;
 
+/// Trade busts and corrections against a bounded execution history.
+pub mod trade_amendment;
+
+/// Price-range sharded price level map backing each side of the book.
+pub mod level_map;
+
+/// Reuse of emptied price levels.
+pub mod level_pool;
+
+/// Hierarchical timing wheel of resting order deadlines.
+pub mod expiry_wheel;
+
+/// Runtime-reloadable validation, fee and risk parameters.
+pub mod book_config;
+
+/// Sweep limits for market orders ("market with protection").
+pub mod market_protection;
+
+/// Reject-or-slide handling of crossing post-only orders.
+pub mod post_only;
+
+/// Cumulative per-book activity counters.
+pub mod book_stats;
+
 /// Operational Prometheus-style metrics hooks (feature-gated).
 pub mod metrics;
 
+/// Time-windowed duplicate order id detection.
+pub mod order_id_dedup;
+
+/// Submission acknowledgements with the resting state of an order.
+pub mod order_ack;
+
+/// Resting order ages and quote-lifetime distributions.
+pub mod order_age;
+
 /// Order state machine for explicit lifecycle tracking.
 pub mod order_state;
 
+/// Application-defined order tags and tag-based mass operations.
+pub mod order_tag;
+
 /// Closed-taxonomy reject reasons surfaced on `OrderStatus::Rejected`.
 pub mod reject_reason;
 
+/// Iceberg and reserve replenishment notifications.
+pub mod replenishment;
+
+/// Perpetual futures funding rate and mark price tracking.
+pub mod perp;
+
+/// Per-symbol decimal scale of raw prices and quantities.
+pub mod price_scale;
+
+/// Per-user and book-wide token-bucket rate limiting of new flow.
+pub mod rate_limit;
+
+/// Externally supplied index and mark prices with staleness tracking.
+pub mod reference_price;
+
+/// Historical replay of recorded market data with interleaved strategy orders.
+pub mod replay;
+
+/// Front-of-queue inspection at a price level.
+pub mod queue_peek;
+
+/// Snapshot-consistent read transactions over the top levels of a book.
+pub mod read_txn;
+
+/// User-scoped resting order queries.
+pub mod user_queries;
+
+/// Per-user spread and time-at-BBO monitoring for market-maker obligations.
+pub mod quote_quality;
+
 /// Pre-trade risk layer: per-account counters, configurable limits.
 pub mod risk;
 
+/// Smart order routing simulation across venues.
+pub mod router;
+
+/// Daily trading session schedule with automatic open and close.
+pub mod session;
+
 /// Pluggable event serialization for NATS publishers and consumers.
 pub mod serialization;
 
+/// Arrow and Parquet export of trades, snapshots and depth series.
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// Per-symbol HDR histograms of executor command latency.
+#[cfg(feature = "latency")]
+pub mod latency;
+
+/// Float-free `*_fixed` analytics returning exact fractions.
+#[cfg(feature = "deterministic")]
+pub mod fixed_point;
+
 /// NATS JetStream trade event publisher.
 #[cfg(feature = "nats")]
 pub mod nats;
@@ -65,38 +187,128 @@ pub mod nats_book_change;
 #[cfg(feature = "special_orders")]
 pub mod repricing;
 
+/// Selectable reference price for stop order triggers.
+#[cfg(feature = "special_orders")]
+pub mod stop_trigger;
+
 /// Sequencer subsystem: types, journal trait, and file-based journal.
 pub mod sequencer;
 
+/// Synthetic order flow and agent-based market simulation.
+pub mod simulation;
+
+/// Speed bump delaying the matching of aggressive orders.
+pub mod speed_bump;
+
+/// Coordinated graceful shutdown across executors, publishers and journals.
+pub mod shutdown;
+
+#[cfg(feature = "arrow")]
+pub use self::arrow::{ArrowExportError, DepthRecorder, DepthSample};
+pub use audit::InvariantViolation;
 pub use book::OrderBook;
+pub use book_config::BookConfig;
+pub use book_stats::BookStats;
+pub use builder::OrderBookBuilder;
+pub use cache::TopOfBook;
+pub use cancel_on_disconnect::{DisconnectEvent, DisconnectListener};
+pub use capacity::{CapacityStats, WARM_MATCHING_BUFFERS};
+pub use circuit_breaker::{
+    CircuitBreakerAction, CircuitBreakerConfig, CircuitBreakerEvent, CircuitBreakerListener,
+};
 pub use clock::{Clock, MonotonicClock, StubClock};
-pub use error::{ManagerError, OrderBookError};
-pub use fees::{FeeOverflow, FeeSchedule};
+pub use consolidated::{ConsolidatedBook, ConsolidatedLevel, VenueLevel};
+pub use dead_mans_switch::{DeadMansSwitch, DeadMansSwitchTrip};
+pub use depth_mode::DepthMode;
+pub use depth_quote::{DepthQuote, QuoteLevel};
+pub use error::{ManagerError, OrderBookError, RejectCode};
+pub use event_log::{EventLogConfig, EventLogError, EventLogFormat, EventLogSink};
+pub use expiry_wheel::ExpiryWheelStats;
+pub use fee_ledger::{FeeLedger, FeeLedgerEntry, FeeSummary};
+pub use fees::{
+    DEFAULT_FEE_VOLUME_WINDOW_MS, FeeOverflow, FeeSchedule, FeeTier, TieredFeeSchedule,
+};
+#[cfg(feature = "deterministic")]
+pub use fixed_point::{FIXED_BASIS_POINTS_MULTIPLIER, PriceRatio, SignedRatio};
 pub use implied_volatility::{
-    BlackScholes, IVConfig, IVError, IVParams, IVQuality, IVResult, OptionType, PriceSource,
-    SolverConfig,
+    Binomial, BlackScholes, DEFAULT_BINOMIAL_STEPS, ExerciseStyle, Greeks, GreeksEntry,
+    GreeksSnapshot, IVConfig, IVError, IVParams, IVQuality, IVResult, IVSurface, IVSurfacePoint,
+    LegQuote, OptionType, ParityArbitrage, ParityParams, ParityReport, ParityStrategy, PriceSource,
+    SolverConfig, SurfaceFailure, SurfaceIV, SurfaceInput,
 };
-pub use iterators::LevelInfo;
-pub use market_impact::{MarketImpact, OrderSimulation};
+pub use iterators::{LadderRow, LevelInfo, OrderCursor, OrdersPage};
+#[cfg(feature = "latency")]
+pub use latency::{LatencyConfig, LatencyRecorder, LatencySummary, SymbolLatency};
+pub use level_map::{LevelRange, PriceLevelMap};
+pub use level_pool::{DEFAULT_LEVEL_POOL_SIZE, LevelPoolStats};
+pub use market_impact::{LiquidationPoint, MarketImpact, OrderSimulation};
+pub use market_protection::{MarketProtection, ProtectionRemainder};
 pub use mass_cancel::MassCancelResult;
+pub use midpoint::MidpointOrder;
+pub use modify::OrderModification;
 #[cfg(feature = "nats")]
 pub use nats::NatsTradePublisher;
 #[cfg(feature = "nats")]
 pub use nats_book_change::{BookChangeBatch, BookChangeEntry, NatsBookChangePublisher};
+pub use order_ack::{OrderAck, RestingInfo};
+pub use order_age::LevelAge;
+pub use order_id_dedup::OrderIdDedup;
 pub use order_state::{CancelReason, OrderStateListener, OrderStateTracker, OrderStatus};
+pub use order_tag::OrderTag;
+pub use perp::{
+    DEFAULT_FUNDING_INTERVAL_MS, FundingConfig, FundingSettlement, MAX_CATCH_UP_SETTLEMENTS,
+    PerpSnapshot, PerpTracker,
+};
+pub use post_only::PostOnlyMode;
+pub use price_scale::{MAX_SCALE_DECIMALS, PriceScale};
+pub use priority_class::PriorityClass;
+pub use queue_peek::QueueEntry;
+pub use quote_quality::{QuoteQualityMonitor, QuoteQualityStats};
+pub use rate_limit::{RateLimitConfig, RateLimiter, TokenBucketConfig};
+pub use read_txn::{DEFAULT_READ_TXN_DEPTH, ReadTxn};
+pub use reference_price::{ExternalPrice, ExternalPriceSource};
 pub use reject_reason::RejectReason;
+pub use replay::{
+    CsvMarketData, MarketDataEvent, MarketReplay, MarketReplayError, ReplayClock, ReplayReport,
+    StrategyAction, StrategyOutcome,
+};
+pub use replenishment::{ReplenishmentEvent, ReplenishmentListener};
 #[cfg(feature = "special_orders")]
 pub use repricing::{RepricingOperations, RepricingResult, SpecialOrderTracker};
 pub use risk::{ReferencePriceSource, RiskConfig, RiskState};
+pub use router::{
+    ChildFill, ChildOrder, RoutingPlan, RoutingReport, RoutingStrategy, SmartOrderRouter,
+};
+pub use sequencer::journal::{Journal, JournalEntry, JournalFilter};
 #[cfg(feature = "journal")]
-pub use sequencer::FileJournal;
-pub use sequencer::journal::{Journal, JournalEntry};
-pub use sequencer::{JournalError, SequencerCommand, SequencerEvent, SequencerResult};
+pub use sequencer::{AppendLatency, FileJournal, JournalHealth, JournalReadOptions};
+pub use sequencer::{
+    JournalError, SequencedResult, SequencerCommand, SequencerCommandKind, SequencerEvent,
+    SequencerResult,
+};
 #[cfg(feature = "bincode")]
 pub use serialization::BincodeEventSerializer;
 pub use serialization::{EventSerializer, JsonEventSerializer, SerializationError};
+pub use session::{MILLIS_PER_DAY, SessionClose, SessionPhase, TradingSession};
+pub use shutdown::{
+    ShutdownCoordinator, ShutdownHandle, ShutdownReport, ShutdownStage, ShutdownStepError,
+};
+pub use simulation::{
+    AgentAction, AgentContext, AgentLatency, AgentPosition, AgentReport, AgentStats,
+    ArrivalProcess, DEFAULT_MAX_ACTIONS_PER_TICK, ExecutionAlgo, ExecutionHandle, ExecutionReport,
+    ExecutionSchedule, LatencyModel, MarketSimulator, SimulatedAction, SimulatedEvent,
+    SimulationConfig, SimulationReport, SimulationRunner, SizeDistribution, Strategy,
+};
 pub use snapshot::{
-    EnrichedSnapshot, MetricFlags, ORDERBOOK_SNAPSHOT_FORMAT_VERSION,
-    ORDERBOOK_SNAPSHOT_MIN_READ_VERSION, OrderBookSnapshot, OrderBookSnapshotPackage,
+    EnrichedSnapshot, LevelAggregate, LevelAggregates, MetricFlags,
+    ORDERBOOK_SNAPSHOT_FORMAT_VERSION, ORDERBOOK_SNAPSHOT_MIN_READ_VERSION, OrderBookSnapshot,
+    OrderBookSnapshotPackage, VenueSnapshot,
 };
-pub use statistics::{DepthStats, DistributionBin};
+pub use speed_bump::{BumpedOrder, SpeedBump, SpeedBumpAdmission, SpeedBumpRelease};
+pub use statistics::{
+    DepthStats, DistributionBin, LIFETIME_BUCKETS, LifetimeDistribution, QuoteLifetimeStats,
+};
+#[cfg(feature = "special_orders")]
+pub use stop_trigger::StopTriggerReference;
+pub use trade_amendment::{DEFAULT_EXECUTION_HISTORY_CAPACITY, ExecutionHistory};
+pub use user_queries::UserOrderSummary;
@@ -1,15 +1,29 @@
 use crate::orderbook::book::OrderBook;
 use crate::orderbook::book_change_event::PriceLevelChangedEvent;
+use crate::orderbook::book_config::BookConfig;
 use crate::orderbook::error::OrderBookError;
 use crate::orderbook::matching::MatchOutcome;
+use crate::orderbook::order_ack::{OrderAck, RestingInfo};
 use crate::orderbook::order_state::{CancelReason, OrderStatus};
 use crate::orderbook::reject_reason::RejectReason;
 use crate::orderbook::trade::TradeResult;
 use either::Either;
-use pricelevel::{Id, OrderType, OrderUpdate, PriceLevel, Quantity, Side, TakerKind};
+use pricelevel::{
+    Id, MatchResult, OrderType, OrderUpdate, Price, PriceLevel, Quantity, Side, TakerKind,
+};
 use std::sync::Arc;
 use tracing::trace;
 
+/// Everything [`OrderBook::add_order_inner`] learned while admitting an
+/// order.
+pub(super) struct AddedOrder<T> {
+    pub(super) order: Arc<OrderType<T>>,
+    trade_result: Option<TradeResult>,
+    /// The match result when it was not moved into `trade_result`.
+    match_result: Option<MatchResult>,
+    resting: Option<RestingInfo>,
+}
+
 /// A trait to abstract quantity access and modification for different order types.
 pub trait OrderQuantity<T = ()> {
     /// Returns the primary quantity used for display or simple matching.
@@ -267,9 +281,11 @@ where
     /// Because the shared validator runs on the projected order, two
     /// previously-accepted shapes are now rejected on `UpdateQuantity`
     /// like they already were on the #98 modify paths: an
-    /// expired-but-unevicted GTD / DAY maker (`InvalidOperation`, expiry
+    /// expired-but-unevicted GTD / DAY maker (`OrderExpired`, expiry
     /// is evaluated against the book clock) and a resting post-only maker
-    /// whose price meanwhile crosses the market (`PriceCrossing`).
+    /// whose price meanwhile crosses the market (`PriceCrossing`). This
+    /// holds under [`PostOnlyMode::Slide`](crate::PostOnlyMode::Slide)
+    /// too: only new submissions are slid, never a modify.
     pub fn update_order(
         &self,
         update: OrderUpdate,
@@ -277,6 +293,16 @@ where
         // #209: shared submit gate for the whole modify — its internal
         // cancel-then-add sequences call the ungated inner variants.
         let _gate = self.submit_gate_read();
+        self.update_order_ungated(update)
+    }
+
+    /// [`Self::update_order`] without the submit gate, for callers that
+    /// already hold it (the pegged order re-price passes).
+    pub(super) fn update_order_ungated(
+        &self,
+        update: OrderUpdate,
+    ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
         // Gate non-cancel variants on the kill switch. Cancel passes
         // through unchanged so operators can drain the book. The
         // existing order stays live — only the modification is
@@ -307,8 +333,9 @@ where
                 if let Some((old_price, _)) = location {
                     // If price doesn't change, do nothing
                     if old_price == new_price.as_u128() {
-                        return Err(OrderBookError::InvalidOperation {
-                            message: "Cannot update price to the same value".to_string(),
+                        return Err(OrderBookError::PriceUnchanged {
+                            order_id,
+                            price: old_price,
                         });
                     }
 
@@ -343,8 +370,10 @@ where
                     // order + the opposite book side, so evaluating them
                     // while the same-side original still rests yields the
                     // same verdict as after cancel.
-                    self.validate_order_shape(&new_order)?;
+                    let config = self.config();
+                    self.validate_order_shape(&config, &new_order)?;
                     self.check_risk_modify_admission(
+                        &config,
                         order_id,
                         new_order.user_id(),
                         new_order.price().as_u128(),
@@ -373,8 +402,7 @@ where
                         !new_order.is_fill_or_kill(),
                         "a resting order can never carry FOK; the shared-gate re-add relies on it"
                     );
-                    self.cancel_order_with_reason(order_id, CancelReason::UserRequested)?;
-                    let result = self.add_order_inner(new_order, false)?.0;
+                    let result = self.replace_resting_order(order_id, new_order)?;
                     Ok(Some(result))
                 } else {
                     Ok(None) // Order not found
@@ -421,8 +449,10 @@ where
                         };
                         let current = self.convert_from_unit_type(current_unit.as_ref());
                         let projected = current.with_reduced_quantity(new_quantity.as_u64());
-                        self.validate_order_shape(&projected)?;
+                        let config = self.config();
+                        self.validate_order_shape(&config, &projected)?;
                         self.check_risk_modify_admission(
+                            &config,
                             order_id,
                             projected.user_id(),
                             price,
@@ -445,14 +475,19 @@ where
                                     order_id,
                                     OrderQuantity::<()>::total_quantity(order.as_ref()),
                                 );
+                                // An increase re-queued the order at the
+                                // back of the level, behind lower classes.
+                                self.enforce_class_priority(price_level);
                                 // notify price level changes
-                                if let Some(ref listener) = self.price_level_changed_listener {
+                                if let Some(listener) = self.price_level_listener() {
                                     let engine_seq = self.next_engine_seq();
                                     listener(PriceLevelChangedEvent {
                                         side,
                                         price: price_level.price(),
                                         quantity: price_level.visible_quantity(),
+                                        order_count: price_level.order_count(),
                                         engine_seq,
+                                        config_version: self.config_version(),
                                     })
                                 }
                                 result = Some(Arc::new(self.convert_from_unit_type(&order)));
@@ -462,18 +497,22 @@ where
                                 return Err(OrderBookError::PriceLevelError(err));
                             }
                         }
+                        self.reconcile_depth(side, price_level);
 
                         is_empty = price_level.order_count() == 0;
                     }
 
                     // If the price level is now empty, remove it
                     if is_empty {
-                        price_levels.remove(&price);
+                        self.remove_level_if_empty(side, price);
                         self.order_locations.remove(&order_id);
+                        self.take_extra_fields(&order_id);
+                        self.expiry_wheel.unschedule(&order_id);
                         self.untrack_order_by_id(&order_id);
                     }
 
                     self.cache.invalidate();
+                    self.refresh_depth_view();
                     if is_empty {
                         // Refresh depth gauges now that a level was
                         // removed during the modification path.
@@ -523,8 +562,10 @@ where
                     // order's shape and run the modify-aware risk check
                     // *before* removing the original. On any rejection the
                     // original order is never cancelled.
-                    self.validate_order_shape(&new_order)?;
+                    let config = self.config();
+                    self.validate_order_shape(&config, &new_order)?;
                     self.check_risk_modify_admission(
+                        &config,
                         order_id,
                         new_order.user_id(),
                         new_order.price().as_u128(),
@@ -551,8 +592,7 @@ where
                         !new_order.is_fill_or_kill(),
                         "a resting order can never carry FOK; the shared-gate re-add relies on it"
                     );
-                    self.cancel_order_with_reason(order_id, CancelReason::UserRequested)?;
-                    let result = self.add_order_inner(new_order, false)?.0;
+                    let result = self.replace_resting_order(order_id, new_order)?;
                     Ok(Some(result))
                 } else {
                     Ok(None) // Order not found
@@ -584,7 +624,7 @@ where
                             let cancel_update = OrderUpdate::Cancel { order_id };
                             let result = price_level.update_order(cancel_update);
                             // notify price level changes
-                            if let Some(ref listener) = self.price_level_changed_listener
+                            if let Some(listener) = self.price_level_listener()
                                 && let Ok(updated_order) = result
                                 && updated_order.is_some()
                             {
@@ -593,22 +633,29 @@ where
                                     side,
                                     price: price_level.price(),
                                     quantity: price_level.visible_quantity(),
+                                    order_count: price_level.order_count(),
                                     engine_seq,
+                                    config_version: self.config_version(),
                                 })
                             }
+                            self.reconcile_depth(side, price_level);
                             is_empty = price_level.order_count() == 0;
                         }
 
                         // Remove from order locations tracking
                         self.order_locations.remove(&order_id);
+                        self.take_extra_fields(&order_id);
+                        self.expiry_wheel.unschedule(&order_id);
                         // Remove from user_orders index
                         self.untrack_order_by_id(&order_id);
                     }
 
                     // If price level is empty, remove it
                     if is_empty {
-                        price_levels.remove(&price);
+                        self.remove_level_if_empty(side, price);
                     }
+                    self.cache.invalidate();
+                    self.refresh_depth_view();
 
                     Ok(result)
                 } else {
@@ -722,8 +769,10 @@ where
                     // *before* removing the original. On any rejection the
                     // original order is never cancelled — no book mutation,
                     // no events, no trades.
-                    self.validate_order_shape(&new_order)?;
+                    let config = self.config();
+                    self.validate_order_shape(&config, &new_order)?;
                     self.check_risk_modify_admission(
+                        &config,
                         order_id,
                         new_order.user_id(),
                         new_order.price().as_u128(),
@@ -750,8 +799,7 @@ where
                         !new_order.is_fill_or_kill(),
                         "a resting order can never carry FOK; the shared-gate re-add relies on it"
                     );
-                    self.cancel_order_with_reason(order_id, CancelReason::UserRequested)?;
-                    let result = self.add_order_inner(new_order, false)?.0;
+                    let result = self.replace_resting_order(order_id, new_order)?;
                     Ok(Some(result))
                 } else {
                     Ok(None) // Original order not found
@@ -760,6 +808,204 @@ where
         }
     }
 
+    /// Reduce a resting order's size by `quantity_to_remove` in place,
+    /// keeping its queue position.
+    ///
+    /// The reduction is applied as an [`OrderUpdate::UpdateQuantity`] to
+    /// the order's current size, with the same validate-first checks (lot
+    /// and size limits, risk) and the same price-level notification. The
+    /// submit gate is held exclusively across reading the size and
+    /// applying the update, so a fill landing in between can never turn
+    /// the reduction into an increase that would cost the order its
+    /// priority. For iceberg and reserve orders the quantity comes out of
+    /// the displayed tranche; the hidden tranche is untouched.
+    ///
+    /// Returns the reduced order, or `Ok(None)` when no order with
+    /// `order_id` is resting.
+    ///
+    /// # Errors
+    /// Returns [`OrderBookError::KillSwitchActive`] while the kill switch
+    /// is engaged, [`OrderBookError::InvalidReduction`] when
+    /// `quantity_to_remove` is zero or not smaller than the displayed
+    /// quantity (cancel the order instead), and the validation errors of
+    /// [`OrderUpdate::UpdateQuantity`] such as
+    /// [`OrderBookError::InvalidLotSize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let first = Id::from_u64(1);
+    /// book.add_limit_order(first, 100, 10, Side::Buy, TimeInForce::Gtc, None)?;
+    /// book.add_limit_order(Id::from_u64(2), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    ///
+    /// let reduced = book.reduce_order(first, 4)?.expect("resting");
+    /// assert_eq!(reduced.visible_quantity().as_u64(), 6);
+    ///
+    /// // Still first in the queue: a sell of 6 fills it alone.
+    /// let fill = book.submit_market_order(Id::from_u64(3), 6, Side::Sell)?;
+    /// assert_eq!(fill.trades().as_vec()[0].maker_order_id(), first);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn reduce_order(
+        &self,
+        order_id: Id,
+        quantity_to_remove: u64,
+    ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let _gate = self.submit_gate_write();
+        self.check_kill_switch()?;
+        let Some(order) = self.get_order(order_id) else {
+            return Ok(None);
+        };
+        let displayed = order.visible_quantity().as_u64();
+        if quantity_to_remove == 0 || quantity_to_remove >= displayed {
+            return Err(OrderBookError::InvalidReduction {
+                order_id,
+                requested: quantity_to_remove,
+                displayed,
+            });
+        }
+        self.update_order_ungated(OrderUpdate::UpdateQuantity {
+            order_id,
+            new_quantity: Quantity::new(displayed - quantity_to_remove),
+        })
+    }
+
+    /// Move a resting trailing stop's stop price and its reference price
+    /// (the market extreme it trails) in one atomic modify.
+    ///
+    /// [`OrderUpdate`] is defined by `pricelevel` and can only carry a new
+    /// price, so a stop re-priced through [`Self::update_order`] keeps a
+    /// stale `last_reference_price`. This entry point updates both fields
+    /// together with the same validate-first cancel-then-add semantics as
+    /// [`OrderUpdate::UpdatePrice`]: a rejected update leaves the order
+    /// untouched, an accepted one re-enters at the back of its level.
+    ///
+    /// Returns `Ok(None)` when no order with `order_id` is resting.
+    ///
+    /// # Errors
+    /// Returns [`OrderBookError::KillSwitchActive`] while the kill switch
+    /// is engaged, [`OrderBookError::NotTrailingStop`] when the order is
+    /// of another type, [`OrderBookError::PriceUnchanged`] when neither
+    /// price changes, and any admission error of the updated order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let id = Id::new();
+    /// book.add_order(OrderType::TrailingStop {
+    ///     id,
+    ///     price: Price::new(95),
+    ///     quantity: Quantity::new(1),
+    ///     side: Side::Sell,
+    ///     user_id: Hash32::zero(),
+    ///     timestamp: TimestampMs::new(0),
+    ///     time_in_force: TimeInForce::Gtc,
+    ///     trail_amount: Quantity::new(5),
+    ///     last_reference_price: Price::new(100),
+    ///     extra_fields: (),
+    /// })
+    /// .unwrap();
+    ///
+    /// let moved = book
+    ///     .update_trailing_state(id, Price::new(105), Price::new(110))
+    ///     .unwrap()
+    ///     .expect("resting");
+    /// assert!(matches!(
+    ///     *moved,
+    ///     OrderType::TrailingStop { price, last_reference_price, .. }
+    ///         if price.as_u128() == 105 && last_reference_price.as_u128() == 110
+    /// ));
+    /// ```
+    pub fn update_trailing_state(
+        &self,
+        order_id: Id,
+        new_stop_price: Price,
+        new_reference_price: Price,
+    ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let _gate = self.submit_gate_read();
+        self.update_trailing_state_ungated(order_id, new_stop_price, new_reference_price)
+    }
+
+    /// [`Self::update_trailing_state`] without the submit gate, for callers
+    /// that already hold it (the trailing-stop re-price pass).
+    pub(super) fn update_trailing_state_ungated(
+        &self,
+        order_id: Id,
+        new_stop_price: Price,
+        new_reference_price: Price,
+    ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
+        self.check_kill_switch()?;
+        self.cache.invalidate();
+
+        let Some(original) = self.get_order(order_id) else {
+            return Ok(None);
+        };
+        let mut new_order = (*original).clone();
+        let OrderType::TrailingStop {
+            price,
+            last_reference_price,
+            ..
+        } = &mut new_order
+        else {
+            return Err(OrderBookError::NotTrailingStop { order_id });
+        };
+        if *price == new_stop_price && *last_reference_price == new_reference_price {
+            return Err(OrderBookError::PriceUnchanged {
+                order_id,
+                price: price.as_u128(),
+            });
+        }
+        *price = new_stop_price;
+        *last_reference_price = new_reference_price;
+
+        // Validate-first atomic modify (#98), as for `UpdatePrice`: every
+        // check runs while the original still rests, so a rejection leaves
+        // both prices as they were.
+        let config = self.config();
+        self.validate_order_shape(&config, &new_order)?;
+        self.check_risk_modify_admission(
+            &config,
+            order_id,
+            new_order.user_id(),
+            new_order.price().as_u128(),
+            new_order.total_quantity(),
+        )?;
+        self.check_modify_stp_self_cross(&new_order)?;
+
+        debug_assert!(
+            !new_order.is_fill_or_kill(),
+            "a resting order can never carry FOK; the shared-gate re-add relies on it"
+        );
+        let result = self.replace_resting_order(order_id, new_order)?;
+        Ok(Some(result))
+    }
+
+    /// Cancel-then-add step of a modification: cancel `order_id` and add
+    /// `new_order` in its place. A tag on the original carries over to the
+    /// new order if it rests.
+    pub(super) fn replace_resting_order(
+        &self,
+        order_id: Id,
+        new_order: OrderType<T>,
+    ) -> Result<Arc<OrderType<T>>, OrderBookError> {
+        let tag = self.order_tag(order_id);
+        self.cancel_order_with_reason(order_id, CancelReason::UserRequested)?;
+        let result = self.add_order_inner(new_order, false)?.order;
+        if let Some(tag) = tag {
+            self.attach_order_tag(result.id(), tag);
+        }
+        Ok(result)
+    }
+
     /// Cancel an order by ID.
     ///
     /// Tracks the cancellation as `CancelReason::UserRequested` in the
@@ -781,6 +1027,7 @@ where
         order_id: Id,
         reason: CancelReason,
     ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
         self.cache.invalidate();
         // First, we find the order's location (price and side) without locking
         let location = self.order_locations.get(&order_id).map(|val| *val);
@@ -804,17 +1051,20 @@ where
                 // Try to cancel the order
                 if let Ok(cancelled) = price_level.update_order(update) {
                     result = cancelled;
+                    self.reconcile_depth(side, price_level);
 
                     // notify price level changes
                     if result.is_some()
-                        && let Some(ref listener) = self.price_level_changed_listener
+                        && let Some(listener) = self.price_level_listener()
                     {
                         let engine_seq = self.next_engine_seq();
                         listener(PriceLevelChangedEvent {
                             side,
                             price: price_level.price(),
                             quantity: price_level.visible_quantity(),
+                            order_count: price_level.order_count(),
                             engine_seq,
+                            config_version: self.config_version(),
                         })
                     }
 
@@ -824,6 +1074,8 @@ where
             }
 
             self.cache.invalidate();
+            self.refresh_depth_view();
+            let mut extra_fields = None;
             // If we got a result and the order was canceled
             if let Some(ref cancelled_order) = result {
                 // Track the cancellation in the order state tracker
@@ -843,6 +1095,8 @@ where
 
                 // Remove the order from the locations map
                 self.order_locations.remove(&order_id);
+                extra_fields = self.take_extra_fields(&order_id);
+                self.expiry_wheel.unschedule(&order_id);
 
                 // Pre-trade risk hook: drop the per-account counter
                 // contribution before the order leaves the index. Does
@@ -865,7 +1119,9 @@ where
 
                 // If the level became empty, remove it
                 if empty_level {
-                    price_levels.remove(&price);
+                    self.remove_level_if_empty(side, price);
+                    self.cache.invalidate();
+                    self.refresh_depth_view();
                     // Refresh the depth gauges now that a level was
                     // removed. No-op when the `metrics` feature is
                     // disabled.
@@ -873,7 +1129,7 @@ where
                 }
             }
 
-            Ok(result.map(|order| Arc::new(self.convert_from_unit_type(&order))))
+            Ok(result.map(|order| Arc::new(self.convert_with_extra_fields(&order, extra_fields))))
         } else {
             Ok(None)
         }
@@ -893,6 +1149,8 @@ where
     /// re-resolves `order_locations`, so a sequence of cancels on the same held
     /// level cannot skip a later order. Used by the STP `CancelMaker` /
     /// `CancelBoth` arms (#95). No-op if `order_id` is not resting on the level.
+    /// Cache invalidation is also left to the caller, which invalidates once
+    /// after the walk.
     pub(super) fn cancel_resting_maker_on_level(
         &self,
         price_level: &PriceLevel,
@@ -903,16 +1161,18 @@ where
         let Ok(Some(cancelled)) = price_level.update_order(OrderUpdate::Cancel { order_id }) else {
             return;
         };
-        self.cache.invalidate();
+        self.reconcile_depth(side, price_level);
 
         // 1. Notify the level change (same shape as cancel_order_with_reason).
-        if let Some(ref listener) = self.price_level_changed_listener {
+        if let Some(listener) = self.price_level_listener() {
             let engine_seq = self.next_engine_seq();
             listener(PriceLevelChangedEvent {
                 side,
                 price: price_level.price(),
                 quantity: price_level.visible_quantity(),
+                order_count: price_level.order_count(),
                 engine_seq,
+                config_version: self.config_version(),
             });
         }
 
@@ -933,6 +1193,8 @@ where
 
         // 3. Drop the per-account risk contribution, then untrack the order.
         self.order_locations.remove(&order_id);
+        self.take_extra_fields(&order_id);
+        self.expiry_wheel.unschedule(&order_id);
         self.risk_state.on_cancel(order_id);
         self.untrack_user_order(cancelled.user_id(), &order_id);
 
@@ -964,13 +1226,18 @@ where
     /// 2. Tick size (`InvalidTickSize`).
     /// 3. Lot size (`InvalidLotSize`, iceberg visible/hidden split).
     /// 4. Min/max order size (`OrderSizeOutOfRange`).
-    /// 5. Expiry (`InvalidOperation` — already expired).
-    /// 6. Post-only would cross (`PriceCrossing`).
-    /// 7. FOK feasibility (`InsufficientLiquidity`).
+    /// 5. Min/max order notional (`OrderNotionalOutOfRange`).
+    /// 6. Expiry (`OrderExpired`).
+    /// 7. Post-only would cross (`PriceCrossing`).
+    /// 8. FOK feasibility (`InsufficientLiquidity`).
     ///
     /// # Errors
     /// Returns the first failing check's typed [`OrderBookError`].
-    pub(super) fn validate_order_shape(&self, order: &OrderType<T>) -> Result<(), OrderBookError> {
+    pub(super) fn validate_order_shape(
+        &self,
+        config: &BookConfig,
+        order: &OrderType<T>,
+    ) -> Result<(), OrderBookError> {
         // Two-tranche total representability (#210): an Iceberg / Reserve
         // whose visible + hidden overflows u64 cannot be tracked by any of
         // the engine's quantity arithmetic — reject it before every other
@@ -983,9 +1250,13 @@ where
             });
         }
 
+        // Trading session: nothing is admitted while closed, and nothing
+        // that would trade during the pre-open auction.
+        self.check_session(|| self.will_cross_market(order.price().as_u128(), order.side()))?;
+
         // STP user_id enforcement: when STP is enabled, all orders must carry
         // a non-zero user_id so that self-trade checks can identify the owner.
-        if self.stp_mode != crate::orderbook::stp::STPMode::None
+        if self.stp_mode() != crate::orderbook::stp::STPMode::None
             && order.user_id() == pricelevel::Hash32::zero()
         {
             return Err(OrderBookError::MissingUserId {
@@ -994,7 +1265,7 @@ where
         }
 
         // Tick size validation: reject orders whose price is not a multiple of tick_size
-        if let Some(tick) = self.tick_size
+        if let Some(tick) = config.tick_size
             && tick > 0
             && !order.price().as_u128().is_multiple_of(tick)
         {
@@ -1006,7 +1277,7 @@ where
 
         // Lot size validation: reject orders whose quantity is not a multiple of lot_size.
         // For iceberg orders, validate visible and hidden quantities individually.
-        if let Some(lot) = self.lot_size
+        if let Some(lot) = config.lot_size
             && lot > 0
         {
             match order {
@@ -1041,28 +1312,43 @@ where
 
         // Min/max order size validation
         let qty = order.total_quantity();
-        if let Some(min) = self.min_order_size
+        if let Some(min) = config.min_order_size
             && qty < min
         {
             return Err(OrderBookError::OrderSizeOutOfRange {
                 quantity: qty,
                 min: Some(min),
-                max: self.max_order_size,
+                max: config.max_order_size,
             });
         }
-        if let Some(max) = self.max_order_size
+        if let Some(max) = config.max_order_size
             && qty > max
         {
             return Err(OrderBookError::OrderSizeOutOfRange {
                 quantity: qty,
-                min: self.min_order_size,
+                min: config.min_order_size,
                 max: Some(max),
             });
         }
 
+        // Min/max notional validation. The product saturates so an
+        // overflowing order is still caught by the maximum.
+        if config.min_order_notional.is_some() || config.max_order_notional.is_some() {
+            let notional = order.price().as_u128().saturating_mul(u128::from(qty));
+            let below_min = config.min_order_notional.is_some_and(|min| notional < min);
+            let above_max = config.max_order_notional.is_some_and(|max| notional > max);
+            if below_min || above_max {
+                return Err(OrderBookError::OrderNotionalOutOfRange {
+                    notional,
+                    min: config.min_order_notional,
+                    max: config.max_order_notional,
+                });
+            }
+        }
+
         if self.has_expired(order) {
-            return Err(OrderBookError::InvalidOperation {
-                message: "Order has already expired".to_string(),
+            return Err(OrderBookError::OrderExpired {
+                order_id: order.id(),
             });
         }
 
@@ -1107,8 +1393,8 @@ where
     /// Closes the one post-match modify-atomicity gap #98 left open. Under
     /// [`STPMode::CancelTaker`](crate::orderbook::stp::STPMode::CancelTaker) /
     /// [`CancelBoth`](crate::orderbook::stp::STPMode::CancelBoth), if a
-    /// re-priced order would cross into the **same user's** resting liquidity on
-    /// the opposite side, `add_order` matches post-cancel and cancels the taker
+    /// re-priced order would cross into the **same user's** (or a member of its
+    /// account group's) resting liquidity on the opposite side, `add_order` matches post-cancel and cancels the taker
     /// (the re-added order) — *after* the original was already removed,
     /// destroying it. This dry-runs the crossable opposite side and, if the
     /// sweep would reach a same-user maker while the taker still has unfilled
@@ -1132,13 +1418,16 @@ where
         let taker_user_id = new_order.user_id();
         // Only CancelTaker / CancelBoth cancel the taker; None / CancelMaker
         // rest it, so the re-added order is never destroyed.
-        match self.stp_mode {
+        let stp_mode = self.stp_mode();
+        match stp_mode {
             STPMode::CancelTaker | STPMode::CancelBoth => {}
             _ => return Ok(()),
         }
         if taker_user_id == pricelevel::Hash32::zero() {
             return Ok(());
         }
+        let stp_scope =
+            crate::orderbook::stp::StpScope::with_groups(taker_user_id, &self.account_groups);
 
         let side = new_order.side();
         let new_price = new_order.price().as_u128();
@@ -1171,12 +1460,15 @@ where
                 break;
             }
             let level = entry.value();
-            if level.iter_orders().any(|o| o.user_id() == taker_user_id) {
+            if level
+                .iter_orders()
+                .any(|o| stp_scope.conflicts_with(o.user_id()))
+            {
                 // The sweep reaches a level holding a same-user maker while the
                 // taker still has unfilled quantity: the engine would cancel the
                 // taker here. Reject the modify before the original is cancelled.
                 return Err(OrderBookError::SelfTradePrevented {
-                    mode: self.stp_mode,
+                    mode: stp_mode,
                     taker_order_id: new_order.id(),
                     user_id: taker_user_id,
                 });
@@ -1197,7 +1489,7 @@ where
     /// pre-#98 reject side-effects of `add_order` while letting the
     /// validate-first modify path reuse the same pure validator without
     /// recording any state. Errors that previously had no side-effect
-    /// (e.g. the already-expired `InvalidOperation`) are intentionally
+    /// (e.g. the already-expired `OrderExpired`) are intentionally
     /// no-ops here.
     fn record_shape_rejection(&self, order: &OrderType<T>, err: &OrderBookError) {
         match err {
@@ -1241,6 +1533,14 @@ where
                     },
                 );
             }
+            OrderBookError::OrderNotionalOutOfRange { .. } => {
+                self.track_state(
+                    order.id(),
+                    OrderStatus::Rejected {
+                        reason: RejectReason::OrderNotionalOutOfRange,
+                    },
+                );
+            }
             OrderBookError::PriceCrossing { .. } => {
                 self.track_state(
                     order.id(),
@@ -1249,6 +1549,14 @@ where
                     },
                 );
             }
+            OrderBookError::MarketClosed { .. } => {
+                self.track_state(
+                    order.id(),
+                    OrderStatus::Rejected {
+                        reason: RejectReason::MarketClosed,
+                    },
+                );
+            }
             OrderBookError::InsufficientLiquidity { .. } => {
                 self.track_state(
                     order.id(),
@@ -1257,9 +1565,9 @@ where
                         reason: CancelReason::InsufficientLiquidity,
                     },
                 );
-                crate::orderbook::metrics::record_reject(RejectReason::InsufficientLiquidity);
+                self.record_reject(RejectReason::InsufficientLiquidity);
             }
-            // The already-expired `InvalidOperation` path historically
+            // The already-expired `OrderExpired` path historically
             // recorded no terminal transition; preserve that.
             _ => {}
         }
@@ -1274,14 +1582,18 @@ where
     ///
     /// # Errors
     /// Returns [`OrderBookError::KillSwitchActive`] when the kill switch
-    /// is engaged. The check runs before any cache invalidation, STP
-    /// validation, tick/lot validation, or matching work.
+    /// is engaged, [`OrderBookError::UserBlocked`] when the submitting user
+    /// is blocked, and [`OrderBookError::RateLimited`] when a configured
+    /// rate limit refuses the submission. These checks run before any cache
+    /// invalidation, STP validation, tick/lot validation, or matching work.
+    /// Returns [`OrderBookError::DuplicateOrderId`] when the id is resting
+    /// or, with a duplicate-id window installed, was recently submitted.
     #[inline]
     pub fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, OrderBookError> {
         // #209: shared gate for ordinary submits, exclusive for FOK so its
         // feasibility + sweep window excludes every concurrent mutation.
         let _gate = self.acquire_submit_gate(order.is_fill_or_kill());
-        self.add_order_inner(order, false).map(|(order, _)| order)
+        self.add_order_inner(order, false).map(|added| added.order)
     }
 
     /// Add a new order to the book, automatically matching it if it's
@@ -1314,8 +1626,12 @@ where
     ///
     /// # Errors
     /// Returns [`OrderBookError::KillSwitchActive`] when the kill switch
-    /// is engaged. The check runs before any cache invalidation, STP
-    /// validation, tick/lot validation, or matching work.
+    /// is engaged, [`OrderBookError::UserBlocked`] when the submitting user
+    /// is blocked, and [`OrderBookError::RateLimited`] when a configured
+    /// rate limit refuses the submission. These checks run before any cache
+    /// invalidation, STP validation, tick/lot validation, or matching work.
+    /// Returns [`OrderBookError::DuplicateOrderId`] when the id is resting
+    /// or, with a duplicate-id window installed, was recently submitted.
     pub fn add_order_with_result(
         &self,
         order: OrderType<T>,
@@ -1323,18 +1639,127 @@ where
         // #209: same gating as `add_order`.
         let _gate = self.acquire_submit_gate(order.is_fill_or_kill());
         self.add_order_inner(order, true)
+            .map(|added| (added.order, added.trade_result))
+    }
+
+    /// Add a new order to the book like [`Self::add_order`] and return an
+    /// [`OrderAck`] with its fills and, when a remainder rested, the price,
+    /// remaining quantity and queue position it rested at.
+    ///
+    /// The trade listener is invoked exactly as for [`Self::add_order`].
+    ///
+    /// # Errors
+    /// Every error documented on [`Self::add_order`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// book.add_limit_order(Id::new(), 100, 5, Side::Sell, TimeInForce::Gtc, None).unwrap();
+    ///
+    /// let ack = book
+    ///     .add_order_with_ack(OrderType::Standard {
+    ///         id: Id::new(),
+    ///         price: Price::new(100),
+    ///         quantity: Quantity::new(8),
+    ///         side: Side::Buy,
+    ///         user_id: Hash32::zero(),
+    ///         timestamp: TimestampMs::new(0),
+    ///         time_in_force: TimeInForce::Gtc,
+    ///         extra_fields: (),
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(ack.match_result.trades().len(), 1);
+    /// let resting = ack.resting.expect("remainder rests");
+    /// assert_eq!((resting.price, resting.remaining_quantity, resting.queue_position), (100, 3, 0));
+    /// ```
+    pub fn add_order_with_ack(&self, order: OrderType<T>) -> Result<OrderAck, OrderBookError> {
+        let _gate = self.acquire_submit_gate(order.is_fill_or_kill());
+        let order_id = order.id();
+        let added = self.add_order_inner(order, true)?;
+        let match_result = match (added.trade_result, added.match_result) {
+            (Some(trade_result), _) => trade_result.match_result,
+            (None, Some(match_result)) => match_result,
+            (None, None) => MatchResult::new(order_id, Quantity::new(0)),
+        };
+        Ok(OrderAck {
+            match_result,
+            resting: added.resting,
+        })
+    }
+
+    /// Add several orders in one call, in the order given.
+    ///
+    /// Each order goes through exactly the same admission, matching and
+    /// resting path as [`Self::add_order`] and gets its own result, so one
+    /// rejected order does not affect the others — the batch is not
+    /// atomic. The submit gate is acquired once for the whole batch
+    /// (exclusively if any order is fill-or-kill) instead of once per
+    /// order, and no other gated submit or mass cancel can interleave with
+    /// the batch. This suits market makers replacing a ladder of quotes.
+    ///
+    /// # Errors
+    ///
+    /// Per order, every error documented on [`Self::add_order`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let quote = |price: u128, side: Side| OrderType::Standard {
+    ///     id: Id::new(),
+    ///     price: Price::new(price),
+    ///     quantity: Quantity::new(5),
+    ///     side,
+    ///     user_id: Hash32::zero(),
+    ///     timestamp: TimestampMs::new(0),
+    ///     time_in_force: TimeInForce::Gtc,
+    ///     extra_fields: (),
+    /// };
+    ///
+    /// let results = book.add_orders_batch(vec![
+    ///     quote(99, Side::Buy),
+    ///     quote(98, Side::Buy),
+    ///     quote(101, Side::Sell),
+    /// ]);
+    /// assert!(results.iter().all(Result::is_ok));
+    /// assert_eq!(book.best_bid(), Some(99));
+    /// assert_eq!(book.best_ask(), Some(101));
+    /// ```
+    pub fn add_orders_batch(
+        &self,
+        orders: Vec<OrderType<T>>,
+    ) -> Vec<Result<Arc<OrderType<T>>, OrderBookError>> {
+        // #209: a single acquisition for the batch; the inner path is
+        // ungated, so there is no nested acquisition.
+        let exclusive = orders.iter().any(OrderType::is_fill_or_kill);
+        let _gate = self.acquire_submit_gate(exclusive);
+        orders
+            .into_iter()
+            .map(|order| self.add_order_inner(order, false).map(|added| added.order))
+            .collect()
     }
 
     /// Shared implementation behind [`Self::add_order`] and
     /// [`Self::add_order_with_result`]. `want_result` gates `TradeResult`
     /// construction so the plain `add_order` path only pays for it when an
     /// installed trade listener needs it anyway.
-    fn add_order_inner(
+    pub(super) fn add_order_inner(
         &self,
         mut order: OrderType<T>,
         want_result: bool,
-    ) -> Result<(Arc<OrderType<T>>, Option<TradeResult>), OrderBookError> {
+    ) -> Result<AddedOrder<T>, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
         self.check_kill_switch_or_reject(order.id())?;
+        self.check_user_blocked_or_reject(order.id(), order.user_id())?;
+        self.check_rate_limit_or_reject(order.id(), order.user_id())?;
         // Representability gate (#210): an unrepresentable two-tranche
         // total must be rejected before the risk gate below, which would
         // otherwise evaluate the account's notional against the SATURATED
@@ -1349,6 +1774,13 @@ where
             self.record_shape_rejection(&order, &err);
             return Err(err);
         }
+        // Under `PostOnlyMode::Slide` a crossing post-only order is moved
+        // behind the opposite best before any price-dependent check, so the
+        // risk gate and shape validation see the price it will rest at.
+        // One copy of the reloadable parameters for the whole admission, so
+        // a concurrent `reload_config` cannot pair old limits with new ones.
+        let config = self.config();
+        let repriced_from = self.slide_post_only(&config, &mut order);
         // Pre-trade risk gate: per-account open-orders / notional /
         // price band. No-op when no `RiskConfig` is installed.
         // Documented order: kill_switch → risk → STP → fees → match.
@@ -1356,6 +1788,7 @@ where
         // transition with the closed `RejectReason` taxonomy before
         // propagating the typed error.
         if let Err(err) = self.check_risk_limit_admission(
+            &config,
             order.user_id(),
             order.price().as_u128(),
             order.total_quantity(),
@@ -1384,11 +1817,22 @@ where
         // id can both pass here and both rest (last-writer-wins on insert).
         // Serializing order ids is the ingress / sequencing layer's job.
         if self.order_locations.contains_key(&order.id()) {
-            crate::orderbook::metrics::record_reject(RejectReason::DuplicateOrderId);
+            self.record_reject(RejectReason::DuplicateOrderId);
             return Err(OrderBookError::DuplicateOrderId {
                 order_id: order.id(),
             });
         }
+        // Windowed duplicate check: the id may belong to an order that has
+        // already left the book, whose terminal tracked state must not be
+        // clobbered either, so again only the metric is recorded. No-op
+        // when no window is installed.
+        if self.order_id_dedup.window_ms().is_some() {
+            let now_ms = self.clock().now_millis().as_u64();
+            if let Err(err) = self.order_id_dedup.check_and_record(order.id(), now_ms) {
+                self.record_reject(RejectReason::DuplicateOrderId);
+                return Err(err);
+            }
+        }
 
         trace!(
             "Order book {}: Adding order {} at price {}",
@@ -1403,7 +1847,7 @@ where
         // record the matching terminal state transition / metric here so
         // the direct (non-modify) `add_order` behavior is preserved
         // exactly.
-        if let Err(err) = self.validate_order_shape(&order) {
+        if let Err(err) = self.validate_order_shape(&config, &order) {
             self.record_shape_rejection(&order, &err);
             return Err(err);
         }
@@ -1440,17 +1884,13 @@ where
                                 reason: RejectReason::InvalidQuantity,
                             },
                         );
-                        crate::orderbook::metrics::record_reject(RejectReason::InvalidQuantity);
                         return Err(OrderBookError::PriceLevelError(err));
                     }
                 };
                 if level_total.checked_add(order.total_quantity()).is_none() {
-                    let err = OrderBookError::InvalidOperation {
-                        message: format!(
-                            "resting order {} would overflow the aggregate capacity of level {}",
-                            order.id(),
-                            order.price()
-                        ),
+                    let err = OrderBookError::LevelQuantityOverflow {
+                        order_id: order.id(),
+                        price: order.price().as_u128(),
                     };
                     self.track_state(
                         order.id(),
@@ -1458,12 +1898,12 @@ where
                             reason: RejectReason::InvalidQuantity,
                         },
                     );
-                    crate::orderbook::metrics::record_reject(RejectReason::InvalidQuantity);
                     return Err(err);
                 }
             }
         }
 
+        self.counters.record_added();
         self.cache.invalidate();
         // Attempt to match the order immediately (with STP user_id propagation).
         // The outcome also carries whether STP cancelled the taker (#97) and
@@ -1481,10 +1921,14 @@ where
         } else {
             TakerKind::Standard
         };
+        // The listener is read once, before the sweep, so the fills are
+        // captured exactly when a `TradeResult` will be built from them.
+        let listener = self.trade_listener();
         let MatchOutcome {
             result: match_result,
             taker_stp_cancelled,
             taker_post_only_rejected,
+            fills,
         } = self.match_order_with_user_outcome(
             order.id(),
             order.side(),
@@ -1492,6 +1936,7 @@ where
             Some(order.price().as_u128()),
             order.user_id(),
             taker_kind,
+            self.capture_fills(want_result || listener.is_some()),
         )?;
 
         // #209: the sweep reached a crossable level with a post-only taker.
@@ -1505,7 +1950,6 @@ where
                     reason: RejectReason::PostOnlyWouldCross,
                 },
             );
-            crate::orderbook::metrics::record_reject(RejectReason::PostOnlyWouldCross);
             return Err(OrderBookError::PriceCrossing {
                 price: order.price().as_u128(),
                 side: order.side(),
@@ -1522,33 +1966,46 @@ where
         // executed, and those fills must still reach the metrics and the trade
         // listener. The `TradeResult` is only constructed when someone consumes
         // it — the installed listener and/or an `add_order_with_result` caller —
-        // so the plain `add_order` hot path skips the `MatchResult` clone.
+        // and it takes the `MatchResult` by value: everything below only needs
+        // the taker's remaining quantity, so no path clones the trade list.
+        let remaining_qty = match_result.remaining_quantity().as_u64();
         let trades_emitted = match_result.trades().len() as u64;
-        let trade_result = if trades_emitted > 0 {
+        let (trade_result, match_result) = if trades_emitted > 0 {
             crate::orderbook::metrics::record_trades(trades_emitted);
-            let listener = self.trade_listener.as_ref();
+            self.counters.record_trades(&match_result);
             if want_result || listener.is_some() {
-                let mut trade_result = TradeResult::with_fees(
-                    self.symbol.clone(),
-                    match_result.clone(),
-                    self.fee_schedule,
-                );
-                trade_result.engine_seq = self.next_engine_seq();
+                let mut trade_result = self.build_trade_result(match_result, fills);
+                if Self::STORES_EXTRA_FIELDS {
+                    trade_result
+                        .extra_fields
+                        .push((order.id(), Arc::new(order.extra_fields().clone())));
+                }
                 if let Some(listener) = listener {
                     listener(&trade_result) // emit trade events to listener
                 }
-                Some(trade_result)
+                self.release_filled_extra_fields(&trade_result.match_result);
+                if want_result {
+                    (Some(trade_result), None)
+                } else {
+                    // Only the listener needed it: keep the fill buffer for
+                    // the next sweep on this thread.
+                    crate::orderbook::matching::recycle_fills(trade_result.fills);
+                    (None, Some(trade_result.match_result))
+                }
             } else {
-                None
+                self.record_tiered_volume(&fills);
+                crate::orderbook::matching::recycle_fills(fills);
+                self.release_filled_extra_fields(&match_result);
+                (None, Some(match_result))
             }
         } else {
-            None
+            (None, Some(match_result))
         };
 
         // True (non-self) executed quantity. `remaining_quantity` only decrements on
         // real trades, so STP-prevented self-fills never count toward it.
         let original_qty = order.total_quantity();
-        let filled_qty = original_qty.saturating_sub(match_result.remaining_quantity().as_u64());
+        let filled_qty = original_qty.saturating_sub(remaining_qty);
 
         // If STP cancelled the taker, the residual must NOT rest — even though some
         // non-self fills already occurred at earlier levels. Record the terminal
@@ -1562,16 +2019,16 @@ where
                     reason: CancelReason::SelfTradePrevention,
                 },
             );
-            crate::orderbook::metrics::record_reject(RejectReason::SelfTradePrevention);
+            self.record_reject(RejectReason::SelfTradePrevention);
             return Err(OrderBookError::SelfTradePrevented {
-                mode: self.stp_mode,
+                mode: self.stp_mode(),
                 taker_order_id: order.id(),
                 user_id: order.user_id(),
             });
         }
 
         // If the order was not fully filled, add the remainder to the book
-        if match_result.remaining_quantity().as_u64() > 0 {
+        if remaining_qty > 0 {
             if order.is_immediate() {
                 // IOC/FOK orders should not have a resting part.
                 // If FOK, it should have been fully filled or cancelled before this point.
@@ -1583,13 +2040,11 @@ where
                         reason: CancelReason::InsufficientLiquidity,
                     },
                 );
-                crate::orderbook::metrics::record_reject(RejectReason::InsufficientLiquidity);
+                self.record_reject(RejectReason::InsufficientLiquidity);
                 return Err(OrderBookError::InsufficientLiquidity {
                     side: order.side(),
                     requested: order.quantity(), // Now uses the trait method
-                    available: order
-                        .quantity()
-                        .saturating_sub(match_result.remaining_quantity().as_u64()),
+                    available: order.quantity().saturating_sub(remaining_qty),
                 });
             }
 
@@ -1600,92 +2055,13 @@ where
             // the rest stays hidden — assigning the total to the visible
             // tranche (the old `set_quantity` semantics) manufactured
             // liquidity by keeping the original hidden tranche on top.
-            if match_result.remaining_quantity().as_u64() < order.total_quantity() {
-                order.set_total_remaining(match_result.remaining_quantity().as_u64());
+            if remaining_qty < order.total_quantity() {
+                order.set_total_remaining(remaining_qty);
             }
 
             let price = order.price().as_u128();
             let side = order.side();
-
-            let price_levels = match side {
-                Side::Buy => &self.bids,
-                Side::Sell => &self.asks,
-            };
-
-            let price_level = price_levels.get_or_insert(price, Arc::new(PriceLevel::new(price)));
-            let level = price_level.value();
-
-            // Convert to unit type for PriceLevel compatibility. Admission
-            // into the level is validated upstream since pricelevel 0.9
-            // (duplicate id, counter capacity). The pre-sweep headroom
-            // check above makes a failure here concurrent-only; if it
-            // still happens, remove the level when this call created it
-            // empty — `best_bid` / `best_ask`, the cache, and the depth
-            // gauges must never expose a phantom level — and surface the
-            // error loudly: the sweep's trades are already irreversible
-            // (#211).
-            let unit_order = self.convert_to_unit_type(&order);
-            let unit_order_arc = match price_level.value().add_order(unit_order) {
-                Ok(admitted) => admitted,
-                Err(err) => {
-                    if level.order_count() == 0 {
-                        price_levels.remove(&price);
-                    }
-                    self.cache.invalidate();
-                    self.record_depth_metric();
-                    tracing::error!(
-                        order_id = %order.id(),
-                        price,
-                        error = %err,
-                        "residual admission failed after irreversible trades; level cleaned up"
-                    );
-                    return Err(OrderBookError::PriceLevelError(err));
-                }
-            };
-            // notify price level changes
-            if let Some(ref listener) = self.price_level_changed_listener {
-                let engine_seq = self.next_engine_seq();
-                listener(PriceLevelChangedEvent {
-                    side,
-                    price: level.price(),
-                    quantity: level.visible_quantity(),
-                    engine_seq,
-                })
-            }
-            self.order_locations
-                .insert(unit_order_arc.id(), (price, side));
-
-            // Refresh the depth gauges. The level may be brand-new
-            // (`get_or_insert` created it) or pre-existing — either
-            // way the gauge reflects current state. No-op when the
-            // `metrics` feature is disabled.
-            self.record_depth_metric();
-
-            // Pre-trade risk hook: register the resting order with
-            // the risk state so per-account counters are updated and
-            // future checks see the new contribution. No-op when no
-            // `RiskConfig` is installed.
-            self.risk_state.on_admission(
-                unit_order_arc.id(),
-                order.user_id(),
-                price,
-                match_result.remaining_quantity().as_u64(),
-            );
-
-            // Track the order in the user_orders index
-            self.track_user_order(order.user_id(), unit_order_arc.id());
-
-            // Register special orders for re-pricing tracking
-            #[cfg(feature = "special_orders")]
-            match &order {
-                OrderType::PeggedOrder { id, .. } => {
-                    self.special_order_tracker.register_pegged_order(*id);
-                }
-                OrderType::TrailingStop { id, .. } => {
-                    self.special_order_tracker.register_trailing_stop(*id);
-                }
-                _ => {}
-            }
+            let (unit_order_arc, queue_position) = self.rest_order(&order, remaining_qty)?;
 
             // Track state: Open (no fills) or PartiallyFilled (some fills, resting)
             if filled_qty > 0 {
@@ -1700,9 +2076,23 @@ where
                 self.track_state(order.id(), OrderStatus::Open);
             }
 
+            let resting = RestingInfo {
+                price,
+                side,
+                remaining_quantity: remaining_qty,
+                queue_position,
+                converted_to_limit: matches!(order, OrderType::MarketToLimit { .. }),
+                repriced_from,
+            };
+
             // Convert back to generic type for return
             let generic_order = self.convert_from_unit_type(&unit_order_arc);
-            Ok((Arc::new(generic_order), trade_result))
+            Ok(AddedOrder {
+                order: Arc::new(generic_order),
+                trade_result,
+                match_result,
+                resting: Some(resting),
+            })
         } else {
             // The order was fully matched
             self.track_state(
@@ -1711,7 +2101,133 @@ where
                     filled_quantity: original_qty,
                 },
             );
-            Ok((Arc::new(order), trade_result))
+            Ok(AddedOrder {
+                order: Arc::new(order),
+                trade_result,
+                match_result,
+                resting: None,
+            })
         }
     }
+
+    /// Rest `order`, with `remaining_qty` left of it, on its side of the
+    /// book: index it, schedule its expiry, register it with the risk
+    /// counters and admit it to its price level, then publish the level
+    /// change. Returns the resting unit order and its queue position.
+    ///
+    /// Shared by the residual of [`Self::add_order_inner`] and the
+    /// protected market-order remainder. The caller owns admission
+    /// checks and the lifecycle state transition.
+    pub(super) fn rest_order(
+        &self,
+        order: &OrderType<T>,
+        remaining_qty: u64,
+    ) -> Result<(Arc<OrderType<()>>, usize), OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
+        let price = order.price().as_u128();
+        let side = order.side();
+
+        let price_levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+
+        // Hold the level gate from lookup to admission so a concurrent
+        // cancel or sweep cannot unlink the level in between.
+        let level_gate = self.level_gate_read(price);
+        let price_level = price_levels.get_or_insert_with(price, || self.level_pool.acquire(price));
+        let level = price_level.value();
+
+        // Convert to unit type for PriceLevel compatibility. Admission
+        // into the level is validated upstream since pricelevel 0.9
+        // (duplicate id, counter capacity). The pre-sweep headroom
+        // check above makes a failure here concurrent-only; if it
+        // still happens, remove the level when this call created it
+        // empty — `best_bid` / `best_ask`, the cache, and the depth
+        // gauges must never expose a phantom level — and surface the
+        // error loudly: the sweep's trades are already irreversible
+        // (#211).
+        //
+        // The order is indexed, and admitted to the risk state, before it
+        // becomes visible in the level: a concurrent sweep may fill it the
+        // moment it is added, and the sweep's clean-up and risk release
+        // must find the entries to remove.
+        let unit_order = self.convert_to_unit_type(order);
+        self.order_locations.insert(order.id(), (price, side));
+        self.order_lifetimes
+            .on_rest(order.id(), order.timestamp().as_u64());
+        self.schedule_expiry(order.id(), order.time_in_force());
+        self.risk_state
+            .on_admission(order.id(), order.user_id(), price, remaining_qty);
+        self.track_user_order(order.user_id(), order.id());
+        let admission = level.add_order(unit_order);
+        drop(level_gate);
+        let (unit_order_arc, queue_position) = match admission {
+            Ok(admitted) => {
+                self.reconcile_depth(side, level);
+                let queue_position = match self.enforce_class_priority(level) {
+                    Some(queue) => queue
+                        .iter()
+                        .position(|resting| resting.id() == order.id())
+                        .unwrap_or(queue.len()),
+                    None => level.order_count().saturating_sub(1),
+                };
+                (admitted, queue_position)
+            }
+            Err(err) => {
+                self.order_locations.remove(&order.id());
+                self.expiry_wheel.unschedule(&order.id());
+                self.risk_state.on_cancel(order.id());
+                self.untrack_user_order(order.user_id(), &order.id());
+                self.remove_level_if_empty(side, price);
+                self.cache.invalidate();
+                self.refresh_depth_view();
+                self.record_depth_metric();
+                tracing::error!(
+                    order_id = %order.id(),
+                    price,
+                    error = %err,
+                    "residual admission failed after irreversible trades; level cleaned up"
+                );
+                return Err(OrderBookError::PriceLevelError(err));
+            }
+        };
+        // notify price level changes
+        if let Some(listener) = self.price_level_listener() {
+            let engine_seq = self.next_engine_seq();
+            listener(PriceLevelChangedEvent {
+                side,
+                price: level.price(),
+                quantity: level.visible_quantity(),
+                order_count: level.order_count(),
+                engine_seq,
+                config_version: self.config_version(),
+            })
+        }
+        self.store_extra_fields(order);
+        // Only the resting side changed; keep the opposite best price
+        // the sweep just published.
+        self.cache.invalidate_side(side);
+        self.refresh_depth_view();
+
+        // Refresh the depth gauges. The level may be brand-new
+        // (`get_or_insert` created it) or pre-existing — either
+        // way the gauge reflects current state. No-op when the
+        // `metrics` feature is disabled.
+        self.record_depth_metric();
+
+        // Register special orders for re-pricing tracking
+        #[cfg(feature = "special_orders")]
+        match order {
+            OrderType::PeggedOrder { id, .. } => {
+                self.special_order_tracker.register_pegged_order(*id);
+            }
+            OrderType::TrailingStop { id, .. } => {
+                self.special_order_tracker.register_trailing_stop(*id);
+            }
+            _ => {}
+        }
+
+        Ok((unit_order_arc, queue_position))
+    }
 }
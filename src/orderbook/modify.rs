@@ -0,0 +1,323 @@
+//! Unified order modification.
+//!
+//! [`OrderUpdate`] is defined by `pricelevel` and can only carry a new
+//! price and a new displayed quantity. [`OrderModification`] also covers
+//! the hidden tranche of iceberg and reserve orders and the time in
+//! force, and [`OrderBook::modify_order`] routes it to the cheapest path
+//! that preserves the book's priority rules:
+//!
+//! - A change to the displayed quantity alone is applied in place as an
+//!   [`OrderUpdate::UpdateQuantity`]: a decrease keeps the order's queue
+//!   position, an increase sends it to the back of its level.
+//! - Any change to the price, the hidden quantity or the time in force
+//!   cancels the order and re-adds the modified copy, which re-enters at
+//!   the back of its (possibly new) price level.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::modifications::OrderQuantity;
+use pricelevel::{Id, OrderType, OrderUpdate, Price, Quantity, TimeInForce};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A change to a resting order. Fields left as `None` keep their current
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderModification {
+    /// The order to modify
+    pub order_id: Id,
+    /// New limit price
+    pub price: Option<u128>,
+    /// New displayed quantity (the whole quantity of one-tranche orders)
+    pub quantity: Option<u64>,
+    /// New hidden quantity, for iceberg and reserve orders only
+    pub hidden_quantity: Option<u64>,
+    /// New time in force; IOC and FOK are rejected
+    pub time_in_force: Option<TimeInForce>,
+}
+
+impl OrderModification {
+    /// A modification of `order_id` that changes nothing yet.
+    #[must_use]
+    pub fn new(order_id: Id) -> Self {
+        Self {
+            order_id,
+            price: None,
+            quantity: None,
+            hidden_quantity: None,
+            time_in_force: None,
+        }
+    }
+
+    /// Set the new limit price.
+    #[must_use]
+    pub fn with_price(mut self, price: u128) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// Set the new displayed quantity.
+    #[must_use]
+    pub fn with_quantity(mut self, quantity: u64) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    /// Set the new hidden quantity.
+    #[must_use]
+    pub fn with_hidden_quantity(mut self, hidden_quantity: u64) -> Self {
+        self.hidden_quantity = Some(hidden_quantity);
+        self
+    }
+
+    /// Set the new time in force.
+    #[must_use]
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+}
+
+impl TryFrom<OrderUpdate> for OrderModification {
+    type Error = OrderBookError;
+
+    /// Price and quantity updates map onto the matching fields. `Cancel`
+    /// and `Replace` are not modifications: use
+    /// [`OrderBook::cancel_order`] or [`OrderBook::update_order`].
+    fn try_from(update: OrderUpdate) -> Result<Self, Self::Error> {
+        match update {
+            OrderUpdate::UpdatePrice {
+                order_id,
+                new_price,
+            } => Ok(Self::new(order_id).with_price(new_price.as_u128())),
+            OrderUpdate::UpdateQuantity {
+                order_id,
+                new_quantity,
+            } => Ok(Self::new(order_id).with_quantity(new_quantity.as_u64())),
+            OrderUpdate::UpdatePriceAndQuantity {
+                order_id,
+                new_price,
+                new_quantity,
+            } => Ok(Self::new(order_id)
+                .with_price(new_price.as_u128())
+                .with_quantity(new_quantity.as_u64())),
+            OrderUpdate::Cancel { order_id } | OrderUpdate::Replace { order_id, .. } => {
+                Err(OrderBookError::NotAModification { order_id })
+            }
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Modify the price, displayed quantity, hidden quantity and/or time
+    /// in force of a resting order of any type.
+    ///
+    /// Fields equal to the order's current values count as unchanged. A
+    /// change to the displayed quantity alone is applied in place, with
+    /// the priority rules of [`OrderUpdate::UpdateQuantity`]: a decrease
+    /// keeps the queue position, an increase loses it. Any other change
+    /// cancels the order and re-adds the modified copy at the back of its
+    /// price level, under the same id, user and admission timestamp.
+    /// Every path is validate-first: a rejected modification leaves the
+    /// order untouched.
+    ///
+    /// The submit gate is held exclusively for the whole modification, so
+    /// a fill cannot land between reading the order and applying the
+    /// change.
+    ///
+    /// An [`OrderUpdate`] price or quantity change converts into an
+    /// [`OrderModification`] with `try_into`.
+    ///
+    /// Returns the modified order, or `Ok(None)` when no order with the
+    /// requested id is resting.
+    ///
+    /// # Errors
+    /// - [`OrderBookError::KillSwitchActive`] while the kill switch is
+    ///   engaged.
+    /// - [`OrderBookError::NothingToModify`] when the modification changes
+    ///   nothing.
+    /// - [`OrderBookError::ZeroQuantity`] for a zero displayed quantity.
+    /// - [`OrderBookError::NoHiddenTranche`] for a hidden quantity on an
+    ///   order without a hidden tranche.
+    /// - [`OrderBookError::ImmediateTifOnResting`] for an IOC or FOK time
+    ///   in force.
+    /// - The admission errors of the re-added order, such as
+    ///   [`OrderBookError::InvalidTickSize`],
+    ///   [`OrderBookError::InvalidLotSize`] or
+    ///   [`OrderBookError::PriceCrossing`] for a post-only order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, OrderModification};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let id = Id::from_u64(1);
+    /// book.add_iceberg_order(id, 100, 5, 20, Side::Sell, TimeInForce::Gtc, None)?;
+    ///
+    /// let modified = book
+    ///     .modify_order(
+    ///         OrderModification::new(id)
+    ///             .with_price(101)
+    ///             .with_hidden_quantity(10)
+    ///             .with_time_in_force(TimeInForce::Day),
+    ///     )?
+    ///     .expect("resting");
+    /// assert_eq!(modified.price().as_u128(), 101);
+    /// assert_eq!(modified.hidden_quantity().as_u64(), 10);
+    /// assert_eq!(modified.time_in_force(), TimeInForce::Day);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn modify_order(
+        &self,
+        modification: OrderModification,
+    ) -> Result<Option<Arc<OrderType<T>>>, OrderBookError> {
+        let order_id = modification.order_id;
+        let _gate = self.submit_gate_write();
+        self.check_kill_switch()?;
+        let Some(current) = self.get_order(order_id) else {
+            return Ok(None);
+        };
+        let price = modification
+            .price
+            .filter(|&price| price != current.price().as_u128());
+        let quantity = modification
+            .quantity
+            .filter(|&quantity| quantity != current.visible_quantity().as_u64());
+        let hidden_quantity = modification
+            .hidden_quantity
+            .filter(|&hidden| hidden != current.hidden_quantity().as_u64());
+        let time_in_force = modification
+            .time_in_force
+            .filter(|&tif| tif != current.time_in_force());
+
+        if quantity == Some(0) {
+            return Err(OrderBookError::ZeroQuantity { order_id });
+        }
+        let two_tranche = matches!(
+            *current,
+            OrderType::IcebergOrder { .. } | OrderType::ReserveOrder { .. }
+        );
+        if modification.hidden_quantity.is_some() && !two_tranche {
+            return Err(OrderBookError::NoHiddenTranche { order_id });
+        }
+        if let Some(time_in_force) = modification.time_in_force.filter(|tif| tif.is_immediate()) {
+            return Err(OrderBookError::ImmediateTifOnResting {
+                order_id,
+                time_in_force,
+            });
+        }
+
+        match (price, quantity, hidden_quantity, time_in_force) {
+            (None, None, None, None) => Err(OrderBookError::NothingToModify { order_id }),
+            (None, Some(quantity), None, None) => {
+                self.update_order_ungated(OrderUpdate::UpdateQuantity {
+                    order_id,
+                    new_quantity: Quantity::new(quantity),
+                })
+            }
+            _ => {
+                let mut new_order = match quantity {
+                    Some(quantity) => with_displayed_quantity(&current, quantity),
+                    None => (*current).clone(),
+                };
+                apply_modification(&mut new_order, price, hidden_quantity, time_in_force);
+
+                // Validate-first (#98): the original only leaves the book
+                // once the modified copy is known to be admissible.
+                let config = self.config();
+                self.validate_order_shape(&config, &new_order)?;
+                self.check_risk_modify_admission(
+                    &config,
+                    order_id,
+                    new_order.user_id(),
+                    new_order.price().as_u128(),
+                    new_order.total_quantity(),
+                )?;
+                self.check_modify_stp_self_cross(&new_order)?;
+
+                let result = self.replace_resting_order(order_id, new_order)?;
+                Ok(Some(result))
+            }
+        }
+    }
+}
+
+/// A copy of `order` with its displayed quantity set to `quantity`, larger
+/// or smaller. Despite its name, `OrderType::with_reduced_quantity` is a
+/// plain setter of the displayed tranche, so it serves increases too.
+fn with_displayed_quantity<T: Clone>(order: &OrderType<T>, quantity: u64) -> OrderType<T> {
+    order.with_reduced_quantity(quantity)
+}
+
+/// Write the changed fields into `order`. `hidden_quantity` is only ever
+/// set for the two-tranche kinds.
+fn apply_modification<T>(
+    order: &mut OrderType<T>,
+    new_price: Option<u128>,
+    new_hidden: Option<u64>,
+    new_time_in_force: Option<TimeInForce>,
+) {
+    match order {
+        OrderType::IcebergOrder {
+            price,
+            hidden_quantity,
+            time_in_force,
+            ..
+        }
+        | OrderType::ReserveOrder {
+            price,
+            hidden_quantity,
+            time_in_force,
+            ..
+        } => {
+            set_common(price, time_in_force, new_price, new_time_in_force);
+            if let Some(hidden) = new_hidden {
+                *hidden_quantity = Quantity::new(hidden);
+            }
+        }
+        OrderType::Standard {
+            price,
+            time_in_force,
+            ..
+        }
+        | OrderType::PostOnly {
+            price,
+            time_in_force,
+            ..
+        }
+        | OrderType::TrailingStop {
+            price,
+            time_in_force,
+            ..
+        }
+        | OrderType::PeggedOrder {
+            price,
+            time_in_force,
+            ..
+        }
+        | OrderType::MarketToLimit {
+            price,
+            time_in_force,
+            ..
+        } => set_common(price, time_in_force, new_price, new_time_in_force),
+    }
+}
+
+fn set_common(
+    price: &mut Price,
+    time_in_force: &mut TimeInForce,
+    new_price: Option<u128>,
+    new_time_in_force: Option<TimeInForce>,
+) {
+    if let Some(new_price) = new_price {
+        *price = Price::new(new_price);
+    }
+    if let Some(new_time_in_force) = new_time_in_force {
+        *time_in_force = new_time_in_force;
+    }
+}
@@ -7,6 +7,16 @@
 //! - `{prefix}.{symbol}` — per-symbol stream
 //! - `{prefix}.all` — aggregate stream
 //!
+//! Chain-wide [`GreeksSnapshot`]s can additionally be published on demand to
+//! `{prefix}.greeks.{underlying}` via [`NatsTradePublisher::publish_greeks`].
+//!
+//! Trades that carry fills are also stamped with their execution ids: an
+//! `Execution-Id-First` / `Execution-Id-Last` header pair naming the range
+//! of [`TradeFill::execution_id`](crate::orderbook::trade::TradeFill::execution_id)s
+//! in the payload, and a `Nats-Msg-Id` derived from the subject, the symbol
+//! and the first execution id, so JetStream's duplicate window drops the
+//! copies a retried publish can produce.
+//!
 //! The listener callback is non-blocking on the matching hot path: it clones
 //! the [`TradeResult`] into a bounded channel and returns immediately — no
 //! serialization, no `format!`, and no per-trade task spawn happen on the
@@ -26,6 +36,7 @@
 //! orderbook-rs = { version = "0.6", features = ["nats"] }
 //! ```
 
+use crate::orderbook::implied_volatility::GreeksSnapshot;
 use crate::orderbook::serialization::{EventSerializer, JsonEventSerializer};
 use crate::orderbook::trade::{TradeListener, TradeResult};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -78,6 +89,51 @@ fn account_publish_outcome(
     }
 }
 
+/// Dedup headers for a trade with fills, derived from its execution ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExecutionHeaders {
+    /// `{symbol}:{first}` — execution ids are per book, so the symbol is
+    /// needed to keep ids from different books apart on the aggregate
+    /// subject.
+    key: String,
+    first: u64,
+    last: u64,
+}
+
+impl ExecutionHeaders {
+    /// `None` when the trade has no fills, or they pre-date execution ids.
+    fn for_trade(trade: &TradeResult) -> Option<Self> {
+        let first = trade.fills.first()?.execution_id;
+        let last = trade.fills.last()?.execution_id;
+        (first != 0).then(|| Self {
+            key: format!("{}:{first}", trade.symbol),
+            first,
+            last,
+        })
+    }
+
+    /// The `Nats-Msg-Id` for a publish to `subject`. Qualified by subject
+    /// so the per-symbol and aggregate copies are not deduplicated against
+    /// each other when one stream captures both.
+    fn msg_id(&self, subject: &str) -> String {
+        format!("{subject}:{}", self.key)
+    }
+
+    fn apply(&self, headers: &mut async_nats::HeaderMap, subject: &str) {
+        headers.insert("Nats-Msg-Id", self.msg_id(subject).as_str());
+        headers.insert("Execution-Id-First", self.first.to_string().as_str());
+        headers.insert("Execution-Id-Last", self.last.to_string().as_str());
+    }
+}
+
+/// Replace the per-fill detail of `trade` with its per-level aggregates.
+fn condense_fills(trade: &mut TradeResult) {
+    if trade.level_fills.is_empty() {
+        trade.level_fills = trade.aggregate_levels();
+    }
+    trade.fills = Vec::new();
+}
+
 /// Clamps a caller-supplied bounded-channel capacity up to the minimum a Tokio
 /// mpsc channel accepts (`1`).
 ///
@@ -226,6 +282,11 @@ pub struct NatsTradePublisher {
     /// [`with_serializer`](NatsTradePublisher::with_serializer).
     serializer: Arc<dyn EventSerializer>,
 
+    /// When `false`, published trades carry [`TradeResult::level_fills`]
+    /// instead of the per-fill `fills`. Set via
+    /// [`with_fill_detail`](NatsTradePublisher::with_fill_detail).
+    fill_detail: bool,
+
     /// Join handle for the single background batch task, populated by
     /// [`into_listener`](NatsTradePublisher::into_listener). Taken and awaited
     /// by [`shutdown`](NatsTradePublisher::shutdown) so teardown can join the
@@ -270,6 +331,7 @@ impl NatsTradePublisher {
             batches_published: AtomicU64::new(0),
             dropped_events: AtomicU64::new(0),
             serializer: Arc::new(JsonEventSerializer),
+            fill_detail: true,
             task_handle: Mutex::new(None),
             shutdown_tx: Mutex::new(None),
         }
@@ -351,6 +413,21 @@ impl NatsTradePublisher {
         self
     }
 
+    /// Choose between per-fill detail and per-level aggregates in the
+    /// published payload.
+    ///
+    /// Defaults to `true`: trades are published as emitted. With `false`,
+    /// each trade's `fills` are dropped and replaced by
+    /// [`TradeResult::level_fills`] (computed here when the book did not
+    /// attach them), which keeps payloads small for large sweeps. The
+    /// execution id headers are still derived from the full fills.
+    #[must_use = "builders do nothing unless consumed"]
+    #[inline]
+    pub fn with_fill_detail(mut self, fill_detail: bool) -> Self {
+        self.fill_detail = fill_detail;
+        self
+    }
+
     /// Returns the number of successfully published trades.
     #[must_use]
     #[inline]
@@ -457,6 +534,44 @@ impl NatsTradePublisher {
         (handle, listener)
     }
 
+    /// Publish a [`GreeksSnapshot`] to `{prefix}.greeks.{underlying}`.
+    ///
+    /// Greeks snapshots are produced on demand (typically on a timer for a
+    /// risk dashboard), not on the matching path, so this publishes
+    /// directly rather than through the trade channel. The payload uses the
+    /// configured serializer and the same retry policy as trades. Snapshot
+    /// publishes do not touch the trade counters or the `Nats-Sequence`
+    /// stream; the snapshot's `timestamp_ms` is sent as the
+    /// `Greeks-Timestamp` header instead.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the snapshot was published, `false` if serialization failed
+    /// or every retry was exhausted.
+    pub async fn publish_greeks(self: &Arc<Self>, snapshot: &GreeksSnapshot) -> bool {
+        let payload = match self.serializer.serialize_greeks(snapshot) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                error!(error = %e, "failed to serialize greeks snapshot for NATS");
+                return false;
+            }
+        };
+
+        let subject = format!("{}.greeks.{}", self.subject_prefix, snapshot.underlying);
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert("Content-Type", self.serializer.content_type());
+        headers.insert(
+            "Greeks-Timestamp",
+            snapshot.timestamp_ms.to_string().as_str(),
+        );
+
+        let published = Self::publish_single(self, &subject, payload.into(), headers).await;
+        if published {
+            trace!(subject, "greeks snapshot published to NATS");
+        }
+        published
+    }
+
     /// Gracefully shut down the background publish task.
     ///
     /// Signals the background task to drain any trades still buffered in the
@@ -582,7 +697,11 @@ impl NatsTradePublisher {
         }
 
         let trades = std::mem::take(batch);
-        for trade in trades {
+        for mut trade in trades {
+            let executions = ExecutionHeaders::for_trade(&trade);
+            if !publisher.fill_detail {
+                condense_fills(&mut trade);
+            }
             let payload = match publisher.serializer.serialize_trade(&trade) {
                 Ok(bytes) => bytes,
                 Err(e) => {
@@ -605,6 +724,7 @@ impl NatsTradePublisher {
                 payload_bytes,
                 symbol_seq,
                 all_seq,
+                executions,
             )
             .await;
         }
@@ -627,7 +747,8 @@ impl NatsTradePublisher {
     ///
     /// Each subject receives its own unique sequence number in the
     /// `Nats-Sequence` header so consumers can deduplicate per-stream without
-    /// collisions between the symbol and aggregate streams.
+    /// collisions between the symbol and aggregate streams. Trades with
+    /// fills additionally carry their [`ExecutionHeaders`].
     async fn publish_with_retry(
         publisher: Arc<Self>,
         symbol_subject: String,
@@ -635,6 +756,7 @@ impl NatsTradePublisher {
         payload: bytes::Bytes,
         symbol_seq: u64,
         all_seq: u64,
+        executions: Option<ExecutionHeaders>,
     ) {
         let content_type = publisher.serializer.content_type();
 
@@ -646,6 +768,11 @@ impl NatsTradePublisher {
         all_headers.insert("Nats-Sequence", all_seq.to_string().as_str());
         all_headers.insert("Content-Type", content_type);
 
+        if let Some(executions) = &executions {
+            executions.apply(&mut symbol_headers, &symbol_subject);
+            executions.apply(&mut all_headers, &all_subject);
+        }
+
         // Publish to symbol-specific subject
         let symbol_ok =
             Self::publish_single(&publisher, &symbol_subject, payload.clone(), symbol_headers)
@@ -761,6 +888,7 @@ impl std::fmt::Debug for NatsTradePublisher {
                 &self.dropped_events.load(Ordering::Relaxed),
             )
             .field("serializer", &self.serializer.content_type())
+            .field("fill_detail", &self.fill_detail)
             .finish()
     }
 }
@@ -998,4 +1126,91 @@ mod tests {
         assert!(display.contains("nats serialization error"));
         assert!(display.contains("invalid utf-8"));
     }
+
+    #[test]
+    fn test_execution_headers_span_the_trade_fills() {
+        use crate::orderbook::book::OrderBook;
+        use pricelevel::{Side, TimeInForce};
+
+        let book = OrderBook::<()>::new("BTC/USD");
+        let trades = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            if let Ok(mut trades) = sink.lock() {
+                trades.push(trade.clone());
+            }
+        }));
+        for price in [100, 101] {
+            let added =
+                book.add_limit_order(Id::new_uuid(), price, 1, Side::Sell, TimeInForce::Gtc, None);
+            assert!(added.is_ok());
+        }
+        assert!(
+            book.submit_market_order(Id::new_uuid(), 2, Side::Buy)
+                .is_ok()
+        );
+
+        let trade = trades.lock().expect("trade sink")[0].clone();
+        let headers = ExecutionHeaders::for_trade(&trade).expect("trade has fills");
+        assert_eq!(
+            headers,
+            ExecutionHeaders {
+                key: "BTC/USD:1".to_string(),
+                first: 1,
+                last: 2,
+            }
+        );
+        assert_eq!(headers.msg_id("trades.all"), "trades.all:BTC/USD:1");
+        assert_ne!(
+            headers.msg_id("trades.all"),
+            headers.msg_id("trades.BTC/USD")
+        );
+
+        // Results built by hand carry no fills and get no dedup headers.
+        assert_eq!(
+            ExecutionHeaders::for_trade(&make_trade_result("BTC/USD")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_condense_fills_keeps_one_entry_per_level() {
+        use crate::orderbook::book::OrderBook;
+        use pricelevel::{Side, TimeInForce};
+
+        let book = OrderBook::<()>::new("BTC/USD");
+        let trades = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            if let Ok(mut trades) = sink.lock() {
+                trades.push(trade.clone());
+            }
+        }));
+        for price in [100, 100, 100, 101] {
+            let added =
+                book.add_limit_order(Id::new_uuid(), price, 1, Side::Sell, TimeInForce::Gtc, None);
+            assert!(added.is_ok());
+        }
+        assert!(
+            book.submit_market_order(Id::new_uuid(), 4, Side::Buy)
+                .is_ok()
+        );
+
+        let mut trade = trades.lock().expect("trade sink")[0].clone();
+        assert_eq!(trade.fills.len(), 4);
+        assert!(trade.level_fills.is_empty(), "aggregation is off");
+        let full = serde_json::to_vec(&trade).expect("serialize trade");
+        condense_fills(&mut trade);
+        assert!(trade.fills.is_empty());
+        assert_eq!(
+            trade
+                .level_fills
+                .iter()
+                .map(|level| (level.price, level.quantity, level.counterparty_count))
+                .collect::<Vec<_>>(),
+            vec![(100, 3, 3), (101, 1, 1)]
+        );
+        let condensed = serde_json::to_vec(&trade).expect("serialize trade");
+        assert!(condensed.len() < full.len());
+    }
 }
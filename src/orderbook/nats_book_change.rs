@@ -25,6 +25,7 @@
 //! ```
 
 use crate::orderbook::book_change_event::{PriceLevelChangedEvent, PriceLevelChangedListener};
+use crate::orderbook::price_scale::PriceScale;
 use pricelevel::Side;
 use serde::Serialize;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -117,6 +118,9 @@ pub struct BookChangeBatch {
 
     /// The individual price level changes.
     pub changes: Vec<BookChangeEntry>,
+
+    /// Decimal scale of the raw `price` and `quantity` of every change.
+    pub price_scale: PriceScale,
 }
 
 /// A single price level change within a [`BookChangeBatch`].
@@ -131,6 +135,9 @@ pub struct BookChangeEntry {
     /// The new visible quantity at this price level after the change.
     pub quantity: u64,
 
+    /// Number of orders resting at this price level after the change.
+    pub order_count: usize,
+
     /// Strictly monotonic global engine sequence number for this entry.
     /// Inherited from [`PriceLevelChangedEvent::engine_seq`] at conversion
     /// time. Independent of [`BookChangeBatch::sequence`] (which is the
@@ -145,6 +152,7 @@ impl From<PriceLevelChangedEvent> for BookChangeEntry {
             side: event.side,
             price: event.price,
             quantity: event.quantity,
+            order_count: event.order_count,
             engine_seq: event.engine_seq,
         }
     }
@@ -234,6 +242,9 @@ pub struct NatsBookChangePublisher {
     /// Maximum retry attempts for transient NATS failures.
     max_retries: u32,
 
+    /// Decimal scale stamped on every published batch.
+    price_scale: PriceScale,
+
     /// Monotonically increasing batch sequence number.
     sequence: AtomicU64,
 
@@ -290,6 +301,7 @@ impl NatsBookChangePublisher {
             channel_capacity: DEFAULT_CHANNEL_CAPACITY,
             min_publish_interval_ms: DEFAULT_MIN_PUBLISH_INTERVAL_MS,
             max_retries: DEFAULT_MAX_RETRIES,
+            price_scale: PriceScale::default(),
             sequence: AtomicU64::new(0),
             publish_count: AtomicU64::new(0),
             error_count: AtomicU64::new(0),
@@ -361,6 +373,18 @@ impl NatsBookChangePublisher {
         self
     }
 
+    /// Set the decimal scale stamped on every published batch.
+    ///
+    /// Pass the source book's [`OrderBook::price_scale`](crate::OrderBook::price_scale)
+    /// so consumers can render display prices. Defaults to the identity
+    /// scale.
+    #[must_use = "builders do nothing unless consumed"]
+    #[inline]
+    pub fn with_price_scale(mut self, price_scale: PriceScale) -> Self {
+        self.price_scale = price_scale;
+        self
+    }
+
     /// Returns the number of successfully published batches.
     #[must_use]
     #[inline]
@@ -608,6 +632,7 @@ impl NatsBookChangePublisher {
             timestamp_ms,
             event_count: changes.len(),
             changes: changes.clone(),
+            price_scale: publisher.price_scale,
         };
 
         // Publish the aggregate changes subject
@@ -628,6 +653,7 @@ impl NatsBookChangePublisher {
                 timestamp_ms,
                 event_count: bid_changes.len(),
                 changes: bid_changes,
+                price_scale: publisher.price_scale,
             };
             let bid_subject = format!("{}.{}.bid", publisher.subject_prefix, publisher.symbol);
             Self::publish_batch(publisher, &bid_subject, &bid_batch, bid_seq).await
@@ -649,6 +675,7 @@ impl NatsBookChangePublisher {
                 timestamp_ms,
                 event_count: ask_changes.len(),
                 changes: ask_changes,
+                price_scale: publisher.price_scale,
             };
             let ask_subject = format!("{}.{}.ask", publisher.subject_prefix, publisher.symbol);
             Self::publish_batch(publisher, &ask_subject, &ask_batch, ask_seq).await
@@ -814,12 +841,15 @@ mod tests {
             side: Side::Buy,
             price: 50_000,
             quantity: 100,
+            order_count: 1,
             engine_seq: 7,
+            config_version: 0,
         };
         let entry = BookChangeEntry::from(event);
         assert_eq!(entry.side, Side::Buy);
         assert_eq!(entry.price, 50_000);
         assert_eq!(entry.quantity, 100);
+        assert_eq!(entry.order_count, 1);
         assert_eq!(
             entry.engine_seq, 7,
             "BookChangeEntry must propagate engine_seq from the source event"
@@ -832,6 +862,7 @@ mod tests {
             side: Side::Buy,
             price: 50_000,
             quantity: 100,
+            order_count: 1,
             engine_seq: 11,
         };
         let result = serde_json::to_value(&entry);
@@ -849,18 +880,21 @@ mod tests {
             symbol: "BTC/USD".to_string(),
             sequence: 42,
             timestamp_ms: 1_700_000_000_000,
+            price_scale: PriceScale::default(),
             event_count: 2,
             changes: vec![
                 BookChangeEntry {
                     side: Side::Buy,
                     price: 50_000,
                     quantity: 100,
+                    order_count: 1,
                     engine_seq: 1,
                 },
                 BookChangeEntry {
                     side: Side::Sell,
                     price: 50_100,
                     quantity: 200,
+                    order_count: 1,
                     engine_seq: 2,
                 },
             ],
@@ -882,11 +916,13 @@ mod tests {
             symbol: "ETH/USDT".to_string(),
             sequence: 7,
             timestamp_ms: 1_700_000_000_000,
+            price_scale: PriceScale::default(),
             event_count: 1,
             changes: vec![BookChangeEntry {
                 side: Side::Sell,
                 price: 2_000,
                 quantity: 50,
+                order_count: 1,
                 engine_seq: 3,
             }],
         };
@@ -968,6 +1004,7 @@ mod tests {
             symbol: "BTC/USD".to_string(),
             sequence: 0,
             timestamp_ms: 0,
+            price_scale: PriceScale::default(),
             event_count: 0,
             changes: vec![],
         };
@@ -984,7 +1021,9 @@ mod tests {
             side: Side::Buy,
             price: 42_000,
             quantity: 500,
+            order_count: 1,
             engine_seq: 0,
+            config_version: 0,
         };
         let result = serde_json::to_value(&event);
         assert!(result.is_ok());
@@ -374,7 +374,9 @@ where
     /// # Errors
     /// Returns [`OrderBookError::KillSwitchActive`] when the kill switch
     /// is engaged. The check happens at the top of the function before
-    /// any matching, fee, or STP work.
+    /// any matching, fee, or STP work. Returns
+    /// [`OrderBookError::MarketClosed`] outside continuous trading of a
+    /// configured trading session.
     pub fn submit_market_order(
         &self,
         id: Id,
@@ -382,6 +384,8 @@ where
         side: Side,
     ) -> Result<MatchResult, OrderBookError> {
         self.check_kill_switch_or_reject(id)?;
+        self.check_session_or_reject(id)?;
+        self.check_rate_limit_or_reject(id, Hash32::zero())?;
         // Pre-trade risk gate. Per design decision C, market orders
         // currently bypass every check (no submitted price; no rest);
         // the call exists to keep the gate ordering consistent across
@@ -408,7 +412,8 @@ where
     /// taker before any fills occur. Returns
     /// [`OrderBookError::KillSwitchActive`] when the kill switch is
     /// engaged; the check happens at the top of the function before any
-    /// matching, fee, or STP work.
+    /// matching, fee, or STP work. Returns [`OrderBookError::MarketClosed`]
+    /// outside continuous trading of a configured trading session.
     pub fn submit_market_order_with_user(
         &self,
         id: Id,
@@ -417,6 +422,9 @@ where
         user_id: Hash32,
     ) -> Result<MatchResult, OrderBookError> {
         self.check_kill_switch_or_reject(id)?;
+        self.check_session_or_reject(id)?;
+        self.check_user_blocked_or_reject(id, user_id)?;
+        self.check_rate_limit_or_reject(id, user_id)?;
         // Pre-trade risk gate. Per design decision C, market orders
         // currently bypass every check; the call exists to keep the
         // gate ordering consistent across submit and add paths.
@@ -439,8 +447,10 @@ where
     ///
     /// # Errors
     /// Returns [`OrderBookError::KillSwitchActive`] when the kill switch
-    /// is engaged. Propagates [`OrderBookError::InsufficientLiquidityNotional`]
-    /// from the matching engine when no liquidity is available.
+    /// is engaged and [`OrderBookError::MarketClosed`] outside continuous
+    /// trading of a configured trading session. Propagates
+    /// [`OrderBookError::InsufficientLiquidityNotional`] from the matching
+    /// engine when no liquidity is available.
     pub fn submit_market_order_by_amount(
         &self,
         id: Id,
@@ -448,6 +458,8 @@ where
         side: Side,
     ) -> Result<MatchResult, OrderBookError> {
         self.check_kill_switch_or_reject(id)?;
+        self.check_session_or_reject(id)?;
+        self.check_rate_limit_or_reject(id, Hash32::zero())?;
         // Pre-trade risk gate. Per design decision C, market orders
         // currently bypass every check (no submitted price; no rest);
         // the call exists to keep the gate ordering consistent across
@@ -469,8 +481,10 @@ where
     /// Returns [`OrderBookError::SelfTradePrevented`] when STP cancels
     /// the taker before any fills occur. Returns
     /// [`OrderBookError::KillSwitchActive`] when the kill switch is
-    /// engaged. Returns [`OrderBookError::InsufficientLiquidityNotional`]
-    /// when the book had zero matchable depth.
+    /// engaged and [`OrderBookError::MarketClosed`] outside continuous
+    /// trading of a configured trading session. Returns
+    /// [`OrderBookError::InsufficientLiquidityNotional`] when the book had
+    /// zero matchable depth.
     pub fn submit_market_order_by_amount_with_user(
         &self,
         id: Id,
@@ -479,6 +493,9 @@ where
         user_id: Hash32,
     ) -> Result<MatchResult, OrderBookError> {
         self.check_kill_switch_or_reject(id)?;
+        self.check_session_or_reject(id)?;
+        self.check_user_blocked_or_reject(id, user_id)?;
+        self.check_rate_limit_or_reject(id, user_id)?;
         self.risk_state.check_market_admission(user_id)?;
         trace!(
             "Submitting notional market order {} amount={} {} (user: {})",
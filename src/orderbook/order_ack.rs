@@ -0,0 +1,53 @@
+//! Submission acknowledgements carrying the resting state of an order.
+
+use pricelevel::{MatchResult, Side};
+use serde::{Deserialize, Serialize};
+
+/// Where the unfilled remainder of a submitted order rested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestingInfo {
+    /// Price level the remainder rests at (in price units)
+    pub price: u128,
+
+    /// Side the remainder rests on
+    pub side: Side,
+
+    /// Remaining visible plus hidden quantity (in units)
+    pub remaining_quantity: u64,
+
+    /// Number of orders ahead of this one in the level's FIFO queue at
+    /// admission; `0` is the front of the queue. Concurrent fills and
+    /// cancels at the level move it forward afterwards.
+    pub queue_position: usize,
+
+    /// `true` when the order was a market-to-limit order whose remainder
+    /// was converted into a resting limit order at its price.
+    pub converted_to_limit: bool,
+
+    /// Price the order was submitted at when a crossing post-only order
+    /// was slid to [`price`](Self::price) under
+    /// [`PostOnlyMode::Slide`](crate::PostOnlyMode::Slide); `None` when it
+    /// rests at its submitted price.
+    #[serde(default)]
+    pub repriced_from: Option<u128>,
+}
+
+/// Result of [`OrderBook::add_order_with_ack`](crate::OrderBook::add_order_with_ack).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderAck {
+    /// Fills of the submitted order; empty when it rested without trading
+    pub match_result: MatchResult,
+
+    /// Resting state of the remainder, or `None` when the order was fully
+    /// filled
+    pub resting: Option<RestingInfo>,
+}
+
+impl OrderAck {
+    /// `true` if any part of the order is resting on the book.
+    #[must_use]
+    #[inline]
+    pub fn is_resting(&self) -> bool {
+        self.resting.is_some()
+    }
+}
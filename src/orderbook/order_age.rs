@@ -0,0 +1,340 @@
+//! Resting order ages and quote-lifetime distributions.
+//!
+//! Every resting order carries the timestamp it entered the book with, so
+//! its age is the book's [`Clock`](crate::Clock) reading minus that
+//! timestamp. [`OrderBook::order_age`], [`OrderBook::level_ages`] and
+//! [`OrderBook::age_weighted_depth`] read those timestamps straight from
+//! the price levels and need no extra bookkeeping.
+//!
+//! Lifetimes of orders that have already left the book need the entry time
+//! after the order is gone, so they are opt-in: after
+//! [`OrderBook::set_lifetime_tracking`] the book remembers the entry time
+//! of every order that rests, and records the time to cancellation or to
+//! the completing fill in a [`QuoteLifetimeStats`] read through
+//! [`OrderBook::quote_lifetimes`]. Orders that rested before tracking was
+//! enabled are not recorded. Like [`OrderBook::stats`] the distributions
+//! are operational only and are not part of snapshots.
+
+use super::book::OrderBook;
+use super::statistics::{LIFETIME_BUCKETS, LifetimeDistribution, QuoteLifetimeStats};
+use dashmap::DashMap;
+use pricelevel::{Id, PriceLevel, Side};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Age profile of the orders resting at one price level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelAge {
+    /// Level price, in price units
+    pub price: u128,
+    /// Orders resting at the level
+    pub order_count: usize,
+    /// Visible plus hidden quantity resting at the level
+    pub quantity: u64,
+    /// Mean age of the level's orders, in milliseconds
+    pub average_age_ms: u64,
+    /// Age of the oldest order, the one at the front of the queue, in
+    /// milliseconds
+    pub oldest_age_ms: u64,
+}
+
+/// Live histogram behind a [`LifetimeDistribution`].
+#[derive(Debug)]
+struct LifetimeHistogram {
+    count: AtomicU64,
+    total_ms: AtomicU64,
+    min_ms: AtomicU64,
+    max_ms: AtomicU64,
+    buckets: [AtomicU64; LIFETIME_BUCKETS],
+}
+
+impl Default for LifetimeHistogram {
+    fn default() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            total_ms: AtomicU64::new(0),
+            min_ms: AtomicU64::new(u64::MAX),
+            max_ms: AtomicU64::new(0),
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+impl LifetimeHistogram {
+    fn record(&self, ms: u64) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_ms.fetch_add(ms, Ordering::Relaxed);
+        self.min_ms.fetch_min(ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(ms, Ordering::Relaxed);
+        self.buckets[LifetimeDistribution::bucket_of(ms)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.total_ms.store(0, Ordering::Relaxed);
+        self.min_ms.store(u64::MAX, Ordering::Relaxed);
+        self.max_ms.store(0, Ordering::Relaxed);
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> LifetimeDistribution {
+        let count = self.count.load(Ordering::Relaxed);
+        let recorded = |value: u64| (count > 0).then_some(value);
+        LifetimeDistribution {
+            count,
+            total_ms: self.total_ms.load(Ordering::Relaxed),
+            min_ms: recorded(self.min_ms.load(Ordering::Relaxed)),
+            max_ms: recorded(self.max_ms.load(Ordering::Relaxed)),
+            buckets: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+}
+
+/// Entry times of resting orders and the lifetime histograms they feed.
+/// Inert until enabled.
+#[derive(Debug, Default)]
+pub(crate) struct OrderLifetimes {
+    enabled: AtomicBool,
+    entered_ms: DashMap<Id, u64>,
+    cancelled: LifetimeHistogram,
+    filled: LifetimeHistogram,
+}
+
+impl OrderLifetimes {
+    #[inline]
+    fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Remember that `order_id` rested at `entered_ms`.
+    #[inline]
+    pub(crate) fn on_rest(&self, order_id: Id, entered_ms: u64) {
+        if self.is_enabled() {
+            self.entered_ms.insert(order_id, entered_ms);
+        }
+    }
+
+    /// Take the entry time of an order leaving the book.
+    #[inline]
+    fn take(&self, order_id: &Id) -> Option<u64> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.entered_ms.remove(order_id).map(|(_, entered)| entered)
+    }
+
+    /// Drop the entry time of an order that left without a terminal
+    /// cancel or fill.
+    #[inline]
+    pub(crate) fn forget(&self, order_id: &Id) {
+        self.take(order_id);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entered_ms.clear();
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Milliseconds `order_id` has been resting, per the book's clock.
+    /// `None` if the order is not resting in the book.
+    #[must_use]
+    pub fn order_age(&self, order_id: Id) -> Option<u64> {
+        let (price, side) = *self.order_locations.get(&order_id)?.value();
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let level = levels.get(&price)?;
+        let entered = level
+            .value()
+            .iter_orders()
+            .find(|order| order.id() == order_id)?
+            .timestamp()
+            .as_u64();
+        Some(self.clock.now_millis().as_u64().saturating_sub(entered))
+    }
+
+    /// Age profile of the best `levels` levels of `side`, best first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{Clock, OrderBook, StubClock};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    /// use std::sync::Arc;
+    ///
+    /// let clock = Arc::new(StubClock::with_step(1_000, 10)) as Arc<dyn Clock>;
+    /// let book = OrderBook::<()>::with_clock("TEST", clock);
+    /// book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    /// book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    ///
+    /// let ages = book.level_ages(Side::Buy, 1);
+    /// assert_eq!(ages[0].order_count, 2);
+    /// assert!(ages[0].oldest_age_ms > ages[0].average_age_ms);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    #[must_use]
+    pub fn level_ages(&self, side: Side, levels: usize) -> Vec<LevelAge> {
+        let now = self.clock.now_millis().as_u64();
+        let walk = |level: &PriceLevel| {
+            let mut age = LevelAge {
+                price: level.price(),
+                order_count: 0,
+                quantity: 0,
+                average_age_ms: 0,
+                oldest_age_ms: 0,
+            };
+            let mut total_age = 0u128;
+            for order in level.iter_orders() {
+                let order_age = now.saturating_sub(order.timestamp().as_u64());
+                age.order_count += 1;
+                age.quantity = age.quantity.saturating_add(
+                    order
+                        .visible_quantity()
+                        .as_u64()
+                        .saturating_add(order.hidden_quantity().as_u64()),
+                );
+                age.oldest_age_ms = age.oldest_age_ms.max(order_age);
+                total_age += u128::from(order_age);
+            }
+            if age.order_count > 0 {
+                age.average_age_ms =
+                    u64::try_from(total_age / age.order_count as u128).unwrap_or(u64::MAX);
+            }
+            age
+        };
+        match side {
+            Side::Buy => self
+                .bids
+                .iter()
+                .rev()
+                .take(levels)
+                .map(|entry| walk(entry.value()))
+                .collect(),
+            Side::Sell => self
+                .asks
+                .iter()
+                .take(levels)
+                .map(|entry| walk(entry.value()))
+                .collect(),
+        }
+    }
+
+    /// Resting quantity times resting age, summed over every order in the
+    /// best `levels` levels of `side`, in unit-milliseconds.
+    ///
+    /// Divided by the quantity of those levels it is the quantity-weighted
+    /// mean age of the depth: high values mean the liquidity has been
+    /// standing for a while, low values that it is being requoted.
+    #[must_use]
+    pub fn age_weighted_depth(&self, side: Side, levels: usize) -> u128 {
+        let now = self.clock.now_millis().as_u64();
+        let level_total = |level: &PriceLevel| -> u128 {
+            level
+                .iter_orders()
+                .map(|order| {
+                    let quantity = order
+                        .visible_quantity()
+                        .as_u64()
+                        .saturating_add(order.hidden_quantity().as_u64());
+                    let age = now.saturating_sub(order.timestamp().as_u64());
+                    u128::from(quantity) * u128::from(age)
+                })
+                .fold(0, u128::saturating_add)
+        };
+        match side {
+            Side::Buy => self
+                .bids
+                .iter()
+                .rev()
+                .take(levels)
+                .map(|entry| level_total(entry.value()))
+                .fold(0, u128::saturating_add),
+            Side::Sell => self
+                .asks
+                .iter()
+                .take(levels)
+                .map(|entry| level_total(entry.value()))
+                .fold(0, u128::saturating_add),
+        }
+    }
+
+    /// Start or stop recording the lifetimes returned by
+    /// [`Self::quote_lifetimes`]. Stopping forgets the entry times of the
+    /// orders resting now; the distributions recorded so far are kept.
+    pub fn set_lifetime_tracking(&self, enabled: bool) {
+        self.order_lifetimes
+            .enabled
+            .store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.order_lifetimes.clear();
+        }
+    }
+
+    /// `true` if order lifetimes are being recorded.
+    #[must_use]
+    pub fn lifetime_tracking(&self) -> bool {
+        self.order_lifetimes.is_enabled()
+    }
+
+    /// Distributions of the time orders rested before being cancelled and
+    /// before being completely filled, recorded while
+    /// [`Self::set_lifetime_tracking`] is on. Zeroed by
+    /// [`Self::reset_stats`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// book.set_lifetime_tracking(true);
+    /// let quote = Id::new_uuid();
+    /// book.add_limit_order(quote, 100, 5, Side::Buy, TimeInForce::Gtc, None)?;
+    /// book.cancel_order(quote)?;
+    ///
+    /// let lifetimes = book.quote_lifetimes();
+    /// assert_eq!(lifetimes.cancelled.count, 1);
+    /// assert_eq!(lifetimes.cancel_to_fill_ratio(), None);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    #[must_use]
+    pub fn quote_lifetimes(&self) -> QuoteLifetimeStats {
+        QuoteLifetimeStats {
+            cancelled: self.order_lifetimes.cancelled.snapshot(),
+            filled: self.order_lifetimes.filled.snapshot(),
+        }
+    }
+
+    /// Record the lifetime of a resting order that was cancelled
+    /// (`filled == false`) or completely filled.
+    #[inline]
+    pub(super) fn record_order_lifetime(&self, order_id: &Id, filled: bool) {
+        let Some(entered) = self.order_lifetimes.take(order_id) else {
+            return;
+        };
+        let lifetime = self.clock.now_millis().as_u64().saturating_sub(entered);
+        if filled {
+            self.order_lifetimes.filled.record(lifetime);
+        } else {
+            self.order_lifetimes.cancelled.record(lifetime);
+        }
+    }
+
+    /// Zero the lifetime distributions.
+    pub(super) fn reset_order_lifetimes(&self) {
+        self.order_lifetimes.cancelled.reset();
+        self.order_lifetimes.filled.reset();
+    }
+}
@@ -0,0 +1,155 @@
+//! Duplicate order id detection for `OrderBook<T>`.
+//!
+//! The book always rejects an `add_order` whose id is already resting.
+//! [`OrderIdDedup`] extends that guard in time: once a window is
+//! installed, every id that reaches the duplicate check is remembered for
+//! `window_ms` milliseconds of book clock, and a resubmission within that
+//! window is rejected with [`OrderBookError::DuplicateOrderId`] even if
+//! the original order has since filled, been cancelled, or been rejected
+//! by a later validation step.
+//!
+//! Check-and-record is atomic under a single [`Mutex`], so unlike the
+//! resting-id check it also rejects two concurrent submissions of the same
+//! fresh id. Memory is bounded by the number of submissions per window.
+
+use crate::orderbook::error::OrderBookError;
+use pricelevel::Id;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Ids seen within the window, with their expiry queue.
+#[derive(Debug, Default)]
+struct DedupState {
+    /// Id → time it was last recorded in milliseconds
+    seen: HashMap<Id, u64>,
+    /// `(recorded_ms, id)` in recording order, used to expire `seen`
+    expiry: VecDeque<(u64, Id)>,
+}
+
+impl DedupState {
+    /// Forget every id recorded at or before `now_ms - window_ms`.
+    fn prune(&mut self, window_ms: u64, now_ms: u64) {
+        while let Some(&(recorded_ms, id)) = self.expiry.front() {
+            if recorded_ms.saturating_add(window_ms) > now_ms {
+                break;
+            }
+            self.expiry.pop_front();
+            if self.seen.get(&id) == Some(&recorded_ms) {
+                self.seen.remove(&id);
+            }
+        }
+    }
+}
+
+/// Time-windowed duplicate order id detector bound to a single
+/// `OrderBook<T>`. Inactive (every check passes) until a window is set.
+#[derive(Debug, Default)]
+pub struct OrderIdDedup {
+    window_ms: Option<u64>,
+    state: Mutex<DedupState>,
+}
+
+impl OrderIdDedup {
+    /// Construct an inactive detector.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install or replace the detection window. Previously recorded ids
+    /// are forgotten.
+    pub fn set_window(&mut self, window_ms: u64) {
+        self.window_ms = Some(window_ms);
+        self.clear();
+    }
+
+    /// The active window in milliseconds, if any.
+    #[inline]
+    #[must_use]
+    pub fn window_ms(&self) -> Option<u64> {
+        self.window_ms
+    }
+
+    /// Deactivate detection and forget every recorded id.
+    pub fn disable(&mut self) {
+        self.window_ms = None;
+        self.clear();
+    }
+
+    /// Number of ids currently remembered.
+    #[must_use]
+    pub fn tracked_ids(&self) -> usize {
+        self.lock().seen.len()
+    }
+
+    /// Forget every recorded id.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.seen.clear();
+        state.expiry.clear();
+    }
+
+    /// Record `order_id` as seen at `now_ms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::DuplicateOrderId`] if `order_id` was
+    /// already recorded within the window; the original record is kept.
+    pub fn check_and_record(&self, order_id: Id, now_ms: u64) -> Result<(), OrderBookError> {
+        let Some(window_ms) = self.window_ms else {
+            return Ok(());
+        };
+
+        let mut state = self.lock();
+        state.prune(window_ms, now_ms);
+        if state.seen.contains_key(&order_id) {
+            return Err(OrderBookError::DuplicateOrderId { order_id });
+        }
+        state.seen.insert(order_id, now_ms);
+        state.expiry.push_back((now_ms, order_id));
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DedupState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inactive_detector_admits_repeats() {
+        let dedup = OrderIdDedup::new();
+        let id = Id::from_u64(1);
+        assert!(dedup.check_and_record(id, 0).is_ok());
+        assert!(dedup.check_and_record(id, 0).is_ok());
+        assert_eq!(dedup.tracked_ids(), 0);
+    }
+
+    #[test]
+    fn test_repeat_within_window_is_rejected_until_expiry() {
+        let mut dedup = OrderIdDedup::new();
+        dedup.set_window(1_000);
+        let id = Id::from_u64(1);
+
+        assert!(dedup.check_and_record(id, 0).is_ok());
+        assert!(matches!(
+            dedup.check_and_record(id, 999),
+            Err(OrderBookError::DuplicateOrderId { order_id }) if order_id == id
+        ));
+        // The rejected repeat does not extend the original record.
+        assert!(dedup.check_and_record(id, 1_000).is_ok());
+        assert_eq!(dedup.tracked_ids(), 1);
+        assert!(dedup.check_and_record(Id::from_u64(2), 1_000).is_ok());
+
+        dedup.disable();
+        assert!(dedup.check_and_record(id, 1_001).is_ok());
+        assert_eq!(dedup.tracked_ids(), 0);
+    }
+}
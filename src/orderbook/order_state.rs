@@ -20,6 +20,10 @@
 //! mass_cancel_*                    → Cancelled { MassCancel* }
 //! STP                              → Cancelled { SelfTradePrevention }
 //! IOC/FOK insufficient liquidity   → Cancelled { InsufficientLiquidity }
+//! block_user                       → Cancelled { UserBlocked }
+//! trading session close            → Cancelled { SessionClosed }
+//! missed heartbeat                 → Cancelled { Disconnected }
+//! dead man's switch lapse          → Cancelled { DeadMansSwitch }
 //! ```
 
 use super::clock::{Clock, MonotonicClock};
@@ -53,8 +57,20 @@ pub enum CancelReason {
     MassCancelByUser,
     /// Cancelled by `cancel_orders_by_price_range`.
     MassCancelByPriceRange,
+    /// Cancelled by `cancel_by_tag`.
+    MassCancelByTag,
     /// IOC or FOK order could not be fully filled.
     InsufficientLiquidity,
+    /// Cancelled because its owner was blocked by `block_user`.
+    UserBlocked,
+    /// Cancelled at the close of a trading session configured to cancel
+    /// resting orders on close.
+    SessionClosed,
+    /// Cancelled because its owner's session missed its heartbeat.
+    Disconnected,
+    /// Cancelled because a dead man's switch lapsed with its owner among
+    /// the switch's purge users.
+    DeadMansSwitch,
 }
 
 impl std::fmt::Display for CancelReason {
@@ -67,7 +83,12 @@ impl std::fmt::Display for CancelReason {
             Self::MassCancelBySide => write!(f, "mass cancel by side"),
             Self::MassCancelByUser => write!(f, "mass cancel by user"),
             Self::MassCancelByPriceRange => write!(f, "mass cancel by price range"),
+            Self::MassCancelByTag => write!(f, "mass cancel by tag"),
             Self::InsufficientLiquidity => write!(f, "insufficient liquidity"),
+            Self::UserBlocked => write!(f, "user blocked"),
+            Self::SessionClosed => write!(f, "session closed"),
+            Self::Disconnected => write!(f, "disconnected"),
+            Self::DeadMansSwitch => write!(f, "dead man's switch"),
         }
     }
 }
@@ -532,10 +553,19 @@ mod tests {
             CancelReason::MassCancelByPriceRange.to_string(),
             "mass cancel by price range"
         );
+        assert_eq!(
+            CancelReason::MassCancelByTag.to_string(),
+            "mass cancel by tag"
+        );
         assert_eq!(
             CancelReason::TimeInForceExpired.to_string(),
             "time-in-force expired"
         );
+        assert_eq!(CancelReason::Disconnected.to_string(), "disconnected");
+        assert_eq!(
+            CancelReason::DeadMansSwitch.to_string(),
+            "dead man's switch"
+        );
     }
 
     #[test]
@@ -918,7 +948,12 @@ mod tests {
             CancelReason::MassCancelBySide,
             CancelReason::MassCancelByUser,
             CancelReason::MassCancelByPriceRange,
+            CancelReason::MassCancelByTag,
             CancelReason::InsufficientLiquidity,
+            CancelReason::UserBlocked,
+            CancelReason::SessionClosed,
+            CancelReason::Disconnected,
+            CancelReason::DeadMansSwitch,
         ];
 
         for reason in &reasons {
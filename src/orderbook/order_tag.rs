@@ -0,0 +1,294 @@
+//! Application-defined order tags and tag-based mass operations.
+//!
+//! A strategy quoting several layers of a ladder usually needs to pull or
+//! inspect one layer at a time. Instead of tracking the ids of each layer
+//! itself, it can attach an [`OrderTag`] to an order, with
+//! [`OrderBook::add_order_with_tag`] on submission or
+//! [`OrderBook::tag_order`] once it rests, and the book indexes it:
+//! [`OrderBook::orders_by_tag`] lists the tagged resting orders and
+//! [`OrderBook::cancel_by_tag`] cancels them, both in time proportional to
+//! the number of orders carrying the tag.
+//!
+//! A tag belongs to a resting order and is dropped when the order leaves
+//! the book, by fill, cancel or expiry. A modification that cancels and
+//! re-adds the order keeps its tag. Tags are saved in snapshot packages.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::mass_cancel::MassCancelResult;
+use super::order_ack::OrderAck;
+use super::order_state::CancelReason;
+use dashmap::DashMap;
+use pricelevel::{Id, OrderType};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::trace;
+
+/// Tag attached to a resting order: a number or a short label.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum OrderTag {
+    /// A numeric tag.
+    Number(u64),
+
+    /// A label of at most [`OrderTag::MAX_LABEL_LEN`] bytes.
+    Label(String),
+}
+
+impl OrderTag {
+    /// Longest accepted label, in bytes.
+    pub const MAX_LABEL_LEN: usize = 32;
+
+    /// Check that a label tag is not longer than
+    /// [`Self::MAX_LABEL_LEN`].
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::OrderTagTooLong`] for a label that is too long.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        match self {
+            Self::Label(label) if label.len() > Self::MAX_LABEL_LEN => {
+                Err(OrderBookError::OrderTagTooLong {
+                    len: label.len(),
+                    max: Self::MAX_LABEL_LEN,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<u64> for OrderTag {
+    fn from(tag: u64) -> Self {
+        Self::Number(tag)
+    }
+}
+
+impl From<&str> for OrderTag {
+    fn from(label: &str) -> Self {
+        Self::Label(label.to_string())
+    }
+}
+
+impl From<String> for OrderTag {
+    fn from(label: String) -> Self {
+        Self::Label(label)
+    }
+}
+
+impl std::fmt::Display for OrderTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(tag) => write!(f, "{tag}"),
+            Self::Label(label) => write!(f, "{label}"),
+        }
+    }
+}
+
+/// Tag index of resting orders, in both directions. Removals
+/// short-circuit until the first tag is attached, so untagged books pay
+/// one flag load per order leaving the book.
+#[derive(Debug, Default)]
+pub(crate) struct OrderTags {
+    in_use: AtomicBool,
+    by_order: DashMap<Id, OrderTag>,
+    by_tag: DashMap<OrderTag, Vec<Id>>,
+}
+
+impl OrderTags {
+    /// Index `order_id` under `tag`, replacing any tag it had.
+    fn attach(&self, order_id: Id, tag: OrderTag) -> Option<OrderTag> {
+        self.in_use.store(true, Ordering::Relaxed);
+        let previous = self.by_order.insert(order_id, tag.clone());
+        if let Some(previous) = &previous {
+            self.remove_from_tag(previous, &order_id);
+        }
+        self.by_tag.entry(tag).or_default().push(order_id);
+        previous
+    }
+
+    fn remove_from_tag(&self, tag: &OrderTag, order_id: &Id) {
+        if let Some(mut entry) = self.by_tag.get_mut(tag) {
+            entry.value_mut().retain(|id| id != order_id);
+            if entry.value().is_empty() {
+                drop(entry);
+                self.by_tag.remove_if(tag, |_, ids| ids.is_empty());
+            }
+        }
+    }
+
+    /// Drop the tag of an order leaving the book, returning it.
+    #[inline]
+    pub(crate) fn forget(&self, order_id: &Id) -> Option<OrderTag> {
+        if !self.in_use.load(Ordering::Relaxed) {
+            return None;
+        }
+        let (_, tag) = self.by_order.remove(order_id)?;
+        self.remove_from_tag(&tag, order_id);
+        Some(tag)
+    }
+
+    pub(crate) fn clear(&self) {
+        self.by_order.clear();
+        self.by_tag.clear();
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Add `order` like [`Self::add_order_with_ack`] and, if any of it
+    /// rests, tag the resting order with `tag`.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::OrderTagTooLong`] for a label longer than
+    /// [`OrderTag::MAX_LABEL_LEN`], checked before the order is admitted,
+    /// and every error of [`Self::add_order_with_ack`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// for (id, price) in [(1, 99), (2, 98)] {
+    ///     let quote = OrderType::Standard {
+    ///         id: Id::from_u64(id),
+    ///         price: Price::new(price),
+    ///         quantity: Quantity::new(5),
+    ///         side: Side::Buy,
+    ///         user_id: Hash32::zero(),
+    ///         timestamp: TimestampMs::new(0),
+    ///         time_in_force: TimeInForce::Gtc,
+    ///         extra_fields: (),
+    ///     };
+    ///     book.add_order_with_tag(quote, "layer-1")?;
+    /// }
+    ///
+    /// assert_eq!(book.orders_by_tag("layer-1"), [Id::from_u64(1), Id::from_u64(2)]);
+    /// assert_eq!(book.cancel_by_tag("layer-1").cancelled_count(), 2);
+    /// assert_eq!(book.best_bid(), None);
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn add_order_with_tag(
+        &self,
+        order: OrderType<T>,
+        tag: impl Into<OrderTag>,
+    ) -> Result<OrderAck, OrderBookError> {
+        let tag = tag.into();
+        tag.validate()?;
+        let order_id = order.id();
+        let ack = self.add_order_with_ack(order)?;
+        if ack.resting.is_some() {
+            self.attach_order_tag(order_id, tag);
+        }
+        Ok(ack)
+    }
+
+    /// Tag the resting order `order_id` with `tag`, replacing any tag it
+    /// had. Returns `false` if the order is not resting in the book.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::OrderTagTooLong`] for a label longer than
+    /// [`OrderTag::MAX_LABEL_LEN`].
+    pub fn tag_order(
+        &self,
+        order_id: Id,
+        tag: impl Into<OrderTag>,
+    ) -> Result<bool, OrderBookError> {
+        let tag = tag.into();
+        tag.validate()?;
+        Ok(self.attach_order_tag(order_id, tag))
+    }
+
+    /// Remove the tag of `order_id`, returning it.
+    pub fn untag_order(&self, order_id: Id) -> Option<OrderTag> {
+        self.order_tags.forget(&order_id)
+    }
+
+    /// The tag of the resting order `order_id`, if it has one.
+    #[must_use]
+    pub fn order_tag(&self, order_id: Id) -> Option<OrderTag> {
+        self.order_tags
+            .by_order
+            .get(&order_id)
+            .map(|tag| tag.value().clone())
+    }
+
+    /// Resting orders tagged `tag`, in the order they were tagged.
+    #[must_use]
+    pub fn orders_by_tag(&self, tag: impl Into<OrderTag>) -> Vec<Id> {
+        self.order_tags
+            .by_tag
+            .get(&tag.into())
+            .map(|ids| ids.value().clone())
+            .unwrap_or_default()
+    }
+
+    /// Cancel every resting order tagged `tag`, in the order they were
+    /// tagged. Cancelled orders are reported with
+    /// [`CancelReason::MassCancelByTag`].
+    pub fn cancel_by_tag(&self, tag: impl Into<OrderTag>) -> MassCancelResult {
+        // #209: shared submit gate, as for the other mass cancels.
+        let _gate = self.submit_gate_read();
+        let tag = tag.into();
+        trace!(
+            "Order book {}: Mass cancel orders tagged {}",
+            self.symbol, tag
+        );
+
+        let order_ids = self
+            .order_tags
+            .by_tag
+            .remove(&tag)
+            .map(|(_, ids)| ids)
+            .unwrap_or_default();
+
+        self.cancel_order_batch_with_reason(&order_ids, CancelReason::MassCancelByTag)
+    }
+
+    /// Every tagged resting order as `(order_id, tag)`, grouped by tag in
+    /// tag order and in tagging order within a tag.
+    pub(super) fn order_tag_entries(&self) -> Vec<(Id, OrderTag)> {
+        let mut tags: Vec<(OrderTag, Vec<Id>)> = self
+            .order_tags
+            .by_tag
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        tags.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        tags.into_iter()
+            .flat_map(|(tag, ids)| ids.into_iter().map(move |id| (id, tag.clone())))
+            .collect()
+    }
+
+    /// Replace the index with `entries`, skipping orders not resting.
+    pub(super) fn restore_order_tags(&self, entries: Vec<(Id, OrderTag)>) {
+        self.order_tags.clear();
+        for (order_id, tag) in entries {
+            self.attach_order_tag(order_id, tag);
+        }
+    }
+
+    /// Tag `order_id` if it rests. The index is written before the
+    /// resting check: an order leaving the book concurrently drops its
+    /// location before its tag, so either its removal finds the tag or the
+    /// check finds it gone and undoes the write.
+    pub(super) fn attach_order_tag(&self, order_id: Id, tag: OrderTag) -> bool {
+        if !self.order_locations.contains_key(&order_id) {
+            return false;
+        }
+        self.order_tags.attach(order_id, tag.clone());
+        if self.order_locations.contains_key(&order_id) {
+            return true;
+        }
+        self.order_tags
+            .by_order
+            .remove_if(&order_id, |_, t| *t == tag);
+        self.order_tags.remove_from_tag(&tag, &order_id);
+        false
+    }
+}
@@ -0,0 +1,421 @@
+//! Perpetual futures funding rate and mark price.
+//!
+//! [`PerpTracker`] maintains, for one perpetual book, the state a crypto
+//! perp venue publishes alongside its order book:
+//!
+//! - the **premium** of the book mid over an external index price,
+//! - a time-weighted average premium per funding interval, turned into a
+//!   **funding rate** as `clamp(P + clamp(I - P, -c, c), -max, max)` where
+//!   `I` is the per-interval interest rate and `c` the interest clamp,
+//! - a **mark price**: the median of the last trade, the index, and the
+//!   index adjusted by the predicted funding over the time left in the
+//!   interval (the fair basis price).
+//!
+//! Like [`QuoteQualityMonitor`](super::QuoteQualityMonitor), the tracker is
+//! a passive observer: callers drive it with [`PerpTracker::update`] (or
+//! [`PerpTracker::update_at`]) whenever a new index price arrives, or with
+//! [`PerpTracker::update_from_reference`] to use the index price injected
+//! into the book with
+//! [`OrderBook::set_reference_price`](super::OrderBook::set_reference_price). Premium
+//! is credited sample-and-hold, and funding settles at every multiple of
+//! [`FundingConfig::interval_ms`] since the epoch. All prices are in the
+//! book's raw price units.
+
+use crate::orderbook::OrderBook;
+use crate::orderbook::error::OrderBookError;
+use crate::orderbook::reference_price::ExternalPriceSource;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Default funding interval: eight hours.
+pub const DEFAULT_FUNDING_INTERVAL_MS: u64 = 8 * 60 * 60 * 1_000;
+
+/// Most settlements a single update returns. A longer gap still settles
+/// every interval, but the intervals past this many are folded into
+/// [`PerpSnapshot::cumulative_funding_rate`] without a
+/// [`FundingSettlement`] each.
+pub const MAX_CATCH_UP_SETTLEMENTS: usize = 1_024;
+
+/// Funding rate parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FundingConfig {
+    /// Length of a funding interval in milliseconds (default: 8 hours)
+    pub interval_ms: u64,
+    /// Interest rate per funding interval (default: 0.0001 = 0.01%)
+    pub interest_rate: f64,
+    /// Bound on the interest adjustment `I - P` (default: 0.0005 = 0.05%)
+    pub interest_clamp: f64,
+    /// Bound on the absolute funding rate (default: 0.0075 = 0.75%)
+    pub max_funding_rate: f64,
+}
+
+impl Default for FundingConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: DEFAULT_FUNDING_INTERVAL_MS,
+            interest_rate: 0.0001,
+            interest_clamp: 0.0005,
+            max_funding_rate: 0.0075,
+        }
+    }
+}
+
+impl FundingConfig {
+    /// Creates a funding configuration with default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the funding interval in milliseconds. A zero interval is
+    /// treated as one millisecond.
+    #[must_use]
+    pub fn with_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.interval_ms = interval_ms.max(1);
+        self
+    }
+
+    /// Sets the interest rate per funding interval.
+    #[must_use]
+    pub fn with_interest_rate(mut self, interest_rate: f64) -> Self {
+        self.interest_rate = interest_rate;
+        self
+    }
+
+    /// Sets the bound on the interest adjustment.
+    #[must_use]
+    pub fn with_interest_clamp(mut self, interest_clamp: f64) -> Self {
+        self.interest_clamp = interest_clamp.abs();
+        self
+    }
+
+    /// Sets the bound on the absolute funding rate.
+    #[must_use]
+    pub fn with_max_funding_rate(mut self, max_funding_rate: f64) -> Self {
+        self.max_funding_rate = max_funding_rate.abs();
+        self
+    }
+
+    /// Funding rate for an average premium `premium`.
+    #[must_use]
+    pub fn funding_rate(&self, premium: f64) -> f64 {
+        let interest =
+            (self.interest_rate - premium).clamp(-self.interest_clamp, self.interest_clamp);
+        (premium + interest).clamp(-self.max_funding_rate, self.max_funding_rate)
+    }
+}
+
+/// A funding payment settled at the end of an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FundingSettlement {
+    /// Funding time in milliseconds since epoch (an interval boundary)
+    pub timestamp_ms: u64,
+    /// Time-weighted average premium over the interval
+    pub average_premium: f64,
+    /// Funding rate applied at this settlement
+    pub funding_rate: f64,
+    /// Mark price the funding payment is computed at
+    pub mark_price: f64,
+}
+
+impl FundingSettlement {
+    /// Funding paid by a position of `quantity` units (positive = long):
+    /// `quantity × mark_price × funding_rate`. Positive values are paid
+    /// by the holder, negative values received.
+    #[must_use]
+    pub fn payment(&self, quantity: i64) -> f64 {
+        quantity as f64 * self.mark_price * self.funding_rate
+    }
+}
+
+/// Point-in-time perp metrics, as returned by [`PerpTracker::snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerpSnapshot {
+    /// Timestamp of the latest update in milliseconds
+    pub timestamp_ms: u64,
+    /// Index price of the latest update
+    pub index_price: f64,
+    /// Premium of the book over the index, `(mid - index) / index`, or
+    /// `None` if the book had no price at the latest update
+    pub premium: Option<f64>,
+    /// Mark price at the latest update
+    pub mark_price: f64,
+    /// Funding rate the current interval would settle at, from the premium
+    /// averaged so far
+    pub predicted_funding_rate: f64,
+    /// Time of the next funding settlement in milliseconds
+    pub next_funding_ms: u64,
+    /// Rate applied at the most recent settlement, if any
+    pub last_funding_rate: Option<f64>,
+    /// Sum of every settled funding rate
+    pub cumulative_funding_rate: f64,
+}
+
+/// Mutable state behind the tracker's lock.
+#[derive(Debug, Default)]
+struct PerpState {
+    /// Latest snapshot, `None` before the first update
+    latest: Option<PerpSnapshot>,
+    /// Premium held since the latest update
+    held_premium: Option<f64>,
+    /// Sum of `premium × elapsed_ms` over the current interval
+    premium_time_product: f64,
+    /// Milliseconds of premium credited in the current interval
+    premium_time_ms: u64,
+    /// Rate applied at the most recent settlement
+    last_funding_rate: Option<f64>,
+    /// Sum of every settled funding rate
+    cumulative_funding_rate: f64,
+}
+
+/// Funding rate and mark price tracker for one perpetual book.
+///
+/// Thread-safe: all state lives behind a single [`Mutex`].
+#[derive(Debug, Default)]
+pub struct PerpTracker {
+    config: FundingConfig,
+    state: Mutex<PerpState>,
+}
+
+impl PerpTracker {
+    /// Create a tracker with the given funding configuration.
+    #[must_use]
+    pub fn new(config: FundingConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(PerpState::default()),
+        }
+    }
+
+    /// The funding configuration.
+    #[must_use]
+    pub fn config(&self) -> &FundingConfig {
+        &self.config
+    }
+
+    /// Update from `book` and `index_price` at the book clock's current
+    /// time.
+    ///
+    /// See [`Self::update_at`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidIndexPrice`] if `index_price` is not
+    /// positive and finite.
+    pub fn update<T>(
+        &self,
+        book: &OrderBook<T>,
+        index_price: f64,
+    ) -> Result<Vec<FundingSettlement>, OrderBookError>
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        self.update_at(book, index_price, book.clock().now_millis().as_u64())
+    }
+
+    /// Update from `book` and its fresh [`ExternalPriceSource::Index`]
+    /// price at the book clock's current time.
+    ///
+    /// See [`Self::update_at`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::ReferencePriceUnavailable`] if the book
+    /// has no index price or it is stale; nothing is credited or settled.
+    pub fn update_from_reference<T>(
+        &self,
+        book: &OrderBook<T>,
+    ) -> Result<Vec<FundingSettlement>, OrderBookError>
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        let source = ExternalPriceSource::Index;
+        let index_price = book
+            .fresh_reference_price(source)
+            .ok_or(OrderBookError::ReferencePriceUnavailable { source })?;
+        self.update(book, index_price as f64)
+    }
+
+    /// Update from `book` and `index_price` at `now_ms`.
+    ///
+    /// The time since the previous update is credited to the premium held
+    /// since then; every funding boundary crossed on the way settles and is
+    /// returned, oldest first, up to [`MAX_CATCH_UP_SETTLEMENTS`]. The
+    /// book's current premium and mark price are then recorded. A `now_ms`
+    /// earlier than the previous update is treated as the previous
+    /// update's time.
+    ///
+    /// Every interval after the first one crossed holds the same premium,
+    /// so they settle at the same rate and are accounted in closed form:
+    /// the cost of an update does not grow with the length of the gap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidIndexPrice`] if `index_price` is not
+    /// positive and finite.
+    pub fn update_at<T>(
+        &self,
+        book: &OrderBook<T>,
+        index_price: f64,
+        now_ms: u64,
+    ) -> Result<Vec<FundingSettlement>, OrderBookError>
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        if !index_price.is_finite() || index_price <= 0.0 {
+            return Err(OrderBookError::InvalidIndexPrice { index_price });
+        }
+
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut now_ms = now_ms;
+        let mut settlements = Vec::new();
+        if let Some(latest) = state.latest {
+            now_ms = now_ms.max(latest.timestamp_ms);
+            let first = self.next_funding_ms(latest.timestamp_ms);
+            if first <= now_ms {
+                let interval = self.interval_ms();
+                state.credit(first - latest.timestamp_ms);
+                settlements.push(state.settle(&self.config, first, latest.mark_price));
+
+                let last = now_ms / interval * interval;
+                let whole = last.saturating_sub(first) / interval;
+                let listed = whole.min(MAX_CATCH_UP_SETTLEMENTS as u64 - 1);
+                for step in 1..=listed {
+                    state.credit(interval);
+                    let boundary = first + step * interval;
+                    settlements.push(state.settle(&self.config, boundary, latest.mark_price));
+                }
+                state.fold(&self.config, whole - listed);
+                state.credit(now_ms - last.max(first));
+            } else {
+                state.credit(now_ms - latest.timestamp_ms);
+            }
+        }
+
+        let reference = book
+            .mid_price()
+            .or_else(|| book.last_trade_price().map(|price| price as f64));
+        let premium = reference.map(|price| (price - index_price) / index_price);
+        let predicted_funding_rate = self.config.funding_rate(
+            state
+                .average_premium()
+                .or(premium)
+                .unwrap_or(self.config.interest_rate),
+        );
+        let next_funding_ms = self.next_funding_ms(now_ms);
+
+        let time_left = (next_funding_ms - now_ms) as f64 / self.interval_ms() as f64;
+        let fair_basis = index_price * (1.0 + predicted_funding_rate * time_left);
+        let mut candidates = vec![index_price, fair_basis];
+        if let Some(last) = book.last_trade_price() {
+            candidates.push(last as f64);
+        }
+
+        state.held_premium = premium;
+        state.latest = Some(PerpSnapshot {
+            timestamp_ms: now_ms,
+            index_price,
+            premium,
+            mark_price: median(&mut candidates),
+            predicted_funding_rate,
+            next_funding_ms,
+            last_funding_rate: state.last_funding_rate,
+            cumulative_funding_rate: state.cumulative_funding_rate,
+        });
+
+        Ok(settlements)
+    }
+
+    /// Metrics as of the latest update, or `None` before the first one.
+    #[must_use]
+    pub fn snapshot(&self) -> Option<PerpSnapshot> {
+        let state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.latest
+    }
+
+    /// Discard all accumulated premium and funding history.
+    pub fn reset(&self) {
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *state = PerpState::default();
+    }
+
+    /// First funding boundary strictly after `timestamp_ms`.
+    fn next_funding_ms(&self, timestamp_ms: u64) -> u64 {
+        let interval = self.interval_ms();
+        (timestamp_ms / interval + 1).saturating_mul(interval)
+    }
+
+    /// The funding interval, with a deserialized zero treated as one
+    /// millisecond as [`FundingConfig::with_interval_ms`] does.
+    fn interval_ms(&self) -> u64 {
+        self.config.interval_ms.max(1)
+    }
+}
+
+impl PerpState {
+    /// Credit `elapsed` milliseconds to the held premium.
+    fn credit(&mut self, elapsed: u64) {
+        if let Some(premium) = self.held_premium {
+            self.premium_time_product += premium * elapsed as f64;
+            self.premium_time_ms = self.premium_time_ms.saturating_add(elapsed);
+        }
+    }
+
+    /// Time-weighted average premium of the current interval.
+    fn average_premium(&self) -> Option<f64> {
+        (self.premium_time_ms > 0).then(|| self.premium_time_product / self.premium_time_ms as f64)
+    }
+
+    /// Settle `intervals` whole intervals of the held premium at once,
+    /// without recording a [`FundingSettlement`] for each.
+    fn fold(&mut self, config: &FundingConfig, intervals: u64) {
+        if intervals == 0 {
+            return;
+        }
+        let funding_rate = config.funding_rate(self.held_premium.unwrap_or(0.0));
+        self.last_funding_rate = Some(funding_rate);
+        self.cumulative_funding_rate += funding_rate * intervals as f64;
+    }
+
+    /// Close the current interval at `timestamp_ms`.
+    fn settle(
+        &mut self,
+        config: &FundingConfig,
+        timestamp_ms: u64,
+        mark_price: f64,
+    ) -> FundingSettlement {
+        let average_premium = self.average_premium().unwrap_or(0.0);
+        let funding_rate = config.funding_rate(average_premium);
+        self.premium_time_product = 0.0;
+        self.premium_time_ms = 0;
+        self.last_funding_rate = Some(funding_rate);
+        self.cumulative_funding_rate += funding_rate;
+        FundingSettlement {
+            timestamp_ms,
+            average_premium,
+            funding_rate,
+            mark_price,
+        }
+    }
+}
+
+/// Median of `values`; the mean of the middle two for an even count.
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_unstable_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
@@ -1,3 +1,4 @@
+use crate::orderbook::trade::TradeFill;
 use pricelevel::Id;
 use std::cell::RefCell;
 use std::sync::Arc;
@@ -13,6 +14,9 @@ pub struct MatchingPool {
     /// one of these via `PriceLevel::snapshot_by_seq_into` instead of allocating
     /// a fresh `Vec<Arc<OrderType<()>>>` per conflicting level (#107).
     order_snapshot_pool: RefCell<Vec<Vec<Arc<pricelevel::OrderType<()>>>>>,
+    /// Reusable buffers for the per-fill detail captured for a published
+    /// trade, handed back once the trade listener has returned.
+    fills_pool: RefCell<Vec<Vec<TradeFill>>>,
 }
 
 impl MatchingPool {
@@ -22,6 +26,7 @@ impl MatchingPool {
             filled_orders_pool: RefCell::new(Vec::with_capacity(4)),
             price_vec_pool: RefCell::new(Vec::with_capacity(4)),
             order_snapshot_pool: RefCell::new(Vec::new()),
+            fills_pool: RefCell::new(Vec::new()),
         }
     }
 
@@ -63,6 +68,21 @@ impl MatchingPool {
         self.order_snapshot_pool.borrow_mut().push(vec);
     }
 
+    /// Retrieves a vector for captured fills from the pool, or an empty
+    /// one that allocates on first use.
+    pub fn get_fills_vec(&self) -> Vec<TradeFill> {
+        self.fills_pool.borrow_mut().pop().unwrap_or_default()
+    }
+
+    /// Returns a captured-fills vector to the pool for reuse. Vectors that
+    /// never allocated are dropped instead.
+    pub fn return_fills_vec(&self, mut vec: Vec<TradeFill>) {
+        if vec.capacity() > 0 {
+            vec.clear();
+            self.fills_pool.borrow_mut().push(vec);
+        }
+    }
+
     /// Retrieves a vector for prices from the pool.
     pub fn get_price_vec(&self) -> Vec<u128> {
         self.price_vec_pool
@@ -78,6 +98,26 @@ impl MatchingPool {
     }
 }
 
+impl MatchingPool {
+    /// Pre-allocate up to `buffers` buffers of every kind, so the first
+    /// matches on this thread reuse them instead of allocating.
+    pub fn warm(&self, buffers: usize) {
+        fill_to(&self.filled_orders_pool, buffers, || Vec::with_capacity(16));
+        fill_to(&self.price_vec_pool, buffers, || Vec::with_capacity(32));
+        fill_to(&self.order_snapshot_pool, buffers, || {
+            Vec::with_capacity(16)
+        });
+        fill_to(&self.fills_pool, buffers, || Vec::with_capacity(16));
+    }
+}
+
+fn fill_to<V>(pool: &RefCell<Vec<V>>, buffers: usize, make: impl Fn() -> V) {
+    let mut pool = pool.borrow_mut();
+    while pool.len() < buffers {
+        pool.push(make());
+    }
+}
+
 impl Default for MatchingPool {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,95 @@
+//! Handling of post-only orders that would cross the book.
+//!
+//! A post-only order must never take liquidity. By default one that would
+//! cross on arrival is rejected with
+//! [`OrderBookError::PriceCrossing`](super::error::OrderBookError::PriceCrossing).
+//! Many crypto venues instead slide it: the order is repriced one tick
+//! behind the opposite best, where it rests as a maker. The book's
+//! [`PostOnlyMode`], part of its [`BookConfig`](super::book_config::BookConfig),
+//! picks the behaviour; a slid order reports the price it was submitted at
+//! in [`RestingInfo::repriced_from`](super::order_ack::RestingInfo::repriced_from).
+
+use super::book::OrderBook;
+use super::book_config::BookConfig;
+use pricelevel::{OrderType, Price, Side};
+use serde::{Deserialize, Serialize};
+
+/// What happens to a post-only order that would cross the book on arrival.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum PostOnlyMode {
+    /// Reject the order with `PriceCrossing` (default).
+    #[default]
+    Reject,
+
+    /// Reprice the order one tick behind the opposite best — below the
+    /// best ask for a buy, above the best bid for a sell — and rest it
+    /// there. A buy that would have to slide to a price of zero is
+    /// rejected instead.
+    Slide,
+}
+
+impl std::fmt::Display for PostOnlyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostOnlyMode::Reject => write!(f, "Reject"),
+            PostOnlyMode::Slide => write!(f, "Slide"),
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Returns the configured post-only crossing behaviour.
+    #[must_use]
+    #[inline]
+    pub fn post_only_mode(&self) -> PostOnlyMode {
+        self.config().post_only_mode
+    }
+
+    /// Under [`PostOnlyMode::Slide`], move a crossing post-only `order` one
+    /// tick behind the opposite best. Returns the submitted price when the
+    /// order was repriced; an order left crossing is rejected downstream.
+    /// Mode and tick size come from the admission's `config` snapshot.
+    pub(super) fn slide_post_only(
+        &self,
+        config: &BookConfig,
+        order: &mut OrderType<T>,
+    ) -> Option<u128> {
+        if !order.is_post_only() || config.post_only_mode != PostOnlyMode::Slide {
+            return None;
+        }
+        let submitted = order.price().as_u128();
+        let tick = config.tick_size.unwrap_or(1);
+        let slid = match order.side() {
+            Side::Buy => self
+                .best_ask()
+                .filter(|&ask| submitted >= ask)?
+                .checked_sub(tick)
+                .filter(|&price| price > 0)?,
+            Side::Sell => self
+                .best_bid()
+                .filter(|&bid| submitted <= bid)?
+                .checked_add(tick)?,
+        };
+        if let OrderType::PostOnly { price, .. } = order {
+            *price = Price::new(slid);
+        }
+        Some(submitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rejects_and_display_names_the_mode() {
+        assert_eq!(PostOnlyMode::default(), PostOnlyMode::Reject);
+        assert_eq!(PostOnlyMode::Slide.to_string(), "Slide");
+        let json = serde_json::to_string(&PostOnlyMode::Slide).expect("serialize");
+        let decoded: PostOnlyMode = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded, PostOnlyMode::Slide);
+    }
+}
@@ -0,0 +1,123 @@
+//! Decimal scale of a symbol's integer prices and quantities.
+//!
+//! The book stores prices as `u128` and quantities as `u64` in the
+//! symbol's smallest increment. [`PriceScale`] records how many decimal
+//! places those integers carry so that snapshots, metrics and published
+//! events can be rendered in display units: with `price_decimals = 2`, a
+//! raw price of `10_050` is `100.50`.
+//!
+//! The default scale is `0` decimals for both, which leaves every value
+//! unchanged.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of decimals accepted by [`PriceScale::new`].
+pub const MAX_SCALE_DECIMALS: u8 = 18;
+
+/// Number of decimal places carried by a symbol's raw prices and
+/// quantities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PriceScale {
+    /// Decimal places of a raw price (`u128`)
+    pub price_decimals: u8,
+    /// Decimal places of a raw quantity (`u64`)
+    pub quantity_decimals: u8,
+}
+
+impl PriceScale {
+    /// Create a scale. Decimals above [`MAX_SCALE_DECIMALS`] are clamped.
+    #[must_use]
+    pub fn new(price_decimals: u8, quantity_decimals: u8) -> Self {
+        Self {
+            price_decimals: price_decimals.min(MAX_SCALE_DECIMALS),
+            quantity_decimals: quantity_decimals.min(MAX_SCALE_DECIMALS),
+        }
+    }
+
+    /// `true` if both decimals are zero, i.e. raw values are display values.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.price_decimals == 0 && self.quantity_decimals == 0
+    }
+
+    /// Raw value of one display price unit: `10^price_decimals`.
+    #[must_use]
+    pub fn price_factor(&self) -> u128 {
+        10u128.pow(u32::from(self.price_decimals))
+    }
+
+    /// Raw value of one display quantity unit: `10^quantity_decimals`.
+    #[must_use]
+    pub fn quantity_factor(&self) -> u64 {
+        10u64.pow(u32::from(self.quantity_decimals))
+    }
+
+    /// Convert a raw price to display units.
+    #[must_use]
+    pub fn price_to_f64(&self, price: u128) -> f64 {
+        price as f64 / self.price_factor() as f64
+    }
+
+    /// Convert a raw price that is already an `f64` (e.g. a mid price or
+    /// VWAP) to display units.
+    #[must_use]
+    pub fn scale_price_f64(&self, price: f64) -> f64 {
+        price / self.price_factor() as f64
+    }
+
+    /// Convert a raw quantity to display units.
+    #[must_use]
+    pub fn quantity_to_f64(&self, quantity: u64) -> f64 {
+        quantity as f64 / self.quantity_factor() as f64
+    }
+
+    /// Convert a raw notional (raw price × raw quantity) to display units.
+    #[must_use]
+    pub fn notional_to_f64(&self, notional: u128) -> f64 {
+        notional as f64 / self.price_factor() as f64 / self.quantity_factor() as f64
+    }
+
+    /// Convert a display price to raw units, rounding to the nearest
+    /// increment. Returns `None` for negative, non-finite or out-of-range
+    /// values.
+    #[must_use]
+    pub fn price_from_f64(&self, price: f64) -> Option<u128> {
+        let raw = (price * self.price_factor() as f64).round();
+        (raw.is_finite() && raw >= 0.0 && raw <= u128::MAX as f64).then_some(raw as u128)
+    }
+
+    /// Convert a display quantity to raw units, rounding to the nearest
+    /// increment. Returns `None` for negative, non-finite or out-of-range
+    /// values.
+    #[must_use]
+    pub fn quantity_from_f64(&self, quantity: f64) -> Option<u64> {
+        let raw = (quantity * self.quantity_factor() as f64).round();
+        (raw.is_finite() && raw >= 0.0 && raw <= u64::MAX as f64).then_some(raw as u64)
+    }
+
+    /// Render a raw price as an exact decimal string, e.g. `"100.50"`.
+    #[must_use]
+    pub fn format_price(&self, price: u128) -> String {
+        format_scaled(price, self.price_decimals)
+    }
+
+    /// Render a raw quantity as an exact decimal string.
+    #[must_use]
+    pub fn format_quantity(&self, quantity: u64) -> String {
+        format_scaled(u128::from(quantity), self.quantity_decimals)
+    }
+}
+
+/// Exact decimal rendering of `value / 10^decimals`, keeping trailing zeros.
+fn format_scaled(value: u128, decimals: u8) -> String {
+    if decimals == 0 {
+        return value.to_string();
+    }
+    let factor = 10u128.pow(u32::from(decimals));
+    format!(
+        "{}.{:0width$}",
+        value / factor,
+        value % factor,
+        width = usize::from(decimals)
+    )
+}
@@ -0,0 +1,271 @@
+//! Priority classes: non-pure price-time priority within a level.
+//!
+//! Under plain price-time priority the orders at one price fill in arrival
+//! order. Some venues rank participants first: customer orders ahead of
+//! proprietary ones at the same price, or a designated market maker ahead
+//! of everyone. The book keeps a registry mapping user ids to a
+//! [`PriorityClass`]; with class priority enabled
+//! ([`OrderBook::set_class_priority`]) every level fills class by class,
+//! in arrival order within a class. Users without a class, and anonymous
+//! orders (`Hash32::zero()`), are [`PriorityClass::Professional`].
+//!
+//! `pricelevel` queues strictly by arrival, so the book keeps each level
+//! sorted instead: when an order joins a level ahead of lower-class
+//! orders, is resized to the back of one, or replenishes its displayed
+//! tranche during a sweep, the lower-class orders behind its place are
+//! re-queued after it. Re-queueing takes the orders out of the level and
+//! back in, which no concurrent operation may observe, so a book with
+//! class priority enabled serializes every submit, modify and cancel
+//! behind the exclusive submit gate.
+//!
+//! Within one sweep a replenished tranche still waits behind the orders
+//! already queued; its class place is restored once the sweep ends.
+
+use super::book::OrderBook;
+use dashmap::DashMap;
+use pricelevel::{Hash32, OrderType, OrderUpdate, PriceLevel, Side};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{error, trace};
+
+/// Rank of a participant in the intra-level allocation order. Earlier
+/// variants fill first.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum PriorityClass {
+    /// Designated market maker, ahead of every other class.
+    MarketMaker,
+
+    /// Customer (agency) flow.
+    Customer,
+
+    /// Proprietary and professional flow (default).
+    #[default]
+    Professional,
+}
+
+impl std::fmt::Display for PriorityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriorityClass::MarketMaker => write!(f, "MarketMaker"),
+            PriorityClass::Customer => write!(f, "Customer"),
+            PriorityClass::Professional => write!(f, "Professional"),
+        }
+    }
+}
+
+/// Class priority switch and the class of each registered user.
+#[derive(Debug, Default)]
+pub(crate) struct PriorityClasses {
+    enabled: AtomicBool,
+    members: DashMap<Hash32, PriorityClass>,
+}
+
+impl PriorityClasses {
+    /// Whether levels are kept in class order.
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// The class of `user_id`.
+    #[inline]
+    pub(crate) fn class_of(&self, user_id: Hash32) -> PriorityClass {
+        self.members
+            .get(&user_id)
+            .map(|class| *class)
+            .unwrap_or_default()
+    }
+
+    /// Replace the switch and every membership.
+    pub(crate) fn replace(&self, enabled: bool, members: Vec<(Hash32, PriorityClass)>) {
+        self.members.clear();
+        for (user_id, class) in members {
+            self.members.insert(user_id, class);
+        }
+        self.enabled.store(enabled, Ordering::Release);
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Enable or disable class priority.
+    ///
+    /// Enabling it re-sorts every level into class order at once;
+    /// disabling it leaves the queues as they are, and later arrivals join
+    /// the back of their level again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, PriorityClass};
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// let prop = Hash32::new([1; 32]);
+    /// let customer = Hash32::new([2; 32]);
+    /// book.set_priority_class(customer, PriorityClass::Customer);
+    /// book.set_class_priority(true);
+    ///
+    /// book.add_limit_order_with_user(Id::from_u64(1), 100, 5, Side::Sell, TimeInForce::Gtc, prop, None)?;
+    /// book.add_limit_order_with_user(Id::from_u64(2), 100, 5, Side::Sell, TimeInForce::Gtc, customer, None)?;
+    ///
+    /// let fill = book.submit_market_order(Id::from_u64(3), 5, Side::Buy)?;
+    /// assert_eq!(fill.trades().as_vec()[0].maker_order_id(), Id::from_u64(2));
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn set_class_priority(&self, enabled: bool) {
+        let _gate = self.submit_gate_write();
+        trace!(
+            "Order book {}: class priority {}",
+            self.symbol,
+            if enabled { "enabled" } else { "disabled" }
+        );
+        self.priority_classes
+            .enabled
+            .store(enabled, Ordering::Release);
+        if enabled {
+            for level in self.bids.iter().chain(self.asks.iter()) {
+                self.enforce_class_priority(level.value());
+            }
+        }
+    }
+
+    /// Whether levels fill class by class.
+    #[inline]
+    #[must_use]
+    pub fn is_class_priority_enabled(&self) -> bool {
+        self.priority_classes.is_enabled()
+    }
+
+    /// Put `user_id` in `class`, returning the class it was registered
+    /// with before, if any.
+    ///
+    /// With class priority enabled the user's resting orders move to
+    /// their new class's place at once, in arrival order among that class.
+    pub fn set_priority_class(
+        &self,
+        user_id: Hash32,
+        class: PriorityClass,
+    ) -> Option<PriorityClass> {
+        let _gate = self.submit_gate_write();
+        trace!(
+            "Order book {}: Account {} joins priority class {}",
+            self.symbol, user_id, class
+        );
+        let previous = self.priority_classes.members.insert(user_id, class);
+        self.requeue_user_levels(user_id);
+        previous
+    }
+
+    /// Remove `user_id` from the registry, returning the class it was
+    /// registered with, if any. Its orders rank as
+    /// [`PriorityClass::Professional`] from then on.
+    pub fn clear_priority_class(&self, user_id: Hash32) -> Option<PriorityClass> {
+        let _gate = self.submit_gate_write();
+        let previous = self
+            .priority_classes
+            .members
+            .remove(&user_id)
+            .map(|(_, class)| class);
+        self.requeue_user_levels(user_id);
+        previous
+    }
+
+    /// The class `user_id`'s orders rank in.
+    #[inline]
+    #[must_use]
+    pub fn priority_class(&self, user_id: Hash32) -> PriorityClass {
+        self.priority_classes.class_of(user_id)
+    }
+
+    /// Every registered user and its class, sorted by id bytes.
+    #[must_use]
+    pub fn priority_classes(&self) -> Vec<(Hash32, PriorityClass)> {
+        let mut members: Vec<(Hash32, PriorityClass)> = self
+            .priority_classes
+            .members
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+        members.sort_unstable_by_key(|(user_id, _)| user_id.0);
+        members
+    }
+
+    /// Re-sort the levels holding `user_id`'s orders.
+    fn requeue_user_levels(&self, user_id: Hash32) {
+        if !self.priority_classes.is_enabled() {
+            return;
+        }
+        let mut levels: Vec<(Side, u128)> = Vec::new();
+        for order in self.orders_for_user(user_id) {
+            if !levels.contains(&(order.side, order.price)) {
+                levels.push((order.side, order.price));
+            }
+        }
+        for (side, price) in levels {
+            let book_side = match side {
+                Side::Buy => &self.bids,
+                Side::Sell => &self.asks,
+            };
+            if let Some(level) = book_side.get(&price) {
+                self.enforce_class_priority(level.value());
+            }
+        }
+    }
+
+    /// Re-queue the orders of `level` that rank below an order queued
+    /// after them, so the level fills class by class. Returns the
+    /// resulting queue, or `None` while class priority is disabled.
+    ///
+    /// Must run under the exclusive submit gate, which every gated entry
+    /// point takes while class priority is enabled.
+    pub(super) fn enforce_class_priority(
+        &self,
+        level: &PriceLevel,
+    ) -> Option<Vec<Arc<OrderType<()>>>> {
+        if !self.priority_classes.is_enabled() {
+            return None;
+        }
+        let queue = level.snapshot_by_insertion_seq();
+        let classes: Vec<PriorityClass> = queue
+            .iter()
+            .map(|order| self.priority_classes.class_of(order.user_id()))
+            .collect();
+        if classes.is_sorted() {
+            return Some(queue);
+        }
+        let mut sorted: Vec<usize> = (0..queue.len()).collect();
+        sorted.sort_by_key(|&index| classes[index]);
+        let in_place = sorted
+            .iter()
+            .enumerate()
+            .take_while(|&(position, &index)| position == index)
+            .count();
+        for &index in &sorted[in_place..] {
+            let order_id = queue[index].id();
+            if let Ok(Some(removed)) = level.update_order(OrderUpdate::Cancel { order_id }) {
+                // Headroom was freed by the removal, so this cannot fail
+                // short of a bug; never lose the order silently.
+                if let Err(err) = level.add_order(*removed) {
+                    error!(
+                        %order_id,
+                        price = level.price(),
+                        error = %err,
+                        "class priority re-queue failed; order dropped from its level"
+                    );
+                }
+            }
+        }
+        Some(
+            sorted
+                .into_iter()
+                .map(|index| Arc::clone(&queue[index]))
+                .collect(),
+        )
+    }
+}
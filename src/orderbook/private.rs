@@ -1,6 +1,8 @@
 use crate::orderbook::book_change_event::PriceLevelChangedEvent;
+use crate::orderbook::matching::MatchOutcome;
+use crate::orderbook::trade::{TradeFill, TradeListener, TradeResult};
 use crate::{OrderBook, OrderBookError};
-use pricelevel::{OrderType, PriceLevel, Side, TimeInForce};
+use pricelevel::{Hash32, Id, MatchResult, OrderType, Side, TimeInForce};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 
@@ -42,6 +44,20 @@ where
         time_in_force.is_expired(now_ms, self.market_close_for_expiry())
     }
 
+    /// Register a resting order with the expiry wheel if its time in force
+    /// can expire. Stamped with the book's clock, which only anchors the
+    /// wheel; deadlines stay absolute.
+    pub(super) fn schedule_expiry(&self, order_id: Id, time_in_force: TimeInForce) {
+        match time_in_force {
+            TimeInForce::Gtd(deadline) => {
+                let now = self.clock().now_millis().as_u64();
+                self.expiry_wheel.schedule(order_id, deadline, now);
+            }
+            TimeInForce::Day => self.expiry_wheel.track_day_order(order_id),
+            _ => {}
+        }
+    }
+
     /// Check if an order has expired **as of the book's own clock**.
     ///
     /// The comparison unit is **milliseconds since the Unix epoch**: the
@@ -75,6 +91,7 @@ where
         &self,
         order: Arc<OrderType<T>>,
     ) -> Result<Arc<OrderType<T>>, OrderBookError> {
+        let _mutation = self.cache.begin_mutation();
         let (side, price, order_id) = (order.side(), order.price().as_u128(), order.id());
 
         let book_side = match side {
@@ -82,28 +99,41 @@ where
             Side::Sell => &self.asks,
         };
 
-        // Get or create the price level
+        // Get or create the price level, keeping it linked until the order
+        // is in it
+        let level_gate = self.level_gate_read(price);
         let price_level = book_side
-            .get_or_insert(price, Arc::new(PriceLevel::new(price)))
+            .get_or_insert_with(price, || self.level_pool.acquire(price))
             .value()
             .clone();
 
         // Convert OrderType<T> to OrderType<()> for compatibility with current PriceLevel API
         let unit_order = self.convert_to_unit_type(&*order);
         let _added_order = price_level.add_order(unit_order)?;
+        self.enforce_class_priority(&price_level);
+        drop(level_gate);
+        self.cache.invalidate();
+        self.refresh_depth_view();
+        self.reconcile_depth(side, &price_level);
 
         // notify price level changes
-        if let Some(ref listener) = self.price_level_changed_listener {
+        if let Some(listener) = self.price_level_listener() {
             let engine_seq = self.next_engine_seq();
             listener(PriceLevelChangedEvent {
                 side,
                 price: price_level.price(),
                 quantity: price_level.visible_quantity(),
+                order_count: price_level.order_count(),
                 engine_seq,
+                config_version: self.config_version(),
             })
         }
         // The location is stored as (price, side) for efficient retrieval in cancel_order
         self.order_locations.insert(order_id, (price, side));
+        self.order_lifetimes
+            .on_rest(order_id, order.timestamp().as_u64());
+        self.store_extra_fields(&order);
+        self.schedule_expiry(order_id, order.time_in_force());
 
         // Track the order in the user_orders index for efficient user-based cancellation
         self.track_user_order(order.user_id(), order_id);
@@ -115,6 +145,44 @@ where
         Ok(order)
     }
 
+    /// Whether `T` carries data worth keeping in the `extra_fields` side
+    /// table. Zero-sized payloads (e.g. `()`) skip it entirely, so the
+    /// default book pays nothing for this.
+    pub(super) const STORES_EXTRA_FIELDS: bool = std::mem::size_of::<T>() != 0;
+
+    /// Record the caller-supplied `extra_fields` of an order that is about
+    /// to rest.
+    #[inline]
+    pub(super) fn store_extra_fields(&self, order: &OrderType<T>) {
+        if Self::STORES_EXTRA_FIELDS {
+            self.extra_fields
+                .insert(order.id(), order.extra_fields().clone());
+        }
+    }
+
+    /// Clone the stored `extra_fields` of a resting order.
+    #[inline]
+    pub(super) fn lookup_extra_fields(&self, order_id: &Id) -> Option<T> {
+        if !Self::STORES_EXTRA_FIELDS {
+            return None;
+        }
+        self.extra_fields
+            .get(order_id)
+            .map(|entry| entry.value().clone())
+    }
+
+    /// Drop the stored `extra_fields` of an order leaving the book,
+    /// returning them.
+    #[inline]
+    pub(super) fn take_extra_fields(&self, order_id: &Id) -> Option<T> {
+        if !Self::STORES_EXTRA_FIELDS {
+            return None;
+        }
+        self.extra_fields
+            .remove(order_id)
+            .map(|(_, extra_fields)| extra_fields)
+    }
+
     /// Register an order in the `user_orders` index.
     ///
     /// Orders with `Hash32::zero()` (anonymous) are still tracked so that
@@ -122,6 +190,7 @@ where
     #[inline]
     pub(super) fn track_user_order(&self, user_id: pricelevel::Hash32, order_id: pricelevel::Id) {
         self.user_orders.entry(user_id).or_default().push(order_id);
+        self.order_owners.insert(order_id, user_id);
     }
 
     /// Remove an order from the `user_orders` index.
@@ -133,48 +202,60 @@ where
         user_id: pricelevel::Hash32,
         order_id: &pricelevel::Id,
     ) {
+        self.order_owners.remove(order_id);
+        self.order_lifetimes.forget(order_id);
+        self.order_tags.forget(order_id);
         if let Some(mut entry) = self.user_orders.get_mut(&user_id) {
             entry.value_mut().retain(|id| id != order_id);
             if entry.value().is_empty() {
                 drop(entry);
-                self.user_orders.remove(&user_id);
+                // Re-checked under the shard lock: a concurrent add may
+                // have tracked a new order for this user in between.
+                self.user_orders
+                    .remove_if(&user_id, |_, ids| ids.is_empty());
             }
         }
     }
 
-    /// Remove an order from the `user_orders` index by scanning all entries.
+    /// Remove an order from the `user_orders` index given only its id.
     ///
     /// This is used in the matching engine where filled orders are already
     /// removed from the price level and their `user_id` is no longer directly
-    /// accessible. The scan is efficient in practice because:
-    /// - Each order belongs to exactly one user (early return on first match)
-    /// - The number of active users is typically small
+    /// accessible; the owner is read back from `order_owners` instead.
     pub(super) fn untrack_order_by_id(&self, order_id: &pricelevel::Id) {
-        let mut user_to_remove = None;
-        for mut entry in self.user_orders.iter_mut() {
+        self.order_lifetimes.forget(order_id);
+        self.order_tags.forget(order_id);
+        let Some((_, user_id)) = self.order_owners.remove(order_id) else {
+            return;
+        };
+        if let Some(mut entry) = self.user_orders.get_mut(&user_id) {
             let ids = entry.value_mut();
             if let Some(pos) = ids.iter().position(|id| id == order_id) {
                 ids.swap_remove(pos);
-                if ids.is_empty() {
-                    user_to_remove = Some(*entry.key());
-                }
-                break;
             }
-        }
-        if let Some(user_id) = user_to_remove {
-            self.user_orders.remove(&user_id);
+            if ids.is_empty() {
+                drop(entry);
+                self.user_orders
+                    .remove_if(&user_id, |_, ids| ids.is_empty());
+            }
         }
     }
 
+    /// Owner of a resting order, or `None` once it has left the book.
+    #[inline]
+    pub(super) fn order_owner(&self, order_id: &pricelevel::Id) -> Option<pricelevel::Hash32> {
+        self.order_owners.get(order_id).map(|owner| *owner.value())
+    }
+
     /// Record an order state transition if a tracker is configured,
-    /// and emit operational metrics when the transition is a
-    /// rejection.
+    /// emit operational metrics when the transition is a rejection, and
+    /// count rejections, cancellations and fills in the book's stats.
     ///
     /// Tracker recording is a no-op when `order_state_tracker` is
     /// `None`. Metrics emission is unconditional but compiles to a
     /// no-op when the `metrics` feature is disabled — see
-    /// [`crate::orderbook::metrics`]. Hooking the metric here keeps
-    /// every reject path in the engine on the same single emission
+    /// [`crate::orderbook::metrics`]. Hooking the metric and stats here
+    /// keeps every reject path in the engine on the same single emission
     /// point.
     #[inline]
     pub(super) fn track_state(
@@ -182,14 +263,33 @@ where
         order_id: pricelevel::Id,
         status: super::order_state::OrderStatus,
     ) {
-        if let super::order_state::OrderStatus::Rejected { reason } = &status {
-            super::metrics::record_reject(*reason);
+        match &status {
+            super::order_state::OrderStatus::Rejected { reason } => self.record_reject(*reason),
+            super::order_state::OrderStatus::Cancelled { .. } => {
+                self.counters.record_cancelled();
+                self.record_order_lifetime(&order_id, false);
+            }
+            super::order_state::OrderStatus::Filled { .. } => {
+                self.counters.record_filled();
+                self.record_order_lifetime(&order_id, true);
+            }
+            _ => {}
         }
         if let Some(ref tracker) = self.order_state_tracker {
             tracker.transition(order_id, status);
         }
     }
 
+    /// Count a rejection in the book's stats and the reject metric.
+    ///
+    /// For reject paths that do not record an `OrderStatus::Rejected`
+    /// transition; [`Self::track_state`] calls it for those that do.
+    #[inline]
+    pub(super) fn record_reject(&self, reason: super::reject_reason::RejectReason) {
+        self.counters.record_reject(reason);
+        super::metrics::record_reject(reason);
+    }
+
     /// Record an `OrderStatus::Rejected` transition for a failed risk
     /// admission, mapping the typed [`OrderBookError`] to its closed
     /// [`super::reject_reason::RejectReason`] code.
@@ -212,6 +312,182 @@ where
         );
     }
 
+    /// Wrap `match_result` in a [`TradeResult`] stamped with the book's
+    /// fees, next engine sequence, config version and price scale, and
+    /// attach the sweep's captured `fills` with each side's fee charged,
+    /// plus the per-level view when trade level aggregation is on.
+    /// The charged fills are also kept in the execution history, if any.
+    ///
+    /// Takes the match result by value so publishing never clones its
+    /// transaction list; callers that still need it read it back from
+    /// [`TradeResult::match_result`].
+    pub(super) fn build_trade_result(
+        &self,
+        match_result: MatchResult,
+        mut fills: Vec<TradeFill>,
+    ) -> TradeResult {
+        let config = self.config();
+        let config_version = self.config_version();
+        let tiered = self.tiered_fee_schedule.as_deref();
+        if let Some(schedule) = tiered {
+            // Price every fill before crediting any of them, so the whole
+            // sweep is charged at the tiers in force when it started.
+            for fill in &mut fills {
+                let notional = fill.notional();
+                let now_ms = fill.matched_at_ns / 1_000_000;
+                fill.maker.fee = schedule.calculate_fee(
+                    fill.maker.user_id,
+                    &self.symbol,
+                    notional,
+                    true,
+                    now_ms,
+                );
+                fill.taker.fee = schedule.calculate_fee(
+                    fill.taker.user_id,
+                    &self.symbol,
+                    notional,
+                    false,
+                    now_ms,
+                );
+            }
+            self.record_tiered_volume(&fills);
+        } else if let Some(schedule) = config.fee_schedule.filter(|s| !s.is_zero_fee()) {
+            for fill in &mut fills {
+                let notional = fill.notional();
+                fill.maker.fee = schedule.calculate_fee(notional, true);
+                fill.taker.fee = schedule.calculate_fee(notional, false);
+            }
+        }
+        if let Some(history) = &self.execution_history {
+            history.record(&fills);
+        }
+        let mut trade_result = match tiered {
+            Some(_) => {
+                let mut trade_result = TradeResult::new(self.symbol.clone(), match_result);
+                for fill in &fills {
+                    trade_result.total_maker_fees = trade_result
+                        .total_maker_fees
+                        .checked_add(fill.maker.fee)
+                        .unwrap_or(trade_result.total_maker_fees);
+                    trade_result.total_taker_fees = trade_result
+                        .total_taker_fees
+                        .checked_add(fill.taker.fee)
+                        .unwrap_or(trade_result.total_taker_fees);
+                }
+                trade_result
+            }
+            None => TradeResult::with_fees(self.symbol.clone(), match_result, config.fee_schedule),
+        };
+        trade_result.engine_seq = self.next_engine_seq();
+        trade_result.config_version = config_version;
+        trade_result.price_scale = self.price_scale;
+        trade_result.fills = fills;
+        if self.trade_level_aggregation.load(Ordering::Relaxed) {
+            trade_result.level_fills = trade_result.aggregate_levels();
+        }
+        if Self::STORES_EXTRA_FIELDS {
+            for trade in trade_result.match_result.trades().as_vec() {
+                let maker_id = trade.maker_order_id();
+                if trade_result
+                    .extra_fields
+                    .iter()
+                    .any(|(id, _)| *id == maker_id)
+                {
+                    continue;
+                }
+                if let Some(extra_fields) = self.lookup_extra_fields(&maker_id) {
+                    trade_result
+                        .extra_fields
+                        .push((maker_id, Arc::new(extra_fields)));
+                }
+            }
+        }
+        trade_result
+    }
+
+    /// Whether a sweep must capture per-fill detail: `publishing` (a
+    /// [`TradeResult`] will be built), or a tiered fee schedule needs each
+    /// fill's owners to credit their volume.
+    pub(super) fn capture_fills(&self, publishing: bool) -> bool {
+        publishing || self.tiered_fee_schedule.is_some()
+    }
+
+    /// Credit `fills` to the tiered fee schedule, if one is installed.
+    ///
+    /// Each side is credited at the fill's match time; sides without a
+    /// user id are skipped.
+    pub(super) fn record_tiered_volume(&self, fills: &[TradeFill]) {
+        let Some(schedule) = &self.tiered_fee_schedule else {
+            return;
+        };
+        for fill in fills {
+            let notional = fill.notional();
+            let now_ms = fill.matched_at_ns / 1_000_000;
+            for user_id in [fill.maker.user_id, fill.taker.user_id] {
+                if user_id != Hash32::zero() {
+                    schedule.record_volume(user_id, notional, now_ms);
+                }
+            }
+        }
+    }
+
+    /// Drop the stored `extra_fields` of makers that `match_result` filled.
+    ///
+    /// The matching loop leaves them in place so [`Self::build_trade_result`]
+    /// can still attach them to the published trade; every caller of the
+    /// loop releases them once the trade has been published (or discarded).
+    pub(super) fn release_filled_extra_fields(&self, match_result: &MatchResult) {
+        if Self::STORES_EXTRA_FIELDS {
+            for order_id in match_result.filled_order_ids() {
+                self.take_extra_fields(order_id);
+            }
+        }
+    }
+
+    /// Record the trade-count metric and book stats for a sweep's match
+    /// result and hand it to `listener`, then release the filled makers'
+    /// `extra_fields` and give the match result back.
+    ///
+    /// `listener` is the one read before the sweep, which was matched with
+    /// fill capture on exactly when it is `Some`. The metric is independent
+    /// of whether a listener is configured. The match result moves through
+    /// the [`TradeResult`] and out again, so the market-sweep paths publish
+    /// without a `MatchResult` clone. Without a listener the fills are
+    /// still credited to the tiered fee schedule.
+    pub(super) fn publish_trades(
+        &self,
+        listener: Option<TradeListener>,
+        outcome: MatchOutcome,
+    ) -> MatchResult {
+        let MatchOutcome {
+            result: match_result,
+            fills,
+            ..
+        } = outcome;
+        let trades_emitted = match_result.trades().len() as u64;
+        if trades_emitted == 0 {
+            super::matching::recycle_fills(fills);
+            return match_result;
+        }
+        super::metrics::record_trades(trades_emitted);
+        self.counters.record_trades(&match_result);
+        let match_result = match listener {
+            Some(listener) => {
+                let trade_result = self.build_trade_result(match_result, fills);
+                listener(&trade_result);
+                super::matching::recycle_fills(trade_result.fills);
+                trade_result.match_result
+            }
+            None => {
+                self.record_tiered_volume(&fills);
+                super::matching::recycle_fills(fills);
+                match_result
+            }
+        };
+        self.release_filled_extra_fields(&match_result);
+        match_result
+    }
+
     /// Convert `OrderType<T>` to OrderType<()> for compatibility with current PriceLevel API
     pub fn convert_to_unit_type(&self, order: &OrderType<T>) -> OrderType<()> {
         match order {
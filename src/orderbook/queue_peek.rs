@@ -0,0 +1,82 @@
+//! Front-of-queue inspection at a price level.
+//!
+//! [`OrderBook::peek_queue`] lists the first orders of a level in the exact
+//! order matching consumes them (ascending insertion sequence, so a
+//! partially filled maker keeps its place and a replenished iceberg tranche
+//! goes to the back). It is meant for matching-priority debugging and for
+//! market makers watching their queue position.
+//!
+//! The level is walked into this thread's pooled order buffer, which holds
+//! shared handles to the resting orders: no order and no level is cloned,
+//! and only the returned entries are allocated.
+
+use super::book::OrderBook;
+use super::matching::with_order_snapshot_vec;
+use pricelevel::{Hash32, Id, Side};
+use serde::{Deserialize, Serialize};
+
+/// One resting order as seen from the front of its level's queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueEntry {
+    /// Order identifier
+    pub id: Id,
+    /// Owner of the order, [`Hash32::zero`] when none was given
+    pub user_id: Hash32,
+    /// Quantity displayed at the level (in units)
+    pub visible_quantity: u64,
+    /// Timestamp the order rests with, in milliseconds of the book's clock
+    pub timestamp: u64,
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// The first `n` orders resting at `price` on `side`, next to match
+    /// first. Empty if there is no level at `price`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("TEST");
+    /// for id in 1..=3 {
+    ///     book.add_limit_order(Id::from_u64(id), 100, 5, Side::Sell, TimeInForce::Gtc, None)?;
+    /// }
+    /// book.submit_market_order(Id::from_u64(9), 2, Side::Buy)?;
+    ///
+    /// let queue = book.peek_queue(100, Side::Sell, 2);
+    /// assert_eq!(queue.len(), 2);
+    /// assert_eq!((queue[0].id, queue[0].visible_quantity), (Id::from_u64(1), 3));
+    /// assert_eq!(queue[1].id, Id::from_u64(2));
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    #[must_use]
+    pub fn peek_queue(&self, price: u128, side: Side, n: usize) -> Vec<QueueEntry> {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let Some(level) = levels.get(&price) else {
+            return Vec::new();
+        };
+        if n == 0 {
+            return Vec::new();
+        }
+        with_order_snapshot_vec(|orders| {
+            level.value().snapshot_by_seq_into(orders);
+            orders
+                .iter()
+                .take(n)
+                .map(|order| QueueEntry {
+                    id: order.id(),
+                    user_id: order.user_id(),
+                    visible_quantity: order.visible_quantity().as_u64(),
+                    timestamp: order.timestamp().as_u64(),
+                })
+                .collect()
+        })
+    }
+}
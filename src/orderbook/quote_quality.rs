@@ -0,0 +1,327 @@
+//! Spread and quote quality monitoring for market-maker obligations.
+//!
+//! [`QuoteQualityMonitor`] is a passive observer that tracks, per user:
+//!
+//! - time spent quoting at the best bid, best ask, and on both sides of
+//!   the BBO simultaneously,
+//! - the time-weighted spread of the user's own two-sided quote,
+//! - fill-to-quote ratios (maker fills against distinct resting quotes).
+//!
+//! The monitor is not wired into the matching path. Callers drive it by
+//! calling [`QuoteQualityMonitor::observe`] (or
+//! [`QuoteQualityMonitor::observe_at`]) whenever they want a sample —
+//! typically after every simulation step — and
+//! [`QuoteQualityMonitor::record_trade`] from a `TradeListener`. Time is
+//! credited sample-and-hold: the interval between two observations is
+//! attributed to the quotes seen at the earlier one.
+
+use crate::orderbook::OrderBook;
+use crate::orderbook::trade::TradeResult;
+use pricelevel::{Hash32, Id, Side};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Accumulated quote quality statistics for a single user.
+///
+/// All durations are in milliseconds of the clock driving the
+/// observations. Exported via [`QuoteQualityMonitor::user_stats`] and
+/// [`QuoteQualityMonitor::export`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuoteQualityStats {
+    /// Total time the user had at least one resting order
+    pub time_quoting_ms: u64,
+
+    /// Time the user had a resting bid at the best bid price
+    pub time_at_best_bid_ms: u64,
+
+    /// Time the user had a resting ask at the best ask price
+    pub time_at_best_ask_ms: u64,
+
+    /// Time the user was at the best bid and the best ask simultaneously
+    pub time_at_bbo_ms: u64,
+
+    /// Time the user had both a resting bid and a resting ask
+    pub time_two_sided_ms: u64,
+
+    /// Sum of `own_spread × elapsed_ms` over the two-sided time, used to
+    /// derive [`Self::avg_quoted_spread`]
+    pub spread_time_product: u128,
+
+    /// Number of distinct resting orders observed for this user
+    pub quotes_observed: u64,
+
+    /// Total quantity of the distinct resting orders observed, taken at
+    /// the first observation of each order (in units)
+    pub quoted_quantity: u64,
+
+    /// Number of maker fills recorded against this user's quotes
+    pub fill_count: u64,
+
+    /// Total maker quantity filled against this user's quotes (in units)
+    pub filled_quantity: u64,
+}
+
+impl QuoteQualityStats {
+    /// Fraction of the user's quoting time spent at the best bid and ask
+    /// simultaneously. Returns `0.0` if the user never quoted.
+    #[must_use]
+    pub fn bbo_presence_ratio(&self) -> f64 {
+        if self.time_quoting_ms == 0 {
+            return 0.0;
+        }
+        self.time_at_bbo_ms as f64 / self.time_quoting_ms as f64
+    }
+
+    /// Time-weighted average spread of the user's own two-sided quote
+    /// (in price units), or `None` if the user was never two-sided for a
+    /// non-zero interval.
+    #[must_use]
+    pub fn avg_quoted_spread(&self) -> Option<f64> {
+        if self.time_two_sided_ms == 0 {
+            return None;
+        }
+        Some(self.spread_time_product as f64 / self.time_two_sided_ms as f64)
+    }
+
+    /// Filled quantity over quoted quantity. Returns `0.0` if nothing was
+    /// quoted.
+    #[must_use]
+    pub fn fill_to_quote_ratio(&self) -> f64 {
+        if self.quoted_quantity == 0 {
+            return 0.0;
+        }
+        self.filled_quantity as f64 / self.quoted_quantity as f64
+    }
+}
+
+/// A user's own best quote as seen at one observation.
+#[derive(Debug, Clone, Copy, Default)]
+struct UserQuote {
+    best_bid: Option<u128>,
+    best_ask: Option<u128>,
+}
+
+/// Mutable state behind the monitor's lock.
+#[derive(Debug, Default)]
+struct MonitorState {
+    /// Timestamp of the most recent observation
+    last_observed_ms: Option<u64>,
+    /// Book BBO at the most recent observation
+    last_bbo: (Option<u128>, Option<u128>),
+    /// Per-user quotes at the most recent observation
+    last_quotes: HashMap<Hash32, UserQuote>,
+    /// Owner of every order resting at the most recent observation, used
+    /// to attribute maker fills
+    order_owners: HashMap<Id, Hash32>,
+    /// Owners of the orders that left the book (filled, cancelled or
+    /// expired) since the observation before, kept for one interval so a
+    /// fill reported after that observation is still attributed
+    departed_owners: HashMap<Id, Hash32>,
+    /// Accumulated statistics per user
+    stats: HashMap<Hash32, QuoteQualityStats>,
+}
+
+/// Per-user spread and time-at-BBO tracker.
+///
+/// Thread-safe: all state lives behind a single [`Mutex`], so a monitor
+/// can be shared between the thread driving observations and a
+/// `TradeListener` closure via `Arc`.
+///
+/// Orders submitted with `Hash32::zero()` as the user id are ignored.
+#[derive(Debug, Default)]
+pub struct QuoteQualityMonitor {
+    state: Mutex<MonitorState>,
+}
+
+impl QuoteQualityMonitor {
+    /// Create an empty monitor.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample `book` at the book clock's current time.
+    ///
+    /// See [`Self::observe_at`].
+    pub fn observe<T>(&self, book: &OrderBook<T>)
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        self.observe_at(book, book.clock().now_millis().as_u64());
+    }
+
+    /// Sample `book` at `now_ms`.
+    ///
+    /// The interval since the previous observation is credited to the
+    /// quotes seen at that previous observation; then the current quotes
+    /// are recorded for the next interval. A `now_ms` earlier than the
+    /// previous observation credits nothing.
+    pub fn observe_at<T>(&self, book: &OrderBook<T>, now_ms: u64)
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Some(last) = state.last_observed_ms {
+            let elapsed = now_ms.saturating_sub(last);
+            if elapsed > 0 {
+                state.credit_interval(elapsed);
+            }
+        }
+
+        let mut quotes: HashMap<Hash32, UserQuote> = HashMap::new();
+        let mut owners: HashMap<Id, Hash32> = HashMap::with_capacity(state.order_owners.len());
+        for entry in book.user_orders.iter() {
+            let user_id = *entry.key();
+            if user_id == Hash32::zero() {
+                continue;
+            }
+            let mut quote = UserQuote::default();
+            for order_id in entry.value() {
+                let Some(location) = book.order_locations.get(order_id) else {
+                    continue;
+                };
+                let (price, side) = *location;
+                drop(location);
+
+                match side {
+                    Side::Buy => {
+                        quote.best_bid = Some(quote.best_bid.map_or(price, |p| p.max(price)));
+                    }
+                    Side::Sell => {
+                        quote.best_ask = Some(quote.best_ask.map_or(price, |p| p.min(price)));
+                    }
+                }
+
+                let known = state.order_owners.remove(order_id).is_some()
+                    || state.departed_owners.contains_key(order_id);
+                owners.insert(*order_id, user_id);
+                if !known {
+                    let quantity = book.get_order(*order_id).map_or(0, |order| {
+                        order
+                            .visible_quantity()
+                            .as_u64()
+                            .saturating_add(order.hidden_quantity().as_u64())
+                    });
+                    let stats = state.stats.entry(user_id).or_default();
+                    stats.quotes_observed = stats.quotes_observed.saturating_add(1);
+                    stats.quoted_quantity = stats.quoted_quantity.saturating_add(quantity);
+                }
+            }
+            if quote.best_bid.is_some() || quote.best_ask.is_some() {
+                quotes.insert(user_id, quote);
+            }
+        }
+
+        // Whatever was not seen again has left the book.
+        state.departed_owners = std::mem::replace(&mut state.order_owners, owners);
+        state.last_observed_ms = Some(now_ms);
+        let bbo = book.bbo();
+        state.last_bbo = (bbo.best_bid, bbo.best_ask);
+        state.last_quotes = quotes;
+    }
+
+    /// Attribute the maker fills in `trade` to the owners of the resting
+    /// orders, as observed by earlier calls to [`Self::observe`].
+    ///
+    /// Makers that were never observed are skipped, as are makers that
+    /// had already left the book two observations ago: the monitor only
+    /// remembers the owners of orders resting at the last two
+    /// observations, so its memory stays bounded by the resting book.
+    pub fn record_trade(&self, trade: &TradeResult) {
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for fill in trade.match_result.trades().as_vec() {
+            let maker = fill.maker_order_id();
+            let Some(user_id) = state
+                .order_owners
+                .get(&maker)
+                .or_else(|| state.departed_owners.get(&maker))
+                .copied()
+            else {
+                continue;
+            };
+            let stats = state.stats.entry(user_id).or_default();
+            stats.fill_count = stats.fill_count.saturating_add(1);
+            stats.filled_quantity = stats
+                .filled_quantity
+                .saturating_add(fill.quantity().as_u64());
+        }
+    }
+
+    /// Statistics for `user_id`, or `None` if the user has never been
+    /// observed quoting.
+    #[must_use]
+    pub fn user_stats(&self, user_id: Hash32) -> Option<QuoteQualityStats> {
+        let state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.stats.get(&user_id).cloned()
+    }
+
+    /// Statistics for every observed user as `(user_id, stats)` pairs.
+    ///
+    /// Sorted by user id bytes so the export is deterministic.
+    #[must_use]
+    pub fn export(&self) -> Vec<(Hash32, QuoteQualityStats)> {
+        let state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut exported: Vec<(Hash32, QuoteQualityStats)> = state
+            .stats
+            .iter()
+            .map(|(user_id, stats)| (*user_id, stats.clone()))
+            .collect();
+        exported.sort_unstable_by_key(|(user_id, _)| user_id.0);
+        exported
+    }
+
+    /// Discard all accumulated statistics and observation history.
+    pub fn reset(&self) {
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *state = MonitorState::default();
+    }
+}
+
+impl MonitorState {
+    /// Credit `elapsed` milliseconds to the quotes held since the last
+    /// observation.
+    fn credit_interval(&mut self, elapsed: u64) {
+        let (best_bid, best_ask) = self.last_bbo;
+        for (user_id, quote) in &self.last_quotes {
+            let stats = self.stats.entry(*user_id).or_default();
+            stats.time_quoting_ms = stats.time_quoting_ms.saturating_add(elapsed);
+
+            let at_bid = quote.best_bid.is_some() && quote.best_bid == best_bid;
+            let at_ask = quote.best_ask.is_some() && quote.best_ask == best_ask;
+            if at_bid {
+                stats.time_at_best_bid_ms = stats.time_at_best_bid_ms.saturating_add(elapsed);
+            }
+            if at_ask {
+                stats.time_at_best_ask_ms = stats.time_at_best_ask_ms.saturating_add(elapsed);
+            }
+            if at_bid && at_ask {
+                stats.time_at_bbo_ms = stats.time_at_bbo_ms.saturating_add(elapsed);
+            }
+
+            if let (Some(bid), Some(ask)) = (quote.best_bid, quote.best_ask) {
+                stats.time_two_sided_ms = stats.time_two_sided_ms.saturating_add(elapsed);
+                let spread = ask.saturating_sub(bid);
+                stats.spread_time_product = stats
+                    .spread_time_product
+                    .saturating_add(spread.saturating_mul(u128::from(elapsed)));
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Order submission rate limiting for `OrderBook<T>`.
+//!
+//! This module provides opt-in token-bucket throttling of new flow:
+//!
+//! - [`TokenBucketConfig`] — bucket capacity (burst) and refill rate.
+//! - [`RateLimitConfig`] — an optional per-user bucket (keyed by the
+//!   order's `user_id`) and an optional aggregate bucket for the whole
+//!   book.
+//! - [`RateLimiter`] — bound to an [`OrderBook`](crate::OrderBook),
+//!   carries the optional config plus the live buckets. When no
+//!   [`RateLimitConfig`] is installed every check returns `Ok(())`.
+//!
+//! Each admitted submission consumes one token from the submitter's
+//! bucket and one from the aggregate bucket. A submission is admitted only
+//! if **both** buckets hold a token; otherwise neither is charged and the
+//! call fails with [`OrderBookError::RateLimited`] carrying the time until
+//! the emptier bucket refills.
+//!
+//! Check ordering on submit is
+//! `kill_switch → user_block → rate_limit → risk → STP → fees → match`.
+//! Cancels are never throttled. Orders submitted without a user (`Hash32::zero()`)
+//! share a single per-user bucket.
+
+use crate::orderbook::error::OrderBookError;
+use dashmap::DashMap;
+use pricelevel::Hash32;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Tokens are tracked in thousandths so integer refill at any
+/// tokens-per-second rate is exact per millisecond.
+const MILLI_TOKENS_PER_TOKEN: u64 = 1_000;
+
+/// Token-bucket parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBucketConfig {
+    /// Maximum number of tokens held, i.e. the largest burst admitted
+    /// back-to-back. A zero capacity rejects every submission.
+    pub capacity: u64,
+
+    /// Tokens added per second. Zero disables refill.
+    pub refill_per_sec: u64,
+}
+
+impl TokenBucketConfig {
+    /// Create a bucket configuration.
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: u64, refill_per_sec: u64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+        }
+    }
+}
+
+/// Per-`OrderBook` rate-limit configuration.
+///
+/// Every bucket is optional; an empty config admits everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Bucket applied to each `user_id` independently.
+    pub per_user: Option<TokenBucketConfig>,
+
+    /// Bucket shared by every submission on the book.
+    pub global: Option<TokenBucketConfig>,
+}
+
+impl RateLimitConfig {
+    /// Create an empty configuration (no limits).
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the per-user bucket.
+    #[inline]
+    #[must_use]
+    pub fn with_per_user(mut self, bucket: TokenBucketConfig) -> Self {
+        self.per_user = Some(bucket);
+        self
+    }
+
+    /// Set the aggregate book bucket.
+    #[inline]
+    #[must_use]
+    pub fn with_global(mut self, bucket: TokenBucketConfig) -> Self {
+        self.global = Some(bucket);
+        self
+    }
+}
+
+/// Live state of one bucket.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// Available tokens, in thousandths
+    milli_tokens: u64,
+    /// Time of the latest refill in milliseconds
+    last_refill_ms: u64,
+}
+
+impl TokenBucket {
+    /// A full bucket as of `now_ms`.
+    fn full(config: &TokenBucketConfig, now_ms: u64) -> Self {
+        Self {
+            milli_tokens: config.capacity.saturating_mul(MILLI_TOKENS_PER_TOKEN),
+            last_refill_ms: now_ms,
+        }
+    }
+
+    /// Credit the tokens accrued since the latest refill. A `now_ms`
+    /// earlier than the latest refill credits nothing.
+    fn refill(&mut self, config: &TokenBucketConfig, now_ms: u64) {
+        let elapsed = now_ms.saturating_sub(self.last_refill_ms);
+        let cap = config.capacity.saturating_mul(MILLI_TOKENS_PER_TOKEN);
+        self.milli_tokens = self
+            .milli_tokens
+            .saturating_add(elapsed.saturating_mul(config.refill_per_sec))
+            .min(cap);
+        self.last_refill_ms = self.last_refill_ms.max(now_ms);
+    }
+
+    /// Milliseconds until one token is available; `0` if one is available
+    /// now and `u64::MAX` if one never will be.
+    fn wait_ms(&self, config: &TokenBucketConfig) -> u64 {
+        if self.milli_tokens >= MILLI_TOKENS_PER_TOKEN {
+            return 0;
+        }
+        if config.refill_per_sec == 0 || config.capacity == 0 {
+            return u64::MAX;
+        }
+        (MILLI_TOKENS_PER_TOKEN - self.milli_tokens).div_ceil(config.refill_per_sec)
+    }
+
+    fn take(&mut self) {
+        self.milli_tokens -= MILLI_TOKENS_PER_TOKEN;
+    }
+}
+
+/// Rate-limit state bound to a single `OrderBook<T>`.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    config: Option<RateLimitConfig>,
+    users: DashMap<Hash32, TokenBucket>,
+    global: Mutex<Option<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Construct a limiter with no configuration installed.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install or replace the active configuration. Buckets are reset to
+    /// full so the new limits apply from a clean slate.
+    pub fn set_config(&mut self, config: RateLimitConfig) {
+        self.config = Some(config);
+        self.clear();
+    }
+
+    /// Read-only access to the active configuration, if any.
+    #[inline]
+    #[must_use]
+    pub fn config(&self) -> Option<&RateLimitConfig> {
+        self.config.as_ref()
+    }
+
+    /// Drop the active configuration and every bucket.
+    pub fn disable(&mut self) {
+        self.config = None;
+        self.clear();
+    }
+
+    /// Number of users with a live per-user bucket.
+    #[must_use]
+    pub fn tracked_users(&self) -> usize {
+        self.users.len()
+    }
+
+    /// Reset every bucket to full.
+    pub fn clear(&self) {
+        self.users.clear();
+        let mut global = match self.global.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *global = None;
+    }
+
+    /// Admission check for one submission by `user_id` at `now_ms`.
+    ///
+    /// Consumes a token from the per-user and the global bucket when both
+    /// have one; otherwise consumes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::RateLimited`] with the milliseconds until
+    /// the submission would be admitted (`u64::MAX` if never).
+    pub fn check_admission(&self, user_id: Hash32, now_ms: u64) -> Result<(), OrderBookError> {
+        let Some(config) = self.config else {
+            return Ok(());
+        };
+
+        // Lock order: global bucket, then the user's map shard.
+        let mut global = config.global.map(|_| match self.global.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        });
+        let mut user = config.per_user.map(|bucket| {
+            self.users
+                .entry(user_id)
+                .or_insert_with(|| TokenBucket::full(&bucket, now_ms))
+        });
+
+        let mut retry_after_ms = 0;
+        if let (Some(bucket), Some(guard)) = (config.global.as_ref(), global.as_mut()) {
+            let state = guard.get_or_insert_with(|| TokenBucket::full(bucket, now_ms));
+            state.refill(bucket, now_ms);
+            retry_after_ms = retry_after_ms.max(state.wait_ms(bucket));
+        }
+        if let (Some(bucket), Some(state)) = (config.per_user.as_ref(), user.as_mut()) {
+            state.refill(bucket, now_ms);
+            retry_after_ms = retry_after_ms.max(state.wait_ms(bucket));
+        }
+
+        if retry_after_ms > 0 {
+            return Err(OrderBookError::RateLimited { retry_after_ms });
+        }
+
+        if let Some(state) = global.as_mut().and_then(|guard| guard.as_mut()) {
+            state.take();
+        }
+        if let Some(state) = user.as_mut() {
+            state.take();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    #[test]
+    fn test_unconfigured_limiter_admits_everything() {
+        let limiter = RateLimiter::new();
+        for _ in 0..1_000 {
+            assert!(limiter.check_admission(user(1), 0).is_ok());
+        }
+        assert_eq!(limiter.tracked_users(), 0);
+    }
+
+    #[test]
+    fn test_per_user_bucket_bursts_then_refills() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_config(RateLimitConfig::new().with_per_user(TokenBucketConfig::new(2, 10)));
+
+        assert!(limiter.check_admission(user(1), 0).is_ok());
+        assert!(limiter.check_admission(user(1), 0).is_ok());
+        // 10 tokens/s → one token every 100 ms.
+        assert!(matches!(
+            limiter.check_admission(user(1), 0),
+            Err(OrderBookError::RateLimited {
+                retry_after_ms: 100
+            })
+        ));
+        assert!(matches!(
+            limiter.check_admission(user(1), 40),
+            Err(OrderBookError::RateLimited { retry_after_ms: 60 })
+        ));
+        assert!(limiter.check_admission(user(1), 100).is_ok());
+
+        // Another user has an independent bucket.
+        assert!(limiter.check_admission(user(2), 100).is_ok());
+        assert_eq!(limiter.tracked_users(), 2);
+    }
+
+    #[test]
+    fn test_global_bucket_is_shared_and_rejection_charges_nothing() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_config(
+            RateLimitConfig::new()
+                .with_per_user(TokenBucketConfig::new(1, 1))
+                .with_global(TokenBucketConfig::new(2, 1)),
+        );
+
+        assert!(limiter.check_admission(user(1), 0).is_ok());
+        // User 1 is out of tokens; the rejection must not drain the
+        // global bucket.
+        assert!(limiter.check_admission(user(1), 0).is_err());
+        assert!(limiter.check_admission(user(2), 0).is_ok());
+        // Global bucket exhausted for every user.
+        assert!(matches!(
+            limiter.check_admission(user(3), 0),
+            Err(OrderBookError::RateLimited {
+                retry_after_ms: 1_000
+            })
+        ));
+    }
+
+    #[test]
+    fn test_zero_refill_never_recovers() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_config(RateLimitConfig::new().with_global(TokenBucketConfig::new(1, 0)));
+
+        assert!(limiter.check_admission(user(1), 0).is_ok());
+        assert!(matches!(
+            limiter.check_admission(user(1), 1_000_000),
+            Err(OrderBookError::RateLimited {
+                retry_after_ms: u64::MAX
+            })
+        ));
+
+        limiter.disable();
+        assert!(limiter.check_admission(user(1), 1_000_000).is_ok());
+    }
+}
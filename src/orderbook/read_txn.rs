@@ -0,0 +1,258 @@
+//! Snapshot-consistent read transactions over the top of a book.
+//!
+//! Each analytics query on [`OrderBook`] walks the live skip maps, so a
+//! pipeline that reads the best bid, then depth, then VWAP, then imbalance
+//! may see a different book state for each while writers are active.
+//! [`OrderBook::read_txn`] instead pins one immutable copy of the top
+//! levels of each side, and every query on the returned [`ReadTxn`]
+//! answers from that copy: all of them observe the same instant of the
+//! book, identified by [`ReadTxn::update_seq`].
+//!
+//! The copy is the same [`LevelAggregates`] view that
+//! [`OrderBook::level_aggregates`] caches until the next mutation, shared
+//! behind an `Arc`, so opening a transaction on an unchanged book does not
+//! walk the skip maps at all. Queries only see the pinned levels: depth,
+//! VWAP and imbalance are computed over at most [`ReadTxn::depth`] levels
+//! per side.
+
+use super::book::OrderBook;
+use super::cache::TopOfBook;
+use super::depth_mode::DepthMode;
+use super::snapshot::{LevelAggregate, LevelAggregates};
+use pricelevel::Side;
+use std::sync::Arc;
+
+/// Levels per side pinned by [`OrderBook::read_txn`].
+pub const DEFAULT_READ_TXN_DEPTH: usize = 20;
+
+/// An immutable view of the top levels of a book at one instant, returned
+/// by [`OrderBook::read_txn`].
+///
+/// Every query answers from the same pinned levels, so values read from one
+/// transaction never mix book states. Prices and quantities are raw units,
+/// like the rest of the book API.
+#[derive(Debug, Clone)]
+pub struct ReadTxn {
+    levels: Arc<LevelAggregates>,
+    depth: usize,
+    consistent: bool,
+}
+
+impl ReadTxn {
+    /// Book update sequence the levels were read at. Transactions with
+    /// equal values observed the same book state.
+    #[must_use]
+    pub fn update_seq(&self) -> u64 {
+        self.levels.update_seq
+    }
+
+    /// Levels per side pinned by this transaction.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Whether every level was read at [`Self::update_seq`], with no
+    /// mutation finishing or in flight during the build.
+    ///
+    /// `true` unless writers kept the book in flux throughout the retried
+    /// build, or the transaction was opened from inside a mutation of this
+    /// book (a listener); the levels may then mix book states.
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    /// Pinned bid levels, highest price first.
+    #[must_use]
+    pub fn bids(&self) -> &[LevelAggregate] {
+        &self.levels.bids[..self.levels.bids.len().min(self.depth)]
+    }
+
+    /// Pinned ask levels, lowest price first.
+    #[must_use]
+    pub fn asks(&self) -> &[LevelAggregate] {
+        &self.levels.asks[..self.levels.asks.len().min(self.depth)]
+    }
+
+    /// Pinned levels of `side`, best first.
+    #[must_use]
+    pub fn side(&self, side: Side) -> &[LevelAggregate] {
+        match side {
+            Side::Buy => self.bids(),
+            Side::Sell => self.asks(),
+        }
+    }
+
+    /// Best bid and ask with the total quantity at each, as
+    /// [`OrderBook::top_of_book`] would have returned at this instant.
+    #[must_use]
+    pub fn top_of_book(&self) -> TopOfBook {
+        let bid = self.bids().first();
+        let ask = self.asks().first();
+        TopOfBook {
+            best_bid: bid.map(|level| level.price),
+            best_ask: ask.map(|level| level.price),
+            bid_quantity: bid.map_or(0, LevelAggregate::total_quantity),
+            ask_quantity: ask.map_or(0, LevelAggregate::total_quantity),
+            update_seq: self.update_seq(),
+        }
+    }
+
+    /// Best (highest) bid price.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<u128> {
+        self.bids().first().map(|level| level.price)
+    }
+
+    /// Best (lowest) ask price.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<u128> {
+        self.asks().first().map(|level| level.price)
+    }
+
+    /// Midpoint of the best bid and ask, if both sides are present.
+    #[must_use]
+    pub fn mid_price(&self) -> Option<f64> {
+        self.top_of_book().mid_price()
+    }
+
+    /// Best ask minus best bid, if both sides are present.
+    #[must_use]
+    pub fn spread(&self) -> Option<u128> {
+        self.top_of_book().spread()
+    }
+
+    /// Quantity-weighted micro price, see [`OrderBook::micro_price`].
+    #[must_use]
+    pub fn micro_price(&self) -> Option<f64> {
+        self.top_of_book().micro_price()
+    }
+
+    /// Total quantity in the top `levels` pinned levels of `side`, see
+    /// [`OrderBook::total_depth_at_levels`].
+    #[must_use]
+    pub fn total_depth_at_levels(&self, levels: usize, side: Side) -> u64 {
+        self.total_depth_at_levels_with_mode(levels, side, DepthMode::Total)
+    }
+
+    /// [`Self::total_depth_at_levels`] counting the depth selected by
+    /// `mode`.
+    #[must_use]
+    pub fn total_depth_at_levels_with_mode(
+        &self,
+        levels: usize,
+        side: Side,
+        mode: DepthMode,
+    ) -> u64 {
+        self.side(side)
+            .iter()
+            .take(levels)
+            .fold(0u64, |total, level| {
+                total.saturating_add(mode.aggregate_quantity(level))
+            })
+    }
+
+    /// Volume-weighted average price of filling `quantity` on `side`, see
+    /// [`OrderBook::vwap`]. `None` when the pinned levels cannot fill it,
+    /// even if deeper levels of the book could.
+    #[must_use]
+    pub fn vwap(&self, quantity: u64, side: Side) -> Option<f64> {
+        self.vwap_with_mode(quantity, side, DepthMode::Total)
+    }
+
+    /// [`Self::vwap`] counting the depth selected by `mode`.
+    #[must_use]
+    pub fn vwap_with_mode(&self, quantity: u64, side: Side, mode: DepthMode) -> Option<f64> {
+        if quantity == 0 {
+            return None;
+        }
+
+        // Buying fills against asks, selling against bids.
+        let levels = match side {
+            Side::Buy => self.asks(),
+            Side::Sell => self.bids(),
+        };
+
+        let mut remaining = quantity;
+        let mut total_cost = 0u128;
+        for level in levels {
+            if remaining == 0 {
+                break;
+            }
+            let fill_qty = remaining.min(mode.aggregate_quantity(level));
+            total_cost =
+                total_cost.saturating_add(level.price.saturating_mul(u128::from(fill_qty)));
+            remaining -= fill_qty;
+        }
+
+        (remaining == 0).then(|| total_cost as f64 / quantity as f64)
+    }
+
+    /// Imbalance of the top `levels` pinned levels of each side, from -1.0
+    /// to 1.0, see [`OrderBook::order_book_imbalance`].
+    #[must_use]
+    pub fn order_book_imbalance(&self, levels: usize) -> f64 {
+        self.order_book_imbalance_with_mode(levels, DepthMode::Total)
+    }
+
+    /// [`Self::order_book_imbalance`] counting the depth selected by
+    /// `mode`.
+    #[must_use]
+    pub fn order_book_imbalance_with_mode(&self, levels: usize, mode: DepthMode) -> f64 {
+        let bid = self.total_depth_at_levels_with_mode(levels, Side::Buy, mode) as f64;
+        let ask = self.total_depth_at_levels_with_mode(levels, Side::Sell, mode) as f64;
+        if bid + ask == 0.0 {
+            return 0.0;
+        }
+        (bid - ask) / (bid + ask)
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Open a read transaction over the top [`DEFAULT_READ_TXN_DEPTH`]
+    /// levels of each side. See [`Self::read_txn_with_depth`].
+    ///
+    /// # Examples
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let _ = book.add_limit_order(Id::new(), 100, 60, Side::Buy, TimeInForce::Gtc, None);
+    /// let _ = book.add_limit_order(Id::new(), 105, 40, Side::Sell, TimeInForce::Gtc, None);
+    ///
+    /// let txn = book.read_txn();
+    /// // A later mutation is not seen by the open transaction.
+    /// let _ = book.add_limit_order(Id::new(), 101, 10, Side::Buy, TimeInForce::Gtc, None);
+    ///
+    /// assert_eq!(txn.best_bid(), Some(100));
+    /// assert_eq!(txn.spread(), Some(5));
+    /// assert_eq!(txn.vwap(40, Side::Buy), Some(105.0));
+    /// assert_eq!(txn.order_book_imbalance(5), 0.2);
+    /// assert_eq!(book.best_bid(), Some(101));
+    /// ```
+    #[must_use]
+    pub fn read_txn(&self) -> ReadTxn {
+        self.read_txn_with_depth(DEFAULT_READ_TXN_DEPTH)
+    }
+
+    /// Open a read transaction pinning the top `depth` levels of each side.
+    ///
+    /// O(depth) on the first call after a mutation, then O(1): the pinned
+    /// levels are the view cached by [`Self::level_aggregates`], shared
+    /// rather than copied. The transaction holds no lock and never blocks
+    /// writers.
+    #[must_use]
+    pub fn read_txn_with_depth(&self, depth: usize) -> ReadTxn {
+        let (levels, consistent) = self.pinned_level_aggregates(depth);
+        ReadTxn {
+            levels,
+            depth,
+            consistent,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Externally supplied index and mark prices.
+//!
+//! A venue usually knows more about fair value than its own book: an index
+//! aggregated across spot venues, or a mark price derived from it. Such
+//! prices are injected with [`OrderBook::set_reference_price`], stamped
+//! with the time they were observed. With
+//! [`BookConfig::reference_price_max_age_ms`](super::book_config::BookConfig::reference_price_max_age_ms)
+//! set, a price older than that limit on the book clock is stale and every
+//! consumer treats it as absent until the next update.
+//!
+//! The consumers are:
+//!
+//! - price bands configured with
+//!   [`ReferencePriceSource::External`](super::risk::ReferencePriceSource::External),
+//!   which read the index price,
+//! - with `special_orders`, stop triggers on `StopTriggerReference::Index`
+//!   or `StopTriggerReference::Mark`, and `LastTrade` pegged orders that
+//!   `OrderBook::set_peg_last_trade_source` redirects to an external price,
+//! - [`PerpTracker::update_from_reference`](super::perp::PerpTracker::update_from_reference),
+//!   which funds against the index price,
+//! - enriched snapshots, which carry both prices with their timestamps.
+//!
+//! Prices are in the book's raw price units and are not persisted across
+//! snapshot/restore.
+
+use super::book::OrderBook;
+use crossbeam::atomic::AtomicCell;
+use serde::{Deserialize, Serialize};
+
+/// Kind of externally supplied reference price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExternalPriceSource {
+    /// Index price, e.g. an average of spot prices across venues.
+    Index,
+    /// Mark price used for margining and funding.
+    Mark,
+}
+
+impl std::fmt::Display for ExternalPriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalPriceSource::Index => write!(f, "Index"),
+            ExternalPriceSource::Mark => write!(f, "Mark"),
+        }
+    }
+}
+
+/// An externally supplied price and the time it was observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalPrice {
+    /// Price in raw price units
+    pub price: u128,
+    /// Observation time in milliseconds since epoch
+    pub timestamp_ms: u64,
+}
+
+impl ExternalPrice {
+    /// Milliseconds elapsed between the observation and `now_ms`; zero
+    /// for an observation stamped in the future.
+    #[must_use]
+    #[inline]
+    pub fn age_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.timestamp_ms)
+    }
+}
+
+/// The latest index and mark prices of one book.
+#[derive(Debug, Default)]
+pub(crate) struct ExternalPrices {
+    index: AtomicCell<Option<ExternalPrice>>,
+    mark: AtomicCell<Option<ExternalPrice>>,
+}
+
+impl ExternalPrices {
+    fn cell(&self, source: ExternalPriceSource) -> &AtomicCell<Option<ExternalPrice>> {
+        match source {
+            ExternalPriceSource::Index => &self.index,
+            ExternalPriceSource::Mark => &self.mark,
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Record the `source` price observed at `timestamp_ms`.
+    ///
+    /// An update older than the stored one is ignored, so a feed delivered
+    /// out of order never moves the price backwards; returns whether the
+    /// update was stored. A zero price clears the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{BookConfig, ExternalPriceSource, OrderBook};
+    ///
+    /// let book = OrderBook::<()>::new("BTC-PERP");
+    /// book.reload_config(BookConfig::new().with_reference_price_max_age_ms(5_000))
+    ///     .expect("valid configuration");
+    /// let now = book.clock().now_millis().as_u64();
+    ///
+    /// assert!(book.set_reference_price(ExternalPriceSource::Index, 50_000, now));
+    /// assert!(!book.set_reference_price(ExternalPriceSource::Index, 49_000, now - 1));
+    /// assert_eq!(book.fresh_reference_price(ExternalPriceSource::Index), Some(50_000));
+    ///
+    /// // A mark observed too long ago is kept but not used.
+    /// book.set_reference_price(ExternalPriceSource::Mark, 50_010, now - 60_000);
+    /// assert!(book.reference_price(ExternalPriceSource::Mark).is_some());
+    /// assert_eq!(book.fresh_reference_price(ExternalPriceSource::Mark), None);
+    /// ```
+    pub fn set_reference_price(
+        &self,
+        source: ExternalPriceSource,
+        price: u128,
+        timestamp_ms: u64,
+    ) -> bool {
+        let _gate = self.submit_gate_read();
+        let cell = self.external_prices.cell(source);
+        if price == 0 {
+            cell.store(None);
+            return true;
+        }
+        let update = ExternalPrice {
+            price,
+            timestamp_ms,
+        };
+        cell.fetch_update(|current| match current {
+            Some(stored) if stored.timestamp_ms > timestamp_ms => None,
+            _ => Some(Some(update)),
+        })
+        .is_ok()
+    }
+
+    /// The latest `source` price, stale or not.
+    #[must_use]
+    #[inline]
+    pub fn reference_price(&self, source: ExternalPriceSource) -> Option<ExternalPrice> {
+        self.external_prices.cell(source).load()
+    }
+
+    /// Whether the latest `source` price is older than
+    /// [`BookConfig::reference_price_max_age_ms`](super::book_config::BookConfig::reference_price_max_age_ms)
+    /// on the book clock. A missing price is not stale, and no price is
+    /// stale while no limit is configured.
+    #[must_use]
+    pub fn is_reference_price_stale(&self, source: ExternalPriceSource) -> bool {
+        self.reference_price(source)
+            .is_some_and(|price| self.is_stale(price, self.config().reference_price_max_age_ms))
+    }
+
+    /// The latest `source` price if it is not stale. This is the value
+    /// every consumer of reference prices reads.
+    #[must_use]
+    pub fn fresh_reference_price(&self, source: ExternalPriceSource) -> Option<u128> {
+        self.reference_price_within(source, self.config().reference_price_max_age_ms)
+    }
+
+    /// [`Self::fresh_reference_price`] against a `max_age_ms` the caller
+    /// already read from its configuration snapshot.
+    pub(super) fn reference_price_within(
+        &self,
+        source: ExternalPriceSource,
+        max_age_ms: Option<u64>,
+    ) -> Option<u128> {
+        self.reference_price(source)
+            .filter(|&price| !self.is_stale(price, max_age_ms))
+            .map(|price| price.price)
+    }
+
+    fn is_stale(&self, price: ExternalPrice, max_age_ms: Option<u64>) -> bool {
+        max_age_ms
+            .is_some_and(|max_age_ms| price.age_ms(self.clock().now_millis().as_u64()) > max_age_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BookConfig;
+
+    #[test]
+    fn updates_are_monotonic_and_zero_clears() {
+        let book = OrderBook::<()>::new("BTC-PERP");
+        assert!(book.set_reference_price(ExternalPriceSource::Index, 100, 10));
+        assert!(book.set_reference_price(ExternalPriceSource::Index, 101, 10));
+        assert!(!book.set_reference_price(ExternalPriceSource::Index, 99, 9));
+        assert_eq!(
+            book.reference_price(ExternalPriceSource::Index),
+            Some(ExternalPrice {
+                price: 101,
+                timestamp_ms: 10
+            })
+        );
+        assert_eq!(book.reference_price(ExternalPriceSource::Mark), None);
+
+        assert!(book.set_reference_price(ExternalPriceSource::Index, 0, 0));
+        assert_eq!(book.reference_price(ExternalPriceSource::Index), None);
+        assert!(book.set_reference_price(ExternalPriceSource::Index, 98, 1));
+    }
+
+    #[test]
+    fn staleness_follows_the_configured_age() {
+        let book = OrderBook::<()>::new("BTC-PERP");
+        let now = book.clock().now_millis().as_u64();
+        book.set_reference_price(ExternalPriceSource::Mark, 100, now - 1_000);
+        assert!(!book.is_reference_price_stale(ExternalPriceSource::Mark));
+
+        book.reload_config(BookConfig::new().with_reference_price_max_age_ms(500))
+            .expect("valid");
+        assert!(book.is_reference_price_stale(ExternalPriceSource::Mark));
+        assert_eq!(book.fresh_reference_price(ExternalPriceSource::Mark), None);
+        assert!(!book.is_reference_price_stale(ExternalPriceSource::Index));
+
+        book.set_reference_price(ExternalPriceSource::Mark, 102, now + 50);
+        assert_eq!(
+            book.fresh_reference_price(ExternalPriceSource::Mark),
+            Some(102)
+        );
+        assert_eq!(ExternalPriceSource::Mark.to_string(), "Mark");
+    }
+}
@@ -53,6 +53,10 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// | `MissingUserId`          | 11  |
 /// | `DuplicateOrderId`       | 12  |
 /// | `InsufficientLiquidity`  | 13  |
+/// | `OrderNotionalOutOfRange`| 14  |
+/// | `RateLimited`            | 15  |
+/// | `UserBlocked`            | 16  |
+/// | `MarketClosed`           | 17  |
 /// | `Other(code)`            | code|
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -87,6 +91,16 @@ pub enum RejectReason {
     /// The order could not be filled with the available resting depth
     /// (IOC / FOK semantics).
     InsufficientLiquidity = 13,
+    /// Submitted notional (`price × quantity`) is outside the configured
+    /// min/max range.
+    OrderNotionalOutOfRange = 14,
+    /// Submission throttled by the per-user or book-wide rate limit.
+    RateLimited = 15,
+    /// Submitting user is blocked by the per-user kill switch.
+    UserBlocked = 16,
+    /// The trading session or circuit breaker does not admit the order
+    /// in the book's current phase.
+    MarketClosed = 17,
     /// Caller-supplied / unmapped code. The library never emits this
     /// variant; it exists so applications can ferry their own reject
     /// codes through the same channel without forking the enum.
@@ -116,6 +130,10 @@ impl RejectReason {
             Self::MissingUserId => 11,
             Self::DuplicateOrderId => 12,
             Self::InsufficientLiquidity => 13,
+            Self::OrderNotionalOutOfRange => 14,
+            Self::RateLimited => 15,
+            Self::UserBlocked => 16,
+            Self::MarketClosed => 17,
             Self::Other(code) => code,
         }
     }
@@ -141,6 +159,10 @@ impl RejectReason {
             11 => Self::MissingUserId,
             12 => Self::DuplicateOrderId,
             13 => Self::InsufficientLiquidity,
+            14 => Self::OrderNotionalOutOfRange,
+            15 => Self::RateLimited,
+            16 => Self::UserBlocked,
+            17 => Self::MarketClosed,
             other => Self::Other(other),
         }
     }
@@ -189,6 +211,10 @@ impl std::fmt::Display for RejectReason {
             Self::MissingUserId => write!(f, "missing user id"),
             Self::DuplicateOrderId => write!(f, "duplicate order id"),
             Self::InsufficientLiquidity => write!(f, "insufficient liquidity"),
+            Self::OrderNotionalOutOfRange => write!(f, "order notional out of range"),
+            Self::RateLimited => write!(f, "rate limited"),
+            Self::UserBlocked => write!(f, "user blocked"),
+            Self::MarketClosed => write!(f, "market closed"),
             Self::Other(code) => write!(f, "other({code})"),
         }
     }
@@ -211,9 +237,14 @@ impl From<&OrderBookError> for RejectReason {
     fn from(err: &OrderBookError) -> Self {
         match err {
             OrderBookError::KillSwitchActive => Self::KillSwitchActive,
+            OrderBookError::RateLimited { .. } => Self::RateLimited,
+            OrderBookError::UserBlocked { .. } => Self::UserBlocked,
+            OrderBookError::MarketClosed { .. } => Self::MarketClosed,
             OrderBookError::RiskMaxOpenOrders { .. } => Self::RiskMaxOpenOrders,
             OrderBookError::RiskMaxNotional { .. } => Self::RiskMaxNotional,
-            OrderBookError::RiskPriceBand { .. } => Self::RiskPriceBand,
+            OrderBookError::RiskPriceBand { .. } | OrderBookError::RiskPriceBandTicks { .. } => {
+                Self::RiskPriceBand
+            }
             OrderBookError::SelfTradePrevented { .. } => Self::SelfTradePrevention,
             OrderBookError::InvalidPriceLevel(_) => Self::InvalidPriceLevel,
             OrderBookError::PriceCrossing { .. } => Self::PostOnlyWouldCross,
@@ -223,14 +254,36 @@ impl From<&OrderBookError> for RejectReason {
             OrderBookError::InvalidLotSize { .. } => Self::InvalidQuantity,
             OrderBookError::QuantityOverflow { .. } => Self::InvalidQuantity,
             OrderBookError::OrderSizeOutOfRange { .. } => Self::OrderSizeOutOfRange,
+            OrderBookError::OrderNotionalOutOfRange { .. } => Self::OrderNotionalOutOfRange,
             OrderBookError::DuplicateOrderId { .. } => Self::DuplicateOrderId,
             OrderBookError::MissingUserId { .. } => Self::MissingUserId,
             OrderBookError::PriceLevelError(_) => Self::Other(0),
             OrderBookError::OrderNotFound(_) => Self::Other(0),
             OrderBookError::InvalidOperation { .. } => Self::Other(0),
+            OrderBookError::InvalidConfiguration { .. } => Self::Other(0),
+            OrderBookError::PriceUnchanged { .. } => Self::Other(0),
+            OrderBookError::OrderExpired { .. } => Self::Other(0),
+            OrderBookError::LevelQuantityOverflow { .. } => Self::Other(0),
+            OrderBookError::UnsupportedSnapshotVersion { .. } => Self::Other(0),
+            OrderBookError::SnapshotSymbolMismatch { .. } => Self::Other(0),
+            OrderBookError::DuplicateSnapshotLevel { .. } => Self::Other(0),
+            OrderBookError::InvalidIndexPrice { .. } => Self::Other(0),
             OrderBookError::SerializationError { .. } => Self::Other(0),
             OrderBookError::DeserializationError { .. } => Self::Other(0),
             OrderBookError::ChecksumMismatch { .. } => Self::Other(0),
+            OrderBookError::ExecutionNotFound { .. } => Self::Other(0),
+            OrderBookError::NotTrailingStop { .. } => Self::Other(0),
+            OrderBookError::ReferencePriceUnavailable { .. } => Self::Other(0),
+            OrderBookError::NotAModification { .. } => Self::Other(0),
+            OrderBookError::NothingToModify { .. } => Self::Other(0),
+            OrderBookError::NoHiddenTranche { .. } => Self::InvalidQuantity,
+            OrderBookError::ImmediateTifOnResting { .. } => Self::Other(0),
+            OrderBookError::ZeroQuantity { .. } => Self::InvalidQuantity,
+            OrderBookError::InvalidReduction { .. } => Self::InvalidQuantity,
+            OrderBookError::CorrectionToZero { .. } => Self::InvalidQuantity,
+            OrderBookError::InvalidMidpointQuantity { .. } => Self::InvalidQuantity,
+            OrderBookError::OrderTagTooLong { .. } => Self::Other(0),
+            OrderBookError::ZeroHeartbeatTtl { .. } => Self::Other(0),
             #[cfg(feature = "nats")]
             OrderBookError::NatsPublishError { .. } => Self::Other(0),
             #[cfg(feature = "nats")]
@@ -246,7 +299,7 @@ mod tests {
 
     /// Every named variant — used to drive exhaustive table-style tests.
     /// The `Other` variant is added explicitly where needed.
-    fn named_variants() -> [RejectReason; 13] {
+    fn named_variants() -> [RejectReason; 17] {
         [
             RejectReason::KillSwitchActive,
             RejectReason::RiskMaxOpenOrders,
@@ -261,6 +314,10 @@ mod tests {
             RejectReason::MissingUserId,
             RejectReason::DuplicateOrderId,
             RejectReason::InsufficientLiquidity,
+            RejectReason::OrderNotionalOutOfRange,
+            RejectReason::RateLimited,
+            RejectReason::UserBlocked,
+            RejectReason::MarketClosed,
         ]
     }
 
@@ -279,6 +336,10 @@ mod tests {
         assert_eq!(RejectReason::MissingUserId.as_u16(), 11);
         assert_eq!(RejectReason::DuplicateOrderId.as_u16(), 12);
         assert_eq!(RejectReason::InsufficientLiquidity.as_u16(), 13);
+        assert_eq!(RejectReason::OrderNotionalOutOfRange.as_u16(), 14);
+        assert_eq!(RejectReason::RateLimited.as_u16(), 15);
+        assert_eq!(RejectReason::UserBlocked.as_u16(), 16);
+        assert_eq!(RejectReason::MarketClosed.as_u16(), 17);
     }
 
     #[test]
@@ -309,6 +370,20 @@ mod tests {
         assert_eq!(RejectReason::from(&err), RejectReason::KillSwitchActive);
     }
 
+    #[test]
+    fn test_from_order_book_error_user_blocked() {
+        let err = OrderBookError::UserBlocked {
+            user_id: Hash32::from([3u8; 32]),
+        };
+        assert_eq!(RejectReason::from(&err), RejectReason::UserBlocked);
+    }
+
+    #[test]
+    fn test_from_order_book_error_rate_limited() {
+        let err = OrderBookError::RateLimited { retry_after_ms: 25 };
+        assert_eq!(RejectReason::from(&err), RejectReason::RateLimited);
+    }
+
     #[test]
     fn test_from_order_book_error_risk_max_open_maps_to_risk_max_open_orders() {
         let err = OrderBookError::RiskMaxOpenOrders {
@@ -339,6 +414,13 @@ mod tests {
             limit_bps: 100,
         };
         assert_eq!(RejectReason::from(&err), RejectReason::RiskPriceBand);
+        let err = OrderBookError::RiskPriceBandTicks {
+            submitted: 120,
+            reference: 100,
+            deviation_ticks: 20,
+            limit_ticks: 10,
+        };
+        assert_eq!(RejectReason::from(&err), RejectReason::RiskPriceBand);
     }
 
     #[test]
@@ -357,6 +439,19 @@ mod tests {
         assert_eq!(RejectReason::from(&err), RejectReason::OrderSizeOutOfRange);
     }
 
+    #[test]
+    fn test_from_order_book_error_order_notional_out_of_range() {
+        let err = OrderBookError::OrderNotionalOutOfRange {
+            notional: 10,
+            min: Some(100),
+            max: None,
+        };
+        assert_eq!(
+            RejectReason::from(&err),
+            RejectReason::OrderNotionalOutOfRange
+        );
+    }
+
     #[test]
     fn test_from_order_book_error_missing_user_id() {
         let err = OrderBookError::MissingUserId {
@@ -0,0 +1,696 @@
+//! Historical replay of recorded market data into an [`OrderBook`].
+//!
+//! [`MarketReplay`] owns a book and drives it through a time-ordered
+//! stream of [`MarketDataEvent`]s: L3 order adds, cancels, modifies and
+//! executions, or L2 aggregated level updates. Each level in L2 data is
+//! represented on the book by one synthetic order carrying the level's
+//! size. The book runs on a [`ReplayClock`] that follows the recorded
+//! timestamps, so time-in-force expiry and every timestamp the book
+//! stamps reflect recorded time rather than wall-clock time.
+//!
+//! Strategy orders interleave with the recorded flow. A strategy callback
+//! sees the book after every recorded event and returns
+//! [`StrategyAction`]s; each action reaches the book after a simulated
+//! latency and is applied before any recorded event with a later
+//! timestamp. Recorded flow then trades against resting strategy orders
+//! exactly as it would against any other liquidity.
+//!
+//! Recorded data is read from any iterator of events.
+//! [`CsvMarketData`] decodes the line format documented on
+//! [`MarketDataEvent::from_csv_line`]; other formats (Parquet, ITCH)
+//! plug in by mapping their records to [`MarketDataEvent`].
+
+use super::book::OrderBook;
+use super::clock::Clock;
+use super::error::OrderBookError;
+use super::order_ack::OrderAck;
+use pricelevel::{Id, OrderType, OrderUpdate, Price, Quantity, Side, TimeInForce, TimestampMs};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// One record of historical market data. Timestamps are milliseconds,
+/// the unit of the book's [`Clock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketDataEvent {
+    /// L3: a new order rests (or trades, if it crosses the book).
+    Add {
+        /// Event time in milliseconds
+        timestamp: u64,
+        /// Venue order id
+        order_id: Id,
+        /// Order side
+        side: Side,
+        /// Limit price
+        price: u128,
+        /// Order quantity
+        quantity: u64,
+    },
+
+    /// L3: an order is cancelled.
+    Cancel {
+        /// Event time in milliseconds
+        timestamp: u64,
+        /// Venue order id
+        order_id: Id,
+    },
+
+    /// L3: an order changes price and quantity.
+    Modify {
+        /// Event time in milliseconds
+        timestamp: u64,
+        /// Venue order id
+        order_id: Id,
+        /// New limit price
+        price: u128,
+        /// New order quantity
+        quantity: u64,
+    },
+
+    /// L3: part of a resting order executed against flow that is not in
+    /// the recording. The order's quantity is reduced, and the order is
+    /// removed once nothing is left.
+    Execute {
+        /// Event time in milliseconds
+        timestamp: u64,
+        /// Venue order id of the resting order
+        order_id: Id,
+        /// Executed quantity
+        quantity: u64,
+    },
+
+    /// L2: the aggregated size of a price level; `0` removes the level.
+    Level {
+        /// Event time in milliseconds
+        timestamp: u64,
+        /// Side of the level
+        side: Side,
+        /// Price of the level
+        price: u128,
+        /// Total size at the level
+        quantity: u64,
+    },
+}
+
+impl MarketDataEvent {
+    /// Event time in milliseconds.
+    #[must_use]
+    pub fn timestamp(&self) -> u64 {
+        match *self {
+            Self::Add { timestamp, .. }
+            | Self::Cancel { timestamp, .. }
+            | Self::Modify { timestamp, .. }
+            | Self::Execute { timestamp, .. }
+            | Self::Level { timestamp, .. } => timestamp,
+        }
+    }
+
+    /// Parse one line of recorded CSV data.
+    ///
+    /// The columns are `timestamp,kind,side,price,quantity,order_id`, with
+    /// `kind` one of `add`, `cancel`, `modify`, `execute` or `level`.
+    /// Columns an event does not use may be left empty: `cancel` needs
+    /// only the order id, `execute` the quantity and order id, `level`
+    /// has no order id. Blank lines, `#` comments and a header row whose
+    /// first column is `timestamp` yield `Ok(None)`.
+    ///
+    /// # Errors
+    /// [`MarketReplayError::Parse`] naming `line_number` when a column is
+    /// missing or malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::MarketDataEvent;
+    /// use pricelevel::{Id, Side};
+    ///
+    /// let event = MarketDataEvent::from_csv_line("1000,add,buy,100,5,42", 1).unwrap();
+    /// assert_eq!(
+    ///     event,
+    ///     Some(MarketDataEvent::Add {
+    ///         timestamp: 1000,
+    ///         order_id: Id::sequential(42),
+    ///         side: Side::Buy,
+    ///         price: 100,
+    ///         quantity: 5,
+    ///     })
+    /// );
+    /// ```
+    pub fn from_csv_line(
+        line: &str,
+        line_number: usize,
+    ) -> Result<Option<Self>, MarketReplayError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+        if columns[0].eq_ignore_ascii_case("timestamp") {
+            return Ok(None);
+        }
+        let column = |index: usize, name: &str| -> Result<&str, MarketReplayError> {
+            match columns.get(index) {
+                Some(value) if !value.is_empty() => Ok(value),
+                _ => Err(MarketReplayError::Parse {
+                    line: line_number,
+                    message: format!("missing {name}"),
+                }),
+            }
+        };
+        let parse_error = |name: &str, value: &str| MarketReplayError::Parse {
+            line: line_number,
+            message: format!("invalid {name} '{value}'"),
+        };
+
+        let timestamp = column(0, "timestamp")?;
+        let timestamp = timestamp
+            .parse()
+            .map_err(|_| parse_error("timestamp", timestamp))?;
+        let side = || -> Result<Side, MarketReplayError> {
+            let value = column(2, "side")?;
+            value.parse().map_err(|_| parse_error("side", value))
+        };
+        let price = || -> Result<u128, MarketReplayError> {
+            let value = column(3, "price")?;
+            value.parse().map_err(|_| parse_error("price", value))
+        };
+        let quantity = || -> Result<u64, MarketReplayError> {
+            let value = column(4, "quantity")?;
+            value.parse().map_err(|_| parse_error("quantity", value))
+        };
+        let order_id = || -> Result<Id, MarketReplayError> {
+            let value = column(5, "order id")?;
+            value.parse().map_err(|_| parse_error("order id", value))
+        };
+
+        let kind = column(1, "kind")?;
+        let event = match kind.to_ascii_lowercase().as_str() {
+            "add" => Self::Add {
+                timestamp,
+                order_id: order_id()?,
+                side: side()?,
+                price: price()?,
+                quantity: quantity()?,
+            },
+            "cancel" => Self::Cancel {
+                timestamp,
+                order_id: order_id()?,
+            },
+            "modify" => Self::Modify {
+                timestamp,
+                order_id: order_id()?,
+                price: price()?,
+                quantity: quantity()?,
+            },
+            "execute" => Self::Execute {
+                timestamp,
+                order_id: order_id()?,
+                quantity: quantity()?,
+            },
+            "level" => Self::Level {
+                timestamp,
+                side: side()?,
+                price: price()?,
+                quantity: quantity()?,
+            },
+            _ => return Err(parse_error("kind", kind)),
+        };
+        Ok(Some(event))
+    }
+}
+
+/// Iterator of [`MarketDataEvent`]s decoded from CSV lines (see
+/// [`MarketDataEvent::from_csv_line`]).
+#[derive(Debug)]
+pub struct CsvMarketData<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> CsvMarketData<R> {
+    /// Decode events from `reader`, one per line.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CsvMarketData<R> {
+    type Item = Result<MarketDataEvent, MarketReplayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => {
+                    return Some(Err(MarketReplayError::Io {
+                        message: error.to_string(),
+                    }));
+                }
+            }
+            self.line_number += 1;
+            match MarketDataEvent::from_csv_line(&self.line, self.line_number) {
+                Ok(None) => continue,
+                Ok(Some(event)) => return Some(Ok(event)),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+/// Clock that reports the replay's current recorded time.
+///
+/// Unlike [`StubClock`](crate::StubClock) it does not advance on reads;
+/// [`MarketReplay`] moves it forward to each event's timestamp.
+#[derive(Debug, Default)]
+pub struct ReplayClock {
+    now: AtomicU64,
+}
+
+impl ReplayClock {
+    /// Create a clock reading `start` milliseconds.
+    #[must_use]
+    pub fn new(start: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start),
+        }
+    }
+
+    /// Move the clock to `timestamp`; earlier timestamps are ignored so
+    /// the clock never runs backwards.
+    pub fn advance_to(&self, timestamp: u64) {
+        self.now.fetch_max(timestamp, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ReplayClock {
+    #[inline]
+    fn now_millis(&self) -> TimestampMs {
+        TimestampMs::new(self.now.load(Ordering::Relaxed))
+    }
+}
+
+/// A strategy decision, applied to the book after the replay latency.
+#[derive(Debug, Clone)]
+pub enum StrategyAction<T> {
+    /// Submit an order.
+    Submit(OrderType<T>),
+    /// Cancel a resting order.
+    Cancel(Id),
+}
+
+/// Result of a [`StrategyAction`] once it reached the book.
+#[derive(Debug, Clone)]
+pub enum StrategyOutcome {
+    /// Result of a [`StrategyAction::Submit`].
+    Submitted {
+        /// Time the order reached the book, in milliseconds
+        arrival: u64,
+        /// Id of the submitted order
+        order_id: Id,
+        /// Fills and resting state, or the reject
+        result: Result<OrderAck, OrderBookError>,
+    },
+    /// Result of a [`StrategyAction::Cancel`].
+    Cancelled {
+        /// Time the cancel reached the book, in milliseconds
+        arrival: u64,
+        /// Id of the order to cancel
+        order_id: Id,
+        /// `Ok(true)` if the order was resting and is now cancelled
+        result: Result<bool, OrderBookError>,
+    },
+}
+
+/// Counters accumulated over a replay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Recorded events applied to the book
+    pub events_applied: u64,
+    /// Recorded events the book rejected, typically because strategy
+    /// flow already consumed the order they refer to
+    pub events_rejected: u64,
+    /// Strategy actions that reached the book
+    pub strategy_actions: u64,
+    /// Recorded time of the last event or action, in milliseconds
+    pub last_timestamp: u64,
+}
+
+/// Errors that stop a [`MarketReplay`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum MarketReplayError {
+    /// Reading the recorded data failed.
+    #[error("failed to read market data: {message}")]
+    Io {
+        /// The underlying I/O error message
+        message: String,
+    },
+
+    /// A record could not be decoded.
+    #[error("invalid market data at line {line}: {message}")]
+    Parse {
+        /// One-based line number of the record
+        line: usize,
+        /// What was wrong with it
+        message: String,
+    },
+
+    /// An event is timestamped before the replay's current time.
+    #[error("market data out of order: event at {timestamp} after {current}")]
+    OutOfOrder {
+        /// Timestamp of the offending event
+        timestamp: u64,
+        /// Replay time when it was read
+        current: u64,
+    },
+}
+
+/// Drives an [`OrderBook`] through recorded market data with strategy
+/// orders interleaved after a fixed latency.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{MarketDataEvent, MarketReplay, OrderBook, StrategyAction};
+/// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+///
+/// let mut replay = MarketReplay::new(OrderBook::<()>::new("BTC/USD"), 5);
+/// let events = [
+///     MarketDataEvent::Level { timestamp: 0, side: Side::Sell, price: 101, quantity: 10 },
+///     MarketDataEvent::Level { timestamp: 10, side: Side::Sell, price: 101, quantity: 4 },
+/// ];
+///
+/// let mut sent = false;
+/// let report = replay
+///     .run(events.into_iter().map(Ok), |_book, _event| {
+///         if std::mem::replace(&mut sent, true) {
+///             return Vec::new();
+///         }
+///         vec![StrategyAction::Submit(OrderType::Standard {
+///             id: Id::new(),
+///             price: Price::new(101),
+///             quantity: Quantity::new(3),
+///             side: Side::Buy,
+///             user_id: Hash32::zero(),
+///             timestamp: TimestampMs::new(0),
+///             time_in_force: TimeInForce::Ioc,
+///             extra_fields: (),
+///         })]
+///     })
+///     .unwrap();
+///
+/// // The buy decided at t=0 arrives at t=5, before the t=10 update.
+/// assert_eq!(report.strategy_actions, 1);
+/// assert_eq!(replay.book().best_ask(), Some(101));
+/// assert_eq!(replay.book().total_quantity_at_price(101, Side::Sell), Some(4));
+/// ```
+pub struct MarketReplay<T = ()> {
+    book: OrderBook<T>,
+    clock: Arc<ReplayClock>,
+    latency: u64,
+    /// Scheduled actions with their arrival time. The clock never runs
+    /// backwards and the latency is fixed, so arrivals are non-decreasing.
+    pending: VecDeque<(u64, StrategyAction<T>)>,
+    /// Synthetic order standing for each L2 level, bids then asks
+    level_orders: [HashMap<u128, Id>; 2],
+    next_level_order: u64,
+    outcomes: Vec<StrategyOutcome>,
+    report: ReplayReport,
+}
+
+impl<T> MarketReplay<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Take over `book`, installing a [`ReplayClock`], and delay every
+    /// strategy action by `latency` milliseconds.
+    pub fn new(mut book: OrderBook<T>, latency: u64) -> Self {
+        let clock = Arc::new(ReplayClock::default());
+        book.set_clock(clock.clone());
+        Self {
+            book,
+            clock,
+            latency,
+            pending: VecDeque::new(),
+            level_orders: [HashMap::new(), HashMap::new()],
+            next_level_order: 0,
+            outcomes: Vec::new(),
+            report: ReplayReport::default(),
+        }
+    }
+
+    /// The replayed book.
+    #[must_use]
+    pub fn book(&self) -> &OrderBook<T> {
+        &self.book
+    }
+
+    /// Give back the replayed book. It keeps the replay clock.
+    #[must_use]
+    pub fn into_book(self) -> OrderBook<T> {
+        self.book
+    }
+
+    /// Current replay time in milliseconds.
+    #[must_use]
+    pub fn now(&self) -> u64 {
+        self.clock.now_millis().as_u64()
+    }
+
+    /// Counters accumulated so far.
+    #[must_use]
+    pub fn report(&self) -> ReplayReport {
+        self.report
+    }
+
+    /// Remove and return the outcomes of strategy actions applied so far,
+    /// in arrival order.
+    pub fn take_outcomes(&mut self) -> Vec<StrategyOutcome> {
+        std::mem::take(&mut self.outcomes)
+    }
+
+    /// Queue `action`, decided now, to reach the book after the latency.
+    /// Actions with the same arrival time apply in scheduling order.
+    pub fn schedule(&mut self, action: StrategyAction<T>) {
+        let arrival = self.now().saturating_add(self.latency);
+        self.pending.push_back((arrival, action));
+    }
+
+    /// Apply one recorded event: first every strategy action arriving at
+    /// or before its timestamp, then the event itself.
+    ///
+    /// # Errors
+    /// [`MarketReplayError::OutOfOrder`] when the event is older than the
+    /// replay time.
+    pub fn step(&mut self, event: &MarketDataEvent) -> Result<(), MarketReplayError> {
+        let timestamp = event.timestamp();
+        let current = self.now();
+        if timestamp < current {
+            return Err(MarketReplayError::OutOfOrder { timestamp, current });
+        }
+        self.release_until(timestamp);
+        self.clock.advance_to(timestamp);
+        self.report.last_timestamp = timestamp;
+
+        if self.apply(event).is_ok() {
+            self.report.events_applied += 1;
+        } else {
+            self.report.events_rejected += 1;
+        }
+        Ok(())
+    }
+
+    /// Apply every strategy action still pending, advancing the clock to
+    /// each arrival time.
+    pub fn flush(&mut self) {
+        self.release_until(u64::MAX);
+    }
+
+    /// Replay `source` to the end. After each recorded event `strategy`
+    /// sees the book and the event and returns the actions to schedule.
+    /// Actions still pending when the source ends are flushed.
+    ///
+    /// # Errors
+    /// The first error yielded by `source`, or
+    /// [`MarketReplayError::OutOfOrder`]. Events before the error have
+    /// been applied.
+    pub fn run<I, F>(
+        &mut self,
+        source: I,
+        mut strategy: F,
+    ) -> Result<ReplayReport, MarketReplayError>
+    where
+        I: IntoIterator<Item = Result<MarketDataEvent, MarketReplayError>>,
+        F: FnMut(&OrderBook<T>, &MarketDataEvent) -> Vec<StrategyAction<T>>,
+    {
+        for event in source {
+            let event = event?;
+            self.step(&event)?;
+            for action in strategy(&self.book, &event) {
+                self.schedule(action);
+            }
+        }
+        self.flush();
+        Ok(self.report)
+    }
+
+    /// Apply pending strategy actions arriving at or before `until`.
+    fn release_until(&mut self, until: u64) {
+        while let Some((arrival, _)) = self.pending.front() {
+            if *arrival > until {
+                break;
+            }
+            let Some((arrival, action)) = self.pending.pop_front() else {
+                break;
+            };
+            self.clock.advance_to(arrival);
+            self.report.last_timestamp = self.report.last_timestamp.max(arrival);
+            self.report.strategy_actions += 1;
+            let outcome = match action {
+                StrategyAction::Submit(order) => StrategyOutcome::Submitted {
+                    arrival,
+                    order_id: order.id(),
+                    result: self.book.add_order_with_ack(order),
+                },
+                StrategyAction::Cancel(order_id) => StrategyOutcome::Cancelled {
+                    arrival,
+                    order_id,
+                    result: self
+                        .book
+                        .cancel_order(order_id)
+                        .map(|cancelled| cancelled.is_some()),
+                },
+            };
+            self.outcomes.push(outcome);
+        }
+    }
+
+    fn apply(&mut self, event: &MarketDataEvent) -> Result<(), OrderBookError> {
+        match *event {
+            MarketDataEvent::Add {
+                order_id,
+                side,
+                price,
+                quantity,
+                ..
+            } => self
+                .book
+                .add_limit_order(order_id, price, quantity, side, TimeInForce::Gtc, None)
+                .map(drop),
+            MarketDataEvent::Cancel { order_id, .. } => self.cancel(order_id),
+            MarketDataEvent::Modify {
+                order_id,
+                price,
+                quantity,
+                ..
+            } => self
+                .book
+                .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                    order_id,
+                    new_price: Price::new(price),
+                    new_quantity: Quantity::new(quantity),
+                })
+                .map(drop),
+            MarketDataEvent::Execute {
+                order_id, quantity, ..
+            } => {
+                let Some(order) = self.book.get_order(order_id) else {
+                    return Err(OrderBookError::OrderNotFound(order_id.to_string()));
+                };
+                let remaining =
+                    order.visible_quantity().as_u64() + order.hidden_quantity().as_u64();
+                if quantity >= remaining {
+                    self.cancel(order_id)
+                } else {
+                    self.book
+                        .update_order(OrderUpdate::UpdateQuantity {
+                            order_id,
+                            new_quantity: Quantity::new(remaining - quantity),
+                        })
+                        .map(drop)
+                }
+            }
+            MarketDataEvent::Level {
+                side,
+                price,
+                quantity,
+                ..
+            } => self.apply_level(side, price, quantity),
+        }
+    }
+
+    /// Move the synthetic order standing for an L2 level to `quantity`.
+    fn apply_level(
+        &mut self,
+        side: Side,
+        price: u128,
+        quantity: u64,
+    ) -> Result<(), OrderBookError> {
+        // Strategy flow may have consumed the level's order entirely.
+        let existing = self.level_orders[side_index(side)]
+            .get(&price)
+            .copied()
+            .filter(|order_id| self.book.get_order(*order_id).is_some());
+        if existing.is_none() {
+            self.level_orders[side_index(side)].remove(&price);
+        }
+        match existing {
+            Some(order_id) if quantity == 0 => {
+                self.level_orders[side_index(side)].remove(&price);
+                self.cancel(order_id)
+            }
+            Some(order_id) => self
+                .book
+                .update_order(OrderUpdate::UpdateQuantity {
+                    order_id,
+                    new_quantity: Quantity::new(quantity),
+                })
+                .map(drop),
+            None if quantity == 0 => Ok(()),
+            None => {
+                let order_id = Id::from_uuid(Uuid::new_v5(
+                    &Uuid::NAMESPACE_OID,
+                    &self.next_level_order.to_be_bytes(),
+                ));
+                self.next_level_order += 1;
+                self.book.add_limit_order(
+                    order_id,
+                    price,
+                    quantity,
+                    side,
+                    TimeInForce::Gtc,
+                    None,
+                )?;
+                if self.book.get_order(order_id).is_some() {
+                    self.level_orders[side_index(side)].insert(price, order_id);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn cancel(&self, order_id: Id) -> Result<(), OrderBookError> {
+        match self.book.cancel_order(order_id)? {
+            Some(_) => Ok(()),
+            None => Err(OrderBookError::OrderNotFound(order_id.to_string())),
+        }
+    }
+}
+
+fn side_index(side: Side) -> usize {
+    match side {
+        Side::Buy => 0,
+        Side::Sell => 1,
+    }
+}
@@ -0,0 +1,118 @@
+//! Notifications for iceberg and reserve replenishment.
+//!
+//! When a sweep consumes the displayed clip of an iceberg order, or takes
+//! an auto-replenishing reserve order below its threshold, the price
+//! level draws a new clip from the order's hidden quantity and moves it
+//! to the back of the queue. Trades and level changes alone do not show
+//! this, so the book reports it as a [`ReplenishmentEvent`] to the
+//! listener installed with [`OrderBook::set_replenishment_listener`] and
+//! counts it in [`BookStats::replenishments`](super::BookStats::replenishments).
+//!
+//! One event is emitted per order and swept price level, after the
+//! level's trades: an order replenished several times by the same sweep
+//! reports its state once, with the total drawn. An order whose last
+//! clip is drawn and filled within the same sweep leaves the book with a
+//! fill and no replenishment event.
+
+use super::book::OrderBook;
+use pricelevel::{Id, MatchResult, PriceLevel, Side};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// An iceberg or reserve order refreshed its displayed quantity from its
+/// hidden quantity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplenishmentEvent {
+    /// Symbol of the book
+    pub symbol: String,
+    /// The replenished order
+    pub order_id: Id,
+    /// Side the order rests on
+    pub side: Side,
+    /// Price the order rests at
+    pub price: u128,
+    /// Displayed quantity after the replenishment
+    pub visible_quantity: u64,
+    /// Hidden quantity left after the replenishment
+    pub hidden_quantity: u64,
+    /// Quantity moved from hidden to displayed by the sweep
+    pub hidden_drawn: u64,
+}
+
+/// Receives [`ReplenishmentEvent`]s.
+pub type ReplenishmentListener = Arc<dyn Fn(&ReplenishmentEvent) + Send + Sync>;
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Install the replenishment listener, replacing any previous one.
+    pub fn set_replenishment_listener(&self, listener: ReplenishmentListener) {
+        self.replenishment_listener.replace(Some(listener));
+    }
+
+    /// Remove the replenishment listener.
+    pub fn remove_replenishment_listener(&self) {
+        self.replenishment_listener.replace(None);
+    }
+
+    /// The installed replenishment listener, if any.
+    #[must_use]
+    pub fn replenishment_listener(&self) -> Option<ReplenishmentListener> {
+        self.replenishment_listener.get()
+    }
+
+    /// Report the orders of `price_level` whose hidden quantity fell below
+    /// the `hidden_before` the sweep recorded, among the makers of
+    /// `level_match`.
+    pub(super) fn report_replenishments(
+        &self,
+        level_match: &MatchResult,
+        price_level: &PriceLevel,
+        side: Side,
+        hidden_before: &[(Id, u64)],
+    ) {
+        let trades = level_match.trades().as_vec();
+        let traded = |id: &Id| trades.iter().any(|trade| trade.maker_order_id() == *id);
+        if !hidden_before.iter().any(|(id, _)| traded(id)) {
+            return;
+        }
+        let listener = self.replenishment_listener.get();
+        for order in price_level.iter_orders() {
+            let Some(&(_, before)) = hidden_before.iter().find(|(id, _)| *id == order.id()) else {
+                continue;
+            };
+            let hidden_quantity = order.hidden_quantity().as_u64();
+            if hidden_quantity >= before {
+                continue;
+            }
+            self.counters.record_replenishment();
+            if let Some(listener) = &listener {
+                listener(&ReplenishmentEvent {
+                    symbol: self.symbol().to_string(),
+                    order_id: order.id(),
+                    side,
+                    price: price_level.price(),
+                    visible_quantity: order.visible_quantity().as_u64(),
+                    hidden_quantity,
+                    hidden_drawn: before - hidden_quantity,
+                });
+            }
+        }
+    }
+}
+
+/// Orders of `price_level` with hidden quantity, and that quantity.
+/// Empty, without allocating, on a level with no hidden depth.
+pub(super) fn hidden_tranches(price_level: &PriceLevel) -> Vec<(Id, u64)> {
+    if price_level.hidden_quantity() == 0 {
+        return Vec::new();
+    }
+    price_level
+        .iter_orders()
+        .filter_map(|order| {
+            let hidden = order.hidden_quantity().as_u64();
+            (hidden > 0).then(|| (order.id(), hidden))
+        })
+        .collect()
+}
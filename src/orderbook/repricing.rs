@@ -20,12 +20,35 @@
 //! // Trigger re-pricing after market changes
 //! book.reprice_special_orders();
 //! ```
+//!
+//! Pegged orders can instead follow the book on their own: with
+//! [`OrderBook::set_auto_reprice_pegged`](crate::OrderBook::set_auto_reprice_pegged)
+//! enabled, every mutating call that moves the best bid, best ask or last
+//! trade re-prices the pegged orders before it returns, in one coalesced
+//! pass.
+//!
+//! [`OrderBook::set_peg_last_trade_source`](crate::OrderBook::set_peg_last_trade_source)
+//! points `LastTrade` pegs at an externally supplied index or mark price
+//! instead of the book's own last trade.
 
 use crate::orderbook::error::OrderBookError;
+use crate::orderbook::reference_price::ExternalPriceSource;
+use crossbeam::atomic::AtomicCell;
 use dashmap::DashSet;
 use pricelevel::{Id, OrderType, PegReferenceType, Side};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::trace;
 
+/// Reference prices a pegged order can track, as seen by one automatic
+/// re-price pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PegReference {
+    pub(crate) best_bid: Option<u128>,
+    pub(crate) best_ask: Option<u128>,
+    pub(crate) last_trade: Option<u128>,
+}
+
 /// Tracks special orders that require re-pricing
 #[derive(Debug, Default)]
 pub struct SpecialOrderTracker {
@@ -33,6 +56,13 @@ pub struct SpecialOrderTracker {
     pegged_orders: DashSet<Id>,
     /// Order IDs of trailing stop orders that need re-pricing when market moves
     trailing_stop_orders: DashSet<Id>,
+    /// Whether pegged orders are re-priced automatically on book changes
+    auto_reprice: AtomicBool,
+    /// References the last automatic pass left the book at; the lock also
+    /// keeps concurrent passes from running at once
+    last_reference: Mutex<Option<PegReference>>,
+    /// External price `LastTrade` pegs track instead of the last trade
+    last_trade_source: AtomicCell<Option<ExternalPriceSource>>,
 }
 
 impl SpecialOrderTracker {
@@ -41,9 +71,56 @@ impl SpecialOrderTracker {
         Self {
             pegged_orders: DashSet::new(),
             trailing_stop_orders: DashSet::new(),
+            auto_reprice: AtomicBool::new(false),
+            last_reference: Mutex::new(None),
+            last_trade_source: AtomicCell::new(None),
+        }
+    }
+
+    /// Enables or disables automatic re-pricing of pegged orders
+    pub fn set_auto_reprice(&self, enabled: bool) {
+        self.auto_reprice.store(enabled, Ordering::Release);
+        if let Ok(mut last) = self.last_reference.lock() {
+            *last = None;
         }
     }
 
+    /// Returns whether pegged orders are re-priced automatically
+    pub fn auto_reprice(&self) -> bool {
+        self.auto_reprice.load(Ordering::Acquire)
+    }
+
+    /// Sets the external price `LastTrade` pegs track; `None` restores
+    /// the book's last trade
+    pub fn set_last_trade_source(&self, source: Option<ExternalPriceSource>) {
+        self.last_trade_source.store(source);
+    }
+
+    /// Returns the external price `LastTrade` pegs track, if any
+    pub fn last_trade_source(&self) -> Option<ExternalPriceSource> {
+        self.last_trade_source.load()
+    }
+
+    /// Runs `reprice` unless `reference` is what the previous automatic
+    /// pass left the book at, or another pass is already running (it will
+    /// see this change or leave it for the next one). `reprice` returns
+    /// the references after the pass, so the pegged orders' own moves do
+    /// not schedule another pass. Returns whether `reprice` ran.
+    pub(crate) fn coalesce_auto_reprice(
+        &self,
+        reference: PegReference,
+        reprice: impl FnOnce() -> PegReference,
+    ) -> bool {
+        let Ok(mut last) = self.last_reference.try_lock() else {
+            return false;
+        };
+        if *last == Some(reference) {
+            return false;
+        }
+        *last = Some(reprice());
+        true
+    }
+
     /// Registers a pegged order for tracking
     pub fn register_pegged_order(&self, order_id: Id) {
         self.pegged_orders.insert(order_id);
@@ -500,6 +577,49 @@ mod tests {
         assert_eq!(result, Some((95, 90))); // new stop = 90 + 5 = 95
     }
 
+    /// Feed `path` through the trailing-stop calculation the way the
+    /// re-price pass does, carrying both the stop and the reference
+    /// forward, and return the stop after each tick.
+    fn trail_path(side: Side, stop: u128, trail: u64, reference: u128, path: &[u128]) -> Vec<u128> {
+        let (mut stop, mut reference) = (stop, reference);
+        path.iter()
+            .map(|&market| {
+                if let Some((new_stop, new_reference)) =
+                    calculate_trailing_stop_price(side, stop, trail, reference, market)
+                {
+                    (stop, reference) = (new_stop, new_reference);
+                }
+                stop
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_trailing_stop_sell_zig_zag_ratchets_up_only() {
+        // Rallies lift the stop; pullbacks and retests below the high
+        // leave it where the last high put it.
+        let stops = trail_path(
+            Side::Sell,
+            95,
+            5,
+            100,
+            &[104, 101, 103, 108, 102, 108, 107, 112, 90],
+        );
+        assert_eq!(stops, vec![99, 99, 99, 103, 103, 103, 103, 107, 107]);
+    }
+
+    #[test]
+    fn test_trailing_stop_buy_zig_zag_ratchets_down_only() {
+        let stops = trail_path(
+            Side::Buy,
+            105,
+            5,
+            100,
+            &[96, 99, 97, 92, 98, 92, 93, 88, 120],
+        );
+        assert_eq!(stops, vec![101, 101, 101, 97, 97, 97, 97, 93, 93]);
+    }
+
     #[test]
     fn test_trailing_stop_buy_market_rises() {
         // Buy trailing stop: market rises from 100 to 110
@@ -540,6 +660,29 @@ mod tests {
         assert_eq!(tracker.trailing_stop_count(), 0);
     }
 
+    #[test]
+    fn test_auto_reprice_passes_are_coalesced() {
+        let tracker = SpecialOrderTracker::new();
+        assert!(!tracker.auto_reprice());
+        tracker.set_auto_reprice(true);
+        assert!(tracker.auto_reprice());
+
+        let quote = |bid: u128, ask: u128| PegReference {
+            best_bid: Some(bid),
+            best_ask: Some(ask),
+            last_trade: None,
+        };
+        // The pass reports where the pegs left the book; only a change
+        // away from that schedules the next pass.
+        assert!(tracker.coalesce_auto_reprice(quote(100, 105), || quote(101, 105)));
+        assert!(!tracker.coalesce_auto_reprice(quote(101, 105), || unreachable!()));
+        assert!(tracker.coalesce_auto_reprice(quote(100, 105), || quote(100, 105)));
+
+        // Re-enabling forgets the last pass.
+        tracker.set_auto_reprice(true);
+        assert!(tracker.coalesce_auto_reprice(quote(100, 105), || quote(100, 105)));
+    }
+
     /// Computes the deterministic order the tracker is expected to return:
     /// sorted by the `Display`/`to_string` key (same key the tracker uses).
     fn expected_sorted(ids: &[Id]) -> Vec<Id> {
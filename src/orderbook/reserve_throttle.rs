@@ -0,0 +1,165 @@
+//! Per-level fill throttling of reserve orders.
+//!
+//! Some venues cap the exposure one counterparty can take against a
+//! single reserve order at a level. With
+//! [`BookConfig::reserve_fill_limit`](crate::BookConfig::reserve_fill_limit)
+//! set, an incoming order fills at most that much from any one reserve
+//! order; once the allowance is used up the reserve is rotated behind the
+//! rest of its level and the taker moves on to the next queue member. An
+//! incoming order that has exhausted every reserve order at a level, with
+//! nothing else resting there, walks on to the next price.
+//!
+//! `pricelevel` matches a level in one pass, so a throttled level is
+//! matched in chunks: each chunk ends at the first reserve order in the
+//! queue, sized so that reserve receives no more than its remaining
+//! allowance. Rotation takes the reserve out of the level and back in,
+//! which no concurrent operation may observe, so a book with a reserve
+//! fill limit serializes every submit, modify and cancel behind the
+//! exclusive submit gate.
+
+use super::book::OrderBook;
+use pricelevel::{
+    Id, MatchResult, OrderType, OrderUpdate, PriceLevel, Quantity, TakerKind, TimeInForce,
+    TimestampMs,
+};
+use std::collections::HashMap;
+use tracing::error;
+
+/// Whether `order` is a reserve order, the kind the fill limit throttles.
+#[inline]
+pub(super) fn is_reserve(order: &OrderType<()>) -> bool {
+    matches!(order, OrderType::ReserveOrder { .. })
+}
+
+/// The part of `depth`, the matchable depth of `order`, one incoming
+/// order can reach under a fill limit of `limit`.
+#[inline]
+pub(super) fn throttled_depth(order: &OrderType<()>, depth: u64, limit: u64) -> u64 {
+    if is_reserve(order) {
+        depth.min(limit)
+    } else {
+        depth
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Match up to `quantity` against `level`, taking at most
+    /// `reserve_fill_limit` from each reserve order. Without a limit, or
+    /// when the level holds no reserve order, this is a plain
+    /// [`PriceLevel::match_order`].
+    ///
+    /// Must run under the exclusive submit gate whenever a limit is set.
+    pub(super) fn match_level(
+        &self,
+        level: &PriceLevel,
+        quantity: u64,
+        order_id: Id,
+        taker_kind: TakerKind,
+        taker_ts: TimestampMs,
+        reserve_fill_limit: Option<u64>,
+    ) -> MatchResult {
+        let match_once = |quantity: u64| {
+            level.match_order(
+                quantity,
+                order_id,
+                TimeInForce::Gtc,
+                taker_kind,
+                taker_ts,
+                &self.transaction_id_generator,
+            )
+        };
+        let Some(limit) = reserve_fill_limit else {
+            return match_once(quantity);
+        };
+        if !level.iter_orders().any(|order| is_reserve(&order)) {
+            return match_once(quantity);
+        }
+
+        let mut merged = MatchResult::new(order_id, Quantity::new(quantity));
+        let mut taken: HashMap<Id, u64> = HashMap::new();
+        let mut remaining = quantity;
+        // Rotations that executed nothing; bounded by the level's size so
+        // a level of exhausted reserves cannot spin.
+        let mut idle_rotations = 0usize;
+        while remaining > 0 {
+            let queue = level.snapshot_by_insertion_seq();
+            if queue.iter().all(|order| {
+                is_reserve(order) && taken.get(&order.id()).is_some_and(|&qty| qty >= limit)
+            }) {
+                break;
+            }
+
+            // Walk the queue up to and including its first reserve order.
+            let mut chunk = 0u64;
+            let mut throttled = None;
+            for order in &queue {
+                let visible = order.visible_quantity().as_u64();
+                if is_reserve(order) {
+                    let allowance =
+                        limit.saturating_sub(taken.get(&order.id()).copied().unwrap_or(0));
+                    chunk = chunk.saturating_add(visible.min(allowance));
+                    if allowance <= visible {
+                        throttled = Some(order.id());
+                    }
+                    break;
+                }
+                chunk = chunk.saturating_add(visible);
+            }
+            let chunk = chunk.min(remaining);
+
+            let mut executed = 0;
+            if chunk > 0 {
+                let result = match_once(chunk);
+                executed = chunk.saturating_sub(result.remaining_quantity().as_u64());
+                for trade in result.trades().as_vec() {
+                    *taken.entry(trade.maker_order_id()).or_insert(0) += trade.quantity().as_u64();
+                    // The merged result is sized for the whole request, so
+                    // the chunk's trades always fit.
+                    let _ = merged.add_trade(*trade);
+                }
+                for filled in result.filled_order_ids() {
+                    merged.add_filled_order_id(*filled);
+                }
+                remaining -= executed;
+            }
+
+            match throttled {
+                Some(reserve_id)
+                    if remaining > 0 && taken.get(&reserve_id).copied().unwrap_or(0) >= limit =>
+                {
+                    self.rotate_reserve(level, reserve_id);
+                    if executed == 0 {
+                        idle_rotations += 1;
+                        if idle_rotations > queue.len() {
+                            break;
+                        }
+                    } else {
+                        idle_rotations = 0;
+                    }
+                }
+                _ if executed == 0 => break,
+                _ => idle_rotations = 0,
+            }
+        }
+        merged
+    }
+
+    /// Move `order_id` to the back of `level`.
+    fn rotate_reserve(&self, level: &PriceLevel, order_id: Id) {
+        if let Ok(Some(removed)) = level.update_order(OrderUpdate::Cancel { order_id }) {
+            // Headroom was freed by the removal, so this cannot fail short
+            // of a bug; never lose the order silently.
+            if let Err(err) = level.add_order(*removed) {
+                error!(
+                    %order_id,
+                    price = level.price(),
+                    error = %err,
+                    "reserve rotation failed; order dropped from its level"
+                );
+            }
+        }
+    }
+}
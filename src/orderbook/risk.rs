@@ -4,7 +4,8 @@
 //! flow on the order book. It is composed of:
 //!
 //! - [`RiskConfig`] — the operator-supplied limits (per-account open
-//!   orders, per-account notional, price band against a reference price).
+//!   orders, per-account notional, price band against a reference price
+//!   in basis points and / or ticks).
 //! - [`ReferencePriceSource`] — selects the reference price used by the
 //!   price-band check.
 //! - [`RiskState`] — bound to an [`OrderBook`](crate::OrderBook),
@@ -38,9 +39,11 @@ use tracing::warn;
 /// Source for the reference price used by the price-band check.
 ///
 /// The price band rejects orders whose limit price deviates from the
-/// reference by more than the configured number of basis points.
-/// `LastTrade` and `Mid` resolve dynamically per check; `FixedPrice`
-/// is operator-pinned (e.g. an external mark price piped in).
+/// reference by more than the configured number of basis points or
+/// ticks. `LastTrade` and `Mid` resolve dynamically per check;
+/// `FixedPrice` is pinned in the config, and `External` reads the index
+/// price last pushed with `OrderBook::set_reference_price` (or its
+/// shorthand `OrderBook::set_external_reference_price`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum ReferencePriceSource {
@@ -54,6 +57,10 @@ pub enum ReferencePriceSource {
     /// Caller-supplied fixed reference price (raw integer ticks). The
     /// check always runs.
     FixedPrice(u128),
+    /// Externally supplied index price, updated at runtime via
+    /// `OrderBook::set_reference_price`. The check is skipped until a
+    /// price has been supplied, and while the supplied price is stale.
+    External,
 }
 
 /// Per-`OrderBook` risk configuration.
@@ -84,7 +91,7 @@ pub enum ReferencePriceSource {
 /// admission gate, run a `peek_match` simulation in your gateway
 /// layer and pass the resulting resting remainder in. Issue a
 /// follow-up if you want this surfaced from the engine itself.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RiskConfig {
     /// Maximum number of resting orders a single account may have on
     /// this book at any time. `None` disables the check.
@@ -99,6 +106,13 @@ pub struct RiskConfig {
     pub price_band_bps: Option<u32>,
     /// Reference price source used by the price-band check.
     pub reference_price: Option<ReferencePriceSource>,
+    /// Maximum allowed deviation in ticks (multiples of the book's tick
+    /// size, or raw price units when no tick size is set) between an
+    /// incoming limit price and the resolved reference price. Evaluated
+    /// alongside `price_band_bps` against the same reference. `None` (or
+    /// `reference_price = None`) disables the check.
+    #[serde(default)]
+    pub price_band_ticks: Option<u64>,
 }
 
 impl RiskConfig {
@@ -134,6 +148,17 @@ impl RiskConfig {
         self.reference_price = Some(source);
         self
     }
+
+    /// Set the price-band tolerance in ticks and the reference price
+    /// source used to evaluate the band. The source is shared with
+    /// [`Self::with_price_band_bps`]; the last call wins.
+    #[inline]
+    #[must_use]
+    pub fn with_price_band_ticks(mut self, ticks: u64, source: ReferencePriceSource) -> Self {
+        self.price_band_ticks = Some(ticks);
+        self.reference_price = Some(source);
+        self
+    }
 }
 
 /// Per-account counters maintained by [`RiskState`].
@@ -166,12 +191,14 @@ pub(super) struct RiskEntry {
 /// Risk state bound to a single [`OrderBook`](crate::OrderBook).
 ///
 /// Carries the optional [`RiskConfig`], the per-account counters, the
-/// per-order entry map, and a one-shot warning latch for the
-/// "no reference price available" code path. All public operations
-/// are no-ops when `config` is `None`.
+/// per-order entry map, and a one-shot warning latch for the "no reference price available" code
+/// path. All public operations are no-ops when `config` is `None`.
+///
+/// The config sits in an atomic cell so it can be replaced through
+/// `&self` while the owning book is shared.
 #[derive(Debug, Default)]
 pub struct RiskState {
-    pub(super) config: Option<RiskConfig>,
+    pub(super) config: AtomicCell<Option<RiskConfig>>,
     pub(super) counters: DashMap<Hash32, RiskCounters>,
     pub(super) orders: DashMap<Id, RiskEntry>,
     pub(super) warned_no_reference: AtomicBool,
@@ -213,23 +240,23 @@ impl RiskState {
     /// Install or replace the active risk configuration. Counters and
     /// per-order entries are preserved so that history rebuilt from a
     /// previous configuration remains consistent.
-    pub fn set_config(&mut self, cfg: RiskConfig) {
-        self.config = Some(cfg);
+    pub fn set_config(&self, cfg: RiskConfig) {
+        self.config.store(Some(cfg));
         self.warned_no_reference.store(false, Ordering::Relaxed);
     }
 
-    /// Read-only access to the active configuration, if any.
+    /// A copy of the active configuration, if any.
     #[inline]
     #[must_use]
-    pub fn config(&self) -> Option<&RiskConfig> {
-        self.config.as_ref()
+    pub fn config(&self) -> Option<RiskConfig> {
+        self.config.load()
     }
 
     /// Drop the active configuration. Counters and per-order entries
     /// are preserved so a subsequent [`Self::set_config`] re-engages
     /// without dropping history.
-    pub fn disable(&mut self) {
-        self.config = None;
+    pub fn disable(&self) {
+        self.config.store(None);
     }
 
     /// Pre-trade limit-order admission check.
@@ -239,17 +266,23 @@ impl RiskState {
     /// skipped when `reference_price` is `None` (caller resolved no
     /// reference; e.g. empty book and no trades yet).
     ///
+    /// `cfg` is the risk section of the caller's
+    /// [`BookConfig`](crate::BookConfig) snapshot rather than this state's
+    /// copy, so the limits always match the rest of the admission; `None`
+    /// admits everything.
+    ///
     /// Allocation-free on the happy path. Cold rejection allocates one
     /// error variant.
     #[inline]
     pub(super) fn check_limit_admission(
         &self,
+        cfg: Option<&RiskConfig>,
         account: Hash32,
         price: u128,
         quantity: u64,
         reference_price: Option<u128>,
     ) -> Result<(), OrderBookError> {
-        let Some(cfg) = self.config.as_ref() else {
+        let Some(cfg) = cfg else {
             return Ok(());
         };
 
@@ -344,21 +377,74 @@ impl RiskState {
             // Band is configured but no reference is currently
             // available (empty book + no trades). Warn once per book
             // and skip the check.
-            if self
-                .warned_no_reference
-                .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
-                .is_ok()
-            {
-                warn!(
-                    "risk: price-band check configured but no reference price available; \
-                     check skipped until a trade or two-sided book establishes a reference"
-                );
-            }
+            self.warn_no_reference();
         }
 
         Ok(())
     }
 
+    /// Log, once per book, that a configured price band was skipped for
+    /// lack of a reference price.
+    #[cold]
+    fn warn_no_reference(&self) {
+        if self
+            .warned_no_reference
+            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            warn!(
+                "risk: price-band check configured but no reference price available; \
+                 check skipped until a trade, two-sided book or external price \
+                 establishes a reference"
+            );
+        }
+    }
+
+    /// Tick-denominated price-band check, run by the book after
+    /// [`Self::check_limit_admission`] / [`Self::check_modify_admission`]
+    /// with the same resolved reference.
+    ///
+    /// Rejects when `|price - reference|` *strictly* exceeds
+    /// `cfg.price_band_ticks × tick_size`; an order exactly at the limit
+    /// is admitted. Skips (warning once per book) when no reference is
+    /// available, and passes when `cfg` is `None`.
+    ///
+    /// # Errors
+    /// Returns [`OrderBookError::RiskPriceBandTicks`] on a breach.
+    #[inline]
+    pub(super) fn check_tick_band(
+        &self,
+        cfg: Option<&RiskConfig>,
+        price: u128,
+        reference_price: Option<u128>,
+        tick_size: u128,
+    ) -> Result<(), OrderBookError> {
+        let Some(cfg) = cfg else {
+            return Ok(());
+        };
+        let Some(limit_ticks) = cfg.price_band_ticks else {
+            return Ok(());
+        };
+        let Some(reference) = reference_price else {
+            if cfg.reference_price.is_some() {
+                self.warn_no_reference();
+            }
+            return Ok(());
+        };
+
+        let tick_size = tick_size.max(1);
+        let diff = price.abs_diff(reference);
+        if diff > u128::from(limit_ticks).saturating_mul(tick_size) {
+            return Err(OrderBookError::RiskPriceBandTicks {
+                submitted: price,
+                reference,
+                deviation_ticks: diff / tick_size,
+                limit_ticks,
+            });
+        }
+        Ok(())
+    }
+
     /// Pre-trade admission check for an in-place **modify** of a resting
     /// order (`UpdatePrice` / `UpdatePriceAndQuantity` / `Replace`).
     ///
@@ -380,7 +466,7 @@ impl RiskState {
     /// - does **not** check `max_open_orders_per_account` (a modify cannot
     ///   change the resting order count).
     ///
-    /// Returns `Ok(())` when no [`RiskConfig`] is installed.
+    /// Returns `Ok(())` when `cfg` is `None`.
     ///
     /// # Errors
     /// Returns [`OrderBookError::RiskMaxNotional`] or
@@ -390,13 +476,14 @@ impl RiskState {
     #[allow(clippy::too_many_arguments)]
     pub(super) fn check_modify_admission(
         &self,
+        cfg: Option<&RiskConfig>,
         order_id: Id,
         account: Hash32,
         new_price: u128,
         new_qty: u64,
         reference_price: Option<u128>,
     ) -> Result<(), OrderBookError> {
-        let Some(cfg) = self.config.as_ref() else {
+        let Some(cfg) = cfg else {
             return Ok(());
         };
 
@@ -411,7 +498,13 @@ impl RiskState {
         // would then reject — which would destroy the original.
         let old_contribution = {
             let Some(entry) = self.orders.get(&order_id) else {
-                return self.check_limit_admission(account, new_price, new_qty, reference_price);
+                return self.check_limit_admission(
+                    Some(cfg),
+                    account,
+                    new_price,
+                    new_qty,
+                    reference_price,
+                );
             };
             u128::from(entry.remaining_qty).saturating_mul(entry.price)
         };
@@ -473,7 +566,7 @@ impl RiskState {
         price: u128,
         remaining_qty: u64,
     ) {
-        if self.config.is_none() {
+        if self.config.load().is_none() {
             return;
         }
         self.orders.insert(
@@ -518,7 +611,7 @@ impl RiskState {
     /// `u128::MAX` and permanently locking the account out of
     /// admission.
     pub(super) fn on_fill(&self, maker_id: Id, filled_qty: u64, maker_price: u128) {
-        if self.config.is_none() {
+        if self.config.load().is_none() {
             return;
         }
         // Read-modify-write the entry. Use `get_mut` for the partial
@@ -566,7 +659,7 @@ impl RiskState {
     /// admission price. No-op when no `RiskConfig` is installed or the
     /// order is not tracked (e.g. admitted before the config was set).
     pub(super) fn on_quantity_update(&self, order_id: Id, new_remaining: u64) {
-        if self.config.is_none() {
+        if self.config.load().is_none() {
             return;
         }
         let (account, entry_price, old_remaining) = {
@@ -628,7 +721,7 @@ impl RiskState {
     /// Both decrements clamp at zero via saturating CAS — same
     /// rationale as \[`on_fill`\].
     pub(super) fn on_cancel(&self, order_id: Id) {
-        if self.config.is_none() {
+        if self.config.load().is_none() {
             return;
         }
         let Some((_, entry)) = self.orders.remove(&order_id) else {
@@ -682,7 +775,7 @@ mod tests {
         const THREADS: usize = 16;
         const LIMIT: u64 = 4;
 
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(LIMIT));
         let state = Arc::new(state);
         let acct = account(7);
@@ -696,7 +789,10 @@ mod tests {
                 let barrier = Arc::clone(&barrier);
                 thread::spawn(move || {
                     barrier.wait();
-                    if state.check_limit_admission(acct, 100, 1, Some(100)).is_ok() {
+                    if state
+                        .check_limit_admission(state.config().as_ref(), acct, 100, 1, Some(100))
+                        .is_ok()
+                    {
                         state.on_admission(Id::from_u64(i as u64), acct, 100, 1);
                         1u64
                     } else {
@@ -736,7 +832,7 @@ mod tests {
 
         const ORDERS: u64 = 32;
 
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(10_000));
         let acct = account(9);
         // Pre-admit ORDERS resting orders (open_count == ORDERS).
@@ -808,7 +904,7 @@ mod tests {
         // Every check returns Ok.
         assert!(
             state
-                .check_limit_admission(acct, 100, 10, Some(100))
+                .check_limit_admission(state.config().as_ref(), acct, 100, 10, Some(100))
                 .is_ok()
         );
         assert!(state.check_market_admission(acct).is_ok());
@@ -825,7 +921,7 @@ mod tests {
 
     #[test]
     fn test_on_admission_then_on_cancel_round_trip() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new()
                 .with_max_open_orders_per_account(10)
@@ -858,7 +954,7 @@ mod tests {
 
     #[test]
     fn test_on_fill_full_evicts_counters_issue_115() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000_000));
 
         let acct = account(4);
@@ -879,7 +975,7 @@ mod tests {
 
     #[test]
     fn test_admission_fill_cancel_notional_self_balances_issue_115() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000_000));
 
         let acct = account(5);
@@ -935,7 +1031,7 @@ mod tests {
         let b = Id::from_u64(2);
 
         for round in 0..ROUNDS {
-            let mut state = RiskState::new();
+            let state = RiskState::new();
             state.set_config(RiskConfig::new().with_max_open_orders_per_account(10_000));
             // Pre-admit A so the account sits at open_count == 1.
             state.on_admission(a, acct, 100, 1);
@@ -990,7 +1086,7 @@ mod tests {
 
     #[test]
     fn test_on_fill_partial_keeps_open_count() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000_000));
 
         let acct = account(3);
@@ -1019,7 +1115,7 @@ mod tests {
 
     #[test]
     fn test_on_fill_full_decrements_open_count() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(10));
 
         let acct = account(4);
@@ -1045,7 +1141,7 @@ mod tests {
 
     #[test]
     fn test_check_limit_admission_max_open_orders_breach_returns_typed_error() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(2));
 
         let acct = account(5);
@@ -1053,7 +1149,7 @@ mod tests {
         state.on_admission(Id::new_uuid(), acct, 100, 1);
 
         let err = state
-            .check_limit_admission(acct, 100, 1, Some(100))
+            .check_limit_admission(state.config().as_ref(), acct, 100, 1, Some(100))
             .expect_err("third admission must breach max_open_orders");
         match err {
             OrderBookError::RiskMaxOpenOrders {
@@ -1071,7 +1167,7 @@ mod tests {
 
     #[test]
     fn test_check_limit_admission_max_notional_breach_returns_typed_error() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000));
 
         let acct = account(6);
@@ -1080,7 +1176,7 @@ mod tests {
 
         // Attempt to add 300 more (price=100, qty=3).
         let err = state
-            .check_limit_admission(acct, 100, 3, Some(100))
+            .check_limit_admission(state.config().as_ref(), acct, 100, 3, Some(100))
             .expect_err("notional should be exceeded");
         match err {
             OrderBookError::RiskMaxNotional {
@@ -1100,7 +1196,7 @@ mod tests {
 
     #[test]
     fn test_check_limit_admission_price_band_breach_returns_typed_error() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         // 100 bps = 1% band.
         state.set_config(
             RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::LastTrade),
@@ -1109,7 +1205,7 @@ mod tests {
         let acct = account(7);
         // Reference 1_000_000, submitted 1_100_000 → +10_000 bps deviation.
         let err = state
-            .check_limit_admission(acct, 1_100_000, 1, Some(1_000_000))
+            .check_limit_admission(state.config().as_ref(), acct, 1_100_000, 1, Some(1_000_000))
             .expect_err("price band should be exceeded");
         match err {
             OrderBookError::RiskPriceBand {
@@ -1127,9 +1223,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_check_tick_band_rejects_strictly_beyond_limit() {
+        let state = RiskState::new();
+        state.set_config(RiskConfig::new().with_price_band_ticks(5, ReferencePriceSource::Mid));
+
+        // Tick size 10 → band is ±50 raw units around 1_000.
+        assert!(
+            state
+                .check_tick_band(state.config().as_ref(), 1_050, Some(1_000), 10)
+                .is_ok()
+        );
+        assert!(
+            state
+                .check_tick_band(state.config().as_ref(), 950, Some(1_000), 10)
+                .is_ok()
+        );
+        match state.check_tick_band(state.config().as_ref(), 1_060, Some(1_000), 10) {
+            Err(OrderBookError::RiskPriceBandTicks {
+                submitted,
+                reference,
+                deviation_ticks,
+                limit_ticks,
+            }) => {
+                assert_eq!(submitted, 1_060);
+                assert_eq!(reference, 1_000);
+                assert_eq!(deviation_ticks, 6);
+                assert_eq!(limit_ticks, 5);
+            }
+            other => panic!("expected RiskPriceBandTicks, got {other:?}"),
+        }
+
+        // No reference: skipped, warning latched.
+        assert!(
+            state
+                .check_tick_band(state.config().as_ref(), 5_000, None, 10)
+                .is_ok()
+        );
+        assert!(state.warned_no_reference.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn test_check_limit_admission_price_band_fractional_bps_is_rejected() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::LastTrade),
         );
@@ -1138,7 +1274,7 @@ mod tests {
         // Reference 30_000, limit 100 bps → the band edge is exactly 30_300
         // (100 bps = 300 ticks). 30_301 is 100.33 bps: truncating division
         // floored this to 100 and admitted it; cross-multiplication rejects it.
-        match state.check_limit_admission(acct, 30_301, 1, Some(30_000)) {
+        match state.check_limit_admission(state.config().as_ref(), acct, 30_301, 1, Some(30_000)) {
             Err(OrderBookError::RiskPriceBand {
                 deviation_bps,
                 limit_bps,
@@ -1154,7 +1290,7 @@ mod tests {
         // the strict-`>` boundary semantics are preserved.
         assert!(
             state
-                .check_limit_admission(acct, 30_300, 1, Some(30_000))
+                .check_limit_admission(state.config().as_ref(), acct, 30_300, 1, Some(30_000))
                 .is_ok(),
             "exact-limit order must still be admitted"
         );
@@ -1162,34 +1298,38 @@ mod tests {
         // And just inside the band (30_299) is admitted.
         assert!(
             state
-                .check_limit_admission(acct, 30_299, 1, Some(30_000))
+                .check_limit_admission(state.config().as_ref(), acct, 30_299, 1, Some(30_000))
                 .is_ok()
         );
     }
 
     #[test]
     fn test_check_limit_admission_no_reference_price_skips_band_check() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::LastTrade),
         );
         // No reference available. Check skipped → Ok.
         assert!(
             state
-                .check_limit_admission(account(8), 999_999_999, 1, None)
+                .check_limit_admission(state.config().as_ref(), account(8), 999_999_999, 1, None)
                 .is_ok()
         );
     }
 
     #[test]
     fn test_check_limit_admission_warns_only_once_when_no_reference_available() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::LastTrade),
         );
 
         let acct = account(9);
-        assert!(state.check_limit_admission(acct, 1, 1, None).is_ok());
+        assert!(
+            state
+                .check_limit_admission(state.config().as_ref(), acct, 1, 1, None)
+                .is_ok()
+        );
         assert!(
             state.warned_no_reference.load(Ordering::Relaxed),
             "first call without reference should flip the latch"
@@ -1198,13 +1338,17 @@ mod tests {
         // additional warning emitted (we cannot assert log count here
         // without a tracing-subscriber harness, but the latch is the
         // gate on the log site).
-        assert!(state.check_limit_admission(acct, 2, 2, None).is_ok());
+        assert!(
+            state
+                .check_limit_admission(state.config().as_ref(), acct, 2, 2, None)
+                .is_ok()
+        );
         assert!(state.warned_no_reference.load(Ordering::Relaxed));
     }
 
     #[test]
     fn test_within_limits_admission_succeeds() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new()
                 .with_max_open_orders_per_account(10)
@@ -1214,12 +1358,16 @@ mod tests {
 
         let acct = account(10);
         // Reference 100, submitted 100 → 0 bps. All checks pass.
-        assert!(state.check_limit_admission(acct, 100, 5, Some(100)).is_ok());
+        assert!(
+            state
+                .check_limit_admission(state.config().as_ref(), acct, 100, 5, Some(100))
+                .is_ok()
+        );
     }
 
     #[test]
     fn test_disable_keeps_counters() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(10));
 
         let acct = account(11);
@@ -1236,7 +1384,7 @@ mod tests {
         // After disable, every check is a passthrough again.
         assert!(
             state
-                .check_limit_admission(acct, 100, 100, Some(100))
+                .check_limit_admission(state.config().as_ref(), acct, 100, 100, Some(100))
                 .is_ok()
         );
     }
@@ -1246,7 +1394,7 @@ mod tests {
         // Regression: a stray double-fill or filled_qty > remaining
         // must not wrap counters via `fetch_sub`. Both decrements
         // saturate at zero.
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(10_000));
 
         let acct = account(12);
@@ -1271,7 +1419,7 @@ mod tests {
         // Regression: cancel after the entry has already been removed
         // by an on_fill must be a no-op and not under-flow the
         // counters that the prior fill already drove to zero.
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(10));
 
         let acct = account(13);
@@ -1298,7 +1446,14 @@ mod tests {
         let state = RiskState::new();
         assert!(
             state
-                .check_modify_admission(Id::new_uuid(), account(1), 999_999, 999, Some(100))
+                .check_modify_admission(
+                    state.config().as_ref(),
+                    Id::new_uuid(),
+                    account(1),
+                    999_999,
+                    999,
+                    Some(100)
+                )
                 .is_ok()
         );
     }
@@ -1308,7 +1463,7 @@ mod tests {
         // A modify of a TRACKED order must never reject on the open-order
         // count: an account sitting exactly at the limit can still modify a
         // resting order (count is net unchanged).
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let acct = account(20);
         let id = Id::new_uuid();
@@ -1316,7 +1471,7 @@ mod tests {
 
         assert!(
             state
-                .check_modify_admission(id, acct, 110, 10, Some(105))
+                .check_modify_admission(state.config().as_ref(), id, acct, 110, 10, Some(105))
                 .is_ok(),
             "modify of a tracked order must not be gated by max_open_orders_per_account"
         );
@@ -1325,7 +1480,7 @@ mod tests {
     #[test]
     fn test_check_modify_admission_projects_notional_swapping_old_for_new() {
         // Notional ceiling 1_000. Original order contributes 100*8 = 800.
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000));
         let acct = account(21);
         let id = Id::new_uuid();
@@ -1334,13 +1489,13 @@ mod tests {
         // Modify to 100*9 = 900 projects to 800 - 800 + 900 = 900 ≤ 1_000.
         assert!(
             state
-                .check_modify_admission(id, acct, 100, 9, Some(100))
+                .check_modify_admission(state.config().as_ref(), id, acct, 100, 9, Some(100))
                 .is_ok(),
             "projected notional 900 must be within the 1_000 ceiling"
         );
 
         // Modify to 100*11 = 1_100 projects to 800 - 800 + 1_100 = 1_100 > 1_000.
-        match state.check_modify_admission(id, acct, 100, 11, Some(100)) {
+        match state.check_modify_admission(state.config().as_ref(), id, acct, 100, 11, Some(100)) {
             Err(OrderBookError::RiskMaxNotional {
                 account: a,
                 attempted,
@@ -1360,7 +1515,7 @@ mod tests {
         // Regression: the naive limit-admission check would add the new
         // contribution on top of the (still-counted) original and falsely
         // reject. The projection subtracts the original's tracked contribution.
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_notional_per_account(1_000));
         let acct = account(22);
         let id = Id::new_uuid();
@@ -1369,7 +1524,7 @@ mod tests {
         // Re-price to the same notional: 1_000 - 1_000 + 1_000 = 1_000 ≤ 1_000.
         assert!(
             state
-                .check_modify_admission(id, acct, 200, 5, Some(150))
+                .check_modify_admission(state.config().as_ref(), id, acct, 200, 5, Some(150))
                 .is_ok(),
             "an unchanged-notional modify must not double-count the original"
         );
@@ -1377,7 +1532,7 @@ mod tests {
 
     #[test]
     fn test_check_modify_admission_price_band_on_new_price() {
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(
             RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::LastTrade),
         );
@@ -1386,7 +1541,14 @@ mod tests {
         state.on_admission(id, acct, 1_000_000, 1);
 
         // New price 1_100_000 vs reference 1_000_000 → +1_000 bps, far over band.
-        match state.check_modify_admission(id, acct, 1_100_000, 1, Some(1_000_000)) {
+        match state.check_modify_admission(
+            state.config().as_ref(),
+            id,
+            acct,
+            1_100_000,
+            1,
+            Some(1_000_000),
+        ) {
             Err(OrderBookError::RiskPriceBand {
                 submitted,
                 reference,
@@ -1403,7 +1565,14 @@ mod tests {
         // A new price inside the band is admitted.
         assert!(
             state
-                .check_modify_admission(id, acct, 1_005_000, 1, Some(1_000_000))
+                .check_modify_admission(
+                    state.config().as_ref(),
+                    id,
+                    acct,
+                    1_005_000,
+                    1,
+                    Some(1_000_000)
+                )
                 .is_ok()
         );
     }
@@ -1416,7 +1585,7 @@ mod tests {
         // admission applies, INCLUDING the open-order count. This mirrors
         // `add_order`'s post-cancel check so the validate-first guard predicts
         // the post-cancel verdict and never destroys the original.
-        let mut state = RiskState::new();
+        let state = RiskState::new();
         state.set_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let acct = account(24);
         // One OTHER tracked resting order already at the limit.
@@ -1425,7 +1594,14 @@ mod tests {
         // The order being modified is NOT tracked → full admission → rejected
         // on the open-order count (would be a 2nd order for the account).
         let untracked = Id::new_uuid();
-        match state.check_modify_admission(untracked, acct, 110, 5, Some(105)) {
+        match state.check_modify_admission(
+            state.config().as_ref(),
+            untracked,
+            acct,
+            110,
+            5,
+            Some(105),
+        ) {
             Err(OrderBookError::RiskMaxOpenOrders { .. }) => {}
             other => panic!(
                 "untracked modify must run full admission and reject on open count, got {other:?}"
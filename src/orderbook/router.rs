@@ -0,0 +1,458 @@
+//! Smart order routing simulation across venues.
+//!
+//! A [`SmartOrderRouter`] splits a parent market order across the venues of
+//! a [`ConsolidatedBook`] according to a [`RoutingStrategy`]:
+//!
+//! - [`RoutingStrategy::SweepBestPrice`] walks the consolidated ladder best
+//!   price first, taking every venue quoting a price before moving on;
+//! - [`RoutingStrategy::ProportionalToDisplayed`] splits the parent pro rata
+//!   to each venue's displayed depth;
+//! - [`RoutingStrategy::MinimizeImpact`] hands the parent out in slices, each
+//!   to the venue whose [`OrderBook::market_impact`] would stay lowest.
+//!
+//! [`SmartOrderRouter::route`] only plans: every child comes with the fills
+//! [`OrderBook::simulate_market_order`] predicts for it and no book is
+//! touched. [`SmartOrderRouter::execute`] plans, then submits every child as
+//! a market order and reports what each venue actually filled.
+
+use super::book::OrderBook;
+use super::consolidated::ConsolidatedBook;
+use super::error::OrderBookError;
+use super::market_impact::OrderSimulation;
+use pricelevel::{Id, Side};
+use serde::{Deserialize, Serialize};
+
+/// How a [`SmartOrderRouter`] splits a parent order across venues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoutingStrategy {
+    /// Take the best consolidated price first, across every venue quoting
+    /// it, then the next price, until the parent is allocated.
+    SweepBestPrice,
+    /// Split the parent in proportion to each venue's displayed quantity on
+    /// the side being hit. A venue is never given more than its total
+    /// depth; a share cut by that cap is left unallocated.
+    ProportionalToDisplayed,
+    /// Allocate the parent in `slices` equal slices, each to the venue whose
+    /// [`MarketImpact::slippage_bps`](super::MarketImpact::slippage_bps)
+    /// would be lowest after taking it, ties broken by average price.
+    /// Spreads the footprint so no single book is pushed far from its
+    /// touch, even at the cost of a worse price than a pure sweep.
+    MinimizeImpact {
+        /// Number of slices the parent is cut into; `0` is treated as `1`
+        slices: u32,
+    },
+}
+
+/// One child order of a [`RoutingPlan`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChildOrder {
+    /// Venue the child is sent to
+    pub venue: String,
+    /// Child quantity (in units)
+    pub quantity: u64,
+    /// Fills the venue's book predicts for the child right now
+    pub simulation: OrderSimulation,
+}
+
+/// Split of a parent order across venues, before execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingPlan {
+    /// Side of the parent order
+    pub side: Side,
+    /// Parent quantity (in units)
+    pub quantity: u64,
+    /// Children, in the order the venues were added to the view
+    pub children: Vec<ChildOrder>,
+    /// Quantity no venue had the depth for (in units)
+    pub unallocated: u64,
+}
+
+impl RoutingPlan {
+    /// Quantity given to children (in units).
+    #[must_use]
+    pub fn allocated(&self) -> u64 {
+        self.children.iter().map(|child| child.quantity).sum()
+    }
+
+    /// The child of `venue`, if the venue got part of the parent.
+    #[must_use]
+    pub fn child(&self, venue: &str) -> Option<&ChildOrder> {
+        self.children.iter().find(|child| child.venue == venue)
+    }
+
+    /// Predicted volume-weighted average price over every child, `None` if
+    /// no fill is predicted.
+    #[must_use]
+    pub fn avg_price(&self) -> Option<f64> {
+        average_price(
+            self.children
+                .iter()
+                .flat_map(|child| child.simulation.fills.iter().copied()),
+        )
+    }
+}
+
+/// What one venue filled of its child order.
+#[derive(Debug)]
+pub struct ChildFill {
+    /// Venue the child was sent to
+    pub venue: String,
+    /// Child quantity sent (in units)
+    pub requested: u64,
+    /// Executions as (price, quantity) pairs, in matching order
+    pub fills: Vec<(u128, u64)>,
+    /// Quantity executed (in units)
+    pub filled: u64,
+    /// Why the venue rejected the child, if it did. A venue whose side
+    /// emptied before the child arrived reports no fill, not an error.
+    pub error: Option<OrderBookError>,
+}
+
+impl ChildFill {
+    /// Volume-weighted average execution price, `None` without fills.
+    #[must_use]
+    pub fn avg_price(&self) -> Option<f64> {
+        average_price(self.fills.iter().copied())
+    }
+}
+
+/// Outcome of routing a parent order with [`SmartOrderRouter::execute`].
+#[derive(Debug)]
+pub struct RoutingReport {
+    /// Side of the parent order
+    pub side: Side,
+    /// Parent quantity (in units)
+    pub quantity: u64,
+    /// One entry per child sent, in plan order
+    pub children: Vec<ChildFill>,
+}
+
+impl RoutingReport {
+    /// Quantity executed over every venue (in units).
+    #[must_use]
+    pub fn filled(&self) -> u64 {
+        self.children.iter().map(|child| child.filled).sum()
+    }
+
+    /// Parent quantity left unexecuted (in units).
+    #[must_use]
+    pub fn unfilled(&self) -> u64 {
+        self.quantity.saturating_sub(self.filled())
+    }
+
+    /// The fill report of `venue`, if a child was sent there.
+    #[must_use]
+    pub fn child(&self, venue: &str) -> Option<&ChildFill> {
+        self.children.iter().find(|child| child.venue == venue)
+    }
+
+    /// Volume-weighted average execution price over every venue, `None`
+    /// without fills.
+    #[must_use]
+    pub fn avg_price(&self) -> Option<f64> {
+        average_price(
+            self.children
+                .iter()
+                .flat_map(|child| child.fills.iter().copied()),
+        )
+    }
+}
+
+/// Splits parent market orders across the venues of a [`ConsolidatedBook`].
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{ConsolidatedBook, OrderBook, RoutingStrategy, SmartOrderRouter};
+/// use pricelevel::{Id, Side, TimeInForce};
+///
+/// let lit = OrderBook::<()>::new("BTC/USD");
+/// let alt = OrderBook::<()>::new("BTC/USD");
+/// lit.add_limit_order(Id::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, None)?;
+/// alt.add_limit_order(Id::new_uuid(), 101, 5, Side::Sell, TimeInForce::Gtc, None)?;
+/// let venues = ConsolidatedBook::new()
+///     .with_venue("LIT", &lit)
+///     .with_venue("ALT", &alt);
+///
+/// let router = SmartOrderRouter::new(RoutingStrategy::SweepBestPrice);
+/// let plan = router.route(&venues, Side::Buy, 7);
+/// assert_eq!(plan.child("LIT").map(|child| child.quantity), Some(5));
+/// assert_eq!(plan.child("ALT").map(|child| child.quantity), Some(2));
+///
+/// let report = router.execute(&venues, Side::Buy, 7);
+/// assert_eq!(report.filled(), 7);
+/// assert_eq!(lit.best_ask(), None);
+/// # Ok::<(), orderbook_rs::OrderBookError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmartOrderRouter {
+    strategy: RoutingStrategy,
+}
+
+impl SmartOrderRouter {
+    /// A router splitting parents with `strategy`.
+    #[must_use]
+    pub fn new(strategy: RoutingStrategy) -> Self {
+        Self { strategy }
+    }
+
+    /// The strategy parents are split with.
+    #[must_use]
+    pub fn strategy(&self) -> RoutingStrategy {
+        self.strategy
+    }
+
+    /// Plan a parent market order of `quantity` on `side` across `venues`,
+    /// without touching any book.
+    #[must_use]
+    pub fn route<T>(
+        &self,
+        venues: &ConsolidatedBook<'_, T>,
+        side: Side,
+        quantity: u64,
+    ) -> RoutingPlan
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        let names: Vec<&str> = venues.venues().collect();
+        let allocation = match self.strategy {
+            RoutingStrategy::SweepBestPrice => sweep_best_price(venues, &names, side, quantity),
+            RoutingStrategy::ProportionalToDisplayed => {
+                proportional_to_displayed(venues, &names, side, quantity)
+            }
+            RoutingStrategy::MinimizeImpact { slices } => {
+                minimize_impact(venues, &names, side, quantity, slices.max(1))
+            }
+        };
+        let children: Vec<ChildOrder> = names
+            .iter()
+            .zip(allocation)
+            .filter(|&(_, child_quantity)| child_quantity > 0)
+            .filter_map(|(name, child_quantity)| {
+                let book = venues.book(name)?;
+                Some(ChildOrder {
+                    venue: (*name).to_string(),
+                    quantity: child_quantity,
+                    simulation: book.simulate_market_order(child_quantity, side),
+                })
+            })
+            .collect();
+        let allocated: u64 = children.iter().map(|child| child.quantity).sum();
+        RoutingPlan {
+            side,
+            quantity,
+            children,
+            unallocated: quantity.saturating_sub(allocated),
+        }
+    }
+
+    /// Plan a parent market order with [`Self::route`], then submit every
+    /// child to its venue as a market order.
+    ///
+    /// Children are sent one after the other, in plan order; a venue that
+    /// changed since the plan was made fills what it has left.
+    #[must_use]
+    pub fn execute<T>(
+        &self,
+        venues: &ConsolidatedBook<'_, T>,
+        side: Side,
+        quantity: u64,
+    ) -> RoutingReport
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        let plan = self.route(venues, side, quantity);
+        let children = plan
+            .children
+            .into_iter()
+            .filter_map(|child| {
+                let book = venues.book(&child.venue)?;
+                Some(send_child(book, child.venue, child.quantity, side))
+            })
+            .collect();
+        RoutingReport {
+            side,
+            quantity,
+            children,
+        }
+    }
+}
+
+/// Submit one child and collect its executions.
+fn send_child<T>(book: &OrderBook<T>, venue: String, quantity: u64, side: Side) -> ChildFill
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let mut child = ChildFill {
+        venue,
+        requested: quantity,
+        fills: Vec::new(),
+        filled: 0,
+        error: None,
+    };
+    match book.submit_market_order(Id::new_uuid(), quantity, side) {
+        Ok(result) => {
+            child.fills = result
+                .trades()
+                .as_vec()
+                .iter()
+                .map(|trade| (trade.price().as_u128(), trade.quantity().as_u64()))
+                .collect();
+            child.filled = child.fills.iter().map(|&(_, filled)| filled).sum();
+        }
+        Err(OrderBookError::InsufficientLiquidity { .. }) => {}
+        Err(error) => child.error = Some(error),
+    }
+    child
+}
+
+/// Best price first, every venue at a price before the next price.
+fn sweep_best_price<T>(
+    venues: &ConsolidatedBook<'_, T>,
+    names: &[&str],
+    side: Side,
+    quantity: u64,
+) -> Vec<u64>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let mut allocation = vec![0u64; names.len()];
+    let mut remaining = quantity;
+    for level in venues.levels(side.opposite(), usize::MAX) {
+        for share in &level.venues {
+            if remaining == 0 {
+                return allocation;
+            }
+            let take = share.quantity.min(remaining);
+            if let Some(index) = names.iter().position(|name| *name == share.venue) {
+                allocation[index] += take;
+                remaining -= take;
+            }
+        }
+    }
+    allocation
+}
+
+/// Pro rata to displayed depth, remainders to the largest fractions,
+/// capped at each venue's total depth.
+fn proportional_to_displayed<T>(
+    venues: &ConsolidatedBook<'_, T>,
+    names: &[&str],
+    side: Side,
+    quantity: u64,
+) -> Vec<u64>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let mut displayed = vec![0u64; names.len()];
+    let mut depth = vec![0u64; names.len()];
+    for level in venues.levels(side.opposite(), usize::MAX) {
+        for share in &level.venues {
+            if let Some(index) = names.iter().position(|name| *name == share.venue) {
+                displayed[index] = displayed[index].saturating_add(share.visible_quantity);
+                depth[index] = depth[index].saturating_add(share.quantity);
+            }
+        }
+    }
+    let total_displayed: u128 = displayed.iter().map(|&shown| u128::from(shown)).sum();
+    if total_displayed == 0 {
+        return vec![0; names.len()];
+    }
+    let mut allocation = Vec::with_capacity(names.len());
+    let mut remainders = Vec::with_capacity(names.len());
+    for (index, &shown) in displayed.iter().enumerate() {
+        let share = u128::from(quantity) * u128::from(shown);
+        allocation.push(u64::try_from(share / total_displayed).unwrap_or(u64::MAX));
+        remainders.push((share % total_displayed, index));
+    }
+    // Largest remainder first; ties to the venue added first.
+    remainders.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    let mut leftover = quantity.saturating_sub(allocation.iter().sum());
+    for (_, index) in remainders {
+        if leftover == 0 {
+            break;
+        }
+        if displayed[index] > 0 {
+            allocation[index] += 1;
+            leftover -= 1;
+        }
+    }
+    for (allocated, &available) in allocation.iter_mut().zip(&depth) {
+        *allocated = (*allocated).min(available);
+    }
+    allocation
+}
+
+/// Slice by slice, to the venue left with the lowest slippage.
+fn minimize_impact<T>(
+    venues: &ConsolidatedBook<'_, T>,
+    names: &[&str],
+    side: Side,
+    quantity: u64,
+    slices: u32,
+) -> Vec<u64>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    let books: Vec<_> = names.iter().map(|name| venues.book(name)).collect();
+    let mut allocation = vec![0u64; names.len()];
+    let slice = quantity.div_ceil(u64::from(slices)).max(1);
+    let mut remaining = quantity;
+    while remaining > 0 {
+        let chunk = slice.min(remaining);
+        let mut best: Option<(usize, f64, f64)> = None;
+        for (index, book) in books.iter().enumerate() {
+            let Some(book) = book else {
+                continue;
+            };
+            let target = allocation[index] + chunk;
+            let impact = book.market_impact(target, side);
+            if !impact.can_fill(target) {
+                continue;
+            }
+            // Buying, a lower average is better; selling, a higher one.
+            let price_rank = match side {
+                Side::Buy => impact.avg_price,
+                Side::Sell => -impact.avg_price,
+            };
+            let better = best.is_none_or(|(_, slippage, rank)| {
+                impact.slippage_bps < slippage
+                    || (impact.slippage_bps == slippage && price_rank < rank)
+            });
+            if better {
+                best = Some((index, impact.slippage_bps, price_rank));
+            }
+        }
+        let Some((index, _, _)) = best else {
+            break;
+        };
+        allocation[index] += chunk;
+        remaining -= chunk;
+    }
+    // No venue can absorb a whole slice any more: spread what is left over
+    // the depth still free, venue by venue.
+    for (index, book) in books.iter().enumerate() {
+        let Some(book) = book else {
+            continue;
+        };
+        if remaining == 0 {
+            break;
+        }
+        let depth = book.market_impact(1, side).total_quantity_available;
+        let take = depth.saturating_sub(allocation[index]).min(remaining);
+        allocation[index] += take;
+        remaining -= take;
+    }
+    allocation
+}
+
+/// Volume-weighted average price of (price, quantity) fills.
+fn average_price(fills: impl Iterator<Item = (u128, u64)>) -> Option<f64> {
+    let (notional, quantity) = fills.fold((0f64, 0u64), |(notional, total), (price, filled)| {
+        (
+            notional + price as f64 * filled as f64,
+            total.saturating_add(filled),
+        )
+    });
+    (quantity > 0).then(|| notional / quantity as f64)
+}
@@ -8,13 +8,28 @@
 //!
 //! ```text
 //! [4 bytes: entry_length][8 bytes: sequence_num][8 bytes: timestamp_ns]
+//! [1 byte: format_version][1 byte: command_tag][8 bytes: symbol_hash]
 //! [N bytes: JSON payload][4 bytes: CRC32]
 //! ```
 //!
 //! - `entry_length` — total bytes after itself (sequence + timestamp +
-//!   payload + CRC = 20 + N).
-//! - CRC32 covers: sequence_num ‖ timestamp_ns ‖ payload (not
-//!   `entry_length`).
+//!   version + tag + symbol hash + payload + CRC = 30 + N).
+//! - `format_version` — [`JOURNAL_FORMAT_VERSION`].
+//! - `command_tag` — the
+//!   [`SequencerCommandKind`](crate::orderbook::sequencer::SequencerCommandKind)
+//!   tag of the command.
+//! - `symbol_hash` — [`symbol_hash`] of the book's symbol, or zero when the
+//!   entry was appended without one.
+//! - CRC32 covers every byte between `entry_length` and the CRC.
+//!
+//! The version, tag and symbol hash let
+//! [`read_filtered`](Journal::read_filtered) skip entries without decoding
+//! their payload.
+//!
+//! Version 1 entries, written before the version byte existed, have no
+//! version, tag or symbol hash: the JSON payload follows `timestamp_ns`
+//! directly. Its opening `{` is never a valid version byte, so both
+//! versions are read from the same segment.
 //!
 //! # Segment Files
 //!
@@ -23,15 +38,23 @@
 //! `.journal.archived`.
 
 use super::error::JournalError;
-use super::journal::{ENTRY_CRC_SIZE, ENTRY_HEADER_SIZE, Journal, JournalEntry, JournalReadIter};
+use super::journal::{
+    ENTRY_CRC_SIZE, ENTRY_HEADER_SIZE_V1, JOURNAL_FORMAT_VERSION, Journal, JournalEntry,
+    JournalFilter, JournalReadIter, symbol_hash,
+};
+use super::journal_health::{JournalCounters, JournalHealth};
+use super::read_options::JournalReadOptions;
 use super::types::SequencerEvent;
 use memmap2::MmapMut;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use tracing::warn;
 
 /// Default segment size in bytes (256 MB).
@@ -47,6 +70,8 @@ struct SegmentWriter {
     capacity: usize,
     /// Path to the segment file on disk.
     path: PathBuf,
+    /// Whether opening the segment cut a torn entry off its tail.
+    torn_tail: bool,
 }
 
 impl SegmentWriter {
@@ -86,6 +111,7 @@ impl SegmentWriter {
             write_pos: 0,
             capacity,
             path: path.to_path_buf(),
+            torn_tail: false,
         })
     }
 
@@ -120,13 +146,14 @@ impl SegmentWriter {
         };
 
         // Scan to find the write position (end of last valid entry)
-        let write_pos = scan_write_position(&mmap, capacity);
+        let (write_pos, torn_tail) = scan_write_position(&mmap, capacity);
 
         Ok(Self {
             mmap,
             write_pos,
             capacity,
             path: path.to_path_buf(),
+            torn_tail,
         })
     }
 
@@ -136,10 +163,19 @@ impl SegmentWriter {
         self.capacity.saturating_sub(self.write_pos)
     }
 
+    /// Fraction of the segment written, 0.0 to 1.0.
+    #[inline]
+    fn utilization(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        self.write_pos as f64 / self.capacity as f64
+    }
+
     /// Write a raw entry to the segment at the current position.
     ///
-    /// Returns `Ok(())` after flushing the written range to disk.
-    fn write_entry(&mut self, entry_bytes: &[u8]) -> Result<(), JournalError> {
+    /// Returns how long flushing the written range to disk took.
+    fn write_entry(&mut self, entry_bytes: &[u8]) -> Result<Duration, JournalError> {
         let end =
             self.write_pos
                 .checked_add(entry_bytes.len())
@@ -156,14 +192,16 @@ impl SegmentWriter {
         }
 
         self.mmap[self.write_pos..end].copy_from_slice(entry_bytes);
+        let flush_started = Instant::now();
         self.mmap
             .flush_range(self.write_pos, entry_bytes.len())
             .map_err(|e| JournalError::Io {
                 message: e.to_string(),
                 path: Some(self.path.clone()),
             })?;
+        let fsync = flush_started.elapsed();
         self.write_pos = end;
-        Ok(())
+        Ok(fsync)
     }
 }
 
@@ -208,6 +246,10 @@ pub struct FileJournal<T> {
     segment_start_seq: Mutex<u64>,
     /// The last sequence number written to the journal.
     last_seq: Mutex<Option<u64>>,
+    /// Health counters, shared with the readers handed out.
+    counters: Arc<JournalCounters>,
+    /// How readers load and decode segments.
+    read_options: JournalReadOptions,
     /// Marker for the generic event payload type.
     _phantom: PhantomData<T>,
 }
@@ -272,16 +314,51 @@ where
             (seg, 0, None)
         };
 
+        let counters = Arc::new(JournalCounters::default());
+        if writer.torn_tail {
+            counters.on_torn_tail();
+        }
+
         Ok(Self {
             dir,
             writer: Mutex::new(writer),
             segment_size,
             segment_start_seq: Mutex::new(segment_start_seq),
             last_seq: Mutex::new(last_seq),
+            counters,
+            read_options: JournalReadOptions::new(),
             _phantom: PhantomData,
         })
     }
 
+    /// Set how [`read_from`](Journal::read_from) and
+    /// [`read_filtered`](Journal::read_filtered) load and decode segments,
+    /// and so how a [`ReplayEngine`](crate::orderbook::sequencer::ReplayEngine)
+    /// replays this journal.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use orderbook_rs::orderbook::sequencer::{FileJournal, JournalReadOptions};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let journal: FileJournal<()> =
+    ///     FileJournal::open("/tmp/journal")?.with_read_options(JournalReadOptions::recovery());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_read_options(mut self, options: JournalReadOptions) -> Self {
+        self.read_options = options;
+        self
+    }
+
+    /// The options readers of this journal use.
+    #[must_use]
+    pub fn read_options(&self) -> JournalReadOptions {
+        self.read_options
+    }
+
     /// Archive all segment files whose start sequence is strictly less
     /// than `before_sequence`.
     ///
@@ -323,6 +400,62 @@ where
         Ok(archived)
     }
 
+    /// Report the journal's health: active segment utilization, disk usage,
+    /// append counters and latency, flush lag and damaged entries met. See
+    /// [`JournalHealth`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JournalError`] if the journal directory cannot be listed
+    /// or the write state lock is poisoned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use orderbook_rs::orderbook::sequencer::FileJournal;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let journal: FileJournal<()> = FileJournal::open("/tmp/journal")?;
+    /// let health = journal.health()?;
+    /// if health.segment_utilization > 0.9 {
+    ///     eprintln!("segment nearly full: {} bytes on disk", health.disk_bytes);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn health(&self) -> Result<JournalHealth, JournalError> {
+        let (segment_bytes_used, segment_utilization) = {
+            let writer = self
+                .writer
+                .lock()
+                .map_err(|_| JournalError::MutexPoisoned)?;
+            (writer.write_pos, writer.utilization())
+        };
+
+        let segments = list_segments(&self.dir)?;
+        let mut disk_bytes = 0u64;
+        for start_seq in &segments {
+            let path = segment_path(&self.dir, *start_seq);
+            let metadata = fs::metadata(&path).map_err(|e| JournalError::Io {
+                message: e.to_string(),
+                path: Some(path.clone()),
+            })?;
+            disk_bytes = disk_bytes.saturating_add(metadata.len());
+        }
+
+        let mut health = JournalHealth {
+            segment_size: self.segment_size,
+            segment_bytes_used,
+            segment_utilization,
+            segments: segments.len(),
+            disk_bytes,
+            last_sequence: self.last_sequence(),
+            ..JournalHealth::default()
+        };
+        self.counters.report(&mut health);
+        Ok(health)
+    }
+
     /// Rotate to a new segment file starting at the given sequence.
     fn rotate_segment(
         &self,
@@ -358,20 +491,23 @@ where
             .lock()
             .map_err(|_| JournalError::MutexPoisoned)?;
         *start = start_seq;
+        self.counters.on_rotation();
 
         Ok(())
     }
 
-    /// Serialize and encode a single event into the on-disk binary format.
-    fn encode_entry(event: &SequencerEvent<T>) -> Result<Vec<u8>, JournalError> {
+    /// Serialize and encode a single event into the on-disk binary format,
+    /// recording `symbol_hash` (zero for none) in its header.
+    fn encode_entry(event: &SequencerEvent<T>, symbol_hash: u64) -> Result<Vec<u8>, JournalError> {
         let payload = serde_json::to_vec(event).map_err(|e| JournalError::SerializationError {
             message: e.to_string(),
         })?;
 
         let payload_len = payload.len();
-        // entry_length = 8 (seq) + 8 (ts) + payload_len + 4 (crc)
+        // entry_length = 8 (seq) + 8 (ts) + 1 (version) + 1 (tag)
+        //   + 8 (symbol hash) + payload_len + 4 (crc)
         let entry_length = 8u32
-            .checked_add(8)
+            .checked_add(8 + 1 + 1 + 8)
             .and_then(|v| v.checked_add(payload_len as u32))
             .and_then(|v| v.checked_add(4))
             .ok_or(JournalError::SerializationError {
@@ -406,13 +542,22 @@ where
                 message: e.to_string(),
             })?;
 
+        // Write format_version, command_tag and symbol_hash
+        buf.push(JOURNAL_FORMAT_VERSION);
+        buf.push(event.command.kind().tag());
+        buf.write_all(&symbol_hash.to_le_bytes()).map_err(|e| {
+            JournalError::SerializationError {
+                message: e.to_string(),
+            }
+        })?;
+
         // Write payload
         buf.write_all(&payload)
             .map_err(|e| JournalError::SerializationError {
                 message: e.to_string(),
             })?;
 
-        // Compute CRC32 over (sequence_num ‖ timestamp_ns ‖ payload). The CRC
+        // Compute CRC32 over (sequence_num ‖ … ‖ payload). The CRC
         // is appended *after* this point, so `buf[4..]` is exactly that range
         // and does not (and must not) cover the CRC field itself — the same
         // range `entry_crc_valid` and `verify_integrity` re-check on read.
@@ -426,14 +571,12 @@ where
 
         Ok(buf)
     }
-}
 
-impl<T> Journal<T> for FileJournal<T>
-where
-    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
-{
-    fn append(&self, event: &SequencerEvent<T>) -> Result<(), JournalError> {
-        let entry_bytes = Self::encode_entry(event)?;
+    /// Encode `event` with `symbol_hash` in its header and append it,
+    /// rotating to a new segment if the current one cannot fit it.
+    fn write_event(&self, event: &SequencerEvent<T>, symbol_hash: u64) -> Result<(), JournalError> {
+        let started = Instant::now();
+        let entry_bytes = Self::encode_entry(event, symbol_hash)?;
 
         let mut writer = self
             .writer
@@ -453,7 +596,7 @@ where
             });
         }
 
-        writer.write_entry(&entry_bytes)?;
+        let fsync = writer.write_entry(&entry_bytes)?;
 
         // Advance last_seq, surfacing a poisoned lock rather than swallowing it.
         // Swallowing would leave `last_sequence()` under-reporting and break
@@ -464,11 +607,43 @@ where
             .lock()
             .map_err(|_| JournalError::MutexPoisoned)?;
         *last = Some(event.sequence_num);
+        drop(last);
 
+        self.counters.on_append(
+            entry_bytes.len(),
+            started.elapsed(),
+            fsync,
+            writer.utilization(),
+        );
         Ok(())
     }
+}
+
+impl<T> Journal<T> for FileJournal<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
+{
+    fn append(&self, event: &SequencerEvent<T>) -> Result<(), JournalError> {
+        self.write_event(event, 0)
+    }
+
+    fn append_for_symbol(
+        &self,
+        symbol: &str,
+        event: &SequencerEvent<T>,
+    ) -> Result<(), JournalError> {
+        self.write_event(event, symbol_hash(symbol))
+    }
 
     fn read_from(&self, sequence: u64) -> Result<JournalReadIter<T>, JournalError> {
+        self.read_filtered(sequence, JournalFilter::new())
+    }
+
+    fn read_filtered(
+        &self,
+        sequence: u64,
+        filter: JournalFilter,
+    ) -> Result<JournalReadIter<T>, JournalError> {
         // Collect all segment files sorted by start sequence
         let mut segments = list_segments(&self.dir)?;
         segments.sort();
@@ -481,22 +656,25 @@ where
             Err(idx) => idx.saturating_sub(1),
         };
 
-        let dir = self.dir.clone();
         let segments_from: Vec<u64> = segments.into_iter().skip(start_idx).collect();
-
-        let iter = SegmentIterator::<T> {
-            dir,
-            segments: segments_from,
-            segment_idx: 0,
-            offset: 0,
-            mmap: None,
-            mmap_len: 0,
+        let reader = SegmentReader {
+            dir: self.dir.clone(),
             start_sequence: sequence,
-            started: false,
-            _phantom: PhantomData,
+            filter,
+            counters: Arc::clone(&self.counters),
+            options: self.read_options,
         };
 
-        Ok(Box::new(iter))
+        if self.read_options.decode_threads() > 1 && segments_from.len() > 1 {
+            return Ok(Box::new(ParallelSegmentIterator {
+                reader,
+                segments: segments_from.into_iter(),
+                in_flight: VecDeque::new(),
+                current: Vec::new().into_iter(),
+            }));
+        }
+
+        Ok(Box::new(reader.iter(segments_from)))
     }
 
     fn last_sequence(&self) -> Option<u64> {
@@ -532,8 +710,8 @@ where
             let data = &mmap[..];
             let mut offset = 0usize;
 
-            while offset.checked_add(ENTRY_HEADER_SIZE).is_some()
-                && offset + ENTRY_HEADER_SIZE <= data.len()
+            while offset.checked_add(ENTRY_HEADER_SIZE_V1).is_some()
+                && offset + ENTRY_HEADER_SIZE_V1 <= data.len()
             {
                 // Read entry_length
                 let el_bytes =
@@ -615,6 +793,7 @@ where
                         seq_bytes[7],
                     ]);
 
+                    self.counters.on_corrupt_entry();
                     return Err(JournalError::CorruptEntry {
                         sequence: seq,
                         expected_crc: stored_crc,
@@ -642,15 +821,63 @@ impl<T> std::fmt::Debug for FileJournal<T> {
 
 // ─── Iteration ──────────────────────────────────────────────────────────────
 
+/// Everything a read needs besides the segments it covers.
+#[derive(Clone)]
+struct SegmentReader {
+    dir: PathBuf,
+    start_sequence: u64,
+    filter: JournalFilter,
+    counters: Arc<JournalCounters>,
+    options: JournalReadOptions,
+}
+
+impl SegmentReader {
+    /// An iterator over `segments`, in order, on the calling thread.
+    fn iter<T>(self, segments: Vec<u64>) -> SegmentIterator<T> {
+        SegmentIterator {
+            dir: self.dir,
+            segments,
+            segment_idx: 0,
+            offset: 0,
+            data: None,
+            start_sequence: self.start_sequence,
+            filter: self.filter,
+            counters: self.counters,
+            options: self.options,
+            started: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// The bytes of the segment being decoded.
+enum SegmentData {
+    /// Read through a mapping, faulting pages in as they are touched.
+    Mapped(memmap2::Mmap),
+    /// The segment's written bytes, copied into memory.
+    Loaded(Vec<u8>),
+}
+
+impl SegmentData {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Loaded(bytes) => bytes,
+        }
+    }
+}
+
 /// An iterator over journal entries across multiple segment files.
 struct SegmentIterator<T> {
     dir: PathBuf,
     segments: Vec<u64>,
     segment_idx: usize,
     offset: usize,
-    mmap: Option<memmap2::Mmap>,
-    mmap_len: usize,
+    data: Option<SegmentData>,
     start_sequence: u64,
+    filter: JournalFilter,
+    counters: Arc<JournalCounters>,
+    options: JournalReadOptions,
     started: bool,
     _phantom: PhantomData<T>,
 }
@@ -688,55 +915,64 @@ where
         let mmap = unsafe {
             memmap2::Mmap::map(&file).map_err(|e| JournalError::Io {
                 message: e.to_string(),
-                path: Some(path),
+                path: Some(path.clone()),
             })?
         };
 
-        self.mmap_len = mmap.len();
-        self.mmap = Some(mmap);
+        if self.options.sequential_advice() {
+            advise_sequential(&mmap, &path);
+        }
+
+        self.data = Some(if self.options.segments_in_memory() {
+            SegmentData::Loaded(mmap[..written_len(&mmap)].to_vec())
+        } else {
+            SegmentData::Mapped(mmap)
+        });
         Ok(true)
     }
 
-    /// Try to decode the next entry from the current mmap at `self.offset`.
+    /// Decode the next entry from the current mmap at `self.offset` that
+    /// is at or past the start sequence and passes the filter. Entries the
+    /// header rules out are skipped without decoding their payload.
     fn decode_next(&mut self) -> Option<Result<JournalEntry<T>, JournalError>> {
-        let mmap = self.mmap.as_ref()?;
-        let data = &mmap[..];
+        let data = self.data.as_ref()?.bytes();
 
-        if self.offset.checked_add(ENTRY_HEADER_SIZE).is_none()
-            || self.offset + ENTRY_HEADER_SIZE > data.len()
-        {
-            return None;
-        }
+        loop {
+            if self.offset.checked_add(ENTRY_HEADER_SIZE_V1).is_none()
+                || self.offset + ENTRY_HEADER_SIZE_V1 > data.len()
+            {
+                return None;
+            }
 
-        // Read entry_length
-        let el_bytes = data.get(self.offset..self.offset + 4)?;
-        let entry_length =
-            u32::from_le_bytes([el_bytes[0], el_bytes[1], el_bytes[2], el_bytes[3]]) as usize;
+            // Read entry_length
+            let el_bytes = data.get(self.offset..self.offset + 4)?;
+            let entry_length =
+                u32::from_le_bytes([el_bytes[0], el_bytes[1], el_bytes[2], el_bytes[3]]) as usize;
 
-        if entry_length == 0 {
-            return None; // End of written data
-        }
+            if entry_length == 0 {
+                return None; // End of written data
+            }
 
-        let entry_end = self.offset.checked_add(4)?.checked_add(entry_length)?;
-        if entry_end > data.len() {
-            return None; // Truncated
-        }
+            let entry_end = self.offset.checked_add(4)?.checked_add(entry_length)?;
+            if entry_end > data.len() {
+                return None; // Truncated
+            }
 
-        let payload_start = self.offset.checked_add(4)?;
-        let crc_start = entry_end.checked_sub(ENTRY_CRC_SIZE)?;
+            let payload_start = self.offset.checked_add(4)?;
+            let crc_start = entry_end.checked_sub(ENTRY_CRC_SIZE)?;
 
-        // Read stored CRC
-        let crc_bytes = data.get(crc_start..entry_end)?;
-        let stored_crc =
-            u32::from_le_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+            // Read stored CRC
+            let crc_bytes = data.get(crc_start..entry_end)?;
+            let stored_crc =
+                u32::from_le_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
 
-        // Verify CRC
-        let checksummed_data = data.get(payload_start..crc_start)?;
-        let computed_crc = crc32fast::hash(checksummed_data);
+            // Verify CRC
+            let checksummed_data = data.get(payload_start..crc_start)?;
+            let computed_crc = crc32fast::hash(checksummed_data);
 
-        if stored_crc != computed_crc {
+            // Read sequence_num (first 8 bytes after entry_length)
             let seq_bytes = data.get(payload_start..payload_start + 8)?;
-            let seq = u64::from_le_bytes([
+            let sequence_num = u64::from_le_bytes([
                 seq_bytes[0],
                 seq_bytes[1],
                 seq_bytes[2],
@@ -746,48 +982,70 @@ where
                 seq_bytes[6],
                 seq_bytes[7],
             ]);
-            self.offset = entry_end;
-            return Some(Err(JournalError::CorruptEntry {
-                sequence: seq,
-                expected_crc: stored_crc,
-                actual_crc: computed_crc,
-            }));
-        }
 
-        // Read sequence_num (first 8 bytes after entry_length)
-        let seq_bytes = data.get(payload_start..payload_start + 8)?;
-        let sequence_num = u64::from_le_bytes([
-            seq_bytes[0],
-            seq_bytes[1],
-            seq_bytes[2],
-            seq_bytes[3],
-            seq_bytes[4],
-            seq_bytes[5],
-            seq_bytes[6],
-            seq_bytes[7],
-        ]);
-
-        // Deserialize the payload (between timestamp_ns and CRC)
-        // The full payload region is: payload_start .. crc_start
-        // But we stored sequence_num + timestamp_ns + JSON payload
-        // The JSON payload starts at payload_start + 8 (seq) + 8 (ts)
-        let json_start = payload_start.checked_add(16)?;
-        let json_data = data.get(json_start..crc_start)?;
-
-        let event: SequencerEvent<T> = match serde_json::from_slice(json_data) {
-            Ok(ev) => ev,
-            Err(e) => {
+            if stored_crc != computed_crc {
                 self.offset = entry_end;
-                return Some(Err(JournalError::DeserializationError {
+                self.counters.on_corrupt_entry();
+                return Some(Err(JournalError::CorruptEntry {
                     sequence: sequence_num,
-                    message: e.to_string(),
+                    expected_crc: stored_crc,
+                    actual_crc: computed_crc,
                 }));
             }
-        };
 
-        self.offset = entry_end;
+            let entry_offset = self.offset;
+            self.offset = entry_end;
+
+            // Skip entries before the requested start sequence
+            if sequence_num < self.start_sequence {
+                continue;
+            }
 
-        Some(Ok(JournalEntry { event, stored_crc }))
+            // The version byte, or the payload's opening brace for a v1
+            // entry, follows sequence_num (8) and timestamp_ns (8).
+            let header =
+                match parse_versioned_header(data, payload_start.checked_add(16)?, crc_start) {
+                    Ok(header) => header,
+                    Err(message) => {
+                        self.counters.on_undecodable_entry();
+                        return Some(Err(JournalError::InvalidEntryHeader {
+                            offset: entry_offset,
+                            message,
+                        }));
+                    }
+                };
+
+            if !self.filter.admits_symbol(header.symbol_hash)
+                || header
+                    .command_tag
+                    .is_some_and(|tag| !self.filter.admits_tag(tag))
+            {
+                continue;
+            }
+
+            let json_data = data.get(header.payload_start..crc_start)?;
+            let event: SequencerEvent<T> = match serde_json::from_slice(json_data) {
+                Ok(ev) => ev,
+                Err(e) => {
+                    self.counters.on_undecodable_entry();
+                    return Some(Err(JournalError::DeserializationError {
+                        sequence: sequence_num,
+                        message: e.to_string(),
+                    }));
+                }
+            };
+
+            // Version 1 entries carry no tag, so their kind is only known now.
+            if header.command_tag.is_none() && !self.filter.admits_kind(event.command.kind()) {
+                continue;
+            }
+
+            return Some(Ok(JournalEntry {
+                event,
+                stored_crc,
+                symbol_hash: header.symbol_hash,
+            }));
+        }
     }
 }
 
@@ -811,12 +1069,6 @@ where
         loop {
             // Try to read from the current segment
             if let Some(result) = self.decode_next() {
-                if let Ok(entry) = &result {
-                    // Skip entries before the requested start sequence
-                    if entry.event.sequence_num < self.start_sequence {
-                        continue;
-                    }
-                }
                 return Some(result);
             }
 
@@ -830,8 +1082,120 @@ where
     }
 }
 
+/// Entries decoded from one segment, or the worker decoding them.
+enum PendingSegment<T> {
+    Worker {
+        start_seq: u64,
+        handle: JoinHandle<Vec<Result<JournalEntry<T>, JournalError>>>,
+    },
+    Decoded(Vec<Result<JournalEntry<T>, JournalError>>),
+}
+
+/// An iterator decoding up to
+/// [`decode_threads`](JournalReadOptions::decode_threads) segments at once
+/// on worker threads while yielding their entries in segment order.
+///
+/// Each in-flight segment is decoded in full before its entries are
+/// yielded, so memory grows with the number of threads times the entries
+/// per segment.
+struct ParallelSegmentIterator<T> {
+    reader: SegmentReader,
+    segments: std::vec::IntoIter<u64>,
+    in_flight: VecDeque<PendingSegment<T>>,
+    current: std::vec::IntoIter<Result<JournalEntry<T>, JournalError>>,
+}
+
+impl<T> ParallelSegmentIterator<T>
+where
+    T: for<'de> Deserialize<'de> + Clone + Send + 'static,
+{
+    /// Start decoding segments until the window is full.
+    fn fill(&mut self) {
+        while self.in_flight.len() < self.reader.options.decode_threads() {
+            let Some(start_seq) = self.segments.next() else {
+                return;
+            };
+            let reader = self.reader.clone();
+            let spawned = std::thread::Builder::new()
+                .name("journal-decode".to_string())
+                .spawn(move || reader.iter::<T>(vec![start_seq]).collect());
+            let pending = match spawned {
+                Ok(handle) => PendingSegment::Worker { start_seq, handle },
+                Err(e) => {
+                    // Without a worker, decode the segment here instead.
+                    warn!(start_seq, error = %e, "failed to spawn journal decode worker");
+                    PendingSegment::Decoded(self.reader.clone().iter(vec![start_seq]).collect())
+                }
+            };
+            self.in_flight.push_back(pending);
+        }
+    }
+}
+
+impl<T> Iterator for ParallelSegmentIterator<T>
+where
+    T: for<'de> Deserialize<'de> + Clone + Send + 'static,
+{
+    type Item = Result<JournalEntry<T>, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.current.next() {
+                return Some(result);
+            }
+
+            self.fill();
+            let entries = match self.in_flight.pop_front()? {
+                PendingSegment::Decoded(entries) => entries,
+                PendingSegment::Worker { start_seq, handle } => match handle.join() {
+                    Ok(entries) => entries,
+                    Err(_) => {
+                        return Some(Err(JournalError::Io {
+                            message: "journal decode worker panicked".to_string(),
+                            path: Some(segment_path(&self.reader.dir, start_seq)),
+                        }));
+                    }
+                },
+            };
+            self.current = entries.into_iter();
+        }
+    }
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
+/// Ask the kernel to read `mmap` ahead sequentially and start paging it in.
+/// Advice is a hint, so failures are logged and otherwise ignored.
+#[cfg(unix)]
+fn advise_sequential(mmap: &memmap2::Mmap, path: &Path) {
+    for advice in [memmap2::Advice::Sequential, memmap2::Advice::WillNeed] {
+        if let Err(e) = mmap.advise(advice) {
+            warn!(path = %path.display(), error = %e, "journal segment read-ahead advice failed");
+        }
+    }
+}
+
+/// Read-ahead advice is only available on Unix.
+#[cfg(not(unix))]
+fn advise_sequential(_mmap: &memmap2::Mmap, _path: &Path) {}
+
+/// Length of the framed entries at the start of `data`, ending at the first
+/// zero or out-of-bounds length. CRCs are not checked; decoding does that.
+fn written_len(data: &[u8]) -> usize {
+    let mut offset = 0usize;
+    while let Some(length) = offset
+        .checked_add(4)
+        .and_then(|end| data.get(offset..end))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    {
+        match offset.checked_add(4).and_then(|v| v.checked_add(length)) {
+            Some(end) if length > 0 && end <= data.len() => offset = end,
+            _ => break,
+        }
+    }
+    offset
+}
+
 /// Build the path for a segment file given its start sequence.
 fn segment_path(dir: &Path, start_sequence: u64) -> PathBuf {
     dir.join(format!("segment-{start_sequence:020}.journal"))
@@ -867,6 +1231,55 @@ fn list_segments(dir: &Path) -> Result<Vec<u64>, JournalError> {
     Ok(seqs)
 }
 
+/// The header fields following `timestamp_ns`, which differ by entry
+/// format version.
+struct VersionedHeader {
+    /// Command tag; `None` for version 1 entries.
+    command_tag: Option<u8>,
+    /// Symbol hash; `None` for version 1 entries and entries appended
+    /// without a symbol.
+    symbol_hash: Option<u64>,
+    /// Offset of the JSON payload.
+    payload_start: usize,
+}
+
+/// Parse the versioned header fields at `start`, the byte after
+/// `timestamp_ns`, of an entry whose CRC begins at `crc_start`.
+fn parse_versioned_header(
+    data: &[u8],
+    start: usize,
+    crc_start: usize,
+) -> Result<VersionedHeader, String> {
+    match data.get(start).copied() {
+        // A version 1 entry: the JSON payload starts right here.
+        Some(b'{') => Ok(VersionedHeader {
+            command_tag: None,
+            symbol_hash: None,
+            payload_start: start,
+        }),
+        Some(JOURNAL_FORMAT_VERSION) => {
+            let payload_start = start
+                .checked_add(1 + 1 + 8)
+                .filter(|end| *end <= crc_start)
+                .ok_or_else(|| "truncated versioned header".to_string())?;
+            let header = data
+                .get(start..payload_start)
+                .ok_or_else(|| "truncated versioned header".to_string())?;
+            let symbol_hash = u64::from_le_bytes([
+                header[2], header[3], header[4], header[5], header[6], header[7], header[8],
+                header[9],
+            ]);
+            Ok(VersionedHeader {
+                command_tag: Some(header[1]),
+                symbol_hash: (symbol_hash != 0).then_some(symbol_hash),
+                payload_start,
+            })
+        }
+        Some(version) => Err(format!("unsupported entry format version {version}")),
+        None => Err("truncated entry".to_string()),
+    }
+}
+
 /// Verifies the CRC32 of the entry occupying `data[offset..entry_end]`.
 ///
 /// `entry_end` must already be bounds-checked against `data`. The CRC covers
@@ -906,8 +1319,9 @@ fn entry_crc_valid(data: &[u8], offset: usize, entry_end: usize) -> bool {
 /// payload/CRC; treating that entry's start as end-of-valid-data means the next
 /// append truncates over the corruption rather than resuming on top of it, and
 /// [`scan_last_sequence`] (which scans only up to this position) reports the
-/// last decodable sequence.
-fn scan_write_position(data: &[u8], capacity: usize) -> usize {
+/// last decodable sequence. The flag reports whether such a torn entry was
+/// found.
+fn scan_write_position(data: &[u8], capacity: usize) -> (usize, bool) {
     let mut offset = 0usize;
 
     while let Some(end) = offset.checked_add(4) {
@@ -939,13 +1353,13 @@ fn scan_write_position(data: &[u8], capacity: usize) -> usize {
                 offset,
                 "torn journal tail detected on reopen; truncating to the last good entry"
             );
-            break;
+            return (offset, true);
         }
 
         offset = entry_end;
     }
 
-    offset
+    (offset, false)
 }
 
 /// Scan a segment to find the last sequence number written.
@@ -953,7 +1367,8 @@ fn scan_last_sequence(data: &[u8], write_pos: usize) -> Option<u64> {
     let mut offset = 0usize;
     let mut last_seq: Option<u64> = None;
 
-    while offset.checked_add(ENTRY_HEADER_SIZE).is_some() && offset + ENTRY_HEADER_SIZE <= write_pos
+    while offset.checked_add(ENTRY_HEADER_SIZE_V1).is_some()
+        && offset + ENTRY_HEADER_SIZE_V1 <= write_pos
     {
         let el_bytes = data.get(offset..offset + 4)?;
         let entry_length =
@@ -992,7 +1407,9 @@ fn scan_last_sequence(data: &[u8], write_pos: usize) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::orderbook::sequencer::types::{SequencerCommand, SequencerResult};
+    use crate::orderbook::sequencer::types::{
+        SequencerCommand, SequencerCommandKind, SequencerResult,
+    };
     use pricelevel::Id;
 
     fn make_event(seq: u64) -> SequencerEvent<()> {
@@ -1009,7 +1426,7 @@ mod tests {
     #[test]
     fn test_encode_entry_and_decode() {
         let event = make_event(42);
-        let entry_bytes = FileJournal::<()>::encode_entry(&event);
+        let entry_bytes = FileJournal::<()>::encode_entry(&event, 0);
         assert!(entry_bytes.is_ok());
         let buf = entry_bytes.unwrap_or_default();
         assert!(!buf.is_empty());
@@ -1209,25 +1626,6 @@ mod tests {
         assert!(err_msg.contains("corrupt journal entry"));
     }
 
-    /// Walks the entry-length headers (the pre-CRC scan) to find the byte
-    /// offset of the end of written data. Used by the torn-tail test to corrupt
-    /// the final entry without depending on the CRC-aware scanner under test.
-    fn written_len(data: &[u8]) -> usize {
-        let mut off = 0usize;
-        while off + 4 <= data.len() {
-            let el = u32::from_le_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]])
-                as usize;
-            if el == 0 {
-                break;
-            }
-            match off.checked_add(4).and_then(|v| v.checked_add(el)) {
-                Some(end) if end <= data.len() => off = end,
-                _ => break,
-            }
-        }
-        off
-    }
-
     #[test]
     fn test_reopen_truncates_torn_tail_then_appends_and_replays() {
         let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
@@ -1309,7 +1707,7 @@ mod tests {
 
         // Size the segment so exactly one entry fits and the second append must
         // rotate (which locks segment_start_seq).
-        let entry_total = FileJournal::<()>::encode_entry(&make_event(0))
+        let entry_total = FileJournal::<()>::encode_entry(&make_event(0), 0)
             .unwrap_or_else(|_| panic!("encode"))
             .len();
         let segment_size = entry_total + 8; // >= one entry, < two entries
@@ -1415,8 +1813,9 @@ mod tests {
 
     #[test]
     fn test_entry_overhead_constant() {
-        assert_eq!(super::super::journal::ENTRY_OVERHEAD, 24);
-        assert_eq!(ENTRY_HEADER_SIZE, 20);
+        assert_eq!(super::super::journal::ENTRY_OVERHEAD, 34);
+        assert_eq!(super::super::journal::ENTRY_HEADER_SIZE, 30);
+        assert_eq!(ENTRY_HEADER_SIZE_V1, 20);
         assert_eq!(ENTRY_CRC_SIZE, 4);
     }
 
@@ -1486,4 +1885,271 @@ mod tests {
             other => panic!("expected EvictExpiredOrders, got {other:?}"),
         }
     }
+
+    /// Frame `body` (everything between `entry_length` and the CRC) as an
+    /// on-disk entry.
+    fn frame(body: &[u8]) -> Vec<u8> {
+        let entry_length = u32::try_from(body.len() + ENTRY_CRC_SIZE).unwrap_or(u32::MAX);
+        let mut buf = entry_length.to_le_bytes().to_vec();
+        buf.extend_from_slice(body);
+        buf.extend_from_slice(&crc32fast::hash(body).to_le_bytes());
+        buf
+    }
+
+    /// Encode `event` in the version 1 format, which has no version byte,
+    /// command tag or symbol hash.
+    fn encode_v1_entry(event: &SequencerEvent<()>) -> Vec<u8> {
+        let mut body = event.sequence_num.to_le_bytes().to_vec();
+        body.extend_from_slice(&event.timestamp_ns.to_le_bytes());
+        body.extend_from_slice(&serde_json::to_vec(event).unwrap_or_default());
+        frame(&body)
+    }
+
+    /// Write `entries` as the first segment of `dir`, zero-padded.
+    fn write_segment(dir: &Path, entries: &[Vec<u8>]) {
+        let mut data = entries.concat();
+        data.resize(64 * 1024, 0);
+        fs::write(segment_path(dir, 0), &data).unwrap_or_else(|_| panic!("write segment"));
+    }
+
+    #[test]
+    fn test_reads_v1_entries_alongside_v2() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
+        write_segment(
+            dir.path(),
+            &[
+                encode_v1_entry(&make_event(0)),
+                encode_v1_entry(&make_event(1)),
+            ],
+        );
+
+        let journal = FileJournal::<()>::open(dir.path()).unwrap_or_else(|_| panic!("open"));
+        assert_eq!(journal.last_sequence(), Some(1));
+        assert!(journal.append_for_symbol("BTC/USD", &make_event(2)).is_ok());
+        assert!(journal.verify_integrity().is_ok());
+
+        let entries: Vec<_> = journal
+            .read_from(0)
+            .unwrap_or_else(|_| panic!("read_from"))
+            .map(|entry| entry.unwrap_or_else(|_| panic!("entry decodes")))
+            .collect();
+        let sequences: Vec<_> = entries.iter().map(|e| e.event.sequence_num).collect();
+        assert_eq!(sequences, [0, 1, 2]);
+        let hashes: Vec<_> = entries.iter().map(|e| e.symbol_hash).collect();
+        assert_eq!(hashes, [None, None, Some(symbol_hash("BTC/USD"))]);
+    }
+
+    #[test]
+    fn test_read_filtered_skips_entries_by_header() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
+        // An ETH/USD cancel whose payload is not JSON: only readers that
+        // decode it can notice.
+        let mut garbled = 1u64.to_le_bytes().to_vec();
+        garbled.extend_from_slice(&0u64.to_le_bytes());
+        garbled.push(JOURNAL_FORMAT_VERSION);
+        garbled.push(SequencerCommandKind::CancelOrder.tag());
+        garbled.extend_from_slice(&symbol_hash("ETH/USD").to_le_bytes());
+        garbled.extend_from_slice(b"not json");
+        write_segment(
+            dir.path(),
+            &[encode_v1_entry(&make_event(0)), frame(&garbled)],
+        );
+
+        let journal = FileJournal::<()>::open(dir.path()).unwrap_or_else(|_| panic!("open"));
+        let mut cancel_all = make_event(2);
+        cancel_all.command = SequencerCommand::CancelAll;
+        assert!(journal.append_for_symbol("BTC/USD", &cancel_all).is_ok());
+        assert!(journal.append(&make_event(3)).is_ok());
+
+        let read = |filter: JournalFilter| -> Vec<Result<u64, ()>> {
+            journal
+                .read_filtered(0, filter)
+                .unwrap_or_else(|_| panic!("read_filtered"))
+                .map(|entry| entry.map(|e| e.event.sequence_num).map_err(|_| ()))
+                .collect()
+        };
+        assert_eq!(read(JournalFilter::new()), [Ok(0), Err(()), Ok(2), Ok(3)]);
+        assert_eq!(read(JournalFilter::new().symbol("BTC/USD")), [Ok(2)]);
+        assert_eq!(
+            read(JournalFilter::new().kind(SequencerCommandKind::CancelAll)),
+            [Ok(2)]
+        );
+        assert_eq!(
+            read(
+                JournalFilter::new()
+                    .kind(SequencerCommandKind::CancelAll)
+                    .kind(SequencerCommandKind::CancelOrder)
+            ),
+            [Ok(0), Err(()), Ok(2), Ok(3)]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_entry_version_is_reported() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
+        let mut body = 0u64.to_le_bytes().to_vec();
+        body.extend_from_slice(&0u64.to_le_bytes());
+        body.extend_from_slice(&[JOURNAL_FORMAT_VERSION + 1; 16]);
+        write_segment(dir.path(), &[frame(&body)]);
+
+        let journal = FileJournal::<()>::open(dir.path()).unwrap_or_else(|_| panic!("open"));
+        assert_eq!(journal.last_sequence(), Some(0));
+        match journal
+            .read_from(0)
+            .unwrap_or_else(|_| panic!("read_from"))
+            .next()
+        {
+            Some(Err(JournalError::InvalidEntryHeader { offset: 0, message })) => {
+                assert!(message.contains("version 3"), "{message}");
+            }
+            other => panic!("expected InvalidEntryHeader, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_health_tracks_writes_rotations_and_damage() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
+        let entry_total = FileJournal::<()>::encode_entry(&make_event(0), 0)
+            .unwrap_or_else(|_| panic!("encode"))
+            .len();
+        // Two entries per segment, so the third append rotates.
+        let segment_size = entry_total * 2 + 8;
+        let journal = FileJournal::<()>::open_with_segment_size(dir.path(), segment_size)
+            .unwrap_or_else(|_| panic!("open"));
+        let empty = journal.health().unwrap_or_else(|_| panic!("health"));
+        assert_eq!(empty.segments, 1);
+        assert_eq!(empty.last_sequence, None);
+        assert_eq!(empty.append_latency.count, 0);
+
+        for i in 0..3 {
+            assert!(journal.append(&make_event(i)).is_ok());
+        }
+        let health = journal.health().unwrap_or_else(|_| panic!("health"));
+        assert_eq!(health.segment_size, segment_size);
+        assert_eq!(health.segments, 2);
+        assert_eq!(health.disk_bytes, 2 * segment_size as u64);
+        assert_eq!(health.segment_bytes_used, entry_total);
+        assert!(
+            (health.segment_utilization - entry_total as f64 / segment_size as f64).abs() < 1e-9
+        );
+        assert_eq!(health.last_sequence, Some(2));
+        assert_eq!(health.entries_written, 3);
+        assert_eq!(health.bytes_written, 3 * entry_total as u64);
+        assert_eq!(health.rotations, 1);
+        assert_eq!(health.append_latency.count, 3);
+        assert!(health.max_fsync_lag >= health.fsync_lag);
+        assert_eq!(health.corrupt_entries, 0);
+        drop(journal);
+
+        // Tear the tail entry, then damage the first entry's payload.
+        let seg_path = segment_path(dir.path(), 2);
+        let mut data = fs::read(&seg_path).unwrap_or_default();
+        data[entry_total - 1] ^= 0xFF;
+        fs::write(&seg_path, &data).unwrap_or_default();
+        let first_path = segment_path(dir.path(), 0);
+        let mut data = fs::read(&first_path).unwrap_or_default();
+        data[40] ^= 0xFF;
+        fs::write(&first_path, &data).unwrap_or_default();
+
+        let journal = FileJournal::<()>::open_with_segment_size(dir.path(), segment_size)
+            .unwrap_or_else(|_| panic!("reopen"));
+        assert!(journal.verify_integrity().is_err());
+        let errors = journal
+            .read_from(0)
+            .unwrap_or_else(|_| panic!("read_from"))
+            .filter(Result::is_err)
+            .count();
+        assert_eq!(errors, 2, "the damaged entry and the torn tail");
+        let health = journal.health().unwrap_or_else(|_| panic!("health"));
+        assert_eq!(health.torn_tails, 1);
+        assert_eq!(
+            health.corrupt_entries, 3,
+            "the integrity check stops at the first, the read meets both"
+        );
+        assert_eq!(health.entries_written, 0, "counters restart on open");
+    }
+
+    /// Reads `journal` from `sequence` through `filter`, rendering each
+    /// entry as its sequence number or error message.
+    fn read_summary(
+        journal: &FileJournal<()>,
+        sequence: u64,
+        filter: JournalFilter,
+    ) -> Vec<Result<u64, String>> {
+        journal
+            .read_filtered(sequence, filter)
+            .unwrap_or_else(|_| panic!("read_filtered"))
+            .map(|entry| {
+                entry
+                    .map(|e| e.event.sequence_num)
+                    .map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_options_return_the_same_entries() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| panic!("tempdir"));
+        let entry_total = FileJournal::<()>::encode_entry(&make_event(0), 0)
+            .unwrap_or_else(|_| panic!("encode"))
+            .len();
+        // Three entries per segment, so 20 appends span seven segments.
+        let segment_size = entry_total * 3 + 8;
+        let journal = FileJournal::<()>::open_with_segment_size(dir.path(), segment_size)
+            .unwrap_or_else(|_| panic!("open"));
+        for i in 0..20 {
+            let symbol = if i % 2 == 0 { "BTC" } else { "ETH" };
+            assert!(journal.append_for_symbol(symbol, &make_event(i)).is_ok());
+        }
+        drop(journal);
+
+        // Damage the first entry of the third segment.
+        let seg_path = segment_path(dir.path(), 6);
+        let mut data = fs::read(&seg_path).unwrap_or_default();
+        data[40] ^= 0xFF;
+        fs::write(&seg_path, &data).unwrap_or_default();
+
+        let journal = FileJournal::<()>::open_with_segment_size(dir.path(), segment_size)
+            .unwrap_or_else(|_| panic!("reopen"));
+        assert_eq!(journal.read_options(), JournalReadOptions::default());
+        let baseline = read_summary(&journal, 0, JournalFilter::new());
+        assert_eq!(baseline.len(), 20);
+        assert!(baseline[6].is_err());
+        let from_seven = read_summary(&journal, 7, JournalFilter::new());
+        let btc = read_summary(&journal, 4, JournalFilter::new().symbol("BTC"));
+        assert_eq!(btc.len(), 8, "BTC entries from 4, the damaged one included");
+
+        let mut journal = journal;
+        for options in [
+            JournalReadOptions::new().with_sequential_advice(true),
+            JournalReadOptions::new().with_segments_in_memory(true),
+            JournalReadOptions::new().with_decode_threads(3),
+            JournalReadOptions::new().with_decode_threads(64),
+            JournalReadOptions::recovery().with_segments_in_memory(true),
+        ] {
+            journal = journal.with_read_options(options);
+            assert_eq!(journal.read_options(), options);
+            assert_eq!(read_summary(&journal, 0, JournalFilter::new()), baseline);
+            assert_eq!(read_summary(&journal, 7, JournalFilter::new()), from_seven);
+            assert_eq!(
+                read_summary(&journal, 4, JournalFilter::new().symbol("BTC")),
+                btc
+            );
+        }
+    }
+
+    #[test]
+    fn test_written_len_stops_at_unwritten_bytes() {
+        let body = [7u8; 12];
+        let mut data = frame(&body);
+        data.extend(frame(&body));
+        let written = data.len();
+        data.extend([0u8; 64]);
+        assert_eq!(written_len(&data), written);
+        // An entry running past the end of the data is not written yet.
+        data.truncate(written + 4);
+        data[written..].copy_from_slice(&100u32.to_le_bytes());
+        assert_eq!(written_len(&data), written);
+        assert_eq!(written_len(&[]), 0);
+    }
 }
@@ -5,7 +5,7 @@
 //! persistence is not required.
 
 use super::error::JournalError;
-use super::journal::{Journal, JournalEntry, JournalReadIter};
+use super::journal::{Journal, JournalEntry, JournalReadIter, symbol_hash};
 use super::types::SequencerEvent;
 use serde::{Deserialize, Serialize};
 use std::sync::RwLock;
@@ -35,7 +35,8 @@ use std::sync::RwLock;
 /// ```
 #[derive(Debug)]
 pub struct InMemoryJournal<T> {
-    events: RwLock<Vec<SequencerEvent<T>>>,
+    /// Events with the symbol hash they were appended under.
+    events: RwLock<Vec<(SequencerEvent<T>, Option<u64>)>>,
 }
 
 impl<T> Default for InMemoryJournal<T> {
@@ -81,20 +82,38 @@ impl<T> InMemoryJournal<T> {
     }
 }
 
-impl<T> Journal<T> for InMemoryJournal<T>
-where
-    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
-{
-    fn append(&self, event: &SequencerEvent<T>) -> Result<(), JournalError> {
+impl<T: Clone> InMemoryJournal<T> {
+    fn push(
+        &self,
+        event: &SequencerEvent<T>,
+        symbol_hash: Option<u64>,
+    ) -> Result<(), JournalError> {
         self.events
             .write()
             .map_err(|_| JournalError::Io {
                 message: "failed to acquire write lock".to_string(),
                 path: None,
             })?
-            .push(event.clone());
+            .push((event.clone(), symbol_hash));
         Ok(())
     }
+}
+
+impl<T> Journal<T> for InMemoryJournal<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
+{
+    fn append(&self, event: &SequencerEvent<T>) -> Result<(), JournalError> {
+        self.push(event, None)
+    }
+
+    fn append_for_symbol(
+        &self,
+        symbol: &str,
+        event: &SequencerEvent<T>,
+    ) -> Result<(), JournalError> {
+        self.push(event, Some(symbol_hash(symbol)))
+    }
 
     fn read_from(&self, sequence: u64) -> Result<JournalReadIter<T>, JournalError> {
         let events = self.events.read().map_err(|_| JournalError::Io {
@@ -104,11 +123,12 @@ where
 
         let filtered: Vec<_> = events
             .iter()
-            .filter(|e| e.sequence_num >= sequence)
-            .map(|event| {
+            .filter(|(e, _)| e.sequence_num >= sequence)
+            .map(|(event, symbol_hash)| {
                 Ok(JournalEntry {
                     event: event.clone(),
                     stored_crc: 0, // No CRC for in-memory journal
+                    symbol_hash: *symbol_hash,
                 })
             })
             .collect();
@@ -117,7 +137,7 @@ where
     }
 
     fn last_sequence(&self) -> Option<u64> {
-        self.events.read().ok()?.last().map(|e| e.sequence_num)
+        self.events.read().ok()?.last().map(|(e, _)| e.sequence_num)
     }
 
     fn verify_integrity(&self) -> Result<(), JournalError> {
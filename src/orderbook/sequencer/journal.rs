@@ -10,13 +10,27 @@
 //! memory-mapped file implementation.
 
 use super::error::JournalError;
-use super::types::SequencerEvent;
+use super::types::{SequencerCommandKind, SequencerEvent};
 use serde::{Deserialize, Serialize};
 
+/// Format version written to the header of every new journal entry.
+///
+/// Version 1 entries carry no version byte: their JSON payload follows the
+/// timestamp directly, so readers tell them apart by the payload's opening
+/// `{`. Readers accept both versions.
+pub const JOURNAL_FORMAT_VERSION: u8 = 2;
+
 /// Size of the fixed-size entry header in bytes.
 ///
+/// Layout: `[4 bytes entry_length][8 bytes sequence_num][8 bytes timestamp_ns]
+/// [1 byte format_version][1 byte command_tag][8 bytes symbol_hash]`
+pub const ENTRY_HEADER_SIZE: usize = ENTRY_HEADER_SIZE_V1 + 1 + 1 + 8;
+
+/// Size of the entry header of version 1 entries in bytes, the prefix
+/// every version shares.
+///
 /// Layout: `[4 bytes entry_length][8 bytes sequence_num][8 bytes timestamp_ns]`
-pub const ENTRY_HEADER_SIZE: usize = 4 + 8 + 8;
+pub const ENTRY_HEADER_SIZE_V1: usize = 4 + 8 + 8;
 
 /// Size of the CRC32 trailer appended to each entry in bytes.
 pub const ENTRY_CRC_SIZE: usize = 4;
@@ -24,6 +38,83 @@ pub const ENTRY_CRC_SIZE: usize = 4;
 /// Total overhead per journal entry (header + CRC trailer) in bytes.
 pub const ENTRY_OVERHEAD: usize = ENTRY_HEADER_SIZE + ENTRY_CRC_SIZE;
 
+/// Stable 64-bit hash of a book symbol, as recorded in journal entry
+/// headers by [`Journal::append_for_symbol`].
+///
+/// FNV-1a, so the value is the same across builds and platforms. Never
+/// zero, which headers use for an entry without a symbol.
+#[must_use]
+pub fn symbol_hash(symbol: &str) -> u64 {
+    let hash = symbol
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    hash.max(1)
+}
+
+/// Selects journal entries by book symbol and command kind, as read by
+/// [`Journal::read_filtered`].
+///
+/// The default filter admits every entry. Entries written without a symbol,
+/// including every version 1 entry, never match a symbol filter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JournalFilter {
+    /// Symbol hash to match; `None` admits any symbol.
+    symbol_hash: Option<u64>,
+    /// Bit `n` admits command tag `n`; zero admits every kind.
+    kinds: u64,
+}
+
+impl JournalFilter {
+    /// A filter admitting every entry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only admit entries of the book trading `symbol`.
+    #[must_use]
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.symbol_hash = Some(symbol_hash(symbol));
+        self
+    }
+
+    /// Admit commands of `kind`. The first call restricts the filter to
+    /// that kind; later calls add to it.
+    #[must_use]
+    pub fn kind(mut self, kind: SequencerCommandKind) -> Self {
+        self.kinds |= 1 << kind.tag();
+        self
+    }
+
+    /// Whether `entry` passes the filter.
+    #[must_use]
+    pub fn matches<T>(&self, entry: &JournalEntry<T>) -> bool {
+        self.admits_symbol(entry.symbol_hash) && self.admits_kind(entry.event.command.kind())
+    }
+
+    /// Whether an entry recording `symbol_hash` passes the symbol part of
+    /// the filter.
+    #[inline]
+    pub(crate) fn admits_symbol(&self, symbol_hash: Option<u64>) -> bool {
+        self.symbol_hash.is_none() || self.symbol_hash == symbol_hash
+    }
+
+    /// Whether a command of `kind` passes the kind part of the filter.
+    #[inline]
+    pub(crate) fn admits_kind(&self, kind: SequencerCommandKind) -> bool {
+        self.admits_tag(kind.tag())
+    }
+
+    /// Whether a header command tag passes the kind part of the filter.
+    /// Tags unknown to this build never pass a kind filter.
+    #[inline]
+    pub(crate) fn admits_tag(&self, tag: u8) -> bool {
+        self.kinds == 0 || (tag < 64 && self.kinds & (1 << tag) != 0)
+    }
+}
+
 /// A single journal entry as read back from storage.
 ///
 /// Contains the deserialized event together with its on-disk metadata.
@@ -34,6 +125,10 @@ pub struct JournalEntry<T> {
 
     /// The CRC32 checksum that was stored alongside the entry.
     pub stored_crc: u32,
+
+    /// [`symbol_hash`] of the book the event was executed against, or
+    /// `None` if it was appended without a symbol.
+    pub symbol_hash: Option<u64>,
 }
 
 /// Type alias for the iterator returned by [`Journal::read_from`].
@@ -75,6 +170,25 @@ where
     /// Returns [`JournalError`] if serialization, I/O, or flushing fails.
     fn append(&self, event: &SequencerEvent<T>) -> Result<(), JournalError>;
 
+    /// Append an event executed against the book trading `symbol`, like
+    /// [`append`](Self::append).
+    ///
+    /// Journals that record the symbol let readers select one book's
+    /// entries with [`read_filtered`](Self::read_filtered). The default
+    /// drops the symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JournalError`] if serialization, I/O, or flushing fails.
+    fn append_for_symbol(
+        &self,
+        symbol: &str,
+        event: &SequencerEvent<T>,
+    ) -> Result<(), JournalError> {
+        let _ = symbol;
+        self.append(event)
+    }
+
     /// Read events starting from the given sequence number.
     ///
     /// Returns an iterator that yields events in sequence order, starting
@@ -87,6 +201,28 @@ where
     /// the starting position cannot be located.
     fn read_from(&self, sequence: u64) -> Result<JournalReadIter<T>, JournalError>;
 
+    /// Read the events from `sequence` onwards that pass `filter`, like
+    /// [`read_from`](Self::read_from). Errors are always yielded.
+    ///
+    /// The default decodes every entry and then filters;
+    /// `FileJournal` matches entry headers and skips the payloads of
+    /// entries that do not pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JournalError`] if the segment files cannot be opened or
+    /// the starting position cannot be located.
+    fn read_filtered(
+        &self,
+        sequence: u64,
+        filter: JournalFilter,
+    ) -> Result<JournalReadIter<T>, JournalError> {
+        let entries = self.read_from(sequence)?;
+        Ok(Box::new(entries.filter(move |entry| {
+            entry.as_ref().map_or(true, |entry| filter.matches(entry))
+        })))
+    }
+
     /// Returns the sequence number of the last entry in the journal.
     ///
     /// Returns `None` if the journal is empty.
@@ -101,4 +237,29 @@ where
     /// Returns the first [`JournalError::CorruptEntry`] encountered, or an
     /// I/O error if segment files cannot be read.
     fn verify_integrity(&self) -> Result<(), JournalError>;
+
+    /// Make every appended entry durable.
+    ///
+    /// Called once during a graceful shutdown. The default does nothing,
+    /// which is right for journals that persist each entry in
+    /// [`append`](Self::append), as both shipped implementations do;
+    /// journals that buffer writes must override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JournalError`] if flushing the underlying storage fails.
+    fn flush(&self) -> Result<(), JournalError> {
+        Ok(())
+    }
+
+    /// Sequence number of the last durable entry, or `None` if no entry is
+    /// durable yet.
+    ///
+    /// The default is [`last_sequence`](Self::last_sequence), which is right
+    /// for journals that persist each entry in [`append`](Self::append);
+    /// journals that buffer writes must report how far they have synced.
+    #[must_use]
+    fn durable_sequence(&self) -> Option<u64> {
+        self.last_sequence()
+    }
 }
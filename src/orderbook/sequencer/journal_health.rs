@@ -0,0 +1,265 @@
+//! Operational health of a [`FileJournal`].
+//!
+//! [`FileJournal::health`] reports how full the active segment is, how much
+//! disk the journal occupies, what it has written since it was opened, how
+//! long appends and their flushes take, and how many damaged entries
+//! readers have met. Operators poll it to alert before a disk fills or
+//! flushes stall; with the `metrics` feature the same figures are also
+//! emitted to the Prometheus recorder as they change (see
+//! [`metrics`]).
+//!
+//! Counters cover the lifetime of the `FileJournal` value: they start at
+//! zero on every open and are not persisted.
+//!
+//! [`FileJournal`]: crate::orderbook::sequencer::FileJournal
+//! [`FileJournal::health`]: crate::orderbook::sequencer::FileJournal::health
+
+use crate::orderbook::metrics;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Number of buckets in an [`AppendLatency`]: one for zero and one per bit
+/// length of a `u64` nanosecond duration.
+pub const APPEND_LATENCY_BUCKETS: usize = 65;
+
+/// Distribution of append latencies, from encoding an entry to its flush
+/// completing.
+///
+/// Bucket `0` counts zero-length appends and bucket `i` counts appends in
+/// `[2^(i-1), 2^i)` nanoseconds, so percentiles are resolved to a power of
+/// two.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppendLatency {
+    /// Appends recorded
+    pub count: u64,
+    /// Sum of every recorded latency (in nanoseconds)
+    pub total_ns: u64,
+    /// Slowest append, `None` when nothing was recorded
+    pub max_ns: Option<u64>,
+    /// Power-of-two histogram, [`APPEND_LATENCY_BUCKETS`] entries
+    pub buckets: Vec<u64>,
+}
+
+impl Default for AppendLatency {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            total_ns: 0,
+            max_ns: None,
+            buckets: vec![0; APPEND_LATENCY_BUCKETS],
+        }
+    }
+}
+
+impl AppendLatency {
+    /// Bucket an append taking `ns` nanoseconds is counted in.
+    #[must_use]
+    pub fn bucket_of(ns: u64) -> usize {
+        (u64::BITS - ns.leading_zeros()) as usize
+    }
+
+    /// Mean latency in nanoseconds, `None` when nothing was recorded.
+    #[must_use]
+    pub fn mean_ns(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total_ns as f64 / self.count as f64)
+    }
+
+    /// Upper bound of the bucket holding the `quantile` (0.0 to 1.0)
+    /// latency, capped at [`Self::max_ns`]. `None` when nothing was
+    /// recorded.
+    #[must_use]
+    pub fn percentile_ns(&self, quantile: f64) -> Option<u64> {
+        let max = self.max_ns?;
+        let rank = ((quantile.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0u64;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen = seen.saturating_add(count);
+            if seen >= rank {
+                let upper = match bucket {
+                    0 => 0,
+                    64 => u64::MAX,
+                    _ => (1u64 << bucket) - 1,
+                };
+                return Some(upper.min(max));
+            }
+        }
+        Some(max)
+    }
+}
+
+/// Health report of a [`FileJournal`](crate::orderbook::sequencer::FileJournal),
+/// as returned by
+/// [`FileJournal::health`](crate::orderbook::sequencer::FileJournal::health).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JournalHealth {
+    /// Capacity of each segment file, in bytes
+    pub segment_size: usize,
+    /// Bytes written to the active segment
+    pub segment_bytes_used: usize,
+    /// Fraction of the active segment in use, 0.0 to 1.0
+    pub segment_utilization: f64,
+    /// Segment files not yet archived, the active one included
+    pub segments: usize,
+    /// Allocated size of those segment files, in bytes
+    pub disk_bytes: u64,
+    /// Sequence number of the last entry, `None` if the journal is empty
+    pub last_sequence: Option<u64>,
+    /// Entries appended since the journal was opened
+    pub entries_written: u64,
+    /// Bytes appended since the journal was opened, framing included
+    pub bytes_written: u64,
+    /// Segment rotations since the journal was opened
+    pub rotations: u64,
+    /// Latency of every append since the journal was opened
+    pub append_latency: AppendLatency,
+    /// How long the most recent append waited for its flush to reach disk
+    pub fsync_lag: Duration,
+    /// Longest any append waited for its flush
+    pub max_fsync_lag: Duration,
+    /// Entries whose CRC did not match, as met by readers and integrity
+    /// checks
+    pub corrupt_entries: u64,
+    /// Entries with a valid CRC whose header or payload could not be
+    /// decoded
+    pub undecodable_entries: u64,
+    /// Torn entries cut off the tail of the active segment when the
+    /// journal was opened
+    pub torn_tails: u64,
+}
+
+/// Live counters behind a [`JournalHealth`], shared by a journal and the
+/// readers it hands out.
+#[derive(Debug)]
+pub(crate) struct JournalCounters {
+    entries_written: AtomicU64,
+    bytes_written: AtomicU64,
+    rotations: AtomicU64,
+    append_total_ns: AtomicU64,
+    append_max_ns: AtomicU64,
+    append_buckets: [AtomicU64; APPEND_LATENCY_BUCKETS],
+    last_fsync_ns: AtomicU64,
+    max_fsync_ns: AtomicU64,
+    corrupt_entries: AtomicU64,
+    undecodable_entries: AtomicU64,
+    torn_tails: AtomicU64,
+}
+
+impl Default for JournalCounters {
+    fn default() -> Self {
+        Self {
+            entries_written: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            rotations: AtomicU64::new(0),
+            append_total_ns: AtomicU64::new(0),
+            append_max_ns: AtomicU64::new(0),
+            append_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            last_fsync_ns: AtomicU64::new(0),
+            max_fsync_ns: AtomicU64::new(0),
+            corrupt_entries: AtomicU64::new(0),
+            undecodable_entries: AtomicU64::new(0),
+            torn_tails: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Saturating nanosecond count of `duration`.
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+impl JournalCounters {
+    /// Record an append of `bytes` that took `latency`, of which `fsync`
+    /// was spent flushing, leaving the active segment `utilization` full.
+    pub(crate) fn on_append(
+        &self,
+        bytes: usize,
+        latency: Duration,
+        fsync: Duration,
+        utilization: f64,
+    ) {
+        let latency_ns = nanos(latency);
+        let fsync_ns = nanos(fsync);
+        self.entries_written.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.append_total_ns
+            .fetch_add(latency_ns, Ordering::Relaxed);
+        self.append_max_ns.fetch_max(latency_ns, Ordering::Relaxed);
+        self.append_buckets[AppendLatency::bucket_of(latency_ns)].fetch_add(1, Ordering::Relaxed);
+        self.last_fsync_ns.store(fsync_ns, Ordering::Relaxed);
+        self.max_fsync_ns.fetch_max(fsync_ns, Ordering::Relaxed);
+        metrics::record_journal_append(bytes as u64, latency, fsync, utilization);
+    }
+
+    /// Record a segment rotation.
+    pub(crate) fn on_rotation(&self) {
+        self.rotations.fetch_add(1, Ordering::Relaxed);
+        metrics::record_journal_rotation();
+    }
+
+    /// Record an entry whose CRC did not match.
+    pub(crate) fn on_corrupt_entry(&self) {
+        self.corrupt_entries.fetch_add(1, Ordering::Relaxed);
+        metrics::record_journal_damaged_entry(metrics::JOURNAL_DAMAGE_CORRUPT);
+    }
+
+    /// Record an entry whose header or payload could not be decoded.
+    pub(crate) fn on_undecodable_entry(&self) {
+        self.undecodable_entries.fetch_add(1, Ordering::Relaxed);
+        metrics::record_journal_damaged_entry(metrics::JOURNAL_DAMAGE_UNDECODABLE);
+    }
+
+    /// Record a torn tail entry cut off on open.
+    pub(crate) fn on_torn_tail(&self) {
+        self.torn_tails.fetch_add(1, Ordering::Relaxed);
+        metrics::record_journal_damaged_entry(metrics::JOURNAL_DAMAGE_TORN_TAIL);
+    }
+
+    /// Fill the counter fields of a health report.
+    pub(crate) fn report(&self, health: &mut JournalHealth) {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let buckets: Vec<u64> = self.append_buckets.iter().map(load).collect();
+        let count = buckets.iter().sum();
+        health.entries_written = load(&self.entries_written);
+        health.bytes_written = load(&self.bytes_written);
+        health.rotations = load(&self.rotations);
+        health.append_latency = AppendLatency {
+            count,
+            total_ns: load(&self.append_total_ns),
+            max_ns: (count > 0).then(|| load(&self.append_max_ns)),
+            buckets,
+        };
+        health.fsync_lag = Duration::from_nanos(load(&self.last_fsync_ns));
+        health.max_fsync_lag = Duration::from_nanos(load(&self.max_fsync_ns));
+        health.corrupt_entries = load(&self.corrupt_entries);
+        health.undecodable_entries = load(&self.undecodable_entries);
+        health.torn_tails = load(&self.torn_tails);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_latency_percentiles_resolve_to_buckets() {
+        let counters = JournalCounters::default();
+        for ns in [0, 900, 1_000, 1_500, 40_000] {
+            counters.on_append(64, Duration::from_nanos(ns), Duration::ZERO, 0.0);
+        }
+        let mut health = JournalHealth::default();
+        counters.report(&mut health);
+
+        let latency = &health.append_latency;
+        assert_eq!(health.bytes_written, 320);
+        assert_eq!(latency.count, 5);
+        assert_eq!(latency.max_ns, Some(40_000));
+        assert_eq!(latency.mean_ns(), Some(8_680.0));
+        assert_eq!(latency.percentile_ns(0.0), Some(0));
+        assert_eq!(latency.percentile_ns(0.6), Some(1_023));
+        assert_eq!(latency.percentile_ns(0.8), Some(2_047));
+        assert_eq!(latency.percentile_ns(1.0), Some(40_000));
+        assert_eq!(AppendLatency::default().percentile_ns(0.5), None);
+    }
+}
@@ -8,14 +8,20 @@
 //! - [`SequencerCommand`] — commands submitted for sequenced execution
 //! - [`SequencerEvent`] — sequenced events emitted after execution
 //! - [`SequencerResult`] — outcomes of command execution
+//! - [`SequencedResult`] — a command's result with its journal sequence number
+//! - [`SequencerCommandKind`] — the variant of a command, as tagged in journal entry headers
 //! - [`JournalError`] — error type for journal operations
 //! - [`Journal`] — trait for append-only event journals
 //! - [`JournalEntry`] — a single entry read back from the journal
+//! - [`JournalFilter`] — selects journal entries by symbol and command kind
 //! - [`crate::orderbook::sequencer::InMemoryJournal`] — in-memory journal implementation for testing
 //! - [`crate::orderbook::sequencer::ReplayEngine`] — deterministic replay engine for event journals
 //! - [`crate::orderbook::sequencer::ReplayError`] — error type for replay operations
 //! - [`crate::orderbook::sequencer::ReplayBookConfig`] — book configuration injected into a fresh book before replay (non-default-config recovery)
+//! - [`crate::orderbook::sequencer::command_ring`] — multi-producer, single-consumer command ring used as the Sequencer's ingress, with a configurable [`crate::orderbook::sequencer::WaitStrategy`]
 //! - `FileJournal` — memory-mapped file journal implementation (requires `journal` feature)
+//! - `JournalHealth` — health report of a `FileJournal`, with its `AppendLatency` histogram (requires `journal` feature)
+//! - `JournalReadOptions` — read-ahead, in-memory and parallel decode tuning for `FileJournal` reads and replay (requires `journal` feature)
 //!
 //! # Feature Gate
 //!
@@ -36,14 +42,30 @@ pub mod file_journal;
 
 pub mod in_memory_journal;
 pub mod journal;
+#[cfg(feature = "journal")]
+pub mod journal_health;
+#[cfg(feature = "journal")]
+pub mod read_options;
 pub mod replay;
+pub mod ring;
 
 pub use error::JournalError;
 #[cfg(feature = "journal")]
 pub use file_journal::FileJournal;
 pub use in_memory_journal::InMemoryJournal;
 pub use journal::{
-    ENTRY_CRC_SIZE, ENTRY_HEADER_SIZE, ENTRY_OVERHEAD, Journal, JournalEntry, JournalReadIter,
+    ENTRY_CRC_SIZE, ENTRY_HEADER_SIZE, ENTRY_HEADER_SIZE_V1, ENTRY_OVERHEAD,
+    JOURNAL_FORMAT_VERSION, Journal, JournalEntry, JournalFilter, JournalReadIter, symbol_hash,
 };
+#[cfg(feature = "journal")]
+pub use journal_health::{APPEND_LATENCY_BUCKETS, AppendLatency, JournalHealth};
+#[cfg(feature = "journal")]
+pub use read_options::JournalReadOptions;
 pub use replay::{ReplayBookConfig, ReplayEngine, ReplayError, snapshots_match};
-pub use types::{SequencerCommand, SequencerEvent, SequencerResult};
+pub use ring::{
+    DEFAULT_RING_SPIN_ITERATIONS, RingIntoIter, RingIter, RingReceiver, RingSender, WaitStrategy,
+    command_ring,
+};
+pub use types::{
+    SequencedResult, SequencerCommand, SequencerCommandKind, SequencerEvent, SequencerResult,
+};
@@ -0,0 +1,122 @@
+//! Read tuning for a [`FileJournal`].
+//!
+//! By default a reader maps each segment and lets the kernel fault its pages
+//! in on demand, decoding entries one at a time on the calling thread. That
+//! keeps memory flat but makes cold-start recovery of a large journal bound
+//! by page-fault latency. [`JournalReadOptions`] trades memory for speed:
+//!
+//! - [`with_sequential_advice`](JournalReadOptions::with_sequential_advice)
+//!   tells the kernel each segment is read front to back, so it reads ahead
+//!   aggressively (`madvise(MADV_SEQUENTIAL)` and `MADV_WILLNEED`; a no-op
+//!   on non-Unix platforms).
+//! - [`with_segments_in_memory`](JournalReadOptions::with_segments_in_memory)
+//!   copies the written part of each segment into memory before decoding
+//!   and releases the mapping.
+//! - [`with_decode_threads`](JournalReadOptions::with_decode_threads)
+//!   decodes up to that many segments at once on worker threads. Entries
+//!   are still yielded in sequence order, so a replay applies them exactly
+//!   as a single-threaded read would.
+//!
+//! Options only change how entries are read, never which entries are
+//! returned.
+//!
+//! [`FileJournal`]: crate::orderbook::sequencer::FileJournal
+
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+
+/// How a [`FileJournal`](crate::orderbook::sequencer::FileJournal) reads its
+/// segments, set with
+/// [`FileJournal::with_read_options`](crate::orderbook::sequencer::FileJournal::with_read_options).
+///
+/// The default reads lazily on the calling thread.
+///
+/// # Example
+///
+/// ```rust
+/// use orderbook_rs::orderbook::sequencer::JournalReadOptions;
+///
+/// let options = JournalReadOptions::new()
+///     .with_sequential_advice(true)
+///     .with_decode_threads(4);
+/// assert_eq!(options.decode_threads(), 4);
+/// assert!(!options.segments_in_memory());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalReadOptions {
+    sequential_advice: bool,
+    segments_in_memory: bool,
+    decode_threads: usize,
+}
+
+impl Default for JournalReadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JournalReadOptions {
+    /// Lazy, single-threaded reads: no advice, segments stay mapped.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sequential_advice: false,
+            segments_in_memory: false,
+            decode_threads: 1,
+        }
+    }
+
+    /// Options suited to cold-start recovery: sequential read-ahead and one
+    /// decode thread per available CPU.
+    #[must_use]
+    pub fn recovery() -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self::new()
+            .with_sequential_advice(true)
+            .with_decode_threads(threads)
+    }
+
+    /// Advise the kernel that segments are read sequentially.
+    #[must_use]
+    pub const fn with_sequential_advice(mut self, enabled: bool) -> Self {
+        self.sequential_advice = enabled;
+        self
+    }
+
+    /// Copy each segment's written bytes into memory before decoding it.
+    #[must_use]
+    pub const fn with_segments_in_memory(mut self, enabled: bool) -> Self {
+        self.segments_in_memory = enabled;
+        self
+    }
+
+    /// Decode up to `threads` segments concurrently. `0` and `1` both
+    /// decode on the calling thread.
+    #[must_use]
+    pub const fn with_decode_threads(mut self, threads: usize) -> Self {
+        self.decode_threads = if threads == 0 { 1 } else { threads };
+        self
+    }
+
+    /// Whether segments are read with sequential advice.
+    #[must_use]
+    pub const fn sequential_advice(&self) -> bool {
+        self.sequential_advice
+    }
+
+    /// Whether segments are copied into memory before decoding.
+    #[must_use]
+    pub const fn segments_in_memory(&self) -> bool {
+        self.segments_in_memory
+    }
+
+    /// Number of segments decoded concurrently, at least `1`.
+    #[must_use]
+    pub const fn decode_threads(&self) -> usize {
+        if self.decode_threads == 0 {
+            1
+        } else {
+            self.decode_threads
+        }
+    }
+}
@@ -8,6 +8,7 @@
 use super::error::JournalError;
 use super::journal::Journal;
 use super::types::{SequencerCommand, SequencerEvent, SequencerResult};
+use crate::orderbook::book_config::BookConfig;
 use crate::orderbook::clock::Clock;
 use crate::orderbook::fees::FeeSchedule;
 use crate::orderbook::stp::STPMode;
@@ -81,6 +82,16 @@ pub struct ReplayBookConfig {
     /// Applied via [`OrderBook::set_max_order_size`] only when `Some`.
     pub max_order_size: Option<u64>,
 
+    /// Minimum order notional the source book used, or `None` for no
+    /// minimum. Applied via [`OrderBook::set_min_order_notional`] only when
+    /// `Some`.
+    pub min_order_notional: Option<u128>,
+
+    /// Maximum order notional the source book used, or `None` for no
+    /// maximum. Applied via [`OrderBook::set_max_order_notional`] only when
+    /// `Some`.
+    pub max_order_notional: Option<u128>,
+
     /// Trade-ID namespace the source book used, or `None` to keep the fresh
     /// book's random namespace. Applied via
     /// [`OrderBook::set_trade_id_namespace`] only when `Some`, before any
@@ -133,10 +144,28 @@ impl ReplayBookConfig {
             lot_size,
             min_order_size,
             max_order_size,
+            min_order_notional: None,
+            max_order_notional: None,
             trade_id_namespace: None,
         }
     }
 
+    /// Returns this configuration with the order notional limits set.
+    ///
+    /// Builder-style companion to [`Self::new`] (which leaves both limits at
+    /// `None`).
+    ///
+    /// # Arguments
+    ///
+    /// * `min` — minimum order notional the source book used, or `None`
+    /// * `max` — maximum order notional the source book used, or `None`
+    #[must_use = "with_order_notional_limits returns the updated config; it does not mutate in place"]
+    pub fn with_order_notional_limits(mut self, min: Option<u128>, max: Option<u128>) -> Self {
+        self.min_order_notional = min;
+        self.max_order_notional = max;
+        self
+    }
+
     /// Returns this configuration with the trade-ID namespace set.
     ///
     /// Builder-style companion to [`Self::new`] (which leaves the namespace
@@ -160,27 +189,39 @@ impl ReplayBookConfig {
     ///
     /// `fee_schedule`, `stp_mode`, `tick_size`, and `lot_size` are applied
     /// unconditionally (a `None` / [`STPMode::None`] value resets the field to
-    /// its default, which is a no-op on a fresh book). `min_order_size` and
-    /// `max_order_size` are applied only when `Some`, mirroring the existing
-    /// `set_min_order_size` / `set_max_order_size` setters which take a bare
-    /// value rather than an `Option`. `trade_id_namespace` is applied only
+    /// its default, which is a no-op on a fresh book). `min_order_size`,
+    /// `max_order_size` and the order notional limits are applied only when
+    /// `Some`, mirroring the existing `set_min_order_size` /
+    /// `set_max_order_size` setters which take a bare value rather than an
+    /// `Option`. `trade_id_namespace` is applied only
     /// when `Some` — the book is fresh (no orders yet), so replacing the
     /// generator here honors the counter-restart contract of
-    /// [`OrderBook::set_trade_id_namespace`].
+    /// [`OrderBook::set_trade_id_namespace`]. The parameters are installed
+    /// as construction-time configuration, so the replayed book keeps
+    /// config version `0`.
     fn apply_to<T>(&self, book: &mut OrderBook<T>)
     where
         T: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + Default + 'static,
     {
-        book.set_fee_schedule(self.fee_schedule);
+        let current = book.config();
+        book.store_config(BookConfig {
+            tick_size: self.tick_size,
+            lot_size: self.lot_size,
+            min_order_size: self.min_order_size.or(current.min_order_size),
+            max_order_size: self.max_order_size.or(current.max_order_size),
+            min_order_notional: self.min_order_notional.or(current.min_order_notional),
+            max_order_notional: self.max_order_notional.or(current.max_order_notional),
+            fee_schedule: self.fee_schedule,
+            risk: current.risk,
+            market_protection: current.market_protection,
+            post_only_mode: current.post_only_mode,
+            reference_price_max_age_ms: current.reference_price_max_age_ms,
+            trading_session: current.trading_session,
+            circuit_breaker: current.circuit_breaker,
+            speed_bump: current.speed_bump,
+            reserve_fill_limit: current.reserve_fill_limit,
+        });
         book.set_stp_mode(self.stp_mode);
-        book.set_tick_size_opt(self.tick_size);
-        book.set_lot_size_opt(self.lot_size);
-        if let Some(min) = self.min_order_size {
-            book.set_min_order_size(min);
-        }
-        if let Some(max) = self.max_order_size {
-            book.set_max_order_size(max);
-        }
         if let Some(namespace) = self.trade_id_namespace {
             book.set_trade_id_namespace(namespace);
         }
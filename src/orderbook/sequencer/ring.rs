@@ -0,0 +1,606 @@
+//! Multi-producer, single-consumer command ring for Sequencer ingress.
+//!
+//! [`command_ring`] returns a bounded, Disruptor-style ring buffer split into
+//! a cloneable [`RingSender`] and a single [`RingReceiver`]. Producers claim
+//! a sequence number with one compare-and-swap on a shared cursor, write
+//! their command into the claimed slot and publish it by advancing the
+//! slot's own sequence stamp, so no lock is taken on the send or receive
+//! path. Capacity is rounded up to a power of two so a sequence maps to its
+//! slot with a mask; both cursors sit on their own cache line.
+//!
+//! How the consumer waits for work is chosen with a [`WaitStrategy`]:
+//! spin, spin then yield, or spin for a while and then park until a
+//! producer wakes it. Producers facing a full ring spin and then yield
+//! until the consumer frees a slot, whatever the strategy; they never park.
+//!
+//! Disconnection mirrors `std::sync::mpsc`: once every sender is dropped the
+//! receiver drains what is left and then reports [`RecvError`], and sending
+//! after the receiver is dropped returns the command in a [`SendError`].
+//!
+//! ```
+//! use orderbook_rs::orderbook::sequencer::{WaitStrategy, command_ring};
+//!
+//! let (sender, receiver) = command_ring::<u64>(8, WaitStrategy::default());
+//! let producer = {
+//!     let sender = sender.clone();
+//!     std::thread::spawn(move || sender.send(1).unwrap())
+//! };
+//! sender.send(2).unwrap();
+//! producer.join().unwrap();
+//! drop(sender);
+//!
+//! let mut received: Vec<u64> = receiver.into_iter().collect();
+//! received.sort_unstable();
+//! assert_eq!(received, vec![1, 2]);
+//! ```
+
+use crossbeam::utils::{Backoff, CachePadded};
+use std::cell::{Cell, UnsafeCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering, fence};
+use std::sync::mpsc::{RecvError, SendError, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::Thread;
+
+/// Spin iterations before an idle consumer parks under the default
+/// [`WaitStrategy::SpinThenPark`].
+pub const DEFAULT_RING_SPIN_ITERATIONS: u32 = 10_000;
+
+/// How the consumer of a command ring waits for the next command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitStrategy {
+    /// Spin on the next slot without ever giving up the core. Lowest
+    /// latency; burns a full core while idle, and needs that core to itself
+    /// or producers sharing it stall for whole scheduler slices.
+    BusySpin,
+    /// Spin briefly, then yield to the scheduler between checks.
+    Yielding,
+    /// Spin for `spins` checks, then park until a producer publishes.
+    /// Producers pay one fence per send to find out whether to wake the
+    /// consumer.
+    SpinThenPark {
+        /// Checks made before parking.
+        spins: u32,
+    },
+}
+
+impl Default for WaitStrategy {
+    fn default() -> Self {
+        Self::SpinThenPark {
+            spins: DEFAULT_RING_SPIN_ITERATIONS,
+        }
+    }
+}
+
+/// One ring slot. `sequence` equals the slot's claimable sequence while it
+/// is free and that sequence plus one once its value is published.
+struct Slot<T> {
+    sequence: AtomicU64,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// State shared by the senders and the receiver.
+struct Ring<T> {
+    slots: Box<[Slot<T>]>,
+    mask: u64,
+    /// Next sequence a producer will claim
+    tail: CachePadded<AtomicU64>,
+    /// Next sequence the consumer will read; written by the consumer only
+    head: CachePadded<AtomicU64>,
+    wait: WaitStrategy,
+    /// Set while the consumer is parked or about to park
+    parked: CachePadded<AtomicBool>,
+    /// Thread to unpark; recorded by the consumer before it parks
+    consumer: Mutex<Option<Thread>>,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+}
+
+// SAFETY: a slot's value is written only by the producer that claimed its
+// sequence and read only by the single consumer after the Release store of
+// the slot's sequence publishes it, so values move between threads but are
+// never shared. That requires `T: Send` and nothing more.
+#[allow(unsafe_code)]
+unsafe impl<T: Send> Send for Ring<T> {}
+// SAFETY: see the `Send` impl above.
+#[allow(unsafe_code)]
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    fn new(capacity: usize, wait: WaitStrategy) -> Self {
+        // With one slot a published stamp (`seq + 1`) equals the next
+        // lap's free stamp, so a producer would overwrite an unread value.
+        let capacity = capacity.max(2).next_power_of_two();
+        let slots = (0..capacity as u64)
+            .map(|sequence| Slot {
+                sequence: AtomicU64::new(sequence),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        Self {
+            slots,
+            mask: capacity as u64 - 1,
+            tail: CachePadded::new(AtomicU64::new(0)),
+            head: CachePadded::new(AtomicU64::new(0)),
+            wait,
+            parked: CachePadded::new(AtomicBool::new(false)),
+            consumer: Mutex::new(None),
+            senders: AtomicUsize::new(1),
+            receiver_alive: AtomicBool::new(true),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn slot(&self, sequence: u64) -> &Slot<T> {
+        &self.slots[(sequence & self.mask) as usize]
+    }
+
+    /// Claim a slot and publish `value` into it, or hand `value` back if
+    /// the ring is full.
+    fn try_push(&self, value: T) -> Result<(), T> {
+        let mut sequence = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = self.slot(sequence);
+            let stamp = slot.sequence.load(Ordering::Acquire);
+            match stamp.wrapping_sub(sequence) as i64 {
+                0 => match self.tail.compare_exchange_weak(
+                    sequence,
+                    sequence.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: winning the CAS gives this producer sole
+                        // ownership of the slot until the stamp below
+                        // publishes it, and a stamp equal to the claimed
+                        // sequence means the consumer has already moved the
+                        // previous value out.
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            (*slot.value.get()).write(value);
+                        }
+                        slot.sequence
+                            .store(sequence.wrapping_add(1), Ordering::Release);
+                        self.wake_consumer();
+                        return Ok(());
+                    }
+                    Err(current) => sequence = current,
+                },
+                // The slot still holds the value from one lap ago: full.
+                lag if lag < 0 => return Err(value),
+                // Another producer claimed this sequence first.
+                _ => sequence = self.tail.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Take the next published value, if any. Only the consumer calls this.
+    fn try_pop(&self) -> Option<T> {
+        let sequence = self.head.load(Ordering::Relaxed);
+        let slot = self.slot(sequence);
+        if slot.sequence.load(Ordering::Acquire) != sequence.wrapping_add(1) {
+            return None;
+        }
+        // SAFETY: the stamp shows the value at `sequence` was published
+        // (the Acquire load pairs with the producer's Release store), and
+        // the single consumer reads each sequence exactly once.
+        #[allow(unsafe_code)]
+        let value = unsafe { (*slot.value.get()).assume_init_read() };
+        slot.sequence.store(
+            sequence.wrapping_add(self.capacity() as u64),
+            Ordering::Release,
+        );
+        self.head.store(sequence.wrapping_add(1), Ordering::Relaxed);
+        Some(value)
+    }
+
+    /// Whether the next slot is published, without consuming it.
+    fn has_next(&self) -> bool {
+        let sequence = self.head.load(Ordering::Relaxed);
+        self.slot(sequence).sequence.load(Ordering::Acquire) == sequence.wrapping_add(1)
+    }
+
+    fn disconnected(&self) -> bool {
+        self.senders.load(Ordering::Acquire) == 0
+    }
+
+    /// Unpark the consumer if it parked (or is about to). The fence pairs
+    /// with the one in [`Ring::park`]: either the consumer sees the newly
+    /// published slot before parking, or this sees `parked` and wakes it.
+    fn wake_consumer(&self) {
+        if !matches!(self.wait, WaitStrategy::SpinThenPark { .. }) {
+            return;
+        }
+        fence(Ordering::SeqCst);
+        if self.parked.load(Ordering::Relaxed) {
+            self.unpark();
+        }
+    }
+
+    fn unpark(&self) {
+        let consumer = self.consumer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(thread) = consumer.as_ref() {
+            thread.unpark();
+        }
+    }
+
+    /// Park the consumer until a producer publishes or the last sender
+    /// goes away.
+    fn park(&self) {
+        *self.consumer.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(std::thread::current());
+        self.parked.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        if !self.has_next() && !self.disconnected() {
+            std::thread::park();
+        }
+        self.parked.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        // No sender is left mid-publish, so every claimed slot is published
+        // and they run contiguously from `head`.
+        while self.try_pop().is_some() {}
+    }
+}
+
+/// Producer half of a command ring. Clone it to add producers.
+pub struct RingSender<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T> RingSender<T> {
+    /// Publish `value`, waiting while the ring is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value in a [`SendError`] if the receiver has been
+    /// dropped.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let backoff = Backoff::new();
+        let mut value = value;
+        loop {
+            if !self.ring.receiver_alive.load(Ordering::Acquire) {
+                return Err(SendError(value));
+            }
+            match self.ring.try_push(value) {
+                Ok(()) => return Ok(()),
+                Err(returned) => value = returned,
+            }
+            backoff.snooze();
+        }
+    }
+
+    /// Publish `value` if a slot is free.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TrySendError::Full`] if the ring is full and
+    /// [`TrySendError::Disconnected`] if the receiver has been dropped.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if !self.ring.receiver_alive.load(Ordering::Acquire) {
+            return Err(TrySendError::Disconnected(value));
+        }
+        self.ring.try_push(value).map_err(TrySendError::Full)
+    }
+
+    /// Number of slots, a power of two.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+impl<T> Clone for RingSender<T> {
+    fn clone(&self) -> Self {
+        self.ring.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            ring: Arc::clone(&self.ring),
+        }
+    }
+}
+
+impl<T> Drop for RingSender<T> {
+    fn drop(&mut self) {
+        if self.ring.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            fence(Ordering::SeqCst);
+            self.ring.unpark();
+        }
+    }
+}
+
+impl<T> fmt::Debug for RingSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingSender")
+            .field("capacity", &self.capacity())
+            .field("wait", &self.ring.wait)
+            .finish()
+    }
+}
+
+/// Consumer half of a command ring. There is exactly one; it can be moved
+/// to another thread but not shared.
+pub struct RingReceiver<T> {
+    ring: Arc<Ring<T>>,
+    /// Opts out of `Sync`: the ring supports a single consumer
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> RingReceiver<T> {
+    /// Take the next command, waiting according to the ring's
+    /// [`WaitStrategy`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError`] once every sender is dropped and the ring is
+    /// empty.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let backoff = Backoff::new();
+        let mut spins: u32 = 0;
+        loop {
+            if let Some(value) = self.ring.try_pop() {
+                return Ok(value);
+            }
+            if self.ring.disconnected() {
+                // A send that completed before the last sender dropped is
+                // visible now.
+                return self.ring.try_pop().ok_or(RecvError);
+            }
+            match self.ring.wait {
+                WaitStrategy::BusySpin => std::hint::spin_loop(),
+                WaitStrategy::Yielding => backoff.snooze(),
+                WaitStrategy::SpinThenPark { spins: limit } => {
+                    if spins < limit {
+                        spins += 1;
+                        std::hint::spin_loop();
+                    } else {
+                        self.ring.park();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Take the next command if one is published.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if nothing is waiting and
+    /// [`TryRecvError::Disconnected`] once every sender is dropped and the
+    /// ring is empty.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if let Some(value) = self.ring.try_pop() {
+            return Ok(value);
+        }
+        if self.ring.disconnected() {
+            return self.ring.try_pop().ok_or(TryRecvError::Disconnected);
+        }
+        Err(TryRecvError::Empty)
+    }
+
+    /// Blocking iterator over commands; ends once every sender is dropped
+    /// and the ring is drained.
+    pub fn iter(&self) -> RingIter<'_, T> {
+        RingIter { receiver: self }
+    }
+
+    /// Number of slots, a power of two.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+impl<T> Drop for RingReceiver<T> {
+    fn drop(&mut self) {
+        self.ring.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for RingReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingReceiver")
+            .field("capacity", &self.capacity())
+            .field("wait", &self.ring.wait)
+            .finish()
+    }
+}
+
+/// Borrowing iterator returned by [`RingReceiver::iter`].
+#[derive(Debug)]
+pub struct RingIter<'a, T> {
+    receiver: &'a RingReceiver<T>,
+}
+
+impl<T> Iterator for RingIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Owning iterator returned by [`RingReceiver::into_iter`].
+#[derive(Debug)]
+pub struct RingIntoIter<T> {
+    receiver: RingReceiver<T>,
+}
+
+impl<T> Iterator for RingIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl<T> IntoIterator for RingReceiver<T> {
+    type Item = T;
+    type IntoIter = RingIntoIter<T>;
+
+    fn into_iter(self) -> RingIntoIter<T> {
+        RingIntoIter { receiver: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingReceiver<T> {
+    type Item = T;
+    type IntoIter = RingIter<'a, T>;
+
+    fn into_iter(self) -> RingIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Create a command ring holding at least `capacity` commands (rounded up
+/// to a power of two, minimum two) whose consumer waits with `wait`.
+#[must_use]
+pub fn command_ring<T>(capacity: usize, wait: WaitStrategy) -> (RingSender<T>, RingReceiver<T>) {
+    let ring = Arc::new(Ring::new(capacity, wait));
+    (
+        RingSender {
+            ring: Arc::clone(&ring),
+        },
+        RingReceiver {
+            ring,
+            _not_sync: PhantomData,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRATEGIES: [WaitStrategy; 3] = [
+        WaitStrategy::BusySpin,
+        WaitStrategy::Yielding,
+        WaitStrategy::SpinThenPark { spins: 4 },
+    ];
+
+    #[test]
+    fn test_capacity_rounds_up_to_a_power_of_two() {
+        assert_eq!(
+            command_ring::<u8>(0, WaitStrategy::BusySpin).0.capacity(),
+            2
+        );
+        assert_eq!(
+            command_ring::<u8>(1, WaitStrategy::BusySpin).0.capacity(),
+            2
+        );
+        assert_eq!(
+            command_ring::<u8>(5, WaitStrategy::BusySpin).0.capacity(),
+            8
+        );
+        assert_eq!(
+            command_ring::<u8>(8, WaitStrategy::BusySpin).1.capacity(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_smallest_ring_reports_full_instead_of_overwriting() {
+        for capacity in [0, 1] {
+            let (sender, receiver) = command_ring(capacity, WaitStrategy::BusySpin);
+            sender.try_send(1).unwrap();
+            sender.try_send(2).unwrap();
+            assert!(matches!(sender.try_send(3), Err(TrySendError::Full(3))));
+            assert_eq!(receiver.try_recv(), Ok(1));
+            assert_eq!(receiver.try_recv(), Ok(2));
+            assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+        }
+    }
+
+    #[test]
+    fn test_fifo_full_and_disconnect() {
+        let (sender, receiver) = command_ring(2, WaitStrategy::BusySpin);
+        sender.try_send(1).unwrap();
+        sender.try_send(2).unwrap();
+        assert!(matches!(sender.try_send(3), Err(TrySendError::Full(3))));
+        assert_eq!(receiver.try_recv(), Ok(1));
+        sender.try_send(3).unwrap();
+        assert_eq!(receiver.try_recv(), Ok(2));
+        assert_eq!(receiver.try_recv(), Ok(3));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        sender.try_send(4).unwrap();
+        drop(sender);
+        assert_eq!(receiver.recv(), Ok(4));
+        assert_eq!(receiver.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn test_send_after_receiver_drop_returns_the_value() {
+        let (sender, receiver) = command_ring(4, WaitStrategy::default());
+        drop(receiver);
+        assert_eq!(sender.send(7), Err(SendError(7)));
+        assert!(matches!(
+            sender.try_send(8),
+            Err(TrySendError::Disconnected(8))
+        ));
+    }
+
+    #[test]
+    fn test_undelivered_values_are_dropped_with_the_ring() {
+        let marker = Arc::new(());
+        let (sender, receiver) = command_ring(4, WaitStrategy::BusySpin);
+        for _ in 0..3 {
+            sender.send(Arc::clone(&marker)).unwrap();
+        }
+        drop(receiver.try_recv());
+        drop((sender, receiver));
+        assert_eq!(Arc::strong_count(&marker), 1);
+    }
+
+    #[test]
+    fn test_many_producers_keep_per_producer_order() {
+        const PRODUCERS: u64 = 4;
+        const PER_PRODUCER: u64 = 5_000;
+        for wait in STRATEGIES {
+            // Large enough that a busy-spinning consumer sharing one core
+            // with the producers is not descheduled after every few sends.
+            let (sender, receiver) = command_ring(1024, wait);
+            let producers: Vec<_> = (0..PRODUCERS)
+                .map(|producer| {
+                    let sender = sender.clone();
+                    std::thread::spawn(move || {
+                        for i in 0..PER_PRODUCER {
+                            sender.send((producer, i)).unwrap();
+                        }
+                    })
+                })
+                .collect();
+            drop(sender);
+
+            let mut next = [0u64; PRODUCERS as usize];
+            for (producer, i) in &receiver {
+                assert_eq!(next[producer as usize], i, "{wait:?}");
+                next[producer as usize] += 1;
+            }
+            assert_eq!(next, [PER_PRODUCER; PRODUCERS as usize], "{wait:?}");
+            for producer in producers {
+                producer.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_parked_consumer_wakes_on_send_and_on_disconnect() {
+        let (sender, receiver) = command_ring(4, WaitStrategy::SpinThenPark { spins: 0 });
+        let consumer = std::thread::spawn(move || receiver.into_iter().collect::<Vec<u32>>());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        sender.send(1).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        sender.send(2).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        drop(sender);
+        assert_eq!(consumer.join().unwrap(), vec![1, 2]);
+    }
+}
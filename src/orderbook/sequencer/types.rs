@@ -124,6 +124,85 @@ pub enum SequencerCommand<T> {
     },
 }
 
+impl<T> SequencerCommand<T> {
+    /// The kind of this command, as recorded in journal entry headers.
+    #[must_use]
+    pub fn kind(&self) -> SequencerCommandKind {
+        match self {
+            Self::AddOrder(_) => SequencerCommandKind::AddOrder,
+            Self::CancelOrder(_) => SequencerCommandKind::CancelOrder,
+            Self::UpdateOrder(_) => SequencerCommandKind::UpdateOrder,
+            Self::MarketOrder { .. } => SequencerCommandKind::MarketOrder,
+            Self::MarketOrderByAmount { .. } => SequencerCommandKind::MarketOrderByAmount,
+            Self::CancelAll => SequencerCommandKind::CancelAll,
+            Self::CancelBySide { .. } => SequencerCommandKind::CancelBySide,
+            Self::CancelByUser { .. } => SequencerCommandKind::CancelByUser,
+            Self::CancelByPriceRange { .. } => SequencerCommandKind::CancelByPriceRange,
+            Self::EvictExpiredOrders { .. } => SequencerCommandKind::EvictExpiredOrders,
+        }
+    }
+}
+
+/// The variant of a [`SequencerCommand`], without its payload.
+///
+/// Journal entries record it as a one-byte tag in their header, so a
+/// reader can pick out commands of interest without decoding every
+/// payload. Tags are the variant's declaration index and, like the
+/// command variants themselves, are only ever appended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum SequencerCommandKind {
+    /// [`SequencerCommand::AddOrder`]
+    AddOrder = 0,
+    /// [`SequencerCommand::CancelOrder`]
+    CancelOrder = 1,
+    /// [`SequencerCommand::UpdateOrder`]
+    UpdateOrder = 2,
+    /// [`SequencerCommand::MarketOrder`]
+    MarketOrder = 3,
+    /// [`SequencerCommand::MarketOrderByAmount`]
+    MarketOrderByAmount = 4,
+    /// [`SequencerCommand::CancelAll`]
+    CancelAll = 5,
+    /// [`SequencerCommand::CancelBySide`]
+    CancelBySide = 6,
+    /// [`SequencerCommand::CancelByUser`]
+    CancelByUser = 7,
+    /// [`SequencerCommand::CancelByPriceRange`]
+    CancelByPriceRange = 8,
+    /// [`SequencerCommand::EvictExpiredOrders`]
+    EvictExpiredOrders = 9,
+}
+
+impl SequencerCommandKind {
+    /// The one-byte tag written to journal entry headers.
+    #[must_use]
+    #[inline]
+    pub fn tag(self) -> u8 {
+        self as u8
+    }
+
+    /// The kind with the given header tag, or `None` for a tag this build
+    /// does not know.
+    #[must_use]
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        Some(match tag {
+            0 => Self::AddOrder,
+            1 => Self::CancelOrder,
+            2 => Self::UpdateOrder,
+            3 => Self::MarketOrder,
+            4 => Self::MarketOrderByAmount,
+            5 => Self::CancelAll,
+            6 => Self::CancelBySide,
+            7 => Self::CancelByUser,
+            8 => Self::CancelByPriceRange,
+            9 => Self::EvictExpiredOrders,
+            _ => return None,
+        })
+    }
+}
+
 /// The outcome of executing a [`SequencerCommand`] against the order book.
 ///
 /// Each variant captures the result of the corresponding command, including
@@ -132,6 +211,10 @@ pub enum SequencerCommand<T> {
 /// Like [`SequencerCommand`], this enum is `#[non_exhaustive]`: new result
 /// shapes accompany new commands, so downstream `match` expressions must
 /// include a wildcard arm.
+// `TradeExecuted` carries the full `TradeResult` inline: it is the
+// common result of a crossing submit, so boxing it would only add an
+// allocation per trade.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum SequencerResult {
@@ -197,3 +280,19 @@ pub struct SequencerEvent<T> {
     /// The result of executing the command.
     pub result: SequencerResult,
 }
+
+/// The result of a command executed by a journaling
+/// [`BookExecutor`](crate::orderbook::executor::BookExecutor), together with
+/// the global sequence number its [`SequencerEvent`] was journaled under.
+///
+/// Sequence numbers order commands across every book of the executor, so
+/// a downstream consumer that records the last one it processed can
+/// resume from the journal without gaps or duplicates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedResult {
+    /// Sequence number the command was journaled under.
+    pub sequence_num: u64,
+
+    /// The result of executing the command.
+    pub result: SequencerResult,
+}
@@ -21,6 +21,7 @@
 //! ```
 
 use crate::orderbook::book_change_event::PriceLevelChangedEvent;
+use crate::orderbook::implied_volatility::GreeksSnapshot;
 use crate::orderbook::trade::TradeResult;
 
 /// Errors that can occur during event serialization or deserialization.
@@ -44,6 +45,11 @@ pub enum SerializationError {
     /// format mismatch).
     #[error("{0}")]
     TrailingBytes(String),
+
+    /// The serializer does not handle this kind of payload; carries the
+    /// payload kind.
+    #[error("serializer does not support {0} payloads")]
+    Unsupported(&'static str),
 }
 
 /// A pluggable serializer for order book events.
@@ -94,6 +100,32 @@ pub trait EventSerializer: Send + Sync + std::fmt::Debug {
         data: &[u8],
     ) -> Result<PriceLevelChangedEvent, SerializationError>;
 
+    /// Serialize a [`GreeksSnapshot`] into a byte buffer.
+    ///
+    /// The default returns [`SerializationError::Unsupported`], so
+    /// serializers written before Greeks snapshots existed keep compiling.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializationError`] if the snapshot cannot be serialized.
+    fn serialize_greeks(&self, snapshot: &GreeksSnapshot) -> Result<Vec<u8>, SerializationError> {
+        let _ = snapshot;
+        Err(SerializationError::Unsupported("greeks"))
+    }
+
+    /// Deserialize a [`GreeksSnapshot`] from a byte buffer.
+    ///
+    /// The default returns [`SerializationError::Unsupported`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializationError`] if the bytes are malformed or
+    /// incompatible with the expected format.
+    fn deserialize_greeks(&self, data: &[u8]) -> Result<GreeksSnapshot, SerializationError> {
+        let _ = data;
+        Err(SerializationError::Unsupported("greeks"))
+    }
+
     /// Returns the MIME-like content type identifier for this format.
     ///
     /// Consumers can use this value to select the correct deserializer.
@@ -148,6 +180,14 @@ impl EventSerializer for JsonEventSerializer {
         serde_json::from_slice(data).map_err(SerializationError::Json)
     }
 
+    fn serialize_greeks(&self, snapshot: &GreeksSnapshot) -> Result<Vec<u8>, SerializationError> {
+        serde_json::to_vec(snapshot).map_err(SerializationError::Json)
+    }
+
+    fn deserialize_greeks(&self, data: &[u8]) -> Result<GreeksSnapshot, SerializationError> {
+        serde_json::from_slice(data).map_err(SerializationError::Json)
+    }
+
     #[inline]
     fn content_type(&self) -> &'static str {
         "application/json"
@@ -234,6 +274,26 @@ impl EventSerializer for BincodeEventSerializer {
         Ok(value)
     }
 
+    fn serialize_greeks(&self, snapshot: &GreeksSnapshot) -> Result<Vec<u8>, SerializationError> {
+        bincode::serde::encode_to_vec(snapshot, bincode::config::standard())
+            .map_err(|e| SerializationError::Bincode(e.to_string()))
+    }
+
+    fn deserialize_greeks(&self, data: &[u8]) -> Result<GreeksSnapshot, SerializationError> {
+        let (value, bytes_read) = bincode::serde::decode_from_slice::<GreeksSnapshot, _>(
+            data,
+            bincode::config::standard(),
+        )
+        .map_err(|e| SerializationError::Bincode(e.to_string()))?;
+        if bytes_read != data.len() {
+            return Err(SerializationError::TrailingBytes(format!(
+                "trailing bytes after greeks payload: consumed {bytes_read} of {}",
+                data.len()
+            )));
+        }
+        Ok(value)
+    }
+
     #[inline]
     fn content_type(&self) -> &'static str {
         "application/x-bincode"
@@ -256,7 +316,33 @@ mod tests {
             side: Side::Buy,
             price: 50_000_000,
             quantity: 1_000,
+            order_count: 1,
             engine_seq: 0,
+            config_version: 0,
+        }
+    }
+
+    fn make_greeks_snapshot() -> GreeksSnapshot {
+        use crate::orderbook::implied_volatility::{
+            BlackScholes, ExerciseStyle, GreeksEntry, IVParams, IVQuality, OptionType,
+        };
+        let params = IVParams::call(100.0, 105.0, 0.25, 0.01);
+        GreeksSnapshot {
+            underlying: "BTC".to_string(),
+            spot: 100.0,
+            risk_free_rate: 0.01,
+            timestamp_ms: 1_700_000_000_000,
+            entries: vec![GreeksEntry {
+                symbol: "BTC-C-105".to_string(),
+                strike: 105.0,
+                time_to_expiry: 0.25,
+                option_type: OptionType::Call,
+                exercise_style: ExerciseStyle::European,
+                iv: 0.6,
+                quality: IVQuality::High,
+                greeks: BlackScholes::greeks(&params, 0.6),
+            }],
+            skipped: vec!["BTC-C-200".to_string()],
         }
     }
 
@@ -315,6 +401,69 @@ mod tests {
         assert_eq!(decoded, event);
     }
 
+    #[test]
+    fn test_json_roundtrip_greeks() {
+        let serializer = JsonEventSerializer::new();
+        let snapshot = make_greeks_snapshot();
+        let bytes = serializer.serialize_greeks(&snapshot);
+        assert!(bytes.is_ok());
+        let decoded = serializer.deserialize_greeks(&bytes.unwrap_or_default());
+        assert!(decoded.is_ok());
+        let decoded = decoded.unwrap_or_else(|_| make_greeks_snapshot());
+        // JSON float round-trips may differ in the last ulp.
+        assert_eq!(decoded.underlying, snapshot.underlying);
+        assert_eq!(decoded.timestamp_ms, snapshot.timestamp_ms);
+        assert_eq!(decoded.skipped, snapshot.skipped);
+        assert_eq!(decoded.entries.len(), 1);
+        assert_eq!(decoded.entries[0].symbol, snapshot.entries[0].symbol);
+        assert!((decoded.entries[0].greeks.delta - snapshot.entries[0].greeks.delta).abs() < 1e-12);
+    }
+
+    /// A serializer written against the trait before Greeks snapshots.
+    #[derive(Debug)]
+    struct TradeOnlySerializer;
+
+    impl EventSerializer for TradeOnlySerializer {
+        fn serialize_trade(&self, trade: &TradeResult) -> Result<Vec<u8>, SerializationError> {
+            JsonEventSerializer.serialize_trade(trade)
+        }
+
+        fn serialize_book_change(
+            &self,
+            event: &PriceLevelChangedEvent,
+        ) -> Result<Vec<u8>, SerializationError> {
+            JsonEventSerializer.serialize_book_change(event)
+        }
+
+        fn deserialize_trade(&self, data: &[u8]) -> Result<TradeResult, SerializationError> {
+            JsonEventSerializer.deserialize_trade(data)
+        }
+
+        fn deserialize_book_change(
+            &self,
+            data: &[u8],
+        ) -> Result<PriceLevelChangedEvent, SerializationError> {
+            JsonEventSerializer.deserialize_book_change(data)
+        }
+
+        fn content_type(&self) -> &'static str {
+            "application/json"
+        }
+    }
+
+    #[test]
+    fn test_greeks_are_unsupported_by_default() {
+        let serializer = TradeOnlySerializer;
+        assert!(matches!(
+            serializer.serialize_greeks(&make_greeks_snapshot()),
+            Err(SerializationError::Unsupported("greeks"))
+        ));
+        assert!(matches!(
+            serializer.deserialize_greeks(b"{}"),
+            Err(SerializationError::Unsupported("greeks"))
+        ));
+    }
+
     #[test]
     fn test_json_content_type() {
         let serializer = JsonEventSerializer::new();
@@ -430,6 +579,24 @@ mod tests {
             assert_eq!(decoded, event);
         }
 
+        #[test]
+        fn test_bincode_roundtrip_greeks() {
+            let serializer = BincodeEventSerializer::new();
+            let snapshot = make_greeks_snapshot();
+            let bytes = serializer.serialize_greeks(&snapshot);
+            assert!(bytes.is_ok());
+            let mut bytes = bytes.unwrap_or_default();
+
+            let decoded = serializer.deserialize_greeks(&bytes);
+            assert_eq!(decoded.ok(), Some(snapshot));
+
+            bytes.push(0);
+            assert!(matches!(
+                serializer.deserialize_greeks(&bytes),
+                Err(SerializationError::TrailingBytes(_))
+            ));
+        }
+
         #[test]
         fn test_bincode_content_type() {
             let serializer = BincodeEventSerializer::new();
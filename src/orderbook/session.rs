@@ -0,0 +1,463 @@
+//! Daily trading session schedule.
+//!
+//! A [`TradingSession`], part of the book's
+//! [`BookConfig`](super::book_config::BookConfig), describes one trading day
+//! as times of day in UTC: an optional pre-open auction window, continuous
+//! trading from the open to the close, and the closed period in between.
+//! The phase is read from the book clock, so an injected clock such as
+//! [`ReplayClock`](super::replay::ReplayClock) drives the schedule
+//! deterministically in tests and replay.
+//!
+//! The book enforces the schedule on its own:
+//!
+//! - while closed, every new order and every modification is rejected with
+//!   [`OrderBookError::MarketClosed`](super::error::OrderBookError::MarketClosed);
+//! - during the pre-open auction, orders may rest but market orders and
+//!   limit orders that would cross are rejected, so the book accumulates
+//!   interest without trading. The book has no uncrossing auction; the
+//!   resting interest simply becomes matchable at the open;
+//! - when a session opens, the market close used for `Day` orders is set
+//!   to its close;
+//! - at the close, `Day` orders are expired and, with
+//!   [`TradingSession::cancel_on_close`], every remaining resting order is
+//!   cancelled with [`CancelReason::SessionClosed`].
+//!
+//! Transitions run before the first gated operation that observes them on
+//! the book clock. A scheduler that needs the close processed on an idle
+//! book calls [`OrderBook::advance_session`], or
+//! [`BookManagerStd::advance_sessions`](super::manager::BookManagerStd::advance_sessions)
+//! across every book.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::order_state::CancelReason;
+use pricelevel::{Id, Side, TimestampMs};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing::trace;
+
+/// Milliseconds in one day.
+pub const MILLIS_PER_DAY: u64 = 86_400_000;
+
+/// Trading phase of a book: from its session schedule, or imposed by
+/// the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SessionPhase {
+    /// Outside the session: no new flow is accepted.
+    Closed,
+    /// Before the open: orders rest but nothing trades.
+    PreOpenAuction,
+    /// Between the open and the close: normal matching.
+    Continuous,
+    /// Halted by the circuit breaker: no new flow is accepted.
+    Halted,
+    /// Circuit breaker auction: orders rest but nothing trades.
+    VolatilityAuction,
+}
+
+impl std::fmt::Display for SessionPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionPhase::Closed => write!(f, "Closed"),
+            SessionPhase::PreOpenAuction => write!(f, "PreOpenAuction"),
+            SessionPhase::Continuous => write!(f, "Continuous"),
+            SessionPhase::Halted => write!(f, "Halted"),
+            SessionPhase::VolatilityAuction => write!(f, "VolatilityAuction"),
+        }
+    }
+}
+
+/// Daily trading schedule, in milliseconds after midnight UTC.
+///
+/// A close earlier than the open describes a session that spans
+/// midnight.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{SessionPhase, TradingSession};
+///
+/// const HOUR: u64 = 3_600_000;
+/// let session = TradingSession::new(9 * HOUR, 17 * HOUR).with_pre_open_auction_ms(HOUR);
+/// assert!(session.validate().is_ok());
+///
+/// assert_eq!(session.phase_at(7 * HOUR), SessionPhase::Closed);
+/// assert_eq!(session.phase_at(8 * HOUR), SessionPhase::PreOpenAuction);
+/// assert_eq!(session.phase_at(12 * HOUR), SessionPhase::Continuous);
+/// assert_eq!(session.phase_at(17 * HOUR), SessionPhase::Closed);
+/// assert_eq!(session.next_close_at(12 * HOUR), 17 * HOUR);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TradingSession {
+    /// Start of continuous trading.
+    pub open_ms: u64,
+    /// End of continuous trading; the session is closed from here on.
+    pub close_ms: u64,
+    /// Length of the pre-open auction window ending at the open; zero
+    /// opens straight into continuous trading.
+    #[serde(default)]
+    pub pre_open_auction_ms: u64,
+    /// Cancel every resting order at the close, not only `Day` orders.
+    #[serde(default)]
+    pub cancel_on_close: bool,
+}
+
+impl TradingSession {
+    /// Continuous trading from `open_ms` to `close_ms`, without a
+    /// pre-open auction or cancel on close.
+    #[must_use]
+    pub fn new(open_ms: u64, close_ms: u64) -> Self {
+        Self {
+            open_ms,
+            close_ms,
+            pre_open_auction_ms: 0,
+            cancel_on_close: false,
+        }
+    }
+
+    /// Set the length of the pre-open auction window.
+    #[must_use]
+    pub fn with_pre_open_auction_ms(mut self, pre_open_auction_ms: u64) -> Self {
+        self.pre_open_auction_ms = pre_open_auction_ms;
+        self
+    }
+
+    /// Cancel every resting order at the close.
+    #[must_use]
+    pub fn with_cancel_on_close(mut self, cancel_on_close: bool) -> Self {
+        self.cancel_on_close = cancel_on_close;
+        self
+    }
+
+    /// Check the schedule for consistency.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::InvalidConfiguration`] when the open or
+    /// close is not within one day, they are equal, or the pre-open
+    /// auction and continuous trading leave no closed period.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        if self.open_ms >= MILLIS_PER_DAY || self.close_ms >= MILLIS_PER_DAY {
+            return Err(OrderBookError::InvalidConfiguration {
+                message: format!(
+                    "session open {} and close {} must be below {MILLIS_PER_DAY} ms",
+                    self.open_ms, self.close_ms
+                ),
+            });
+        }
+        if self.open_ms == self.close_ms {
+            return Err(OrderBookError::InvalidConfiguration {
+                message: "session open and close must differ".to_string(),
+            });
+        }
+        if self
+            .continuous_ms()
+            .saturating_add(self.pre_open_auction_ms)
+            >= MILLIS_PER_DAY
+        {
+            return Err(OrderBookError::InvalidConfiguration {
+                message: "session leaves no closed period".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Phase of the session at `now_ms`, milliseconds since the Unix
+    /// epoch.
+    #[must_use]
+    pub fn phase_at(&self, now_ms: u64) -> SessionPhase {
+        let since_open = day_offset(self.open_ms, now_ms % MILLIS_PER_DAY);
+        if since_open < self.continuous_ms() {
+            SessionPhase::Continuous
+        } else if MILLIS_PER_DAY - since_open <= self.pre_open_auction_ms {
+            SessionPhase::PreOpenAuction
+        } else {
+            SessionPhase::Closed
+        }
+    }
+
+    /// First close at or after `now_ms`, in milliseconds since the Unix
+    /// epoch.
+    #[must_use]
+    pub fn next_close_at(&self, now_ms: u64) -> u64 {
+        now_ms + day_offset(now_ms % MILLIS_PER_DAY, self.close_ms)
+    }
+
+    /// Length of continuous trading.
+    fn continuous_ms(&self) -> u64 {
+        day_offset(self.open_ms, self.close_ms)
+    }
+}
+
+/// Milliseconds from time of day `from` forward to time of day `to`.
+fn day_offset(from: u64, to: u64) -> u64 {
+    (to + MILLIS_PER_DAY - from) % MILLIS_PER_DAY
+}
+
+/// Orders removed when a trading session closed, as returned by
+/// [`OrderBook::advance_session`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionClose {
+    /// The close that was processed, in milliseconds since the Unix epoch.
+    pub close_ms: u64,
+    /// `Day` (and due `Gtd`) orders expired at the close, in eviction order.
+    pub expired: Vec<Id>,
+    /// Orders cancelled by [`TradingSession::cancel_on_close`], bids
+    /// ascending then asks ascending.
+    pub cancelled: Vec<Id>,
+}
+
+/// Per-book schedule progress.
+#[derive(Debug, Default)]
+pub(crate) struct SessionState {
+    /// Mirrors `BookConfig::trading_session.is_some()` so books without a
+    /// schedule skip the clock read on every operation.
+    enabled: AtomicBool,
+    /// Close of the session last observed open; zero when none is.
+    open_until: AtomicU64,
+}
+
+impl SessionState {
+    /// Track a newly configured schedule, forgetting the progress of
+    /// the previous one.
+    pub(crate) fn configure(&self, session: Option<TradingSession>) {
+        self.open_until.store(0, Ordering::Release);
+        self.enabled.store(session.is_some(), Ordering::Release);
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// The configured trading session, if any.
+    #[must_use]
+    #[inline]
+    pub fn trading_session(&self) -> Option<TradingSession> {
+        self.config().trading_session
+    }
+
+    /// Phase of the trading session now on the book clock, or `None`
+    /// when no session is configured and the book trades continuously.
+    #[must_use]
+    pub fn session_phase(&self) -> Option<SessionPhase> {
+        if !self.session.enabled.load(Ordering::Acquire) {
+            return None;
+        }
+        let session = self.trading_session()?;
+        Some(session.phase_at(self.clock().now_millis().as_u64()))
+    }
+
+    /// Apply every session transition due on the book clock: expire `Day`
+    /// orders (and cancel the rest, with
+    /// [`TradingSession::cancel_on_close`]) at a close that has passed,
+    /// and set the market close of a session that has opened. Returns the
+    /// orders removed at the close, or `None` when no close was due.
+    ///
+    /// Gated operations apply due transitions themselves; a scheduler
+    /// calls this to process the close of a book that sees no traffic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{BookConfig, Clock, OrderBook, ReplayClock, TradingSession};
+    /// use pricelevel::{Id, Side, TimeInForce};
+    /// use std::sync::Arc;
+    ///
+    /// const HOUR: u64 = 3_600_000;
+    /// let clock = Arc::new(ReplayClock::new(10 * HOUR));
+    /// let book = OrderBook::<()>::with_clock("TEST", Arc::clone(&clock) as Arc<dyn Clock>);
+    /// book.reload_config(
+    ///     BookConfig::new().with_trading_session(TradingSession::new(9 * HOUR, 17 * HOUR)),
+    /// )
+    /// .expect("valid schedule");
+    ///
+    /// let day = Id::from_u64(1);
+    /// book.add_limit_order(day, 100, 1, Side::Buy, TimeInForce::Day, None)
+    ///     .expect("open");
+    ///
+    /// clock.advance_to(17 * HOUR);
+    /// let closed = book.advance_session().expect("close processed");
+    /// assert_eq!(closed.close_ms, 17 * HOUR);
+    /// assert_eq!(closed.expired, vec![day]);
+    /// assert!(book.advance_session().is_none());
+    /// ```
+    pub fn advance_session(&self) -> Option<SessionClose> {
+        if !self.session.enabled.load(Ordering::Acquire) {
+            return None;
+        }
+        let _gate = self
+            .submit_gate
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.advance_session_ungated(self.clock().now_millis().as_u64())
+    }
+
+    /// Apply due session transitions before a gated operation. Called by
+    /// the submit gate accessors before they acquire the gate, so it can
+    /// take the gate exclusively itself.
+    pub(super) fn run_session_schedule(&self) {
+        if !self.session.enabled.load(Ordering::Acquire) {
+            return;
+        }
+        let Some(session) = self.trading_session() else {
+            return;
+        };
+        let now = self.clock().now_millis().as_u64();
+        let open_until = self.session.open_until.load(Ordering::Acquire);
+        let due = match open_until {
+            0 => session.phase_at(now) != SessionPhase::Closed,
+            close => now >= close,
+        };
+        if due {
+            let _gate = self
+                .submit_gate
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            self.advance_session_ungated(now);
+        }
+    }
+
+    /// Phase admission enforces now: the circuit breaker's while it is
+    /// tripped, otherwise the trading session's. `None` when neither
+    /// restricts the book.
+    #[must_use]
+    pub fn trading_phase(&self) -> Option<SessionPhase> {
+        self.circuit_breaker_phase()
+            .or_else(|| self.session_phase())
+    }
+
+    /// Reject new flow the trading phase does not admit now. `crossing`
+    /// is consulted only during an auction, where orders that would trade
+    /// are refused.
+    pub(super) fn check_session(
+        &self,
+        crossing: impl FnOnce() -> bool,
+    ) -> Result<(), OrderBookError> {
+        match self.trading_phase() {
+            Some(phase @ (SessionPhase::Closed | SessionPhase::Halted)) => {
+                Err(OrderBookError::MarketClosed { phase })
+            }
+            Some(phase @ (SessionPhase::PreOpenAuction | SessionPhase::VolatilityAuction)) => {
+                if crossing() {
+                    Err(OrderBookError::MarketClosed { phase })
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// [`Self::check_session`] for a market order, which always trades,
+    /// recording an `OrderStatus::Rejected` transition for `order_id`
+    /// when it is refused.
+    pub(super) fn check_session_or_reject(&self, order_id: Id) -> Result<(), OrderBookError> {
+        let result = self.check_session(|| true);
+        if let Err(err) = &result {
+            self.track_state(
+                order_id,
+                super::order_state::OrderStatus::Rejected { reason: err.into() },
+            );
+        }
+        result
+    }
+
+    /// Process the close last observed open if `now_ms` has reached it,
+    /// then record the session open at `now_ms`, if any. The caller holds
+    /// the submit gate exclusively.
+    fn advance_session_ungated(&self, now_ms: u64) -> Option<SessionClose> {
+        let session = self.trading_session()?;
+        let mut closed = None;
+        let open_until = self.session.open_until.load(Ordering::Acquire);
+        if open_until != 0 && now_ms >= open_until {
+            self.session.open_until.store(0, Ordering::Release);
+            closed = Some(self.close_session(open_until, session.cancel_on_close, now_ms));
+        }
+        if self.session.open_until.load(Ordering::Acquire) == 0
+            && session.phase_at(now_ms) != SessionPhase::Closed
+        {
+            let close_ms = session.next_close_at(now_ms);
+            self.session.open_until.store(close_ms, Ordering::Release);
+            self.set_market_close_timestamp(close_ms);
+        }
+        closed
+    }
+
+    /// Expire `Day` orders at `close_ms` and, with `cancel_on_close`,
+    /// cancel every other resting order.
+    fn close_session(&self, close_ms: u64, cancel_on_close: bool, now_ms: u64) -> SessionClose {
+        self.set_market_close_timestamp(close_ms);
+        let expired: Vec<Id> = self
+            .evict_expired_orders_ungated(TimestampMs::new(now_ms))
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        let cancelled = if cancel_on_close {
+            let mut order_ids = self.collect_order_ids_by_side(Side::Buy);
+            order_ids.extend(self.collect_order_ids_by_side(Side::Sell));
+            self.cancel_order_batch_with_reason(&order_ids, CancelReason::SessionClosed)
+                .cancelled_order_ids()
+                .to_vec()
+        } else {
+            Vec::new()
+        };
+        trace!(
+            symbol = %self.symbol,
+            close_ms,
+            expired = expired.len(),
+            cancelled = cancelled.len(),
+            "trading session closed"
+        );
+        SessionClose {
+            close_ms,
+            expired,
+            cancelled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    #[test]
+    fn phases_follow_the_schedule_across_midnight() {
+        // 22:00 – 04:00 with a 30 minute pre-open from 21:30.
+        let session = TradingSession::new(22 * HOUR, 4 * HOUR).with_pre_open_auction_ms(HOUR / 2);
+        assert!(session.validate().is_ok());
+        let day = 3 * MILLIS_PER_DAY;
+        assert_eq!(session.phase_at(day + 21 * HOUR), SessionPhase::Closed);
+        assert_eq!(
+            session.phase_at(day + 21 * HOUR + HOUR / 2),
+            SessionPhase::PreOpenAuction
+        );
+        assert_eq!(session.phase_at(day + 23 * HOUR), SessionPhase::Continuous);
+        assert_eq!(session.phase_at(day + HOUR), SessionPhase::Continuous);
+        assert_eq!(session.phase_at(day + 4 * HOUR), SessionPhase::Closed);
+        assert_eq!(
+            session.next_close_at(day + 23 * HOUR),
+            day + MILLIS_PER_DAY + 4 * HOUR
+        );
+        assert_eq!(SessionPhase::PreOpenAuction.to_string(), "PreOpenAuction");
+    }
+
+    #[test]
+    fn validate_rejects_degenerate_schedules() {
+        for session in [
+            TradingSession::new(HOUR, HOUR),
+            TradingSession::new(MILLIS_PER_DAY, HOUR),
+            TradingSession::new(HOUR, 0).with_pre_open_auction_ms(HOUR),
+        ] {
+            assert!(
+                matches!(
+                    session.validate(),
+                    Err(OrderBookError::InvalidConfiguration { .. })
+                ),
+                "{session:?}"
+            );
+        }
+    }
+}
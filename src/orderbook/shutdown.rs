@@ -0,0 +1,650 @@
+//! Coordinated graceful shutdown across subsystems.
+//!
+//! Without coordination each background task stops on its own when the
+//! channel feeding it drops, in whatever order the owning values happen to
+//! be dropped. [`ShutdownCoordinator`] runs the teardown as an explicit
+//! sequence of [`ShutdownStage`]s instead:
+//!
+//! 1. **Stop intake** — the registered hooks stop new commands at the
+//!    edge, e.g. by closing a gateway listener.
+//! 2. **Drain queues** — every [`BookExecutor`] executes what is already
+//!    queued and hands its books back, then the drain steps run: NATS
+//!    publishers flush their batches, custom queues empty.
+//! 3. **Flush journals** — journals and event logs make what they were
+//!    given durable.
+//! 4. **Final snapshot** — every book is captured as a checksum-protected
+//!    [`OrderBookSnapshotPackage`] and, if a sink is configured, handed to
+//!    it.
+//!
+//! [`start`](ShutdownCoordinator::start) runs the sequence on a dedicated
+//! thread and returns a [`ShutdownHandle`], which can be awaited as a
+//! future or waited on with [`wait`](ShutdownHandle::wait). Both yield a
+//! [`ShutdownReport`] with the books, the final snapshots and every step
+//! that failed. A failing or panicking step is recorded and the sequence
+//! carries on, so one broken subsystem does not keep the others from
+//! flushing.
+//!
+//! ```
+//! use orderbook_rs::{BookExecutor, ExecutorConfig, OrderBook, ShutdownCoordinator};
+//!
+//! let mut executor = BookExecutor::<()>::new(ExecutorConfig::new());
+//! executor.spawn_book(OrderBook::new("BTC/USD"))?;
+//!
+//! let report = ShutdownCoordinator::new()
+//!     .with_executor(executor)
+//!     .on_flush("audit-file", || Ok::<(), std::io::Error>(()))
+//!     .with_final_snapshot(10)
+//!     .start()?
+//!     .wait();
+//! assert!(report.is_clean());
+//! assert!(report.snapshots().contains_key("BTC/USD"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::orderbook::OrderBook;
+use crate::orderbook::event_log::EventLogSink;
+use crate::orderbook::executor::BookExecutor;
+#[cfg(feature = "nats")]
+use crate::orderbook::nats::NatsTradePublisher;
+#[cfg(feature = "nats")]
+use crate::orderbook::nats_book_change::NatsBookChangePublisher;
+use crate::orderbook::sequencer::Journal;
+use crate::orderbook::snapshot::OrderBookSnapshotPackage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::task::{Context, Poll};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+/// Phase of a coordinated shutdown, in the order the phases run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum ShutdownStage {
+    /// The shutdown has not started yet.
+    Pending,
+    /// Intake hooks are stopping new commands.
+    StopIntake,
+    /// Executors and drain steps are emptying their queues.
+    DrainQueues,
+    /// Journals and logs are being flushed.
+    FlushJournals,
+    /// Final snapshots of every book are being taken.
+    FinalSnapshot,
+    /// Every stage has run.
+    Complete,
+}
+
+impl ShutdownStage {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Pending,
+            1 => Self::StopIntake,
+            2 => Self::DrainQueues,
+            3 => Self::FlushJournals,
+            4 => Self::FinalSnapshot,
+            _ => Self::Complete,
+        }
+    }
+}
+
+impl fmt::Display for ShutdownStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Pending => "pending",
+            Self::StopIntake => "stop intake",
+            Self::DrainQueues => "drain queues",
+            Self::FlushJournals => "flush journals",
+            Self::FinalSnapshot => "final snapshot",
+            Self::Complete => "complete",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A shutdown step that returned an error or panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("shutdown step `{step}` failed during {stage}: {message}")]
+pub struct ShutdownStepError {
+    /// Stage the step belongs to.
+    pub stage: ShutdownStage,
+    /// Name the step was registered under.
+    pub step: String,
+    /// The step's error, or its panic message.
+    pub message: String,
+}
+
+type StepResult = Result<(), String>;
+type StepFuture = Pin<Box<dyn Future<Output = StepResult> + Send>>;
+type SnapshotSink = Box<dyn FnMut(&str, &OrderBookSnapshotPackage) -> StepResult + Send>;
+
+enum StepKind {
+    Blocking(Box<dyn FnOnce() -> StepResult + Send>),
+    Async(Box<dyn FnOnce() -> StepFuture + Send>),
+}
+
+/// A registered step and the name failures are reported under.
+struct Step {
+    name: String,
+    kind: StepKind,
+}
+
+impl Step {
+    fn blocking<F, E>(name: &str, step: F) -> Self
+    where
+        F: FnOnce() -> Result<(), E> + Send + 'static,
+        E: fmt::Display,
+    {
+        Self {
+            name: name.to_string(),
+            kind: StepKind::Blocking(Box::new(move || step().map_err(|e| e.to_string()))),
+        }
+    }
+
+    fn future<F, Fut, E>(name: &str, step: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: fmt::Display,
+    {
+        Self {
+            name: name.to_string(),
+            kind: StepKind::Async(Box::new(move || {
+                let future = step();
+                Box::pin(async move { future.await.map_err(|e| e.to_string()) })
+            })),
+        }
+    }
+}
+
+/// Books, final snapshots and failures of a completed shutdown.
+pub struct ShutdownReport<T> {
+    books: HashMap<String, OrderBook<T>>,
+    snapshots: BTreeMap<String, OrderBookSnapshotPackage>,
+    errors: Vec<ShutdownStepError>,
+    elapsed: Duration,
+}
+
+impl<T> ShutdownReport<T> {
+    /// Books handed back by the executors or registered directly, by
+    /// symbol. Books whose executor worker panicked are missing.
+    #[must_use]
+    pub fn books(&self) -> &HashMap<String, OrderBook<T>> {
+        &self.books
+    }
+
+    /// Take ownership of the books.
+    #[must_use]
+    pub fn into_books(self) -> HashMap<String, OrderBook<T>> {
+        self.books
+    }
+
+    /// Final snapshot of each book, by symbol. Empty unless a final
+    /// snapshot or snapshot sink was configured.
+    #[must_use]
+    pub fn snapshots(&self) -> &BTreeMap<String, OrderBookSnapshotPackage> {
+        &self.snapshots
+    }
+
+    /// Every step that failed, in the order it ran.
+    #[must_use]
+    pub fn errors(&self) -> &[ShutdownStepError] {
+        &self.errors
+    }
+
+    /// Whether every step succeeded.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Wall-clock time the whole sequence took.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<T> fmt::Debug for ShutdownReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut symbols: Vec<&String> = self.books.keys().collect();
+        symbols.sort();
+        f.debug_struct("ShutdownReport")
+            .field("books", &symbols)
+            .field("snapshots", &self.snapshots.keys().collect::<Vec<_>>())
+            .field("errors", &self.errors)
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
+
+/// Builder and runner of a coordinated shutdown. See the
+/// [module docs](self).
+pub struct ShutdownCoordinator<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    executors: Vec<BookExecutor<T>>,
+    books: HashMap<String, OrderBook<T>>,
+    stop_intake: Vec<Step>,
+    drains: Vec<Step>,
+    flushes: Vec<Step>,
+    snapshot_depth: Option<usize>,
+    snapshot_sink: Option<SnapshotSink>,
+}
+
+impl<T> ShutdownCoordinator<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// A coordinator with nothing registered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            executors: Vec::new(),
+            books: HashMap::new(),
+            stop_intake: Vec::new(),
+            drains: Vec::new(),
+            flushes: Vec::new(),
+            snapshot_depth: None,
+            snapshot_sink: None,
+        }
+    }
+
+    /// Drain `executor` in the drain stage and collect its books. Owning
+    /// the executor is what stops it from accepting further commands.
+    #[must_use]
+    pub fn with_executor(mut self, executor: BookExecutor<T>) -> Self {
+        self.executors.push(executor);
+        self
+    }
+
+    /// Include `book`, which no executor runs, in the final snapshot and
+    /// the report.
+    #[must_use]
+    pub fn with_book(mut self, book: OrderBook<T>) -> Self {
+        self.books.insert(book.symbol().to_string(), book);
+        self
+    }
+
+    /// Include every book of `books`; see [`with_book`](Self::with_book).
+    #[must_use]
+    pub fn with_books<I>(self, books: I) -> Self
+    where
+        I: IntoIterator<Item = OrderBook<T>>,
+    {
+        books.into_iter().fold(self, Self::with_book)
+    }
+
+    /// Run `hook` first, to stop new commands reaching the engine.
+    #[must_use]
+    pub fn on_stop_intake<F, E>(mut self, name: &str, hook: F) -> Self
+    where
+        F: FnOnce() -> Result<(), E> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.stop_intake.push(Step::blocking(name, hook));
+        self
+    }
+
+    /// Run `drain` after the executors have drained.
+    #[must_use]
+    pub fn on_drain<F, E>(mut self, name: &str, drain: F) -> Self
+    where
+        F: FnOnce() -> Result<(), E> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.drains.push(Step::blocking(name, drain));
+        self
+    }
+
+    /// Await the future `drain` returns after the executors have drained.
+    /// The future runs on a current-thread Tokio runtime owned by the
+    /// shutdown, so it may await handles of tasks on another runtime.
+    #[must_use]
+    pub fn on_drain_async<F, Fut, E>(mut self, name: &str, drain: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.drains.push(Step::future(name, drain));
+        self
+    }
+
+    /// Run `flush` in the flush stage.
+    #[must_use]
+    pub fn on_flush<F, E>(mut self, name: &str, flush: F) -> Self
+    where
+        F: FnOnce() -> Result<(), E> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.flushes.push(Step::blocking(name, flush));
+        self
+    }
+
+    /// Flush `journal` in the flush stage.
+    #[must_use]
+    pub fn with_journal<J>(self, name: &str, journal: Arc<J>) -> Self
+    where
+        J: Journal<T> + ?Sized + 'static,
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        self.on_flush(name, move || journal.flush())
+    }
+
+    /// Flush `sink` in the flush stage.
+    #[must_use]
+    pub fn with_event_log(self, name: &str, sink: Arc<EventLogSink>) -> Self {
+        self.on_flush(name, move || sink.flush())
+    }
+
+    /// Drain `publisher`'s batch task in the drain stage.
+    #[cfg(feature = "nats")]
+    #[must_use]
+    pub fn with_nats_trade_publisher(self, name: &str, publisher: Arc<NatsTradePublisher>) -> Self {
+        self.on_drain_async(name, move || async move {
+            publisher.shutdown().await;
+            Ok::<(), std::convert::Infallible>(())
+        })
+    }
+
+    /// Drain `publisher`'s batch task in the drain stage.
+    #[cfg(feature = "nats")]
+    #[must_use]
+    pub fn with_nats_book_change_publisher(
+        self,
+        name: &str,
+        publisher: Arc<NatsBookChangePublisher>,
+    ) -> Self {
+        self.on_drain_async(name, move || async move {
+            publisher.shutdown().await;
+            Ok::<(), std::convert::Infallible>(())
+        })
+    }
+
+    /// Snapshot every book `depth` levels deep in the final stage; the
+    /// packages are returned in the report.
+    #[must_use]
+    pub fn with_final_snapshot(mut self, depth: usize) -> Self {
+        self.snapshot_depth = Some(depth);
+        self
+    }
+
+    /// Hand each final snapshot to `sink`, e.g. to persist it. Takes full
+    /// depth snapshots unless [`with_final_snapshot`](Self::with_final_snapshot)
+    /// sets a depth.
+    #[must_use]
+    pub fn with_snapshot_sink<F, E>(mut self, mut sink: F) -> Self
+    where
+        F: FnMut(&str, &OrderBookSnapshotPackage) -> Result<(), E> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.snapshot_sink = Some(Box::new(move |symbol, package| {
+            sink(symbol, package).map_err(|e| e.to_string())
+        }));
+        self
+    }
+
+    /// Run the shutdown on a dedicated thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the thread cannot be spawned; nothing has
+    /// been shut down in that case.
+    pub fn start(self) -> std::io::Result<ShutdownHandle<T>> {
+        let stage = Arc::new(AtomicU8::new(ShutdownStage::Pending as u8));
+        let (report_tx, report_rx) = oneshot::channel();
+        let thread_stage = Arc::clone(&stage);
+        let thread = std::thread::Builder::new()
+            .name("orderbook-shutdown".to_string())
+            .spawn(move || {
+                let report = self.execute(&thread_stage);
+                // The handle may have been dropped; the report is unwanted then.
+                let _ = report_tx.send(report);
+            })?;
+        Ok(ShutdownHandle {
+            stage,
+            report: report_rx,
+            thread: Some(thread),
+        })
+    }
+
+    /// Run the shutdown on the calling thread and return its report.
+    ///
+    /// Async drain steps are driven by a runtime this call creates, so do
+    /// not call it from inside a Tokio runtime when any are registered:
+    /// those steps would fail. Use [`start`](Self::start) there instead.
+    pub fn run(self) -> ShutdownReport<T> {
+        self.execute(&AtomicU8::new(ShutdownStage::Pending as u8))
+    }
+
+    fn execute(mut self, stage: &AtomicU8) -> ShutdownReport<T> {
+        let started = Instant::now();
+        let mut run = StageRunner {
+            stage,
+            runtime: None,
+            errors: Vec::new(),
+        };
+
+        run.enter(ShutdownStage::StopIntake);
+        run.steps(std::mem::take(&mut self.stop_intake));
+
+        run.enter(ShutdownStage::DrainQueues);
+        for executor in std::mem::take(&mut self.executors) {
+            for (symbol, book) in executor.shutdown() {
+                if self.books.insert(symbol.clone(), book).is_some() {
+                    run.fail(
+                        "executor",
+                        format!("two books trade {symbol}; kept the last"),
+                    );
+                }
+            }
+        }
+        run.steps(std::mem::take(&mut self.drains));
+
+        run.enter(ShutdownStage::FlushJournals);
+        run.steps(std::mem::take(&mut self.flushes));
+
+        run.enter(ShutdownStage::FinalSnapshot);
+        let mut snapshots = BTreeMap::new();
+        if self.snapshot_depth.is_some() || self.snapshot_sink.is_some() {
+            let depth = self.snapshot_depth.unwrap_or(usize::MAX);
+            let mut symbols: Vec<&String> = self.books.keys().collect();
+            symbols.sort();
+            for symbol in symbols {
+                let package = match self.books[symbol].create_snapshot_package(depth) {
+                    Ok(package) => package,
+                    Err(err) => {
+                        run.fail(&format!("snapshot {symbol}"), err.to_string());
+                        continue;
+                    }
+                };
+                if let Some(sink) = self.snapshot_sink.as_mut() {
+                    run.guarded(&format!("snapshot sink {symbol}"), || {
+                        sink(symbol, &package)
+                    });
+                }
+                snapshots.insert(symbol.clone(), package);
+            }
+        }
+
+        run.enter(ShutdownStage::Complete);
+        let elapsed = started.elapsed();
+        info!(
+            "Shutdown complete in {:?} with {} failed step(s)",
+            elapsed,
+            run.errors.len()
+        );
+        ShutdownReport {
+            books: std::mem::take(&mut self.books),
+            snapshots,
+            errors: run.errors,
+            elapsed,
+        }
+    }
+}
+
+impl<T> Default for ShutdownCoordinator<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for ShutdownCoordinator<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = |steps: &[Step]| steps.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        f.debug_struct("ShutdownCoordinator")
+            .field("executors", &self.executors.len())
+            .field("books", &self.books.len())
+            .field("stop_intake", &names(&self.stop_intake))
+            .field("drains", &names(&self.drains))
+            .field("flushes", &names(&self.flushes))
+            .field("snapshot_depth", &self.snapshot_depth)
+            .field("snapshot_sink", &self.snapshot_sink.is_some())
+            .finish()
+    }
+}
+
+/// Progress and failures of the stage sequence while it runs.
+struct StageRunner<'a> {
+    stage: &'a AtomicU8,
+    /// Created on the first async step
+    runtime: Option<tokio::runtime::Runtime>,
+    errors: Vec<ShutdownStepError>,
+}
+
+impl StageRunner<'_> {
+    fn current(&self) -> ShutdownStage {
+        ShutdownStage::from_u8(self.stage.load(Ordering::Acquire))
+    }
+
+    fn enter(&self, stage: ShutdownStage) {
+        info!("Shutdown stage: {}", stage);
+        self.stage.store(stage as u8, Ordering::Release);
+    }
+
+    fn fail(&mut self, step: &str, message: String) {
+        let error = ShutdownStepError {
+            stage: self.current(),
+            step: step.to_string(),
+            message,
+        };
+        warn!("{}", error);
+        self.errors.push(error);
+    }
+
+    /// Run `step`, recording an error or panic instead of propagating it.
+    fn guarded<F>(&mut self, name: &str, step: F)
+    where
+        F: FnOnce() -> StepResult,
+    {
+        let outcome = catch_unwind(AssertUnwindSafe(step)).unwrap_or_else(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "non-string panic payload".to_string());
+            Err(format!("panicked: {message}"))
+        });
+        if let Err(message) = outcome {
+            self.fail(name, message);
+        }
+    }
+
+    fn steps(&mut self, steps: Vec<Step>) {
+        for Step { name, kind } in steps {
+            match kind {
+                StepKind::Blocking(step) => self.guarded(&name, step),
+                StepKind::Async(step) => {
+                    let runtime = match self.runtime.take() {
+                        Some(runtime) => runtime,
+                        None => match tokio::runtime::Builder::new_current_thread()
+                            .enable_time()
+                            .build()
+                        {
+                            Ok(runtime) => runtime,
+                            Err(err) => {
+                                self.fail(&name, format!("cannot build runtime: {err}"));
+                                continue;
+                            }
+                        },
+                    };
+                    self.guarded(&name, || runtime.block_on(step()));
+                    self.runtime = Some(runtime);
+                }
+            }
+        }
+    }
+}
+
+/// Completion handle of a shutdown started with
+/// [`ShutdownCoordinator::start`].
+///
+/// Await it from async code, or call [`wait`](Self::wait) from a plain
+/// thread; either yields the [`ShutdownReport`].
+pub struct ShutdownHandle<T> {
+    stage: Arc<AtomicU8>,
+    report: oneshot::Receiver<ShutdownReport<T>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T> ShutdownHandle<T> {
+    /// Stage the shutdown is in right now.
+    #[must_use]
+    pub fn stage(&self) -> ShutdownStage {
+        ShutdownStage::from_u8(self.stage.load(Ordering::Acquire))
+    }
+
+    /// Whether every stage has run.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.stage() == ShutdownStage::Complete
+    }
+
+    /// Block until the shutdown completes and return its report.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from inside an async context (await the handle
+    /// instead), or if the shutdown thread itself panicked.
+    pub fn wait(mut self) -> ShutdownReport<T> {
+        let report = self.report.blocking_recv();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        report.expect("shutdown thread exited without a report")
+    }
+}
+
+impl<T> Future for ShutdownHandle<T> {
+    type Output = ShutdownReport<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ShutdownReport<T>> {
+        Pin::new(&mut self.get_mut().report)
+            .poll(cx)
+            .map(|report| report.expect("shutdown thread exited without a report"))
+    }
+}
+
+impl<T> fmt::Debug for ShutdownHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShutdownHandle")
+            .field("stage", &self.stage())
+            .finish()
+    }
+}
@@ -0,0 +1,760 @@
+//! Agent-based simulation: pluggable strategies trading on shared books.
+//!
+//! A [`Strategy`] reacts to three kinds of stimulus — a clock tick, a
+//! trade and a price-level change — by returning [`AgentAction`]s.
+//! [`SimulationRunner`] owns one or more books and the agents, and runs a
+//! single-threaded event loop:
+//!
+//! 1. Each tick advances the books' [`ReplayClock`] by the tick interval
+//!    and calls [`Strategy::on_tick`] on every agent in registration
+//!    order.
+//! 2. Actions execute first-in first-out. The trades and price-level
+//!    changes they cause are delivered to every agent, in registration
+//!    order, before the next action executes; the actions returned from
+//!    those callbacks join the back of the queue.
+//! 3. The tick ends when the queue is empty.
+//!
+//! Agents added with [`SimulationRunner::add_agent_with_latency`] see
+//! this loop through an [`AgentLatency`]: their actions reach the book,
+//! and book events reach them, only after a sampled delay. Delayed
+//! messages wait on a time-ordered schedule and are processed when the
+//! clock reaches them, possibly in a later tick, so a fast agent can
+//! take liquidity a slow one reacted to first. Strategies still read
+//! books directly through [`AgentContext::book`], which shows the
+//! current state; delayed views have to come from the callbacks.
+//!
+//! Orders go through each book's
+//! [speed bump](crate::orderbook::speed_bump). A book configured with a
+//! [`SpeedBump`](crate::SpeedBump) holds aggressive orders, and the
+//! runner releases them when the clock reaches their release time. A
+//! held order counts as submitted, or rejected, when it is released, and
+//! can be cancelled while held.
+//!
+//! Given deterministic strategies and a fixed
+//! [`seed`](SimulationRunner::set_seed) the whole run is deterministic.
+//! The runner assigns every order its id and its owner's user id, and
+//! attributes each fill to the agents on both sides, at the moment the
+//! book matches, to maintain their [`AgentStats`].
+
+use super::latency::AgentLatency;
+use super::rng::SplitMix64;
+use crate::orderbook::book::OrderBook;
+use crate::orderbook::book_change_event::PriceLevelChangedEvent;
+use crate::orderbook::error::OrderBookError;
+use crate::orderbook::replay::ReplayClock;
+use crate::orderbook::speed_bump::{BumpedOrder, SpeedBumpAdmission};
+use crate::orderbook::trade::TradeResult;
+use crate::orderbook::user_queries::UserOrderSummary;
+use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Default cap on actions executed within one tick; see
+/// [`SimulationRunner::set_max_actions_per_tick`].
+pub const DEFAULT_MAX_ACTIONS_PER_TICK: usize = 10_000;
+
+/// An order instruction from an agent. The runner fills in the order id
+/// and the agent's user id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgentAction {
+    /// Submit a limit order.
+    Limit {
+        /// Book to trade on
+        symbol: String,
+        /// Order side
+        side: Side,
+        /// Limit price
+        price: u128,
+        /// Order quantity
+        quantity: u64,
+        /// Time in force
+        time_in_force: TimeInForce,
+    },
+
+    /// Submit a market order.
+    Market {
+        /// Book to trade on
+        symbol: String,
+        /// Order side
+        side: Side,
+        /// Order quantity
+        quantity: u64,
+    },
+
+    /// Cancel one of the agent's resting orders.
+    Cancel {
+        /// Book the order rests on
+        symbol: String,
+        /// Order to cancel
+        order_id: Id,
+    },
+}
+
+impl AgentAction {
+    /// A GTC limit order.
+    #[must_use]
+    pub fn limit(symbol: &str, side: Side, price: u128, quantity: u64) -> Self {
+        Self::Limit {
+            symbol: symbol.to_string(),
+            side,
+            price,
+            quantity,
+            time_in_force: TimeInForce::Gtc,
+        }
+    }
+
+    /// A market order.
+    #[must_use]
+    pub fn market(symbol: &str, side: Side, quantity: u64) -> Self {
+        Self::Market {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+        }
+    }
+
+    /// A cancel.
+    #[must_use]
+    pub fn cancel(symbol: &str, order_id: Id) -> Self {
+        Self::Cancel {
+            symbol: symbol.to_string(),
+            order_id,
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        match self {
+            Self::Limit { symbol, .. }
+            | Self::Market { symbol, .. }
+            | Self::Cancel { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// An agent's net position in one symbol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentPosition {
+    /// Net quantity, positive when long
+    pub quantity: i128,
+    /// Cash flow from fills: sale proceeds minus purchase costs
+    pub cash: i128,
+}
+
+impl AgentPosition {
+    /// Cash plus the position valued at `mark_price`.
+    #[must_use]
+    pub fn pnl(&self, mark_price: u128) -> i128 {
+        let mark = i128::try_from(mark_price).unwrap_or(i128::MAX);
+        self.cash.saturating_add(self.quantity.saturating_mul(mark))
+    }
+}
+
+/// Activity and fills of one agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentStats {
+    /// Orders accepted by a book
+    pub orders_submitted: u64,
+    /// Orders and cancels a book rejected
+    pub rejected: u64,
+    /// Orders cancelled at the agent's request
+    pub cancelled: u64,
+    /// Fills on either side of a trade
+    pub fills: u64,
+    /// Quantity bought
+    pub bought: u64,
+    /// Quantity sold
+    pub sold: u64,
+    /// Position per symbol
+    pub positions: BTreeMap<String, AgentPosition>,
+}
+
+impl AgentStats {
+    fn record_fill(&mut self, symbol: &str, side: Side, price: u128, quantity: u64) {
+        let notional = i128::try_from(price)
+            .unwrap_or(i128::MAX)
+            .saturating_mul(i128::from(quantity));
+        let position = self.positions.entry(symbol.to_string()).or_default();
+        self.fills += 1;
+        match side {
+            Side::Buy => {
+                self.bought += quantity;
+                position.quantity += i128::from(quantity);
+                position.cash = position.cash.saturating_sub(notional);
+            }
+            Side::Sell => {
+                self.sold += quantity;
+                position.quantity -= i128::from(quantity);
+                position.cash = position.cash.saturating_add(notional);
+            }
+        }
+    }
+}
+
+/// Read-only view of the simulation handed to a [`Strategy`] callback.
+pub struct AgentContext<'a, T> {
+    now: u64,
+    user_id: Hash32,
+    stats: &'a AgentStats,
+    books: &'a [OrderBook<T>],
+}
+
+impl<'a, T> AgentContext<'a, T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Simulation time in milliseconds.
+    #[must_use]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// User id the runner stamps on this agent's orders.
+    #[must_use]
+    pub fn user_id(&self) -> Hash32 {
+        self.user_id
+    }
+
+    /// This agent's statistics so far.
+    #[must_use]
+    pub fn stats(&self) -> &'a AgentStats {
+        self.stats
+    }
+
+    /// The book trading `symbol`, if the runner has one.
+    #[must_use]
+    pub fn book(&self, symbol: &str) -> Option<&'a OrderBook<T>> {
+        self.books.iter().find(|book| book.symbol() == symbol)
+    }
+
+    /// Every book in the simulation, in registration order.
+    #[must_use]
+    pub fn books(&self) -> &'a [OrderBook<T>] {
+        self.books
+    }
+
+    /// This agent's resting orders on `symbol`.
+    #[must_use]
+    pub fn open_orders(&self, symbol: &str) -> Vec<UserOrderSummary> {
+        self.book(symbol)
+            .map(|book| book.orders_for_user(self.user_id))
+            .unwrap_or_default()
+    }
+}
+
+/// A trading agent driven by [`SimulationRunner`].
+pub trait Strategy<T = ()> {
+    /// Name used in the [`SimulationReport`].
+    fn name(&self) -> &str;
+
+    /// Called once per tick.
+    fn on_tick(&mut self, ctx: &AgentContext<'_, T>) -> Vec<AgentAction>;
+
+    /// Called for every trade on any book, including the agent's own.
+    fn on_trade(&mut self, _ctx: &AgentContext<'_, T>, _trade: &TradeResult) -> Vec<AgentAction> {
+        Vec::new()
+    }
+
+    /// Called for every price-level change on any book.
+    fn on_book_change(
+        &mut self,
+        _ctx: &AgentContext<'_, T>,
+        _symbol: &str,
+        _change: &PriceLevelChangedEvent,
+    ) -> Vec<AgentAction> {
+        Vec::new()
+    }
+}
+
+/// Per-agent results of a run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentReport {
+    /// [`Strategy::name`] of the agent
+    pub name: String,
+    /// User id the agent traded under
+    pub user_id: Hash32,
+    /// Activity and fills
+    pub stats: AgentStats,
+    /// Sum over symbols of [`AgentPosition::pnl`] at the book's mark price:
+    /// the last trade price, else the integer mid, else zero
+    pub pnl: i128,
+}
+
+/// Result of [`SimulationRunner::run`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Ticks run so far
+    pub ticks: u64,
+    /// Simulation time in milliseconds
+    pub now: u64,
+    /// Actions dropped because a tick hit its action cap
+    pub dropped_actions: u64,
+    /// One entry per agent, in registration order
+    pub agents: Vec<AgentReport>,
+}
+
+/// Book output captured by the listeners the runner installs.
+enum BookEvent {
+    Trade(TradeResult),
+    Change(String, PriceLevelChangedEvent),
+}
+
+/// A message in flight between an agent and the books.
+enum Scheduled {
+    /// A book event reaching an agent
+    Deliver(usize, Arc<BookEvent>),
+    /// Orders held by a book's speed bump falling due
+    Release(usize),
+    /// An agent's action reaching its book
+    Execute(usize, AgentAction),
+}
+
+impl Scheduled {
+    /// Deliveries due in the same millisecond go before executions, so
+    /// with zero latency every agent hears of an action's effects before
+    /// the next action runs. Held orders falling due go between the two:
+    /// they arrived before any action reaching the book in that
+    /// millisecond.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Deliver(..) => 0,
+            Self::Release(..) => 1,
+            Self::Execute(..) => 2,
+        }
+    }
+}
+
+struct Agent<T> {
+    strategy: Box<dyn Strategy<T>>,
+    user_id: Hash32,
+    stats: AgentStats,
+    latency: AgentLatency,
+}
+
+/// Deterministic event loop running [`Strategy`] agents against books.
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{AgentAction, AgentContext, OrderBook, SimulationRunner, Strategy};
+/// use pricelevel::Side;
+///
+/// struct Maker;
+/// impl Strategy for Maker {
+///     fn name(&self) -> &str { "maker" }
+///     fn on_tick(&mut self, ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+///         if ctx.open_orders("BTC").is_empty() {
+///             vec![AgentAction::limit("BTC", Side::Sell, 101, 5)]
+///         } else {
+///             Vec::new()
+///         }
+///     }
+/// }
+///
+/// struct Taker;
+/// impl Strategy for Taker {
+///     fn name(&self) -> &str { "taker" }
+///     fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+///         vec![AgentAction::market("BTC", Side::Buy, 1)]
+///     }
+/// }
+///
+/// let mut runner = SimulationRunner::new(1_000);
+/// runner.add_book(OrderBook::new("BTC"));
+/// runner.add_agent(Maker);
+/// runner.add_agent(Taker);
+///
+/// let report = runner.run(3);
+/// assert_eq!(report.agents[1].stats.bought, 3);
+/// assert_eq!(report.agents[0].stats.positions["BTC"].quantity, -3);
+/// ```
+pub struct SimulationRunner<T = ()> {
+    books: Vec<OrderBook<T>>,
+    agents: Vec<Agent<T>>,
+    clock: Arc<ReplayClock>,
+    events: Arc<Mutex<VecDeque<BookEvent>>>,
+    /// Agent index owning each order the runner submitted
+    owners: HashMap<Id, usize>,
+    /// Messages in flight, keyed by due time, [`Scheduled::rank`] and
+    /// scheduling order
+    scheduled: BTreeMap<(u64, u8, u64), Scheduled>,
+    next_sequence: u64,
+    rng: SplitMix64,
+    next_order_id: u64,
+    tick_interval: u64,
+    max_actions_per_tick: usize,
+    now: u64,
+    ticks: u64,
+    dropped_actions: u64,
+}
+
+impl<T> SimulationRunner<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Create a runner whose clock advances `tick_interval` milliseconds
+    /// per tick.
+    #[must_use]
+    pub fn new(tick_interval: u64) -> Self {
+        Self {
+            books: Vec::new(),
+            agents: Vec::new(),
+            clock: Arc::new(ReplayClock::default()),
+            events: Arc::new(Mutex::new(VecDeque::new())),
+            owners: HashMap::new(),
+            scheduled: BTreeMap::new(),
+            next_sequence: 0,
+            rng: SplitMix64(0),
+            next_order_id: 1,
+            tick_interval,
+            max_actions_per_tick: DEFAULT_MAX_ACTIONS_PER_TICK,
+            now: 0,
+            ticks: 0,
+            dropped_actions: 0,
+        }
+    }
+
+    /// Cap the actions executed within one tick, so agents that keep
+    /// reacting to each other cannot stall the run. Actions beyond the
+    /// cap are dropped and counted in
+    /// [`SimulationReport::dropped_actions`].
+    pub fn set_max_actions_per_tick(&mut self, max: usize) {
+        self.max_actions_per_tick = max;
+    }
+
+    /// Seed the sampler behind agents' [`AgentLatency`] models; equal
+    /// seeds give equal delays.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SplitMix64(seed);
+    }
+
+    /// Add a book. The runner installs its clock and replaces the book's
+    /// trade and price-level listeners with its own.
+    pub fn add_book(&mut self, mut book: OrderBook<T>) {
+        book.set_clock(self.clock.clone());
+        let events = Arc::clone(&self.events);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            push_event(&events, BookEvent::Trade(trade.clone()));
+        }));
+        let events = Arc::clone(&self.events);
+        let symbol = book.symbol().to_string();
+        book.set_price_level_listener(Arc::new(move |change| {
+            push_event(&events, BookEvent::Change(symbol.clone(), change));
+        }));
+        self.books.push(book);
+    }
+
+    /// Add an agent with zero latency and return the user id its orders
+    /// carry.
+    pub fn add_agent<S>(&mut self, strategy: S) -> Hash32
+    where
+        S: Strategy<T> + 'static,
+    {
+        self.push_agent(Box::new(strategy), AgentLatency::default())
+    }
+
+    /// Add an agent whose actions and market data are delayed by
+    /// `latency`, and return the user id its orders carry.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] if a latency model is
+    /// invalid.
+    pub fn add_agent_with_latency<S>(
+        &mut self,
+        strategy: S,
+        latency: AgentLatency,
+    ) -> Result<Hash32, OrderBookError>
+    where
+        S: Strategy<T> + 'static,
+    {
+        latency.validate()?;
+        Ok(self.push_agent(Box::new(strategy), latency))
+    }
+
+    fn push_agent(&mut self, strategy: Box<dyn Strategy<T>>, latency: AgentLatency) -> Hash32 {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&(self.agents.len() as u64 + 1).to_be_bytes());
+        let user_id = Hash32::new(bytes);
+        self.agents.push(Agent {
+            strategy,
+            user_id,
+            stats: AgentStats::default(),
+            latency,
+        });
+        user_id
+    }
+
+    /// The book trading `symbol`.
+    #[must_use]
+    pub fn book(&self, symbol: &str) -> Option<&OrderBook<T>> {
+        self.books.iter().find(|book| book.symbol() == symbol)
+    }
+
+    /// Simulation time in milliseconds.
+    #[must_use]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Run `ticks` ticks and report.
+    pub fn run(&mut self, ticks: u64) -> SimulationReport {
+        for _ in 0..ticks {
+            self.step();
+        }
+        self.report()
+    }
+
+    /// Run one tick: process the messages that fell due since the last
+    /// tick, call every agent's [`Strategy::on_tick`], then process what
+    /// is due by the tick's time.
+    pub fn step(&mut self) {
+        self.now = self.now.saturating_add(self.tick_interval);
+        self.ticks += 1;
+        let mut executed = 0;
+        self.process_until(self.now, &mut executed);
+        self.clock.advance_to(self.now);
+
+        for index in 0..self.agents.len() {
+            let agent = &mut self.agents[index];
+            let ctx = AgentContext {
+                now: self.now,
+                user_id: agent.user_id,
+                stats: &agent.stats,
+                books: &self.books,
+            };
+            let actions = agent.strategy.on_tick(&ctx);
+            self.submit(index, self.now, actions);
+        }
+        self.process_until(self.now, &mut executed);
+    }
+
+    /// Results so far, with positions marked to each book's current mark
+    /// price.
+    #[must_use]
+    pub fn report(&self) -> SimulationReport {
+        let agents = self
+            .agents
+            .iter()
+            .map(|agent| AgentReport {
+                name: agent.strategy.name().to_string(),
+                user_id: agent.user_id,
+                stats: agent.stats.clone(),
+                pnl: agent
+                    .stats
+                    .positions
+                    .iter()
+                    .map(|(symbol, position)| position.pnl(self.book(symbol).map_or(0, mark_price)))
+                    .fold(0i128, i128::saturating_add),
+            })
+            .collect();
+        SimulationReport {
+            ticks: self.ticks,
+            now: self.now,
+            dropped_actions: self.dropped_actions,
+            agents,
+        }
+    }
+
+    fn execute(&mut self, index: usize, action: &AgentAction) {
+        let Some(book_index) = self
+            .books
+            .iter()
+            .position(|book| book.symbol() == action.symbol())
+        else {
+            self.agents[index].stats.rejected += 1;
+            return;
+        };
+        let book = &self.books[book_index];
+        let user_id = self.agents[index].user_id;
+        let order = match *action {
+            AgentAction::Limit {
+                side,
+                price,
+                quantity,
+                time_in_force,
+                ..
+            } => BumpedOrder::Order(OrderType::Standard {
+                id: Id::sequential(self.next_order_id),
+                price: Price::new(price),
+                quantity: Quantity::new(quantity),
+                side,
+                user_id,
+                timestamp: book.clock().now_millis(),
+                time_in_force,
+                extra_fields: T::default(),
+            }),
+            AgentAction::Market { side, quantity, .. } => BumpedOrder::Market {
+                id: Id::sequential(self.next_order_id),
+                quantity,
+                side,
+                user_id,
+            },
+            AgentAction::Cancel { order_id, .. } => {
+                let cancelled = self.owners.get(&order_id) == Some(&index)
+                    && (book.cancel_held_order(order_id).is_some()
+                        || matches!(book.cancel_order(order_id), Ok(Some(_))));
+                let stats = &mut self.agents[index].stats;
+                if cancelled {
+                    stats.cancelled += 1;
+                } else {
+                    stats.rejected += 1;
+                }
+                return;
+            }
+        };
+        self.next_order_id += 1;
+        // Register the owner first: the submit may trade at once.
+        self.owners.insert(order.id(), index);
+        match book.submit_through_speed_bump(order) {
+            Ok(SpeedBumpAdmission::Processed(_)) => self.agents[index].stats.orders_submitted += 1,
+            Ok(SpeedBumpAdmission::Held { release_at }) => {
+                self.schedule(release_at, Scheduled::Release(book_index));
+            }
+            Err(_) => self.agents[index].stats.rejected += 1,
+        }
+    }
+
+    /// Process the orders `book_index`'s speed bump releases now and
+    /// count each against its owner.
+    fn release(&mut self, book_index: usize) {
+        for release in self.books[book_index].release_speed_bump() {
+            let Some(&index) = self.owners.get(&release.order_id) else {
+                continue;
+            };
+            let stats = &mut self.agents[index].stats;
+            match release.result {
+                Ok(_) => stats.orders_submitted += 1,
+                Err(_) => stats.rejected += 1,
+            }
+        }
+    }
+
+    /// Process scheduled messages due at or before `until`, in order,
+    /// counting executions against the tick's action cap.
+    fn process_until(&mut self, until: u64, executed: &mut usize) {
+        while let Some(entry) = self.scheduled.first_entry() {
+            let at = entry.key().0;
+            if at > until {
+                return;
+            }
+            self.clock.advance_to(at);
+            match entry.remove() {
+                Scheduled::Execute(index, action) => {
+                    if *executed == self.max_actions_per_tick {
+                        self.dropped_actions += 1;
+                        continue;
+                    }
+                    *executed += 1;
+                    self.execute(index, &action);
+                    self.dispatch(at);
+                }
+                Scheduled::Release(book_index) => {
+                    self.release(book_index);
+                    self.dispatch(at);
+                }
+                Scheduled::Deliver(index, event) => {
+                    let agent = &mut self.agents[index];
+                    let ctx = AgentContext {
+                        now: at,
+                        user_id: agent.user_id,
+                        stats: &agent.stats,
+                        books: &self.books,
+                    };
+                    let actions = match &*event {
+                        BookEvent::Trade(trade) => agent.strategy.on_trade(&ctx, trade),
+                        BookEvent::Change(symbol, change) => {
+                            agent.strategy.on_book_change(&ctx, symbol, change)
+                        }
+                    };
+                    self.submit(index, at, actions);
+                }
+            }
+        }
+    }
+
+    /// Schedule `actions` returned by agent `index` at time `now` to reach
+    /// the book after its submission delay.
+    fn submit(&mut self, index: usize, now: u64, actions: Vec<AgentAction>) {
+        for action in actions {
+            let delay = self.agents[index].latency.submission.sample(&mut self.rng);
+            self.schedule(now.saturating_add(delay), Scheduled::Execute(index, action));
+        }
+    }
+
+    fn schedule(&mut self, at: u64, message: Scheduled) {
+        let key = (at, message.rank(), self.next_sequence);
+        self.next_sequence += 1;
+        self.scheduled.insert(key, message);
+    }
+
+    /// Attribute fills of the book events captured at time `now` and
+    /// schedule their delivery to every agent after its market-data delay.
+    fn dispatch(&mut self, now: u64) {
+        loop {
+            let event = match self.events.lock() {
+                Ok(mut events) => events.pop_front(),
+                Err(poisoned) => poisoned.into_inner().pop_front(),
+            };
+            let Some(event) = event else {
+                return;
+            };
+            if let BookEvent::Trade(trade) = &event {
+                self.attribute_fills(trade);
+            }
+            let event = Arc::new(event);
+            for index in 0..self.agents.len() {
+                let delay = self.agents[index].latency.market_data.sample(&mut self.rng);
+                self.schedule(
+                    now.saturating_add(delay),
+                    Scheduled::Deliver(index, Arc::clone(&event)),
+                );
+            }
+        }
+    }
+
+    fn attribute_fills(&mut self, trade: &TradeResult) {
+        for fill in trade.match_result.trades().as_vec() {
+            let price = fill.price().as_u128();
+            let quantity = fill.quantity().as_u64();
+            let taker_side = fill.taker_side();
+            let maker_side = match taker_side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            for (order_id, side) in [
+                (fill.taker_order_id(), taker_side),
+                (fill.maker_order_id(), maker_side),
+            ] {
+                if let Some(&index) = self.owners.get(&order_id) {
+                    self.agents[index]
+                        .stats
+                        .record_fill(&trade.symbol, side, price, quantity);
+                }
+            }
+        }
+    }
+}
+
+fn push_event(events: &Mutex<VecDeque<BookEvent>>, event: BookEvent) {
+    match events.lock() {
+        Ok(mut events) => events.push_back(event),
+        Err(poisoned) => poisoned.into_inner().push_back(event),
+    }
+}
+
+/// Last trade price, else the integer mid, else zero.
+fn mark_price<T>(book: &OrderBook<T>) -> u128
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    if let Some(price) = book.last_trade_price() {
+        return price;
+    }
+    let bbo = book.bbo();
+    match (bbo.best_bid, bbo.best_ask) {
+        (Some(bid), Some(ask)) => bid.midpoint(ask),
+        (Some(price), None) | (None, Some(price)) => price,
+        (None, None) => 0,
+    }
+}
@@ -0,0 +1,375 @@
+//! Parent-order execution algorithms for [`SimulationRunner`] runs.
+//!
+//! An [`ExecutionAlgo`] is a [`Strategy`] that works one parent order on
+//! one book by sending market child orders as the simulation clock
+//! advances. Its [`ExecutionSchedule`] decides how much of the parent is
+//! due at each tick:
+//!
+//! - **TWAP** — equal slices released at evenly spaced times over a
+//!   horizon;
+//! - **VWAP** — slices sized by a volume profile, one bucket per equal
+//!   share of the horizon. The profile is the expected volume per bucket,
+//!   e.g. the volume column of historical candles over the same horizon;
+//! - **POV** — a target share of the volume other participants trade on
+//!   the book since the algo started, with no horizon.
+//!
+//! The algo records the book's mid price at its first tick as the
+//! arrival price and reports the implementation shortfall of its fills
+//! against it through an [`ExecutionHandle`], which stays readable after
+//! the runner takes ownership of the algo.
+//!
+//! A child that finds no liquidity is not retried: market orders do not
+//! rest, so its quantity shows up in [`ExecutionReport::unfilled`].
+//!
+//! [`SimulationRunner`]: super::SimulationRunner
+
+use super::agents::{AgentAction, AgentContext, Strategy};
+use crate::orderbook::error::OrderBookError;
+use crate::orderbook::trade::TradeResult;
+use pricelevel::Side;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// How an [`ExecutionAlgo`] spreads its parent order over time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionSchedule {
+    /// Time-weighted: `slices` equal slices, the first at the start and
+    /// the others every `duration / slices` milliseconds.
+    Twap {
+        /// Number of slices
+        slices: u32,
+        /// Horizon in milliseconds
+        duration: u64,
+    },
+
+    /// Volume-weighted: one slice per entry of `profile`, sized in
+    /// proportion to it and released at the start of its bucket.
+    Vwap {
+        /// Expected volume of each bucket, in order
+        profile: Vec<u64>,
+        /// Horizon in milliseconds, split into equal buckets
+        duration: u64,
+    },
+
+    /// Percent of volume: keep the quantity sent at `participation_bps`
+    /// basis points of the volume other participants traded on the book
+    /// since the start.
+    Pov {
+        /// Target participation in basis points of others' volume
+        participation_bps: u32,
+    },
+}
+
+impl ExecutionSchedule {
+    /// Check the schedule can release its whole parent order.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] if a TWAP has no slice, a
+    /// VWAP profile sums to zero or a POV participation is zero.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        let valid = match self {
+            Self::Twap { slices, .. } => *slices > 0,
+            Self::Vwap { profile, .. } => profile.iter().any(|&volume| volume > 0),
+            Self::Pov { participation_bps } => *participation_bps > 0,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(OrderBookError::InvalidConfiguration {
+                message: format!("invalid execution schedule {self:?}"),
+            })
+        }
+    }
+
+    /// Cumulative quantity of a `quantity` parent due `elapsed`
+    /// milliseconds after the start, given `volume` traded by others.
+    fn target(&self, quantity: u64, elapsed: u64, volume: u64) -> u64 {
+        let due = match self {
+            Self::Twap { slices, duration } => {
+                let released = released_buckets(*slices as usize, *duration, elapsed);
+                u128::from(quantity) * released as u128 / u128::from(*slices)
+            }
+            Self::Vwap { profile, duration } => {
+                let released = released_buckets(profile.len(), *duration, elapsed);
+                let total: u128 = profile.iter().map(|&volume| u128::from(volume)).sum();
+                let done: u128 = profile[..released]
+                    .iter()
+                    .map(|&volume| u128::from(volume))
+                    .sum();
+                u128::from(quantity) * done / total
+            }
+            Self::Pov { participation_bps } => {
+                u128::from(volume) * u128::from(*participation_bps) / 10_000
+            }
+        };
+        u64::try_from(due).unwrap_or(u64::MAX).min(quantity)
+    }
+}
+
+/// Buckets of `count` equal buckets over `duration` that have started
+/// `elapsed` milliseconds in: at least the first, at most all of them.
+fn released_buckets(count: usize, duration: u64, elapsed: u64) -> usize {
+    if elapsed >= duration {
+        return count;
+    }
+    let started = u128::from(elapsed) * count as u128 / u128::from(duration);
+    (started as usize + 1).min(count)
+}
+
+/// Progress and cost of an [`ExecutionAlgo`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    /// Book the parent order works on
+    pub symbol: String,
+    /// Parent order side
+    pub side: Side,
+    /// Parent order quantity
+    pub quantity: u64,
+    /// Book mid price at the first tick, else its last trade price
+    pub arrival_price: Option<u128>,
+    /// Simulation time of the first tick, in milliseconds
+    pub started_at: Option<u64>,
+    /// Simulation time the last unit filled, in milliseconds
+    pub completed_at: Option<u64>,
+    /// Child orders sent
+    pub child_orders: u64,
+    /// Quantity sent in child orders
+    pub sent: u64,
+    /// Quantity filled
+    pub filled: u64,
+    /// Sum of price times quantity over the fills
+    pub notional: u128,
+    /// Volume other participants traded on the book since the start
+    pub market_volume: u64,
+}
+
+impl ExecutionReport {
+    fn new(symbol: &str, side: Side, quantity: u64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+            arrival_price: None,
+            started_at: None,
+            completed_at: None,
+            child_orders: 0,
+            sent: 0,
+            filled: 0,
+            notional: 0,
+            market_volume: 0,
+        }
+    }
+
+    /// Parent quantity not filled.
+    #[must_use]
+    pub fn unfilled(&self) -> u64 {
+        self.quantity.saturating_sub(self.filled)
+    }
+
+    /// Volume-weighted fill price, if anything filled.
+    #[must_use]
+    pub fn avg_price(&self) -> Option<f64> {
+        (self.filled > 0).then(|| self.notional as f64 / self.filled as f64)
+    }
+
+    /// Execution cost per unit filled against the arrival price, positive
+    /// when the fills were worse than arrival: paid above it on a buy,
+    /// received below it on a sell.
+    #[must_use]
+    pub fn shortfall(&self) -> Option<f64> {
+        let avg = self.avg_price()?;
+        let arrival = self.arrival_price? as f64;
+        Some(match self.side {
+            Side::Buy => avg - arrival,
+            Side::Sell => arrival - avg,
+        })
+    }
+
+    /// [`Self::shortfall`] in basis points of the arrival price.
+    #[must_use]
+    pub fn shortfall_bps(&self) -> Option<f64> {
+        let arrival = self.arrival_price.filter(|&price| price > 0)? as f64;
+        Some(self.shortfall()? / arrival * 10_000.0)
+    }
+
+    /// Total execution cost of the filled quantity against the arrival
+    /// price, in price units times quantity.
+    #[must_use]
+    pub fn shortfall_cost(&self) -> Option<f64> {
+        Some(self.shortfall()? * self.filled as f64)
+    }
+}
+
+/// Shared view of an [`ExecutionAlgo`]'s [`ExecutionReport`].
+#[derive(Debug, Clone)]
+pub struct ExecutionHandle(Arc<Mutex<ExecutionReport>>);
+
+impl ExecutionHandle {
+    /// The algo's progress so far.
+    #[must_use]
+    pub fn report(&self) -> ExecutionReport {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ExecutionReport> {
+        match self.0.lock() {
+            Ok(report) => report,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// A [`Strategy`] working one parent order on an [`ExecutionSchedule`].
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{ExecutionAlgo, ExecutionSchedule, OrderBook, SimulationRunner};
+/// use pricelevel::{Id, Side, TimeInForce};
+///
+/// let book = OrderBook::<()>::new("BTC");
+/// book.add_limit_order(Id::from_u64(1), 99, 50, Side::Buy, TimeInForce::Gtc, None)?;
+/// book.add_limit_order(Id::from_u64(2), 101, 50, Side::Sell, TimeInForce::Gtc, None)?;
+///
+/// let twap = ExecutionAlgo::new(
+///     "BTC",
+///     Side::Buy,
+///     20,
+///     ExecutionSchedule::Twap { slices: 4, duration: 4_000 },
+/// )?;
+/// let handle = twap.handle();
+///
+/// let mut runner = SimulationRunner::new(1_000);
+/// runner.add_book(book);
+/// runner.add_agent(twap);
+/// runner.run(4);
+///
+/// let report = handle.report();
+/// assert_eq!((report.child_orders, report.filled), (4, 20));
+/// assert_eq!(report.arrival_price, Some(100));
+/// assert_eq!(report.shortfall(), Some(1.0));
+/// # Ok::<(), orderbook_rs::OrderBookError>(())
+/// ```
+#[derive(Debug)]
+pub struct ExecutionAlgo {
+    name: String,
+    schedule: ExecutionSchedule,
+    report: ExecutionHandle,
+    /// Traded volume on the book seen since the start, own fills included
+    seen_volume: u64,
+}
+
+impl ExecutionAlgo {
+    /// An algo buying or selling `quantity` on `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] if the schedule is invalid,
+    /// see [`ExecutionSchedule::validate`].
+    pub fn new(
+        symbol: &str,
+        side: Side,
+        quantity: u64,
+        schedule: ExecutionSchedule,
+    ) -> Result<Self, OrderBookError> {
+        schedule.validate()?;
+        let kind = match schedule {
+            ExecutionSchedule::Twap { .. } => "twap",
+            ExecutionSchedule::Vwap { .. } => "vwap",
+            ExecutionSchedule::Pov { .. } => "pov",
+        };
+        Ok(Self {
+            name: format!("{kind}:{symbol}"),
+            schedule,
+            report: ExecutionHandle(Arc::new(Mutex::new(ExecutionReport::new(
+                symbol, side, quantity,
+            )))),
+            seen_volume: 0,
+        })
+    }
+
+    /// Use `name` in the [`SimulationReport`](super::SimulationReport)
+    /// instead of the default `"<kind>:<symbol>"`.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// The schedule the algo follows.
+    #[must_use]
+    pub fn schedule(&self) -> &ExecutionSchedule {
+        &self.schedule
+    }
+
+    /// A handle to the algo's report, valid after the runner owns it.
+    #[must_use]
+    pub fn handle(&self) -> ExecutionHandle {
+        self.report.clone()
+    }
+
+    /// Refresh the fills from the agent's position on the symbol.
+    fn record_fills<T>(report: &mut ExecutionReport, ctx: &AgentContext<'_, T>)
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        let Some(position) = ctx.stats().positions.get(&report.symbol) else {
+            return;
+        };
+        report.filled = u64::try_from(position.quantity.unsigned_abs()).unwrap_or(u64::MAX);
+        report.notional = position.cash.unsigned_abs();
+        if report.completed_at.is_none() && report.filled >= report.quantity {
+            report.completed_at = Some(ctx.now());
+        }
+    }
+}
+
+impl<T> Strategy<T> for ExecutionAlgo
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_tick(&mut self, ctx: &AgentContext<'_, T>) -> Vec<AgentAction> {
+        let mut report = self.report.lock();
+        let started_at = *report.started_at.get_or_insert(ctx.now());
+        if report.arrival_price.is_none() && started_at == ctx.now() {
+            report.arrival_price = ctx
+                .book(&report.symbol)
+                .and_then(|book| book.bbo().midpoint().or_else(|| book.last_trade_price()));
+        }
+        Self::record_fills(&mut report, ctx);
+        report.market_volume = self.seen_volume.saturating_sub(report.filled);
+
+        let elapsed = ctx.now().saturating_sub(started_at);
+        let target = self
+            .schedule
+            .target(report.quantity, elapsed, report.market_volume);
+        let child = target.saturating_sub(report.sent);
+        if child == 0 {
+            return Vec::new();
+        }
+        report.sent += child;
+        report.child_orders += 1;
+        vec![AgentAction::market(&report.symbol, report.side, child)]
+    }
+
+    fn on_trade(&mut self, ctx: &AgentContext<'_, T>, trade: &TradeResult) -> Vec<AgentAction> {
+        let mut report = self.report.lock();
+        if report.started_at.is_none() || trade.symbol != report.symbol {
+            return Vec::new();
+        }
+        self.seen_volume = self.seen_volume.saturating_add(
+            trade
+                .match_result
+                .executed_quantity()
+                .map_or(0, |q| q.as_u64()),
+        );
+        Self::record_fills(&mut report, ctx);
+        report.market_volume = self.seen_volume.saturating_sub(report.filled);
+        Vec::new()
+    }
+}
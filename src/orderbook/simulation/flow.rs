@@ -0,0 +1,579 @@
+//! Synthetic order flow from parameterized stochastic models.
+//!
+//! [`MarketSimulator`] is an endless iterator of [`SimulatedEvent`]s:
+//! limit orders, market orders and cancels of previously generated limit
+//! orders, timestamped by an [`ArrivalProcess`].
+//!
+//! - [`ArrivalProcess::Poisson`] — independent arrivals at a constant rate.
+//! - [`ArrivalProcess::Hawkes`] — self-exciting arrivals: every event
+//!   raises the intensity by `excitation`, which decays back to the
+//!   baseline at rate `decay`, reproducing the bursty clustering of real
+//!   order flow. Simulated by Ogata thinning.
+//!
+//! Each arrival is a cancel with probability
+//! [`SimulationConfig::cancel_ratio`], a market order with probability
+//! [`SimulationConfig::market_ratio`], and otherwise a limit order placed
+//! a [`SizeDistribution`]-drawn number of ticks away from the reference
+//! price. Sizes come from their own distributions.
+//!
+//! The generator is driven by a seeded PRNG, so the same
+//! [`SimulationConfig`] yields the same event stream on a given platform,
+//! which makes it suitable for benchmark workloads and reproducible
+//! tests. It does not observe the book: a cancel may target an order that
+//! has since filled, which the book reports as not found.
+
+use super::rng::SplitMix64;
+use crate::orderbook::book::OrderBook;
+use crate::orderbook::error::OrderBookError;
+use pricelevel::{Hash32, Id, Side, TimeInForce};
+use serde::{Deserialize, Serialize};
+
+const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+
+/// Timing of order arrivals. Rates are events per second.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ArrivalProcess {
+    /// Exponential inter-arrival times at a constant rate.
+    Poisson {
+        /// Arrivals per second
+        rate: f64,
+    },
+
+    /// Self-exciting process with intensity
+    /// `baseline + Σ excitation · e^(−decay · (t − tᵢ))` over past
+    /// arrivals `tᵢ`. Stationary only when `excitation < decay`.
+    Hawkes {
+        /// Background arrivals per second
+        baseline: f64,
+        /// Intensity jump per arrival, per second
+        excitation: f64,
+        /// Decay rate of the excitation, per second
+        decay: f64,
+    },
+}
+
+/// Distribution of a positive integer (a size, or an offset in ticks).
+/// Every draw is at least `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SizeDistribution {
+    /// Always the same value.
+    Fixed(u64),
+
+    /// Uniform over `min..=max`.
+    Uniform {
+        /// Smallest value
+        min: u64,
+        /// Largest value
+        max: u64,
+    },
+
+    /// Geometric on `1, 2, 3, …` with the given mean.
+    Geometric {
+        /// Mean value, at least `1.0`
+        mean: f64,
+    },
+
+    /// `round(e^(mu + sigma · Z))` for a standard normal `Z`.
+    LogNormal {
+        /// Mean of the underlying normal
+        mu: f64,
+        /// Standard deviation of the underlying normal
+        sigma: f64,
+    },
+}
+
+/// Parameters of a [`MarketSimulator`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    /// PRNG seed; equal seeds give equal event streams.
+    pub seed: u64,
+
+    /// Arrival timing.
+    pub arrivals: ArrivalProcess,
+
+    /// Probability that an arrival cancels a live generated order.
+    pub cancel_ratio: f64,
+
+    /// Probability that an arrival is a market order.
+    pub market_ratio: f64,
+
+    /// Reference price limit orders are placed around.
+    pub mid_price: u128,
+
+    /// Price increment between levels.
+    pub tick_size: u128,
+
+    /// Distance of a limit order from `mid_price`, in ticks. Bids rest
+    /// below the reference price and asks above it.
+    pub price_offset_ticks: SizeDistribution,
+
+    /// Limit order quantity.
+    pub limit_size: SizeDistribution,
+
+    /// Market order quantity.
+    pub market_size: SizeDistribution,
+
+    /// Number of distinct users orders are spread over.
+    pub users: u8,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x5EED,
+            arrivals: ArrivalProcess::Poisson { rate: 1_000.0 },
+            cancel_ratio: 0.3,
+            market_ratio: 0.1,
+            mid_price: 10_000,
+            tick_size: 1,
+            price_offset_ticks: SizeDistribution::Geometric { mean: 4.0 },
+            limit_size: SizeDistribution::LogNormal {
+                mu: 3.0,
+                sigma: 1.0,
+            },
+            market_size: SizeDistribution::Uniform { min: 1, max: 20 },
+            users: 4,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Create the default configuration.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the PRNG seed.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the arrival process.
+    #[inline]
+    #[must_use]
+    pub fn with_arrivals(mut self, arrivals: ArrivalProcess) -> Self {
+        self.arrivals = arrivals;
+        self
+    }
+
+    /// Set the cancel and market order probabilities.
+    #[inline]
+    #[must_use]
+    pub fn with_ratios(mut self, cancel_ratio: f64, market_ratio: f64) -> Self {
+        self.cancel_ratio = cancel_ratio;
+        self.market_ratio = market_ratio;
+        self
+    }
+
+    /// Set the reference price and tick size.
+    #[inline]
+    #[must_use]
+    pub fn with_price(mut self, mid_price: u128, tick_size: u128) -> Self {
+        self.mid_price = mid_price;
+        self.tick_size = tick_size;
+        self
+    }
+
+    /// Set the limit order offset distribution, in ticks.
+    #[inline]
+    #[must_use]
+    pub fn with_price_offset_ticks(mut self, offset: SizeDistribution) -> Self {
+        self.price_offset_ticks = offset;
+        self
+    }
+
+    /// Set the limit and market order size distributions.
+    #[inline]
+    #[must_use]
+    pub fn with_sizes(
+        mut self,
+        limit_size: SizeDistribution,
+        market_size: SizeDistribution,
+    ) -> Self {
+        self.limit_size = limit_size;
+        self.market_size = market_size;
+        self
+    }
+
+    /// Set the number of distinct users.
+    #[inline]
+    #[must_use]
+    pub fn with_users(mut self, users: u8) -> Self {
+        self.users = users;
+        self
+    }
+
+    /// Check that every parameter is usable.
+    ///
+    /// # Errors
+    /// [`OrderBookError::InvalidConfiguration`] for a non-positive or
+    /// non-finite rate, a non-stationary Hawkes process
+    /// (`excitation >= decay`), ratios outside `0..=1` or summing above
+    /// `1`, a zero tick size or reference price, an invalid size
+    /// distribution, or zero users.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        match self.arrivals {
+            ArrivalProcess::Poisson { rate } => positive("poisson rate", rate)?,
+            ArrivalProcess::Hawkes {
+                baseline,
+                excitation,
+                decay,
+            } => {
+                positive("hawkes baseline", baseline)?;
+                positive("hawkes decay", decay)?;
+                if !(0.0..decay).contains(&excitation) {
+                    return Err(invalid(format!(
+                        "hawkes excitation {excitation} must be in [0, decay {decay})"
+                    )));
+                }
+            }
+        }
+        for (name, ratio) in [
+            ("cancel ratio", self.cancel_ratio),
+            ("market ratio", self.market_ratio),
+        ] {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(invalid(format!("{name} {ratio} must be in [0, 1]")));
+            }
+        }
+        if self.cancel_ratio + self.market_ratio > 1.0 {
+            return Err(invalid(format!(
+                "cancel ratio {} plus market ratio {} exceeds 1",
+                self.cancel_ratio, self.market_ratio
+            )));
+        }
+        if self.tick_size == 0 || self.mid_price == 0 {
+            return Err(invalid(
+                "mid price and tick size must be non-zero".to_string(),
+            ));
+        }
+        for (name, distribution) in [
+            ("price offset", self.price_offset_ticks),
+            ("limit size", self.limit_size),
+            ("market size", self.market_size),
+        ] {
+            distribution.validate(name)?;
+        }
+        if self.users == 0 {
+            return Err(invalid("users must be non-zero".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl SizeDistribution {
+    fn validate(&self, name: &str) -> Result<(), OrderBookError> {
+        let valid = match *self {
+            Self::Fixed(value) => value >= 1,
+            Self::Uniform { min, max } => min >= 1 && min <= max,
+            Self::Geometric { mean } => mean.is_finite() && mean >= 1.0,
+            Self::LogNormal { mu, sigma } => mu.is_finite() && sigma.is_finite() && sigma >= 0.0,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(invalid(format!("invalid {name} distribution {self:?}")))
+        }
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> u64 {
+        let value = match *self {
+            Self::Fixed(value) => value,
+            Self::Uniform { min, max } => {
+                let span = max - min;
+                if span == u64::MAX {
+                    rng.next_u64()
+                } else {
+                    min + rng.next_u64() % (span + 1)
+                }
+            }
+            Self::Geometric { mean } => {
+                if mean <= 1.0 {
+                    1
+                } else {
+                    let failure = 1.0 - 1.0 / mean;
+                    1 + (rng.next_open_f64().ln() / failure.ln()).floor() as u64
+                }
+            }
+            Self::LogNormal { mu, sigma } => (mu + sigma * rng.next_normal()).exp().round() as u64,
+        };
+        value.max(1)
+    }
+}
+
+/// What a [`SimulatedEvent`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SimulatedAction {
+    /// Submit a GTC limit order.
+    Limit {
+        /// New order id
+        order_id: Id,
+        /// Order side
+        side: Side,
+        /// Limit price
+        price: u128,
+        /// Order quantity
+        quantity: u64,
+        /// Submitting user
+        user_id: Hash32,
+    },
+
+    /// Submit a market order.
+    Market {
+        /// New order id
+        order_id: Id,
+        /// Order side
+        side: Side,
+        /// Order quantity
+        quantity: u64,
+        /// Submitting user
+        user_id: Hash32,
+    },
+
+    /// Cancel a previously generated limit order.
+    Cancel {
+        /// Order to cancel
+        order_id: Id,
+    },
+}
+
+/// One generated order-flow event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedEvent {
+    /// Arrival time in nanoseconds since the start of the simulation
+    pub timestamp_ns: u64,
+    /// The order action
+    pub action: SimulatedAction,
+}
+
+impl SimulatedEvent {
+    /// Submit or cancel on `book`.
+    ///
+    /// # Errors
+    /// Whatever the book returns for the action, e.g.
+    /// [`OrderBookError::OrderNotFound`] for a cancel of an order that
+    /// has already filled, or [`OrderBookError::InsufficientLiquidity`]
+    /// for a market order against an empty side.
+    pub fn apply<T>(&self, book: &OrderBook<T>) -> Result<(), OrderBookError>
+    where
+        T: Clone + Send + Sync + Default + 'static,
+    {
+        match self.action {
+            SimulatedAction::Limit {
+                order_id,
+                side,
+                price,
+                quantity,
+                user_id,
+            } => book
+                .add_limit_order_with_user(
+                    order_id,
+                    price,
+                    quantity,
+                    side,
+                    TimeInForce::Gtc,
+                    user_id,
+                    None,
+                )
+                .map(drop),
+            SimulatedAction::Market {
+                order_id,
+                side,
+                quantity,
+                user_id,
+            } => book
+                .submit_market_order_with_user(order_id, quantity, side, user_id)
+                .map(drop),
+            SimulatedAction::Cancel { order_id } => match book.cancel_order(order_id)? {
+                Some(_) => Ok(()),
+                None => Err(OrderBookError::OrderNotFound(order_id.to_string())),
+            },
+        }
+    }
+}
+
+/// Seeded generator of synthetic order flow; see the
+/// [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use orderbook_rs::{ArrivalProcess, MarketSimulator, OrderBook, SimulationConfig};
+///
+/// let config = SimulationConfig::new()
+///     .with_seed(7)
+///     .with_arrivals(ArrivalProcess::Hawkes { baseline: 500.0, excitation: 800.0, decay: 1_000.0 });
+/// let book = OrderBook::<()>::new("SIM");
+///
+/// for event in MarketSimulator::new(config).unwrap().take(1_000) {
+///     let _ = event.apply(&book);
+/// }
+/// assert!(book.best_bid() < book.best_ask());
+///
+/// // The same seed reproduces the same flow.
+/// let first: Vec<_> = MarketSimulator::new(config).unwrap().take(100).collect();
+/// let again: Vec<_> = MarketSimulator::new(config).unwrap().take(100).collect();
+/// assert_eq!(first, again);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarketSimulator {
+    config: SimulationConfig,
+    rng: SplitMix64,
+    /// Current time in seconds
+    now: f64,
+    /// Hawkes excitation above the baseline as of `now`
+    excitation: f64,
+    next_order_id: u64,
+    /// Generated limit orders not yet cancelled by the generator
+    live: Vec<Id>,
+}
+
+impl MarketSimulator {
+    /// Create a generator for `config`.
+    ///
+    /// # Errors
+    /// [`OrderBookError::InvalidConfiguration`] when
+    /// [`SimulationConfig::validate`] rejects `config`.
+    pub fn new(config: SimulationConfig) -> Result<Self, OrderBookError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            rng: SplitMix64(config.seed),
+            now: 0.0,
+            excitation: 0.0,
+            next_order_id: 1,
+            live: Vec::new(),
+        })
+    }
+
+    /// The generator's configuration.
+    #[must_use]
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Generated limit orders the generator has not cancelled. Some may
+    /// have filled on the book since.
+    #[must_use]
+    pub fn live_orders(&self) -> usize {
+        self.live.len()
+    }
+
+    /// Advance `now` to the next arrival.
+    fn advance(&mut self) {
+        match self.config.arrivals {
+            ArrivalProcess::Poisson { rate } => {
+                self.now += self.rng.next_exponential(rate);
+            }
+            ArrivalProcess::Hawkes {
+                baseline,
+                excitation,
+                decay,
+            } => loop {
+                // The intensity only decays until the next arrival, so
+                // its current value bounds it for the thinning step.
+                let bound = baseline + self.excitation;
+                let wait = self.rng.next_exponential(bound);
+                self.now += wait;
+                self.excitation *= (-decay * wait).exp();
+                if self.rng.next_open_f64() * bound <= baseline + self.excitation {
+                    self.excitation += excitation;
+                    break;
+                }
+            },
+        }
+    }
+
+    fn next_id(&mut self) -> Id {
+        let id = Id::sequential(self.next_order_id);
+        self.next_order_id += 1;
+        id
+    }
+
+    fn next_side(&mut self) -> Side {
+        if self.rng.next_u64() & 1 == 0 {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+
+    fn next_user(&mut self) -> Hash32 {
+        let mut bytes = [0u8; 32];
+        bytes[0] = (self.rng.next_u64() % u64::from(self.config.users)) as u8 + 1;
+        Hash32::new(bytes)
+    }
+
+    fn next_action(&mut self) -> SimulatedAction {
+        let draw = self.rng.next_open_f64();
+        if draw < self.config.cancel_ratio && !self.live.is_empty() {
+            let index = (self.rng.next_u64() % self.live.len() as u64) as usize;
+            return SimulatedAction::Cancel {
+                order_id: self.live.swap_remove(index),
+            };
+        }
+        if draw >= self.config.cancel_ratio
+            && draw < self.config.cancel_ratio + self.config.market_ratio
+        {
+            return SimulatedAction::Market {
+                order_id: self.next_id(),
+                side: self.next_side(),
+                quantity: self.config.market_size.sample(&mut self.rng),
+                user_id: self.next_user(),
+            };
+        }
+
+        let side = self.next_side();
+        let offset = u128::from(self.config.price_offset_ticks.sample(&mut self.rng))
+            .saturating_mul(self.config.tick_size);
+        let price = match side {
+            Side::Buy => self
+                .config
+                .mid_price
+                .saturating_sub(offset)
+                .max(self.config.tick_size),
+            Side::Sell => self.config.mid_price.saturating_add(offset),
+        };
+        let order_id = self.next_id();
+        self.live.push(order_id);
+        SimulatedAction::Limit {
+            order_id,
+            side,
+            price,
+            quantity: self.config.limit_size.sample(&mut self.rng),
+            user_id: self.next_user(),
+        }
+    }
+}
+
+impl Iterator for MarketSimulator {
+    type Item = SimulatedEvent;
+
+    fn next(&mut self) -> Option<SimulatedEvent> {
+        self.advance();
+        let action = self.next_action();
+        Some(SimulatedEvent {
+            timestamp_ns: (self.now * NANOS_PER_SEC) as u64,
+            action,
+        })
+    }
+}
+
+fn positive(name: &str, value: f64) -> Result<(), OrderBookError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(format!(
+            "{name} {value} must be positive and finite"
+        )))
+    }
+}
+
+fn invalid(message: String) -> OrderBookError {
+    OrderBookError::InvalidConfiguration { message }
+}
@@ -0,0 +1,141 @@
+//! Latency models for [`SimulationRunner`](super::SimulationRunner) agents.
+//!
+//! Without latency every agent sees every book event and reaches the book
+//! in the same instant, so races are settled by registration order. An
+//! [`AgentLatency`] gives an agent two delays, each drawn per message from
+//! a [`LatencyModel`]:
+//!
+//! - **submission** — from the moment a callback returns an action to the
+//!   moment the book processes it;
+//! - **market data** — from the moment the book emits a trade or
+//!   price-level change to the moment the agent's callback sees it.
+//!
+//! Delays are in milliseconds, the resolution of the simulation clock.
+//! Messages due in the same millisecond keep their causal order.
+
+use super::rng::SplitMix64;
+use crate::orderbook::error::OrderBookError;
+use serde::{Deserialize, Serialize};
+
+/// Distribution of a one-way delay in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LatencyModel {
+    /// Always the same delay.
+    Fixed(u64),
+
+    /// Normal delay, rounded to the millisecond and truncated at zero.
+    Normal {
+        /// Mean delay in milliseconds
+        mean: f64,
+        /// Standard deviation in milliseconds
+        std_dev: f64,
+    },
+
+    /// Uniform draw from observed delays, e.g. measured round trips.
+    Empirical(Vec<u64>),
+}
+
+impl Default for LatencyModel {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+impl LatencyModel {
+    /// Check the parameters: a normal needs a finite, non-negative mean and
+    /// standard deviation, an empirical model at least one sample.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] naming the bad parameter.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        let valid = match self {
+            Self::Fixed(_) => true,
+            Self::Normal { mean, std_dev } => {
+                mean.is_finite() && std_dev.is_finite() && *mean >= 0.0 && *std_dev >= 0.0
+            }
+            Self::Empirical(samples) => !samples.is_empty(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(OrderBookError::InvalidConfiguration {
+                message: format!("invalid latency model {self:?}"),
+            })
+        }
+    }
+
+    pub(super) fn sample(&self, rng: &mut SplitMix64) -> u64 {
+        match self {
+            Self::Fixed(delay) => *delay,
+            Self::Normal { mean, std_dev } => {
+                if *std_dev == 0.0 {
+                    mean.round() as u64
+                } else {
+                    (mean + std_dev * rng.next_normal()).round().max(0.0) as u64
+                }
+            }
+            Self::Empirical(samples) => match samples.len() {
+                0 => 0,
+                1 => samples[0],
+                len => samples[(rng.next_u64() % len as u64) as usize],
+            },
+        }
+    }
+}
+
+/// Submission and market-data delays of one agent. The default is zero
+/// latency on both paths.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentLatency {
+    /// Delay from a strategy callback to the book.
+    pub submission: LatencyModel,
+
+    /// Delay from a book event to the strategy callback.
+    pub market_data: LatencyModel,
+}
+
+impl AgentLatency {
+    /// Zero latency on both paths.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same fixed delay on both paths.
+    #[inline]
+    #[must_use]
+    pub fn fixed(delay: u64) -> Self {
+        Self {
+            submission: LatencyModel::Fixed(delay),
+            market_data: LatencyModel::Fixed(delay),
+        }
+    }
+
+    /// Set the submission delay.
+    #[inline]
+    #[must_use]
+    pub fn with_submission(mut self, model: LatencyModel) -> Self {
+        self.submission = model;
+        self
+    }
+
+    /// Set the market-data delay.
+    #[inline]
+    #[must_use]
+    pub fn with_market_data(mut self, model: LatencyModel) -> Self {
+        self.market_data = model;
+        self
+    }
+
+    /// Validate both models.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] if either model is invalid.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        self.submission.validate()?;
+        self.market_data.validate()
+    }
+}
@@ -0,0 +1,31 @@
+//! Market simulation: synthetic order flow and agent-based runs.
+//!
+//! - [`flow`] — [`MarketSimulator`], a seeded generator of order flow
+//!   from Poisson or Hawkes arrivals and configurable size
+//!   distributions.
+//! - [`agents`] — [`SimulationRunner`], a deterministic event loop
+//!   running pluggable [`Strategy`] agents against one or more books and
+//!   collecting per-agent fill and PnL statistics.
+//! - [`latency`] — [`AgentLatency`], per-agent submission and
+//!   market-data delays drawn from fixed, normal or empirical
+//!   distributions.
+//! - [`execution`] — [`ExecutionAlgo`], TWAP, VWAP and percent-of-volume
+//!   schedulers working a parent order and reporting its implementation
+//!   shortfall against the arrival price.
+
+pub mod agents;
+pub mod execution;
+pub mod flow;
+pub mod latency;
+pub(crate) mod rng;
+
+pub use agents::{
+    AgentAction, AgentContext, AgentPosition, AgentReport, AgentStats,
+    DEFAULT_MAX_ACTIONS_PER_TICK, SimulationReport, SimulationRunner, Strategy,
+};
+pub use execution::{ExecutionAlgo, ExecutionHandle, ExecutionReport, ExecutionSchedule};
+pub use flow::{
+    ArrivalProcess, MarketSimulator, SimulatedAction, SimulatedEvent, SimulationConfig,
+    SizeDistribution,
+};
+pub use latency::{AgentLatency, LatencyModel};
@@ -0,0 +1,33 @@
+//! Seeded pseudo-random source shared by the simulation modules and the
+//! speed bump.
+
+/// SplitMix64: small, fast and well distributed for every seed,
+/// including zero.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in the open interval `(0, 1)`.
+    pub(super) fn next_open_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Exponential with the given rate.
+    pub(super) fn next_exponential(&mut self, rate: f64) -> f64 {
+        -self.next_open_f64().ln() / rate
+    }
+
+    /// Standard normal via Box–Muller.
+    pub(super) fn next_normal(&mut self) -> f64 {
+        let radius = (-2.0 * self.next_open_f64().ln()).sqrt();
+        radius * (std::f64::consts::TAU * self.next_open_f64()).cos()
+    }
+}
@@ -1,14 +1,25 @@
 //! Order book snapshot for market data
 
 use bitflags::bitflags;
-use pricelevel::PriceLevelSnapshot;
+use pricelevel::{Hash32, Id, PriceLevelSnapshot};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use tracing::trace;
 
+use super::circuit_breaker::CircuitBreakerConfig;
 use super::error::OrderBookError;
 use super::fees::FeeSchedule;
+use super::market_protection::MarketProtection;
+use super::midpoint::MidpointOrder;
+use super::order_tag::OrderTag;
+use super::post_only::PostOnlyMode;
+use super::price_scale::PriceScale;
+use super::priority_class::PriorityClass;
+use super::reference_price::ExternalPrice;
 use super::risk::RiskConfig;
+use super::session::TradingSession;
+use super::speed_bump::SpeedBump;
 use super::stp::STPMode;
 
 /// A snapshot of the order book state at a specific point in time
@@ -207,6 +218,14 @@ pub struct OrderBookSnapshotPackage {
     #[serde(default)]
     pub max_order_size: Option<u64>,
 
+    /// Minimum order notional active at the time of the snapshot.
+    #[serde(default)]
+    pub min_order_notional: Option<u128>,
+
+    /// Maximum order notional active at the time of the snapshot.
+    #[serde(default)]
+    pub max_order_notional: Option<u128>,
+
     /// Engine sequence at the time of snapshot. Restored as the new
     /// counter value on
     /// [`OrderBook::restore_from_snapshot_package`](super::book::OrderBook::restore_from_snapshot_package)
@@ -220,6 +239,25 @@ pub struct OrderBookSnapshotPackage {
     #[serde(default)]
     pub engine_seq: u64,
 
+    /// [`OrderBook::execution_seq`](super::book::OrderBook::execution_seq)
+    /// at the time of snapshot: the execution id the restored book hands
+    /// its next fill.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `0`, which
+    /// restores as `1`.
+    #[serde(default)]
+    pub execution_seq: u64,
+
+    /// [`OrderBook::config_version`](super::book::OrderBook::config_version)
+    /// at the time of snapshot, restored alongside the configuration it
+    /// stamps.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `0`.
+    #[serde(default)]
+    pub config_version: u64,
+
     /// Operational state of the kill switch at the time of snapshot.
     /// Restored as-is by
     /// [`OrderBook::restore_from_snapshot_package`](super::book::OrderBook::restore_from_snapshot_package)
@@ -233,6 +271,61 @@ pub struct OrderBookSnapshotPackage {
     #[serde(default)]
     pub kill_switch_engaged: bool,
 
+    /// Users blocked by `OrderBook::block_user` at the time of snapshot,
+    /// sorted by id bytes. Restored as-is so a recovered book keeps
+    /// rejecting their flow.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with no blocked
+    /// users.
+    #[serde(default)]
+    pub blocked_users: Vec<Hash32>,
+
+    /// Account group memberships (`OrderBook::set_account_group`) at the
+    /// time of snapshot as `(user_id, group)`, sorted by id bytes.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with no groups.
+    #[serde(default)]
+    pub account_groups: Vec<(Hash32, u64)>,
+
+    /// Whether class priority (`OrderBook::set_class_priority`) was
+    /// enabled at the time of snapshot. Levels are captured in their
+    /// class-sorted queue order.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `false`.
+    #[serde(default)]
+    pub class_priority: bool,
+
+    /// Priority class registrations (`OrderBook::set_priority_class`) at
+    /// the time of snapshot as `(user_id, class)`, sorted by id bytes.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with no
+    /// registrations.
+    #[serde(default)]
+    pub priority_classes: Vec<(Hash32, PriorityClass)>,
+
+    /// Tags of resting orders (`OrderBook::tag_order`) at the time of
+    /// snapshot as `(order_id, tag)`, grouped by tag in tag order and in
+    /// tagging order within a tag.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with no tags.
+    #[serde(default)]
+    pub order_tags: Vec<(Id, OrderTag)>,
+
+    /// Resting orders of the midpoint segment
+    /// (`OrderBook::submit_midpoint_order`) at the time of snapshot: bids
+    /// then asks, each in time priority.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with no midpoint
+    /// orders.
+    #[serde(default)]
+    pub midpoint_orders: Vec<MidpointOrder>,
+
     /// Risk configuration active at the time of snapshot. `None` means
     /// no risk gating. Counters and per-order risk state are rebuilt
     /// post-restore by walking the snapshot's resting orders.
@@ -244,6 +337,60 @@ pub struct OrderBookSnapshotPackage {
     #[serde(default)]
     pub risk_config: Option<RiskConfig>,
 
+    /// Market order protection active at the time of snapshot.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub market_protection: Option<MarketProtection>,
+
+    /// Post-only crossing behaviour active at the time of snapshot.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with
+    /// [`PostOnlyMode::Reject`].
+    #[serde(default)]
+    pub post_only_mode: PostOnlyMode,
+
+    /// Reference price staleness limit active at the time of snapshot.
+    /// The prices themselves are not captured.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub reference_price_max_age_ms: Option<u64>,
+
+    /// Trading session schedule active at the time of snapshot. Progress
+    /// through the session is recomputed from the clock after restore.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub trading_session: Option<TradingSession>,
+
+    /// Circuit breaker limits active at the time of snapshot. The trade
+    /// window and any trip in progress are not captured.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+
+    /// Speed bump delay active at the time of snapshot. Orders the speed
+    /// bump holds are not captured.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub speed_bump: Option<SpeedBump>,
+
+    /// Reserve fill limit active at the time of snapshot.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads
+    /// written before this field existed deserialize with `None`.
+    #[serde(default)]
+    pub reserve_fill_limit: Option<u64>,
+
     /// Scheduled market-close timestamp (milliseconds since epoch) active at the
     /// time of snapshot — drives DAY / GTD expiry. `0` together with
     /// `has_market_close = false` means no close is configured. Restored by
@@ -260,6 +407,14 @@ pub struct OrderBookSnapshotPackage {
     /// [`Self::market_close_timestamp`].
     #[serde(default)]
     pub has_market_close: bool,
+
+    /// Decimal scale of the book's raw prices and quantities. Lives on the
+    /// package rather than the snapshot so the checksum is unchanged.
+    ///
+    /// `#[serde(default)]` keeps the format version at `2`: payloads written
+    /// before this field existed deserialize with the identity scale.
+    #[serde(default)]
+    pub price_scale: PriceScale,
 }
 
 impl OrderBookSnapshotPackage {
@@ -279,11 +434,29 @@ impl OrderBookSnapshotPackage {
             lot_size: None,
             min_order_size: None,
             max_order_size: None,
+            min_order_notional: None,
+            max_order_notional: None,
             engine_seq: 0,
+            execution_seq: 0,
+            config_version: 0,
             kill_switch_engaged: false,
+            blocked_users: Vec::new(),
+            account_groups: Vec::new(),
+            class_priority: false,
+            priority_classes: Vec::new(),
+            order_tags: Vec::new(),
+            midpoint_orders: Vec::new(),
             risk_config: None,
+            market_protection: None,
+            post_only_mode: PostOnlyMode::Reject,
+            reference_price_max_age_ms: None,
+            trading_session: None,
+            circuit_breaker: None,
+            speed_bump: None,
+            reserve_fill_limit: None,
             market_close_timestamp: 0,
             has_market_close: false,
+            price_scale: PriceScale::default(),
         })
     }
 
@@ -315,13 +488,10 @@ impl OrderBookSnapshotPackage {
         if self.version < ORDERBOOK_SNAPSHOT_MIN_READ_VERSION
             || self.version > ORDERBOOK_SNAPSHOT_FORMAT_VERSION
         {
-            return Err(OrderBookError::InvalidOperation {
-                message: format!(
-                    "Unsupported snapshot version: {} (supported {}..={})",
-                    self.version,
-                    ORDERBOOK_SNAPSHOT_MIN_READ_VERSION,
-                    ORDERBOOK_SNAPSHOT_FORMAT_VERSION
-                ),
+            return Err(OrderBookError::UnsupportedSnapshotVersion {
+                version: self.version,
+                min_supported: ORDERBOOK_SNAPSHOT_MIN_READ_VERSION,
+                max_supported: ORDERBOOK_SNAPSHOT_FORMAT_VERSION,
             });
         }
 
@@ -364,6 +534,115 @@ impl OrderBookSnapshotPackage {
     }
 }
 
+/// Snapshot packages of every book of a venue, captured together.
+///
+/// Produced by `snapshot_all` on
+/// [`BookManagerStd`](super::manager::BookManagerStd) and
+/// [`BookManagerTokio`](super::manager::BookManagerTokio), which hold every
+/// book's submit gate while the packages are taken, so no book mutates
+/// between the first and the last package. Each package keeps its own
+/// checksum; the envelope adds the coordination `epoch` shared by all of
+/// them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueSnapshot {
+    /// Coordination epoch of the capture, incremented by the manager on
+    /// every `snapshot_all`. Packages with the same epoch come from the
+    /// same capture.
+    pub epoch: u64,
+    /// Snapshot package of each book, keyed by symbol
+    pub books: BTreeMap<String, OrderBookSnapshotPackage>,
+}
+
+impl VenueSnapshot {
+    /// The package of `symbol`, if the venue had a book for it.
+    #[must_use]
+    pub fn book(&self, symbol: &str) -> Option<&OrderBookSnapshotPackage> {
+        self.books.get(symbol)
+    }
+
+    /// Validates the checksum and version of every package.
+    #[must_use = "an unchecked venue snapshot must not be restored"]
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        self.books
+            .values()
+            .try_for_each(OrderBookSnapshotPackage::validate)
+    }
+
+    /// Serializes the envelope to JSON.
+    pub fn to_json(&self) -> Result<String, OrderBookError> {
+        serde_json::to_string(self).map_err(|error| OrderBookError::SerializationError {
+            message: error.to_string(),
+        })
+    }
+
+    /// Deserializes the envelope from JSON.
+    pub fn from_json(data: &str) -> Result<Self, OrderBookError> {
+        serde_json::from_str(data).map_err(|error| OrderBookError::DeserializationError {
+            message: error.to_string(),
+        })
+    }
+
+    /// Serializes the envelope to bincode (standard configuration).
+    ///
+    /// Each package is embedded in its JSON form: price level snapshots
+    /// only decode from self-describing formats, and the package checksum
+    /// is defined over JSON anyway.
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, OrderBookError> {
+        let frame = VenueSnapshotFrame {
+            epoch: self.epoch,
+            books: self
+                .books
+                .iter()
+                .map(|(symbol, package)| Ok((symbol.clone(), package.to_json()?)))
+                .collect::<Result<_, OrderBookError>>()?,
+        };
+        bincode::serde::encode_to_vec(&frame, bincode::config::standard()).map_err(|error| {
+            OrderBookError::SerializationError {
+                message: error.to_string(),
+            }
+        })
+    }
+
+    /// Deserializes the envelope from bincode, rejecting trailing bytes.
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(data: &[u8]) -> Result<Self, OrderBookError> {
+        let (frame, bytes_read) = bincode::serde::decode_from_slice::<VenueSnapshotFrame, _>(
+            data,
+            bincode::config::standard(),
+        )
+        .map_err(|error| OrderBookError::DeserializationError {
+            message: error.to_string(),
+        })?;
+        if bytes_read != data.len() {
+            return Err(OrderBookError::DeserializationError {
+                message: format!(
+                    "trailing bytes after venue snapshot: consumed {bytes_read} of {}",
+                    data.len()
+                ),
+            });
+        }
+        Ok(Self {
+            epoch: frame.epoch,
+            books: frame
+                .books
+                .into_iter()
+                .map(|(symbol, package)| {
+                    Ok((symbol, OrderBookSnapshotPackage::from_json(&package)?))
+                })
+                .collect::<Result<_, OrderBookError>>()?,
+        })
+    }
+}
+
+/// Bincode layout of a [`VenueSnapshot`]: packages as JSON, by symbol.
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct VenueSnapshotFrame {
+    epoch: u64,
+    books: Vec<(String, String)>,
+}
+
 bitflags! {
     /// Flags for selecting which metrics to calculate in enriched snapshots
     ///
@@ -394,15 +673,79 @@ bitflags! {
         /// Calculate VWAP for top N levels
         const VWAP = 1 << 3;
 
-        /// Calculate order book imbalance
+        /// Calculate order book imbalance for top N levels
         const IMBALANCE = 1 << 4;
 
+        /// Calculate micro price from the best bid and ask
+        const MICRO_PRICE = 1 << 5;
+
+        /// Calculate the depth-weighted mid over the top N levels
+        const DEPTH_WEIGHTED_MID = 1 << 6;
+
+        /// Calculate the cumulative depth profile of each side
+        const CUMULATIVE_DEPTH_PROFILE = 1 << 7;
+
         /// Calculate all metrics
         const ALL = Self::MID_PRICE.bits() | Self::SPREAD.bits()
-                  | Self::DEPTH.bits() | Self::VWAP.bits() | Self::IMBALANCE.bits();
+                  | Self::DEPTH.bits() | Self::VWAP.bits() | Self::IMBALANCE.bits()
+                  | Self::MICRO_PRICE.bits() | Self::DEPTH_WEIGHTED_MID.bits()
+                  | Self::CUMULATIVE_DEPTH_PROFILE.bits();
+    }
+}
+
+/// Aggregate state of one price level, without its orders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelAggregate {
+    /// The price of this level (in price units)
+    pub price: u128,
+    /// Total visible quantity at this level (in units)
+    pub visible_quantity: u64,
+    /// Total hidden quantity at this level (in units)
+    pub hidden_quantity: u64,
+    /// Number of orders resting at this level
+    pub order_count: usize,
+}
+
+impl LevelAggregate {
+    /// Visible plus hidden quantity, saturating at `u64::MAX`.
+    #[must_use]
+    pub fn total_quantity(&self) -> u64 {
+        self.visible_quantity.saturating_add(self.hidden_quantity)
+    }
+
+    /// Mean visible plus hidden quantity per order, `None` for a level
+    /// with no orders.
+    #[must_use]
+    pub fn average_order_size(&self) -> Option<f64> {
+        (self.order_count > 0).then(|| self.total_quantity() as f64 / self.order_count as f64)
+    }
+}
+
+impl From<&PriceLevelSnapshot> for LevelAggregate {
+    fn from(level: &PriceLevelSnapshot) -> Self {
+        Self {
+            price: level.price().as_u128(),
+            visible_quantity: level.visible_quantity().as_u64(),
+            hidden_quantity: level.hidden_quantity().as_u64(),
+            order_count: level.order_count(),
+        }
     }
 }
 
+/// The top levels of each side of the book as [`LevelAggregate`]s, best
+/// level first, returned by
+/// [`OrderBook::level_aggregates`](crate::OrderBook::level_aggregates).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelAggregates {
+    /// Bid levels, highest price first
+    pub bids: Vec<LevelAggregate>,
+    /// Ask levels, lowest price first
+    pub asks: Vec<LevelAggregate>,
+    /// Book update sequence observed before the levels were read. Equal
+    /// values come from the same book state.
+    pub update_seq: u64,
+}
+
 /// An enriched snapshot with pre-calculated metrics
 ///
 /// This provides better performance than creating a snapshot and calculating
@@ -440,12 +783,24 @@ pub struct EnrichedSnapshot {
     /// Timestamp when the snapshot was created (milliseconds since epoch)
     pub timestamp: u64,
 
-    /// Snapshot of bid price levels
+    /// Snapshot of bid price levels, including their orders. Empty in
+    /// snapshots built from aggregates only; see [`Self::bid_levels`].
     pub bids: Vec<PriceLevelSnapshot>,
 
-    /// Snapshot of ask price levels
+    /// Snapshot of ask price levels, including their orders. Empty in
+    /// snapshots built from aggregates only; see [`Self::ask_levels`].
     pub asks: Vec<PriceLevelSnapshot>,
 
+    /// Aggregates of the included bid levels, highest price first. The
+    /// metrics below are computed from these.
+    #[serde(default)]
+    pub bid_levels: Vec<LevelAggregate>,
+
+    /// Aggregates of the included ask levels, lowest price first. The
+    /// metrics below are computed from these.
+    #[serde(default)]
+    pub ask_levels: Vec<LevelAggregate>,
+
     /// Mid price (average of best bid and best ask)
     pub mid_price: Option<f64>,
 
@@ -461,11 +816,50 @@ pub struct EnrichedSnapshot {
     /// Order book imbalance (-1.0 to 1.0)
     pub order_book_imbalance: f64,
 
+    /// Imbalance of resting order counts rather than quantity over the
+    /// same levels (-1.0 to 1.0). Set together with
+    /// [`Self::order_book_imbalance`]
+    #[serde(default)]
+    pub order_count_imbalance: f64,
+
     /// VWAP for top N bid levels
     pub vwap_bid: Option<f64>,
 
     /// VWAP for top N ask levels
     pub vwap_ask: Option<f64>,
+
+    /// Micro price: best bid and ask weighted by the opposite side's
+    /// quantity at the touch
+    #[serde(default)]
+    pub micro_price: Option<f64>,
+
+    /// Depth-weighted mid: the micro price formula applied to the VWAP and
+    /// total quantity of the top N levels of each side
+    #[serde(default)]
+    pub depth_weighted_mid: Option<f64>,
+
+    /// Cumulative bid depth at each included level, best level first (in units)
+    #[serde(default)]
+    pub bid_depth_profile: Vec<u64>,
+
+    /// Cumulative ask depth at each included level, best level first (in units)
+    #[serde(default)]
+    pub ask_depth_profile: Vec<u64>,
+
+    /// Decimal scale of the raw prices and quantities above, used by the
+    /// `scaled_*` accessors
+    #[serde(default)]
+    pub price_scale: PriceScale,
+
+    /// Latest externally supplied index price and its observation time,
+    /// stale or not; compare its timestamp with [`Self::timestamp`]
+    #[serde(default)]
+    pub index_price: Option<ExternalPrice>,
+
+    /// Latest externally supplied mark price and its observation time,
+    /// stale or not
+    #[serde(default)]
+    pub mark_price: Option<ExternalPrice>,
 }
 
 impl EnrichedSnapshot {
@@ -505,7 +899,8 @@ impl EnrichedSnapshot {
     /// - `bids`: Bid price levels
     /// - `asks`: Ask price levels
     /// - `vwap_levels`: Number of levels to use for VWAP calculation
-    /// - `imbalance_levels`: Number of levels to use for imbalance calculation
+    /// - `imbalance_levels`: Number of levels to use for imbalance and
+    ///   depth-weighted mid calculation
     /// - `flags`: Metrics to calculate
     pub fn with_metrics(
         symbol: String,
@@ -516,16 +911,57 @@ impl EnrichedSnapshot {
         imbalance_levels: usize,
         flags: MetricFlags,
     ) -> Self {
+        let bid_levels = bids.iter().map(LevelAggregate::from).collect();
+        let ask_levels = asks.iter().map(LevelAggregate::from).collect();
+        Self {
+            bids,
+            asks,
+            ..Self::from_aggregates(
+                symbol,
+                timestamp,
+                bid_levels,
+                ask_levels,
+                vwap_levels,
+                imbalance_levels,
+                flags,
+            )
+        }
+    }
+
+    /// Creates an enriched snapshot from level aggregates alone, with
+    /// custom metric selection. `bids` and `asks` are left empty; no order
+    /// is visited.
+    ///
+    /// # Arguments
+    /// - `symbol`: Symbol identifier
+    /// - `timestamp`: Timestamp in milliseconds
+    /// - `bid_levels`: Bid level aggregates, highest price first
+    /// - `ask_levels`: Ask level aggregates, lowest price first
+    /// - `vwap_levels`: Number of levels to use for VWAP calculation
+    /// - `imbalance_levels`: Number of levels to use for imbalance and
+    ///   depth-weighted mid calculation
+    /// - `flags`: Metrics to calculate
+    pub fn from_aggregates(
+        symbol: String,
+        timestamp: u64,
+        bid_levels: Vec<LevelAggregate>,
+        ask_levels: Vec<LevelAggregate>,
+        vwap_levels: usize,
+        imbalance_levels: usize,
+        flags: MetricFlags,
+    ) -> Self {
+        let (bids, asks) = (&bid_levels, &ask_levels);
+
         // Calculate mid price if needed
         let mid_price = if flags.contains(MetricFlags::MID_PRICE) {
-            Self::calculate_mid_price(&bids, &asks)
+            Self::calculate_mid_price(bids, asks)
         } else {
             None
         };
 
         // Calculate spread if needed
         let spread_bps = if flags.contains(MetricFlags::SPREAD) {
-            Self::calculate_spread_bps(&bids, &asks)
+            Self::calculate_spread_bps(bids, asks)
         } else {
             None
         };
@@ -533,8 +969,8 @@ impl EnrichedSnapshot {
         // Calculate depths if needed
         let (bid_depth_total, ask_depth_total) = if flags.contains(MetricFlags::DEPTH) {
             (
-                Self::calculate_total_depth(&bids),
-                Self::calculate_total_depth(&asks),
+                Self::calculate_total_depth(bids),
+                Self::calculate_total_depth(asks),
             )
         } else {
             (0, 0)
@@ -543,50 +979,142 @@ impl EnrichedSnapshot {
         // Calculate VWAP if needed
         let (vwap_bid, vwap_ask) = if flags.contains(MetricFlags::VWAP) {
             (
-                Self::calculate_vwap(&bids, vwap_levels),
-                Self::calculate_vwap(&asks, vwap_levels),
+                Self::calculate_vwap(bids, vwap_levels),
+                Self::calculate_vwap(asks, vwap_levels),
             )
         } else {
             (None, None)
         };
 
-        // Calculate imbalance if needed
-        let order_book_imbalance = if flags.contains(MetricFlags::IMBALANCE) {
-            Self::calculate_imbalance(&bids, &asks, imbalance_levels)
+        // Calculate imbalances if needed
+        let (order_book_imbalance, order_count_imbalance) =
+            if flags.contains(MetricFlags::IMBALANCE) {
+                (
+                    Self::calculate_imbalance(bids, asks, imbalance_levels),
+                    Self::calculate_order_count_imbalance(bids, asks, imbalance_levels),
+                )
+            } else {
+                (0.0, 0.0)
+            };
+
+        // Calculate micro price if needed
+        let micro_price = if flags.contains(MetricFlags::MICRO_PRICE) {
+            Self::calculate_weighted_mid(bids, asks, 1)
         } else {
-            0.0
+            None
+        };
+
+        // Calculate depth-weighted mid if needed
+        let depth_weighted_mid = if flags.contains(MetricFlags::DEPTH_WEIGHTED_MID) {
+            Self::calculate_weighted_mid(bids, asks, imbalance_levels)
+        } else {
+            None
         };
 
+        // Calculate cumulative depth profiles if needed
+        let (bid_depth_profile, ask_depth_profile) =
+            if flags.contains(MetricFlags::CUMULATIVE_DEPTH_PROFILE) {
+                (
+                    Self::calculate_depth_profile(bids),
+                    Self::calculate_depth_profile(asks),
+                )
+            } else {
+                (Vec::new(), Vec::new())
+            };
+
         Self {
             symbol,
             timestamp,
-            bids,
-            asks,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            bid_levels,
+            ask_levels,
             mid_price,
             spread_bps,
             bid_depth_total,
             ask_depth_total,
             order_book_imbalance,
+            order_count_imbalance,
             vwap_bid,
             vwap_ask,
+            micro_price,
+            depth_weighted_mid,
+            bid_depth_profile,
+            ask_depth_profile,
+            price_scale: PriceScale::default(),
+            index_price: None,
+            mark_price: None,
         }
     }
 
-    fn calculate_mid_price(
-        bids: &[PriceLevelSnapshot],
-        asks: &[PriceLevelSnapshot],
-    ) -> Option<f64> {
-        let best_bid = bids.first().map(|l| l.price())?;
-        let best_ask = asks.first().map(|l| l.price())?;
-        Some((best_bid.to_f64_lossy() + best_ask.to_f64_lossy()) / 2.0)
+    /// Sets the decimal scale used by the `scaled_*` accessors.
+    #[must_use]
+    pub fn with_price_scale(mut self, price_scale: PriceScale) -> Self {
+        self.price_scale = price_scale;
+        self
     }
 
-    fn calculate_spread_bps(
-        bids: &[PriceLevelSnapshot],
-        asks: &[PriceLevelSnapshot],
-    ) -> Option<f64> {
-        let best_bid = bids.first().map(|l| l.price())?.to_f64_lossy();
-        let best_ask = asks.first().map(|l| l.price())?.to_f64_lossy();
+    /// Sets the externally supplied index and mark prices.
+    #[must_use]
+    pub fn with_reference_prices(
+        mut self,
+        index_price: Option<ExternalPrice>,
+        mark_price: Option<ExternalPrice>,
+    ) -> Self {
+        self.index_price = index_price;
+        self.mark_price = mark_price;
+        self
+    }
+
+    /// Mid price in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_mid_price(&self) -> Option<f64> {
+        self.mid_price
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
+
+    /// Micro price in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_micro_price(&self) -> Option<f64> {
+        self.micro_price
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
+
+    /// Bid VWAP in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_vwap_bid(&self) -> Option<f64> {
+        self.vwap_bid
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
+
+    /// Ask VWAP in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_vwap_ask(&self) -> Option<f64> {
+        self.vwap_ask
+            .map(|price| self.price_scale.scale_price_f64(price))
+    }
+
+    /// Total bid depth in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_bid_depth_total(&self) -> f64 {
+        self.price_scale.quantity_to_f64(self.bid_depth_total)
+    }
+
+    /// Total ask depth in display units, per [`Self::price_scale`]
+    #[must_use]
+    pub fn scaled_ask_depth_total(&self) -> f64 {
+        self.price_scale.quantity_to_f64(self.ask_depth_total)
+    }
+
+    fn calculate_mid_price(bids: &[LevelAggregate], asks: &[LevelAggregate]) -> Option<f64> {
+        let best_bid = bids.first().map(|l| l.price)?;
+        let best_ask = asks.first().map(|l| l.price)?;
+        Some((best_bid as f64 + best_ask as f64) / 2.0)
+    }
+
+    fn calculate_spread_bps(bids: &[LevelAggregate], asks: &[LevelAggregate]) -> Option<f64> {
+        let best_bid = bids.first().map(|l| l.price)? as f64;
+        let best_ask = asks.first().map(|l| l.price)? as f64;
         let mid_price = (best_bid + best_ask) / 2.0;
 
         if mid_price == 0.0 {
@@ -597,38 +1125,61 @@ impl EnrichedSnapshot {
         Some((spread / mid_price) * 10000.0)
     }
 
-    fn calculate_total_depth(levels: &[PriceLevelSnapshot]) -> u64 {
-        levels
-            .iter()
-            .map(|l| l.total_quantity().map_or(0, |q| q.as_u64()))
-            .sum()
+    fn calculate_total_depth(levels: &[LevelAggregate]) -> u64 {
+        levels.iter().map(LevelAggregate::total_quantity).sum()
+    }
+
+    fn calculate_vwap(levels: &[LevelAggregate], max_levels: usize) -> Option<f64> {
+        Self::vwap_and_quantity(levels, max_levels).map(|(vwap, _)| vwap)
     }
 
-    fn calculate_vwap(levels: &[PriceLevelSnapshot], max_levels: usize) -> Option<f64> {
-        let levels_to_use = levels.iter().take(max_levels);
+    /// Weights each side's VWAP over the top `max_levels` levels by the
+    /// opposite side's quantity: `(ask_vwap * bid_qty + bid_vwap * ask_qty) /
+    /// (bid_qty + ask_qty)`. With `max_levels == 1` this is the micro price.
+    fn calculate_weighted_mid(
+        bids: &[LevelAggregate],
+        asks: &[LevelAggregate],
+        max_levels: usize,
+    ) -> Option<f64> {
+        let (bid_vwap, bid_qty) = Self::vwap_and_quantity(bids, max_levels)?;
+        let (ask_vwap, ask_qty) = Self::vwap_and_quantity(asks, max_levels)?;
 
+        let bid_qty = bid_qty as f64;
+        let ask_qty = ask_qty as f64;
+        Some((ask_vwap * bid_qty + bid_vwap * ask_qty) / (bid_qty + ask_qty))
+    }
+
+    fn vwap_and_quantity(levels: &[LevelAggregate], max_levels: usize) -> Option<(f64, u64)> {
         let mut total_value = 0u128;
         let mut total_quantity = 0u64;
 
-        for level in levels_to_use {
-            let quantity = level.total_quantity().map_or(0, |q| q.as_u64());
-            if quantity > 0 {
-                total_value = total_value
-                    .saturating_add(level.price().as_u128().saturating_mul(u128::from(quantity)));
-                total_quantity = total_quantity.saturating_add(quantity);
-            }
+        for level in levels.iter().take(max_levels) {
+            let quantity = level.total_quantity();
+            total_value =
+                total_value.saturating_add(level.price.saturating_mul(u128::from(quantity)));
+            total_quantity = total_quantity.saturating_add(quantity);
         }
 
         if total_quantity == 0 {
             None
         } else {
-            Some(total_value as f64 / total_quantity as f64)
+            Some((total_value as f64 / total_quantity as f64, total_quantity))
         }
     }
 
+    fn calculate_depth_profile(levels: &[LevelAggregate]) -> Vec<u64> {
+        levels
+            .iter()
+            .scan(0u64, |cumulative, level| {
+                *cumulative = cumulative.saturating_add(level.total_quantity());
+                Some(*cumulative)
+            })
+            .collect()
+    }
+
     fn calculate_imbalance(
-        bids: &[PriceLevelSnapshot],
-        asks: &[PriceLevelSnapshot],
+        bids: &[LevelAggregate],
+        asks: &[LevelAggregate],
         max_levels: usize,
     ) -> f64 {
         // Saturating folds so an astronomical aggregate depth caps at u64::MAX
@@ -636,13 +1187,13 @@ impl EnrichedSnapshot {
         let bid_volume: u64 = bids
             .iter()
             .take(max_levels)
-            .map(|l| l.total_quantity().map_or(0, |q| q.as_u64()))
+            .map(LevelAggregate::total_quantity)
             .fold(0u64, u64::saturating_add);
 
         let ask_volume: u64 = asks
             .iter()
             .take(max_levels)
-            .map(|l| l.total_quantity().map_or(0, |q| q.as_u64()))
+            .map(LevelAggregate::total_quantity)
             .fold(0u64, u64::saturating_add);
 
         let total = bid_volume.saturating_add(ask_volume);
@@ -653,22 +1204,40 @@ impl EnrichedSnapshot {
             (bid_volume as f64 - ask_volume as f64) / total as f64
         }
     }
+
+    /// Calculate order count imbalance for top N levels
+    fn calculate_order_count_imbalance(
+        bids: &[LevelAggregate],
+        asks: &[LevelAggregate],
+        max_levels: usize,
+    ) -> f64 {
+        let count = |levels: &[LevelAggregate]| -> usize {
+            levels
+                .iter()
+                .take(max_levels)
+                .map(|level| level.order_count)
+                .fold(0, usize::saturating_add)
+        };
+        let (bid_orders, ask_orders) = (count(bids), count(asks));
+        let total = bid_orders.saturating_add(ask_orders);
+        if total == 0 {
+            0.0
+        } else {
+            (bid_orders as f64 - ask_orders as f64) / total as f64
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pricelevel::PriceLevelSnapshot;
-
-    fn level(visible: u64) -> PriceLevelSnapshot {
-        serde_json::from_value(serde_json::json!({
-            "price": 100,
-            "visible_quantity": visible,
-            "hidden_quantity": 0,
-            "order_count": 1,
-            "orders": []
-        }))
-        .expect("valid PriceLevelSnapshot JSON")
+    fn level(visible: u64) -> LevelAggregate {
+        LevelAggregate {
+            price: 100,
+            visible_quantity: visible,
+            hidden_quantity: 0,
+            order_count: 1,
+        }
     }
 
     #[test]
@@ -0,0 +1,340 @@
+//! Speed bump: delayed matching of aggressive orders.
+//!
+//! With a [`SpeedBump`] in its [`BookConfig`](crate::BookConfig),
+//! a book holds every aggressive order submitted through
+//! [`OrderBook::submit_through_speed_bump`] for a fixed or randomized
+//! delay before matching it, while passive orders post at once. Resting
+//! liquidity can therefore be repriced or cancelled before a taker that
+//! arrived earlier reaches it, the property IEX-style market designs are
+//! built around.
+//!
+//! An order is aggressive when it is a market order, a market-to-limit
+//! order, an IOC or FOK order, or a limit order that crosses the opposite
+//! best on arrival; a post-only order is always passive. Delays are drawn
+//! per order, in milliseconds of the book clock, from a seeded generator
+//! (see [`OrderBook::set_speed_bump_seed`]), so a run against a
+//! [`ReplayClock`](crate::ReplayClock) is reproducible.
+//!
+//! Held orders are released by [`OrderBook::release_speed_bump`] once the
+//! clock reaches their release time, in release-time order and, on ties,
+//! in arrival order. They go through full admission at release, against
+//! the book as it is then. They are neither visible in any book query nor
+//! persisted in snapshots, and they are cancelled with
+//! [`OrderBook::cancel_held_order`], not [`OrderBook::cancel_order`].
+//! [`SimulationRunner`](crate::SimulationRunner) submits every
+//! agent order through the speed bump and releases held orders on time.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::order_ack::OrderAck;
+use super::simulation::rng::SplitMix64;
+use pricelevel::{Hash32, Id, OrderType, Side};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{MutexGuard, PoisonError};
+use tracing::trace;
+
+/// Delay applied to aggressive orders, drawn uniformly from
+/// `min_delay_ms..=max_delay_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpeedBump {
+    /// Shortest delay in milliseconds
+    pub min_delay_ms: u64,
+    /// Longest delay in milliseconds
+    pub max_delay_ms: u64,
+}
+
+impl SpeedBump {
+    /// The same delay for every aggressive order.
+    #[must_use]
+    pub fn fixed(delay_ms: u64) -> Self {
+        Self {
+            min_delay_ms: delay_ms,
+            max_delay_ms: delay_ms,
+        }
+    }
+
+    /// A delay drawn uniformly from `min_delay_ms..=max_delay_ms`.
+    #[must_use]
+    pub fn randomized(min_delay_ms: u64, max_delay_ms: u64) -> Self {
+        Self {
+            min_delay_ms,
+            max_delay_ms,
+        }
+    }
+
+    /// Check that the range is non-empty and delays something.
+    ///
+    /// # Errors
+    ///
+    /// [`OrderBookError::InvalidConfiguration`] when the minimum exceeds
+    /// the maximum or the maximum is zero.
+    pub fn validate(&self) -> Result<(), OrderBookError> {
+        if self.min_delay_ms > self.max_delay_ms || self.max_delay_ms == 0 {
+            return Err(OrderBookError::InvalidConfiguration {
+                message: format!(
+                    "invalid speed bump delay range {}..={} ms",
+                    self.min_delay_ms, self.max_delay_ms
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> u64 {
+        let span = self.max_delay_ms.saturating_sub(self.min_delay_ms);
+        if span == 0 {
+            return self.min_delay_ms;
+        }
+        let offset = match span.checked_add(1) {
+            Some(width) => rng.next_u64() % width,
+            None => rng.next_u64(),
+        };
+        self.min_delay_ms + offset
+    }
+}
+
+/// An order submitted through the speed bump.
+#[derive(Debug, Clone)]
+pub enum BumpedOrder<T> {
+    /// A limit-priced order of any type.
+    Order(OrderType<T>),
+
+    /// A market order for `quantity`.
+    Market {
+        /// Order identifier
+        id: Id,
+        /// Quantity to buy or sell
+        quantity: u64,
+        /// Buy or sell
+        side: Side,
+        /// Owner, for self-trade prevention
+        user_id: Hash32,
+    },
+}
+
+impl<T: Clone> BumpedOrder<T> {
+    /// The order's identifier.
+    #[must_use]
+    pub fn id(&self) -> Id {
+        match self {
+            Self::Order(order) => order.id(),
+            Self::Market { id, .. } => *id,
+        }
+    }
+}
+
+/// What [`OrderBook::submit_through_speed_bump`] did with an order.
+#[derive(Debug, Clone)]
+pub enum SpeedBumpAdmission {
+    /// Processed at once: the order was passive or the book has no speed
+    /// bump.
+    Processed(OrderAck),
+
+    /// Held until the book clock reaches `release_at`.
+    Held {
+        /// Release time in milliseconds of the book clock
+        release_at: u64,
+    },
+}
+
+/// A held order processed by [`OrderBook::release_speed_bump`].
+#[derive(Debug)]
+pub struct SpeedBumpRelease {
+    /// The released order
+    pub order_id: Id,
+    /// When it was due, in milliseconds of the book clock
+    pub release_at: u64,
+    /// Its admission at release
+    pub result: Result<OrderAck, OrderBookError>,
+}
+
+/// Orders held by the speed bump, keyed by release time and arrival.
+pub(crate) struct SpeedBumpQueue<T> {
+    held: BTreeMap<(u64, u64), BumpedOrder<T>>,
+    next_sequence: u64,
+    rng: SplitMix64,
+}
+
+impl<T> Default for SpeedBumpQueue<T> {
+    fn default() -> Self {
+        Self {
+            held: BTreeMap::new(),
+            next_sequence: 0,
+            rng: SplitMix64(0),
+        }
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    fn speed_bump_queue(&self) -> MutexGuard<'_, SpeedBumpQueue<T>> {
+        self.speed_bump
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The configured speed bump, if any.
+    #[must_use]
+    #[inline]
+    pub fn speed_bump(&self) -> Option<SpeedBump> {
+        self.config().speed_bump
+    }
+
+    /// Seed the generator behind randomized delays; equal seeds give
+    /// equal delays for the same sequence of held orders.
+    pub fn set_speed_bump_seed(&self, seed: u64) {
+        self.speed_bump_queue().rng = SplitMix64(seed);
+    }
+
+    /// Submit `order`, holding it for the configured delay when it is
+    /// aggressive and the book has a speed bump, and processing it at
+    /// once otherwise.
+    ///
+    /// # Errors
+    ///
+    /// The admission errors of an order processed at once. A held order
+    /// is only admitted at release, so its errors are reported by
+    /// [`Self::release_speed_bump`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{
+    ///     BookConfig, BumpedOrder, Clock, OrderBook, ReplayClock, SpeedBump, SpeedBumpAdmission,
+    /// };
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    /// use std::sync::Arc;
+    ///
+    /// let clock = Arc::new(ReplayClock::new(1_000));
+    /// let book = OrderBook::<()>::with_clock("BTC/USD", Arc::clone(&clock) as Arc<dyn Clock>);
+    /// book.reload_config(BookConfig::new().with_speed_bump(SpeedBump::fixed(5)))?;
+    /// book.add_limit_order(Id::from_u64(1), 100, 10, Side::Sell, TimeInForce::Gtc, None)?;
+    ///
+    /// let taker = BumpedOrder::Market {
+    ///     id: Id::from_u64(2),
+    ///     quantity: 4,
+    ///     side: Side::Buy,
+    ///     user_id: Hash32::zero(),
+    /// };
+    /// let admission = book.submit_through_speed_bump(taker)?;
+    /// assert!(matches!(admission, SpeedBumpAdmission::Held { release_at: 1_005 }));
+    ///
+    /// assert!(book.release_speed_bump().is_empty());
+    /// clock.advance_to(1_005);
+    /// let released = book.release_speed_bump();
+    /// let ack = released[0].result.as_ref().expect("admitted");
+    /// assert!(ack.match_result.is_complete());
+    /// # Ok::<(), orderbook_rs::OrderBookError>(())
+    /// ```
+    pub fn submit_through_speed_bump(
+        &self,
+        order: BumpedOrder<T>,
+    ) -> Result<SpeedBumpAdmission, OrderBookError> {
+        if let Some(bump) = self.speed_bump()
+            && self.is_aggressive(&order)
+        {
+            let now = self.clock().now_millis().as_u64();
+            let mut queue = self.speed_bump_queue();
+            let release_at = now.saturating_add(bump.sample(&mut queue.rng));
+            let sequence = queue.next_sequence;
+            queue.next_sequence += 1;
+            trace!(
+                "Order book {}: holding order {} until {}",
+                self.symbol(),
+                order.id(),
+                release_at
+            );
+            queue.held.insert((release_at, sequence), order);
+            return Ok(SpeedBumpAdmission::Held { release_at });
+        }
+        self.process_bumped(order)
+            .map(SpeedBumpAdmission::Processed)
+    }
+
+    /// Process every held order whose release time the book clock has
+    /// reached, in release order, and report each admission.
+    pub fn release_speed_bump(&self) -> Vec<SpeedBumpRelease> {
+        let now = self.clock().now_millis().as_u64();
+        let mut released = Vec::new();
+        loop {
+            // The queue lock is not held while the order is processed.
+            let due = {
+                let mut queue = self.speed_bump_queue();
+                match queue.held.first_entry() {
+                    Some(entry) if entry.key().0 <= now => {
+                        let release_at = entry.key().0;
+                        Some((release_at, entry.remove()))
+                    }
+                    _ => None,
+                }
+            };
+            let Some((release_at, order)) = due else {
+                return released;
+            };
+            released.push(SpeedBumpRelease {
+                order_id: order.id(),
+                release_at,
+                result: self.process_bumped(order),
+            });
+        }
+    }
+
+    /// Release time of the next held order, if any.
+    #[must_use]
+    pub fn next_speed_bump_release(&self) -> Option<u64> {
+        self.speed_bump_queue()
+            .held
+            .keys()
+            .next()
+            .map(|&(release_at, _)| release_at)
+    }
+
+    /// Number of orders the speed bump holds.
+    #[must_use]
+    pub fn held_order_count(&self) -> usize {
+        self.speed_bump_queue().held.len()
+    }
+
+    /// Remove a held order before its release.
+    pub fn cancel_held_order(&self, order_id: Id) -> Option<BumpedOrder<T>> {
+        let mut queue = self.speed_bump_queue();
+        let key = queue
+            .held
+            .iter()
+            .find(|(_, order)| order.id() == order_id)
+            .map(|(&key, _)| key)?;
+        queue.held.remove(&key)
+    }
+
+    fn is_aggressive(&self, order: &BumpedOrder<T>) -> bool {
+        match order {
+            BumpedOrder::Market { .. } => true,
+            BumpedOrder::Order(order) => {
+                matches!(order, OrderType::MarketToLimit { .. })
+                    || order.is_immediate()
+                    || (!order.is_post_only()
+                        && self.will_cross_market(order.price().as_u128(), order.side()))
+            }
+        }
+    }
+
+    fn process_bumped(&self, order: BumpedOrder<T>) -> Result<OrderAck, OrderBookError> {
+        match order {
+            BumpedOrder::Order(order) => self.add_order_with_ack(order),
+            BumpedOrder::Market {
+                id,
+                quantity,
+                side,
+                user_id,
+            } => self
+                .submit_market_order_with_user(id, quantity, side, user_id)
+                .map(|match_result| OrderAck {
+                    match_result,
+                    resting: None,
+                }),
+        }
+    }
+}
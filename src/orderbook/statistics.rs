@@ -92,6 +92,96 @@ impl DistributionBin {
     }
 }
 
+/// Number of buckets in a [`LifetimeDistribution`]: one for zero and one
+/// per bit length of a `u64` millisecond duration.
+pub const LIFETIME_BUCKETS: usize = 65;
+
+/// Distribution of how long orders rested before leaving the book.
+///
+/// Durations are in milliseconds of the book's clock. Bucket `0` counts
+/// zero-length lifetimes and bucket `i` counts lifetimes in
+/// `[2^(i-1), 2^i)`, so percentiles are resolved to a power of two.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LifetimeDistribution {
+    /// Lifetimes recorded
+    pub count: u64,
+    /// Sum of every recorded lifetime (in milliseconds)
+    pub total_ms: u64,
+    /// Shortest lifetime, `None` when nothing was recorded
+    pub min_ms: Option<u64>,
+    /// Longest lifetime, `None` when nothing was recorded
+    pub max_ms: Option<u64>,
+    /// Power-of-two histogram, [`LIFETIME_BUCKETS`] entries
+    pub buckets: Vec<u64>,
+}
+
+impl Default for LifetimeDistribution {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            total_ms: 0,
+            min_ms: None,
+            max_ms: None,
+            buckets: vec![0; LIFETIME_BUCKETS],
+        }
+    }
+}
+
+impl LifetimeDistribution {
+    /// Bucket a lifetime of `ms` milliseconds is counted in.
+    #[must_use]
+    pub fn bucket_of(ms: u64) -> usize {
+        (u64::BITS - ms.leading_zeros()) as usize
+    }
+
+    /// Mean lifetime in milliseconds, `None` when nothing was recorded.
+    #[must_use]
+    pub fn mean_ms(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total_ms as f64 / self.count as f64)
+    }
+
+    /// Upper bound of the bucket holding the `quantile` (0.0 to 1.0)
+    /// lifetime, capped at [`Self::max_ms`]. `None` when nothing was
+    /// recorded.
+    #[must_use]
+    pub fn percentile_ms(&self, quantile: f64) -> Option<u64> {
+        let max = self.max_ms?;
+        let rank = ((quantile.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0u64;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen = seen.saturating_add(count);
+            if seen >= rank {
+                let upper = match bucket {
+                    0 => 0,
+                    64 => u64::MAX,
+                    _ => (1u64 << bucket) - 1,
+                };
+                return Some(upper.min(max));
+            }
+        }
+        Some(max)
+    }
+}
+
+/// Quote-lifetime analytics: how long resting orders lived before being
+/// cancelled and before being completely filled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuoteLifetimeStats {
+    /// Time from resting to cancellation, for any cancel reason
+    pub cancelled: LifetimeDistribution,
+    /// Time from resting to the fill that completed the order
+    pub filled: LifetimeDistribution,
+}
+
+impl QuoteLifetimeStats {
+    /// Cancelled orders per completely filled order, `None` before the
+    /// first fill.
+    #[must_use]
+    pub fn cancel_to_fill_ratio(&self) -> Option<f64> {
+        (self.filled.count > 0).then(|| self.cancelled.count as f64 / self.filled.count as f64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +211,26 @@ mod tests {
         assert!(!stats.is_empty());
     }
 
+    #[test]
+    fn test_lifetime_percentiles_resolve_to_power_of_two_buckets() {
+        let mut distribution = LifetimeDistribution::default();
+        assert_eq!(distribution.percentile_ms(0.5), None);
+        for ms in [0, 3, 5, 6, 900] {
+            distribution.count += 1;
+            distribution.total_ms += ms;
+            distribution.buckets[LifetimeDistribution::bucket_of(ms)] += 1;
+        }
+        distribution.min_ms = Some(0);
+        distribution.max_ms = Some(900);
+
+        assert_eq!(LifetimeDistribution::bucket_of(u64::MAX), 64);
+        assert_eq!(distribution.mean_ms(), Some(182.8));
+        assert_eq!(distribution.percentile_ms(0.0), Some(0));
+        assert_eq!(distribution.percentile_ms(0.4), Some(3));
+        assert_eq!(distribution.percentile_ms(0.8), Some(7));
+        assert_eq!(distribution.percentile_ms(1.0), Some(900));
+    }
+
     #[test]
     fn test_distribution_bin_midpoint() {
         let bin = DistributionBin {
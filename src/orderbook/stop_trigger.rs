@@ -0,0 +1,201 @@
+//! Reference price that stop orders trigger on.
+//!
+//! Triggering on the last trade alone is manipulable in a thin book: one
+//! small print through the stop fires it. A book can instead trigger on
+//! the midpoint of the touch, or on an index or mark price supplied from
+//! outside with [`OrderBook::set_reference_price`]. The reference is chosen
+//! per book with [`OrderBook::set_stop_trigger_reference`], and can be
+//! overridden per evaluation with
+//! [`OrderBook::triggered_trailing_stops_on`].
+
+use super::book::OrderBook;
+use super::reference_price::ExternalPriceSource;
+use super::repricing::RepricingOperations;
+use crossbeam::atomic::AtomicCell;
+use pricelevel::Id;
+use serde::{Deserialize, Serialize};
+
+/// Price a stop order is compared against to decide whether it fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum StopTriggerReference {
+    /// Last executed trade price (default). No trigger price until the
+    /// book has traded.
+    #[default]
+    LastTrade,
+    /// Integer midpoint of the best bid and ask. No trigger price while
+    /// the book is one-sided.
+    Mid,
+    /// Fresh index price supplied with [`OrderBook::set_reference_price`].
+    /// No trigger price while none is supplied or it is stale.
+    Index,
+    /// Fresh mark price supplied with [`OrderBook::set_reference_price`]
+    /// or [`OrderBook::update_mark_price`]. No trigger price while none is
+    /// supplied or it is stale.
+    Mark,
+}
+
+impl std::fmt::Display for StopTriggerReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StopTriggerReference::LastTrade => write!(f, "LastTrade"),
+            StopTriggerReference::Mid => write!(f, "Mid"),
+            StopTriggerReference::Index => write!(f, "Index"),
+            StopTriggerReference::Mark => write!(f, "Mark"),
+        }
+    }
+}
+
+/// Per-book trigger settings.
+#[derive(Debug, Default)]
+pub(crate) struct StopTriggerState {
+    reference: AtomicCell<StopTriggerReference>,
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Select the price stop orders trigger on for this book.
+    pub fn set_stop_trigger_reference(&self, reference: StopTriggerReference) {
+        self.stop_trigger.reference.store(reference);
+    }
+
+    /// The price stop orders trigger on for this book.
+    #[must_use]
+    pub fn stop_trigger_reference(&self) -> StopTriggerReference {
+        self.stop_trigger.reference.load()
+    }
+
+    /// Supply the mark price used by [`StopTriggerReference::Mark`], in
+    /// raw price units. Shorthand for [`Self::set_reference_price`] of the
+    /// [`ExternalPriceSource::Mark`] price observed now on the book clock.
+    /// A zero price clears it.
+    pub fn update_mark_price(&self, price: u128) {
+        let now_ms = self.clock().now_millis().as_u64();
+        self.set_reference_price(ExternalPriceSource::Mark, price, now_ms);
+    }
+
+    /// The mark price stops trigger on, unless missing or stale.
+    #[must_use]
+    pub fn mark_price(&self) -> Option<u128> {
+        self.fresh_reference_price(ExternalPriceSource::Mark)
+    }
+
+    /// Resolve `reference` against the book now, or `None` when it has no
+    /// value yet.
+    #[must_use]
+    pub fn stop_trigger_price(&self, reference: StopTriggerReference) -> Option<u128> {
+        match reference {
+            StopTriggerReference::LastTrade => self.last_trade_price(),
+            StopTriggerReference::Mid => self.bbo().midpoint(),
+            StopTriggerReference::Index => self.fresh_reference_price(ExternalPriceSource::Index),
+            StopTriggerReference::Mark => self.mark_price(),
+        }
+    }
+
+    /// Tracked trailing stops whose stop is reached by the book's
+    /// [trigger reference](Self::stop_trigger_reference), in tracker
+    /// order. Empty while the reference has no value.
+    #[must_use]
+    pub fn triggered_trailing_stops(&self) -> Vec<Id> {
+        self.triggered_trailing_stops_on(self.stop_trigger_reference())
+    }
+
+    /// [`Self::triggered_trailing_stops`] against an explicit `reference`,
+    /// for callers that select the trigger per order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::{OrderBook, StopTriggerReference};
+    /// use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let stop = Id::new();
+    /// book.add_order(OrderType::TrailingStop {
+    ///     id: stop,
+    ///     price: Price::new(95),
+    ///     quantity: Quantity::new(1),
+    ///     side: Side::Sell,
+    ///     user_id: Hash32::zero(),
+    ///     timestamp: TimestampMs::new(0),
+    ///     time_in_force: TimeInForce::Gtc,
+    ///     trail_amount: Quantity::new(5),
+    ///     last_reference_price: Price::new(100),
+    ///     extra_fields: (),
+    /// })
+    /// .unwrap();
+    ///
+    /// book.update_mark_price(94);
+    /// assert_eq!(book.triggered_trailing_stops_on(StopTriggerReference::Mark), vec![stop]);
+    /// assert!(book.triggered_trailing_stops().is_empty(), "no trade yet");
+    /// ```
+    #[must_use]
+    pub fn triggered_trailing_stops_on(&self, reference: StopTriggerReference) -> Vec<Id> {
+        let Some(trigger_price) = self.stop_trigger_price(reference) else {
+            return Vec::new();
+        };
+        self.trailing_stop_ids()
+            .into_iter()
+            .filter(|&id| {
+                self.get_order(id).is_some_and(|order| {
+                    self.should_trigger_trailing_stop(order.as_ref(), trigger_price)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_resolve_against_the_book() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        assert_eq!(
+            book.stop_trigger_reference(),
+            StopTriggerReference::LastTrade
+        );
+        assert_eq!(
+            book.stop_trigger_price(StopTriggerReference::LastTrade),
+            None
+        );
+        assert_eq!(book.stop_trigger_price(StopTriggerReference::Mid), None);
+        assert_eq!(book.stop_trigger_price(StopTriggerReference::Mark), None);
+
+        book.add_limit_order(
+            Id::new(),
+            100,
+            1,
+            pricelevel::Side::Buy,
+            pricelevel::TimeInForce::Gtc,
+            None,
+        )
+        .expect("bid");
+        book.add_limit_order(
+            Id::new(),
+            103,
+            1,
+            pricelevel::Side::Sell,
+            pricelevel::TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+        assert_eq!(
+            book.stop_trigger_price(StopTriggerReference::Mid),
+            Some(101)
+        );
+
+        book.update_mark_price(250);
+        assert_eq!(
+            book.stop_trigger_price(StopTriggerReference::Mark),
+            Some(250)
+        );
+        book.update_mark_price(0);
+        assert_eq!(book.mark_price(), None);
+
+        book.set_stop_trigger_reference(StopTriggerReference::Mid);
+        assert_eq!(book.stop_trigger_reference().to_string(), "Mid");
+    }
+}
@@ -15,7 +15,15 @@
 //!
 //! Orders with `user_id == Hash32::zero()` (anonymous) always bypass STP checks,
 //! regardless of the configured mode.
+//!
+//! # Account groups
+//!
+//! A taker that belongs to an account group (see
+//! [`OrderBook::set_account_group`](super::book::OrderBook::set_account_group))
+//! also conflicts with resting orders of the other members of its group, and
+//! the configured mode applies to them exactly as to same-user orders.
 
+use super::account_groups::AccountGroups;
 use pricelevel::{Hash32, Id};
 use serde::{Deserialize, Serialize};
 
@@ -70,6 +78,53 @@ impl STPMode {
     }
 }
 
+/// The resting owners a taker must not trade with: the taker's own user
+/// and, when it belongs to an account group, every other member of that
+/// group.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StpScope<'a> {
+    user_id: Hash32,
+    group: Option<(u64, &'a AccountGroups)>,
+}
+
+impl<'a> StpScope<'a> {
+    /// A scope covering `user_id` alone.
+    #[inline]
+    pub(crate) fn user(user_id: Hash32) -> Self {
+        Self {
+            user_id,
+            group: None,
+        }
+    }
+
+    /// A scope covering `user_id` and the members of its account group, if
+    /// it has one. Resolves the taker's group once, so a sweep sees one
+    /// membership even if the registry changes concurrently.
+    #[inline]
+    pub(crate) fn with_groups(user_id: Hash32, groups: &'a AccountGroups) -> Self {
+        Self {
+            user_id,
+            group: groups.group_of(user_id).map(|group| (group, groups)),
+        }
+    }
+
+    /// The taker's user id.
+    #[inline]
+    pub(crate) fn user_id(&self) -> Hash32 {
+        self.user_id
+    }
+
+    /// `true` if a resting order owned by `owner` conflicts with the taker.
+    /// Anonymous owners never join a group conflict.
+    #[inline]
+    pub(crate) fn conflicts_with(&self, owner: Hash32) -> bool {
+        owner == self.user_id
+            || self.group.is_some_and(|(group, groups)| {
+                owner != Hash32::zero() && groups.group_of(owner) == Some(group)
+            })
+    }
+}
+
 /// Result of an STP check against a single price level.
 ///
 /// Used internally by the matching engine to decide how to proceed
@@ -108,7 +163,7 @@ pub(crate) enum STPAction {
 ///
 /// # Arguments
 /// * `orders` — Resting orders at the price level, in FIFO (time-priority) order.
-/// * `taker_user_id` — The user ID of the incoming (taker) order.
+/// * `scope` — The owners the incoming (taker) order must not trade with.
 /// * `mode` — The active STP mode.
 ///
 /// # Returns
@@ -116,11 +171,11 @@ pub(crate) enum STPAction {
 #[inline]
 pub(crate) fn check_stp_at_level(
     orders: &[std::sync::Arc<pricelevel::OrderType<()>>],
-    taker_user_id: Hash32,
+    scope: StpScope<'_>,
     mode: STPMode,
 ) -> STPAction {
     // Fast path: no STP or anonymous taker
-    if mode == STPMode::None || taker_user_id == Hash32::zero() {
+    if mode == STPMode::None || scope.user_id() == Hash32::zero() {
         return STPAction::NoConflict;
     }
 
@@ -131,7 +186,7 @@ pub(crate) fn check_stp_at_level(
             // Find the first same-user order and sum quantity before it
             let mut safe_quantity: u64 = 0;
             for order in orders {
-                if order.user_id() == taker_user_id {
+                if scope.conflicts_with(order.user_id()) {
                     return STPAction::CancelTaker { safe_quantity };
                 }
                 // Sum visible quantity of non-same-user orders
@@ -144,7 +199,7 @@ pub(crate) fn check_stp_at_level(
             // Signal a conflict if any resting order belongs to the taker; the
             // caller cancels the same-user makers by re-scanning the snapshot in
             // insertion-sequence order, so no `Vec<Id>` is built here (#107).
-            if orders.iter().any(|o| o.user_id() == taker_user_id) {
+            if orders.iter().any(|o| scope.conflicts_with(o.user_id())) {
                 STPAction::CancelMaker
             } else {
                 STPAction::NoConflict
@@ -155,7 +210,7 @@ pub(crate) fn check_stp_at_level(
             // Find the first same-user order and sum quantity before it
             let mut safe_quantity: u64 = 0;
             for order in orders {
-                if order.user_id() == taker_user_id {
+                if scope.conflicts_with(order.user_id()) {
                     return STPAction::CancelBoth {
                         safe_quantity,
                         maker_order_id: order.id(),
@@ -196,7 +251,7 @@ mod tests {
     #[test]
     fn test_check_stp_none_mode_returns_no_conflict() {
         let orders = vec![];
-        let action = check_stp_at_level(&orders, Hash32::zero(), STPMode::None);
+        let action = check_stp_at_level(&orders, StpScope::user(Hash32::zero()), STPMode::None);
         assert!(matches!(action, STPAction::NoConflict));
     }
 
@@ -214,7 +269,7 @@ mod tests {
             extra_fields: (),
         });
         let orders = vec![order];
-        let action = check_stp_at_level(&orders, user, STPMode::CancelTaker);
+        let action = check_stp_at_level(&orders, StpScope::user(user), STPMode::CancelTaker);
         assert!(matches!(action, STPAction::NoConflict));
     }
 
@@ -232,7 +287,7 @@ mod tests {
             extra_fields: (),
         });
         let orders = vec![order];
-        let action = check_stp_at_level(&orders, user, STPMode::CancelTaker);
+        let action = check_stp_at_level(&orders, StpScope::user(user), STPMode::CancelTaker);
         match action {
             STPAction::CancelTaker { safe_quantity } => assert_eq!(safe_quantity, 0),
             _ => panic!("expected CancelTaker action"),
@@ -265,7 +320,7 @@ mod tests {
             extra_fields: (),
         });
         let orders = vec![other_order, same_order];
-        let action = check_stp_at_level(&orders, taker_user, STPMode::CancelTaker);
+        let action = check_stp_at_level(&orders, StpScope::user(taker_user), STPMode::CancelTaker);
         match action {
             STPAction::CancelTaker { safe_quantity } => assert_eq!(safe_quantity, 5),
             _ => panic!("expected CancelTaker action"),
@@ -311,7 +366,7 @@ mod tests {
         // CancelMaker is now a unit variant; per-id cancellation is the caller's
         // responsibility (it re-scans the snapshot), so the action just signals
         // that a same-user maker exists at this level (#107).
-        let action = check_stp_at_level(&orders, taker_user, STPMode::CancelMaker);
+        let action = check_stp_at_level(&orders, StpScope::user(taker_user), STPMode::CancelMaker);
         assert!(matches!(action, STPAction::CancelMaker));
     }
 
@@ -341,7 +396,7 @@ mod tests {
             extra_fields: (),
         });
         let orders = vec![other, same.clone()];
-        let action = check_stp_at_level(&orders, user, STPMode::CancelBoth);
+        let action = check_stp_at_level(&orders, StpScope::user(user), STPMode::CancelBoth);
         match action {
             STPAction::CancelBoth {
                 safe_quantity,
@@ -354,6 +409,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stp_scope_covers_account_group_members() {
+        let taker_user = Hash32::new([1u8; 32]);
+        let member = Hash32::new([2u8; 32]);
+        let outsider = Hash32::new([3u8; 32]);
+        let groups = AccountGroups::default();
+        groups.replace(vec![
+            (taker_user, 7),
+            (member, 7),
+            (outsider, 8),
+            (Hash32::zero(), 7),
+        ]);
+
+        let scope = StpScope::with_groups(taker_user, &groups);
+        assert!(scope.conflicts_with(taker_user));
+        assert!(scope.conflicts_with(member));
+        assert!(!scope.conflicts_with(outsider));
+        assert!(!scope.conflicts_with(Hash32::zero()));
+        assert!(!StpScope::user(taker_user).conflicts_with(member));
+
+        let order = std::sync::Arc::new(pricelevel::OrderType::Standard {
+            id: Id::new(),
+            price: pricelevel::Price::new(100),
+            quantity: pricelevel::Quantity::new(10),
+            side: pricelevel::Side::Sell,
+            user_id: member,
+            timestamp: pricelevel::TimestampMs::new(0),
+            time_in_force: pricelevel::TimeInForce::Gtc,
+            extra_fields: (),
+        });
+        let orders = vec![order];
+        assert!(matches!(
+            check_stp_at_level(&orders, scope, STPMode::CancelTaker),
+            STPAction::CancelTaker { safe_quantity: 0 }
+        ));
+    }
+
     #[test]
     fn test_check_stp_no_conflict_when_different_users() {
         let taker_user = Hash32::new([1u8; 32]);
@@ -373,15 +465,15 @@ mod tests {
 
         // All modes should return NoConflict for different users
         assert!(matches!(
-            check_stp_at_level(&orders, taker_user, STPMode::CancelTaker),
+            check_stp_at_level(&orders, StpScope::user(taker_user), STPMode::CancelTaker),
             STPAction::NoConflict
         ));
         assert!(matches!(
-            check_stp_at_level(&orders, taker_user, STPMode::CancelMaker),
+            check_stp_at_level(&orders, StpScope::user(taker_user), STPMode::CancelMaker),
             STPAction::NoConflict
         ));
         assert!(matches!(
-            check_stp_at_level(&orders, taker_user, STPMode::CancelBoth),
+            check_stp_at_level(&orders, StpScope::user(taker_user), STPMode::CancelBoth),
             STPAction::NoConflict
         ));
     }
@@ -0,0 +1,52 @@
+//! Synchronization primitives used by the lock-free caches.
+//!
+//! Normal builds re-export `std` atomics, `std::sync::RwLock` and
+//! crossbeam's `AtomicCell`. Built with `RUSTFLAGS="--cfg loom"` the same
+//! names come from [loom](https://docs.rs/loom), so the cache protocols
+//! can be model checked over every interleaving:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --lib loom
+//! ```
+//!
+//! Code that needs a primitive shared with a loom model imports it from
+//! here rather than from `std`.
+
+#[cfg(not(loom))]
+pub(crate) use crossbeam::atomic::AtomicCell;
+#[cfg(not(loom))]
+pub(crate) use std::sync::RwLock;
+#[cfg(not(loom))]
+pub(crate) use std::sync::atomic::{AtomicBool, AtomicU64, Ordering, fence};
+#[cfg(not(loom))]
+pub(crate) use std::thread_local;
+
+#[cfg(loom)]
+pub(crate) use loom::sync::RwLock;
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, AtomicU64, Ordering, fence};
+#[cfg(loom)]
+pub(crate) use loom::thread_local;
+
+/// Loom has no 128-bit atomic; a mutex-backed cell gives the model the
+/// same load / store surface as crossbeam's `AtomicCell`.
+#[cfg(loom)]
+#[derive(Debug, Default)]
+pub(crate) struct AtomicCell<T>(loom::sync::Mutex<T>);
+
+#[cfg(loom)]
+impl<T: Copy> AtomicCell<T> {
+    pub(crate) fn load(&self) -> T {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn store(&self, value: T) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = value;
+    }
+}
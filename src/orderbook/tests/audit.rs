@@ -0,0 +1,91 @@
+//! Tests for the invariant auditor against deliberately corrupted indexes
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::audit::InvariantViolation;
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn book_with_order(order_id: Id) -> OrderBook<()> {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        book.add_limit_order(order_id, 100, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("rest");
+        book.add_limit_order(Id::new(), 110, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("rest");
+        book
+    }
+
+    #[test]
+    fn test_missing_order_location() {
+        let order_id = Id::new();
+        let book = book_with_order(order_id);
+        book.order_locations.remove(&order_id);
+
+        assert_eq!(
+            book.audit(),
+            vec![InvariantViolation::MissingOrderLocation {
+                order_id,
+                side: Side::Buy,
+                price: 100,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_misplaced_and_stale_order_locations() {
+        let order_id = Id::new();
+        let ghost = Id::new();
+        let book = book_with_order(order_id);
+        book.order_locations.insert(order_id, (105, Side::Buy));
+        book.order_locations.insert(ghost, (90, Side::Sell));
+
+        let violations = book.audit();
+        assert_eq!(violations.len(), 2);
+        assert!(
+            violations.contains(&InvariantViolation::MisplacedOrderLocation {
+                order_id,
+                resting: (Side::Buy, 100),
+                indexed: (Side::Buy, 105),
+            })
+        );
+        assert!(
+            violations.contains(&InvariantViolation::StaleOrderLocation {
+                order_id: ghost,
+                side: Side::Sell,
+                price: 90,
+            })
+        );
+    }
+
+    #[test]
+    fn test_user_order_index_drift() {
+        let order_id = Id::new();
+        let ghost = Id::new();
+        let book = book_with_order(order_id);
+        let user_id = Hash32::zero();
+        book.user_orders.insert(user_id, Vec::new());
+        book.user_orders.insert(Hash32::new([1; 32]), vec![ghost]);
+
+        let violations = book.audit();
+        assert!(violations.contains(&InvariantViolation::MissingUserOrder { user_id, order_id }));
+        assert!(violations.contains(&InvariantViolation::StaleUserOrder {
+            user_id: Hash32::new([1; 32]),
+            order_id: ghost,
+        }));
+    }
+
+    #[test]
+    fn test_stale_cached_best_price() {
+        let book = book_with_order(Id::new());
+        book.cache.update_best_bid(Some(99));
+
+        assert_eq!(
+            book.audit(),
+            vec![InvariantViolation::StaleCachedBestPrice {
+                side: Side::Buy,
+                cached: 99,
+                actual: Some(100),
+            }]
+        );
+    }
+}
@@ -991,8 +991,9 @@ mod test_book_remaining {
 
 #[cfg(test)]
 mod test_book_specific {
-    use crate::OrderBook;
+    use crate::{OrderBook, TopOfBook};
     use pricelevel::{Id, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
 
     fn create_order_id() -> Id {
         Id::new_uuid()
@@ -1081,7 +1082,6 @@ mod test_book_specific {
     #[test]
     fn test_with_clock_stamps_orders_via_injected_clock() {
         use crate::orderbook::clock::{Clock, StubClock};
-        use std::sync::Arc;
 
         // Start the stub at 1000 ms, step of 1; each `now_millis` advances it.
         let clock: Arc<dyn Clock> = Arc::new(StubClock::starting_at(1000));
@@ -1200,7 +1200,7 @@ mod test_book_specific {
                     guard.push(trade_result.engine_seq);
                 }
             });
-        let mut book: OrderBook<()> = OrderBook::with_trade_listener("TEST", listener);
+        let book: OrderBook<()> = OrderBook::with_trade_listener("TEST", listener);
         // No price-level listener so trades are the only outbound events.
         book.remove_price_level_listener();
 
@@ -1255,7 +1255,7 @@ mod test_book_specific {
                 guard.push(event.engine_seq);
             }
         });
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_price_level_listener(listener);
 
         // Open two distinct price levels — each emits one event.
@@ -1664,4 +1664,189 @@ mod test_book_specific {
             "default construction must keep per-book random namespaces"
         );
     }
+
+    #[test]
+    fn test_with_order_capacity_presizes_order_index() {
+        let book: OrderBook<()> = OrderBook::with_order_capacity("TEST", 10_000);
+        assert!(book.order_index_capacity() >= 10_000);
+
+        for i in 0..100 {
+            book.add_limit_order(
+                Id::new_uuid(),
+                100 + i,
+                1,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("rest order");
+        }
+        assert!(book.order_index_capacity() >= 10_000);
+        assert_eq!(book.best_bid(), Some(199));
+    }
+
+    #[test]
+    fn test_user_index_is_sized_by_users_not_orders() {
+        let book: OrderBook<()> = OrderBook::with_order_capacity("TEST", 100_000);
+        assert!(book.order_owners.capacity() >= 100_000);
+        assert!(book.user_orders.capacity() < 100_000);
+
+        let book: OrderBook<()> = OrderBook::builder("TEST")
+            .order_capacity(100_000)
+            .user_capacity(500)
+            .build()
+            .expect("book");
+        assert!(book.order_index_capacity() >= 100_000);
+        assert!(book.user_orders.capacity() >= 500);
+        assert!(book.user_orders.capacity() < 100_000);
+    }
+
+    #[test]
+    fn test_with_order_index_accepts_any_shard_count() {
+        for shards in [0, 1, 3, 64, 1000, usize::MAX] {
+            let book: OrderBook<()> = OrderBook::with_order_index("TEST", 1_000, shards);
+            assert!(book.order_index_capacity() >= 1_000);
+            let id = Id::new_uuid();
+            book.add_limit_order(id, 100, 1, Side::Buy, TimeInForce::Gtc, None)
+                .expect("rest order");
+            assert!(book.cancel_order(id).expect("cancel").is_some());
+        }
+    }
+
+    #[test]
+    fn test_reclaim_removed_levels_keeps_book_consistent() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let mut ids = Vec::new();
+        for price in 100..150 {
+            let id = Id::new_uuid();
+            book.add_limit_order(id, price, 1, Side::Sell, TimeInForce::Gtc, None)
+                .expect("rest order");
+            ids.push(id);
+        }
+        let held = book.get_asks();
+        for id in &ids[..49] {
+            book.cancel_order(*id).expect("cancel");
+        }
+
+        book.reclaim_removed_levels();
+
+        assert_eq!(book.best_ask(), Some(149));
+        assert_eq!(book.get_asks().len(), 1);
+        // A snapshot taken before the removals stays readable.
+        assert_eq!(held.len(), 50);
+    }
+
+    #[test]
+    fn test_reclaim_removed_levels_destroys_unlinked_levels() {
+        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        // Without the pool the skip map node holds the only handle.
+        book.set_level_pool_max_size(0);
+        let id = Id::new_uuid();
+        book.add_limit_order(id, 100, 1, Side::Sell, TimeInForce::Gtc, None)
+            .expect("rest order");
+        let level = Arc::downgrade(book.asks.get(&100).expect("level").value());
+
+        book.cancel_order(id).expect("cancel");
+        assert!(book.asks.get(&100).is_none());
+        // Unlinked, but its destruction is deferred to a later epoch.
+        assert!(level.upgrade().is_some());
+
+        // Other test threads may hold the epoch back for a moment.
+        let mut rounds = 0;
+        while level.upgrade().is_some() {
+            assert!(rounds < 10_000, "level never reclaimed");
+            book.reclaim_removed_levels();
+            rounds += 1;
+        }
+    }
+
+    #[test]
+    fn test_top_of_book_tracks_prices_and_quantities() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let empty = book.top_of_book();
+        assert_eq!(empty.best_bid, None);
+        assert_eq!(empty.best_ask, None);
+
+        book.add_limit_order(Id::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_limit_order(Id::new_uuid(), 110, 2, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        let first = book.top_of_book();
+        assert_eq!(first.best_bid, Some(100));
+        assert_eq!(first.best_ask, Some(110));
+        assert_eq!((first.bid_quantity, first.ask_quantity), (5, 2));
+        // Served from cache while nothing changes.
+        assert_eq!(book.top_of_book(), first);
+
+        // Joining the best bid changes only its quantity.
+        book.add_limit_order(Id::new_uuid(), 100, 3, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        let second = book.top_of_book();
+        assert_eq!(second.bid_quantity, 8);
+        assert!(second.update_seq > first.update_seq);
+        assert_eq!(book.mid_price(), Some(105.0));
+        assert_eq!(book.spread(), Some(10));
+        assert_eq!(book.micro_price(), second.micro_price());
+    }
+
+    #[test]
+    fn test_bbo_is_the_pair_behind_derived_metrics() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        assert_eq!(book.bbo().midpoint(), None);
+
+        book.add_limit_order(Id::new_uuid(), 101, 4, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        let one_sided = book.bbo();
+        assert_eq!(one_sided.midpoint(), None);
+        book.add_limit_order(Id::new_uuid(), 104, 2, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+
+        let bbo = book.bbo();
+        assert_eq!(bbo, book.top_of_book());
+        assert!(bbo.update_seq > one_sided.update_seq);
+        assert_eq!(
+            (bbo.best_bid, bbo.best_ask),
+            (book.best_bid(), book.best_ask())
+        );
+        assert_eq!(bbo.midpoint(), Some(102));
+        assert_eq!(book.midpoint_price(), bbo.midpoint());
+        assert_eq!(book.mid_price(), bbo.mid_price());
+        assert_eq!(book.spread(), bbo.spread());
+        assert_eq!(book.micro_price(), bbo.micro_price());
+
+        let extreme = TopOfBook {
+            best_bid: Some(u128::MAX - 1),
+            best_ask: Some(u128::MAX),
+            ..bbo
+        };
+        assert_eq!(extreme.midpoint(), Some(u128::MAX - 1));
+    }
+
+    #[test]
+    fn test_sweep_publishes_the_new_best_price_once() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        for price in 101..=110 {
+            book.add_limit_order(Id::new_uuid(), price, 1, Side::Sell, TimeInForce::Gtc, None)
+                .expect("ask");
+        }
+        book.submit_market_order(Id::new_uuid(), 4, Side::Buy)
+            .expect("sweep");
+        // The sweep left the swept side's best price in the cache.
+        assert_eq!(book.cache.get_cached_best_ask(), Some(105));
+        assert_eq!(book.best_ask(), Some(105));
+
+        // A limit that sweeps and rests keeps the swept side cached and
+        // drops only its own side.
+        book.add_limit_order(Id::new_uuid(), 107, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("crossing bid");
+        assert_eq!(book.cache.get_cached_best_ask(), Some(108));
+        assert_eq!(book.cache.get_cached_best_bid(), None);
+        assert_eq!(book.best_bid(), Some(107));
+
+        // Sweeping the last level leaves the side uncached and empty.
+        book.submit_market_order(Id::new_uuid(), 3, Side::Buy)
+            .expect("sweep to empty");
+        assert_eq!(book.cache.get_cached_best_ask(), None);
+        assert_eq!(book.best_ask(), None);
+    }
 }
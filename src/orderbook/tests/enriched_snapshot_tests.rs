@@ -240,6 +240,9 @@ mod tests {
         assert!(flags.contains(MetricFlags::DEPTH));
         assert!(flags.contains(MetricFlags::VWAP));
         assert!(flags.contains(MetricFlags::IMBALANCE));
+        assert!(flags.contains(MetricFlags::MICRO_PRICE));
+        assert!(flags.contains(MetricFlags::DEPTH_WEIGHTED_MID));
+        assert!(flags.contains(MetricFlags::CUMULATIVE_DEPTH_PROFILE));
     }
 
     #[test]
@@ -258,6 +261,75 @@ mod tests {
         assert!((vwap_bid - 99.333).abs() < 0.01);
     }
 
+    #[test]
+    fn test_enriched_snapshot_micro_price() {
+        let book = setup_test_book();
+        let snapshot = book.enriched_snapshot_with_metrics(10, MetricFlags::MICRO_PRICE);
+
+        // (101 * 10 + 100 * 15) / 25 = 100.4
+        let micro = snapshot.micro_price.unwrap();
+        assert!((micro - 100.4).abs() < 1e-9);
+        assert_eq!(Some(micro), book.micro_price());
+
+        assert!(snapshot.mid_price.is_none());
+        assert!(snapshot.depth_weighted_mid.is_none());
+        assert!(snapshot.bid_depth_profile.is_empty());
+    }
+
+    #[test]
+    fn test_enriched_snapshot_depth_weighted_mid() {
+        let book = setup_test_book();
+        let snapshot = book.enriched_snapshot_with_metrics(2, MetricFlags::DEPTH_WEIGHTED_MID);
+
+        // Bid VWAP over 2 levels = 2980 / 30, ask VWAP = 4065 / 40
+        let bid_vwap = 2980.0 / 30.0;
+        let ask_vwap = 4065.0 / 40.0;
+        let expected = (ask_vwap * 30.0 + bid_vwap * 40.0) / 70.0;
+        let dwm = snapshot.depth_weighted_mid.unwrap();
+        assert!((dwm - expected).abs() < 1e-9);
+        assert!(snapshot.micro_price.is_none());
+    }
+
+    #[test]
+    fn test_enriched_snapshot_depth_weighted_mid_one_sided_book() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+
+        let snapshot = book.enriched_snapshot(5);
+        assert!(snapshot.micro_price.is_none());
+        assert!(snapshot.depth_weighted_mid.is_none());
+        assert_eq!(snapshot.bid_depth_profile, vec![10]);
+        assert!(snapshot.ask_depth_profile.is_empty());
+    }
+
+    #[test]
+    fn test_enriched_snapshot_cumulative_depth_profile() {
+        let book = setup_test_book();
+        let snapshot =
+            book.enriched_snapshot_with_metrics(10, MetricFlags::CUMULATIVE_DEPTH_PROFILE);
+
+        assert_eq!(snapshot.bid_depth_profile, vec![10, 30, 60]);
+        assert_eq!(snapshot.ask_depth_profile, vec![15, 40, 75]);
+    }
+
+    #[test]
+    fn test_enriched_snapshot_deserializes_without_new_fields() {
+        let book = setup_test_book();
+        let snapshot = book.enriched_snapshot(10);
+
+        let mut value = serde_json::to_value(&snapshot).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("micro_price");
+        object.remove("depth_weighted_mid");
+        object.remove("bid_depth_profile");
+        object.remove("ask_depth_profile");
+
+        let decoded: crate::orderbook::snapshot::EnrichedSnapshot =
+            serde_json::from_value(value).unwrap();
+        assert!(decoded.micro_price.is_none());
+        assert!(decoded.bid_depth_profile.is_empty());
+    }
+
     #[test]
     fn test_enriched_snapshot_serialization() {
         let book = setup_test_book();
@@ -277,4 +349,28 @@ mod tests {
         assert_eq!(deserialized_snapshot.symbol, snapshot.symbol);
         assert_eq!(deserialized_snapshot.bids.len(), snapshot.bids.len());
     }
+
+    #[test]
+    fn test_enriched_snapshot_carries_reference_prices() {
+        use crate::{ExternalPrice, ExternalPriceSource};
+
+        let book = setup_test_book();
+        assert_eq!(book.enriched_snapshot(5).index_price, None);
+
+        book.set_reference_price(ExternalPriceSource::Index, 100, 7);
+        book.set_reference_price(ExternalPriceSource::Mark, 101, 8);
+        for snapshot in [
+            book.enriched_snapshot(5),
+            book.enriched_aggregate_snapshot(5),
+        ] {
+            assert_eq!(
+                snapshot.index_price,
+                Some(ExternalPrice {
+                    price: 100,
+                    timestamp_ms: 7
+                })
+            );
+            assert_eq!(snapshot.mark_price.map(|mark| mark.price), Some(101));
+        }
+    }
 }
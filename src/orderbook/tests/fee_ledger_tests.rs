@@ -0,0 +1,232 @@
+//! Tests for per-user fee accrual
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::clock::StubClock;
+    use crate::orderbook::fee_ledger::FeeLedger;
+    use crate::orderbook::fees::{FeeSchedule, TieredFeeSchedule};
+    use crate::orderbook::trade::{TradeListener, TradeResult};
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    /// Book with a listener collecting every `TradeResult`, plus an
+    /// order-owner registry for the ledger lookups.
+    struct Fixture {
+        book: OrderBook<()>,
+        trades: Arc<Mutex<Vec<TradeResult>>>,
+        owners: HashMap<Id, Hash32>,
+    }
+
+    impl Fixture {
+        fn new(symbol: &str, schedule: FeeSchedule) -> Self {
+            let trades = Arc::new(Mutex::new(Vec::new()));
+            let sink = Arc::clone(&trades);
+            let listener: TradeListener = Arc::new(move |trade: &TradeResult| {
+                sink.lock().unwrap().push(trade.clone());
+            });
+            let book = OrderBook::with_trade_listener(symbol, listener);
+            book.set_fee_schedule(Some(schedule));
+            Self {
+                book,
+                trades,
+                owners: HashMap::new(),
+            }
+        }
+
+        fn order(&mut self, price: u128, quantity: u64, side: Side, owner: Hash32) {
+            let id = Id::new();
+            self.owners.insert(id, owner);
+            let _ = self.book.add_limit_order_with_user(
+                id,
+                price,
+                quantity,
+                side,
+                TimeInForce::Gtc,
+                owner,
+                None,
+            );
+        }
+    }
+
+    #[test]
+    fn test_ledger_matches_trade_result_totals() {
+        let schedule = FeeSchedule::new(-2, 5);
+        let mut fx = Fixture::new("BTC/USD", schedule);
+        fx.order(10_000, 10, Side::Sell, user(1));
+        fx.order(10_010, 10, Side::Sell, user(2));
+        fx.order(10_010, 15, Side::Buy, user(3));
+
+        let ledger = FeeLedger::new();
+        let trades = fx.trades.lock().unwrap().clone();
+        assert_eq!(trades.len(), 1);
+        for trade in &trades {
+            ledger.record_trade(trade, &schedule, |id| fx.owners.get(&id).copied());
+        }
+
+        let taker = ledger.fees_for_user_symbol(user(3), "BTC/USD").unwrap();
+        assert_eq!(taker.taker_fees, trades[0].total_taker_fees);
+        assert_eq!(taker.taker_fills, 2);
+        assert_eq!(taker.taker_notional, 10_000 * 10 + 10_010 * 5);
+
+        let maker_rebates =
+            ledger.user_total(user(1)).maker_fees + ledger.user_total(user(2)).maker_fees;
+        assert_eq!(maker_rebates, trades[0].total_maker_fees);
+        assert_eq!(ledger.user_total(user(1)).maker_fees, -20);
+    }
+
+    #[test]
+    fn test_record_charged_matches_recomputed_fees() {
+        let schedule = FeeSchedule::new(-2, 5);
+        let mut fx = Fixture::new("BTC/USD", schedule);
+        fx.order(10_000, 10, Side::Sell, user(1));
+        fx.order(10_010, 10, Side::Sell, user(2));
+        fx.order(10_010, 15, Side::Buy, user(3));
+
+        let charged = FeeLedger::new();
+        let recomputed = FeeLedger::new();
+        for trade in fx.trades.lock().unwrap().iter() {
+            charged.record_charged(trade);
+            recomputed.record_trade(trade, &schedule, |id| fx.owners.get(&id).copied());
+        }
+        assert_eq!(charged.export(), recomputed.export());
+        assert_eq!(charged.user_total(user(3)).taker_fills, 2);
+    }
+
+    #[test]
+    fn test_fees_for_user_spans_symbols_and_export_is_sorted() {
+        let ledger = FeeLedger::new();
+        ledger.record_fee(user(2), "ETH/USD", 1_000, 5, false);
+        ledger.record_fee(user(1), "ETH/USD", 1_000, -2, true);
+        ledger.record_fee(user(1), "BTC/USD", 2_000, 10, false);
+
+        let fees = ledger.fees_for_user(user(1));
+        let symbols: Vec<&str> = fees.iter().map(|(symbol, _)| symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["BTC/USD", "ETH/USD"]);
+
+        let total = ledger.user_total(user(1));
+        assert_eq!(total.net_fees(), 8);
+        assert_eq!(total.total_notional(), 3_000);
+
+        let exported = ledger.export();
+        let keys: Vec<(Hash32, &str)> = exported
+            .iter()
+            .map(|entry| (entry.user_id, entry.symbol.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (user(1), "BTC/USD"),
+                (user(1), "ETH/USD"),
+                (user(2), "ETH/USD")
+            ]
+        );
+        assert!(serde_json::to_string(&exported).is_ok());
+
+        ledger.reset();
+        assert!(ledger.export().is_empty());
+    }
+
+    #[test]
+    fn test_tiered_ledger_charges_each_user_at_their_tier() {
+        let mut fx = Fixture::new("BTC/USD", FeeSchedule::zero_fee());
+        let tiers = TieredFeeSchedule::new(FeeSchedule::new(0, 10))
+            .with_tier(1_000_000, FeeSchedule::new(-1, 4));
+        // user(3) already qualifies for the discounted tier.
+        tiers.record_volume(user(3), 1_000_000, 0);
+
+        fx.order(10_000, 10, Side::Sell, user(1));
+        fx.order(10_000, 5, Side::Buy, user(3));
+        fx.order(10_000, 5, Side::Buy, user(4));
+
+        let ledger = FeeLedger::new();
+        for trade in fx.trades.lock().unwrap().iter() {
+            ledger.record_trade_tiered(trade, &tiers, |id| fx.owners.get(&id).copied(), 0);
+        }
+
+        // 50_000 notional each: 4 bps vs 10 bps.
+        assert_eq!(ledger.user_total(user(3)).taker_fees, 20);
+        assert_eq!(ledger.user_total(user(4)).taker_fees, 50);
+        assert_eq!(ledger.user_total(user(1)).maker_fills, 2);
+    }
+
+    #[test]
+    fn test_book_tiered_schedule_prices_fills_at_the_pre_trade_tier() {
+        let mut fx = Fixture::new("BTC/USD", FeeSchedule::new(0, 100));
+        let tiers = Arc::new(
+            TieredFeeSchedule::new(FeeSchedule::new(0, 10))
+                .with_tier(100_000, FeeSchedule::new(-1, 4)),
+        );
+        fx.book.set_tiered_fee_schedule(Some(Arc::clone(&tiers)));
+
+        fx.order(10_000, 20, Side::Sell, user(1));
+        // Maker and taker each reach 100_000 of volume with the second fill.
+        fx.order(10_000, 5, Side::Buy, user(3));
+        fx.order(10_000, 5, Side::Buy, user(3));
+        fx.order(10_000, 5, Side::Buy, user(3));
+
+        let trades = fx.trades.lock().unwrap().clone();
+        let taker_fees: Vec<i128> = trades.iter().map(|t| t.total_taker_fees).collect();
+        let maker_fees: Vec<i128> = trades.iter().map(|t| t.total_maker_fees).collect();
+        // 50_000 notional per trade: 10 bps, 10 bps, then 4 bps and a
+        // 1 bp maker rebate. The flat 100 bps schedule is not charged.
+        assert_eq!(taker_fees, vec![50, 50, 20]);
+        assert_eq!(maker_fees, vec![0, 0, -5]);
+        for trade in &trades {
+            let fills: i128 = trade.fills.iter().map(|fill| fill.taker.fee).sum();
+            assert_eq!(fills, trade.total_taker_fees);
+        }
+
+        let now_ms = fx.book.clock().now_millis().as_u64();
+        assert_eq!(tiers.rolling_volume(user(1), now_ms), 150_000);
+        assert_eq!(tiers.rolling_volume(user(3), now_ms), 150_000);
+
+        // The same owners are what the ledger reads off the fills.
+        let ledger = FeeLedger::new();
+        for trade in &trades {
+            ledger.record_charged(trade);
+        }
+        assert_eq!(ledger.user_total(user(3)).taker_fees, 120);
+        assert_eq!(ledger.user_total(user(1)).maker_fees, -5);
+    }
+
+    #[test]
+    fn test_book_tiered_schedule_credits_volume_without_a_listener() {
+        let mut book: OrderBook<()> =
+            OrderBook::with_clock("BTC/USD", Arc::new(StubClock::starting_at(1_000)));
+        let tiers = Arc::new(TieredFeeSchedule::new(FeeSchedule::new(0, 10)));
+        book.set_tiered_fee_schedule(Some(Arc::clone(&tiers)));
+
+        let rest = |price: u128, quantity: u64, side: Side, owner: Hash32| {
+            book.add_limit_order_with_user(
+                Id::new(),
+                price,
+                quantity,
+                side,
+                TimeInForce::Gtc,
+                owner,
+                None,
+            )
+        };
+        assert!(rest(100, 10, Side::Sell, user(1)).is_ok());
+        assert!(rest(100, 4, Side::Buy, user(2)).is_ok());
+        assert!(
+            book.submit_market_order_with_user(Id::new(), 6, Side::Buy, user(3))
+                .is_ok()
+        );
+        // Anonymous takers are charged the base tier but not tracked.
+        assert!(rest(101, 1, Side::Sell, user(1)).is_ok());
+        assert!(rest(101, 1, Side::Buy, Hash32::zero()).is_ok());
+
+        let now_ms = book.clock().now_millis().as_u64();
+        assert_eq!(tiers.rolling_volume(user(1), now_ms), 1_101);
+        assert_eq!(tiers.rolling_volume(user(2), now_ms), 400);
+        assert_eq!(tiers.rolling_volume(user(3), now_ms), 600);
+        assert_eq!(tiers.rolling_volume(Hash32::zero(), now_ms), 0);
+    }
+}
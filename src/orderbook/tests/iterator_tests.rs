@@ -339,4 +339,48 @@ mod tests {
 
         assert_eq!(count, 2); // 25 and 30
     }
+
+    #[test]
+    fn test_ladder_aligns_sides_from_touch() {
+        let book = setup_test_book();
+
+        let rows: Vec<_> = book.ladder(10).collect();
+
+        // 5 bid levels, 4 ask levels: the longer side decides the row count
+        assert_eq!(rows.len(), 5);
+        let bid = rows[0].bid.as_ref().unwrap();
+        let ask = rows[0].ask.as_ref().unwrap();
+        assert_eq!((bid.price, ask.price), (100, 105));
+        assert_eq!(rows[3].ask.as_ref().unwrap().cumulative_depth, 84);
+        assert_eq!(rows[4].bid.as_ref().unwrap().price, 80);
+        assert!(rows[4].ask.is_none());
+    }
+
+    #[test]
+    fn test_ladder_respects_depth_and_empty_book() {
+        let book = setup_test_book();
+        assert_eq!(book.ladder(2).count(), 2);
+        assert_eq!(book.ladder(0).count(), 0);
+
+        let empty: OrderBook = OrderBook::new("EMPTY");
+        assert_eq!(empty.ladder(5).count(), 0);
+    }
+
+    #[test]
+    fn test_format_ladder_is_aligned() {
+        let book = setup_test_book();
+
+        let text = book.format_ladder(5);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 6); // header + 5 rows
+        let separator = lines[0].find('|').unwrap();
+        assert!(lines.iter().all(|line| line.find('|') == Some(separator)));
+        assert!(lines[1].contains("100") && lines[1].contains("105"));
+        assert_eq!(rows_display(&book), "10 @ 100 | 105 @ 12");
+    }
+
+    fn rows_display(book: &OrderBook) -> String {
+        book.ladder(1).next().unwrap().to_string()
+    }
 }
@@ -0,0 +1,172 @@
+//! Tests for admission racing the removal of an emptied price level
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::level_gate::LevelGate;
+    use crate::orderbook::risk::RiskConfig;
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::mpsc;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Duration;
+
+    const ROUNDS: u64 = 5_000;
+
+    #[test]
+    fn test_order_added_while_its_level_empties_stays_linked() {
+        // Each round, one thread empties a fresh level (add, then cancel,
+        // which unlinks it) while the other rests an order at the same
+        // price. Without the level gate the resting order could land in
+        // the level just as it is unlinked and be lost from the book.
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let round = Barrier::new(2);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for i in 0..ROUNDS {
+                    let id = Id::from_u64(ROUNDS + i + 1);
+                    book.add_limit_order(id, 1 + i as u128, 1, Side::Buy, TimeInForce::Gtc, None)
+                        .expect("rest");
+                    round.wait();
+                    book.cancel_order(id).expect("cancel");
+                }
+            });
+            scope.spawn(|| {
+                for i in 0..ROUNDS {
+                    round.wait();
+                    book.add_limit_order(
+                        Id::from_u64(i + 1),
+                        1 + i as u128,
+                        1,
+                        Side::Buy,
+                        TimeInForce::Gtc,
+                        None,
+                    )
+                    .expect("rest");
+                }
+            });
+        });
+
+        assert_eq!(book.audit(), vec![]);
+        assert!((1..=ROUNDS).all(|i| book.get_order(Id::from_u64(i)).is_some()));
+        assert_eq!(book.bids.len() as u64, ROUNDS);
+    }
+
+    #[test]
+    fn test_level_removal_only_blocks_admission_at_its_stripe() {
+        let book: Arc<OrderBook<()>> = Arc::new(OrderBook::new("TEST"));
+        let blocked_price = 100;
+        let free_price = (101..)
+            .find(|&price| LevelGate::stripe_of(price) != LevelGate::stripe_of(blocked_price))
+            .expect("another stripe");
+        let same_stripe_price = (101..)
+            .find(|&price| LevelGate::stripe_of(price) == LevelGate::stripe_of(blocked_price))
+            .expect("same stripe");
+
+        // Hold the stripe a removal at `blocked_price` would take.
+        let removal = book.level_gate.write(blocked_price);
+        let add = |id: u64, price: u128| {
+            let book = Arc::clone(&book);
+            let (done, added) = mpsc::channel();
+            thread::spawn(move || {
+                book.add_limit_order(
+                    Id::from_u64(id),
+                    price,
+                    1,
+                    Side::Buy,
+                    TimeInForce::Gtc,
+                    None,
+                )
+                .expect("rest");
+                done.send(()).expect("send");
+            });
+            added
+        };
+
+        let elsewhere = add(1, free_price);
+        elsewhere
+            .recv_timeout(Duration::from_secs(10))
+            .expect("admission at another stripe is not blocked");
+        let same_stripe = add(2, same_stripe_price);
+        assert!(
+            same_stripe.recv_timeout(Duration::from_millis(50)).is_err(),
+            "admission at the removal's stripe waits for it"
+        );
+
+        drop(removal);
+        same_stripe
+            .recv_timeout(Duration::from_secs(10))
+            .expect("admission resumes once the removal is done");
+        assert_eq!(book.audit(), vec![]);
+    }
+
+    /// Each round, one thread rests an ask at a new lowest price while the
+    /// other takes exactly that price with IOC bids, so bids overlap the
+    /// ask's admission and often fill it the instant it becomes visible.
+    fn race_asks_against_ioc_bids(book: &OrderBook<()>) {
+        let round = Barrier::new(2);
+        let price = |i: u64| u128::from(2 * ROUNDS - i);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for i in 0..ROUNDS {
+                    round.wait();
+                    book.add_limit_order_with_user(
+                        Id::from_u64(i + 1),
+                        price(i),
+                        1,
+                        Side::Sell,
+                        TimeInForce::Gtc,
+                        Hash32::new([1; 32]),
+                        None,
+                    )
+                    .expect("rest");
+                }
+            });
+            scope.spawn(|| {
+                let mut next_id = ROUNDS;
+                for i in 0..ROUNDS {
+                    round.wait();
+                    while book.last_trade_price() != Some(price(i)) {
+                        next_id += 1;
+                        let _ = book.add_limit_order_with_user(
+                            Id::from_u64(next_id),
+                            price(i),
+                            1,
+                            Side::Buy,
+                            TimeInForce::Ioc,
+                            Hash32::new([2; 32]),
+                            None,
+                        );
+                    }
+                }
+            });
+        });
+        assert_eq!(book.best_ask(), None);
+    }
+
+    #[test]
+    fn test_order_filled_as_it_rests_leaves_no_index_entry() {
+        // The sweep's clean-up must find the ask already indexed, or its
+        // location would outlive it.
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        race_asks_against_ioc_bids(&book);
+
+        assert_eq!(book.audit(), vec![]);
+        assert!(book.order_locations.is_empty());
+        assert!(book.user_orders.is_empty());
+    }
+
+    #[test]
+    fn test_order_filled_as_it_rests_releases_its_risk_entry() {
+        // The fill's risk release must find the ask already admitted to
+        // the risk state, or the account would keep a phantom open order.
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(2 * ROUNDS));
+        race_asks_against_ioc_bids(&book);
+
+        assert!(book.risk_state.orders.is_empty());
+        assert!(book.risk_state.counters.is_empty());
+    }
+}
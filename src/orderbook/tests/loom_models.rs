@@ -0,0 +1,263 @@
+//! Loom model checks of the best-price and top-of-book cache protocols.
+//!
+//! The skip maps and `PriceLevel`s are not loom-aware, so each side of the
+//! book is stood in for by a `Mutex<BTreeMap<price, quantity>>` and the
+//! tests replay the exact cache calls the engine makes around it: a
+//! mutation marks its start, writes the map and then invalidates, a reader
+//! stamps its read with `update_seq()` and publishes through the
+//! sequence-checked paths.
+//! Loom then explores every interleaving of those calls.
+//!
+//! Run with:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --lib loom
+//! ```
+
+#[cfg(test)]
+mod tests {
+    use crate::orderbook::cache::{PriceLevelCache, TopOfBook};
+    use loom::sync::{Arc, Mutex};
+    use loom::thread;
+    use pricelevel::Side;
+    use std::collections::BTreeMap;
+
+    /// Price → total resting quantity, standing in for one skip map.
+    type Levels = Arc<Mutex<BTreeMap<u128, u64>>>;
+
+    struct Book {
+        cache: PriceLevelCache,
+        bids: Levels,
+        asks: Levels,
+    }
+
+    impl Book {
+        fn new(bids: &[(u128, u64)], asks: &[(u128, u64)]) -> Arc<Self> {
+            Arc::new(Self {
+                cache: PriceLevelCache::new(),
+                bids: Arc::new(Mutex::new(bids.iter().copied().collect())),
+                asks: Arc::new(Mutex::new(asks.iter().copied().collect())),
+            })
+        }
+
+        /// `OrderBook::best_bid`.
+        fn best_bid(&self) -> Option<u128> {
+            if let Some(price) = self.cache.get_cached_best_bid() {
+                return Some(price);
+            }
+            let update_seq = self.cache.update_seq();
+            let best = self.bids.lock().unwrap().keys().next_back().copied();
+            self.cache.publish_best_price(Side::Buy, best, update_seq);
+            best
+        }
+
+        /// `OrderBook::compute_top_of_book`: the two sides are read under
+        /// separate locks, so a mutation can land between them.
+        fn compute_top_of_book(&self) -> TopOfBook {
+            let update_seq = self.cache.update_seq();
+            let bid = self
+                .bids
+                .lock()
+                .unwrap()
+                .iter()
+                .next_back()
+                .map(|(p, q)| (*p, *q));
+            let ask = self
+                .asks
+                .lock()
+                .unwrap()
+                .iter()
+                .next()
+                .map(|(p, q)| (*p, *q));
+            TopOfBook {
+                best_bid: bid.map(|(price, _)| price),
+                best_ask: ask.map(|(price, _)| price),
+                bid_quantity: bid.map_or(0, |(_, quantity)| quantity),
+                ask_quantity: ask.map_or(0, |(_, quantity)| quantity),
+                update_seq,
+            }
+        }
+
+        /// `OrderBook::top_of_book`, with a single publish attempt.
+        fn top_of_book(&self) -> TopOfBook {
+            if let Some(top) = self.cache.get_cached_top_of_book() {
+                return top;
+            }
+            let top = self.compute_top_of_book();
+            if self.cache.is_stable_at(top.update_seq) {
+                self.cache.update_top_of_book(&top);
+            }
+            top
+        }
+
+        /// Best bid, best ask and their quantities, ignoring the sequence.
+        fn quote(top: &TopOfBook) -> (Option<u128>, u64, Option<u128>, u64) {
+            (
+                top.best_bid,
+                top.bid_quantity,
+                top.best_ask,
+                top.ask_quantity,
+            )
+        }
+    }
+
+    /// A reader probing the best bid while the last order at that level is
+    /// cancelled must not leave the removed price cached once the cancel
+    /// has invalidated.
+    #[test]
+    fn best_price_publish_never_outlives_level_removal() {
+        loom::model(|| {
+            let book = Book::new(&[(100, 5), (101, 5)], &[]);
+
+            let reader = {
+                let book = Arc::clone(&book);
+                thread::spawn(move || book.best_bid())
+            };
+
+            let mutation = book.cache.begin_mutation();
+            book.bids.lock().unwrap().remove(&101);
+            book.cache.invalidate();
+            drop(mutation);
+
+            let seen = reader.join().unwrap();
+            assert!(matches!(seen, Some(100) | Some(101)));
+            let cached = book.cache.get_cached_best_bid();
+            assert!(
+                matches!(cached, None | Some(100)),
+                "removed level 101 survived in the cache: {cached:?} seen {seen:?} seq {}",
+                book.cache.update_seq()
+            );
+            assert_eq!(book.best_bid(), Some(100));
+        });
+    }
+
+    /// An add and a cancel on the same level race each other and a
+    /// top-of-book reader. Whatever the interleaving, once both writers
+    /// have invalidated, the cache serves either nothing or the final
+    /// state — never a quantity that one of the writers overwrote.
+    #[test]
+    fn concurrent_add_and_cancel_on_the_same_level() {
+        let mut builder = loom::model::Builder::new();
+        builder.preemption_bound = Some(3);
+        builder.check(|| {
+            let book = Book::new(&[(100, 5)], &[(101, 4)]);
+
+            let add = {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    let _mutation = book.cache.begin_mutation();
+                    *book.bids.lock().unwrap().entry(100).or_insert(0) += 3;
+                    book.cache.invalidate_side(Side::Buy);
+                })
+            };
+            let cancel = {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    let _mutation = book.cache.begin_mutation();
+                    let mut bids = book.bids.lock().unwrap();
+                    if let Some(quantity) = bids.get_mut(&100) {
+                        *quantity -= 5;
+                        if *quantity == 0 {
+                            bids.remove(&100);
+                        }
+                    }
+                    drop(bids);
+                    book.cache.invalidate();
+                })
+            };
+
+            let during = book.top_of_book();
+            assert!(matches!(
+                Book::quote(&during),
+                (Some(100), 5 | 8 | 3, Some(101), 4) | (None, 0, Some(101), 4)
+            ));
+
+            add.join().unwrap();
+            cancel.join().unwrap();
+
+            let expected = (Some(100), 3, Some(101), 4);
+            if let Some(cached) = book.cache.get_cached_top_of_book() {
+                assert_eq!(Book::quote(&cached), expected, "stale top of book served");
+            }
+            assert_eq!(Book::quote(&book.top_of_book()), expected);
+        });
+    }
+
+    /// A snapshot taken while a sell walks two bid levels may see the walk
+    /// half done, but the seqlock never hands out a torn value and nothing
+    /// from the partial walk is served after the match has invalidated.
+    #[test]
+    fn snapshot_during_match() {
+        let mut builder = loom::model::Builder::new();
+        builder.preemption_bound = Some(3);
+        builder.check(|| {
+            let book = Book::new(&[(99, 5), (100, 5)], &[(101, 4)]);
+            // Publish the pre-match value so the snapshot can race the
+            // seqlock as well as the skip-map reads.
+            let initial = book.top_of_book();
+
+            let matcher = {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    let _mutation = book.cache.begin_mutation();
+                    book.cache.invalidate();
+                    book.bids.lock().unwrap().remove(&100);
+                    *book.bids.lock().unwrap().get_mut(&99).unwrap() -= 2;
+                    book.cache.invalidate();
+                })
+            };
+
+            let snapshot = book.top_of_book();
+            let consistent = [
+                Book::quote(&initial),
+                (Some(99), 5, Some(101), 4),
+                (Some(99), 3, Some(101), 4),
+            ];
+            assert!(
+                consistent.contains(&Book::quote(&snapshot)),
+                "torn snapshot {snapshot:?}"
+            );
+
+            matcher.join().unwrap();
+
+            let expected = (Some(99), 3, Some(101), 4);
+            assert_eq!(Book::quote(&book.top_of_book()), expected);
+        });
+    }
+
+    /// A match that only invalidates after its last write: a snapshot
+    /// taken between its writes sees no sequence change, so only the
+    /// in-flight mark keeps the half-applied walk from being published.
+    #[test]
+    fn half_applied_match_is_never_published() {
+        let mut builder = loom::model::Builder::new();
+        builder.preemption_bound = Some(3);
+        builder.check(|| {
+            let book = Book::new(&[(99, 5), (100, 5)], &[(101, 4)]);
+            let initial = Book::quote(&book.compute_top_of_book());
+
+            let matcher = {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    let _mutation = book.cache.begin_mutation();
+                    book.bids.lock().unwrap().remove(&100);
+                    *book.bids.lock().unwrap().get_mut(&99).unwrap() -= 2;
+                    book.cache.invalidate();
+                })
+            };
+
+            book.top_of_book();
+            let expected = (Some(99), 3, Some(101), 4);
+            if let Some(cached) = book.cache.get_cached_top_of_book() {
+                let cached = Book::quote(&cached);
+                assert!(
+                    cached == initial || cached == expected,
+                    "half-applied match published: {cached:?}"
+                );
+            }
+
+            matcher.join().unwrap();
+            assert_eq!(Book::quote(&book.top_of_book()), expected);
+        });
+    }
+}
@@ -190,6 +190,91 @@ mod tests {
         assert_eq!(simulation.total_cost(), 2050);
     }
 
+    #[test]
+    fn test_liquidation_curve_matches_market_impact() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Sell, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 105, 15, Side::Sell, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 110, 20, Side::Sell, TimeInForce::Gtc, None);
+
+        let curve = book.liquidation_curve(Side::Buy, 45, 9);
+        assert_eq!(curve.len(), 9);
+
+        for point in &curve {
+            let impact = book.market_impact(point.quantity, Side::Buy);
+            assert_eq!(point.avg_price, impact.avg_price);
+        }
+
+        // 5, 10, ..., 45 units
+        assert_eq!(curve[0].quantity, 5);
+        assert_eq!(curve[8].quantity, 45);
+        assert_eq!(curve[0].slippage_bps, 0.0);
+        // 20 units: (100*10 + 105*10) / 20 = 102.5 -> 250 bps from 100
+        assert_eq!(curve[3].avg_price, 102.5);
+        assert_eq!(curve[3].slippage_bps, 250.0);
+    }
+
+    #[test]
+    fn test_liquidation_curve_sell_side_slippage_is_positive() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 90, 10, Side::Buy, TimeInForce::Gtc, None);
+
+        let curve = book.liquidation_curve(Side::Sell, 20, 2);
+        assert_eq!(curve.len(), 2);
+        assert_eq!(curve[0].avg_price, 100.0);
+        assert_eq!(curve[1].avg_price, 95.0);
+        assert_eq!(curve[1].slippage_bps, 500.0);
+    }
+
+    #[test]
+    fn test_liquidation_curve_truncates_at_available_depth() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Sell, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 105, 15, Side::Sell, TimeInForce::Gtc, None);
+
+        // 10, 20, 30, 40 requested; only 25 units rest.
+        let curve = book.liquidation_curve(Side::Buy, 40, 4);
+        let quantities: Vec<u64> = curve.iter().map(|p| p.quantity).collect();
+        assert_eq!(quantities, vec![10, 20]);
+    }
+
+    #[test]
+    fn test_liquidation_curve_degenerate_inputs() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+
+        assert!(book.liquidation_curve(Side::Buy, 10, 5).is_empty());
+
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Sell, TimeInForce::Gtc, None);
+        assert!(book.liquidation_curve(Side::Buy, 0, 5).is_empty());
+        assert!(book.liquidation_curve(Side::Buy, 10, 0).is_empty());
+
+        // More steps than units: zero-sized and repeated points are skipped.
+        let curve = book.liquidation_curve(Side::Buy, 2, 4);
+        let quantities: Vec<u64> = curve.iter().map(|p| p.quantity).collect();
+        assert_eq!(quantities, vec![1, 2]);
+
+        // A huge step count neither allocates nor iterates per step.
+        let curve = book.liquidation_curve(Side::Buy, 3, usize::MAX);
+        let quantities: Vec<u64> = curve.iter().map(|p| p.quantity).collect();
+        assert_eq!(quantities, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_liquidation_curve_huge_request_against_a_shallow_book() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let _ = book.add_limit_order(Id::new(), 100, 10, Side::Sell, TimeInForce::Gtc, None);
+
+        // Sizing the result from the arguments would ask for gigabytes;
+        // the curve holds only the ten sizes the book can absorb.
+        let curve = book.liquidation_curve(Side::Buy, 1_000_000_000, 1_000_000_000);
+        let quantities: Vec<u64> = curve.iter().map(|p| p.quantity).collect();
+        assert_eq!(quantities, (1..=10).collect::<Vec<u64>>());
+    }
+
     #[test]
     fn test_liquidity_in_range_basic() {
         let book: OrderBook<()> = OrderBook::new("TEST");
@@ -1,8 +1,13 @@
+mod audit;
 mod book;
 mod depth_analysis;
 mod enriched_snapshot_tests;
 mod error;
+mod fee_ledger_tests;
 mod iterator_tests;
+mod level_races;
+#[cfg(loom)]
+mod loom_models;
 mod market_impact_tests;
 mod market_metrics;
 mod matching;
@@ -10,6 +15,9 @@ mod modifications;
 mod operations;
 mod order;
 mod order_placement_tests;
+mod perp_tests;
+mod price_scale_tests;
+mod quote_quality_tests;
 #[cfg(feature = "special_orders")]
 mod repricing;
 mod serialize_tests;
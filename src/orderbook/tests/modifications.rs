@@ -31,10 +31,11 @@ mod test_order_modifications {
         let result = book.update_order(update);
         assert!(result.is_err());
         match result {
-            Err(OrderBookError::InvalidOperation { message }) => {
-                assert!(message.contains("Cannot update price to the same value"));
+            Err(OrderBookError::PriceUnchanged { order_id, price: p }) => {
+                assert_eq!(order_id, id);
+                assert_eq!(p, price);
             }
-            _ => panic!("Expected InvalidOperation error"),
+            _ => panic!("Expected PriceUnchanged error"),
         }
     }
 
@@ -701,10 +702,7 @@ mod tests {
         };
 
         let result = book.add_order(expired_order);
-        assert!(matches!(
-            result,
-            Err(OrderBookError::InvalidOperation { .. })
-        ));
+        assert!(matches!(result, Err(OrderBookError::OrderExpired { .. })));
     }
 
     #[test]
@@ -930,7 +928,7 @@ mod test_add_order_with_result {
     /// but the real (non-self) fills must still reach the trade listener.
     #[test]
     fn test_add_order_stp_cancel_taker_partial_fill_still_reaches_listener() {
-        let (mut book, captured) = book_with_capturing_listener();
+        let (book, captured) = book_with_capturing_listener();
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(7);
@@ -966,7 +964,7 @@ mod test_add_order_with_result {
     /// typed error is returned (no trade result), the listener still fires.
     #[test]
     fn test_add_order_with_result_stp_cancel_taker_partial_fill_errors_and_emits() {
-        let (mut book, captured) = book_with_capturing_listener();
+        let (book, captured) = book_with_capturing_listener();
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(7);
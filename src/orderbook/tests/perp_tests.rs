@@ -0,0 +1,172 @@
+//! Tests for perpetual futures funding and mark price tracking
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::error::OrderBookError;
+    use crate::orderbook::perp::{FundingConfig, MAX_CATCH_UP_SETTLEMENTS, PerpTracker};
+    use crate::{BookConfig, ExternalPriceSource};
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn book(bid: u128, ask: u128) -> OrderBook<()> {
+        let book: OrderBook<()> = OrderBook::new("BTC-PERP");
+        let _ = book.add_limit_order(Id::new(), bid, 10, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), ask, 10, Side::Sell, TimeInForce::Gtc, None);
+        book
+    }
+
+    #[test]
+    fn test_funding_rate_clamps() {
+        let config = FundingConfig::default();
+        // Small premium: interest adjustment pulls it to the interest rate.
+        assert!((config.funding_rate(0.0002) - 0.0001).abs() < 1e-12);
+        // Large premium: interest adjustment clamped, then capped.
+        assert!((config.funding_rate(0.003) - 0.0025).abs() < 1e-12);
+        assert!((config.funding_rate(0.05) - 0.0075).abs() < 1e-12);
+        assert!((config.funding_rate(-0.05) + 0.0075).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_premium_averages_and_settles_at_boundary() {
+        let config = FundingConfig::new()
+            .with_interval_ms(1_000)
+            .with_interest_rate(0.0)
+            .with_interest_clamp(0.0);
+        let tracker = PerpTracker::new(config);
+
+        // Mid 10_100 over index 10_000: 1% premium for 500ms.
+        let rich = book(10_090, 10_110);
+        assert!(tracker.update_at(&rich, 10_000.0, 0).unwrap().is_empty());
+        let snapshot = tracker.snapshot().unwrap();
+        assert!((snapshot.premium.unwrap() - 0.01).abs() < 1e-12);
+        assert_eq!(snapshot.next_funding_ms, 1_000);
+
+        // Book at the index for the remaining 500ms.
+        let flat = book(9_990, 10_010);
+        assert!(tracker.update_at(&flat, 10_000.0, 500).unwrap().is_empty());
+        let settlements = tracker.update_at(&flat, 10_000.0, 1_200).unwrap();
+
+        assert_eq!(settlements.len(), 1);
+        assert_eq!(settlements[0].timestamp_ms, 1_000);
+        assert!((settlements[0].average_premium - 0.005).abs() < 1e-12);
+        assert!((settlements[0].funding_rate - 0.005).abs() < 1e-12);
+        let expected_payment = 2.0 * settlements[0].mark_price * 0.005;
+        assert!((settlements[0].payment(2) - expected_payment).abs() < 1e-9);
+
+        let snapshot = tracker.snapshot().unwrap();
+        assert_eq!(
+            snapshot.last_funding_rate,
+            Some(settlements[0].funding_rate)
+        );
+        assert_eq!(snapshot.next_funding_ms, 2_000);
+        // The new interval has only seen the flat book.
+        assert!(snapshot.predicted_funding_rate.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_every_crossed_boundary_settles() {
+        let tracker = PerpTracker::new(FundingConfig::new().with_interval_ms(100));
+        let perp = book(9_990, 10_010);
+        tracker.update_at(&perp, 10_000.0, 50).unwrap();
+        let settlements = tracker.update_at(&perp, 10_000.0, 350).unwrap();
+        let times: Vec<u64> = settlements.iter().map(|s| s.timestamp_ms).collect();
+        assert_eq!(times, vec![100, 200, 300]);
+        let snapshot = tracker.snapshot().unwrap();
+        assert!((snapshot.cumulative_funding_rate - 3.0 * 0.0001).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_long_gap_settles_in_closed_form() {
+        let tracker = PerpTracker::new(FundingConfig::new().with_interval_ms(1));
+        let perp = book(9_990, 10_010);
+        tracker.update_at(&perp, 10_000.0, 0).unwrap();
+
+        // A wall-clock epoch after a start at zero: ~1.7e12 intervals.
+        let now_ms = 1_700_000_000_000;
+        let settlements = tracker.update_at(&perp, 10_000.0, now_ms).unwrap();
+        assert_eq!(settlements.len(), MAX_CATCH_UP_SETTLEMENTS);
+        let times: Vec<u64> = settlements.iter().map(|s| s.timestamp_ms).take(3).collect();
+        assert_eq!(times, vec![1, 2, 3]);
+
+        let snapshot = tracker.snapshot().unwrap();
+        let expected = now_ms as f64 * 0.0001;
+        assert!((snapshot.cumulative_funding_rate - expected).abs() / expected < 1e-9);
+        assert_eq!(
+            snapshot.last_funding_rate,
+            Some(settlements[0].funding_rate)
+        );
+        assert_eq!(snapshot.next_funding_ms, now_ms + 1);
+    }
+
+    #[test]
+    fn test_zero_interval_from_serde_is_treated_as_one_millisecond() {
+        let mut config = FundingConfig::new();
+        config.interval_ms = 0;
+        let tracker = PerpTracker::new(config);
+        let perp = book(9_990, 10_010);
+        tracker.update_at(&perp, 10_000.0, 0).unwrap();
+        let settlements = tracker.update_at(&perp, 10_000.0, 3).unwrap();
+        assert_eq!(settlements.len(), 3);
+
+        let snapshot = tracker.snapshot().unwrap();
+        assert!(snapshot.mark_price.is_finite());
+        assert_eq!(snapshot.next_funding_ms, 4);
+    }
+
+    #[test]
+    fn test_mark_price_is_median_of_last_index_and_fair_basis() {
+        let tracker = PerpTracker::new(FundingConfig::new().with_interval_ms(1_000));
+        let perp = book(10_190, 10_210);
+        // Trade far above the index.
+        let _ = perp.match_market_order(Id::new(), 1, Side::Buy);
+        assert_eq!(perp.last_trade_price(), Some(10_210));
+
+        tracker.update_at(&perp, 10_000.0, 0).unwrap();
+        let snapshot = tracker.snapshot().unwrap();
+        // Fair basis sits between the index and the last trade.
+        let fair_basis = 10_000.0 * (1.0 + snapshot.predicted_funding_rate);
+        assert!(fair_basis > 10_000.0 && fair_basis < 10_210.0);
+        assert!((snapshot.mark_price - fair_basis).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rejects_invalid_index_and_resets() {
+        let tracker = PerpTracker::default();
+        let perp = book(99, 101);
+        assert!(matches!(
+            tracker.update(&perp, 0.0),
+            Err(OrderBookError::InvalidIndexPrice { .. })
+        ));
+        assert!(tracker.snapshot().is_none());
+
+        tracker.update(&perp, 100.0).unwrap();
+        assert!(tracker.snapshot().is_some());
+        tracker.reset();
+        assert!(tracker.snapshot().is_none());
+    }
+
+    #[test]
+    fn test_update_from_reference_uses_the_fresh_index() {
+        let tracker = PerpTracker::default();
+        let perp = book(99, 101);
+        perp.reload_config(BookConfig::new().with_reference_price_max_age_ms(1_000))
+            .unwrap();
+        assert!(matches!(
+            tracker.update_from_reference(&perp),
+            Err(OrderBookError::ReferencePriceUnavailable {
+                source: ExternalPriceSource::Index
+            })
+        ));
+
+        let now = perp.clock().now_millis().as_u64();
+        perp.set_reference_price(ExternalPriceSource::Index, 100, now - 5_000);
+        assert!(tracker.update_from_reference(&perp).is_err());
+        assert!(tracker.snapshot().is_none());
+
+        perp.set_reference_price(ExternalPriceSource::Index, 100, now);
+        tracker.update_from_reference(&perp).unwrap();
+        let snapshot = tracker.snapshot().unwrap();
+        assert!((snapshot.index_price - 100.0).abs() < 1e-12);
+        assert!(snapshot.premium.unwrap().abs() < 1e-12);
+    }
+}
@@ -0,0 +1,114 @@
+//! Tests for per-symbol price scaling
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::price_scale::{MAX_SCALE_DECIMALS, PriceScale};
+    use crate::orderbook::snapshot::OrderBookSnapshotPackage;
+    use crate::orderbook::trade::{TradeListener, TradeResult};
+    use pricelevel::{Id, MatchResult, Quantity, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_price_scale_conversions() {
+        let scale = PriceScale::new(2, 3);
+        assert_eq!(scale.price_factor(), 100);
+        assert_eq!(scale.quantity_factor(), 1_000);
+        assert_eq!(scale.price_to_f64(10_050), 100.5);
+        assert_eq!(scale.quantity_to_f64(1_500), 1.5);
+        assert_eq!(scale.notional_to_f64(10_050 * 1_500), 150.75);
+        assert_eq!(scale.price_from_f64(100.5), Some(10_050));
+        assert_eq!(scale.quantity_from_f64(1.5), Some(1_500));
+        assert_eq!(scale.price_from_f64(-1.0), None);
+        assert_eq!(scale.price_from_f64(f64::NAN), None);
+        assert_eq!(scale.format_price(10_050), "100.50");
+        assert_eq!(scale.format_price(5), "0.05");
+        assert_eq!(scale.format_quantity(1_500), "1.500");
+    }
+
+    #[test]
+    fn test_price_scale_default_is_identity_and_new_clamps() {
+        let identity = PriceScale::default();
+        assert!(identity.is_identity());
+        assert_eq!(identity.price_to_f64(12_345), 12_345.0);
+        assert_eq!(identity.format_price(12_345), "12345");
+
+        let clamped = PriceScale::new(u8::MAX, 40);
+        assert_eq!(clamped.price_decimals, MAX_SCALE_DECIMALS);
+        assert_eq!(clamped.quantity_decimals, MAX_SCALE_DECIMALS);
+    }
+
+    #[test]
+    fn test_book_scaled_metrics() {
+        let mut book = OrderBook::<()>::new("BTC/USD");
+        book.set_price_scale(PriceScale::new(2, 0));
+        let _ = book.add_limit_order(Id::new(), 10_000, 10, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), 10_100, 30, Side::Sell, TimeInForce::Gtc, None);
+
+        assert_eq!(book.scaled_best_bid(), Some(100.0));
+        assert_eq!(book.scaled_best_ask(), Some(101.0));
+        assert_eq!(book.scaled_mid_price(), Some(100.5));
+        assert_eq!(book.scaled_vwap(10, Side::Buy), Some(101.0));
+
+        let micro = book.micro_price().unwrap();
+        assert_eq!(book.scaled_micro_price(), Some(micro / 100.0));
+
+        let snapshot = book.enriched_snapshot(5);
+        assert_eq!(snapshot.price_scale, book.price_scale());
+        assert_eq!(snapshot.scaled_mid_price(), Some(100.5));
+        assert_eq!(snapshot.scaled_vwap_ask(), Some(101.0));
+        assert_eq!(snapshot.scaled_bid_depth_total(), 10.0);
+    }
+
+    #[test]
+    fn test_price_scale_survives_snapshot_package_roundtrip() {
+        let mut book = OrderBook::<()>::new("ETH/USD");
+        book.set_price_scale(PriceScale::new(4, 6));
+        let _ = book.add_limit_order(Id::new(), 25_000_000, 1, Side::Buy, TimeInForce::Gtc, None);
+
+        let json = book.snapshot_to_json(10).unwrap();
+        let package = OrderBookSnapshotPackage::from_json(&json).unwrap();
+        assert_eq!(package.price_scale, PriceScale::new(4, 6));
+
+        let mut restored = OrderBook::<()>::new("ETH/USD");
+        restored.restore_from_snapshot_package(package).unwrap();
+        assert_eq!(restored.price_scale(), PriceScale::new(4, 6));
+        assert_eq!(restored.scaled_best_bid(), Some(2_500.0));
+    }
+
+    #[test]
+    fn test_trade_result_carries_price_scale() {
+        let trades = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&trades);
+        let listener: TradeListener = Arc::new(move |trade: &TradeResult| {
+            sink.lock().unwrap().push(trade.clone());
+        });
+        let mut book = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
+        book.set_price_scale(PriceScale::new(2, 1));
+
+        let _ = book.add_limit_order(Id::new(), 10_000, 20, Side::Sell, TimeInForce::Gtc, None);
+        let _ = book.submit_market_order(Id::new(), 20, Side::Buy);
+
+        let trades = trades.lock().unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].price_scale, PriceScale::new(2, 1));
+        assert_eq!(trades[0].scaled_quote_notional(), 200.0);
+
+        let json = serde_json::to_string(&trades[0]).unwrap();
+        assert!(json.contains("\"price_scale\""));
+        let decoded: TradeResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.price_scale, PriceScale::new(2, 1));
+    }
+
+    #[test]
+    fn test_trade_result_without_price_scale_deserializes_as_identity() {
+        let trade = TradeResult::new(
+            "BTC/USD".to_string(),
+            MatchResult::new(Id::new(), Quantity::new(0)),
+        );
+        let mut value = serde_json::to_value(&trade).unwrap();
+        value.as_object_mut().unwrap().remove("price_scale");
+        let decoded: TradeResult = serde_json::from_value(value).unwrap();
+        assert!(decoded.price_scale.is_identity());
+    }
+}
@@ -0,0 +1,178 @@
+//! Tests for per-user quote quality monitoring
+
+#[cfg(test)]
+mod tests {
+    use crate::OrderBook;
+    use crate::orderbook::quote_quality::QuoteQualityMonitor;
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    fn quote(book: &OrderBook<()>, price: u128, quantity: u64, side: Side, user_id: Hash32) -> Id {
+        let id = Id::new();
+        let added = book.add_limit_order_with_user(
+            id,
+            price,
+            quantity,
+            side,
+            TimeInForce::Gtc,
+            user_id,
+            None,
+        );
+        assert!(added.is_ok());
+        id
+    }
+
+    #[test]
+    fn test_time_at_bbo_is_credited_sample_and_hold() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let monitor = QuoteQualityMonitor::new();
+        let mm = user(1);
+        let other = user(2);
+
+        // mm quotes 99 / 101 and is alone at the BBO.
+        quote(&book, 99, 10, Side::Buy, mm);
+        quote(&book, 101, 10, Side::Sell, mm);
+        monitor.observe_at(&book, 1_000);
+
+        // After 500ms another user improves the bid.
+        quote(&book, 100, 5, Side::Buy, other);
+        monitor.observe_at(&book, 1_500);
+
+        // Another 250ms passes with mm only at the best ask.
+        monitor.observe_at(&book, 1_750);
+
+        let stats = monitor.user_stats(mm).expect("mm observed");
+        assert_eq!(stats.time_quoting_ms, 750);
+        assert_eq!(stats.time_at_best_bid_ms, 500);
+        assert_eq!(stats.time_at_best_ask_ms, 750);
+        assert_eq!(stats.time_at_bbo_ms, 500);
+        assert_eq!(stats.time_two_sided_ms, 750);
+        assert_eq!(stats.avg_quoted_spread(), Some(2.0));
+        assert!((stats.bbo_presence_ratio() - 500.0 / 750.0).abs() < 1e-12);
+
+        let other_stats = monitor.user_stats(other).expect("other observed");
+        assert_eq!(other_stats.time_quoting_ms, 250);
+        assert_eq!(other_stats.time_at_best_bid_ms, 250);
+        assert_eq!(other_stats.time_at_bbo_ms, 0);
+        assert_eq!(other_stats.avg_quoted_spread(), None);
+    }
+
+    #[test]
+    fn test_fill_to_quote_ratio_attributes_maker_fills() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let monitor = QuoteQualityMonitor::new();
+        let mm = user(1);
+        let taker = user(2);
+
+        quote(&book, 99, 20, Side::Buy, mm);
+        quote(&book, 101, 20, Side::Sell, mm);
+        monitor.observe_at(&book, 0);
+
+        let (_, trade) = book
+            .add_limit_order_with_user_and_result(
+                Id::new(),
+                101,
+                10,
+                Side::Buy,
+                TimeInForce::Ioc,
+                taker,
+                None,
+            )
+            .expect("taker accepted");
+        monitor.record_trade(&trade.expect("taker crossed"));
+
+        let stats = monitor.user_stats(mm).expect("mm observed");
+        assert_eq!(stats.quotes_observed, 2);
+        assert_eq!(stats.quoted_quantity, 40);
+        assert_eq!(stats.fill_count, 1);
+        assert_eq!(stats.filled_quantity, 10);
+        assert_eq!(stats.fill_to_quote_ratio(), 0.25);
+        // The IOC taker never rested, so it was never observed.
+        assert!(monitor.user_stats(taker).is_none());
+    }
+
+    #[test]
+    fn test_departed_orders_are_forgotten_after_one_interval() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let monitor = QuoteQualityMonitor::new();
+        let mm = user(1);
+        let taker = user(2);
+        let take = |price: u128| {
+            let (_, trade) = book
+                .add_limit_order_with_user_and_result(
+                    Id::new(),
+                    price,
+                    10,
+                    Side::Buy,
+                    TimeInForce::Ioc,
+                    taker,
+                    None,
+                )
+                .expect("taker accepted");
+            trade.expect("taker crossed")
+        };
+
+        quote(&book, 101, 10, Side::Sell, mm);
+        quote(&book, 102, 10, Side::Sell, mm);
+        monitor.observe_at(&book, 0);
+
+        // Filled out of the book, reported after the next observation.
+        let late = take(101);
+        monitor.observe_at(&book, 10);
+        monitor.record_trade(&late);
+        assert_eq!(monitor.user_stats(mm).expect("mm").fill_count, 1);
+
+        // Reported two observations after it left: no longer remembered.
+        let stale = take(102);
+        monitor.observe_at(&book, 20);
+        monitor.observe_at(&book, 30);
+        monitor.record_trade(&stale);
+        let stats = monitor.user_stats(mm).expect("mm");
+        assert_eq!(stats.fill_count, 1);
+        assert_eq!(stats.quotes_observed, 2);
+    }
+
+    #[test]
+    fn test_export_is_sorted_and_reset_clears() {
+        let book: OrderBook<()> = OrderBook::new("TEST");
+        let monitor = QuoteQualityMonitor::new();
+
+        quote(&book, 99, 1, Side::Buy, user(3));
+        quote(&book, 101, 1, Side::Sell, user(1));
+        // Anonymous orders are not tracked.
+        quote(&book, 98, 1, Side::Buy, Hash32::zero());
+        monitor.observe_at(&book, 0);
+        monitor.observe_at(&book, 10);
+
+        let exported = monitor.export();
+        let users: Vec<Hash32> = exported.iter().map(|(user_id, _)| *user_id).collect();
+        assert_eq!(users, vec![user(1), user(3)]);
+        assert!(serde_json::to_string(&exported).is_ok());
+
+        monitor.reset();
+        assert!(monitor.export().is_empty());
+    }
+
+    #[test]
+    fn test_observe_uses_book_clock() {
+        use crate::orderbook::clock::StubClock;
+        use std::sync::Arc;
+
+        let book: OrderBook<()> =
+            OrderBook::with_clock("TEST", Arc::new(StubClock::with_step(0, 100)));
+        let monitor = QuoteQualityMonitor::new();
+        let mm = user(1);
+        quote(&book, 99, 1, Side::Buy, mm);
+        quote(&book, 101, 1, Side::Sell, mm);
+
+        let start = book.clock().now_millis().as_u64();
+        monitor.observe_at(&book, start);
+        monitor.observe(&book);
+
+        let stats = monitor.user_stats(mm).expect("mm observed");
+        assert_eq!(stats.time_at_bbo_ms, 100);
+    }
+}
@@ -707,8 +707,8 @@ mod test_snapshot_engine_seq {
     }
 
     /// `version: 1` payloads — the legacy format that lacked `engine_seq`
-    /// — must be rejected by `validate()` with the existing
-    /// `Unsupported snapshot version` error. Unlike `version: 2` (which
+    /// — must be rejected by `validate()` with
+    /// `UnsupportedSnapshotVersion`. Unlike `version: 2` (which
     /// stays readable after the v3 bump, #206), v1 has no migration path.
     #[test]
     fn test_snapshot_package_v1_payload_rejected_by_validate() {
@@ -743,21 +743,15 @@ mod test_snapshot_engine_seq {
             .expect_err("v1 payload must be rejected after the v2 bump");
 
         match err {
-            OrderBookError::InvalidOperation { message } => {
-                assert!(
-                    message.contains("Unsupported snapshot version"),
-                    "error message must mention the unsupported version, got: {message}"
-                );
-                assert!(
-                    message.contains('1'),
-                    "error message must mention version 1, got: {message}"
-                );
-                assert!(
-                    message.contains("2..=3"),
-                    "error message must state the supported range, got: {message}"
-                );
+            OrderBookError::UnsupportedSnapshotVersion {
+                version,
+                min_supported,
+                max_supported,
+            } => {
+                assert_eq!(version, 1);
+                assert_eq!((min_supported, max_supported), (2, 3));
             }
-            other => panic!("expected InvalidOperation, got {other:?}"),
+            other => panic!("expected UnsupportedSnapshotVersion, got {other:?}"),
         }
     }
 
@@ -983,13 +977,10 @@ mod test_snapshot_format_v3 {
             .validate()
             .expect_err("future versions must be rejected");
         match err {
-            OrderBookError::InvalidOperation { message } => {
-                assert!(
-                    message.contains("Unsupported snapshot version"),
-                    "error must mention the unsupported version, got: {message}"
-                );
+            OrderBookError::UnsupportedSnapshotVersion { version, .. } => {
+                assert_eq!(version, ORDERBOOK_SNAPSHOT_FORMAT_VERSION + 1);
             }
-            other => panic!("expected InvalidOperation, got {other:?}"),
+            other => panic!("expected UnsupportedSnapshotVersion, got {other:?}"),
         }
     }
 
@@ -101,7 +101,7 @@ mod tests {
     /// the older non-self liquidity before the self-trade is prevented.
     #[test]
     fn test_cancel_taker_scan_follows_time_priority_at_one_level() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(9);
@@ -150,7 +150,7 @@ mod tests {
     /// order, and fills the older non-self liquidity.
     #[test]
     fn test_cancel_both_cancels_earliest_self_maker_by_time() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
 
         let taker_user = user(9);
@@ -196,7 +196,7 @@ mod tests {
     /// insertion-order scanning gets right (a timestamp scan would cancel with 0 fills).
     #[test]
     fn test_cancel_taker_scan_follows_insertion_not_timestamp_order() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(9);
@@ -382,7 +382,7 @@ mod tests {
     /// self quantity and let the FOK proceed, cancelling the maker and filling 0.
     #[test]
     fn test_fok_under_stp_cancel_maker_kills_without_touching_makers() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let u = user(7);
@@ -488,7 +488,7 @@ mod tests {
     /// applies to the lifecycle-managing `add_order` path.
     #[test]
     fn test_notional_market_partial_self_cross_returns_partial() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(7);
@@ -539,7 +539,7 @@ mod tests {
 
     #[test]
     fn test_cancel_taker_prevents_self_trade() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -563,7 +563,7 @@ mod tests {
 
     #[test]
     fn test_cancel_taker_allows_different_users() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let maker_user = user(1);
@@ -581,7 +581,7 @@ mod tests {
 
     #[test]
     fn test_cancel_taker_partial_fill_before_self_trade() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(1);
@@ -606,7 +606,7 @@ mod tests {
 
     #[test]
     fn test_cancel_taker_zero_taker_user_bypasses_stp_during_matching() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let maker_user = user(1);
@@ -626,7 +626,7 @@ mod tests {
 
     #[test]
     fn test_cancel_maker_removes_same_user_orders() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let same_user = user(1);
@@ -651,7 +651,7 @@ mod tests {
 
     #[test]
     fn test_cancel_maker_all_same_user_orders_cancelled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let same_user = user(1);
@@ -678,7 +678,7 @@ mod tests {
 
     #[test]
     fn test_cancel_maker_across_price_levels() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let same_user = user(1);
@@ -706,7 +706,7 @@ mod tests {
 
     #[test]
     fn test_cancel_both_cancels_maker_and_taker() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
 
         let same_user = user(1);
@@ -729,7 +729,7 @@ mod tests {
 
     #[test]
     fn test_cancel_both_partial_fill_before_self() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
 
         let taker_user = user(1);
@@ -761,7 +761,7 @@ mod tests {
 
     #[test]
     fn test_stp_cancel_taker_via_add_order() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -793,7 +793,7 @@ mod tests {
 
     #[test]
     fn test_stp_cancel_maker_via_add_order() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let same_user = user(1);
@@ -829,7 +829,7 @@ mod tests {
 
     #[test]
     fn test_stp_cancel_taker_sell_side() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -853,7 +853,7 @@ mod tests {
 
     #[test]
     fn test_stp_cancel_maker_sell_side() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let same_user = user(1);
@@ -881,7 +881,7 @@ mod tests {
 
     #[test]
     fn test_stp_mode_setter_getter() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         assert_eq!(book.stp_mode(), STPMode::None);
 
         book.set_stp_mode(STPMode::CancelTaker);
@@ -910,7 +910,7 @@ mod tests {
 
     #[test]
     fn test_stp_empty_book_returns_insufficient_liquidity() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_id = Id::new();
@@ -924,7 +924,7 @@ mod tests {
 
     #[test]
     fn test_stp_limit_order_no_cross_adds_to_book() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -949,7 +949,7 @@ mod tests {
 
     #[test]
     fn test_stp_submit_market_order_with_user() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -969,7 +969,7 @@ mod tests {
 
     #[test]
     fn test_stp_match_limit_order_with_user() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -989,7 +989,7 @@ mod tests {
 
     #[test]
     fn test_stp_backward_compat_no_user_id() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let same_user = user(1);
@@ -1005,7 +1005,7 @@ mod tests {
 
     #[test]
     fn test_stp_multiple_levels_cancel_taker() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let taker_user = user(1);
@@ -1032,7 +1032,7 @@ mod tests {
 
     #[test]
     fn test_missing_user_id_limit_order_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         // add_limit_order defaults to Hash32::zero() → should be rejected
@@ -1055,7 +1055,7 @@ mod tests {
 
     #[test]
     fn test_limit_order_with_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         // Non-zero user_id → accepted
@@ -1073,7 +1073,7 @@ mod tests {
 
     #[test]
     fn test_limit_order_with_zero_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         // Explicitly zero user_id via _with_user → should be rejected
@@ -1094,7 +1094,7 @@ mod tests {
 
     #[test]
     fn test_missing_user_id_iceberg_order_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
 
         let result =
@@ -1107,7 +1107,7 @@ mod tests {
 
     #[test]
     fn test_iceberg_order_with_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let result = book.add_iceberg_order_with_user(
@@ -1134,7 +1134,7 @@ mod tests {
 
     #[test]
     fn test_missing_user_id_post_only_order_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let result =
@@ -1147,7 +1147,7 @@ mod tests {
 
     #[test]
     fn test_post_only_order_with_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
 
         let result = book.add_post_only_order_with_user(
@@ -1173,7 +1173,7 @@ mod tests {
 
     #[test]
     fn test_add_order_direct_zero_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         // Direct add_order with zero user_id → should be rejected
@@ -1196,7 +1196,7 @@ mod tests {
 
     #[test]
     fn test_add_order_direct_nonzero_user_stp_enabled() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
 
         // Direct add_order with non-zero user_id → should be accepted
@@ -1222,7 +1222,7 @@ mod tests {
             STPMode::CancelMaker,
             STPMode::CancelBoth,
         ] {
-            let mut book: OrderBook<()> = OrderBook::new("TEST");
+            let book: OrderBook<()> = OrderBook::new("TEST");
             book.set_stp_mode(mode);
 
             let result =
@@ -1236,7 +1236,7 @@ mod tests {
 
     #[test]
     fn test_missing_user_id_error_contains_order_id() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
 
         let oid = Id::new();
@@ -1279,7 +1279,7 @@ mod tests {
     /// unchanged and the opposite-side maker is untouched.
     #[test]
     fn test_modify_self_cross_under_cancel_taker_rejected_preserves_original_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
         let u = user(7);
 
@@ -1320,7 +1320,7 @@ mod tests {
     /// cancelled under CancelBoth too), preserving the original.
     #[test]
     fn test_modify_self_cross_under_cancel_both_rejected_preserves_original_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelBoth);
         let u = user(8);
 
@@ -1349,7 +1349,7 @@ mod tests {
     /// STP cancel the same-user maker. Confirms no spurious pre-rejection.
     #[test]
     fn test_modify_self_cross_under_cancel_maker_not_prerejected_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelMaker);
         let u = user(9);
 
@@ -1379,7 +1379,7 @@ mod tests {
     /// is a normal cross, not a self-cross, so it must NOT be pre-rejected.
     #[test]
     fn test_modify_cross_other_user_under_cancel_taker_allowed_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
         let u = user(1);
         let other = user(2);
@@ -1408,7 +1408,7 @@ mod tests {
     /// the existence of a same-user maker in range.
     #[test]
     fn test_modify_self_cross_taker_fills_before_self_maker_allowed_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::new("TEST");
+        let book: OrderBook<()> = OrderBook::new("TEST");
         book.set_stp_mode(STPMode::CancelTaker);
         let u = user(3);
         let other = user(4);
@@ -1444,7 +1444,7 @@ mod tests {
     /// sub-lot dust that could strand a same-user maker. Pins that invariant.
     #[test]
     fn test_modify_self_cross_under_cancel_taker_with_lot_size_rejected_issue_168() {
-        let mut book: OrderBook<()> = OrderBook::with_lot_size("TEST", 5);
+        let book: OrderBook<()> = OrderBook::with_lot_size("TEST", 5);
         book.set_stp_mode(STPMode::CancelTaker);
         let u = user(5);
         let other = user(6);
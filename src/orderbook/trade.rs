@@ -4,10 +4,16 @@
    Date: 2/10/25
 ******************************************************************************/
 use crate::orderbook::fees::FeeSchedule;
-use pricelevel::MatchResult;
+use crate::orderbook::price_scale::PriceScale;
+use pricelevel::{Hash32, Id, MatchResult, Side};
 use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+/// Type-erased `extra_fields` payload attached to a [`TradeResult`].
+pub type TradeExtraFields = Arc<dyn Any + Send + Sync>;
+
 /// Enhanced trade result that includes symbol information and fee details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResult {
@@ -35,6 +41,13 @@ pub struct TradeResult {
     /// that pre-date `engine_seq` so existing consumers keep parsing.
     #[serde(default)]
     pub engine_seq: u64,
+    /// `OrderBook::config_version` when the trade was emitted, naming the
+    /// fee schedule and validation parameters it executed under.
+    ///
+    /// Defaults to `0` when deserializing payloads from format versions
+    /// that pre-date `config_version`.
+    #[serde(default)]
+    pub config_version: u64,
     /// Total quote-asset notional consumed by this trade, computed as
     /// `Σ price × quantity` across every transaction. Populated for both
     /// base-quantity (`match_market_order`) and quote-notional
@@ -45,6 +58,170 @@ pub struct TradeResult {
     /// that pre-date `quote_notional` so existing consumers keep parsing.
     #[serde(default)]
     pub quote_notional: u128,
+    /// Decimal scale of the prices and quantities in `match_result`, copied
+    /// from the emitting book so consumers can render display values.
+    ///
+    /// Defaults to the identity scale when deserializing payloads from
+    /// format versions that pre-date `price_scale`.
+    #[serde(default)]
+    pub price_scale: PriceScale,
+    /// `extra_fields` of the orders on either side of these trades, keyed
+    /// by order id: every maker, plus the taker when it was submitted with
+    /// a payload through `add_order`. Stored type-erased because
+    /// `TradeResult` is not generic over the book's `T`; read them back
+    /// with [`Self::extra_fields_for`]. Empty when `T` is zero-sized.
+    ///
+    /// Process-local: not serialized, and empty after deserialization.
+    #[serde(skip)]
+    pub extra_fields: Vec<(Id, TradeExtraFields)>,
+    /// Per-fill detail for clearing and PnL: both counterparties' user
+    /// ids and liquidity flags, the fee charged to each side and the
+    /// maker's remaining quantity, one entry per trade in `match_result`
+    /// and in the same order.
+    ///
+    /// Populated on every `TradeResult` an `OrderBook` emits. Empty for
+    /// results built directly with [`Self::new`] / [`Self::with_fees`],
+    /// and when deserializing payloads from format versions that pre-date
+    /// `fills`.
+    #[serde(default)]
+    pub fills: Vec<TradeFill>,
+    /// Set on the compensating result `OrderBook::bust_trade` and
+    /// `OrderBook::correct_trade` emit, naming the fill it amends. Such a
+    /// result has an empty `match_result`, and its fee totals are the
+    /// change in fees: negated for a bust, the difference for a
+    /// correction.
+    ///
+    /// `None` for every result a match produces, and when deserializing
+    /// payloads from format versions that pre-date `amendment`. Boxed to
+    /// keep every ordinary result small.
+    #[serde(default)]
+    pub amendment: Option<Box<TradeAmendment>>,
+    /// The trades of `match_result` aggregated per price level, in sweep
+    /// order: one entry per price with the total quantity and the number
+    /// of resting orders it traded with.
+    ///
+    /// Populated when the emitting book has trade level aggregation
+    /// enabled (`OrderBook::set_trade_level_aggregation`); otherwise
+    /// empty, and [`Self::aggregate_levels`] computes the same view on
+    /// demand. Defaults to empty when deserializing payloads from format
+    /// versions that pre-date `level_fills`.
+    #[serde(default)]
+    pub level_fills: Vec<PriceLevelFill>,
+    /// `true` for executions of the midpoint (dark) segment
+    /// (`OrderBook::submit_midpoint_order`), which happen at the lit
+    /// midpoint and are not lit trades. Defaults to `false` when
+    /// deserializing payloads from format versions that pre-date `dark`.
+    #[serde(default)]
+    pub dark: bool,
+}
+
+/// The part of a sweep that executed at one price level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceLevelFill {
+    /// Execution price of the level.
+    pub price: u128,
+    /// Total quantity executed at this price.
+    pub quantity: u64,
+    /// `price × quantity`, saturating.
+    pub notional: u128,
+    /// Number of distinct resting orders the taker traded with here.
+    pub counterparty_count: u32,
+}
+
+/// What a compensating [`TradeResult`] does to a previously emitted fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeAmendment {
+    /// The fill, as last reported, is cancelled. `fills` is empty.
+    Bust(TradeFill),
+    /// The fill, as last reported, is replaced by the single entry in
+    /// `fills`, which keeps its execution id and match time.
+    Correction(TradeFill),
+}
+
+impl TradeAmendment {
+    /// The fill being busted or corrected, as it was last reported.
+    #[must_use]
+    pub fn superseded(&self) -> &TradeFill {
+        match self {
+            Self::Bust(fill) | Self::Correction(fill) => fill,
+        }
+    }
+}
+
+/// Whether a side of a fill added liquidity to the book or removed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LiquidityFlag {
+    /// The resting order that supplied the liquidity.
+    Maker,
+    /// The incoming order that took the liquidity.
+    Taker,
+}
+
+/// One counterparty of a [`TradeFill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FillParty {
+    /// The counterparty's order.
+    pub order_id: Id,
+    /// Owner of the order; `Hash32::zero()` when it was submitted without one.
+    pub user_id: Hash32,
+    /// Side of the book the order was on.
+    pub side: Side,
+    /// Whether this side made or took the liquidity.
+    pub liquidity: LiquidityFlag,
+    /// Fee charged to this side for the fill, in the same unit as the
+    /// notional (price × quantity). Negative values represent rebates.
+    pub fee: i128,
+}
+
+/// A single fill of a [`TradeResult`] with both counterparties attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeFill {
+    /// Identifier of the underlying `pricelevel` trade.
+    pub trade_id: Id,
+    /// Per-book execution id: strictly increasing across every fill the
+    /// book emits, starting at `1`, and carried across snapshot restore.
+    /// Key downstream dedup on `(symbol, execution_id)`.
+    ///
+    /// Defaults to `0` when deserializing payloads from format versions
+    /// that pre-date `execution_id`.
+    #[serde(default)]
+    pub execution_id: u64,
+    /// When the fill was matched, in nanoseconds, read from the book's
+    /// [`Clock`](crate::orderbook::clock::Clock) via `now_nanos`.
+    ///
+    /// Defaults to `0` when deserializing payloads from format versions
+    /// that pre-date `matched_at_ns`.
+    #[serde(default)]
+    pub matched_at_ns: u64,
+    /// Execution price.
+    pub price: u128,
+    /// Executed quantity.
+    pub quantity: u64,
+    /// The resting side.
+    pub maker: FillParty,
+    /// The incoming side.
+    pub taker: FillParty,
+    /// Quantity the maker still had resting (visible plus hidden) right
+    /// after this fill; `0` when the fill completed it.
+    pub maker_remaining_quantity: u64,
+}
+
+impl TradeFill {
+    /// `price × quantity`, saturating.
+    #[must_use]
+    #[inline]
+    pub fn notional(&self) -> u128 {
+        self.price.saturating_mul(u128::from(self.quantity))
+    }
+
+    /// The side of this fill that `order_id` was on, or `None` when it is
+    /// neither the maker nor the taker.
+    #[must_use]
+    pub fn party(&self, order_id: Id) -> Option<&FillParty> {
+        [&self.maker, &self.taker]
+            .into_iter()
+            .find(|party| party.order_id == order_id)
+    }
 }
 
 impl TradeResult {
@@ -62,7 +239,14 @@ impl TradeResult {
             total_maker_fees: 0,
             total_taker_fees: 0,
             engine_seq: 0,
+            config_version: 0,
             quote_notional,
+            price_scale: PriceScale::default(),
+            extra_fields: Vec::new(),
+            fills: Vec::new(),
+            amendment: None,
+            level_fills: Vec::new(),
+            dark: false,
         }
     }
 
@@ -110,10 +294,28 @@ impl TradeResult {
             total_maker_fees,
             total_taker_fees,
             engine_seq: 0,
+            config_version: 0,
             quote_notional,
+            price_scale: PriceScale::default(),
+            extra_fields: Vec::new(),
+            fills: Vec::new(),
+            amendment: None,
+            level_fills: Vec::new(),
+            dark: false,
         }
     }
 
+    /// The `extra_fields` of `order_id` as supplied at submission, or
+    /// `None` when the order carried none or `T` does not match the
+    /// emitting book's payload type.
+    #[must_use]
+    pub fn extra_fields_for<T: 'static>(&self, order_id: Id) -> Option<&T> {
+        self.extra_fields
+            .iter()
+            .find(|(id, _)| *id == order_id)
+            .and_then(|(_, extra_fields)| extra_fields.downcast_ref::<T>())
+    }
+
     /// Returns the sum of all fees (maker + taker) for this trade
     ///
     /// A positive value means net fees charged; a negative value means
@@ -125,6 +327,48 @@ impl TradeResult {
             .checked_add(self.total_taker_fees)
             .unwrap_or(i128::MAX)
     }
+
+    /// Aggregate the trades of [`Self::match_result`] per price level.
+    ///
+    /// Consecutive trades at one price form one [`PriceLevelFill`]; a
+    /// sweep visits each level once, so the result has one entry per
+    /// price, best price first.
+    #[must_use]
+    pub fn aggregate_levels(&self) -> Vec<PriceLevelFill> {
+        let mut levels: Vec<PriceLevelFill> = Vec::new();
+        let mut makers: HashSet<Id> = HashSet::new();
+        for trade in self.match_result.trades().as_vec() {
+            let price = trade.price().as_u128();
+            let quantity = trade.quantity().as_u64();
+            if levels.last().is_none_or(|level| level.price != price) {
+                makers.clear();
+                levels.push(PriceLevelFill {
+                    price,
+                    quantity: 0,
+                    notional: 0,
+                    counterparty_count: 0,
+                });
+            }
+            let Some(level) = levels.last_mut() else {
+                continue;
+            };
+            level.quantity = level.quantity.saturating_add(quantity);
+            level.notional = level
+                .notional
+                .saturating_add(price.saturating_mul(u128::from(quantity)));
+            // An iceberg that replenishes mid-sweep trades more than once.
+            if makers.insert(trade.maker_order_id()) {
+                level.counterparty_count = level.counterparty_count.saturating_add(1);
+            }
+        }
+        levels
+    }
+
+    /// [`Self::quote_notional`] in display units, per [`Self::price_scale`].
+    #[must_use]
+    pub fn scaled_quote_notional(&self) -> f64 {
+        self.price_scale.notional_to_f64(self.quote_notional)
+    }
 }
 
 /// Sum of `price × quantity` across every trade in `match_result`.
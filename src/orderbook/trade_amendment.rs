@@ -0,0 +1,366 @@
+//! Trade busts and corrections.
+//!
+//! An exchange occasionally has to cancel (bust) or restate (correct) an
+//! execution after the fact: an erroneous-trade policy, a fat-finger
+//! review, an operator fixing a bad print. [`OrderBook::bust_trade`] and
+//! [`OrderBook::correct_trade`] do that for fills the book has emitted.
+//! They look the fill up by its [`TradeFill::execution_id`] in the book's
+//! [`ExecutionHistory`], restate the book's trade statistics and last
+//! trade price, and publish a compensating [`TradeResult`] carrying a
+//! [`TradeAmendment`] through the trade listener.
+//!
+//! Amendments never touch resting orders: a busted fill does not
+//! reinstate the liquidity it consumed, and a correction does not change
+//! what rests. The history is opt-in (see
+//! [`OrderBook::set_execution_history`]) and bounded, so only fills
+//! emitted while it is installed, and not yet evicted, can be amended.
+
+use super::book::OrderBook;
+use super::error::OrderBookError;
+use super::trade::{TradeAmendment, TradeFill, TradeResult};
+use pricelevel::{MatchResult, Quantity};
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::sync::{Mutex, MutexGuard};
+
+/// Default number of fills an [`ExecutionHistory`] retains.
+pub const DEFAULT_EXECUTION_HISTORY_CAPACITY: usize = 10_000;
+
+/// A retained fill, as last reported.
+#[derive(Debug, Clone, Copy)]
+struct ExecutionRecord {
+    fill: TradeFill,
+    busted: bool,
+}
+
+/// Bounded history of the fills a book has emitted, oldest evicted first.
+///
+/// Recording takes one [`Mutex`] per emitted trade. Lookups scan the
+/// retained fills, which suits the operational calls that make them.
+///
+/// # Example
+///
+/// ```
+/// use orderbook_rs::orderbook::trade_amendment::ExecutionHistory;
+///
+/// let history = ExecutionHistory::with_capacity(1_000);
+/// assert_eq!(history.capacity(), 1_000);
+/// assert!(history.get(1).is_none());
+/// ```
+#[derive(Debug)]
+pub struct ExecutionHistory {
+    records: Mutex<VecDeque<ExecutionRecord>>,
+    capacity: usize,
+}
+
+impl Default for ExecutionHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionHistory {
+    /// Create a history retaining [`DEFAULT_EXECUTION_HISTORY_CAPACITY`]
+    /// fills.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_EXECUTION_HISTORY_CAPACITY)
+    }
+
+    /// Create a history retaining the `capacity` most recent fills. A
+    /// `capacity` of `0` is clamped to `1`.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Maximum number of fills retained.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of fills retained, busted ones included.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` when no fill is retained.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// The fill with `execution_id` as last reported, or `None` when it is
+    /// unknown, evicted or busted.
+    #[must_use]
+    pub fn get(&self, execution_id: u64) -> Option<TradeFill> {
+        self.lock()
+            .iter()
+            .rev()
+            .find(|record| record.fill.execution_id == execution_id && !record.busted)
+            .map(|record| record.fill)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<ExecutionRecord>> {
+        match self.records.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Retain `fills`, evicting the oldest beyond capacity.
+    pub(crate) fn record(&self, fills: &[TradeFill]) {
+        if fills.is_empty() {
+            return;
+        }
+        let mut records = self.lock();
+        for fill in fills {
+            if records.len() == self.capacity {
+                records.pop_front();
+            }
+            records.push_back(ExecutionRecord {
+                fill: *fill,
+                busted: false,
+            });
+        }
+    }
+
+    /// Forget every fill.
+    pub(crate) fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Mark `execution_id` busted. Returns the busted fill and the most
+    /// recent fill still standing, or `None` when there is nothing to bust.
+    fn bust(&self, execution_id: u64) -> Option<(TradeFill, Option<TradeFill>)> {
+        let mut records = self.lock();
+        let record = records
+            .iter_mut()
+            .rev()
+            .find(|record| record.fill.execution_id == execution_id && !record.busted)?;
+        record.busted = true;
+        let busted = record.fill;
+        let latest = records
+            .iter()
+            .filter(|record| !record.busted)
+            .max_by_key(|record| record.fill.execution_id)
+            .map(|record| record.fill);
+        Some((busted, latest))
+    }
+
+    /// Replace `execution_id` with `restate(fill)`. Returns the fill as it
+    /// was and as it now stands, or `None` when there is nothing to correct.
+    fn correct(
+        &self,
+        execution_id: u64,
+        restate: impl FnOnce(&TradeFill) -> TradeFill,
+    ) -> Option<(TradeFill, TradeFill)> {
+        let mut records = self.lock();
+        let record = records
+            .iter_mut()
+            .rev()
+            .find(|record| record.fill.execution_id == execution_id && !record.busted)?;
+        let superseded = record.fill;
+        record.fill = restate(&superseded);
+        Some((superseded, record.fill))
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Install an execution history, replacing any previous one.
+    ///
+    /// From then on every fill the book emits is retained in it, so that
+    /// [`bust_trade`](Self::bust_trade) and
+    /// [`correct_trade`](Self::correct_trade) can find it.
+    pub fn set_execution_history(&mut self, history: ExecutionHistory) {
+        self.execution_history = Some(history);
+    }
+
+    /// Returns the execution history, if one is installed.
+    #[must_use]
+    pub fn execution_history(&self) -> Option<&ExecutionHistory> {
+        self.execution_history.as_ref()
+    }
+
+    /// Cancel the fill with `execution_id` after the fact.
+    ///
+    /// Takes the fill out of the trade, volume and notional counters of
+    /// [`stats`](Self::stats) (the traded price range is kept), and when it
+    /// was the book's most recent trade rolls
+    /// [`last_trade_price`](Self::last_trade_price) back to the latest fill
+    /// still standing in the history, or clears it when none is. Resting
+    /// orders are untouched.
+    ///
+    /// The compensating [`TradeResult`] is handed to the trade listener and
+    /// returned: its [`amendment`](TradeResult::amendment) is
+    /// [`TradeAmendment::Bust`] with the fill as last reported, and its fee
+    /// totals refund that fill's fees.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrderBookError::ExecutionNotFound`] when no execution
+    /// history is installed, or it holds no standing fill with
+    /// `execution_id`.
+    pub fn bust_trade(&self, execution_id: u64) -> Result<TradeResult, OrderBookError> {
+        // Exclusive: no sweep may move the last trade while it is restated.
+        let _gate = self.submit_gate_write();
+        let (busted, latest) = self
+            .execution_history
+            .as_ref()
+            .and_then(|history| history.bust(execution_id))
+            .ok_or(OrderBookError::ExecutionNotFound { execution_id })?;
+
+        self.counters.record_bust(busted.price, busted.quantity);
+        if self.last_execution_id.load(Ordering::Relaxed) == execution_id {
+            match latest {
+                Some(fill) => {
+                    self.last_trade_price.store(fill.price);
+                    self.last_execution_id
+                        .store(fill.execution_id, Ordering::Relaxed);
+                }
+                None => {
+                    self.has_traded.store(false, Ordering::Relaxed);
+                    self.last_trade_price.store(0);
+                    self.last_execution_id.store(0, Ordering::Relaxed);
+                }
+            }
+        }
+
+        Ok(self.publish_amendment(
+            TradeAmendment::Bust(busted),
+            Vec::new(),
+            busted.maker.fee.saturating_neg(),
+            busted.taker.fee.saturating_neg(),
+        ))
+    }
+
+    /// Restate the fill with `execution_id` at `new_quantity` and
+    /// `new_price`.
+    ///
+    /// The corrected fill keeps its execution id, match time and
+    /// counterparties; both sides' fees are recharged at the book's current
+    /// fee schedule (each user's current tier under a
+    /// [tiered schedule](Self::set_tiered_fee_schedule), whose tracked
+    /// volume is left as it is). The volume and notional counters of
+    /// [`stats`](Self::stats) are restated, and when it was the book's most
+    /// recent trade [`last_trade_price`](Self::last_trade_price) becomes
+    /// `new_price`. Resting orders are untouched.
+    ///
+    /// The compensating [`TradeResult`] is handed to the trade listener and
+    /// returned: its [`amendment`](TradeResult::amendment) is
+    /// [`TradeAmendment::Correction`] with the fill as last reported, its
+    /// `fills` hold the corrected fill, and its fee totals are the change
+    /// in fees.
+    ///
+    /// # Errors
+    ///
+    /// - [`OrderBookError::CorrectionToZero`] when `new_quantity` is zero;
+    ///   bust the fill instead.
+    /// - [`OrderBookError::InvalidPriceLevel`] when `new_price` is zero.
+    /// - [`OrderBookError::ExecutionNotFound`] when no execution history is
+    ///   installed, or it holds no standing fill with `execution_id`.
+    pub fn correct_trade(
+        &self,
+        execution_id: u64,
+        new_quantity: u64,
+        new_price: u128,
+    ) -> Result<TradeResult, OrderBookError> {
+        if new_quantity == 0 {
+            return Err(OrderBookError::CorrectionToZero { execution_id });
+        }
+        if new_price == 0 {
+            return Err(OrderBookError::InvalidPriceLevel(new_price));
+        }
+
+        let _gate = self.submit_gate_write();
+        let fee_schedule = self.config().fee_schedule.filter(|s| !s.is_zero_fee());
+        let now_ms = self.clock().now_millis().as_u64();
+        let (superseded, corrected) = self
+            .execution_history
+            .as_ref()
+            .and_then(|history| {
+                history.correct(execution_id, |fill| {
+                    let mut corrected = *fill;
+                    corrected.price = new_price;
+                    corrected.quantity = new_quantity;
+                    let notional = corrected.notional();
+                    (corrected.maker.fee, corrected.taker.fee) =
+                        match (&self.tiered_fee_schedule, fee_schedule) {
+                            (Some(tiered), _) => (
+                                tiered.calculate_fee(
+                                    corrected.maker.user_id,
+                                    &self.symbol,
+                                    notional,
+                                    true,
+                                    now_ms,
+                                ),
+                                tiered.calculate_fee(
+                                    corrected.taker.user_id,
+                                    &self.symbol,
+                                    notional,
+                                    false,
+                                    now_ms,
+                                ),
+                            ),
+                            (None, Some(schedule)) => (
+                                schedule.calculate_fee(notional, true),
+                                schedule.calculate_fee(notional, false),
+                            ),
+                            (None, None) => (0, 0),
+                        };
+                    corrected
+                })
+            })
+            .ok_or(OrderBookError::ExecutionNotFound { execution_id })?;
+
+        self.counters.record_correction(
+            (superseded.price, superseded.quantity),
+            (corrected.price, corrected.quantity),
+        );
+        if self.last_execution_id.load(Ordering::Relaxed) == execution_id {
+            self.last_trade_price.store(new_price);
+        }
+
+        Ok(self.publish_amendment(
+            TradeAmendment::Correction(superseded),
+            vec![corrected],
+            corrected.maker.fee.saturating_sub(superseded.maker.fee),
+            corrected.taker.fee.saturating_sub(superseded.taker.fee),
+        ))
+    }
+
+    /// Build the compensating result for `amendment` and hand it to the
+    /// trade listener.
+    fn publish_amendment(
+        &self,
+        amendment: TradeAmendment,
+        fills: Vec<TradeFill>,
+        maker_fees: i128,
+        taker_fees: i128,
+    ) -> TradeResult {
+        let taker_order_id = amendment.superseded().taker.order_id;
+        let mut trade_result = TradeResult::new(
+            self.symbol().to_string(),
+            MatchResult::new(taker_order_id, Quantity::new(0)),
+        );
+        trade_result.total_maker_fees = maker_fees;
+        trade_result.total_taker_fees = taker_fees;
+        trade_result.engine_seq = self.next_engine_seq();
+        trade_result.config_version = self.config_version();
+        trade_result.price_scale = self.price_scale;
+        trade_result.fills = fills;
+        trade_result.amendment = Some(Box::new(amendment));
+        if let Some(listener) = self.trade_listener() {
+            listener(&trade_result);
+        }
+        trade_result
+    }
+}
@@ -0,0 +1,147 @@
+//! User-scoped order queries.
+//!
+//! Reads a single user's resting orders through the `user_orders` index
+//! instead of walking the whole book. Each order is located via
+//! `order_locations` and read from its price level, so the cost is
+//! proportional to the user's own orders and the levels they rest on,
+//! not to the size of the book. Intended for OMS reconciliation.
+
+use super::book::OrderBook;
+use pricelevel::{Hash32, Id, OrderType, Side, TimeInForce};
+use serde::{Deserialize, Serialize};
+
+/// Summary of one resting order, as returned by
+/// [`OrderBook::orders_for_user`].
+///
+/// Carries the fields an order management system needs to reconcile its
+/// own view against the book, without the `extra_fields` payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserOrderSummary {
+    /// Order identifier
+    pub order_id: Id,
+
+    /// Side the order rests on
+    pub side: Side,
+
+    /// Limit price (in price units)
+    pub price: u128,
+
+    /// Displayed quantity (in units)
+    pub visible_quantity: u64,
+
+    /// Undisplayed reserve quantity (in units)
+    pub hidden_quantity: u64,
+
+    /// Time in force of the order
+    pub time_in_force: TimeInForce,
+
+    /// Order timestamp (milliseconds)
+    pub timestamp: u64,
+}
+
+impl UserOrderSummary {
+    fn from_order(order: &OrderType<()>) -> Self {
+        Self {
+            order_id: order.id(),
+            side: order.side(),
+            price: order.price().as_u128(),
+            visible_quantity: order.visible_quantity().as_u64(),
+            hidden_quantity: order.hidden_quantity().as_u64(),
+            time_in_force: order.time_in_force(),
+            timestamp: order.timestamp().as_u64(),
+        }
+    }
+
+    /// Visible plus hidden quantity, saturating on overflow.
+    #[must_use]
+    #[inline]
+    pub fn open_quantity(&self) -> u64 {
+        self.visible_quantity.saturating_add(self.hidden_quantity)
+    }
+}
+
+impl<T> OrderBook<T>
+where
+    T: Clone + Send + Sync + Default + 'static,
+{
+    /// Returns a summary of every resting order owned by `user_id`.
+    ///
+    /// Orders are returned in the order the user's index holds them
+    /// (admission order). Orders submitted without a user id are indexed
+    /// under [`Hash32::zero()`], so querying the zero id returns every
+    /// anonymous resting order.
+    ///
+    /// # Consistency
+    /// The index and the price levels are read without the submit gate:
+    /// an order matched or cancelled concurrently may be missing from the
+    /// result, and its quantities reflect the level at the time of the
+    /// read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orderbook_rs::OrderBook;
+    /// use pricelevel::{Hash32, Id, Side, TimeInForce};
+    ///
+    /// let book = OrderBook::<()>::new("BTC/USD");
+    /// let user = Hash32::new([7u8; 32]);
+    /// book.add_limit_order_with_user(Id::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, user, None)
+    ///     .expect("rest");
+    ///
+    /// let orders = book.orders_for_user(user);
+    /// assert_eq!(orders.len(), 1);
+    /// assert_eq!(orders[0].price, 100);
+    /// ```
+    #[must_use]
+    pub fn orders_for_user(&self, user_id: Hash32) -> Vec<UserOrderSummary> {
+        let order_ids: Vec<Id> = match self.user_orders.get(&user_id) {
+            Some(ids) => ids.value().clone(),
+            None => return Vec::new(),
+        };
+
+        order_ids
+            .into_iter()
+            .filter_map(|order_id| self.user_order_summary(order_id))
+            .collect()
+    }
+
+    /// Returns the total open (visible + hidden) quantity `user_id` has
+    /// resting on `side`, in units.
+    ///
+    /// Same index lookup and consistency caveats as
+    /// [`Self::orders_for_user`]. The sum saturates at `u64::MAX`.
+    #[must_use]
+    pub fn open_quantity_for_user(&self, user_id: Hash32, side: Side) -> u64 {
+        let order_ids: Vec<Id> = match self.user_orders.get(&user_id) {
+            Some(ids) => ids.value().clone(),
+            None => return 0,
+        };
+
+        order_ids
+            .into_iter()
+            .filter(|order_id| {
+                self.order_locations
+                    .get(order_id)
+                    .is_some_and(|location| location.value().1 == side)
+            })
+            .filter_map(|order_id| self.user_order_summary(order_id))
+            .fold(0u64, |total, summary| {
+                total.saturating_add(summary.open_quantity())
+            })
+    }
+
+    /// Locates a resting order through `order_locations` and summarizes it.
+    fn user_order_summary(&self, order_id: Id) -> Option<UserOrderSummary> {
+        let (price, side) = *self.order_locations.get(&order_id)?;
+        let price_levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let entry = price_levels.get(&price)?;
+        entry
+            .value()
+            .iter_orders()
+            .find(|order| order.id() == order_id)
+            .map(|order| UserOrderSummary::from_order(&order))
+    }
+}
@@ -18,29 +18,54 @@
 
 // Core order book types
 pub use crate::orderbook::OrderBook;
+pub use crate::orderbook::TopOfBook;
+pub use crate::orderbook::book_config::BookConfig;
 pub use crate::orderbook::clock::{Clock, MonotonicClock, StubClock};
+pub use crate::orderbook::consolidated::{ConsolidatedBook, ConsolidatedLevel, VenueLevel};
 pub use crate::orderbook::manager::{BookManager, BookManagerStd, BookManagerTokio};
+pub use crate::orderbook::modify::OrderModification;
+pub use crate::orderbook::{DepthQuote, QuoteLevel};
 pub use crate::orderbook::{ManagerError, OrderBookError};
 
 // Iterator types
 pub use crate::orderbook::iterators::LevelInfo;
 
 // Market impact and simulation types
-pub use crate::orderbook::market_impact::{MarketImpact, OrderSimulation};
+pub use crate::orderbook::depth_mode::DepthMode;
+pub use crate::orderbook::market_impact::{LiquidationPoint, MarketImpact, OrderSimulation};
+pub use crate::orderbook::market_protection::{MarketProtection, ProtectionRemainder};
+pub use crate::orderbook::midpoint::MidpointOrder;
+pub use crate::orderbook::order_age::LevelAge;
+pub use crate::orderbook::order_tag::OrderTag;
+pub use crate::orderbook::post_only::PostOnlyMode;
+pub use crate::orderbook::priority_class::PriorityClass;
+pub use crate::orderbook::queue_peek::QueueEntry;
+pub use crate::orderbook::read_txn::{DEFAULT_READ_TXN_DEPTH, ReadTxn};
+pub use crate::orderbook::speed_bump::{
+    BumpedOrder, SpeedBump, SpeedBumpAdmission, SpeedBumpRelease,
+};
 
 // Snapshot types
-pub use crate::orderbook::snapshot::{EnrichedSnapshot, MetricFlags, OrderBookSnapshot};
+pub use crate::orderbook::snapshot::{
+    EnrichedSnapshot, LevelAggregate, LevelAggregates, MetricFlags, OrderBookSnapshot,
+    VenueSnapshot,
+};
 
 // Statistics types
-pub use crate::orderbook::statistics::{DepthStats, DistributionBin};
+pub use crate::orderbook::statistics::{
+    DepthStats, DistributionBin, LIFETIME_BUCKETS, LifetimeDistribution, QuoteLifetimeStats,
+};
 
 // Trade-related types
 pub use crate::orderbook::trade::{
-    TradeEvent, TradeInfo, TradeListener, TradeResult, TransactionInfo,
+    FillParty, LiquidityFlag, PriceLevelFill, TradeAmendment, TradeEvent, TradeFill, TradeInfo,
+    TradeListener, TradeResult, TransactionInfo,
 };
+pub use crate::orderbook::trade_amendment::ExecutionHistory;
 
 // Book change event types
 pub use crate::orderbook::book_change_event::{PriceLevelChangedEvent, PriceLevelChangedListener};
+pub use crate::orderbook::replenishment::{ReplenishmentEvent, ReplenishmentListener};
 
 // Order types and enums from pricelevel
 pub use pricelevel::{Id, OrderType, Side, TimeInForce, TimestampMs};
@@ -53,12 +78,36 @@ pub use crate::orderbook::order_state::{
     CancelReason, OrderStateListener, OrderStateTracker, OrderStatus,
 };
 
+// Perpetual futures funding and mark price
+pub use crate::orderbook::perp::{FundingConfig, FundingSettlement, PerpSnapshot, PerpTracker};
+pub use crate::orderbook::price_scale::PriceScale;
+
+// Quote quality monitoring
+pub use crate::orderbook::quote_quality::{QuoteQualityMonitor, QuoteQualityStats};
+
 // Rejection taxonomy
 pub use crate::orderbook::reject_reason::RejectReason;
 
 // Pre-trade risk layer types
+pub use crate::orderbook::rate_limit::{RateLimitConfig, TokenBucketConfig};
+pub use crate::orderbook::reference_price::{ExternalPrice, ExternalPriceSource};
 pub use crate::orderbook::risk::{ReferencePriceSource, RiskConfig, RiskState};
 
+// Smart order routing
+pub use crate::orderbook::router::{
+    ChildFill, ChildOrder, RoutingPlan, RoutingReport, RoutingStrategy, SmartOrderRouter,
+};
+
+// Trading session schedule and circuit breaker
+pub use crate::orderbook::circuit_breaker::{
+    CircuitBreakerAction, CircuitBreakerConfig, CircuitBreakerEvent,
+};
+pub use crate::orderbook::session::{SessionClose, SessionPhase, TradingSession};
+
+// Cancel-on-disconnect and dead-man's switch
+pub use crate::orderbook::cancel_on_disconnect::{DisconnectEvent, DisconnectListener};
+pub use crate::orderbook::dead_mans_switch::{DeadMansSwitch, DeadMansSwitchTrip};
+
 // Event serialization types
 #[cfg(feature = "bincode")]
 pub use crate::orderbook::serialization::BincodeEventSerializer;
@@ -76,10 +125,14 @@ pub use crate::orderbook::nats_book_change::{
 
 // Sequencer and journal types
 #[cfg(feature = "journal")]
-pub use crate::orderbook::sequencer::FileJournal;
 pub use crate::orderbook::sequencer::{
-    InMemoryJournal, Journal, JournalEntry, JournalError, JournalReadIter, ReplayBookConfig,
-    ReplayEngine, ReplayError, SequencerCommand, SequencerEvent, SequencerResult, snapshots_match,
+    AppendLatency, FileJournal, JournalHealth, JournalReadOptions,
+};
+pub use crate::orderbook::sequencer::{
+    InMemoryJournal, Journal, JournalEntry, JournalError, JournalFilter, JournalReadIter,
+    ReplayBookConfig, ReplayEngine, ReplayError, RingReceiver, RingSender, SequencedResult,
+    SequencerCommand, SequencerCommandKind, SequencerEvent, SequencerResult, WaitStrategy,
+    command_ring, snapshots_match,
 };
 
 // Utility functions
@@ -2,7 +2,7 @@ mod time;
 
 mod tests;
 
-pub use time::current_time_millis;
+pub use time::{current_time_millis, current_time_nanos};
 
 #[cfg(feature = "alloc-counters")]
 pub mod counting_allocator;
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::current_time_millis;
+    use crate::utils::current_time_nanos;
     use std::thread;
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
     use tracing::info;
@@ -55,4 +56,12 @@ mod tests {
         let time3 = current_time_millis();
         assert!(time3 > time1, "Time should increase after sleep");
     }
+
+    #[test]
+    fn test_current_time_nanos_agrees_with_millis() {
+        let millis = current_time_millis();
+        let nanos = current_time_nanos();
+        assert!(nanos / 1_000_000 >= millis);
+        assert!(nanos / 1_000_000 - millis <= 10);
+    }
 }
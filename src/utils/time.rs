@@ -24,3 +24,16 @@ pub fn current_time_millis() -> u64 {
         .map(|d| d.as_millis() as u64)
         .unwrap_or(0)
 }
+
+/// Returns the current wall-clock time in nanoseconds since the UNIX epoch.
+///
+/// The nanosecond counterpart of [`current_time_millis`], with the same
+/// caveats: wall-clock, non-monotonic, and `0` before the epoch. Saturates
+/// at `u64::MAX`, which is not reached until the year 2554.
+#[must_use = "the current time is returned and should be used"]
+pub fn current_time_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
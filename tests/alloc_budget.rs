@@ -10,17 +10,25 @@
 //! shard-grow events or platform-specific allocator behaviour. A real
 //! "one alloc per regression" guard belongs in the bench output's
 //! tighter floor. This integration test is the CI guard.
+//!
+//! A second test checks that installing a trade listener costs only the
+//! `TradeResult` symbol string per match — never a `MatchResult` clone.
 
 #![cfg(feature = "alloc-counters")]
 
-use orderbook_rs::OrderBook;
 use orderbook_rs::utils::CountingAllocator;
+use orderbook_rs::{OrderBook, TradeResult};
 use pricelevel::{Hash32, Id, Side, TimeInForce};
 use std::alloc::System;
+use std::sync::{Arc, Mutex};
 
 #[global_allocator]
 static GLOBAL: CountingAllocator<System> = CountingAllocator::new(System);
 
+/// The counters are process-global, so tests in this binary must not
+/// overlap or they would count each other's allocations.
+static SERIAL: Mutex<()> = Mutex::new(());
+
 const WARMUP_OPS: u64 = 1_000;
 const MEASURED_OPS: u64 = 10_000;
 // Conservative ceiling. Mixed workload allocates per-op via `DashMap`
@@ -65,6 +73,9 @@ fn run_workload(book: &OrderBook<()>, count: u64, base: u64) {
 
 #[test]
 fn alloc_budget_mixed_workload_stays_under_ceiling() {
+    let _serial = SERIAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
     let book = OrderBook::<()>::new("BUDGET");
 
     // Seed liquidity so cancels and aggressive market orders find
@@ -98,3 +109,107 @@ fn alloc_budget_mixed_workload_stays_under_ceiling() {
         ALLOCS_PER_OP_CEILING,
     );
 }
+
+/// Allocations for one crossing limit submit that takes `fills` makers.
+fn small_match_allocs(book: &OrderBook<()>, fills: u64, next_id: &mut u64) -> u64 {
+    for _ in 0..fills {
+        *next_id += 1;
+        let _ = book.add_limit_order(
+            Id::from_u64(*next_id),
+            100,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        );
+    }
+    *next_id += 1;
+    let before = GLOBAL.snapshot();
+    let _ = book.add_limit_order(
+        Id::from_u64(*next_id),
+        100,
+        fills * 10,
+        Side::Buy,
+        TimeInForce::Ioc,
+        None,
+    );
+    GLOBAL.snapshot().since(before).allocs
+}
+
+#[test]
+fn trade_listener_does_not_clone_the_match_result() {
+    let _serial = SERIAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    const MATCHES: u64 = 2_000;
+    let book = OrderBook::<()>::new("BUDGET");
+    let mut next_id = 0;
+
+    for fills in 1..=4 {
+        book.remove_trade_listener();
+        let bare: u64 = (0..MATCHES)
+            .map(|_| small_match_allocs(&book, fills, &mut next_id))
+            .sum();
+        book.set_trade_listener(Arc::new(|trade: &TradeResult| {
+            std::hint::black_box(trade.quote_notional);
+        }));
+        let with_listener: u64 = (0..MATCHES)
+            .map(|_| small_match_allocs(&book, fills, &mut next_id))
+            .sum();
+
+        // Publishing costs the `TradeResult::symbol` string and nothing
+        // else; a `MatchResult` clone would add two vectors per match.
+        let extra_per_match = (with_listener as f64 - bare as f64) / MATCHES as f64;
+        assert!(
+            extra_per_match < 1.5,
+            "{fills}-fill match: listener adds {extra_per_match:.2} allocs/match"
+        );
+    }
+}
+
+#[test]
+fn depth_walk_analytics_do_not_allocate() {
+    let _serial = SERIAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let book = OrderBook::<()>::new("BUDGET");
+    for i in 0..20u128 {
+        let _ = book.add_limit_order(
+            Id::new_uuid(),
+            90 - i,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            None,
+        );
+        let _ = book.add_limit_order(
+            Id::new_uuid(),
+            110 + i,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        );
+    }
+
+    let walk = |book: &OrderBook<()>| {
+        for side in [Side::Buy, Side::Sell] {
+            std::hint::black_box(book.vwap(150, side));
+            std::hint::black_box(book.price_at_depth(150, side));
+            std::hint::black_box(book.cumulative_depth_to_target(150, side));
+            std::hint::black_box(book.total_depth_at_levels(15, side));
+            std::hint::black_box(book.market_impact(150, side));
+        }
+        std::hint::black_box(book.depth_quote::<5>());
+    };
+    // The first walk registers this thread with the skip maps' epoch
+    // collector, which allocates once.
+    walk(&book);
+
+    let before = GLOBAL.snapshot();
+    for _ in 0..100 {
+        walk(&book);
+    }
+    let allocs = GLOBAL.snapshot().since(before).allocs;
+    assert_eq!(allocs, 0, "depth walks allocated {allocs} times");
+}
@@ -96,7 +96,7 @@ fn test_orderbook_fee_schedule_default() {
 
 #[test]
 fn test_orderbook_set_fee_schedule() {
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
     let schedule = FeeSchedule::new(-2, 5);
 
     book.set_fee_schedule(Some(schedule));
@@ -105,7 +105,7 @@ fn test_orderbook_set_fee_schedule() {
 
 #[test]
 fn test_orderbook_update_fee_schedule() {
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
 
     // Set initial schedule
     let initial_schedule = FeeSchedule::new(-1, 3);
@@ -124,7 +124,7 @@ fn test_orderbook_update_fee_schedule() {
 
 #[test]
 fn test_orderbook_fee_schedule_persistence() {
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
     let schedule = FeeSchedule::with_maker_rebate(2, 6);
 
     book.set_fee_schedule(Some(schedule));
@@ -143,7 +143,7 @@ fn test_orderbook_constructors_with_fee_schedule() {
     let schedule = FeeSchedule::new(-2, 5);
 
     // Test basic constructor
-    let mut book1 = OrderBook::<()>::new("BTC/USD");
+    let book1 = OrderBook::<()>::new("BTC/USD");
     book1.set_fee_schedule(Some(schedule));
     assert_eq!(book1.fee_schedule(), Some(schedule));
 
@@ -152,7 +152,7 @@ fn test_orderbook_constructors_with_fee_schedule() {
         Arc::new(|_trade_result: &TradeResult| {
             // Empty listener for testing
         });
-    let mut book2 = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
+    let book2 = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
     book2.set_fee_schedule(Some(schedule));
     assert_eq!(book2.fee_schedule(), Some(schedule));
 }
@@ -177,7 +177,7 @@ fn test_fee_schedule_serialization() {
 
 #[test]
 fn test_orderbook_serialization_with_fee_schedule() {
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
     let schedule = FeeSchedule::with_maker_rebate(3, 7);
     book.set_fee_schedule(Some(schedule));
 
@@ -245,7 +245,7 @@ mod integration_tests {
 
     #[test]
     fn test_fee_schedule_with_matching() {
-        let mut book = OrderBook::<()>::new("BTC/USD");
+        let book = OrderBook::<()>::new("BTC/USD");
         let schedule = FeeSchedule::new(-2, 5);
         book.set_fee_schedule(Some(schedule));
 
@@ -272,7 +272,7 @@ mod integration_tests {
 
     #[test]
     fn test_fee_schedule_with_multiple_operations() {
-        let mut book = OrderBook::<()>::new("BTC/USD");
+        let book = OrderBook::<()>::new("BTC/USD");
 
         // Start with no fees
         assert_eq!(book.fee_schedule(), None);
@@ -312,7 +312,7 @@ mod integration_tests {
                 trades.push(trade_result.clone());
             });
 
-        let mut book = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
+        let book = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
 
         // Set fee schedule: -2 bps maker rebate, 5 bps taker fee
         let schedule = FeeSchedule::new(-2, 5);
@@ -382,7 +382,7 @@ mod integration_tests {
                 trades.push(trade_result.clone());
             });
 
-        let mut book = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
+        let book = OrderBook::<()>::with_trade_listener("BTC/USD", listener);
 
         // 10 bps taker, -3 bps maker rebate
         let schedule = FeeSchedule::new(-3, 10);
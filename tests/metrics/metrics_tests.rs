@@ -261,6 +261,60 @@ fn metrics_do_not_affect_order_semantics() {
     );
 }
 
+#[cfg(feature = "journal")]
+#[test]
+fn journal_appends_and_damage_are_exported() {
+    use orderbook_rs::orderbook::metrics::{
+        JOURNAL_BYTES_WRITTEN_TOTAL, JOURNAL_DAMAGED_ENTRIES_TOTAL, JOURNAL_ROTATIONS_TOTAL,
+        JOURNAL_SEGMENT_UTILIZATION,
+    };
+    use orderbook_rs::orderbook::sequencer::{
+        FileJournal, Journal, SequencerCommand, SequencerEvent, SequencerResult,
+    };
+
+    let _guard = serialized_test_lock().lock().expect("serialized lock");
+    install_recorder();
+    let dir = tempfile::tempdir().expect("tempdir");
+    let event = |seq: u64| SequencerEvent::<()> {
+        sequence_num: seq,
+        timestamp_ns: 0,
+        command: SequencerCommand::CancelAll,
+        result: SequencerResult::Rejected {
+            reason: "empty".to_string(),
+        },
+    };
+    let bytes_before = counter_value(JOURNAL_BYTES_WRITTEN_TOTAL);
+    let rotations_before = counter_value(JOURNAL_ROTATIONS_TOTAL);
+    let corrupt_key = format!("{JOURNAL_DAMAGED_ENTRIES_TOTAL}{{kind=corrupt}}");
+    let corrupt_before = counter_value(&corrupt_key);
+
+    // Room for two entries per segment, so the third append rotates.
+    let journal: FileJournal<()> =
+        FileJournal::open_with_segment_size(dir.path(), 300).expect("open journal");
+    for seq in 0..3 {
+        journal.append(&event(seq)).expect("append");
+    }
+    let health = journal.health().expect("health");
+    assert_eq!(health.rotations, 1);
+    assert_eq!(
+        counter_value(JOURNAL_BYTES_WRITTEN_TOTAL) - bytes_before,
+        health.bytes_written
+    );
+    assert_eq!(counter_value(JOURNAL_ROTATIONS_TOTAL) - rotations_before, 1);
+    assert!((gauge_value(JOURNAL_SEGMENT_UTILIZATION) - health.segment_utilization).abs() < 1e-9);
+    drop(journal);
+
+    // Flip a payload byte of the first entry: the integrity check reports it.
+    let segment = dir.path().join(format!("segment-{:020}.journal", 0));
+    let mut data = std::fs::read(&segment).expect("read segment");
+    data[40] ^= 0xFF;
+    std::fs::write(&segment, &data).expect("write segment");
+    let journal: FileJournal<()> =
+        FileJournal::open_with_segment_size(dir.path(), 300).expect("reopen journal");
+    assert!(journal.verify_integrity().is_err());
+    assert_eq!(counter_value(&corrupt_key) - corrupt_before, 1);
+}
+
 /// Removes the per-level `statistics` object from each bid/ask level of a
 /// serialized order-book snapshot. Those statistics carry wall-clock timestamps
 /// not governed by the injected clock; stripping them makes a snapshot equality
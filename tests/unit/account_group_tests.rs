@@ -0,0 +1,109 @@
+//! Integration tests for account group anti-internalization.
+//!
+//! Accounts in the same group never trade with each other while
+//! self-trade prevention is enabled; the STP mode decides whether the
+//! taker or the resting orders are cancelled.
+
+#[cfg(test)]
+mod tests_account_group {
+    use super::super::common::rest_for;
+    use orderbook_rs::orderbook::order_state::{CancelReason, OrderStateTracker, OrderStatus};
+    use orderbook_rs::{OrderBook, OrderBookError, STPMode};
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    /// Users 1 and 2 share group 7; user 3 is in group 8.
+    fn grouped_book(mode: STPMode) -> OrderBook<()> {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book.set_stp_mode(mode);
+        book.set_account_group(user(1), 7);
+        book.set_account_group(user(2), 7);
+        book.set_account_group(user(3), 8);
+        book
+    }
+
+    #[test]
+    fn cancel_taker_keeps_group_members_apart() {
+        let book = grouped_book(STPMode::CancelTaker);
+        let ask = rest_for(&book, 100, 10, Side::Sell, user(1));
+
+        let taker = Id::new_uuid();
+        assert!(matches!(
+            book.add_limit_order_with_user(taker, 100, 10, Side::Buy, TimeInForce::Gtc, user(2), None),
+            Err(OrderBookError::SelfTradePrevented { user_id, .. }) if user_id == user(2)
+        ));
+        assert!(book.get_order(taker).is_none());
+        assert!(book.get_order(ask).is_some());
+        assert!(book.last_trade_price().is_none());
+
+        rest_for(&book, 100, 10, Side::Buy, user(3));
+        assert_eq!(book.last_trade_price(), Some(100));
+        assert!(book.get_order(ask).is_none());
+    }
+
+    #[test]
+    fn cancel_maker_removes_resting_orders_of_the_group() {
+        let book = grouped_book(STPMode::CancelMaker);
+        let member_ask = rest_for(&book, 100, 10, Side::Sell, user(1));
+        let outsider_ask = rest_for(&book, 101, 10, Side::Sell, user(3));
+
+        let taker = rest_for(&book, 101, 10, Side::Buy, user(2));
+        assert!(book.get_order(member_ask).is_none());
+        assert!(matches!(
+            book.order_status(member_ask),
+            Some(OrderStatus::Cancelled {
+                filled_quantity: 0,
+                reason: CancelReason::SelfTradePrevention,
+            })
+        ));
+        assert_eq!(book.last_trade_price(), Some(101));
+        assert!(book.get_order(outsider_ask).is_none());
+        assert!(book.get_order(taker).is_none(), "fully filled");
+    }
+
+    #[test]
+    fn leaving_the_group_or_disabling_stp_allows_trading() {
+        let book = grouped_book(STPMode::CancelTaker);
+        rest_for(&book, 100, 10, Side::Sell, user(1));
+        assert_eq!(book.clear_account_group(user(2)), Some(7));
+        assert_eq!(book.clear_account_group(user(2)), None);
+        rest_for(&book, 100, 10, Side::Buy, user(2));
+        assert_eq!(book.last_trade_price(), Some(100));
+
+        let book = grouped_book(STPMode::None);
+        rest_for(&book, 100, 10, Side::Sell, user(1));
+        rest_for(&book, 100, 10, Side::Buy, user(2));
+        assert_eq!(book.last_trade_price(), Some(100));
+    }
+
+    #[test]
+    fn registry_queries_and_snapshot_round_trip() {
+        let original = grouped_book(STPMode::CancelTaker);
+        assert_eq!(original.set_account_group(user(3), 7), Some(8));
+        assert_eq!(original.account_group(user(3)), Some(7));
+        assert_eq!(original.account_group(user(4)), None);
+        assert_eq!(
+            original.account_group_members(7),
+            vec![user(1), user(2), user(3)]
+        );
+
+        let package = original
+            .create_snapshot_package(10)
+            .expect("snapshot package");
+        assert_eq!(
+            package.account_groups,
+            vec![(user(1), 7), (user(2), 7), (user(3), 7)]
+        );
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored.set_account_group(user(9), 1);
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.account_groups(), original.account_groups());
+        assert_eq!(restored.account_group(user(9)), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Integration tests for the feature-gated Arrow / Parquet export.
+
+#[cfg(test)]
+mod tests_arrow_export {
+    use super::super::common::rest_as;
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{TimestampMillisecondType, UInt32Type, UInt64Type};
+    use orderbook_rs::orderbook::arrow::{
+        ARROW_SCHEMA_VERSION, depth_schema, snapshot_schema, snapshots_to_record_batch,
+        trade_schema, trades_to_record_batch, write_parquet,
+    };
+    use orderbook_rs::{
+        ArrowExportError, DepthRecorder, OrderBook, OrderBookSnapshot, StubClock, TradeResult,
+    };
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use pricelevel::{Hash32, Id, Side};
+    use std::sync::{Arc, Mutex};
+
+    fn book_at(millis: u64) -> OrderBook<()> {
+        let mut book = OrderBook::new("BTC/USD");
+        book.set_clock(Arc::new(StubClock::starting_at(millis)));
+        book
+    }
+
+    #[test]
+    fn trade_tape_has_one_row_per_fill() {
+        let book = book_at(1_000);
+        let tape = Arc::new(Mutex::new(Vec::<TradeResult>::new()));
+        let sink = Arc::clone(&tape);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().expect("tape").push(trade.clone());
+        }));
+        rest_as(&book, Id::sequential(1), 101, 3, Side::Sell, Hash32::zero());
+        rest_as(&book, Id::sequential(2), 102, 4, Side::Sell, Hash32::zero());
+        book.submit_market_order(Id::sequential(3), 5, Side::Buy)
+            .expect("market order");
+
+        let tape = tape.lock().expect("tape");
+        let batch = trades_to_record_batch(&tape).expect("batch");
+        assert_eq!(batch.schema(), trade_schema());
+        assert_eq!(batch.num_rows(), 2);
+
+        let prices = batch.column_by_name("price").expect("price");
+        let prices = prices.as_primitive::<UInt64Type>();
+        assert_eq!((prices.value(0), prices.value(1)), (101, 102));
+        let quantities = batch.column_by_name("quantity").expect("quantity");
+        let quantities = quantities.as_primitive::<UInt64Type>();
+        assert_eq!((quantities.value(0), quantities.value(1)), (3, 2));
+        let sides = batch.column_by_name("taker_side").expect("taker_side");
+        assert_eq!(sides.as_string::<i32>().value(1), "buy");
+        let makers = batch.column_by_name("maker_order_id").expect("maker");
+        assert_eq!(
+            makers.as_string::<i32>().value(0),
+            Id::sequential(1).to_string()
+        );
+        let timestamps = batch.column_by_name("timestamp").expect("timestamp");
+        assert!(
+            timestamps
+                .as_primitive::<TimestampMillisecondType>()
+                .values()
+                .iter()
+                .all(|&ts| ts >= 1_000)
+        );
+    }
+
+    #[test]
+    fn snapshots_round_trip_through_parquet() {
+        let book = book_at(5_000);
+        rest_as(&book, Id::sequential(1), 99, 10, Side::Buy, Hash32::zero());
+        rest_as(&book, Id::sequential(2), 98, 4, Side::Buy, Hash32::zero());
+        rest_as(&book, Id::sequential(3), 98, 6, Side::Buy, Hash32::zero());
+        rest_as(&book, Id::sequential(4), 101, 7, Side::Sell, Hash32::zero());
+        let batch = snapshots_to_record_batch(&[book.create_snapshot(10), book.create_snapshot(1)])
+            .expect("batch");
+        assert_eq!(batch.num_rows(), 5);
+
+        let levels = batch.column_by_name("level").expect("level");
+        let levels = levels.as_primitive::<UInt32Type>();
+        assert_eq!(levels.values().to_vec(), vec![0, 1, 0, 0, 0]);
+        let counts = batch.column_by_name("order_count").expect("order_count");
+        assert_eq!(counts.as_primitive::<UInt32Type>().value(1), 2);
+
+        let file = tempfile::tempfile().expect("tempfile");
+        write_parquet(file.try_clone().expect("clone"), &batch).expect("write");
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).expect("parquet");
+        assert_eq!(builder.schema(), &snapshot_schema());
+        let metadata = builder.schema().metadata();
+        assert_eq!(metadata["orderbook_rs.schema"], "snapshots");
+        assert_eq!(
+            metadata["orderbook_rs.schema_version"],
+            ARROW_SCHEMA_VERSION.to_string()
+        );
+        let batches: Vec<_> = builder
+            .build()
+            .expect("reader")
+            .collect::<Result<_, _>>()
+            .expect("read");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].columns(), batch.columns());
+    }
+
+    #[test]
+    fn depth_recorder_samples_and_nulls_empty_sides() {
+        let book = book_at(10);
+        let mut recorder = DepthRecorder::new(2);
+        recorder.record(&book);
+        rest_as(&book, Id::sequential(1), 99, 10, Side::Buy, Hash32::zero());
+        rest_as(&book, Id::sequential(2), 98, 5, Side::Buy, Hash32::zero());
+        rest_as(&book, Id::sequential(3), 97, 1, Side::Buy, Hash32::zero());
+        recorder.record(&book);
+
+        let samples = recorder.samples();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[1].best_bid, Some(99));
+        assert_eq!(samples[1].bid_depth, 15);
+        assert_eq!(samples[1].bid_levels, 2);
+
+        let batch = recorder.to_record_batch().expect("batch");
+        assert_eq!(batch.schema(), depth_schema());
+        let best_bid = batch.column_by_name("best_bid").expect("best_bid");
+        assert!(best_bid.is_null(0));
+        assert_eq!(best_bid.as_primitive::<UInt64Type>().value(1), 99);
+        assert!(
+            batch
+                .column_by_name("best_ask")
+                .expect("best_ask")
+                .is_null(1)
+        );
+
+        assert_eq!(recorder.take_samples().len(), 2);
+        assert!(recorder.samples().is_empty());
+    }
+
+    #[test]
+    fn prices_beyond_u64_are_rejected() {
+        let book = book_at(0);
+        let price = u128::from(u64::MAX) + 1;
+        rest_as(
+            &book,
+            Id::sequential(1),
+            price,
+            1,
+            Side::Sell,
+            Hash32::zero(),
+        );
+        let snapshot: OrderBookSnapshot = book.create_snapshot(1);
+        assert!(matches!(
+            snapshots_to_record_batch(&[snapshot]),
+            Err(ArrowExportError::ValueOverflow { column: "price", value }) if value == price
+        ));
+    }
+}
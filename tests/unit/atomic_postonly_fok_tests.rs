@@ -105,10 +105,10 @@ mod tests_atomic_postonly_fok {
         let violations = Arc::new(AtomicU64::new(0));
 
         for round in 0..ROUNDS {
-            let mut book: OrderBook<()> = DefaultOrderBook::new("PORACE");
+            let book: OrderBook<()> = DefaultOrderBook::new("PORACE");
             let trades: Arc<Mutex<Vec<(Id, u64)>>> = Arc::new(Mutex::new(Vec::new()));
             let sink = Arc::clone(&trades);
-            book.trade_listener = Some(Arc::new(move |tr: &TradeResult| {
+            book.set_trade_listener(Arc::new(move |tr: &TradeResult| {
                 let mut sunk = sink.lock().expect("trade sink lock");
                 for t in tr.match_result.trades().as_vec() {
                     sunk.push((t.taker_order_id(), t.quantity().as_u64()));
@@ -253,11 +253,11 @@ mod tests_post_only_stp_precedence {
         let user = Hash32::new([7u8; 32]);
 
         for round in 0..ROUNDS {
-            let mut book: OrderBook<()> = DefaultOrderBook::new("POSTP");
+            let book: OrderBook<()> = DefaultOrderBook::new("POSTP");
             book.set_stp_mode(STPMode::CancelMaker);
             let trades: Arc<Mutex<u64>> = Arc::new(Mutex::new(0));
             let sink = Arc::clone(&trades);
-            book.trade_listener = Some(Arc::new(move |tr: &TradeResult| {
+            book.set_trade_listener(Arc::new(move |tr: &TradeResult| {
                 *sink.lock().expect("sink") += tr.match_result.trades().len() as u64;
             }));
             let book = Arc::new(book);
@@ -0,0 +1,132 @@
+//! Integration tests for `OrderBook::audit`.
+
+#[cfg(test)]
+mod tests_audit {
+    use orderbook_rs::{InvariantViolation, OrderBook};
+    use pricelevel::{Hash32, Id, OrderUpdate, Price, Quantity, Side, TimeInForce};
+
+    #[test]
+    fn empty_book_is_consistent() {
+        let book = OrderBook::<()>::new("TEST");
+        assert!(book.audit().is_empty());
+    }
+
+    #[test]
+    fn book_stays_consistent_through_matching_and_maintenance() {
+        let book = OrderBook::<()>::new("TEST");
+        let alice = Hash32::new([1; 32]);
+        let bob = Hash32::new([2; 32]);
+
+        let mut resting = Vec::new();
+        for i in 0..10u128 {
+            let bid = Id::new_uuid();
+            book.add_limit_order_with_user(
+                bid,
+                100 - i,
+                10,
+                Side::Buy,
+                TimeInForce::Gtc,
+                alice,
+                None,
+            )
+            .expect("rest bid");
+            let ask = Id::new_uuid();
+            book.add_limit_order_with_user(
+                ask,
+                101 + i,
+                10,
+                Side::Sell,
+                TimeInForce::Gtc,
+                bob,
+                None,
+            )
+            .expect("rest ask");
+            resting.push((bid, ask));
+        }
+        book.add_iceberg_order(
+            Id::new_uuid(),
+            101,
+            5,
+            20,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("rest iceberg");
+        assert!(book.audit().is_empty());
+
+        book.submit_market_order(Id::new_uuid(), 25, Side::Buy)
+            .expect("sweep asks");
+        book.submit_market_order(Id::new_uuid(), 15, Side::Sell)
+            .expect("sweep bids");
+        assert!(book.audit().is_empty());
+
+        let (bid, ask) = resting[5];
+        book.cancel_order(bid).expect("cancel bid");
+        book.update_order(OrderUpdate::UpdatePrice {
+            order_id: ask,
+            new_price: Price::new(120),
+        })
+        .expect("reprice ask");
+        book.update_order(OrderUpdate::UpdateQuantity {
+            order_id: resting[7].0,
+            new_quantity: Quantity::new(3),
+        })
+        .expect("resize bid");
+        assert!(!book.cancel_all_orders().is_empty());
+        assert!(book.audit().is_empty());
+    }
+
+    #[test]
+    fn crossed_snapshot_restore_is_reported() {
+        let bids = OrderBook::<()>::new("TEST");
+        bids.add_limit_order(Id::new_uuid(), 105, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("rest bid");
+        let asks = OrderBook::<()>::new("TEST");
+        asks.add_limit_order(Id::new_uuid(), 100, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("rest ask");
+
+        let mut snapshot = bids.create_snapshot(usize::MAX);
+        snapshot.asks = asks.create_snapshot(usize::MAX).asks;
+        let book = OrderBook::<()>::new("TEST");
+        book.restore_from_snapshot(snapshot).expect("restore");
+
+        assert_eq!(
+            book.audit(),
+            vec![InvariantViolation::CrossedBook {
+                best_bid: 105,
+                best_ask: 100,
+            }]
+        );
+    }
+
+    #[test]
+    fn violations_serialize_and_display() {
+        let violation = InvariantViolation::LockedBook { price: 100 };
+        assert_eq!(violation.to_string(), "locked book at 100");
+        let json = serde_json::to_string(&violation).expect("serialize");
+        assert_eq!(json, r#"{"LockedBook":{"price":100}}"#);
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn scheduled_audit_runs_on_a_consistent_book() {
+        let book = OrderBook::<()>::new("TEST");
+        book.set_audit_interval(1);
+        for i in 0..20u128 {
+            book.add_limit_order(
+                Id::new_uuid(),
+                100 + i,
+                10,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("rest");
+        }
+        book.submit_market_order(Id::new_uuid(), 55, Side::Buy)
+            .expect("sweep");
+        assert!(!book.cancel_all_orders().is_empty());
+        assert!(book.audit().is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Integration tests for `OrderBook::add_orders_batch`.
+
+#[cfg(test)]
+mod tests_batch_submit {
+    use orderbook_rs::{OrderBook, OrderBookError};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn order(id: Id, price: u128, quantity: u64, side: Side, tif: TimeInForce) -> OrderType<()> {
+        OrderType::Standard {
+            id,
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: tif,
+            extra_fields: (),
+        }
+    }
+
+    fn gtc(price: u128, quantity: u64, side: Side) -> OrderType<()> {
+        order(Id::new_uuid(), price, quantity, side, TimeInForce::Gtc)
+    }
+
+    #[test]
+    fn batch_rests_a_two_sided_ladder() {
+        let book = OrderBook::<()>::new("TEST");
+        let orders: Vec<_> = (0..5)
+            .map(|i| gtc(99 - i, 10, Side::Buy))
+            .chain((0..5).map(|i| gtc(101 + i, 10, Side::Sell)))
+            .collect();
+
+        let results = book.add_orders_batch(orders);
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(book.best_bid(), Some(99));
+        assert_eq!(book.best_ask(), Some(101));
+        assert_eq!(book.get_bids().len(), 5);
+        assert_eq!(book.get_asks().len(), 5);
+    }
+
+    #[test]
+    fn batch_orders_are_processed_in_sequence_and_match() {
+        let book = OrderBook::<()>::new("TEST");
+        let ask = Id::new_uuid();
+        let results = book.add_orders_batch(vec![
+            order(ask, 100, 10, Side::Sell, TimeInForce::Gtc),
+            // Crosses the ask submitted earlier in the same batch.
+            gtc(100, 4, Side::Buy),
+        ]);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(book.last_trade_price(), Some(100));
+        assert_eq!(book.best_bid(), None);
+        let resting = book.get_order(ask).expect("ask still resting");
+        assert_eq!(resting.visible_quantity().as_u64(), 6);
+    }
+
+    #[test]
+    fn one_rejection_does_not_affect_the_rest_of_the_batch() {
+        let book = OrderBook::<()>::new("TEST");
+        let dup = Id::new_uuid();
+        let results = book.add_orders_batch(vec![
+            order(dup, 99, 10, Side::Buy, TimeInForce::Gtc),
+            order(dup, 98, 10, Side::Buy, TimeInForce::Gtc),
+            // Nothing to fill against: FOK is killed.
+            order(Id::new_uuid(), 110, 10, Side::Buy, TimeInForce::Fok),
+            gtc(97, 10, Side::Buy),
+        ]);
+
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(OrderBookError::DuplicateOrderId { order_id }) if order_id == dup
+        ));
+        assert!(results[2].is_err());
+        assert!(results[3].is_ok());
+        assert_eq!(book.get_bids().len(), 2);
+    }
+
+    #[test]
+    fn empty_batch_is_a_no_op() {
+        let book = OrderBook::<()>::new("TEST");
+        assert!(book.add_orders_batch(Vec::new()).is_empty());
+        assert_eq!(book.best_bid(), None);
+    }
+}
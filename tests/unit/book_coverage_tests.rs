@@ -27,7 +27,7 @@ mod tests {
         let book = OrderBook::<()>::with_trade_listener("TEST", dummy_listener);
 
         assert_eq!(book.symbol(), "TEST");
-        assert!(book.trade_listener.is_some());
+        assert!(book.trade_listener().is_some());
         assert_eq!(book.best_bid(), None);
         assert_eq!(book.best_ask(), None);
     }
@@ -81,7 +81,9 @@ mod tests {
             side: Side::Buy,
             price: 100,
             quantity: 5,
+            order_count: 1,
             engine_seq: 1,
+            config_version: 0,
         };
         assert_eq!(evt.price, 100);
         // The remaining types only need to name-resolve at the root.
@@ -677,10 +679,10 @@ mod tests {
         use orderbook_rs::{TradeListener, TradeResult};
         use std::sync::{Arc, Mutex};
 
-        let mut book = OrderBook::<()>::new("ETH/USD");
+        let book = OrderBook::<()>::new("ETH/USD");
 
         // Initially no listener
-        assert!(book.trade_listener.is_none());
+        assert!(book.trade_listener().is_none());
 
         let captured_trades = Arc::new(Mutex::new(Vec::<TradeResult>::new()));
         let captured_trades_clone = captured_trades.clone();
@@ -692,10 +694,10 @@ mod tests {
 
         // Set listener
         book.set_trade_listener(trade_listener);
-        assert!(book.trade_listener.is_some());
+        assert!(book.trade_listener().is_some());
 
         // Remove listener
         book.remove_trade_listener();
-        assert!(book.trade_listener.is_none());
+        assert!(book.trade_listener().is_none());
     }
 }
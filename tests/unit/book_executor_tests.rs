@@ -0,0 +1,462 @@
+//! Integration tests for the thread-per-book `BookExecutor`.
+
+#[cfg(test)]
+mod tests_book_executor {
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::{
+        BookExecutor, ExecutorConfig, InMemoryJournal, Journal, JournalError, JournalFilter,
+        JournalReadIter, ManagerError, OrderBook, SequencerCommand, SequencerEvent,
+        SequencerResult, WaitStrategy,
+    };
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn limit(price: u128, quantity: u64, side: Side) -> SequencerCommand<()> {
+        SequencerCommand::AddOrder(OrderType::Standard {
+            id: Id::new_uuid(),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    fn executor_with(symbols: &[&str], config: ExecutorConfig) -> BookExecutor<()> {
+        let mut executor = BookExecutor::new(config);
+        for symbol in symbols {
+            executor
+                .spawn_book(OrderBook::new(symbol))
+                .expect("spawn book");
+        }
+        executor
+    }
+
+    #[test]
+    fn submit_routes_commands_to_the_symbols_book() {
+        let executor = executor_with(&["BTC/USD", "ETH/USD"], ExecutorConfig::new());
+
+        let added = executor
+            .submit("BTC/USD", limit(100, 10, Side::Sell))
+            .expect("submit")
+            .recv()
+            .expect("result");
+        assert!(matches!(added, SequencerResult::OrderAdded { .. }));
+
+        let market = SequencerCommand::MarketOrder {
+            id: Id::new_uuid(),
+            quantity: 4,
+            side: Side::Buy,
+        };
+        match executor.submit("BTC/USD", market).unwrap().recv().unwrap() {
+            SequencerResult::TradeExecuted { trade_result } => {
+                assert_eq!(trade_result.symbol, "BTC/USD");
+                assert_eq!(
+                    trade_result
+                        .match_result
+                        .executed_quantity()
+                        .unwrap()
+                        .as_u64(),
+                    4
+                );
+            }
+            other => panic!("expected a trade, got {other:?}"),
+        }
+
+        // Nothing rests on ETH/USD, so the market order is rejected there.
+        let market = SequencerCommand::MarketOrder {
+            id: Id::new_uuid(),
+            quantity: 4,
+            side: Side::Buy,
+        };
+        let rejected = executor.submit("ETH/USD", market).unwrap().recv().unwrap();
+        assert!(matches!(rejected, SequencerResult::Rejected { .. }));
+
+        let books = executor.shutdown();
+        assert_eq!(books["BTC/USD"].best_ask(), Some(100));
+        assert_eq!(books["ETH/USD"].best_ask(), None);
+    }
+
+    #[test]
+    fn shutdown_drains_commands_from_many_producers() {
+        let executor = Arc::new(executor_with(
+            &["BTC/USD"],
+            ExecutorConfig::new().with_queue_capacity(4),
+        ));
+
+        let producers: Vec<_> = (0..4u128)
+            .map(|producer| {
+                let executor = Arc::clone(&executor);
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        // Dropping the receiver does not cancel the command.
+                        let _ = executor
+                            .submit("BTC/USD", limit(1_000 - producer * 100 - i, 1, Side::Buy))
+                            .expect("submit");
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().expect("producer");
+        }
+
+        let executor = Arc::into_inner(executor).expect("sole owner");
+        let books = executor.shutdown();
+        assert_eq!(books["BTC/USD"].get_bids().len(), 200);
+    }
+
+    #[test]
+    fn every_wait_strategy_executes_and_drains_in_order() {
+        for wait in [
+            WaitStrategy::BusySpin,
+            WaitStrategy::Yielding,
+            WaitStrategy::SpinThenPark { spins: 0 },
+        ] {
+            let config = ExecutorConfig::new()
+                .with_queue_capacity(3)
+                .with_wait_strategy(wait);
+            assert_eq!(config.wait_strategy(), wait);
+            let executor = executor_with(&["BTC/USD"], config);
+
+            for i in 0..100 {
+                let _ = executor
+                    .submit("BTC/USD", limit(1_000 + i, 1, Side::Sell))
+                    .expect("submit");
+            }
+            // Let a parking worker go idle before the next command wakes it.
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            let market = SequencerCommand::MarketOrder {
+                id: Id::new_uuid(),
+                quantity: 1,
+                side: Side::Buy,
+            };
+            let traded = executor.submit("BTC/USD", market).unwrap().recv().unwrap();
+            assert!(
+                matches!(traded, SequencerResult::TradeExecuted { .. }),
+                "{wait:?}"
+            );
+
+            let books = executor.shutdown();
+            assert_eq!(books["BTC/USD"].best_ask(), Some(1_001), "{wait:?}");
+            assert_eq!(books["BTC/USD"].get_asks().len(), 99, "{wait:?}");
+        }
+    }
+
+    #[test]
+    fn unknown_and_removed_symbols_are_not_found() {
+        let mut executor = executor_with(&["BTC/USD"], ExecutorConfig::new());
+        assert!(matches!(
+            executor.submit("XRP/USD", SequencerCommand::CancelAll),
+            Err(ManagerError::BookNotFound { symbol }) if symbol == "XRP/USD"
+        ));
+        assert!(matches!(
+            executor.spawn_book(OrderBook::new("BTC/USD")),
+            Err(ManagerError::BookAlreadyExists { .. })
+        ));
+
+        let _ = executor
+            .submit("BTC/USD", limit(100, 10, Side::Buy))
+            .expect("submit");
+        let book = executor.remove_book("BTC/USD").expect("book");
+        assert_eq!(book.best_bid(), Some(100));
+        assert!(!executor.has_book("BTC/USD"));
+        assert!(matches!(
+            executor.submit("BTC/USD", SequencerCommand::CancelAll),
+            Err(ManagerError::BookNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn worker_start_hook_receives_round_robin_cores() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let hook_seen = Arc::clone(&seen);
+        let config = ExecutorConfig::new()
+            .with_core_ids(vec![3])
+            .with_worker_start_hook(move |symbol, core| {
+                hook_seen.lock().unwrap().push((symbol.to_string(), core));
+            });
+        let executor = executor_with(&["BTC/USD", "ETH/USD"], config);
+        drop(executor.shutdown());
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(
+            seen,
+            vec![
+                ("BTC/USD".to_string(), Some(3)),
+                ("ETH/USD".to_string(), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn spawn_book_with_builds_the_book_on_the_worker_after_the_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let hook_events = Arc::clone(&events);
+        let config = ExecutorConfig::new().with_worker_start_hook(move |_, _| {
+            hook_events
+                .lock()
+                .unwrap()
+                .push(("hook", std::thread::current().id()));
+        });
+        let mut executor = BookExecutor::<()>::new(config);
+        let build_events = Arc::clone(&events);
+        executor
+            .spawn_book_with("BTC/USD", move || {
+                build_events
+                    .lock()
+                    .unwrap()
+                    .push(("build", std::thread::current().id()));
+                OrderBook::new("BTC/USD")
+            })
+            .expect("spawn book");
+        assert!(matches!(
+            executor.spawn_book_with("BTC/USD", || OrderBook::new("BTC/USD")),
+            Err(ManagerError::BookAlreadyExists { .. })
+        ));
+
+        executor
+            .submit("BTC/USD", limit(100, 10, Side::Buy))
+            .expect("submit")
+            .recv()
+            .expect("result");
+        let books = executor.shutdown();
+        assert_eq!(books["BTC/USD"].best_bid(), Some(100));
+
+        let events = events.lock().unwrap().clone();
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].0, events[1].0), ("hook", "build"));
+        assert_eq!(events[0].1, events[1].1);
+        assert_ne!(events[0].1, std::thread::current().id());
+    }
+
+    #[test]
+    fn manager_converts_into_executor_with_resting_state() {
+        let mut manager: BookManagerStd<()> = BookManagerStd::new();
+        manager.add_book("BTC/USD").expect("add book");
+        manager
+            .get_book("BTC/USD")
+            .expect("book")
+            .add_limit_order(Id::new_uuid(), 100, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("rest");
+
+        let executor = manager
+            .into_executor(ExecutorConfig::new())
+            .expect("executor");
+        assert_eq!(executor.symbols(), vec!["BTC/USD".to_string()]);
+        let cancelled = executor
+            .submit("BTC/USD", SequencerCommand::CancelAll)
+            .unwrap()
+            .recv()
+            .unwrap();
+        match cancelled {
+            SequencerResult::MassCancelled { result } => assert_eq!(result.cancelled_count(), 1),
+            other => panic!("expected mass cancel, got {other:?}"),
+        }
+    }
+
+    /// Journal that only reports entries durable once flushed, like one
+    /// that batches its syncs.
+    #[derive(Default)]
+    struct BufferedJournal {
+        inner: InMemoryJournal<()>,
+        synced: Mutex<Option<u64>>,
+    }
+
+    impl Journal<()> for BufferedJournal {
+        fn append(&self, event: &SequencerEvent<()>) -> Result<(), JournalError> {
+            self.inner.append(event)
+        }
+
+        fn read_from(&self, sequence: u64) -> Result<JournalReadIter<()>, JournalError> {
+            self.inner.read_from(sequence)
+        }
+
+        fn last_sequence(&self) -> Option<u64> {
+            self.inner.last_sequence()
+        }
+
+        fn verify_integrity(&self) -> Result<(), JournalError> {
+            self.inner.verify_integrity()
+        }
+
+        fn flush(&self) -> Result<(), JournalError> {
+            *self.synced.lock().expect("synced") = self.inner.last_sequence();
+            Ok(())
+        }
+
+        fn durable_sequence(&self) -> Option<u64> {
+            *self.synced.lock().expect("synced")
+        }
+    }
+
+    #[test]
+    fn journaled_executor_numbers_commands_across_books() {
+        let journal = Arc::new(InMemoryJournal::<()>::new());
+        let mut executor = BookExecutor::with_journal(ExecutorConfig::new(), journal.clone());
+        for symbol in ["BTC/USD", "ETH/USD"] {
+            executor
+                .spawn_book(OrderBook::new(symbol))
+                .expect("spawn book");
+        }
+
+        let mut sequences = Vec::new();
+        for symbol in ["BTC/USD", "ETH/USD", "BTC/USD"] {
+            let sequenced = executor
+                .submit_sequenced(symbol, limit(100, 10, Side::Sell))
+                .expect("submit")
+                .recv()
+                .expect("reply")
+                .expect("journaled");
+            assert!(matches!(
+                sequenced.result,
+                SequencerResult::OrderAdded { .. }
+            ));
+            sequences.push(sequenced.sequence_num);
+        }
+        // Plain submits are journaled too, so the numbering stays gap-free.
+        executor
+            .submit("ETH/USD", SequencerCommand::CancelAll)
+            .expect("submit")
+            .recv()
+            .expect("result");
+        assert_eq!(sequences, [0, 1, 2]);
+        assert_eq!(journal.last_sequence(), Some(3));
+        assert_eq!(executor.durable_sequence(), Some(3));
+        drop(executor);
+
+        // Each command is journaled under its book's symbol.
+        let eth: Vec<_> = journal
+            .read_filtered(0, JournalFilter::new().symbol("ETH/USD"))
+            .expect("read")
+            .map(|entry| entry.expect("entry").event.sequence_num)
+            .collect();
+        assert_eq!(eth, [1, 3]);
+
+        // A restarted executor continues after the journal's tail.
+        let mut executor = BookExecutor::with_journal(ExecutorConfig::new(), journal.clone());
+        executor
+            .spawn_book(OrderBook::new("BTC/USD"))
+            .expect("spawn book");
+        let sequenced = executor
+            .submit_sequenced("BTC/USD", SequencerCommand::CancelAll)
+            .expect("submit")
+            .recv()
+            .expect("reply")
+            .expect("journaled");
+        assert_eq!(sequenced.sequence_num, 4);
+
+        let plain = executor_with(&["BTC/USD"], ExecutorConfig::new());
+        assert!(matches!(
+            plain.submit_sequenced("BTC/USD", SequencerCommand::CancelAll),
+            Err(ManagerError::JournalUnavailable)
+        ));
+        assert_eq!(plain.durable_sequence(), None);
+    }
+
+    #[tokio::test]
+    async fn await_sequence_durable_resolves_once_the_journal_syncs() {
+        let journal = Arc::new(BufferedJournal::default());
+        let mut executor = BookExecutor::with_journal(ExecutorConfig::new(), journal);
+        executor
+            .spawn_book(OrderBook::new("BTC/USD"))
+            .expect("spawn book");
+        let sequenced = executor
+            .submit_sequenced("BTC/USD", limit(100, 10, Side::Sell))
+            .expect("submit")
+            .recv()
+            .expect("reply")
+            .expect("journaled");
+
+        let mut durable = tokio::spawn(executor.await_sequence_durable(sequenced.sequence_num));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), &mut durable)
+                .await
+                .is_err(),
+            "appended but not yet synced"
+        );
+        executor.flush_journal().expect("flush");
+        durable
+            .await
+            .expect("task")
+            .expect("durable after the flush");
+        assert_eq!(executor.durable_sequence(), Some(0));
+
+        let plain = executor_with(&["BTC/USD"], ExecutorConfig::new());
+        assert!(matches!(
+            plain.await_sequence_durable(0).await,
+            Err(ManagerError::JournalUnavailable)
+        ));
+    }
+
+    /// Journal whose appends wait while the test holds its gate, like one
+    /// stalled on a slow disk.
+    #[derive(Default)]
+    struct GatedJournal {
+        inner: InMemoryJournal<()>,
+        gate: Mutex<()>,
+    }
+
+    impl Journal<()> for GatedJournal {
+        fn append(&self, event: &SequencerEvent<()>) -> Result<(), JournalError> {
+            let _open = self.gate.lock().expect("gate");
+            self.inner.append(event)
+        }
+
+        fn read_from(&self, sequence: u64) -> Result<JournalReadIter<()>, JournalError> {
+            self.inner.read_from(sequence)
+        }
+
+        fn last_sequence(&self) -> Option<u64> {
+            self.inner.last_sequence()
+        }
+
+        fn verify_integrity(&self) -> Result<(), JournalError> {
+            self.inner.verify_integrity()
+        }
+    }
+
+    #[test]
+    fn workers_keep_executing_while_the_journal_is_stalled() {
+        let journal = Arc::new(GatedJournal::default());
+        let mut executor = BookExecutor::with_journal(ExecutorConfig::new(), journal.clone());
+        let book = OrderBook::new("BTC/USD");
+        let (trades_tx, trades) = std::sync::mpsc::channel();
+        book.set_trade_listener(Arc::new(move |_trade: &orderbook_rs::TradeResult| {
+            let _ = trades_tx.send(());
+        }));
+        executor.spawn_book(book).expect("spawn book");
+
+        let gate = journal.gate.lock().expect("gate");
+        let resting = executor
+            .submit_sequenced("BTC/USD", limit(100, 10, Side::Sell))
+            .expect("submit");
+        let crossing = executor
+            .submit_sequenced("BTC/USD", limit(100, 10, Side::Buy))
+            .expect("submit");
+        // The worker executes the crossing order although the resting
+        // order's append is still blocked.
+        trades
+            .recv_timeout(Duration::from_secs(5))
+            .expect("trade executed while the journal is stalled");
+        assert_eq!(journal.last_sequence(), None);
+
+        drop(gate);
+        let sequences: Vec<u64> = [resting, crossing]
+            .into_iter()
+            .map(|reply| {
+                reply
+                    .recv()
+                    .expect("reply")
+                    .expect("journaled")
+                    .sequence_num
+            })
+            .collect();
+        assert_eq!(sequences, [0, 1]);
+        assert_eq!(journal.last_sequence(), Some(1));
+    }
+}
@@ -0,0 +1,86 @@
+//! Integration tests for the per-book cumulative counters (`OrderBook::stats`).
+
+#[cfg(test)]
+mod tests_book_stats {
+    use super::super::common::rest;
+    use orderbook_rs::OrderBook;
+    use orderbook_rs::orderbook::reject_reason::RejectReason;
+    use pricelevel::{Id, Side, TimeInForce};
+
+    #[test]
+    fn counts_adds_cancels_fills_and_trades() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 100, 10, Side::Sell);
+        rest(&book, 102, 10, Side::Sell);
+        let cancelled = rest(&book, 95, 10, Side::Buy);
+        book.cancel_order(cancelled).expect("cancel");
+
+        // Crosses both asks: the 100 level fills completely, 102 partially.
+        book.add_limit_order(Id::new_uuid(), 102, 15, Side::Buy, TimeInForce::Ioc, None)
+            .expect("taker");
+
+        let stats = book.stats();
+        assert_eq!(stats.orders_added, 4);
+        assert_eq!(stats.orders_cancelled, 1);
+        assert_eq!(stats.orders_filled, 2);
+        assert_eq!(stats.trades, 2);
+        assert_eq!(stats.volume, 15);
+        assert_eq!(stats.notional_volume, 100 * 10 + 102 * 5);
+        assert_eq!(stats.highest_trade_price, Some(102));
+        assert_eq!(stats.lowest_trade_price, Some(100));
+        assert_eq!(stats.rejects_total, 0);
+    }
+
+    #[test]
+    fn market_orders_count_trades() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 100, 10, Side::Buy);
+        book.match_market_order(Id::new_uuid(), 4, Side::Sell)
+            .expect("market");
+
+        let stats = book.stats();
+        assert_eq!(stats.trades, 1);
+        assert_eq!(stats.volume, 4);
+        assert_eq!(stats.orders_filled, 0);
+    }
+
+    #[test]
+    fn rejects_are_counted_once_by_reason() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 100, 10, Side::Sell);
+        assert!(
+            book.add_post_only_order(Id::new_uuid(), 101, 1, Side::Buy, TimeInForce::Gtc, None)
+                .is_err()
+        );
+        book.engage_kill_switch();
+        assert!(
+            book.add_limit_order(Id::new_uuid(), 90, 1, Side::Buy, TimeInForce::Gtc, None)
+                .is_err()
+        );
+        book.release_kill_switch();
+
+        let stats = book.stats();
+        assert_eq!(stats.rejects_total, 2);
+        assert_eq!(stats.rejects_for(RejectReason::PostOnlyWouldCross), 1);
+        assert_eq!(stats.rejects_for(RejectReason::KillSwitchActive), 1);
+    }
+
+    #[test]
+    fn reset_zeroes_counters_and_stats_export_as_json() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 100, 10, Side::Sell);
+        book.match_market_order(Id::new_uuid(), 10, Side::Buy)
+            .expect("market");
+        assert_eq!(book.stats().trades, 1);
+
+        book.reset_stats();
+        let stats = book.stats();
+        assert_eq!(stats.orders_added, 0);
+        assert_eq!(stats.trades, 0);
+        assert_eq!(stats.highest_trade_price, None);
+
+        let json = serde_json::to_string(&stats).expect("serialize");
+        let decoded: orderbook_rs::BookStats = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded, stats);
+    }
+}
@@ -0,0 +1,173 @@
+//! Integration tests for `OrderBookBuilder`.
+
+#[cfg(test)]
+mod tests_builder {
+    use orderbook_rs::{
+        Clock, FeeSchedule, OrderBook, OrderBookError, PriceScale, RateLimitConfig, STPMode,
+        StubClock, TokenBucketConfig, TradeListener, TradeResult,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn assert_invalid(result: Result<OrderBook<()>, OrderBookError>) {
+        assert!(matches!(
+            result,
+            Err(OrderBookError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[test]
+    fn default_builder_matches_new() {
+        let book = OrderBook::<()>::builder("TEST").build().expect("build");
+        assert_eq!(book.symbol(), "TEST");
+        assert_eq!(book.tick_size(), None);
+        assert_eq!(book.lot_size(), None);
+        assert_eq!(book.stp_mode(), STPMode::None);
+        assert!(book.fee_schedule().is_none());
+        assert!(book.trade_listener().is_none());
+    }
+
+    #[test]
+    fn applies_every_option_in_one_chain() {
+        let trades = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&trades);
+        let listener: TradeListener = Arc::new(move |_: &TradeResult| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        let book = OrderBook::<()>::builder("TEST")
+            .clock(Arc::new(StubClock::new()) as Arc<dyn Clock>)
+            .order_capacity(1_024)
+            .order_index_shards(8)
+            .trade_listener(listener)
+            .price_level_listener(Arc::new(|_| {}))
+            .tick_size(5)
+            .lot_size(10)
+            .order_size_range(Some(10), Some(1_000))
+            .order_notional_range(Some(100), Some(1_000_000))
+            .stp_mode(STPMode::CancelTaker)
+            .fee_schedule(FeeSchedule::new(-1, 3))
+            .price_scale(PriceScale::new(2, 0))
+            .rate_limit_config(RateLimitConfig::new().with_global(TokenBucketConfig::new(100, 100)))
+            .duplicate_order_id_window(1_000)
+            .market_close_timestamp(5_000)
+            .level_pool_max_size(4)
+            .level_shards(50, 4)
+            .build()
+            .expect("build");
+
+        assert_eq!(book.tick_size(), Some(5));
+        assert_eq!(book.lot_size(), Some(10));
+        assert_eq!(book.min_order_size(), Some(10));
+        assert_eq!(book.max_order_size(), Some(1_000));
+        assert_eq!(book.min_order_notional(), Some(100));
+        assert_eq!(book.max_order_notional(), Some(1_000_000));
+        assert_eq!(book.stp_mode(), STPMode::CancelTaker);
+        assert_eq!(book.fee_schedule(), Some(FeeSchedule::new(-1, 3)));
+        assert_eq!(book.price_scale(), PriceScale::new(2, 0));
+        assert!(book.rate_limit_config().is_some());
+        assert_eq!(book.duplicate_order_id_window(), Some(1_000));
+        assert!(book.order_index_capacity() >= 1_024);
+
+        let maker = Hash32::new([1u8; 32]);
+        let taker = Hash32::new([2u8; 32]);
+        book.add_limit_order_with_user(
+            Id::new_uuid(),
+            100,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            maker,
+            None,
+        )
+        .expect("rest");
+        assert_eq!(book.level_shard_occupancy(Side::Sell), vec![0, 0, 1, 0]);
+        assert!(matches!(
+            book.add_limit_order_with_user(
+                Id::new_uuid(),
+                102,
+                10,
+                Side::Sell,
+                TimeInForce::Gtc,
+                maker,
+                None
+            ),
+            Err(OrderBookError::InvalidTickSize { .. })
+        ));
+        book.submit_market_order_with_user(Id::new_uuid(), 10, Side::Buy, taker)
+            .expect("match");
+        assert_eq!(trades.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn rejects_empty_symbol() {
+        assert_invalid(OrderBook::<()>::builder("").build());
+    }
+
+    #[test]
+    fn rejects_zero_tick_and_lot_size() {
+        assert_invalid(OrderBook::<()>::builder("TEST").tick_size(0).build());
+        assert_invalid(OrderBook::<()>::builder("TEST").lot_size(0).build());
+    }
+
+    #[test]
+    fn rejects_inverted_ranges() {
+        assert_invalid(
+            OrderBook::<()>::builder("TEST")
+                .order_size_range(Some(10), Some(5))
+                .build(),
+        );
+        assert_invalid(
+            OrderBook::<()>::builder("TEST")
+                .order_notional_range(Some(10), Some(5))
+                .build(),
+        );
+        assert!(
+            OrderBook::<()>::builder("TEST")
+                .order_size_range(Some(5), Some(5))
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_negative_taker_fee_and_zero_market_close() {
+        assert_invalid(
+            OrderBook::<()>::builder("TEST")
+                .fee_schedule(FeeSchedule::new(0, -1))
+                .build(),
+        );
+        assert_invalid(
+            OrderBook::<()>::builder("TEST")
+                .market_close_timestamp(0)
+                .build(),
+        );
+    }
+
+    #[test]
+    fn oversized_shard_count_is_clamped() {
+        let book = OrderBook::<()>::builder("TEST")
+            .order_capacity(16)
+            .order_index_shards(usize::MAX)
+            .build()
+            .expect("build");
+        let id = Id::new_uuid();
+        book.add_limit_order(id, 100, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("rest order");
+        assert!(book.cancel_order(id).expect("cancel").is_some());
+    }
+
+    #[test]
+    fn invalid_configuration_displays_reason() {
+        let err = OrderBook::<()>::builder("TEST")
+            .tick_size(0)
+            .build()
+            .err()
+            .expect("rejected");
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: tick size must be non-zero"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Integration tests for heartbeat-driven cancel-on-disconnect: expiry on
+//! the next gated operation and on a timer, keep-alive and logout, and
+//! basket coverage through the book manager.
+
+#[cfg(test)]
+mod tests_cancel_on_disconnect {
+    use super::super::common::rest_as;
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::{
+        CancelReason, Clock, DisconnectEvent, OrderBook, OrderBookError, OrderStateTracker,
+        OrderStatus, RejectCode, ReplayClock,
+    };
+    use pricelevel::{Hash32, Id, Side};
+    use std::sync::{Arc, Mutex};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    fn clocked_book() -> (OrderBook<()>, Arc<ReplayClock>) {
+        let clock = Arc::new(ReplayClock::new(0));
+        let mut book = OrderBook::<()>::with_clock("TEST", Arc::clone(&clock) as Arc<dyn Clock>);
+        book.set_order_state_tracker(OrderStateTracker::new());
+        (book, clock)
+    }
+
+    #[test]
+    fn missed_heartbeat_cancels_on_the_next_operation() {
+        let (book, clock) = clocked_book();
+        let events: Arc<Mutex<Vec<DisconnectEvent>>> = Arc::default();
+        let sink = Arc::clone(&events);
+        book.set_disconnect_listener(Arc::new(move |event: &DisconnectEvent| {
+            sink.lock().expect("events").push(event.clone());
+        }));
+
+        book.register_heartbeat(user(1), 1_000).expect("register");
+        rest_as(&book, Id::from_u64(1), 100, 5, Side::Buy, user(1));
+        rest_as(&book, Id::from_u64(2), 99, 5, Side::Buy, user(1));
+        rest_as(&book, Id::from_u64(3), 98, 5, Side::Buy, user(2));
+        assert_eq!(book.heartbeat_deadline(user(1)), Some(1_000));
+
+        clock.advance_to(999);
+        rest_as(&book, Id::from_u64(4), 97, 5, Side::Buy, user(2));
+        assert!(book.get_order(Id::from_u64(1)).is_some());
+
+        clock.advance_to(1_200);
+        rest_as(&book, Id::from_u64(5), 96, 5, Side::Buy, user(2));
+        assert!(book.get_order(Id::from_u64(1)).is_none());
+        assert!(book.get_order(Id::from_u64(2)).is_none());
+        assert_eq!(book.best_bid(), Some(98));
+        assert!(matches!(
+            book.order_status(Id::from_u64(1)),
+            Some(OrderStatus::Cancelled {
+                reason: CancelReason::Disconnected,
+                ..
+            })
+        ));
+
+        let events = events.lock().expect("events");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].symbol, "TEST");
+        assert_eq!(events[0].user_id, user(1));
+        assert_eq!(events[0].deadline_ms, 1_000);
+        assert_eq!(events[0].timestamp_ms, 1_200);
+        assert_eq!(
+            events[0].cancelled.cancelled_order_ids(),
+            [Id::from_u64(1), Id::from_u64(2)]
+        );
+        assert_eq!(book.heartbeat_deadline(user(1)), None);
+        assert!(!book.heartbeat(user(1)));
+    }
+
+    #[test]
+    fn heartbeats_keep_the_session_alive_until_logout() {
+        let (book, clock) = clocked_book();
+        let err = book.register_heartbeat(user(1), 0).expect_err("zero TTL");
+        assert!(matches!(err, OrderBookError::ZeroHeartbeatTtl { user_id } if user_id == user(1)));
+        assert_eq!(err.code(), RejectCode::ZeroHeartbeatTtl);
+        assert!(!book.heartbeat(user(1)));
+
+        book.register_heartbeat(user(1), 1_000).expect("register");
+        rest_as(&book, Id::from_u64(1), 100, 5, Side::Buy, user(1));
+        for now in [800, 1_600, 2_400] {
+            clock.advance_to(now);
+            assert!(book.heartbeat(user(1)));
+        }
+        assert_eq!(book.heartbeat_deadline(user(1)), Some(3_400));
+        assert!(book.expire_heartbeats().is_empty());
+
+        // An orderly logout leaves the orders resting.
+        assert!(book.unregister_heartbeat(user(1)));
+        assert!(!book.unregister_heartbeat(user(1)));
+        clock.advance_to(10_000);
+        assert!(book.expire_heartbeats().is_empty());
+        assert!(book.get_order(Id::from_u64(1)).is_some());
+    }
+
+    #[test]
+    fn one_session_covers_every_managed_book() {
+        let clock = Arc::new(ReplayClock::new(0));
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        for symbol in ["BTC/USD", "ETH/USD"] {
+            mgr.add_book(symbol).expect("add book");
+            mgr.get_book_mut(symbol)
+                .expect("book")
+                .set_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        }
+        for (id, symbol) in [(1, "BTC/USD"), (2, "ETH/USD")] {
+            let book = mgr.get_book(symbol).expect("book");
+            rest_as(book, Id::from_u64(id), 100, 5, Side::Buy, user(1));
+            rest_as(book, Id::from_u64(id + 10), 99, 5, Side::Buy, user(2));
+        }
+
+        mgr.register_heartbeat_across_books(user(1), 500)
+            .expect("register");
+        clock.advance_to(400);
+        assert_eq!(mgr.heartbeat_across_books(user(1)), 2);
+        assert!(mgr.expire_heartbeats_across_books().is_empty());
+
+        clock.advance_to(900);
+        let disconnects = mgr.expire_heartbeats_across_books();
+        assert_eq!(disconnects.len(), 2);
+        assert_eq!(
+            disconnects["BTC/USD"][0].cancelled.cancelled_order_ids(),
+            [Id::from_u64(1)]
+        );
+        for symbol in ["BTC/USD", "ETH/USD"] {
+            assert_eq!(mgr.get_book(symbol).expect("book").best_bid(), Some(99));
+        }
+        assert_eq!(mgr.heartbeat_across_books(user(1)), 0);
+        assert_eq!(mgr.unregister_heartbeat_across_books(user(1)), 0);
+    }
+}
@@ -0,0 +1,59 @@
+//! Integration tests for warm-start preallocation: pre-sized indexes do not
+//! grow while the book fills to its hints, and occupancy is reported
+//! against capacity.
+
+#[cfg(test)]
+mod tests_capacity {
+    use orderbook_rs::OrderBook;
+    use pricelevel::{Id, Side, TimeInForce};
+
+    const LEVELS: usize = 64;
+    const ORDERS: usize = 2_048;
+
+    fn fill(book: &OrderBook<()>) {
+        for i in 0..ORDERS as u64 {
+            let level = u128::from(i / 2 % LEVELS as u64);
+            let (price, side) = if i % 2 == 0 {
+                (1_000 - level, Side::Buy)
+            } else {
+                (1_001 + level, Side::Sell)
+            };
+            book.add_limit_order(Id::from_u64(i), price, 1, side, TimeInForce::Gtc, None)
+                .expect("rest");
+        }
+    }
+
+    #[test]
+    fn presized_indexes_do_not_grow_up_to_the_hints() {
+        let book = OrderBook::<()>::with_capacity("TEST", LEVELS, ORDERS);
+        let before = book.capacity_stats();
+        assert!(before.order_index_capacity >= ORDERS);
+        assert!(before.level_index_capacity >= LEVELS);
+        assert_eq!(before.order_index_load_factor(), 0.0);
+
+        fill(&book);
+        let after = book.capacity_stats();
+        assert_eq!(after.order_index_capacity, before.order_index_capacity);
+        assert_eq!(after.level_index_capacity, before.level_index_capacity);
+        assert_eq!(after.resting_orders, ORDERS);
+        assert_eq!((after.bid_levels, after.ask_levels), (LEVELS, LEVELS));
+        assert!(after.order_index_load_factor() > 0.0);
+        assert!(after.order_index_load_factor() <= 1.0);
+        assert!(after.level_index_load_factor() <= 1.0);
+    }
+
+    #[test]
+    fn builder_capacity_matches_the_constructor() {
+        let book = OrderBook::<()>::builder("TEST")
+            .capacity(LEVELS, ORDERS)
+            .build()
+            .expect("build");
+        let stats = book.capacity_stats();
+        assert!(stats.order_index_capacity >= ORDERS);
+        assert!(stats.level_index_capacity >= LEVELS);
+
+        let unsized_book = OrderBook::<()>::new("TEST");
+        assert!(unsized_book.capacity_stats().level_index_capacity < LEVELS);
+        unsized_book.warm_matching_pool();
+    }
+}
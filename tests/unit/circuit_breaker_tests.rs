@@ -0,0 +1,148 @@
+//! Integration tests for the price velocity circuit breaker: tripping on
+//! the trade window and on a reference price, the halted and auction
+//! phases, and resumption after the cool-down.
+
+#[cfg(test)]
+mod tests_circuit_breaker {
+    use orderbook_rs::{
+        BookConfig, CircuitBreakerAction, CircuitBreakerConfig, CircuitBreakerEvent, Clock,
+        ExternalPriceSource, OrderBook, OrderBookError, ReplayClock, SessionPhase,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    const START: u64 = 1_000_000;
+
+    type Events = Arc<Mutex<Vec<CircuitBreakerEvent>>>;
+
+    fn book_with(breaker: CircuitBreakerConfig) -> (OrderBook<()>, Arc<ReplayClock>, Events) {
+        let clock = Arc::new(ReplayClock::new(START));
+        let book = OrderBook::<()>::with_clock("TEST", Arc::clone(&clock) as Arc<dyn Clock>);
+        book.reload_config(BookConfig::new().with_circuit_breaker(breaker))
+            .expect("valid breaker");
+        let events: Events = Arc::default();
+        let sink = Arc::clone(&events);
+        book.set_circuit_breaker_listener(Arc::new(move |event: &CircuitBreakerEvent| {
+            sink.lock().expect("events").push(event.clone());
+        }));
+        (book, clock, events)
+    }
+
+    fn ask(book: &OrderBook<()>, id: u64, price: u128) {
+        book.add_limit_order(
+            Id::from_u64(id),
+            price,
+            1,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask rests");
+    }
+
+    #[test]
+    fn fast_move_halts_until_the_cool_down_ends() {
+        let (book, clock, events) = book_with(CircuitBreakerConfig::new(500, 1_000, 5_000));
+        ask(&book, 1, 100);
+        ask(&book, 2, 110);
+        ask(&book, 3, 111);
+
+        book.submit_market_order(Id::from_u64(10), 1, Side::Buy)
+            .expect("first trade");
+        assert_eq!(book.trading_phase(), None);
+        book.submit_market_order(Id::from_u64(11), 1, Side::Buy)
+            .expect("the tripping trade executes");
+        assert_eq!(book.last_trade_price(), Some(110));
+        assert_eq!(book.trading_phase(), Some(SessionPhase::Halted));
+        assert_eq!(
+            events.lock().expect("events").as_slice(),
+            [CircuitBreakerEvent::Tripped {
+                symbol: "TEST".to_string(),
+                action: CircuitBreakerAction::Halt,
+                anchor_price: 100,
+                trigger_price: 110,
+                timestamp_ms: START,
+                resumes_at_ms: START + 5_000,
+            }]
+        );
+
+        assert!(matches!(
+            book.submit_market_order(Id::from_u64(12), 1, Side::Buy),
+            Err(OrderBookError::MarketClosed {
+                phase: SessionPhase::Halted
+            })
+        ));
+        assert!(matches!(
+            book.add_limit_order(Id::from_u64(13), 90, 1, Side::Buy, TimeInForce::Gtc, None),
+            Err(OrderBookError::MarketClosed { .. })
+        ));
+        assert!(
+            book.cancel_order(Id::from_u64(3))
+                .expect("cancel")
+                .is_some()
+        );
+
+        clock.advance_to(START + 5_000);
+        book.add_limit_order(Id::from_u64(14), 90, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("resumed");
+        assert_eq!(book.trading_phase(), None);
+        assert!(matches!(
+            events.lock().expect("events").last(),
+            Some(CircuitBreakerEvent::Resumed { timestamp_ms, .. }) if *timestamp_ms == START + 5_000
+        ));
+    }
+
+    #[test]
+    fn moves_spread_beyond_the_window_do_not_trip() {
+        let (book, clock, events) = book_with(CircuitBreakerConfig::new(500, 1_000, 5_000));
+        ask(&book, 1, 100);
+        ask(&book, 2, 106);
+        ask(&book, 3, 112);
+        for (id, at) in [(10, START), (11, START + 1_001), (12, START + 2_002)] {
+            clock.advance_to(at);
+            book.submit_market_order(Id::from_u64(id), 1, Side::Buy)
+                .expect("trade");
+        }
+        assert_eq!(book.last_trade_price(), Some(112));
+        assert_eq!(book.trading_phase(), None);
+        assert!(events.lock().expect("events").is_empty());
+    }
+
+    #[test]
+    fn reference_deviation_switches_to_an_auction() {
+        let (book, _clock, events) = book_with(
+            CircuitBreakerConfig::new(500, 1_000, 5_000)
+                .with_action(CircuitBreakerAction::Auction)
+                .with_reference(ExternalPriceSource::Index),
+        );
+        book.set_reference_price(ExternalPriceSource::Index, 100, START);
+        ask(&book, 1, 107);
+        ask(&book, 2, 108);
+
+        book.submit_market_order(Id::from_u64(10), 1, Side::Buy)
+            .expect("trade away from the index");
+        assert_eq!(book.trading_phase(), Some(SessionPhase::VolatilityAuction));
+        assert!(matches!(
+            events.lock().expect("events").first(),
+            Some(CircuitBreakerEvent::Tripped {
+                anchor_price: 100,
+                trigger_price: 107,
+                ..
+            })
+        ));
+
+        book.add_limit_order(Id::from_u64(11), 100, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("non-crossing bid rests");
+        assert!(matches!(
+            book.add_limit_order(Id::from_u64(12), 108, 1, Side::Buy, TimeInForce::Gtc, None),
+            Err(OrderBookError::MarketClosed {
+                phase: SessionPhase::VolatilityAuction
+            })
+        ));
+
+        assert!(book.reset_circuit_breaker());
+        assert!(!book.reset_circuit_breaker());
+        assert_eq!(book.trading_phase(), None);
+        assert_eq!(events.lock().expect("events").len(), 2);
+    }
+}
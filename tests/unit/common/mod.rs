@@ -4,4 +4,47 @@
 //! issues (#57 byte-identical replay widening, #52 engine_seq monotonicity,
 //! etc.) need shared machinery.
 
+pub mod reference_book;
 pub mod strategies;
+pub mod stress;
+
+use orderbook_rs::OrderBook;
+use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+/// Rest a GTC limit order under a fresh id and return the id.
+pub fn rest(book: &OrderBook<()>, price: u128, quantity: u64, side: Side) -> Id {
+    rest_with_tif(book, price, quantity, side, TimeInForce::Gtc)
+}
+
+/// [`rest`] with `time_in_force` instead of GTC.
+pub fn rest_with_tif(
+    book: &OrderBook<()>,
+    price: u128,
+    quantity: u64,
+    side: Side,
+    time_in_force: TimeInForce,
+) -> Id {
+    let id = Id::new_uuid();
+    book.add_limit_order(id, price, quantity, side, time_in_force, None)
+        .expect("rest order");
+    id
+}
+
+/// [`rest`] owned by `user`.
+pub fn rest_for(book: &OrderBook<()>, price: u128, quantity: u64, side: Side, user: Hash32) -> Id {
+    rest_as(book, Id::new_uuid(), price, quantity, side, user)
+}
+
+/// [`rest_for`] under the caller's `id`, for tests that assert on ids.
+pub fn rest_as(
+    book: &OrderBook<()>,
+    id: Id,
+    price: u128,
+    quantity: u64,
+    side: Side,
+    user: Hash32,
+) -> Id {
+    book.add_limit_order_with_user(id, price, quantity, side, TimeInForce::Gtc, user, None)
+        .expect("rest order");
+    id
+}
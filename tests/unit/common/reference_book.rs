@@ -0,0 +1,262 @@
+//! A deliberately naive, single-threaded reference order book.
+//!
+//! [`ReferenceBook`] implements price-time priority with two
+//! `BTreeMap<price, VecDeque<order>>` sides and nothing else: no caches,
+//! no pools, no atomics. It is the oracle the `model_check` properties
+//! compare `OrderBook` against, so it favours obviousness over speed.
+//!
+//! Supported operations and their contract (mirroring the engine):
+//!
+//! - GTC limit: match the opposite side at prices no worse than the limit,
+//!   rest the remainder at the back of its level.
+//! - IOC limit: match, then reject with `InsufficientLiquidity` if any
+//!   quantity is left; fills made before the rejection stand.
+//! - FOK limit: reject with `InsufficientLiquidity` and no fills unless the
+//!   whole quantity can fill within the limit.
+//! - Market: match without a limit; reject with `InsufficientLiquidity`
+//!   only when nothing fills, a partial fill is accepted.
+//! - Cancel: remove the order if it rests.
+//! - Quantity update: a decrease (or no change) keeps queue position, an
+//!   increase moves the order to the back of its level.
+//!
+//! Downstream contributors adding an order type extend [`ReferenceBook`]
+//! with the plain-language version of its rules and add an operation to
+//! the `model_check` generator; the comparison harness needs no change.
+
+use orderbook_rs::RejectCode;
+use pricelevel::{Id, Side, TimeInForce};
+use std::collections::{BTreeMap, VecDeque};
+
+/// A resting order: id and remaining quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefOrder {
+    pub id: Id,
+    pub quantity: u64,
+}
+
+/// One fill, as reported by the engine's trade stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefFill {
+    pub taker: Id,
+    pub maker: Id,
+    pub price: u128,
+    pub quantity: u64,
+}
+
+/// Result of one operation. `NotFound` is a successful call that found
+/// no order (`Ok(None)` in the engine API).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefOutcome {
+    Accepted,
+    NotFound,
+    Rejected(RejectCode),
+}
+
+/// One side's levels, best first, each with its queue front to back.
+pub type RefDepth = Vec<(u128, Vec<RefOrder>)>;
+
+#[derive(Debug, Default, Clone)]
+pub struct ReferenceBook {
+    bids: BTreeMap<u128, VecDeque<RefOrder>>,
+    asks: BTreeMap<u128, VecDeque<RefOrder>>,
+}
+
+impl ReferenceBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submit a limit order.
+    pub fn limit(
+        &mut self,
+        id: Id,
+        side: Side,
+        price: u128,
+        quantity: u64,
+        time_in_force: TimeInForce,
+    ) -> (RefOutcome, Vec<RefFill>) {
+        if time_in_force == TimeInForce::Fok && self.available(side, Some(price)) < quantity {
+            return (
+                RefOutcome::Rejected(RejectCode::InsufficientLiquidity),
+                Vec::new(),
+            );
+        }
+        let (remaining, fills) = self.sweep(id, side, quantity, Some(price));
+        if remaining == 0 {
+            return (RefOutcome::Accepted, fills);
+        }
+        if time_in_force == TimeInForce::Gtc {
+            self.side_mut(side)
+                .entry(price)
+                .or_default()
+                .push_back(RefOrder {
+                    id,
+                    quantity: remaining,
+                });
+            (RefOutcome::Accepted, fills)
+        } else {
+            (
+                RefOutcome::Rejected(RejectCode::InsufficientLiquidity),
+                fills,
+            )
+        }
+    }
+
+    /// Submit a market order.
+    pub fn market(&mut self, id: Id, side: Side, quantity: u64) -> (RefOutcome, Vec<RefFill>) {
+        let (_, fills) = self.sweep(id, side, quantity, None);
+        if fills.is_empty() {
+            (
+                RefOutcome::Rejected(RejectCode::InsufficientLiquidity),
+                fills,
+            )
+        } else {
+            (RefOutcome::Accepted, fills)
+        }
+    }
+
+    /// Cancel a resting order.
+    pub fn cancel(&mut self, id: Id) -> RefOutcome {
+        match self.locate(id) {
+            Some((side, price, index)) => {
+                let levels = self.side_mut(side);
+                if let Some(queue) = levels.get_mut(&price) {
+                    queue.remove(index);
+                    if queue.is_empty() {
+                        levels.remove(&price);
+                    }
+                }
+                RefOutcome::Accepted
+            }
+            None => RefOutcome::NotFound,
+        }
+    }
+
+    /// Change a resting order's quantity.
+    pub fn update_quantity(&mut self, id: Id, quantity: u64) -> RefOutcome {
+        let Some((side, price, index)) = self.locate(id) else {
+            return RefOutcome::NotFound;
+        };
+        let Some(queue) = self.side_mut(side).get_mut(&price) else {
+            return RefOutcome::NotFound;
+        };
+        if quantity <= queue[index].quantity {
+            queue[index].quantity = quantity;
+        } else if let Some(mut order) = queue.remove(index) {
+            order.quantity = quantity;
+            queue.push_back(order);
+        }
+        RefOutcome::Accepted
+    }
+
+    /// Bids best (highest) first.
+    pub fn bids(&self) -> RefDepth {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(price, queue)| (*price, queue.iter().copied().collect()))
+            .collect()
+    }
+
+    /// Asks best (lowest) first.
+    pub fn asks(&self) -> RefDepth {
+        self.asks
+            .iter()
+            .map(|(price, queue)| (*price, queue.iter().copied().collect()))
+            .collect()
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut BTreeMap<u128, VecDeque<RefOrder>> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+
+    /// Opposite-side prices a taker on `side` may trade at, best first.
+    fn crossable(&self, side: Side, limit: Option<u128>) -> Vec<u128> {
+        match side {
+            Side::Buy => self
+                .asks
+                .keys()
+                .copied()
+                .take_while(|&price| limit.is_none_or(|limit| price <= limit))
+                .collect(),
+            Side::Sell => self
+                .bids
+                .keys()
+                .rev()
+                .copied()
+                .take_while(|&price| limit.is_none_or(|limit| price >= limit))
+                .collect(),
+        }
+    }
+
+    fn available(&self, side: Side, limit: Option<u128>) -> u64 {
+        let opposite = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        self.crossable(side, limit)
+            .iter()
+            .flat_map(|price| opposite[price].iter())
+            .map(|order| order.quantity)
+            .sum()
+    }
+
+    /// Match `quantity` against the opposite side; returns what is left.
+    fn sweep(
+        &mut self,
+        taker: Id,
+        side: Side,
+        mut quantity: u64,
+        limit: Option<u128>,
+    ) -> (u64, Vec<RefFill>) {
+        let mut fills = Vec::new();
+        let prices = self.crossable(side, limit);
+        let opposite = match side {
+            Side::Buy => &mut self.asks,
+            Side::Sell => &mut self.bids,
+        };
+        for price in prices {
+            let Some(queue) = opposite.get_mut(&price) else {
+                continue;
+            };
+            while quantity > 0 {
+                let Some(maker) = queue.front_mut() else {
+                    break;
+                };
+                let traded = quantity.min(maker.quantity);
+                fills.push(RefFill {
+                    taker,
+                    maker: maker.id,
+                    price,
+                    quantity: traded,
+                });
+                quantity -= traded;
+                maker.quantity -= traded;
+                if maker.quantity == 0 {
+                    queue.pop_front();
+                }
+            }
+            if queue.is_empty() {
+                opposite.remove(&price);
+            }
+            if quantity == 0 {
+                break;
+            }
+        }
+        (quantity, fills)
+    }
+
+    fn locate(&self, id: Id) -> Option<(Side, u128, usize)> {
+        for (side, levels) in [(Side::Buy, &self.bids), (Side::Sell, &self.asks)] {
+            for (price, queue) in levels {
+                if let Some(index) = queue.iter().position(|order| order.id == id) {
+                    return Some((side, *price, index));
+                }
+            }
+        }
+        None
+    }
+}
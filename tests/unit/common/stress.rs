@@ -0,0 +1,546 @@
+//! Reusable multi-threaded stress harness with invariant checkpoints.
+//!
+//! [`run_stress`] drives one shared `OrderBook<()>` from three kinds of
+//! worker threads for a configured wall-clock duration:
+//!
+//! - **makers** post passive GTC limit orders on a narrow band either
+//!   side of a mid price;
+//! - **takers** send IOC limits and market orders through the touch;
+//! - **cancellers** cancel orders recently posted by the makers.
+//!
+//! Makers, cancellers and the matching sweep all run concurrently on the
+//! same levels. Takers are serialized with one another: a `PriceLevel`
+//! supports a single matcher at a time, and the book leaves that to the
+//! caller (a sequencer, a per-book thread) just like the ordering of two
+//! crossing submissions.
+//!
+//! Every [`StressConfig::check_interval`] the checker takes a pause gate
+//! exclusively, so the book is quiescent, and verifies:
+//!
+//! 1. `OrderBook::audit()` reports no structural violation;
+//! 2. quantity is conserved: everything the makers added is either still
+//!    resting, executed, or cancelled. IOC and market takers never rest,
+//!    so each fill consumes its quantity from exactly one maker.
+//!
+//! A passing checkpoint snapshots the book. When a checkpoint fails, the
+//! operations since the last passing one are replayed sequentially (in
+//! the order they took their tickets) on a book restored from that
+//! snapshot. If the replay reproduces a violation the window is shrunk
+//! with [`minimize`] and the [`StressFailure`] carries the shortest
+//! failing trace; a violation that needs real concurrency to show up is
+//! reported with the full window instead.
+
+use orderbook_rs::orderbook::OrderBookSnapshotPackage;
+use orderbook_rs::{OrderBook, TradeResult};
+use pricelevel::{Id, Side, TimeInForce};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Orders kept for the cancellers to pick from.
+const LIVE_ORDER_CAP: usize = 4_096;
+
+/// Shape of one stress run.
+#[derive(Debug, Clone)]
+pub struct StressConfig {
+    pub makers: usize,
+    pub takers: usize,
+    pub cancellers: usize,
+    pub duration: Duration,
+    pub check_interval: Duration,
+    pub seed: u64,
+    pub mid_price: u128,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            makers: 2,
+            takers: 2,
+            cancellers: 1,
+            duration: Duration::from_millis(300),
+            check_interval: Duration::from_millis(50),
+            seed: 0x5EED,
+            mid_price: 10_000,
+        }
+    }
+}
+
+impl StressConfig {
+    /// Defaults, with the duration taken from `ORDERBOOK_STRESS_MS` when
+    /// set so long soak runs need no code change.
+    pub fn from_env() -> Self {
+        let config = Self::default();
+        match std::env::var("ORDERBOOK_STRESS_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+        {
+            Some(ms) => config.with_duration(Duration::from_millis(ms)),
+            None => config,
+        }
+    }
+
+    pub fn with_threads(mut self, makers: usize, takers: usize, cancellers: usize) -> Self {
+        self.makers = makers;
+        self.takers = takers;
+        self.cancellers = cancellers;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// One operation as submitted by a worker (or replayed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StressOp {
+    Limit {
+        id: Id,
+        side: Side,
+        price: u128,
+        quantity: u64,
+        time_in_force: TimeInForce,
+    },
+    Market {
+        id: Id,
+        side: Side,
+        quantity: u64,
+    },
+    Cancel {
+        id: Id,
+    },
+}
+
+impl fmt::Display for StressOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Limit {
+                id,
+                side,
+                price,
+                quantity,
+                time_in_force,
+            } => write!(f, "limit {id} {side} {quantity}@{price} {time_in_force:?}"),
+            Self::Market { id, side, quantity } => write!(f, "market {id} {side} {quantity}"),
+            Self::Cancel { id } => write!(f, "cancel {id}"),
+        }
+    }
+}
+
+/// Totals of a passing run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StressReport {
+    pub checkpoints: usize,
+    pub operations: u64,
+    pub added: u64,
+    pub executed: u64,
+    pub cancelled: u64,
+    pub resting: u64,
+}
+
+/// A failed checkpoint with the trace that led to it.
+#[derive(Debug, Clone)]
+pub struct StressFailure {
+    /// Index of the failing checkpoint (0-based).
+    pub checkpoint: usize,
+    /// Violations seen on the live book.
+    pub violations: Vec<String>,
+    /// Operations since the last passing checkpoint, in ticket order.
+    pub window: Vec<StressOp>,
+    /// Shortest sequential sub-trace that still fails, if the window
+    /// reproduces without concurrency.
+    pub minimized: Option<Vec<StressOp>>,
+}
+
+impl fmt::Display for StressFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "stress checkpoint {} failed:", self.checkpoint)?;
+        for violation in &self.violations {
+            writeln!(f, "  - {violation}")?;
+        }
+        let (label, trace) = match &self.minimized {
+            Some(trace) => ("minimized trace", trace),
+            None => ("window (not reproducible sequentially)", &self.window),
+        };
+        writeln!(f, "{label}, {} ops:", trace.len())?;
+        for op in trace {
+            writeln!(f, "  {op}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Quantity flows of one book, fed by the operations and its trade
+/// listener.
+#[derive(Debug, Default)]
+struct Ledger {
+    added: AtomicU64,
+    executed: AtomicU64,
+    cancelled: AtomicU64,
+}
+
+impl Ledger {
+    fn attach(self: &Arc<Self>, book: &mut OrderBook<()>) {
+        let ledger = Arc::clone(self);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            for fill in trade.match_result.trades().as_vec() {
+                ledger
+                    .executed
+                    .fetch_add(fill.quantity().as_u64(), Ordering::Relaxed);
+            }
+        }));
+    }
+
+    /// Violations of `start_resting + added == resting + executed + cancelled`
+    /// and of the auditor, on a quiescent `book`.
+    fn check(&self, book: &OrderBook<()>, start_resting: u64) -> Vec<String> {
+        let mut violations: Vec<String> = book
+            .audit()
+            .iter()
+            .map(|violation| format!("audit: {violation}"))
+            .collect();
+        let resting = resting_quantity(book);
+        let added = self.added.load(Ordering::Relaxed);
+        let executed = self.executed.load(Ordering::Relaxed);
+        let cancelled = self.cancelled.load(Ordering::Relaxed);
+        if start_resting + added != resting + executed + cancelled {
+            violations.push(format!(
+                "conservation: start {start_resting} + added {added} != \
+                 resting {resting} + executed {executed} + cancelled {cancelled}"
+            ));
+        }
+        violations
+    }
+}
+
+fn resting_quantity(book: &OrderBook<()>) -> u64 {
+    let snapshot = book.create_snapshot(usize::MAX);
+    snapshot
+        .bids
+        .iter()
+        .chain(snapshot.asks.iter())
+        .map(|level| level.visible_quantity().as_u64() + level.hidden_quantity().as_u64())
+        .sum()
+}
+
+/// Submit `op`, recording added and cancelled quantity. Results are
+/// otherwise ignored: rejections (an IOC with nothing to hit, a cancel of
+/// a filled order) are normal traffic.
+fn apply(book: &OrderBook<()>, ledger: &Ledger, op: &StressOp) {
+    match *op {
+        StressOp::Limit {
+            id,
+            side,
+            price,
+            quantity,
+            time_in_force,
+        } => {
+            let accepted = book
+                .add_limit_order(id, price, quantity, side, time_in_force, None)
+                .is_ok();
+            if accepted && time_in_force == TimeInForce::Gtc {
+                ledger.added.fetch_add(quantity, Ordering::Relaxed);
+            }
+        }
+        StressOp::Market { id, side, quantity } => {
+            let _ = book.submit_market_order(id, quantity, side);
+        }
+        StressOp::Cancel { id } => {
+            if let Ok(Some(order)) = book.cancel_order(id) {
+                let remaining =
+                    order.visible_quantity().as_u64() + order.hidden_quantity().as_u64();
+                ledger.cancelled.fetch_add(remaining, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// SplitMix64, so each worker's stream depends only on the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn side(&mut self) -> Side {
+        if self.next() & 1 == 0 {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Role {
+    Maker,
+    Taker,
+    Canceller,
+}
+
+struct Shared {
+    book: OrderBook<()>,
+    ledger: Arc<Ledger>,
+    gate: RwLock<()>,
+    stop: AtomicBool,
+    tickets: AtomicU64,
+    log: Mutex<Vec<(u64, StressOp)>>,
+    live: Mutex<Vec<Id>>,
+    /// Held by takers for the whole submit: one matcher at a time.
+    matching: Mutex<()>,
+    mid: u128,
+}
+
+fn next_op(shared: &Shared, role: Role, rng: &mut Rng, id: Id) -> Option<StressOp> {
+    let mid = shared.mid;
+    match role {
+        Role::Maker => {
+            let side = rng.side();
+            let offset = 1 + u128::from(rng.below(5));
+            let price = match side {
+                Side::Buy => mid - offset,
+                Side::Sell => mid + offset,
+            };
+            Some(StressOp::Limit {
+                id,
+                side,
+                price,
+                quantity: 1 + rng.below(50),
+                time_in_force: TimeInForce::Gtc,
+            })
+        }
+        Role::Taker => {
+            let side = rng.side();
+            let quantity = 1 + rng.below(30);
+            if rng.next() & 1 == 0 {
+                Some(StressOp::Market { id, side, quantity })
+            } else {
+                let price = match side {
+                    Side::Buy => mid + 3,
+                    Side::Sell => mid - 3,
+                };
+                Some(StressOp::Limit {
+                    id,
+                    side,
+                    price,
+                    quantity,
+                    time_in_force: TimeInForce::Ioc,
+                })
+            }
+        }
+        Role::Canceller => {
+            let mut live = shared
+                .live
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if live.is_empty() {
+                return None;
+            }
+            let index = rng.below(live.len() as u64) as usize;
+            Some(StressOp::Cancel {
+                id: live.swap_remove(index),
+            })
+        }
+    }
+}
+
+fn worker(shared: &Shared, role: Role, thread_index: u64, seed: u64) {
+    let mut rng = Rng(seed ^ (thread_index + 1).wrapping_mul(0xA076_1D64_78BD_642F));
+    let mut counter = 0u64;
+    while !shared.stop.load(Ordering::Relaxed) {
+        counter += 1;
+        // Thread index in bits 40..56 keeps ids unique across workers.
+        let id = Id::sequential((thread_index << 40) | counter);
+        let Some(op) = next_op(shared, role, &mut rng, id) else {
+            thread::yield_now();
+            continue;
+        };
+        {
+            let _running = shared
+                .gate
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let _matching = matches!(role, Role::Taker).then(|| {
+                shared
+                    .matching
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+            });
+            let ticket = shared.tickets.fetch_add(1, Ordering::Relaxed);
+            apply(&shared.book, &shared.ledger, &op);
+            shared
+                .log
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push((ticket, op));
+        }
+        if let Role::Maker = role {
+            let mut live = shared
+                .live
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if live.len() >= LIVE_ORDER_CAP {
+                live.swap_remove(0);
+            }
+            live.push(id);
+        }
+    }
+}
+
+/// Replay `ops` sequentially on a book restored from `checkpoint` and
+/// return the violations it ends with (empty if it passes).
+pub fn replay(checkpoint: &OrderBookSnapshotPackage, ops: &[StressOp]) -> Vec<String> {
+    let mut book = OrderBook::new("STRESS");
+    if let Err(error) = book.restore_from_snapshot_package(checkpoint.clone()) {
+        return vec![format!("checkpoint restore failed: {error}")];
+    }
+    let start_resting = resting_quantity(&book);
+    let ledger = Arc::new(Ledger::default());
+    ledger.attach(&mut book);
+    for op in ops {
+        apply(&book, &ledger, op);
+    }
+    ledger.check(&book, start_resting)
+}
+
+/// Shrink `ops` to a shorter sequence on which `fails` still holds, by
+/// removing ever smaller chunks (delta debugging without the complement
+/// step). `fails(ops)` must be true on entry.
+pub fn minimize(mut ops: Vec<StressOp>, fails: impl Fn(&[StressOp]) -> bool) -> Vec<StressOp> {
+    let mut chunk = ops.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        let mut shrunk = false;
+        while start < ops.len() {
+            let end = (start + chunk).min(ops.len());
+            let candidate: Vec<StressOp> =
+                ops[..start].iter().chain(&ops[end..]).copied().collect();
+            if fails(&candidate) {
+                ops = candidate;
+                shrunk = true;
+            } else {
+                start = end;
+            }
+        }
+        if !shrunk {
+            chunk /= 2;
+        }
+    }
+    ops
+}
+
+/// Run the configured workload, checking invariants at every checkpoint.
+pub fn run_stress(config: &StressConfig) -> Result<StressReport, StressFailure> {
+    let ledger = Arc::new(Ledger::default());
+    let mut book = OrderBook::new("STRESS");
+    ledger.attach(&mut book);
+    let shared = Arc::new(Shared {
+        book,
+        ledger: Arc::clone(&ledger),
+        gate: RwLock::new(()),
+        stop: AtomicBool::new(false),
+        tickets: AtomicU64::new(0),
+        log: Mutex::new(Vec::new()),
+        live: Mutex::new(Vec::new()),
+        matching: Mutex::new(()),
+        mid: config.mid_price,
+    });
+
+    let roles = std::iter::repeat_n(Role::Maker, config.makers)
+        .chain(std::iter::repeat_n(Role::Taker, config.takers))
+        .chain(std::iter::repeat_n(Role::Canceller, config.cancellers));
+    let workers: Vec<_> = roles
+        .enumerate()
+        .map(|(index, role)| {
+            let shared = Arc::clone(&shared);
+            let seed = config.seed;
+            thread::spawn(move || worker(&shared, role, index as u64, seed))
+        })
+        .collect();
+
+    let mut report = StressReport::default();
+    let mut checkpoint = shared
+        .book
+        .create_snapshot_package(usize::MAX)
+        .expect("empty book snapshots");
+    let deadline = Instant::now() + config.duration;
+    let mut workers = Some(workers);
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            shared.stop.store(true, Ordering::Relaxed);
+            for handle in workers.take().into_iter().flatten() {
+                handle.join().expect("stress worker panicked");
+            }
+        } else {
+            thread::sleep(config.check_interval.min(remaining));
+        }
+
+        let paused = shared
+            .gate
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut window = std::mem::take(
+            &mut *shared
+                .log
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+        report.operations += window.len() as u64;
+        let violations = ledger.check(&shared.book, 0);
+        if !violations.is_empty() {
+            shared.stop.store(true, Ordering::Relaxed);
+            drop(paused);
+            for handle in workers.take().into_iter().flatten() {
+                handle.join().expect("stress worker panicked");
+            }
+            window.sort_by_key(|(ticket, _)| *ticket);
+            let window: Vec<StressOp> = window.into_iter().map(|(_, op)| op).collect();
+            let minimized = (!replay(&checkpoint, &window).is_empty())
+                .then(|| minimize(window.clone(), |ops| !replay(&checkpoint, ops).is_empty()));
+            return Err(StressFailure {
+                checkpoint: report.checkpoints,
+                violations,
+                window,
+                minimized,
+            });
+        }
+        report.checkpoints += 1;
+        if workers.is_none() {
+            break;
+        }
+        checkpoint = shared
+            .book
+            .create_snapshot_package(usize::MAX)
+            .expect("quiescent book snapshots");
+    }
+
+    report.added = ledger.added.load(Ordering::Relaxed);
+    report.executed = ledger.executed.load(Ordering::Relaxed);
+    report.cancelled = ledger.cancelled.load(Ordering::Relaxed);
+    report.resting = resting_quantity(&shared.book);
+    Ok(report)
+}
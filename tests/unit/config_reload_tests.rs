@@ -0,0 +1,168 @@
+//! Integration tests for runtime configuration reloads on a shared book.
+
+#[cfg(test)]
+mod tests_config_reload {
+    use orderbook_rs::{
+        BookConfig, FeeSchedule, OrderBook, OrderBookError, PriceLevelChangedEvent,
+        ReferencePriceSource, RiskConfig, TradeResult,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn concurrent_admissions_never_see_a_mix_of_two_configs() {
+        let narrow = BookConfig::new().with_order_size_range(Some(1), Some(10));
+        let wide = BookConfig::new().with_order_size_range(Some(20), Some(30));
+        let book = Arc::new(OrderBook::<()>::new("BTC/USD"));
+        book.reload_config(narrow).expect("narrow");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let reloader = {
+            let book = Arc::clone(&book);
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut reloads = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    let next = if reloads.is_multiple_of(2) {
+                        wide
+                    } else {
+                        narrow
+                    };
+                    book.reload_config(next).expect("reload");
+                    reloads += 1;
+                    thread::yield_now();
+                }
+                reloads
+            })
+        };
+
+        let submitters: Vec<_> = (0..2)
+            .map(|_| {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    for _ in 0..2_000 {
+                        // Fifteen fits neither range, so every submit is
+                        // rejected with the bounds it was checked against.
+                        let result = book.add_limit_order(
+                            Id::new_uuid(),
+                            100,
+                            15,
+                            Side::Buy,
+                            TimeInForce::Gtc,
+                            None,
+                        );
+                        match result {
+                            Err(OrderBookError::OrderSizeOutOfRange { min, max, .. }) => {
+                                assert!(
+                                    (min, max) == (Some(1), Some(10))
+                                        || (min, max) == (Some(20), Some(30)),
+                                    "torn config: min {min:?} max {max:?}"
+                                );
+                            }
+                            other => panic!("unexpected admission result {other:?}"),
+                        }
+                    }
+                })
+            })
+            .collect();
+        for submitter in submitters {
+            submitter.join().expect("submitter");
+        }
+        stop.store(true, Ordering::Relaxed);
+        let reloads = reloader.join().expect("reloader");
+
+        assert_eq!(book.config_version(), reloads + 1);
+        assert!(book.get_bids().is_empty());
+    }
+
+    #[test]
+    fn reloaded_fees_and_bands_apply_to_later_flow_with_version_stamps() {
+        let trades: Arc<Mutex<Vec<TradeResult>>> = Arc::default();
+        let events: Arc<Mutex<Vec<PriceLevelChangedEvent>>> = Arc::default();
+        let book = OrderBook::<()>::new("BTC/USD");
+        let trade_sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            trade_sink.lock().unwrap().push(trade.clone());
+        }));
+        let event_sink = Arc::clone(&events);
+        book.set_price_level_listener(Arc::new(move |event| {
+            event_sink.lock().unwrap().push(event);
+        }));
+        let book = Arc::new(book);
+
+        book.add_limit_order(
+            Id::new_uuid(),
+            1_000,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+        assert_eq!(events.lock().unwrap()[0].config_version, 0);
+
+        let version = book
+            .reload_config(
+                BookConfig::new()
+                    .with_fee_schedule(FeeSchedule::new(-2, 5))
+                    .with_risk(
+                        RiskConfig::new().with_price_band_ticks(20, ReferencePriceSource::Mid),
+                    ),
+            )
+            .expect("reload");
+        assert_eq!(version, 1);
+
+        book.submit_market_order(Id::new_uuid(), 4, Side::Buy)
+            .expect("trade");
+        let trade = trades.lock().unwrap()[0].clone();
+        assert_eq!(trade.config_version, 1);
+        assert_eq!(trade.total_taker_fees, 2); // 5 bps of 4_000
+        assert_eq!(events.lock().unwrap().last().unwrap().config_version, 1);
+
+        // The price band came in with the same reload.
+        book.add_limit_order(Id::new_uuid(), 990, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        let outside_band =
+            book.add_limit_order(Id::new_uuid(), 900, 1, Side::Buy, TimeInForce::Gtc, None);
+        assert!(matches!(
+            outside_band,
+            Err(OrderBookError::RiskPriceBandTicks { .. })
+        ));
+
+        book.disable_risk();
+        assert_eq!(book.config_version(), 2);
+        assert!(book.risk_config().is_none());
+        assert_eq!(book.fee_schedule(), Some(FeeSchedule::new(-2, 5)));
+    }
+
+    #[test]
+    fn rejected_reload_keeps_config_and_snapshots_carry_the_version() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.set_tick_size(10);
+        book.update_config(|config| config.lot_size = Some(5))
+            .expect("update");
+        assert_eq!(book.config_version(), 2);
+
+        book.set_min_order_size(50);
+        let rejected = book.update_config(|config| config.max_order_size = Some(10));
+        assert!(matches!(
+            rejected,
+            Err(OrderBookError::InvalidConfiguration { .. })
+        ));
+        assert_eq!(book.config_version(), 3);
+        assert_eq!(book.max_order_size(), None);
+
+        let package = book.create_snapshot_package(10).expect("package");
+        assert_eq!(package.config_version, 3);
+        assert_eq!(package.tick_size, Some(10));
+
+        let mut restored = OrderBook::<()>::new("BTC/USD");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.config_version(), 3);
+        assert_eq!(restored.config(), book.config());
+    }
+}
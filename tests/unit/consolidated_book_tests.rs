@@ -0,0 +1,115 @@
+//! Tests for the consolidated best-price view across several books.
+
+#[cfg(test)]
+mod tests_consolidated_book {
+    use super::super::common::rest_as;
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::{ConsolidatedBook, OrderBook};
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    #[test]
+    fn merges_levels_with_venue_attribution() {
+        let lit = OrderBook::<()>::new("BTC/USD");
+        let alt = OrderBook::<()>::new("BTC/USD");
+        rest_as(&lit, Id::from_u64(1), 100, 5, Side::Buy, Hash32::zero());
+        rest_as(&lit, Id::from_u64(2), 100, 2, Side::Buy, Hash32::zero());
+        rest_as(&lit, Id::from_u64(3), 98, 4, Side::Buy, Hash32::zero());
+        rest_as(&alt, Id::from_u64(4), 100, 3, Side::Buy, Hash32::zero());
+        rest_as(&alt, Id::from_u64(5), 99, 6, Side::Buy, Hash32::zero());
+        rest_as(&lit, Id::from_u64(6), 103, 1, Side::Sell, Hash32::zero());
+        rest_as(&alt, Id::from_u64(7), 102, 8, Side::Sell, Hash32::zero());
+        lit.add_iceberg_order(
+            Id::from_u64(8),
+            102,
+            2,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+
+        let book = ConsolidatedBook::new()
+            .with_venue("LIT", &lit)
+            .with_venue("ALT", &alt);
+        assert_eq!(book.venues().collect::<Vec<_>>(), ["LIT", "ALT"]);
+
+        let bids = book.levels(Side::Buy, 2);
+        assert_eq!(
+            bids.iter().map(|level| level.price).collect::<Vec<_>>(),
+            [100, 99]
+        );
+        let best = &bids[0];
+        assert_eq!((best.quantity, best.order_count), (10, 3));
+        assert_eq!(best.venue("LIT").map(|venue| venue.quantity), Some(7));
+        assert_eq!(best.venue("ALT").map(|venue| venue.order_count), Some(1));
+        assert_eq!(bids[1].venues.len(), 1);
+        assert_eq!(book.levels(Side::Buy, 10).len(), 3);
+
+        let ask = book.best_ask().expect("ask");
+        assert_eq!(ask.price, 102);
+        assert_eq!((ask.visible_quantity, ask.quantity), (10, 20));
+        assert_eq!(
+            ask.venue("LIT").map(|venue| venue.visible_quantity),
+            Some(2)
+        );
+        assert_eq!(book.spread(), Some(2));
+        assert!(!book.is_crossed());
+    }
+
+    #[test]
+    fn reports_crossed_venues() {
+        let a = OrderBook::<()>::new("ETH/USD");
+        let b = OrderBook::<()>::new("ETH/USD");
+        rest_as(&a, Id::from_u64(1), 101, 1, Side::Buy, Hash32::zero());
+        rest_as(&b, Id::from_u64(2), 100, 1, Side::Sell, Hash32::zero());
+
+        let mut book = ConsolidatedBook::new();
+        book.add_venue("A", &a);
+        book.add_venue("B", &b);
+        assert!(book.is_crossed());
+        assert_eq!(book.spread(), None);
+
+        book.add_venue("B", &a);
+        assert_eq!(book.venues().count(), 2);
+        assert!(book.best_ask().is_none());
+        assert!(ConsolidatedBook::<()>::new().best_bid().is_none());
+    }
+
+    #[test]
+    fn from_manager_uses_every_book_as_a_venue() {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        for venue in ["VENUE-B", "VENUE-A"] {
+            mgr.add_book(venue).expect("add book");
+        }
+        rest_as(
+            mgr.get_book("VENUE-A").expect("a"),
+            Id::from_u64(1),
+            50,
+            2,
+            Side::Sell,
+            Hash32::zero(),
+        );
+        rest_as(
+            mgr.get_book("VENUE-B").expect("b"),
+            Id::from_u64(2),
+            50,
+            3,
+            Side::Sell,
+            Hash32::zero(),
+        );
+
+        let book = ConsolidatedBook::from_manager(&mgr);
+        assert_eq!(book.venues().collect::<Vec<_>>(), ["VENUE-A", "VENUE-B"]);
+        assert!(book.book("VENUE-A").is_some());
+        let ask = book.best_ask().expect("ask");
+        assert_eq!(ask.quantity, 5);
+        assert_eq!(
+            ask.venues
+                .iter()
+                .map(|venue| venue.venue.as_str())
+                .collect::<Vec<_>>(),
+            ["VENUE-A", "VENUE-B"]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Integration tests for the venue-wide dead-man's switch: refreshing,
+//! lapsing into cancel-only with the purge list cancelled, and the async
+//! watchdog wait.
+
+#[cfg(test)]
+mod tests_dead_mans_switch {
+    use super::super::common::rest_as;
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd, BookManagerTokio};
+    use orderbook_rs::{
+        CancelReason, Clock, DeadMansSwitch, OrderBookError, OrderStateTracker, OrderStatus,
+        ReplayClock,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const SYMBOLS: [&str; 2] = ["BTC/USD", "ETH/USD"];
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    /// Two books, each with one order of users 1, 2 and 3; the switch
+    /// purges users 1 and 2.
+    fn manager(clock: &Arc<ReplayClock>) -> BookManagerStd<()> {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        for (offset, symbol) in [(0, SYMBOLS[0]), (10, SYMBOLS[1])] {
+            mgr.add_book(symbol).expect("add book");
+            mgr.get_book_mut(symbol)
+                .expect("book")
+                .set_order_state_tracker(OrderStateTracker::new());
+            let book = mgr.get_book(symbol).expect("book");
+            rest_as(book, Id::from_u64(offset + 1), 100, 5, Side::Buy, user(1));
+            rest_as(book, Id::from_u64(offset + 2), 99, 5, Side::Buy, user(2));
+            rest_as(book, Id::from_u64(offset + 3), 98, 5, Side::Buy, user(3));
+        }
+        mgr.set_dead_mans_switch(
+            DeadMansSwitch::with_clock(Arc::clone(clock) as Arc<dyn Clock>)
+                .with_purge_users(vec![user(1), user(2)]),
+        );
+        mgr
+    }
+
+    #[test]
+    fn refreshed_switch_never_trips() {
+        let clock = Arc::new(ReplayClock::new(0));
+        let mgr = manager(&clock);
+        let switch = mgr.dead_mans_switch().clone();
+        clock.advance_to(10_000);
+        assert!(mgr.check_dead_mans_switch().is_none(), "disarmed");
+
+        for now in [10_000, 10_900, 11_800] {
+            clock.advance_to(now);
+            switch.arm(Duration::from_secs(1));
+            assert!(mgr.check_dead_mans_switch().is_none());
+        }
+        assert_eq!(switch.deadline_ms(), Some(12_800));
+
+        switch.disarm();
+        clock.advance_to(20_000);
+        assert!(mgr.check_dead_mans_switch().is_none());
+        assert!(
+            SYMBOLS
+                .iter()
+                .all(|symbol| !mgr.get_book(symbol).expect("book").is_kill_switch_engaged())
+        );
+    }
+
+    #[test]
+    fn lapsed_switch_leaves_every_book_cancel_only() {
+        let clock = Arc::new(ReplayClock::new(0));
+        let mgr = manager(&clock);
+        mgr.dead_mans_switch().arm(Duration::from_millis(500));
+
+        clock.advance_to(750);
+        let trip = mgr.check_dead_mans_switch().expect("lapsed");
+        assert_eq!(trip.deadline_ms, 500);
+        assert_eq!(trip.timestamp_ms, 750);
+        assert_eq!(
+            trip.purged["ETH/USD"].cancelled_order_ids(),
+            [Id::from_u64(11), Id::from_u64(12)]
+        );
+        assert!(mgr.check_dead_mans_switch().is_none(), "trips once");
+        assert!(!mgr.dead_mans_switch().is_armed());
+
+        let book = mgr.get_book("BTC/USD").expect("book");
+        assert!(book.is_kill_switch_engaged());
+        assert_eq!(book.best_bid(), Some(98));
+        assert!(matches!(
+            book.order_status(Id::from_u64(2)),
+            Some(OrderStatus::Cancelled {
+                reason: CancelReason::DeadMansSwitch,
+                ..
+            })
+        ));
+        assert!(matches!(
+            book.add_limit_order(Id::from_u64(4), 97, 5, Side::Buy, TimeInForce::Gtc, None),
+            Err(OrderBookError::KillSwitchActive)
+        ));
+        assert!(book.cancel_order(Id::from_u64(3)).is_ok(), "cancels pass");
+    }
+
+    #[tokio::test]
+    async fn watchdog_awaits_the_lapse() {
+        let mut mgr: BookManagerTokio<()> = BookManagerTokio::new();
+        mgr.add_book("BTC/USD").expect("add book");
+        let switch = mgr.dead_mans_switch().clone();
+
+        let watchdog = tokio::spawn({
+            let switch = switch.clone();
+            async move { switch.expired().await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        switch.arm(Duration::from_millis(20));
+
+        let deadline = tokio::time::timeout(Duration::from_secs(5), watchdog)
+            .await
+            .expect("lapses in time")
+            .expect("watchdog task");
+        assert_eq!(Some(deadline), switch.deadline_ms());
+        let trip = mgr.check_dead_mans_switch().expect("lapsed");
+        assert_eq!(trip.deadline_ms, deadline);
+        assert!(
+            mgr.get_book("BTC/USD")
+                .is_some_and(|book| book.is_kill_switch_engaged())
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Tests for visible-only and total depth in the analytics API.
+
+#[cfg(test)]
+mod tests_depth_mode {
+    use orderbook_rs::{DepthMode, OrderBook};
+    use pricelevel::{Id, Side, TimeInForce};
+
+    /// Asks: an iceberg showing 5 of 25 at 100 and a plain 10 at 101.
+    /// Bids: a plain 10 at 99.
+    fn book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            5,
+            20,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        book.add_limit_order(Id::from_u64(2), 101, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book.add_limit_order(Id::from_u64(3), 99, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book
+    }
+
+    #[test]
+    fn depth_queries_exclude_hidden_quantity_in_visible_mode() {
+        let book = book();
+        let (visible, total) = (DepthMode::Visible, DepthMode::Total);
+
+        assert_eq!(
+            book.total_depth_at_levels_with_mode(1, Side::Sell, visible),
+            5
+        );
+        assert_eq!(
+            book.total_depth_at_levels_with_mode(1, Side::Sell, total),
+            25
+        );
+        assert_eq!(
+            book.total_depth_at_levels_with_mode(5, Side::Sell, visible),
+            15
+        );
+        assert_eq!(book.total_depth_at_levels(5, Side::Sell), 35);
+
+        assert_eq!(
+            book.price_at_depth_with_mode(12, Side::Sell, visible),
+            Some(101)
+        );
+        assert_eq!(book.price_at_depth(12, Side::Sell), Some(100));
+        assert_eq!(book.price_at_depth_with_mode(20, Side::Sell, visible), None);
+        assert_eq!(
+            book.cumulative_depth_to_target_with_mode(10, Side::Sell, visible),
+            Some((101, 15))
+        );
+        assert_eq!(
+            book.liquidity_in_range_with_mode(100, 101, Side::Sell, visible),
+            15
+        );
+        assert_eq!(book.liquidity_in_range(100, 101, Side::Sell), 35);
+
+        assert_eq!(book.order_book_imbalance_with_mode(5, visible), -0.2);
+        assert_eq!(
+            book.order_book_imbalance(5),
+            book.order_book_imbalance_with_mode(5, total)
+        );
+        assert_eq!(DepthMode::default(), DepthMode::Total);
+    }
+
+    #[test]
+    fn execution_estimates_walk_only_the_displayed_book_in_visible_mode() {
+        let book = book();
+        let visible = DepthMode::Visible;
+
+        assert_eq!(book.vwap(15, Side::Buy), Some(100.0));
+        let vwap = book
+            .vwap_with_mode(15, Side::Buy, visible)
+            .expect("enough visible depth");
+        assert!((vwap - 1_510.0 / 15.0).abs() < 1e-9);
+        assert_eq!(book.vwap_with_mode(20, Side::Buy, visible), None);
+
+        let impact = book.market_impact_with_mode(10, Side::Buy, visible);
+        assert_eq!(
+            (
+                impact.levels_consumed,
+                impact.worst_price,
+                impact.total_quantity_available
+            ),
+            (2, 101, 15)
+        );
+        let impact = book.market_impact(10, Side::Buy);
+        assert_eq!(
+            (impact.levels_consumed, impact.total_quantity_available),
+            (1, 35)
+        );
+
+        let simulation = book.simulate_market_order_with_mode(20, Side::Buy, visible);
+        assert_eq!(simulation.fills, [(100, 5), (101, 10)]);
+        assert_eq!(simulation.remaining_quantity, 5);
+
+        let curve = book.liquidation_curve_with_mode(Side::Buy, 15, 3, visible);
+        let prices: Vec<f64> = curve.iter().map(|point| point.avg_price).collect();
+        assert_eq!(prices[..2], [100.0, 100.5]);
+        assert_eq!(curve.len(), 3);
+        assert!(
+            book.liquidation_curve(Side::Buy, 15, 3)
+                .iter()
+                .all(|point| point.avg_price == 100.0)
+        );
+    }
+}
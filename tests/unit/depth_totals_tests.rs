@@ -0,0 +1,240 @@
+//! Integration tests for the O(1) per-side depth totals on `OrderBook<T>`.
+
+#[cfg(test)]
+mod tests_depth_totals {
+    use orderbook_rs::OrderBook;
+    use pricelevel::{
+        Hash32, Id, OrderType, OrderUpdate, Price, Quantity, Side, TimeInForce, TimestampMs,
+    };
+    use std::num::NonZeroU64;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    /// `(quantity, order_count)` of `side` from a walk over every level.
+    fn walked(book: &OrderBook<()>, side: Side) -> (u64, usize) {
+        let levels = book.level_aggregates(usize::MAX);
+        let levels = match side {
+            Side::Buy => levels.bids,
+            Side::Sell => levels.asks,
+        };
+        levels.iter().fold((0, 0), |(quantity, count), level| {
+            (quantity + level.total_quantity(), count + level.order_count)
+        })
+    }
+
+    fn assert_totals_match_walk(book: &OrderBook<()>) {
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(
+                (book.side_total_quantity(side), book.side_order_count(side)),
+                walked(book, side),
+                "{side:?} totals diverged from the levels"
+            );
+        }
+    }
+
+    #[test]
+    fn totals_follow_adds_fills_updates_and_cancels() {
+        let book = OrderBook::<()>::new("TEST");
+        let bid = Id::new_uuid();
+        book.add_limit_order(bid, 99, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_iceberg_order(
+            Id::new_uuid(),
+            101,
+            5,
+            20,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        book.add_limit_order(Id::new_uuid(), 102, 7, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        assert_eq!(book.side_total_quantity(Side::Sell), 32);
+        assert_eq!(book.side_order_count(Side::Sell), 2);
+        assert_eq!(book.buy_sell_pressure(), (10, 32));
+
+        // Partial fill of the iceberg, replenishing from its hidden tranche.
+        book.submit_market_order(Id::new_uuid(), 8, Side::Buy)
+            .expect("market buy");
+        assert_eq!(book.side_total_quantity(Side::Sell), 24);
+        assert_totals_match_walk(&book);
+
+        book.update_order(OrderUpdate::UpdateQuantity {
+            order_id: bid,
+            new_quantity: Quantity::new(4),
+        })
+        .expect("resize");
+        assert_eq!(book.side_total_quantity(Side::Buy), 4);
+
+        book.update_order(OrderUpdate::UpdatePrice {
+            order_id: bid,
+            new_price: Price::new(98),
+        })
+        .expect("reprice");
+        assert_eq!(
+            (
+                book.side_total_quantity(Side::Buy),
+                book.side_order_count(Side::Buy)
+            ),
+            (4, 1)
+        );
+
+        book.cancel_order(bid).expect("cancel");
+        assert_eq!(book.side_order_count(Side::Buy), 0);
+        assert_totals_match_walk(&book);
+
+        assert_eq!(book.cancel_all_orders().cancelled_count(), 2);
+        assert_eq!(book.buy_sell_pressure(), (0, 0));
+    }
+
+    #[test]
+    fn hidden_quantity_dropped_with_a_consumed_reserve_order_is_removed() {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_order(OrderType::ReserveOrder {
+            id: Id::new_uuid(),
+            price: Price::new(100),
+            visible_quantity: Quantity::new(2),
+            hidden_quantity: Quantity::new(8),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(0),
+            replenish_amount: NonZeroU64::new(2),
+            auto_replenish: false,
+            extra_fields: (),
+        })
+        .expect("reserve");
+        book.submit_market_order(Id::new_uuid(), 2, Side::Buy)
+            .expect("market buy");
+        assert_totals_match_walk(&book);
+    }
+
+    #[test]
+    fn totals_survive_snapshot_restore() {
+        let book = OrderBook::<()>::new("TEST");
+        for i in 0..10u128 {
+            book.add_limit_order(Id::new_uuid(), 90 + i, 3, Side::Buy, TimeInForce::Gtc, None)
+                .expect("bid");
+        }
+        let snapshot = book.create_snapshot(usize::MAX);
+
+        let restored = OrderBook::<()>::new("TEST");
+        restored
+            .add_limit_order(Id::new_uuid(), 200, 1, Side::Sell, TimeInForce::Gtc, None)
+            .expect("replaced on restore");
+        restored.restore_from_snapshot(snapshot).expect("restore");
+        assert_eq!(restored.buy_sell_pressure(), (30, 0));
+        assert_eq!(restored.side_order_count(Side::Buy), 10);
+        assert_totals_match_walk(&restored);
+    }
+
+    #[test]
+    fn depth_queries_agree_with_level_walks() {
+        let book = OrderBook::<()>::new("TEST");
+        for i in 0..5u128 {
+            book.add_limit_order(
+                Id::new_uuid(),
+                95 + i,
+                10,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("bid");
+            book.add_limit_order(
+                Id::new_uuid(),
+                101 + i,
+                5,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("ask");
+        }
+        assert_eq!(book.total_depth_at_levels(2, Side::Buy), 20);
+        assert_eq!(book.total_depth_at_levels(100, Side::Buy), 50);
+        assert!((book.order_book_imbalance(100) - 25.0 / 75.0).abs() < 1e-12);
+        assert!(book.is_thin_book(30, 0));
+        assert!(!book.is_thin_book(25, 0));
+        assert!(book.is_thin_book(25, 2));
+    }
+
+    #[test]
+    fn totals_are_exact_after_concurrent_churn() {
+        const THREADS: usize = 8;
+        const OPS: u64 = 500;
+
+        let book = Arc::new(OrderBook::<()>::new("TEST"));
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let book = Arc::clone(&book);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..OPS {
+                        let price = 100 + u128::from(i % 5);
+                        let id = Id::new_uuid();
+                        match (t + i as usize) % 4 {
+                            0 => {
+                                let _ = book.add_limit_order(
+                                    id,
+                                    price,
+                                    3,
+                                    Side::Sell,
+                                    TimeInForce::Gtc,
+                                    None,
+                                );
+                            }
+                            1 => {
+                                let _ = book.add_iceberg_order(
+                                    id,
+                                    price,
+                                    2,
+                                    4,
+                                    Side::Sell,
+                                    TimeInForce::Gtc,
+                                    None,
+                                );
+                            }
+                            2 => {
+                                let _ = book.add_limit_order(
+                                    id,
+                                    price,
+                                    5,
+                                    Side::Sell,
+                                    TimeInForce::Gtc,
+                                    None,
+                                );
+                                let _ = book.cancel_order(id);
+                            }
+                            _ => {
+                                let _ = book.submit_market_order(id, 4, Side::Buy);
+                            }
+                        }
+                        let _ = book.add_limit_order(
+                            Id::new_uuid(),
+                            90 - u128::from(i % 5),
+                            1,
+                            Side::Buy,
+                            TimeInForce::Gtc,
+                            None,
+                        );
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("worker");
+        }
+
+        assert_totals_match_walk(&book);
+        assert_eq!(
+            book.side_order_count(Side::Buy),
+            THREADS * OPS as usize,
+            "every bid rests"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Integration tests for the RCU-published top-N depth view.
+
+#[cfg(test)]
+mod tests_depth_view {
+    use super::super::common::rest;
+    use orderbook_rs::OrderBook;
+    use pricelevel::{Id, Side};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    /// The published view must equal a fresh walk of the book.
+    fn assert_view_matches_book(book: &OrderBook<()>, depth: usize) {
+        let view = book.depth_view().expect("view enabled");
+        let walked = book.level_aggregates(depth);
+        assert_eq!(view.bids, walked.bids);
+        assert_eq!(view.asks, walked.asks);
+    }
+
+    #[test]
+    fn view_is_none_until_enabled_and_after_disabling() {
+        let mut book = OrderBook::<()>::new("TEST");
+        rest(&book, 100, 5, Side::Buy);
+        assert!(book.depth_view().is_none());
+
+        book.enable_depth_view(3);
+        assert_eq!(book.depth_view().expect("enabled").bids.len(), 1);
+
+        book.disable_depth_view();
+        assert!(book.depth_view().is_none());
+    }
+
+    #[test]
+    fn view_follows_adds_cancels_updates_and_matches() {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.enable_depth_view(2);
+
+        let bid = rest(&book, 100, 5, Side::Buy);
+        rest(&book, 99, 5, Side::Buy);
+        rest(&book, 98, 5, Side::Buy);
+        for price in 101..=103 {
+            rest(&book, price, 10, Side::Sell);
+        }
+        assert_view_matches_book(&book, 2);
+        assert_eq!(book.depth_view().expect("view").bids.len(), 2);
+
+        book.cancel_order(bid).expect("cancel");
+        assert_eq!(book.depth_view().expect("view").bids[0].price, 99);
+
+        book.update_order(pricelevel::OrderUpdate::UpdateQuantity {
+            order_id: rest(&book, 99, 1, Side::Buy),
+            new_quantity: pricelevel::Quantity::new(4),
+        })
+        .expect("update");
+        assert_view_matches_book(&book, 2);
+
+        book.submit_market_order(Id::new_uuid(), 15, Side::Buy)
+            .expect("sweep");
+        let view = book.depth_view().expect("view");
+        assert_eq!(view.asks[0].price, 102);
+        assert_eq!(view.asks[0].visible_quantity, 5);
+        assert_view_matches_book(&book, 2);
+
+        let _ = book.cancel_all_orders();
+        let view = book.depth_view().expect("view");
+        assert!(view.bids.is_empty() && view.asks.is_empty());
+    }
+
+    #[test]
+    fn view_is_rebuilt_on_restore() {
+        let source = OrderBook::<()>::new("TEST");
+        rest(&source, 100, 5, Side::Buy);
+        rest(&source, 105, 7, Side::Sell);
+        let snapshot = source.create_snapshot(usize::MAX);
+
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored.enable_depth_view(5);
+        rest(&restored, 90, 1, Side::Buy);
+        restored.restore_from_snapshot(snapshot).expect("restore");
+        assert_view_matches_book(&restored, 5);
+    }
+
+    #[test]
+    fn readers_keep_their_view_while_it_is_republished() {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.enable_depth_view(5);
+        rest(&book, 100, 5, Side::Buy);
+        let held = book.depth_view().expect("view");
+        rest(&book, 101, 5, Side::Buy);
+
+        assert_eq!(held.bids.len(), 1);
+        assert_eq!(book.depth_view().expect("view").bids.len(), 2);
+        assert!(book.depth_view().expect("view").update_seq > held.update_seq);
+    }
+
+    #[test]
+    fn view_settles_on_the_final_state_after_concurrent_writers() {
+        const THREADS: usize = 8;
+        const OPS: u64 = 300;
+
+        let mut book = OrderBook::<()>::new("TEST");
+        book.enable_depth_view(4);
+        let book = Arc::new(book);
+        let barrier = Arc::new(Barrier::new(THREADS + 1));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let book = Arc::clone(&book);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..OPS {
+                        let price = 100 + u128::from(i % 6);
+                        match (t + i as usize) % 3 {
+                            0 => {
+                                rest(&book, price, 3, Side::Sell);
+                            }
+                            1 => {
+                                let id = rest(&book, price - 10, 2, Side::Buy);
+                                let _ = book.cancel_order(id);
+                            }
+                            _ => {
+                                let _ = book.submit_market_order(Id::new_uuid(), 2, Side::Buy);
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        // A concurrent reader only ever sees well-formed views.
+        barrier.wait();
+        for _ in 0..1_000 {
+            let view = book.depth_view().expect("view");
+            assert!(view.asks.len() <= 4);
+            assert!(view.asks.windows(2).all(|w| w[0].price < w[1].price));
+        }
+        for handle in handles {
+            handle.join().expect("writer");
+        }
+        assert_view_matches_book(&book, 4);
+    }
+}
@@ -0,0 +1,116 @@
+//! Integration tests for the time-windowed duplicate order id check on
+//! `OrderBook<T>`.
+
+#[cfg(test)]
+mod tests_duplicate_order_id_window {
+    use orderbook_rs::orderbook::order_state::{CancelReason, OrderStateTracker, OrderStatus};
+    use orderbook_rs::{Clock, OrderBook, OrderBookError};
+    use pricelevel::{Id, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Clock whose time only moves when the test says so.
+    #[derive(Debug, Default)]
+    struct ManualClock(AtomicU64);
+
+    impl ManualClock {
+        fn set(&self, now_ms: u64) {
+            self.0.store(now_ms, Ordering::Relaxed);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now_millis(&self) -> TimestampMs {
+            TimestampMs::new(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    fn book_with_window(window_ms: u64) -> (OrderBook<()>, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::default());
+        let mut book = OrderBook::<()>::with_clock("TEST", clock.clone());
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book.set_duplicate_order_id_window(window_ms);
+        (book, clock)
+    }
+
+    fn add(book: &OrderBook<()>, id: Id, side: Side) -> Result<(), OrderBookError> {
+        book.add_limit_order(id, 100, 10, side, TimeInForce::Gtc, None)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn resubmitting_a_cancelled_id_within_window_is_rejected() {
+        let (book, clock) = book_with_window(1_000);
+        let id = Id::new_uuid();
+        add(&book, id, Side::Buy).expect("first submission");
+        book.cancel_order(id).expect("cancel");
+
+        clock.set(999);
+        let dup = add(&book, id, Side::Buy);
+        assert!(
+            matches!(dup, Err(OrderBookError::DuplicateOrderId { order_id }) if order_id == id),
+            "expected DuplicateOrderId, got {dup:?}"
+        );
+        // The original order's terminal state is left untouched.
+        assert!(matches!(
+            book.order_status(id),
+            Some(OrderStatus::Cancelled {
+                reason: CancelReason::UserRequested,
+                ..
+            })
+        ));
+        assert_eq!(book.best_bid(), None);
+    }
+
+    #[test]
+    fn resubmitting_a_filled_id_within_window_is_rejected() {
+        let (book, _clock) = book_with_window(1_000);
+        add(&book, Id::new_uuid(), Side::Sell).expect("resting ask");
+        let id = Id::new_uuid();
+        add(&book, id, Side::Buy).expect("crossing bid fills");
+        assert!(book.get_order(id).is_none());
+
+        assert!(matches!(
+            add(&book, id, Side::Buy),
+            Err(OrderBookError::DuplicateOrderId { .. })
+        ));
+    }
+
+    #[test]
+    fn id_is_accepted_again_once_the_window_expires() {
+        let (book, clock) = book_with_window(1_000);
+        let id = Id::new_uuid();
+        add(&book, id, Side::Buy).expect("first submission");
+        book.cancel_order(id).expect("cancel");
+
+        clock.set(1_000);
+        assert!(add(&book, id, Side::Buy).is_ok());
+    }
+
+    #[test]
+    fn disabling_the_window_only_keeps_the_resting_id_check() {
+        let (mut book, _clock) = book_with_window(1_000);
+        assert_eq!(book.duplicate_order_id_window(), Some(1_000));
+        let id = Id::new_uuid();
+        add(&book, id, Side::Buy).expect("first submission");
+
+        book.disable_duplicate_order_id_window();
+        assert_eq!(book.duplicate_order_id_window(), None);
+        // Still resting: rejected by the permanent check.
+        assert!(matches!(
+            add(&book, id, Side::Buy),
+            Err(OrderBookError::DuplicateOrderId { .. })
+        ));
+
+        book.cancel_order(id).expect("cancel");
+        assert!(add(&book, id, Side::Buy).is_ok());
+    }
+
+    #[test]
+    fn fresh_ids_are_unaffected() {
+        let (book, _clock) = book_with_window(60_000);
+        for _ in 0..100 {
+            assert!(add(&book, Id::new_uuid(), Side::Buy).is_ok());
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Integration tests for the rotating `EventLogSink`.
+
+#[cfg(test)]
+mod tests_event_log {
+    use orderbook_rs::{
+        EventLogConfig, EventLogFormat, EventLogSink, OrderBook, PriceLevelChangedEvent,
+        ReplayClock,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    fn logged_book(sink: &Arc<EventLogSink>, symbol: &str) -> OrderBook<()> {
+        let book = OrderBook::new(symbol);
+        book.set_trade_listener(sink.trade_listener());
+        book.set_price_level_listener(sink.price_level_listener(symbol));
+        book
+    }
+
+    fn trade_twice(book: &OrderBook<()>) {
+        book.add_limit_order(
+            Id::sequential(1),
+            100,
+            3,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+        book.add_limit_order(
+            Id::sequential(2),
+            101,
+            3,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+        book.submit_market_order(Id::sequential(3), 4, Side::Buy)
+            .expect("market order");
+    }
+
+    fn files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .expect("dir")
+            .map(|entry| entry.expect("entry").path())
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn lines(path: &Path) -> Vec<String> {
+        fs::read_to_string(path)
+            .expect("read")
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn json_lines_record_fills_and_level_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let sink = Arc::new(EventLogSink::new(EventLogConfig::new(dir.path())).expect("sink"));
+        trade_twice(&logged_book(&sink, "BTC/USD"));
+        sink.flush().expect("flush");
+
+        let records: Vec<serde_json::Value> = lines(&sink.current_path())
+            .iter()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(records.len() as u64, sink.events_written());
+
+        let trades: Vec<_> = records.iter().filter(|r| r["kind"] == "trade").collect();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0]["symbol"], "BTC/USD");
+        assert_eq!(trades[0]["side"], "buy");
+        assert_eq!(trades[0]["price"], 100);
+        assert_eq!(trades[0]["quantity"], 3);
+        assert_eq!(trades[1]["price"], 101);
+        assert_eq!(trades[1]["quantity"], 1);
+        assert_eq!(trades[0]["maker_order_id"], Id::sequential(1).to_string());
+
+        let levels: Vec<_> = records.iter().filter(|r| r["kind"] == "level").collect();
+        assert!(!levels.is_empty());
+        assert!(levels.iter().all(|r| r.get("taker_order_id").is_none()));
+        assert!(
+            levels
+                .iter()
+                .any(|r| r["price"] == 100 && r["quantity"] == 0 && r["side"] == "sell")
+        );
+
+        let seqs: Vec<u64> = records
+            .iter()
+            .map(|r| r["engine_seq"].as_u64().expect("seq"))
+            .collect();
+        assert!(seqs.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn csv_has_header_and_quotes_symbols() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = EventLogConfig::new(dir.path()).with_format(EventLogFormat::Csv);
+        let sink = Arc::new(EventLogSink::new(config).expect("sink"));
+        trade_twice(&logged_book(&sink, "A,B"));
+        sink.flush().expect("flush");
+
+        let path = sink.current_path();
+        assert_eq!(path.extension().and_then(|ext| ext.to_str()), Some("csv"));
+        let lines = lines(&path);
+        assert_eq!(
+            lines[0],
+            "kind,symbol,engine_seq,timestamp,side,price,quantity,taker_order_id,maker_order_id"
+        );
+        assert_eq!(lines.len() as u64, sink.events_written() + 1);
+        let level = lines
+            .iter()
+            .find(|line| line.starts_with("level,"))
+            .expect("level row");
+        assert!(level.starts_with("level,\"A,B\","));
+        assert!(level.ends_with(",,"));
+        let trade = lines
+            .iter()
+            .find(|line| line.starts_with("trade,"))
+            .expect("trade row");
+        assert!(trade.contains(",buy,100,3,"));
+        assert!(trade.ends_with(&Id::sequential(1).to_string()));
+    }
+
+    #[test]
+    fn rotates_by_size_without_losing_records() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = EventLogConfig::new(dir.path())
+            .with_file_prefix("tape")
+            .with_max_file_bytes(400);
+        let sink = Arc::new(EventLogSink::new(config).expect("sink"));
+        trade_twice(&logged_book(&sink, "BTC/USD"));
+        sink.flush().expect("flush");
+
+        let files = files(dir.path());
+        assert!(files.len() > 1);
+        assert_eq!(sink.rotations() as usize, files.len() - 1);
+        assert!(files[0].ends_with("tape-000000.jsonl"));
+        let mut total = 0;
+        for file in &files {
+            let size = fs::metadata(file).expect("metadata").len();
+            let records = lines(file).len();
+            assert!(size <= 400 || records == 1);
+            total += records;
+        }
+        assert_eq!(total as u64, sink.events_written());
+    }
+
+    #[test]
+    fn rotates_by_age_on_the_sink_clock() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let clock = Arc::new(ReplayClock::new(1_000));
+        let config = EventLogConfig::new(dir.path()).with_max_file_age_ms(60_000);
+        let sink = EventLogSink::with_clock(config, clock.clone()).expect("sink");
+        let change = |price| PriceLevelChangedEvent {
+            side: Side::Buy,
+            price,
+            quantity: 1,
+            order_count: 1,
+            engine_seq: 0,
+            config_version: 0,
+        };
+
+        sink.log_book_change("X", &change(1)).expect("log");
+        clock.advance_to(30_000);
+        sink.log_book_change("X", &change(2)).expect("log");
+        clock.advance_to(61_000);
+        sink.log_book_change("X", &change(3)).expect("log");
+        sink.flush().expect("flush");
+
+        assert_eq!(sink.rotations(), 1);
+        let files = files(dir.path());
+        assert_eq!(files.len(), 2);
+        assert_eq!(lines(&files[0]).len(), 2);
+        let last: serde_json::Value = serde_json::from_str(&lines(&files[1])[0]).expect("json");
+        assert_eq!(last["timestamp"], 61_000);
+    }
+
+    #[test]
+    fn reopening_continues_after_existing_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = EventLogConfig::new(dir.path());
+        let first = EventLogSink::new(config.clone()).expect("sink");
+        first
+            .log_book_change(
+                "X",
+                &PriceLevelChangedEvent {
+                    side: Side::Sell,
+                    price: 5,
+                    quantity: 5,
+                    order_count: 1,
+                    engine_seq: 1,
+                    config_version: 0,
+                },
+            )
+            .expect("log");
+        drop(first);
+
+        let second = EventLogSink::new(config).expect("sink");
+        assert!(second.current_path().ends_with("events-000001.jsonl"));
+        assert_eq!(lines(&dir.path().join("events-000000.jsonl")).len(), 1);
+    }
+}
@@ -120,7 +120,7 @@ fn eviction_fires_book_change_event_for_touched_level() {
 
     let events: Arc<Mutex<Vec<PriceLevelChangedEvent>>> = Arc::new(Mutex::new(Vec::new()));
     let sink = Arc::clone(&events);
-    let mut book = expiring_book("TEST");
+    let book = expiring_book("TEST");
     book.set_price_level_listener(Arc::new(move |ev: PriceLevelChangedEvent| {
         if let Ok(mut v) = sink.lock() {
             v.push(ev);
@@ -0,0 +1,197 @@
+//! Tests for the TWAP, VWAP and percent-of-volume execution algos run by
+//! SimulationRunner.
+
+#[cfg(test)]
+mod tests_execution_algo {
+    use orderbook_rs::{
+        AgentAction, AgentContext, ExecutionAlgo, ExecutionSchedule, OrderBook, OrderBookError,
+        SimulationRunner, Strategy,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn book_with(levels: &[(u64, u128, u64, Side)]) -> OrderBook<()> {
+        let book = OrderBook::<()>::new("BTC");
+        for &(id, price, quantity, side) in levels {
+            book.add_limit_order(
+                Id::from_u64(id),
+                price,
+                quantity,
+                side,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("resting order");
+        }
+        book
+    }
+
+    #[test]
+    fn twap_releases_equal_slices_and_reports_shortfall() {
+        let book = book_with(&[
+            (1, 98, 100, Side::Buy),
+            (2, 102, 10, Side::Sell),
+            (3, 104, 100, Side::Sell),
+        ]);
+        let twap = ExecutionAlgo::new(
+            "BTC",
+            Side::Buy,
+            20,
+            ExecutionSchedule::Twap {
+                slices: 4,
+                duration: 4_000,
+            },
+        )
+        .expect("valid schedule");
+        let handle = twap.handle();
+        let mut runner = SimulationRunner::new(1_000);
+        runner.add_book(book);
+        runner.add_agent(twap);
+
+        runner.run(1);
+        let report = handle.report();
+        assert_eq!(
+            (report.started_at, report.arrival_price),
+            (Some(1_000), Some(100))
+        );
+        assert_eq!((report.sent, report.filled), (5, 5));
+        assert_eq!(report.completed_at, None);
+
+        let sim = runner.run(5);
+        let report = handle.report();
+        assert_eq!((report.child_orders, report.filled), (4, 20));
+        assert_eq!(report.completed_at, Some(4_000));
+        assert_eq!(report.notional, 102 * 10 + 104 * 10);
+        assert_eq!(report.avg_price(), Some(103.0));
+        assert_eq!(report.shortfall(), Some(3.0));
+        assert_eq!(report.shortfall_bps(), Some(300.0));
+        assert_eq!(report.shortfall_cost(), Some(60.0));
+        assert_eq!(sim.agents[0].name, "twap:BTC");
+        assert_eq!(sim.agents[0].stats.bought, 20);
+    }
+
+    #[test]
+    fn vwap_follows_the_volume_profile() {
+        let book = book_with(&[(1, 100, 100, Side::Buy), (2, 102, 100, Side::Sell)]);
+        let vwap = ExecutionAlgo::new(
+            "BTC",
+            Side::Sell,
+            40,
+            ExecutionSchedule::Vwap {
+                profile: vec![1, 0, 3],
+                duration: 3_000,
+            },
+        )
+        .expect("valid schedule")
+        .with_name("vwap-seller");
+        let handle = vwap.handle();
+        let mut runner = SimulationRunner::new(1_000);
+        runner.add_book(book);
+        runner.add_agent(vwap);
+
+        let mut sent = Vec::new();
+        for _ in 0..4 {
+            runner.step();
+            sent.push(handle.report().sent);
+        }
+        assert_eq!(sent, [10, 10, 40, 40]);
+
+        let report = handle.report();
+        assert_eq!((report.child_orders, report.filled), (2, 40));
+        // Sold at the 100 bid against a 101 arrival mid.
+        assert_eq!(report.shortfall(), Some(1.0));
+        assert_eq!(runner.report().agents[0].name, "vwap-seller");
+    }
+
+    /// Buys `quantity` at market every tick.
+    struct Flow {
+        quantity: u64,
+    }
+
+    impl Strategy for Flow {
+        fn name(&self) -> &str {
+            "flow"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            vec![AgentAction::market("BTC", Side::Buy, self.quantity)]
+        }
+    }
+
+    #[test]
+    fn pov_tracks_the_volume_of_others() {
+        let book = book_with(&[(1, 99, 10, Side::Buy), (2, 101, 1_000, Side::Sell)]);
+        let pov = ExecutionAlgo::new(
+            "BTC",
+            Side::Buy,
+            100,
+            ExecutionSchedule::Pov {
+                participation_bps: 5_000,
+            },
+        )
+        .expect("valid schedule");
+        let handle = pov.handle();
+        let mut runner = SimulationRunner::new(1_000);
+        runner.add_book(book);
+        runner.add_agent(Flow { quantity: 10 });
+        runner.add_agent(pov);
+
+        // The first tick only observes: the flow trades after every agent ticked.
+        runner.run(3);
+        let report = handle.report();
+        assert_eq!(report.child_orders, 2);
+        assert_eq!(report.filled, 10);
+        assert_eq!(report.market_volume, 30);
+        assert_eq!(report.unfilled(), 90);
+        assert_eq!(report.shortfall(), Some(1.0));
+    }
+
+    #[test]
+    fn children_without_liquidity_stay_unfilled() {
+        let book = book_with(&[(1, 100, 5, Side::Sell)]);
+        let twap = ExecutionAlgo::new(
+            "BTC",
+            Side::Buy,
+            10,
+            ExecutionSchedule::Twap {
+                slices: 2,
+                duration: 2_000,
+            },
+        )
+        .expect("valid schedule");
+        let handle = twap.handle();
+        let mut runner = SimulationRunner::new(1_000);
+        runner.add_book(book);
+        runner.add_agent(twap);
+
+        let sim = runner.run(3);
+        let report = handle.report();
+        // No bid: the arrival price falls back to nothing until a trade prints.
+        assert_eq!(report.arrival_price, None);
+        assert_eq!((report.sent, report.filled, report.unfilled()), (10, 5, 5));
+        assert_eq!(report.shortfall(), None);
+        assert_eq!(report.completed_at, None);
+        assert_eq!(sim.agents[0].stats.rejected, 1);
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        for schedule in [
+            ExecutionSchedule::Twap {
+                slices: 0,
+                duration: 1_000,
+            },
+            ExecutionSchedule::Vwap {
+                profile: vec![0, 0],
+                duration: 1_000,
+            },
+            ExecutionSchedule::Pov {
+                participation_bps: 0,
+            },
+        ] {
+            assert!(matches!(
+                ExecutionAlgo::new("BTC", Side::Buy, 10, schedule),
+                Err(OrderBookError::InvalidConfiguration { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Integration tests for the expiry timing wheel behind
+//! `evict_expired_orders`.
+
+#[cfg(test)]
+mod tests_expiry_wheel {
+    use super::super::common::rest_with_tif;
+    use orderbook_rs::{Clock, OrderBook, StubClock};
+    use pricelevel::{Id, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
+
+    fn expiring_book() -> OrderBook<()> {
+        OrderBook::with_clock(
+            "TEST",
+            Arc::new(StubClock::starting_at(0)) as Arc<dyn Clock>,
+        )
+    }
+
+    #[test]
+    fn eviction_keeps_the_determinism_contract_order() {
+        let book = expiring_book();
+        // Deadlines deliberately disagree with the contract order.
+        let ask_hi = rest_with_tif(&book, 110, 10, Side::Sell, TimeInForce::Gtd(100));
+        let bid_hi_old = rest_with_tif(&book, 99, 10, Side::Buy, TimeInForce::Gtd(900));
+        let ask_lo = rest_with_tif(&book, 105, 10, Side::Sell, TimeInForce::Gtd(500));
+        let bid_lo = rest_with_tif(&book, 90, 10, Side::Buy, TimeInForce::Gtd(300));
+        let bid_hi_new = rest_with_tif(&book, 99, 10, Side::Buy, TimeInForce::Gtd(200));
+        let gtc = rest_with_tif(&book, 95, 10, Side::Buy, TimeInForce::Gtc);
+
+        let evicted: Vec<Id> = book
+            .evict_expired_orders(TimestampMs::new(1_000))
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        assert_eq!(
+            evicted,
+            vec![bid_lo, bid_hi_old, bid_hi_new, ask_lo, ask_hi]
+        );
+        assert_eq!(book.best_bid(), Some(95));
+        assert!(book.cancel_order(gtc).expect("cancel").is_some());
+    }
+
+    #[test]
+    fn only_due_orders_are_taken_from_the_wheel() {
+        let book = expiring_book();
+        let soon = rest_with_tif(&book, 100, 10, Side::Buy, TimeInForce::Gtd(1_000));
+        let later = rest_with_tif(&book, 101, 10, Side::Buy, TimeInForce::Gtd(5_000_000));
+        assert_eq!(book.expiry_wheel_stats().scheduled, 2);
+
+        let evicted = book.evict_expired_orders(TimestampMs::new(4_000));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id(), soon);
+
+        let stats = book.expiry_wheel_stats();
+        assert_eq!(stats.scheduled, 1);
+        assert_eq!(stats.total_due, 1);
+        assert_eq!(stats.elapsed_ms, Some(4_000));
+
+        let evicted = book.evict_expired_orders(TimestampMs::new(5_000_000));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id(), later);
+        assert_eq!(book.expiry_wheel_stats().scheduled, 0);
+    }
+
+    #[test]
+    fn cancelled_and_filled_orders_leave_the_wheel() {
+        let book = expiring_book();
+        let cancelled = rest_with_tif(&book, 100, 10, Side::Sell, TimeInForce::Gtd(1_000));
+        rest_with_tif(&book, 101, 10, Side::Sell, TimeInForce::Gtd(1_000));
+        assert_eq!(book.expiry_wheel_stats().scheduled, 2);
+
+        book.cancel_order(cancelled).expect("cancel");
+        book.match_market_order(Id::new_uuid(), 10, Side::Buy)
+            .expect("fill");
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.expiry_wheel_stats().scheduled, 0);
+
+        assert!(
+            book.evict_expired_orders(TimestampMs::new(1_000))
+                .is_empty()
+        );
+        let stats = book.expiry_wheel_stats();
+        assert_eq!(stats.total_due, 0);
+        assert_eq!(stats.stale_discarded, 2);
+    }
+
+    #[test]
+    fn day_orders_expire_at_market_close() {
+        let book = expiring_book();
+        let day = rest_with_tif(&book, 100, 10, Side::Buy, TimeInForce::Day);
+        assert_eq!(book.expiry_wheel_stats().day_orders, 1);
+
+        // No market close configured: Day orders never expire.
+        assert!(
+            book.evict_expired_orders(TimestampMs::new(10_000))
+                .is_empty()
+        );
+        assert_eq!(book.expiry_wheel_stats().day_orders, 1);
+
+        book.set_market_close_timestamp(20_000);
+        assert!(
+            book.evict_expired_orders(TimestampMs::new(19_999))
+                .is_empty()
+        );
+
+        let evicted = book.evict_expired_orders(TimestampMs::new(20_000));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id(), day);
+        assert_eq!(book.expiry_wheel_stats().day_orders, 0);
+    }
+
+    #[test]
+    fn restored_book_reschedules_resting_orders() {
+        let book = expiring_book();
+        let gtd = rest_with_tif(&book, 100, 10, Side::Buy, TimeInForce::Gtd(1_000));
+        rest_with_tif(&book, 101, 10, Side::Sell, TimeInForce::Gtc);
+        let snapshot = book.create_snapshot(usize::MAX);
+
+        let restored = expiring_book();
+        rest_with_tif(&restored, 50, 10, Side::Buy, TimeInForce::Gtd(500));
+        restored.restore_from_snapshot(snapshot).expect("restore");
+        assert_eq!(restored.expiry_wheel_stats().scheduled, 1);
+
+        let evicted = restored.evict_expired_orders(TimestampMs::new(1_000));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id(), gtd);
+        assert_eq!(restored.best_ask(), Some(101));
+    }
+}
@@ -0,0 +1,147 @@
+//! Integration tests for `extra_fields` flow-through: the payload supplied
+//! at submission is returned by order queries and attached to trade events.
+
+#[cfg(test)]
+mod tests_extra_fields {
+    use orderbook_rs::{OrderBook, TradeResult};
+    use pricelevel::{Id, OrderUpdate, Price, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct ClientTag {
+        account: u32,
+        note: String,
+    }
+
+    fn tag(account: u32) -> ClientTag {
+        ClientTag {
+            account,
+            note: format!("acct-{account}"),
+        }
+    }
+
+    fn rest(
+        book: &OrderBook<ClientTag>,
+        price: u128,
+        quantity: u64,
+        side: Side,
+        account: u32,
+    ) -> Id {
+        let id = Id::new_uuid();
+        book.add_limit_order(
+            id,
+            price,
+            quantity,
+            side,
+            TimeInForce::Gtc,
+            Some(tag(account)),
+        )
+        .expect("rest order");
+        id
+    }
+
+    #[test]
+    fn queries_return_submitted_extra_fields() {
+        let book = OrderBook::<ClientTag>::new("TEST");
+        let bid = rest(&book, 100, 10, Side::Buy, 1);
+        let ask = rest(&book, 105, 10, Side::Sell, 2);
+
+        assert_eq!(book.get_order(bid).unwrap().extra_fields(), &tag(1));
+        let all = book.get_all_orders();
+        let ask_order = all.iter().find(|o| o.id() == ask).unwrap();
+        assert_eq!(ask_order.extra_fields(), &tag(2));
+
+        let cancelled = book.cancel_order(bid).unwrap().unwrap();
+        assert_eq!(cancelled.extra_fields(), &tag(1));
+        assert!(book.get_order(bid).is_none());
+    }
+
+    #[test]
+    fn price_update_keeps_extra_fields() {
+        let book = OrderBook::<ClientTag>::new("TEST");
+        let id = rest(&book, 100, 10, Side::Buy, 7);
+
+        book.update_order(OrderUpdate::UpdatePrice {
+            order_id: id,
+            new_price: Price::new(99),
+        })
+        .unwrap();
+
+        let order = book.get_order(id).unwrap();
+        assert_eq!(order.price().as_u128(), 99);
+        assert_eq!(order.extra_fields(), &tag(7));
+    }
+
+    #[test]
+    fn trade_events_carry_maker_and_taker_extra_fields() {
+        let seen: Arc<Mutex<Vec<TradeResult>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let book = OrderBook::<ClientTag>::with_trade_listener(
+            "TEST",
+            Arc::new(move |trade: &TradeResult| sink.lock().unwrap().push(trade.clone())),
+        );
+        let maker = rest(&book, 100, 5, Side::Sell, 1);
+        let taker = rest(&book, 100, 5, Side::Buy, 2);
+
+        let trades = seen.lock().unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(
+            trades[0].extra_fields_for::<ClientTag>(maker),
+            Some(&tag(1))
+        );
+        assert_eq!(
+            trades[0].extra_fields_for::<ClientTag>(taker),
+            Some(&tag(2))
+        );
+        assert_eq!(trades[0].extra_fields_for::<u32>(maker), None);
+        drop(trades);
+
+        // The fully filled maker's payload is released with the order.
+        assert!(book.get_order(maker).is_none());
+    }
+
+    #[test]
+    fn market_sweep_reports_maker_extra_fields() {
+        let seen: Arc<Mutex<Vec<TradeResult>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let book = OrderBook::<ClientTag>::with_trade_listener(
+            "TEST",
+            Arc::new(move |trade: &TradeResult| sink.lock().unwrap().push(trade.clone())),
+        );
+        let first = rest(&book, 100, 5, Side::Sell, 1);
+        let second = rest(&book, 101, 5, Side::Sell, 3);
+
+        book.match_market_order(Id::new_uuid(), 7, Side::Buy)
+            .unwrap();
+
+        let trades = seen.lock().unwrap();
+        assert_eq!(
+            trades[0].extra_fields_for::<ClientTag>(first),
+            Some(&tag(1))
+        );
+        assert_eq!(
+            trades[0].extra_fields_for::<ClientTag>(second),
+            Some(&tag(3))
+        );
+        drop(trades);
+
+        // The partially filled maker still rests with its payload.
+        assert_eq!(book.get_order(second).unwrap().extra_fields(), &tag(3));
+    }
+
+    #[test]
+    fn unit_books_attach_nothing() {
+        let seen: Arc<Mutex<Vec<TradeResult>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let book = OrderBook::<()>::with_trade_listener(
+            "TEST",
+            Arc::new(move |trade: &TradeResult| sink.lock().unwrap().push(trade.clone())),
+        );
+        book.add_limit_order(Id::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+        book.match_market_order(Id::new_uuid(), 5, Side::Buy)
+            .unwrap();
+
+        assert!(seen.lock().unwrap()[0].extra_fields.is_empty());
+    }
+}
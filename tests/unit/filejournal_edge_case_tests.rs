@@ -7,7 +7,7 @@
 mod tests_filejournal_edge_cases {
     use orderbook_rs::orderbook::sequencer::journal::Journal;
     use orderbook_rs::orderbook::sequencer::{
-        FileJournal, SequencerCommand, SequencerEvent, SequencerResult,
+        ENTRY_OVERHEAD, FileJournal, SequencerCommand, SequencerEvent, SequencerResult,
     };
     use pricelevel::Id;
     use std::fs;
@@ -358,8 +358,9 @@ mod tests_filejournal_edge_cases {
         // Measure the size of one encoded event to calibrate segment size
         let sample_event = make_event(0);
         let encoded = serde_json::to_vec(&sample_event).expect("encode");
-        // Total entry size: 4 (entry_length) + 8 (seq) + 8 (ts) + payload + 4 (crc)
-        let entry_size = 4 + 8 + 8 + encoded.len() + 4;
+        // Total entry size: header (length, seq, ts, version, tag, symbol
+        // hash) + payload + CRC
+        let entry_size = ENTRY_OVERHEAD + encoded.len();
 
         // Segment size = exactly 2 entries
         let segment_size = entry_size * 2;
@@ -0,0 +1,111 @@
+//! Integration tests for the `deterministic` feature's `*_fixed` analytics.
+
+#[cfg(test)]
+mod tests_fixed_point {
+    use orderbook_rs::{OrderBook, PriceRatio, SignedRatio};
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn ladder() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        for (price, quantity) in [(100, 30), (99, 20), (98, 50)] {
+            book.add_limit_order(
+                Id::new_uuid(),
+                price,
+                quantity,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("bid");
+        }
+        for (price, quantity) in [(103, 10), (104, 25), (105, 40)] {
+            book.add_limit_order(
+                Id::new_uuid(),
+                price,
+                quantity,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("ask");
+        }
+        book
+    }
+
+    #[test]
+    fn fixed_analytics_are_exact_fractions() {
+        let book = ladder();
+
+        assert_eq!(book.mid_price_fixed(), PriceRatio::new(203, 2));
+        // (103 * 30 + 100 * 10) / 40
+        assert_eq!(book.micro_price_fixed(), PriceRatio::new(4_090, 40));
+        // 3 / 101.5 * 10_000
+        assert_eq!(book.spread_bps_fixed(None), PriceRatio::new(60_000, 203));
+        // (103 * 10 + 104 * 20) / 30
+        assert_eq!(book.vwap_fixed(30, Side::Buy), PriceRatio::new(3_110, 30));
+        // (50 - 35) / 85
+        assert_eq!(
+            book.order_book_imbalance_fixed(2),
+            SignedRatio::from_difference(50, 35, 85).expect("ratio")
+        );
+    }
+
+    #[test]
+    fn fixed_analytics_agree_with_the_float_api() {
+        let book = ladder();
+        let close = |fixed: f64, float: f64| (fixed - float).abs() < 1e-9;
+
+        assert!(close(
+            book.mid_price_fixed().expect("mid").to_f64(),
+            book.mid_price().expect("mid")
+        ));
+        assert!(close(
+            book.micro_price_fixed().expect("micro").to_f64(),
+            book.micro_price().expect("micro")
+        ));
+        assert!(close(
+            book.spread_bps_fixed(None).expect("spread").to_f64(),
+            book.spread_bps(None).expect("spread")
+        ));
+        for quantity in [1, 10, 35, 75] {
+            for side in [Side::Buy, Side::Sell] {
+                assert!(close(
+                    book.vwap_fixed(quantity, side).expect("vwap").to_f64(),
+                    book.vwap(quantity, side).expect("vwap")
+                ));
+            }
+        }
+        for levels in 1..=3 {
+            assert!(close(
+                book.order_book_imbalance_fixed(levels).to_f64(),
+                book.order_book_imbalance(levels)
+            ));
+        }
+    }
+
+    #[test]
+    fn fixed_analytics_handle_empty_and_unfillable_books() {
+        let empty = OrderBook::<()>::new("TEST");
+        assert_eq!(empty.mid_price_fixed(), None);
+        assert_eq!(empty.micro_price_fixed(), None);
+        assert_eq!(empty.spread_bps_fixed(None), None);
+        assert_eq!(empty.vwap_fixed(1, Side::Buy), None);
+        assert_eq!(empty.order_book_imbalance_fixed(5), SignedRatio::ZERO);
+
+        let book = ladder();
+        assert_eq!(book.vwap_fixed(0, Side::Buy), None);
+        assert_eq!(book.vwap_fixed(76, Side::Buy), None);
+        assert_eq!(book.order_book_imbalance_fixed(0), SignedRatio::ZERO);
+    }
+
+    #[test]
+    fn identical_books_produce_identical_fixed_values() {
+        let (a, b) = (ladder(), ladder());
+        assert_eq!(a.vwap_fixed(60, Side::Sell), b.vwap_fixed(60, Side::Sell));
+        assert_eq!(a.micro_price_fixed(), b.micro_price_fixed());
+        assert_eq!(
+            a.order_book_imbalance_fixed(3).to_scaled(18),
+            b.order_book_imbalance_fixed(3).to_scaled(18)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Integration tests for the coordinated `ShutdownCoordinator`.
+
+#[cfg(test)]
+mod tests_graceful_shutdown {
+    use orderbook_rs::{
+        BookExecutor, EventLogConfig, EventLogSink, ExecutorConfig, InMemoryJournal, OrderBook,
+        SequencerCommand, ShutdownCoordinator, ShutdownStage,
+    };
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::sync::{Arc, Mutex};
+
+    fn limit(price: u128, side: Side) -> SequencerCommand<()> {
+        SequencerCommand::AddOrder(OrderType::Standard {
+            id: Id::new_uuid(),
+            price: Price::new(price),
+            quantity: Quantity::new(1),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    fn record(log: &Log, entry: &str) {
+        log.lock().unwrap().push(entry.to_string());
+    }
+
+    #[test]
+    fn stages_run_in_order_after_queued_commands_execute() {
+        let mut executor = BookExecutor::new(ExecutorConfig::new());
+        executor
+            .spawn_book(OrderBook::<()>::new("BTC/USD"))
+            .expect("spawn");
+        for i in 0..50 {
+            // Not waited on: the drain stage must still execute them.
+            let _ = executor
+                .submit("BTC/USD", limit(1_000 - i, Side::Buy))
+                .expect("submit");
+        }
+
+        let log: Log = Arc::default();
+        let (stop, drain, flush, sink) = (log.clone(), log.clone(), log.clone(), log.clone());
+        let handle = ShutdownCoordinator::new()
+            .with_executor(executor)
+            .with_book(OrderBook::new("ETH/USD"))
+            .on_stop_intake("gateway", move || {
+                record(&stop, "stop");
+                Ok::<(), String>(())
+            })
+            .on_drain("outbox", move || {
+                record(&drain, "drain");
+                Ok::<(), String>(())
+            })
+            .on_flush("wal", move || {
+                record(&flush, "flush");
+                Ok::<(), String>(())
+            })
+            .with_journal("journal", Arc::new(InMemoryJournal::<()>::new()))
+            .with_snapshot_sink(move |symbol, package| {
+                record(&sink, &format!("snapshot {symbol}"));
+                package.validate()
+            })
+            .start()
+            .expect("start");
+        let report = handle.wait();
+
+        assert!(report.is_clean(), "{:?}", report.errors());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "stop",
+                "drain",
+                "flush",
+                "snapshot BTC/USD",
+                "snapshot ETH/USD"
+            ]
+        );
+        assert_eq!(report.books()["BTC/USD"].get_bids().len(), 50);
+        let btc = &report.snapshots()["BTC/USD"].snapshot;
+        assert_eq!(btc.bids.len(), 50);
+        assert_eq!(report.snapshots().len(), 2);
+        assert_eq!(report.into_books().len(), 2);
+    }
+
+    #[test]
+    fn failing_and_panicking_steps_are_recorded_and_later_stages_still_run() {
+        let flushed: Log = Arc::default();
+        let flushed_by_step = flushed.clone();
+        let report = ShutdownCoordinator::<()>::new()
+            .with_book(OrderBook::new("BTC/USD"))
+            .on_drain("broken-drain", || -> Result<(), String> {
+                panic!("queue poisoned")
+            })
+            .on_flush("full-disk", || Err("no space left"))
+            .on_flush("second-log", move || {
+                record(&flushed_by_step, "flushed");
+                Ok::<(), String>(())
+            })
+            .with_final_snapshot(5)
+            .run();
+
+        assert!(!report.is_clean());
+        let errors = report.errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].stage, ShutdownStage::DrainQueues);
+        assert_eq!(errors[0].step, "broken-drain");
+        assert!(errors[0].message.contains("queue poisoned"));
+        assert_eq!(errors[1].stage, ShutdownStage::FlushJournals);
+        assert_eq!(errors[1].message, "no space left");
+        assert_eq!(*flushed.lock().unwrap(), vec!["flushed"]);
+        assert!(report.snapshots().contains_key("BTC/USD"));
+    }
+
+    #[tokio::test]
+    async fn awaiting_the_handle_drives_async_drains_and_flushes_the_event_log() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let sink = Arc::new(EventLogSink::new(EventLogConfig::new(dir.path())).expect("sink"));
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.set_trade_listener(sink.trade_listener());
+        book.add_limit_order(Id::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book.submit_market_order(Id::new_uuid(), 2, Side::Buy)
+            .expect("trade");
+
+        // Stands in for a publisher's batch task on the caller's runtime.
+        let published: Log = Arc::default();
+        let task_log = published.clone();
+        let batch_task = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            record(&task_log, "batch flushed");
+        });
+
+        let path = sink.current_path();
+        let handle = ShutdownCoordinator::new()
+            .with_book(book)
+            .on_drain_async("publisher", move || async move {
+                batch_task.await.map_err(|err| err.to_string())
+            })
+            .with_event_log("event-log", sink)
+            .start()
+            .expect("start");
+        let report = handle.await;
+
+        assert!(report.is_clean(), "{:?}", report.errors());
+        assert_eq!(*published.lock().unwrap(), vec!["batch flushed"]);
+        assert!(report.snapshots().is_empty());
+        let logged = std::fs::read_to_string(path).expect("log file");
+        assert_eq!(logged.lines().count(), 1);
+    }
+}
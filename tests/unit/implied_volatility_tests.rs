@@ -254,3 +254,161 @@ fn test_iv_params_moneyness() {
     let otm_put = IVParams::put(110.0, 100.0, 0.25, 0.05);
     assert!(otm_put.is_otm());
 }
+
+#[test]
+fn test_iv_surface_from_book_manager() {
+    use orderbook_rs::{
+        BlackScholes, BookManager, BookManagerStd, IVError, IVSurface, OptionType, SurfaceInput,
+    };
+
+    let spot = 100.0;
+    let rate = 0.0;
+    let vol = 0.25;
+    let mut manager: BookManagerStd<()> = BookManagerStd::new();
+    let mut inputs = Vec::new();
+
+    // Quote each contract one cent either side of its 25%-vol fair value.
+    for &expiry in &[0.25, 0.5] {
+        for &strike in &[90.0, 100.0, 110.0] {
+            let symbol = format!("OPT-C-{strike}-{expiry}");
+            manager.add_book(&symbol).expect("new symbol");
+            let fair = BlackScholes::price(&IVParams::call(spot, strike, expiry, rate), vol);
+            let cents = (fair * 100.0).round() as u128;
+            let book = manager.get_book(&symbol).expect("book exists");
+            let _ =
+                book.add_limit_order(Id::new(), cents - 1, 10, Side::Buy, TimeInForce::Gtc, None);
+            let _ =
+                book.add_limit_order(Id::new(), cents + 1, 10, Side::Sell, TimeInForce::Gtc, None);
+            inputs.push(SurfaceInput::new(symbol, strike, expiry, OptionType::Call));
+        }
+    }
+    inputs.push(SurfaceInput::new("MISSING", 120.0, 0.5, OptionType::Call));
+
+    let config = IVConfig::default().with_price_scale(100.0);
+    let surface = IVSurface::from_books(
+        &manager,
+        &inputs,
+        spot,
+        rate,
+        PriceSource::MidPrice,
+        &config,
+    )
+    .expect("surface builds");
+
+    assert_eq!(surface.points().len(), 6);
+    assert_eq!(surface.expiries(), vec![0.25, 0.5]);
+    assert_eq!(surface.failures().len(), 1);
+    assert!(matches!(
+        surface.failures()[0].error,
+        IVError::BookNotFound { .. }
+    ));
+
+    // A flat 25% input surface stays flat between nodes and expiries.
+    let between = surface.iv_at(105.0, 0.375).expect("in range");
+    assert!((between.iv - vol).abs() < 0.01, "iv = {}", between.iv);
+    assert_eq!(between.quality, IVQuality::Interpolated);
+    assert!(!between.extrapolated);
+}
+
+#[test]
+fn test_greeks_snapshot_from_book_manager() {
+    use orderbook_rs::{
+        BlackScholes, BookManager, BookManagerStd, EventSerializer, GreeksSnapshot,
+        JsonEventSerializer, OptionType, SurfaceInput,
+    };
+
+    let spot = 100.0;
+    let rate = 0.01;
+    let vol = 0.3;
+    let mut manager: BookManagerStd<()> = BookManagerStd::new();
+    let mut inputs = Vec::new();
+    for (symbol, strike, option_type) in [
+        ("OPT-C-100", 100.0, OptionType::Call),
+        ("OPT-P-100", 100.0, OptionType::Put),
+    ] {
+        manager.add_book(symbol).expect("new symbol");
+        let fair = BlackScholes::price(&IVParams::new(spot, strike, 0.5, rate, option_type), vol);
+        let cents = (fair * 100.0).round() as u128;
+        let book = manager.get_book(symbol).expect("book exists");
+        let _ = book.add_limit_order(Id::new(), cents - 1, 10, Side::Buy, TimeInForce::Gtc, None);
+        let _ = book.add_limit_order(Id::new(), cents + 1, 10, Side::Sell, TimeInForce::Gtc, None);
+        inputs.push(SurfaceInput::new(symbol, strike, 0.5, option_type));
+    }
+    // A contract with an empty book is skipped, not fatal.
+    manager.add_book("OPT-C-150").expect("new symbol");
+    inputs.push(SurfaceInput::new("OPT-C-150", 150.0, 0.5, OptionType::Call));
+
+    let config = IVConfig::default().with_price_scale(100.0);
+    let (snapshot, failures) = GreeksSnapshot::from_books_with_failures(
+        &manager,
+        "OPT",
+        &inputs,
+        spot,
+        rate,
+        PriceSource::MidPrice,
+        &config,
+        1_000,
+    );
+
+    assert_eq!(snapshot.entries.len(), 2);
+    assert_eq!(snapshot.skipped, vec!["OPT-C-150".to_string()]);
+    assert_eq!(failures.len(), 1);
+
+    let call = snapshot.entry("OPT-C-100").expect("call solved");
+    let put = snapshot.entry("OPT-P-100").expect("put solved");
+    assert!((call.iv - vol).abs() < 0.01);
+    // Call and put deltas at the same strike differ by one.
+    assert!((call.greeks.delta - put.greeks.delta - 1.0).abs() < 0.01);
+    assert!(call.greeks.rho > 0.0 && put.greeks.rho < 0.0);
+
+    let total = snapshot.total();
+    assert!((total.gamma - call.greeks.gamma - put.greeks.gamma).abs() < 1e-12);
+
+    let serializer = JsonEventSerializer::new();
+    let bytes = serializer.serialize_greeks(&snapshot).expect("serializes");
+    let decoded = serializer.deserialize_greeks(&bytes).expect("deserializes");
+    assert_eq!(decoded.entries.len(), 2);
+    assert_eq!(decoded.skipped, snapshot.skipped);
+}
+
+#[test]
+fn test_greeks_snapshot_solves_american_contracts_on_the_tree() {
+    use orderbook_rs::{
+        Binomial, BookManager, BookManagerStd, ExerciseStyle, GreeksSnapshot, OptionType,
+        SurfaceInput,
+    };
+
+    let (spot, rate, vol) = (80.0, 0.05, 0.2);
+    let american =
+        IVParams::put(spot, 100.0, 1.0, rate).with_exercise_style(ExerciseStyle::American);
+    let config = IVConfig::default()
+        .with_price_scale(100.0)
+        .with_solver(SolverConfig::default().with_binomial_steps(100));
+
+    // Quoted around the early-exercise price, which Black-Scholes cannot
+    // reach for a deep in-the-money put.
+    let mut manager: BookManagerStd<()> = BookManagerStd::new();
+    manager.add_book("OPT-P-100").expect("new symbol");
+    let fair = Binomial::price(&american, vol, 100);
+    let cents = (fair * 100.0).round() as u128;
+    let book = manager.get_book("OPT-P-100").expect("book exists");
+    let _ = book.add_limit_order(Id::new(), cents - 1, 10, Side::Buy, TimeInForce::Gtc, None);
+    let _ = book.add_limit_order(Id::new(), cents + 1, 10, Side::Sell, TimeInForce::Gtc, None);
+    let inputs = [SurfaceInput::new("OPT-P-100", 100.0, 1.0, OptionType::Put)
+        .with_exercise_style(ExerciseStyle::American)];
+
+    let snapshot = GreeksSnapshot::from_books(
+        &manager,
+        "OPT",
+        &inputs,
+        spot,
+        rate,
+        PriceSource::MidPrice,
+        &config,
+        1_000,
+    );
+
+    let entry = snapshot.entry("OPT-P-100").expect("put solved");
+    assert_eq!(entry.exercise_style, ExerciseStyle::American);
+    assert_eq!(entry.greeks, Binomial::greeks(&american, entry.iv, 100));
+}
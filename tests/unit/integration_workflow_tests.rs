@@ -258,7 +258,9 @@ fn json_serializer_book_change_round_trip() {
         side: Side::Buy,
         price: 3000,
         quantity: 100,
+        order_count: 1,
         engine_seq: 0,
+        config_version: 0,
     };
 
     let bytes = serializer.serialize_book_change(&event);
@@ -511,7 +513,7 @@ fn mass_cancel_then_snapshot_shows_empty_book() {
 
 #[test]
 fn validation_prevents_invalid_then_valid_order_succeeds() {
-    let mut book = OrderBook::<()>::new("BTC/USD");
+    let book = OrderBook::<()>::new("BTC/USD");
     book.set_tick_size(10);
     book.set_lot_size(5);
 
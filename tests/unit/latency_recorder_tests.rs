@@ -0,0 +1,121 @@
+//! Integration tests for the feature-gated executor latency recorder.
+
+#[cfg(test)]
+mod tests_latency_recorder {
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::{
+        BookExecutor, Clock, ExecutorConfig, LatencyConfig, LatencyRecorder, OrderBook,
+        SequencerCommand,
+    };
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct ManualClock(AtomicU64);
+
+    impl Clock for ManualClock {
+        fn now_millis(&self) -> TimestampMs {
+            TimestampMs::new(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    fn limit(price: u128, side: Side) -> SequencerCommand<()> {
+        SequencerCommand::AddOrder(OrderType::Standard {
+            id: Id::new_uuid(),
+            price: Price::new(price),
+            quantity: Quantity::new(1),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    #[test]
+    fn manager_recorder_follows_it_into_the_executor() {
+        let mut manager: BookManagerStd<()> = BookManagerStd::new();
+        manager.add_book("BTC/USD").expect("add book");
+        manager.add_book("ETH/USD").expect("add book");
+        let recorder = manager
+            .enable_latency_recording(LatencyConfig::new())
+            .expect("recorder");
+        assert_eq!(manager.percentile_report(), Some(Default::default()));
+
+        let executor = manager.into_executor(ExecutorConfig::new()).expect("spawn");
+        let pending: Vec<_> = (0..100)
+            .map(|i| {
+                executor
+                    .submit("BTC/USD", limit(100 + i, Side::Sell))
+                    .expect("submit")
+            })
+            .chain(std::iter::once(
+                executor
+                    .submit("ETH/USD", limit(50, Side::Buy))
+                    .expect("submit"),
+            ))
+            .collect();
+        for result in pending {
+            result.recv().expect("result");
+        }
+
+        let report = executor.percentile_report().expect("recording enabled");
+        // The manager's handle shares the executor's histograms.
+        assert_eq!(recorder.percentile_report()["BTC/USD"].current.count, 100);
+        let btc = report["BTC/USD"].current;
+        assert_eq!(btc.count, 100);
+        assert!(btc.min_ns > 0);
+        assert!(btc.min_ns <= btc.p50_ns && btc.p50_ns <= btc.p99_ns);
+        assert!(btc.p99_ns <= btc.p999_ns && btc.p999_ns <= btc.max_ns);
+        assert_eq!(report["ETH/USD"].current.count, 1);
+    }
+
+    #[test]
+    fn executor_without_a_recorder_reports_nothing() {
+        let mut executor = BookExecutor::new(ExecutorConfig::new());
+        executor
+            .spawn_book(OrderBook::<()>::new("BTC/USD"))
+            .expect("spawn");
+        executor
+            .submit("BTC/USD", limit(100, Side::Buy))
+            .expect("submit")
+            .recv()
+            .expect("result");
+        assert!(executor.percentile_report().is_none());
+    }
+
+    #[test]
+    fn periodic_reset_closes_the_window_between_bursts() {
+        let clock = Arc::new(ManualClock(AtomicU64::new(0)));
+        let recorder = LatencyRecorder::with_clock(
+            LatencyConfig::new().with_reset_interval(Duration::from_millis(20)),
+            clock.clone(),
+        )
+        .expect("recorder");
+        let mut executor =
+            BookExecutor::new(ExecutorConfig::new().with_latency_recorder(recorder.clone()));
+        executor
+            .spawn_book(OrderBook::<()>::new("BTC/USD"))
+            .expect("spawn");
+
+        for i in 0..10 {
+            executor
+                .submit("BTC/USD", limit(100 + i, Side::Sell))
+                .expect("submit")
+                .recv()
+                .expect("result");
+        }
+        clock.0.store(40, Ordering::Relaxed);
+        executor
+            .submit("BTC/USD", limit(200, Side::Sell))
+            .expect("submit")
+            .recv()
+            .expect("result");
+
+        let latency = recorder.percentile_report()["BTC/USD"];
+        assert_eq!(latency.current.count, 1);
+        assert_eq!(latency.previous.expect("closed window").count, 10);
+    }
+}
@@ -0,0 +1,164 @@
+//! Integration tests for `OrderBook::level_aggregates` and the
+//! aggregate-only enriched snapshot.
+
+#[cfg(test)]
+mod tests_level_aggregates {
+    use orderbook_rs::{MetricFlags, OrderBook, PriceLevelChangedEvent};
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    fn two_sided_book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        for i in 0..5u128 {
+            book.add_limit_order(
+                Id::new_uuid(),
+                99 - i,
+                10 + i as u64,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("bid");
+            book.add_limit_order(
+                Id::new_uuid(),
+                101 + i,
+                20 + i as u64,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("ask");
+        }
+        book
+    }
+
+    #[test]
+    fn levels_are_best_first_and_truncated_to_depth() {
+        let book = two_sided_book();
+        book.add_limit_order(Id::new_uuid(), 99, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("second bid at 99");
+
+        let levels = book.level_aggregates(3);
+        let bid_prices: Vec<_> = levels.bids.iter().map(|l| l.price).collect();
+        let ask_prices: Vec<_> = levels.asks.iter().map(|l| l.price).collect();
+        assert_eq!(bid_prices, vec![99, 98, 97]);
+        assert_eq!(ask_prices, vec![101, 102, 103]);
+        assert_eq!(levels.bids[0].visible_quantity, 11);
+        assert_eq!(levels.bids[0].order_count, 2);
+    }
+
+    #[test]
+    fn iceberg_hidden_quantity_is_counted() {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_iceberg_order(
+            Id::new_uuid(),
+            100,
+            5,
+            45,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+
+        let level = book.level_aggregates(1).asks[0];
+        assert_eq!(level.visible_quantity, 5);
+        assert_eq!(level.hidden_quantity, 45);
+        assert_eq!(level.total_quantity(), 50);
+    }
+
+    #[test]
+    fn view_is_reused_until_the_book_changes() {
+        let book = two_sided_book();
+        let first = book.level_aggregates(5);
+        // A shallower read is served from the deeper cached view.
+        let shallow = book.level_aggregates(2);
+        assert_eq!(shallow.update_seq, first.update_seq);
+        assert_eq!(shallow.bids[..], first.bids[..2]);
+
+        let id = Id::new_uuid();
+        book.add_limit_order(id, 100, 7, Side::Buy, TimeInForce::Gtc, None)
+            .expect("new best bid");
+        let after_add = book.level_aggregates(5);
+        assert!(after_add.update_seq > first.update_seq);
+        assert_eq!(after_add.bids[0].price, 100);
+
+        book.cancel_order(id).expect("cancel");
+        let after_cancel = book.level_aggregates(5);
+        assert_eq!(after_cancel.bids, first.bids);
+
+        // A trade that empties the best ask is reflected too.
+        book.submit_market_order(Id::new_uuid(), 20, Side::Buy)
+            .expect("market buy");
+        assert_eq!(book.level_aggregates(1).asks[0].price, 102);
+    }
+
+    #[test]
+    fn aggregate_snapshot_metrics_match_the_full_snapshot() {
+        let book = two_sided_book();
+        for depth in [1, 3, 10] {
+            let full = book.enriched_snapshot(depth);
+            let aggregate = book.enriched_aggregate_snapshot(depth);
+            assert!(aggregate.bids.is_empty() && aggregate.asks.is_empty());
+            assert_eq!(aggregate.bid_levels, full.bid_levels);
+            assert_eq!(aggregate.ask_levels, full.ask_levels);
+            assert_eq!(aggregate.mid_price, full.mid_price);
+            assert_eq!(aggregate.spread_bps, full.spread_bps);
+            assert_eq!(aggregate.bid_depth_total, full.bid_depth_total);
+            assert_eq!(aggregate.ask_depth_total, full.ask_depth_total);
+            assert_eq!(aggregate.order_book_imbalance, full.order_book_imbalance);
+            assert_eq!(aggregate.order_count_imbalance, full.order_count_imbalance);
+            assert_eq!(aggregate.vwap_bid, full.vwap_bid);
+            assert_eq!(aggregate.vwap_ask, full.vwap_ask);
+        }
+
+        let mid_only = book.enriched_aggregate_snapshot_with_metrics(5, MetricFlags::MID_PRICE);
+        assert_eq!(mid_only.mid_price, Some(100.0));
+        assert_eq!(mid_only.bid_depth_total, 0);
+    }
+
+    #[test]
+    fn order_counts_reach_depth_consumers_and_level_events() {
+        let book = OrderBook::<()>::new("TEST");
+        let events: Arc<Mutex<Vec<PriceLevelChangedEvent>>> = Arc::default();
+        let sink = Arc::clone(&events);
+        book.set_price_level_listener(Arc::new(move |event| {
+            sink.lock().expect("events").push(event);
+        }));
+        book.add_limit_order(Id::new_uuid(), 100, 30, Side::Buy, TimeInForce::Gtc, None)
+            .expect("one large bid");
+        for _ in 0..3 {
+            book.add_limit_order(Id::new_uuid(), 101, 10, Side::Sell, TimeInForce::Gtc, None)
+                .expect("small ask");
+        }
+
+        let bid = book
+            .levels_with_cumulative_depth(Side::Buy)
+            .next()
+            .expect("bid level");
+        let ask = book
+            .levels_in_range(0, u128::MAX, Side::Sell)
+            .next()
+            .expect("ask level");
+        assert_eq!((bid.order_count, bid.average_order_size()), (1, Some(30.0)));
+        assert_eq!((ask.order_count, ask.average_order_size()), (3, Some(10.0)));
+        assert_eq!(
+            book.level_aggregates(1).asks[0].average_order_size(),
+            Some(10.0)
+        );
+
+        assert_eq!(book.order_book_imbalance(5), 0.0);
+        assert_eq!(book.order_count_imbalance(5), -0.5);
+        assert_eq!(book.enriched_snapshot(5).order_count_imbalance, -0.5);
+
+        book.submit_market_order(Id::new_uuid(), 15, Side::Buy)
+            .expect("market buy");
+        let counts: Vec<usize> = events
+            .lock()
+            .expect("events")
+            .iter()
+            .map(|event| event.order_count)
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2, 3, 2]);
+    }
+}
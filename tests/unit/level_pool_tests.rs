@@ -0,0 +1,91 @@
+//! Integration tests for the per-book pool of emptied price levels.
+
+#[cfg(test)]
+mod tests_level_pool {
+    use super::super::common::rest;
+    use orderbook_rs::{DEFAULT_LEVEL_POOL_SIZE, OrderBook};
+    use pricelevel::{Id, Side};
+
+    #[test]
+    fn cancelled_level_is_reused_when_its_price_returns() {
+        let book = OrderBook::<()>::new("TEST");
+        assert_eq!(book.level_pool_stats().max_size, DEFAULT_LEVEL_POOL_SIZE);
+
+        for _ in 0..10 {
+            let id = rest(&book, 100, 5, Side::Buy);
+            book.cancel_order(id).expect("cancel");
+        }
+
+        let stats = book.level_pool_stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 9);
+        assert_eq!(stats.recycled, 10);
+        assert_eq!(stats.pooled, 1);
+        assert_eq!(book.best_bid(), None);
+    }
+
+    #[test]
+    fn levels_emptied_by_matching_are_recycled() {
+        let book = OrderBook::<()>::new("TEST");
+        for price in 101..=103 {
+            rest(&book, price, 10, Side::Sell);
+        }
+        book.submit_market_order(Id::new_uuid(), 30, Side::Buy)
+            .expect("sweep");
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.level_pool_stats().recycled, 3);
+
+        rest(&book, 102, 7, Side::Sell);
+        assert_eq!(book.level_pool_stats().hits, 1);
+        assert_eq!(book.best_ask(), Some(102));
+        assert_eq!(book.side_total_quantity(Side::Sell), 7);
+    }
+
+    #[test]
+    fn reused_level_starts_with_fresh_statistics() {
+        let book = OrderBook::<()>::new("TEST");
+        let maker = rest(&book, 100, 10, Side::Sell);
+        book.submit_market_order(Id::new_uuid(), 10, Side::Buy)
+            .expect("fill");
+        assert!(book.get_order(maker).is_none());
+
+        rest(&book, 100, 4, Side::Sell);
+        assert_eq!(book.level_pool_stats().hits, 1);
+        let asks = book.get_asks();
+        let level = asks.get(&100).expect("level at 100");
+        assert_eq!(level.visible_quantity(), 4);
+        let stats = level.stats();
+        assert_eq!(stats.orders_added(), 1);
+        assert_eq!(stats.orders_executed(), 0);
+        assert_eq!(stats.quantity_executed(), 0);
+    }
+
+    #[test]
+    fn zero_max_size_disables_pooling() {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.set_level_pool_max_size(0);
+        for _ in 0..3 {
+            let id = rest(&book, 100, 5, Side::Buy);
+            book.cancel_order(id).expect("cancel");
+        }
+        let stats = book.level_pool_stats();
+        assert_eq!((stats.hits, stats.misses), (0, 3));
+        assert_eq!((stats.pooled, stats.recycled, stats.discarded), (0, 0, 3));
+    }
+
+    #[test]
+    fn pool_is_bounded_by_max_size() {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.set_level_pool_max_size(2);
+        let ids: Vec<Id> = (0..5).map(|i| rest(&book, 100 - i, 1, Side::Buy)).collect();
+        for id in ids {
+            book.cancel_order(id).expect("cancel");
+        }
+        let stats = book.level_pool_stats();
+        assert_eq!(stats.pooled, 2);
+        assert_eq!(stats.discarded, 3);
+
+        book.set_level_pool_max_size(1);
+        assert_eq!(book.level_pool_stats().pooled, 1);
+    }
+}
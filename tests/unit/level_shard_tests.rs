@@ -0,0 +1,105 @@
+//! Integration tests for price-range sharding of the price level maps: a
+//! sharded book must be indistinguishable from a single-structure book
+//! through the public API.
+
+#[cfg(test)]
+mod tests_level_shard {
+    use orderbook_rs::{LevelInfo, OrderBook};
+    use pricelevel::{Id, Side, TimeInForce};
+
+    /// Same order flow on a single-structure and a sharded book, with
+    /// prices spread across shard boundaries and sweeps through several
+    /// shards.
+    fn drive(book: &OrderBook<()>) -> Vec<(u128, u64)> {
+        let mut trades = Vec::new();
+        for i in 0..60u64 {
+            let offset = u128::from(i * 7 % 45);
+            book.add_limit_order(
+                Id::from_u64(i * 2),
+                1_000 - offset,
+                1 + i % 4,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("bid");
+            book.add_limit_order(
+                Id::from_u64(i * 2 + 1),
+                1_001 + offset,
+                1 + i % 3,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("ask");
+        }
+        for (id, side) in [(1_000, Side::Buy), (1_001, Side::Sell)] {
+            let result = book
+                .submit_market_order(Id::from_u64(id), 40, side)
+                .expect("sweep");
+            trades.extend(
+                result
+                    .trades()
+                    .as_vec()
+                    .iter()
+                    .map(|trade| (trade.price().as_u128(), trade.quantity().as_u64())),
+            );
+        }
+        book.cancel_order(Id::from_u64(100)).expect("cancel");
+        trades
+    }
+
+    fn depth(book: &OrderBook<()>, side: Side) -> Vec<(u128, u64, u64)> {
+        book.levels_with_cumulative_depth(side)
+            .map(|level: LevelInfo| (level.price, level.quantity, level.cumulative_depth))
+            .collect()
+    }
+
+    #[test]
+    fn sharded_book_matches_the_single_structure() {
+        let single = OrderBook::<()>::new("TEST");
+        let sharded = OrderBook::<()>::with_level_shards("TEST", 10, 128);
+
+        assert_eq!(drive(&sharded), drive(&single));
+        assert_eq!(sharded.best_bid(), single.best_bid());
+        assert_eq!(sharded.best_ask(), single.best_ask());
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(depth(&sharded, side), depth(&single, side));
+        }
+        assert_eq!(
+            sharded.levels_in_range(985, 1_020, Side::Sell).count(),
+            single.levels_in_range(985, 1_020, Side::Sell).count()
+        );
+        assert_eq!(
+            sharded.create_snapshot(usize::MAX).bids.len(),
+            single.create_snapshot(usize::MAX).bids.len()
+        );
+
+        let occupancy = sharded.level_shard_occupancy(Side::Sell);
+        assert_eq!(occupancy.len(), 128);
+        assert!(occupancy.iter().filter(|&&levels| levels > 0).count() > 1);
+        assert_eq!(
+            occupancy.iter().sum::<usize>(),
+            depth(&single, Side::Sell).len()
+        );
+        assert_eq!(single.level_shard_occupancy(Side::Sell).len(), 1);
+    }
+
+    #[test]
+    fn snapshot_restore_keeps_the_shard_layout() {
+        let source = OrderBook::<()>::new("TEST");
+        drive(&source);
+        let package = source
+            .create_snapshot_package(usize::MAX)
+            .expect("snapshot package");
+
+        let mut restored = OrderBook::<()>::with_level_shards("TEST", 10, 128);
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.level_shard_occupancy(Side::Buy).len(), 128);
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(depth(&restored, side), depth(&source, side));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Integration tests for immediate-or-cancel and fill-or-kill limit
+//! matches, and the public `can_fill_fully` feasibility query.
+
+#[cfg(test)]
+mod tests_limit_time_in_force {
+    use orderbook_rs::{
+        CancelReason, OrderBook, OrderBookError, OrderStateTracker, OrderStatus, STPMode,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    const ALICE: Hash32 = Hash32::new([1; 32]);
+    const BOB: Hash32 = Hash32::new([2; 32]);
+    const CAROL: Hash32 = Hash32::new([3; 32]);
+
+    fn seeded_book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_limit_order_with_user(
+            Id::from_u64(1),
+            100,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            ALICE,
+            None,
+        )
+        .expect("alice ask");
+        book.add_limit_order_with_user(
+            Id::from_u64(2),
+            101,
+            5,
+            Side::Sell,
+            TimeInForce::Gtc,
+            BOB,
+            None,
+        )
+        .expect("bob ask");
+        book
+    }
+
+    #[test]
+    fn can_fill_fully_respects_limit_lot_and_stp() {
+        let book = seeded_book();
+        assert!(book.can_fill_fully(15, Side::Buy, None));
+        assert!(!book.can_fill_fully(16, Side::Buy, None));
+        assert!(book.can_fill_fully(10, Side::Buy, Some(100)));
+        assert!(!book.can_fill_fully(11, Side::Buy, Some(100)));
+        assert!(!book.can_fill_fully(1, Side::Sell, None));
+
+        // STP withholds Alice's own ask from Alice, but not from Carol.
+        book.set_stp_mode(STPMode::CancelMaker);
+        assert!(!book.can_fill_fully_with_user(10, Side::Buy, None, ALICE));
+        assert!(book.can_fill_fully_with_user(5, Side::Buy, None, ALICE));
+        assert!(book.can_fill_fully_with_user(15, Side::Buy, None, CAROL));
+
+        // A lot size turns a budget below one lot into dust.
+        book.set_lot_size(2);
+        assert!(!book.can_fill_fully(15, Side::Buy, None));
+        assert!(book.can_fill_fully(14, Side::Buy, None));
+    }
+
+    #[test]
+    fn fill_or_kill_match_never_partially_executes() {
+        let mut book = seeded_book();
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book.set_stp_mode(STPMode::CancelMaker);
+
+        let killed = Id::from_u64(3);
+        let err = book
+            .match_limit_order_with_tif(killed, 10, Side::Buy, 101, TimeInForce::Fok, ALICE)
+            .expect_err("only 5 reachable for alice");
+        assert!(matches!(
+            err,
+            OrderBookError::InsufficientLiquidity {
+                requested: 10,
+                available: 5,
+                ..
+            }
+        ));
+        assert!(book.last_trade_price().is_none());
+        assert!(book.get_order(Id::from_u64(1)).is_some(), "maker untouched");
+        assert_eq!(
+            book.order_state_tracker().and_then(|t| t.get(killed)),
+            Some(OrderStatus::Cancelled {
+                filled_quantity: 0,
+                reason: CancelReason::InsufficientLiquidity,
+            })
+        );
+
+        let filled = book
+            .match_limit_order_with_tif(
+                Id::from_u64(4),
+                15,
+                Side::Buy,
+                101,
+                TimeInForce::Fok,
+                CAROL,
+            )
+            .expect("carol fills in full");
+        assert_eq!(filled.remaining_quantity().as_u64(), 0);
+        assert_eq!(book.best_ask(), None);
+    }
+
+    #[test]
+    fn immediate_matches_return_the_remainder_without_resting() {
+        let book = seeded_book();
+        for (id, time_in_force) in [(3, TimeInForce::Ioc), (4, TimeInForce::Gtc)] {
+            let result = book
+                .match_limit_order_with_tif(
+                    Id::from_u64(id),
+                    4,
+                    Side::Buy,
+                    99,
+                    time_in_force,
+                    Hash32::zero(),
+                )
+                .expect("nothing crosses");
+            assert_eq!(result.remaining_quantity().as_u64(), 4);
+            assert!(book.get_order(Id::from_u64(id)).is_none());
+        }
+
+        let result = book
+            .match_limit_order_with_user(Id::from_u64(5), 12, Side::Buy, 100, BOB)
+            .expect("partial");
+        assert_eq!(result.remaining_quantity().as_u64(), 2);
+        assert_eq!(book.best_bid(), None);
+
+        // `add_order` agrees with the query: an IOC remainder is cancelled,
+        // an infeasible FOK is killed before trading.
+        assert!(!book.can_fill_fully(6, Side::Buy, Some(101)));
+        let fok = book.add_limit_order(Id::from_u64(7), 101, 6, Side::Buy, TimeInForce::Fok, None);
+        assert!(matches!(
+            fok,
+            Err(OrderBookError::InsufficientLiquidity { .. })
+        ));
+        assert_eq!(book.best_ask(), Some(101));
+        let ioc = book.add_limit_order(Id::from_u64(6), 101, 6, Side::Buy, TimeInForce::Ioc, None);
+        assert!(matches!(
+            ioc,
+            Err(OrderBookError::InsufficientLiquidity { .. })
+        ));
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.best_bid(), None);
+    }
+}
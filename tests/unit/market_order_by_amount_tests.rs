@@ -212,7 +212,7 @@ fn test_sell_empty_book_errors_with_notional_variant() {
 
 #[test]
 fn test_quote_notional_carried_through_to_listener() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     let captured_notional = Arc::new(AtomicU64::new(0));
     let captured_count = Arc::new(AtomicUsize::new(0));
     let n_clone = captured_notional.clone();
@@ -241,7 +241,7 @@ fn test_quote_notional_carried_through_to_listener() {
 
 #[test]
 fn test_fee_schedule_applies_to_notional_path() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     book.set_fee_schedule(Some(FeeSchedule::new(0, 5))); // 5 bps taker
     let captured_taker_fee = Arc::new(AtomicU64::new(0));
     let f_clone = captured_taker_fee.clone();
@@ -290,7 +290,7 @@ fn test_existing_base_qty_path_unaffected_by_refactor() {
 fn test_quote_notional_populated_on_base_qty_path() {
     // The new `quote_notional` field is populated for both market-order
     // paths so consumers see it uniformly.
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     let captured = Arc::new(AtomicU64::new(0));
     let c_clone = captured.clone();
     let listener: TradeListener = Arc::new(move |tr: &TradeResult| {
@@ -0,0 +1,135 @@
+//! Integration tests for market order protection (max sweep levels and
+//! max slippage from the touch).
+
+#[cfg(test)]
+mod tests_market_protection {
+    use orderbook_rs::{
+        BookConfig, MarketProtection, OrderBook, OrderBookBuilder, OrderBookError,
+        OrderStateTracker, OrderStatus, ProtectionRemainder,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn ladder(book: &OrderBook<()>, side: Side, prices: &[u128]) {
+        for &price in prices {
+            book.add_limit_order(Id::new(), price, 10, side, TimeInForce::Gtc, None)
+                .expect("resting order");
+        }
+    }
+
+    #[test]
+    fn level_cap_cancels_the_remainder() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        ladder(&book, Side::Sell, &[100, 101, 102, 103]);
+        book.reload_config(
+            BookConfig::new().with_market_protection(MarketProtection::new().with_max_levels(2)),
+        )
+        .expect("valid protection");
+        assert_eq!(book.market_protection_price(Side::Buy), Some(101));
+
+        let taker = Id::new();
+        let result = book
+            .submit_market_order(taker, 50, Side::Buy)
+            .expect("partial fill");
+
+        assert_eq!(result.executed_quantity().expect("executed").as_u64(), 20);
+        assert_eq!(result.remaining_quantity().as_u64(), 30);
+        assert_eq!(book.best_ask(), Some(102));
+        assert_eq!(book.best_bid(), None);
+        assert!(book.get_order(taker).is_none());
+    }
+
+    #[test]
+    fn slippage_cap_rests_the_remainder_at_the_protection_price() {
+        let mut book = OrderBook::<()>::new("BTC/USD");
+        book.set_order_state_tracker(OrderStateTracker::new());
+        ladder(&book, Side::Buy, &[1_000, 995, 990, 980]);
+        book.reload_config(
+            BookConfig::new().with_market_protection(
+                MarketProtection::new()
+                    .with_max_slippage_bps(100)
+                    .with_remainder(ProtectionRemainder::RestAsLimit),
+            ),
+        )
+        .expect("valid protection");
+
+        let taker = Id::new();
+        let user = Hash32::new([7; 32]);
+        let result = book
+            .submit_market_order_with_user(taker, 50, Side::Sell, user)
+            .expect("partial fill");
+
+        // 1% below the 1_000 touch: 1_000, 995 and 990 trade, 980 does not.
+        assert_eq!(result.remaining_quantity().as_u64(), 20);
+        assert_eq!(book.best_bid(), Some(980));
+        assert_eq!(book.best_ask(), Some(990));
+        let resting = book.get_order(taker).expect("remainder rests");
+        assert_eq!(resting.price().as_u128(), 990);
+        assert_eq!(resting.visible_quantity().as_u64(), 20);
+        assert_eq!(resting.user_id(), user);
+        assert_eq!(
+            book.order_state_tracker().and_then(|t| t.get(taker)),
+            Some(OrderStatus::PartiallyFilled {
+                original_quantity: 50,
+                filled_quantity: 30,
+            })
+        );
+
+        // A resting id cannot be reused by a protected market order that
+        // would rest, and nothing trades.
+        let err = book.submit_market_order(taker, 5, Side::Buy);
+        assert!(matches!(err, Err(OrderBookError::DuplicateOrderId { .. })));
+        assert_eq!(book.best_ask(), Some(990));
+    }
+
+    #[test]
+    fn notional_orders_stop_at_the_protection_price_and_never_rest() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        ladder(&book, Side::Sell, &[100, 110, 120]);
+        book.reload_config(
+            BookConfig::new().with_market_protection(
+                MarketProtection::new()
+                    .with_max_levels(2)
+                    .with_max_slippage_bps(500)
+                    .with_remainder(ProtectionRemainder::RestAsLimit),
+            ),
+        )
+        .expect("valid protection");
+        // The 5% slippage bound (105) is tighter than the second level.
+        assert_eq!(book.market_protection_price(Side::Buy), Some(105));
+
+        let taker = Id::new();
+        let result = book
+            .submit_market_order_by_amount(taker, 10_000, Side::Buy)
+            .expect("partial fill");
+
+        assert_eq!(result.executed_value().expect("value"), 1_000);
+        assert_eq!(book.best_ask(), Some(110));
+        assert!(book.get_order(taker).is_none());
+    }
+
+    #[test]
+    fn protection_is_validated_built_and_snapshotted() {
+        let invalid = BookConfig::new().with_market_protection(MarketProtection::new());
+        assert!(matches!(
+            OrderBook::<()>::new("BTC/USD").reload_config(invalid),
+            Err(OrderBookError::InvalidConfiguration { .. })
+        ));
+
+        let protection = MarketProtection::new()
+            .with_max_levels(3)
+            .with_remainder(ProtectionRemainder::RestAsLimit);
+        let book = OrderBookBuilder::<()>::new("BTC/USD")
+            .market_protection(protection)
+            .build()
+            .expect("valid builder");
+        assert_eq!(book.market_protection(), Some(protection));
+        assert_eq!(book.market_protection_price(Side::Buy), None);
+
+        let package = book.create_snapshot_package(10).expect("package");
+        let mut restored = OrderBook::<()>::new("BTC/USD");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.market_protection(), Some(protection));
+    }
+}
@@ -0,0 +1,226 @@
+//! Integration tests for `MarketReplay` over recorded market data.
+
+#[cfg(test)]
+mod tests_market_replay {
+    use orderbook_rs::{
+        CsvMarketData, MarketDataEvent, MarketReplay, MarketReplayError, OrderBook, StrategyAction,
+        StrategyOutcome,
+    };
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn limit(id: Id, price: u128, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id,
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    const L3: &str = "\
+timestamp,kind,side,price,quantity,order_id
+# opening book
+1000,add,buy,99,10,1
+1000,add,sell,101,10,2
+1005,add,sell,102,5,3
+1010,execute,,,4,2
+1020,modify,,100,8,1
+1030,cancel,,,,3
+";
+
+    #[test]
+    fn csv_l3_rebuilds_the_book() {
+        let mut replay = MarketReplay::new(OrderBook::<()>::new("TEST"), 0);
+        let report = replay
+            .run(CsvMarketData::new(L3.as_bytes()), |_, _| Vec::new())
+            .expect("replay");
+
+        assert_eq!(report.events_applied, 6);
+        assert_eq!(report.events_rejected, 0);
+        assert_eq!(report.last_timestamp, 1030);
+        assert_eq!(replay.now(), 1030);
+
+        let book = replay.book();
+        assert_eq!(book.best_bid(), Some(100));
+        assert_eq!(book.best_ask(), Some(101));
+        assert_eq!(book.total_quantity_at_price(100, Side::Buy), Some(8));
+        assert_eq!(book.total_quantity_at_price(101, Side::Sell), Some(6));
+        assert!(book.get_order(Id::sequential(3)).is_none());
+        assert!(book.audit().is_empty());
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let data = "1000,add,buy,99,10,1\n1001,add,buy,abc,10,2\n";
+        let events: Vec<_> = CsvMarketData::new(data.as_bytes()).collect();
+        assert!(events[0].is_ok());
+        assert_eq!(
+            events[1],
+            Err(MarketReplayError::Parse {
+                line: 2,
+                message: "invalid price 'abc'".to_string(),
+            })
+        );
+
+        assert!(matches!(
+            MarketDataEvent::from_csv_line("1000,cancel", 7),
+            Err(MarketReplayError::Parse { line: 7, .. })
+        ));
+        assert!(matches!(
+            MarketDataEvent::from_csv_line("1000,trade,buy,1,1,1", 1),
+            Err(MarketReplayError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn out_of_order_events_stop_the_replay() {
+        let mut replay = MarketReplay::new(OrderBook::<()>::new("TEST"), 0);
+        let events = [
+            MarketDataEvent::Level {
+                timestamp: 20,
+                side: Side::Buy,
+                price: 99,
+                quantity: 5,
+            },
+            MarketDataEvent::Level {
+                timestamp: 10,
+                side: Side::Buy,
+                price: 99,
+                quantity: 6,
+            },
+        ];
+        let result = replay.run(events.into_iter().map(Ok), |_, _| Vec::new());
+        assert_eq!(
+            result,
+            Err(MarketReplayError::OutOfOrder {
+                timestamp: 10,
+                current: 20,
+            })
+        );
+        assert_eq!(
+            replay.book().total_quantity_at_price(99, Side::Buy),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn l2_levels_track_aggregated_size() {
+        let mut replay = MarketReplay::new(OrderBook::<()>::new("TEST"), 0);
+        for (timestamp, price, quantity) in [(1, 101, 10), (2, 102, 7), (3, 101, 4), (4, 102, 0)] {
+            replay
+                .step(&MarketDataEvent::Level {
+                    timestamp,
+                    side: Side::Sell,
+                    price,
+                    quantity,
+                })
+                .expect("step");
+        }
+
+        let book = replay.book();
+        assert_eq!(book.total_quantity_at_price(101, Side::Sell), Some(4));
+        assert_eq!(book.total_quantity_at_price(102, Side::Sell), None);
+        assert_eq!(replay.report().events_applied, 4);
+    }
+
+    #[test]
+    fn strategy_orders_arrive_after_latency_and_trade_with_recorded_flow() {
+        let mut replay = MarketReplay::new(OrderBook::<()>::new("TEST"), 5);
+        let strategy_id = Id::sequential(1_000);
+        let events = [
+            MarketDataEvent::Add {
+                timestamp: 100,
+                order_id: Id::sequential(1),
+                side: Side::Sell,
+                price: 101,
+                quantity: 10,
+            },
+            // Cancels the resting ask before the strategy's buy arrives at 105.
+            MarketDataEvent::Cancel {
+                timestamp: 103,
+                order_id: Id::sequential(1),
+            },
+            MarketDataEvent::Add {
+                timestamp: 110,
+                order_id: Id::sequential(2),
+                side: Side::Sell,
+                price: 100,
+                quantity: 3,
+            },
+        ];
+
+        let report = replay
+            .run(events.into_iter().map(Ok), |book, event| match event {
+                MarketDataEvent::Add { timestamp: 100, .. } => {
+                    assert_eq!(book.best_ask(), Some(101));
+                    vec![StrategyAction::Submit(limit(
+                        strategy_id,
+                        101,
+                        5,
+                        Side::Buy,
+                    ))]
+                }
+                _ => Vec::new(),
+            })
+            .expect("replay");
+
+        assert_eq!(report.strategy_actions, 1);
+        assert_eq!(report.events_applied, 3);
+
+        let outcomes = replay.take_outcomes();
+        assert_eq!(outcomes.len(), 1);
+        let StrategyOutcome::Submitted {
+            arrival,
+            order_id,
+            result,
+        } = &outcomes[0]
+        else {
+            panic!("expected a submit outcome");
+        };
+        assert_eq!((*arrival, *order_id), (105, strategy_id));
+        let ack = result.as_ref().expect("accepted");
+        assert!(ack.match_result.trades().is_empty());
+        assert!(ack.is_resting());
+
+        // The recorded sell at 110 crossed the resting strategy bid.
+        let book = replay.book();
+        let resting = book.get_order(strategy_id).expect("still resting");
+        assert_eq!(resting.visible_quantity().as_u64(), 2);
+        assert!(book.best_ask().is_none());
+        assert!(book.audit().is_empty());
+    }
+
+    #[test]
+    fn pending_actions_flush_at_the_end() {
+        let mut replay = MarketReplay::new(OrderBook::<()>::new("TEST"), 50);
+        replay
+            .step(&MarketDataEvent::Level {
+                timestamp: 10,
+                side: Side::Buy,
+                price: 99,
+                quantity: 5,
+            })
+            .expect("step");
+        let order_id = Id::sequential(7);
+        replay.schedule(StrategyAction::Submit(limit(order_id, 98, 1, Side::Buy)));
+        replay.schedule(StrategyAction::Cancel(order_id));
+        assert!(replay.book().get_order(order_id).is_none());
+
+        replay.flush();
+        assert_eq!(replay.now(), 60);
+        let outcomes = replay.take_outcomes();
+        assert!(matches!(
+            outcomes[1],
+            StrategyOutcome::Cancelled {
+                arrival: 60,
+                result: Ok(true),
+                ..
+            }
+        ));
+        assert!(replay.book().get_order(order_id).is_none());
+    }
+}
@@ -384,7 +384,7 @@ fn mass_cancel_result_display() {
 
 #[test]
 fn cancel_by_user_on_stp_enabled_book() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     book.set_stp_mode(STPMode::CancelTaker);
 
     let user_a = uid(1);
@@ -647,7 +647,7 @@ fn cancel_all_emits_price_level_changed_events() {
     let event_count = Arc::new(AtomicUsize::new(0));
     let counter = event_count.clone();
 
-    let mut book: OrderBook<()> = OrderBook::new("EVENTS");
+    let book: OrderBook<()> = OrderBook::new("EVENTS");
     book.set_price_level_listener(Arc::new(move |_event| {
         counter.fetch_add(1, Ordering::Relaxed);
     }));
@@ -0,0 +1,177 @@
+//! Integration tests for the midpoint (dark) segment: execution at the lit
+//! midpoint, minimum execution sizes, limits, self-trade prevention and
+//! snapshot round trips.
+
+#[cfg(test)]
+mod tests_midpoint {
+    use orderbook_rs::{
+        MidpointOrder, OrderBook, OrderBookError, RejectCode, STPMode, TradeResult,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    /// A book quoted 100 / 110, so the midpoint is 105.
+    fn quoted_book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_limit_order(Id::from_u64(1), 100, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_limit_order(Id::from_u64(2), 110, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book
+    }
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    #[test]
+    fn executes_at_the_midpoint_and_stays_out_of_the_lit_book() {
+        let book = quoted_book();
+        let trades: Arc<Mutex<Vec<TradeResult>>> = Arc::default();
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().expect("trades").push(trade.clone());
+        }));
+
+        assert!(
+            book.submit_midpoint_order(MidpointOrder::new(Id::from_u64(10), Side::Sell, 30))
+                .expect("rests")
+                .is_none()
+        );
+        assert_eq!(book.best_ask(), Some(110));
+        let trade = book
+            .submit_midpoint_order(MidpointOrder::new(Id::from_u64(11), Side::Buy, 50))
+            .expect("crosses")
+            .expect("executed");
+        assert!(trade.dark);
+        assert_eq!(trade.fills.len(), 1);
+        assert_eq!(trade.fills[0].price, 105);
+        assert_eq!(trade.fills[0].quantity, 30);
+        assert_eq!(trade.fills[0].maker.order_id, Id::from_u64(10));
+
+        assert!(book.last_trade_price().is_none());
+        assert!(book.midpoint_orders(Side::Sell).is_empty());
+        assert_eq!(book.midpoint_orders(Side::Buy)[0].quantity, 20);
+        let published = trades.lock().expect("trades");
+        assert_eq!(published.len(), 1);
+        assert!(published[0].dark);
+        assert_eq!(published[0].fills, trade.fills);
+        drop(published);
+
+        assert!(matches!(
+            book.submit_midpoint_order(MidpointOrder::new(Id::from_u64(1), Side::Buy, 1)),
+            Err(OrderBookError::DuplicateOrderId { .. })
+        ));
+        assert!(
+            book.cancel_midpoint_order(Id::from_u64(11))
+                .is_some_and(|order| order.quantity == 20)
+        );
+        assert!(book.cancel_midpoint_order(Id::from_u64(11)).is_none());
+    }
+
+    #[test]
+    fn minimum_execution_size_and_limits_hold_orders_back() {
+        let book = quoted_book();
+        let big =
+            MidpointOrder::new(Id::from_u64(10), Side::Sell, 100).with_min_execution_quantity(40);
+        assert!(book.submit_midpoint_order(big).expect("rests").is_none());
+        assert!(
+            book.submit_midpoint_order(MidpointOrder::new(Id::from_u64(11), Side::Buy, 10))
+                .expect("too small")
+                .is_none()
+        );
+        let err = book
+            .submit_midpoint_order(
+                MidpointOrder::new(Id::from_u64(12), Side::Buy, 5).with_min_execution_quantity(6),
+            )
+            .expect_err("minimum above quantity");
+        assert!(matches!(
+            err,
+            OrderBookError::InvalidMidpointQuantity {
+                quantity: 5,
+                min_execution_quantity: 6,
+                ..
+            }
+        ));
+        assert_eq!(err.code(), RejectCode::InvalidMidpointQuantity);
+        assert!(matches!(
+            book.submit_midpoint_order(MidpointOrder::new(Id::from_u64(14), Side::Buy, 0)),
+            Err(OrderBookError::InvalidMidpointQuantity { quantity: 0, .. })
+        ));
+
+        let capped = MidpointOrder::new(Id::from_u64(13), Side::Buy, 50).with_limit_price(104);
+        assert!(book.submit_midpoint_order(capped).expect("rests").is_none());
+        assert!(book.cross_midpoint_orders().is_empty());
+
+        // A lower ask brings the midpoint within the buy limit.
+        book.cancel_order(Id::from_u64(2)).expect("cancel ask");
+        book.add_limit_order(Id::from_u64(3), 108, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        assert_eq!(book.midpoint_price(), Some(104));
+        let trades = book.cross_midpoint_orders();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].fills[0].price, 104);
+        assert_eq!(trades[0].fills[0].quantity, 50);
+        assert_eq!(book.midpoint_orders(Side::Sell)[0].quantity, 50);
+        assert_eq!(book.midpoint_orders(Side::Buy)[0].id, Id::from_u64(11));
+    }
+
+    #[test]
+    fn self_trade_prevention_skips_same_group_contras() {
+        let book = quoted_book();
+        book.set_stp_mode(STPMode::CancelTaker);
+        book.set_account_group(user(1), 7);
+        book.set_account_group(user(2), 7);
+        assert!(matches!(
+            book.submit_midpoint_order(MidpointOrder::new(Id::from_u64(9), Side::Sell, 10)),
+            Err(OrderBookError::MissingUserId { .. })
+        ));
+        for (id, owner) in [(10, user(1)), (11, user(3))] {
+            book.submit_midpoint_order(
+                MidpointOrder::new(Id::from_u64(id), Side::Sell, 10).with_user_id(owner),
+            )
+            .expect("rests");
+        }
+
+        let trade = book
+            .submit_midpoint_order(
+                MidpointOrder::new(Id::from_u64(12), Side::Buy, 20).with_user_id(user(2)),
+            )
+            .expect("crosses")
+            .expect("executed");
+        assert_eq!(trade.fills.len(), 1);
+        assert_eq!(trade.fills[0].maker.user_id, user(3));
+        assert_eq!(book.midpoint_orders(Side::Sell)[0].id, Id::from_u64(10));
+        assert_eq!(book.midpoint_orders(Side::Buy)[0].quantity, 10);
+    }
+
+    #[test]
+    fn resting_midpoint_orders_survive_a_snapshot_round_trip() {
+        let original = OrderBook::<()>::new("TEST");
+        for id in [10, 11] {
+            original
+                .submit_midpoint_order(MidpointOrder::new(Id::from_u64(id), Side::Buy, id))
+                .expect("rests without a midpoint");
+        }
+        original
+            .submit_midpoint_order(MidpointOrder::new(Id::from_u64(12), Side::Sell, 7))
+            .expect("rests without a midpoint");
+
+        let package = original
+            .create_snapshot_package(10)
+            .expect("snapshot package");
+        assert_eq!(package.midpoint_orders.len(), 3);
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(
+            restored.midpoint_orders(Side::Buy),
+            original.midpoint_orders(Side::Buy)
+        );
+        assert_eq!(
+            restored.midpoint_orders(Side::Sell),
+            original.midpoint_orders(Side::Sell)
+        );
+    }
+}
@@ -1,41 +1,105 @@
+mod account_group_tests;
+#[cfg(feature = "arrow")]
+mod arrow_export_tests;
 mod atomic_postonly_fok_tests;
+mod audit_tests;
+mod batch_submit_tests;
 mod book_coverage_tests;
+mod book_executor_tests;
 mod book_manager_cross_cancel_tests;
+mod book_stats_tests;
+mod builder_tests;
+mod cancel_on_disconnect_tests;
+mod capacity_tests;
+mod circuit_breaker_tests;
 mod clock_determinism_tests;
 mod common;
+mod config_reload_tests;
+mod consolidated_book_tests;
+mod dead_mans_switch_tests;
+mod depth_mode_tests;
+mod depth_totals_tests;
+mod depth_view_tests;
+mod duplicate_order_id_window_tests;
 mod engine_seq_monotonic_tests;
+mod event_log_tests;
 mod evict_expired_tests;
+mod execution_algo_tests;
+mod expiry_wheel_tests;
+mod extra_fields_tests;
 #[cfg(feature = "journal")]
 mod filejournal_edge_case_tests;
+#[cfg(feature = "deterministic")]
+mod fixed_point_tests;
+mod graceful_shutdown_tests;
 mod implied_volatility_tests;
 mod integration_workflow_tests;
 mod kill_switch_tests;
+#[cfg(feature = "latency")]
+mod latency_recorder_tests;
+mod level_aggregates_tests;
+mod level_pool_tests;
+mod level_shard_tests;
+mod limit_time_in_force_tests;
 mod manager_coverage_tests;
 mod market_order_by_amount_tests;
+mod market_protection_tests;
+mod market_replay_tests;
 mod mass_cancel_determinism_tests;
 mod mass_cancel_tests;
 mod matching_coverage_tests;
 mod matching_coverage_tests_extended;
+mod midpoint_tests;
+mod model_check;
 mod modifications_coverage_tests;
 mod modify_atomic_tests;
+mod modify_order_tests;
 mod mutation_failure_atomicity_tests;
 mod operations_coverage_tests;
 mod operations_coverage_tests_extended;
+mod order_ack_tests;
+mod order_age_tests;
+mod order_iteration_tests;
 mod order_state_tests;
+mod order_tag_tests;
+mod pegged_auto_reprice_tests;
+mod post_only_slide_tests;
+mod priority_class_tests;
 mod private_coverage_tests;
 mod props_quantity_update_priority;
+mod queue_peek_tests;
+mod rate_limit_tests;
+mod read_txn_tests;
+mod reduce_order_tests;
+mod reference_price_tests;
 mod reject_reason_tests;
 mod replay_config_tests;
 mod replay_coverage_tests;
 #[cfg(feature = "journal")]
 mod replay_determinism;
+mod replenishment_event_tests;
 #[cfg(feature = "special_orders")]
 mod repricing_determinism_tests;
+mod reserve_throttle_tests;
 mod restore_user_orders_determinism_tests;
 mod risk_layer_tests;
 mod sequencer_types_tests;
+mod shared_book_reconfiguration_tests;
+mod simulation_runner_tests;
+mod simulation_tests;
+mod smart_order_router_tests;
 mod snapshot_restore_tests;
 #[cfg(feature = "special_orders")]
 mod special_order_restore_tests;
+mod speed_bump_tests;
+mod stop_trigger_tests;
+mod stress_harness_tests;
+mod trade_amendment_tests;
+mod trade_fill_enrichment_tests;
+mod trading_session_tests;
+mod trailing_state_tests;
 mod two_tranche_conservation_tests;
+mod user_block_tests;
+mod user_queries_tests;
 mod validation_tests;
+mod venue_snapshot_tests;
@@ -122,7 +122,7 @@ fn post_only_replace_that_would_cross_leaves_original_untouched() {
 
 #[test]
 fn modify_outside_price_band_leaves_original_untouched() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     // 100 bps = 1% band around the mid.
     book.set_risk_config(RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::Mid));
     let acct = account(31);
@@ -172,7 +172,7 @@ fn modify_outside_price_band_leaves_original_untouched() {
 
 #[test]
 fn modify_over_max_notional_leaves_original_untouched() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     // Notional ceiling 1_000 per account.
     book.set_risk_config(RiskConfig::new().with_max_notional_per_account(1_000));
     let acct = account(32);
@@ -214,7 +214,7 @@ fn modify_over_max_notional_leaves_original_untouched() {
 
 #[test]
 fn modify_at_max_open_orders_succeeds() {
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     // Account may hold at most 2 resting orders.
     book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(2));
     let acct = account(33);
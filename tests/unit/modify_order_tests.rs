@@ -0,0 +1,200 @@
+//! Tests for the unified `modify_order` API.
+
+#[cfg(test)]
+mod tests_modify_order {
+    use orderbook_rs::{OrderBook, OrderBookError, OrderModification, RejectCode};
+    use pricelevel::{Hash32, Id, OrderType, OrderUpdate, Price, Quantity, Side, TimeInForce};
+    use std::num::NonZeroU64;
+
+    fn bid(book: &OrderBook<()>, id: u64, price: u128, quantity: u64) {
+        book.add_limit_order(
+            Id::from_u64(id),
+            price,
+            quantity,
+            Side::Buy,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("resting bid");
+    }
+
+    fn first_maker(book: &OrderBook<()>, quantity: u64) -> Id {
+        let fill = book
+            .submit_market_order(Id::from_u64(99), quantity, Side::Sell)
+            .expect("sell");
+        fill.trades().as_vec()[0].maker_order_id()
+    }
+
+    fn rejected(result: Result<Option<impl Sized>, OrderBookError>) -> OrderBookError {
+        match result {
+            Err(err) => err,
+            Ok(_) => panic!("modification was accepted"),
+        }
+    }
+
+    #[test]
+    fn quantity_changes_follow_update_quantity_priority() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        bid(&book, 1, 100, 10);
+        bid(&book, 2, 100, 10);
+
+        book.modify_order(OrderModification::new(Id::from_u64(1)).with_quantity(6))
+            .expect("decrease");
+        assert_eq!(first_maker(&book, 1), Id::from_u64(1));
+
+        book.modify_order(OrderModification::new(Id::from_u64(1)).with_quantity(20))
+            .expect("increase");
+        assert_eq!(first_maker(&book, 1), Id::from_u64(2));
+    }
+
+    #[test]
+    fn price_and_time_in_force_changes_requeue_the_order() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        bid(&book, 1, 100, 10);
+        bid(&book, 2, 100, 10);
+
+        let modified = book
+            .modify_order(
+                OrderModification::new(Id::from_u64(1)).with_time_in_force(TimeInForce::Day),
+            )
+            .expect("tif change")
+            .expect("resting");
+        assert_eq!(modified.time_in_force(), TimeInForce::Day);
+        assert_eq!(modified.visible_quantity().as_u64(), 10);
+        assert_eq!(first_maker(&book, 1), Id::from_u64(2));
+
+        let update = OrderUpdate::UpdatePriceAndQuantity {
+            order_id: Id::from_u64(1),
+            new_price: Price::new(101),
+            new_quantity: Quantity::new(4),
+        };
+        let modified = book
+            .modify_order(update.try_into().expect("modification"))
+            .expect("reprice")
+            .expect("resting");
+        assert_eq!(modified.price().as_u128(), 101);
+        assert_eq!(modified.time_in_force(), TimeInForce::Day);
+        assert_eq!(book.best_bid(), Some(101));
+        assert_eq!(first_maker(&book, 1), Id::from_u64(1));
+    }
+
+    #[test]
+    fn hidden_tranches_of_icebergs_and_reserves_are_adjustable() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            5,
+            20,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        book.add_order(OrderType::ReserveOrder {
+            id: Id::from_u64(2),
+            price: Price::new(105),
+            visible_quantity: Quantity::new(10),
+            hidden_quantity: Quantity::new(20),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: pricelevel::TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(4),
+            replenish_amount: NonZeroU64::new(6),
+            auto_replenish: true,
+            extra_fields: (),
+        })
+        .expect("reserve");
+
+        let iceberg = book
+            .modify_order(
+                OrderModification::new(Id::from_u64(1))
+                    .with_quantity(3)
+                    .with_hidden_quantity(9),
+            )
+            .expect("iceberg change")
+            .expect("resting");
+        assert_eq!(
+            (
+                iceberg.visible_quantity().as_u64(),
+                iceberg.hidden_quantity().as_u64()
+            ),
+            (3, 9)
+        );
+
+        let reserve = book
+            .modify_order(OrderModification::new(Id::from_u64(2)).with_hidden_quantity(0))
+            .expect("reserve change")
+            .expect("resting");
+        assert!(matches!(*reserve, OrderType::ReserveOrder { .. }));
+        assert_eq!(
+            (
+                reserve.visible_quantity().as_u64(),
+                reserve.hidden_quantity().as_u64()
+            ),
+            (10, 0)
+        );
+    }
+
+    #[test]
+    fn unsupported_modifications_are_rejected_without_side_effects() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        bid(&book, 1, 100, 10);
+        book.add_post_only_order(Id::from_u64(2), 102, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("post-only ask");
+        let id = Id::from_u64(1);
+
+        let err = rejected(book.modify_order(OrderModification::new(id)));
+        assert!(matches!(err, OrderBookError::NothingToModify { order_id } if order_id == id));
+        assert_eq!(err.code(), RejectCode::NothingToModify);
+        let err = rejected(book.modify_order(OrderModification::new(id).with_price(100)));
+        assert!(matches!(err, OrderBookError::NothingToModify { .. }));
+
+        let err = rejected(book.modify_order(OrderModification::new(id).with_quantity(0)));
+        assert!(matches!(err, OrderBookError::ZeroQuantity { order_id } if order_id == id));
+        assert_eq!(err.code(), RejectCode::ZeroQuantity);
+
+        let err = rejected(book.modify_order(OrderModification::new(id).with_hidden_quantity(5)));
+        assert!(matches!(err, OrderBookError::NoHiddenTranche { order_id } if order_id == id));
+        assert_eq!(err.code(), RejectCode::NoHiddenTranche);
+
+        let err = rejected(
+            book.modify_order(OrderModification::new(id).with_time_in_force(TimeInForce::Ioc)),
+        );
+        assert!(matches!(
+            err,
+            OrderBookError::ImmediateTifOnResting {
+                order_id,
+                time_in_force: TimeInForce::Ioc,
+            } if order_id == id
+        ));
+        assert_eq!(err.code(), RejectCode::ImmediateTifOnResting);
+
+        let err = OrderModification::try_from(OrderUpdate::Cancel { order_id: id })
+            .expect_err("a cancel is not a modification");
+        assert!(matches!(err, OrderBookError::NotAModification { order_id } if order_id == id));
+        assert_eq!(err.code(), RejectCode::NotAModification);
+        assert!(matches!(
+            book.modify_order(OrderModification::new(Id::from_u64(2)).with_price(100)),
+            Err(OrderBookError::PriceCrossing { .. })
+        ));
+        assert!(matches!(
+            book.modify_order(OrderModification::new(Id::from_u64(9)).with_price(1)),
+            Ok(None)
+        ));
+
+        book.engage_kill_switch();
+        assert!(matches!(
+            book.modify_order(OrderModification::new(id).with_price(101)),
+            Err(OrderBookError::KillSwitchActive)
+        ));
+        book.release_kill_switch();
+
+        assert_eq!(book.best_bid(), Some(100));
+        assert_eq!(book.best_ask(), Some(102));
+        let order = book.get_order(id).expect("resting");
+        assert_eq!(order.visible_quantity().as_u64(), 10);
+        assert_eq!(order.time_in_force(), TimeInForce::Gtc);
+    }
+}
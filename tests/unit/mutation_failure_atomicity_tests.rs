@@ -58,7 +58,10 @@ mod tests_mutation_failure_atomicity {
             .add_limit_order(Id::from_u64(3), 100, 10, Side::Buy, TimeInForce::Gtc, None)
             .expect_err("residual could not rest; submit must be rejected pre-trade");
         assert!(
-            matches!(err, OrderBookError::InvalidOperation { .. }),
+            matches!(
+                err,
+                OrderBookError::LevelQuantityOverflow { price: 100, .. }
+            ),
             "expected the typed capacity rejection, got {err:?}"
         );
 
@@ -76,7 +79,7 @@ mod tests_mutation_failure_atomicity {
     /// maker unchanged on rejection.
     #[test]
     fn update_quantity_enforces_lot_size_on_projected_state() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("LOTU");
+        let book: OrderBook<()> = DefaultOrderBook::new("LOTU");
         book.set_lot_size(5);
         book.add_limit_order(Id::from_u64(1), 100, 10, Side::Buy, TimeInForce::Gtc, None)
             .expect("seed maker");
@@ -103,7 +106,7 @@ mod tests_mutation_failure_atomicity {
     /// `UpdateQuantity` enforces min/max order size on the projected state.
     #[test]
     fn update_quantity_enforces_max_order_size() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("MAXU");
+        let book: OrderBook<()> = DefaultOrderBook::new("MAXU");
         book.set_max_order_size(50);
         book.add_limit_order(Id::from_u64(1), 100, 10, Side::Buy, TimeInForce::Gtc, None)
             .expect("seed maker");
@@ -126,7 +129,7 @@ mod tests_mutation_failure_atomicity {
     /// projected notional and leaves the maker unchanged on rejection.
     #[test]
     fn update_quantity_enforces_risk_notional() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("RSKU");
+        let book: OrderBook<()> = DefaultOrderBook::new("RSKU");
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(2_000));
         let user = pricelevel::Hash32::new([9u8; 32]);
         book.add_limit_order_with_user(
@@ -159,7 +162,7 @@ mod tests_mutation_failure_atomicity {
     /// a follow-up admission that only fits after the decrease succeeds.
     #[test]
     fn update_quantity_releases_risk_notional_on_success() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("RSKD");
+        let book: OrderBook<()> = DefaultOrderBook::new("RSKD");
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(2_000));
         let user = pricelevel::Hash32::new([9u8; 32]);
         book.add_limit_order_with_user(
@@ -307,7 +310,7 @@ mod tests_mutation_failure_review_gaps {
     /// before the increase is now rejected.
     #[test]
     fn update_quantity_books_risk_notional_on_increase() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("RSKI");
+        let book: OrderBook<()> = DefaultOrderBook::new("RSKI");
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(2_000));
         let user = pricelevel::Hash32::new([9u8; 32]);
         book.add_limit_order_with_user(
@@ -376,7 +379,7 @@ mod tests_mutation_failure_review_gaps {
             })
             .expect_err("resizing an expired maker must be rejected");
         assert!(
-            matches!(err, OrderBookError::InvalidOperation { .. }),
+            matches!(err, OrderBookError::OrderExpired { .. }),
             "expected the expiry rejection, got {err:?}"
         );
         let maker = book.get_order(Id::from_u64(1)).expect("maker still rests");
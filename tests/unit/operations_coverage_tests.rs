@@ -45,9 +45,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                // They are converted to T::default() when retrieved from storage
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, extra_fields);
             }
             _ => panic!("Expected Standard order type"),
         }
@@ -112,8 +110,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, extra_fields);
             }
             _ => panic!("Expected IcebergOrder type"),
         }
@@ -178,8 +175,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, extra_fields);
             }
             _ => panic!("Expected PostOnly order type"),
         }
@@ -243,8 +239,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, complex_extra_fields);
             }
             _ => panic!("Expected Standard order type"),
         }
@@ -279,8 +274,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, empty_extra_fields);
             }
             _ => panic!("Expected IcebergOrder type"),
         }
@@ -327,8 +321,7 @@ mod tests {
         let order1 = book.get_order(order_id1).unwrap();
         match order1.as_ref() {
             OrderType::Standard { extra_fields, .. } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*extra_fields, TestExtraFields::default());
+                assert_eq!(*extra_fields, extra_fields1);
             }
             _ => panic!("Expected Standard order type"),
         }
@@ -336,8 +329,7 @@ mod tests {
         let order2 = book.get_order(order_id2).unwrap();
         match order2.as_ref() {
             OrderType::PostOnly { extra_fields, .. } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*extra_fields, TestExtraFields::default());
+                assert_eq!(*extra_fields, extra_fields2);
             }
             _ => panic!("Expected PostOnly order type"),
         }
@@ -379,8 +371,7 @@ mod tests {
                 extra_fields: order_extra,
                 ..
             } => {
-                // Note: extra_fields are not preserved in the current implementation
-                assert_eq!(*order_extra, TestExtraFields::default());
+                assert_eq!(*order_extra, unicode_extra_fields);
             }
             _ => panic!("Expected PostOnly order type"),
         }
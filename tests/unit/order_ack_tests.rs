@@ -0,0 +1,129 @@
+//! Integration tests for `OrderBook::add_order_with_ack`.
+
+#[cfg(test)]
+mod tests_order_ack {
+    use orderbook_rs::{OrderBook, OrderBookError};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn limit(price: u128, quantity: u64, side: Side, time_in_force: TimeInForce) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::new_uuid(),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn resting_order_reports_queue_position() {
+        let book = OrderBook::<()>::new("TEST");
+
+        let first = book
+            .add_order_with_ack(limit(100, 10, Side::Buy, TimeInForce::Gtc))
+            .expect("rest");
+        assert!(first.match_result.trades().is_empty());
+        let resting = first.resting.expect("rests");
+        assert_eq!(resting.price, 100);
+        assert_eq!(resting.side, Side::Buy);
+        assert_eq!(resting.remaining_quantity, 10);
+        assert_eq!(resting.queue_position, 0);
+        assert!(!resting.converted_to_limit);
+
+        let second = book
+            .add_order_with_ack(limit(100, 4, Side::Buy, TimeInForce::Gtc))
+            .expect("rest");
+        assert_eq!(second.resting.expect("rests").queue_position, 1);
+
+        let other_level = book
+            .add_order_with_ack(limit(99, 4, Side::Buy, TimeInForce::Gtc))
+            .expect("rest");
+        assert_eq!(other_level.resting.expect("rests").queue_position, 0);
+    }
+
+    #[test]
+    fn partial_fill_reports_fills_and_remainder() {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_limit_order(Id::new_uuid(), 100, 6, Side::Sell, TimeInForce::Gtc, None)
+            .expect("maker");
+
+        let ack = book
+            .add_order_with_ack(limit(101, 10, Side::Buy, TimeInForce::Gtc))
+            .expect("match");
+        assert_eq!(ack.match_result.trades().len(), 1);
+        assert_eq!(ack.match_result.remaining_quantity().as_u64(), 4);
+        let resting = ack.resting.expect("remainder rests");
+        assert_eq!((resting.price, resting.remaining_quantity), (101, 4));
+        assert_eq!(book.best_bid(), Some(101));
+    }
+
+    #[test]
+    fn fully_filled_order_does_not_rest() {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_limit_order(Id::new_uuid(), 100, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("maker");
+
+        let ack = book
+            .add_order_with_ack(limit(100, 10, Side::Buy, TimeInForce::Gtc))
+            .expect("match");
+        assert!(!ack.is_resting());
+        assert!(ack.match_result.is_complete());
+        assert_eq!(ack.match_result.trades().len(), 1);
+    }
+
+    #[test]
+    fn unfilled_ioc_is_rejected() {
+        let book = OrderBook::<()>::new("TEST");
+        let result = book.add_order_with_ack(limit(100, 10, Side::Buy, TimeInForce::Ioc));
+        assert!(matches!(
+            result,
+            Err(OrderBookError::InsufficientLiquidity { .. })
+        ));
+    }
+
+    #[test]
+    fn iceberg_remainder_includes_hidden_quantity() {
+        let book = OrderBook::<()>::new("TEST");
+        let ack = book
+            .add_order_with_ack(OrderType::IcebergOrder {
+                id: Id::new_uuid(),
+                price: Price::new(100),
+                visible_quantity: Quantity::new(5),
+                hidden_quantity: Quantity::new(15),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(0),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .expect("rest");
+        assert_eq!(ack.resting.expect("rests").remaining_quantity, 20);
+    }
+
+    #[test]
+    fn market_to_limit_remainder_is_flagged_converted() {
+        let book = OrderBook::<()>::new("TEST");
+        book.add_limit_order(Id::new_uuid(), 100, 3, Side::Sell, TimeInForce::Gtc, None)
+            .expect("maker");
+
+        let ack = book
+            .add_order_with_ack(OrderType::MarketToLimit {
+                id: Id::new_uuid(),
+                price: Price::new(100),
+                quantity: Quantity::new(10),
+                side: Side::Buy,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(0),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .expect("match");
+        assert_eq!(ack.match_result.trades().len(), 1);
+        let resting = ack.resting.expect("remainder rests");
+        assert!(resting.converted_to_limit);
+        assert_eq!((resting.price, resting.remaining_quantity), (100, 7));
+    }
+}
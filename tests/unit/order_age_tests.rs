@@ -0,0 +1,92 @@
+//! Integration tests for resting order ages and quote-lifetime
+//! distributions.
+
+#[cfg(test)]
+mod tests_order_age {
+    use orderbook_rs::{Clock, OrderBook, StubClock};
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::Arc;
+
+    fn book() -> OrderBook<()> {
+        let clock = Arc::new(StubClock::with_step(10_000, 5)) as Arc<dyn Clock>;
+        OrderBook::with_clock("TEST", clock)
+    }
+
+    #[test]
+    fn ages_grow_with_time_in_the_queue() {
+        let book = book();
+        for (id, price, quantity) in [(1, 100, 4), (2, 100, 6), (3, 99, 10)] {
+            book.add_limit_order(
+                Id::from_u64(id),
+                price,
+                quantity,
+                Side::Buy,
+                TimeInForce::Gtc,
+                None,
+            )
+            .expect("bid");
+        }
+
+        let first = book.order_age(Id::from_u64(1)).expect("resting");
+        let second = book.order_age(Id::from_u64(2)).expect("resting");
+        assert!(first > second && second > 0);
+        assert_eq!(book.order_age(Id::from_u64(9)), None);
+
+        let ages = book.level_ages(Side::Buy, 5);
+        assert_eq!(
+            ages.iter()
+                .map(|age| (age.price, age.order_count, age.quantity))
+                .collect::<Vec<_>>(),
+            vec![(100, 2, 10), (99, 1, 10)]
+        );
+        assert!(ages[0].oldest_age_ms > ages[0].average_age_ms);
+        assert_eq!(ages[1].oldest_age_ms, ages[1].average_age_ms);
+        assert!(book.level_ages(Side::Sell, 5).is_empty());
+
+        let weighted = book.age_weighted_depth(Side::Buy, 5);
+        let (youngest, oldest) = (ages[1].oldest_age_ms, ages[0].oldest_age_ms);
+        assert!(weighted > 20 * u128::from(youngest));
+        assert!(weighted < 20 * u128::from(oldest + 100));
+        assert_eq!(book.age_weighted_depth(Side::Sell, 5), 0);
+    }
+
+    #[test]
+    fn cancels_and_fills_feed_the_lifetime_distributions() {
+        let book = book();
+        book.add_limit_order(Id::from_u64(1), 101, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("untracked ask");
+        book.set_lifetime_tracking(true);
+        assert!(book.lifetime_tracking());
+        for id in 2..=4 {
+            book.add_limit_order(Id::from_u64(id), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+                .expect("ask");
+        }
+
+        book.cancel_order(Id::from_u64(2)).expect("cancel");
+        book.cancel_order(Id::from_u64(1))
+            .expect("cancel untracked");
+        // Fills id 3 and half of id 4; the taker never rests.
+        book.submit_market_order(Id::from_u64(10), 8, Side::Buy)
+            .expect("fill");
+
+        let lifetimes = book.quote_lifetimes();
+        assert_eq!(lifetimes.cancelled.count, 1);
+        assert_eq!(lifetimes.filled.count, 1);
+        assert_eq!(lifetimes.cancel_to_fill_ratio(), Some(1.0));
+        let filled = lifetimes.filled.min_ms.expect("recorded");
+        assert!(filled > 0);
+        assert_eq!(lifetimes.filled.percentile_ms(1.0), Some(filled));
+        assert_eq!(lifetimes.filled.buckets.iter().sum::<u64>(), 1);
+
+        // Turning tracking off forgets the resting id 4.
+        book.set_lifetime_tracking(false);
+        book.set_lifetime_tracking(true);
+        book.cancel_order(Id::from_u64(4)).expect("cancel");
+        assert_eq!(book.quote_lifetimes().cancelled.count, 1);
+
+        book.reset_stats();
+        let reset = book.quote_lifetimes();
+        assert_eq!(reset.cancelled.count, 0);
+        assert_eq!(reset.filled.max_ms, None);
+    }
+}
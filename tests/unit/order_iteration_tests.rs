@@ -0,0 +1,119 @@
+//! Integration tests for lazy and paged order iteration
+//! (`OrderBook::iter_all_orders`, `iter_orders_on_side`, `orders_page`).
+
+#[cfg(test)]
+mod tests_order_iteration {
+    use super::super::common::rest;
+    use orderbook_rs::{OrderBook, OrderCursor};
+    use pricelevel::{Id, Side};
+
+    #[test]
+    fn side_iterator_walks_best_price_first() {
+        let book = OrderBook::<()>::new("TEST");
+        let b99 = rest(&book, 99, 1, Side::Buy);
+        let b100 = rest(&book, 100, 1, Side::Buy);
+        let b100_second = rest(&book, 100, 2, Side::Buy);
+        let a102 = rest(&book, 102, 1, Side::Sell);
+        let a101 = rest(&book, 101, 1, Side::Sell);
+
+        let bids: Vec<Id> = book
+            .iter_orders_on_side(Side::Buy)
+            .map(|o| o.id())
+            .collect();
+        assert_eq!(bids, vec![b100, b100_second, b99]);
+
+        let asks: Vec<Id> = book
+            .iter_orders_on_side(Side::Sell)
+            .map(|o| o.id())
+            .collect();
+        assert_eq!(asks, vec![a101, a102]);
+
+        let all: Vec<Id> = book.iter_all_orders().map(|o| o.id()).collect();
+        assert_eq!(all, vec![b100, b100_second, b99, a101, a102]);
+        assert_eq!(book.get_all_orders().len(), 5);
+    }
+
+    #[test]
+    fn iterator_is_lazy_and_short_circuits() {
+        let book = OrderBook::<()>::new("TEST");
+        for price in 1..=100 {
+            rest(&book, price, 1, Side::Sell);
+        }
+        let first_three: Vec<u128> = book
+            .iter_orders_on_side(Side::Sell)
+            .take(3)
+            .map(|o| o.price().as_u128())
+            .collect();
+        assert_eq!(first_three, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn pages_cover_side_exactly_once() {
+        let book = OrderBook::<()>::new("TEST");
+        let mut expected = Vec::new();
+        for price in (90..100).rev() {
+            expected.push(rest(&book, price, 1, Side::Buy));
+            expected.push(rest(&book, price, 2, Side::Buy));
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<OrderCursor> = None;
+        let mut pages = 0;
+        loop {
+            let page = book.orders_page(Side::Buy, cursor.as_ref(), 3);
+            assert!(page.orders.len() <= 3);
+            seen.extend(page.orders.iter().map(|o| o.id()));
+            pages += 1;
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected);
+        assert_eq!(pages, 7);
+    }
+
+    #[test]
+    fn exact_fit_page_reports_no_next_cursor() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 101, 1, Side::Sell);
+        rest(&book, 102, 1, Side::Sell);
+
+        let page = book.orders_page(Side::Sell, None, 2);
+        assert_eq!(page.orders.len(), 2);
+        assert!(page.next_cursor.is_none());
+
+        let empty = OrderBook::<()>::new("EMPTY").orders_page(Side::Sell, None, 10);
+        assert!(empty.orders.is_empty());
+        assert!(empty.next_cursor.is_none());
+    }
+
+    #[test]
+    fn page_resumes_when_cursor_order_is_cancelled() {
+        let book = OrderBook::<()>::new("TEST");
+        let first = rest(&book, 101, 1, Side::Sell);
+        let second = rest(&book, 101, 1, Side::Sell);
+        let third = rest(&book, 102, 1, Side::Sell);
+
+        let page = book.orders_page(Side::Sell, None, 1);
+        assert_eq!(page.orders[0].id(), first);
+        let cursor = page.next_cursor.expect("more orders");
+
+        book.cancel_order(first).expect("cancel");
+        let rest_page = book.orders_page(Side::Sell, Some(&cursor), 10);
+        let ids: Vec<Id> = rest_page.orders.iter().map(|o| o.id()).collect();
+        assert!(ids.contains(&second));
+        assert!(ids.contains(&third));
+        assert!(!ids.contains(&first));
+        assert!(rest_page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn zero_limit_returns_cursor_unchanged() {
+        let book = OrderBook::<()>::new("TEST");
+        rest(&book, 101, 1, Side::Sell);
+        let page = book.orders_page(Side::Sell, None, 0);
+        assert!(page.orders.is_empty());
+        assert!(page.next_cursor.is_none());
+    }
+}
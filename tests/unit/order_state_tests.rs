@@ -94,7 +94,7 @@ mod tests_order_state {
 
     #[test]
     fn rejected_order_tick_size() {
-        let mut book = book_with_tracker("TEST");
+        let book = book_with_tracker("TEST");
         book.set_tick_size(100);
 
         let id = Id::new_uuid();
@@ -0,0 +1,128 @@
+//! Tests for order tags and tag-based mass cancels.
+
+#[cfg(test)]
+mod tests_order_tag {
+    use orderbook_rs::{
+        CancelReason, OrderBook, OrderBookError, OrderStateTracker, OrderStatus, OrderTag,
+        RejectCode,
+    };
+    use pricelevel::{
+        Hash32, Id, OrderType, OrderUpdate, Price, Quantity, Side, TimeInForce, TimestampMs,
+    };
+
+    fn bid(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn ids<const N: usize>(ids: [u64; N]) -> Vec<Id> {
+        ids.into_iter().map(Id::from_u64).collect()
+    }
+
+    fn layered_book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        for (id, price, layer) in [(1, 99, 1), (2, 98, 2), (3, 97, 1), (4, 96, 2)] {
+            book.add_order_with_tag(bid(id, price, 5), layer)
+                .expect("quote");
+        }
+        book
+    }
+
+    #[test]
+    fn cancel_by_tag_sweeps_one_layer() {
+        let book = layered_book();
+        book.add_limit_order(Id::from_u64(5), 95, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("untagged");
+        assert_eq!(book.orders_by_tag(1), ids([1, 3]));
+        assert_eq!(book.order_tag(Id::from_u64(2)), Some(OrderTag::Number(2)));
+
+        let result = book.cancel_by_tag(1);
+        assert_eq!(result.cancelled_order_ids(), ids([1, 3]).as_slice());
+        assert!(book.orders_by_tag(1).is_empty());
+        assert_eq!(book.best_bid(), Some(98));
+        assert_eq!(book.orders_by_tag(2), ids([2, 4]));
+        assert!(book.cancel_by_tag("missing").is_empty());
+    }
+
+    #[test]
+    fn tags_follow_the_order_through_fills_and_modifications() {
+        let book = layered_book();
+
+        // A fill removes the tag with the order.
+        book.submit_market_order(Id::from_u64(90), 5, Side::Sell)
+            .expect("sell");
+        assert_eq!(book.orders_by_tag(1), ids([3]));
+        assert_eq!(book.order_tag(Id::from_u64(1)), None);
+
+        // A price change re-adds the order and keeps its tag.
+        book.update_order(OrderUpdate::UpdatePrice {
+            order_id: Id::from_u64(2),
+            new_price: Price::new(94),
+        })
+        .expect("reprice");
+        assert_eq!(book.orders_by_tag(2), ids([4, 2]));
+
+        // Retagging moves the order between layers; untagging drops it.
+        assert!(matches!(book.tag_order(Id::from_u64(2), "hedge"), Ok(true)));
+        assert_eq!(book.orders_by_tag(2), ids([4]));
+        assert_eq!(
+            book.untag_order(Id::from_u64(2)),
+            Some(OrderTag::from("hedge"))
+        );
+        assert!(book.orders_by_tag("hedge").is_empty());
+        assert!(matches!(book.tag_order(Id::from_u64(1), 1), Ok(false)));
+
+        // An order that fills in full on arrival is never tagged.
+        book.add_limit_order(Id::from_u64(6), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        let ack = book.add_order_with_tag(bid(7, 100, 5), 3).expect("taker");
+        assert!(ack.resting.is_none());
+        assert!(book.orders_by_tag(3).is_empty());
+
+        let long = "x".repeat(OrderTag::MAX_LABEL_LEN + 1);
+        let err = book
+            .add_order_with_tag(bid(8, 90, 5), long.as_str())
+            .expect_err("label too long");
+        assert!(matches!(
+            err,
+            OrderBookError::OrderTagTooLong { len, max }
+                if len == OrderTag::MAX_LABEL_LEN + 1 && max == OrderTag::MAX_LABEL_LEN
+        ));
+        assert_eq!(err.code(), RejectCode::OrderTagTooLong);
+        assert_eq!(book.get_order(Id::from_u64(8)), None);
+    }
+
+    #[test]
+    fn tags_survive_snapshot_restore_and_report_their_cancel_reason() {
+        let mut book = layered_book();
+        book.set_order_state_tracker(OrderStateTracker::new());
+        let package = book.create_snapshot_package(10).expect("package");
+        assert_eq!(package.order_tags.len(), 4);
+
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.orders_by_tag(1), ids([1, 3]));
+        assert_eq!(restored.orders_by_tag(2), ids([2, 4]));
+
+        let _ = book.cancel_by_tag(2);
+        assert!(matches!(
+            book.order_status(Id::from_u64(2)),
+            Some(OrderStatus::Cancelled {
+                reason: CancelReason::MassCancelByTag,
+                ..
+            })
+        ));
+        let _ = book.cancel_all_orders();
+        assert!(book.orders_by_tag(1).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+//! Integration tests for automatic re-pricing of pegged orders on book
+//! changes, and its batching.
+//!
+//! Gated on `special_orders`, the only configuration in which pegged
+//! orders are tracked.
+
+#[cfg(all(test, feature = "special_orders"))]
+mod tests_pegged_auto_reprice {
+    use orderbook_rs::OrderBook;
+    use pricelevel::{
+        Hash32, Id, OrderType, PegReferenceType, Price, Quantity, Side, TimeInForce, TimestampMs,
+    };
+
+    fn peg(
+        book: &OrderBook<()>,
+        price: u128,
+        side: Side,
+        reference: PegReferenceType,
+        offset: i64,
+    ) -> Id {
+        let id = Id::new();
+        book.add_order(OrderType::PeggedOrder {
+            id,
+            price: Price::new(price),
+            quantity: Quantity::new(5),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            reference_price_offset: offset,
+            reference_price_type: reference,
+            extra_fields: (),
+        })
+        .expect("pegged order");
+        id
+    }
+
+    fn price_of(book: &OrderBook<()>, id: Id) -> u128 {
+        book.get_order(id).expect("resting").price().as_u128()
+    }
+
+    fn quoted_book() -> (OrderBook<()>, Id, Id) {
+        let book = OrderBook::<()>::new("BTC/USD");
+        let bid = Id::new();
+        let ask = Id::new();
+        book.add_limit_order(bid, 100, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_limit_order(ask, 110, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        (book, bid, ask)
+    }
+
+    #[test]
+    fn pegs_follow_the_touch_only_when_enabled() {
+        let (book, bid, _) = quoted_book();
+        let buy = peg(&book, 100, Side::Buy, PegReferenceType::BestBid, -2);
+        let sell = peg(&book, 110, Side::Sell, PegReferenceType::BestAsk, 0);
+        assert!(!book.auto_reprice_pegged());
+
+        // Off by default: the pegs keep their submitted prices.
+        book.add_limit_order(Id::new(), 104, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("new best bid");
+        assert_eq!(price_of(&book, buy), 100);
+
+        // Enabled: the next mutating call re-prices before it returns.
+        book.set_auto_reprice_pegged(true);
+        book.cancel_order(bid).expect("cancel");
+        assert_eq!(price_of(&book, buy), 102);
+        assert_eq!(price_of(&book, sell), 110);
+
+        book.add_limit_order(Id::new(), 108, 3, Side::Sell, TimeInForce::Gtc, None)
+            .expect("new best ask");
+        assert_eq!(price_of(&book, sell), 108);
+
+        // A trade that clears the best bid leaves the peg at the touch; it
+        // re-prices off itself once, in the same call, and stops there.
+        book.submit_market_order(Id::new(), 10, Side::Sell)
+            .expect("sweep the 104 bid");
+        assert_eq!(price_of(&book, buy), 100);
+        assert_eq!(book.best_bid(), Some(100));
+    }
+
+    #[test]
+    fn last_trade_pegs_move_with_executions() {
+        let (book, _, _) = quoted_book();
+        book.set_auto_reprice_pegged(true);
+        let buy = peg(&book, 90, Side::Buy, PegReferenceType::LastTrade, -5);
+        assert_eq!(price_of(&book, buy), 90, "no trade yet");
+
+        book.submit_market_order(Id::new(), 2, Side::Sell)
+            .expect("trade at 100");
+        assert_eq!(book.last_trade_price(), Some(100));
+        assert_eq!(price_of(&book, buy), 95);
+    }
+
+    #[test]
+    fn a_peg_moving_the_touch_does_not_chase_itself() {
+        let (book, _, _) = quoted_book();
+        book.set_auto_reprice_pegged(true);
+
+        // Joining one tick above the best bid makes the peg the best bid.
+        let buy = peg(&book, 100, Side::Buy, PegReferenceType::BestBid, 1);
+        assert_eq!(price_of(&book, buy), 101);
+        assert_eq!(book.best_bid(), Some(101));
+
+        // Calls that leave the touch where the pass left it run no pass,
+        // so the peg does not keep stepping over itself.
+        for price in [90, 91, 92] {
+            book.add_limit_order(Id::new(), price, 1, Side::Buy, TimeInForce::Gtc, None)
+                .expect("deep bid");
+        }
+        assert_eq!(price_of(&book, buy), 101);
+
+        // Disabling stops the automatic passes again.
+        book.set_auto_reprice_pegged(false);
+        book.add_limit_order(Id::new(), 105, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("new best bid");
+        assert_eq!(price_of(&book, buy), 101);
+    }
+}
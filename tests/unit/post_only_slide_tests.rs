@@ -0,0 +1,99 @@
+//! Integration tests for sliding crossing post-only orders behind the
+//! opposite best instead of rejecting them.
+
+#[cfg(test)]
+mod tests_post_only_slide {
+    use orderbook_rs::{BookConfig, OrderBook, OrderBookBuilder, OrderBookError, PostOnlyMode};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn post_only(price: u128, side: Side) -> OrderType<()> {
+        OrderType::PostOnly {
+            id: Id::new(),
+            price: Price::new(price),
+            quantity: Quantity::new(5),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn quoted_book(mode: PostOnlyMode) -> OrderBook<()> {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.reload_config(
+            BookConfig::new()
+                .with_tick_size(5)
+                .with_post_only_mode(mode),
+        )
+        .expect("valid config");
+        book.add_limit_order(Id::new(), 100, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_limit_order(Id::new(), 110, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book
+    }
+
+    #[test]
+    fn reject_mode_keeps_rejecting_crossing_post_only_orders() {
+        let book = quoted_book(PostOnlyMode::Reject);
+        assert_eq!(book.post_only_mode(), PostOnlyMode::Reject);
+        let err = book.add_order_with_ack(post_only(110, Side::Buy));
+        assert!(matches!(err, Err(OrderBookError::PriceCrossing { .. })));
+        assert_eq!(book.best_bid(), Some(100));
+    }
+
+    #[test]
+    fn slide_mode_reprices_one_tick_behind_the_opposite_best() {
+        let book = quoted_book(PostOnlyMode::Slide);
+
+        let ack = book
+            .add_order_with_ack(post_only(120, Side::Buy))
+            .expect("buy slides");
+        assert!(ack.match_result.trades().is_empty());
+        let resting = ack.resting.expect("rests");
+        assert_eq!((resting.price, resting.repriced_from), (105, Some(120)));
+        assert_eq!(book.best_bid(), Some(105));
+
+        let ack = book
+            .add_order_with_ack(post_only(100, Side::Sell))
+            .expect("sell slides");
+        let resting = ack.resting.expect("rests");
+        assert_eq!((resting.price, resting.repriced_from), (110, Some(100)));
+        assert_eq!(book.best_ask(), Some(110));
+
+        // A non-crossing post-only order rests where it was submitted.
+        let ack = book
+            .add_order_with_ack(post_only(95, Side::Buy))
+            .expect("passive");
+        let resting = ack.resting.expect("rests");
+        assert_eq!((resting.price, resting.repriced_from), (95, None));
+    }
+
+    #[test]
+    fn a_buy_that_cannot_slide_above_zero_is_rejected() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.reload_config(BookConfig::new().with_post_only_mode(PostOnlyMode::Slide))
+            .expect("valid config");
+        book.add_limit_order(Id::new(), 1, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        let err = book.add_order(post_only(1, Side::Buy));
+        assert!(matches!(err, Err(OrderBookError::PriceCrossing { .. })));
+    }
+
+    #[test]
+    fn mode_is_built_and_snapshotted() {
+        let book = OrderBookBuilder::<()>::new("BTC/USD")
+            .post_only_mode(PostOnlyMode::Slide)
+            .build()
+            .expect("valid builder");
+        assert_eq!(book.post_only_mode(), PostOnlyMode::Slide);
+
+        let package = book.create_snapshot_package(10).expect("package");
+        let mut restored = OrderBook::<()>::new("BTC/USD");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.post_only_mode(), PostOnlyMode::Slide);
+    }
+}
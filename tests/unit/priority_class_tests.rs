@@ -0,0 +1,153 @@
+//! Tests for class priority within a price level.
+
+#[cfg(test)]
+mod tests_priority_class {
+    use orderbook_rs::{OrderBook, PriorityClass};
+    use pricelevel::{Hash32, Id, OrderType, OrderUpdate, Price, Quantity, Side, TimeInForce};
+
+    const PROP: Hash32 = Hash32::new([1; 32]);
+    const CUSTOMER: Hash32 = Hash32::new([2; 32]);
+    const MAKER: Hash32 = Hash32::new([3; 32]);
+
+    fn ask(book: &OrderBook<()>, id: u64, quantity: u64, user: Hash32) {
+        book.add_limit_order_with_user(
+            Id::from_u64(id),
+            100,
+            quantity,
+            Side::Sell,
+            TimeInForce::Gtc,
+            user,
+            None,
+        )
+        .expect("resting ask");
+    }
+
+    fn ids<const N: usize>(ids: [u64; N]) -> Vec<Id> {
+        ids.into_iter().map(Id::from_u64).collect()
+    }
+
+    /// Maker order ids of a market buy sweeping `quantity`.
+    fn fill_order(book: &OrderBook<()>, quantity: u64) -> Vec<Id> {
+        book.submit_market_order(Id::from_u64(99), quantity, Side::Buy)
+            .expect("buy")
+            .trades()
+            .as_vec()
+            .iter()
+            .map(|trade| trade.maker_order_id())
+            .collect()
+    }
+
+    fn classified_book() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        book.set_priority_class(CUSTOMER, PriorityClass::Customer);
+        book.set_priority_class(MAKER, PriorityClass::MarketMaker);
+        book
+    }
+
+    #[test]
+    fn levels_fill_class_by_class_then_by_arrival() {
+        let book = classified_book();
+        book.set_class_priority(true);
+        ask(&book, 1, 1, PROP);
+        ask(&book, 2, 1, CUSTOMER);
+        ask(&book, 3, 1, PROP);
+        ask(&book, 4, 1, MAKER);
+        ask(&book, 5, 1, CUSTOMER);
+
+        let ack = book
+            .add_order_with_ack(OrderType::Standard {
+                id: Id::from_u64(6),
+                price: Price::new(100),
+                quantity: Quantity::new(1),
+                side: Side::Sell,
+                user_id: CUSTOMER,
+                timestamp: pricelevel::TimestampMs::new(0),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .expect("ack");
+        assert_eq!(ack.resting.map(|resting| resting.queue_position), Some(3));
+
+        assert_eq!(fill_order(&book, 6), ids([4, 2, 5, 6, 1, 3]));
+        assert_eq!(
+            book.priority_class(Hash32::zero()),
+            PriorityClass::Professional
+        );
+    }
+
+    #[test]
+    fn switching_on_and_reclassifying_requeue_resting_orders() {
+        let book = classified_book();
+        ask(&book, 1, 1, PROP);
+        ask(&book, 2, 1, CUSTOMER);
+        ask(&book, 3, 1, PROP);
+        assert_eq!(fill_order(&book, 1), ids([1]));
+        assert!(!book.is_class_priority_enabled());
+
+        ask(&book, 4, 1, PROP);
+        book.set_class_priority(true);
+        assert_eq!(
+            book.set_priority_class(PROP, PriorityClass::MarketMaker),
+            None
+        );
+        assert_eq!(fill_order(&book, 1), ids([3]));
+        assert_eq!(
+            book.clear_priority_class(PROP),
+            Some(PriorityClass::MarketMaker)
+        );
+        assert_eq!(fill_order(&book, 2), ids([2, 4]));
+        assert_eq!(
+            book.priority_classes(),
+            [
+                (CUSTOMER, PriorityClass::Customer),
+                (MAKER, PriorityClass::MarketMaker)
+            ]
+        );
+
+        book.set_class_priority(false);
+        ask(&book, 5, 1, PROP);
+        ask(&book, 6, 1, CUSTOMER);
+        assert_eq!(fill_order(&book, 2), ids([5, 6]));
+    }
+
+    #[test]
+    fn resized_and_replenished_orders_keep_their_class_place() {
+        let book = classified_book();
+        book.set_class_priority(true);
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            1,
+            4,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        book.set_priority_class(Hash32::zero(), PriorityClass::Customer);
+        ask(&book, 2, 1, PROP);
+        ask(&book, 3, 1, CUSTOMER);
+
+        // The iceberg's refreshed tranche re-enters behind the customer,
+        // not behind the proprietary order.
+        assert_eq!(fill_order(&book, 1), ids([1]));
+        assert_eq!(fill_order(&book, 2), ids([3, 1]));
+
+        book.update_order(OrderUpdate::UpdateQuantity {
+            order_id: Id::from_u64(1),
+            new_quantity: Quantity::new(5),
+        })
+        .expect("increase");
+        ask(&book, 4, 1, CUSTOMER);
+        assert_eq!(fill_order(&book, 7), ids([1, 4, 2]));
+
+        let package = book.create_snapshot_package(10).expect("package");
+        assert!(package.class_priority);
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert!(restored.is_class_priority_enabled());
+        assert_eq!(restored.priority_classes(), book.priority_classes());
+    }
+}
@@ -0,0 +1,71 @@
+//! Integration tests for front-of-queue inspection at a price level.
+
+#[cfg(test)]
+mod tests_queue_peek {
+    use orderbook_rs::OrderBook;
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn ids(book: &OrderBook<()>, n: usize) -> Vec<Id> {
+        book.peek_queue(100, Side::Sell, n)
+            .iter()
+            .map(|entry| entry.id)
+            .collect()
+    }
+
+    #[test]
+    fn lists_orders_in_matching_priority() {
+        let book = OrderBook::<()>::new("TEST");
+        let owner = Hash32::new([7; 32]);
+        book.add_limit_order_with_user(
+            Id::from_u64(1),
+            100,
+            5,
+            Side::Sell,
+            TimeInForce::Gtc,
+            owner,
+            None,
+        )
+        .expect("first ask");
+        book.add_iceberg_order(
+            Id::from_u64(2),
+            100,
+            2,
+            6,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        book.add_limit_order(Id::from_u64(3), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("third ask");
+
+        let front = book.peek_queue(100, Side::Sell, 1);
+        assert_eq!(front.len(), 1);
+        assert_eq!(front[0].user_id, owner);
+        assert_eq!(front[0].visible_quantity, 5);
+        assert_eq!(
+            ids(&book, 10),
+            vec![Id::from_u64(1), Id::from_u64(2), Id::from_u64(3)]
+        );
+
+        // A partial fill keeps the maker at the front.
+        book.submit_market_order(Id::from_u64(10), 3, Side::Buy)
+            .expect("partial fill");
+        assert_eq!(book.peek_queue(100, Side::Sell, 1)[0].visible_quantity, 2);
+
+        // Consuming the iceberg's displayed tranche sends the refill to the
+        // back of the queue.
+        book.submit_market_order(Id::from_u64(11), 4, Side::Buy)
+            .expect("take the tranche");
+        let queue = book.peek_queue(100, Side::Sell, 10);
+        assert_eq!(
+            queue.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+            vec![Id::from_u64(3), Id::from_u64(2)]
+        );
+        assert_eq!(queue[1].visible_quantity, 2);
+
+        assert!(book.peek_queue(100, Side::Sell, 0).is_empty());
+        assert!(book.peek_queue(101, Side::Sell, 5).is_empty());
+        assert!(book.peek_queue(100, Side::Buy, 5).is_empty());
+    }
+}
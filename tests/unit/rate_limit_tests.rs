@@ -0,0 +1,170 @@
+//! Integration tests for per-user and book-wide rate limiting on
+//! `OrderBook<T>`.
+
+#[cfg(test)]
+mod tests_rate_limit {
+    use orderbook_rs::orderbook::order_state::{OrderStateTracker, OrderStatus};
+    use orderbook_rs::{
+        Clock, OrderBook, OrderBookError, RateLimitConfig, RejectReason, TokenBucketConfig,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Clock whose time only moves when the test says so.
+    #[derive(Debug, Default)]
+    struct ManualClock(AtomicU64);
+
+    impl ManualClock {
+        fn set(&self, now_ms: u64) {
+            self.0.store(now_ms, Ordering::Relaxed);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now_millis(&self) -> TimestampMs {
+            TimestampMs::new(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    fn book_with_limits(config: RateLimitConfig) -> (OrderBook<()>, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::default());
+        let mut book = OrderBook::<()>::with_clock("TEST", clock.clone());
+        book.set_rate_limit_config(config);
+        (book, clock)
+    }
+
+    fn add(book: &OrderBook<()>, user_id: Hash32) -> Result<(), OrderBookError> {
+        book.add_limit_order_with_user(
+            Id::new_uuid(),
+            100,
+            1,
+            Side::Buy,
+            TimeInForce::Gtc,
+            user_id,
+            None,
+        )
+        .map(|_| ())
+    }
+
+    #[test]
+    fn unconfigured_book_is_not_rate_limited() {
+        let book = OrderBook::<()>::new("TEST");
+        assert!(book.rate_limit_config().is_none());
+        for _ in 0..100 {
+            assert!(add(&book, user(1)).is_ok());
+        }
+    }
+
+    #[test]
+    fn per_user_limit_returns_retry_after_and_refills_from_book_clock() {
+        let (book, clock) =
+            book_with_limits(RateLimitConfig::new().with_per_user(TokenBucketConfig::new(2, 4)));
+
+        assert!(add(&book, user(1)).is_ok());
+        assert!(add(&book, user(1)).is_ok());
+        let result = add(&book, user(1));
+        assert!(
+            matches!(
+                result,
+                Err(OrderBookError::RateLimited {
+                    retry_after_ms: 250
+                })
+            ),
+            "expected RateLimited after 250 ms, got {result:?}"
+        );
+
+        // Other users are unaffected.
+        assert!(add(&book, user(2)).is_ok());
+
+        clock.set(250);
+        assert!(add(&book, user(1)).is_ok());
+        assert!(add(&book, user(1)).is_err());
+    }
+
+    #[test]
+    fn global_limit_caps_every_user() {
+        let (book, _clock) =
+            book_with_limits(RateLimitConfig::new().with_global(TokenBucketConfig::new(3, 1)));
+
+        assert!(add(&book, user(1)).is_ok());
+        assert!(add(&book, user(2)).is_ok());
+        assert!(add(&book, user(3)).is_ok());
+        assert!(matches!(
+            add(&book, user(4)),
+            Err(OrderBookError::RateLimited { .. })
+        ));
+    }
+
+    #[test]
+    fn market_submissions_consume_tokens() {
+        let (book, _clock) =
+            book_with_limits(RateLimitConfig::new().with_per_user(TokenBucketConfig::new(2, 1)));
+        assert!(add(&book, user(1)).is_ok());
+
+        let taker = user(2);
+        assert!(
+            book.submit_market_order_with_user(Id::new_uuid(), 1, Side::Sell, taker)
+                .is_ok()
+        );
+        assert!(add(&book, taker).is_ok());
+        let result = book.submit_market_order_with_user(Id::new_uuid(), 1, Side::Sell, taker);
+        assert!(
+            matches!(result, Err(OrderBookError::RateLimited { .. })),
+            "expected RateLimited, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn cancels_are_never_rate_limited() {
+        let (book, _clock) =
+            book_with_limits(RateLimitConfig::new().with_per_user(TokenBucketConfig::new(1, 0)));
+        let id = Id::new_uuid();
+        book.add_limit_order_with_user(id, 100, 1, Side::Buy, TimeInForce::Gtc, user(1), None)
+            .expect("first order admitted");
+        assert!(add(&book, user(1)).is_err());
+
+        assert!(book.cancel_order(id).expect("cancel succeeds").is_some());
+    }
+
+    #[test]
+    fn rejection_is_tracked_with_rate_limited_reason() {
+        let clock = Arc::new(ManualClock::default());
+        let mut book = OrderBook::<()>::with_clock("TEST", clock);
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book.set_rate_limit_config(
+            RateLimitConfig::new().with_global(TokenBucketConfig::new(0, 0)),
+        );
+
+        let id = Id::new_uuid();
+        let result =
+            book.add_limit_order_with_user(id, 100, 1, Side::Buy, TimeInForce::Gtc, user(1), None);
+        assert!(matches!(
+            result,
+            Err(OrderBookError::RateLimited {
+                retry_after_ms: u64::MAX
+            })
+        ));
+        assert_eq!(
+            book.order_status(id),
+            Some(OrderStatus::Rejected {
+                reason: RejectReason::RateLimited
+            })
+        );
+    }
+
+    #[test]
+    fn disabling_rate_limit_restores_admission() {
+        let (mut book, _clock) =
+            book_with_limits(RateLimitConfig::new().with_global(TokenBucketConfig::new(1, 0)));
+        assert!(add(&book, user(1)).is_ok());
+        assert!(add(&book, user(1)).is_err());
+
+        book.disable_rate_limit();
+        assert!(add(&book, user(1)).is_ok());
+    }
+}
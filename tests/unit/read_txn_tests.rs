@@ -0,0 +1,199 @@
+//! Integration tests for snapshot-consistent read transactions.
+
+#[cfg(test)]
+mod tests_read_txn {
+    use super::super::common::rest;
+    use orderbook_rs::{DepthMode, OrderBook, TradeResult};
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Barrier, Mutex, mpsc};
+    use std::thread;
+
+    fn ladder() -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        for i in 0..5u64 {
+            rest(&book, 100 - u128::from(i), 10 + i, Side::Buy);
+            rest(&book, 105 + u128::from(i), 20 - i, Side::Sell);
+        }
+        book.add_iceberg_order(Id::new_uuid(), 100, 2, 8, Side::Buy, TimeInForce::Gtc, None)
+            .expect("iceberg");
+        book
+    }
+
+    #[test]
+    fn queries_match_the_book_when_quiescent() {
+        let book = ladder();
+        let txn = book.read_txn();
+
+        assert!(txn.is_consistent());
+        assert_eq!(txn.depth(), orderbook_rs::DEFAULT_READ_TXN_DEPTH);
+        assert_eq!(txn.top_of_book(), book.top_of_book());
+        assert_eq!(txn.best_bid(), book.best_bid());
+        assert_eq!(txn.best_ask(), book.best_ask());
+        assert_eq!(txn.mid_price(), book.mid_price());
+        assert_eq!(txn.spread(), book.spread());
+        assert_eq!(txn.micro_price(), book.micro_price());
+        for mode in [DepthMode::Visible, DepthMode::Total] {
+            for levels in [0, 1, 3, 10] {
+                for side in [Side::Buy, Side::Sell] {
+                    assert_eq!(
+                        txn.total_depth_at_levels_with_mode(levels, side, mode),
+                        book.total_depth_at_levels_with_mode(levels, side, mode)
+                    );
+                }
+                assert_eq!(
+                    txn.order_book_imbalance_with_mode(levels, mode),
+                    book.order_book_imbalance_with_mode(levels, mode)
+                );
+            }
+            for quantity in [0, 1, 25, 70, 1_000] {
+                for side in [Side::Buy, Side::Sell] {
+                    assert_eq!(
+                        txn.vwap_with_mode(quantity, side, mode),
+                        book.vwap_with_mode(quantity, side, mode)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn an_open_transaction_ignores_later_mutations() {
+        let book = ladder();
+        let txn = book.read_txn();
+        let before = (
+            txn.best_ask(),
+            txn.vwap(30, Side::Buy),
+            txn.order_book_imbalance(5),
+        );
+
+        book.submit_market_order(Id::new_uuid(), 25, Side::Buy)
+            .expect("sweep");
+        rest(&book, 101, 50, Side::Buy);
+
+        assert_eq!(
+            (
+                txn.best_ask(),
+                txn.vwap(30, Side::Buy),
+                txn.order_book_imbalance(5)
+            ),
+            before
+        );
+        let next = book.read_txn();
+        assert!(next.update_seq() > txn.update_seq());
+        assert_eq!(next.best_ask(), Some(106));
+        assert_eq!(next.best_bid(), Some(101));
+    }
+
+    #[test]
+    fn queries_only_see_the_pinned_levels() {
+        let book = ladder();
+        let txn = book.read_txn_with_depth(2);
+
+        assert_eq!(txn.bids().len(), 2);
+        assert_eq!(txn.asks().len(), 2);
+        assert_eq!(txn.total_depth_at_levels(10, Side::Sell), 20 + 19);
+        assert_eq!(txn.vwap(39, Side::Buy), book.vwap(39, Side::Buy));
+        assert_eq!(txn.vwap(40, Side::Buy), None);
+        assert!(book.vwap(40, Side::Buy).is_some());
+
+        // A shallow transaction opened after a deep one shares its view.
+        let deep = book.read_txn_with_depth(5);
+        let shallow = book.read_txn_with_depth(1);
+        assert_eq!(shallow.update_seq(), deep.update_seq());
+        assert_eq!(shallow.bids(), &deep.bids()[..1]);
+    }
+
+    #[test]
+    fn consistent_transactions_never_see_a_crossed_book_while_a_writer_trades() {
+        const OPS: u64 = 2_000;
+
+        let book = Arc::new(ladder());
+        let barrier = Arc::new(Barrier::new(2));
+        let done = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let book = Arc::clone(&book);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for i in 0..OPS {
+                    // Crossing orders match on arrival, so with a single
+                    // writer no state of the book is ever crossed.
+                    let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+                    let _ = book.add_limit_order(
+                        Id::new_uuid(),
+                        98 + u128::from(i % 10),
+                        1 + i % 4,
+                        side,
+                        TimeInForce::Gtc,
+                        None,
+                    );
+                }
+            })
+        };
+
+        let reader = {
+            let book = Arc::clone(&book);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                barrier.wait();
+                while !done.load(Ordering::Acquire) {
+                    let txn = book.read_txn_with_depth(4);
+                    if !txn.is_consistent() {
+                        continue;
+                    }
+                    if let (Some(bid), Some(ask)) = (txn.best_bid(), txn.best_ask()) {
+                        assert!(bid < ask, "crossed at seq {}", txn.update_seq());
+                    }
+                    assert_eq!(
+                        txn.total_depth_at_levels(1, Side::Buy),
+                        txn.top_of_book().bid_quantity
+                    );
+                }
+            })
+        };
+
+        writer.join().expect("writer");
+        done.store(true, Ordering::Release);
+        reader.join().expect("reader");
+
+        let txn = book.read_txn_with_depth(4);
+        assert!(txn.is_consistent());
+        assert_eq!(txn.bids(), book.level_aggregates(4).bids.as_slice());
+        assert_eq!(txn.asks(), book.level_aggregates(4).asks.as_slice());
+    }
+
+    #[test]
+    fn a_build_overlapping_an_unfinished_sweep_is_not_consistent() {
+        let book = Arc::new(ladder());
+        let (paused_tx, paused_rx) = mpsc::channel();
+        let (resume_tx, resume_rx) = mpsc::channel::<()>();
+        let resume_rx = Mutex::new(resume_rx);
+        // The listener runs inside the sweep, after its last skip-map
+        // write: the book already looks final but the sweep has not ended.
+        book.set_trade_listener(Arc::new(move |_: &TradeResult| {
+            let _ = paused_tx.send(());
+            let _ = resume_rx.lock().expect("resume").recv();
+        }));
+
+        let sweep = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || {
+                book.add_limit_order(Id::new_uuid(), 106, 30, Side::Buy, TimeInForce::Ioc, None)
+                    .expect("sweep two ask levels")
+            })
+        };
+        paused_rx.recv().expect("sweep paused");
+
+        let during = book.read_txn();
+        assert!(!during.is_consistent());
+        assert!(!book.read_txn().is_consistent(), "unstable build published");
+
+        resume_tx.send(()).expect("resume");
+        sweep.join().expect("sweep");
+        let after = book.read_txn();
+        assert!(after.is_consistent());
+        assert_eq!(after.best_ask(), Some(106));
+        assert_eq!(after.asks()[0].total_quantity(), 9);
+    }
+}
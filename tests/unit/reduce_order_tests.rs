@@ -0,0 +1,132 @@
+//! Tests for partial cancels with `reduce_order`.
+
+#[cfg(test)]
+mod tests_reduce_order {
+    use orderbook_rs::{OrderBook, OrderBookError, PriceLevelChangedEvent, RejectCode};
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    fn bid(book: &OrderBook<()>, id: u64, quantity: u64) {
+        book.add_limit_order(
+            Id::from_u64(id),
+            100,
+            quantity,
+            Side::Buy,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("resting bid");
+    }
+
+    #[test]
+    fn reduction_keeps_queue_priority_and_notifies_the_level() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        bid(&book, 1, 10);
+        bid(&book, 2, 5);
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&changes);
+        book.set_price_level_listener(Arc::new(move |event: PriceLevelChangedEvent| {
+            sink.lock().expect("changes").push(event);
+        }));
+
+        let reduced = book
+            .reduce_order(Id::from_u64(1), 7)
+            .expect("reduce")
+            .expect("resting");
+        assert_eq!(reduced.visible_quantity().as_u64(), 3);
+        assert_eq!(book.best_bid(), Some(100));
+        {
+            let changes = changes.lock().expect("changes");
+            assert_eq!(changes.len(), 1);
+            assert_eq!((changes[0].quantity, changes[0].order_count), (8, 2));
+        }
+
+        let fill = book
+            .submit_market_order(Id::from_u64(3), 4, Side::Sell)
+            .expect("sell");
+        let makers: Vec<_> = fill
+            .trades()
+            .as_vec()
+            .iter()
+            .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+            .collect();
+        assert_eq!(makers, [(Id::from_u64(1), 3), (Id::from_u64(2), 1)]);
+    }
+
+    #[test]
+    fn invalid_reductions_leave_the_order_unchanged() {
+        let book = OrderBook::<()>::builder("BTC/USD")
+            .lot_size(5)
+            .build()
+            .expect("book");
+        bid(&book, 1, 20);
+
+        let err = book
+            .reduce_order(Id::from_u64(1), 0)
+            .expect_err("zero reduction");
+        assert!(matches!(
+            err,
+            OrderBookError::InvalidReduction {
+                requested: 0,
+                displayed: 20,
+                ..
+            }
+        ));
+        assert_eq!(err.code(), RejectCode::InvalidReduction);
+        assert!(matches!(
+            book.reduce_order(Id::from_u64(1), 20),
+            Err(OrderBookError::InvalidReduction {
+                requested: 20,
+                displayed: 20,
+                ..
+            })
+        ));
+        assert!(matches!(
+            book.reduce_order(Id::from_u64(1), 3),
+            Err(OrderBookError::InvalidLotSize {
+                quantity: 17,
+                lot_size: 5
+            })
+        ));
+        assert!(matches!(book.reduce_order(Id::from_u64(9), 5), Ok(None)));
+
+        book.engage_kill_switch();
+        assert!(matches!(
+            book.reduce_order(Id::from_u64(1), 5),
+            Err(OrderBookError::KillSwitchActive)
+        ));
+        book.release_kill_switch();
+
+        let order = book.get_order(Id::from_u64(1)).expect("resting");
+        assert_eq!(order.visible_quantity().as_u64(), 20);
+        assert!(book.reduce_order(Id::from_u64(1), 5).is_ok());
+    }
+
+    #[test]
+    fn iceberg_reduction_comes_out_of_the_displayed_clip() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            6,
+            30,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+
+        let reduced = book
+            .reduce_order(Id::from_u64(1), 2)
+            .expect("reduce")
+            .expect("resting");
+        assert_eq!(
+            (
+                reduced.visible_quantity().as_u64(),
+                reduced.hidden_quantity().as_u64()
+            ),
+            (4, 30)
+        );
+        assert!(book.reduce_order(Id::from_u64(1), 4).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Integration tests for externally supplied index and mark prices and
+//! the consumers that read them.
+
+#[cfg(test)]
+mod tests_reference_price {
+    use orderbook_rs::{
+        BookConfig, ExternalPriceSource, OrderBook, OrderBookBuilder, OrderBookError,
+        ReferencePriceSource, RiskConfig,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn now(book: &OrderBook<()>) -> u64 {
+        book.clock().now_millis().as_u64()
+    }
+
+    #[test]
+    fn price_band_skips_a_stale_index() {
+        let book = OrderBook::<()>::new("BTC-PERP");
+        book.reload_config(
+            BookConfig::new()
+                .with_risk(
+                    RiskConfig::new().with_price_band_ticks(5, ReferencePriceSource::External),
+                )
+                .with_reference_price_max_age_ms(1_000),
+        )
+        .expect("valid configuration");
+
+        book.set_reference_price(ExternalPriceSource::Index, 1_000, now(&book));
+        assert_eq!(book.external_reference_price(), Some(1_000));
+        let banded = book.add_limit_order(Id::new(), 1_050, 1, Side::Buy, TimeInForce::Gtc, None);
+        assert!(matches!(
+            banded,
+            Err(OrderBookError::RiskPriceBandTicks { .. })
+        ));
+
+        // A stale index switches the band off until the feed recovers.
+        book.set_reference_price(ExternalPriceSource::Index, 0, 0);
+        book.set_reference_price(ExternalPriceSource::Index, 1_000, now(&book) - 5_000);
+        assert!(book.is_reference_price_stale(ExternalPriceSource::Index));
+        assert_eq!(book.external_reference_price(), None);
+        assert!(
+            book.add_limit_order(Id::new(), 1_050, 1, Side::Buy, TimeInForce::Gtc, None)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn max_age_is_configured_validated_and_persisted() {
+        let book = OrderBookBuilder::<()>::new("BTC-PERP")
+            .reference_price_max_age_ms(2_500)
+            .build()
+            .expect("valid builder");
+        assert_eq!(book.config().reference_price_max_age_ms, Some(2_500));
+        assert!(matches!(
+            book.reload_config(BookConfig::new().with_reference_price_max_age_ms(0)),
+            Err(OrderBookError::InvalidConfiguration { .. })
+        ));
+
+        book.set_reference_price(ExternalPriceSource::Mark, 42, now(&book));
+        let package = book.create_snapshot_package(10).expect("package");
+        assert_eq!(package.reference_price_max_age_ms, Some(2_500));
+
+        let mut restored = OrderBook::<()>::new("BTC-PERP");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.config().reference_price_max_age_ms, Some(2_500));
+        assert_eq!(restored.reference_price(ExternalPriceSource::Mark), None);
+    }
+
+    #[cfg(feature = "special_orders")]
+    #[test]
+    fn stop_triggers_and_pegs_read_the_injected_prices() {
+        use orderbook_rs::StopTriggerReference;
+        use pricelevel::{Hash32, OrderType, PegReferenceType, Price, Quantity, TimestampMs};
+
+        let book = OrderBook::<()>::new("BTC-PERP");
+        book.reload_config(BookConfig::new().with_reference_price_max_age_ms(1_000))
+            .expect("valid configuration");
+        book.set_stop_trigger_reference(StopTriggerReference::Index);
+        let stop = Id::new();
+        book.add_order(OrderType::TrailingStop {
+            id: stop,
+            price: Price::new(95),
+            quantity: Quantity::new(1),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            trail_amount: Quantity::new(5),
+            last_reference_price: Price::new(100),
+            extra_fields: (),
+        })
+        .expect("trailing stop rests");
+
+        book.set_reference_price(ExternalPriceSource::Index, 94, now(&book) - 5_000);
+        assert!(book.triggered_trailing_stops().is_empty(), "stale index");
+        book.set_reference_price(ExternalPriceSource::Index, 94, now(&book));
+        assert_eq!(book.triggered_trailing_stops(), vec![stop]);
+
+        // A LastTrade peg redirected to the mark follows each fresh mark.
+        book.set_auto_reprice_pegged(true);
+        book.set_peg_last_trade_source(Some(ExternalPriceSource::Mark));
+        let peg = Id::new();
+        book.add_order(OrderType::PeggedOrder {
+            id: peg,
+            price: Price::new(80),
+            quantity: Quantity::new(1),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            reference_price_offset: -5,
+            reference_price_type: PegReferenceType::LastTrade,
+            extra_fields: (),
+        })
+        .expect("peg rests");
+        book.update_mark_price(90);
+        assert_eq!(book.get_order(peg).expect("peg").price().as_u128(), 85);
+        book.update_mark_price(88);
+        assert_eq!(book.get_order(peg).expect("peg").price().as_u128(), 83);
+    }
+}
@@ -66,7 +66,7 @@ mod tests_reject_reason {
 
     #[test]
     fn risk_max_open_reject_records_risk_max_open_orders_in_tracker() {
-        let mut book = book_with_tracker();
+        let book = book_with_tracker();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let acct = account(11);
 
@@ -110,7 +110,7 @@ mod tests_reject_reason {
 
     #[test]
     fn risk_max_notional_reject_records_risk_max_notional_in_tracker() {
-        let mut book = book_with_tracker();
+        let book = book_with_tracker();
         // 1_000 notional ceiling per account.
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(1_000));
         let acct = account(13);
@@ -155,7 +155,7 @@ mod tests_reject_reason {
 
     #[test]
     fn risk_price_band_reject_records_risk_price_band_in_tracker() {
-        let mut book = book_with_tracker();
+        let book = book_with_tracker();
         seed_last_trade_price(&book, 1_000_000);
         // 1000 bps = 10% allowed band.
         book.set_risk_config(
@@ -209,7 +209,7 @@ fn lot_size_replay_without_config_diverges() {
     let (journal, last_seq) = lot_size_journal();
 
     // Ground-truth: a live book WITH lot_size = 5 driven through the same ops.
-    let mut live = OrderBook::<()>::with_clock("TEST", stub_clock());
+    let live = OrderBook::<()>::with_clock("TEST", stub_clock());
     live.set_lot_size(5);
     live.add_order(OrderType::Standard {
         id: Id::from_u64(LOT_ASK_ID),
@@ -246,7 +246,7 @@ fn lot_size_replay_with_config_matches() {
     let (journal, last_seq) = lot_size_journal();
 
     // Ground-truth live book WITH lot_size = 5.
-    let mut live = OrderBook::<()>::with_clock("TEST", stub_clock());
+    let live = OrderBook::<()>::with_clock("TEST", stub_clock());
     live.set_lot_size(5);
     live.add_order(OrderType::Standard {
         id: Id::from_u64(LOT_ASK_ID),
@@ -330,7 +330,7 @@ fn stp_prevented_order_recorded_rejected_is_skipped_on_replay() {
 
     // Ground truth: a live STP book where the second add is prevented. The
     // resting ask stays in the book.
-    let mut live = OrderBook::<()>::with_clock("TEST", stub_clock());
+    let live = OrderBook::<()>::with_clock("TEST", stub_clock());
     live.set_stp_mode(STPMode::CancelTaker);
     live.add_order(OrderType::Standard {
         id: ask_id,
@@ -394,7 +394,7 @@ fn full_config_injection_preserves_lot_size_parity() {
 
     // Ground-truth live book WITH the same full config.
     let fee = FeeSchedule::new(-2, 5);
-    let mut live = OrderBook::<()>::with_clock("TEST", stub_clock());
+    let live = OrderBook::<()>::with_clock("TEST", stub_clock());
     live.set_lot_size(5);
     live.set_fee_schedule(Some(fee));
     live.set_min_order_size(1);
@@ -0,0 +1,153 @@
+//! Tests for iceberg and reserve replenishment events and their count in
+//! book statistics.
+
+#[cfg(test)]
+mod tests_replenishment_event {
+    use orderbook_rs::{OrderBook, ReplenishmentEvent};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::num::NonZeroU64;
+    use std::sync::{Arc, Mutex};
+
+    fn recording(book: &OrderBook<()>) -> Arc<Mutex<Vec<ReplenishmentEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        book.set_replenishment_listener(Arc::new(move |event: &ReplenishmentEvent| {
+            sink.lock().expect("events").push(event.clone());
+        }));
+        events
+    }
+
+    fn taken(events: &Mutex<Vec<ReplenishmentEvent>>) -> Vec<ReplenishmentEvent> {
+        std::mem::take(&mut *events.lock().expect("events"))
+    }
+
+    #[test]
+    fn consuming_the_clip_emits_one_event_per_sweep() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            5,
+            15,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        let events = recording(&book);
+
+        // A partial clip fill draws nothing from the hidden quantity.
+        book.submit_market_order(Id::from_u64(10), 3, Side::Buy)
+            .expect("partial clip");
+        assert!(taken(&events).is_empty());
+
+        // The new clip is as large as the clip it replaces: 2 units.
+        book.submit_market_order(Id::from_u64(11), 2, Side::Buy)
+            .expect("rest of the clip");
+        assert_eq!(
+            taken(&events),
+            [ReplenishmentEvent {
+                symbol: "BTC/USD".to_string(),
+                order_id: Id::from_u64(1),
+                side: Side::Sell,
+                price: 100,
+                visible_quantity: 2,
+                hidden_quantity: 13,
+                hidden_drawn: 2,
+            }]
+        );
+
+        // Three clips drawn by one sweep are reported once, with the total.
+        book.submit_market_order(Id::from_u64(12), 7, Side::Buy)
+            .expect("three clips");
+        let events = taken(&events);
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            (
+                events[0].visible_quantity,
+                events[0].hidden_quantity,
+                events[0].hidden_drawn
+            ),
+            (1, 7, 6)
+        );
+        assert_eq!(book.stats().replenishments, 2);
+    }
+
+    #[test]
+    fn reserve_below_threshold_replenishes_in_place() {
+        let book = OrderBook::<()>::new("ETH/USD");
+        book.add_order(OrderType::ReserveOrder {
+            id: Id::from_u64(1),
+            price: Price::new(50),
+            visible_quantity: Quantity::new(10),
+            hidden_quantity: Quantity::new(20),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(4),
+            replenish_amount: NonZeroU64::new(6),
+            auto_replenish: true,
+            extra_fields: (),
+        })
+        .expect("reserve");
+        let events = recording(&book);
+
+        book.submit_market_order(Id::from_u64(10), 5, Side::Sell)
+            .expect("above threshold");
+        assert!(taken(&events).is_empty());
+
+        book.submit_market_order(Id::from_u64(11), 2, Side::Sell)
+            .expect("below threshold");
+        let events = taken(&events);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].side, Side::Buy);
+        assert_eq!(
+            (events[0].visible_quantity, events[0].hidden_quantity),
+            (9, 14)
+        );
+        assert_eq!(book.stats().replenishments, 1);
+    }
+
+    #[test]
+    fn filled_icebergs_and_removed_listeners_report_nothing() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.add_iceberg_order(
+            Id::from_u64(1),
+            100,
+            2,
+            2,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("small iceberg");
+        book.add_iceberg_order(
+            Id::from_u64(2),
+            101,
+            2,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("deep iceberg");
+        let events = recording(&book);
+
+        // The first iceberg's last clip is drawn and filled by the sweep.
+        book.submit_market_order(Id::from_u64(10), 4, Side::Buy)
+            .expect("fill the first iceberg");
+        assert!(taken(&events).is_empty());
+        assert_eq!(book.stats().replenishments, 0);
+
+        book.remove_replenishment_listener();
+        assert!(book.replenishment_listener().is_none());
+        book.submit_market_order(Id::from_u64(11), 2, Side::Buy)
+            .expect("clip of the second iceberg");
+        assert!(taken(&events).is_empty());
+        assert_eq!(book.stats().replenishments, 1);
+
+        book.reset_stats();
+        assert_eq!(book.stats().replenishments, 0);
+    }
+}
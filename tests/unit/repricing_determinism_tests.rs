@@ -213,7 +213,7 @@ fn buy_pegged_reprice_tick_aligned_and_moves_issue_106() {
     let trade_count = Arc::new(AtomicUsize::new(0));
     let counter = trade_count.clone();
 
-    let mut book = OrderBook::<()>::with_tick_size("TICK/USD", 5);
+    let book = OrderBook::<()>::with_tick_size("TICK/USD", 5);
     book.set_trade_listener(Arc::new(move |_trade| {
         counter.fetch_add(1, Ordering::SeqCst);
     }));
@@ -282,7 +282,7 @@ fn sell_pegged_reprice_tick_aligned_and_moves_issue_106() {
     let trade_count = Arc::new(AtomicUsize::new(0));
     let counter = trade_count.clone();
 
-    let mut book = OrderBook::<()>::with_tick_size("TICK/USD", 5);
+    let book = OrderBook::<()>::with_tick_size("TICK/USD", 5);
     book.set_trade_listener(Arc::new(move |_trade| {
         counter.fetch_add(1, Ordering::SeqCst);
     }));
@@ -346,7 +346,7 @@ fn sell_pegged_reprice_tick_aligned_and_moves_issue_106() {
 fn reprice_records_risk_rejected_peg_in_failed_orders_issue_174() {
     use orderbook_rs::RiskConfig;
 
-    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    let book: OrderBook<()> = OrderBook::new("TEST");
     book.set_risk_config(RiskConfig::new().with_max_notional_per_account(600));
 
     // Market maker (anonymous account) provides best bid 100, best ask 105.
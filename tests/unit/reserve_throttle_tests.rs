@@ -0,0 +1,126 @@
+//! Tests for per-level fill throttling of reserve orders.
+
+#[cfg(test)]
+mod tests_reserve_throttle {
+    use orderbook_rs::{BookConfig, OrderBook, OrderBookError};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::num::NonZeroU64;
+
+    fn reserve(book: &OrderBook<()>, id: u64, visible: u64, hidden: u64) {
+        book.add_order(OrderType::ReserveOrder {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(1),
+            replenish_amount: NonZeroU64::new(10),
+            auto_replenish: true,
+            extra_fields: (),
+        })
+        .expect("reserve");
+    }
+
+    fn ask(book: &OrderBook<()>, id: u64, price: u128, quantity: u64) {
+        book.add_limit_order(
+            Id::from_u64(id),
+            price,
+            quantity,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("ask");
+    }
+
+    fn throttled_book(limit: u64) -> OrderBook<()> {
+        let book = OrderBook::<()>::new("TEST");
+        book.reload_config(BookConfig::new().with_reserve_fill_limit(limit))
+            .expect("valid limit");
+        book
+    }
+
+    /// `(maker, quantity)` of each trade of a market buy for `quantity`.
+    fn fills(book: &OrderBook<()>, id: u64, quantity: u64) -> Vec<(Id, u64)> {
+        book.submit_market_order(Id::from_u64(id), quantity, Side::Buy)
+            .expect("buy")
+            .trades()
+            .as_vec()
+            .iter()
+            .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+            .collect()
+    }
+
+    #[test]
+    fn reserves_rotate_once_a_taker_reaches_the_limit() {
+        let book = throttled_book(4);
+        reserve(&book, 1, 10, 20);
+        ask(&book, 2, 100, 5);
+        reserve(&book, 3, 10, 0);
+        ask(&book, 4, 101, 20);
+
+        assert_eq!(
+            fills(&book, 90, 12),
+            [
+                (Id::from_u64(1), 4),
+                (Id::from_u64(2), 5),
+                (Id::from_u64(3), 3)
+            ]
+        );
+
+        // A new taker gets a fresh allowance from each reserve, then walks
+        // on once only exhausted reserves rest at the level.
+        assert_eq!(
+            fills(&book, 91, 20),
+            [
+                (Id::from_u64(3), 4),
+                (Id::from_u64(1), 4),
+                (Id::from_u64(4), 12)
+            ]
+        );
+        assert_eq!(book.best_ask(), Some(100));
+
+        let plain = OrderBook::<()>::new("TEST");
+        reserve(&plain, 1, 10, 20);
+        assert_eq!(fills(&plain, 90, 10), [(Id::from_u64(1), 10)]);
+    }
+
+    #[test]
+    fn fill_or_kill_counts_only_the_allowance_of_each_reserve() {
+        let book = throttled_book(4);
+        reserve(&book, 1, 10, 20);
+        ask(&book, 2, 100, 3);
+        assert!(book.can_fill_fully(7, Side::Buy, Some(100)));
+        assert!(!book.can_fill_fully(8, Side::Buy, Some(100)));
+
+        let killed =
+            book.add_limit_order(Id::from_u64(3), 100, 8, Side::Buy, TimeInForce::Fok, None);
+        assert!(killed.is_err());
+        assert_eq!(
+            book.get_order(Id::from_u64(1))
+                .map(|order| order.visible_quantity().as_u64()),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn limit_is_validated_and_persisted() {
+        let book = OrderBook::<()>::new("TEST");
+        assert!(matches!(
+            book.reload_config(BookConfig::new().with_reserve_fill_limit(0)),
+            Err(OrderBookError::InvalidConfiguration { .. })
+        ));
+
+        let book = throttled_book(4);
+        let package = book.create_snapshot_package(10).expect("package");
+        assert_eq!(package.reserve_fill_limit, Some(4));
+        let mut restored = OrderBook::<()>::new("TEST");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.config().reserve_fill_limit, Some(4));
+    }
+}
@@ -12,7 +12,7 @@
 #[cfg(test)]
 mod tests_risk_layer {
     use orderbook_rs::{OrderBook, OrderBookError, ReferencePriceSource, RiskConfig};
-    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use pricelevel::{Hash32, Id, OrderUpdate, Price, Side, TimeInForce};
 
     fn new_book() -> OrderBook<()> {
         OrderBook::new("TEST")
@@ -28,14 +28,14 @@ mod tests_risk_layer {
 
     #[test]
     fn risk_config_set_get_disable_round_trip() {
-        let mut book = new_book();
+        let book = new_book();
         assert!(book.risk_config().is_none());
 
         let cfg = RiskConfig::new()
             .with_max_open_orders_per_account(7)
             .with_max_notional_per_account(123_456)
             .with_price_band_bps(250, ReferencePriceSource::LastTrade);
-        book.set_risk_config(cfg.clone());
+        book.set_risk_config(cfg);
 
         let installed = book.risk_config().expect("config installed");
         assert_eq!(installed.max_open_orders_per_account, Some(7));
@@ -80,7 +80,7 @@ mod tests_risk_layer {
 
     #[test]
     fn limit_far_outside_price_band_returns_risk_price_band() {
-        let mut book = new_book();
+        let book = new_book();
         seed_last_trade_price(&book, 1_000_000);
         // 1000 bps = 10% allowed band.
         book.set_risk_config(
@@ -114,7 +114,7 @@ mod tests_risk_layer {
 
     #[test]
     fn limit_within_price_band_succeeds() {
-        let mut book = new_book();
+        let book = new_book();
         seed_last_trade_price(&book, 1_000_000);
         book.set_risk_config(
             RiskConfig::new().with_price_band_bps(1_000, ReferencePriceSource::LastTrade),
@@ -137,7 +137,7 @@ mod tests_risk_layer {
 
     #[test]
     fn mid_reference_falls_back_to_last_trade_when_one_sided() {
-        let mut book = new_book();
+        let book = new_book();
         // Seed a last trade and confirm.
         seed_last_trade_price(&book, 1_000_000);
         // Add a single bid so the book is one-sided (no asks).
@@ -172,7 +172,7 @@ mod tests_risk_layer {
 
     #[test]
     fn band_skipped_with_warn_when_no_reference_available() {
-        let mut book = new_book();
+        let book = new_book();
         // Empty book + no trades → no reference price exists.
         book.set_risk_config(RiskConfig::new().with_price_band_bps(100, ReferencePriceSource::Mid));
 
@@ -192,6 +192,107 @@ mod tests_risk_layer {
         );
     }
 
+    // ───────────────────────────────────────────────────────────────
+    // Tick band and external reference (fat-finger protection)
+    // ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn limit_beyond_tick_band_returns_risk_price_band_ticks() {
+        let book = OrderBook::<()>::with_tick_size("TEST", 10);
+        book.set_risk_config(
+            RiskConfig::new().with_price_band_ticks(3, ReferencePriceSource::LastTrade),
+        );
+        seed_last_trade_price(&book, 1_000);
+
+        // 3 ticks of 10 → [970, 1_030] admitted.
+        assert!(
+            book.add_limit_order(Id::new_uuid(), 1_030, 1, Side::Sell, TimeInForce::Gtc, None)
+                .is_ok()
+        );
+        assert!(
+            book.add_limit_order(Id::new_uuid(), 970, 1, Side::Buy, TimeInForce::Gtc, None)
+                .is_ok()
+        );
+        let result =
+            book.add_limit_order(Id::new_uuid(), 1_040, 1, Side::Sell, TimeInForce::Gtc, None);
+        match result {
+            Err(OrderBookError::RiskPriceBandTicks {
+                submitted,
+                reference,
+                deviation_ticks,
+                limit_ticks,
+            }) => {
+                assert_eq!(submitted, 1_040);
+                assert_eq!(reference, 1_000);
+                assert_eq!(deviation_ticks, 4);
+                assert_eq!(limit_ticks, 3);
+            }
+            other => panic!("expected RiskPriceBandTicks, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tick_band_also_gates_price_modifications() {
+        let book = new_book();
+        book.set_risk_config(RiskConfig::new().with_price_band_ticks(5, ReferencePriceSource::Mid));
+        let bid = Id::new_uuid();
+        book.add_limit_order(bid, 98, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        book.add_limit_order(Id::new_uuid(), 102, 1, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+
+        // Mid 100: moving the bid to 90 is 10 ticks away.
+        let result = book.update_order(OrderUpdate::UpdatePrice {
+            order_id: bid,
+            new_price: Price::new(90),
+        });
+        assert!(
+            matches!(result, Err(OrderBookError::RiskPriceBandTicks { .. })),
+            "expected RiskPriceBandTicks, got {result:?}"
+        );
+        assert_eq!(book.best_bid(), Some(98));
+    }
+
+    #[test]
+    fn external_reference_price_drives_the_band() {
+        let book = new_book();
+        book.set_risk_config(
+            RiskConfig::new().with_price_band_bps(500, ReferencePriceSource::External),
+        );
+
+        // No external price yet: the band is skipped.
+        assert_eq!(book.external_reference_price(), None);
+        assert!(
+            book.add_limit_order(
+                Id::new_uuid(),
+                10_000,
+                1,
+                Side::Sell,
+                TimeInForce::Gtc,
+                None
+            )
+            .is_ok()
+        );
+
+        book.set_external_reference_price(1_000);
+        assert_eq!(book.external_reference_price(), Some(1_000));
+        assert!(
+            book.add_limit_order(Id::new_uuid(), 1_050, 1, Side::Sell, TimeInForce::Gtc, None)
+                .is_ok()
+        );
+        assert!(matches!(
+            book.add_limit_order(Id::new_uuid(), 1_051, 1, Side::Sell, TimeInForce::Gtc, None),
+            Err(OrderBookError::RiskPriceBand { .. })
+        ));
+
+        // The feed moves; the same price is now inside the band.
+        book.set_external_reference_price(1_040);
+        assert!(
+            book.add_limit_order(Id::new_uuid(), 1_051, 1, Side::Sell, TimeInForce::Gtc, None)
+                .is_ok()
+        );
+    }
+
     // ───────────────────────────────────────────────────────────────
     // Market-order bypass
     // ───────────────────────────────────────────────────────────────
@@ -202,7 +303,7 @@ mod tests_risk_layer {
 
     #[test]
     fn submit_above_max_open_orders_returns_risk_max_open() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(2));
         let acct = account(11);
 
@@ -254,7 +355,7 @@ mod tests_risk_layer {
 
     #[test]
     fn submit_within_max_open_orders_succeeds() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(3));
         let acct = account(12);
 
@@ -274,7 +375,7 @@ mod tests_risk_layer {
 
     #[test]
     fn submit_above_max_notional_returns_risk_max_notional() {
-        let mut book = new_book();
+        let book = new_book();
         // 1_000 notional ceiling per account.
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(1_000));
         let acct = account(13);
@@ -319,7 +420,7 @@ mod tests_risk_layer {
 
     #[test]
     fn submit_within_max_notional_succeeds() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(1_000));
         let acct = account(14);
 
@@ -351,7 +452,7 @@ mod tests_risk_layer {
 
     #[test]
     fn cancel_decrements_counters() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let acct = account(15);
 
@@ -394,7 +495,7 @@ mod tests_risk_layer {
 
     #[test]
     fn partial_fill_decrements_notional_and_keeps_count() {
-        let mut book = new_book();
+        let book = new_book();
         // High open ceiling, tight notional ceiling: we want the
         // partial fill to free notional headroom for a follow-up.
         book.set_risk_config(
@@ -454,7 +555,7 @@ mod tests_risk_layer {
 
     #[test]
     fn full_fill_decrements_open_count() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let maker_acct = account(18);
         let taker_acct = account(19);
@@ -490,7 +591,7 @@ mod tests_risk_layer {
 
     #[test]
     fn disable_risk_clears_gates_keeps_counters() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(1));
         let acct = account(20);
 
@@ -540,7 +641,7 @@ mod tests_risk_layer {
 
     #[test]
     fn market_orders_bypass_risk_checks() {
-        let mut book = new_book();
+        let book = new_book();
         // Seed resting liquidity for both market-order calls BEFORE
         // installing the risk config, so the seeding limits aren't
         // themselves blocked by the gate we're about to configure.
@@ -599,12 +700,12 @@ mod tests_risk_layer {
         // Build the original book, install a fully-configured risk
         // layer, and rest a few orders across two accounts so the
         // per-account counters carry meaningful state.
-        let mut original = new_book();
+        let original = new_book();
         let cfg = RiskConfig::new()
             .with_max_open_orders_per_account(2)
             .with_max_notional_per_account(1_000)
             .with_price_band_bps(5_000, ReferencePriceSource::LastTrade);
-        original.set_risk_config(cfg.clone());
+        original.set_risk_config(cfg);
 
         let acct_a = account(31);
         let acct_b = account(32);
@@ -752,7 +853,7 @@ mod tests_risk_layer {
     /// rejected after a bulk unwind (the exact failure bulk cancel exists to avoid).
     #[test]
     fn cancel_all_orders_resets_open_order_counter() {
-        let mut book = new_book();
+        let book = new_book();
         book.set_risk_config(RiskConfig::new().with_max_open_orders_per_account(2));
         let acct = account(11);
 
@@ -0,0 +1,164 @@
+//! Integration tests for swapping listeners and the STP mode on a book
+//! shared across threads.
+
+#[cfg(test)]
+mod tests_shared_book_reconfiguration {
+    use orderbook_rs::{
+        OrderBook, OrderBookError, PriceLevelChangedEvent, STPMode, TradeListener, TradeResult,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    const ROUNDS: u64 = 2_000;
+
+    fn counting_listener(counter: &Arc<AtomicU64>) -> TradeListener {
+        let counter = Arc::clone(counter);
+        Arc::new(move |trade: &TradeResult| {
+            counter.fetch_add(trade.match_result.trades().len() as u64, Ordering::Relaxed);
+        })
+    }
+
+    #[test]
+    fn swapping_trade_listeners_delivers_every_trade_exactly_once() {
+        let first = Arc::new(AtomicU64::new(0));
+        let second = Arc::new(AtomicU64::new(0));
+        let book = Arc::new(OrderBook::<()>::new("BTC/USD"));
+        book.set_trade_listener(counting_listener(&first));
+
+        let start = Arc::new(Barrier::new(2));
+        let done = Arc::new(AtomicBool::new(false));
+        let swapper = {
+            let (book, start, done) = (Arc::clone(&book), Arc::clone(&start), Arc::clone(&done));
+            let listeners = [counting_listener(&first), counting_listener(&second)];
+            thread::spawn(move || {
+                start.wait();
+                let mut swaps = 0usize;
+                while !done.load(Ordering::Relaxed) {
+                    book.set_trade_listener(Arc::clone(&listeners[swaps % 2]));
+                    swaps += 1;
+                    thread::yield_now();
+                }
+                swaps
+            })
+        };
+
+        start.wait();
+        for _ in 0..ROUNDS {
+            book.add_limit_order(Id::new_uuid(), 100, 1, Side::Sell, TimeInForce::Gtc, None)
+                .expect("ask");
+            let fill = book
+                .submit_market_order(Id::new_uuid(), 1, Side::Buy)
+                .expect("fill");
+            assert_eq!(fill.trades().len(), 1);
+        }
+        done.store(true, Ordering::Relaxed);
+        swapper.join().expect("swapper");
+
+        let delivered = first.load(Ordering::Relaxed) + second.load(Ordering::Relaxed);
+        assert_eq!(delivered, ROUNDS);
+
+        book.remove_trade_listener();
+        assert!(book.trade_listener().is_none());
+        book.add_limit_order(Id::new_uuid(), 100, 1, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book.submit_market_order(Id::new_uuid(), 1, Side::Buy)
+            .expect("fill");
+        let after_removal = first.load(Ordering::Relaxed) + second.load(Ordering::Relaxed);
+        assert_eq!(after_removal, ROUNDS);
+    }
+
+    #[test]
+    fn stp_mode_toggles_while_shared_and_each_submit_sees_one_mode() {
+        let book = Arc::new(OrderBook::<()>::new("BTC/USD"));
+        let user = Hash32::new([7; 32]);
+        let start = Arc::new(Barrier::new(2));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let toggler = {
+            let (book, start, done) = (Arc::clone(&book), Arc::clone(&start), Arc::clone(&done));
+            thread::spawn(move || {
+                start.wait();
+                let mut toggles = 0u64;
+                while !done.load(Ordering::Relaxed) {
+                    let mode = if toggles.is_multiple_of(2) {
+                        STPMode::CancelTaker
+                    } else {
+                        STPMode::None
+                    };
+                    book.set_stp_mode(mode);
+                    toggles += 1;
+                    thread::yield_now();
+                }
+                toggles
+            })
+        };
+
+        start.wait();
+        let (mut prevented, mut traded) = (0u64, 0u64);
+        for _ in 0..ROUNDS {
+            book.add_limit_order_with_user(
+                Id::new_uuid(),
+                100,
+                1,
+                Side::Sell,
+                TimeInForce::Gtc,
+                user,
+                None,
+            )
+            .expect("ask");
+            match book.submit_market_order_with_user(Id::new_uuid(), 1, Side::Buy, user) {
+                Ok(fill) => {
+                    assert_eq!(fill.trades().len(), 1);
+                    traded += 1;
+                }
+                Err(OrderBookError::SelfTradePrevented { mode, .. }) => {
+                    assert_eq!(mode, STPMode::CancelTaker);
+                    prevented += 1;
+                }
+                Err(other) => panic!("unexpected error {other}"),
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+        let toggles = toggler.join().expect("toggler");
+
+        assert_eq!(traded + prevented, ROUNDS);
+        assert_eq!(book.config_version(), toggles);
+        let resting: u64 = book
+            .get_asks()
+            .iter()
+            .map(|level| level.value().total_quantity().unwrap_or(0))
+            .sum();
+        assert_eq!(resting, prevented);
+    }
+
+    #[test]
+    fn price_level_listener_installed_after_sharing_sees_later_changes() {
+        let book = Arc::new(OrderBook::<()>::new("BTC/USD"));
+        book.add_limit_order(Id::new_uuid(), 100, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("before listener");
+
+        let seen = Arc::new(AtomicU64::new(0));
+        let sink = Arc::clone(&seen);
+        let installer = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || {
+                book.set_price_level_listener(Arc::new(move |_: PriceLevelChangedEvent| {
+                    sink.fetch_add(1, Ordering::Relaxed);
+                }));
+            })
+        };
+        installer.join().expect("installer");
+        assert!(book.price_level_listener().is_some());
+
+        book.add_limit_order(Id::new_uuid(), 101, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("after listener");
+        assert_eq!(seen.load(Ordering::Relaxed), 1);
+
+        book.remove_price_level_listener();
+        book.add_limit_order(Id::new_uuid(), 102, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("after removal");
+        assert_eq!(seen.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,411 @@
+//! Integration tests for the agent-based `SimulationRunner`.
+
+#[cfg(test)]
+mod tests_simulation_runner {
+    use orderbook_rs::{
+        AgentAction, AgentContext, AgentLatency, LatencyModel, OrderBook, OrderBookError,
+        PriceLevelChangedEvent, SimulationReport, SimulationRunner, Strategy, TradeResult,
+    };
+    use pricelevel::Side;
+
+    /// Keeps one ask resting at `price` and re-quotes as soon as it fills.
+    struct Quoter {
+        symbol: &'static str,
+        price: u128,
+    }
+
+    impl Strategy for Quoter {
+        fn name(&self) -> &str {
+            "quoter"
+        }
+
+        fn on_tick(&mut self, ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            if ctx.open_orders(self.symbol).is_empty() {
+                vec![AgentAction::limit(self.symbol, Side::Sell, self.price, 2)]
+            } else {
+                Vec::new()
+            }
+        }
+
+        fn on_trade(
+            &mut self,
+            ctx: &AgentContext<'_, ()>,
+            trade: &TradeResult,
+        ) -> Vec<AgentAction> {
+            if trade.symbol == self.symbol && ctx.open_orders(self.symbol).is_empty() {
+                vec![AgentAction::limit(self.symbol, Side::Sell, self.price, 2)]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    /// Buys `quantity` at market every tick.
+    struct Buyer {
+        symbol: &'static str,
+        quantity: u64,
+    }
+
+    impl Strategy for Buyer {
+        fn name(&self) -> &str {
+            "buyer"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            vec![AgentAction::market(self.symbol, Side::Buy, self.quantity)]
+        }
+    }
+
+    fn quoter_and_buyer(ticks: u64) -> SimulationReport {
+        let mut runner = SimulationRunner::new(10);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(Quoter {
+            symbol: "AAA",
+            price: 100,
+        });
+        runner.add_agent(Buyer {
+            symbol: "AAA",
+            quantity: 3,
+        });
+        runner.run(ticks)
+    }
+
+    #[test]
+    fn fills_are_attributed_to_both_sides() {
+        let report = quoter_and_buyer(4);
+        assert_eq!(report.ticks, 4);
+        assert_eq!(report.now, 40);
+
+        let quoter = &report.agents[0];
+        let buyer = &report.agents[1];
+        assert_eq!(quoter.name, "quoter");
+        // Each 3-lot buy takes the resting 2-lot ask, the quoter re-quotes
+        // on the trade and the taker's remaining lot is unfilled.
+        assert_eq!(buyer.stats.bought, 8);
+        assert_eq!(quoter.stats.sold, 8);
+        assert_eq!(buyer.stats.positions["AAA"].quantity, 8);
+        assert_eq!(buyer.stats.positions["AAA"].cash, -800);
+        assert_eq!(quoter.stats.positions["AAA"].cash, 800);
+        assert_eq!((quoter.pnl, buyer.pnl), (0, 0));
+        assert_eq!(report.dropped_actions, 0);
+    }
+
+    #[test]
+    fn runs_are_deterministic() {
+        assert_eq!(quoter_and_buyer(25), quoter_and_buyer(25));
+    }
+
+    /// Cancels every order it learns about, including other agents'.
+    struct Canceller {
+        seen: Vec<pricelevel::Id>,
+    }
+
+    impl Strategy for Canceller {
+        fn name(&self) -> &str {
+            "canceller"
+        }
+
+        fn on_tick(&mut self, ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            let Some(book) = ctx.book("AAA") else {
+                return Vec::new();
+            };
+            self.seen
+                .extend(book.get_all_orders().iter().map(|order| order.id()));
+            self.seen
+                .drain(..)
+                .map(|order_id| AgentAction::cancel("AAA", order_id))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn agents_cannot_cancel_other_agents_orders() {
+        let mut runner = SimulationRunner::new(1);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(Quoter {
+            symbol: "AAA",
+            price: 100,
+        });
+        runner.add_agent(Canceller { seen: Vec::new() });
+
+        let report = runner.run(2);
+        assert_eq!(report.agents[1].stats.rejected, 1);
+        assert_eq!(report.agents[1].stats.cancelled, 0);
+        assert_eq!(
+            runner.book("AAA").map(|book| book.best_ask()),
+            Some(Some(100))
+        );
+    }
+
+    /// Answers every book change with a new order, so two of them feed
+    /// each other without end.
+    struct Echo;
+
+    impl Strategy for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            vec![AgentAction::limit("AAA", Side::Buy, 90, 1)]
+        }
+
+        fn on_book_change(
+            &mut self,
+            _ctx: &AgentContext<'_, ()>,
+            _symbol: &str,
+            _change: &PriceLevelChangedEvent,
+        ) -> Vec<AgentAction> {
+            vec![AgentAction::limit("AAA", Side::Buy, 90, 1)]
+        }
+    }
+
+    #[test]
+    fn action_cap_stops_feedback_loops() {
+        let mut runner = SimulationRunner::new(1);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(Echo);
+        runner.add_agent(Echo);
+        runner.set_max_actions_per_tick(50);
+
+        let report = runner.run(1);
+        assert_eq!(
+            report.agents[0].stats.orders_submitted + report.agents[1].stats.orders_submitted,
+            50
+        );
+        assert!(report.dropped_actions > 0);
+    }
+
+    #[test]
+    fn positions_are_tracked_per_symbol_and_marked_to_last_trade() {
+        let mut runner = SimulationRunner::new(1);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_book(OrderBook::new("BBB"));
+        runner.add_agent(Quoter {
+            symbol: "AAA",
+            price: 100,
+        });
+        runner.add_agent(Quoter {
+            symbol: "BBB",
+            price: 50,
+        });
+        runner.add_agent(Buyer {
+            symbol: "AAA",
+            quantity: 2,
+        });
+        runner.add_agent(Buyer {
+            symbol: "BBB",
+            quantity: 1,
+        });
+
+        let report = runner.run(3);
+        assert_eq!(report.agents[0].stats.positions["AAA"].quantity, -6);
+        assert!(!report.agents[0].stats.positions.contains_key("BBB"));
+        assert_eq!(report.agents[1].stats.positions["BBB"].quantity, -3);
+        assert_eq!(report.agents[3].stats.positions["BBB"].cash, -150);
+        assert!(report.agents.iter().all(|agent| agent.pnl == 0));
+    }
+
+    #[test]
+    fn unknown_symbol_is_rejected() {
+        let mut runner = SimulationRunner::new(1);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(Buyer {
+            symbol: "ZZZ",
+            quantity: 1,
+        });
+        let report = runner.run(2);
+        assert_eq!(report.agents[0].stats.rejected, 2);
+    }
+
+    /// Posts one 2-lot ask at 100 on the first tick.
+    struct OneShotMaker {
+        placed: bool,
+    }
+
+    impl Strategy for OneShotMaker {
+        fn name(&self) -> &str {
+            "maker"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            if std::mem::replace(&mut self.placed, true) {
+                Vec::new()
+            } else {
+                vec![AgentAction::limit("AAA", Side::Sell, 100, 2)]
+            }
+        }
+    }
+
+    /// Lifts any ask it hears about with a market buy.
+    struct Sniper {
+        name: &'static str,
+    }
+
+    impl Strategy for Sniper {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            Vec::new()
+        }
+
+        fn on_book_change(
+            &mut self,
+            _ctx: &AgentContext<'_, ()>,
+            _symbol: &str,
+            change: &PriceLevelChangedEvent,
+        ) -> Vec<AgentAction> {
+            if change.side == Side::Sell && change.quantity > 0 {
+                vec![AgentAction::market("AAA", Side::Buy, 2)]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    #[test]
+    fn faster_agent_wins_the_race_regardless_of_registration_order() {
+        let mut runner = SimulationRunner::new(100);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(OneShotMaker { placed: false });
+        runner
+            .add_agent_with_latency(Sniper { name: "slow" }, AgentLatency::fixed(20))
+            .expect("valid latency");
+        runner
+            .add_agent_with_latency(
+                Sniper { name: "fast" },
+                AgentLatency::new()
+                    .with_submission(LatencyModel::Fixed(1))
+                    .with_market_data(LatencyModel::Fixed(2)),
+            )
+            .expect("valid latency");
+
+        // The ask rests at 100. Both snipers react after the tick, so their
+        // orders are processed by the next one: the fast buy at 103, the
+        // slow one at 140 against an empty book.
+        runner.step();
+        assert_eq!(runner.report().agents[2].stats.orders_submitted, 0);
+        let report = runner.run(1);
+        assert_eq!(report.agents[2].stats.bought, 2);
+        assert_eq!(report.agents[1].stats.bought, 0);
+        let slow = &report.agents[1].stats;
+        assert_eq!(slow.orders_submitted + slow.rejected, 1);
+    }
+
+    /// Records the simulation time at which it hears of each trade.
+    struct Listener {
+        heard: std::rc::Rc<std::cell::RefCell<Vec<u64>>>,
+    }
+
+    impl Strategy for Listener {
+        fn name(&self) -> &str {
+            "listener"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            Vec::new()
+        }
+
+        fn on_trade(
+            &mut self,
+            ctx: &AgentContext<'_, ()>,
+            _trade: &TradeResult,
+        ) -> Vec<AgentAction> {
+            self.heard.borrow_mut().push(ctx.now());
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn market_data_is_delivered_after_the_delay() {
+        let heard = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut runner = SimulationRunner::new(10);
+        runner.add_book(OrderBook::new("AAA"));
+        runner.add_agent(Quoter {
+            symbol: "AAA",
+            price: 100,
+        });
+        runner.add_agent(Buyer {
+            symbol: "AAA",
+            quantity: 1,
+        });
+        runner
+            .add_agent_with_latency(
+                Listener {
+                    heard: heard.clone(),
+                },
+                AgentLatency::new().with_market_data(LatencyModel::Fixed(15)),
+            )
+            .expect("valid latency");
+
+        // Trades at 10, 20 and 30 arrive 15ms later.
+        runner.run(3);
+        assert_eq!(*heard.borrow(), vec![25]);
+        runner.run(2);
+        assert_eq!(*heard.borrow(), vec![25, 35, 45]);
+    }
+
+    #[test]
+    fn sampled_latencies_are_reproducible_per_seed() {
+        let run = |seed| {
+            let mut runner = SimulationRunner::new(5);
+            runner.set_seed(seed);
+            runner.add_book(OrderBook::new("AAA"));
+            runner.add_agent(Quoter {
+                symbol: "AAA",
+                price: 100,
+            });
+            for latency in [
+                LatencyModel::Normal {
+                    mean: 4.0,
+                    std_dev: 3.0,
+                },
+                LatencyModel::Empirical(vec![1, 2, 3, 8, 13]),
+            ] {
+                runner
+                    .add_agent_with_latency(
+                        Sniper { name: "sniper" },
+                        AgentLatency::new()
+                            .with_submission(latency.clone())
+                            .with_market_data(latency),
+                    )
+                    .expect("valid latency");
+            }
+            runner.run(200)
+        };
+        assert_eq!(run(7), run(7));
+        let report = run(7);
+        assert!(report.agents[1].stats.bought > 0);
+        assert!(report.agents[2].stats.bought > 0);
+    }
+
+    #[test]
+    fn invalid_latency_models_are_rejected() {
+        let mut runner = SimulationRunner::new(1);
+        for model in [
+            LatencyModel::Normal {
+                mean: 1.0,
+                std_dev: -1.0,
+            },
+            LatencyModel::Normal {
+                mean: f64::NAN,
+                std_dev: 1.0,
+            },
+            LatencyModel::Empirical(Vec::new()),
+        ] {
+            assert!(matches!(
+                runner.add_agent_with_latency(
+                    Buyer {
+                        symbol: "AAA",
+                        quantity: 1,
+                    },
+                    AgentLatency::new().with_submission(model),
+                ),
+                Err(OrderBookError::InvalidConfiguration { .. })
+            ));
+        }
+        assert!(runner.report().agents.is_empty());
+    }
+}
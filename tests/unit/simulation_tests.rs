@@ -0,0 +1,157 @@
+//! Integration tests for the `MarketSimulator` order flow generator.
+
+#[cfg(test)]
+mod tests_simulation {
+    use orderbook_rs::{
+        ArrivalProcess, MarketSimulator, OrderBook, OrderBookError, SimulatedAction,
+        SimulatedEvent, SimulationConfig, SizeDistribution,
+    };
+    use pricelevel::Side;
+
+    fn events(config: SimulationConfig, count: usize) -> Vec<SimulatedEvent> {
+        MarketSimulator::new(config)
+            .expect("valid config")
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_stream() {
+        let config = SimulationConfig::new().with_seed(42);
+        assert_eq!(events(config, 500), events(config, 500));
+        assert_ne!(events(config, 500), events(config.with_seed(43), 500));
+    }
+
+    #[test]
+    fn timestamps_increase() {
+        for arrivals in [
+            ArrivalProcess::Poisson { rate: 10_000.0 },
+            ArrivalProcess::Hawkes {
+                baseline: 1_000.0,
+                excitation: 900.0,
+                decay: 1_000.0,
+            },
+        ] {
+            let stream = events(SimulationConfig::new().with_arrivals(arrivals), 2_000);
+            assert!(
+                stream
+                    .windows(2)
+                    .all(|pair| pair[0].timestamp_ns <= pair[1].timestamp_ns)
+            );
+        }
+    }
+
+    #[test]
+    fn poisson_rate_matches_mean_interarrival() {
+        let stream = events(
+            SimulationConfig::new().with_arrivals(ArrivalProcess::Poisson { rate: 1_000.0 }),
+            20_000,
+        );
+        let elapsed_ns = stream.last().expect("events").timestamp_ns as f64;
+        let rate = stream.len() as f64 / (elapsed_ns / 1e9);
+        assert!((900.0..1_100.0).contains(&rate), "rate {rate}");
+    }
+
+    #[test]
+    fn hawkes_rate_matches_stationary_mean() {
+        // Stationary intensity is baseline / (1 - excitation / decay).
+        let stream = events(
+            SimulationConfig::new().with_arrivals(ArrivalProcess::Hawkes {
+                baseline: 500.0,
+                excitation: 500.0,
+                decay: 1_000.0,
+            }),
+            50_000,
+        );
+        let elapsed_ns = stream.last().expect("events").timestamp_ns as f64;
+        let rate = stream.len() as f64 / (elapsed_ns / 1e9);
+        assert!((850.0..1_150.0).contains(&rate), "rate {rate}");
+    }
+
+    #[test]
+    fn action_mix_follows_ratios_and_cancels_live_orders() {
+        let config = SimulationConfig::new().with_ratios(0.25, 0.15);
+        let stream = events(config, 20_000);
+
+        let mut limits = std::collections::HashSet::new();
+        let (mut cancels, mut markets) = (0usize, 0usize);
+        for event in &stream {
+            match event.action {
+                SimulatedAction::Limit {
+                    order_id,
+                    side,
+                    price,
+                    ..
+                } => {
+                    match side {
+                        Side::Buy => assert!(price < config.mid_price),
+                        Side::Sell => assert!(price > config.mid_price),
+                    }
+                    limits.insert(order_id);
+                }
+                SimulatedAction::Market { .. } => markets += 1,
+                SimulatedAction::Cancel { order_id } => {
+                    assert!(limits.remove(&order_id), "cancel of unknown order");
+                    cancels += 1;
+                }
+            }
+        }
+        let share = |count: usize| count as f64 / stream.len() as f64;
+        assert!((0.22..0.28).contains(&share(cancels)));
+        assert!((0.13..0.17).contains(&share(markets)));
+    }
+
+    #[test]
+    fn size_distributions_stay_in_range() {
+        let config = SimulationConfig::new().with_sizes(
+            SizeDistribution::Uniform { min: 5, max: 9 },
+            SizeDistribution::Fixed(3),
+        );
+        for event in events(config, 5_000) {
+            match event.action {
+                SimulatedAction::Limit { quantity, .. } => assert!((5..=9).contains(&quantity)),
+                SimulatedAction::Market { quantity, .. } => assert_eq!(quantity, 3),
+                SimulatedAction::Cancel { .. } => {}
+            }
+        }
+    }
+
+    #[test]
+    fn generated_flow_keeps_the_book_consistent() {
+        let book = OrderBook::<()>::new("SIM");
+        let config = SimulationConfig::new().with_arrivals(ArrivalProcess::Hawkes {
+            baseline: 200.0,
+            excitation: 150.0,
+            decay: 200.0,
+        });
+        for event in events(config, 5_000) {
+            let _ = event.apply(&book);
+        }
+        assert!(book.audit().is_empty());
+        assert!(!book.get_all_orders().is_empty());
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        for config in [
+            SimulationConfig::new().with_arrivals(ArrivalProcess::Poisson { rate: 0.0 }),
+            SimulationConfig::new().with_arrivals(ArrivalProcess::Hawkes {
+                baseline: 100.0,
+                excitation: 200.0,
+                decay: 200.0,
+            }),
+            SimulationConfig::new().with_ratios(0.7, 0.4),
+            SimulationConfig::new().with_price(100, 0),
+            SimulationConfig::new().with_sizes(
+                SizeDistribution::Uniform { min: 9, max: 5 },
+                SizeDistribution::Fixed(1),
+            ),
+            SimulationConfig::new().with_users(0),
+        ] {
+            assert!(matches!(
+                MarketSimulator::new(config),
+                Err(OrderBookError::InvalidConfiguration { .. })
+            ));
+        }
+    }
+}
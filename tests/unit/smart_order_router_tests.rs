@@ -0,0 +1,155 @@
+//! Tests for splitting parent orders across venues with the smart order
+//! router.
+
+#[cfg(test)]
+mod tests_smart_order_router {
+    use orderbook_rs::{
+        ConsolidatedBook, OrderBook, OrderBookError, RoutingStrategy, SmartOrderRouter,
+    };
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn ask(book: &OrderBook<()>, id: u64, price: u128, quantity: u64) {
+        book.add_limit_order(
+            Id::from_u64(id),
+            price,
+            quantity,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("resting ask");
+    }
+
+    fn split(plan: &orderbook_rs::RoutingPlan) -> Vec<(&str, u64)> {
+        plan.children
+            .iter()
+            .map(|child| (child.venue.as_str(), child.quantity))
+            .collect()
+    }
+
+    #[test]
+    fn sweep_takes_the_best_price_across_venues() {
+        let a = OrderBook::<()>::new("BTC/USD");
+        let b = OrderBook::<()>::new("BTC/USD");
+        ask(&a, 1, 100, 4);
+        ask(&a, 2, 103, 10);
+        ask(&b, 3, 101, 3);
+        ask(&b, 4, 102, 2);
+        let venues = ConsolidatedBook::new()
+            .with_venue("A", &a)
+            .with_venue("B", &b);
+        let router = SmartOrderRouter::new(RoutingStrategy::SweepBestPrice);
+
+        let plan = router.route(&venues, Side::Buy, 10);
+        assert_eq!(split(&plan), [("A", 5), ("B", 5)]);
+        assert_eq!(plan.unallocated, 0);
+        assert_eq!(
+            plan.child("A").map(|child| child.simulation.fills.clone()),
+            Some(vec![(100, 4), (103, 1)])
+        );
+
+        let report = router.execute(&venues, Side::Buy, 10);
+        assert_eq!((report.filled(), report.unfilled()), (10, 0));
+        assert_eq!(
+            report.child("B").map(|child| child.fills.clone()),
+            Some(vec![(101, 3), (102, 2)])
+        );
+        let expected = (100.0 * 4.0 + 103.0 + 101.0 * 3.0 + 102.0 * 2.0) / 10.0;
+        assert_eq!(report.avg_price(), Some(expected));
+        assert_eq!(b.best_ask(), None);
+        assert_eq!(a.best_ask(), Some(103));
+    }
+
+    #[test]
+    fn proportional_split_follows_displayed_size() {
+        let a = OrderBook::<()>::new("BTC/USD");
+        let b = OrderBook::<()>::new("BTC/USD");
+        ask(&a, 1, 100, 6);
+        b.add_iceberg_order(
+            Id::from_u64(2),
+            101,
+            3,
+            30,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("iceberg");
+        let venues = ConsolidatedBook::new()
+            .with_venue("A", &a)
+            .with_venue("B", &b);
+        let router = SmartOrderRouter::new(RoutingStrategy::ProportionalToDisplayed);
+
+        assert_eq!(
+            split(&router.route(&venues, Side::Buy, 6)),
+            [("A", 4), ("B", 2)]
+        );
+        // 5 * 6 / 9 = 3 r3 and 5 * 3 / 9 = 1 r6: the unit left goes to B.
+        assert_eq!(
+            split(&router.route(&venues, Side::Buy, 5)),
+            [("A", 3), ("B", 2)]
+        );
+
+        // A's share of 18 would be 12, capped at its depth of 6.
+        let capped = router.route(&venues, Side::Buy, 18);
+        assert_eq!(split(&capped), [("A", 6), ("B", 6)]);
+        assert_eq!(capped.unallocated, 6);
+    }
+
+    #[test]
+    fn minimize_impact_keeps_each_venue_at_its_touch() {
+        let a = OrderBook::<()>::new("BTC/USD");
+        let b = OrderBook::<()>::new("BTC/USD");
+        ask(&a, 1, 100, 10);
+        ask(&a, 2, 101, 10);
+        ask(&b, 3, 101, 10);
+        let venues = ConsolidatedBook::new()
+            .with_venue("A", &a)
+            .with_venue("B", &b);
+
+        let sweep = SmartOrderRouter::new(RoutingStrategy::SweepBestPrice);
+        assert_eq!(split(&sweep.route(&venues, Side::Buy, 20)), [("A", 20)]);
+
+        let router = SmartOrderRouter::new(RoutingStrategy::MinimizeImpact { slices: 4 });
+        let plan = router.route(&venues, Side::Buy, 20);
+        assert_eq!(split(&plan), [("A", 10), ("B", 10)]);
+        assert!(
+            plan.children
+                .iter()
+                .all(|child| child.simulation.levels_count() == 1)
+        );
+
+        // One slice larger than any single venue spreads over the depth left.
+        let one_slice = SmartOrderRouter::new(RoutingStrategy::MinimizeImpact { slices: 0 });
+        let plan = one_slice.route(&venues, Side::Buy, 25);
+        assert_eq!(split(&plan), [("A", 20), ("B", 5)]);
+        assert_eq!(plan.unallocated, 0);
+    }
+
+    #[test]
+    fn execute_reports_rejected_children_per_venue() {
+        let a = OrderBook::<()>::new("BTC/USD");
+        let b = OrderBook::<()>::new("BTC/USD");
+        ask(&a, 1, 100, 5);
+        ask(&b, 2, 100, 5);
+        b.engage_kill_switch();
+        let venues = ConsolidatedBook::new()
+            .with_venue("A", &a)
+            .with_venue("B", &b);
+        let router = SmartOrderRouter::new(RoutingStrategy::SweepBestPrice);
+
+        let report = router.execute(&venues, Side::Buy, 12);
+        assert_eq!(report.children.len(), 2);
+        assert_eq!(report.filled(), 5);
+        assert_eq!(report.unfilled(), 7);
+        let rejected = report.child("B").expect("child sent to B");
+        assert_eq!(rejected.filled, 0);
+        assert!(matches!(
+            rejected.error,
+            Some(OrderBookError::KillSwitchActive)
+        ));
+        assert!(report.child("A").is_some_and(|child| child.error.is_none()));
+
+        assert!(router.route(&venues, Side::Sell, 3).children.is_empty());
+    }
+}
@@ -95,7 +95,10 @@ mod tests_snapshot_restore {
             .restore_from_snapshot_package(package)
             .expect_err("version mismatch should be rejected");
 
-        assert!(matches!(err, OrderBookError::InvalidOperation { .. }));
+        assert!(matches!(
+            err,
+            OrderBookError::UnsupportedSnapshotVersion { .. }
+        ));
     }
 
     #[test]
@@ -110,7 +113,7 @@ mod tests_snapshot_restore {
             .restore_from_snapshot_package(package)
             .expect_err("restore should fail when symbol differs");
 
-        assert!(matches!(err, OrderBookError::InvalidOperation { .. }));
+        assert!(matches!(err, OrderBookError::SnapshotSymbolMismatch { .. }));
     }
 
     // ── Config round-trip tests ─────────────────────────────────────────
@@ -119,7 +122,7 @@ mod tests_snapshot_restore {
     fn snapshot_package_preserves_fee_schedule() {
         use orderbook_rs::FeeSchedule;
 
-        let mut original = DefaultOrderBook::new("FEE");
+        let original = DefaultOrderBook::new("FEE");
         populate_order_book(&original);
         original.set_fee_schedule(Some(FeeSchedule::new(-2, 5)));
 
@@ -138,7 +141,7 @@ mod tests_snapshot_restore {
     fn snapshot_package_preserves_stp_mode() {
         use orderbook_rs::orderbook::stp::STPMode;
 
-        let mut original = DefaultOrderBook::new("STP");
+        let original = DefaultOrderBook::new("STP");
         populate_order_book(&original);
         original.set_stp_mode(STPMode::CancelTaker);
 
@@ -155,7 +158,7 @@ mod tests_snapshot_restore {
 
     #[test]
     fn snapshot_package_preserves_tick_size() {
-        let mut original = DefaultOrderBook::new("TICK");
+        let original = DefaultOrderBook::new("TICK");
         populate_order_book(&original);
         original.set_tick_size(100);
 
@@ -172,7 +175,7 @@ mod tests_snapshot_restore {
 
     #[test]
     fn snapshot_package_preserves_lot_size() {
-        let mut original = DefaultOrderBook::new("LOT");
+        let original = DefaultOrderBook::new("LOT");
         populate_order_book(&original);
         original.set_lot_size(10);
 
@@ -189,7 +192,7 @@ mod tests_snapshot_restore {
 
     #[test]
     fn snapshot_package_preserves_min_max_order_size() {
-        let mut original = DefaultOrderBook::new("SIZE");
+        let original = DefaultOrderBook::new("SIZE");
         populate_order_book(&original);
         original.set_min_order_size(1);
         original.set_max_order_size(1000);
@@ -207,12 +210,32 @@ mod tests_snapshot_restore {
         assert_eq!(restored.max_order_size(), Some(1000));
     }
 
+    #[test]
+    fn snapshot_package_preserves_min_max_order_notional() {
+        let original = DefaultOrderBook::new("NOTIONAL");
+        populate_order_book(&original);
+        original.set_min_order_notional(100);
+        original.set_max_order_notional(10_000_000);
+
+        let package = original.create_snapshot_package(10).expect("snapshot");
+        assert_eq!(package.min_order_notional, Some(100));
+        assert_eq!(package.max_order_notional, Some(10_000_000));
+
+        let mut restored = DefaultOrderBook::new("NOTIONAL");
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+
+        assert_eq!(restored.min_order_notional(), Some(100));
+        assert_eq!(restored.max_order_notional(), Some(10_000_000));
+    }
+
     #[test]
     fn snapshot_package_preserves_all_config_fields() {
         use orderbook_rs::FeeSchedule;
         use orderbook_rs::orderbook::stp::STPMode;
 
-        let mut original = DefaultOrderBook::new("ALL");
+        let original = DefaultOrderBook::new("ALL");
         populate_order_book(&original);
         original.set_fee_schedule(Some(FeeSchedule::new(-1, 3)));
         original.set_stp_mode(STPMode::CancelBoth);
@@ -280,7 +303,7 @@ mod tests_snapshot_restore {
         use orderbook_rs::FeeSchedule;
         use orderbook_rs::orderbook::stp::STPMode;
 
-        let mut original = DefaultOrderBook::new("JCFG");
+        let original = DefaultOrderBook::new("JCFG");
         populate_order_book(&original);
         original.set_fee_schedule(Some(FeeSchedule::new(0, 4)));
         original.set_stp_mode(STPMode::CancelMaker);
@@ -531,17 +554,11 @@ mod tests_restore_duplicate_price_levels {
             .restore_from_snapshot(bad)
             .expect_err("duplicate-price levels must be rejected");
         match err {
-            OrderBookError::InvalidOperation { message } => {
-                assert!(
-                    message.contains("same price"),
-                    "error names the duplicate-price condition, got: {message}"
-                );
-                assert!(
-                    message.contains("9000"),
-                    "error carries the offending price, got: {message}"
-                );
+            OrderBookError::DuplicateSnapshotLevel { side, price } => {
+                assert_eq!(side, Side::Buy);
+                assert_eq!(price, 9000, "error carries the offending price");
             }
-            other => panic!("expected InvalidOperation, got {other:?}"),
+            other => panic!("expected DuplicateSnapshotLevel, got {other:?}"),
         }
 
         assert_eq!(book.best_bid(), Some(10_000), "pre-restore book untouched");
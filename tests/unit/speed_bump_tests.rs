@@ -0,0 +1,206 @@
+//! Tests for the speed bump delaying aggressive orders.
+
+#[cfg(test)]
+mod tests_speed_bump {
+    use orderbook_rs::{
+        AgentAction, AgentContext, BookConfig, BumpedOrder, Clock, OrderBook, OrderBookError,
+        ReplayClock, SimulationRunner, SpeedBump, SpeedBumpAdmission, Strategy,
+    };
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+    use std::sync::Arc;
+
+    fn bumped_book(bump: SpeedBump) -> (OrderBook<()>, Arc<ReplayClock>) {
+        let clock = Arc::new(ReplayClock::new(1_000));
+        let book = OrderBook::with_clock("BTC/USD", Arc::clone(&clock) as Arc<dyn Clock>);
+        book.reload_config(BookConfig::new().with_speed_bump(bump))
+            .expect("valid speed bump");
+        (book, clock)
+    }
+
+    fn limit(id: u64, price: u128, quantity: u64, side: Side) -> BumpedOrder<()> {
+        BumpedOrder::Order(OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    fn market(id: u64, quantity: u64) -> BumpedOrder<()> {
+        BumpedOrder::Market {
+            id: Id::from_u64(id),
+            quantity,
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+        }
+    }
+
+    fn release_at(admission: Result<SpeedBumpAdmission, OrderBookError>) -> Option<u64> {
+        match admission {
+            Ok(SpeedBumpAdmission::Held { release_at }) => Some(release_at),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn aggressive_orders_wait_while_passive_orders_post() {
+        let (book, clock) = bumped_book(SpeedBump::fixed(5));
+
+        let ask = book.submit_through_speed_bump(limit(1, 100, 10, Side::Sell));
+        assert!(matches!(
+            ask,
+            Ok(SpeedBumpAdmission::Processed(ref ack)) if ack.resting.is_some()
+        ));
+        let bid = book.submit_through_speed_bump(limit(2, 99, 10, Side::Buy));
+        assert!(matches!(bid, Ok(SpeedBumpAdmission::Processed(_))));
+        assert_eq!(book.best_bid(), Some(99));
+
+        let crossing = book.submit_through_speed_bump(limit(3, 100, 4, Side::Buy));
+        assert_eq!(release_at(crossing), Some(1_005));
+        clock.advance_to(1_002);
+        assert_eq!(
+            release_at(book.submit_through_speed_bump(market(4, 2))),
+            Some(1_007)
+        );
+        assert_eq!(book.held_order_count(), 2);
+        assert_eq!(book.next_speed_bump_release(), Some(1_005));
+        assert_eq!(book.best_ask(), Some(100));
+        assert_eq!(book.get_order(Id::from_u64(3)), None);
+
+        assert!(book.release_speed_bump().is_empty());
+        clock.advance_to(1_005);
+        let released = book.release_speed_bump();
+        assert_eq!(released.len(), 1);
+        assert_eq!(
+            (released[0].order_id, released[0].release_at),
+            (Id::from_u64(3), 1_005)
+        );
+        let ack = released[0].result.as_ref().expect("admitted at release");
+        assert!(ack.match_result.is_complete());
+        assert_eq!(book.best_ask(), Some(100));
+
+        assert!(book.cancel_held_order(Id::from_u64(4)).is_some());
+        assert!(book.cancel_held_order(Id::from_u64(4)).is_none());
+        clock.advance_to(1_010);
+        assert!(book.release_speed_bump().is_empty());
+        assert_eq!(book.held_order_count(), 0);
+        assert_eq!(
+            book.get_order(Id::from_u64(1))
+                .map(|order| order.visible_quantity().as_u64()),
+            Some(6)
+        );
+
+        let plain = OrderBook::<()>::new("BTC/USD");
+        plain
+            .add_limit_order(Id::from_u64(1), 100, 10, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        assert_eq!(plain.speed_bump(), None);
+        assert!(matches!(
+            plain.submit_through_speed_bump(market(2, 3)),
+            Ok(SpeedBumpAdmission::Processed(_))
+        ));
+    }
+
+    #[test]
+    fn randomized_delays_are_seeded_and_bounded() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        for bump in [SpeedBump::randomized(5, 3), SpeedBump::fixed(0)] {
+            assert!(matches!(
+                book.reload_config(BookConfig::new().with_speed_bump(bump)),
+                Err(OrderBookError::InvalidConfiguration { .. })
+            ));
+        }
+
+        let delays = |seed: u64| -> Vec<u64> {
+            let (book, _clock) = bumped_book(SpeedBump::randomized(1, 10));
+            book.set_speed_bump_seed(seed);
+            (0..20)
+                .filter_map(|id| release_at(book.submit_through_speed_bump(market(id, 1))))
+                .map(|release_at| release_at - 1_000)
+                .collect()
+        };
+        let first = delays(7);
+        assert_eq!(first.len(), 20);
+        assert_eq!(first, delays(7));
+        assert_ne!(first, delays(8));
+        assert!(first.iter().all(|delay| (1..=10).contains(delay)));
+        assert!(first.iter().any(|&delay| delay != first[0]));
+    }
+
+    /// Quotes one ask on the first tick and pulls it on the second.
+    struct Fader {
+        ticks: u64,
+    }
+
+    impl Strategy for Fader {
+        fn name(&self) -> &str {
+            "fader"
+        }
+
+        fn on_tick(&mut self, ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            self.ticks += 1;
+            match self.ticks {
+                1 => vec![AgentAction::limit("AAA", Side::Sell, 100, 2)],
+                2 => ctx
+                    .open_orders("AAA")
+                    .iter()
+                    .map(|order| AgentAction::cancel("AAA", order.order_id))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    /// Buys once at market on the first tick.
+    struct Sniper {
+        fired: bool,
+    }
+
+    impl Strategy for Sniper {
+        fn name(&self) -> &str {
+            "sniper"
+        }
+
+        fn on_tick(&mut self, _ctx: &AgentContext<'_, ()>) -> Vec<AgentAction> {
+            if std::mem::replace(&mut self.fired, true) {
+                Vec::new()
+            } else {
+                vec![AgentAction::market("AAA", Side::Buy, 1)]
+            }
+        }
+    }
+
+    #[test]
+    fn simulation_runner_releases_held_orders_on_the_simulation_clock() {
+        let run = |bump: Option<SpeedBump>| {
+            let book = OrderBook::new("AAA");
+            if let Some(bump) = bump {
+                book.reload_config(BookConfig::new().with_speed_bump(bump))
+                    .expect("valid speed bump");
+            }
+            let mut runner = SimulationRunner::new(10);
+            runner.add_book(book);
+            runner.add_agent(Fader { ticks: 0 });
+            runner.add_agent(Sniper { fired: false });
+            runner.run(3)
+        };
+
+        let report = run(None);
+        assert_eq!(report.agents[1].stats.bought, 1);
+        assert_eq!(report.agents[0].stats.cancelled, 1);
+
+        // The sniper's order is held from 10 to 25; the fader pulls its
+        // quote at 20.
+        let report = run(Some(SpeedBump::fixed(15)));
+        let (fader, sniper) = (&report.agents[0].stats, &report.agents[1].stats);
+        assert_eq!((fader.orders_submitted, fader.cancelled), (1, 1));
+        assert_eq!(
+            (sniper.bought, sniper.orders_submitted, sniper.rejected),
+            (0, 0, 1)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Integration tests for the selectable stop trigger reference: last
+//! trade, touch midpoint, or an externally supplied mark price.
+
+#[cfg(all(test, feature = "special_orders"))]
+mod tests_stop_trigger {
+    use orderbook_rs::{OrderBook, StopTriggerReference};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn sell_trailing_stop(book: &OrderBook<()>, stop: u128) -> Id {
+        let id = Id::new();
+        book.add_order(OrderType::TrailingStop {
+            id,
+            price: Price::new(stop),
+            quantity: Quantity::new(2),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            trail_amount: Quantity::new(5),
+            last_reference_price: Price::new(stop + 5),
+            extra_fields: (),
+        })
+        .expect("trailing stop rests");
+        id
+    }
+
+    #[test]
+    fn mark_reference_ignores_prints_in_the_book() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.set_stop_trigger_reference(StopTriggerReference::Mark);
+        let stop = sell_trailing_stop(&book, 95);
+
+        // A print at the stop would fire a last-trade trigger, but the mark
+        // is still above it.
+        book.add_limit_order(Id::new(), 95, 1, Side::Buy, TimeInForce::Ioc, None)
+            .expect("print at the stop");
+        assert_eq!(book.last_trade_price(), Some(95));
+        assert_eq!(
+            book.triggered_trailing_stops_on(StopTriggerReference::LastTrade),
+            vec![stop]
+        );
+        assert!(book.triggered_trailing_stops().is_empty(), "no mark yet");
+
+        book.update_mark_price(99);
+        assert!(book.triggered_trailing_stops().is_empty());
+        book.update_mark_price(95);
+        assert_eq!(book.triggered_trailing_stops(), vec![stop]);
+    }
+
+    #[test]
+    fn mid_reference_needs_a_two_sided_book() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.set_stop_trigger_reference(StopTriggerReference::Mid);
+        let stop = sell_trailing_stop(&book, 95);
+        assert!(book.triggered_trailing_stops().is_empty(), "one-sided");
+
+        book.add_limit_order(Id::new(), 94, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("bid");
+        assert_eq!(book.stop_trigger_price(StopTriggerReference::Mid), Some(94));
+        assert_eq!(book.triggered_trailing_stops(), vec![stop]);
+
+        // Switching the book back to last trade un-fires it: nothing traded.
+        book.set_stop_trigger_reference(StopTriggerReference::LastTrade);
+        assert!(book.triggered_trailing_stops().is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+//! Concurrent stress runs through the invariant-checking harness in
+//! `common::stress`. Set `ORDERBOOK_STRESS_MS` for a longer soak.
+
+#[cfg(test)]
+mod tests_stress_harness {
+    use super::super::common::stress::{
+        StressConfig, StressOp, StressReport, minimize, replay, run_stress,
+    };
+    use orderbook_rs::OrderBook;
+    use pricelevel::{Id, Side, TimeInForce};
+    use std::time::Duration;
+
+    fn run(config: &StressConfig) -> StressReport {
+        run_stress(config).unwrap_or_else(|failure| panic!("{failure}"))
+    }
+
+    fn assert_conserved(report: &StressReport) {
+        assert_eq!(
+            report.added,
+            report.resting + report.executed + report.cancelled,
+            "{report:?}"
+        );
+    }
+
+    #[test]
+    fn mixed_workload_passes_every_checkpoint() {
+        let report = run(&StressConfig::from_env().with_threads(2, 2, 2));
+        assert!(report.checkpoints >= 2, "{report:?}");
+        assert!(report.operations > 0);
+        assert!(report.executed > 0);
+        assert!(report.cancelled > 0);
+        assert_conserved(&report);
+    }
+
+    #[test]
+    fn makers_and_cancellers_alone_conserve_quantity() {
+        let config = StressConfig::default()
+            .with_threads(3, 0, 2)
+            .with_duration(Duration::from_millis(150))
+            .with_check_interval(Duration::from_millis(25))
+            .with_seed(7);
+        let report = run(&config);
+        assert_eq!(report.executed, 0, "passive makers crossed: {report:?}");
+        assert!(report.cancelled > 0, "{report:?}");
+        assert_conserved(&report);
+    }
+
+    #[test]
+    fn replay_of_a_sequential_window_passes() {
+        let book = OrderBook::<()>::new("STRESS");
+        book.add_limit_order(
+            Id::sequential(1),
+            100,
+            5,
+            Side::Sell,
+            TimeInForce::Gtc,
+            None,
+        )
+        .expect("seed ask");
+        let checkpoint = book.create_snapshot_package(usize::MAX).expect("package");
+
+        let ops = [
+            StressOp::Limit {
+                id: Id::sequential(2),
+                side: Side::Buy,
+                price: 100,
+                quantity: 3,
+                time_in_force: TimeInForce::Ioc,
+            },
+            StressOp::Market {
+                id: Id::sequential(3),
+                side: Side::Buy,
+                quantity: 1,
+            },
+            StressOp::Cancel {
+                id: Id::sequential(1),
+            },
+        ];
+        assert_eq!(replay(&checkpoint, &ops), Vec::<String>::new());
+    }
+
+    #[test]
+    fn minimize_keeps_only_the_ops_the_failure_needs() {
+        let cancel = |n| StressOp::Cancel {
+            id: Id::sequential(n),
+        };
+        let ops: Vec<StressOp> = (1..=40).map(cancel).collect();
+        let needs = [cancel(7), cancel(31)];
+        let minimized = minimize(ops, |ops| needs.iter().all(|op| ops.contains(op)));
+        assert_eq!(minimized, needs);
+    }
+}
@@ -0,0 +1,171 @@
+//! Integration tests for `OrderBook::bust_trade` / `OrderBook::correct_trade`.
+
+#[cfg(test)]
+mod tests_trade_amendment {
+    use orderbook_rs::{
+        ExecutionHistory, FeeLedger, FeeSchedule, OrderBook, OrderBookError, RejectCode,
+        TradeAmendment, TradeResult,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    /// A book with an execution history, a 5 bps taker fee and a 2 bps
+    /// maker rebate, that has traded 10 @ 1_000 and then 5 @ 1_010.
+    fn traded_book() -> (OrderBook<()>, Arc<Mutex<Vec<TradeResult>>>) {
+        let mut book = OrderBook::<()>::new("BTC/USD");
+        book.set_execution_history(ExecutionHistory::with_capacity(16));
+        book.set_fee_schedule(Some(FeeSchedule::new(-2, 5)));
+        let trades: Arc<Mutex<Vec<TradeResult>>> = Arc::default();
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().unwrap().push(trade.clone());
+        }));
+        for (price, maker) in [(1_000, 1), (1_010, 2)] {
+            book.add_limit_order_with_user(
+                Id::new_uuid(),
+                price,
+                10,
+                Side::Sell,
+                TimeInForce::Gtc,
+                user(maker),
+                None,
+            )
+            .expect("ask");
+        }
+        book.submit_market_order_with_user(Id::new_uuid(), 10, Side::Buy, user(3))
+            .expect("first buy");
+        book.submit_market_order_with_user(Id::new_uuid(), 5, Side::Buy, user(3))
+            .expect("second buy");
+        (book, trades)
+    }
+
+    #[test]
+    fn bust_emits_a_refund_and_rolls_back_the_last_trade() {
+        let (book, trades) = traded_book();
+        assert_eq!(book.last_trade_price(), Some(1_010));
+        let ledger = FeeLedger::new();
+        for trade in trades.lock().unwrap().iter() {
+            ledger.record_charged(trade);
+        }
+        let busted = book
+            .execution_history()
+            .and_then(|h| h.get(2))
+            .expect("fill 2");
+
+        let result = book.bust_trade(2).expect("bust");
+        assert_eq!(
+            result.amendment.as_deref(),
+            Some(&TradeAmendment::Bust(busted))
+        );
+        assert!(result.fills.is_empty());
+        assert!(result.match_result.trades().is_empty());
+        assert_eq!(result.total_taker_fees, -busted.taker.fee);
+        assert_eq!(result.total_maker_fees, -busted.maker.fee);
+        assert_eq!(
+            trades.lock().unwrap().last().map(|t| t.engine_seq),
+            Some(result.engine_seq)
+        );
+
+        // The previous fill is the last trade again; the counters forget the bust.
+        assert_eq!(book.last_trade_price(), Some(1_000));
+        let stats = book.stats();
+        assert_eq!((stats.trades, stats.volume), (1, 10));
+        assert_eq!(stats.notional_volume, 10_000);
+
+        ledger.record_charged(&result);
+        let taker = ledger.user_total(user(3));
+        assert_eq!((taker.taker_fills, taker.taker_notional), (1, 10_000));
+        assert_eq!(taker.taker_fees, 5);
+        assert_eq!(ledger.user_total(user(2)).maker_fills, 0);
+
+        // A fill can only be busted once, and busting the rest clears the last trade.
+        let again = book.bust_trade(2);
+        assert!(matches!(
+            again,
+            Err(OrderBookError::ExecutionNotFound { execution_id: 2 })
+        ));
+        book.bust_trade(1).expect("bust first");
+        assert_eq!(book.last_trade_price(), None);
+        assert_eq!(book.stats().volume, 0);
+        // Resting liquidity is not reinstated.
+        assert_eq!(book.best_ask(), Some(1_010));
+    }
+
+    #[test]
+    fn correction_restates_price_quantity_fees_and_statistics() {
+        let (book, trades) = traded_book();
+        let original = book
+            .execution_history()
+            .and_then(|h| h.get(2))
+            .expect("fill 2");
+
+        let result = book.correct_trade(2, 4, 1_020).expect("correct");
+        let [corrected] = result.fills.as_slice() else {
+            panic!("expected the corrected fill, got {:?}", result.fills);
+        };
+        assert_eq!(
+            result.amendment.as_deref(),
+            Some(&TradeAmendment::Correction(original))
+        );
+        assert_eq!((corrected.price, corrected.quantity), (1_020, 4));
+        assert_eq!(corrected.execution_id, 2);
+        assert_eq!(corrected.matched_at_ns, original.matched_at_ns);
+        assert_eq!(corrected.maker.user_id, user(2));
+        // 5 bps of 4_080 truncates to 2; the original 5_050 was charged 2 too.
+        assert_eq!(corrected.taker.fee, 2);
+        assert_eq!(
+            result.total_taker_fees,
+            corrected.taker.fee - original.taker.fee
+        );
+        assert_eq!(trades.lock().unwrap().len(), 3);
+
+        assert_eq!(book.last_trade_price(), Some(1_020));
+        let stats = book.stats();
+        assert_eq!((stats.trades, stats.volume), (2, 14));
+        assert_eq!(stats.notional_volume, 10_000 + 4_080);
+        assert_eq!(stats.highest_trade_price, Some(1_020));
+        assert_eq!(
+            book.execution_history().and_then(|h| h.get(2)),
+            Some(*corrected)
+        );
+
+        // Correcting an older fill leaves the last trade price alone.
+        book.correct_trade(1, 10, 990).expect("correct first");
+        assert_eq!(book.last_trade_price(), Some(1_020));
+    }
+
+    #[test]
+    fn amendments_need_a_known_execution_and_valid_terms() {
+        let (book, _) = traded_book();
+        let zero = book
+            .correct_trade(2, 0, 1_000)
+            .expect_err("zero correction");
+        assert!(matches!(
+            zero,
+            OrderBookError::CorrectionToZero { execution_id: 2 }
+        ));
+        assert_eq!(zero.code(), RejectCode::CorrectionToZero);
+        assert!(matches!(
+            book.correct_trade(2, 1, 0),
+            Err(OrderBookError::InvalidPriceLevel(0))
+        ));
+        let missing = book.bust_trade(99).expect_err("unknown execution");
+        assert_eq!(missing.code(), RejectCode::ExecutionNotFound);
+
+        // Without a history nothing can be amended.
+        let book = OrderBook::<()>::new("BTC/USD");
+        book.set_trade_listener(Arc::new(|_: &TradeResult| {}));
+        book.add_limit_order(Id::new_uuid(), 100, 1, Side::Sell, TimeInForce::Gtc, None)
+            .expect("ask");
+        book.submit_market_order(Id::new_uuid(), 1, Side::Buy)
+            .expect("fill");
+        assert!(matches!(
+            book.bust_trade(1),
+            Err(OrderBookError::ExecutionNotFound { execution_id: 1 })
+        ));
+    }
+}
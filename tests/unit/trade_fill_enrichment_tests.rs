@@ -0,0 +1,276 @@
+//! Integration tests for the per-fill detail carried on `TradeResult::fills`.
+
+#[cfg(test)]
+mod tests_trade_fill_enrichment {
+    use orderbook_rs::{
+        Clock, FeeSchedule, LiquidityFlag, OrderBook, PriceLevelFill, StubClock, TradeResult,
+    };
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+    use std::sync::{Arc, Mutex};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    fn book_with_sink() -> (OrderBook<()>, Arc<Mutex<Vec<TradeResult>>>) {
+        let trades: Arc<Mutex<Vec<TradeResult>>> = Arc::default();
+        let book = OrderBook::<()>::new("BTC/USD");
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().unwrap().push(trade.clone());
+        }));
+        (book, trades)
+    }
+
+    #[test]
+    fn limit_sweep_names_both_sides_with_fees_and_maker_leaves() {
+        let (book, trades) = book_with_sink();
+        book.set_fee_schedule(Some(FeeSchedule::new(-2, 5)));
+        let (full, partial, taker) = (Id::new_uuid(), Id::new_uuid(), Id::new_uuid());
+        book.add_limit_order_with_user(full, 1_000, 4, Side::Sell, TimeInForce::Gtc, user(1), None)
+            .expect("first ask");
+        book.add_limit_order_with_user(
+            partial,
+            1_010,
+            10,
+            Side::Sell,
+            TimeInForce::Gtc,
+            user(2),
+            None,
+        )
+        .expect("second ask");
+        book.add_limit_order_with_user(taker, 1_010, 7, Side::Buy, TimeInForce::Gtc, user(3), None)
+            .expect("taker");
+
+        let trades = trades.lock().unwrap();
+        let trade = &trades[0];
+        assert_eq!(trade.fills.len(), trade.match_result.trades().len());
+        let [first, second] = trade.fills.as_slice() else {
+            panic!("expected two fills, got {:?}", trade.fills);
+        };
+
+        assert_eq!((first.maker.order_id, first.maker.user_id), (full, user(1)));
+        assert_eq!(
+            (first.taker.order_id, first.taker.user_id),
+            (taker, user(3))
+        );
+        assert_eq!(first.maker.liquidity, LiquidityFlag::Maker);
+        assert_eq!(first.taker.liquidity, LiquidityFlag::Taker);
+        assert_eq!(
+            (first.maker.side, first.taker.side),
+            (Side::Sell, Side::Buy)
+        );
+        assert_eq!(first.maker_remaining_quantity, 0);
+
+        assert_eq!(second.maker.user_id, user(2));
+        assert_eq!((second.price, second.quantity), (1_010, 3));
+        assert_eq!(second.maker_remaining_quantity, 7);
+        assert_eq!(
+            second.party(taker).map(|p| p.liquidity),
+            Some(LiquidityFlag::Taker)
+        );
+        assert!(second.party(full).is_none());
+
+        assert_eq!(first.taker.fee, 2); // 5 bps of 4_000
+        let maker_fees: i128 = trade.fills.iter().map(|f| f.maker.fee).sum();
+        let taker_fees: i128 = trade.fills.iter().map(|f| f.taker.fee).sum();
+        assert_eq!(maker_fees, trade.total_maker_fees);
+        assert_eq!(taker_fees, trade.total_taker_fees);
+
+        // The filled maker left its user's index; the partial one did not.
+        assert!(book.orders_for_user(user(1)).is_empty());
+        assert_eq!(book.orders_for_user(user(2)).len(), 1);
+        assert!(book.audit().is_empty());
+    }
+
+    #[test]
+    fn market_paths_and_with_result_callers_get_fills() {
+        let (book, trades) = book_with_sink();
+        book.add_iceberg_order_with_user(
+            Id::new_uuid(),
+            500,
+            5,
+            20,
+            Side::Buy,
+            TimeInForce::Gtc,
+            user(4),
+            None,
+        )
+        .expect("iceberg bid");
+
+        book.submit_market_order_with_user(Id::new_uuid(), 3, Side::Sell, user(5))
+            .expect("market sell");
+        book.match_market_order_by_amount_with_user(Id::new_uuid(), 1_000, Side::Sell, user(6))
+            .expect("notional sell");
+        {
+            let trades = trades.lock().unwrap();
+            let market = &trades[0].fills[0];
+            assert_eq!(
+                (market.maker.user_id, market.taker.user_id),
+                (user(4), user(5))
+            );
+            // Visible and hidden both count toward what is still resting.
+            assert_eq!(market.maker_remaining_quantity, 22);
+            let notional = &trades[1].fills[0];
+            assert_eq!(notional.taker.user_id, user(6));
+            assert_eq!(notional.quantity, 2);
+            assert_eq!(notional.maker_remaining_quantity, 20);
+        }
+
+        // No listener: the result handed back to the caller still carries them.
+        book.remove_trade_listener();
+        let (_, result) = book
+            .add_limit_order_with_user_and_result(
+                Id::new_uuid(),
+                500,
+                1,
+                Side::Sell,
+                TimeInForce::Ioc,
+                user(7),
+                None,
+            )
+            .expect("ioc sell");
+        let result = result.expect("trade result");
+        assert_eq!(result.fills.len(), 1);
+        assert_eq!(result.fills[0].taker.user_id, user(7));
+        assert_eq!(result.fills[0].maker.fee, 0);
+    }
+
+    #[test]
+    fn fills_survive_json_and_default_to_empty_for_older_payloads() {
+        let (book, trades) = book_with_sink();
+        book.add_limit_order_with_user(
+            Id::new_uuid(),
+            100,
+            5,
+            Side::Sell,
+            TimeInForce::Gtc,
+            user(1),
+            None,
+        )
+        .expect("ask");
+        book.submit_market_order_with_user(Id::new_uuid(), 2, Side::Buy, user(2))
+            .expect("fill");
+        let trade = trades.lock().unwrap()[0].clone();
+
+        let json = serde_json::to_string(&trade).expect("serialize");
+        let decoded: TradeResult = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded.fills, trade.fills);
+
+        let mut legacy: serde_json::Value = serde_json::from_str(&json).expect("value");
+        legacy.as_object_mut().expect("object").remove("fills");
+        let decoded: TradeResult = serde_json::from_value(legacy).expect("legacy payload");
+        assert!(decoded.fills.is_empty());
+        assert_eq!(decoded.match_result.trades().len(), 1);
+    }
+
+    #[test]
+    fn execution_ids_and_match_times_are_per_fill_and_survive_restore() {
+        let clock = Arc::new(StubClock::starting_at(1_000));
+        let book = OrderBook::<()>::with_clock("BTC/USD", Arc::clone(&clock) as Arc<dyn Clock>);
+        let trades: Arc<Mutex<Vec<TradeResult>>> = Arc::default();
+        let sink = Arc::clone(&trades);
+        book.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().unwrap().push(trade.clone());
+        }));
+        for price in [100, 101, 102] {
+            book.add_limit_order(Id::new_uuid(), price, 1, Side::Sell, TimeInForce::Gtc, None)
+                .expect("ask");
+        }
+        assert_eq!(book.execution_seq(), 1);
+        book.submit_market_order(Id::new_uuid(), 2, Side::Buy)
+            .expect("sweep");
+
+        let fills = trades.lock().unwrap()[0].fills.clone();
+        let ids: Vec<u64> = fills.iter().map(|f| f.execution_id).collect();
+        assert_eq!(ids, [1, 2]);
+        // Each fill reads the clock; the stub ticks once per read.
+        assert!(fills[0].matched_at_ns >= 1_000_000_000);
+        assert!(fills[1].matched_at_ns > fills[0].matched_at_ns);
+        assert_eq!(book.execution_seq(), 3);
+
+        let json = serde_json::to_string(&trades.lock().unwrap()[0]).expect("serialize");
+        let decoded: TradeResult = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded.fills[1].execution_id, 2);
+        assert_eq!(decoded.fills[1].matched_at_ns, fills[1].matched_at_ns);
+
+        // A restored book carries on from the source book's counter.
+        let mut restored = OrderBook::<()>::new("BTC/USD");
+        restored
+            .restore_from_snapshot_package(book.create_snapshot_package(10).expect("package"))
+            .expect("restore");
+        let sink = Arc::clone(&trades);
+        restored.set_trade_listener(Arc::new(move |trade: &TradeResult| {
+            sink.lock().unwrap().push(trade.clone());
+        }));
+        restored
+            .submit_market_order(Id::new_uuid(), 1, Side::Buy)
+            .expect("last ask");
+        assert_eq!(trades.lock().unwrap()[1].fills[0].execution_id, 3);
+
+        // Legacy fills without the new keys decode as unassigned.
+        let mut legacy: serde_json::Value = serde_json::from_str(&json).expect("value");
+        let fill = legacy["fills"][0].as_object_mut().expect("fill object");
+        fill.remove("execution_id");
+        fill.remove("matched_at_ns");
+        let decoded: TradeResult = serde_json::from_value(legacy).expect("legacy fill");
+        assert_eq!(
+            (
+                decoded.fills[0].execution_id,
+                decoded.fills[0].matched_at_ns
+            ),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn level_aggregation_condenses_a_sweep_per_price() {
+        let (book, trades) = book_with_sink();
+        assert!(!book.trade_level_aggregation());
+        for (price, owner) in [(100, 1), (100, 2), (100, 1), (101, 3)] {
+            book.add_limit_order_with_user(
+                Id::new_uuid(),
+                price,
+                2,
+                Side::Sell,
+                TimeInForce::Gtc,
+                user(owner),
+                None,
+            )
+            .expect("ask");
+        }
+        book.submit_market_order(Id::new_uuid(), 1, Side::Buy)
+            .expect("unaggregated sweep");
+        book.set_trade_level_aggregation(true);
+        book.submit_market_order(Id::new_uuid(), 6, Side::Buy)
+            .expect("aggregated sweep");
+
+        let trades = trades.lock().unwrap();
+        assert!(trades[0].level_fills.is_empty());
+        let sweep = &trades[1];
+        assert_eq!(sweep.fills.len(), 4, "detailed fills are kept");
+        assert_eq!(
+            sweep.level_fills,
+            vec![
+                PriceLevelFill {
+                    price: 100,
+                    quantity: 5,
+                    notional: 500,
+                    counterparty_count: 3,
+                },
+                PriceLevelFill {
+                    price: 101,
+                    quantity: 1,
+                    notional: 101,
+                    counterparty_count: 1,
+                },
+            ]
+        );
+        assert_eq!(sweep.aggregate_levels(), sweep.level_fills);
+        assert_eq!(trades[0].aggregate_levels().len(), 1);
+
+        let decoded: TradeResult =
+            serde_json::from_slice(&serde_json::to_vec(sweep).unwrap()).unwrap();
+        assert_eq!(decoded.level_fills, sweep.level_fills);
+    }
+}
@@ -0,0 +1,171 @@
+//! Integration tests for the trading session schedule: admission per
+//! phase, and `Day` expiry and cancel-on-close at the close, driven by an
+//! injected clock.
+
+#[cfg(test)]
+mod tests_trading_session {
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::{
+        BookConfig, CancelReason, Clock, OrderBook, OrderBookError, OrderStateTracker, OrderStatus,
+        RejectReason, ReplayClock, SessionPhase, TradingSession,
+    };
+    use pricelevel::{Id, OrderUpdate, Price, Side, TimeInForce};
+    use std::sync::Arc;
+
+    const HOUR: u64 = 3_600_000;
+
+    /// 09:00 – 17:00 with a one hour pre-open auction from 08:00.
+    fn session() -> TradingSession {
+        TradingSession::new(9 * HOUR, 17 * HOUR).with_pre_open_auction_ms(HOUR)
+    }
+
+    fn book_at(start: u64, session: TradingSession) -> (OrderBook<()>, Arc<ReplayClock>) {
+        let clock = Arc::new(ReplayClock::new(start));
+        let mut book = OrderBook::<()>::with_clock("TEST", Arc::clone(&clock) as Arc<dyn Clock>);
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book.reload_config(BookConfig::new().with_trading_session(session))
+            .expect("valid schedule");
+        (book, clock)
+    }
+
+    #[test]
+    fn closed_session_rejects_new_flow_and_modifications() {
+        let (book, clock) = book_at(10 * HOUR, session());
+        let resting = Id::from_u64(1);
+        book.add_limit_order(resting, 100, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("continuous");
+
+        clock.advance_to(18 * HOUR);
+        assert_eq!(book.session_phase(), Some(SessionPhase::Closed));
+        let limit = Id::from_u64(2);
+        assert!(matches!(
+            book.add_limit_order(limit, 101, 5, Side::Buy, TimeInForce::Gtc, None),
+            Err(OrderBookError::MarketClosed {
+                phase: SessionPhase::Closed
+            })
+        ));
+        let market = Id::from_u64(3);
+        assert!(matches!(
+            book.submit_market_order(market, 5, Side::Sell),
+            Err(OrderBookError::MarketClosed { .. })
+        ));
+        for id in [limit, market] {
+            assert_eq!(
+                book.order_state_tracker().and_then(|t| t.get(id)),
+                Some(OrderStatus::Rejected {
+                    reason: RejectReason::MarketClosed
+                })
+            );
+        }
+        assert!(matches!(
+            book.update_order(OrderUpdate::UpdatePrice {
+                order_id: resting,
+                new_price: Price::new(99),
+            }),
+            Err(OrderBookError::MarketClosed { .. })
+        ));
+        assert_eq!(book.best_bid(), Some(100), "GTC order survives the close");
+        assert!(book.cancel_order(resting).expect("cancel").is_some());
+    }
+
+    #[test]
+    fn pre_open_auction_rests_but_never_trades() {
+        let (book, clock) = book_at(8 * HOUR + 1, session());
+        assert_eq!(book.session_phase(), Some(SessionPhase::PreOpenAuction));
+        book.add_limit_order(Id::from_u64(1), 100, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("non-crossing ask rests");
+        assert!(matches!(
+            book.add_limit_order(Id::from_u64(2), 100, 5, Side::Buy, TimeInForce::Gtc, None),
+            Err(OrderBookError::MarketClosed {
+                phase: SessionPhase::PreOpenAuction
+            })
+        ));
+        assert!(matches!(
+            book.submit_market_order(Id::from_u64(3), 1, Side::Buy),
+            Err(OrderBookError::MarketClosed { .. })
+        ));
+        assert!(book.last_trade_price().is_none());
+
+        clock.advance_to(9 * HOUR);
+        assert_eq!(book.session_phase(), Some(SessionPhase::Continuous));
+        book.add_limit_order(Id::from_u64(4), 100, 5, Side::Buy, TimeInForce::Gtc, None)
+            .expect("crosses once open");
+        assert_eq!(book.last_trade_price(), Some(100));
+    }
+
+    #[test]
+    fn close_expires_day_orders_and_optionally_cancels_the_rest() {
+        let (book, clock) = book_at(10 * HOUR, session());
+        let day = Id::from_u64(1);
+        let gtc = Id::from_u64(2);
+        book.add_limit_order(day, 100, 5, Side::Buy, TimeInForce::Day, None)
+            .expect("day");
+        book.add_limit_order(gtc, 110, 5, Side::Sell, TimeInForce::Gtc, None)
+            .expect("gtc");
+
+        // The close is applied by the next gated operation.
+        clock.advance_to(17 * HOUR + 5);
+        assert!(book.cancel_order(Id::from_u64(99)).is_ok());
+        assert!(book.get_order(day).is_none());
+        assert_eq!(
+            book.order_state_tracker().and_then(|t| t.get(day)),
+            Some(OrderStatus::Cancelled {
+                filled_quantity: 0,
+                reason: CancelReason::TimeInForceExpired
+            })
+        );
+        assert!(book.get_order(gtc).is_some());
+        assert!(book.advance_session().is_none(), "close already applied");
+
+        // Next day, with cancel on close: the scheduler processes the close.
+        book.reload_config(
+            BookConfig::new().with_trading_session(session().with_cancel_on_close(true)),
+        )
+        .expect("valid schedule");
+        clock.advance_to(24 * HOUR + 12 * HOUR);
+        assert!(book.advance_session().is_none(), "opening closes nothing");
+        let next_day = Id::from_u64(3);
+        book.add_limit_order(next_day, 101, 5, Side::Buy, TimeInForce::Day, None)
+            .expect("day order of the next session");
+
+        clock.advance_to(24 * HOUR + 17 * HOUR);
+        let closed = book.advance_session().expect("close due");
+        assert_eq!(closed.close_ms, 24 * HOUR + 17 * HOUR);
+        assert_eq!(closed.expired, vec![next_day]);
+        assert_eq!(closed.cancelled, vec![gtc]);
+        assert_eq!(
+            book.order_state_tracker().and_then(|t| t.get(gtc)),
+            Some(OrderStatus::Cancelled {
+                filled_quantity: 0,
+                reason: CancelReason::SessionClosed
+            })
+        );
+        assert_eq!(book.best_ask(), None);
+    }
+
+    #[test]
+    fn manager_advances_every_scheduled_book() {
+        let clock = Arc::new(ReplayClock::new(10 * HOUR));
+        let mut manager = BookManagerStd::<()>::new();
+        for symbol in ["AAA", "BBB"] {
+            manager.add_book(symbol).expect("add book");
+            manager
+                .get_book_mut(symbol)
+                .expect("book")
+                .set_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        }
+        let scheduled = manager.get_book("AAA").expect("book");
+        scheduled
+            .reload_config(BookConfig::new().with_trading_session(session()))
+            .expect("valid schedule");
+        scheduled
+            .add_limit_order(Id::from_u64(1), 100, 5, Side::Buy, TimeInForce::Day, None)
+            .expect("day");
+
+        clock.advance_to(17 * HOUR);
+        let closes = manager.advance_sessions();
+        assert_eq!(closes.len(), 1);
+        assert_eq!(closes["AAA"].expired, vec![Id::from_u64(1)]);
+        assert!(manager.advance_sessions().is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+//! Integration tests for `update_trailing_state`, which moves a resting
+//! trailing stop's stop price and reference price in one modify.
+
+#[cfg(test)]
+mod tests_trailing_state {
+    use orderbook_rs::{BookConfig, OrderBook, OrderBookError, RejectCode};
+    use pricelevel::{Hash32, Id, OrderType, Price, Quantity, Side, TimeInForce, TimestampMs};
+
+    fn trailing_stop(book: &OrderBook<()>, side: Side, stop: u128, reference: u128) -> Id {
+        let id = Id::new();
+        book.add_order(OrderType::TrailingStop {
+            id,
+            price: Price::new(stop),
+            quantity: Quantity::new(2),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(0),
+            time_in_force: TimeInForce::Gtc,
+            trail_amount: Quantity::new(5),
+            last_reference_price: Price::new(reference),
+            extra_fields: (),
+        })
+        .expect("trailing stop rests");
+        id
+    }
+
+    fn state(book: &OrderBook<()>, id: Id) -> (u128, u128) {
+        match *book.get_order(id).expect("resting") {
+            OrderType::TrailingStop {
+                price,
+                last_reference_price,
+                ..
+            } => (price.as_u128(), last_reference_price.as_u128()),
+            _ => panic!("not a trailing stop"),
+        }
+    }
+
+    #[test]
+    fn stop_and_reference_move_together_in_both_directions() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        let sell = trailing_stop(&book, Side::Sell, 95, 100);
+        let buy = trailing_stop(&book, Side::Buy, 80, 75);
+
+        book.update_trailing_state(sell, Price::new(105), Price::new(110))
+            .expect("update")
+            .expect("resting");
+        assert_eq!(state(&book, sell), (105, 110));
+        assert_eq!(book.best_ask(), Some(105));
+
+        book.update_trailing_state(buy, Price::new(70), Price::new(65))
+            .expect("update")
+            .expect("resting");
+        assert_eq!(state(&book, buy), (70, 65));
+        assert_eq!(book.best_bid(), Some(70));
+
+        // The reference alone may move, e.g. to record a new extreme.
+        book.update_trailing_state(buy, Price::new(70), Price::new(64))
+            .expect("update")
+            .expect("resting");
+        assert_eq!(state(&book, buy), (70, 64));
+    }
+
+    #[test]
+    fn rejected_updates_leave_both_prices_untouched() {
+        let book = OrderBook::<()>::new("BTC/USD");
+        let sell = trailing_stop(&book, Side::Sell, 95, 100);
+
+        assert!(matches!(
+            book.update_trailing_state(sell, Price::new(95), Price::new(100)),
+            Err(OrderBookError::PriceUnchanged { price: 95, .. })
+        ));
+
+        book.reload_config(BookConfig::new().with_tick_size(5))
+            .expect("valid config");
+        assert!(matches!(
+            book.update_trailing_state(sell, Price::new(103), Price::new(108)),
+            Err(OrderBookError::InvalidTickSize { price: 103, .. })
+        ));
+        assert_eq!(state(&book, sell), (95, 100));
+
+        let limit = Id::new();
+        book.add_limit_order(limit, 50, 1, Side::Buy, TimeInForce::Gtc, None)
+            .expect("limit");
+        let err = book
+            .update_trailing_state(limit, Price::new(55), Price::new(60))
+            .expect_err("not a trailing stop");
+        assert!(matches!(err, OrderBookError::NotTrailingStop { order_id } if order_id == limit));
+        assert_eq!(err.code(), RejectCode::NotTrailingStop);
+        assert_eq!(book.best_bid(), Some(50));
+
+        assert!(matches!(
+            book.update_trailing_state(Id::new(), Price::new(55), Price::new(60)),
+            Ok(None)
+        ));
+    }
+}
@@ -241,7 +241,7 @@ mod tests_two_tranche_review_gaps {
     /// from the saturated `u64::MAX` total.
     #[test]
     fn overflow_beats_risk_gate() {
-        let mut book: OrderBook<()> = DefaultOrderBook::new("OVR");
+        let book: OrderBook<()> = DefaultOrderBook::new("OVR");
         book.set_risk_config(RiskConfig::new().with_max_notional_per_account(1_000_000));
 
         let err = book
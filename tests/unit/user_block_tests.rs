@@ -0,0 +1,157 @@
+//! Integration tests for the per-user kill switch on `OrderBook<T>`.
+//!
+//! `block_user` cancels every resting order of one user and rejects that
+//! user's new flow until `unblock_user`; other users are unaffected.
+
+#[cfg(test)]
+mod tests_user_block {
+    use super::super::common::rest_for;
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd};
+    use orderbook_rs::orderbook::order_state::{CancelReason, OrderStateTracker, OrderStatus};
+    use orderbook_rs::{OrderBook, OrderBookError, RejectReason};
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    fn user(byte: u8) -> Hash32 {
+        Hash32::new([byte; 32])
+    }
+
+    fn book_with_tracker() -> OrderBook<()> {
+        let mut book = OrderBook::<()>::new("TEST");
+        book.set_order_state_tracker(OrderStateTracker::new());
+        book
+    }
+
+    #[test]
+    fn block_user_cancels_only_that_users_resting_orders() {
+        let book = book_with_tracker();
+        let a1 = rest_for(&book, 100, 10, Side::Buy, user(1));
+        let a2 = rest_for(&book, 110, 10, Side::Sell, user(1));
+        let b1 = rest_for(&book, 99, 10, Side::Buy, user(2));
+
+        let result = book.block_user(user(1));
+        assert_eq!(result.cancelled_count(), 2);
+        assert!(result.cancelled_order_ids().contains(&a1));
+        assert!(result.cancelled_order_ids().contains(&a2));
+
+        assert!(book.get_order(a1).is_none());
+        assert!(book.get_order(a2).is_none());
+        assert!(book.get_order(b1).is_some());
+        assert!(matches!(
+            book.order_status(a1),
+            Some(OrderStatus::Cancelled {
+                reason: CancelReason::UserBlocked,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn blocked_user_new_flow_is_rejected() {
+        let book = book_with_tracker();
+        rest_for(&book, 100, 10, Side::Sell, user(2));
+        let _ = book.block_user(user(1));
+        assert!(book.is_user_blocked(user(1)));
+        assert!(!book.is_user_blocked(user(2)));
+
+        let order_id = Id::new_uuid();
+        let limit = book.add_limit_order_with_user(
+            order_id,
+            90,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            user(1),
+            None,
+        );
+        assert!(matches!(
+            limit,
+            Err(OrderBookError::UserBlocked { user_id }) if user_id == user(1)
+        ));
+        assert_eq!(
+            book.order_status(order_id),
+            Some(OrderStatus::Rejected {
+                reason: RejectReason::UserBlocked
+            })
+        );
+
+        let market = book.submit_market_order_with_user(Id::new_uuid(), 5, Side::Buy, user(1));
+        assert!(matches!(market, Err(OrderBookError::UserBlocked { .. })));
+
+        let by_amount =
+            book.submit_market_order_by_amount_with_user(Id::new_uuid(), 500, Side::Buy, user(1));
+        assert!(matches!(by_amount, Err(OrderBookError::UserBlocked { .. })));
+
+        // Other users keep trading.
+        assert!(
+            book.submit_market_order_with_user(Id::new_uuid(), 5, Side::Buy, user(3))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn unblock_user_restores_admission() {
+        let book = OrderBook::<()>::new("TEST");
+        let _ = book.block_user(user(1));
+        assert_eq!(book.blocked_users(), vec![user(1)]);
+
+        assert!(book.unblock_user(user(1)));
+        assert!(!book.unblock_user(user(1)));
+        assert!(book.blocked_users().is_empty());
+
+        rest_for(&book, 100, 10, Side::Buy, user(1));
+        assert_eq!(book.best_bid(), Some(100));
+    }
+
+    #[test]
+    fn cancels_from_blocked_user_are_accepted() {
+        let book = OrderBook::<()>::new("TEST");
+        let _ = book.block_user(user(1));
+        // Nothing rests for the user, but cancel paths are never gated.
+        assert!(book.cancel_orders_by_user(user(1)).is_empty());
+        assert!(book.cancel_order(Id::new_uuid()).is_ok());
+    }
+
+    #[test]
+    fn blocked_users_round_trip_through_snapshot() {
+        let original = OrderBook::<()>::new("TEST");
+        let _ = original.block_user(user(2));
+        let _ = original.block_user(user(1));
+        let package = original
+            .create_snapshot_package(10)
+            .expect("snapshot package");
+        assert_eq!(package.blocked_users, vec![user(1), user(2)]);
+
+        let mut restored = OrderBook::<()>::new("TEST");
+        let _ = restored.block_user(user(9));
+        restored
+            .restore_from_snapshot_package(package)
+            .expect("restore");
+        assert_eq!(restored.blocked_users(), vec![user(1), user(2)]);
+        assert!(!restored.is_user_blocked(user(9)));
+    }
+
+    #[test]
+    fn manager_blocks_user_across_books() {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        mgr.add_book("BTC/USD").expect("add book");
+        mgr.add_book("ETH/USD").expect("add book");
+        for symbol in ["BTC/USD", "ETH/USD"] {
+            let book = mgr.get_book(symbol).expect("book");
+            rest_for(book, 100, 10, Side::Buy, user(1));
+            rest_for(book, 101, 10, Side::Sell, user(2));
+        }
+
+        let results = mgr.block_user_across_books(user(1));
+        assert_eq!(results.len(), 2);
+        assert!(results.values().all(|result| result.cancelled_count() == 1));
+        for symbol in ["BTC/USD", "ETH/USD"] {
+            let book = mgr.get_book(symbol).expect("book");
+            assert!(book.is_user_blocked(user(1)));
+            assert_eq!(book.best_ask(), Some(101));
+            assert_eq!(book.best_bid(), None);
+        }
+
+        assert_eq!(mgr.unblock_user_across_books(user(1)), 2);
+        assert_eq!(mgr.unblock_user_across_books(user(1)), 0);
+    }
+}
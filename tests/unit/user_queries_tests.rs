@@ -0,0 +1,59 @@
+//! Integration tests for user-scoped order queries
+//! (`OrderBook::orders_for_user`, `open_quantity_for_user`).
+
+#[cfg(test)]
+mod tests_user_queries {
+    use super::super::common::rest_for;
+    use orderbook_rs::OrderBook;
+    use pricelevel::{Hash32, Id, Side, TimeInForce};
+
+    #[test]
+    fn returns_only_the_users_orders() {
+        let book = OrderBook::<()>::new("TEST");
+        let alice = Hash32::new([1u8; 32]);
+        let bob = Hash32::new([2u8; 32]);
+        let a1 = rest_for(&book, 100, 10, Side::Buy, alice);
+        let a2 = rest_for(&book, 105, 4, Side::Sell, alice);
+        rest_for(&book, 100, 7, Side::Buy, bob);
+
+        let orders = book.orders_for_user(alice);
+        let ids: Vec<Id> = orders.iter().map(|o| o.order_id).collect();
+        assert_eq!(ids, vec![a1, a2]);
+        assert_eq!(orders[0].side, Side::Buy);
+        assert_eq!(orders[0].price, 100);
+        assert_eq!(orders[0].visible_quantity, 10);
+        assert_eq!(orders[1].side, Side::Sell);
+        assert_eq!(orders[1].time_in_force, TimeInForce::Gtc);
+
+        assert!(book.orders_for_user(Hash32::new([9u8; 32])).is_empty());
+    }
+
+    #[test]
+    fn open_quantity_is_per_side_and_tracks_fills() {
+        let book = OrderBook::<()>::new("TEST");
+        let alice = Hash32::new([1u8; 32]);
+        rest_for(&book, 100, 10, Side::Sell, alice);
+        rest_for(&book, 101, 5, Side::Sell, alice);
+        rest_for(&book, 90, 3, Side::Buy, alice);
+
+        assert_eq!(book.open_quantity_for_user(alice, Side::Sell), 15);
+        assert_eq!(book.open_quantity_for_user(alice, Side::Buy), 3);
+
+        book.submit_market_order(Id::new_uuid(), 12, Side::Buy)
+            .expect("taker");
+        assert_eq!(book.open_quantity_for_user(alice, Side::Sell), 3);
+        let orders = book.orders_for_user(alice);
+        assert_eq!(orders.len(), 2);
+    }
+
+    #[test]
+    fn cancelled_orders_drop_out() {
+        let book = OrderBook::<()>::new("TEST");
+        let alice = Hash32::new([1u8; 32]);
+        let id = rest_for(&book, 100, 10, Side::Buy, alice);
+        book.cancel_order(id).expect("cancel");
+
+        assert!(book.orders_for_user(alice).is_empty());
+        assert_eq!(book.open_quantity_for_user(alice, Side::Buy), 0);
+    }
+}
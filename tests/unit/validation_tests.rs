@@ -66,7 +66,7 @@ mod tests {
 
     #[test]
     fn test_set_tick_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         assert_eq!(book.tick_size(), None);
         book.set_tick_size(50);
         assert_eq!(book.tick_size(), Some(50));
@@ -206,7 +206,7 @@ mod tests {
 
     #[test]
     fn test_set_tick_size_changes_validation() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
 
         // No tick size — any price accepted
         let order = make_standard_order(150, 100, Side::Buy);
@@ -268,7 +268,7 @@ mod tests {
 
     #[test]
     fn test_set_lot_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         assert_eq!(book.lot_size(), None);
         book.set_lot_size(25);
         assert_eq!(book.lot_size(), Some(25));
@@ -423,7 +423,7 @@ mod tests {
 
     #[test]
     fn test_set_lot_size_changes_validation() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
 
         // No lot size — any quantity accepted
         let order = make_standard_order(1000, 7, Side::Buy);
@@ -473,7 +473,7 @@ mod tests {
 
     #[test]
     fn test_tick_and_lot_size_both_valid() {
-        let mut book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
+        let book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
         book.set_lot_size(10);
         let order = make_standard_order(1000, 50, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -481,7 +481,7 @@ mod tests {
 
     #[test]
     fn test_tick_valid_lot_invalid() {
-        let mut book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
+        let book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
         book.set_lot_size(10);
         let order = make_standard_order(1000, 15, Side::Buy);
         let result = book.add_order(order);
@@ -492,7 +492,7 @@ mod tests {
 
     #[test]
     fn test_tick_invalid_lot_valid() {
-        let mut book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
+        let book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
         book.set_lot_size(10);
         let order = make_standard_order(150, 50, Side::Buy);
         let result = book.add_order(order);
@@ -512,7 +512,7 @@ mod tests {
 
     #[test]
     fn test_set_min_order_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         assert_eq!(book.min_order_size(), None);
         book.set_min_order_size(10);
         assert_eq!(book.min_order_size(), Some(10));
@@ -520,7 +520,7 @@ mod tests {
 
     #[test]
     fn test_set_max_order_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         assert_eq!(book.max_order_size(), None);
         book.set_max_order_size(1000);
         assert_eq!(book.max_order_size(), Some(1000));
@@ -539,7 +539,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_rejects_below() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let order = make_standard_order(1000, 5, Side::Buy);
         let result = book.add_order(order);
@@ -553,7 +553,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_accepts_equal() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let order = make_standard_order(1000, 10, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -561,7 +561,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_accepts_above() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let order = make_standard_order(1000, 50, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -571,7 +571,7 @@ mod tests {
 
     #[test]
     fn test_max_order_size_rejects_above() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 150, Side::Buy);
         let result = book.add_order(order);
@@ -585,7 +585,7 @@ mod tests {
 
     #[test]
     fn test_max_order_size_accepts_equal() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 100, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -593,7 +593,7 @@ mod tests {
 
     #[test]
     fn test_max_order_size_accepts_below() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 50, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -603,7 +603,7 @@ mod tests {
 
     #[test]
     fn test_only_min_set_large_quantity_accepted() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let order = make_standard_order(1000, 999_999, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -613,7 +613,7 @@ mod tests {
 
     #[test]
     fn test_only_max_set_small_quantity_accepted() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(1000);
         let order = make_standard_order(1000, 1, Side::Buy);
         assert!(book.add_order(order).is_ok());
@@ -623,7 +623,7 @@ mod tests {
 
     #[test]
     fn test_min_and_max_within_range() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 50, Side::Buy);
@@ -632,7 +632,7 @@ mod tests {
 
     #[test]
     fn test_min_and_max_below_min() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 5, Side::Buy);
@@ -641,7 +641,7 @@ mod tests {
 
     #[test]
     fn test_min_and_max_above_max() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 150, Side::Buy);
@@ -652,7 +652,7 @@ mod tests {
 
     #[test]
     fn test_max_order_size_iceberg_total_above_max() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(50);
         // default iceberg: visible=10, hidden=90 → total=100 > 50
         let order = make_iceberg_order(1000, Side::Buy);
@@ -661,7 +661,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_iceberg_total_accepted() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(50);
         // default iceberg: visible=10, hidden=90 → total=100 >= 50
         let order = make_iceberg_order(1000, Side::Buy);
@@ -672,7 +672,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_rejects_post_only() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(200);
         // make_post_only_order uses quantity=100
         let order = make_post_only_order(1000, Side::Buy);
@@ -683,7 +683,7 @@ mod tests {
 
     #[test]
     fn test_min_order_size_rejects_sell_below() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let order = make_standard_order(1000, 5, Side::Sell);
         assert!(book.add_order(order).is_err());
@@ -693,7 +693,7 @@ mod tests {
 
     #[test]
     fn test_set_min_max_changes_validation() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
 
         // No limits — any quantity accepted
         let order = make_standard_order(1000, 1, Side::Buy);
@@ -718,7 +718,7 @@ mod tests {
 
     #[test]
     fn test_add_limit_order_respects_min_order_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         let result =
             book.add_limit_order(Id::new_uuid(), 1000, 5, Side::Buy, TimeInForce::Gtc, None);
@@ -727,7 +727,7 @@ mod tests {
 
     #[test]
     fn test_add_limit_order_respects_max_order_size() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_max_order_size(100);
         let result =
             book.add_limit_order(Id::new_uuid(), 1000, 150, Side::Buy, TimeInForce::Gtc, None);
@@ -738,7 +738,7 @@ mod tests {
 
     #[test]
     fn test_order_size_out_of_range_error_display() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_min_order_size(10);
         book.set_max_order_size(100);
         let order = make_standard_order(1000, 5, Side::Buy);
@@ -753,7 +753,7 @@ mod tests {
 
     #[test]
     fn test_all_validations_pass() {
-        let mut book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
+        let book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
         book.set_lot_size(10);
         book.set_min_order_size(10);
         book.set_max_order_size(1000);
@@ -763,7 +763,7 @@ mod tests {
 
     #[test]
     fn test_tick_fails_before_min_max() {
-        let mut book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
+        let book: OrderBook<()> = OrderBook::with_tick_size("BTC/USD", 100);
         book.set_min_order_size(10);
         let order = make_standard_order(150, 50, Side::Buy);
         let result = book.add_order(order);
@@ -774,7 +774,7 @@ mod tests {
 
     #[test]
     fn test_lot_fails_before_min_max() {
-        let mut book: OrderBook<()> = OrderBook::new("BTC/USD");
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
         book.set_lot_size(10);
         book.set_min_order_size(5);
         // quantity 7 fails lot (not multiple of 10), but is above min 5
@@ -785,6 +785,108 @@ mod tests {
         assert!(msg.contains("lot size"), "Should fail on lot: {msg}");
     }
 
+    // --- Min / max order notional ---
+
+    #[test]
+    fn test_set_min_max_order_notional() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        assert_eq!(book.min_order_notional(), None);
+        assert_eq!(book.max_order_notional(), None);
+        book.set_min_order_notional(10_000);
+        book.set_max_order_notional(1_000_000);
+        assert_eq!(book.min_order_notional(), Some(10_000));
+        assert_eq!(book.max_order_notional(), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_min_order_notional_rejects_below() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_min_order_notional(10_000);
+        // 1000 × 5 = 5_000 < 10_000
+        let order = make_standard_order(1000, 5, Side::Buy);
+        let result = book.add_order(order);
+        assert!(
+            matches!(
+                result,
+                Err(OrderBookError::OrderNotionalOutOfRange {
+                    notional: 5_000,
+                    min: Some(10_000),
+                    max: None,
+                })
+            ),
+            "expected OrderNotionalOutOfRange, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_min_order_notional_accepts_equal() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_min_order_notional(10_000);
+        let order = make_standard_order(1000, 10, Side::Buy);
+        assert!(book.add_order(order).is_ok());
+    }
+
+    #[test]
+    fn test_max_order_notional_rejects_above() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_max_order_notional(100_000);
+        // Quantity is small but the price makes the notional too large.
+        let order = make_standard_order(50_000, 3, Side::Sell);
+        let err = book.add_order(order).unwrap_err();
+        let msg = format!("{err}");
+        assert!(msg.contains("notional"), "Should mention notional: {msg}");
+        assert!(msg.contains("150000"), "Should contain notional: {msg}");
+    }
+
+    #[test]
+    fn test_max_order_notional_accepts_equal() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_max_order_notional(100_000);
+        let order = make_standard_order(50_000, 2, Side::Sell);
+        assert!(book.add_order(order).is_ok());
+    }
+
+    #[test]
+    fn test_max_order_notional_overflow_is_rejected() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_max_order_notional(u128::MAX - 1);
+        // u128::MAX / 2 × u64::MAX overflows u128 and saturates.
+        let order = make_standard_order(u128::MAX / 2, u64::MAX, Side::Sell);
+        let result = book.add_order(order);
+        assert!(
+            matches!(
+                result,
+                Err(OrderBookError::OrderNotionalOutOfRange {
+                    notional: u128::MAX,
+                    ..
+                })
+            ),
+            "overflowing notional must be rejected, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_iceberg_notional_uses_total_quantity() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        // default iceberg: visible=10, hidden=90 → 1000 × 100 = 100_000
+        book.set_max_order_notional(50_000);
+        let order = make_iceberg_order(1000, Side::Buy);
+        assert!(book.add_order(order).is_err());
+    }
+
+    #[test]
+    fn test_size_fails_before_notional() {
+        let book: OrderBook<()> = OrderBook::new("BTC/USD");
+        book.set_min_order_size(10);
+        book.set_min_order_notional(1_000_000);
+        let order = make_standard_order(1000, 5, Side::Buy);
+        let result = book.add_order(order);
+        assert!(
+            matches!(result, Err(OrderBookError::OrderSizeOutOfRange { .. })),
+            "size check must run first, got {result:?}"
+        );
+    }
+
     #[test]
     fn test_add_order_rejects_duplicate_order_id_issue_119() {
         let book: OrderBook<()> = OrderBook::new("TEST");
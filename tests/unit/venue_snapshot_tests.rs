@@ -0,0 +1,108 @@
+//! Tests for whole-venue snapshot capture with `snapshot_all` on
+//! BookManagerStd and BookManagerTokio.
+
+#[cfg(test)]
+mod tests_venue_snapshot {
+    use orderbook_rs::orderbook::manager::{BookManager, BookManagerStd, BookManagerTokio};
+    use orderbook_rs::{OrderBook, VenueSnapshot};
+    use pricelevel::{Id, Side, TimeInForce};
+
+    fn populate<M: BookManager<()>>(mgr: &mut M) {
+        for symbol in ["ETH/USD", "BTC/USD"] {
+            mgr.add_book(symbol).expect("add book");
+        }
+        let btc = mgr.get_book("BTC/USD").expect("btc");
+        btc.add_limit_order(Id::from_u64(1), 100, 10, Side::Buy, TimeInForce::Gtc, None)
+            .expect("btc bid");
+        btc.add_limit_order(Id::from_u64(2), 105, 4, Side::Sell, TimeInForce::Gtc, None)
+            .expect("btc ask");
+        let eth = mgr.get_book("ETH/USD").expect("eth");
+        eth.add_limit_order(Id::from_u64(3), 50, 20, Side::Buy, TimeInForce::Gtc, None)
+            .expect("eth bid");
+    }
+
+    #[test]
+    fn std_snapshot_all_captures_every_book_under_one_epoch() {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        populate(&mut mgr);
+
+        let first = mgr.snapshot_all(usize::MAX).expect("first capture");
+        assert_eq!(first.epoch, 1);
+        assert_eq!(
+            first.books.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["BTC/USD", "ETH/USD"]
+        );
+        first.validate().expect("valid packages");
+        let btc = first.book("BTC/USD").expect("btc package");
+        assert_eq!(btc.snapshot.best_bid(), Some((100, 10)));
+        assert_eq!(btc.snapshot.best_ask(), Some((105, 4)));
+        assert!(first.book("SOL/USD").is_none());
+
+        let second = mgr.snapshot_all(1).expect("second capture");
+        assert_eq!(second.epoch, 2);
+
+        let decoded =
+            VenueSnapshot::from_json(&first.to_json().expect("to json")).expect("from json");
+        assert_eq!(decoded.epoch, first.epoch);
+        decoded.validate().expect("decoded packages stay valid");
+
+        let mut restored = OrderBook::<()>::new("ETH/USD");
+        restored
+            .restore_from_snapshot_package(decoded.books["ETH/USD"].clone())
+            .expect("restore");
+        assert_eq!(restored.best_bid(), Some(50));
+    }
+
+    #[test]
+    fn tampered_package_fails_venue_validation() {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        populate(&mut mgr);
+
+        let mut venue = mgr.snapshot_all(usize::MAX).expect("capture");
+        if let Some(package) = venue.books.get_mut("ETH/USD") {
+            package.snapshot.bids.clear();
+        }
+        assert!(venue.validate().is_err());
+    }
+
+    #[test]
+    fn tokio_snapshot_all_matches_std() {
+        let mut std_mgr: BookManagerStd<()> = BookManagerStd::new();
+        let mut tokio_mgr: BookManagerTokio<()> = BookManagerTokio::new();
+        populate(&mut std_mgr);
+        populate(&mut tokio_mgr);
+
+        let from_std = std_mgr.snapshot_all(usize::MAX).expect("std capture");
+        let from_tokio = tokio_mgr.snapshot_all(usize::MAX).expect("tokio capture");
+        assert_eq!(from_tokio.epoch, 1);
+        for (symbol, package) in &from_std.books {
+            let snapshot = &from_tokio.books[symbol].snapshot;
+            assert_eq!(snapshot.best_bid(), package.snapshot.best_bid());
+            assert_eq!(snapshot.best_ask(), package.snapshot.best_ask());
+        }
+    }
+
+    #[test]
+    fn empty_manager_yields_an_empty_envelope() {
+        let mgr: BookManagerStd<()> = BookManagerStd::new();
+        let venue = mgr.snapshot_all(usize::MAX).expect("capture");
+        assert!(venue.books.is_empty());
+        assert!(venue.validate().is_ok());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip_rejects_trailing_bytes() {
+        let mut mgr: BookManagerStd<()> = BookManagerStd::new();
+        populate(&mut mgr);
+
+        let venue = mgr.snapshot_all(usize::MAX).expect("capture");
+        let mut bytes = venue.to_bincode().expect("encode");
+        let decoded = VenueSnapshot::from_bincode(&bytes).expect("decode");
+        assert_eq!(decoded.books.len(), 2);
+        decoded.validate().expect("decoded packages stay valid");
+
+        bytes.push(0);
+        assert!(VenueSnapshot::from_bincode(&bytes).is_err());
+    }
+}